@@ -50,6 +50,8 @@ INCLUDES = """
     (OPENSSL_VERSION_NUMBER >= 0x30000000 && !CRYPTOGRAPHY_IS_LIBRESSL)
 #define CRYPTOGRAPHY_OPENSSL_320_OR_GREATER \
     (OPENSSL_VERSION_NUMBER >= 0x30200000 && !CRYPTOGRAPHY_IS_LIBRESSL)
+#define CRYPTOGRAPHY_OPENSSL_350_OR_GREATER \
+    (OPENSSL_VERSION_NUMBER >= 0x30500000 && !CRYPTOGRAPHY_IS_LIBRESSL)
 
 #define CRYPTOGRAPHY_OPENSSL_LESS_THAN_111E \
     (OPENSSL_VERSION_NUMBER < 0x10101050 || CRYPTOGRAPHY_IS_LIBRESSL)
@@ -66,6 +68,7 @@ INCLUDES = """
 TYPES = """
 static const int CRYPTOGRAPHY_OPENSSL_300_OR_GREATER;
 static const int CRYPTOGRAPHY_OPENSSL_320_OR_GREATER;
+static const int CRYPTOGRAPHY_OPENSSL_350_OR_GREATER;
 
 static const int CRYPTOGRAPHY_OPENSSL_LESS_THAN_111E;
 
@@ -0,0 +1,48 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from __future__ import annotations
+
+import typing
+
+from cryptography.hazmat.bindings._rust import openssl as rust_openssl
+from cryptography.hazmat.primitives.asymmetric import x25519
+
+__all__ = [
+    "Decryptor",
+    "Encryptor",
+    "Identity",
+    "Recipient",
+    "ScryptIdentity",
+    "ScryptRecipient",
+    "decrypt",
+    "encode_x25519_identity",
+    "encode_x25519_recipient",
+    "encrypt",
+    "load_x25519_identity",
+    "load_x25519_recipient",
+]
+
+ScryptRecipient = rust_openssl.age.ScryptRecipient
+ScryptIdentity = rust_openssl.age.ScryptIdentity
+Encryptor = rust_openssl.age.Encryptor
+Decryptor = rust_openssl.age.Decryptor
+
+load_x25519_recipient = rust_openssl.age.load_x25519_recipient
+load_x25519_identity = rust_openssl.age.load_x25519_identity
+encode_x25519_recipient = rust_openssl.age.encode_x25519_recipient
+encode_x25519_identity = rust_openssl.age.encode_x25519_identity
+
+Recipient = typing.Union[x25519.X25519PublicKey, ScryptRecipient]
+Identity = typing.Union[x25519.X25519PrivateKey, ScryptIdentity]
+
+
+def encrypt(data: bytes, recipients: typing.Iterable[Recipient]) -> bytes:
+    encryptor = Encryptor(list(recipients))
+    return encryptor.update(data) + encryptor.finalize()
+
+
+def decrypt(data: bytes, identities: typing.Iterable[Identity]) -> bytes:
+    decryptor = Decryptor(list(identities))
+    return decryptor.update(data) + decryptor.finalize()
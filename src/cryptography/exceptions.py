@@ -50,3 +50,7 @@ class InternalError(Exception):
 
 class InvalidKey(Exception):
     pass
+
+
+class FIPSViolation(ValueError):
+    pass
@@ -39,6 +39,8 @@ class ExtensionOID:
     PRECERT_POISON = ObjectIdentifier("1.3.6.1.4.1.11129.2.4.3")
     SIGNED_CERTIFICATE_TIMESTAMPS = ObjectIdentifier("1.3.6.1.4.1.11129.2.4.5")
     MS_CERTIFICATE_TEMPLATE = ObjectIdentifier("1.3.6.1.4.1.311.21.7")
+    ANDROID_KEY_ATTESTATION = ObjectIdentifier("1.3.6.1.4.1.11129.2.1.17")
+    APPLE_ATTESTATION_NONCE = ObjectIdentifier("1.2.840.113635.100.8.2")
 
 
 class OCSPExtensionOID:
@@ -117,6 +119,21 @@ class SignatureAlgorithmOID:
     DSA_WITH_SHA512 = ObjectIdentifier("2.16.840.1.101.3.4.3.4")
     ED25519 = ObjectIdentifier("1.3.101.112")
     ED448 = ObjectIdentifier("1.3.101.113")
+    ML_DSA_44 = ObjectIdentifier("2.16.840.1.101.3.4.3.17")
+    ML_DSA_65 = ObjectIdentifier("2.16.840.1.101.3.4.3.18")
+    ML_DSA_87 = ObjectIdentifier("2.16.840.1.101.3.4.3.19")
+    SLH_DSA_SHA2_128S = ObjectIdentifier("2.16.840.1.101.3.4.3.20")
+    SLH_DSA_SHA2_128F = ObjectIdentifier("2.16.840.1.101.3.4.3.21")
+    SLH_DSA_SHA2_192S = ObjectIdentifier("2.16.840.1.101.3.4.3.22")
+    SLH_DSA_SHA2_192F = ObjectIdentifier("2.16.840.1.101.3.4.3.23")
+    SLH_DSA_SHA2_256S = ObjectIdentifier("2.16.840.1.101.3.4.3.24")
+    SLH_DSA_SHA2_256F = ObjectIdentifier("2.16.840.1.101.3.4.3.25")
+    SLH_DSA_SHAKE_128S = ObjectIdentifier("2.16.840.1.101.3.4.3.26")
+    SLH_DSA_SHAKE_128F = ObjectIdentifier("2.16.840.1.101.3.4.3.27")
+    SLH_DSA_SHAKE_192S = ObjectIdentifier("2.16.840.1.101.3.4.3.28")
+    SLH_DSA_SHAKE_192F = ObjectIdentifier("2.16.840.1.101.3.4.3.29")
+    SLH_DSA_SHAKE_256S = ObjectIdentifier("2.16.840.1.101.3.4.3.30")
+    SLH_DSA_SHAKE_256F = ObjectIdentifier("2.16.840.1.101.3.4.3.31")
     GOSTR3411_94_WITH_3410_2001 = ObjectIdentifier("1.2.643.2.2.3")
     GOSTR3410_2012_WITH_3411_2012_256 = ObjectIdentifier("1.2.643.7.1.1.3.2")
     GOSTR3410_2012_WITH_3411_2012_512 = ObjectIdentifier("1.2.643.7.1.1.3.3")
@@ -148,6 +165,21 @@ _SIG_OIDS_TO_HASH: dict[ObjectIdentifier, hashes.HashAlgorithm | None] = {
     SignatureAlgorithmOID.DSA_WITH_SHA256: hashes.SHA256(),
     SignatureAlgorithmOID.ED25519: None,
     SignatureAlgorithmOID.ED448: None,
+    SignatureAlgorithmOID.ML_DSA_44: None,
+    SignatureAlgorithmOID.ML_DSA_65: None,
+    SignatureAlgorithmOID.ML_DSA_87: None,
+    SignatureAlgorithmOID.SLH_DSA_SHA2_128S: None,
+    SignatureAlgorithmOID.SLH_DSA_SHA2_128F: None,
+    SignatureAlgorithmOID.SLH_DSA_SHA2_192S: None,
+    SignatureAlgorithmOID.SLH_DSA_SHA2_192F: None,
+    SignatureAlgorithmOID.SLH_DSA_SHA2_256S: None,
+    SignatureAlgorithmOID.SLH_DSA_SHA2_256F: None,
+    SignatureAlgorithmOID.SLH_DSA_SHAKE_128S: None,
+    SignatureAlgorithmOID.SLH_DSA_SHAKE_128F: None,
+    SignatureAlgorithmOID.SLH_DSA_SHAKE_192S: None,
+    SignatureAlgorithmOID.SLH_DSA_SHAKE_192F: None,
+    SignatureAlgorithmOID.SLH_DSA_SHAKE_256S: None,
+    SignatureAlgorithmOID.SLH_DSA_SHAKE_256F: None,
     SignatureAlgorithmOID.GOSTR3411_94_WITH_3410_2001: None,
     SignatureAlgorithmOID.GOSTR3410_2012_WITH_3411_2012_256: None,
     SignatureAlgorithmOID.GOSTR3410_2012_WITH_3411_2012_512: None,
@@ -236,6 +268,21 @@ _OID_NAMES = {
     SignatureAlgorithmOID.DSA_WITH_SHA256: "dsa-with-sha256",
     SignatureAlgorithmOID.ED25519: "ed25519",
     SignatureAlgorithmOID.ED448: "ed448",
+    SignatureAlgorithmOID.ML_DSA_44: "id-ml-dsa-44",
+    SignatureAlgorithmOID.ML_DSA_65: "id-ml-dsa-65",
+    SignatureAlgorithmOID.ML_DSA_87: "id-ml-dsa-87",
+    SignatureAlgorithmOID.SLH_DSA_SHA2_128S: "id-slh-dsa-sha2-128s",
+    SignatureAlgorithmOID.SLH_DSA_SHA2_128F: "id-slh-dsa-sha2-128f",
+    SignatureAlgorithmOID.SLH_DSA_SHA2_192S: "id-slh-dsa-sha2-192s",
+    SignatureAlgorithmOID.SLH_DSA_SHA2_192F: "id-slh-dsa-sha2-192f",
+    SignatureAlgorithmOID.SLH_DSA_SHA2_256S: "id-slh-dsa-sha2-256s",
+    SignatureAlgorithmOID.SLH_DSA_SHA2_256F: "id-slh-dsa-sha2-256f",
+    SignatureAlgorithmOID.SLH_DSA_SHAKE_128S: "id-slh-dsa-shake-128s",
+    SignatureAlgorithmOID.SLH_DSA_SHAKE_128F: "id-slh-dsa-shake-128f",
+    SignatureAlgorithmOID.SLH_DSA_SHAKE_192S: "id-slh-dsa-shake-192s",
+    SignatureAlgorithmOID.SLH_DSA_SHAKE_192F: "id-slh-dsa-shake-192f",
+    SignatureAlgorithmOID.SLH_DSA_SHAKE_256S: "id-slh-dsa-shake-256s",
+    SignatureAlgorithmOID.SLH_DSA_SHAKE_256F: "id-slh-dsa-shake-256f",
     SignatureAlgorithmOID.GOSTR3411_94_WITH_3410_2001: (
         "GOST R 34.11-94 with GOST R 34.10-2001"
     ),
@@ -267,6 +314,8 @@ _OID_NAMES = {
     ),
     ExtensionOID.PRECERT_POISON: "ctPoison",
     ExtensionOID.MS_CERTIFICATE_TEMPLATE: "msCertificateTemplate",
+    ExtensionOID.ANDROID_KEY_ATTESTATION: "androidKeyAttestation",
+    ExtensionOID.APPLE_ATTESTATION_NONCE: "appleAttestationNonce",
     CRLEntryExtensionOID.CRL_REASON: "cRLReason",
     CRLEntryExtensionOID.INVALIDITY_DATE: "invalidityDate",
     CRLEntryExtensionOID.CERTIFICATE_ISSUER: "certificateIssuer",
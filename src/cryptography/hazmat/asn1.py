@@ -0,0 +1,314 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from __future__ import annotations
+
+import typing
+
+from cryptography import utils
+from cryptography.hazmat.bindings._rust import asn1 as rust_asn1
+
+__all__ = [
+    "Any",
+    "BitString",
+    "BitStringValue",
+    "Boolean",
+    "Choice",
+    "Enumerated",
+    "GeneralizedTime",
+    "IA5String",
+    "Integer",
+    "Nested",
+    "Null",
+    "ObjectIdentifier",
+    "OctetString",
+    "PrintableString",
+    "Sequence",
+    "SequenceOf",
+    "SetOf",
+    "TagClass",
+    "UTCTime",
+    "UTF8String",
+]
+
+
+class TagClass(utils.Enum):
+    APPLICATION = 1
+    CONTEXT_SPECIFIC = 2
+    PRIVATE = 3
+
+
+class BitStringValue:
+    def __init__(self, data: bytes, padding_bits: int) -> None:
+        if not isinstance(data, bytes):
+            raise TypeError("data must be bytes")
+        if not 0 <= padding_bits <= 7:
+            raise ValueError("padding_bits must be between 0 and 7")
+        if padding_bits and not data:
+            raise ValueError("An empty BIT STRING can't have padding bits")
+
+        self._data = data
+        self._padding_bits = padding_bits
+
+    @property
+    def data(self) -> bytes:
+        return self._data
+
+    @property
+    def padding_bits(self) -> int:
+        return self._padding_bits
+
+    def __eq__(self, other: object) -> bool:
+        if not isinstance(other, BitStringValue):
+            return NotImplemented
+
+        return (self.data, self.padding_bits) == (
+            other.data,
+            other.padding_bits,
+        )
+
+    def __hash__(self) -> int:
+        return hash((self.data, self.padding_bits))
+
+    def __repr__(self) -> str:
+        return (
+            f"<BitStringValue(data={self.data!r}, "
+            f"padding_bits={self.padding_bits})>"
+        )
+
+
+_NO_DEFAULT = object()
+
+
+class _Field:
+    _kind: str
+
+    def __init__(
+        self,
+        *,
+        implicit: typing.Optional[int] = None,
+        explicit: typing.Optional[int] = None,
+        tag_class: TagClass = TagClass.CONTEXT_SPECIFIC,
+        optional: bool = False,
+        default: typing.Any = _NO_DEFAULT,
+    ) -> None:
+        if implicit is not None and explicit is not None:
+            raise ValueError(
+                "A field can't be both implicitly and explicitly tagged"
+            )
+        for tag in (implicit, explicit):
+            if tag is not None and (not isinstance(tag, int) or tag < 0):
+                raise ValueError("Tag numbers must be non-negative integers")
+        if not isinstance(tag_class, TagClass):
+            raise TypeError("tag_class must be a TagClass")
+        if optional and default is not _NO_DEFAULT:
+            raise ValueError(
+                "A field can't be both optional and have a default"
+            )
+
+        self._implicit = implicit
+        self._explicit = explicit
+        self._tag_class = tag_class
+        self._optional = optional
+        self._default = default
+        self._schema: typing.Optional[rust_asn1.Schema] = None
+
+    def _base_spec(self) -> tuple:
+        return (self._kind,)
+
+    def _spec(self) -> tuple:
+        spec = self._base_spec()
+        if self._implicit is not None:
+            return ("implicit", self._tag_class.value, self._implicit, spec)
+        if self._explicit is not None:
+            return ("explicit", self._tag_class.value, self._explicit, spec)
+        return spec
+
+    def _get_schema(self) -> rust_asn1.Schema:
+        if self._schema is None:
+            self._schema = rust_asn1.Schema(self._spec())
+        return self._schema
+
+    def decode_der(self, data: bytes) -> typing.Any:
+        return self._get_schema().decode_der(data)
+
+    def encode_der(self, value: typing.Any) -> bytes:
+        return self._get_schema().encode_der(value)
+
+
+class Boolean(_Field):
+    _kind = "boolean"
+
+
+class Integer(_Field):
+    _kind = "integer"
+
+
+class Enumerated(_Field):
+    _kind = "enumerated"
+
+
+class Null(_Field):
+    _kind = "null"
+
+
+class OctetString(_Field):
+    _kind = "octet_string"
+
+
+class BitString(_Field):
+    _kind = "bit_string"
+
+
+class ObjectIdentifier(_Field):
+    _kind = "object_identifier"
+
+
+class UTF8String(_Field):
+    _kind = "utf8_string"
+
+
+class PrintableString(_Field):
+    _kind = "printable_string"
+
+
+class IA5String(_Field):
+    _kind = "ia5_string"
+
+
+class UTCTime(_Field):
+    _kind = "utc_time"
+
+
+class GeneralizedTime(_Field):
+    _kind = "generalized_time"
+
+
+class Any(_Field):
+    _kind = "any"
+
+
+class SequenceOf(_Field):
+    _kind = "sequence_of"
+
+    def __init__(self, element: _Field, **kwargs: typing.Any) -> None:
+        if not isinstance(element, _Field):
+            raise TypeError("element must be an ASN.1 field")
+        super().__init__(**kwargs)
+        self._element = element
+
+    def _base_spec(self) -> tuple:
+        return (self._kind, self._element._spec())
+
+
+class SetOf(SequenceOf):
+    _kind = "set_of"
+
+
+class Choice(_Field):
+    _kind = "choice"
+
+    def __init__(
+        self, alternatives: typing.Dict[str, _Field], **kwargs: typing.Any
+    ) -> None:
+        if not alternatives:
+            raise ValueError("A CHOICE needs at least one alternative")
+        if not all(isinstance(f, _Field) for f in alternatives.values()):
+            raise TypeError("Alternatives must be ASN.1 fields")
+        super().__init__(**kwargs)
+        self._alternatives = dict(alternatives)
+
+    def _base_spec(self) -> tuple:
+        return (
+            self._kind,
+            [(name, f._spec()) for name, f in self._alternatives.items()],
+        )
+
+
+class Nested(_Field):
+    _kind = "sequence"
+
+    def __init__(
+        self, sequence: typing.Type[Sequence], **kwargs: typing.Any
+    ) -> None:
+        if not (isinstance(sequence, type) and issubclass(sequence, Sequence)):
+            raise TypeError("sequence must be a Sequence subclass")
+        super().__init__(**kwargs)
+        self._sequence = sequence
+
+    def _base_spec(self) -> tuple:
+        return self._sequence._sequence_spec()
+
+
+class Sequence:
+    _fields: typing.ClassVar[typing.Dict[str, _Field]] = {}
+    _schema: typing.ClassVar[typing.Optional[rust_asn1.Schema]] = None
+
+    def __init_subclass__(cls, **kwargs: typing.Any) -> None:
+        super().__init_subclass__(**kwargs)
+        fields = dict(cls._fields)
+        for name, value in vars(cls).items():
+            if isinstance(value, _Field):
+                fields[name] = value
+        cls._fields = fields
+        cls._schema = None
+
+    def __init__(self, **kwargs: typing.Any) -> None:
+        for name, field in self._fields.items():
+            if name in kwargs:
+                value = kwargs.pop(name)
+            elif field._optional:
+                value = None
+            elif field._default is not _NO_DEFAULT:
+                value = field._default
+            else:
+                raise TypeError(f"Missing required field: {name}")
+            setattr(self, name, value)
+        if kwargs:
+            raise TypeError(f"Unknown fields: {', '.join(sorted(kwargs))}")
+
+    @classmethod
+    def _sequence_spec(cls) -> tuple:
+        return (
+            "sequence",
+            cls,
+            [
+                (
+                    name,
+                    field._spec(),
+                    field._optional,
+                    field._default is not _NO_DEFAULT,
+                    None if field._default is _NO_DEFAULT else field._default,
+                )
+                for name, field in cls._fields.items()
+            ],
+        )
+
+    @classmethod
+    def _get_schema(cls) -> rust_asn1.Schema:
+        if cls._schema is None:
+            cls._schema = rust_asn1.Schema(cls._sequence_spec())
+        return cls._schema
+
+    @classmethod
+    def decode_der(cls, data: bytes) -> Sequence:
+        return cls._get_schema().decode_der(data)
+
+    def encode_der(self) -> bytes:
+        return self._get_schema().encode_der(self)
+
+    def __eq__(self, other: object) -> bool:
+        if type(other) is not type(self):
+            return NotImplemented
+
+        return all(
+            getattr(self, name) == getattr(other, name)
+            for name in self._fields
+        )
+
+    def __repr__(self) -> str:
+        values = ", ".join(
+            f"{name}={getattr(self, name)!r}" for name in self._fields
+        )
+        return f"<{type(self).__name__}({values})>"
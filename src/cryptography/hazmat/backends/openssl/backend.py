@@ -14,6 +14,7 @@ from cryptography.exceptions import UnsupportedAlgorithm, _Reasons
 from cryptography.hazmat.backends.openssl import aead
 from cryptography.hazmat.backends.openssl.ciphers import _CipherContext
 from cryptography.hazmat.bindings._rust import openssl as rust_openssl
+from cryptography.hazmat.bindings._rust import pkcs12 as rust_pkcs12
 from cryptography.hazmat.bindings.openssl import binding
 from cryptography.hazmat.primitives import hashes, serialization
 from cryptography.hazmat.primitives._asymmetric import AsymmetricPadding
@@ -373,6 +374,17 @@ class Backend:
         if self._fips_enabled and isinstance(algorithm, hashes.SHA1):
             return False
 
+        if isinstance(
+            algorithm,
+            (
+                hashes.SHA3_224,
+                hashes.SHA3_256,
+                hashes.SHA3_384,
+                hashes.SHA3_512,
+            ),
+        ):
+            return self.hash_supported(algorithm)
+
         return isinstance(
             algorithm,
             (
@@ -521,6 +533,18 @@ class Backend:
             or self.hash_supported(signature_algorithm.algorithm)
         )
 
+    def ecdsa_deterministic_supported(self) -> bool:
+        return (
+            self._lib.CRYPTOGRAPHY_OPENSSL_320_OR_GREATER
+            and not self._fips_enabled
+        )
+
+    def dsa_deterministic_supported(self) -> bool:
+        return self._lib.CRYPTOGRAPHY_OPENSSL_320_OR_GREATER
+
+    def dsa_verifiable_parameters_supported(self) -> bool:
+        return self._lib.CRYPTOGRAPHY_OPENSSL_300_OR_GREATER
+
     def elliptic_curve_exchange_algorithm_supported(
         self, algorithm: ec.ECDH, curve: ec.EllipticCurve
     ) -> bool:
@@ -560,6 +584,12 @@ class Backend:
             return False
         return True
 
+    def ed25519_variants_supported(self) -> bool:
+        return (
+            self.ed25519_supported()
+            and self._lib.CRYPTOGRAPHY_OPENSSL_320_OR_GREATER
+        )
+
     def ed448_supported(self) -> bool:
         if self._fips_enabled:
             return False
@@ -568,6 +598,55 @@ class Backend:
             and not self._lib.CRYPTOGRAPHY_IS_BORINGSSL
         )
 
+    def ed448_variants_supported(self) -> bool:
+        return (
+            self.ed448_supported()
+            and self._lib.CRYPTOGRAPHY_OPENSSL_320_OR_GREATER
+        )
+
+    def sm2_supported(self) -> bool:
+        if self._fips_enabled:
+            return False
+        return self._lib.CRYPTOGRAPHY_OPENSSL_300_OR_GREATER
+
+    def mldsa_supported(self) -> bool:
+        return self._lib.CRYPTOGRAPHY_OPENSSL_350_OR_GREATER
+
+    def slhdsa_supported(self) -> bool:
+        return self._lib.CRYPTOGRAPHY_OPENSSL_350_OR_GREATER
+
+    def xwing_supported(self) -> bool:
+        return self._lib.CRYPTOGRAPHY_OPENSSL_350_OR_GREATER
+
+    def kem_supported(self) -> bool:
+        return self._lib.CRYPTOGRAPHY_OPENSSL_320_OR_GREATER
+
+    def hybrid_key_exchange_supported(self) -> bool:
+        return self._lib.CRYPTOGRAPHY_OPENSSL_350_OR_GREATER
+
+    def falcon_supported(self) -> bool:
+        if self._fips_enabled:
+            return False
+        return (
+            not self._lib.CRYPTOGRAPHY_IS_LIBRESSL
+            and not self._lib.CRYPTOGRAPHY_IS_BORINGSSL
+        )
+
+    def key_uri_supported(self) -> bool:
+        return self._lib.CRYPTOGRAPHY_OPENSSL_300_OR_GREATER
+
+    def locked_memory_supported(self) -> bool:
+        return (
+            not self._lib.CRYPTOGRAPHY_IS_LIBRESSL
+            and not self._lib.CRYPTOGRAPHY_IS_BORINGSSL
+        )
+
+    def constant_time_bignum_supported(self) -> bool:
+        return (
+            not self._lib.CRYPTOGRAPHY_IS_LIBRESSL
+            and not self._lib.CRYPTOGRAPHY_IS_BORINGSSL
+        )
+
     def aead_cipher_supported(self, cipher) -> bool:
         return aead._aead_cipher_supported(self, cipher)
 
@@ -602,13 +681,16 @@ class Backend:
                 self._zero_data(self._ffi.cast("uint8_t *", buf), data_len)
 
     def load_key_and_certificates_from_pkcs12(
-        self, data: bytes, password: bytes | None
+        self,
+        data: bytes,
+        password: bytes | None,
+        allow_legacy: bool = False,
     ) -> tuple[
         PrivateKeyTypes | None,
         x509.Certificate | None,
         list[x509.Certificate],
     ]:
-        pkcs12 = self.load_pkcs12(data, password)
+        pkcs12 = self.load_pkcs12(data, password, allow_legacy)
         return (
             pkcs12.key,
             pkcs12.cert.certificate if pkcs12.cert else None,
@@ -616,11 +698,30 @@ class Backend:
         )
 
     def load_pkcs12(
-        self, data: bytes, password: bytes | None
+        self,
+        data: bytes,
+        password: bytes | None,
+        allow_legacy: bool = False,
     ) -> PKCS12KeyAndCertificates:
         if password is not None:
             utils._check_byteslike("password", password)
 
+        if allow_legacy:
+            try:
+                key, cert, certs = rust_pkcs12.load_legacy_pkcs12(
+                    data, password
+                )
+            except UnsupportedAlgorithm:
+                # The contents use a scheme (such as PBES2) which isn't
+                # legacy, so OpenSSL can handle it.
+                pass
+            else:
+                return PKCS12KeyAndCertificates(
+                    key,
+                    PKCS12Certificate(*cert) if cert is not None else None,
+                    [PKCS12Certificate(c, name) for c, name in certs],
+                )
+
         bio = self._bytes_to_bio(data)
         p12 = self._lib.d2i_PKCS12_bio(bio.bio, self._ffi.NULL)
         if p12 == self._ffi.NULL:
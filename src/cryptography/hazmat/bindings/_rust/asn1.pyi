@@ -2,6 +2,8 @@
 # 2.0, and the BSD License. See the LICENSE file in the root of this repository
 # for complete details.
 
+import typing
+
 class TestCertificate:
     not_after_tag: int
     not_before_tag: int
@@ -10,5 +12,12 @@ class TestCertificate:
 
 def decode_dss_signature(signature: bytes) -> tuple[int, int]: ...
 def encode_dss_signature(r: int, s: int) -> bytes: ...
+def der_to_raw_signature(signature: bytes, size: int) -> bytes: ...
+def raw_to_der_signature(signature: bytes, size: int) -> bytes: ...
 def parse_spki_for_data(data: bytes) -> bytes: ...
 def test_parse_certificate(data: bytes) -> TestCertificate: ...
+
+class Schema:
+    def __init__(self, spec: tuple) -> None: ...
+    def decode_der(self, data: bytes) -> typing.Any: ...
+    def encode_der(self, value: typing.Any) -> bytes: ...
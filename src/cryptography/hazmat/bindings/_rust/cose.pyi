@@ -0,0 +1,41 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+import typing
+
+from cryptography.hazmat.primitives.asymmetric.types import (
+    CertificateIssuerPrivateKeyTypes,
+    CertificateIssuerPublicKeyTypes,
+)
+
+class COSESign1:
+    @property
+    def algorithm(self) -> int: ...
+    @property
+    def protected_headers(self) -> dict[int | str, typing.Any]: ...
+    @property
+    def unprotected_headers(self) -> dict[int | str, typing.Any]: ...
+    @property
+    def payload(self) -> bytes | None: ...
+    @property
+    def signature(self) -> bytes: ...
+    def verify(
+        self,
+        public_key: CertificateIssuerPublicKeyTypes,
+        *,
+        external_aad: bytes | None = None,
+        detached_payload: bytes | None = None,
+    ) -> bytes: ...
+
+def sign1(
+    payload: bytes,
+    private_key: CertificateIssuerPrivateKeyTypes,
+    algorithm: int,
+    *,
+    protected_headers: dict[int | str, typing.Any] | None = None,
+    unprotected_headers: dict[int | str, typing.Any] | None = None,
+    external_aad: bytes | None = None,
+    detached: bool = False,
+) -> bytes: ...
+def load_cose_sign1(data: bytes) -> COSESign1: ...
@@ -0,0 +1,15 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from cryptography import x509
+from cryptography.hazmat.primitives.asymmetric.types import PrivateKeyTypes
+
+def load_jks(
+    data: bytes,
+    password: bytes,
+    key_password: bytes | None,
+) -> tuple[
+    list[tuple[str, PrivateKeyTypes, list[x509.Certificate]]],
+    list[tuple[str, x509.Certificate]],
+]: ...
@@ -3,6 +3,8 @@
 # for complete details.
 
 from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric.padding import PKCS1v15, PSS
+from cryptography.hazmat.primitives.asymmetric.signer import ExternalSigner
 from cryptography.hazmat.primitives.asymmetric.types import PrivateKeyTypes
 from cryptography.x509.ocsp import (
     OCSPRequest,
@@ -18,6 +20,7 @@ def create_ocsp_request(builder: OCSPRequestBuilder) -> OCSPRequest: ...
 def create_ocsp_response(
     status: OCSPResponseStatus,
     builder: OCSPResponseBuilder | None,
-    private_key: PrivateKeyTypes | None,
+    private_key: PrivateKeyTypes | ExternalSigner | None,
     hash_algorithm: hashes.HashAlgorithm | None,
+    rsa_padding: PKCS1v15 | PSS | None,
 ) -> OCSPResponse: ...
@@ -6,40 +6,84 @@ import typing
 
 from cryptography.hazmat.bindings._rust.openssl import (
     aead,
+    age,
+    bignum,
+    bls,
     cmac,
     dh,
     dsa,
     ec,
+    ecvrf,
     ed448,
     ed25519,
+    falcon,
+    fernet,
     hashes,
     hmac,
+    hybrid,
+    jwk,
     kdf,
+    kem,
     keys,
+    keystore,
+    locked_memory,
+    mldsa,
+    openpgp,
     poly1305,
+    ppk,
+    ristretto255,
     rsa,
+    slhdsa,
+    sm2,
+    ssh_files,
+    store,
+    threshold_ecdsa,
+    twofactor,
     x448,
     x25519,
+    xwing,
 )
 
 __all__ = [
     "openssl_version",
     "raise_openssl_error",
     "aead",
+    "age",
+    "bignum",
+    "bls",
     "cmac",
     "dh",
     "dsa",
     "ec",
+    "ecvrf",
+    "falcon",
+    "fernet",
     "hashes",
     "hmac",
+    "hybrid",
+    "jwk",
     "kdf",
+    "kem",
     "keys",
+    "keystore",
+    "locked_memory",
+    "mldsa",
     "ed448",
     "ed25519",
+    "ristretto255",
     "rsa",
+    "slhdsa",
+    "openpgp",
     "poly1305",
+    "ppk",
+    "sm2",
+    "ssh_files",
+    "store",
+    "threshold_ecdsa",
+    "twofactor",
     "x448",
     "x25519",
+    "xwing",
 ]
 
 def openssl_version() -> int: ...
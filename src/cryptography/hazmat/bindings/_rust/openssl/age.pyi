@@ -0,0 +1,40 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+import typing
+
+from cryptography.hazmat.primitives.asymmetric import x25519
+
+class ScryptRecipient:
+    def __init__(self, passphrase: bytes, work_factor: int = 18) -> None: ...
+    @property
+    def work_factor(self) -> int: ...
+
+class ScryptIdentity:
+    def __init__(
+        self, passphrase: bytes, max_work_factor: int = 22
+    ) -> None: ...
+    @property
+    def max_work_factor(self) -> int: ...
+
+class Encryptor:
+    def __init__(
+        self,
+        recipients: typing.Sequence[x25519.X25519PublicKey | ScryptRecipient],
+    ) -> None: ...
+    def update(self, data: bytes) -> bytes: ...
+    def finalize(self) -> bytes: ...
+
+class Decryptor:
+    def __init__(
+        self,
+        identities: typing.Sequence[x25519.X25519PrivateKey | ScryptIdentity],
+    ) -> None: ...
+    def update(self, data: bytes) -> bytes: ...
+    def finalize(self) -> bytes: ...
+
+def load_x25519_recipient(data: str) -> x25519.X25519PublicKey: ...
+def load_x25519_identity(data: str) -> x25519.X25519PrivateKey: ...
+def encode_x25519_recipient(public_key: x25519.X25519PublicKey) -> str: ...
+def encode_x25519_identity(private_key: x25519.X25519PrivateKey) -> str: ...
@@ -0,0 +1,7 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+def mod_exp(base: int, exponent: int, modulus: int) -> int: ...
+def mod_inverse(value: int, modulus: int) -> int: ...
+def random_in_range(low: int, high: int) -> int: ...
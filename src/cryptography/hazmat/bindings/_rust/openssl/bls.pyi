@@ -0,0 +1,34 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+import typing
+
+from cryptography.hazmat.primitives.asymmetric import bls
+
+class BLSPrivateKey: ...
+class BLSPublicKey: ...
+
+def generate_private_key(variant: bls.BLSVariant) -> bls.BLSPrivateKey: ...
+def derive_private_key(
+    ikm: bytes, variant: bls.BLSVariant, key_info: bytes
+) -> bls.BLSPrivateKey: ...
+def from_private_bytes(
+    data: bytes, variant: bls.BLSVariant
+) -> bls.BLSPrivateKey: ...
+def from_public_bytes(
+    data: bytes, variant: bls.BLSVariant
+) -> bls.BLSPublicKey: ...
+def aggregate_signatures(
+    signatures: typing.Sequence[bytes], variant: bls.BLSVariant
+) -> bytes: ...
+def aggregate_verify(
+    public_keys: typing.Sequence[bls.BLSPublicKey],
+    messages: typing.Sequence[bytes],
+    signature: bytes,
+) -> None: ...
+def fast_aggregate_verify(
+    public_keys: typing.Sequence[bls.BLSPublicKey],
+    message: bytes,
+    signature: bytes,
+) -> None: ...
@@ -41,8 +41,14 @@ class DHParameterNumbers:
     def q(self) -> int | None: ...
 
 def generate_parameters(
-    generator: int, key_size: int, backend: typing.Any = None
+    generator: int,
+    key_size: int,
+    backend: typing.Any = None,
+    *,
+    progress: typing.Callable[[int, int], object] | None = None,
+    cancel: typing.Any = None,
 ) -> dh.DHParameters: ...
+def named_group_parameters(name: str) -> dh.DHParameters: ...
 def from_pem_parameters(
     data: bytes, backend: typing.Any = None
 ) -> dh.DHParameters: ...
@@ -38,4 +38,15 @@ class DSAParameterNumbers:
     def g(self) -> int: ...
     def parameters(self, backend: typing.Any = None) -> dsa.DSAParameters: ...
 
-def generate_parameters(key_size: int) -> dsa.DSAParameters: ...
+def generate_parameters(
+    key_size: int,
+    *,
+    progress: typing.Callable[[int, int], object] | None = None,
+    cancel: typing.Any = None,
+) -> dsa.DSAParameters: ...
+def generate_verifiable_parameters(
+    key_size: int,
+) -> tuple[dsa.DSAParameters, bytes, int]: ...
+def validate_parameters(
+    parameters: dsa.DSAParameters, seed: bytes, counter: int
+) -> None: ...
@@ -4,11 +4,20 @@
 
 import typing
 
+from cryptography.hazmat.primitives import _serialization
 from cryptography.hazmat.primitives.asymmetric import ec
 
 class ECPrivateKey: ...
 class ECPublicKey: ...
 
+class ECDSASigningContext:
+    def update(self, data: bytes) -> None: ...
+    def finalize(self) -> bytes: ...
+
+class ECDSAVerificationContext:
+    def update(self, data: bytes) -> None: ...
+    def verify(self) -> None: ...
+
 class EllipticCurvePrivateNumbers:
     def __init__(
         self, private_value: int, public_numbers: EllipticCurvePublicNumbers
@@ -34,9 +43,56 @@ class EllipticCurvePublicNumbers:
     def curve(self) -> ec.EllipticCurve: ...
     def __eq__(self, other: object) -> bool: ...
 
+class ECPoint:
+    @staticmethod
+    def generator(curve: ec.EllipticCurve) -> ECPoint: ...
+    @staticmethod
+    def infinity(curve: ec.EllipticCurve) -> ECPoint: ...
+    @staticmethod
+    def from_encoded_point(
+        curve: ec.EllipticCurve, data: bytes
+    ) -> ECPoint: ...
+    @staticmethod
+    def from_coordinates(
+        curve: ec.EllipticCurve, x: int, y: int
+    ) -> ECPoint: ...
+    @staticmethod
+    def from_public_key(key: ec.EllipticCurvePublicKey) -> ECPoint: ...
+    @property
+    def curve(self) -> ec.EllipticCurve: ...
+    @property
+    def is_infinity(self) -> bool: ...
+    @property
+    def x(self) -> int: ...
+    @property
+    def y(self) -> int: ...
+    def encode(self, compressed: bool = False) -> bytes: ...
+    def public_key(self) -> ec.EllipticCurvePublicKey: ...
+    def __add__(self, other: ECPoint) -> ECPoint: ...
+    def __sub__(self, other: ECPoint) -> ECPoint: ...
+    def __neg__(self) -> ECPoint: ...
+    def __mul__(self, scalar: int) -> ECPoint: ...
+    def __rmul__(self, scalar: int) -> ECPoint: ...
+    def __eq__(self, other: object) -> bool: ...
+
+class EllipticCurveParameters:
+    def __init__(self, curve: ec.EllipticCurve) -> None: ...
+    @property
+    def curve(self) -> ec.EllipticCurve: ...
+    def generate_private_key(self) -> ec.EllipticCurvePrivateKey: ...
+    def parameter_bytes(
+        self,
+        encoding: _serialization.Encoding,
+        format: _serialization.ParameterFormat,
+    ) -> bytes: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
 def curve_supported(curve: ec.EllipticCurve) -> bool: ...
+def supported_curves() -> list[type[ec.EllipticCurve]]: ...
 def generate_private_key(
-    curve: ec.EllipticCurve, backend: typing.Any = None
+    curve: ec.EllipticCurve | EllipticCurveParameters,
+    backend: typing.Any = None,
 ) -> ec.EllipticCurvePrivateKey: ...
 def from_private_numbers(
     numbers: ec.EllipticCurvePrivateNumbers,
@@ -44,9 +100,29 @@ def from_private_numbers(
 def from_public_numbers(
     numbers: ec.EllipticCurvePublicNumbers,
 ) -> ec.EllipticCurvePublicKey: ...
+def derive_private_key_from_seed(
+    seed: bytes, curve: ec.EllipticCurve, info: bytes
+) -> ec.EllipticCurvePrivateKey: ...
+def from_private_bytes(
+    curve: ec.EllipticCurve, data: bytes
+) -> ec.EllipticCurvePrivateKey: ...
 def from_public_bytes(
     curve: ec.EllipticCurve, data: bytes
 ) -> ec.EllipticCurvePublicKey: ...
 def derive_private_key(
     private_value: int, curve: ec.EllipticCurve
 ) -> ec.EllipticCurvePrivateKey: ...
+def normalize_signature(
+    signature: bytes,
+    curve: ec.EllipticCurve,
+    signature_format: ec.ECDSASignatureFormat,
+) -> bytes: ...
+def from_pem_parameters(data: bytes) -> EllipticCurveParameters: ...
+def from_der_parameters(data: bytes) -> EllipticCurveParameters: ...
+def recover_public_key(
+    curve: ec.EllipticCurve,
+    signature: bytes,
+    data: bytes,
+    signature_algorithm: ec.EllipticCurveSignatureAlgorithm,
+    recovery_id: int,
+) -> ec.EllipticCurvePublicKey: ...
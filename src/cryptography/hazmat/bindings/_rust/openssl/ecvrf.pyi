@@ -0,0 +1,11 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from cryptography.hazmat.primitives.asymmetric import ecvrf
+
+def prove(private_key: ecvrf.ECVRFPrivateKeyTypes, alpha: bytes) -> bytes: ...
+def verify(
+    public_key: ecvrf.ECVRFPublicKeyTypes, proof: bytes, alpha: bytes
+) -> bytes: ...
+def proof_to_hash(proof: bytes, suite: ecvrf.ECVRFSuite) -> bytes: ...
@@ -0,0 +1,9 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from cryptography.hazmat.primitives.asymmetric import falcon
+
+class FalconPublicKey: ...
+
+def from_public_bytes(data: bytes) -> falcon.FalconPublicKey: ...
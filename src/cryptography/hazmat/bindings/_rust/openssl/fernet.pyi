@@ -0,0 +1,28 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+class Fernet:
+    def __init__(self, key: bytes) -> None: ...
+    def encrypt(self, data: bytes, current_time: int, iv: bytes) -> bytes: ...
+    def extract_timestamp(self, token: bytes | str) -> int: ...
+
+class FernetV2:
+    def __init__(
+        self, key: bytes, algorithm: int, key_id: int | None
+    ) -> None: ...
+    @property
+    def key_id(self) -> int: ...
+    @property
+    def nonce_length(self) -> int: ...
+    def encrypt(
+        self, data: bytes, current_time: int, nonce: bytes
+    ) -> bytes: ...
+    def extract_timestamp(self, token: bytes | str) -> int: ...
+
+def decrypt(
+    fernets: list[Fernet | FernetV2],
+    token: bytes | str,
+    time_info: tuple[int, int] | None,
+) -> bytes: ...
+def rotate(fernets: list[Fernet | FernetV2], token: bytes | str) -> bytes: ...
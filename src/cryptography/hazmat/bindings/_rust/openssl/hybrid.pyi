@@ -0,0 +1,20 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from cryptography.hazmat.primitives.asymmetric import hybrid
+from cryptography.hazmat.primitives.kdf import KeyDerivationFunction
+
+class HybridPrivateKeyShare: ...
+
+def generate_key_share(
+    group: hybrid.HybridGroup,
+) -> hybrid.HybridPrivateKeyShare: ...
+def from_private_bytes(
+    group: hybrid.HybridGroup, data: bytes
+) -> hybrid.HybridPrivateKeyShare: ...
+def respond(
+    group: hybrid.HybridGroup,
+    peer_key_share: bytes,
+    kdf: KeyDerivationFunction,
+) -> tuple[bytes, bytes]: ...
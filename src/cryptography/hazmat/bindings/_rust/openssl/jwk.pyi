@@ -0,0 +1,14 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric.types import (
+    PrivateKeyTypes,
+    PublicKeyTypes,
+)
+
+def load_jwk_key(data: bytes) -> PrivateKeyTypes | PublicKeyTypes: ...
+def jwk_thumbprint(
+    data: bytes, algorithm: hashes.HashAlgorithm | None = None
+) -> bytes: ...
@@ -2,7 +2,11 @@
 # 2.0, and the BSD License. See the LICENSE file in the root of this repository
 # for complete details.
 
+import typing
+
+from cryptography.hazmat.primitives.asymmetric import dh, ec
 from cryptography.hazmat.primitives.hashes import HashAlgorithm
+from cryptography.hazmat.primitives.kdf import KeyDerivationFunction
 
 def derive_pbkdf2_hmac(
     key_material: bytes,
@@ -20,3 +24,10 @@ def derive_scrypt(
     max_mem: int,
     length: int,
 ) -> bytes: ...
+def derive_unified_model(
+    exchanges: typing.Sequence[
+        tuple[dh.DHPrivateKey, dh.DHPublicKey]
+        | tuple[ec.EllipticCurvePrivateKey, ec.EllipticCurvePublicKey]
+    ],
+    kdf: KeyDerivationFunction,
+) -> bytes: ...
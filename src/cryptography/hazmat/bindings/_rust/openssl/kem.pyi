@@ -0,0 +1,13 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from cryptography.hazmat.primitives.asymmetric.kem import (
+    KEMPrivateKeyTypes,
+    KEMPublicKeyTypes,
+)
+
+def encapsulate(public_key: KEMPublicKeyTypes) -> tuple[bytes, bytes]: ...
+def decapsulate(
+    private_key: KEMPrivateKeyTypes, ciphertext: bytes
+) -> bytes: ...
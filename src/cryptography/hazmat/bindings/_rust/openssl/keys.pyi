@@ -2,12 +2,22 @@
 # 2.0, and the BSD License. See the LICENSE file in the root of this repository
 # for complete details.
 
+import os
 import typing
 
+from cryptography import x509
+from cryptography.hazmat.primitives._serialization import (
+    KeySerializationEncryptionBuilder,
+)
+from cryptography.hazmat.primitives.asymmetric import dh, ec
 from cryptography.hazmat.primitives.asymmetric.types import (
     PrivateKeyTypes,
     PublicKeyTypes,
 )
+from cryptography.hazmat.primitives.serialization.ssh import (
+    SSHPrivateKeyTypes,
+    SSHSecurityKeyPrivateKey,
+)
 
 def private_key_from_ptr(
     ptr: int,
@@ -15,18 +25,50 @@ def private_key_from_ptr(
 ) -> PrivateKeyTypes: ...
 def load_der_private_key(
     data: bytes,
-    password: bytes | None,
+    password: bytes | typing.Callable[[], bytes] | None,
     backend: typing.Any = None,
     *,
     unsafe_skip_rsa_key_validation: bool = False,
+    allow_legacy: bool = False,
 ) -> PrivateKeyTypes: ...
 def load_pem_private_key(
+    data: bytes,
+    password: bytes | typing.Callable[[], bytes] | None,
+    backend: typing.Any = None,
+    *,
+    unsafe_skip_rsa_key_validation: bool = False,
+    allow_legacy: bool = False,
+) -> PrivateKeyTypes: ...
+def load_pem_private_keys(
     data: bytes,
     password: bytes | None,
     backend: typing.Any = None,
     *,
     unsafe_skip_rsa_key_validation: bool = False,
+) -> list[PrivateKeyTypes]: ...
+def load_pem_private_key_from_file(
+    file: str | os.PathLike[str] | typing.BinaryIO,
+    password: bytes | typing.Callable[[], bytes] | None,
+    *,
+    unsafe_skip_rsa_key_validation: bool = False,
+    allow_legacy: bool = False,
 ) -> PrivateKeyTypes: ...
+def load_pem_key_and_certificates(
+    data: bytes,
+    password: bytes | None,
+    *,
+    unsafe_skip_rsa_key_validation: bool = False,
+) -> tuple[
+    PrivateKeyTypes | None,
+    x509.Certificate | None,
+    list[x509.Certificate],
+]: ...
+def load_pem_parameters(
+    data: bytes, backend: typing.Any = None
+) -> dh.DHParameters | ec.EllipticCurveParameters: ...
+def load_der_parameters(
+    data: bytes, backend: typing.Any = None
+) -> dh.DHParameters | ec.EllipticCurveParameters: ...
 def load_der_public_key(
     data: bytes,
     backend: typing.Any = None,
@@ -35,3 +77,34 @@ def load_pem_public_key(
     data: bytes,
     backend: typing.Any = None,
 ) -> PublicKeyTypes: ...
+def load_ssh_private_key(
+    data: bytes,
+    password: bytes | None,
+    backend: typing.Any = None,
+) -> SSHPrivateKeyTypes | SSHSecurityKeyPrivateKey: ...
+def load_private_key(
+    data: bytes,
+    password: bytes | None,
+    *,
+    unsafe_skip_rsa_key_validation: bool = False,
+) -> PrivateKeyTypes | SSHSecurityKeyPrivateKey: ...
+def reencrypt_private_key(
+    data: bytes,
+    old_password: bytes | None,
+    new_password: bytes,
+    params: KeySerializationEncryptionBuilder | None = None,
+) -> bytes: ...
+
+class KeyInfo:
+    @property
+    def algorithm(self) -> str | None: ...
+    @property
+    def key_size(self) -> int | None: ...
+    @property
+    def curve(self) -> str | None: ...
+    @property
+    def private(self) -> bool: ...
+    @property
+    def encrypted(self) -> bool: ...
+
+def identify_key(data: bytes) -> KeyInfo: ...
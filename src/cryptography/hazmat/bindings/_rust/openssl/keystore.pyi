@@ -0,0 +1,18 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric.types import (
+    CertificateIssuerPublicKeyTypes,
+)
+
+class KeystoreKey:
+    @property
+    def name(self) -> str: ...
+    def public_key(self) -> CertificateIssuerPublicKeyTypes: ...
+    def sign(
+        self, data: bytes, algorithm: hashes.HashAlgorithm | None
+    ) -> bytes: ...
+
+def list_keys() -> list[KeystoreKey]: ...
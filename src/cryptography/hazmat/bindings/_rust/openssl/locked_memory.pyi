@@ -0,0 +1,15 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+class LockedBuffer:
+    def __init__(self, size: int) -> None: ...
+    def __len__(self) -> int: ...
+    def write(self, data: bytes, offset: int = 0) -> None: ...
+    def randomize(self) -> None: ...
+    def clear(self) -> None: ...
+    def to_bytes(self) -> bytes: ...
+
+def initialize(size: int, min_size: int) -> None: ...
+def is_initialized() -> bool: ...
+def used() -> int: ...
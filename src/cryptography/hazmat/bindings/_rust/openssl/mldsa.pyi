@@ -0,0 +1,18 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from cryptography.hazmat.primitives.asymmetric import mldsa
+
+class MLDSAPrivateKey: ...
+class MLDSAPublicKey: ...
+
+def generate_key(
+    parameter_set: mldsa.MLDSAParameterSet,
+) -> mldsa.MLDSAPrivateKey: ...
+def from_seed_bytes(
+    parameter_set: mldsa.MLDSAParameterSet, data: bytes
+) -> mldsa.MLDSAPrivateKey: ...
+def from_public_bytes(
+    parameter_set: mldsa.MLDSAParameterSet, data: bytes
+) -> mldsa.MLDSAPublicKey: ...
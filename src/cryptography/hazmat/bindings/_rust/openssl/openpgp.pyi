@@ -0,0 +1,24 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from cryptography.hazmat.primitives.asymmetric.types import (
+    PrivateKeyTypes,
+    PublicKeyTypes,
+)
+
+def load_openpgp_public_key(
+    data: bytes,
+) -> tuple[
+    tuple[int, int, bytes, PublicKeyTypes],
+    list[str],
+    list[tuple[int, int, bytes, PublicKeyTypes]],
+]: ...
+def load_openpgp_private_key(
+    data: bytes,
+    password: bytes | None,
+) -> tuple[
+    tuple[int, int, bytes, PrivateKeyTypes | PublicKeyTypes],
+    list[str],
+    list[tuple[int, int, bytes, PrivateKeyTypes | PublicKeyTypes]],
+]: ...
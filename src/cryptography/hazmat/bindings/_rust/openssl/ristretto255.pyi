@@ -0,0 +1,23 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from cryptography.hazmat.primitives.asymmetric import ristretto255
+
+class Ristretto255Element: ...
+class Ristretto255Scalar: ...
+
+def element_from_bytes(data: bytes) -> ristretto255.Ristretto255Element: ...
+def element_from_uniform_bytes(
+    data: bytes,
+) -> ristretto255.Ristretto255Element: ...
+def hash_to_group(
+    data: bytes, dst: bytes
+) -> ristretto255.Ristretto255Element: ...
+def generator() -> ristretto255.Ristretto255Element: ...
+def identity() -> ristretto255.Ristretto255Element: ...
+def scalar_from_bytes(data: bytes) -> ristretto255.Ristretto255Scalar: ...
+def scalar_from_uniform_bytes(
+    data: bytes,
+) -> ristretto255.Ristretto255Scalar: ...
+def random_scalar() -> ristretto255.Ristretto255Scalar: ...
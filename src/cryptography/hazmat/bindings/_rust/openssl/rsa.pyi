@@ -4,10 +4,13 @@
 
 import typing
 
+from cryptography.hazmat.primitives import hashes
 from cryptography.hazmat.primitives.asymmetric import rsa
 
 class RSAPrivateKey: ...
 class RSAPublicKey: ...
+class RSAPSSPrivateKey: ...
+class RSAPSSPublicKey: ...
 
 class RSAPrivateNumbers:
     def __init__(
@@ -19,6 +22,8 @@ class RSAPrivateNumbers:
         dmq1: int,
         iqmp: int,
         public_numbers: RSAPublicNumbers,
+        *,
+        other_primes: typing.Sequence[tuple[int, int, int]] = (),
     ) -> None: ...
     @property
     def p(self) -> int: ...
@@ -34,6 +39,8 @@ class RSAPrivateNumbers:
     def iqmp(self) -> int: ...
     @property
     def public_numbers(self) -> RSAPublicNumbers: ...
+    @property
+    def other_primes(self) -> tuple[tuple[int, int, int], ...]: ...
     def private_key(
         self,
         backend: typing.Any = None,
@@ -49,7 +56,74 @@ class RSAPublicNumbers:
     def e(self) -> int: ...
     def public_key(self, backend: typing.Any = None) -> rsa.RSAPublicKey: ...
 
+class RSAKeyCheck:
+    @property
+    def public_exponent_valid(self) -> bool: ...
+    @property
+    def modulus_matches_primes(self) -> bool: ...
+    @property
+    def primes_are_prime(self) -> bool: ...
+    @property
+    def prime_sizes_valid(self) -> bool: ...
+    @property
+    def private_exponent_valid(self) -> bool: ...
+    @property
+    def crt_exponents_valid(self) -> bool: ...
+    @property
+    def crt_coefficients_valid(self) -> bool: ...
+    @property
+    def valid(self) -> bool: ...
+
+class RSASigner:
+    def sign(self, data: bytes) -> bytes: ...
+
+class RSAVerifier:
+    def verify(self, signature: bytes, data: bytes) -> None: ...
+
+class RSAEncryptor:
+    def encrypt(self, plaintext: bytes) -> bytes: ...
+
+class RSABSSA:
+    def __init__(
+        self,
+        algorithm: hashes.HashAlgorithm,
+        salt_length: int,
+        *,
+        randomized: bool,
+    ) -> None: ...
+    @property
+    def algorithm(self) -> hashes.HashAlgorithm: ...
+    @property
+    def salt_length(self) -> int: ...
+    @property
+    def randomized(self) -> bool: ...
+    def prepare(self, msg: bytes) -> bytes: ...
+    def blind(
+        self, public_key: rsa.RSAPublicKey, msg: bytes
+    ) -> tuple[bytes, bytes]: ...
+    def blind_sign(
+        self, private_key: rsa.RSAPrivateKey, blinded_msg: bytes
+    ) -> bytes: ...
+    def finalize(
+        self,
+        public_key: rsa.RSAPublicKey,
+        msg: bytes,
+        blind_sig: bytes,
+        inv: bytes,
+    ) -> bytes: ...
+    def verify(
+        self, public_key: rsa.RSAPublicKey, signature: bytes, msg: bytes
+    ) -> None: ...
+
 def generate_private_key(
     public_exponent: int,
     key_size: int,
+    primes: int,
 ) -> rsa.RSAPrivateKey: ...
+def recover_private_numbers(n: int, e: int, d: int) -> RSAPrivateNumbers: ...
+def generate_private_keys(
+    count: int,
+    public_exponent: int,
+    key_size: int,
+    primes: int,
+) -> list[rsa.RSAPrivateKey]: ...
@@ -0,0 +1,18 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from cryptography.hazmat.primitives.asymmetric import slhdsa
+
+class SLHDSAPrivateKey: ...
+class SLHDSAPublicKey: ...
+
+def generate_key(
+    parameter_set: slhdsa.SLHDSAParameterSet,
+) -> slhdsa.SLHDSAPrivateKey: ...
+def from_private_bytes(
+    parameter_set: slhdsa.SLHDSAParameterSet, data: bytes
+) -> slhdsa.SLHDSAPrivateKey: ...
+def from_public_bytes(
+    parameter_set: slhdsa.SLHDSAParameterSet, data: bytes
+) -> slhdsa.SLHDSAPublicKey: ...
@@ -0,0 +1,11 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from cryptography.hazmat.primitives.asymmetric import sm2
+
+class SM2PrivateKey: ...
+class SM2PublicKey: ...
+
+def generate_private_key() -> sm2.SM2PrivateKey: ...
+def from_encoded_point(data: bytes) -> sm2.SM2PublicKey: ...
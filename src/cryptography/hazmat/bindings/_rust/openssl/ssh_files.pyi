@@ -0,0 +1,47 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from cryptography.hazmat.primitives.serialization.ssh import (
+    SSHCertPublicKeyTypes,
+    SSHPublicKeyTypes,
+    SSHSecurityKeyPublicKey,
+)
+
+class SSHAuthorizedKey:
+    @property
+    def options(self) -> list[tuple[bytes, bytes | None]]: ...
+    def public_key(self) -> SSHCertPublicKeyTypes: ...
+    @property
+    def comment(self) -> bytes | None: ...
+
+class SSHKnownHost:
+    @property
+    def hosts(self) -> list[bytes] | None: ...
+    @property
+    def cert_authority(self) -> bool: ...
+    @property
+    def revoked(self) -> bool: ...
+    def public_key(self) -> SSHCertPublicKeyTypes: ...
+    @property
+    def comment(self) -> bytes | None: ...
+    def matches(self, hostname: bytes, port: int = 22) -> bool: ...
+
+def load_ssh_authorized_keys(data: bytes) -> list[SSHAuthorizedKey]: ...
+def serialize_ssh_authorized_key(
+    public_key: SSHPublicKeyTypes | SSHSecurityKeyPublicKey,
+    *,
+    options: list[tuple[bytes, bytes | None]] | None = None,
+    comment: bytes | None = None,
+) -> bytes: ...
+def load_ssh_known_hosts(data: bytes) -> list[SSHKnownHost]: ...
+def serialize_ssh_known_host(
+    public_key: SSHPublicKeyTypes | SSHSecurityKeyPublicKey,
+    hostnames: list[bytes],
+    *,
+    port: int = 22,
+    hash_hostname: bool = False,
+    cert_authority: bool = False,
+    revoked: bool = False,
+    comment: bytes | None = None,
+) -> bytes: ...
@@ -0,0 +1,12 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from cryptography.hazmat.primitives.asymmetric import ec, rsa
+
+class OpaqueRSAPrivateKey: ...
+class OpaqueEllipticCurvePrivateKey: ...
+
+def load_private_key_from_uri(
+    uri: str, *, pin: bytes | None = None
+) -> rsa.RSAPrivateKey | ec.EllipticCurvePrivateKey: ...
@@ -0,0 +1,45 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from cryptography.hazmat.primitives.asymmetric import ec
+
+class Party1KeyGen:
+    def __init__(self, curve: ec.EllipticCurve) -> None: ...
+    def message(self) -> bytes: ...
+    def finish(self, message: bytes) -> Party1KeyShare: ...
+
+class Party2KeyGen:
+    def __init__(self, curve: ec.EllipticCurve) -> None: ...
+    def message(self) -> bytes: ...
+    def finish(self, message: bytes) -> Party2KeyShare: ...
+
+class Party1KeyShare:
+    def public_key(self) -> ec.EllipticCurvePublicKey: ...
+    def share_bytes(self) -> bytes: ...
+    def sign_session(
+        self,
+        data: bytes,
+        signature_algorithm: ec.EllipticCurveSignatureAlgorithm,
+    ) -> Party1SignSession: ...
+
+class Party2KeyShare:
+    def public_key(self) -> ec.EllipticCurvePublicKey: ...
+    def share_bytes(self) -> bytes: ...
+    def sign_session(
+        self,
+        data: bytes,
+        signature_algorithm: ec.EllipticCurveSignatureAlgorithm,
+    ) -> Party2SignSession: ...
+
+class Party1SignSession:
+    def commitment(self) -> bytes: ...
+    def decommit(self, message: bytes) -> bytes: ...
+    def finish(self, message: bytes) -> bytes: ...
+
+class Party2SignSession:
+    def message(self, commitment: bytes) -> bytes: ...
+    def partial_signature(self, message: bytes) -> bytes: ...
+
+def load_party1_key_share(data: bytes) -> Party1KeyShare: ...
+def load_party2_key_share(data: bytes) -> Party2KeyShare: ...
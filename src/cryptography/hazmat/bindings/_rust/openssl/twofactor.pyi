@@ -0,0 +1,19 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from cryptography.hazmat.primitives import hashes
+
+class HOTP:
+    def __init__(
+        self,
+        key: bytes,
+        length: int,
+        algorithm: hashes.HashAlgorithm,
+        steam_guard: bool,
+    ) -> None: ...
+    def dynamic_truncate(self, counter: int) -> int: ...
+    def generate(self, counter: int) -> bytes: ...
+    def verify(
+        self, code: bytes, counter: int, look_behind: int, look_ahead: int
+    ) -> int | None: ...
@@ -9,4 +9,7 @@ class X25519PublicKey: ...
 
 def generate_key() -> x25519.X25519PrivateKey: ...
 def from_private_bytes(data: bytes) -> x25519.X25519PrivateKey: ...
+def from_scalar(
+    data: bytes, *, clamp: bool = ...
+) -> x25519.X25519PrivateKey: ...
 def from_public_bytes(data: bytes) -> x25519.X25519PublicKey: ...
@@ -9,4 +9,5 @@ class X448PublicKey: ...
 
 def generate_key() -> x448.X448PrivateKey: ...
 def from_private_bytes(data: bytes) -> x448.X448PrivateKey: ...
+def from_scalar(data: bytes, *, clamp: bool = ...) -> x448.X448PrivateKey: ...
 def from_public_bytes(data: bytes) -> x448.X448PublicKey: ...
@@ -0,0 +1,12 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from cryptography.hazmat.primitives.asymmetric import xwing
+
+class XWingPrivateKey: ...
+class XWingPublicKey: ...
+
+def generate_key() -> xwing.XWingPrivateKey: ...
+def from_private_bytes(data: bytes) -> xwing.XWingPrivateKey: ...
+def from_public_bytes(data: bytes) -> xwing.XWingPublicKey: ...
@@ -0,0 +1,22 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+import typing
+
+class PEMObject:
+    @property
+    def label(self) -> str: ...
+    @property
+    def headers(self) -> dict[str, str]: ...
+    @property
+    def der(self) -> bytes: ...
+    @property
+    def value(self) -> typing.Any: ...
+
+def parse_all(
+    data: bytes,
+    password: bytes | None = None,
+    *,
+    unsafe_skip_rsa_key_validation: bool = False,
+) -> list[PEMObject]: ...
@@ -0,0 +1,15 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from cryptography import x509
+from cryptography.hazmat.primitives.asymmetric.types import PrivateKeyTypes
+
+def load_legacy_pkcs12(
+    data: bytes,
+    password: bytes | None,
+) -> tuple[
+    PrivateKeyTypes | None,
+    tuple[x509.Certificate, bytes | None] | None,
+    list[tuple[x509.Certificate, bytes | None]],
+]: ...
@@ -3,11 +3,13 @@
 # for complete details.
 
 import datetime
+import os
 import typing
 
 from cryptography import x509
 from cryptography.hazmat.primitives import hashes
 from cryptography.hazmat.primitives.asymmetric.padding import PSS, PKCS1v15
+from cryptography.hazmat.primitives.asymmetric.signer import ExternalSigner
 from cryptography.hazmat.primitives.asymmetric.types import PrivateKeyTypes
 
 def load_pem_x509_certificate(
@@ -19,6 +21,9 @@ def load_der_x509_certificate(
 def load_pem_x509_certificates(
     data: bytes,
 ) -> list[x509.Certificate]: ...
+def load_pem_x509_certificates_from_file(
+    file: str | os.PathLike[str] | typing.BinaryIO,
+) -> list[x509.Certificate]: ...
 def load_pem_x509_crl(
     data: bytes, backend: typing.Any = None
 ) -> x509.CertificateRevocationList: ...
@@ -35,19 +40,19 @@ def encode_name_bytes(name: x509.Name) -> bytes: ...
 def encode_extension_value(extension: x509.ExtensionType) -> bytes: ...
 def create_x509_certificate(
     builder: x509.CertificateBuilder,
-    private_key: PrivateKeyTypes,
+    private_key: PrivateKeyTypes | ExternalSigner,
     hash_algorithm: hashes.HashAlgorithm | None,
     rsa_padding: PKCS1v15 | PSS | None,
 ) -> x509.Certificate: ...
 def create_x509_csr(
     builder: x509.CertificateSigningRequestBuilder,
-    private_key: PrivateKeyTypes,
+    private_key: PrivateKeyTypes | ExternalSigner,
     hash_algorithm: hashes.HashAlgorithm | None,
     rsa_padding: PKCS1v15 | PSS | None,
 ) -> x509.CertificateSigningRequest: ...
 def create_x509_crl(
     builder: x509.CertificateRevocationListBuilder,
-    private_key: PrivateKeyTypes,
+    private_key: PrivateKeyTypes | ExternalSigner,
     hash_algorithm: hashes.HashAlgorithm | None,
     rsa_padding: PKCS1v15 | PSS | None,
 ) -> x509.CertificateRevocationList: ...
@@ -80,9 +85,19 @@ class ServerVerifier:
         leaf: x509.Certificate,
         intermediates: list[x509.Certificate],
     ) -> list[x509.Certificate]: ...
+    def verify_with_ocsp(
+        self,
+        leaf: x509.Certificate,
+        intermediates: list[x509.Certificate],
+        ocsp_response: bytes,
+    ) -> list[x509.Certificate]: ...
 
 class Store:
     def __init__(self, certs: list[x509.Certificate]) -> None: ...
 
+def subject_matches(
+    certificate: x509.Certificate, subject: x509.verification.Subject
+) -> bool: ...
+
 class VerificationError(Exception):
     pass
@@ -7,7 +7,15 @@ from __future__ import annotations
 import abc
 
 from cryptography import utils
-from cryptography.hazmat.primitives.hashes import HashAlgorithm
+from cryptography.exceptions import UnsupportedAlgorithm, _Reasons
+from cryptography.hazmat.primitives.hashes import (
+    SHA1,
+    SHA224,
+    SHA256,
+    SHA384,
+    SHA512,
+    HashAlgorithm,
+)
 
 # This exists to break an import cycle. These classes are normally accessible
 # from the serialization module.
@@ -18,6 +26,11 @@ class PBES(utils.Enum):
     PBESv2SHA256AndAES256CBC = "PBESv2 using SHA256 PBKDF2 and AES256 CBC"
 
 
+class PrivateKeyCipher(utils.Enum):
+    AES128CBC = "AES-128-CBC"
+    AES256CBC = "AES-256-CBC"
+
+
 class Encoding(utils.Enum):
     PEM = "PEM"
     DER = "DER"
@@ -33,12 +46,17 @@ class PrivateFormat(utils.Enum):
     Raw = "Raw"
     OpenSSH = "OpenSSH"
     PKCS12 = "PKCS12"
+    OneAsymmetricKey = "OneAsymmetricKey"
 
     def encryption_builder(self) -> KeySerializationEncryptionBuilder:
-        if self not in (PrivateFormat.OpenSSH, PrivateFormat.PKCS12):
+        if self not in (
+            PrivateFormat.OpenSSH,
+            PrivateFormat.PKCS12,
+            PrivateFormat.PKCS8,
+        ):
             raise ValueError(
                 "encryption_builder only supported with PrivateFormat.OpenSSH"
-                " and PrivateFormat.PKCS12"
+                ", PrivateFormat.PKCS12 and PrivateFormat.PKCS8"
             )
         return KeySerializationEncryptionBuilder(self)
 
@@ -54,6 +72,8 @@ class PublicFormat(utils.Enum):
 
 class ParameterFormat(utils.Enum):
     PKCS3 = "PKCS3"
+    X942 = "X9.42"
+    ECParameters = "ECParameters"
 
 
 class KeySerializationEncryption(metaclass=abc.ABCMeta):
@@ -72,6 +92,9 @@ class NoEncryption(KeySerializationEncryption):
     pass
 
 
+_PBKDF2_PRF_HASHES = (SHA1, SHA224, SHA256, SHA384, SHA512)
+
+
 class KeySerializationEncryptionBuilder:
     def __init__(
         self,
@@ -80,17 +103,26 @@ class KeySerializationEncryptionBuilder:
         _kdf_rounds: int | None = None,
         _hmac_hash: HashAlgorithm | None = None,
         _key_cert_algorithm: PBES | None = None,
+        _scrypt: tuple[int, int, int] | None = None,
+        _cipher: PrivateKeyCipher | None = None,
+        _pbkdf2_prf: HashAlgorithm | None = None,
     ) -> None:
         self._format = format
 
         self._kdf_rounds = _kdf_rounds
         self._hmac_hash = _hmac_hash
         self._key_cert_algorithm = _key_cert_algorithm
+        self._scrypt = _scrypt
+        self._cipher = _cipher
+        self._pbkdf2_prf = _pbkdf2_prf
 
     def kdf_rounds(self, rounds: int) -> KeySerializationEncryptionBuilder:
         if self._kdf_rounds is not None:
             raise ValueError("kdf_rounds already set")
 
+        if self._scrypt is not None:
+            raise ValueError("kdf_rounds can't be combined with scrypt")
+
         if not isinstance(rounds, int):
             raise TypeError("kdf_rounds must be an integer")
 
@@ -102,6 +134,9 @@ class KeySerializationEncryptionBuilder:
             _kdf_rounds=rounds,
             _hmac_hash=self._hmac_hash,
             _key_cert_algorithm=self._key_cert_algorithm,
+            _scrypt=self._scrypt,
+            _cipher=self._cipher,
+            _pbkdf2_prf=self._pbkdf2_prf,
         )
 
     def hmac_hash(
@@ -119,6 +154,9 @@ class KeySerializationEncryptionBuilder:
             _kdf_rounds=self._kdf_rounds,
             _hmac_hash=algorithm,
             _key_cert_algorithm=self._key_cert_algorithm,
+            _scrypt=self._scrypt,
+            _cipher=self._cipher,
+            _pbkdf2_prf=self._pbkdf2_prf,
         )
 
     def key_cert_algorithm(
@@ -136,6 +174,96 @@ class KeySerializationEncryptionBuilder:
             _kdf_rounds=self._kdf_rounds,
             _hmac_hash=self._hmac_hash,
             _key_cert_algorithm=algorithm,
+            _scrypt=self._scrypt,
+            _cipher=self._cipher,
+            _pbkdf2_prf=self._pbkdf2_prf,
+        )
+
+    def scrypt(
+        self, n: int, r: int, p: int
+    ) -> KeySerializationEncryptionBuilder:
+        from cryptography.hazmat.backends.openssl.backend import (
+            backend as ossl,
+        )
+
+        if self._format is not PrivateFormat.PKCS8:
+            raise TypeError("scrypt only supported with PrivateFormat.PKCS8")
+        if self._scrypt is not None:
+            raise ValueError("scrypt already set")
+        if self._kdf_rounds is not None or self._pbkdf2_prf is not None:
+            raise ValueError(
+                "scrypt can't be combined with kdf_rounds or pbkdf2_prf"
+            )
+        if not ossl.scrypt_supported():
+            raise UnsupportedAlgorithm(
+                "This version of OpenSSL does not support scrypt",
+                _Reasons.UNSUPPORTED_CIPHER,
+            )
+        if not all(isinstance(x, int) for x in (n, r, p)):
+            raise TypeError("n, r and p must be integers")
+        if n < 2 or (n & (n - 1)) != 0:
+            raise ValueError("n must be greater than 1 and be a power of 2.")
+        if r < 1:
+            raise ValueError("r must be greater than or equal to 1.")
+        if p < 1:
+            raise ValueError("p must be greater than or equal to 1.")
+
+        return KeySerializationEncryptionBuilder(
+            self._format,
+            _kdf_rounds=self._kdf_rounds,
+            _hmac_hash=self._hmac_hash,
+            _key_cert_algorithm=self._key_cert_algorithm,
+            _scrypt=(n, r, p),
+            _cipher=self._cipher,
+            _pbkdf2_prf=self._pbkdf2_prf,
+        )
+
+    def cipher(
+        self, algorithm: PrivateKeyCipher
+    ) -> KeySerializationEncryptionBuilder:
+        if self._format is not PrivateFormat.PKCS8:
+            raise TypeError("cipher only supported with PrivateFormat.PKCS8")
+        if self._cipher is not None:
+            raise ValueError("cipher already set")
+        if not isinstance(algorithm, PrivateKeyCipher):
+            raise TypeError(
+                "algorithm must be an item from the PrivateKeyCipher enum"
+            )
+
+        return KeySerializationEncryptionBuilder(
+            self._format,
+            _kdf_rounds=self._kdf_rounds,
+            _hmac_hash=self._hmac_hash,
+            _key_cert_algorithm=self._key_cert_algorithm,
+            _scrypt=self._scrypt,
+            _cipher=algorithm,
+            _pbkdf2_prf=self._pbkdf2_prf,
+        )
+
+    def pbkdf2_prf(
+        self, algorithm: HashAlgorithm
+    ) -> KeySerializationEncryptionBuilder:
+        if self._format is not PrivateFormat.PKCS8:
+            raise TypeError(
+                "pbkdf2_prf only supported with PrivateFormat.PKCS8"
+            )
+        if self._pbkdf2_prf is not None:
+            raise ValueError("pbkdf2_prf already set")
+        if self._scrypt is not None:
+            raise ValueError("pbkdf2_prf can't be combined with scrypt")
+        if not isinstance(algorithm, _PBKDF2_PRF_HASHES):
+            raise TypeError(
+                "algorithm must be SHA1, SHA224, SHA256, SHA384 or SHA512"
+            )
+
+        return KeySerializationEncryptionBuilder(
+            self._format,
+            _kdf_rounds=self._kdf_rounds,
+            _hmac_hash=self._hmac_hash,
+            _key_cert_algorithm=self._key_cert_algorithm,
+            _scrypt=self._scrypt,
+            _cipher=self._cipher,
+            _pbkdf2_prf=algorithm,
         )
 
     def build(self, password: bytes) -> KeySerializationEncryption:
@@ -148,6 +276,9 @@ class KeySerializationEncryptionBuilder:
             kdf_rounds=self._kdf_rounds,
             hmac_hash=self._hmac_hash,
             key_cert_algorithm=self._key_cert_algorithm,
+            scrypt=self._scrypt,
+            cipher=self._cipher,
+            pbkdf2_prf=self._pbkdf2_prf,
         )
 
 
@@ -160,6 +291,9 @@ class _KeySerializationEncryption(KeySerializationEncryption):
         kdf_rounds: int | None,
         hmac_hash: HashAlgorithm | None,
         key_cert_algorithm: PBES | None,
+        scrypt: tuple[int, int, int] | None = None,
+        cipher: PrivateKeyCipher | None = None,
+        pbkdf2_prf: HashAlgorithm | None = None,
     ):
         self._format = format
         self.password = password
@@ -167,3 +301,6 @@ class _KeySerializationEncryption(KeySerializationEncryption):
         self._kdf_rounds = kdf_rounds
         self._hmac_hash = hmac_hash
         self._key_cert_algorithm = key_cert_algorithm
+        self._scrypt = scrypt
+        self._cipher = cipher
+        self._pbkdf2_prf = pbkdf2_prf
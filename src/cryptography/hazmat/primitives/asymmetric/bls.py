@@ -0,0 +1,132 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from __future__ import annotations
+
+import abc
+import typing
+
+from cryptography import utils
+from cryptography.hazmat.bindings._rust import openssl as rust_openssl
+
+
+class BLSVariant(utils.Enum):
+    MIN_PK = "minimal-pubkey-size"
+    MIN_SIG = "minimal-signature-size"
+
+
+class BLSPublicKey(metaclass=abc.ABCMeta):
+    @classmethod
+    def from_public_bytes(
+        cls, data: bytes, variant: BLSVariant
+    ) -> BLSPublicKey:
+        return rust_openssl.bls.from_public_bytes(data, variant)
+
+    @property
+    @abc.abstractmethod
+    def variant(self) -> BLSVariant:
+        """
+        Whether public keys or signatures use the smaller group.
+        """
+
+    @abc.abstractmethod
+    def verify(self, signature: bytes, data: bytes) -> None:
+        """
+        Verify the signature.
+        """
+
+    @abc.abstractmethod
+    def verify_possession(self, proof: bytes) -> None:
+        """
+        Verify a proof of possession of the private key.
+        """
+
+    @abc.abstractmethod
+    def public_bytes_raw(self) -> bytes:
+        """
+        The compressed encoding of the public key.
+        """
+
+    @abc.abstractmethod
+    def __eq__(self, other: object) -> bool:
+        """
+        Checks equality.
+        """
+
+
+BLSPublicKey.register(rust_openssl.bls.BLSPublicKey)
+
+
+class BLSPrivateKey(metaclass=abc.ABCMeta):
+    @classmethod
+    def generate(cls, variant: BLSVariant) -> BLSPrivateKey:
+        return rust_openssl.bls.generate_private_key(variant)
+
+    @classmethod
+    def derive(
+        cls, ikm: bytes, variant: BLSVariant, key_info: bytes = b""
+    ) -> BLSPrivateKey:
+        return rust_openssl.bls.derive_private_key(ikm, variant, key_info)
+
+    @classmethod
+    def from_private_bytes(
+        cls, data: bytes, variant: BLSVariant
+    ) -> BLSPrivateKey:
+        return rust_openssl.bls.from_private_bytes(data, variant)
+
+    @property
+    @abc.abstractmethod
+    def variant(self) -> BLSVariant:
+        """
+        Whether public keys or signatures use the smaller group.
+        """
+
+    @abc.abstractmethod
+    def public_key(self) -> BLSPublicKey:
+        """
+        The BLSPublicKey derived from the private key.
+        """
+
+    @abc.abstractmethod
+    def sign(self, data: bytes) -> bytes:
+        """
+        Signs the data.
+        """
+
+    @abc.abstractmethod
+    def prove_possession(self) -> bytes:
+        """
+        A proof of possession of the private key.
+        """
+
+    @abc.abstractmethod
+    def private_bytes_raw(self) -> bytes:
+        """
+        The private key as a 32 byte big endian integer.
+        """
+
+
+BLSPrivateKey.register(rust_openssl.bls.BLSPrivateKey)
+
+
+def aggregate_signatures(
+    signatures: typing.Sequence[bytes], variant: BLSVariant
+) -> bytes:
+    return rust_openssl.bls.aggregate_signatures(signatures, variant)
+
+
+def aggregate_verify(
+    public_keys: typing.Sequence[BLSPublicKey],
+    messages: typing.Sequence[bytes],
+    signature: bytes,
+) -> None:
+    rust_openssl.bls.aggregate_verify(public_keys, messages, signature)
+
+
+def fast_aggregate_verify(
+    public_keys: typing.Sequence[BLSPublicKey],
+    message: bytes,
+    signature: bytes,
+) -> None:
+    rust_openssl.bls.fast_aggregate_verify(public_keys, message, signature)
@@ -5,11 +5,17 @@
 from __future__ import annotations
 
 import abc
+import typing
 
 from cryptography.hazmat.bindings._rust import openssl as rust_openssl
-from cryptography.hazmat.primitives import _serialization
+from cryptography.hazmat.primitives import _serialization, hashes
+from cryptography.hazmat.primitives.kdf import KeyDerivationFunction
+
+if typing.TYPE_CHECKING:
+    from cryptography import x509
 
 generate_parameters = rust_openssl.dh.generate_parameters
+named_group_parameters = rust_openssl.dh.named_group_parameters
 
 
 DHPrivateNumbers = rust_openssl.dh.DHPrivateNumbers
@@ -40,6 +46,13 @@ class DHParameters(metaclass=abc.ABCMeta):
         Returns a DHParameterNumbers.
         """
 
+    @property
+    @abc.abstractmethod
+    def group_name(self) -> str | None:
+        """
+        The name of the RFC 7919 group, if the parameters are one.
+        """
+
 
 DHParametersWithSerialization = DHParameters
 DHParameters.register(rust_openssl.dh.DHParameters)
@@ -75,6 +88,18 @@ class DHPublicKey(metaclass=abc.ABCMeta):
         Returns the key serialized as bytes.
         """
 
+    @abc.abstractmethod
+    def fingerprint(self, algorithm: hashes.HashAlgorithm) -> bytes:
+        """
+        The hash of the DER encoded SubjectPublicKeyInfo of the key.
+        """
+
+    @abc.abstractmethod
+    def validate(self) -> None:
+        """
+        Performs full public key validation per NIST SP 800-56A.
+        """
+
     @abc.abstractmethod
     def __eq__(self, other: object) -> bool:
         """
@@ -100,6 +125,21 @@ class DHPrivateKey(metaclass=abc.ABCMeta):
         The DHPublicKey associated with this private key.
         """
 
+    @abc.abstractmethod
+    def public_key_matches(
+        self, public_key: x509.Certificate | DHPublicKey
+    ) -> bool:
+        """
+        Checks whether the public key, or the certificate's public key, is
+        the public key of this private key.
+        """
+
+    @abc.abstractmethod
+    def __eq__(self, other: object) -> bool:
+        """
+        Checks equality.
+        """
+
     @abc.abstractmethod
     def parameters(self) -> DHParameters:
         """
@@ -107,12 +147,28 @@ class DHPrivateKey(metaclass=abc.ABCMeta):
         """
 
     @abc.abstractmethod
-    def exchange(self, peer_public_key: DHPublicKey) -> bytes:
+    def exchange(
+        self, peer_public_key: DHPublicKey, *, validate: bool = False
+    ) -> bytes:
         """
         Given peer's DHPublicKey, carry out the key exchange and
         return shared key as bytes.
         """
 
+    @abc.abstractmethod
+    def exchange_and_derive(
+        self,
+        peer_public_key: DHPublicKey,
+        kdf: KeyDerivationFunction,
+        *,
+        validate: bool = False,
+        pad: bool = True,
+    ) -> bytes:
+        """
+        Performs a key exchange and passes the shared key directly to the
+        provided key derivation function.
+        """
+
     @abc.abstractmethod
     def private_numbers(self) -> DHPrivateNumbers:
         """
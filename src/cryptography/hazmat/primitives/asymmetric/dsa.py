@@ -11,6 +11,9 @@ from cryptography.hazmat.bindings._rust import openssl as rust_openssl
 from cryptography.hazmat.primitives import _serialization, hashes
 from cryptography.hazmat.primitives.asymmetric import utils as asym_utils
 
+if typing.TYPE_CHECKING:
+    from cryptography import x509
+
 
 class DSAParameters(metaclass=abc.ABCMeta):
     @abc.abstractmethod
@@ -44,6 +47,21 @@ class DSAPrivateKey(metaclass=abc.ABCMeta):
         The DSAPublicKey associated with this private key.
         """
 
+    @abc.abstractmethod
+    def public_key_matches(
+        self, public_key: x509.Certificate | DSAPublicKey
+    ) -> bool:
+        """
+        Checks whether the public key, or the certificate's public key, is
+        the public key of this private key.
+        """
+
+    @abc.abstractmethod
+    def __eq__(self, other: object) -> bool:
+        """
+        Checks equality.
+        """
+
     @abc.abstractmethod
     def parameters(self) -> DSAParameters:
         """
@@ -55,6 +73,8 @@ class DSAPrivateKey(metaclass=abc.ABCMeta):
         self,
         data: bytes,
         algorithm: asym_utils.Prehashed | hashes.HashAlgorithm,
+        *,
+        deterministic_signing: bool = False,
     ) -> bytes:
         """
         Signs the data
@@ -123,6 +143,12 @@ class DSAPublicKey(metaclass=abc.ABCMeta):
         Verifies the signature of the data.
         """
 
+    @abc.abstractmethod
+    def fingerprint(self, algorithm: hashes.HashAlgorithm) -> bytes:
+        """
+        The hash of the DER encoded SubjectPublicKeyInfo of the key.
+        """
+
     @abc.abstractmethod
     def __eq__(self, other: object) -> bool:
         """
@@ -139,12 +165,30 @@ DSAParameterNumbers = rust_openssl.dsa.DSAParameterNumbers
 
 
 def generate_parameters(
-    key_size: int, backend: typing.Any = None
+    key_size: int,
+    backend: typing.Any = None,
+    *,
+    progress: typing.Callable[[int, int], object] | None = None,
+    cancel: typing.Any = None,
 ) -> DSAParameters:
     if key_size not in (1024, 2048, 3072, 4096):
         raise ValueError("Key size must be 1024, 2048, 3072, or 4096 bits.")
 
-    return rust_openssl.dsa.generate_parameters(key_size)
+    return rust_openssl.dsa.generate_parameters(
+        key_size, progress=progress, cancel=cancel
+    )
+
+
+def generate_verifiable_parameters(
+    key_size: int,
+) -> tuple[DSAParameters, bytes, int]:
+    if key_size not in (1024, 2048, 3072):
+        raise ValueError("Key size must be 1024, 2048, or 3072 bits.")
+
+    return rust_openssl.dsa.generate_verifiable_parameters(key_size)
+
+
+validate_parameters = rust_openssl.dsa.validate_parameters
 
 
 def generate_private_key(
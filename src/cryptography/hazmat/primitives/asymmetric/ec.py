@@ -8,14 +8,20 @@ import abc
 import typing
 
 from cryptography import utils
+from cryptography.exceptions import UnsupportedAlgorithm, _Reasons
 from cryptography.hazmat._oid import ObjectIdentifier
 from cryptography.hazmat.bindings._rust import openssl as rust_openssl
 from cryptography.hazmat.primitives import _serialization, hashes
 from cryptography.hazmat.primitives.asymmetric import utils as asym_utils
+from cryptography.hazmat.primitives.kdf import KeyDerivationFunction
+
+if typing.TYPE_CHECKING:
+    from cryptography import x509
 
 
 class EllipticCurveOID:
     SECP192R1 = ObjectIdentifier("1.2.840.10045.3.1.1")
+    SECP224K1 = ObjectIdentifier("1.3.132.0.32")
     SECP224R1 = ObjectIdentifier("1.3.132.0.33")
     SECP256K1 = ObjectIdentifier("1.3.132.0.10")
     SECP256R1 = ObjectIdentifier("1.2.840.10045.3.1.7")
@@ -24,6 +30,10 @@ class EllipticCurveOID:
     BRAINPOOLP256R1 = ObjectIdentifier("1.3.36.3.3.2.8.1.1.7")
     BRAINPOOLP384R1 = ObjectIdentifier("1.3.36.3.3.2.8.1.1.11")
     BRAINPOOLP512R1 = ObjectIdentifier("1.3.36.3.3.2.8.1.1.13")
+    BRAINPOOLP256T1 = ObjectIdentifier("1.3.36.3.3.2.8.1.1.8")
+    BRAINPOOLP384T1 = ObjectIdentifier("1.3.36.3.3.2.8.1.1.12")
+    BRAINPOOLP512T1 = ObjectIdentifier("1.3.36.3.3.2.8.1.1.14")
+    FRP256V1 = ObjectIdentifier("1.2.250.1.223.101.256.1")
     SECT163K1 = ObjectIdentifier("1.3.132.0.1")
     SECT163R2 = ObjectIdentifier("1.3.132.0.15")
     SECT233K1 = ObjectIdentifier("1.3.132.0.26")
@@ -64,6 +74,12 @@ class EllipticCurveSignatureAlgorithm(metaclass=abc.ABCMeta):
 
 
 class EllipticCurvePrivateKey(metaclass=abc.ABCMeta):
+    @classmethod
+    def from_private_bytes(
+        cls, curve: EllipticCurve, data: bytes
+    ) -> EllipticCurvePrivateKey:
+        return rust_openssl.ec.from_private_bytes(curve, data)
+
     @abc.abstractmethod
     def exchange(
         self, algorithm: ECDH, peer_public_key: EllipticCurvePublicKey
@@ -73,12 +89,49 @@ class EllipticCurvePrivateKey(metaclass=abc.ABCMeta):
         provided peer's public key.
         """
 
+    @abc.abstractmethod
+    def exchange_and_derive(
+        self,
+        algorithm: ECDH,
+        peer_public_key: EllipticCurvePublicKey,
+        kdf: KeyDerivationFunction,
+    ) -> bytes:
+        """
+        Performs a key exchange and passes the shared key directly to the
+        provided key derivation function.
+        """
+
+    @abc.abstractmethod
+    def exchange_many(
+        self,
+        algorithm: ECDH,
+        peer_public_keys: typing.Sequence[EllipticCurvePublicKey],
+    ) -> list[bytes]:
+        """
+        Performs a key exchange with each of the peers.
+        """
+
     @abc.abstractmethod
     def public_key(self) -> EllipticCurvePublicKey:
         """
         The EllipticCurvePublicKey for this private key.
         """
 
+    @abc.abstractmethod
+    def public_key_matches(
+        self, public_key: x509.Certificate | EllipticCurvePublicKey
+    ) -> bool:
+        """
+        Checks whether the public key, or the certificate's public key, is
+        the public key of this private key.
+        """
+
+    @abc.abstractmethod
+    def __eq__(self, other: object) -> bool:
+        """
+        Checks equality.
+        """
+
     @property
     @abc.abstractmethod
     def curve(self) -> EllipticCurve:
@@ -103,12 +156,30 @@ class EllipticCurvePrivateKey(metaclass=abc.ABCMeta):
         Signs the data
         """
 
+    @abc.abstractmethod
+    def sign_init(self, signature_algorithm: ECDSA) -> ECDSASigningContext:
+        """
+        Returns a context for signing data which is provided incrementally.
+        """
+
+    @abc.abstractmethod
+    def precompute(self) -> None:
+        """
+        Prepares state which is reused to speed up subsequent signatures.
+        """
+
     @abc.abstractmethod
     def private_numbers(self) -> EllipticCurvePrivateNumbers:
         """
         Returns an EllipticCurvePrivateNumbers.
         """
 
+    @abc.abstractmethod
+    def private_bytes_raw(self) -> bytes:
+        """
+        The private scalar as a fixed length big-endian integer.
+        """
+
     @abc.abstractmethod
     def private_bytes(
         self,
@@ -120,9 +191,18 @@ class EllipticCurvePrivateKey(metaclass=abc.ABCMeta):
         Returns the key serialized as bytes.
         """
 
+    @abc.abstractmethod
+    def jwk_private_bytes(self, kid: str | None = None) -> bytes:
+        """
+        The private key as a JSON Web Key.
+        """
+
 
 EllipticCurvePrivateKeyWithSerialization = EllipticCurvePrivateKey
 EllipticCurvePrivateKey.register(rust_openssl.ec.ECPrivateKey)
+EllipticCurvePrivateKey.register(
+    rust_openssl.store.OpaqueEllipticCurvePrivateKey
+)
 
 
 class EllipticCurvePublicKey(metaclass=abc.ABCMeta):
@@ -156,6 +236,12 @@ class EllipticCurvePublicKey(metaclass=abc.ABCMeta):
         Returns the key serialized as bytes.
         """
 
+    @abc.abstractmethod
+    def jwk_public_bytes(self, kid: str | None = None) -> bytes:
+        """
+        The public key as a JSON Web Key.
+        """
+
     @abc.abstractmethod
     def verify(
         self,
@@ -167,6 +253,21 @@ class EllipticCurvePublicKey(metaclass=abc.ABCMeta):
         Verifies the signature of the data.
         """
 
+    @abc.abstractmethod
+    def verify_init(
+        self, signature: bytes, signature_algorithm: ECDSA
+    ) -> ECDSAVerificationContext:
+        """
+        Returns a context for verifying the signature of data which is
+        provided incrementally.
+        """
+
+    @abc.abstractmethod
+    def precompute(self) -> None:
+        """
+        Prepares state which is reused to speed up subsequent verifications.
+        """
+
     @classmethod
     def from_encoded_point(
         cls, curve: EllipticCurve, data: bytes
@@ -181,6 +282,20 @@ class EllipticCurvePublicKey(metaclass=abc.ABCMeta):
 
         return rust_openssl.ec.from_public_bytes(curve, data)
 
+    @abc.abstractmethod
+    def fingerprint(self, algorithm: hashes.HashAlgorithm) -> bytes:
+        """
+        The hash of the DER encoded SubjectPublicKeyInfo of the key.
+        """
+
+    @abc.abstractmethod
+    def jwk_thumbprint(
+        self, algorithm: hashes.HashAlgorithm | None = None
+    ) -> bytes:
+        """
+        The RFC 7638 thumbprint of the key's JSON Web Key.
+        """
+
     @abc.abstractmethod
     def __eq__(self, other: object) -> bool:
         """
@@ -191,8 +306,20 @@ class EllipticCurvePublicKey(metaclass=abc.ABCMeta):
 EllipticCurvePublicKeyWithSerialization = EllipticCurvePublicKey
 EllipticCurvePublicKey.register(rust_openssl.ec.ECPublicKey)
 
+
+def _load_der_public_key(data: bytes) -> EllipticCurvePublicKey:
+    # Unpickles the keys pickled by `ECPublicKey.__reduce__`. Functions defined
+    # in Rust can't be found by pickle, so this stands in for them.
+    return typing.cast(
+        EllipticCurvePublicKey, rust_openssl.keys.load_der_public_key(data)
+    )
+
 EllipticCurvePrivateNumbers = rust_openssl.ec.EllipticCurvePrivateNumbers
 EllipticCurvePublicNumbers = rust_openssl.ec.EllipticCurvePublicNumbers
+ECPoint = rust_openssl.ec.ECPoint
+EllipticCurveParameters = rust_openssl.ec.EllipticCurveParameters
+ECDSASigningContext = rust_openssl.ec.ECDSASigningContext
+ECDSAVerificationContext = rust_openssl.ec.ECDSAVerificationContext
 
 
 class SECT571R1(EllipticCurve):
@@ -265,6 +392,11 @@ class SECP256K1(EllipticCurve):
     key_size = 256
 
 
+class SECP224K1(EllipticCurve):
+    name = "secp224k1"
+    key_size = 225
+
+
 class SECP224R1(EllipticCurve):
     name = "secp224r1"
     key_size = 224
@@ -290,6 +422,26 @@ class BrainpoolP512R1(EllipticCurve):
     key_size = 512
 
 
+class BrainpoolP256T1(EllipticCurve):
+    name = "brainpoolP256t1"
+    key_size = 256
+
+
+class BrainpoolP384T1(EllipticCurve):
+    name = "brainpoolP384t1"
+    key_size = 384
+
+
+class BrainpoolP512T1(EllipticCurve):
+    name = "brainpoolP512t1"
+    key_size = 512
+
+
+class FRP256V1(EllipticCurve):
+    name = "FRP256v1"
+    key_size = 256
+
+
 _CURVE_TYPES: dict[str, EllipticCurve] = {
     "prime192v1": SECP192R1(),
     "prime256v1": SECP256R1(),
@@ -299,6 +451,7 @@ _CURVE_TYPES: dict[str, EllipticCurve] = {
     "secp384r1": SECP384R1(),
     "secp521r1": SECP521R1(),
     "secp256k1": SECP256K1(),
+    "secp224k1": SECP224K1(),
     "sect163k1": SECT163K1(),
     "sect233k1": SECT233K1(),
     "sect283k1": SECT283K1(),
@@ -312,15 +465,57 @@ _CURVE_TYPES: dict[str, EllipticCurve] = {
     "brainpoolP256r1": BrainpoolP256R1(),
     "brainpoolP384r1": BrainpoolP384R1(),
     "brainpoolP512r1": BrainpoolP512R1(),
+    "brainpoolP256t1": BrainpoolP256T1(),
+    "brainpoolP384t1": BrainpoolP384T1(),
+    "brainpoolP512t1": BrainpoolP512T1(),
+    "FRP256v1": FRP256V1(),
 }
 
 
+class ECDSASignatureFormat(utils.Enum):
+    DER = "DER"
+    P1363 = "IEEE P1363"
+
+
 class ECDSA(EllipticCurveSignatureAlgorithm):
     def __init__(
         self,
         algorithm: asym_utils.Prehashed | hashes.HashAlgorithm,
+        deterministic_signing: bool = False,
+        signature_format: ECDSASignatureFormat = ECDSASignatureFormat.DER,
+        low_s: bool = False,
     ):
+        from cryptography.hazmat.backends.openssl.backend import backend
+
+        if not isinstance(signature_format, ECDSASignatureFormat):
+            raise TypeError(
+                "signature_format must be an ECDSASignatureFormat"
+            )
+        if (
+            deterministic_signing
+            and not backend.ecdsa_deterministic_supported()
+        ):
+            raise UnsupportedAlgorithm(
+                "ECDSA with deterministic signature (RFC 6979) is not "
+                "supported by this version of OpenSSL.",
+                _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
+            )
+        hash_algorithm = (
+            algorithm._algorithm
+            if isinstance(algorithm, asym_utils.Prehashed)
+            else algorithm
+        )
+        if deterministic_signing and isinstance(
+            hash_algorithm, hashes.ExtendableOutputFunction
+        ):
+            raise ValueError(
+                "Deterministic signing is not supported with extendable "
+                "output functions."
+            )
         self._algorithm = algorithm
+        self._deterministic_signing = deterministic_signing
+        self._signature_format = signature_format
+        self._low_s = low_s
 
     @property
     def algorithm(
@@ -328,10 +523,49 @@ class ECDSA(EllipticCurveSignatureAlgorithm):
     ) -> asym_utils.Prehashed | hashes.HashAlgorithm:
         return self._algorithm
 
+    @property
+    def deterministic_signing(self) -> bool:
+        return self._deterministic_signing
+
+    @property
+    def signature_format(self) -> ECDSASignatureFormat:
+        return self._signature_format
+
+    @property
+    def low_s(self) -> bool:
+        return self._low_s
+
+
+class BIP340Schnorr(EllipticCurveSignatureAlgorithm):
+    def __init__(self, aux_rand: bytes | None = None):
+        if aux_rand is not None:
+            utils._check_bytes("aux_rand", aux_rand)
+            if len(aux_rand) != 32:
+                raise ValueError("aux_rand must be 32 bytes long")
+        self._aux_rand = aux_rand
+
+    @property
+    def algorithm(self) -> hashes.HashAlgorithm:
+        return hashes.SHA256()
+
+    @property
+    def aux_rand(self) -> bytes | None:
+        return self._aux_rand
+
 
 generate_private_key = rust_openssl.ec.generate_private_key
 
 
+def supported_curves() -> list[type[EllipticCurve]]:
+    from cryptography.hazmat.backends.openssl.backend import backend
+
+    return [
+        curve
+        for curve in rust_openssl.ec.supported_curves()
+        if backend.elliptic_curve_supported(curve())
+    ]
+
+
 def derive_private_key(
     private_value: int,
     curve: EllipticCurve,
@@ -346,12 +580,62 @@ def derive_private_key(
     return rust_openssl.ec.derive_private_key(private_value, curve)
 
 
+def derive_private_key_from_seed(
+    seed: bytes, curve: EllipticCurve, info: bytes = b""
+) -> EllipticCurvePrivateKey:
+    utils._check_byteslike("seed", seed)
+    utils._check_bytes("info", info)
+    if len(seed) < 16:
+        raise ValueError("seed must be at least 16 bytes long.")
+
+    return rust_openssl.ec.derive_private_key_from_seed(seed, curve, info)
+
+
+def normalize_signature(
+    signature: bytes,
+    curve: EllipticCurve,
+    signature_format: ECDSASignatureFormat = ECDSASignatureFormat.DER,
+) -> bytes:
+    if not isinstance(signature_format, ECDSASignatureFormat):
+        raise TypeError("signature_format must be an ECDSASignatureFormat")
+
+    return rust_openssl.ec.normalize_signature(
+        signature, curve, signature_format
+    )
+
+
+def recover_public_key(
+    curve: EllipticCurve,
+    signature: bytes,
+    data: bytes,
+    signature_algorithm: EllipticCurveSignatureAlgorithm,
+    recovery_id: int,
+) -> EllipticCurvePublicKey:
+    if not isinstance(curve, EllipticCurve):
+        raise TypeError("curve must be an EllipticCurve instance")
+
+    return rust_openssl.ec.recover_public_key(
+        curve, signature, data, signature_algorithm, recovery_id
+    )
+
+
 class ECDH:
-    pass
+    def __init__(self, cofactor: bool = False, full_point: bool = False):
+        self._cofactor = cofactor
+        self._full_point = full_point
+
+    @property
+    def cofactor(self) -> bool:
+        return self._cofactor
+
+    @property
+    def full_point(self) -> bool:
+        return self._full_point
 
 
 _OID_TO_CURVE = {
     EllipticCurveOID.SECP192R1: SECP192R1,
+    EllipticCurveOID.SECP224K1: SECP224K1,
     EllipticCurveOID.SECP224R1: SECP224R1,
     EllipticCurveOID.SECP256K1: SECP256K1,
     EllipticCurveOID.SECP256R1: SECP256R1,
@@ -360,6 +644,10 @@ _OID_TO_CURVE = {
     EllipticCurveOID.BRAINPOOLP256R1: BrainpoolP256R1,
     EllipticCurveOID.BRAINPOOLP384R1: BrainpoolP384R1,
     EllipticCurveOID.BRAINPOOLP512R1: BrainpoolP512R1,
+    EllipticCurveOID.BRAINPOOLP256T1: BrainpoolP256T1,
+    EllipticCurveOID.BRAINPOOLP384T1: BrainpoolP384T1,
+    EllipticCurveOID.BRAINPOOLP512T1: BrainpoolP512T1,
+    EllipticCurveOID.FRP256V1: FRP256V1,
     EllipticCurveOID.SECT163K1: SECT163K1,
     EllipticCurveOID.SECT163R2: SECT163R2,
     EllipticCurveOID.SECT233K1: SECT233K1,
@@ -0,0 +1,37 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from __future__ import annotations
+
+import typing
+
+from cryptography import utils
+from cryptography.hazmat.bindings._rust import openssl as rust_openssl
+from cryptography.hazmat.primitives.asymmetric import ec, ed25519
+
+__all__ = [
+    "ECVRFPrivateKeyTypes",
+    "ECVRFPublicKeyTypes",
+    "ECVRFSuite",
+    "proof_to_hash",
+    "prove",
+    "verify",
+]
+
+
+class ECVRFSuite(utils.Enum):
+    P256_SHA256_TAI = "ECVRF-P256-SHA256-TAI"
+    EDWARDS25519_SHA512_TAI = "ECVRF-EDWARDS25519-SHA512-TAI"
+
+
+ECVRFPrivateKeyTypes = typing.Union[
+    ec.EllipticCurvePrivateKey, ed25519.Ed25519PrivateKey
+]
+ECVRFPublicKeyTypes = typing.Union[
+    ec.EllipticCurvePublicKey, ed25519.Ed25519PublicKey
+]
+
+prove = rust_openssl.ecvrf.prove
+verify = rust_openssl.ecvrf.verify
+proof_to_hash = rust_openssl.ecvrf.proof_to_hash
@@ -5,10 +5,15 @@
 from __future__ import annotations
 
 import abc
+import typing
 
 from cryptography.exceptions import UnsupportedAlgorithm, _Reasons
 from cryptography.hazmat.bindings._rust import openssl as rust_openssl
-from cryptography.hazmat.primitives import _serialization
+from cryptography.hazmat.primitives import _serialization, hashes
+from cryptography.hazmat.primitives.asymmetric import x25519
+
+if typing.TYPE_CHECKING:
+    from cryptography import x509
 
 
 class Ed25519PublicKey(metaclass=abc.ABCMeta):
@@ -34,6 +39,12 @@ class Ed25519PublicKey(metaclass=abc.ABCMeta):
         The serialized bytes of the public key.
         """
 
+    @abc.abstractmethod
+    def jwk_public_bytes(self, kid: str | None = None) -> bytes:
+        """
+        The public key as a JSON Web Key.
+        """
+
     @abc.abstractmethod
     def public_bytes_raw(self) -> bytes:
         """
@@ -42,11 +53,40 @@ class Ed25519PublicKey(metaclass=abc.ABCMeta):
         """
 
     @abc.abstractmethod
-    def verify(self, signature: bytes, data: bytes) -> None:
+    def to_x25519_public_key(self) -> x25519.X25519PublicKey:
+        """
+        The equivalent X25519 public key.
+        """
+
+    @abc.abstractmethod
+    def verify(
+        self,
+        signature: bytes,
+        data: bytes,
+        *,
+        context: bytes | None = None,
+        prehash: bool = False,
+        cofactored: bool = False,
+        strict: bool = False,
+    ) -> None:
         """
         Verify the signature.
         """
 
+    @abc.abstractmethod
+    def fingerprint(self, algorithm: hashes.HashAlgorithm) -> bytes:
+        """
+        The hash of the DER encoded SubjectPublicKeyInfo of the key.
+        """
+
+    @abc.abstractmethod
+    def jwk_thumbprint(
+        self, algorithm: hashes.HashAlgorithm | None = None
+    ) -> bytes:
+        """
+        The RFC 7638 thumbprint of the key's JSON Web Key.
+        """
+
     @abc.abstractmethod
     def __eq__(self, other: object) -> bool:
         """
@@ -88,6 +128,21 @@ class Ed25519PrivateKey(metaclass=abc.ABCMeta):
         The Ed25519PublicKey derived from the private key.
         """
 
+    @abc.abstractmethod
+    def public_key_matches(
+        self, public_key: x509.Certificate | Ed25519PublicKey
+    ) -> bool:
+        """
+        Checks whether the public key, or the certificate's public key, is
+        the public key of this private key.
+        """
+
+    @abc.abstractmethod
+    def __eq__(self, other: object) -> bool:
+        """
+        Checks equality.
+        """
+
     @abc.abstractmethod
     def private_bytes(
         self,
@@ -99,6 +154,12 @@ class Ed25519PrivateKey(metaclass=abc.ABCMeta):
         The serialized bytes of the private key.
         """
 
+    @abc.abstractmethod
+    def jwk_private_bytes(self, kid: str | None = None) -> bytes:
+        """
+        The private key as a JSON Web Key.
+        """
+
     @abc.abstractmethod
     def private_bytes_raw(self) -> bytes:
         """
@@ -107,7 +168,19 @@ class Ed25519PrivateKey(metaclass=abc.ABCMeta):
         """
 
     @abc.abstractmethod
-    def sign(self, data: bytes) -> bytes:
+    def to_x25519_private_key(self) -> x25519.X25519PrivateKey:
+        """
+        The equivalent X25519 private key.
+        """
+
+    @abc.abstractmethod
+    def sign(
+        self,
+        data: bytes,
+        *,
+        context: bytes | None = None,
+        prehash: bool = False,
+    ) -> bytes:
         """
         Signs the data.
         """
@@ -0,0 +1,62 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from __future__ import annotations
+
+import abc
+
+from cryptography import utils
+from cryptography.exceptions import UnsupportedAlgorithm, _Reasons
+from cryptography.hazmat.bindings._rust import openssl as rust_openssl
+
+
+class FalconParameterSet(utils.Enum):
+    FALCON_512 = "Falcon-512"
+    FALCON_1024 = "Falcon-1024"
+
+
+def _check_supported() -> None:
+    from cryptography.hazmat.backends.openssl.backend import backend
+
+    if not backend.falcon_supported():
+        raise UnsupportedAlgorithm(
+            "Falcon is not supported by this version of OpenSSL.",
+            _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
+        )
+
+
+class FalconPublicKey(metaclass=abc.ABCMeta):
+    @classmethod
+    def from_public_bytes(cls, data: bytes) -> FalconPublicKey:
+        _check_supported()
+        return rust_openssl.falcon.from_public_bytes(data)
+
+    @property
+    @abc.abstractmethod
+    def parameter_set(self) -> FalconParameterSet:
+        """
+        The Falcon parameter set of the key.
+        """
+
+    @abc.abstractmethod
+    def public_bytes_raw(self) -> bytes:
+        """
+        The encoded public key, including its header byte.
+        """
+
+    @abc.abstractmethod
+    def verify(self, signature: bytes, data: bytes) -> None:
+        """
+        Verify the signature.
+        """
+
+    @abc.abstractmethod
+    def __eq__(self, other: object) -> bool:
+        """
+        Checks equality.
+        """
+
+
+if hasattr(rust_openssl, "falcon"):
+    FalconPublicKey.register(rust_openssl.falcon.FalconPublicKey)
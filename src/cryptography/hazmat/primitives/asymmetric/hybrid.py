@@ -0,0 +1,82 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from __future__ import annotations
+
+import abc
+
+from cryptography import utils
+from cryptography.exceptions import UnsupportedAlgorithm, _Reasons
+from cryptography.hazmat.bindings._rust import openssl as rust_openssl
+from cryptography.hazmat.primitives.kdf import KeyDerivationFunction
+
+
+class HybridGroup(utils.Enum):
+    X25519MLKEM768 = "X25519MLKEM768"
+    SECP256R1MLKEM768 = "SecP256r1MLKEM768"
+    SECP384R1MLKEM1024 = "SecP384r1MLKEM1024"
+
+
+def _check_supported() -> None:
+    from cryptography.hazmat.backends.openssl.backend import backend
+
+    if not backend.hybrid_key_exchange_supported():
+        raise UnsupportedAlgorithm(
+            "Hybrid key exchange is not supported by this version of "
+            "OpenSSL.",
+            _Reasons.UNSUPPORTED_EXCHANGE_ALGORITHM,
+        )
+
+
+class HybridPrivateKeyShare(metaclass=abc.ABCMeta):
+    @classmethod
+    def generate(cls, group: HybridGroup) -> HybridPrivateKeyShare:
+        _check_supported()
+        return rust_openssl.hybrid.generate_key_share(group)
+
+    @classmethod
+    def from_private_bytes(
+        cls, group: HybridGroup, data: bytes
+    ) -> HybridPrivateKeyShare:
+        _check_supported()
+        return rust_openssl.hybrid.from_private_bytes(group, data)
+
+    @property
+    @abc.abstractmethod
+    def group(self) -> HybridGroup:
+        """
+        The hybrid group of the key share.
+        """
+
+    @abc.abstractmethod
+    def key_share(self) -> bytes:
+        """
+        The key share to send to the peer.
+        """
+
+    @abc.abstractmethod
+    def derive(
+        self, peer_key_share: bytes, kdf: KeyDerivationFunction
+    ) -> bytes:
+        """
+        Completes the exchange with the peer's response and passes the
+        combined shared secret directly to the key derivation function.
+        """
+
+    @abc.abstractmethod
+    def private_bytes_raw(self) -> bytes:
+        """
+        The ML-KEM seed followed by the ECDH private key.
+        """
+
+
+if hasattr(rust_openssl, "hybrid"):
+    HybridPrivateKeyShare.register(rust_openssl.hybrid.HybridPrivateKeyShare)
+
+
+def respond(
+    group: HybridGroup, peer_key_share: bytes, kdf: KeyDerivationFunction
+) -> tuple[bytes, bytes]:
+    _check_supported()
+    return rust_openssl.hybrid.respond(group, peer_key_share, kdf)
@@ -0,0 +1,53 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from __future__ import annotations
+
+import typing
+
+from cryptography.exceptions import UnsupportedAlgorithm, _Reasons
+from cryptography.hazmat.bindings._rust import openssl as rust_openssl
+from cryptography.hazmat.primitives.asymmetric import (
+    ec,
+    rsa,
+    x448,
+    x25519,
+    xwing,
+)
+
+# Every key type that can be used as a KEM
+KEMPublicKeyTypes = typing.Union[
+    x25519.X25519PublicKey,
+    x448.X448PublicKey,
+    ec.EllipticCurvePublicKey,
+    rsa.RSAPublicKey,
+    xwing.XWingPublicKey,
+]
+KEMPrivateKeyTypes = typing.Union[
+    x25519.X25519PrivateKey,
+    x448.X448PrivateKey,
+    ec.EllipticCurvePrivateKey,
+    rsa.RSAPrivateKey,
+    xwing.XWingPrivateKey,
+]
+
+
+def _check_supported() -> None:
+    from cryptography.hazmat.backends.openssl.backend import backend
+
+    if not backend.kem_supported():
+        raise UnsupportedAlgorithm(
+            "Key encapsulation is not supported by this version of OpenSSL.",
+            _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
+        )
+
+
+def encapsulate(public_key: KEMPublicKeyTypes) -> tuple[bytes, bytes]:
+    _check_supported()
+    return rust_openssl.kem.encapsulate(public_key)
+
+
+def decapsulate(private_key: KEMPrivateKeyTypes, ciphertext: bytes) -> bytes:
+    _check_supported()
+    return rust_openssl.kem.decapsulate(private_key, ciphertext)
@@ -0,0 +1,12 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from __future__ import annotations
+
+from cryptography.hazmat.bindings._rust import openssl as rust_openssl
+
+KeystoreKey = rust_openssl.keystore.KeystoreKey
+list_keys = rust_openssl.keystore.list_keys
+
+__all__ = ["KeystoreKey", "list_keys"]
@@ -0,0 +1,159 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from __future__ import annotations
+
+import abc
+import typing
+
+from cryptography import utils
+from cryptography.exceptions import UnsupportedAlgorithm, _Reasons
+from cryptography.hazmat.bindings._rust import openssl as rust_openssl
+from cryptography.hazmat.primitives import _serialization, hashes
+
+if typing.TYPE_CHECKING:
+    from cryptography import x509
+
+
+class MLDSAParameterSet(utils.Enum):
+    ML_DSA_44 = "ML-DSA-44"
+    ML_DSA_65 = "ML-DSA-65"
+    ML_DSA_87 = "ML-DSA-87"
+
+
+def _check_supported() -> None:
+    from cryptography.hazmat.backends.openssl.backend import backend
+
+    if not backend.mldsa_supported():
+        raise UnsupportedAlgorithm(
+            "ML-DSA is not supported by this version of OpenSSL.",
+            _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
+        )
+
+
+class MLDSAPublicKey(metaclass=abc.ABCMeta):
+    @classmethod
+    def from_public_bytes(
+        cls, parameter_set: MLDSAParameterSet, data: bytes
+    ) -> MLDSAPublicKey:
+        _check_supported()
+        return rust_openssl.mldsa.from_public_bytes(parameter_set, data)
+
+    @property
+    @abc.abstractmethod
+    def parameter_set(self) -> MLDSAParameterSet:
+        """
+        The ML-DSA parameter set of the key.
+        """
+
+    @abc.abstractmethod
+    def public_bytes(
+        self,
+        encoding: _serialization.Encoding,
+        format: _serialization.PublicFormat,
+    ) -> bytes:
+        """
+        The serialized bytes of the public key.
+        """
+
+    @abc.abstractmethod
+    def public_bytes_raw(self) -> bytes:
+        """
+        The raw bytes of the public key.
+        Equivalent to public_bytes(Raw, Raw).
+        """
+
+    @abc.abstractmethod
+    def verify(
+        self, signature: bytes, data: bytes, *, context: bytes | None = None
+    ) -> None:
+        """
+        Verify the signature.
+        """
+
+    @abc.abstractmethod
+    def fingerprint(self, algorithm: hashes.HashAlgorithm) -> bytes:
+        """
+        The hash of the DER encoded SubjectPublicKeyInfo of the key.
+        """
+
+    @abc.abstractmethod
+    def __eq__(self, other: object) -> bool:
+        """
+        Checks equality.
+        """
+
+
+if hasattr(rust_openssl, "mldsa"):
+    MLDSAPublicKey.register(rust_openssl.mldsa.MLDSAPublicKey)
+
+
+class MLDSAPrivateKey(metaclass=abc.ABCMeta):
+    @classmethod
+    def generate(cls, parameter_set: MLDSAParameterSet) -> MLDSAPrivateKey:
+        _check_supported()
+        return rust_openssl.mldsa.generate_key(parameter_set)
+
+    @classmethod
+    def from_seed_bytes(
+        cls, parameter_set: MLDSAParameterSet, data: bytes
+    ) -> MLDSAPrivateKey:
+        _check_supported()
+        return rust_openssl.mldsa.from_seed_bytes(parameter_set, data)
+
+    @property
+    @abc.abstractmethod
+    def parameter_set(self) -> MLDSAParameterSet:
+        """
+        The ML-DSA parameter set of the key.
+        """
+
+    @abc.abstractmethod
+    def public_key(self) -> MLDSAPublicKey:
+        """
+        The MLDSAPublicKey derived from the private key.
+        """
+
+    @abc.abstractmethod
+    def public_key_matches(
+        self, public_key: x509.Certificate | MLDSAPublicKey
+    ) -> bool:
+        """
+        Checks whether the public key, or the certificate's public key, is
+        the public key of this private key.
+        """
+
+    @abc.abstractmethod
+    def private_bytes(
+        self,
+        encoding: _serialization.Encoding,
+        format: _serialization.PrivateFormat,
+        encryption_algorithm: _serialization.KeySerializationEncryption,
+    ) -> bytes:
+        """
+        The serialized bytes of the private key.
+        """
+
+    @abc.abstractmethod
+    def private_bytes_raw(self) -> bytes:
+        """
+        The seed the private key was derived from.
+        Equivalent to private_bytes(Raw, Raw, NoEncryption()).
+        """
+
+    @abc.abstractmethod
+    def sign(self, data: bytes, *, context: bytes | None = None) -> bytes:
+        """
+        Signs the data.
+        """
+
+    @abc.abstractmethod
+    def __eq__(self, other: object) -> bool:
+        """
+        Checks equality.
+        """
+
+
+if hasattr(rust_openssl, "mldsa"):
+    MLDSAPrivateKey.register(rust_openssl.mldsa.MLDSAPrivateKey)
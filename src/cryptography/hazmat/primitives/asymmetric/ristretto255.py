@@ -0,0 +1,135 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from __future__ import annotations
+
+import abc
+
+from cryptography.hazmat.bindings._rust import openssl as rust_openssl
+
+
+class Ristretto255Scalar(metaclass=abc.ABCMeta):
+    @classmethod
+    def from_bytes(cls, data: bytes) -> Ristretto255Scalar:
+        return rust_openssl.ristretto255.scalar_from_bytes(data)
+
+    @classmethod
+    def from_uniform_bytes(cls, data: bytes) -> Ristretto255Scalar:
+        return rust_openssl.ristretto255.scalar_from_uniform_bytes(data)
+
+    @classmethod
+    def random(cls) -> Ristretto255Scalar:
+        return rust_openssl.ristretto255.random_scalar()
+
+    @abc.abstractmethod
+    def to_bytes(self) -> bytes:
+        """
+        The 32 byte little-endian encoding of the scalar.
+        """
+
+    @abc.abstractmethod
+    def invert(self) -> Ristretto255Scalar:
+        """
+        The multiplicative inverse of the scalar.
+        """
+
+    @abc.abstractmethod
+    def __add__(self, other: Ristretto255Scalar) -> Ristretto255Scalar:
+        """
+        Adds two scalars.
+        """
+
+    @abc.abstractmethod
+    def __sub__(self, other: Ristretto255Scalar) -> Ristretto255Scalar:
+        """
+        Subtracts two scalars.
+        """
+
+    @abc.abstractmethod
+    def __mul__(self, other: Ristretto255Scalar) -> Ristretto255Scalar:
+        """
+        Multiplies two scalars.
+        """
+
+    @abc.abstractmethod
+    def __neg__(self) -> Ristretto255Scalar:
+        """
+        Negates the scalar.
+        """
+
+    @abc.abstractmethod
+    def __eq__(self, other: object) -> bool:
+        """
+        Checks equality.
+        """
+
+
+Ristretto255Scalar.register(rust_openssl.ristretto255.Ristretto255Scalar)
+
+
+class Ristretto255Element(metaclass=abc.ABCMeta):
+    @classmethod
+    def from_bytes(cls, data: bytes) -> Ristretto255Element:
+        return rust_openssl.ristretto255.element_from_bytes(data)
+
+    @classmethod
+    def from_uniform_bytes(cls, data: bytes) -> Ristretto255Element:
+        return rust_openssl.ristretto255.element_from_uniform_bytes(data)
+
+    @classmethod
+    def hash_to_group(cls, data: bytes, dst: bytes) -> Ristretto255Element:
+        return rust_openssl.ristretto255.hash_to_group(data, dst)
+
+    @classmethod
+    def generator(cls) -> Ristretto255Element:
+        return rust_openssl.ristretto255.generator()
+
+    @classmethod
+    def identity(cls) -> Ristretto255Element:
+        return rust_openssl.ristretto255.identity()
+
+    @abc.abstractmethod
+    def to_bytes(self) -> bytes:
+        """
+        The 32 byte canonical encoding of the element.
+        """
+
+    @abc.abstractmethod
+    def __add__(self, other: Ristretto255Element) -> Ristretto255Element:
+        """
+        Adds two elements.
+        """
+
+    @abc.abstractmethod
+    def __sub__(self, other: Ristretto255Element) -> Ristretto255Element:
+        """
+        Subtracts two elements.
+        """
+
+    @abc.abstractmethod
+    def __neg__(self) -> Ristretto255Element:
+        """
+        Negates the element.
+        """
+
+    @abc.abstractmethod
+    def __mul__(self, scalar: Ristretto255Scalar) -> Ristretto255Element:
+        """
+        Multiplies the element by a scalar.
+        """
+
+    @abc.abstractmethod
+    def __rmul__(self, scalar: Ristretto255Scalar) -> Ristretto255Element:
+        """
+        Multiplies the element by a scalar.
+        """
+
+    @abc.abstractmethod
+    def __eq__(self, other: object) -> bool:
+        """
+        Checks equality.
+        """
+
+
+Ristretto255Element.register(rust_openssl.ristretto255.Ristretto255Element)
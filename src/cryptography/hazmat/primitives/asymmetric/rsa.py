@@ -13,6 +13,10 @@ from cryptography.hazmat.primitives import _serialization, hashes
 from cryptography.hazmat.primitives._asymmetric import AsymmetricPadding
 from cryptography.hazmat.primitives.asymmetric import utils as asym_utils
 
+if typing.TYPE_CHECKING:
+    from cryptography import x509
+    from cryptography.hazmat.primitives.asymmetric.padding import PSS
+
 
 class RSAPrivateKey(metaclass=abc.ABCMeta):
     @abc.abstractmethod
@@ -21,6 +25,12 @@ class RSAPrivateKey(metaclass=abc.ABCMeta):
         Decrypts the provided ciphertext.
         """
 
+    @abc.abstractmethod
+    def unsafe_raw_decrypt(self, ciphertext: bytes) -> bytes:
+        """
+        Applies the private key operation with no padding.
+        """
+
     @property
     @abc.abstractmethod
     def key_size(self) -> int:
@@ -34,6 +44,21 @@ class RSAPrivateKey(metaclass=abc.ABCMeta):
         The RSAPublicKey associated with this private key.
         """
 
+    @abc.abstractmethod
+    def public_key_matches(
+        self, public_key: x509.Certificate | RSAPublicKey
+    ) -> bool:
+        """
+        Checks whether the public key, or the certificate's public key, is
+        the public key of this private key.
+        """
+
+    @abc.abstractmethod
+    def __eq__(self, other: object) -> bool:
+        """
+        Checks equality.
+        """
+
     @abc.abstractmethod
     def sign(
         self,
@@ -45,12 +70,28 @@ class RSAPrivateKey(metaclass=abc.ABCMeta):
         Signs the data.
         """
 
+    @abc.abstractmethod
+    def signer(
+        self,
+        padding: AsymmetricPadding,
+        algorithm: asym_utils.Prehashed | hashes.HashAlgorithm,
+    ) -> RSASigner:
+        """
+        Returns a reusable RSASigner for the padding and algorithm.
+        """
+
     @abc.abstractmethod
     def private_numbers(self) -> RSAPrivateNumbers:
         """
         Returns an RSAPrivateNumbers.
         """
 
+    @abc.abstractmethod
+    def check(self) -> RSAKeyCheck:
+        """
+        Checks the consistency of the key's components.
+        """
+
     @abc.abstractmethod
     def private_bytes(
         self,
@@ -62,9 +103,16 @@ class RSAPrivateKey(metaclass=abc.ABCMeta):
         Returns the key serialized as bytes.
         """
 
+    @abc.abstractmethod
+    def jwk_private_bytes(self, kid: str | None = None) -> bytes:
+        """
+        The private key as a JSON Web Key.
+        """
+
 
 RSAPrivateKeyWithSerialization = RSAPrivateKey
 RSAPrivateKey.register(rust_openssl.rsa.RSAPrivateKey)
+RSAPrivateKey.register(rust_openssl.store.OpaqueRSAPrivateKey)
 
 
 class RSAPublicKey(metaclass=abc.ABCMeta):
@@ -74,6 +122,18 @@ class RSAPublicKey(metaclass=abc.ABCMeta):
         Encrypts the given plaintext.
         """
 
+    @abc.abstractmethod
+    def encryptor(self, padding: AsymmetricPadding) -> RSAEncryptor:
+        """
+        Returns a reusable RSAEncryptor for the padding.
+        """
+
+    @abc.abstractmethod
+    def unsafe_raw_encrypt(self, plaintext: bytes) -> bytes:
+        """
+        Applies the public key operation with no padding.
+        """
+
     @property
     @abc.abstractmethod
     def key_size(self) -> int:
@@ -97,6 +157,12 @@ class RSAPublicKey(metaclass=abc.ABCMeta):
         Returns the key serialized as bytes.
         """
 
+    @abc.abstractmethod
+    def jwk_public_bytes(self, kid: str | None = None) -> bytes:
+        """
+        The public key as a JSON Web Key.
+        """
+
     @abc.abstractmethod
     def verify(
         self,
@@ -109,6 +175,28 @@ class RSAPublicKey(metaclass=abc.ABCMeta):
         Verifies the signature of the data.
         """
 
+    @abc.abstractmethod
+    def verifier(
+        self,
+        padding: AsymmetricPadding,
+        algorithm: asym_utils.Prehashed | hashes.HashAlgorithm,
+    ) -> RSAVerifier:
+        """
+        Returns a reusable RSAVerifier for the padding and algorithm.
+        """
+
+    @abc.abstractmethod
+    def verify_pss(
+        self,
+        signature: bytes,
+        data: bytes,
+        padding: PSS,
+        algorithm: asym_utils.Prehashed | hashes.HashAlgorithm,
+    ) -> int:
+        """
+        Verifies a PSS signature and returns the signer's salt length.
+        """
+
     @abc.abstractmethod
     def recover_data_from_signature(
         self,
@@ -120,6 +208,20 @@ class RSAPublicKey(metaclass=abc.ABCMeta):
         Recovers the original data from the signature.
         """
 
+    @abc.abstractmethod
+    def fingerprint(self, algorithm: hashes.HashAlgorithm) -> bytes:
+        """
+        The hash of the DER encoded SubjectPublicKeyInfo of the key.
+        """
+
+    @abc.abstractmethod
+    def jwk_thumbprint(
+        self, algorithm: hashes.HashAlgorithm | None = None
+    ) -> bytes:
+        """
+        The RFC 7638 thumbprint of the key's JSON Web Key.
+        """
+
     @abc.abstractmethod
     def __eq__(self, other: object) -> bool:
         """
@@ -130,17 +232,103 @@ class RSAPublicKey(metaclass=abc.ABCMeta):
 RSAPublicKeyWithSerialization = RSAPublicKey
 RSAPublicKey.register(rust_openssl.rsa.RSAPublicKey)
 
+
+class RSAPSSPrivateKey(RSAPrivateKey):
+    @property
+    @abc.abstractmethod
+    def hash_algorithm(self) -> hashes.HashAlgorithm | None:
+        """
+        The only hash algorithm the key may sign with, if it's restricted.
+        """
+
+    @property
+    @abc.abstractmethod
+    def padding(self) -> PSS | None:
+        """
+        The PSS padding with the key's MGF1 hash and minimum salt length, if
+        it's restricted.
+        """
+
+    @abc.abstractmethod
+    def public_key(self) -> RSAPSSPublicKey:
+        """
+        The RSAPSSPublicKey associated with this private key.
+        """
+
+
+RSAPSSPrivateKey.register(rust_openssl.rsa.RSAPSSPrivateKey)
+
+
+class RSAPSSPublicKey(RSAPublicKey):
+    @property
+    @abc.abstractmethod
+    def hash_algorithm(self) -> hashes.HashAlgorithm | None:
+        """
+        The only hash algorithm the key may verify with, if it's restricted.
+        """
+
+    @property
+    @abc.abstractmethod
+    def padding(self) -> PSS | None:
+        """
+        The PSS padding with the key's MGF1 hash and minimum salt length, if
+        it's restricted.
+        """
+
+
+RSAPSSPublicKey.register(rust_openssl.rsa.RSAPSSPublicKey)
+
 RSAPrivateNumbers = rust_openssl.rsa.RSAPrivateNumbers
 RSAPublicNumbers = rust_openssl.rsa.RSAPublicNumbers
+RSAKeyCheck = rust_openssl.rsa.RSAKeyCheck
+RSASigner = rust_openssl.rsa.RSASigner
+RSAVerifier = rust_openssl.rsa.RSAVerifier
+RSAEncryptor = rust_openssl.rsa.RSAEncryptor
+RSABSSA = rust_openssl.rsa.RSABSSA
+
+# The variants defined in RFC 9474, section 5.
+RSABSSA_SHA384_PSS_RANDOMIZED = RSABSSA(hashes.SHA384(), 48, randomized=True)
+RSABSSA_SHA384_PSSZERO_RANDOMIZED = RSABSSA(
+    hashes.SHA384(), 0, randomized=True
+)
+RSABSSA_SHA384_PSS_DETERMINISTIC = RSABSSA(
+    hashes.SHA384(), 48, randomized=False
+)
+RSABSSA_SHA384_PSSZERO_DETERMINISTIC = RSABSSA(
+    hashes.SHA384(), 0, randomized=False
+)
 
 
 def generate_private_key(
     public_exponent: int,
     key_size: int,
     backend: typing.Any = None,
+    *,
+    primes: int = 2,
 ) -> RSAPrivateKey:
     _verify_rsa_parameters(public_exponent, key_size)
-    return rust_openssl.rsa.generate_private_key(public_exponent, key_size)
+    if primes < 2:
+        raise ValueError("primes must be at least 2.")
+    return rust_openssl.rsa.generate_private_key(
+        public_exponent, key_size, primes
+    )
+
+
+def generate_private_keys(
+    count: int,
+    public_exponent: int,
+    key_size: int,
+    *,
+    primes: int = 2,
+) -> list[RSAPrivateKey]:
+    if count < 0:
+        raise ValueError("count must not be negative.")
+    _verify_rsa_parameters(public_exponent, key_size)
+    if primes < 2:
+        raise ValueError("primes must be at least 2.")
+    return rust_openssl.rsa.generate_private_keys(
+        count, public_exponent, key_size, primes
+    )
 
 
 def _verify_rsa_parameters(public_exponent: int, key_size: int) -> None:
@@ -237,3 +425,11 @@ def rsa_recover_prime_factors(n: int, e: int, d: int) -> tuple[int, int]:
     assert r == 0
     p, q = sorted((p, q), reverse=True)
     return (p, q)
+
+
+def rsa_recover_private_numbers(n: int, e: int, d: int) -> RSAPrivateNumbers:
+    """
+    Compute the full private numbers, with p > q, from the modulus and the
+    public and private exponents.
+    """
+    return rust_openssl.rsa.recover_private_numbers(n, e, d)
@@ -0,0 +1,33 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from __future__ import annotations
+
+import abc
+
+from cryptography.hazmat.bindings._rust import openssl as rust_openssl
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric.types import (
+    CertificateIssuerPublicKeyTypes,
+)
+
+
+class ExternalSigner(metaclass=abc.ABCMeta):
+    @abc.abstractmethod
+    def public_key(self) -> CertificateIssuerPublicKeyTypes:
+        """
+        The public key of the key used for signing.
+        """
+
+    @abc.abstractmethod
+    def sign(
+        self, data: bytes, algorithm: hashes.HashAlgorithm | None
+    ) -> bytes:
+        """
+        Signs the data, hashing it with algorithm, in the same scheme and
+        signature encoding as the private key type of public_key().
+        """
+
+
+ExternalSigner.register(rust_openssl.keystore.KeystoreKey)
@@ -0,0 +1,168 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from __future__ import annotations
+
+import abc
+import typing
+
+from cryptography import utils
+from cryptography.exceptions import UnsupportedAlgorithm, _Reasons
+from cryptography.hazmat.bindings._rust import openssl as rust_openssl
+from cryptography.hazmat.primitives import _serialization, hashes
+
+if typing.TYPE_CHECKING:
+    from cryptography import x509
+
+
+class SLHDSAParameterSet(utils.Enum):
+    SLH_DSA_SHA2_128S = "SLH-DSA-SHA2-128s"
+    SLH_DSA_SHA2_128F = "SLH-DSA-SHA2-128f"
+    SLH_DSA_SHA2_192S = "SLH-DSA-SHA2-192s"
+    SLH_DSA_SHA2_192F = "SLH-DSA-SHA2-192f"
+    SLH_DSA_SHA2_256S = "SLH-DSA-SHA2-256s"
+    SLH_DSA_SHA2_256F = "SLH-DSA-SHA2-256f"
+    SLH_DSA_SHAKE_128S = "SLH-DSA-SHAKE-128s"
+    SLH_DSA_SHAKE_128F = "SLH-DSA-SHAKE-128f"
+    SLH_DSA_SHAKE_192S = "SLH-DSA-SHAKE-192s"
+    SLH_DSA_SHAKE_192F = "SLH-DSA-SHAKE-192f"
+    SLH_DSA_SHAKE_256S = "SLH-DSA-SHAKE-256s"
+    SLH_DSA_SHAKE_256F = "SLH-DSA-SHAKE-256f"
+
+
+def _check_supported() -> None:
+    from cryptography.hazmat.backends.openssl.backend import backend
+
+    if not backend.slhdsa_supported():
+        raise UnsupportedAlgorithm(
+            "SLH-DSA is not supported by this version of OpenSSL.",
+            _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
+        )
+
+
+class SLHDSAPublicKey(metaclass=abc.ABCMeta):
+    @classmethod
+    def from_public_bytes(
+        cls, parameter_set: SLHDSAParameterSet, data: bytes
+    ) -> SLHDSAPublicKey:
+        _check_supported()
+        return rust_openssl.slhdsa.from_public_bytes(parameter_set, data)
+
+    @property
+    @abc.abstractmethod
+    def parameter_set(self) -> SLHDSAParameterSet:
+        """
+        The SLH-DSA parameter set of the key.
+        """
+
+    @abc.abstractmethod
+    def public_bytes(
+        self,
+        encoding: _serialization.Encoding,
+        format: _serialization.PublicFormat,
+    ) -> bytes:
+        """
+        The serialized bytes of the public key.
+        """
+
+    @abc.abstractmethod
+    def public_bytes_raw(self) -> bytes:
+        """
+        The raw bytes of the public key.
+        Equivalent to public_bytes(Raw, Raw).
+        """
+
+    @abc.abstractmethod
+    def verify(
+        self, signature: bytes, data: bytes, *, context: bytes | None = None
+    ) -> None:
+        """
+        Verify the signature.
+        """
+
+    @abc.abstractmethod
+    def fingerprint(self, algorithm: hashes.HashAlgorithm) -> bytes:
+        """
+        The hash of the DER encoded SubjectPublicKeyInfo of the key.
+        """
+
+    @abc.abstractmethod
+    def __eq__(self, other: object) -> bool:
+        """
+        Checks equality.
+        """
+
+
+if hasattr(rust_openssl, "slhdsa"):
+    SLHDSAPublicKey.register(rust_openssl.slhdsa.SLHDSAPublicKey)
+
+
+class SLHDSAPrivateKey(metaclass=abc.ABCMeta):
+    @classmethod
+    def generate(cls, parameter_set: SLHDSAParameterSet) -> SLHDSAPrivateKey:
+        _check_supported()
+        return rust_openssl.slhdsa.generate_key(parameter_set)
+
+    @classmethod
+    def from_private_bytes(
+        cls, parameter_set: SLHDSAParameterSet, data: bytes
+    ) -> SLHDSAPrivateKey:
+        _check_supported()
+        return rust_openssl.slhdsa.from_private_bytes(parameter_set, data)
+
+    @property
+    @abc.abstractmethod
+    def parameter_set(self) -> SLHDSAParameterSet:
+        """
+        The SLH-DSA parameter set of the key.
+        """
+
+    @abc.abstractmethod
+    def public_key(self) -> SLHDSAPublicKey:
+        """
+        The SLHDSAPublicKey derived from the private key.
+        """
+
+    @abc.abstractmethod
+    def public_key_matches(
+        self, public_key: x509.Certificate | SLHDSAPublicKey
+    ) -> bool:
+        """
+        Checks whether the public key, or the certificate's public key, is
+        the public key of this private key.
+        """
+
+    @abc.abstractmethod
+    def private_bytes(
+        self,
+        encoding: _serialization.Encoding,
+        format: _serialization.PrivateFormat,
+        encryption_algorithm: _serialization.KeySerializationEncryption,
+    ) -> bytes:
+        """
+        The serialized bytes of the private key.
+        """
+
+    @abc.abstractmethod
+    def private_bytes_raw(self) -> bytes:
+        """
+        The raw bytes of the private key, which include the public key.
+        Equivalent to private_bytes(Raw, Raw, NoEncryption()).
+        """
+
+    @abc.abstractmethod
+    def sign(self, data: bytes, *, context: bytes | None = None) -> bytes:
+        """
+        Signs the data.
+        """
+
+    @abc.abstractmethod
+    def __eq__(self, other: object) -> bool:
+        """
+        Checks equality.
+        """
+
+
+if hasattr(rust_openssl, "slhdsa"):
+    SLHDSAPrivateKey.register(rust_openssl.slhdsa.SLHDSAPrivateKey)
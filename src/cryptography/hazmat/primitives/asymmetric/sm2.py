@@ -0,0 +1,133 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from __future__ import annotations
+
+import abc
+import typing
+
+from cryptography.exceptions import UnsupportedAlgorithm, _Reasons
+from cryptography.hazmat.bindings._rust import openssl as rust_openssl
+from cryptography.hazmat.primitives import _serialization, hashes
+
+if typing.TYPE_CHECKING:
+    from cryptography import x509
+
+
+def _check_supported() -> None:
+    from cryptography.hazmat.backends.openssl.backend import backend
+
+    if not backend.sm2_supported():
+        raise UnsupportedAlgorithm(
+            "SM2 is not supported by this version of OpenSSL.",
+            _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
+        )
+
+
+class SM2PublicKey(metaclass=abc.ABCMeta):
+    @classmethod
+    def from_encoded_point(cls, data: bytes) -> SM2PublicKey:
+        _check_supported()
+        return rust_openssl.sm2.from_encoded_point(data)
+
+    @abc.abstractmethod
+    def verify(
+        self, signature: bytes, data: bytes, user_id: bytes | None = None
+    ) -> None:
+        """
+        Verify the signature.
+        """
+
+    @abc.abstractmethod
+    def encrypt(self, plaintext: bytes) -> bytes:
+        """
+        Encrypts the given plaintext.
+        """
+
+    @abc.abstractmethod
+    def z_value(self, user_id: bytes | None = None) -> bytes:
+        """
+        The Z value identifying the signer, which is hashed with the data.
+        """
+
+    @abc.abstractmethod
+    def public_bytes(
+        self,
+        encoding: _serialization.Encoding,
+        format: _serialization.PublicFormat,
+    ) -> bytes:
+        """
+        The serialized bytes of the public key.
+        """
+
+    @abc.abstractmethod
+    def fingerprint(self, algorithm: hashes.HashAlgorithm) -> bytes:
+        """
+        The hash of the DER encoded SubjectPublicKeyInfo of the key.
+        """
+
+    @abc.abstractmethod
+    def __eq__(self, other: object) -> bool:
+        """
+        Checks equality.
+        """
+
+
+if hasattr(rust_openssl, "sm2"):
+    SM2PublicKey.register(rust_openssl.sm2.SM2PublicKey)
+
+
+class SM2PrivateKey(metaclass=abc.ABCMeta):
+    @classmethod
+    def generate(cls) -> SM2PrivateKey:
+        _check_supported()
+        return rust_openssl.sm2.generate_private_key()
+
+    @abc.abstractmethod
+    def public_key(self) -> SM2PublicKey:
+        """
+        The SM2PublicKey derived from the private key.
+        """
+
+    @abc.abstractmethod
+    def public_key_matches(
+        self, public_key: x509.Certificate | SM2PublicKey
+    ) -> bool:
+        """
+        Checks whether the public key, or the certificate's public key, is
+        the public key of this private key.
+        """
+
+    @abc.abstractmethod
+    def __eq__(self, other: object) -> bool:
+        """
+        Checks equality.
+        """
+
+    @abc.abstractmethod
+    def sign(self, data: bytes, user_id: bytes | None = None) -> bytes:
+        """
+        Signs the data.
+        """
+
+    @abc.abstractmethod
+    def decrypt(self, ciphertext: bytes) -> bytes:
+        """
+        Decrypts the provided ciphertext.
+        """
+
+    @abc.abstractmethod
+    def private_bytes(
+        self,
+        encoding: _serialization.Encoding,
+        format: _serialization.PrivateFormat,
+        encryption_algorithm: _serialization.KeySerializationEncryption,
+    ) -> bytes:
+        """
+        The serialized bytes of the private key.
+        """
+
+
+if hasattr(rust_openssl, "sm2"):
+    SM2PrivateKey.register(rust_openssl.sm2.SM2PrivateKey)
@@ -0,0 +1,28 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from __future__ import annotations
+
+from cryptography.hazmat.bindings._rust import openssl as rust_openssl
+
+__all__ = [
+    "Party1KeyGen",
+    "Party1KeyShare",
+    "Party1SignSession",
+    "Party2KeyGen",
+    "Party2KeyShare",
+    "Party2SignSession",
+    "load_party1_key_share",
+    "load_party2_key_share",
+]
+
+Party1KeyGen = rust_openssl.threshold_ecdsa.Party1KeyGen
+Party1KeyShare = rust_openssl.threshold_ecdsa.Party1KeyShare
+Party1SignSession = rust_openssl.threshold_ecdsa.Party1SignSession
+Party2KeyGen = rust_openssl.threshold_ecdsa.Party2KeyGen
+Party2KeyShare = rust_openssl.threshold_ecdsa.Party2KeyShare
+Party2SignSession = rust_openssl.threshold_ecdsa.Party2SignSession
+
+load_party1_key_share = rust_openssl.threshold_ecdsa.load_party1_key_share
+load_party2_key_share = rust_openssl.threshold_ecdsa.load_party2_key_share
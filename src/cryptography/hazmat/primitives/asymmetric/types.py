@@ -13,7 +13,10 @@ from cryptography.hazmat.primitives.asymmetric import (
     ec,
     ed448,
     ed25519,
+    mldsa,
     rsa,
+    slhdsa,
+    sm2,
     x448,
     x25519,
 )
@@ -28,6 +31,9 @@ PublicKeyTypes = typing.Union[
     ed448.Ed448PublicKey,
     x25519.X25519PublicKey,
     x448.X448PublicKey,
+    sm2.SM2PublicKey,
+    mldsa.MLDSAPublicKey,
+    slhdsa.SLHDSAPublicKey,
 ]
 PUBLIC_KEY_TYPES = PublicKeyTypes
 utils.deprecated(
@@ -47,6 +53,9 @@ PrivateKeyTypes = typing.Union[
     ec.EllipticCurvePrivateKey,
     x25519.X25519PrivateKey,
     x448.X448PrivateKey,
+    sm2.SM2PrivateKey,
+    mldsa.MLDSAPrivateKey,
+    slhdsa.SLHDSAPrivateKey,
 ]
 PRIVATE_KEY_TYPES = PrivateKeyTypes
 utils.deprecated(
@@ -64,6 +73,8 @@ CertificateIssuerPrivateKeyTypes = typing.Union[
     rsa.RSAPrivateKey,
     dsa.DSAPrivateKey,
     ec.EllipticCurvePrivateKey,
+    mldsa.MLDSAPrivateKey,
+    slhdsa.SLHDSAPrivateKey,
 ]
 CERTIFICATE_PRIVATE_KEY_TYPES = CertificateIssuerPrivateKeyTypes
 utils.deprecated(
@@ -81,6 +92,8 @@ CertificateIssuerPublicKeyTypes = typing.Union[
     ec.EllipticCurvePublicKey,
     ed25519.Ed25519PublicKey,
     ed448.Ed448PublicKey,
+    mldsa.MLDSAPublicKey,
+    slhdsa.SLHDSAPublicKey,
 ]
 CERTIFICATE_ISSUER_PUBLIC_KEY_TYPES = CertificateIssuerPublicKeyTypes
 utils.deprecated(
@@ -100,6 +113,8 @@ CertificatePublicKeyTypes = typing.Union[
     ed448.Ed448PublicKey,
     x25519.X25519PublicKey,
     x448.X448PublicKey,
+    mldsa.MLDSAPublicKey,
+    slhdsa.SLHDSAPublicKey,
 ]
 CERTIFICATE_PUBLIC_KEY_TYPES = CertificatePublicKeyTypes
 utils.deprecated(
@@ -5,10 +5,14 @@
 from __future__ import annotations
 
 from cryptography.hazmat.bindings._rust import asn1
+from cryptography.hazmat.bindings._rust import openssl as rust_openssl
 from cryptography.hazmat.primitives import hashes
 
 decode_dss_signature = asn1.decode_dss_signature
 encode_dss_signature = asn1.encode_dss_signature
+der_to_raw_signature = asn1.der_to_raw_signature
+raw_to_der_signature = asn1.raw_to_der_signature
+unified_exchange_and_derive = rust_openssl.kdf.derive_unified_model
 
 
 class Prehashed:
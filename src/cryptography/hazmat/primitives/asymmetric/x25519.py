@@ -5,10 +5,15 @@
 from __future__ import annotations
 
 import abc
+import typing
 
 from cryptography.exceptions import UnsupportedAlgorithm, _Reasons
 from cryptography.hazmat.bindings._rust import openssl as rust_openssl
-from cryptography.hazmat.primitives import _serialization
+from cryptography.hazmat.primitives import _serialization, hashes
+
+if typing.TYPE_CHECKING:
+    from cryptography import x509
+    from cryptography.hazmat.primitives.asymmetric import ed25519
 
 
 class X25519PublicKey(metaclass=abc.ABCMeta):
@@ -34,6 +39,12 @@ class X25519PublicKey(metaclass=abc.ABCMeta):
         The serialized bytes of the public key.
         """
 
+    @abc.abstractmethod
+    def jwk_public_bytes(self, kid: str | None = None) -> bytes:
+        """
+        The public key as a JSON Web Key.
+        """
+
     @abc.abstractmethod
     def public_bytes_raw(self) -> bytes:
         """
@@ -41,6 +52,32 @@ class X25519PublicKey(metaclass=abc.ABCMeta):
         Equivalent to public_bytes(Raw, Raw).
         """
 
+    @abc.abstractmethod
+    def to_ed25519_public_key(self) -> ed25519.Ed25519PublicKey:
+        """
+        The equivalent Ed25519 public key, with a positive x coordinate.
+        """
+
+    @abc.abstractmethod
+    def xeddsa_verify(self, signature: bytes, data: bytes) -> None:
+        """
+        Verify an XEdDSA signature.
+        """
+
+    @abc.abstractmethod
+    def fingerprint(self, algorithm: hashes.HashAlgorithm) -> bytes:
+        """
+        The hash of the DER encoded SubjectPublicKeyInfo of the key.
+        """
+
+    @abc.abstractmethod
+    def jwk_thumbprint(
+        self, algorithm: hashes.HashAlgorithm | None = None
+    ) -> bytes:
+        """
+        The RFC 7638 thumbprint of the key's JSON Web Key.
+        """
+
     @abc.abstractmethod
     def __eq__(self, other: object) -> bool:
         """
@@ -75,12 +112,41 @@ class X25519PrivateKey(metaclass=abc.ABCMeta):
 
         return rust_openssl.x25519.from_private_bytes(data)
 
+    @classmethod
+    def from_scalar(
+        cls, scalar: bytes, *, clamp: bool = True
+    ) -> X25519PrivateKey:
+        from cryptography.hazmat.backends.openssl.backend import backend
+
+        if not backend.x25519_supported():
+            raise UnsupportedAlgorithm(
+                "X25519 is not supported by this version of OpenSSL.",
+                _Reasons.UNSUPPORTED_EXCHANGE_ALGORITHM,
+            )
+
+        return rust_openssl.x25519.from_scalar(scalar, clamp=clamp)
+
     @abc.abstractmethod
     def public_key(self) -> X25519PublicKey:
         """
         Returns the public key associated with this private key
         """
 
+    @abc.abstractmethod
+    def public_key_matches(
+        self, public_key: x509.Certificate | X25519PublicKey
+    ) -> bool:
+        """
+        Checks whether the public key, or the certificate's public key, is
+        the public key of this private key.
+        """
+
+    @abc.abstractmethod
+    def __eq__(self, other: object) -> bool:
+        """
+        Checks equality.
+        """
+
     @abc.abstractmethod
     def private_bytes(
         self,
@@ -92,6 +158,12 @@ class X25519PrivateKey(metaclass=abc.ABCMeta):
         The serialized bytes of the private key.
         """
 
+    @abc.abstractmethod
+    def jwk_private_bytes(self, kid: str | None = None) -> bytes:
+        """
+        The private key as a JSON Web Key.
+        """
+
     @abc.abstractmethod
     def private_bytes_raw(self) -> bytes:
         """
@@ -99,11 +171,31 @@ class X25519PrivateKey(metaclass=abc.ABCMeta):
         Equivalent to private_bytes(Raw, Raw, NoEncryption()).
         """
 
+    @abc.abstractmethod
+    def clamped_scalar(self) -> bytes:
+        """
+        The RFC 7748 clamped scalar that the key exchange uses.
+        """
+
+    @abc.abstractmethod
+    def xeddsa_sign(self, data: bytes) -> bytes:
+        """
+        Signs the data using XEdDSA.
+        """
+
     @abc.abstractmethod
     def exchange(self, peer_public_key: X25519PublicKey) -> bytes:
         """
         Performs a key exchange operation using the provided peer's public key.
         """
 
+    @abc.abstractmethod
+    def exchange_many(
+        self, peer_keys: typing.Sequence[X25519PublicKey]
+    ) -> list[bytes]:
+        """
+        Performs a key exchange with each of the peers' public keys.
+        """
+
 
 X25519PrivateKey.register(rust_openssl.x25519.X25519PrivateKey)
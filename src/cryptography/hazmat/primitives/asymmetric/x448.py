@@ -5,10 +5,14 @@
 from __future__ import annotations
 
 import abc
+import typing
 
 from cryptography.exceptions import UnsupportedAlgorithm, _Reasons
 from cryptography.hazmat.bindings._rust import openssl as rust_openssl
-from cryptography.hazmat.primitives import _serialization
+from cryptography.hazmat.primitives import _serialization, hashes
+
+if typing.TYPE_CHECKING:
+    from cryptography import x509
 
 
 class X448PublicKey(metaclass=abc.ABCMeta):
@@ -34,6 +38,12 @@ class X448PublicKey(metaclass=abc.ABCMeta):
         The serialized bytes of the public key.
         """
 
+    @abc.abstractmethod
+    def jwk_public_bytes(self, kid: str | None = None) -> bytes:
+        """
+        The public key as a JSON Web Key.
+        """
+
     @abc.abstractmethod
     def public_bytes_raw(self) -> bytes:
         """
@@ -41,6 +51,20 @@ class X448PublicKey(metaclass=abc.ABCMeta):
         Equivalent to public_bytes(Raw, Raw).
         """
 
+    @abc.abstractmethod
+    def fingerprint(self, algorithm: hashes.HashAlgorithm) -> bytes:
+        """
+        The hash of the DER encoded SubjectPublicKeyInfo of the key.
+        """
+
+    @abc.abstractmethod
+    def jwk_thumbprint(
+        self, algorithm: hashes.HashAlgorithm | None = None
+    ) -> bytes:
+        """
+        The RFC 7638 thumbprint of the key's JSON Web Key.
+        """
+
     @abc.abstractmethod
     def __eq__(self, other: object) -> bool:
         """
@@ -77,12 +101,41 @@ class X448PrivateKey(metaclass=abc.ABCMeta):
 
         return rust_openssl.x448.from_private_bytes(data)
 
+    @classmethod
+    def from_scalar(
+        cls, scalar: bytes, *, clamp: bool = True
+    ) -> X448PrivateKey:
+        from cryptography.hazmat.backends.openssl.backend import backend
+
+        if not backend.x448_supported():
+            raise UnsupportedAlgorithm(
+                "X448 is not supported by this version of OpenSSL.",
+                _Reasons.UNSUPPORTED_EXCHANGE_ALGORITHM,
+            )
+
+        return rust_openssl.x448.from_scalar(scalar, clamp=clamp)
+
     @abc.abstractmethod
     def public_key(self) -> X448PublicKey:
         """
         Returns the public key associated with this private key
         """
 
+    @abc.abstractmethod
+    def public_key_matches(
+        self, public_key: x509.Certificate | X448PublicKey
+    ) -> bool:
+        """
+        Checks whether the public key, or the certificate's public key, is
+        the public key of this private key.
+        """
+
+    @abc.abstractmethod
+    def __eq__(self, other: object) -> bool:
+        """
+        Checks equality.
+        """
+
     @abc.abstractmethod
     def private_bytes(
         self,
@@ -94,6 +147,12 @@ class X448PrivateKey(metaclass=abc.ABCMeta):
         The serialized bytes of the private key.
         """
 
+    @abc.abstractmethod
+    def jwk_private_bytes(self, kid: str | None = None) -> bytes:
+        """
+        The private key as a JSON Web Key.
+        """
+
     @abc.abstractmethod
     def private_bytes_raw(self) -> bytes:
         """
@@ -101,6 +160,12 @@ class X448PrivateKey(metaclass=abc.ABCMeta):
         Equivalent to private_bytes(Raw, Raw, NoEncryption()).
         """
 
+    @abc.abstractmethod
+    def clamped_scalar(self) -> bytes:
+        """
+        The RFC 7748 clamped scalar that the key exchange uses.
+        """
+
     @abc.abstractmethod
     def exchange(self, peer_public_key: X448PublicKey) -> bytes:
         """
@@ -0,0 +1,90 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from __future__ import annotations
+
+import abc
+
+from cryptography.exceptions import UnsupportedAlgorithm, _Reasons
+from cryptography.hazmat.bindings._rust import openssl as rust_openssl
+
+
+def _check_supported() -> None:
+    from cryptography.hazmat.backends.openssl.backend import backend
+
+    if not backend.xwing_supported():
+        raise UnsupportedAlgorithm(
+            "X-Wing is not supported by this version of OpenSSL.",
+            _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
+        )
+
+
+class XWingPublicKey(metaclass=abc.ABCMeta):
+    @classmethod
+    def from_public_bytes(cls, data: bytes) -> XWingPublicKey:
+        _check_supported()
+        return rust_openssl.xwing.from_public_bytes(data)
+
+    @abc.abstractmethod
+    def encapsulate(self) -> tuple[bytes, bytes]:
+        """
+        Generates a shared secret and the ciphertext that encapsulates it,
+        returned as (shared_secret, ciphertext).
+        """
+
+    @abc.abstractmethod
+    def public_bytes_raw(self) -> bytes:
+        """
+        The raw bytes of the public key.
+        """
+
+    @abc.abstractmethod
+    def __eq__(self, other: object) -> bool:
+        """
+        Checks equality.
+        """
+
+
+if hasattr(rust_openssl, "xwing"):
+    XWingPublicKey.register(rust_openssl.xwing.XWingPublicKey)
+
+
+class XWingPrivateKey(metaclass=abc.ABCMeta):
+    @classmethod
+    def generate(cls) -> XWingPrivateKey:
+        _check_supported()
+        return rust_openssl.xwing.generate_key()
+
+    @classmethod
+    def from_private_bytes(cls, data: bytes) -> XWingPrivateKey:
+        _check_supported()
+        return rust_openssl.xwing.from_private_bytes(data)
+
+    @abc.abstractmethod
+    def public_key(self) -> XWingPublicKey:
+        """
+        The XWingPublicKey derived from the private key.
+        """
+
+    @abc.abstractmethod
+    def decapsulate(self, ciphertext: bytes) -> bytes:
+        """
+        Recovers the shared secret from a ciphertext.
+        """
+
+    @abc.abstractmethod
+    def private_bytes_raw(self) -> bytes:
+        """
+        The 32 byte seed the private key is derived from.
+        """
+
+    @abc.abstractmethod
+    def __eq__(self, other: object) -> bool:
+        """
+        Checks equality.
+        """
+
+
+if hasattr(rust_openssl, "xwing"):
+    XWingPrivateKey.register(rust_openssl.xwing.XWingPrivateKey)
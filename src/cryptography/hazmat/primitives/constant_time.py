@@ -6,9 +6,38 @@ from __future__ import annotations
 
 import hmac
 
+from cryptography.exceptions import UnsupportedAlgorithm, _Reasons
+from cryptography.hazmat.bindings._rust import openssl as rust_openssl
+
 
 def bytes_eq(a: bytes, b: bytes) -> bool:
     if not isinstance(a, bytes) or not isinstance(b, bytes):
         raise TypeError("a and b must be bytes.")
 
     return hmac.compare_digest(a, b)
+
+
+def _check_bignum_supported() -> None:
+    from cryptography.hazmat.backends.openssl.backend import backend
+
+    if not backend.constant_time_bignum_supported():
+        raise UnsupportedAlgorithm(
+            "Constant time big integer arithmetic is not supported by this "
+            "version of OpenSSL.",
+            _Reasons.BACKEND_MISSING_INTERFACE,
+        )
+
+
+def mod_exp(base: int, exponent: int, modulus: int) -> int:
+    _check_bignum_supported()
+    return rust_openssl.bignum.mod_exp(base, exponent, modulus)
+
+
+def mod_inverse(value: int, modulus: int) -> int:
+    _check_bignum_supported()
+    return rust_openssl.bignum.mod_inverse(value, modulus)
+
+
+def random_in_range(low: int, high: int) -> int:
+    _check_bignum_supported()
+    return rust_openssl.bignum.random_in_range(low, high)
@@ -0,0 +1,81 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from __future__ import annotations
+
+import abc
+
+from cryptography.exceptions import UnsupportedAlgorithm, _Reasons
+from cryptography.hazmat.bindings._rust import openssl as rust_openssl
+
+
+def _check_supported() -> None:
+    from cryptography.hazmat.backends.openssl.backend import backend
+
+    if not backend.locked_memory_supported():
+        raise UnsupportedAlgorithm(
+            "Locked memory is not supported by this version of OpenSSL.",
+            _Reasons.BACKEND_MISSING_INTERFACE,
+        )
+
+
+def initialize(size: int, min_size: int = 16) -> None:
+    _check_supported()
+    rust_openssl.locked_memory.initialize(size, min_size)
+
+
+def is_initialized() -> bool:
+    from cryptography.hazmat.backends.openssl.backend import backend
+
+    if not backend.locked_memory_supported():
+        return False
+
+    return rust_openssl.locked_memory.is_initialized()
+
+
+def bytes_used() -> int:
+    _check_supported()
+    return rust_openssl.locked_memory.used()
+
+
+class LockedBuffer(metaclass=abc.ABCMeta):
+    @classmethod
+    def allocate(cls, size: int) -> LockedBuffer:
+        _check_supported()
+        return rust_openssl.locked_memory.LockedBuffer(size)
+
+    @abc.abstractmethod
+    def __len__(self) -> int:
+        """
+        The size of the buffer in bytes.
+        """
+
+    @abc.abstractmethod
+    def write(self, data: bytes, offset: int = 0) -> None:
+        """
+        Copies data into the buffer, starting at offset.
+        """
+
+    @abc.abstractmethod
+    def randomize(self) -> None:
+        """
+        Fills the buffer with random bytes.
+        """
+
+    @abc.abstractmethod
+    def clear(self) -> None:
+        """
+        Sets every byte of the buffer to zero.
+        """
+
+    @abc.abstractmethod
+    def to_bytes(self) -> bytes:
+        """
+        Copies the contents of the buffer into a bytes object, which is not
+        in locked memory.
+        """
+
+
+if hasattr(rust_openssl, "locked_memory"):
+    LockedBuffer.register(rust_openssl.locked_memory.LockedBuffer)
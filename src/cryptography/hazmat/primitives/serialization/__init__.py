@@ -11,45 +11,92 @@ from cryptography.hazmat.primitives._serialization import (
     NoEncryption,
     ParameterFormat,
     PrivateFormat,
+    PrivateKeyCipher,
     PublicFormat,
     _KeySerializationEncryption,
 )
 from cryptography.hazmat.primitives.serialization.base import (
+    KeyInfo,
+    identify_key,
+    jwk_thumbprint,
     load_der_parameters,
     load_der_private_key,
     load_der_public_key,
+    load_jwk_key,
+    load_pem_key_and_certificates,
     load_pem_parameters,
     load_pem_private_key,
+    load_pem_private_key_from_file,
+    load_pem_private_keys,
     load_pem_public_key,
+    load_private_key,
+    load_private_key_from_uri,
+    reencrypt_private_key,
+)
+from cryptography.hazmat.primitives.serialization.ppk import (
+    load_ppk_private_key,
 )
 from cryptography.hazmat.primitives.serialization.ssh import (
+    SSHAllowedSigner,
+    SSHAuthorizedKey,
     SSHCertificate,
     SSHCertificateBuilder,
     SSHCertificateType,
     SSHCertPrivateKeyTypes,
     SSHCertPublicKeyTypes,
+    SSHKnownHost,
     SSHPrivateKeyTypes,
     SSHPublicKeyTypes,
+    SSHSecurityKeyFlags,
+    SSHSecurityKeyPrivateKey,
+    SSHSecurityKeyPublicKey,
+    SSHSignature,
+    create_ssh_signature,
+    load_ssh_allowed_signers,
+    load_ssh_authorized_keys,
+    load_ssh_known_hosts,
     load_ssh_private_key,
     load_ssh_public_identity,
     load_ssh_public_key,
+    load_ssh_signature,
+    serialize_ssh_authorized_key,
+    serialize_ssh_known_host,
 )
 
 __all__ = [
+    "create_ssh_signature",
+    "identify_key",
+    "jwk_thumbprint",
     "load_der_parameters",
     "load_der_private_key",
     "load_der_public_key",
+    "load_jwk_key",
+    "load_pem_key_and_certificates",
     "load_pem_parameters",
     "load_pem_private_key",
+    "load_pem_private_key_from_file",
+    "load_pem_private_keys",
     "load_pem_public_key",
+    "load_ppk_private_key",
+    "load_private_key",
+    "load_private_key_from_uri",
+    "load_ssh_allowed_signers",
+    "load_ssh_authorized_keys",
+    "load_ssh_known_hosts",
     "load_ssh_private_key",
     "load_ssh_public_identity",
     "load_ssh_public_key",
+    "load_ssh_signature",
+    "reencrypt_private_key",
+    "serialize_ssh_authorized_key",
+    "serialize_ssh_known_host",
     "Encoding",
     "PrivateFormat",
+    "PrivateKeyCipher",
     "PublicFormat",
     "ParameterFormat",
     "KeySerializationEncryption",
+    "KeyInfo",
     "BestAvailableEncryption",
     "NoEncryption",
     "_KeySerializationEncryption",
@@ -60,4 +107,11 @@ __all__ = [
     "SSHCertPrivateKeyTypes",
     "SSHPrivateKeyTypes",
     "SSHPublicKeyTypes",
+    "SSHSecurityKeyFlags",
+    "SSHSecurityKeyPrivateKey",
+    "SSHSecurityKeyPublicKey",
+    "SSHSignature",
+    "SSHAllowedSigner",
+    "SSHAuthorizedKey",
+    "SSHKnownHost",
 ]
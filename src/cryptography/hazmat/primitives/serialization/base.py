@@ -5,10 +5,26 @@
 from cryptography.hazmat.bindings._rust import openssl as rust_openssl
 
 load_pem_private_key = rust_openssl.keys.load_pem_private_key
+load_pem_private_keys = rust_openssl.keys.load_pem_private_keys
+load_pem_private_key_from_file = (
+    rust_openssl.keys.load_pem_private_key_from_file
+)
+load_pem_key_and_certificates = rust_openssl.keys.load_pem_key_and_certificates
 load_der_private_key = rust_openssl.keys.load_der_private_key
+load_private_key = rust_openssl.keys.load_private_key
 
 load_pem_public_key = rust_openssl.keys.load_pem_public_key
 load_der_public_key = rust_openssl.keys.load_der_public_key
 
-load_pem_parameters = rust_openssl.dh.from_pem_parameters
-load_der_parameters = rust_openssl.dh.from_der_parameters
+reencrypt_private_key = rust_openssl.keys.reencrypt_private_key
+
+identify_key = rust_openssl.keys.identify_key
+KeyInfo = rust_openssl.keys.KeyInfo
+
+load_jwk_key = rust_openssl.jwk.load_jwk_key
+jwk_thumbprint = rust_openssl.jwk.jwk_thumbprint
+
+load_private_key_from_uri = rust_openssl.store.load_private_key_from_uri
+
+load_pem_parameters = rust_openssl.keys.load_pem_parameters
+load_der_parameters = rust_openssl.keys.load_der_parameters
@@ -0,0 +1,45 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from __future__ import annotations
+
+import enum
+
+from cryptography.hazmat.bindings._rust import cose as rust_cose
+
+
+class COSEAlgorithm(enum.IntEnum):
+    ES256 = -7
+    ES384 = -35
+    ES512 = -36
+    EdDSA = -8
+    PS256 = -37
+    PS384 = -38
+    PS512 = -39
+    RS256 = -257
+    RS384 = -258
+    RS512 = -259
+
+
+class COSEHeader(enum.IntEnum):
+    ALG = 1
+    CRIT = 2
+    CONTENT_TYPE = 3
+    KID = 4
+    IV = 5
+    PARTIAL_IV = 6
+    X5CHAIN = 33
+
+
+COSESign1 = rust_cose.COSESign1
+sign1 = rust_cose.sign1
+load_cose_sign1 = rust_cose.load_cose_sign1
+
+__all__ = [
+    "COSEAlgorithm",
+    "COSEHeader",
+    "COSESign1",
+    "load_cose_sign1",
+    "sign1",
+]
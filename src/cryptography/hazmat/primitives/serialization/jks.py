@@ -0,0 +1,165 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from __future__ import annotations
+
+from cryptography import x509
+from cryptography.hazmat.bindings._rust import jks as rust_jks
+from cryptography.hazmat.primitives.asymmetric.types import PrivateKeyTypes
+
+__all__ = [
+    "JKSKeyStore",
+    "JKSPrivateKeyEntry",
+    "JKSTrustedCertificateEntry",
+    "load_jks",
+]
+
+
+class JKSPrivateKeyEntry:
+    def __init__(
+        self,
+        alias: str,
+        key: PrivateKeyTypes,
+        certificates: list[x509.Certificate],
+    ):
+        if not isinstance(alias, str):
+            raise TypeError("alias must be a str")
+        if not all(isinstance(c, x509.Certificate) for c in certificates):
+            raise TypeError(
+                "all values in certificates must be x509.Certificate objects"
+            )
+        self._alias = alias
+        self._key = key
+        self._certificates = certificates
+
+    @property
+    def alias(self) -> str:
+        return self._alias
+
+    @property
+    def key(self) -> PrivateKeyTypes:
+        return self._key
+
+    @property
+    def certificates(self) -> list[x509.Certificate]:
+        return self._certificates
+
+    def __eq__(self, other: object) -> bool:
+        if not isinstance(other, JKSPrivateKeyEntry):
+            return NotImplemented
+
+        return (
+            self.alias == other.alias
+            and self.key == other.key
+            and self.certificates == other.certificates
+        )
+
+    def __hash__(self) -> int:
+        return hash((self.alias, self.key, tuple(self.certificates)))
+
+    def __repr__(self) -> str:
+        fmt = "<JKSPrivateKeyEntry(alias={!r}, key={}, certificates={})>"
+        return fmt.format(self.alias, self.key, self.certificates)
+
+
+class JKSTrustedCertificateEntry:
+    def __init__(self, alias: str, certificate: x509.Certificate):
+        if not isinstance(alias, str):
+            raise TypeError("alias must be a str")
+        if not isinstance(certificate, x509.Certificate):
+            raise TypeError("Expecting x509.Certificate object")
+        self._alias = alias
+        self._certificate = certificate
+
+    @property
+    def alias(self) -> str:
+        return self._alias
+
+    @property
+    def certificate(self) -> x509.Certificate:
+        return self._certificate
+
+    def __eq__(self, other: object) -> bool:
+        if not isinstance(other, JKSTrustedCertificateEntry):
+            return NotImplemented
+
+        return (
+            self.alias == other.alias and self.certificate == other.certificate
+        )
+
+    def __hash__(self) -> int:
+        return hash((self.alias, self.certificate))
+
+    def __repr__(self) -> str:
+        fmt = "<JKSTrustedCertificateEntry(alias={!r}, certificate={})>"
+        return fmt.format(self.alias, self.certificate)
+
+
+class JKSKeyStore:
+    def __init__(
+        self,
+        private_keys: list[JKSPrivateKeyEntry],
+        trusted_certificates: list[JKSTrustedCertificateEntry],
+    ):
+        if not all(isinstance(e, JKSPrivateKeyEntry) for e in private_keys):
+            raise TypeError(
+                "all values in private_keys must be JKSPrivateKeyEntry"
+                " objects"
+            )
+        if not all(
+            isinstance(e, JKSTrustedCertificateEntry)
+            for e in trusted_certificates
+        ):
+            raise TypeError(
+                "all values in trusted_certificates must be"
+                " JKSTrustedCertificateEntry objects"
+            )
+        self._private_keys = private_keys
+        self._trusted_certificates = trusted_certificates
+
+    @property
+    def private_keys(self) -> list[JKSPrivateKeyEntry]:
+        return self._private_keys
+
+    @property
+    def trusted_certificates(self) -> list[JKSTrustedCertificateEntry]:
+        return self._trusted_certificates
+
+    def __eq__(self, other: object) -> bool:
+        if not isinstance(other, JKSKeyStore):
+            return NotImplemented
+
+        return (
+            self.private_keys == other.private_keys
+            and self.trusted_certificates == other.trusted_certificates
+        )
+
+    def __hash__(self) -> int:
+        return hash(
+            (tuple(self.private_keys), tuple(self.trusted_certificates))
+        )
+
+    def __repr__(self) -> str:
+        fmt = "<JKSKeyStore(private_keys={}, trusted_certificates={})>"
+        return fmt.format(self.private_keys, self.trusted_certificates)
+
+
+def load_jks(
+    data: bytes,
+    password: bytes,
+    key_password: bytes | None = None,
+) -> JKSKeyStore:
+    private_keys, trusted_certificates = rust_jks.load_jks(
+        data, password, key_password
+    )
+    return JKSKeyStore(
+        [
+            JKSPrivateKeyEntry(alias, key, certificates)
+            for alias, key, certificates in private_keys
+        ],
+        [
+            JKSTrustedCertificateEntry(alias, certificate)
+            for alias, certificate in trusted_certificates
+        ],
+    )
@@ -0,0 +1,172 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from __future__ import annotations
+
+import datetime
+import typing
+
+from cryptography.hazmat.bindings._rust import openssl as rust_openssl
+from cryptography.hazmat.primitives.asymmetric.types import (
+    PrivateKeyTypes,
+    PublicKeyTypes,
+)
+
+__all__ = [
+    "OpenPGPKey",
+    "OpenPGPTransferableKey",
+    "load_openpgp_private_key",
+    "load_openpgp_public_key",
+]
+
+_OpenPGPKeyTypes = typing.Union[PrivateKeyTypes, PublicKeyTypes]
+
+
+class OpenPGPKey:
+    def __init__(
+        self,
+        key: _OpenPGPKeyTypes,
+        fingerprint: bytes,
+        creation_time: datetime.datetime,
+        version: int,
+    ):
+        if not isinstance(fingerprint, bytes):
+            raise TypeError("fingerprint must be bytes")
+        if not isinstance(creation_time, datetime.datetime):
+            raise TypeError("creation_time must be a datetime")
+        if not isinstance(version, int):
+            raise TypeError("version must be an int")
+        self._key = key
+        self._fingerprint = fingerprint
+        self._creation_time = creation_time
+        self._version = version
+
+    @property
+    def key(self) -> _OpenPGPKeyTypes:
+        return self._key
+
+    @property
+    def fingerprint(self) -> bytes:
+        return self._fingerprint
+
+    @property
+    def key_id(self) -> bytes:
+        # Version 4 key IDs are the end of the fingerprint, while later
+        # versions use its start.
+        if self.version == 4:
+            return self.fingerprint[-8:]
+        return self.fingerprint[:8]
+
+    @property
+    def creation_time(self) -> datetime.datetime:
+        return self._creation_time
+
+    @property
+    def version(self) -> int:
+        return self._version
+
+    def __eq__(self, other: object) -> bool:
+        if not isinstance(other, OpenPGPKey):
+            return NotImplemented
+
+        return (
+            self.key == other.key
+            and self.fingerprint == other.fingerprint
+            and self.creation_time == other.creation_time
+            and self.version == other.version
+        )
+
+    def __hash__(self) -> int:
+        # Not every key type is hashable, but the fingerprint covers the
+        # public key.
+        return hash((self.fingerprint, self.creation_time, self.version))
+
+    def __repr__(self) -> str:
+        fmt = "<OpenPGPKey(fingerprint={}, version={}, key={})>"
+        return fmt.format(self.fingerprint.hex(), self.version, self.key)
+
+
+class OpenPGPTransferableKey:
+    def __init__(
+        self,
+        primary_key: OpenPGPKey,
+        user_ids: list[str],
+        subkeys: list[OpenPGPKey],
+    ):
+        if not isinstance(primary_key, OpenPGPKey):
+            raise TypeError("primary_key must be an OpenPGPKey")
+        if not all(isinstance(u, str) for u in user_ids):
+            raise TypeError("all values in user_ids must be str")
+        if not all(isinstance(k, OpenPGPKey) for k in subkeys):
+            raise TypeError("all values in subkeys must be OpenPGPKey objects")
+        self._primary_key = primary_key
+        self._user_ids = user_ids
+        self._subkeys = subkeys
+
+    @property
+    def primary_key(self) -> OpenPGPKey:
+        return self._primary_key
+
+    @property
+    def user_ids(self) -> list[str]:
+        return self._user_ids
+
+    @property
+    def subkeys(self) -> list[OpenPGPKey]:
+        return self._subkeys
+
+    def __eq__(self, other: object) -> bool:
+        if not isinstance(other, OpenPGPTransferableKey):
+            return NotImplemented
+
+        return (
+            self.primary_key == other.primary_key
+            and self.user_ids == other.user_ids
+            and self.subkeys == other.subkeys
+        )
+
+    def __hash__(self) -> int:
+        return hash(
+            (self.primary_key, tuple(self.user_ids), tuple(self.subkeys))
+        )
+
+    def __repr__(self) -> str:
+        fmt = "<OpenPGPTransferableKey(primary_key={}, user_ids={})>"
+        return fmt.format(self.primary_key, self.user_ids)
+
+
+def _openpgp_key(
+    key: tuple[int, int, bytes, _OpenPGPKeyTypes],
+) -> OpenPGPKey:
+    version, creation_time, fingerprint, k = key
+    return OpenPGPKey(
+        k,
+        fingerprint,
+        datetime.datetime.fromtimestamp(creation_time, datetime.timezone.utc),
+        version,
+    )
+
+
+def load_openpgp_public_key(data: bytes) -> OpenPGPTransferableKey:
+    primary_key, user_ids, subkeys = (
+        rust_openssl.openpgp.load_openpgp_public_key(data)
+    )
+    return OpenPGPTransferableKey(
+        _openpgp_key(primary_key),
+        user_ids,
+        [_openpgp_key(k) for k in subkeys],
+    )
+
+
+def load_openpgp_private_key(
+    data: bytes, password: bytes | None
+) -> OpenPGPTransferableKey:
+    primary_key, user_ids, subkeys = (
+        rust_openssl.openpgp.load_openpgp_private_key(data, password)
+    )
+    return OpenPGPTransferableKey(
+        _openpgp_key(primary_key),
+        user_ids,
+        [_openpgp_key(k) for k in subkeys],
+    )
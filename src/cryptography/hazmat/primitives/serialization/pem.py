@@ -0,0 +1,15 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from __future__ import annotations
+
+from cryptography.hazmat.bindings._rust import pem as rust_pem
+
+PEMObject = rust_pem.PEMObject
+parse_all = rust_pem.parse_all
+
+__all__ = [
+    "PEMObject",
+    "parse_all",
+]
@@ -147,6 +147,8 @@ def load_key_and_certificates(
     data: bytes,
     password: bytes | None,
     backend: typing.Any = None,
+    *,
+    allow_legacy: bool = False,
 ) -> tuple[
     PrivateKeyTypes | None,
     x509.Certificate | None,
@@ -154,17 +156,21 @@ def load_key_and_certificates(
 ]:
     from cryptography.hazmat.backends.openssl.backend import backend as ossl
 
-    return ossl.load_key_and_certificates_from_pkcs12(data, password)
+    return ossl.load_key_and_certificates_from_pkcs12(
+        data, password, allow_legacy
+    )
 
 
 def load_pkcs12(
     data: bytes,
     password: bytes | None,
     backend: typing.Any = None,
+    *,
+    allow_legacy: bool = False,
 ) -> PKCS12KeyAndCertificates:
     from cryptography.hazmat.backends.openssl.backend import backend as ossl
 
-    return ossl.load_pkcs12(data, password)
+    return ossl.load_pkcs12(data, password, allow_legacy)
 
 
 _PKCS12CATypes = typing.Union[
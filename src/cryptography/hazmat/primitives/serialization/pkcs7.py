@@ -15,6 +15,7 @@ from cryptography import utils, x509
 from cryptography.hazmat.bindings._rust import pkcs7 as rust_pkcs7
 from cryptography.hazmat.primitives import hashes, serialization
 from cryptography.hazmat.primitives.asymmetric import ec, padding, rsa
+from cryptography.hazmat.primitives.asymmetric.signer import ExternalSigner
 from cryptography.utils import _check_byteslike
 
 
@@ -61,7 +62,7 @@ class PKCS7SignatureBuilder:
         signers: list[
             tuple[
                 x509.Certificate,
-                PKCS7PrivateKeyTypes,
+                PKCS7PrivateKeyTypes | ExternalSigner,
                 PKCS7HashTypes,
                 padding.PSS | padding.PKCS1v15 | None,
             ]
@@ -82,7 +83,7 @@ class PKCS7SignatureBuilder:
     def add_signer(
         self,
         certificate: x509.Certificate,
-        private_key: PKCS7PrivateKeyTypes,
+        private_key: PKCS7PrivateKeyTypes | ExternalSigner,
         hash_algorithm: PKCS7HashTypes,
         *,
         rsa_padding: padding.PSS | padding.PKCS1v15 | None = None,
@@ -103,9 +104,16 @@ class PKCS7SignatureBuilder:
         if not isinstance(certificate, x509.Certificate):
             raise TypeError("certificate must be a x509.Certificate")
 
-        if not isinstance(
-            private_key, (rsa.RSAPrivateKey, ec.EllipticCurvePrivateKey)
-        ):
+        if isinstance(private_key, ExternalSigner):
+            supported = isinstance(
+                private_key.public_key(),
+                (rsa.RSAPublicKey, ec.EllipticCurvePublicKey),
+            )
+        else:
+            supported = isinstance(
+                private_key, (rsa.RSAPrivateKey, ec.EllipticCurvePrivateKey)
+            )
+        if not supported:
             raise TypeError("Only RSA & EC keys are supported at this time.")
 
         if rsa_padding is not None:
@@ -0,0 +1,44 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from __future__ import annotations
+
+from cryptography.exceptions import UnsupportedAlgorithm
+from cryptography.hazmat.bindings._rust import openssl as rust_openssl
+
+try:
+    from argon2.low_level import Type as _Argon2Type
+    from argon2.low_level import hash_secret_raw as _argon2_hash_secret_raw
+
+    _argon2_supported = True
+except ImportError:
+    _argon2_supported = False
+
+
+def _argon2_kdf(
+    flavor: str,
+    password: bytes,
+    salt: bytes,
+    passes: int,
+    memory: int,
+    parallelism: int,
+    length: int,
+) -> bytes:
+    if not _argon2_supported:
+        raise UnsupportedAlgorithm("Need argon2-cffi module")
+
+    types = {
+        "Argon2id": _Argon2Type.ID,
+        "Argon2i": _Argon2Type.I,
+        "Argon2d": _Argon2Type.D,
+    }
+    if flavor not in types:
+        raise UnsupportedAlgorithm(f"Unsupported key derivation: {flavor}")
+
+    return _argon2_hash_secret_raw(
+        password, salt, passes, memory, parallelism, length, types[flavor]
+    )
+
+
+load_ppk_private_key = rust_openssl.ppk.load_ppk_private_key
@@ -5,16 +5,19 @@
 from __future__ import annotations
 
 import binascii
+import datetime
 import enum
 import os
 import re
+import time
 import typing
 import warnings
 from base64 import encodebytes as _base64_encode
 from dataclasses import dataclass
 
 from cryptography import utils
-from cryptography.exceptions import UnsupportedAlgorithm
+from cryptography.exceptions import InvalidSignature, UnsupportedAlgorithm
+from cryptography.hazmat.bindings._rust import openssl as rust_openssl
 from cryptography.hazmat.primitives import hashes
 from cryptography.hazmat.primitives.asymmetric import (
     dsa,
@@ -25,12 +28,12 @@ from cryptography.hazmat.primitives.asymmetric import (
 )
 from cryptography.hazmat.primitives.asymmetric import utils as asym_utils
 from cryptography.hazmat.primitives.ciphers import (
-    AEADDecryptionContext,
     Cipher,
     algorithms,
     modes,
 )
 from cryptography.hazmat.primitives.serialization import (
+    BestAvailableEncryption,
     Encoding,
     KeySerializationEncryption,
     NoEncryption,
@@ -39,6 +42,11 @@ from cryptography.hazmat.primitives.serialization import (
     _KeySerializationEncryption,
 )
 
+if typing.TYPE_CHECKING:
+    from cryptography.hazmat.primitives.serialization.ssh_agent import (
+        SSHAgentKey,
+    )
+
 try:
     from bcrypt import kdf as _bcrypt_kdf
 
@@ -62,7 +70,10 @@ _SSH_DSA = b"ssh-dss"
 _ECDSA_NISTP256 = b"ecdsa-sha2-nistp256"
 _ECDSA_NISTP384 = b"ecdsa-sha2-nistp384"
 _ECDSA_NISTP521 = b"ecdsa-sha2-nistp521"
+_SK_SSH_ED25519 = b"sk-ssh-ed25519@openssh.com"
+_SK_ECDSA_NISTP256 = b"sk-ecdsa-sha2-nistp256@openssh.com"
 _CERT_SUFFIX = b"-cert-v01@openssh.com"
+_OPENSSH_SUFFIX = b"@openssh.com"
 
 # These are not key types, only algorithms, so they cannot appear
 # as a public key type
@@ -78,9 +89,6 @@ _NONE = b"none"
 _DEFAULT_CIPHER = b"aes256-ctr"
 _DEFAULT_ROUNDS = 16
 
-# re is only way to work on bytes-like data
-_PEM_RC = re.compile(_SK_START + b"(.*?)" + _SK_END, re.DOTALL)
-
 # padding for max blocksize
 _PADDING = memoryview(bytearray(range(1, 1 + 16)))
 
@@ -135,8 +143,17 @@ _ECDSA_KEY_TYPE = {
 }
 
 
-def _get_ssh_key_type(key: SSHPrivateKeyTypes | SSHPublicKeyTypes) -> bytes:
-    if isinstance(key, ec.EllipticCurvePrivateKey):
+def _get_ssh_key_type(
+    key: SSHPrivateKeyTypes | SSHPublicKeyTypes | _SSHSecurityKeyTypes,
+) -> bytes:
+    if isinstance(key, SSHSecurityKeyPrivateKey):
+        key = key.public_key()
+    if isinstance(key, SSHSecurityKeyPublicKey):
+        if isinstance(key.public_key(), ed25519.Ed25519PublicKey):
+            key_type = _SK_SSH_ED25519
+        else:
+            key_type = _SK_ECDSA_NISTP256
+    elif isinstance(key, ec.EllipticCurvePrivateKey):
         key_type = _ecdsa_key_type(key.public_key())
     elif isinstance(key, ec.EllipticCurvePublicKey):
         key_type = _ecdsa_key_type(key)
@@ -154,6 +171,17 @@ def _get_ssh_key_type(key: SSHPrivateKeyTypes | SSHPublicKeyTypes) -> bytes:
     return key_type
 
 
+def _cert_key_type(key_type: bytes) -> bytes:
+    """Return the certificate type for a key type.
+
+    Key types with an @openssh.com suffix have it after the -cert-v01 part,
+    e.g. sk-ssh-ed25519@openssh.com and sk-ssh-ed25519-cert-v01@openssh.com.
+    """
+    if key_type.endswith(_OPENSSH_SUFFIX):
+        key_type = key_type[: -len(_OPENSSH_SUFFIX)]
+    return key_type + _CERT_SUFFIX
+
+
 def _ecdsa_key_type(public_key: ec.EllipticCurvePublicKey) -> bytes:
     """Return SSH key_type and curve_name for private key."""
     curve = public_key.curve
@@ -172,12 +200,6 @@ def _ssh_pem_encode(
     return b"".join([prefix, _base64_encode(data), suffix])
 
 
-def _check_block_size(data: bytes, block_len: int) -> None:
-    """Require data to be full blocks"""
-    if not data or len(data) % block_len != 0:
-        raise ValueError("Corrupt data: missing padding")
-
-
 def _check_empty(data: bytes) -> None:
     """All data should have been parsed."""
     if data:
@@ -322,28 +344,6 @@ class _SSHFormatRSA:
         public_key = public_numbers.public_key()
         return public_key, data
 
-    def load_private(
-        self, data: memoryview, pubfields
-    ) -> tuple[rsa.RSAPrivateKey, memoryview]:
-        """Make RSA private key from data."""
-        n, data = _get_mpint(data)
-        e, data = _get_mpint(data)
-        d, data = _get_mpint(data)
-        iqmp, data = _get_mpint(data)
-        p, data = _get_mpint(data)
-        q, data = _get_mpint(data)
-
-        if (e, n) != pubfields:
-            raise ValueError("Corrupt data: rsa field mismatch")
-        dmp1 = rsa.rsa_crt_dmp1(d, p)
-        dmq1 = rsa.rsa_crt_dmq1(d, q)
-        public_numbers = rsa.RSAPublicNumbers(e, n)
-        private_numbers = rsa.RSAPrivateNumbers(
-            p, q, d, dmp1, dmq1, iqmp, public_numbers
-        )
-        private_key = private_numbers.private_key()
-        return private_key, data
-
     def encode_public(
         self, public_key: rsa.RSAPublicKey, f_pub: _FragList
     ) -> None:
@@ -396,22 +396,6 @@ class _SSHFormatDSA:
         public_key = public_numbers.public_key()
         return public_key, data
 
-    def load_private(
-        self, data: memoryview, pubfields
-    ) -> tuple[dsa.DSAPrivateKey, memoryview]:
-        """Make DSA private key from data."""
-        (p, q, g, y), data = self.get_public(data)
-        x, data = _get_mpint(data)
-
-        if (p, q, g, y) != pubfields:
-            raise ValueError("Corrupt data: dsa field mismatch")
-        parameter_numbers = dsa.DSAParameterNumbers(p, q, g)
-        public_numbers = dsa.DSAPublicNumbers(y, parameter_numbers)
-        self._validate(public_numbers)
-        private_numbers = dsa.DSAPrivateNumbers(x, public_numbers)
-        private_key = private_numbers.private_key()
-        return private_key, data
-
     def encode_public(
         self, public_key: dsa.DSAPublicKey, f_pub: _FragList
     ) -> None:
@@ -474,18 +458,6 @@ class _SSHFormatECDSA:
         )
         return public_key, data
 
-    def load_private(
-        self, data: memoryview, pubfields
-    ) -> tuple[ec.EllipticCurvePrivateKey, memoryview]:
-        """Make ECDSA private key from data."""
-        (curve_name, point), data = self.get_public(data)
-        secret, data = _get_mpint(data)
-
-        if (curve_name, point) != pubfields:
-            raise ValueError("Corrupt data: ecdsa field mismatch")
-        private_key = ec.derive_private_key(secret, self.curve)
-        return private_key, data
-
     def encode_public(
         self, public_key: ec.EllipticCurvePublicKey, f_pub: _FragList
     ) -> None:
@@ -532,20 +504,6 @@ class _SSHFormatEd25519:
         )
         return public_key, data
 
-    def load_private(
-        self, data: memoryview, pubfields
-    ) -> tuple[ed25519.Ed25519PrivateKey, memoryview]:
-        """Make Ed25519 private key from data."""
-        (point,), data = self.get_public(data)
-        keypair, data = _get_sshstr(data)
-
-        secret = keypair[:32]
-        point2 = keypair[32:]
-        if point != point2 or (point,) != pubfields:
-            raise ValueError("Corrupt data: ed25519 field mismatch")
-        private_key = ed25519.Ed25519PrivateKey.from_private_bytes(secret)
-        return private_key, data
-
     def encode_public(
         self, public_key: ed25519.Ed25519PublicKey, f_pub: _FragList
     ) -> None:
@@ -572,6 +530,53 @@ class _SSHFormatEd25519:
         f_priv.put_sshstr(f_keypair)
 
 
+class _SSHFormatSecurityKey:
+    """Format for FIDO security keys, as described in PROTOCOL.u2f.
+
+    Public:
+        public fields of the underlying Ed25519 or ECDSA key
+        str application
+    Private:
+        public fields of the underlying Ed25519 or ECDSA key
+        str application
+        uint8 flags
+        bytes key_handle
+        bytes reserved
+    """
+
+    def __init__(
+        self, key_format: _SSHFormatEd25519 | _SSHFormatECDSA
+    ) -> None:
+        self.key_format = key_format
+
+    def load_public(
+        self, data: memoryview
+    ) -> tuple[SSHSecurityKeyPublicKey, memoryview]:
+        """Make security key public key from data."""
+        public_key, data = self.key_format.load_public(data)
+        application, data = _get_sshstr(data)
+        return SSHSecurityKeyPublicKey(public_key, bytes(application)), data
+
+    def encode_public(
+        self, public_key: SSHSecurityKeyPublicKey, f_pub: _FragList
+    ) -> None:
+        """Write security key public key"""
+        self.key_format.encode_public(
+            public_key.public_key(),  # type: ignore[arg-type]
+            f_pub,
+        )
+        f_pub.put_sshstr(public_key.application)
+
+    def encode_private(
+        self, private_key: SSHSecurityKeyPrivateKey, f_priv: _FragList
+    ) -> None:
+        """Write security key private key"""
+        self.encode_public(private_key.public_key(), f_priv)
+        f_priv.put_raw(bytes([private_key.flags]))
+        f_priv.put_sshstr(private_key.key_handle)
+        f_priv.put_sshstr(private_key._reserved)
+
+
 _KEY_FORMATS = {
     _SSH_RSA: _SSHFormatRSA(),
     _SSH_DSA: _SSHFormatDSA(),
@@ -579,6 +584,10 @@ _KEY_FORMATS = {
     _ECDSA_NISTP256: _SSHFormatECDSA(b"nistp256", ec.SECP256R1()),
     _ECDSA_NISTP384: _SSHFormatECDSA(b"nistp384", ec.SECP384R1()),
     _ECDSA_NISTP521: _SSHFormatECDSA(b"nistp521", ec.SECP521R1()),
+    _SK_SSH_ED25519: _SSHFormatSecurityKey(_SSHFormatEd25519()),
+    _SK_ECDSA_NISTP256: _SSHFormatSecurityKey(
+        _SSHFormatECDSA(b"nistp256", ec.SECP256R1())
+    ),
 }
 
 
@@ -599,112 +608,142 @@ SSHPrivateKeyTypes = typing.Union[
 ]
 
 
-def load_ssh_private_key(
-    data: bytes,
-    password: bytes | None,
-    backend: typing.Any = None,
-) -> SSHPrivateKeyTypes:
-    """Load private key from OpenSSH custom encoding."""
-    utils._check_byteslike("data", data)
-    if password is not None:
-        utils._check_bytes("password", password)
+class SSHSecurityKeyFlags(enum.IntFlag):
+    USER_PRESENCE_REQUIRED = 0x01
+    USER_VERIFICATION_REQUIRED = 0x04
+    RESIDENT_KEY = 0x20
 
-    m = _PEM_RC.search(data)
-    if not m:
-        raise ValueError("Not OpenSSH private key format")
-    p1 = m.start(1)
-    p2 = m.end(1)
-    data = binascii.a2b_base64(memoryview(data)[p1:p2])
-    if not data.startswith(_SK_MAGIC):
-        raise ValueError("Not OpenSSH private key format")
-    data = memoryview(data)[len(_SK_MAGIC) :]
-
-    # parse header
-    ciphername, data = _get_sshstr(data)
-    kdfname, data = _get_sshstr(data)
-    kdfoptions, data = _get_sshstr(data)
-    nkeys, data = _get_u32(data)
-    if nkeys != 1:
-        raise ValueError("Only one key supported")
-
-    # load public key data
-    pubdata, data = _get_sshstr(data)
-    pub_key_type, pubdata = _get_sshstr(pubdata)
-    kformat = _lookup_kformat(pub_key_type)
-    pubfields, pubdata = kformat.get_public(pubdata)
-    _check_empty(pubdata)
-
-    if (ciphername, kdfname) != (_NONE, _NONE):
-        ciphername_bytes = ciphername.tobytes()
-        if ciphername_bytes not in _SSH_CIPHERS:
-            raise UnsupportedAlgorithm(
-                f"Unsupported cipher: {ciphername_bytes!r}"
+
+class SSHSecurityKeyPublicKey:
+    def __init__(
+        self,
+        public_key: ec.EllipticCurvePublicKey | ed25519.Ed25519PublicKey,
+        application: bytes,
+    ) -> None:
+        if isinstance(public_key, ec.EllipticCurvePublicKey):
+            if not isinstance(public_key.curve, ec.SECP256R1):
+                raise ValueError(
+                    "Security keys only support the SECP256R1 curve"
+                )
+        elif not isinstance(public_key, ed25519.Ed25519PublicKey):
+            raise TypeError(
+                "public_key must be an Ed25519PublicKey or an "
+                "EllipticCurvePublicKey"
             )
-        if kdfname != _BCRYPT:
-            raise UnsupportedAlgorithm(f"Unsupported KDF: {kdfname!r}")
-        blklen = _SSH_CIPHERS[ciphername_bytes].block_len
-        tag_len = _SSH_CIPHERS[ciphername_bytes].tag_len
-        # load secret data
-        edata, data = _get_sshstr(data)
-        # see https://bugzilla.mindrot.org/show_bug.cgi?id=3553 for
-        # information about how OpenSSH handles AEAD tags
-        if _SSH_CIPHERS[ciphername_bytes].is_aead:
-            tag = bytes(data)
-            if len(tag) != tag_len:
-                raise ValueError("Corrupt data: invalid tag length for cipher")
-        else:
-            _check_empty(data)
-        _check_block_size(edata, blklen)
-        salt, kbuf = _get_sshstr(kdfoptions)
-        rounds, kbuf = _get_u32(kbuf)
-        _check_empty(kbuf)
-        ciph = _init_cipher(ciphername_bytes, password, salt.tobytes(), rounds)
-        dec = ciph.decryptor()
-        edata = memoryview(dec.update(edata))
-        if _SSH_CIPHERS[ciphername_bytes].is_aead:
-            assert isinstance(dec, AEADDecryptionContext)
-            _check_empty(dec.finalize_with_tag(tag))
-        else:
-            # _check_block_size requires data to be a full block so there
-            # should be no output from finalize
-            _check_empty(dec.finalize())
-    else:
-        # load secret data
-        edata, data = _get_sshstr(data)
-        _check_empty(data)
-        blklen = 8
-        _check_block_size(edata, blklen)
-    ck1, edata = _get_u32(edata)
-    ck2, edata = _get_u32(edata)
-    if ck1 != ck2:
-        raise ValueError("Corrupt data: broken checksum")
-
-    # load per-key struct
-    key_type, edata = _get_sshstr(edata)
-    if key_type != pub_key_type:
-        raise ValueError("Corrupt data: key type mismatch")
-    private_key, edata = kformat.load_private(edata, pubfields)
-    # We don't use the comment
-    _, edata = _get_sshstr(edata)
-
-    # yes, SSH does padding check *after* all other parsing is done.
-    # need to follow as it writes zero-byte padding too.
-    if edata != _PADDING[: len(edata)]:
-        raise ValueError("Corrupt data: invalid padding")
+        utils._check_bytes("application", application)
 
-    if isinstance(private_key, dsa.DSAPrivateKey):
-        warnings.warn(
-            "SSH DSA keys are deprecated and will be removed in a future "
-            "release.",
-            utils.DeprecatedIn40,
-            stacklevel=2,
+        self._public_key = public_key
+        self._application = application
+
+    def public_key(
+        self,
+    ) -> ec.EllipticCurvePublicKey | ed25519.Ed25519PublicKey:
+        return self._public_key
+
+    @property
+    def application(self) -> bytes:
+        return self._application
+
+    def public_bytes(self, encoding: Encoding, format: PublicFormat) -> bytes:
+        if (
+            encoding is not Encoding.OpenSSH
+            or format is not PublicFormat.OpenSSH
+        ):
+            raise ValueError(
+                "Security keys can only be serialized with Encoding.OpenSSH "
+                "and PublicFormat.OpenSSH"
+            )
+
+        return serialize_ssh_public_key(self)
+
+    def __eq__(self, other: object) -> bool:
+        if not isinstance(other, SSHSecurityKeyPublicKey):
+            return NotImplemented
+
+        return (
+            type(self._public_key) is type(other._public_key)
+            and self._public_key == other._public_key
+            and self._application == other._application
         )
 
-    return private_key
+
+class SSHSecurityKeyPrivateKey:
+    def __init__(
+        self,
+        public_key: SSHSecurityKeyPublicKey,
+        flags: int,
+        key_handle: bytes,
+        _reserved: bytes = b"",
+    ) -> None:
+        if not isinstance(public_key, SSHSecurityKeyPublicKey):
+            raise TypeError("public_key must be an SSHSecurityKeyPublicKey")
+        if not isinstance(flags, int):
+            raise TypeError("flags must be an integer")
+        if not 0 <= flags < 2**8:
+            raise ValueError("flags must be between 0 and 255")
+        utils._check_bytes("key_handle", key_handle)
+
+        self._public_key = public_key
+        self._flags = flags
+        self._key_handle = key_handle
+        self._reserved = _reserved
+
+    def public_key(self) -> SSHSecurityKeyPublicKey:
+        return self._public_key
+
+    @property
+    def flags(self) -> SSHSecurityKeyFlags:
+        return SSHSecurityKeyFlags(self._flags)
+
+    @property
+    def key_handle(self) -> bytes:
+        return self._key_handle
+
+    def private_bytes(
+        self,
+        encoding: Encoding,
+        format: PrivateFormat,
+        encryption_algorithm: KeySerializationEncryption,
+    ) -> bytes:
+        if encoding is not Encoding.PEM or format is not PrivateFormat.OpenSSH:
+            raise ValueError(
+                "Security keys can only be serialized with Encoding.PEM and "
+                "PrivateFormat.OpenSSH"
+            )
+
+        if isinstance(encryption_algorithm, NoEncryption):
+            password = b""
+        elif isinstance(encryption_algorithm, BestAvailableEncryption) or (
+            isinstance(encryption_algorithm, _KeySerializationEncryption)
+            and encryption_algorithm._format is PrivateFormat.OpenSSH
+        ):
+            password = encryption_algorithm.password
+        else:
+            raise ValueError("Unsupported encryption type")
+
+        return _serialize_ssh_private_key(self, password, encryption_algorithm)
+
+
+_SSHSecurityKeyTypes = typing.Union[
+    SSHSecurityKeyPrivateKey, SSHSecurityKeyPublicKey
+]
+
+
+def _load_ssh_security_key_private_key(
+    public_blob: bytes, flags: int, key_handle: bytes, reserved: bytes
+) -> SSHSecurityKeyPrivateKey:
+    key_type, data = _get_sshstr(memoryview(public_blob))
+    kformat = _lookup_kformat(key_type)
+    public_key, data = kformat.load_public(data)
+    _check_empty(data)
+    return SSHSecurityKeyPrivateKey(public_key, flags, key_handle, reserved)
+
+
+load_ssh_private_key = rust_openssl.keys.load_ssh_private_key
 
 
 def _serialize_ssh_private_key(
-    private_key: SSHPrivateKeyTypes,
+    private_key: SSHPrivateKeyTypes | SSHSecurityKeyPrivateKey,
     password: bytes,
     encryption_algorithm: KeySerializationEncryption,
 ) -> bytes:
@@ -791,6 +830,7 @@ SSHCertPublicKeyTypes = typing.Union[
     ec.EllipticCurvePublicKey,
     rsa.RSAPublicKey,
     ed25519.Ed25519PublicKey,
+    SSHSecurityKeyPublicKey,
 ]
 
 
@@ -803,7 +843,7 @@ class SSHCertificate:
     def __init__(
         self,
         _nonce: memoryview,
-        _public_key: SSHPublicKeyTypes,
+        _public_key: SSHPublicKeyTypes | SSHSecurityKeyPublicKey,
         _serial: int,
         _cctype: int,
         _key_id: memoryview,
@@ -819,6 +859,7 @@ class SSHCertificate:
         _tbs_cert_body: memoryview,
         _cert_key_type: bytes,
         _cert_body: memoryview,
+        _sk_sig_fields: tuple[int, int] | None = None,
     ):
         self._nonce = _nonce
         self._public_key = _public_key
@@ -840,6 +881,7 @@ class SSHCertificate:
         self._cert_key_type = _cert_key_type
         self._cert_body = _cert_body
         self._tbs_cert_body = _tbs_cert_body
+        self._sk_sig_fields = _sk_sig_fields
 
     @property
     def nonce(self) -> bytes:
@@ -896,36 +938,13 @@ class SSHCertificate:
         )
 
     def verify_cert_signature(self) -> None:
-        signature_key = self.signature_key()
-        if isinstance(signature_key, ed25519.Ed25519PublicKey):
-            signature_key.verify(
-                bytes(self._signature), bytes(self._tbs_cert_body)
-            )
-        elif isinstance(signature_key, ec.EllipticCurvePublicKey):
-            # The signature is encoded as a pair of big-endian integers
-            r, data = _get_mpint(self._signature)
-            s, data = _get_mpint(data)
-            _check_empty(data)
-            computed_sig = asym_utils.encode_dss_signature(r, s)
-            hash_alg = _get_ec_hash_alg(signature_key.curve)
-            signature_key.verify(
-                computed_sig, bytes(self._tbs_cert_body), ec.ECDSA(hash_alg)
-            )
-        else:
-            assert isinstance(signature_key, rsa.RSAPublicKey)
-            if self._inner_sig_type == _SSH_RSA:
-                hash_alg = hashes.SHA1()
-            elif self._inner_sig_type == _SSH_RSA_SHA256:
-                hash_alg = hashes.SHA256()
-            else:
-                assert self._inner_sig_type == _SSH_RSA_SHA512
-                hash_alg = hashes.SHA512()
-            signature_key.verify(
-                bytes(self._signature),
-                bytes(self._tbs_cert_body),
-                padding.PKCS1v15(),
-                hash_alg,
-            )
+        _verify_ssh_signature(
+            self.signature_key(),
+            self._inner_sig_type,
+            self._signature,
+            self._sk_sig_fields,
+            bytes(self._tbs_cert_body),
+        )
 
 
 def _get_ec_hash_alg(curve: ec.EllipticCurve) -> hashes.HashAlgorithm:
@@ -938,10 +957,87 @@ def _get_ec_hash_alg(curve: ec.EllipticCurve) -> hashes.HashAlgorithm:
         return hashes.SHA512()
 
 
+def _get_ssh_signature(
+    key_type: memoryview | bytes, signature_raw: memoryview
+) -> tuple[memoryview, memoryview, tuple[int, int] | None]:
+    """Parse a signature made by a key_type key.
+
+    Returns the signature type, the signature and, for security keys, the
+    flags and counter the authenticator signed along with the data.
+    """
+    sig_type, sig_rest = _get_sshstr(signature_raw)
+    # RSA keys can make signatures with several algorithm types
+    if (
+        key_type == _SSH_RSA
+        and sig_type not in [_SSH_RSA_SHA256, _SSH_RSA_SHA512, _SSH_RSA]
+    ) or (key_type != _SSH_RSA and sig_type != key_type):
+        raise ValueError("Signature key type does not match")
+    signature, sig_rest = _get_sshstr(sig_rest)
+    sk_sig_fields = None
+    if key_type in (_SK_SSH_ED25519, _SK_ECDSA_NISTP256):
+        if len(sig_rest) < 1:
+            raise ValueError("Invalid data")
+        flags, sig_rest = sig_rest[0], sig_rest[1:]
+        counter, sig_rest = _get_u32(sig_rest)
+        sk_sig_fields = (flags, counter)
+    _check_empty(sig_rest)
+    return sig_type, signature, sk_sig_fields
+
+
+def _verify_ssh_signature(
+    signature_key: SSHCertPublicKeyTypes,
+    sig_type: memoryview | bytes,
+    signature: memoryview,
+    sk_sig_fields: tuple[int, int] | None,
+    data: bytes,
+) -> None:
+    if isinstance(signature_key, SSHSecurityKeyPublicKey):
+        # The authenticator signs hashes of the application and the data,
+        # along with the flags and counter it reports.
+        assert sk_sig_fields is not None
+        flags, counter = sk_sig_fields
+        app_hash = hashes.Hash(hashes.SHA256())
+        app_hash.update(signature_key.application)
+        data_hash = hashes.Hash(hashes.SHA256())
+        data_hash.update(data)
+        data = b"".join(
+            [
+                app_hash.finalize(),
+                bytes([flags]),
+                counter.to_bytes(4, "big"),
+                data_hash.finalize(),
+            ]
+        )
+        signature_key = signature_key.public_key()
+
+    if isinstance(signature_key, ed25519.Ed25519PublicKey):
+        signature_key.verify(bytes(signature), data)
+    elif isinstance(signature_key, ec.EllipticCurvePublicKey):
+        # The signature is encoded as a pair of big-endian integers
+        r, rest = _get_mpint(signature)
+        s, rest = _get_mpint(rest)
+        _check_empty(rest)
+        computed_sig = asym_utils.encode_dss_signature(r, s)
+        hash_alg = _get_ec_hash_alg(signature_key.curve)
+        signature_key.verify(computed_sig, data, ec.ECDSA(hash_alg))
+    else:
+        assert isinstance(signature_key, rsa.RSAPublicKey)
+        if sig_type == _SSH_RSA:
+            hash_alg = hashes.SHA1()
+        elif sig_type == _SSH_RSA_SHA256:
+            hash_alg = hashes.SHA256()
+        else:
+            assert sig_type == _SSH_RSA_SHA512
+            hash_alg = hashes.SHA512()
+        signature_key.verify(
+            bytes(signature), data, padding.PKCS1v15(), hash_alg
+        )
+
+
 def _load_ssh_public_identity(
     data: bytes,
     _legacy_dsa_allowed=False,
-) -> SSHCertificate | SSHPublicKeyTypes:
+) -> SSHCertificate | SSHPublicKeyTypes | SSHSecurityKeyPublicKey:
     utils._check_byteslike("data", data)
 
     m = _SSH_PUBKEY_RC.match(data)
@@ -953,6 +1049,8 @@ def _load_ssh_public_identity(
     if key_type.endswith(_CERT_SUFFIX):
         with_cert = True
         key_type = key_type[: -len(_CERT_SUFFIX)]
+        if key_type.startswith(b"sk-"):
+            key_type += _OPENSSH_SUFFIX
     if key_type == _SSH_DSA and not _legacy_dsa_allowed:
         raise UnsupportedAlgorithm(
             "DSA keys aren't supported in SSH certificates"
@@ -999,16 +1097,9 @@ def _load_ssh_public_identity(
         tbs_cert_body = cert_body[: -len(rest)]
         signature_raw, rest = _get_sshstr(rest)
         _check_empty(rest)
-        inner_sig_type, sig_rest = _get_sshstr(signature_raw)
-        # RSA certs can have multiple algorithm types
-        if (
-            sig_type == _SSH_RSA
-            and inner_sig_type
-            not in [_SSH_RSA_SHA256, _SSH_RSA_SHA512, _SSH_RSA]
-        ) or (sig_type != _SSH_RSA and inner_sig_type != sig_type):
-            raise ValueError("Signature key type does not match")
-        signature, sig_rest = _get_sshstr(sig_rest)
-        _check_empty(sig_rest)
+        inner_sig_type, signature, sk_sig_fields = _get_ssh_signature(
+            sig_type, signature_raw
+        )
         return SSHCertificate(
             nonce,
             public_key,
@@ -1027,6 +1118,7 @@ def _load_ssh_public_identity(
             tbs_cert_body,
             orig_key_type,
             cert_body,
+            sk_sig_fields,
         )
     else:
         _check_empty(rest)
@@ -1035,7 +1127,7 @@ def _load_ssh_public_identity(
 
 def load_ssh_public_identity(
     data: bytes,
-) -> SSHCertificate | SSHPublicKeyTypes:
+) -> SSHCertificate | SSHPublicKeyTypes | SSHSecurityKeyPublicKey:
     return _load_ssh_public_identity(data)
 
 
@@ -1063,12 +1155,18 @@ def load_ssh_public_key(
     data: bytes, backend: typing.Any = None
 ) -> SSHPublicKeyTypes:
     cert_or_key = _load_ssh_public_identity(data, _legacy_dsa_allowed=True)
-    public_key: SSHPublicKeyTypes
+    public_key: SSHPublicKeyTypes | SSHSecurityKeyPublicKey
     if isinstance(cert_or_key, SSHCertificate):
         public_key = cert_or_key.public_key()
     else:
         public_key = cert_or_key
 
+    if isinstance(public_key, SSHSecurityKeyPublicKey):
+        raise UnsupportedAlgorithm(
+            "Security key types are only supported by "
+            "load_ssh_public_identity"
+        )
+
     if isinstance(public_key, dsa.DSAPublicKey):
         warnings.warn(
             "SSH DSA keys are deprecated and will be removed in a future "
@@ -1079,7 +1177,9 @@ def load_ssh_public_key(
     return public_key
 
 
-def serialize_ssh_public_key(public_key: SSHPublicKeyTypes) -> bytes:
+def serialize_ssh_public_key(
+    public_key: SSHPublicKeyTypes | SSHSecurityKeyPublicKey,
+) -> bytes:
     """One-line public key format for OpenSSH"""
     if isinstance(public_key, dsa.DSAPublicKey):
         warnings.warn(
@@ -1106,6 +1206,48 @@ SSHCertPrivateKeyTypes = typing.Union[
 ]
 
 
+def _sign_ssh_data(
+    private_key: SSHCertPrivateKeyTypes | SSHAgentKey, data: bytes
+) -> bytes:
+    """Sign data, returning the signature encoded as SSH does."""
+    from cryptography.hazmat.primitives.serialization.ssh_agent import (
+        SSHAgentKey,
+    )
+
+    if isinstance(private_key, SSHAgentKey):
+        # The agent returns the signature already encoded.
+        return private_key.sign(data)
+
+    # Sigs according to the rules defined for the key
+    # (RFC4253 section 6.6 for ssh-rsa, RFC5656 for ECDSA,
+    # and RFC8032 for Ed25519).
+    key_type = _get_ssh_key_type(private_key)
+    fsig = _FragList()
+    if isinstance(private_key, ed25519.Ed25519PrivateKey):
+        fsig.put_sshstr(key_type)
+        fsig.put_sshstr(private_key.sign(data))
+    elif isinstance(private_key, ec.EllipticCurvePrivateKey):
+        hash_alg = _get_ec_hash_alg(private_key.curve)
+        signature = private_key.sign(data, ec.ECDSA(hash_alg))
+        r, s = asym_utils.decode_dss_signature(signature)
+        fsig.put_sshstr(key_type)
+        fsigblob = _FragList()
+        fsigblob.put_mpint(r)
+        fsigblob.put_mpint(s)
+        fsig.put_sshstr(fsigblob.tobytes())
+    else:
+        assert isinstance(private_key, rsa.RSAPrivateKey)
+        # Just like Golang, we're going to use SHA512 for RSA
+        # https://cs.opensource.google/go/x/crypto/+/refs/tags/
+        # v0.4.0:ssh/certs.go;l=445
+        # RFC 8332 defines SHA256 and 512 as options
+        fsig.put_sshstr(_SSH_RSA_SHA512)
+        fsig.put_sshstr(
+            private_key.sign(data, padding.PKCS1v15(), hashes.SHA512())
+        )
+    return fsig.tobytes()
+
+
 # This is an undocumented limit enforced in the openssh codebase for sshd and
 # ssh-keygen, but it is undefined in the ssh certificates spec.
 _SSHKEY_CERT_MAX_PRINCIPALS = 256
@@ -1145,6 +1287,7 @@ class SSHCertificateBuilder:
                 ec.EllipticCurvePublicKey,
                 rsa.RSAPublicKey,
                 ed25519.Ed25519PublicKey,
+                SSHSecurityKeyPublicKey,
             ),
         ):
             raise TypeError("Unsupported key type")
@@ -1369,13 +1512,20 @@ class SSHCertificateBuilder:
             _extensions=[*self._extensions, (name, value)],
         )
 
-    def sign(self, private_key: SSHCertPrivateKeyTypes) -> SSHCertificate:
+    def sign(
+        self, private_key: SSHCertPrivateKeyTypes | SSHAgentKey
+    ) -> SSHCertificate:
+        from cryptography.hazmat.primitives.serialization.ssh_agent import (
+            SSHAgentKey,
+        )
+
         if not isinstance(
             private_key,
             (
                 ec.EllipticCurvePrivateKey,
                 rsa.RSAPrivateKey,
                 ed25519.Ed25519PrivateKey,
+                SSHAgentKey,
             ),
         ):
             raise TypeError("Unsupported private key type")
@@ -1416,7 +1566,7 @@ class SSHCertificateBuilder:
         self._extensions.sort(key=lambda x: x[0])
 
         key_type = _get_ssh_key_type(self._public_key)
-        cert_prefix = key_type + _CERT_SUFFIX
+        cert_prefix = _cert_key_type(key_type)
 
         # Marshal the bytes to be signed
         nonce = os.urandom(32)
@@ -1456,46 +1606,14 @@ class SSHCertificateBuilder:
         f.put_sshstr(fext.tobytes())
         f.put_sshstr(b"")  # RESERVED FIELD
         # encode CA public key
-        ca_type = _get_ssh_key_type(private_key)
+        ca_public_key = private_key.public_key()
+        ca_type = _get_ssh_key_type(ca_public_key)
         caformat = _lookup_kformat(ca_type)
         caf = _FragList()
         caf.put_sshstr(ca_type)
-        caformat.encode_public(private_key.public_key(), caf)
+        caformat.encode_public(ca_public_key, caf)
         f.put_sshstr(caf.tobytes())
-        # Sigs according to the rules defined for the CA's public key
-        # (RFC4253 section 6.6 for ssh-rsa, RFC5656 for ECDSA,
-        # and RFC8032 for Ed25519).
-        if isinstance(private_key, ed25519.Ed25519PrivateKey):
-            signature = private_key.sign(f.tobytes())
-            fsig = _FragList()
-            fsig.put_sshstr(ca_type)
-            fsig.put_sshstr(signature)
-            f.put_sshstr(fsig.tobytes())
-        elif isinstance(private_key, ec.EllipticCurvePrivateKey):
-            hash_alg = _get_ec_hash_alg(private_key.curve)
-            signature = private_key.sign(f.tobytes(), ec.ECDSA(hash_alg))
-            r, s = asym_utils.decode_dss_signature(signature)
-            fsig = _FragList()
-            fsig.put_sshstr(ca_type)
-            fsigblob = _FragList()
-            fsigblob.put_mpint(r)
-            fsigblob.put_mpint(s)
-            fsig.put_sshstr(fsigblob.tobytes())
-            f.put_sshstr(fsig.tobytes())
-
-        else:
-            assert isinstance(private_key, rsa.RSAPrivateKey)
-            # Just like Golang, we're going to use SHA512 for RSA
-            # https://cs.opensource.google/go/x/crypto/+/refs/tags/
-            # v0.4.0:ssh/certs.go;l=445
-            # RFC 8332 defines SHA256 and 512 as options
-            fsig = _FragList()
-            fsig.put_sshstr(_SSH_RSA_SHA512)
-            signature = private_key.sign(
-                f.tobytes(), padding.PKCS1v15(), hashes.SHA512()
-            )
-            fsig.put_sshstr(signature)
-            f.put_sshstr(fsig.tobytes())
+        f.put_sshstr(_sign_ssh_data(private_key, f.tobytes()))
 
         cert_data = binascii.b2a_base64(f.tobytes()).strip()
         # load_ssh_public_identity returns a union, but this is
@@ -1505,3 +1623,413 @@ class SSHCertificateBuilder:
             SSHCertificate,
             load_ssh_public_identity(b"".join([cert_prefix, b" ", cert_data])),
         )
+
+
+_SSHSIG_MAGIC = b"SSHSIG"
+_SSHSIG_VERSION = 1
+_SSHSIG_START = b"-----BEGIN SSH SIGNATURE-----"
+_SSHSIG_END = b"-----END SSH SIGNATURE-----"
+_SSHSIG_LINE_LENGTH = 70
+_SSHSIG_HASHES: dict[bytes, type[hashes.HashAlgorithm]] = {
+    b"sha256": hashes.SHA256,
+    b"sha512": hashes.SHA512,
+}
+
+
+def _sshsig_signed_data(
+    namespace: bytes, reserved: bytes, hash_algorithm: bytes, digest: bytes
+) -> bytes:
+    f = _FragList([_SSHSIG_MAGIC])
+    f.put_sshstr(namespace)
+    f.put_sshstr(reserved)
+    f.put_sshstr(hash_algorithm)
+    f.put_sshstr(digest)
+    return f.tobytes()
+
+
+class SSHSignature:
+    def __init__(
+        self,
+        _public_key: SSHCertPublicKeyTypes,
+        _namespace: bytes,
+        _reserved: bytes,
+        _hash_algorithm: bytes,
+        _sig_type: memoryview,
+        _signature: memoryview,
+        _sk_sig_fields: tuple[int, int] | None,
+        _blob: bytes,
+    ):
+        self._public_key = _public_key
+        self._namespace = _namespace
+        self._reserved = _reserved
+        self._hash_algorithm = _hash_algorithm
+        self._sig_type = _sig_type
+        self._signature = _signature
+        self._sk_sig_fields = _sk_sig_fields
+        self._blob = _blob
+
+    def public_key(self) -> SSHCertPublicKeyTypes:
+        return self._public_key
+
+    @property
+    def namespace(self) -> bytes:
+        return self._namespace
+
+    @property
+    def hash_algorithm(self) -> hashes.HashAlgorithm:
+        return _SSHSIG_HASHES[self._hash_algorithm]()
+
+    def public_bytes(self) -> bytes:
+        data = binascii.b2a_base64(self._blob, newline=False)
+        lines = [_SSHSIG_START]
+        for i in range(0, len(data), _SSHSIG_LINE_LENGTH):
+            lines.append(data[i : i + _SSHSIG_LINE_LENGTH])
+        lines.append(_SSHSIG_END)
+        return b"\n".join(lines) + b"\n"
+
+    def verify(self, data: bytes, namespace: bytes) -> None:
+        utils._check_byteslike("data", data)
+        utils._check_bytes("namespace", namespace)
+        if namespace != self._namespace:
+            raise InvalidSignature("Signature namespace mismatch")
+
+        h = hashes.Hash(self.hash_algorithm)
+        h.update(data)
+        _verify_ssh_signature(
+            self._public_key,
+            self._sig_type,
+            self._signature,
+            self._sk_sig_fields,
+            _sshsig_signed_data(
+                self._namespace,
+                self._reserved,
+                self._hash_algorithm,
+                h.finalize(),
+            ),
+        )
+
+    def verify_allowed_signers(
+        self,
+        data: bytes,
+        namespace: bytes,
+        principal: bytes,
+        allowed_signers: typing.Iterable[SSHAllowedSigner],
+        timestamp: int | None = None,
+    ) -> None:
+        utils._check_bytes("principal", principal)
+        self.verify(data, namespace)
+        if timestamp is None:
+            timestamp = int(time.time())
+
+        for signer in allowed_signers:
+            if (
+                not signer.cert_authority
+                and signer.public_key() == self._public_key
+                and _match_pattern_list(principal, signer.principals)
+                and (
+                    signer.namespaces is None
+                    or _match_pattern_list(namespace, signer.namespaces)
+                )
+                and (
+                    signer.valid_after is None
+                    or timestamp >= signer.valid_after
+                )
+                and (
+                    signer.valid_before is None
+                    or timestamp <= signer.valid_before
+                )
+            ):
+                return
+
+        raise InvalidSignature("No allowed signer matches the signature")
+
+
+def load_ssh_signature(data: bytes) -> SSHSignature:
+    utils._check_byteslike("data", data)
+    data = bytes(data).strip()
+    if not data.startswith(_SSHSIG_START) or not data.endswith(_SSHSIG_END):
+        raise ValueError("Not an SSH signature")
+    body = data[len(_SSHSIG_START) : -len(_SSHSIG_END)]
+    try:
+        blob = binascii.a2b_base64(b"".join(body.split()))
+    except binascii.Error:
+        raise ValueError("Invalid format")
+
+    rest = memoryview(blob)
+    if rest[: len(_SSHSIG_MAGIC)] != _SSHSIG_MAGIC:
+        raise ValueError("Not an SSH signature")
+    version, rest = _get_u32(rest[len(_SSHSIG_MAGIC) :])
+    if version != _SSHSIG_VERSION:
+        raise ValueError(f"Unsupported SSH signature version: {version}")
+    public_key_raw, rest = _get_sshstr(rest)
+    namespace, rest = _get_sshstr(rest)
+    reserved, rest = _get_sshstr(rest)
+    hash_algorithm, rest = _get_sshstr(rest)
+    signature_raw, rest = _get_sshstr(rest)
+    _check_empty(rest)
+
+    key_type, key_rest = _get_sshstr(public_key_raw)
+    if key_type == _SSH_DSA:
+        raise UnsupportedAlgorithm(
+            "DSA keys aren't supported in SSH signatures"
+        )
+    kformat = _lookup_kformat(key_type)
+    public_key, key_rest = kformat.load_public(key_rest)
+    _check_empty(key_rest)
+
+    if bytes(hash_algorithm) not in _SSHSIG_HASHES:
+        raise UnsupportedAlgorithm(
+            f"Unsupported SSH signature hash algorithm: {hash_algorithm!r}"
+        )
+    sig_type, signature, sk_sig_fields = _get_ssh_signature(
+        key_type, signature_raw
+    )
+    # Like OpenSSH, don't allow RSA signatures using SHA1
+    if sig_type == _SSH_RSA:
+        raise ValueError("RSA SSH signatures must use SHA256 or SHA512")
+
+    return SSHSignature(
+        public_key,
+        bytes(namespace),
+        bytes(reserved),
+        bytes(hash_algorithm),
+        sig_type,
+        signature,
+        sk_sig_fields,
+        blob,
+    )
+
+
+def create_ssh_signature(
+    data: bytes,
+    private_key: SSHCertPrivateKeyTypes | SSHAgentKey,
+    namespace: bytes,
+    hash_algorithm: hashes.HashAlgorithm | None = None,
+) -> SSHSignature:
+    from cryptography.hazmat.primitives.serialization.ssh_agent import (
+        SSHAgentKey,
+    )
+
+    utils._check_byteslike("data", data)
+    utils._check_bytes("namespace", namespace)
+    if not namespace:
+        raise ValueError("namespace must not be empty")
+    if not isinstance(
+        private_key,
+        (
+            ec.EllipticCurvePrivateKey,
+            rsa.RSAPrivateKey,
+            ed25519.Ed25519PrivateKey,
+            SSHAgentKey,
+        ),
+    ):
+        raise TypeError("Unsupported private key type")
+    if hash_algorithm is None:
+        hash_algorithm = hashes.SHA512()
+    if not isinstance(hash_algorithm, (hashes.SHA256, hashes.SHA512)):
+        raise UnsupportedAlgorithm(
+            "SSH signatures only support SHA256 and SHA512"
+        )
+
+    hash_name = hash_algorithm.name.encode()
+    h = hashes.Hash(hash_algorithm)
+    h.update(data)
+    signed_data = _sshsig_signed_data(namespace, b"", hash_name, h.finalize())
+
+    public_key = private_key.public_key()
+    key_type = _get_ssh_key_type(public_key)
+    fkey = _FragList()
+    fkey.put_sshstr(key_type)
+    _lookup_kformat(key_type).encode_public(public_key, fkey)
+
+    f = _FragList([_SSHSIG_MAGIC])
+    f.put_u32(_SSHSIG_VERSION)
+    f.put_sshstr(fkey)
+    f.put_sshstr(namespace)
+    f.put_sshstr(b"")  # RESERVED FIELD
+    f.put_sshstr(hash_name)
+    f.put_sshstr(_sign_ssh_data(private_key, signed_data))
+    return load_ssh_signature(
+        b"".join(
+            [
+                _SSHSIG_START,
+                b"\n",
+                binascii.b2a_base64(f.tobytes()),
+                _SSHSIG_END,
+            ]
+        )
+    )
+
+
+class SSHAllowedSigner:
+    def __init__(
+        self,
+        _principals: list[bytes],
+        _public_key: SSHCertPublicKeyTypes,
+        _cert_authority: bool,
+        _namespaces: list[bytes] | None,
+        _valid_after: int | None,
+        _valid_before: int | None,
+    ):
+        self._principals = _principals
+        self._public_key = _public_key
+        self._cert_authority = _cert_authority
+        self._namespaces = _namespaces
+        self._valid_after = _valid_after
+        self._valid_before = _valid_before
+
+    @property
+    def principals(self) -> list[bytes]:
+        return self._principals
+
+    def public_key(self) -> SSHCertPublicKeyTypes:
+        return self._public_key
+
+    @property
+    def cert_authority(self) -> bool:
+        return self._cert_authority
+
+    @property
+    def namespaces(self) -> list[bytes] | None:
+        return self._namespaces
+
+    @property
+    def valid_after(self) -> int | None:
+        return self._valid_after
+
+    @property
+    def valid_before(self) -> int | None:
+        return self._valid_before
+
+
+def _match_pattern(value: bytes, pattern: bytes) -> bool:
+    """Match value against a pattern using the * and ? wildcards."""
+    regex = b"".join(
+        b".*" if c == b"*" else b"." if c == b"?" else re.escape(c)
+        for c in (pattern[i : i + 1] for i in range(len(pattern)))
+    )
+    return re.fullmatch(regex, value, re.DOTALL) is not None
+
+
+def _match_pattern_list(value: bytes, patterns: list[bytes]) -> bool:
+    """Match value against a list of patterns, as OpenSSH does.
+
+    A pattern prefixed with ! that matches rejects the value, even if another
+    pattern matches it.
+    """
+    matched = False
+    for pattern in patterns:
+        if pattern.startswith(b"!"):
+            if _match_pattern(value, pattern[1:]):
+                return False
+        elif _match_pattern(value, pattern):
+            matched = True
+    return matched
+
+
+def _split_unquoted(line: bytes, separators: bytes) -> list[bytes]:
+    """Split line at any of separators, except inside double quotes."""
+    fields = []
+    field = bytearray()
+    in_quotes = False
+    for c in line:
+        if c == ord('"'):
+            in_quotes = not in_quotes
+        if not in_quotes and c in separators:
+            if field:
+                fields.append(bytes(field))
+            field = bytearray()
+        else:
+            field.append(c)
+    if in_quotes:
+        raise ValueError("Unterminated quote")
+    if field:
+        fields.append(bytes(field))
+    return fields
+
+
+def _unquote(value: bytes) -> bytes:
+    if len(value) >= 2 and value.startswith(b'"') and value.endswith(b'"'):
+        return value[1:-1]
+    return value
+
+
+def _parse_allowed_signer_time(value: bytes) -> int:
+    """Parse a YYYYMMDD[HHMM[SS]][Z] time. Without a Z it is local time."""
+    utc = value.endswith(b"Z")
+    if utc:
+        value = value[:-1]
+    if len(value) not in (8, 12, 14) or not value.isdigit():
+        raise ValueError(f"Invalid time: {value!r}")
+    parts = [int(value[i : i + 2]) for i in range(4, len(value), 2)]
+    when = datetime.datetime(int(value[:4]), *parts)
+    if utc:
+        when = when.replace(tzinfo=datetime.timezone.utc)
+    return int(when.timestamp())
+
+
+def load_ssh_allowed_signers(data: bytes) -> list[SSHAllowedSigner]:
+    utils._check_byteslike("data", data)
+    signers = []
+    for line in bytes(data).splitlines():
+        line = line.strip()
+        if not line or line.startswith(b"#"):
+            continue
+        fields = _split_unquoted(line, b" \t")
+        if len(fields) < 3:
+            raise ValueError("Invalid allowed signers line")
+        principals = _unquote(fields[0]).split(b",")
+
+        cert_authority = False
+        namespaces = None
+        valid_after = None
+        valid_before = None
+        # The options are optional, and come before the key
+        if fields[1] in _KEY_FORMATS or fields[1].endswith(_CERT_SUFFIX):
+            key_fields = fields[1:]
+        else:
+            key_fields = fields[2:]
+            for option in _split_unquoted(fields[1], b","):
+                name, _, value = option.partition(b"=")
+                name = name.lower()
+                value = _unquote(value)
+                if name == b"cert-authority":
+                    cert_authority = True
+                elif name == b"namespaces":
+                    namespaces = value.split(b",")
+                elif name == b"valid-after":
+                    valid_after = _parse_allowed_signer_time(value)
+                elif name == b"valid-before":
+                    valid_before = _parse_allowed_signer_time(value)
+                else:
+                    raise ValueError(f"Unsupported option: {name!r}")
+        if len(key_fields) < 2:
+            raise ValueError("Invalid allowed signers line")
+
+        public_key = load_ssh_public_identity(
+            key_fields[0] + b" " + key_fields[1]
+        )
+        if isinstance(public_key, SSHCertificate):
+            raise ValueError("Allowed signers must be keys, not certificates")
+        signers.append(
+            SSHAllowedSigner(
+                principals,
+                # load_ssh_public_identity doesn't load DSA keys
+                typing.cast(SSHCertPublicKeyTypes, public_key),
+                cert_authority,
+                namespaces,
+                valid_after,
+                valid_before,
+            )
+        )
+    return signers
+
+
+SSHAuthorizedKey = rust_openssl.ssh_files.SSHAuthorizedKey
+SSHKnownHost = rust_openssl.ssh_files.SSHKnownHost
+load_ssh_authorized_keys = rust_openssl.ssh_files.load_ssh_authorized_keys
+serialize_ssh_authorized_key = (
+    rust_openssl.ssh_files.serialize_ssh_authorized_key
+)
+load_ssh_known_hosts = rust_openssl.ssh_files.load_ssh_known_hosts
+serialize_ssh_known_host = rust_openssl.ssh_files.serialize_ssh_known_host
@@ -0,0 +1,179 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+from __future__ import annotations
+
+import binascii
+import os
+import socket
+import sys
+import typing
+
+from cryptography.hazmat.primitives.asymmetric import rsa
+from cryptography.hazmat.primitives.serialization.ssh import (
+    SSHCertificate,
+    SSHCertPublicKeyTypes,
+    _check_empty,
+    _FragList,
+    _get_sshstr,
+    _get_u32,
+    load_ssh_public_identity,
+)
+
+__all__ = ["SSHAgentClient", "SSHAgentKey"]
+
+# Message numbers from draft-miller-ssh-agent
+_SSH_AGENTC_REQUEST_IDENTITIES = 11
+_SSH_AGENT_IDENTITIES_ANSWER = 12
+_SSH_AGENTC_SIGN_REQUEST = 13
+_SSH_AGENT_SIGN_RESPONSE = 14
+
+_SSH_AGENT_RSA_SHA2_512 = 4
+
+# The largest message OpenSSH's agent will send or accept.
+_MAX_MESSAGE_LEN = 256 * 1024
+
+_WINDOWS_PIPE_PREFIX = "\\\\.\\pipe\\"
+_WINDOWS_DEFAULT_PIPE = _WINDOWS_PIPE_PREFIX + "openssh-ssh-agent"
+
+
+class _AgentConnection:
+    """A connection to an agent over a Unix socket or Windows named pipe."""
+
+    def __init__(self, path: str) -> None:
+        self._sock: socket.socket | None = None
+        self._pipe: typing.BinaryIO | None = None
+        if sys.platform == "win32" and path.startswith(_WINDOWS_PIPE_PREFIX):
+            self._pipe = open(path, "r+b", buffering=0)
+        else:
+            sock = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+            try:
+                sock.connect(path)
+            except BaseException:
+                sock.close()
+                raise
+            self._sock = sock
+
+    def __enter__(self) -> _AgentConnection:
+        return self
+
+    def __exit__(self, *args: typing.Any) -> None:
+        if self._sock is not None:
+            self._sock.close()
+        if self._pipe is not None:
+            self._pipe.close()
+
+    def send(self, data: bytes) -> None:
+        if self._sock is not None:
+            self._sock.sendall(data)
+        else:
+            assert self._pipe is not None
+            view = memoryview(data)
+            while view:
+                view = view[self._pipe.write(view) :]
+
+    def recv(self, length: int) -> bytes:
+        buf = bytearray()
+        while len(buf) < length:
+            if self._sock is not None:
+                chunk = self._sock.recv(length - len(buf))
+            else:
+                assert self._pipe is not None
+                chunk = self._pipe.read(length - len(buf))
+            if not chunk:
+                raise ValueError("Connection to the SSH agent was closed")
+            buf += chunk
+        return bytes(buf)
+
+
+class SSHAgentClient:
+    def __init__(self, path: str | None = None) -> None:
+        if path is None:
+            path = os.environ.get("SSH_AUTH_SOCK")
+        if path is None:
+            if sys.platform != "win32":
+                raise ValueError(
+                    "path must be provided when SSH_AUTH_SOCK is not set"
+                )
+            path = _WINDOWS_DEFAULT_PIPE
+        if not isinstance(path, str):
+            raise TypeError("path must be a str")
+
+        self._path = path
+
+    def list_identities(self) -> list[SSHAgentKey]:
+        response = self._request(bytes([_SSH_AGENTC_REQUEST_IDENTITIES]))
+        if response[0] != _SSH_AGENT_IDENTITIES_ANSWER:
+            raise ValueError("The SSH agent refused to list its identities")
+
+        count, data = _get_u32(response[1:])
+        keys = []
+        for _ in range(count):
+            key_blob, data = _get_sshstr(data)
+            comment, data = _get_sshstr(data)
+            keys.append(SSHAgentKey(self, bytes(key_blob), bytes(comment)))
+        _check_empty(data)
+        return keys
+
+    def _sign(self, key_blob: bytes, data: bytes, flags: int) -> bytes:
+        f = _FragList([bytes([_SSH_AGENTC_SIGN_REQUEST])])
+        f.put_sshstr(key_blob)
+        f.put_sshstr(data)
+        f.put_u32(flags)
+        response = self._request(f.tobytes())
+        if response[0] != _SSH_AGENT_SIGN_RESPONSE:
+            raise ValueError("The SSH agent refused to sign the data")
+
+        signature, rest = _get_sshstr(response[1:])
+        _check_empty(rest)
+        return bytes(signature)
+
+    def _request(self, message: bytes) -> memoryview:
+        with _AgentConnection(self._path) as conn:
+            conn.send(len(message).to_bytes(4, "big") + message)
+            length = int.from_bytes(conn.recv(4), "big")
+            if length == 0 or length > _MAX_MESSAGE_LEN:
+                raise ValueError("Invalid SSH agent response")
+            return memoryview(conn.recv(length))
+
+
+class SSHAgentKey:
+    def __init__(
+        self, client: SSHAgentClient, key_blob: bytes, comment: bytes
+    ) -> None:
+        self._client = client
+        self._key_blob = key_blob
+        self._comment = comment
+
+    @property
+    def key_blob(self) -> bytes:
+        return self._key_blob
+
+    @property
+    def comment(self) -> bytes:
+        return self._comment
+
+    def public_key(self) -> SSHCertPublicKeyTypes:
+        key_type, _ = _get_sshstr(memoryview(self._key_blob))
+        identity = load_ssh_public_identity(
+            bytes(key_type)
+            + b" "
+            + binascii.b2a_base64(self._key_blob, newline=False)
+        )
+        # Agents hold certificates alongside the key they certify, and sign
+        # with that key.
+        if isinstance(identity, SSHCertificate):
+            return identity.public_key()
+        # load_ssh_public_identity doesn't load DSA keys, so this is one of
+        # SSHCertPublicKeyTypes.
+        return typing.cast(SSHCertPublicKeyTypes, identity)
+
+    def sign(self, data: bytes) -> bytes:
+        # Like SSHCertificateBuilder, ask for SHA512 signatures from RSA keys
+        # rather than the SHA1 the agent defaults to.
+        if isinstance(self.public_key(), rsa.RSAPublicKey):
+            flags = _SSH_AGENT_RSA_SHA2_512
+        else:
+            flags = 0
+        return self._client._sign(self._key_blob, data, flags)
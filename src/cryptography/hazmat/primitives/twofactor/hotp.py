@@ -8,12 +8,17 @@ import base64
 import typing
 from urllib.parse import quote, urlencode
 
-from cryptography.hazmat.primitives import constant_time, hmac
+from cryptography.hazmat.bindings._rust import openssl as rust_openssl
 from cryptography.hazmat.primitives.hashes import SHA1, SHA256, SHA512
 from cryptography.hazmat.primitives.twofactor import InvalidToken
 
 HOTPHashTypes = typing.Union[SHA1, SHA256, SHA512]
 
+# The largest number of counter values that verify() will check on either
+# side of the expected one. Every extra value accepted makes guessing a
+# valid one time password easier.
+_MAX_WINDOW = 10
+
 
 def _generate_uri(
     hotp: HOTP,
@@ -65,24 +70,21 @@ class HOTP:
         self._key = key
         self._length = length
         self._algorithm = algorithm
+        self._hotp = rust_openssl.twofactor.HOTP(key, length, algorithm, False)
 
     def generate(self, counter: int) -> bytes:
-        truncated_value = self._dynamic_truncate(counter)
-        hotp = truncated_value % (10**self._length)
-        return "{0:0{1}}".format(hotp, self._length).encode()
+        return self._hotp.generate(counter)
 
-    def verify(self, hotp: bytes, counter: int) -> None:
-        if not constant_time.bytes_eq(self.generate(counter), hotp):
+    def verify(self, hotp: bytes, counter: int, window: int = 0) -> int:
+        if not 0 <= window <= _MAX_WINDOW:
+            raise ValueError(f"window must be between 0 and {_MAX_WINDOW}.")
+        offset = self._hotp.verify(hotp, counter, 0, window)
+        if offset is None:
             raise InvalidToken("Supplied HOTP value does not match.")
+        return offset
 
     def _dynamic_truncate(self, counter: int) -> int:
-        ctx = hmac.HMAC(self._key, self._algorithm)
-        ctx.update(counter.to_bytes(length=8, byteorder="big"))
-        hmac_value = ctx.finalize()
-
-        offset = hmac_value[len(hmac_value) - 1] & 0b1111
-        p = hmac_value[offset : offset + 4]
-        return int.from_bytes(p, byteorder="big") & 0x7FFFFFFF
+        return self._hotp.dynamic_truncate(counter)
 
     def get_provisioning_uri(
         self, account_name: str, counter: int, issuer: str | None
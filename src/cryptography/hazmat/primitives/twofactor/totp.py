@@ -6,9 +6,11 @@ from __future__ import annotations
 
 import typing
 
-from cryptography.hazmat.primitives import constant_time
+from cryptography.hazmat.bindings._rust import openssl as rust_openssl
+from cryptography.hazmat.primitives.hashes import SHA1
 from cryptography.hazmat.primitives.twofactor import InvalidToken
 from cryptography.hazmat.primitives.twofactor.hotp import (
+    _MAX_WINDOW,
     HOTP,
     HOTPHashTypes,
     _generate_uri,
@@ -34,9 +36,10 @@ class TOTP:
         counter = int(time / self._time_step)
         return self._hotp.generate(counter)
 
-    def verify(self, totp: bytes, time: int) -> None:
-        if not constant_time.bytes_eq(self.generate(time), totp):
-            raise InvalidToken("Supplied TOTP value does not match.")
+    def verify(self, totp: bytes, time: int, window: int = 0) -> int:
+        return _verify_totp(
+            self._hotp._hotp, self._time_step, totp, time, window
+        )
 
     def get_provisioning_uri(
         self, account_name: str, issuer: str | None
@@ -48,3 +51,32 @@ class TOTP:
             issuer,
             [("period", int(self._time_step))],
         )
+
+
+class SteamGuardTOTP:
+    def __init__(self, key: bytes) -> None:
+        self._time_step = 30
+        self._hotp = rust_openssl.twofactor.HOTP(key, 5, SHA1(), True)
+
+    def generate(self, time: int | float) -> bytes:
+        counter = int(time / self._time_step)
+        return self._hotp.generate(counter)
+
+    def verify(self, code: bytes, time: int, window: int = 0) -> int:
+        return _verify_totp(self._hotp, self._time_step, code, time, window)
+
+
+def _verify_totp(
+    hotp: rust_openssl.twofactor.HOTP,
+    time_step: int,
+    totp: bytes,
+    time: int,
+    window: int,
+) -> int:
+    if not 0 <= window <= _MAX_WINDOW:
+        raise ValueError(f"window must be between 0 and {_MAX_WINDOW}.")
+    counter = int(time / time_step)
+    offset = hotp.verify(totp, counter, window, window)
+    if offset is None:
+        raise InvalidToken("Supplied TOTP value does not match.")
+    return offset
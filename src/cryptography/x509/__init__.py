@@ -24,12 +24,19 @@ from cryptography.x509.base import (
     load_der_x509_csr,
     load_pem_x509_certificate,
     load_pem_x509_certificates,
+    load_pem_x509_certificates_from_file,
     load_pem_x509_crl,
     load_pem_x509_csr,
     random_serial_number,
 )
 from cryptography.x509.extensions import (
     AccessDescription,
+    AndroidAuthorizationList,
+    AndroidKeyAttestation,
+    AndroidRootOfTrust,
+    AndroidSecurityLevel,
+    AndroidVerifiedBootState,
+    AppleAttestationNonce,
     AuthorityInformationAccess,
     AuthorityKeyIdentifier,
     BasicConstraints,
@@ -174,6 +181,7 @@ __all__ = [
     "verification",
     "load_pem_x509_certificate",
     "load_pem_x509_certificates",
+    "load_pem_x509_certificates_from_file",
     "load_der_x509_certificate",
     "load_pem_x509_csr",
     "load_der_x509_csr",
@@ -254,4 +262,10 @@ __all__ = [
     "SignatureAlgorithmOID",
     "NameOID",
     "MSCertificateTemplate",
+    "AndroidKeyAttestation",
+    "AndroidAuthorizationList",
+    "AndroidRootOfTrust",
+    "AndroidSecurityLevel",
+    "AndroidVerifiedBootState",
+    "AppleAttestationNonce",
 ]
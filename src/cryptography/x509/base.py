@@ -18,11 +18,14 @@ from cryptography.hazmat.primitives.asymmetric import (
     ec,
     ed448,
     ed25519,
+    mldsa,
     padding,
     rsa,
+    slhdsa,
     x448,
     x25519,
 )
+from cryptography.hazmat.primitives.asymmetric.signer import ExternalSigner
 from cryptography.hazmat.primitives.asymmetric.types import (
     CertificateIssuerPrivateKeyTypes,
     CertificateIssuerPublicKeyTypes,
@@ -645,6 +648,9 @@ load_pem_x509_certificate = rust_x509.load_pem_x509_certificate
 load_der_x509_certificate = rust_x509.load_der_x509_certificate
 
 load_pem_x509_certificates = rust_x509.load_pem_x509_certificates
+load_pem_x509_certificates_from_file = (
+    rust_x509.load_pem_x509_certificates_from_file
+)
 
 load_pem_x509_csr = rust_x509.load_pem_x509_csr
 load_der_x509_csr = rust_x509.load_der_x509_csr
@@ -731,7 +737,7 @@ class CertificateSigningRequestBuilder:
 
     def sign(
         self,
-        private_key: CertificateIssuerPrivateKeyTypes,
+        private_key: CertificateIssuerPrivateKeyTypes | ExternalSigner,
         algorithm: _AllowedHashTypes | None,
         backend: typing.Any = None,
         *,
@@ -829,13 +835,15 @@ class CertificateBuilder:
                 ed448.Ed448PublicKey,
                 x25519.X25519PublicKey,
                 x448.X448PublicKey,
+                mldsa.MLDSAPublicKey,
+                slhdsa.SLHDSAPublicKey,
             ),
         ):
             raise TypeError(
                 "Expecting one of DSAPublicKey, RSAPublicKey,"
                 " EllipticCurvePublicKey, Ed25519PublicKey,"
-                " Ed448PublicKey, X25519PublicKey, or "
-                "X448PublicKey."
+                " Ed448PublicKey, X25519PublicKey, X448PublicKey,"
+                " MLDSAPublicKey, or SLHDSAPublicKey."
             )
         if self._public_key is not None:
             raise ValueError("The public key may only be set once.")
@@ -961,7 +969,7 @@ class CertificateBuilder:
 
     def sign(
         self,
-        private_key: CertificateIssuerPrivateKeyTypes,
+        private_key: CertificateIssuerPrivateKeyTypes | ExternalSigner,
         algorithm: _AllowedHashTypes | None,
         backend: typing.Any = None,
         *,
@@ -1118,7 +1126,7 @@ class CertificateRevocationListBuilder:
 
     def sign(
         self,
-        private_key: CertificateIssuerPrivateKeyTypes,
+        private_key: CertificateIssuerPrivateKeyTypes | ExternalSigner,
         algorithm: _AllowedHashTypes | None,
         backend: typing.Any = None,
         *,
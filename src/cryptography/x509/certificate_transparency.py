@@ -5,11 +5,24 @@
 from __future__ import annotations
 
 import abc
+import base64
 import datetime
+import json
+import typing
 
 from cryptography import utils
+from cryptography.exceptions import InvalidSignature
 from cryptography.hazmat.bindings._rust import x509 as rust_x509
+from cryptography.hazmat.primitives import hashes, serialization
+from cryptography.hazmat.primitives.asymmetric import ec, rsa
+from cryptography.hazmat.primitives.asymmetric.types import (
+    CertificatePublicKeyTypes,
+)
 from cryptography.hazmat.primitives.hashes import HashAlgorithm
+from cryptography.x509.oid import ExtensionOID
+
+if typing.TYPE_CHECKING:
+    from cryptography.x509.base import Certificate
 
 
 class LogEntryType(utils.Enum):
@@ -93,5 +106,285 @@ class SignedCertificateTimestamp(metaclass=abc.ABCMeta):
         Returns the raw bytes of any extensions for this SCT.
         """
 
+    @abc.abstractmethod
+    def verify(
+        self,
+        public_key: CertificatePublicKeyTypes,
+        certificate: Certificate,
+        issuer: Certificate | None = None,
+    ) -> None:
+        """
+        Verifies that this SCT was signed by the log with the given public
+        key for the given certificate.
+        """
+
 
 SignedCertificateTimestamp.register(rust_x509.Sct)
+
+
+class LogState(utils.Enum):
+    PENDING = "pending"
+    QUALIFIED = "qualified"
+    USABLE = "usable"
+    READONLY = "readonly"
+    RETIRED = "retired"
+    REJECTED = "rejected"
+
+
+_LogPublicKeyTypes = typing.Union[
+    ec.EllipticCurvePublicKey, rsa.RSAPublicKey
+]
+
+_QUALIFYING_STATES = frozenset(
+    [LogState.QUALIFIED, LogState.USABLE, LogState.READONLY]
+)
+
+
+class Log:
+    def __init__(
+        self,
+        log_id: bytes,
+        public_key: _LogPublicKeyTypes,
+        description: str,
+        operator: str,
+        url: str,
+        mmd: int,
+        state: LogState | None,
+        state_timestamp: datetime.datetime | None,
+    ) -> None:
+        if not isinstance(
+            public_key, (ec.EllipticCurvePublicKey, rsa.RSAPublicKey)
+        ):
+            raise TypeError("public_key must be an EC or RSA public key")
+        if (state is None) != (state_timestamp is None):
+            raise ValueError(
+                "state and state_timestamp must both be set or both be None"
+            )
+
+        self._log_id = log_id
+        self._public_key = public_key
+        self._description = description
+        self._operator = operator
+        self._url = url
+        self._mmd = mmd
+        self._state = state
+        self._state_timestamp = state_timestamp
+
+    @property
+    def log_id(self) -> bytes:
+        return self._log_id
+
+    @property
+    def public_key(self) -> _LogPublicKeyTypes:
+        return self._public_key
+
+    @property
+    def description(self) -> str:
+        return self._description
+
+    @property
+    def operator(self) -> str:
+        return self._operator
+
+    @property
+    def url(self) -> str:
+        return self._url
+
+    @property
+    def mmd(self) -> int:
+        return self._mmd
+
+    @property
+    def state(self) -> LogState | None:
+        return self._state
+
+    @property
+    def state_timestamp(self) -> datetime.datetime | None:
+        return self._state_timestamp
+
+    def is_qualifying(self, timestamp: datetime.datetime) -> bool:
+        """
+        Returns whether an SCT issued by this log at ``timestamp`` counts
+        towards a CT policy.
+        """
+        if self._state in _QUALIFYING_STATES:
+            return True
+        if self._state == LogState.RETIRED:
+            assert self._state_timestamp is not None
+            return _as_utc(timestamp) < self._state_timestamp
+        return False
+
+    def __repr__(self) -> str:
+        return (
+            f"<Log(description={self._description!r}, "
+            f"operator={self._operator!r}, state={self._state})>"
+        )
+
+
+class LogList:
+    def __init__(self, logs: typing.Iterable[Log]) -> None:
+        self._logs: dict[bytes, Log] = {}
+        for log in logs:
+            if log.log_id in self._logs:
+                raise ValueError(
+                    f"Duplicate log ID in log list: {log.description}"
+                )
+            self._logs[log.log_id] = log
+
+    @property
+    def logs(self) -> list[Log]:
+        return list(self._logs.values())
+
+    def get(self, log_id: bytes) -> Log | None:
+        return self._logs.get(log_id)
+
+    def __len__(self) -> int:
+        return len(self._logs)
+
+    def __iter__(self) -> typing.Iterator[Log]:
+        return iter(self._logs.values())
+
+
+def _as_utc(dt: datetime.datetime) -> datetime.datetime:
+    if dt.tzinfo is None:
+        return dt.replace(tzinfo=datetime.timezone.utc)
+    return dt.astimezone(datetime.timezone.utc)
+
+
+def _parse_timestamp(value: str) -> datetime.datetime:
+    if value.endswith("Z"):
+        value = value[:-1] + "+00:00"
+    return _as_utc(datetime.datetime.fromisoformat(value))
+
+
+def _parse_log(operator: str, data: typing.Any) -> Log:
+    log_id = base64.b64decode(data["log_id"], validate=True)
+    key_der = base64.b64decode(data["key"], validate=True)
+    h = hashes.Hash(hashes.SHA256())
+    h.update(key_der)
+    if h.finalize() != log_id:
+        raise ValueError(
+            f"Log ID does not match the log's key: {data['description']}"
+        )
+    public_key = serialization.load_der_public_key(key_der)
+    if not isinstance(
+        public_key, (ec.EllipticCurvePublicKey, rsa.RSAPublicKey)
+    ):
+        raise ValueError(
+            f"Log key must be an EC or RSA key: {data['description']}"
+        )
+
+    state = None
+    state_timestamp = None
+    states = data.get("state")
+    if states is not None:
+        if len(states) != 1:
+            raise ValueError(
+                f"Log must have exactly one state: {data['description']}"
+            )
+        [(name, value)] = states.items()
+        state = LogState(name)
+        state_timestamp = _parse_timestamp(value["timestamp"])
+
+    return Log(
+        log_id=log_id,
+        public_key=public_key,
+        description=data["description"],
+        operator=operator,
+        # Tiled (static CT API) logs have separate submission and monitoring
+        # URLs, rather than a single one.
+        url=data.get("url", data.get("submission_url", "")),
+        mmd=data.get("mmd", 0),
+        state=state,
+        state_timestamp=state_timestamp,
+    )
+
+
+def load_log_list(data: bytes) -> LogList:
+    """
+    Loads a CT log list in the JSON format published by Chrome and Apple.
+    """
+    try:
+        parsed = json.loads(data)
+        logs = []
+        for operator in parsed["operators"]:
+            for log in operator.get("logs", []) + operator.get(
+                "tiled_logs", []
+            ):
+                logs.append(_parse_log(operator["name"], log))
+    except (KeyError, TypeError, AttributeError) as e:
+        raise ValueError(f"Invalid CT log list: {e!r}") from e
+    return LogList(logs)
+
+
+class SCTPolicy:
+    def __init__(
+        self, log_list: LogList, min_logs: int, min_operators: int = 1
+    ) -> None:
+        if min_logs < 1:
+            raise ValueError("min_logs must be at least 1")
+        if min_operators < 1 or min_operators > min_logs:
+            raise ValueError("min_operators must be between 1 and min_logs")
+
+        self._log_list = log_list
+        self._min_logs = min_logs
+        self._min_operators = min_operators
+
+    @property
+    def log_list(self) -> LogList:
+        return self._log_list
+
+    @property
+    def min_logs(self) -> int:
+        return self._min_logs
+
+    @property
+    def min_operators(self) -> int:
+        return self._min_operators
+
+    def verify(
+        self,
+        certificate: Certificate,
+        issuer: Certificate,
+        validation_time: datetime.datetime | None = None,
+    ) -> list[SignedCertificateTimestamp]:
+        """
+        Checks the SCTs embedded in ``certificate`` against this policy,
+        returning one qualifying SCT per distinct log.
+        """
+        if validation_time is None:
+            validation_time = datetime.datetime.now(datetime.timezone.utc)
+        validation_time = _as_utc(validation_time)
+
+        scts: typing.Iterable[SignedCertificateTimestamp] = []
+        for ext in certificate.extensions:
+            if ext.oid == ExtensionOID.PRECERT_SIGNED_CERTIFICATE_TIMESTAMPS:
+                scts = ext.value  # type: ignore[assignment]
+
+        qualifying: dict[bytes, SignedCertificateTimestamp] = {}
+        operators = set()
+        for sct in scts:
+            log = self._log_list.get(sct.log_id)
+            if log is None or sct.log_id in qualifying:
+                continue
+            if _as_utc(sct.timestamp) > validation_time:
+                continue
+            if not log.is_qualifying(sct.timestamp):
+                continue
+            try:
+                sct.verify(log.public_key, certificate, issuer)
+            except (InvalidSignature, ValueError):
+                continue
+            qualifying[sct.log_id] = sct
+            operators.add(log.operator)
+
+        if (
+            len(qualifying) < self._min_logs
+            or len(operators) < self._min_operators
+        ):
+            raise ValueError(
+                f"Certificate has SCTs from {len(qualifying)} qualifying "
+                f"logs and {len(operators)} operators, policy requires "
+                f"{self._min_logs} logs and {self._min_operators} operators"
+            )
+        return list(qualifying.values())
@@ -2141,6 +2141,587 @@ class MSCertificateTemplate(ExtensionType):
         return rust_x509.encode_extension_value(self)
 
 
+class AndroidSecurityLevel(utils.Enum):
+    SOFTWARE = 0
+    TRUSTED_ENVIRONMENT = 1
+    STRONG_BOX = 2
+
+
+class AndroidVerifiedBootState(utils.Enum):
+    VERIFIED = 0
+    SELF_SIGNED = 1
+    UNVERIFIED = 2
+    FAILED = 3
+
+
+class AndroidRootOfTrust:
+    def __init__(
+        self,
+        verified_boot_key: bytes,
+        device_locked: bool,
+        verified_boot_state: AndroidVerifiedBootState,
+        verified_boot_hash: bytes | None,
+    ) -> None:
+        if not isinstance(verified_boot_key, bytes):
+            raise TypeError("verified_boot_key must be bytes")
+        if not isinstance(device_locked, bool):
+            raise TypeError("device_locked must be a boolean")
+        if not isinstance(verified_boot_state, AndroidVerifiedBootState):
+            raise TypeError(
+                "verified_boot_state must be an AndroidVerifiedBootState"
+            )
+        if verified_boot_hash is not None and not isinstance(
+            verified_boot_hash, bytes
+        ):
+            raise TypeError("verified_boot_hash must be bytes or None")
+
+        self._verified_boot_key = verified_boot_key
+        self._device_locked = device_locked
+        self._verified_boot_state = verified_boot_state
+        self._verified_boot_hash = verified_boot_hash
+
+    @property
+    def verified_boot_key(self) -> bytes:
+        return self._verified_boot_key
+
+    @property
+    def device_locked(self) -> bool:
+        return self._device_locked
+
+    @property
+    def verified_boot_state(self) -> AndroidVerifiedBootState:
+        return self._verified_boot_state
+
+    @property
+    def verified_boot_hash(self) -> bytes | None:
+        return self._verified_boot_hash
+
+    def __repr__(self) -> str:
+        return (
+            f"<AndroidRootOfTrust(device_locked={self.device_locked}, "
+            f"verified_boot_state={self.verified_boot_state})>"
+        )
+
+    def __eq__(self, other: object) -> bool:
+        if not isinstance(other, AndroidRootOfTrust):
+            return NotImplemented
+
+        return (
+            self.verified_boot_key == other.verified_boot_key
+            and self.device_locked == other.device_locked
+            and self.verified_boot_state == other.verified_boot_state
+            and self.verified_boot_hash == other.verified_boot_hash
+        )
+
+    def __hash__(self) -> int:
+        return hash(
+            (
+                self.verified_boot_key,
+                self.device_locked,
+                self.verified_boot_state,
+                self.verified_boot_hash,
+            )
+        )
+
+
+_ANDROID_INTEGER_SET_FIELDS = ("purpose", "digest", "padding", "mgf_digest")
+_ANDROID_INTEGER_FIELDS = (
+    "algorithm",
+    "key_size",
+    "ec_curve",
+    "rsa_public_exponent",
+    "active_date_time",
+    "origination_expire_date_time",
+    "usage_expire_date_time",
+    "usage_count_limit",
+    "user_auth_type",
+    "auth_timeout",
+    "creation_date_time",
+    "origin",
+    "os_version",
+    "os_patch_level",
+    "vendor_patch_level",
+    "boot_patch_level",
+)
+_ANDROID_FLAG_FIELDS = (
+    "rollback_resistance",
+    "early_boot_only",
+    "no_auth_required",
+    "allow_while_on_body",
+    "trusted_user_presence_required",
+    "trusted_confirmation_required",
+    "unlocked_device_required",
+    "device_unique_attestation",
+)
+_ANDROID_BYTES_FIELDS = (
+    "attestation_application_id",
+    "attestation_id_brand",
+    "attestation_id_device",
+    "attestation_id_product",
+    "attestation_id_serial",
+    "attestation_id_imei",
+    "attestation_id_meid",
+    "attestation_id_manufacturer",
+    "attestation_id_model",
+    "attestation_id_second_imei",
+    "module_hash",
+)
+
+
+class AndroidAuthorizationList:
+    def __init__(
+        self,
+        *,
+        purpose: typing.Iterable[int] | None = None,
+        algorithm: int | None = None,
+        key_size: int | None = None,
+        digest: typing.Iterable[int] | None = None,
+        padding: typing.Iterable[int] | None = None,
+        ec_curve: int | None = None,
+        rsa_public_exponent: int | None = None,
+        mgf_digest: typing.Iterable[int] | None = None,
+        rollback_resistance: bool = False,
+        early_boot_only: bool = False,
+        active_date_time: int | None = None,
+        origination_expire_date_time: int | None = None,
+        usage_expire_date_time: int | None = None,
+        usage_count_limit: int | None = None,
+        no_auth_required: bool = False,
+        user_auth_type: int | None = None,
+        auth_timeout: int | None = None,
+        allow_while_on_body: bool = False,
+        trusted_user_presence_required: bool = False,
+        trusted_confirmation_required: bool = False,
+        unlocked_device_required: bool = False,
+        creation_date_time: int | None = None,
+        origin: int | None = None,
+        root_of_trust: AndroidRootOfTrust | None = None,
+        os_version: int | None = None,
+        os_patch_level: int | None = None,
+        attestation_application_id: bytes | None = None,
+        attestation_id_brand: bytes | None = None,
+        attestation_id_device: bytes | None = None,
+        attestation_id_product: bytes | None = None,
+        attestation_id_serial: bytes | None = None,
+        attestation_id_imei: bytes | None = None,
+        attestation_id_meid: bytes | None = None,
+        attestation_id_manufacturer: bytes | None = None,
+        attestation_id_model: bytes | None = None,
+        vendor_patch_level: int | None = None,
+        boot_patch_level: int | None = None,
+        device_unique_attestation: bool = False,
+        attestation_id_second_imei: bytes | None = None,
+        module_hash: bytes | None = None,
+    ) -> None:
+        fields = locals()
+        sets: dict[str, frozenset[int] | None] = {}
+        for name in _ANDROID_INTEGER_SET_FIELDS:
+            value = fields[name]
+            if value is not None:
+                value = frozenset(value)
+                if not all(isinstance(x, int) for x in value):
+                    raise TypeError(f"{name} must only contain integers")
+            sets[name] = value
+        for name in _ANDROID_INTEGER_FIELDS:
+            if fields[name] is not None and not isinstance(fields[name], int):
+                raise TypeError(f"{name} must be an integer or None")
+        for name in _ANDROID_FLAG_FIELDS:
+            if not isinstance(fields[name], bool):
+                raise TypeError(f"{name} must be a boolean")
+        for name in _ANDROID_BYTES_FIELDS:
+            if fields[name] is not None and not isinstance(
+                fields[name], bytes
+            ):
+                raise TypeError(f"{name} must be bytes or None")
+        if root_of_trust is not None and not isinstance(
+            root_of_trust, AndroidRootOfTrust
+        ):
+            raise TypeError("root_of_trust must be an AndroidRootOfTrust")
+
+        self._purpose = sets["purpose"]
+        self._algorithm = algorithm
+        self._key_size = key_size
+        self._digest = sets["digest"]
+        self._padding = sets["padding"]
+        self._ec_curve = ec_curve
+        self._rsa_public_exponent = rsa_public_exponent
+        self._mgf_digest = sets["mgf_digest"]
+        self._rollback_resistance = rollback_resistance
+        self._early_boot_only = early_boot_only
+        self._active_date_time = active_date_time
+        self._origination_expire_date_time = origination_expire_date_time
+        self._usage_expire_date_time = usage_expire_date_time
+        self._usage_count_limit = usage_count_limit
+        self._no_auth_required = no_auth_required
+        self._user_auth_type = user_auth_type
+        self._auth_timeout = auth_timeout
+        self._allow_while_on_body = allow_while_on_body
+        self._trusted_user_presence_required = trusted_user_presence_required
+        self._trusted_confirmation_required = trusted_confirmation_required
+        self._unlocked_device_required = unlocked_device_required
+        self._creation_date_time = creation_date_time
+        self._origin = origin
+        self._root_of_trust = root_of_trust
+        self._os_version = os_version
+        self._os_patch_level = os_patch_level
+        self._attestation_application_id = attestation_application_id
+        self._attestation_id_brand = attestation_id_brand
+        self._attestation_id_device = attestation_id_device
+        self._attestation_id_product = attestation_id_product
+        self._attestation_id_serial = attestation_id_serial
+        self._attestation_id_imei = attestation_id_imei
+        self._attestation_id_meid = attestation_id_meid
+        self._attestation_id_manufacturer = attestation_id_manufacturer
+        self._attestation_id_model = attestation_id_model
+        self._vendor_patch_level = vendor_patch_level
+        self._boot_patch_level = boot_patch_level
+        self._device_unique_attestation = device_unique_attestation
+        self._attestation_id_second_imei = attestation_id_second_imei
+        self._module_hash = module_hash
+
+    @property
+    def purpose(self) -> frozenset[int] | None:
+        return self._purpose
+
+    @property
+    def algorithm(self) -> int | None:
+        return self._algorithm
+
+    @property
+    def key_size(self) -> int | None:
+        return self._key_size
+
+    @property
+    def digest(self) -> frozenset[int] | None:
+        return self._digest
+
+    @property
+    def padding(self) -> frozenset[int] | None:
+        return self._padding
+
+    @property
+    def ec_curve(self) -> int | None:
+        return self._ec_curve
+
+    @property
+    def rsa_public_exponent(self) -> int | None:
+        return self._rsa_public_exponent
+
+    @property
+    def mgf_digest(self) -> frozenset[int] | None:
+        return self._mgf_digest
+
+    @property
+    def rollback_resistance(self) -> bool:
+        return self._rollback_resistance
+
+    @property
+    def early_boot_only(self) -> bool:
+        return self._early_boot_only
+
+    @property
+    def active_date_time(self) -> int | None:
+        return self._active_date_time
+
+    @property
+    def origination_expire_date_time(self) -> int | None:
+        return self._origination_expire_date_time
+
+    @property
+    def usage_expire_date_time(self) -> int | None:
+        return self._usage_expire_date_time
+
+    @property
+    def usage_count_limit(self) -> int | None:
+        return self._usage_count_limit
+
+    @property
+    def no_auth_required(self) -> bool:
+        return self._no_auth_required
+
+    @property
+    def user_auth_type(self) -> int | None:
+        return self._user_auth_type
+
+    @property
+    def auth_timeout(self) -> int | None:
+        return self._auth_timeout
+
+    @property
+    def allow_while_on_body(self) -> bool:
+        return self._allow_while_on_body
+
+    @property
+    def trusted_user_presence_required(self) -> bool:
+        return self._trusted_user_presence_required
+
+    @property
+    def trusted_confirmation_required(self) -> bool:
+        return self._trusted_confirmation_required
+
+    @property
+    def unlocked_device_required(self) -> bool:
+        return self._unlocked_device_required
+
+    @property
+    def creation_date_time(self) -> int | None:
+        return self._creation_date_time
+
+    @property
+    def origin(self) -> int | None:
+        return self._origin
+
+    @property
+    def root_of_trust(self) -> AndroidRootOfTrust | None:
+        return self._root_of_trust
+
+    @property
+    def os_version(self) -> int | None:
+        return self._os_version
+
+    @property
+    def os_patch_level(self) -> int | None:
+        return self._os_patch_level
+
+    @property
+    def attestation_application_id(self) -> bytes | None:
+        return self._attestation_application_id
+
+    @property
+    def attestation_id_brand(self) -> bytes | None:
+        return self._attestation_id_brand
+
+    @property
+    def attestation_id_device(self) -> bytes | None:
+        return self._attestation_id_device
+
+    @property
+    def attestation_id_product(self) -> bytes | None:
+        return self._attestation_id_product
+
+    @property
+    def attestation_id_serial(self) -> bytes | None:
+        return self._attestation_id_serial
+
+    @property
+    def attestation_id_imei(self) -> bytes | None:
+        return self._attestation_id_imei
+
+    @property
+    def attestation_id_meid(self) -> bytes | None:
+        return self._attestation_id_meid
+
+    @property
+    def attestation_id_manufacturer(self) -> bytes | None:
+        return self._attestation_id_manufacturer
+
+    @property
+    def attestation_id_model(self) -> bytes | None:
+        return self._attestation_id_model
+
+    @property
+    def vendor_patch_level(self) -> int | None:
+        return self._vendor_patch_level
+
+    @property
+    def boot_patch_level(self) -> int | None:
+        return self._boot_patch_level
+
+    @property
+    def device_unique_attestation(self) -> bool:
+        return self._device_unique_attestation
+
+    @property
+    def attestation_id_second_imei(self) -> bytes | None:
+        return self._attestation_id_second_imei
+
+    @property
+    def module_hash(self) -> bytes | None:
+        return self._module_hash
+
+    def _fields(self) -> tuple[typing.Any, ...]:
+        return tuple(
+            getattr(self, name)
+            for name in (
+                _ANDROID_INTEGER_SET_FIELDS
+                + _ANDROID_INTEGER_FIELDS
+                + _ANDROID_FLAG_FIELDS
+                + _ANDROID_BYTES_FIELDS
+                + ("root_of_trust",)
+            )
+        )
+
+    def __repr__(self) -> str:
+        return (
+            f"<AndroidAuthorizationList(purpose={self.purpose}, "
+            f"algorithm={self.algorithm}, key_size={self.key_size}, "
+            f"origin={self.origin}, root_of_trust={self.root_of_trust})>"
+        )
+
+    def __eq__(self, other: object) -> bool:
+        if not isinstance(other, AndroidAuthorizationList):
+            return NotImplemented
+
+        return self._fields() == other._fields()
+
+    def __hash__(self) -> int:
+        return hash(self._fields())
+
+
+class AndroidKeyAttestation(ExtensionType):
+    oid = ExtensionOID.ANDROID_KEY_ATTESTATION
+
+    def __init__(
+        self,
+        attestation_version: int,
+        attestation_security_level: AndroidSecurityLevel,
+        keymaster_version: int,
+        keymaster_security_level: AndroidSecurityLevel,
+        attestation_challenge: bytes,
+        unique_id: bytes,
+        software_enforced: AndroidAuthorizationList,
+        hardware_enforced: AndroidAuthorizationList,
+    ) -> None:
+        if not isinstance(attestation_version, int) or not isinstance(
+            keymaster_version, int
+        ):
+            raise TypeError(
+                "attestation_version and keymaster_version must be integers"
+            )
+        if not isinstance(
+            attestation_security_level, AndroidSecurityLevel
+        ) or not isinstance(keymaster_security_level, AndroidSecurityLevel):
+            raise TypeError(
+                "attestation_security_level and keymaster_security_level "
+                "must be AndroidSecurityLevel"
+            )
+        if not isinstance(attestation_challenge, bytes) or not isinstance(
+            unique_id, bytes
+        ):
+            raise TypeError(
+                "attestation_challenge and unique_id must be bytes"
+            )
+        if not isinstance(
+            software_enforced, AndroidAuthorizationList
+        ) or not isinstance(hardware_enforced, AndroidAuthorizationList):
+            raise TypeError(
+                "software_enforced and hardware_enforced must be "
+                "AndroidAuthorizationList"
+            )
+
+        self._attestation_version = attestation_version
+        self._attestation_security_level = attestation_security_level
+        self._keymaster_version = keymaster_version
+        self._keymaster_security_level = keymaster_security_level
+        self._attestation_challenge = attestation_challenge
+        self._unique_id = unique_id
+        self._software_enforced = software_enforced
+        self._hardware_enforced = hardware_enforced
+
+    @property
+    def attestation_version(self) -> int:
+        return self._attestation_version
+
+    @property
+    def attestation_security_level(self) -> AndroidSecurityLevel:
+        return self._attestation_security_level
+
+    @property
+    def keymaster_version(self) -> int:
+        return self._keymaster_version
+
+    @property
+    def keymaster_security_level(self) -> AndroidSecurityLevel:
+        return self._keymaster_security_level
+
+    @property
+    def attestation_challenge(self) -> bytes:
+        return self._attestation_challenge
+
+    @property
+    def unique_id(self) -> bytes:
+        return self._unique_id
+
+    @property
+    def software_enforced(self) -> AndroidAuthorizationList:
+        return self._software_enforced
+
+    @property
+    def hardware_enforced(self) -> AndroidAuthorizationList:
+        return self._hardware_enforced
+
+    def __repr__(self) -> str:
+        return (
+            f"<AndroidKeyAttestation("
+            f"attestation_version={self.attestation_version}, "
+            f"attestation_security_level={self.attestation_security_level}, "
+            f"attestation_challenge={self.attestation_challenge!r})>"
+        )
+
+    def __eq__(self, other: object) -> bool:
+        if not isinstance(other, AndroidKeyAttestation):
+            return NotImplemented
+
+        return (
+            self.attestation_version == other.attestation_version
+            and self.attestation_security_level
+            == other.attestation_security_level
+            and self.keymaster_version == other.keymaster_version
+            and self.keymaster_security_level
+            == other.keymaster_security_level
+            and self.attestation_challenge == other.attestation_challenge
+            and self.unique_id == other.unique_id
+            and self.software_enforced == other.software_enforced
+            and self.hardware_enforced == other.hardware_enforced
+        )
+
+    def __hash__(self) -> int:
+        return hash(
+            (
+                self.attestation_version,
+                self.attestation_security_level,
+                self.keymaster_version,
+                self.keymaster_security_level,
+                self.attestation_challenge,
+                self.unique_id,
+                self.software_enforced,
+                self.hardware_enforced,
+            )
+        )
+
+    def public_bytes(self) -> bytes:
+        return rust_x509.encode_extension_value(self)
+
+
+class AppleAttestationNonce(ExtensionType):
+    oid = ExtensionOID.APPLE_ATTESTATION_NONCE
+
+    def __init__(self, nonce: bytes) -> None:
+        if not isinstance(nonce, bytes):
+            raise TypeError("nonce must be bytes")
+
+        self._nonce = nonce
+
+    @property
+    def nonce(self) -> bytes:
+        return self._nonce
+
+    def __repr__(self) -> str:
+        return f"<AppleAttestationNonce(nonce={self.nonce!r})>"
+
+    def __eq__(self, other: object) -> bool:
+        if not isinstance(other, AppleAttestationNonce):
+            return NotImplemented
+
+        return self.nonce == other.nonce
+
+    def __hash__(self) -> int:
+        return hash(self.nonce)
+
+    def public_bytes(self) -> bytes:
+        return rust_x509.encode_extension_value(self)
+
+
 class UnrecognizedExtension(ExtensionType):
     def __init__(self, oid: ObjectIdentifier, value: bytes) -> None:
         if not isinstance(oid, ObjectIdentifier):
@@ -11,6 +11,8 @@ import typing
 from cryptography import utils, x509
 from cryptography.hazmat.bindings._rust import ocsp
 from cryptography.hazmat.primitives import hashes, serialization
+from cryptography.hazmat.primitives.asymmetric import ec, padding, rsa
+from cryptography.hazmat.primitives.asymmetric.signer import ExternalSigner
 from cryptography.hazmat.primitives.asymmetric.types import (
     CertificateIssuerPrivateKeyTypes,
 )
@@ -271,6 +273,15 @@ class OCSPResponse(metaclass=abc.ABCMeta):
         Returns a HashAlgorithm corresponding to the type of the digest signed
         """
 
+    @property
+    @abc.abstractmethod
+    def signature_algorithm_parameters(
+        self,
+    ) -> None | padding.PSS | padding.PKCS1v15 | ec.ECDSA:
+        """
+        Returns the signature algorithm parameters.
+        """
+
     @property
     @abc.abstractmethod
     def signature(self) -> bytes:
@@ -585,16 +596,28 @@ class OCSPResponseBuilder:
 
     def sign(
         self,
-        private_key: CertificateIssuerPrivateKeyTypes,
+        private_key: CertificateIssuerPrivateKeyTypes | ExternalSigner,
         algorithm: hashes.HashAlgorithm | None,
+        *,
+        rsa_padding: padding.PSS | padding.PKCS1v15 | None = None,
     ) -> OCSPResponse:
         if self._response is None:
             raise ValueError("You must add a response before signing")
         if self._responder_id is None:
             raise ValueError("You must add a responder_id before signing")
 
+        if rsa_padding is not None:
+            if not isinstance(rsa_padding, (padding.PSS, padding.PKCS1v15)):
+                raise TypeError("Padding must be PSS or PKCS1v15")
+            if not isinstance(private_key, rsa.RSAPrivateKey):
+                raise TypeError("Padding is only supported for RSA keys")
+
         return ocsp.create_ocsp_response(
-            OCSPResponseStatus.SUCCESSFUL, self, private_key, algorithm
+            OCSPResponseStatus.SUCCESSFUL,
+            self,
+            private_key,
+            algorithm,
+            rsa_padding,
         )
 
     @classmethod
@@ -608,7 +631,9 @@ class OCSPResponseBuilder:
         if response_status is OCSPResponseStatus.SUCCESSFUL:
             raise ValueError("response_status cannot be SUCCESSFUL")
 
-        return ocsp.create_ocsp_response(response_status, None, None, None)
+        return ocsp.create_ocsp_response(
+            response_status, None, None, None, None
+        )
 
 
 load_der_ocsp_request = ocsp.load_der_ocsp_request
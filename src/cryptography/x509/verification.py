@@ -15,6 +15,7 @@ __all__ = [
     "ServerVerifier",
     "PolicyBuilder",
     "VerificationError",
+    "subject_matches",
 ]
 
 Store = rust_x509.Store
@@ -22,3 +23,4 @@ Subject = typing.Union[DNSName, IPAddress]
 ServerVerifier = rust_x509.ServerVerifier
 PolicyBuilder = rust_x509.PolicyBuilder
 VerificationError = rust_x509.VerificationError
+subject_matches = rust_x509.subject_matches
@@ -0,0 +1,132 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+use cryptography_x509::common::EcParameters;
+
+use crate::{KeyParsingError, KeyParsingResult};
+
+/// Returns the group for EC domain parameters, which must name a curve.
+pub fn group_from_parameters(params: &EcParameters<'_>) -> KeyParsingResult<openssl::ec::EcGroup> {
+    let curve_oid = match params {
+        EcParameters::NamedCurve(curve_oid) => curve_oid.clone(),
+        EcParameters::ImplicitCurve(_) | EcParameters::SpecifiedCurve(_) => {
+            return Err(KeyParsingError::ExplicitCurveUnsupported)
+        }
+    };
+
+    let curve_nid = match curve_oid {
+        cryptography_x509::oid::EC_SECP192R1 => openssl::nid::Nid::X9_62_PRIME192V1,
+        cryptography_x509::oid::EC_SECP224R1 => openssl::nid::Nid::SECP224R1,
+        cryptography_x509::oid::EC_SECP256R1 => openssl::nid::Nid::X9_62_PRIME256V1,
+        cryptography_x509::oid::EC_SECP384R1 => openssl::nid::Nid::SECP384R1,
+        cryptography_x509::oid::EC_SECP521R1 => openssl::nid::Nid::SECP521R1,
+
+        cryptography_x509::oid::EC_SECP256K1 => openssl::nid::Nid::SECP256K1,
+
+        cryptography_x509::oid::EC_SECT233R1 => openssl::nid::Nid::SECT233R1,
+        cryptography_x509::oid::EC_SECT283R1 => openssl::nid::Nid::SECT283R1,
+        cryptography_x509::oid::EC_SECT409R1 => openssl::nid::Nid::SECT409R1,
+        cryptography_x509::oid::EC_SECT571R1 => openssl::nid::Nid::SECT571R1,
+
+        cryptography_x509::oid::EC_SECT163R2 => openssl::nid::Nid::SECT163R2,
+
+        cryptography_x509::oid::EC_SECT163K1 => openssl::nid::Nid::SECT163K1,
+        cryptography_x509::oid::EC_SECT233K1 => openssl::nid::Nid::SECT233K1,
+        cryptography_x509::oid::EC_SECT283K1 => openssl::nid::Nid::SECT283K1,
+        cryptography_x509::oid::EC_SECT409K1 => openssl::nid::Nid::SECT409K1,
+        cryptography_x509::oid::EC_SECT571K1 => openssl::nid::Nid::SECT571K1,
+
+        #[cfg(not(CRYPTOGRAPHY_IS_BORINGSSL))]
+        cryptography_x509::oid::EC_BRAINPOOLP256R1 => openssl::nid::Nid::BRAINPOOL_P256R1,
+        #[cfg(not(CRYPTOGRAPHY_IS_BORINGSSL))]
+        cryptography_x509::oid::EC_BRAINPOOLP384R1 => openssl::nid::Nid::BRAINPOOL_P384R1,
+        #[cfg(not(CRYPTOGRAPHY_IS_BORINGSSL))]
+        cryptography_x509::oid::EC_BRAINPOOLP512R1 => openssl::nid::Nid::BRAINPOOL_P512R1,
+
+        #[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
+        cryptography_x509::oid::EC_SM2 => openssl::nid::Nid::SM2,
+
+        _ => {
+            // These curves' NIDs differ between OpenSSL forks, or
+            // they're only available in some builds.
+            let short_name = match curve_oid {
+                cryptography_x509::oid::EC_SECP224K1 => "secp224k1",
+                cryptography_x509::oid::EC_BRAINPOOLP256T1 => "brainpoolP256t1",
+                cryptography_x509::oid::EC_BRAINPOOLP384T1 => "brainpoolP384t1",
+                cryptography_x509::oid::EC_BRAINPOOLP512T1 => "brainpoolP512t1",
+                cryptography_x509::oid::EC_FRP256V1 => "FRP256v1",
+                _ => return Err(KeyParsingError::UnsupportedEllipticCurve(curve_oid)),
+            };
+            match cryptography_openssl::nid::from_short_name(short_name) {
+                Some(nid) => nid,
+                None => return Err(KeyParsingError::UnsupportedEllipticCurve(curve_oid)),
+            }
+        }
+    };
+
+    openssl::ec::EcGroup::from_curve_name(curve_nid)
+        .map_err(|_| KeyParsingError::UnsupportedEllipticCurve(curve_oid))
+}
+
+/// Returns the OID naming a group's curve, or `None` if it isn't a named
+/// curve with an OID.
+pub fn curve_oid(group: &openssl::ec::EcGroupRef) -> Option<asn1::ObjectIdentifier> {
+    // Curves are matched by OpenSSL's short name, rather than by NID, since
+    // some of their NIDs differ between OpenSSL forks.
+    let oid = match group.curve_name()?.short_name().ok()? {
+        "prime192v1" => cryptography_x509::oid::EC_SECP192R1,
+        "secp224r1" => cryptography_x509::oid::EC_SECP224R1,
+        "prime256v1" => cryptography_x509::oid::EC_SECP256R1,
+        "secp384r1" => cryptography_x509::oid::EC_SECP384R1,
+        "secp521r1" => cryptography_x509::oid::EC_SECP521R1,
+
+        "secp224k1" => cryptography_x509::oid::EC_SECP224K1,
+        "secp256k1" => cryptography_x509::oid::EC_SECP256K1,
+
+        "sect233r1" => cryptography_x509::oid::EC_SECT233R1,
+        "sect283r1" => cryptography_x509::oid::EC_SECT283R1,
+        "sect409r1" => cryptography_x509::oid::EC_SECT409R1,
+        "sect571r1" => cryptography_x509::oid::EC_SECT571R1,
+
+        "sect163r2" => cryptography_x509::oid::EC_SECT163R2,
+
+        "sect163k1" => cryptography_x509::oid::EC_SECT163K1,
+        "sect233k1" => cryptography_x509::oid::EC_SECT233K1,
+        "sect283k1" => cryptography_x509::oid::EC_SECT283K1,
+        "sect409k1" => cryptography_x509::oid::EC_SECT409K1,
+        "sect571k1" => cryptography_x509::oid::EC_SECT571K1,
+
+        "brainpoolP256r1" => cryptography_x509::oid::EC_BRAINPOOLP256R1,
+        "brainpoolP384r1" => cryptography_x509::oid::EC_BRAINPOOLP384R1,
+        "brainpoolP512r1" => cryptography_x509::oid::EC_BRAINPOOLP512R1,
+        "brainpoolP256t1" => cryptography_x509::oid::EC_BRAINPOOLP256T1,
+        "brainpoolP384t1" => cryptography_x509::oid::EC_BRAINPOOLP384T1,
+        "brainpoolP512t1" => cryptography_x509::oid::EC_BRAINPOOLP512T1,
+
+        "FRP256v1" => cryptography_x509::oid::EC_FRP256V1,
+        "SM2" => cryptography_x509::oid::EC_SM2,
+
+        _ => return None,
+    };
+    Some(oid)
+}
+
+#[cfg(test)]
+mod tests {
+    use cryptography_x509::common::EcParameters;
+
+    use super::{curve_oid, group_from_parameters};
+
+    #[test]
+    fn test_curve_oid_round_trip() {
+        for oid in [
+            cryptography_x509::oid::EC_SECP256R1,
+            cryptography_x509::oid::EC_SECP384R1,
+            cryptography_x509::oid::EC_SECP256K1,
+        ] {
+            let group = group_from_parameters(&EcParameters::NamedCurve(oid.clone())).ok();
+            assert_eq!(group.and_then(|g| curve_oid(&g)), Some(oid));
+        }
+    }
+}
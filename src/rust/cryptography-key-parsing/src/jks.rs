@@ -0,0 +1,306 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Parsing for Java's JKS and JCEKS keystores. Private key and trusted
+//! certificate entries are supported. JCEKS secret key entries are serialized
+//! Java objects, so keystores containing them can't be parsed.
+
+use cryptography_x509::common::{AlgorithmParameters, PbeParams};
+use cryptography_x509::pkcs8::EncryptedPrivateKeyInfo;
+use openssl::hash::MessageDigest;
+
+use crate::pbe;
+use crate::{KeyParsingError, KeyParsingResult};
+
+const JKS_MAGIC: u32 = 0xfeed_feed;
+const JCEKS_MAGIC: u32 = 0xcece_cece;
+
+const PRIVATE_KEY_TAG: u32 = 1;
+const TRUSTED_CERT_TAG: u32 = 2;
+
+const X509_CERT_TYPE: &str = "X.509";
+
+// The keystore integrity check is a SHA-1 digest of the password, this
+// string, and the rest of the keystore.
+const MAC_WHITENER: &[u8] = b"Mighty Aphrodite";
+
+const SHA1_LEN: usize = 20;
+const DES_BLOCK_LEN: usize = 8;
+
+pub struct JksPrivateKey {
+    pub alias: String,
+    /// A DER encoded PKCS#8 PrivateKeyInfo.
+    pub der: Vec<u8>,
+    /// DER encoded certificates, starting with the key's own certificate.
+    pub cert_chain: Vec<Vec<u8>>,
+}
+
+pub struct JksTrustedCert {
+    pub alias: String,
+    /// A DER encoded certificate.
+    pub der: Vec<u8>,
+}
+
+#[derive(Default)]
+pub struct ParsedJks {
+    pub keys: Vec<JksPrivateKey>,
+    pub certs: Vec<JksTrustedCert>,
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn read_bytes(&mut self, n: usize) -> KeyParsingResult<&'a [u8]> {
+        if self.data.len() < n {
+            return Err(KeyParsingError::InvalidKey);
+        }
+        let (value, rest) = self.data.split_at(n);
+        self.data = rest;
+        Ok(value)
+    }
+
+    fn read_u16(&mut self) -> KeyParsingResult<u16> {
+        let b = self.read_bytes(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn read_u32(&mut self) -> KeyParsingResult<u32> {
+        let b = self.read_bytes(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn read_u64(&mut self) -> KeyParsingResult<u64> {
+        let b = self.read_bytes(8)?;
+        let mut value = [0; 8];
+        value.copy_from_slice(b);
+        Ok(u64::from_be_bytes(value))
+    }
+
+    fn read_u32_prefixed(&mut self) -> KeyParsingResult<&'a [u8]> {
+        let n = self.read_u32()?;
+        self.read_bytes(n as usize)
+    }
+
+    // A string as written by Java's `DataOutput.writeUTF`.
+    fn read_utf(&mut self) -> KeyParsingResult<String> {
+        let n = self.read_u16()?;
+        decode_modified_utf8(self.read_bytes(n.into())?).ok_or(KeyParsingError::InvalidKey)
+    }
+
+    fn read_cert(&mut self, version: u32) -> KeyParsingResult<Vec<u8>> {
+        // Version 1 keystores could only contain X.509 certificates.
+        if version == 2 && self.read_utf()? != X509_CERT_TYPE {
+            return Err(KeyParsingError::InvalidKey);
+        }
+        Ok(self.read_u32_prefixed()?.to_vec())
+    }
+}
+
+// Java's modified UTF-8 encodes NUL as two bytes, and characters outside the
+// BMP as a surrogate pair of three bytes each, so it's decoded to UTF-16.
+fn decode_modified_utf8(data: &[u8]) -> Option<String> {
+    let mut units = vec![];
+    let mut i = 0;
+    while i < data.len() {
+        let b = u16::from(data[i]);
+        let continuation = |j: usize| -> Option<u16> {
+            data.get(i + j)
+                .filter(|c| *c & 0xc0 == 0x80)
+                .map(|c| u16::from(*c & 0x3f))
+        };
+        let (unit, n) = match data[i] {
+            0x01..=0x7f => (b, 1),
+            0xc0..=0xdf => ((b & 0x1f) << 6 | continuation(1)?, 2),
+            0xe0..=0xef => (
+                (b & 0x0f) << 12 | continuation(1)? << 6 | continuation(2)?,
+                3,
+            ),
+            _ => return None,
+        };
+        units.push(unit);
+        i += n;
+    }
+    char::decode_utf16(units).collect::<Result<_, _>>().ok()
+}
+
+// Java passwords are arrays of UTF-16 code units, which the JKS formats hash
+// as big endian bytes.
+fn java_password(password: &[u8]) -> Vec<u8> {
+    let mut result = pbe::bmp_password(password);
+    result.truncate(result.len() - 2);
+    result
+}
+
+fn digest(md: MessageDigest, parts: &[&[u8]]) -> KeyParsingResult<Vec<u8>> {
+    let mut h = openssl::hash::Hasher::new(md)?;
+    for part in parts {
+        h.update(part)?;
+    }
+    Ok(h.finish()?.to_vec())
+}
+
+// Sun's proprietary JKS key protection: the key is XORed with a keystream of
+// chained SHA-1 digests, starting from a random salt, and followed by a SHA-1
+// digest of the password and the key.
+fn jks_unprotect(java_password: &[u8], data: &[u8]) -> KeyParsingResult<Vec<u8>> {
+    if data.len() < 2 * SHA1_LEN {
+        return Err(KeyParsingError::InvalidKey);
+    }
+    let (salt, rest) = data.split_at(SHA1_LEN);
+    let (ciphertext, check) = rest.split_at(rest.len() - SHA1_LEN);
+
+    let mut plaintext = Vec::with_capacity(ciphertext.len());
+    let mut keystream = salt.to_vec();
+    for chunk in ciphertext.chunks(SHA1_LEN) {
+        keystream = digest(MessageDigest::sha1(), &[java_password, &keystream])?;
+        plaintext.extend(chunk.iter().zip(&keystream).map(|(c, k)| c ^ k));
+    }
+
+    let expected = digest(MessageDigest::sha1(), &[java_password, &plaintext])?;
+    if !openssl::memcmp::eq(&expected, check) {
+        return Err(KeyParsingError::IncorrectPassword);
+    }
+    Ok(plaintext)
+}
+
+// SunJCE's proprietary PBEWithMD5AndTripleDES, used by JCEKS. Each half of
+// the salt is hashed with the password to derive half of the key and IV.
+// Passwords are restricted to ASCII, so are used as is.
+fn jceks_unprotect(
+    password: &[u8],
+    params: &PbeParams<'_>,
+    data: &[u8],
+) -> KeyParsingResult<Vec<u8>> {
+    if params.salt.len() != 8 || params.iterations == 0 {
+        return Err(KeyParsingError::InvalidKey);
+    }
+    if data.is_empty() || data.len() % DES_BLOCK_LEN != 0 {
+        return Err(KeyParsingError::InvalidKey);
+    }
+
+    let (mut first, second) = params.salt.split_at(4);
+    // Identical halves would derive identical key parts, so Java mixes up
+    // the first one.
+    let mixed;
+    if first == second {
+        mixed = [first[3], first[0], first[1], first[3]];
+        first = &mixed;
+    }
+    let mut derived = vec![];
+    for half in [first, second] {
+        let mut h = half.to_vec();
+        for _ in 0..params.iterations {
+            h = digest(MessageDigest::md5(), &[&h, password])?;
+        }
+        derived.extend_from_slice(&h);
+    }
+    let (key, iv) = derived.split_at(24);
+
+    // A wrong password almost always shows up as invalid padding.
+    openssl::symm::decrypt(openssl::symm::Cipher::des_ede3_cbc(), key, Some(iv), data)
+        .map_err(|_| KeyParsingError::IncorrectPassword)
+}
+
+fn decrypt_key(password: &[u8], data: &[u8]) -> KeyParsingResult<Vec<u8>> {
+    let info = asn1::parse_single::<EncryptedPrivateKeyInfo<'_>>(data)?;
+    match &info.encryption_algorithm.params {
+        AlgorithmParameters::JksKeyProtector(_) => {
+            jks_unprotect(&java_password(password), info.encrypted_data)
+        }
+        AlgorithmParameters::PbeWithMd5AndTripleDes(params) => {
+            jceks_unprotect(password, params, info.encrypted_data)
+        }
+        _ => Err(KeyParsingError::UnsupportedEncryptionAlgorithm(
+            info.encryption_algorithm.oid().clone(),
+        )),
+    }
+}
+
+/// Parses a JKS or JCEKS keystore, verifying its integrity with `password`
+/// and decrypting its private keys with `key_password`.
+pub fn parse_jks(data: &[u8], password: &[u8], key_password: &[u8]) -> KeyParsingResult<ParsedJks> {
+    if data.len() < SHA1_LEN {
+        return Err(KeyParsingError::InvalidKey);
+    }
+    let (contents, mac) = data.split_at(data.len() - SHA1_LEN);
+    let mut reader = Reader { data: contents };
+    let magic = reader.read_u32()?;
+    let version = reader.read_u32()?;
+    if (magic != JKS_MAGIC && magic != JCEKS_MAGIC) || (version != 1 && version != 2) {
+        return Err(KeyParsingError::InvalidKey);
+    }
+
+    let expected = digest(
+        MessageDigest::sha1(),
+        &[&java_password(password), MAC_WHITENER, contents],
+    )?;
+    if !openssl::memcmp::eq(&expected, mac) {
+        return Err(KeyParsingError::IncorrectPassword);
+    }
+
+    let count = reader.read_u32()?;
+    let mut result = ParsedJks::default();
+    for _ in 0..count {
+        let tag = reader.read_u32()?;
+        let alias = reader.read_utf()?;
+        // The creation date, in milliseconds since the epoch.
+        reader.read_u64()?;
+        match tag {
+            PRIVATE_KEY_TAG => {
+                let encrypted = reader.read_u32_prefixed()?;
+                let cert_count = reader.read_u32()?;
+                let cert_chain = (0..cert_count)
+                    .map(|_| reader.read_cert(version))
+                    .collect::<KeyParsingResult<_>>()?;
+                result.keys.push(JksPrivateKey {
+                    alias,
+                    der: decrypt_key(key_password, encrypted)?,
+                    cert_chain,
+                });
+            }
+            TRUSTED_CERT_TAG => result.certs.push(JksTrustedCert {
+                alias,
+                der: reader.read_cert(version)?,
+            }),
+            _ => return Err(KeyParsingError::InvalidKey),
+        }
+    }
+    if !reader.data.is_empty() {
+        return Err(KeyParsingError::InvalidKey);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_modified_utf8, java_password};
+
+    #[test]
+    fn test_decode_modified_utf8() {
+        assert_eq!(decode_modified_utf8(b"alias").as_deref(), Some("alias"));
+        assert_eq!(
+            decode_modified_utf8(b"schl\xc3\xbcssel").as_deref(),
+            Some("schl\u{fc}ssel")
+        );
+        assert_eq!(decode_modified_utf8(b"\xc0\x80").as_deref(), Some("\0"));
+        assert_eq!(
+            decode_modified_utf8(b"\xed\xa0\xbd\xed\xb8\x80").as_deref(),
+            Some("\u{1f600}")
+        );
+        // Unpaired surrogates, truncated sequences, and four byte UTF-8 are
+        // all invalid.
+        assert_eq!(decode_modified_utf8(b"\xed\xa0\xbd"), None);
+        assert_eq!(decode_modified_utf8(b"\xc3"), None);
+        assert_eq!(decode_modified_utf8(b"\xf0\x9f\x98\x80"), None);
+        assert_eq!(decode_modified_utf8(b"\x00"), None);
+    }
+
+    #[test]
+    fn test_java_password() {
+        assert_eq!(java_password(b""), b"");
+        assert_eq!(java_password(b"ab"), [0, b'a', 0, b'b']);
+    }
+}
@@ -2,6 +2,12 @@
 // 2.0, and the BSD License. See the LICENSE file in the root of this repository
 // for complete details.
 
+pub mod ec;
+pub mod jks;
+pub mod pbe;
+pub mod pkcs12;
+pub mod pkcs8;
+mod rc2;
 pub mod rsa;
 pub mod spki;
 
@@ -10,6 +16,8 @@ pub enum KeyParsingError {
     ExplicitCurveUnsupported,
     UnsupportedKeyType(asn1::ObjectIdentifier),
     UnsupportedEllipticCurve(asn1::ObjectIdentifier),
+    UnsupportedEncryptionAlgorithm(asn1::ObjectIdentifier),
+    IncorrectPassword,
     Parse(asn1::ParseError),
     OpenSSL(openssl::error::ErrorStack),
 }
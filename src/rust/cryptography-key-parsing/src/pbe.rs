@@ -0,0 +1,392 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Decryption for the legacy password based encryption schemes from PKCS#5
+//! (PBES1, RFC 8018 Section 6.1) and PKCS#12 (RFC 7292 Appendix C), and for
+//! traditional PEM encryption with the DES and RC2 ciphers. These are only
+//! supported for decryption, so that old keys can be migrated.
+
+use cryptography_x509::common::{AlgorithmIdentifier, AlgorithmParameters, PbeParams};
+use openssl::hash::MessageDigest;
+
+use crate::rc2::{self, Rc2};
+use crate::{KeyParsingError, KeyParsingResult};
+
+enum Kdf {
+    Pbkdf1(MessageDigest),
+    Pkcs12,
+}
+
+enum Cipher {
+    Des,
+    TwoKeyTripleDes,
+    ThreeKeyTripleDes,
+    Rc2 {
+        key_len: usize,
+        effective_bits: usize,
+    },
+}
+
+impl Cipher {
+    fn key_len(&self) -> usize {
+        match self {
+            Cipher::Des => 8,
+            Cipher::TwoKeyTripleDes => 16,
+            Cipher::ThreeKeyTripleDes => 24,
+            Cipher::Rc2 { key_len, .. } => *key_len,
+        }
+    }
+}
+
+const IV_LEN: usize = 8;
+
+fn scheme<'a>(params: &'a AlgorithmParameters<'a>) -> Option<(Kdf, Cipher, &'a PbeParams<'a>)> {
+    let rc2_64 = Cipher::Rc2 {
+        key_len: 8,
+        effective_bits: 64,
+    };
+    Some(match params {
+        AlgorithmParameters::PbeWithMd5AndDesCbc(p) => {
+            (Kdf::Pbkdf1(MessageDigest::md5()), Cipher::Des, p)
+        }
+        AlgorithmParameters::PbeWithSha1AndDesCbc(p) => {
+            (Kdf::Pbkdf1(MessageDigest::sha1()), Cipher::Des, p)
+        }
+        AlgorithmParameters::PbeWithMd5AndRc2Cbc(p) => {
+            (Kdf::Pbkdf1(MessageDigest::md5()), rc2_64, p)
+        }
+        AlgorithmParameters::PbeWithSha1AndRc2Cbc(p) => {
+            (Kdf::Pbkdf1(MessageDigest::sha1()), rc2_64, p)
+        }
+        AlgorithmParameters::PbeWithShaAnd3KeyTripleDesCbc(p) => {
+            (Kdf::Pkcs12, Cipher::ThreeKeyTripleDes, p)
+        }
+        AlgorithmParameters::PbeWithShaAnd2KeyTripleDesCbc(p) => {
+            (Kdf::Pkcs12, Cipher::TwoKeyTripleDes, p)
+        }
+        AlgorithmParameters::PbeWithShaAnd128BitRc2Cbc(p) => (
+            Kdf::Pkcs12,
+            Cipher::Rc2 {
+                key_len: 16,
+                effective_bits: 128,
+            },
+            p,
+        ),
+        AlgorithmParameters::PbeWithShaAnd40BitRc2Cbc(p) => (
+            Kdf::Pkcs12,
+            Cipher::Rc2 {
+                key_len: 5,
+                effective_bits: 40,
+            },
+            p,
+        ),
+        _ => return None,
+    })
+}
+
+/// Returns whether `algorithm` is one of the schemes `decrypt` handles.
+pub fn is_legacy_pbe(algorithm: &AlgorithmIdentifier<'_>) -> bool {
+    scheme(&algorithm.params).is_some()
+}
+
+/// Encodes a password as a NUL terminated BMPString, as the PKCS#12 KDF
+/// requires. Passwords which aren't valid UTF-8 have each byte treated as a
+/// code point, like OpenSSL does.
+pub fn bmp_password(password: &[u8]) -> Vec<u8> {
+    let mut result = match std::str::from_utf8(password) {
+        Ok(s) => s.encode_utf16().flat_map(u16::to_be_bytes).collect(),
+        Err(_) => password.iter().flat_map(|b| [0, *b]).collect::<Vec<u8>>(),
+    };
+    result.extend_from_slice(&[0, 0]);
+    result
+}
+
+/// The PKCS#12 key derivation function, RFC 7292 Appendix B.2. `password`
+/// must already be BMP encoded.
+pub fn pkcs12_kdf(
+    md: MessageDigest,
+    password: &[u8],
+    salt: &[u8],
+    id: u8,
+    iterations: u64,
+    length: usize,
+) -> KeyParsingResult<Vec<u8>> {
+    let u = md.size();
+    let v = md.block_size();
+    let fill = |s: &[u8]| -> Vec<u8> {
+        if s.is_empty() {
+            return vec![];
+        }
+        (0..v * ((s.len() + v - 1) / v))
+            .map(|i| s[i % s.len()])
+            .collect()
+    };
+    let d = vec![id; v];
+    let mut i_buf = fill(salt);
+    i_buf.extend_from_slice(&fill(password));
+
+    let mut result = Vec::with_capacity(length);
+    loop {
+        let mut h = openssl::hash::Hasher::new(md)?;
+        h.update(&d)?;
+        h.update(&i_buf)?;
+        let mut a = h.finish()?;
+        for _ in 1..iterations {
+            a = openssl::hash::hash(md, &a)?;
+        }
+
+        let n = u.min(length - result.len());
+        result.extend_from_slice(&a[..n]);
+        if result.len() == length {
+            return Ok(result);
+        }
+
+        // Each v-byte block of I becomes (I_j + B + 1) mod 2^(8v), with B
+        // being A repeated to fill v bytes.
+        for block in i_buf.chunks_exact_mut(v) {
+            let mut carry = 1u16;
+            for k in (0..v).rev() {
+                let sum = u16::from(block[k]) + u16::from(a[k % u]) + carry;
+                block[k] = sum as u8;
+                carry = sum >> 8;
+            }
+        }
+    }
+}
+
+// PBKDF1, RFC 8018 Section 5.1. Only the first 16 bytes are ever needed, an
+// 8 byte DES or RC2 key followed by the IV.
+fn pbkdf1(
+    md: MessageDigest,
+    password: &[u8],
+    salt: &[u8],
+    iterations: u64,
+) -> KeyParsingResult<Vec<u8>> {
+    let mut h = openssl::hash::Hasher::new(md)?;
+    h.update(password)?;
+    h.update(salt)?;
+    let mut t = h.finish()?;
+    for _ in 1..iterations {
+        t = openssl::hash::hash(md, &t)?;
+    }
+    Ok(t[..16].to_vec())
+}
+
+/// Decrypts `data`, which was encrypted with a legacy PBE `algorithm`,
+/// returning the plaintext with its padding removed.
+pub fn decrypt(
+    algorithm: &AlgorithmIdentifier<'_>,
+    password: &[u8],
+    data: &[u8],
+) -> KeyParsingResult<Vec<u8>> {
+    decrypt_with(algorithm, password, &bmp_password(password), data)
+}
+
+// Like `decrypt`, but with an explicit BMP encoding of the password for the
+// PKCS#12 KDF, since PKCS#12 files may encode empty passwords differently.
+pub(crate) fn decrypt_with(
+    algorithm: &AlgorithmIdentifier<'_>,
+    password: &[u8],
+    bmp_password: &[u8],
+    data: &[u8],
+) -> KeyParsingResult<Vec<u8>> {
+    let (kdf, cipher, params) = scheme(&algorithm.params)
+        .ok_or_else(|| KeyParsingError::UnsupportedEncryptionAlgorithm(algorithm.oid().clone()))?;
+    if params.iterations == 0 {
+        return Err(KeyParsingError::InvalidKey);
+    }
+
+    let (key, iv) = match kdf {
+        Kdf::Pbkdf1(md) => {
+            if params.salt.len() != 8 {
+                return Err(KeyParsingError::InvalidKey);
+            }
+            let dk = pbkdf1(md, password, params.salt, params.iterations)?;
+            (dk[..8].to_vec(), dk[8..].to_vec())
+        }
+        Kdf::Pkcs12 => {
+            let md = MessageDigest::sha1();
+            let key = pkcs12_kdf(
+                md,
+                bmp_password,
+                params.salt,
+                1,
+                params.iterations,
+                cipher.key_len(),
+            )?;
+            let iv = pkcs12_kdf(md, bmp_password, params.salt, 2, params.iterations, IV_LEN)?;
+            (key, iv)
+        }
+    };
+
+    decrypt_cbc(&cipher, &key, &iv, data)
+}
+
+// Decrypts `data` with `cipher` in CBC mode, removing its padding.
+fn decrypt_cbc(cipher: &Cipher, key: &[u8], iv: &[u8], data: &[u8]) -> KeyParsingResult<Vec<u8>> {
+    if data.is_empty() || data.len() % IV_LEN != 0 {
+        return Err(KeyParsingError::InvalidKey);
+    }
+    let mut plaintext = match cipher {
+        Cipher::Rc2 { effective_bits, .. } => {
+            let mut plaintext = data.to_vec();
+            let mut rc2_iv = [0; rc2::BLOCK_SIZE];
+            rc2_iv.copy_from_slice(iv);
+            Rc2::new(key, *effective_bits).decrypt_cbc(&rc2_iv, &mut plaintext);
+            plaintext
+        }
+        // Single and two key DES are run as three key triple DES (EDE with
+        // repeated keys), as single DES is only in OpenSSL's legacy
+        // provider.
+        Cipher::Des | Cipher::TwoKeyTripleDes | Cipher::ThreeKeyTripleDes => {
+            let key = match cipher {
+                Cipher::Des => [key, key, key].concat(),
+                Cipher::TwoKeyTripleDes => [key, &key[..8]].concat(),
+                _ => key.to_vec(),
+            };
+            let mut crypter = openssl::symm::Crypter::new(
+                openssl::symm::Cipher::des_ede3_cbc(),
+                openssl::symm::Mode::Decrypt,
+                &key,
+                Some(iv),
+            )?;
+            crypter.pad(false);
+            let mut plaintext = vec![0; data.len() + IV_LEN];
+            let mut n = crypter.update(data, &mut plaintext)?;
+            n += crypter.finalize(&mut plaintext[n..])?;
+            plaintext.truncate(n);
+            plaintext
+        }
+    };
+
+    // A wrong password almost always shows up as invalid padding.
+    let pad = usize::from(*plaintext.last().unwrap());
+    if pad == 0
+        || pad > IV_LEN
+        || plaintext[plaintext.len() - pad..]
+            .iter()
+            .any(|b| usize::from(*b) != pad)
+    {
+        return Err(KeyParsingError::IncorrectPassword);
+    }
+    plaintext.truncate(plaintext.len() - pad);
+    Ok(plaintext)
+}
+
+// The ciphers that may appear in the DEK-Info header of a traditional
+// encrypted PEM key that are handled by `decrypt_pem`.
+fn pem_cipher(name: &str) -> Option<Cipher> {
+    Some(match name {
+        "DES-CBC" => Cipher::Des,
+        "DES-EDE-CBC" => Cipher::TwoKeyTripleDes,
+        "DES-EDE3-CBC" => Cipher::ThreeKeyTripleDes,
+        "RC2-CBC" => Cipher::Rc2 {
+            key_len: 16,
+            effective_bits: 128,
+        },
+        "RC2-64-CBC" => Cipher::Rc2 {
+            key_len: 8,
+            effective_bits: 64,
+        },
+        "RC2-40-CBC" => Cipher::Rc2 {
+            key_len: 5,
+            effective_bits: 40,
+        },
+        _ => return None,
+    })
+}
+
+fn parse_dek_info(dek_info: &str) -> Option<(Cipher, Vec<u8>)> {
+    let (name, iv) = dek_info.split_once(',')?;
+    let cipher = pem_cipher(name.trim())?;
+    let iv = iv.trim();
+    if iv.len() != IV_LEN * 2 || !iv.is_ascii() {
+        return None;
+    }
+    let iv = (0..IV_LEN)
+        .map(|i| u8::from_str_radix(&iv[i * 2..i * 2 + 2], 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    Some((cipher, iv))
+}
+
+/// Returns whether the `DEK-Info` header of a traditional encrypted PEM key
+/// names one of the ciphers `decrypt_pem` handles.
+pub fn is_legacy_pem_cipher(dek_info: &str) -> bool {
+    dek_info
+        .split_once(',')
+        .map_or(false, |(name, _)| pem_cipher(name.trim()).is_some())
+}
+
+/// Decrypts the contents of a traditional encrypted PEM key, as described by
+/// its `DEK-Info` header, returning the DER encoded key. The key is derived
+/// with OpenSSL's `EVP_BytesToKey`, using MD5, a single iteration and the
+/// first 8 bytes of the IV as the salt.
+pub fn decrypt_pem(dek_info: &str, password: &[u8], data: &[u8]) -> KeyParsingResult<Vec<u8>> {
+    let (cipher, iv) = parse_dek_info(dek_info).ok_or(KeyParsingError::InvalidKey)?;
+
+    let mut key = Vec::with_capacity(cipher.key_len());
+    let mut prev: Option<openssl::hash::DigestBytes> = None;
+    while key.len() < cipher.key_len() {
+        let mut h = openssl::hash::Hasher::new(MessageDigest::md5())?;
+        if let Some(prev) = &prev {
+            h.update(prev)?;
+        }
+        h.update(password)?;
+        h.update(&iv[..8])?;
+        let d = h.finish()?;
+        let n = d.len().min(cipher.key_len() - key.len());
+        key.extend_from_slice(&d[..n]);
+        prev = Some(d);
+    }
+
+    decrypt_cbc(&cipher, &key, &iv, data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bmp_password, is_legacy_pem_cipher, parse_dek_info, pkcs12_kdf};
+    use openssl::hash::MessageDigest;
+
+    #[test]
+    fn test_bmp_password() {
+        assert_eq!(bmp_password(b""), [0, 0]);
+        assert_eq!(bmp_password(b"ab"), [0, b'a', 0, b'b', 0, 0]);
+        assert_eq!(bmp_password("\u{e9}".as_bytes()), [0, 0xe9, 0, 0]);
+        assert_eq!(bmp_password(b"\xff"), [0, 0xff, 0, 0]);
+    }
+
+    #[test]
+    fn test_pkcs12_kdf() {
+        let password = bmp_password(b"smeg");
+        let salt = [0x0a, 0x58, 0xcf, 0x64, 0x53, 0x0d, 0x82, 0x3f];
+        let key = pkcs12_kdf(MessageDigest::sha1(), &password, &salt, 1, 1, 24).ok();
+        assert_eq!(
+            key,
+            Some(vec![
+                0x8a, 0xaa, 0xe6, 0x29, 0x7b, 0x6c, 0xb0, 0x46, 0x42, 0xab, 0x5b, 0x07, 0x78, 0x51,
+                0x28, 0x4e, 0xb7, 0x12, 0x8f, 0x1a, 0x2a, 0x7f, 0xbc, 0xa3
+            ])
+        );
+        let iv = pkcs12_kdf(MessageDigest::sha1(), &password, &salt, 2, 1, 8).ok();
+        assert_eq!(
+            iv,
+            Some(vec![0x79, 0x99, 0x3d, 0xfe, 0x04, 0x8d, 0x3b, 0x76])
+        );
+    }
+
+    #[test]
+    fn test_parse_dek_info() {
+        assert!(is_legacy_pem_cipher("DES-CBC,0E5EE90148D4022A"));
+        assert!(is_legacy_pem_cipher("RC2-40-CBC,7AB18970C4A3916C"));
+        assert!(!is_legacy_pem_cipher(
+            "AES-128-CBC,5E22A2BD85A653FB7A3ED20DE84F54CD"
+        ));
+        assert!(!is_legacy_pem_cipher("DES-CBC"));
+
+        let (_, iv) = parse_dek_info("DES-EDE3-CBC,AB057C2D11E06AA6").unwrap();
+        assert_eq!(iv, [0xab, 0x05, 0x7c, 0x2d, 0x11, 0xe0, 0x6a, 0xa6]);
+        assert!(parse_dek_info("DES-CBC,AB057C2D11E06A").is_none());
+        assert!(parse_dek_info("DES-CBC,AB057C2D11E06AZZ").is_none());
+    }
+}
@@ -0,0 +1,168 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Parsing for PKCS#12 files whose contents are encrypted with the legacy
+//! PBE schemes handled by `pbe`.
+
+use cryptography_x509::common::AlgorithmParameters;
+use cryptography_x509::pkcs12::{
+    AuthenticatedSafe, BagValue, CertType, Content, Pfx, SafeContents, FRIENDLY_NAME_OID,
+    LOCAL_KEY_ID_OID,
+};
+use openssl::hash::MessageDigest;
+
+use crate::pbe;
+use crate::{KeyParsingError, KeyParsingResult};
+
+pub struct Pkcs12Item {
+    pub der: Vec<u8>,
+    pub friendly_name: Option<String>,
+    pub local_key_id: Option<Vec<u8>>,
+}
+
+#[derive(Default)]
+pub struct ParsedPkcs12 {
+    /// DER encoded PKCS#8 PrivateKeyInfos.
+    pub keys: Vec<Pkcs12Item>,
+    /// DER encoded certificates.
+    pub certs: Vec<Pkcs12Item>,
+}
+
+fn verify_mac(pfx: &Pfx<'_>, auth_safe: &[u8], bmp_password: &[u8]) -> KeyParsingResult<bool> {
+    let mac_data = match &pfx.mac_data {
+        Some(mac_data) => mac_data,
+        None => return Ok(true),
+    };
+    let md = match mac_data.mac.algorithm.params {
+        AlgorithmParameters::Sha1(_) => MessageDigest::sha1(),
+        AlgorithmParameters::Sha224(_) => MessageDigest::sha224(),
+        AlgorithmParameters::Sha256(_) => MessageDigest::sha256(),
+        AlgorithmParameters::Sha384(_) => MessageDigest::sha384(),
+        AlgorithmParameters::Sha512(_) => MessageDigest::sha512(),
+        _ => {
+            return Err(KeyParsingError::UnsupportedEncryptionAlgorithm(
+                mac_data.mac.algorithm.oid().clone(),
+            ))
+        }
+    };
+    let key = pbe::pkcs12_kdf(
+        md,
+        bmp_password,
+        mac_data.salt,
+        3,
+        mac_data.iterations.unwrap_or(1),
+        md.size(),
+    )?;
+    let pkey = openssl::pkey::PKey::hmac(&key)?;
+    let mut signer = openssl::sign::Signer::new(md, &pkey)?;
+    let mac = signer.sign_oneshot_to_vec(auth_safe)?;
+    Ok(mac.len() == mac_data.mac.digest.len() && openssl::memcmp::eq(&mac, mac_data.mac.digest))
+}
+
+fn parse_safe_contents(
+    data: &[u8],
+    password: &[u8],
+    bmp_password: &[u8],
+    result: &mut ParsedPkcs12,
+) -> KeyParsingResult<()> {
+    for bag in asn1::parse_single::<SafeContents<'_>>(data)? {
+        let mut friendly_name = None;
+        let mut local_key_id = None;
+        for attribute in bag.attributes.into_iter().flatten() {
+            let value = match attribute.values.unwrap_read().clone().next() {
+                Some(value) => value,
+                None => continue,
+            };
+            if attribute.type_id == FRIENDLY_NAME_OID {
+                let name = asn1::parse_single::<asn1::BMPString<'_>>(value.full_data())?;
+                let units = name
+                    .as_utf16_be_bytes()
+                    .chunks_exact(2)
+                    .map(|c| u16::from_be_bytes([c[0], c[1]]));
+                friendly_name = Some(
+                    char::decode_utf16(units)
+                        .collect::<Result<String, _>>()
+                        .map_err(|_| KeyParsingError::InvalidKey)?,
+                );
+            } else if attribute.type_id == LOCAL_KEY_ID_OID {
+                local_key_id = Some(asn1::parse_single::<&[u8]>(value.full_data())?.to_vec());
+            }
+        }
+
+        let (der, items) = match bag.bag_value {
+            BagValue::KeyBag(key) => (key.as_inner().full_data().to_vec(), &mut result.keys),
+            BagValue::ShroudedKeyBag(key) => {
+                let key = key.as_inner();
+                let der = pbe::decrypt_with(
+                    &key.encryption_algorithm,
+                    password,
+                    bmp_password,
+                    key.encrypted_data,
+                )?;
+                (der, &mut result.keys)
+            }
+            BagValue::CertBag(cert) => match &cert.as_inner().cert_value {
+                CertType::X509(der) => (der.as_inner().to_vec(), &mut result.certs),
+                CertType::Other(..) => continue,
+            },
+            BagValue::Other(..) => continue,
+        };
+        items.push(Pkcs12Item {
+            der,
+            friendly_name,
+            local_key_id,
+        });
+    }
+    Ok(())
+}
+
+/// Parses a password integrity mode PKCS#12 file. Any encrypted contents must
+/// use a legacy PBE scheme, otherwise `UnsupportedEncryptionAlgorithm` is
+/// returned.
+pub fn parse_legacy_pkcs12(data: &[u8], password: &[u8]) -> KeyParsingResult<ParsedPkcs12> {
+    let pfx = asn1::parse_single::<Pfx<'_>>(data)?;
+    if pfx.version != 3 {
+        return Err(KeyParsingError::InvalidKey);
+    }
+    let auth_safe = match &pfx.auth_safe.content {
+        Content::Data(data) => *data.as_inner(),
+        Content::EncryptedData(_) => return Err(KeyParsingError::InvalidKey),
+    };
+
+    // Like OpenSSL, an empty password may have been encoded either as an
+    // empty BMPString or not at all.
+    let mut candidates = vec![pbe::bmp_password(password)];
+    if password.is_empty() {
+        candidates.push(vec![]);
+    }
+    let mut bmp_password = None;
+    for candidate in candidates {
+        if verify_mac(&pfx, auth_safe, &candidate)? {
+            bmp_password = Some(candidate);
+            break;
+        }
+    }
+    let bmp_password = bmp_password.ok_or(KeyParsingError::IncorrectPassword)?;
+
+    let mut result = ParsedPkcs12::default();
+    for content_info in asn1::parse_single::<AuthenticatedSafe<'_>>(auth_safe)? {
+        match content_info.content {
+            Content::Data(data) => {
+                parse_safe_contents(data.as_inner(), password, &bmp_password, &mut result)?
+            }
+            Content::EncryptedData(encrypted) => {
+                let info = &encrypted.as_inner().encrypted_content_info;
+                let ciphertext = info.encrypted_content.ok_or(KeyParsingError::InvalidKey)?;
+                let plaintext = pbe::decrypt_with(
+                    &info.content_encryption_algorithm,
+                    password,
+                    &bmp_password,
+                    ciphertext,
+                )?;
+                parse_safe_contents(&plaintext, password, &bmp_password, &mut result)?;
+            }
+        }
+    }
+    Ok(result)
+}
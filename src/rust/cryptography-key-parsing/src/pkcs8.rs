@@ -0,0 +1,147 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+use asn1::SimpleAsn1Readable;
+use cryptography_x509::common::AlgorithmParameters;
+use cryptography_x509::pkcs8::{EncryptedPrivateKeyInfo, PrivateKeyInfo};
+
+use crate::{KeyParsingError, KeyParsingResult};
+
+/// The DER encodings of a private key that can be told apart from their
+/// outer structure alone.
+pub enum PrivateKeyFormat<'a> {
+    /// An unencrypted PKCS#8 `PrivateKeyInfo` (or `OneAsymmetricKey`).
+    Pkcs8(PrivateKeyInfo<'a>),
+    /// A PKCS#8 `EncryptedPrivateKeyInfo`.
+    EncryptedPkcs8(EncryptedPrivateKeyInfo<'a>),
+    /// A SEC1 `ECPrivateKey`.
+    Sec1,
+    /// A PKCS#1 `RSAPrivateKey`.
+    Pkcs1Rsa,
+    /// OpenSSL's traditional `DSAPrivateKey` format.
+    TraditionalDsa,
+}
+
+/// Works out which format `data` is in, without parsing the key itself.
+/// Unencrypted PKCS#8 keys must be for one of the algorithms we support,
+/// otherwise `UnsupportedKeyType` is returned.
+pub fn identify_private_key(data: &[u8]) -> KeyParsingResult<PrivateKeyFormat<'_>> {
+    let tags = asn1::parse_single::<asn1::SequenceOf<'_, asn1::Tlv<'_>>>(data)?
+        .map(|tlv| tlv.tag())
+        .collect::<Vec<_>>();
+
+    match tags.as_slice() {
+        [alg, data_tag] if *alg == asn1::Sequence::TAG && *data_tag == <&[u8]>::TAG => {
+            Ok(PrivateKeyFormat::EncryptedPkcs8(asn1::parse_single(data)?))
+        }
+        [version, alg, ..] if *version == asn1::BigInt::TAG && *alg == asn1::Sequence::TAG => {
+            let info = asn1::parse_single::<PrivateKeyInfo<'_>>(data)?;
+            if info.version > 1 {
+                return Err(KeyParsingError::InvalidKey);
+            }
+            match info.algorithm.params {
+                AlgorithmParameters::Rsa(_)
+                | AlgorithmParameters::RsaPss(_)
+                | AlgorithmParameters::Ec(_)
+                | AlgorithmParameters::Ed25519
+                | AlgorithmParameters::Ed448
+                | AlgorithmParameters::X25519
+                | AlgorithmParameters::X448
+                | AlgorithmParameters::Dsa(_)
+                | AlgorithmParameters::Dh(_)
+                | AlgorithmParameters::DhKeyAgreement(_) => Ok(PrivateKeyFormat::Pkcs8(info)),
+                #[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+                AlgorithmParameters::MlDsa44
+                | AlgorithmParameters::MlDsa65
+                | AlgorithmParameters::MlDsa87 => Ok(PrivateKeyFormat::Pkcs8(info)),
+                #[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+                AlgorithmParameters::SlhDsaSha2_128s
+                | AlgorithmParameters::SlhDsaSha2_128f
+                | AlgorithmParameters::SlhDsaSha2_192s
+                | AlgorithmParameters::SlhDsaSha2_192f
+                | AlgorithmParameters::SlhDsaSha2_256s
+                | AlgorithmParameters::SlhDsaSha2_256f
+                | AlgorithmParameters::SlhDsaShake128s
+                | AlgorithmParameters::SlhDsaShake128f
+                | AlgorithmParameters::SlhDsaShake192s
+                | AlgorithmParameters::SlhDsaShake192f
+                | AlgorithmParameters::SlhDsaShake256s
+                | AlgorithmParameters::SlhDsaShake256f => Ok(PrivateKeyFormat::Pkcs8(info)),
+                _ => Err(KeyParsingError::UnsupportedKeyType(
+                    info.algorithm.oid().clone(),
+                )),
+            }
+        }
+        [version, key, ..] if *version == asn1::BigInt::TAG && *key == <&[u8]>::TAG => {
+            Ok(PrivateKeyFormat::Sec1)
+        }
+        // RSAPrivateKey starts with nine integers (followed by the other
+        // primes for multi-prime keys) and DSAPrivateKey has six.
+        _ => {
+            let integers = tags.iter().take_while(|t| **t == asn1::BigInt::TAG).count();
+            match (integers, tags.len()) {
+                (6, 6) => Ok(PrivateKeyFormat::TraditionalDsa),
+                (9, 9 | 10) => Ok(PrivateKeyFormat::Pkcs1Rsa),
+                _ => Err(KeyParsingError::InvalidKey),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{identify_private_key, PrivateKeyFormat};
+    use crate::KeyParsingError;
+
+    #[test]
+    fn test_identify_private_key() {
+        let rsa = openssl::rsa::Rsa::generate(1024).unwrap();
+        assert!(matches!(
+            identify_private_key(&rsa.private_key_to_der().unwrap()),
+            Ok(PrivateKeyFormat::Pkcs1Rsa)
+        ));
+        let pkey = openssl::pkey::PKey::from_rsa(rsa).unwrap();
+        assert!(matches!(
+            identify_private_key(&pkey.private_key_to_pkcs8().unwrap()),
+            Ok(PrivateKeyFormat::Pkcs8(_))
+        ));
+        let encrypted = pkey
+            .private_key_to_pkcs8_passphrase(openssl::symm::Cipher::aes_128_cbc(), b"password")
+            .unwrap();
+        assert!(matches!(
+            identify_private_key(&encrypted),
+            Ok(PrivateKeyFormat::EncryptedPkcs8(_))
+        ));
+
+        let group =
+            openssl::ec::EcGroup::from_curve_name(openssl::nid::Nid::X9_62_PRIME256V1).unwrap();
+        let ec = openssl::ec::EcKey::generate(&group).unwrap();
+        assert!(matches!(
+            identify_private_key(&ec.private_key_to_der().unwrap()),
+            Ok(PrivateKeyFormat::Sec1)
+        ));
+
+        let dsa = openssl::dsa::Dsa::generate(1024).unwrap();
+        assert!(matches!(
+            identify_private_key(&dsa.private_key_to_der().unwrap()),
+            Ok(PrivateKeyFormat::TraditionalDsa)
+        ));
+
+        // A PrivateKeyInfo with an unknown algorithm.
+        let unknown = b"\x30\x0e\x02\x01\x00\x30\x04\x06\x02\x2a\x03\x04\x03key";
+        assert!(matches!(
+            identify_private_key(unknown),
+            Err(KeyParsingError::UnsupportedKeyType(_))
+        ));
+
+        assert!(matches!(
+            identify_private_key(b"\x30\x03\x02\x01\x00"),
+            Err(KeyParsingError::InvalidKey)
+        ));
+        assert!(matches!(
+            identify_private_key(b"not a key"),
+            Err(KeyParsingError::Parse(_))
+        ));
+    }
+}
@@ -2,8 +2,38 @@
 // 2.0, and the BSD License. See the LICENSE file in the root of this repository
 // for complete details.
 
+use cryptography_x509::common::Asn1ReadableOrWritable;
+
 use crate::KeyParsingResult;
 
+// RSAPrivateKey from RFC 8017, appendix A.1.2. OpenSSL's RSA API only deals
+// with two primes, so multi-prime keys are converted through this.
+#[derive(asn1::Asn1Read, asn1::Asn1Write)]
+pub struct Pkcs1RsaPrivateKey<'a> {
+    pub version: u8,
+    pub n: asn1::BigUint<'a>,
+    pub e: asn1::BigUint<'a>,
+    pub d: asn1::BigUint<'a>,
+    pub p: asn1::BigUint<'a>,
+    pub q: asn1::BigUint<'a>,
+    pub dmp1: asn1::BigUint<'a>,
+    pub dmq1: asn1::BigUint<'a>,
+    pub iqmp: asn1::BigUint<'a>,
+    pub other_prime_infos: Option<OtherPrimeInfos<'a>>,
+}
+
+pub type OtherPrimeInfos<'a> = Asn1ReadableOrWritable<
+    asn1::SequenceOf<'a, OtherPrimeInfo<'a>>,
+    asn1::SequenceOfWriter<'a, OtherPrimeInfo<'a>, Vec<OtherPrimeInfo<'a>>>,
+>;
+
+#[derive(asn1::Asn1Read, asn1::Asn1Write)]
+pub struct OtherPrimeInfo<'a> {
+    pub prime: asn1::BigUint<'a>,
+    pub exponent: asn1::BigUint<'a>,
+    pub coefficient: asn1::BigUint<'a>,
+}
+
 #[derive(asn1::Asn1Read)]
 struct Pksc1RsaPublicKey<'a> {
     n: asn1::BigUint<'a>,
@@ -2,7 +2,7 @@
 // 2.0, and the BSD License. See the LICENSE file in the root of this repository
 // for complete details.
 
-use cryptography_x509::common::{AlgorithmParameters, EcParameters, SubjectPublicKeyInfo};
+use cryptography_x509::common::{AlgorithmParameters, SubjectPublicKeyInfo};
 
 use crate::{KeyParsingError, KeyParsingResult};
 
@@ -12,62 +12,18 @@ pub fn parse_public_key(
     let k = asn1::parse_single::<SubjectPublicKeyInfo>(data)?;
 
     match k.algorithm.params {
-        AlgorithmParameters::Ec(ec_params) => match ec_params {
-            EcParameters::NamedCurve(curve_oid) => {
-                let curve_nid = match curve_oid {
-                    cryptography_x509::oid::EC_SECP192R1 => openssl::nid::Nid::X9_62_PRIME192V1,
-                    cryptography_x509::oid::EC_SECP224R1 => openssl::nid::Nid::SECP224R1,
-                    cryptography_x509::oid::EC_SECP256R1 => openssl::nid::Nid::X9_62_PRIME256V1,
-                    cryptography_x509::oid::EC_SECP384R1 => openssl::nid::Nid::SECP384R1,
-                    cryptography_x509::oid::EC_SECP521R1 => openssl::nid::Nid::SECP521R1,
-
-                    cryptography_x509::oid::EC_SECP256K1 => openssl::nid::Nid::SECP256K1,
-
-                    cryptography_x509::oid::EC_SECT233R1 => openssl::nid::Nid::SECT233R1,
-                    cryptography_x509::oid::EC_SECT283R1 => openssl::nid::Nid::SECT283R1,
-                    cryptography_x509::oid::EC_SECT409R1 => openssl::nid::Nid::SECT409R1,
-                    cryptography_x509::oid::EC_SECT571R1 => openssl::nid::Nid::SECT571R1,
-
-                    cryptography_x509::oid::EC_SECT163R2 => openssl::nid::Nid::SECT163R2,
-
-                    cryptography_x509::oid::EC_SECT163K1 => openssl::nid::Nid::SECT163K1,
-                    cryptography_x509::oid::EC_SECT233K1 => openssl::nid::Nid::SECT233K1,
-                    cryptography_x509::oid::EC_SECT283K1 => openssl::nid::Nid::SECT283K1,
-                    cryptography_x509::oid::EC_SECT409K1 => openssl::nid::Nid::SECT409K1,
-                    cryptography_x509::oid::EC_SECT571K1 => openssl::nid::Nid::SECT571K1,
-
-                    #[cfg(not(CRYPTOGRAPHY_IS_BORINGSSL))]
-                    cryptography_x509::oid::EC_BRAINPOOLP256R1 => {
-                        openssl::nid::Nid::BRAINPOOL_P256R1
-                    }
-                    #[cfg(not(CRYPTOGRAPHY_IS_BORINGSSL))]
-                    cryptography_x509::oid::EC_BRAINPOOLP384R1 => {
-                        openssl::nid::Nid::BRAINPOOL_P384R1
-                    }
-                    #[cfg(not(CRYPTOGRAPHY_IS_BORINGSSL))]
-                    cryptography_x509::oid::EC_BRAINPOOLP512R1 => {
-                        openssl::nid::Nid::BRAINPOOL_P512R1
-                    }
-
-                    _ => return Err(KeyParsingError::UnsupportedEllipticCurve(curve_oid)),
-                };
-
-                let group = openssl::ec::EcGroup::from_curve_name(curve_nid)
-                    .map_err(|_| KeyParsingError::UnsupportedEllipticCurve(curve_oid))?;
-                let mut bn_ctx = openssl::bn::BigNumContext::new()?;
-                let ec_point = openssl::ec::EcPoint::from_bytes(
-                    &group,
-                    k.subject_public_key.as_bytes(),
-                    &mut bn_ctx,
-                )
-                .map_err(|_| KeyParsingError::InvalidKey)?;
-                let ec_key = openssl::ec::EcKey::from_public_key(&group, &ec_point)?;
-                Ok(openssl::pkey::PKey::from_ec_key(ec_key)?)
-            }
-            EcParameters::ImplicitCurve(_) | EcParameters::SpecifiedCurve(_) => {
-                Err(KeyParsingError::ExplicitCurveUnsupported)
-            }
-        },
+        AlgorithmParameters::Ec(ec_params) => {
+            let group = crate::ec::group_from_parameters(&ec_params)?;
+            let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+            let ec_point = openssl::ec::EcPoint::from_bytes(
+                &group,
+                k.subject_public_key.as_bytes(),
+                &mut bn_ctx,
+            )
+            .map_err(|_| KeyParsingError::InvalidKey)?;
+            let ec_key = openssl::ec::EcKey::from_public_key(&group, &ec_point)?;
+            Ok(openssl::pkey::PKey::from_ec_key(ec_key)?)
+        }
         AlgorithmParameters::Ed25519 => Ok(openssl::pkey::PKey::public_key_from_raw_bytes(
             k.subject_public_key.as_bytes(),
             openssl::pkey::Id::ED25519,
@@ -86,10 +42,60 @@ pub fn parse_public_key(
             k.subject_public_key.as_bytes(),
             openssl::pkey::Id::X448,
         )?),
-        AlgorithmParameters::Rsa(_) | AlgorithmParameters::RsaPss(_) => {
-            // RSA-PSS keys are treated the same as bare RSA keys.
+        #[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+        AlgorithmParameters::MlDsa44 => Ok(cryptography_openssl::mldsa::public_key_from_raw_bytes(
+            cryptography_openssl::mldsa::Variant::MlDsa44,
+            k.subject_public_key.as_bytes(),
+        )?),
+        #[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+        AlgorithmParameters::MlDsa65 => Ok(cryptography_openssl::mldsa::public_key_from_raw_bytes(
+            cryptography_openssl::mldsa::Variant::MlDsa65,
+            k.subject_public_key.as_bytes(),
+        )?),
+        #[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+        AlgorithmParameters::MlDsa87 => Ok(cryptography_openssl::mldsa::public_key_from_raw_bytes(
+            cryptography_openssl::mldsa::Variant::MlDsa87,
+            k.subject_public_key.as_bytes(),
+        )?),
+        // OpenSSL validates the key length for each parameter set.
+        #[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+        AlgorithmParameters::SlhDsaSha2_128s
+        | AlgorithmParameters::SlhDsaSha2_128f
+        | AlgorithmParameters::SlhDsaSha2_192s
+        | AlgorithmParameters::SlhDsaSha2_192f
+        | AlgorithmParameters::SlhDsaSha2_256s
+        | AlgorithmParameters::SlhDsaSha2_256f
+        | AlgorithmParameters::SlhDsaShake128s
+        | AlgorithmParameters::SlhDsaShake128f
+        | AlgorithmParameters::SlhDsaShake192s
+        | AlgorithmParameters::SlhDsaShake192f
+        | AlgorithmParameters::SlhDsaShake256s
+        | AlgorithmParameters::SlhDsaShake256f => {
+            Ok(openssl::pkey::PKey::public_key_from_der(data)?)
+        }
+        AlgorithmParameters::Rsa(_) => {
             crate::rsa::parse_pkcs1_public_key(k.subject_public_key.as_bytes())
         }
+        AlgorithmParameters::RsaPss(_) => {
+            cfg_if::cfg_if! {
+                if #[cfg(any(
+                    not(CRYPTOGRAPHY_IS_LIBRESSL),
+                    CRYPTOGRAPHY_LIBRESSL_380_OR_GREATER
+                ))] {
+                    // Loading the SPKI itself keeps the PSS parameters, which
+                    // restrict what the key can be used for. Where that isn't
+                    // supported the key is treated as a bare RSA key.
+                    match openssl::pkey::PKey::public_key_from_der(data) {
+                        Ok(pkey) => Ok(pkey),
+                        Err(_) => {
+                            crate::rsa::parse_pkcs1_public_key(k.subject_public_key.as_bytes())
+                        }
+                    }
+                } else {
+                    crate::rsa::parse_pkcs1_public_key(k.subject_public_key.as_bytes())
+                }
+            }
+        }
         AlgorithmParameters::Dsa(dsa_params) => {
             let p = openssl::bn::BigNum::from_slice(dsa_params.p.as_bytes())?;
             let q = openssl::bn::BigNum::from_slice(dsa_params.q.as_bytes())?;
@@ -0,0 +1,105 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Access to the private keys held by the operating system's key store: the
+//! CNG key storage providers on Windows and the keychain on macOS. These keys
+//! usually can't be exported, so they're only used to sign digests.
+
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(windows)]
+mod windows;
+
+#[cfg(target_os = "macos")]
+use macos as platform;
+#[cfg(windows)]
+use windows as platform;
+
+#[derive(Debug)]
+pub enum KeystoreError {
+    /// There's no supported key store on this platform.
+    Unsupported,
+    /// A key store function failed, with the function's name and the status
+    /// it returned.
+    Os(&'static str, i64),
+}
+
+pub type KeystoreResult<T> = Result<T, KeystoreError>;
+
+#[derive(Clone, Copy)]
+pub enum Curve {
+    P256,
+    P384,
+    P521,
+}
+
+pub enum PublicKey {
+    /// An RSA public key's big endian modulus and public exponent.
+    Rsa { n: Vec<u8>, e: Vec<u8> },
+    /// A DER encoded PKCS#1 RSAPublicKey.
+    RsaPkcs1(Vec<u8>),
+    /// An EC public key's curve and uncompressed point.
+    Ec { curve: Curve, point: Vec<u8> },
+}
+
+#[derive(Clone, Copy)]
+pub enum HashAlgorithm {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+pub struct Key {
+    pub name: String,
+    pub public_key: PublicKey,
+    handle: platform::KeyHandle,
+}
+
+impl Key {
+    /// Signs `digest`, which was computed with `hash`. RSA keys sign with
+    /// PKCS#1 v1.5 padding. EC signatures are the concatenation of r and s,
+    /// each padded to the size of the curve.
+    pub fn sign_digest(&self, hash: HashAlgorithm, digest: &[u8]) -> KeystoreResult<Vec<u8>> {
+        platform::sign(&self.handle, &self.public_key, hash, digest)
+    }
+}
+
+/// Lists the current user's RSA and EC private keys. Keys of other types are
+/// skipped.
+pub fn list_keys() -> KeystoreResult<Vec<Key>> {
+    platform::list_keys()
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+mod platform {
+    use crate::{HashAlgorithm, Key, KeystoreError, KeystoreResult, PublicKey};
+
+    pub(crate) enum KeyHandle {}
+
+    pub(crate) fn list_keys() -> KeystoreResult<Vec<Key>> {
+        Err(KeystoreError::Unsupported)
+    }
+
+    pub(crate) fn sign(
+        handle: &KeyHandle,
+        _public_key: &PublicKey,
+        _hash: HashAlgorithm,
+        _digest: &[u8],
+    ) -> KeystoreResult<Vec<u8>> {
+        match *handle {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(not(any(windows, target_os = "macos")))]
+    #[test]
+    fn test_unsupported() {
+        assert!(matches!(
+            super::list_keys(),
+            Err(super::KeystoreError::Unsupported)
+        ));
+    }
+}
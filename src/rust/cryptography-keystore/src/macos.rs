@@ -0,0 +1,244 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Keys from the keychain, via the Security framework.
+
+use std::ffi::c_void;
+use std::os::raw::c_char;
+use std::ptr;
+
+use crate::{Curve, HashAlgorithm, Key, KeystoreError, KeystoreResult, PublicKey};
+
+type CFTypeRef = *const c_void;
+type CFIndex = isize;
+type OSStatus = i32;
+
+#[repr(C)]
+struct CFDictionaryCallBacks {
+    _private: [u8; 0],
+}
+
+const ERR_SEC_ITEM_NOT_FOUND: OSStatus = -25300;
+const CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+    static kCFTypeDictionaryKeyCallBacks: CFDictionaryCallBacks;
+    static kCFTypeDictionaryValueCallBacks: CFDictionaryCallBacks;
+    static kCFBooleanTrue: CFTypeRef;
+
+    fn CFRelease(cf: CFTypeRef);
+    fn CFRetain(cf: CFTypeRef) -> CFTypeRef;
+    fn CFGetTypeID(cf: CFTypeRef) -> usize;
+    fn CFStringGetTypeID() -> usize;
+    fn CFDictionaryCreate(
+        allocator: CFTypeRef,
+        keys: *const CFTypeRef,
+        values: *const CFTypeRef,
+        num_values: CFIndex,
+        key_callbacks: *const CFDictionaryCallBacks,
+        value_callbacks: *const CFDictionaryCallBacks,
+    ) -> CFTypeRef;
+    fn CFDictionaryGetValue(dict: CFTypeRef, key: CFTypeRef) -> CFTypeRef;
+    fn CFArrayGetCount(array: CFTypeRef) -> CFIndex;
+    fn CFArrayGetValueAtIndex(array: CFTypeRef, index: CFIndex) -> CFTypeRef;
+    fn CFDataCreate(allocator: CFTypeRef, bytes: *const u8, length: CFIndex) -> CFTypeRef;
+    fn CFDataGetBytePtr(data: CFTypeRef) -> *const u8;
+    fn CFDataGetLength(data: CFTypeRef) -> CFIndex;
+    fn CFStringGetLength(s: CFTypeRef) -> CFIndex;
+    fn CFStringGetMaximumSizeForEncoding(length: CFIndex, encoding: u32) -> CFIndex;
+    fn CFStringGetCString(
+        s: CFTypeRef,
+        buffer: *mut c_char,
+        buffer_size: CFIndex,
+        encoding: u32,
+    ) -> u8;
+    fn CFErrorGetCode(error: CFTypeRef) -> CFIndex;
+}
+
+#[link(name = "Security", kind = "framework")]
+extern "C" {
+    static kSecClass: CFTypeRef;
+    static kSecClassKey: CFTypeRef;
+    static kSecAttrKeyClass: CFTypeRef;
+    static kSecAttrKeyClassPrivate: CFTypeRef;
+    static kSecAttrLabel: CFTypeRef;
+    static kSecMatchLimit: CFTypeRef;
+    static kSecMatchLimitAll: CFTypeRef;
+    static kSecReturnRef: CFTypeRef;
+    static kSecReturnAttributes: CFTypeRef;
+    static kSecValueRef: CFTypeRef;
+
+    static kSecKeyAlgorithmRSASignatureDigestPKCS1v15SHA1: CFTypeRef;
+    static kSecKeyAlgorithmRSASignatureDigestPKCS1v15SHA256: CFTypeRef;
+    static kSecKeyAlgorithmRSASignatureDigestPKCS1v15SHA384: CFTypeRef;
+    static kSecKeyAlgorithmRSASignatureDigestPKCS1v15SHA512: CFTypeRef;
+    static kSecKeyAlgorithmECDSASignatureDigestRFC4754: CFTypeRef;
+
+    fn SecItemCopyMatching(query: CFTypeRef, result: *mut CFTypeRef) -> OSStatus;
+    fn SecKeyCopyPublicKey(key: CFTypeRef) -> CFTypeRef;
+    fn SecKeyCopyExternalRepresentation(key: CFTypeRef, error: *mut CFTypeRef) -> CFTypeRef;
+    fn SecKeyCreateSignature(
+        key: CFTypeRef,
+        algorithm: CFTypeRef,
+        data_to_sign: CFTypeRef,
+        error: *mut CFTypeRef,
+    ) -> CFTypeRef;
+}
+
+// An owned reference to a Core Foundation object.
+struct CfObject(CFTypeRef);
+
+impl Drop for CfObject {
+    fn drop(&mut self) {
+        unsafe {
+            CFRelease(self.0);
+        }
+    }
+}
+
+pub(crate) struct KeyHandle(CfObject);
+
+// SecKey objects are immutable and can be used from any thread.
+unsafe impl Send for KeyHandle {}
+unsafe impl Sync for KeyHandle {}
+
+// Takes ownership of `value`, which failed if it's NULL, in which case the
+// code of `error` is returned.
+fn owned(function: &'static str, value: CFTypeRef, error: CFTypeRef) -> KeystoreResult<CfObject> {
+    if value.is_null() {
+        let code = if error.is_null() {
+            0
+        } else {
+            let error = CfObject(error);
+            unsafe { CFErrorGetCode(error.0) }
+        };
+        return Err(KeystoreError::Os(function, code as i64));
+    }
+    Ok(CfObject(value))
+}
+
+unsafe fn data_bytes(data: &CfObject) -> Vec<u8> {
+    std::slice::from_raw_parts(CFDataGetBytePtr(data.0), CFDataGetLength(data.0) as usize).to_vec()
+}
+
+unsafe fn string_value(s: CFTypeRef) -> Option<String> {
+    if s.is_null() || CFGetTypeID(s) != CFStringGetTypeID() {
+        return None;
+    }
+    let size = CFStringGetMaximumSizeForEncoding(CFStringGetLength(s), CF_STRING_ENCODING_UTF8) + 1;
+    let mut buf = vec![0u8; size as usize];
+    if CFStringGetCString(s, buf.as_mut_ptr().cast(), size, CF_STRING_ENCODING_UTF8) == 0 {
+        return None;
+    }
+    let len = buf.iter().position(|b| *b == 0).unwrap_or(buf.len());
+    buf.truncate(len);
+    String::from_utf8(buf).ok()
+}
+
+pub(crate) fn list_keys() -> KeystoreResult<Vec<Key>> {
+    let items = unsafe {
+        let keys = [
+            kSecClass,
+            kSecAttrKeyClass,
+            kSecMatchLimit,
+            kSecReturnRef,
+            kSecReturnAttributes,
+        ];
+        let values = [
+            kSecClassKey,
+            kSecAttrKeyClassPrivate,
+            kSecMatchLimitAll,
+            kCFBooleanTrue,
+            kCFBooleanTrue,
+        ];
+        let query = CfObject(CFDictionaryCreate(
+            ptr::null(),
+            keys.as_ptr(),
+            values.as_ptr(),
+            keys.len() as CFIndex,
+            &kCFTypeDictionaryKeyCallBacks,
+            &kCFTypeDictionaryValueCallBacks,
+        ));
+        let mut result = ptr::null();
+        match SecItemCopyMatching(query.0, &mut result) {
+            0 => CfObject(result),
+            ERR_SEC_ITEM_NOT_FOUND => return Ok(vec![]),
+            status => return Err(KeystoreError::Os("SecItemCopyMatching", status.into())),
+        }
+    };
+
+    let mut keys = vec![];
+    for i in 0..unsafe { CFArrayGetCount(items.0) } {
+        let (key, name) = unsafe {
+            let attributes = CFArrayGetValueAtIndex(items.0, i);
+            let key = CFDictionaryGetValue(attributes, kSecValueRef);
+            if key.is_null() {
+                continue;
+            }
+            let name = string_value(CFDictionaryGetValue(attributes, kSecAttrLabel));
+            (KeyHandle(CfObject(CFRetain(key))), name.unwrap_or_default())
+        };
+        // Keys whose public key can't be read, or isn't RSA or EC, are
+        // skipped rather than failing the whole listing.
+        if let Ok(public_key) = public_key(&key) {
+            keys.push(Key {
+                name,
+                public_key,
+                handle: key,
+            });
+        }
+    }
+    Ok(keys)
+}
+
+fn public_key(key: &KeyHandle) -> KeystoreResult<PublicKey> {
+    let public_key = owned(
+        "SecKeyCopyPublicKey",
+        unsafe { SecKeyCopyPublicKey((key.0).0) },
+        ptr::null(),
+    )?;
+    let mut error = ptr::null();
+    let data = unsafe { SecKeyCopyExternalRepresentation(public_key.0, &mut error) };
+    let data = unsafe { data_bytes(&owned("SecKeyCopyExternalRepresentation", data, error)?) };
+
+    // RSA keys are represented as a PKCS#1 RSAPublicKey, and EC keys as an
+    // uncompressed point.
+    match data.first() {
+        Some(0x30) => Ok(PublicKey::RsaPkcs1(data)),
+        Some(0x04) => {
+            let curve = match data.len() {
+                65 => Curve::P256,
+                97 => Curve::P384,
+                133 => Curve::P521,
+                _ => return Err(KeystoreError::Os("SecKeyCopyExternalRepresentation", 0)),
+            };
+            Ok(PublicKey::Ec { curve, point: data })
+        }
+        _ => Err(KeystoreError::Os("SecKeyCopyExternalRepresentation", 0)),
+    }
+}
+
+pub(crate) fn sign(
+    handle: &KeyHandle,
+    public_key: &PublicKey,
+    hash: HashAlgorithm,
+    digest: &[u8],
+) -> KeystoreResult<Vec<u8>> {
+    let algorithm = unsafe {
+        match (public_key, hash) {
+            (PublicKey::Ec { .. }, _) => kSecKeyAlgorithmECDSASignatureDigestRFC4754,
+            (_, HashAlgorithm::Sha1) => kSecKeyAlgorithmRSASignatureDigestPKCS1v15SHA1,
+            (_, HashAlgorithm::Sha256) => kSecKeyAlgorithmRSASignatureDigestPKCS1v15SHA256,
+            (_, HashAlgorithm::Sha384) => kSecKeyAlgorithmRSASignatureDigestPKCS1v15SHA384,
+            (_, HashAlgorithm::Sha512) => kSecKeyAlgorithmRSASignatureDigestPKCS1v15SHA512,
+        }
+    };
+    let data =
+        CfObject(unsafe { CFDataCreate(ptr::null(), digest.as_ptr(), digest.len() as CFIndex) });
+    let mut error = ptr::null();
+    let signature = unsafe { SecKeyCreateSignature((handle.0).0, algorithm, data.0, &mut error) };
+    let signature = owned("SecKeyCreateSignature", signature, error)?;
+    Ok(unsafe { data_bytes(&signature) })
+}
@@ -0,0 +1,357 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Keys from the CNG key storage providers, via NCrypt.
+
+use std::ffi::c_void;
+use std::ptr;
+use std::sync::Arc;
+
+use crate::{Curve, HashAlgorithm, Key, KeystoreError, KeystoreResult, PublicKey};
+
+type SecurityStatus = i32;
+type NcryptHandle = usize;
+
+#[repr(C)]
+struct NcryptKeyName {
+    name: *mut u16,
+    algid: *mut u16,
+    legacy_key_spec: u32,
+    flags: u32,
+}
+
+#[repr(C)]
+struct BcryptPkcs1PaddingInfo {
+    alg_id: *const u16,
+}
+
+const NTE_NO_MORE_ITEMS: SecurityStatus = 0x8009_002a_u32 as i32;
+const NCRYPT_SILENT_FLAG: u32 = 0x40;
+const BCRYPT_PAD_PKCS1: u32 = 0x2;
+
+const BCRYPT_RSAPUBLIC_MAGIC: u32 = 0x3141_5352;
+const BCRYPT_ECDSA_PUBLIC_P256_MAGIC: u32 = 0x3153_4345;
+const BCRYPT_ECDSA_PUBLIC_P384_MAGIC: u32 = 0x3353_4345;
+const BCRYPT_ECDSA_PUBLIC_P521_MAGIC: u32 = 0x3553_4345;
+
+#[link(name = "ncrypt")]
+extern "system" {
+    fn NCryptOpenStorageProvider(
+        provider: *mut NcryptHandle,
+        provider_name: *const u16,
+        flags: u32,
+    ) -> SecurityStatus;
+    fn NCryptEnumKeys(
+        provider: NcryptHandle,
+        scope: *const u16,
+        key_name: *mut *mut NcryptKeyName,
+        enum_state: *mut *mut c_void,
+        flags: u32,
+    ) -> SecurityStatus;
+    fn NCryptOpenKey(
+        provider: NcryptHandle,
+        key: *mut NcryptHandle,
+        key_name: *const u16,
+        legacy_key_spec: u32,
+        flags: u32,
+    ) -> SecurityStatus;
+    fn NCryptExportKey(
+        key: NcryptHandle,
+        export_key: NcryptHandle,
+        blob_type: *const u16,
+        parameter_list: *const c_void,
+        output: *mut u8,
+        output_len: u32,
+        result_len: *mut u32,
+        flags: u32,
+    ) -> SecurityStatus;
+    fn NCryptSignHash(
+        key: NcryptHandle,
+        padding_info: *const c_void,
+        hash_value: *const u8,
+        hash_value_len: u32,
+        signature: *mut u8,
+        signature_len: u32,
+        result_len: *mut u32,
+        flags: u32,
+    ) -> SecurityStatus;
+    fn NCryptFreeBuffer(input: *mut c_void) -> SecurityStatus;
+    fn NCryptFreeObject(object: NcryptHandle) -> SecurityStatus;
+}
+
+// The providers whose keys are listed. Only the software provider is always
+// present, the others need a TPM or a smart card reader.
+const PROVIDERS: &[(&str, bool)] = &[
+    ("Microsoft Software Key Storage Provider", true),
+    ("Microsoft Platform Crypto Provider", false),
+    ("Microsoft Smart Card Key Storage Provider", false),
+];
+
+struct Provider(NcryptHandle);
+
+impl Drop for Provider {
+    fn drop(&mut self) {
+        unsafe {
+            NCryptFreeObject(self.0);
+        }
+    }
+}
+
+pub(crate) struct KeyHandle {
+    key: NcryptHandle,
+    // Keys are opened from a provider, which is kept open for as long as
+    // they are.
+    _provider: Arc<Provider>,
+}
+
+impl Drop for KeyHandle {
+    fn drop(&mut self) {
+        unsafe {
+            NCryptFreeObject(self.key);
+        }
+    }
+}
+
+fn check(function: &'static str, status: SecurityStatus) -> KeystoreResult<()> {
+    if status == 0 {
+        Ok(())
+    } else {
+        Err(KeystoreError::Os(function, status.into()))
+    }
+}
+
+fn wide(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain(Some(0)).collect()
+}
+
+// Reads a NUL terminated UTF-16 string.
+unsafe fn from_wide(s: *const u16) -> String {
+    let mut len = 0;
+    while *s.add(len) != 0 {
+        len += 1;
+    }
+    String::from_utf16_lossy(std::slice::from_raw_parts(s, len))
+}
+
+pub(crate) fn list_keys() -> KeystoreResult<Vec<Key>> {
+    let mut keys = vec![];
+    for (name, required) in PROVIDERS {
+        match list_provider_keys(name, &mut keys) {
+            Ok(()) => {}
+            Err(_) if !required => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(keys)
+}
+
+fn list_provider_keys(provider_name: &str, keys: &mut Vec<Key>) -> KeystoreResult<()> {
+    let provider_name = wide(provider_name);
+    let mut handle = 0;
+    check("NCryptOpenStorageProvider", unsafe {
+        NCryptOpenStorageProvider(&mut handle, provider_name.as_ptr(), 0)
+    })?;
+    let provider = Arc::new(Provider(handle));
+
+    let mut state: *mut c_void = ptr::null_mut();
+    let result = loop {
+        let mut key_name: *mut NcryptKeyName = ptr::null_mut();
+        let status = unsafe {
+            NCryptEnumKeys(
+                provider.0,
+                ptr::null(),
+                &mut key_name,
+                &mut state,
+                NCRYPT_SILENT_FLAG,
+            )
+        };
+        if status == NTE_NO_MORE_ITEMS {
+            break Ok(());
+        }
+        if let Err(e) = check("NCryptEnumKeys", status) {
+            break Err(e);
+        }
+        let (name, legacy_key_spec) =
+            unsafe { (from_wide((*key_name).name), (*key_name).legacy_key_spec) };
+        unsafe {
+            NCryptFreeBuffer(key_name.cast());
+        }
+        // Keys that can't be opened, or whose public key isn't RSA or EC,
+        // are skipped rather than failing the whole listing.
+        if let Ok(key) = open_key(&provider, name, legacy_key_spec) {
+            keys.push(key);
+        }
+    };
+    if !state.is_null() {
+        unsafe {
+            NCryptFreeBuffer(state);
+        }
+    }
+    result
+}
+
+fn open_key(provider: &Arc<Provider>, name: String, legacy_key_spec: u32) -> KeystoreResult<Key> {
+    let wide_name = wide(&name);
+    let mut key = 0;
+    check("NCryptOpenKey", unsafe {
+        NCryptOpenKey(
+            provider.0,
+            &mut key,
+            wide_name.as_ptr(),
+            legacy_key_spec,
+            NCRYPT_SILENT_FLAG,
+        )
+    })?;
+    let handle = KeyHandle {
+        key,
+        _provider: Arc::clone(provider),
+    };
+
+    let public_key = match export_public_key(&handle, "RSAPUBLICBLOB") {
+        Ok(blob) => parse_rsa_blob(&blob)?,
+        Err(_) => parse_ecc_blob(&export_public_key(&handle, "ECCPUBLICBLOB")?)?,
+    };
+    Ok(Key {
+        name,
+        public_key,
+        handle,
+    })
+}
+
+fn export_public_key(handle: &KeyHandle, blob_type: &str) -> KeystoreResult<Vec<u8>> {
+    let blob_type = wide(blob_type);
+    let mut len = 0;
+    check("NCryptExportKey", unsafe {
+        NCryptExportKey(
+            handle.key,
+            0,
+            blob_type.as_ptr(),
+            ptr::null(),
+            ptr::null_mut(),
+            0,
+            &mut len,
+            NCRYPT_SILENT_FLAG,
+        )
+    })?;
+    let mut blob = vec![0; len as usize];
+    check("NCryptExportKey", unsafe {
+        NCryptExportKey(
+            handle.key,
+            0,
+            blob_type.as_ptr(),
+            ptr::null(),
+            blob.as_mut_ptr(),
+            len,
+            &mut len,
+            NCRYPT_SILENT_FLAG,
+        )
+    })?;
+    blob.truncate(len as usize);
+    Ok(blob)
+}
+
+fn invalid_blob() -> KeystoreError {
+    KeystoreError::Os("NCryptExportKey", 0)
+}
+
+// Reads the little endian u32 fields at the start of a key blob.
+fn blob_fields<const N: usize>(blob: &[u8]) -> KeystoreResult<[usize; N]> {
+    if blob.len() < N * 4 {
+        return Err(invalid_blob());
+    }
+    let mut fields = [0; N];
+    for (i, field) in fields.iter_mut().enumerate() {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(&blob[i * 4..i * 4 + 4]);
+        *field = u32::from_le_bytes(bytes) as usize;
+    }
+    Ok(fields)
+}
+
+// A BCRYPT_RSAKEY_BLOB: the magic, the modulus length in bits, the lengths
+// of the public exponent, modulus and primes, followed by the big endian
+// public exponent and modulus.
+fn parse_rsa_blob(blob: &[u8]) -> KeystoreResult<PublicKey> {
+    let [magic, _, e_len, n_len, _, _] = blob_fields::<6>(blob)?;
+    let e_start = 24;
+    let n_start = e_start + e_len;
+    if magic != BCRYPT_RSAPUBLIC_MAGIC as usize || blob.len() < n_start + n_len {
+        return Err(invalid_blob());
+    }
+    Ok(PublicKey::Rsa {
+        n: blob[n_start..n_start + n_len].to_vec(),
+        e: blob[e_start..n_start].to_vec(),
+    })
+}
+
+// A BCRYPT_ECCKEY_BLOB: the magic, which identifies the curve, and the
+// coordinate length, followed by the big endian x and y coordinates.
+fn parse_ecc_blob(blob: &[u8]) -> KeystoreResult<PublicKey> {
+    let [magic, len] = blob_fields::<2>(blob)?;
+    let curve = match magic as u32 {
+        BCRYPT_ECDSA_PUBLIC_P256_MAGIC => Curve::P256,
+        BCRYPT_ECDSA_PUBLIC_P384_MAGIC => Curve::P384,
+        BCRYPT_ECDSA_PUBLIC_P521_MAGIC => Curve::P521,
+        _ => return Err(invalid_blob()),
+    };
+    if blob.len() < 8 + len * 2 {
+        return Err(invalid_blob());
+    }
+    let mut point = vec![0x04];
+    point.extend_from_slice(&blob[8..8 + len * 2]);
+    Ok(PublicKey::Ec { curve, point })
+}
+
+pub(crate) fn sign(
+    handle: &KeyHandle,
+    public_key: &PublicKey,
+    hash: HashAlgorithm,
+    digest: &[u8],
+) -> KeystoreResult<Vec<u8>> {
+    let alg_id = wide(match hash {
+        HashAlgorithm::Sha1 => "SHA1",
+        HashAlgorithm::Sha256 => "SHA256",
+        HashAlgorithm::Sha384 => "SHA384",
+        HashAlgorithm::Sha512 => "SHA512",
+    });
+    let pkcs1 = BcryptPkcs1PaddingInfo {
+        alg_id: alg_id.as_ptr(),
+    };
+    let (padding_info, flags): (*const c_void, u32) = match public_key {
+        PublicKey::Ec { .. } => (ptr::null(), 0),
+        PublicKey::Rsa { .. } | PublicKey::RsaPkcs1(_) => (
+            (&pkcs1 as *const BcryptPkcs1PaddingInfo).cast(),
+            BCRYPT_PAD_PKCS1,
+        ),
+    };
+
+    let mut len = 0;
+    check("NCryptSignHash", unsafe {
+        NCryptSignHash(
+            handle.key,
+            padding_info,
+            digest.as_ptr(),
+            digest.len() as u32,
+            ptr::null_mut(),
+            0,
+            &mut len,
+            flags,
+        )
+    })?;
+    let mut signature = vec![0; len as usize];
+    check("NCryptSignHash", unsafe {
+        NCryptSignHash(
+            handle.key,
+            padding_info,
+            digest.as_ptr(),
+            digest.len() as u32,
+            signature.as_mut_ptr(),
+            len,
+            &mut len,
+            flags,
+        )
+    })?;
+    signature.truncate(len as usize);
+    Ok(signature)
+}
@@ -0,0 +1,37 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+use std::os::raw::c_long;
+
+use foreign_types_shared::ForeignType;
+
+use crate::{cvt_p, OpenSSLResult};
+
+/// Parses a DER encoded DSA private key in OpenSSL's traditional
+/// `DSAPrivateKey` format, which the openssl crate only exposes for PEM.
+pub fn private_key_from_der(
+    data: &[u8],
+) -> OpenSSLResult<openssl::dsa::Dsa<openssl::pkey::Private>> {
+    let len = std::cmp::min(data.len(), c_long::MAX as usize) as c_long;
+    let mut p = data.as_ptr();
+    // SAFETY: `p` points to `len` readable bytes, and d2i_DSAPrivateKey
+    // returns a newly allocated DSA, which is owned by the returned value.
+    unsafe {
+        let dsa = cvt_p(ffi::d2i_DSAPrivateKey(std::ptr::null_mut(), &mut p, len))?;
+        Ok(openssl::dsa::Dsa::from_ptr(dsa))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_private_key_from_der() {
+        let dsa = openssl::dsa::Dsa::generate(1024).unwrap();
+        let der = dsa.private_key_to_der().unwrap();
+        let parsed = super::private_key_from_der(&der).unwrap();
+        assert_eq!(parsed.priv_key(), dsa.priv_key());
+
+        assert!(super::private_key_from_der(&der[..der.len() - 1]).is_err());
+    }
+}
@@ -0,0 +1,23 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+use std::os::raw::c_int;
+
+use foreign_types_shared::ForeignTypeRef;
+
+use crate::{cvt, OpenSSLResult};
+
+extern "C" {
+    fn EC_KEY_precompute_mult(key: *mut ffi::EC_KEY, ctx: *mut ffi::BN_CTX) -> c_int;
+}
+
+/// Precomputes multiples of the generator of `key`'s group, which speeds up
+/// subsequent scalar multiplications with it. The table is stored in the
+/// group owned by `key`.
+pub fn precompute_mult<T>(key: &mut openssl::ec::EcKeyRef<T>) -> OpenSSLResult<()> {
+    // SAFETY: `key` is a valid EC_KEY, and a NULL BN_CTX makes OpenSSL
+    // allocate a temporary one.
+    cvt(unsafe { EC_KEY_precompute_mult(key.as_ptr(), std::ptr::null_mut()) })?;
+    Ok(())
+}
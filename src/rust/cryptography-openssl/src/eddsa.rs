@@ -0,0 +1,93 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! The EdDSA variants from RFC 8032 that take a context string or sign a
+//! SHA-512/SHAKE256 hash of the message, available as of OpenSSL 3.2.
+
+use std::os::raw::{c_char, c_void};
+
+use foreign_types_shared::ForeignTypeRef;
+
+use crate::params::{
+    EVP_PKEY_CTX_set_params, OSSL_PARAM_construct_end, OSSL_PARAM_construct_octet_string,
+    OSSL_PARAM_construct_utf8_string,
+};
+use crate::{cvt, OpenSSLResult};
+
+// OSSL_SIGNATURE_PARAM_INSTANCE and OSSL_SIGNATURE_PARAM_CONTEXT_STRING
+const INSTANCE: *const c_char = b"instance\0".as_ptr().cast();
+const CONTEXT_STRING: *const c_char = b"context-string\0".as_ptr().cast();
+
+#[derive(Clone, Copy)]
+pub enum Instance {
+    Ed25519ctx,
+    Ed25519ph,
+    Ed448,
+    Ed448ph,
+}
+
+impl Instance {
+    fn name(self) -> &'static [u8] {
+        match self {
+            Instance::Ed25519ctx => b"Ed25519ctx\0",
+            Instance::Ed25519ph => b"Ed25519ph\0",
+            Instance::Ed448 => b"Ed448\0",
+            Instance::Ed448ph => b"Ed448ph\0",
+        }
+    }
+}
+
+fn set_instance<T>(
+    ctx: &openssl::pkey_ctx::PkeyCtxRef<T>,
+    instance: Instance,
+    context: &[u8],
+) -> OpenSSLResult<()> {
+    // SAFETY: The keys and the instance name are NUL terminated strings, and
+    // the name and `context` outlive the parameter array that points to them.
+    // OpenSSL copies the context and doesn't write to either.
+    let params = unsafe {
+        [
+            OSSL_PARAM_construct_utf8_string(INSTANCE, instance.name().as_ptr() as *mut c_char, 0),
+            OSSL_PARAM_construct_octet_string(
+                CONTEXT_STRING,
+                context.as_ptr() as *mut c_void,
+                context.len(),
+            ),
+            OSSL_PARAM_construct_end(),
+        ]
+    };
+    // SAFETY: `ctx` is a valid context and `params` is terminated by an end
+    // marker.
+    cvt(unsafe { EVP_PKEY_CTX_set_params(ctx.as_ptr(), params.as_ptr()) })?;
+    Ok(())
+}
+
+/// Signs `data` with the `instance` variant of EdDSA and `context`. For the
+/// prehash variants `data` is the whole message, which OpenSSL hashes.
+pub fn sign(
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+    instance: Instance,
+    context: &[u8],
+    data: &[u8],
+) -> OpenSSLResult<Vec<u8>> {
+    let mut ctx = openssl::md_ctx::MdCtx::new()?;
+    set_instance(ctx.digest_sign_init(None, pkey)?, instance, context)?;
+    let mut signature = vec![];
+    ctx.digest_sign_to_vec(data, &mut signature)?;
+    Ok(signature)
+}
+
+/// Verifies a signature created by `sign` with the same `instance` and
+/// `context`.
+pub fn verify(
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Public>,
+    instance: Instance,
+    context: &[u8],
+    signature: &[u8],
+    data: &[u8],
+) -> OpenSSLResult<bool> {
+    let mut ctx = openssl::md_ctx::MdCtx::new()?;
+    set_instance(ctx.digest_verify_init(None, pkey)?, instance, context)?;
+    ctx.digest_verify(data, signature)
+}
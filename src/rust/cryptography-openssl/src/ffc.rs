@@ -0,0 +1,186 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! FIPS 186-4 verifiable generation and validation of DSA domain parameters.
+//! `p` and `q` are derived from a seed, and anyone with the seed and the
+//! counter can check that they were generated that way.
+
+use std::os::raw::{c_char, c_int};
+
+use foreign_types_shared::{ForeignType, ForeignTypeRef};
+
+use crate::params::{
+    EVP_PKEY_CTX_new_from_name, EVP_PKEY_CTX_new_from_pkey, EVP_PKEY_CTX_set_params,
+    EVP_PKEY_fromdata, EVP_PKEY_fromdata_init, EVP_PKEY_generate, EVP_PKEY_get_int_param,
+    EVP_PKEY_get_octet_string_param, EVP_PKEY_param_check, EVP_PKEY_paramgen_init,
+    OSSL_PARAM_BLD_free, OSSL_PARAM_BLD_new, OSSL_PARAM_BLD_push_BN, OSSL_PARAM_BLD_push_int,
+    OSSL_PARAM_BLD_push_octet_string, OSSL_PARAM_BLD_to_param, OSSL_PARAM_construct_end,
+    OSSL_PARAM_construct_size_t, OSSL_PARAM_construct_utf8_string, OSSL_PARAM_free, OsslParamBld,
+};
+use crate::{cvt, cvt_p, OpenSSLResult};
+
+const DSA: *const c_char = b"DSA\0".as_ptr().cast();
+// OSSL_PKEY_PARAM_FFC_*
+const TYPE: *const c_char = b"type\0".as_ptr().cast();
+const PBITS: *const c_char = b"pbits\0".as_ptr().cast();
+const QBITS: *const c_char = b"qbits\0".as_ptr().cast();
+const P: *const c_char = b"p\0".as_ptr().cast();
+const Q: *const c_char = b"q\0".as_ptr().cast();
+const G: *const c_char = b"g\0".as_ptr().cast();
+const SEED: *const c_char = b"seed\0".as_ptr().cast();
+const PCOUNTER: *const c_char = b"pcounter\0".as_ptr().cast();
+// OSSL_FFC_PARAM_TYPE_DSA
+const FIPS186_4: &[u8] = b"fips186_4\0";
+// OSSL_KEYMGMT_SELECT_ALL_PARAMETERS
+const SELECT_ALL_PARAMETERS: c_int = 0x04 | 0x80;
+
+// The largest seed is the length of the largest `q`, 256 bits.
+const MAX_SEED_LEN: usize = 32;
+
+struct ParamBld(*mut OsslParamBld);
+
+impl Drop for ParamBld {
+    fn drop(&mut self) {
+        // SAFETY: The builder was allocated by `OSSL_PARAM_BLD_new`.
+        unsafe { OSSL_PARAM_BLD_free(self.0) }
+    }
+}
+
+/// Generates DSA parameters with an `L` of `bits` as described in FIPS
+/// 186-4 appendix A.1.1.2, returning them with the seed and counter needed
+/// to validate them. Like `DSA_generate_parameters_ex`, `N` is 160 for an
+/// `L` below 2048 and 256 otherwise, and the hash is the one OpenSSL picks
+/// for `N`.
+pub fn generate_verifiable_dsa_params(
+    bits: u32,
+) -> OpenSSLResult<(openssl::dsa::Dsa<openssl::pkey::Params>, Vec<u8>, i32)> {
+    // SAFETY: `DSA` is a NUL terminated string and the other arguments may
+    // be null. The returned context is owned by `ctx`.
+    let ctx: openssl::pkey_ctx::PkeyCtx<()> = unsafe {
+        openssl::pkey_ctx::PkeyCtx::from_ptr(cvt_p(EVP_PKEY_CTX_new_from_name(
+            std::ptr::null_mut(),
+            DSA,
+            std::ptr::null(),
+        ))?)
+    };
+    // SAFETY: `ctx` is a valid context.
+    cvt(unsafe { EVP_PKEY_paramgen_init(ctx.as_ptr()) })?;
+
+    let mut pbits = bits as usize;
+    let mut qbits: usize = if bits < 2048 { 160 } else { 256 };
+    // SAFETY: The keys are NUL terminated strings, and `FIPS186_4`, `pbits`
+    // and `qbits` outlive the parameter array that points to them. OpenSSL
+    // doesn't write to the string.
+    let params = unsafe {
+        [
+            OSSL_PARAM_construct_utf8_string(TYPE, FIPS186_4.as_ptr() as *mut c_char, 0),
+            OSSL_PARAM_construct_size_t(PBITS, &mut pbits),
+            OSSL_PARAM_construct_size_t(QBITS, &mut qbits),
+            OSSL_PARAM_construct_end(),
+        ]
+    };
+    // SAFETY: `ctx` is a valid context and `params` is terminated by an end
+    // marker.
+    cvt(unsafe { EVP_PKEY_CTX_set_params(ctx.as_ptr(), params.as_ptr()) })?;
+
+    let mut pkey = std::ptr::null_mut();
+    // SAFETY: `ctx` was initialized for parameter generation, and the
+    // generated key is owned by `pkey` afterwards.
+    let pkey: openssl::pkey::PKey<openssl::pkey::Params> = unsafe {
+        cvt(EVP_PKEY_generate(ctx.as_ptr(), &mut pkey))?;
+        openssl::pkey::PKey::from_ptr(pkey)
+    };
+
+    let mut seed = vec![0; MAX_SEED_LEN];
+    let mut seed_len = 0;
+    let mut counter = 0;
+    // SAFETY: `seed` is `MAX_SEED_LEN` bytes long, and the keys are NUL
+    // terminated strings.
+    unsafe {
+        cvt(EVP_PKEY_get_octet_string_param(
+            pkey.as_ptr(),
+            SEED,
+            seed.as_mut_ptr(),
+            seed.len(),
+            &mut seed_len,
+        ))?;
+        cvt(EVP_PKEY_get_int_param(
+            pkey.as_ptr(),
+            PCOUNTER,
+            &mut counter,
+        ))?;
+    }
+    seed.truncate(seed_len);
+
+    Ok((pkey.dsa()?, seed, counter))
+}
+
+/// Checks that DSA parameters were generated from `seed` and `counter` as
+/// described in FIPS 186-4 appendix A.1.1.3. `g` can't be verified without
+/// its index, so only the partial validation from appendix A.2.2 is done
+/// for it.
+pub fn validate_dsa_params(
+    dsa: &openssl::dsa::DsaRef<openssl::pkey::Params>,
+    seed: &[u8],
+    counter: i32,
+) -> OpenSSLResult<bool> {
+    // SAFETY: The builder is owned by `bld`.
+    let bld = ParamBld(cvt_p(unsafe { OSSL_PARAM_BLD_new() })?);
+    // SAFETY: The keys are NUL terminated strings, and the builder copies
+    // the values.
+    let params = unsafe {
+        cvt(OSSL_PARAM_BLD_push_BN(bld.0, P, dsa.p().as_ptr()))?;
+        cvt(OSSL_PARAM_BLD_push_BN(bld.0, Q, dsa.q().as_ptr()))?;
+        cvt(OSSL_PARAM_BLD_push_BN(bld.0, G, dsa.g().as_ptr()))?;
+        cvt(OSSL_PARAM_BLD_push_octet_string(
+            bld.0,
+            SEED,
+            seed.as_ptr().cast(),
+            seed.len(),
+        ))?;
+        cvt(OSSL_PARAM_BLD_push_int(bld.0, PCOUNTER, counter))?;
+        cvt_p(OSSL_PARAM_BLD_to_param(bld.0))?
+    };
+
+    let mut pkey = std::ptr::null_mut();
+    // SAFETY: `params` was created by the builder and is freed once the key
+    // has been created from it. The created key is owned by `pkey`.
+    let pkey: openssl::pkey::PKey<openssl::pkey::Params> = unsafe {
+        let ctx: openssl::pkey_ctx::PkeyCtx<()> = openssl::pkey_ctx::PkeyCtx::from_ptr(cvt_p(
+            EVP_PKEY_CTX_new_from_name(std::ptr::null_mut(), DSA, std::ptr::null()),
+        )?);
+        let result = cvt(EVP_PKEY_fromdata_init(ctx.as_ptr())).and_then(|_| {
+            cvt(EVP_PKEY_fromdata(
+                ctx.as_ptr(),
+                &mut pkey,
+                SELECT_ALL_PARAMETERS,
+                params,
+            ))
+        });
+        OSSL_PARAM_free(params);
+        result?;
+        openssl::pkey::PKey::from_ptr(pkey)
+    };
+
+    // SAFETY: `pkey` is a valid key, and the returned context is owned by
+    // `ctx`.
+    let ctx: openssl::pkey_ctx::PkeyCtx<openssl::pkey::Params> = unsafe {
+        openssl::pkey_ctx::PkeyCtx::from_ptr(cvt_p(EVP_PKEY_CTX_new_from_pkey(
+            std::ptr::null_mut(),
+            pkey.as_ptr(),
+            std::ptr::null(),
+        ))?)
+    };
+    // SAFETY: `ctx` is a valid context. A negative result is an error, and
+    // zero means the parameters are invalid.
+    match unsafe { EVP_PKEY_param_check(ctx.as_ptr()) } {
+        1 => Ok(true),
+        0 => {
+            // Drain the reason for the failure from the error queue.
+            let _ = openssl::error::ErrorStack::get();
+            Ok(false)
+        }
+        _ => Err(openssl::error::ErrorStack::get()),
+    }
+}
@@ -0,0 +1,140 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Key encapsulation with `EVP_PKEY_encapsulate` and `EVP_PKEY_decapsulate`.
+//! RSA and EC keys need an explicit KEM operation, while ML-KEM keys have a
+//! single one. DHKEM is available as of OpenSSL 3.2.
+
+use std::os::raw::c_char;
+
+use foreign_types_shared::ForeignTypeRef;
+
+use crate::params::{
+    EVP_PKEY_decapsulate, EVP_PKEY_decapsulate_init, EVP_PKEY_encapsulate,
+    EVP_PKEY_encapsulate_init, OSSL_PARAM_construct_end, OSSL_PARAM_construct_utf8_string,
+    OsslParam,
+};
+use crate::{cvt, OpenSSLResult};
+
+// OSSL_KEM_PARAM_OPERATION
+const OPERATION: *const c_char = b"operation\0".as_ptr().cast();
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Operation {
+    /// RSASVE from NIST SP 800-56B, where the shared secret is a random
+    /// integer encrypted with raw RSA.
+    RsaSve,
+    /// DHKEM from RFC 9180, for X25519, X448, and P-256, P-384 and P-521
+    /// keys.
+    DhKem,
+}
+
+impl Operation {
+    fn name(self) -> *const c_char {
+        let name: &[u8] = match self {
+            Operation::RsaSve => b"RSASVE\0",
+            Operation::DhKem => b"DHKEM\0",
+        };
+        name.as_ptr().cast()
+    }
+}
+
+fn with_operation<R>(operation: Option<Operation>, f: impl FnOnce(*const OsslParam) -> R) -> R {
+    match operation {
+        Some(operation) => {
+            // SAFETY: The key and the operation name are NUL terminated
+            // static strings, which OpenSSL doesn't write to.
+            let params = unsafe {
+                [
+                    OSSL_PARAM_construct_utf8_string(OPERATION, operation.name() as *mut c_char, 0),
+                    OSSL_PARAM_construct_end(),
+                ]
+            };
+            f(params.as_ptr())
+        }
+        None => f(std::ptr::null()),
+    }
+}
+
+/// Encapsulates a fresh shared secret to `pkey`, returning the shared secret
+/// and the ciphertext.
+pub fn encapsulate<T: openssl::pkey::HasPublic>(
+    pkey: &openssl::pkey::PKeyRef<T>,
+    operation: Option<Operation>,
+) -> OpenSSLResult<(Vec<u8>, Vec<u8>)> {
+    let ctx = openssl::pkey_ctx::PkeyCtx::new(pkey)?;
+    with_operation(operation, |params| {
+        // SAFETY: `ctx` is a valid context and the parameters, if any, are
+        // terminated by an end marker.
+        cvt(unsafe { EVP_PKEY_encapsulate_init(ctx.as_ptr(), params) })
+    })?;
+    let mut ciphertext_len = 0;
+    let mut secret_len = 0;
+    // SAFETY: `ctx` is a valid context, and passing null output buffers
+    // queries the lengths.
+    cvt(unsafe {
+        EVP_PKEY_encapsulate(
+            ctx.as_ptr(),
+            std::ptr::null_mut(),
+            &mut ciphertext_len,
+            std::ptr::null_mut(),
+            &mut secret_len,
+        )
+    })?;
+    let mut ciphertext = vec![0; ciphertext_len];
+    let mut secret = vec![0; secret_len];
+    // SAFETY: The buffers are the lengths that OpenSSL asked for.
+    cvt(unsafe {
+        EVP_PKEY_encapsulate(
+            ctx.as_ptr(),
+            ciphertext.as_mut_ptr(),
+            &mut ciphertext_len,
+            secret.as_mut_ptr(),
+            &mut secret_len,
+        )
+    })?;
+    ciphertext.truncate(ciphertext_len);
+    secret.truncate(secret_len);
+    Ok((secret, ciphertext))
+}
+
+/// Recovers the shared secret from a ciphertext created by `encapsulate`
+/// with the same `operation`.
+pub fn decapsulate(
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+    operation: Option<Operation>,
+    ciphertext: &[u8],
+) -> OpenSSLResult<Vec<u8>> {
+    let ctx = openssl::pkey_ctx::PkeyCtx::new(pkey)?;
+    with_operation(operation, |params| {
+        // SAFETY: `ctx` is a valid context and the parameters, if any, are
+        // terminated by an end marker.
+        cvt(unsafe { EVP_PKEY_decapsulate_init(ctx.as_ptr(), params) })
+    })?;
+    let mut secret_len = 0;
+    // SAFETY: `ctx` is a valid context, and passing a null output buffer
+    // queries the length.
+    cvt(unsafe {
+        EVP_PKEY_decapsulate(
+            ctx.as_ptr(),
+            std::ptr::null_mut(),
+            &mut secret_len,
+            ciphertext.as_ptr(),
+            ciphertext.len(),
+        )
+    })?;
+    let mut secret = vec![0; secret_len];
+    // SAFETY: `secret` is the length that OpenSSL asked for.
+    cvt(unsafe {
+        EVP_PKEY_decapsulate(
+            ctx.as_ptr(),
+            secret.as_mut_ptr(),
+            &mut secret_len,
+            ciphertext.as_ptr(),
+            ciphertext.len(),
+        )
+    })?;
+    secret.truncate(secret_len);
+    Ok(secret)
+}
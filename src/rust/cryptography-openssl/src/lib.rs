@@ -7,10 +7,39 @@
 #[cfg(CRYPTOGRAPHY_IS_BORINGSSL)]
 pub mod aead;
 pub mod cmac;
+pub mod dsa;
+#[cfg(not(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER))]
+pub mod ec;
+#[cfg(CRYPTOGRAPHY_OPENSSL_320_OR_GREATER)]
+pub mod eddsa;
+#[cfg(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER)]
+pub mod ffc;
 pub mod fips;
 pub mod hmac;
+#[cfg(CRYPTOGRAPHY_OPENSSL_320_OR_GREATER)]
+pub mod kem;
+#[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+pub mod mldsa;
+#[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+pub mod mlkem;
+pub mod nid;
+#[cfg(CRYPTOGRAPHY_OPENSSL_320_OR_GREATER)]
+pub mod nonce;
+pub mod paramgen;
+#[cfg(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER)]
+mod params;
 #[cfg(any(CRYPTOGRAPHY_IS_BORINGSSL, CRYPTOGRAPHY_IS_LIBRESSL))]
 pub mod poly1305;
+#[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
+pub mod rsa;
+#[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
+pub mod secure_heap;
+#[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+pub mod slhdsa;
+#[cfg(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER)]
+pub mod sm2;
+#[cfg(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER)]
+pub mod store;
 
 pub type OpenSSLResult<T> = Result<T, openssl::error::ErrorStack>;
 
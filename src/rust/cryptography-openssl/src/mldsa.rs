@@ -0,0 +1,203 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! ML-DSA, the module-lattice-based signature scheme from FIPS 204,
+//! available as of OpenSSL 3.5. The keys only exist in OpenSSL's providers,
+//! so they're created by algorithm name rather than with an `Id`.
+
+use std::os::raw::{c_char, c_int, c_void};
+
+use foreign_types_shared::{ForeignType, ForeignTypeRef};
+
+use crate::params::{
+    EVP_PKEY_CTX_new_from_name, EVP_PKEY_CTX_set_params, EVP_PKEY_fromdata, EVP_PKEY_fromdata_init,
+    EVP_PKEY_generate, EVP_PKEY_get_octet_string_param, EVP_PKEY_is_a,
+    EVP_PKEY_new_raw_public_key_ex, OSSL_PARAM_construct_end, OSSL_PARAM_construct_octet_string,
+};
+use crate::{cvt, cvt_p, OpenSSLResult};
+
+// OSSL_PKEY_PARAM_ML_DSA_SEED
+const SEED: *const c_char = b"seed\0".as_ptr().cast();
+// OSSL_SIGNATURE_PARAM_CONTEXT_STRING
+const CONTEXT_STRING: *const c_char = b"context-string\0".as_ptr().cast();
+// OSSL_KEYMGMT_SELECT_KEYPAIR
+const SELECT_KEYPAIR: c_int = 0x01 | 0x02;
+
+/// The length of the seed `ξ` that a key pair is derived from.
+pub const SEED_LENGTH: usize = 32;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Variant {
+    MlDsa44,
+    MlDsa65,
+    MlDsa87,
+}
+
+impl Variant {
+    fn name(self) -> *const c_char {
+        let name: &[u8] = match self {
+            Variant::MlDsa44 => b"ML-DSA-44\0",
+            Variant::MlDsa65 => b"ML-DSA-65\0",
+            Variant::MlDsa87 => b"ML-DSA-87\0",
+        };
+        name.as_ptr().cast()
+    }
+
+    /// The parameter set of `pkey`, or `None` if it isn't an ML-DSA key.
+    pub fn from_pkey<T>(pkey: &openssl::pkey::PKeyRef<T>) -> Option<Variant> {
+        // `EVP_PKEY_is_a` matches every name for an empty `EVP_PKEY`, whose
+        // type is `EVP_PKEY_NONE`, so only ask about keys which have a type.
+        if pkey.id() == openssl::pkey::Id::from_raw(ffi::NID_undef) {
+            return None;
+        }
+        [Variant::MlDsa44, Variant::MlDsa65, Variant::MlDsa87]
+            .into_iter()
+            // SAFETY: `pkey` is a valid key and the name is a NUL terminated
+            // string.
+            .find(|v| unsafe { EVP_PKEY_is_a(pkey.as_ptr(), v.name()) } == 1)
+    }
+}
+
+fn new_ctx(variant: Variant) -> OpenSSLResult<openssl::pkey_ctx::PkeyCtx<()>> {
+    // SAFETY: The name is a NUL terminated string and the other arguments
+    // may be null. The returned context is owned by the `PkeyCtx`.
+    unsafe {
+        Ok(openssl::pkey_ctx::PkeyCtx::from_ptr(cvt_p(
+            EVP_PKEY_CTX_new_from_name(std::ptr::null_mut(), variant.name(), std::ptr::null()),
+        )?))
+    }
+}
+
+/// Generates a new key pair from a random seed.
+pub fn generate(variant: Variant) -> OpenSSLResult<openssl::pkey::PKey<openssl::pkey::Private>> {
+    let ctx = new_ctx(variant)?;
+    let mut pkey = std::ptr::null_mut();
+    // SAFETY: `ctx` is a valid context, and the generated key is owned by
+    // `pkey` afterwards.
+    unsafe {
+        cvt(ffi::EVP_PKEY_keygen_init(ctx.as_ptr()))?;
+        cvt(EVP_PKEY_generate(ctx.as_ptr(), &mut pkey))?;
+        Ok(openssl::pkey::PKey::from_ptr(pkey))
+    }
+}
+
+/// Derives the key pair for a `SEED_LENGTH` byte seed, as ML-DSA.KeyGen_internal
+/// does in FIPS 204.
+pub fn private_key_from_seed(
+    variant: Variant,
+    seed: &[u8],
+) -> OpenSSLResult<openssl::pkey::PKey<openssl::pkey::Private>> {
+    let ctx = new_ctx(variant)?;
+    // SAFETY: The key is a NUL terminated string, and `seed` outlives the
+    // parameter array that points to it. OpenSSL doesn't write to it.
+    let mut params = unsafe {
+        [
+            OSSL_PARAM_construct_octet_string(SEED, seed.as_ptr() as *mut c_void, seed.len()),
+            OSSL_PARAM_construct_end(),
+        ]
+    };
+    let mut pkey = std::ptr::null_mut();
+    // SAFETY: `ctx` is a valid context, `params` is terminated by an end
+    // marker and the created key is owned by `pkey` afterwards.
+    unsafe {
+        cvt(EVP_PKEY_fromdata_init(ctx.as_ptr()))?;
+        cvt(EVP_PKEY_fromdata(
+            ctx.as_ptr(),
+            &mut pkey,
+            SELECT_KEYPAIR,
+            params.as_mut_ptr(),
+        ))?;
+        Ok(openssl::pkey::PKey::from_ptr(pkey))
+    }
+}
+
+/// Creates a public key from its FIPS 204 encoding.
+pub fn public_key_from_raw_bytes(
+    variant: Variant,
+    data: &[u8],
+) -> OpenSSLResult<openssl::pkey::PKey<openssl::pkey::Public>> {
+    // SAFETY: The name is a NUL terminated string and OpenSSL copies the key.
+    // The returned key is owned by the `PKey`.
+    unsafe {
+        Ok(openssl::pkey::PKey::from_ptr(cvt_p(
+            EVP_PKEY_new_raw_public_key_ex(
+                std::ptr::null_mut(),
+                variant.name(),
+                std::ptr::null(),
+                data.as_ptr(),
+                data.len(),
+            ),
+        )?))
+    }
+}
+
+/// The seed the key pair was derived from. Keys loaded from an encoding of
+/// only the expanded private key don't have one.
+pub fn seed(pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>) -> Option<Vec<u8>> {
+    let mut seed = vec![0; SEED_LENGTH];
+    let mut seed_len = 0;
+    // SAFETY: `seed` is `SEED_LENGTH` bytes long and the key is a NUL
+    // terminated string.
+    let result = unsafe {
+        EVP_PKEY_get_octet_string_param(
+            pkey.as_ptr(),
+            SEED,
+            seed.as_mut_ptr(),
+            seed.len(),
+            &mut seed_len,
+        )
+    };
+    if result != 1 || seed_len != SEED_LENGTH {
+        // Drain the reason for the failure from the error queue.
+        let _ = openssl::error::ErrorStack::get();
+        return None;
+    }
+    Some(seed)
+}
+
+fn set_context<T>(ctx: &openssl::pkey_ctx::PkeyCtxRef<T>, context: &[u8]) -> OpenSSLResult<()> {
+    // SAFETY: The key is a NUL terminated string, and `context` outlives the
+    // parameter array that points to it. OpenSSL copies the context and
+    // doesn't write to it.
+    let params = unsafe {
+        [
+            OSSL_PARAM_construct_octet_string(
+                CONTEXT_STRING,
+                context.as_ptr() as *mut c_void,
+                context.len(),
+            ),
+            OSSL_PARAM_construct_end(),
+        ]
+    };
+    // SAFETY: `ctx` is a valid context and `params` is terminated by an end
+    // marker.
+    cvt(unsafe { EVP_PKEY_CTX_set_params(ctx.as_ptr(), params.as_ptr()) })?;
+    Ok(())
+}
+
+/// Signs `data` with the hedged variant of ML-DSA.Sign and `context`, which
+/// can be at most 255 bytes long.
+pub fn sign(
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+    context: &[u8],
+    data: &[u8],
+) -> OpenSSLResult<Vec<u8>> {
+    let mut ctx = openssl::md_ctx::MdCtx::new()?;
+    set_context(ctx.digest_sign_init(None, pkey)?, context)?;
+    let mut signature = vec![];
+    ctx.digest_sign_to_vec(data, &mut signature)?;
+    Ok(signature)
+}
+
+/// Verifies a signature created by `sign` with the same `context`.
+pub fn verify(
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Public>,
+    context: &[u8],
+    signature: &[u8],
+    data: &[u8],
+) -> OpenSSLResult<bool> {
+    let mut ctx = openssl::md_ctx::MdCtx::new()?;
+    set_context(ctx.digest_verify_init(None, pkey)?, context)?;
+    ctx.digest_verify(data, signature)
+}
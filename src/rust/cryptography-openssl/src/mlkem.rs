@@ -0,0 +1,130 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! ML-KEM, the module-lattice-based key encapsulation mechanism from FIPS
+//! 203, available as of OpenSSL 3.5. Like ML-DSA, the keys only exist in
+//! OpenSSL's providers and are created by algorithm name. Encapsulation and
+//! decapsulation are done with `crate::kem`.
+
+use std::os::raw::{c_char, c_int, c_void};
+
+use foreign_types_shared::{ForeignType, ForeignTypeRef};
+
+use crate::params::{
+    EVP_PKEY_CTX_new_from_name, EVP_PKEY_fromdata, EVP_PKEY_fromdata_init, EVP_PKEY_is_a,
+    EVP_PKEY_new_raw_public_key_ex, OSSL_PARAM_construct_end, OSSL_PARAM_construct_octet_string,
+};
+use crate::{cvt, cvt_p, OpenSSLResult};
+
+// OSSL_PKEY_PARAM_ML_KEM_SEED
+const SEED: *const c_char = b"seed\0".as_ptr().cast();
+// OSSL_KEYMGMT_SELECT_KEYPAIR
+const SELECT_KEYPAIR: c_int = 0x01 | 0x02;
+
+/// The length of the seed `d || z` that a key pair is derived from.
+pub const SEED_LENGTH: usize = 64;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Variant {
+    MlKem512,
+    MlKem768,
+    MlKem1024,
+}
+
+impl Variant {
+    fn name(self) -> *const c_char {
+        let name: &[u8] = match self {
+            Variant::MlKem512 => b"ML-KEM-512\0",
+            Variant::MlKem768 => b"ML-KEM-768\0",
+            Variant::MlKem1024 => b"ML-KEM-1024\0",
+        };
+        name.as_ptr().cast()
+    }
+
+    /// The parameter set of `pkey`, or `None` if it isn't an ML-KEM key.
+    pub fn from_pkey<T>(pkey: &openssl::pkey::PKeyRef<T>) -> Option<Variant> {
+        [Variant::MlKem512, Variant::MlKem768, Variant::MlKem1024]
+            .into_iter()
+            // SAFETY: `pkey` is a valid key and the name is a NUL terminated
+            // string.
+            .find(|v| unsafe { EVP_PKEY_is_a(pkey.as_ptr(), v.name()) } == 1)
+    }
+
+    /// The length of an encapsulation key.
+    pub fn public_key_length(self) -> usize {
+        match self {
+            Variant::MlKem512 => 800,
+            Variant::MlKem768 => 1184,
+            Variant::MlKem1024 => 1568,
+        }
+    }
+
+    /// The length of a ciphertext.
+    pub fn ciphertext_length(self) -> usize {
+        match self {
+            Variant::MlKem512 => 768,
+            Variant::MlKem768 => 1088,
+            Variant::MlKem1024 => 1568,
+        }
+    }
+}
+
+/// Derives the key pair for a `SEED_LENGTH` byte seed, as ML-KEM.KeyGen_internal
+/// does in FIPS 203.
+pub fn private_key_from_seed(
+    variant: Variant,
+    seed: &[u8],
+) -> OpenSSLResult<openssl::pkey::PKey<openssl::pkey::Private>> {
+    // SAFETY: The name is a NUL terminated string and the other arguments
+    // may be null. The returned context is owned by the `PkeyCtx`.
+    let ctx = unsafe {
+        openssl::pkey_ctx::PkeyCtx::<()>::from_ptr(cvt_p(EVP_PKEY_CTX_new_from_name(
+            std::ptr::null_mut(),
+            variant.name(),
+            std::ptr::null(),
+        ))?)
+    };
+    // SAFETY: The key is a NUL terminated string, and `seed` outlives the
+    // parameter array that points to it. OpenSSL doesn't write to it.
+    let mut params = unsafe {
+        [
+            OSSL_PARAM_construct_octet_string(SEED, seed.as_ptr() as *mut c_void, seed.len()),
+            OSSL_PARAM_construct_end(),
+        ]
+    };
+    let mut pkey = std::ptr::null_mut();
+    // SAFETY: `ctx` is a valid context, `params` is terminated by an end
+    // marker and the created key is owned by `pkey` afterwards.
+    unsafe {
+        cvt(EVP_PKEY_fromdata_init(ctx.as_ptr()))?;
+        cvt(EVP_PKEY_fromdata(
+            ctx.as_ptr(),
+            &mut pkey,
+            SELECT_KEYPAIR,
+            params.as_mut_ptr(),
+        ))?;
+        Ok(openssl::pkey::PKey::from_ptr(pkey))
+    }
+}
+
+/// Creates an encapsulation key from its FIPS 203 encoding. OpenSSL performs
+/// the modulus check on it.
+pub fn public_key_from_raw_bytes(
+    variant: Variant,
+    data: &[u8],
+) -> OpenSSLResult<openssl::pkey::PKey<openssl::pkey::Public>> {
+    // SAFETY: The name is a NUL terminated string and OpenSSL copies the key.
+    // The returned key is owned by the `PKey`.
+    unsafe {
+        Ok(openssl::pkey::PKey::from_ptr(cvt_p(
+            EVP_PKEY_new_raw_public_key_ex(
+                std::ptr::null_mut(),
+                variant.name(),
+                std::ptr::null(),
+                data.as_ptr(),
+                data.len(),
+            ),
+        )?))
+    }
+}
@@ -0,0 +1,21 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Lookups for objects whose NIDs vary between OpenSSL forks, or which are
+//! only present in some builds.
+
+use std::ffi::CString;
+
+/// Returns the NID OpenSSL has assigned to the object with the short name
+/// `sn`, or `None` if this build doesn't know about it.
+pub fn from_short_name(sn: &str) -> Option<openssl::nid::Nid> {
+    let sn = CString::new(sn).ok()?;
+    // SAFETY: `sn` is a valid NUL terminated string.
+    let nid = unsafe { ffi::OBJ_sn2nid(sn.as_ptr()) };
+    if nid == ffi::NID_undef {
+        None
+    } else {
+        Some(openssl::nid::Nid::from_raw(nid))
+    }
+}
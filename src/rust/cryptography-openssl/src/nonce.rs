@@ -0,0 +1,37 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Deterministic (RFC 6979) nonce generation for ECDSA and DSA signatures,
+//! available as of OpenSSL 3.2.
+
+use std::os::raw::{c_char, c_uint};
+
+use foreign_types_shared::ForeignTypeRef;
+
+use crate::params::{EVP_PKEY_CTX_set_params, OSSL_PARAM_construct_end, OSSL_PARAM_construct_uint};
+use crate::{cvt, OpenSSLResult};
+
+// OSSL_SIGNATURE_PARAM_NONCE_TYPE
+const NONCE_TYPE: *const c_char = b"nonce-type\0".as_ptr().cast();
+const NONCE_TYPE_DETERMINISTIC: c_uint = 1;
+
+/// Makes signatures created with `ctx` use a nonce derived from the private
+/// key and the digest being signed, as described in RFC 6979. `ctx` must
+/// already have been initialized for signing and had its signature digest
+/// set, since that digest is also used for the HMAC-DRBG.
+pub fn set_deterministic<T>(ctx: &openssl::pkey_ctx::PkeyCtxRef<T>) -> OpenSSLResult<()> {
+    let mut nonce_type = NONCE_TYPE_DETERMINISTIC;
+    // SAFETY: `NONCE_TYPE` is a NUL terminated string, and `nonce_type`
+    // outlives the parameter array that points to it.
+    let params = unsafe {
+        [
+            OSSL_PARAM_construct_uint(NONCE_TYPE, &mut nonce_type),
+            OSSL_PARAM_construct_end(),
+        ]
+    };
+    // SAFETY: `ctx` is a valid context and `params` is terminated by an end
+    // marker.
+    cvt(unsafe { EVP_PKEY_CTX_set_params(ctx.as_ptr(), params.as_ptr()) })?;
+    Ok(())
+}
@@ -0,0 +1,100 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! DH and DSA parameter generation with a callback for OpenSSL's progress
+//! indicators (see `BN_GENCB_call`). The callback is called with the stage
+//! and a counter, and generation is aborted if it returns `false`.
+
+use std::os::raw::{c_int, c_void};
+
+use foreign_types_shared::ForeignType;
+
+use crate::{cvt, cvt_p, OpenSSLResult};
+
+type GencbCallback = extern "C" fn(c_int, c_int, *mut ffi::BN_GENCB) -> c_int;
+
+extern "C" {
+    fn BN_GENCB_new() -> *mut ffi::BN_GENCB;
+    fn BN_GENCB_free(cb: *mut ffi::BN_GENCB);
+    fn BN_GENCB_set(gencb: *mut ffi::BN_GENCB, callback: GencbCallback, cb_arg: *mut c_void);
+    fn BN_GENCB_get_arg(cb: *mut ffi::BN_GENCB) -> *mut c_void;
+}
+
+extern "C" fn trampoline<F: FnMut(i32, i32) -> bool>(
+    stage: c_int,
+    count: c_int,
+    cb: *mut ffi::BN_GENCB,
+) -> c_int {
+    // SAFETY: The argument was set to a `F` in `Gencb::new`, which outlives
+    // the generation that calls this.
+    let callback = unsafe { &mut *(BN_GENCB_get_arg(cb) as *mut F) };
+    // Unwinding into OpenSSL is undefined behavior, so a panic aborts
+    // generation instead.
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| callback(stage, count)));
+    c_int::from(result.unwrap_or(false))
+}
+
+struct Gencb(*mut ffi::BN_GENCB);
+
+impl Gencb {
+    fn new<F: FnMut(i32, i32) -> bool>(callback: &mut F) -> OpenSSLResult<Gencb> {
+        // SAFETY: The `BN_GENCB` is owned by the returned `Gencb`, and the
+        // callback's type matches the trampoline's.
+        unsafe {
+            let cb = Gencb(cvt_p(BN_GENCB_new())?);
+            BN_GENCB_set(cb.0, trampoline::<F>, (callback as *mut F).cast());
+            Ok(cb)
+        }
+    }
+}
+
+impl Drop for Gencb {
+    fn drop(&mut self) {
+        // SAFETY: The `BN_GENCB` was allocated by `BN_GENCB_new`.
+        unsafe { BN_GENCB_free(self.0) }
+    }
+}
+
+pub fn generate_dh_params<F: FnMut(i32, i32) -> bool>(
+    prime_len: u32,
+    generator: u32,
+    mut callback: F,
+) -> OpenSSLResult<openssl::dh::Dh<openssl::pkey::Params>> {
+    let cb = Gencb::new(&mut callback)?;
+    // SAFETY: The DH is owned by `dh` as soon as it's allocated, so it's
+    // freed if generation fails.
+    unsafe {
+        let dh = openssl::dh::Dh::from_ptr(cvt_p(ffi::DH_new())?);
+        cvt(ffi::DH_generate_parameters_ex(
+            dh.as_ptr(),
+            prime_len as c_int,
+            generator as c_int,
+            cb.0,
+        ))?;
+        Ok(dh)
+    }
+}
+
+pub fn generate_dsa_params<F: FnMut(i32, i32) -> bool>(
+    bits: u32,
+    mut callback: F,
+) -> OpenSSLResult<openssl::dsa::Dsa<openssl::pkey::Params>> {
+    let cb = Gencb::new(&mut callback)?;
+    // SAFETY: The DSA is owned by `dsa` as soon as it's allocated, so it's
+    // freed if generation fails. No seed is passed, and the counter and `h`
+    // aren't returned.
+    unsafe {
+        let dsa = openssl::dsa::Dsa::from_ptr(cvt_p(ffi::DSA_new())?);
+        cvt(ffi::DSA_generate_parameters_ex(
+            dsa.as_ptr(),
+            bits as _,
+            std::ptr::null(),
+            0,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            cb.0,
+        ))?;
+        Ok(dsa)
+    }
+}
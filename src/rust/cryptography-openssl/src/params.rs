@@ -0,0 +1,143 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Declarations of the OpenSSL 3 `OSSL_PARAM` and provider based `EVP_PKEY`
+//! functions, which the version of openssl-sys we depend on doesn't bind.
+
+use std::os::raw::{c_char, c_int, c_uint, c_void};
+
+#[repr(C)]
+pub(crate) struct OsslParam {
+    key: *const c_char,
+    data_type: c_uint,
+    data: *mut c_void,
+    data_size: usize,
+    return_size: usize,
+}
+
+pub(crate) enum OsslParamBld {}
+
+extern "C" {
+    pub(crate) fn EVP_PKEY_CTX_new_from_name(
+        libctx: *mut c_void,
+        name: *const c_char,
+        propquery: *const c_char,
+    ) -> *mut ffi::EVP_PKEY_CTX;
+    pub(crate) fn EVP_PKEY_CTX_new_from_pkey(
+        libctx: *mut c_void,
+        pkey: *mut ffi::EVP_PKEY,
+        propquery: *const c_char,
+    ) -> *mut ffi::EVP_PKEY_CTX;
+    pub(crate) fn EVP_PKEY_CTX_set_params(
+        ctx: *mut ffi::EVP_PKEY_CTX,
+        params: *const OsslParam,
+    ) -> c_int;
+    #[cfg(CRYPTOGRAPHY_OPENSSL_320_OR_GREATER)]
+    pub(crate) fn EVP_PKEY_decapsulate(
+        ctx: *mut ffi::EVP_PKEY_CTX,
+        unwrapped: *mut u8,
+        unwrappedlen: *mut usize,
+        wrapped: *const u8,
+        wrappedlen: usize,
+    ) -> c_int;
+    #[cfg(CRYPTOGRAPHY_OPENSSL_320_OR_GREATER)]
+    pub(crate) fn EVP_PKEY_decapsulate_init(
+        ctx: *mut ffi::EVP_PKEY_CTX,
+        params: *const OsslParam,
+    ) -> c_int;
+    #[cfg(CRYPTOGRAPHY_OPENSSL_320_OR_GREATER)]
+    pub(crate) fn EVP_PKEY_encapsulate(
+        ctx: *mut ffi::EVP_PKEY_CTX,
+        wrappedkey: *mut u8,
+        wrappedkeylen: *mut usize,
+        genkey: *mut u8,
+        genkeylen: *mut usize,
+    ) -> c_int;
+    #[cfg(CRYPTOGRAPHY_OPENSSL_320_OR_GREATER)]
+    pub(crate) fn EVP_PKEY_encapsulate_init(
+        ctx: *mut ffi::EVP_PKEY_CTX,
+        params: *const OsslParam,
+    ) -> c_int;
+    pub(crate) fn EVP_PKEY_fromdata(
+        ctx: *mut ffi::EVP_PKEY_CTX,
+        pkey: *mut *mut ffi::EVP_PKEY,
+        selection: c_int,
+        params: *mut OsslParam,
+    ) -> c_int;
+    pub(crate) fn EVP_PKEY_fromdata_init(ctx: *mut ffi::EVP_PKEY_CTX) -> c_int;
+    pub(crate) fn EVP_PKEY_generate(
+        ctx: *mut ffi::EVP_PKEY_CTX,
+        pkey: *mut *mut ffi::EVP_PKEY,
+    ) -> c_int;
+    pub(crate) fn EVP_PKEY_get_int_param(
+        pkey: *const ffi::EVP_PKEY,
+        key_name: *const c_char,
+        out: *mut c_int,
+    ) -> c_int;
+    pub(crate) fn EVP_PKEY_get_octet_string_param(
+        pkey: *const ffi::EVP_PKEY,
+        key_name: *const c_char,
+        buf: *mut u8,
+        max_buf_sz: usize,
+        out_len: *mut usize,
+    ) -> c_int;
+    pub(crate) fn EVP_PKEY_is_a(pkey: *const ffi::EVP_PKEY, name: *const c_char) -> c_int;
+    #[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+    pub(crate) fn EVP_PKEY_new_raw_private_key_ex(
+        libctx: *mut c_void,
+        keytype: *const c_char,
+        propq: *const c_char,
+        key: *const u8,
+        keylen: usize,
+    ) -> *mut ffi::EVP_PKEY;
+    #[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+    pub(crate) fn EVP_PKEY_new_raw_public_key_ex(
+        libctx: *mut c_void,
+        keytype: *const c_char,
+        propq: *const c_char,
+        key: *const u8,
+        keylen: usize,
+    ) -> *mut ffi::EVP_PKEY;
+    #[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+    pub(crate) fn EVP_PKEY_pairwise_check(ctx: *mut ffi::EVP_PKEY_CTX) -> c_int;
+    pub(crate) fn EVP_PKEY_param_check(ctx: *mut ffi::EVP_PKEY_CTX) -> c_int;
+    pub(crate) fn EVP_PKEY_paramgen_init(ctx: *mut ffi::EVP_PKEY_CTX) -> c_int;
+
+    pub(crate) fn OSSL_PARAM_construct_end() -> OsslParam;
+    #[cfg(CRYPTOGRAPHY_OPENSSL_320_OR_GREATER)]
+    pub(crate) fn OSSL_PARAM_construct_octet_string(
+        key: *const c_char,
+        buf: *mut c_void,
+        bsize: usize,
+    ) -> OsslParam;
+    pub(crate) fn OSSL_PARAM_construct_size_t(key: *const c_char, buf: *mut usize) -> OsslParam;
+    #[cfg(CRYPTOGRAPHY_OPENSSL_320_OR_GREATER)]
+    pub(crate) fn OSSL_PARAM_construct_uint(key: *const c_char, buf: *mut c_uint) -> OsslParam;
+    pub(crate) fn OSSL_PARAM_construct_utf8_string(
+        key: *const c_char,
+        buf: *mut c_char,
+        bsize: usize,
+    ) -> OsslParam;
+    pub(crate) fn OSSL_PARAM_free(params: *mut OsslParam);
+
+    pub(crate) fn OSSL_PARAM_BLD_free(bld: *mut OsslParamBld);
+    pub(crate) fn OSSL_PARAM_BLD_new() -> *mut OsslParamBld;
+    pub(crate) fn OSSL_PARAM_BLD_push_BN(
+        bld: *mut OsslParamBld,
+        key: *const c_char,
+        bn: *const ffi::BIGNUM,
+    ) -> c_int;
+    pub(crate) fn OSSL_PARAM_BLD_push_int(
+        bld: *mut OsslParamBld,
+        key: *const c_char,
+        val: c_int,
+    ) -> c_int;
+    pub(crate) fn OSSL_PARAM_BLD_push_octet_string(
+        bld: *mut OsslParamBld,
+        key: *const c_char,
+        buf: *const c_void,
+        bsize: usize,
+    ) -> c_int;
+    pub(crate) fn OSSL_PARAM_BLD_to_param(bld: *mut OsslParamBld) -> *mut OsslParam;
+}
@@ -0,0 +1,41 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+use std::os::raw::c_int;
+
+use foreign_types_shared::{ForeignType, ForeignTypeRef};
+
+use crate::{cvt, cvt_p, OpenSSLResult};
+
+extern "C" {
+    fn RSA_generate_multi_prime_key(
+        rsa: *mut ffi::RSA,
+        bits: c_int,
+        primes: c_int,
+        e: *mut ffi::BIGNUM,
+        cb: *mut ffi::BN_GENCB,
+    ) -> c_int;
+}
+
+/// Generates an RSA key whose modulus is the product of `primes` primes.
+/// OpenSSL limits how many primes can be used for a given key size.
+pub fn generate_multi_prime_key(
+    bits: u32,
+    primes: u32,
+    e: &openssl::bn::BigNumRef,
+) -> OpenSSLResult<openssl::rsa::Rsa<openssl::pkey::Private>> {
+    // SAFETY: The RSA is owned by `rsa` as soon as it's allocated, so it's
+    // freed if generation fails. `e` is only read.
+    unsafe {
+        let rsa = openssl::rsa::Rsa::from_ptr(cvt_p(ffi::RSA_new())?);
+        cvt(RSA_generate_multi_prime_key(
+            rsa.as_ptr(),
+            bits as c_int,
+            primes as c_int,
+            e.as_ptr(),
+            std::ptr::null_mut(),
+        ))?;
+        Ok(rsa)
+    }
+}
@@ -0,0 +1,114 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Bindings to OpenSSL's secure heap, an arena of `mlock`ed memory surrounded
+//! by guard pages. Once it is initialized OpenSSL also stores private key
+//! material (e.g. the `BIGNUM`s of RSA and EC private keys) in it.
+
+use std::os::raw::{c_char, c_int, c_void};
+
+use crate::{cvt, cvt_p, OpenSSLResult};
+
+#[cfg(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER)]
+type MinSize = usize;
+#[cfg(not(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER))]
+type MinSize = c_int;
+
+extern "C" {
+    fn CRYPTO_secure_malloc_init(size: usize, minsize: MinSize) -> c_int;
+    fn CRYPTO_secure_malloc_done() -> c_int;
+    fn CRYPTO_secure_malloc_initialized() -> c_int;
+    fn CRYPTO_secure_used() -> usize;
+    fn CRYPTO_secure_zalloc(num: usize, file: *const c_char, line: c_int) -> *mut c_void;
+    fn CRYPTO_secure_clear_free(ptr: *mut c_void, num: usize, file: *const c_char, line: c_int);
+}
+
+const FILE: *const c_char = concat!(file!(), "\0").as_ptr().cast();
+
+/// Whether the secure heap was locked into memory after `init`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum InitStatus {
+    Locked,
+    NotLocked,
+}
+
+/// Creates the secure heap. `size` and `min_size` must both be powers of
+/// two, and `min_size` must be smaller than `size`. This can only succeed
+/// once per process.
+pub fn init(size: usize, min_size: usize) -> OpenSSLResult<InitStatus> {
+    let min_size = MinSize::try_from(min_size).expect("min_size too large");
+    // SAFETY: No pointers are involved, OpenSSL validates the sizes.
+    let r = cvt(unsafe { CRYPTO_secure_malloc_init(size, min_size) })?;
+    Ok(if r == 1 {
+        InitStatus::Locked
+    } else {
+        InitStatus::NotLocked
+    })
+}
+
+/// Tears down the secure heap. This fails if there are still outstanding
+/// allocations.
+pub fn done() -> bool {
+    // SAFETY: No pointers are involved.
+    unsafe { CRYPTO_secure_malloc_done() == 1 }
+}
+
+pub fn is_initialized() -> bool {
+    // SAFETY: No pointers are involved.
+    unsafe { CRYPTO_secure_malloc_initialized() == 1 }
+}
+
+/// The number of bytes of the secure heap currently allocated.
+pub fn used() -> usize {
+    // SAFETY: No pointers are involved.
+    unsafe { CRYPTO_secure_used() }
+}
+
+/// A zero initialized buffer allocated from the secure heap. The contents
+/// are cleansed when it is dropped.
+pub struct SecureBuffer {
+    ptr: *mut u8,
+    len: usize,
+}
+
+// SAFETY: The allocation is exclusively owned by the `SecureBuffer`, and
+// OpenSSL's secure heap is thread safe.
+unsafe impl Send for SecureBuffer {}
+// SAFETY: Shared references only allow reading the buffer.
+unsafe impl Sync for SecureBuffer {}
+
+impl SecureBuffer {
+    /// Allocates `len` bytes. Unlike OpenSSL's `CRYPTO_secure_zalloc`, this
+    /// never falls back to the regular heap: it fails if the secure heap
+    /// hasn't been initialized or is exhausted.
+    pub fn new(len: usize) -> OpenSSLResult<SecureBuffer> {
+        assert!(len > 0);
+        assert!(is_initialized());
+        // SAFETY: `FILE` is a NUL terminated string.
+        let ptr = cvt_p(unsafe { CRYPTO_secure_zalloc(len, FILE, line!() as c_int) })?;
+        Ok(SecureBuffer {
+            ptr: ptr.cast(),
+            len,
+        })
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        // SAFETY: `ptr` points to `len` initialized bytes which we own.
+        unsafe { std::slice::from_raw_parts(self.ptr, self.len) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        // SAFETY: `ptr` points to `len` initialized bytes which we own, and
+        // we have an exclusive reference.
+        unsafe { std::slice::from_raw_parts_mut(self.ptr, self.len) }
+    }
+}
+
+impl Drop for SecureBuffer {
+    fn drop(&mut self) {
+        // SAFETY: `ptr` was returned by `CRYPTO_secure_zalloc` with size
+        // `len`, and `FILE` is a NUL terminated string.
+        unsafe { CRYPTO_secure_clear_free(self.ptr.cast(), self.len, FILE, line!() as c_int) }
+    }
+}
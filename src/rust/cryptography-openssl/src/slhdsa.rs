@@ -0,0 +1,218 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! SLH-DSA, the stateless hash-based signature scheme from FIPS 205,
+//! available as of OpenSSL 3.5. Like ML-DSA, the keys only exist in
+//! OpenSSL's providers and are created by algorithm name.
+
+use std::os::raw::{c_char, c_void};
+
+use foreign_types_shared::{ForeignType, ForeignTypeRef};
+
+use crate::params::{
+    EVP_PKEY_CTX_new_from_name, EVP_PKEY_CTX_set_params, EVP_PKEY_generate, EVP_PKEY_is_a,
+    EVP_PKEY_new_raw_private_key_ex, EVP_PKEY_new_raw_public_key_ex, EVP_PKEY_pairwise_check,
+    OSSL_PARAM_construct_end, OSSL_PARAM_construct_octet_string,
+};
+use crate::{cvt, cvt_p, OpenSSLResult};
+
+// OSSL_SIGNATURE_PARAM_CONTEXT_STRING
+const CONTEXT_STRING: *const c_char = b"context-string\0".as_ptr().cast();
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Variant {
+    Sha2_128s,
+    Sha2_128f,
+    Sha2_192s,
+    Sha2_192f,
+    Sha2_256s,
+    Sha2_256f,
+    Shake128s,
+    Shake128f,
+    Shake192s,
+    Shake192f,
+    Shake256s,
+    Shake256f,
+}
+
+impl Variant {
+    pub const ALL: [Variant; 12] = [
+        Variant::Sha2_128s,
+        Variant::Sha2_128f,
+        Variant::Sha2_192s,
+        Variant::Sha2_192f,
+        Variant::Sha2_256s,
+        Variant::Sha2_256f,
+        Variant::Shake128s,
+        Variant::Shake128f,
+        Variant::Shake192s,
+        Variant::Shake192f,
+        Variant::Shake256s,
+        Variant::Shake256f,
+    ];
+
+    fn c_name(self) -> &'static [u8] {
+        match self {
+            Variant::Sha2_128s => b"SLH-DSA-SHA2-128s\0",
+            Variant::Sha2_128f => b"SLH-DSA-SHA2-128f\0",
+            Variant::Sha2_192s => b"SLH-DSA-SHA2-192s\0",
+            Variant::Sha2_192f => b"SLH-DSA-SHA2-192f\0",
+            Variant::Sha2_256s => b"SLH-DSA-SHA2-256s\0",
+            Variant::Sha2_256f => b"SLH-DSA-SHA2-256f\0",
+            Variant::Shake128s => b"SLH-DSA-SHAKE-128s\0",
+            Variant::Shake128f => b"SLH-DSA-SHAKE-128f\0",
+            Variant::Shake192s => b"SLH-DSA-SHAKE-192s\0",
+            Variant::Shake192f => b"SLH-DSA-SHAKE-192f\0",
+            Variant::Shake256s => b"SLH-DSA-SHAKE-256s\0",
+            Variant::Shake256f => b"SLH-DSA-SHAKE-256f\0",
+        }
+    }
+
+    /// The name of the parameter set in FIPS 205, e.g. `SLH-DSA-SHA2-128s`.
+    pub fn name(self) -> &'static str {
+        let name = self.c_name();
+        // The names are all ASCII.
+        std::str::from_utf8(&name[..name.len() - 1]).unwrap()
+    }
+
+    /// The parameter set called `name`, as returned by `Variant::name`.
+    pub fn from_name(name: &str) -> Option<Variant> {
+        Variant::ALL.into_iter().find(|v| v.name() == name)
+    }
+
+    /// The parameter set of `pkey`, or `None` if it isn't an SLH-DSA key.
+    pub fn from_pkey<T>(pkey: &openssl::pkey::PKeyRef<T>) -> Option<Variant> {
+        // An empty `EVP_PKEY` would otherwise be a match for every name.
+        if pkey.id() == openssl::pkey::Id::from_raw(ffi::NID_undef) {
+            return None;
+        }
+        Variant::ALL
+            .into_iter()
+            // SAFETY: `pkey` is a valid key and the name is a NUL terminated
+            // string.
+            .find(|v| unsafe { EVP_PKEY_is_a(pkey.as_ptr(), v.c_name().as_ptr().cast()) } == 1)
+    }
+
+    /// The security parameter `n`, in bytes. Public keys are `2n` bytes long
+    /// and private keys `4n`.
+    pub fn n(self) -> usize {
+        match self {
+            Variant::Sha2_128s | Variant::Sha2_128f | Variant::Shake128s | Variant::Shake128f => 16,
+            Variant::Sha2_192s | Variant::Sha2_192f | Variant::Shake192s | Variant::Shake192f => 24,
+            Variant::Sha2_256s | Variant::Sha2_256f | Variant::Shake256s | Variant::Shake256f => 32,
+        }
+    }
+}
+
+/// Generates a new key pair.
+pub fn generate(variant: Variant) -> OpenSSLResult<openssl::pkey::PKey<openssl::pkey::Private>> {
+    // SAFETY: The name is a NUL terminated string and the other arguments
+    // may be null. The returned context is owned by the `PkeyCtx`.
+    let ctx = unsafe {
+        openssl::pkey_ctx::PkeyCtx::<()>::from_ptr(cvt_p(EVP_PKEY_CTX_new_from_name(
+            std::ptr::null_mut(),
+            variant.c_name().as_ptr().cast(),
+            std::ptr::null(),
+        ))?)
+    };
+    let mut pkey = std::ptr::null_mut();
+    // SAFETY: `ctx` is a valid context, and the generated key is owned by
+    // `pkey` afterwards.
+    unsafe {
+        cvt(ffi::EVP_PKEY_keygen_init(ctx.as_ptr()))?;
+        cvt(EVP_PKEY_generate(ctx.as_ptr(), &mut pkey))?;
+        Ok(openssl::pkey::PKey::from_ptr(pkey))
+    }
+}
+
+/// Creates a private key from its FIPS 205 encoding, which contains the
+/// public key as well. The public key is checked against the rest of the
+/// private key, since OpenSSL would otherwise create signatures that can't be
+/// verified.
+pub fn private_key_from_raw_bytes(
+    variant: Variant,
+    data: &[u8],
+) -> OpenSSLResult<openssl::pkey::PKey<openssl::pkey::Private>> {
+    // SAFETY: The name is a NUL terminated string and OpenSSL copies the key.
+    // The returned key is owned by the `PKey`.
+    let pkey = unsafe {
+        openssl::pkey::PKey::from_ptr(cvt_p(EVP_PKEY_new_raw_private_key_ex(
+            std::ptr::null_mut(),
+            variant.c_name().as_ptr().cast(),
+            std::ptr::null(),
+            data.as_ptr(),
+            data.len(),
+        ))?)
+    };
+    let ctx = openssl::pkey_ctx::PkeyCtx::new(&pkey)?;
+    // SAFETY: `ctx` is a valid context for `pkey`.
+    cvt(unsafe { EVP_PKEY_pairwise_check(ctx.as_ptr()) })?;
+    Ok(pkey)
+}
+
+/// Creates a public key from its FIPS 205 encoding.
+pub fn public_key_from_raw_bytes(
+    variant: Variant,
+    data: &[u8],
+) -> OpenSSLResult<openssl::pkey::PKey<openssl::pkey::Public>> {
+    // SAFETY: The name is a NUL terminated string and OpenSSL copies the key.
+    // The returned key is owned by the `PKey`.
+    unsafe {
+        Ok(openssl::pkey::PKey::from_ptr(cvt_p(
+            EVP_PKEY_new_raw_public_key_ex(
+                std::ptr::null_mut(),
+                variant.c_name().as_ptr().cast(),
+                std::ptr::null(),
+                data.as_ptr(),
+                data.len(),
+            ),
+        )?))
+    }
+}
+
+fn set_context<T>(ctx: &openssl::pkey_ctx::PkeyCtxRef<T>, context: &[u8]) -> OpenSSLResult<()> {
+    // SAFETY: The key is a NUL terminated string, and `context` outlives the
+    // parameter array that points to it. OpenSSL copies the context and
+    // doesn't write to it.
+    let params = unsafe {
+        [
+            OSSL_PARAM_construct_octet_string(
+                CONTEXT_STRING,
+                context.as_ptr() as *mut c_void,
+                context.len(),
+            ),
+            OSSL_PARAM_construct_end(),
+        ]
+    };
+    // SAFETY: `ctx` is a valid context and `params` is terminated by an end
+    // marker.
+    cvt(unsafe { EVP_PKEY_CTX_set_params(ctx.as_ptr(), params.as_ptr()) })?;
+    Ok(())
+}
+
+/// Signs `data` with the hedged variant of slh_sign and `context`, which can
+/// be at most 255 bytes long.
+pub fn sign(
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+    context: &[u8],
+    data: &[u8],
+) -> OpenSSLResult<Vec<u8>> {
+    let mut ctx = openssl::md_ctx::MdCtx::new()?;
+    set_context(ctx.digest_sign_init(None, pkey)?, context)?;
+    let mut signature = vec![];
+    ctx.digest_sign_to_vec(data, &mut signature)?;
+    Ok(signature)
+}
+
+/// Verifies a signature created by `sign` with the same `context`.
+pub fn verify(
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Public>,
+    context: &[u8],
+    signature: &[u8],
+    data: &[u8],
+) -> OpenSSLResult<bool> {
+    let mut ctx = openssl::md_ctx::MdCtx::new()?;
+    set_context(ctx.digest_verify_init(None, pkey)?, context)?;
+    ctx.digest_verify(data, signature)
+}
@@ -0,0 +1,19 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+use foreign_types_shared::ForeignTypeRef;
+
+use crate::params::EVP_PKEY_is_a;
+
+/// Whether `pkey` is of OpenSSL's SM2 key type. Keys decoded by a provider
+/// don't have a legacy `Id`, so this asks by name rather than checking for
+/// `Id::SM2`.
+pub fn is_sm2<T>(pkey: &openssl::pkey::PKeyRef<T>) -> bool {
+    // `EVP_PKEY_is_a` matches every name for an empty `EVP_PKEY`.
+    if pkey.id() == openssl::pkey::Id::from_raw(ffi::NID_undef) {
+        return false;
+    }
+    // SAFETY: `pkey` is a valid key and the name is a NUL terminated string.
+    unsafe { EVP_PKEY_is_a(pkey.as_ptr(), b"SM2\0".as_ptr().cast()) == 1 }
+}
@@ -0,0 +1,144 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Bindings to OpenSSL 3's store API, which loads objects by URI from
+//! whichever provider handles the URI's scheme (e.g. `pkcs11:` with
+//! pkcs11-provider, or the built-in `file:`).
+
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int, c_void};
+use std::ptr;
+
+use foreign_types_shared::ForeignType;
+
+use crate::{cvt, cvt_p, OpenSSLResult};
+
+#[allow(non_camel_case_types)]
+enum OSSL_STORE_CTX {}
+#[allow(non_camel_case_types)]
+enum OSSL_STORE_INFO {}
+#[allow(non_camel_case_types)]
+enum UI_METHOD {}
+
+const OSSL_STORE_INFO_PKEY: c_int = 4;
+
+type PostProcessCallback =
+    Option<unsafe extern "C" fn(*mut OSSL_STORE_INFO, *mut c_void) -> *mut OSSL_STORE_INFO>;
+
+extern "C" {
+    fn OSSL_STORE_open(
+        uri: *const c_char,
+        ui_method: *const UI_METHOD,
+        ui_data: *mut c_void,
+        post_process: PostProcessCallback,
+        post_process_data: *mut c_void,
+    ) -> *mut OSSL_STORE_CTX;
+    fn OSSL_STORE_expect(ctx: *mut OSSL_STORE_CTX, expected_type: c_int) -> c_int;
+    fn OSSL_STORE_load(ctx: *mut OSSL_STORE_CTX) -> *mut OSSL_STORE_INFO;
+    fn OSSL_STORE_eof(ctx: *mut OSSL_STORE_CTX) -> c_int;
+    fn OSSL_STORE_error(ctx: *mut OSSL_STORE_CTX) -> c_int;
+    fn OSSL_STORE_close(ctx: *mut OSSL_STORE_CTX) -> c_int;
+
+    fn OSSL_STORE_INFO_get_type(info: *const OSSL_STORE_INFO) -> c_int;
+    fn OSSL_STORE_INFO_get1_PKEY(info: *const OSSL_STORE_INFO) -> *mut ffi::EVP_PKEY;
+    fn OSSL_STORE_INFO_free(info: *mut OSSL_STORE_INFO);
+
+    fn UI_UTIL_wrap_read_pem_callback(cb: ffi::pem_password_cb, rwflag: c_int) -> *mut UI_METHOD;
+    fn UI_destroy_method(ui_method: *mut UI_METHOD);
+}
+
+struct Store(*mut OSSL_STORE_CTX);
+
+impl Drop for Store {
+    fn drop(&mut self) {
+        // SAFETY: `self.0` is a valid store which is only closed here.
+        unsafe {
+            OSSL_STORE_close(self.0);
+        }
+    }
+}
+
+struct Info(*mut OSSL_STORE_INFO);
+
+impl Drop for Info {
+    fn drop(&mut self) {
+        // SAFETY: `self.0` is a valid store info which is only freed here.
+        unsafe { OSSL_STORE_INFO_free(self.0) }
+    }
+}
+
+struct UiMethod(*mut UI_METHOD);
+
+impl Drop for UiMethod {
+    fn drop(&mut self) {
+        // SAFETY: `self.0` is a valid UI method which is only freed here.
+        unsafe { UI_destroy_method(self.0) }
+    }
+}
+
+// Answers the provider's PIN or passphrase prompt with the `Option<&[u8]>`
+// passed to `OSSL_STORE_open`. Without a PIN the prompt fails, rather than
+// falling back to reading from the terminal.
+extern "C" fn pin_callback(
+    buf: *mut c_char,
+    size: c_int,
+    _rwflag: c_int,
+    userdata: *mut c_void,
+) -> c_int {
+    // SAFETY: `userdata` is the `Option<&[u8]>` which `load_private_key`
+    // keeps alive for as long as the store is open.
+    let pin = unsafe { *userdata.cast::<Option<&[u8]>>() };
+    let pin = match pin {
+        Some(pin) => pin,
+        None => return -1,
+    };
+    match c_int::try_from(pin.len()) {
+        Ok(len) if len <= size => {
+            // SAFETY: OpenSSL guarantees `buf` has room for `size` bytes.
+            unsafe { ptr::copy_nonoverlapping(pin.as_ptr(), buf.cast::<u8>(), pin.len()) };
+            len
+        }
+        _ => -1,
+    }
+}
+
+/// Loads the first private key found at `uri`, or `None` if there isn't
+/// one. `pin` answers any PIN or passphrase prompt.
+pub fn load_private_key(
+    uri: &CStr,
+    pin: Option<&[u8]>,
+) -> OpenSSLResult<Option<openssl::pkey::PKey<openssl::pkey::Private>>> {
+    // SAFETY: All FFI conditions are handled. `pin` outlives `store`, which
+    // is dropped before it.
+    unsafe {
+        let ui_method = UiMethod(cvt_p(UI_UTIL_wrap_read_pem_callback(
+            Some(pin_callback),
+            0,
+        ))?);
+        let store = Store(cvt_p(OSSL_STORE_open(
+            uri.as_ptr(),
+            ui_method.0,
+            ptr::addr_of!(pin) as *mut c_void,
+            None,
+            ptr::null_mut(),
+        ))?);
+        cvt(OSSL_STORE_expect(store.0, OSSL_STORE_INFO_PKEY))?;
+
+        while OSSL_STORE_eof(store.0) == 0 {
+            let info = OSSL_STORE_load(store.0);
+            if info.is_null() {
+                if OSSL_STORE_error(store.0) == 1 {
+                    return Err(openssl::error::ErrorStack::get());
+                }
+                continue;
+            }
+            let info = Info(info);
+            if OSSL_STORE_INFO_get_type(info.0) == OSSL_STORE_INFO_PKEY {
+                let pkey = cvt_p(OSSL_STORE_INFO_get1_PKEY(info.0))?;
+                return Ok(Some(openssl::pkey::PKey::from_ptr(pkey)));
+            }
+        }
+        Ok(None)
+    }
+}
@@ -45,6 +45,41 @@ pub enum AlgorithmParameters<'a> {
     #[defined_by(oid::ED448_OID)]
     Ed448,
 
+    // ML-DSA uses the same identifier, with no parameters, for keys and
+    // signatures.
+    #[defined_by(oid::ML_DSA_44_OID)]
+    MlDsa44,
+    #[defined_by(oid::ML_DSA_65_OID)]
+    MlDsa65,
+    #[defined_by(oid::ML_DSA_87_OID)]
+    MlDsa87,
+
+    // So does SLH-DSA.
+    #[defined_by(oid::SLH_DSA_SHA2_128S_OID)]
+    SlhDsaSha2_128s,
+    #[defined_by(oid::SLH_DSA_SHA2_128F_OID)]
+    SlhDsaSha2_128f,
+    #[defined_by(oid::SLH_DSA_SHA2_192S_OID)]
+    SlhDsaSha2_192s,
+    #[defined_by(oid::SLH_DSA_SHA2_192F_OID)]
+    SlhDsaSha2_192f,
+    #[defined_by(oid::SLH_DSA_SHA2_256S_OID)]
+    SlhDsaSha2_256s,
+    #[defined_by(oid::SLH_DSA_SHA2_256F_OID)]
+    SlhDsaSha2_256f,
+    #[defined_by(oid::SLH_DSA_SHAKE_128S_OID)]
+    SlhDsaShake128s,
+    #[defined_by(oid::SLH_DSA_SHAKE_128F_OID)]
+    SlhDsaShake128f,
+    #[defined_by(oid::SLH_DSA_SHAKE_192S_OID)]
+    SlhDsaShake192s,
+    #[defined_by(oid::SLH_DSA_SHAKE_192F_OID)]
+    SlhDsaShake192f,
+    #[defined_by(oid::SLH_DSA_SHAKE_256S_OID)]
+    SlhDsaShake256s,
+    #[defined_by(oid::SLH_DSA_SHAKE_256F_OID)]
+    SlhDsaShake256f,
+
     #[defined_by(oid::X25519_OID)]
     X25519,
     #[defined_by(oid::X448_OID)]
@@ -125,6 +160,54 @@ pub enum AlgorithmParameters<'a> {
     #[defined_by(oid::DH_KEY_AGREEMENT_OID)]
     DhKeyAgreement(BasicDHParams<'a>),
 
+    #[defined_by(oid::PBE_WITH_MD5_AND_DES_CBC_OID)]
+    PbeWithMd5AndDesCbc(PbeParams<'a>),
+    #[defined_by(oid::PBE_WITH_MD5_AND_RC2_CBC_OID)]
+    PbeWithMd5AndRc2Cbc(PbeParams<'a>),
+    #[defined_by(oid::PBE_WITH_SHA1_AND_DES_CBC_OID)]
+    PbeWithSha1AndDesCbc(PbeParams<'a>),
+    #[defined_by(oid::PBE_WITH_SHA1_AND_RC2_CBC_OID)]
+    PbeWithSha1AndRc2Cbc(PbeParams<'a>),
+    #[defined_by(oid::PBE_WITH_SHA_AND_3KEY_TRIPLEDES_CBC_OID)]
+    PbeWithShaAnd3KeyTripleDesCbc(PbeParams<'a>),
+    #[defined_by(oid::PBE_WITH_SHA_AND_2KEY_TRIPLEDES_CBC_OID)]
+    PbeWithShaAnd2KeyTripleDesCbc(PbeParams<'a>),
+    #[defined_by(oid::PBE_WITH_SHA_AND_128_BIT_RC2_CBC_OID)]
+    PbeWithShaAnd128BitRc2Cbc(PbeParams<'a>),
+    #[defined_by(oid::PBE_WITH_SHA_AND_40_BIT_RC2_CBC_OID)]
+    PbeWithShaAnd40BitRc2Cbc(PbeParams<'a>),
+
+    #[defined_by(oid::JKS_KEY_PROTECTOR_OID)]
+    JksKeyProtector(Option<asn1::Null>),
+    #[defined_by(oid::PBE_WITH_MD5_AND_TRIPLE_DES_OID)]
+    PbeWithMd5AndTripleDes(PbeParams<'a>),
+
+    #[defined_by(oid::PBES2_OID)]
+    Pbes2(Pbes2Params<'a>),
+    #[defined_by(oid::PBKDF2_OID)]
+    Pbkdf2(Box<Pbkdf2Params<'a>>),
+    #[defined_by(oid::SCRYPT_OID)]
+    Scrypt(ScryptParams<'a>),
+
+    // The PRFs for PBKDF2. RFC 8018 specifies NULL parameters, but they're
+    // sometimes omitted.
+    #[defined_by(oid::HMAC_WITH_SHA1_OID)]
+    HmacWithSha1(Option<asn1::Null>),
+    #[defined_by(oid::HMAC_WITH_SHA224_OID)]
+    HmacWithSha224(Option<asn1::Null>),
+    #[defined_by(oid::HMAC_WITH_SHA256_OID)]
+    HmacWithSha256(Option<asn1::Null>),
+    #[defined_by(oid::HMAC_WITH_SHA384_OID)]
+    HmacWithSha384(Option<asn1::Null>),
+    #[defined_by(oid::HMAC_WITH_SHA512_OID)]
+    HmacWithSha512(Option<asn1::Null>),
+
+    // The IV for the CBC mode ciphers used in PBES2.
+    #[defined_by(oid::AES_128_CBC_OID)]
+    Aes128Cbc(&'a [u8]),
+    #[defined_by(oid::AES_256_CBC_OID)]
+    Aes256Cbc(&'a [u8]),
+
     #[default]
     Other(asn1::ObjectIdentifier, Option<asn1::Tlv<'a>>),
 }
@@ -280,6 +363,73 @@ pub struct DHXParams<'a> {
     pub validation_params: Option<asn1::Sequence<'a>>,
 }
 
+// From PKCS#5 (RFC 8018) and PKCS#12 (RFC 7292), which both use the same
+// structure:
+//
+// PBEParameter ::= SEQUENCE {
+//     salt OCTET STRING,
+//     iterationCount INTEGER
+// }
+#[derive(asn1::Asn1Read, asn1::Asn1Write, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct PbeParams<'a> {
+    pub salt: &'a [u8],
+    pub iterations: u64,
+}
+
+// From RFC 8018 Appendix A.4:
+//
+// PBES2-params ::= SEQUENCE {
+//     keyDerivationFunc AlgorithmIdentifier {{PBES2-KDFs}},
+//     encryptionScheme AlgorithmIdentifier {{PBES2-Encs}}
+// }
+#[derive(asn1::Asn1Read, asn1::Asn1Write, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct Pbes2Params<'a> {
+    pub key_derivation_func: Box<AlgorithmIdentifier<'a>>,
+    pub encryption_scheme: Box<AlgorithmIdentifier<'a>>,
+}
+
+// From RFC 8018 Appendix A.2:
+//
+// PBKDF2-params ::= SEQUENCE {
+//     salt CHOICE {
+//         specified OCTET STRING,
+//         otherSource AlgorithmIdentifier {{PBKDF2-SaltSources}}
+//     },
+//     iterationCount INTEGER (1..MAX),
+//     keyLength INTEGER (1..MAX) OPTIONAL,
+//     prf AlgorithmIdentifier {{PBKDF2-PRFs}} DEFAULT algid-hmacWithSHA1
+// }
+//
+// No salt sources other than `specified` are defined, so only it is
+// supported. `prf` isn't a DEFAULT field because some encoders, such as
+// Java's, include it even when it's the default, so `None` means
+// hmacWithSHA1.
+#[derive(asn1::Asn1Read, asn1::Asn1Write, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct Pbkdf2Params<'a> {
+    pub salt: &'a [u8],
+    pub iteration_count: u64,
+    pub key_length: Option<u64>,
+    pub prf: Option<AlgorithmIdentifier<'a>>,
+}
+
+// From RFC 7914 Section 7.1:
+//
+// scrypt-params ::= SEQUENCE {
+//     salt OCTET STRING,
+//     costParameter INTEGER (1..MAX),
+//     blockSize INTEGER (1..MAX),
+//     parallelizationParameter INTEGER (1..MAX),
+//     keyLength INTEGER (1..MAX) OPTIONAL
+// }
+#[derive(asn1::Asn1Read, asn1::Asn1Write, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct ScryptParams<'a> {
+    pub salt: &'a [u8],
+    pub cost_parameter: u64,
+    pub block_size: u64,
+    pub parallelization_parameter: u64,
+    pub key_length: Option<u64>,
+}
+
 // RSA-PSS ASN.1 default hash algorithm
 pub const PSS_SHA1_HASH_ALG: AlgorithmIdentifier<'_> = AlgorithmIdentifier {
     oid: asn1::DefinedByMarker::marker(),
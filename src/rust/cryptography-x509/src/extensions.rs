@@ -183,6 +183,40 @@ pub struct MSCertificateTemplate {
     pub minor_version: Option<u32>,
 }
 
+// The Android Keystore attestation `KeyDescription`. The authorization lists
+// are kept as raw elements, since new fields are regularly added to them.
+#[derive(asn1::Asn1Read, asn1::Asn1Write)]
+pub struct AndroidKeyDescription<'a> {
+    pub attestation_version: i64,
+    pub attestation_security_level: asn1::Enumerated,
+    pub keymaster_version: i64,
+    pub keymaster_security_level: asn1::Enumerated,
+    pub attestation_challenge: &'a [u8],
+    pub unique_id: &'a [u8],
+    pub software_enforced: AndroidAuthorizationList<'a>,
+    pub hardware_enforced: AndroidAuthorizationList<'a>,
+}
+
+pub type AndroidAuthorizationList<'a> = common::Asn1ReadableOrWritable<
+    asn1::SequenceOf<'a, common::RawTlv<'a>>,
+    asn1::SequenceOfWriter<'a, common::RawTlv<'a>, Vec<common::RawTlv<'a>>>,
+>;
+
+#[derive(asn1::Asn1Read, asn1::Asn1Write)]
+pub struct AndroidRootOfTrust<'a> {
+    pub verified_boot_key: &'a [u8],
+    pub device_locked: bool,
+    pub verified_boot_state: asn1::Enumerated,
+    // Only present from attestation version 3 onwards.
+    pub verified_boot_hash: Option<&'a [u8]>,
+}
+
+#[derive(asn1::Asn1Read, asn1::Asn1Write)]
+pub struct AppleAttestationNonce<'a> {
+    #[explicit(1, required)]
+    pub nonce: &'a [u8],
+}
+
 #[derive(asn1::Asn1Read, asn1::Asn1Write)]
 pub struct DistributionPoint<'a> {
     #[explicit(0)]
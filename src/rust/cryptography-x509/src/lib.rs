@@ -14,4 +14,6 @@ pub mod name;
 pub mod ocsp_req;
 pub mod ocsp_resp;
 pub mod oid;
+pub mod pkcs12;
 pub mod pkcs7;
+pub mod pkcs8;
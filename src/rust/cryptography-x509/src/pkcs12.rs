@@ -0,0 +1,135 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+use crate::pkcs7::PKCS7_DATA_OID;
+use crate::{common, csr, pkcs8};
+
+pub const PKCS7_ENCRYPTED_DATA_OID: asn1::ObjectIdentifier = asn1::oid!(1, 2, 840, 113549, 1, 7, 6);
+
+pub const KEY_BAG_OID: asn1::ObjectIdentifier = asn1::oid!(1, 2, 840, 113549, 1, 12, 10, 1, 1);
+pub const SHROUDED_KEY_BAG_OID: asn1::ObjectIdentifier =
+    asn1::oid!(1, 2, 840, 113549, 1, 12, 10, 1, 2);
+pub const CERT_BAG_OID: asn1::ObjectIdentifier = asn1::oid!(1, 2, 840, 113549, 1, 12, 10, 1, 3);
+
+pub const X509_CERTIFICATE_OID: asn1::ObjectIdentifier = asn1::oid!(1, 2, 840, 113549, 1, 9, 22, 1);
+
+pub const FRIENDLY_NAME_OID: asn1::ObjectIdentifier = asn1::oid!(1, 2, 840, 113549, 1, 9, 20);
+pub const LOCAL_KEY_ID_OID: asn1::ObjectIdentifier = asn1::oid!(1, 2, 840, 113549, 1, 9, 21);
+
+// RFC 7292 Section 4
+#[derive(asn1::Asn1Read)]
+pub struct Pfx<'a> {
+    pub version: u8,
+    pub auth_safe: ContentInfo<'a>,
+    pub mac_data: Option<MacData<'a>>,
+}
+
+// `iterations` isn't a DEFAULT field because some encoders, such as older
+// versions of OpenSSL, include it even when it's the default, so `None` means
+// one iteration.
+#[derive(asn1::Asn1Read)]
+pub struct MacData<'a> {
+    pub mac: DigestInfo<'a>,
+    pub salt: &'a [u8],
+    pub iterations: Option<u64>,
+}
+
+#[derive(asn1::Asn1Read)]
+pub struct DigestInfo<'a> {
+    pub algorithm: common::AlgorithmIdentifier<'a>,
+    pub digest: &'a [u8],
+}
+
+#[derive(asn1::Asn1Read)]
+pub struct ContentInfo<'a> {
+    pub _content_type: asn1::DefinedByMarker<asn1::ObjectIdentifier>,
+
+    #[defined_by(_content_type)]
+    pub content: Content<'a>,
+}
+
+#[derive(asn1::Asn1DefinedByRead)]
+pub enum Content<'a> {
+    #[defined_by(PKCS7_DATA_OID)]
+    Data(asn1::Explicit<'a, &'a [u8], 0>),
+    #[defined_by(PKCS7_ENCRYPTED_DATA_OID)]
+    EncryptedData(asn1::Explicit<'a, EncryptedData<'a>, 0>),
+}
+
+// RFC 5652 Section 8
+#[derive(asn1::Asn1Read)]
+pub struct EncryptedData<'a> {
+    pub version: u8,
+    pub encrypted_content_info: EncryptedContentInfo<'a>,
+}
+
+#[derive(asn1::Asn1Read)]
+pub struct EncryptedContentInfo<'a> {
+    pub content_type: asn1::ObjectIdentifier,
+    pub content_encryption_algorithm: common::AlgorithmIdentifier<'a>,
+    #[implicit(0)]
+    pub encrypted_content: Option<&'a [u8]>,
+}
+
+pub type AuthenticatedSafe<'a> = asn1::SequenceOf<'a, ContentInfo<'a>>;
+pub type SafeContents<'a> = asn1::SequenceOf<'a, SafeBag<'a>>;
+
+#[derive(asn1::Asn1Read)]
+pub struct SafeBag<'a> {
+    pub _bag_id: asn1::DefinedByMarker<asn1::ObjectIdentifier>,
+    #[defined_by(_bag_id)]
+    pub bag_value: BagValue<'a>,
+    pub attributes: Option<BagAttributes<'a>>,
+}
+
+// The attributes are a SET OF, but encoders such as OpenSSL don't sort them as
+// DER requires, so they're read in the order they appear rather than rejected.
+pub struct BagAttributes<'a>(asn1::SequenceOf<'a, csr::Attribute<'a>>);
+
+impl<'a> asn1::SimpleAsn1Readable<'a> for BagAttributes<'a> {
+    const TAG: asn1::Tag = <asn1::SetOf<'a, csr::Attribute<'a>>>::TAG;
+
+    fn parse_data(data: &'a [u8]) -> asn1::ParseResult<Self> {
+        Ok(BagAttributes(asn1::SequenceOf::parse_data(data)?))
+    }
+}
+
+impl<'a> IntoIterator for BagAttributes<'a> {
+    type Item = csr::Attribute<'a>;
+    type IntoIter = asn1::SequenceOf<'a, csr::Attribute<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0
+    }
+}
+
+#[derive(asn1::Asn1DefinedByRead)]
+pub enum BagValue<'a> {
+    #[defined_by(KEY_BAG_OID)]
+    KeyBag(asn1::Explicit<'a, asn1::Tlv<'a>, 0>),
+    #[defined_by(SHROUDED_KEY_BAG_OID)]
+    ShroudedKeyBag(asn1::Explicit<'a, pkcs8::EncryptedPrivateKeyInfo<'a>, 0>),
+    #[defined_by(CERT_BAG_OID)]
+    CertBag(asn1::Explicit<'a, CertBag<'a>, 0>),
+
+    // CRL and secret bags, and nested SafeContents.
+    #[default]
+    Other(asn1::ObjectIdentifier, asn1::Tlv<'a>),
+}
+
+#[derive(asn1::Asn1Read)]
+pub struct CertBag<'a> {
+    pub _cert_id: asn1::DefinedByMarker<asn1::ObjectIdentifier>,
+    #[defined_by(_cert_id)]
+    pub cert_value: CertType<'a>,
+}
+
+#[derive(asn1::Asn1DefinedByRead)]
+pub enum CertType<'a> {
+    #[defined_by(X509_CERTIFICATE_OID)]
+    X509(asn1::Explicit<'a, &'a [u8], 0>),
+
+    #[default]
+    Other(asn1::ObjectIdentifier, asn1::Tlv<'a>),
+}
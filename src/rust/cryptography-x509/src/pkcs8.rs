@@ -0,0 +1,24 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+use crate::{common, csr};
+
+// RFC 5958 Section 3
+#[derive(asn1::Asn1Read, asn1::Asn1Write)]
+pub struct EncryptedPrivateKeyInfo<'a> {
+    pub encryption_algorithm: common::AlgorithmIdentifier<'a>,
+    pub encrypted_data: &'a [u8],
+}
+
+// RFC 5958 Section 2. Version 1 (OneAsymmetricKey) adds the public key.
+#[derive(asn1::Asn1Read, asn1::Asn1Write)]
+pub struct PrivateKeyInfo<'a> {
+    pub version: u8,
+    pub algorithm: common::AlgorithmIdentifier<'a>,
+    pub private_key: &'a [u8],
+    #[implicit(0)]
+    pub attributes: Option<csr::Attributes<'a>>,
+    #[implicit(1)]
+    pub public_key: Option<asn1::BitString<'a>>,
+}
@@ -9,6 +9,7 @@ use cryptography_x509::name::Name;
 use pyo3::types::IntoPyDict;
 use pyo3::ToPyObject;
 
+use crate::buf::CffiBuf;
 use crate::error::{CryptographyError, CryptographyResult};
 use crate::types;
 
@@ -125,6 +126,85 @@ fn encode_dss_signature(
     Ok(pyo3::types::PyBytes::new(py, &result).to_object(py))
 }
 
+pub(crate) fn dss_signature_der_to_raw(der: &[u8], size: usize) -> CryptographyResult<Vec<u8>> {
+    let sig = asn1::parse_single::<DssSignature<'_>>(der)?;
+    let mut raw = vec![0; size * 2];
+    for (v, out) in [sig.r.as_bytes(), sig.s.as_bytes()]
+        .iter()
+        .zip(raw.chunks_mut(size))
+    {
+        let start = v.iter().position(|b| *b != 0).unwrap_or(v.len());
+        let v = &v[start..];
+        if v.len() > size {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("Signature component is too large"),
+            ));
+        }
+        out[size - v.len()..].copy_from_slice(v);
+    }
+    Ok(raw)
+}
+
+pub(crate) fn dss_signature_raw_to_der(raw: &[u8], size: usize) -> Option<Vec<u8>> {
+    if raw.len() != size * 2 {
+        return None;
+    }
+    let (r, s) = raw.split_at(size);
+    let (r, s) = (minimal_uint(r), minimal_uint(s));
+    asn1::write_single(&DssSignature {
+        r: asn1::BigUint::new(&r)?,
+        s: asn1::BigUint::new(&s)?,
+    })
+    .ok()
+}
+
+fn minimal_uint(v: &[u8]) -> Vec<u8> {
+    let start = v.iter().position(|b| *b != 0).unwrap_or(v.len());
+    let mut out = vec![];
+    if start == v.len() || v[start] & 0x80 != 0 {
+        out.push(0);
+    }
+    out.extend_from_slice(&v[start..]);
+    out
+}
+
+// Converts a DER encoded DSA or ECDSA signature into the fixed width
+// `r || s` form used by JOSE, COSE and PKCS#11, where each of `r` and `s` is
+// `size` bytes long.
+#[pyo3::prelude::pyfunction]
+fn der_to_raw_signature<'p>(
+    py: pyo3::Python<'p>,
+    signature: CffiBuf<'_>,
+    size: usize,
+) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+    if size == 0 {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("size must be positive"),
+        ));
+    }
+    Ok(pyo3::types::PyBytes::new(
+        py,
+        &dss_signature_der_to_raw(signature.as_bytes(), size)?,
+    ))
+}
+
+#[pyo3::prelude::pyfunction]
+fn raw_to_der_signature<'p>(
+    py: pyo3::Python<'p>,
+    signature: CffiBuf<'_>,
+    size: usize,
+) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+    let der = dss_signature_raw_to_der(signature.as_bytes(), size)
+        .filter(|_| size > 0)
+        .ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "Raw signatures must be {} bytes long",
+                size * 2
+            ))
+        })?;
+    Ok(pyo3::types::PyBytes::new(py, &der))
+}
+
 #[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.asn1")]
 struct TestCertificate {
     #[pyo3(get)]
@@ -173,8 +253,32 @@ pub(crate) fn create_submodule(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::pr
 
     submod.add_function(pyo3::wrap_pyfunction!(decode_dss_signature, submod)?)?;
     submod.add_function(pyo3::wrap_pyfunction!(encode_dss_signature, submod)?)?;
+    submod.add_function(pyo3::wrap_pyfunction!(der_to_raw_signature, submod)?)?;
+    submod.add_function(pyo3::wrap_pyfunction!(raw_to_der_signature, submod)?)?;
 
     submod.add_function(pyo3::wrap_pyfunction!(test_parse_certificate, submod)?)?;
 
+    crate::declarative_asn1::add_to_module(submod)?;
+
     Ok(submod)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{dss_signature_der_to_raw, dss_signature_raw_to_der, minimal_uint};
+
+    #[test]
+    fn test_signature_conversion_roundtrip() {
+        let raw = [[0x00; 31].as_slice(), &[0x80], &[0x01; 32]].concat();
+        let der = dss_signature_raw_to_der(&raw, 32).unwrap();
+        assert_eq!(dss_signature_der_to_raw(&der, 32).ok().unwrap(), raw);
+        assert!(dss_signature_raw_to_der(&raw, 31).is_none());
+    }
+
+    #[test]
+    fn test_minimal_uint() {
+        assert_eq!(minimal_uint(&[0, 0]), vec![0]);
+        assert_eq!(minimal_uint(&[0, 0x7f]), vec![0x7f]);
+        assert_eq!(minimal_uint(&[0, 0x80]), vec![0, 0x80]);
+    }
+}
@@ -0,0 +1,967 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+use crate::backend::hashes::already_finalized_error;
+use crate::backend::{kdf, x25519};
+use crate::buf::CffiBuf;
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::exceptions;
+
+const INTRO: &[u8] = b"age-encryption.org/v1\n";
+const STANZA_PREFIX: &[u8] = b"-> ";
+const MAC_PREFIX: &[u8] = b"---";
+const X25519_LABEL: &[u8] = b"age-encryption.org/v1/X25519";
+#[cfg(not(CRYPTOGRAPHY_IS_LIBRESSL))]
+const SCRYPT_LABEL: &[u8] = b"age-encryption.org/v1/scrypt";
+
+const FILE_KEY_LENGTH: usize = 16;
+const WRAPPED_KEY_LENGTH: usize = FILE_KEY_LENGTH + TAG_LENGTH;
+const NONCE_LENGTH: usize = 16;
+const SCRYPT_SALT_LENGTH: usize = 16;
+const TAG_LENGTH: usize = 16;
+const CHUNK_LENGTH: usize = 64 * 1024;
+const BODY_COLUMNS: usize = 64;
+
+const DEFAULT_WORK_FACTOR: u8 = 18;
+const DEFAULT_MAX_WORK_FACTOR: u8 = 22;
+const MAX_WORK_FACTOR: u8 = 30;
+
+const RECIPIENT_HRP: &str = "age";
+const IDENTITY_HRP: &str = "age-secret-key-";
+const BECH32_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+fn invalid_header() -> CryptographyError {
+    CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+        "Invalid age header",
+    ))
+}
+
+fn invalid_tag() -> CryptographyError {
+    CryptographyError::from(exceptions::InvalidTag::new_err(()))
+}
+
+fn check_supported() -> CryptographyResult<()> {
+    if cfg!(CRYPTOGRAPHY_IS_BORINGSSL) || cryptography_openssl::fips::is_enabled() {
+        return Err(CryptographyError::from(
+            exceptions::UnsupportedAlgorithm::new_err((
+                "ChaCha20Poly1305 is not supported by this version of OpenSSL",
+                exceptions::Reasons::UNSUPPORTED_CIPHER,
+            )),
+        ));
+    }
+    Ok(())
+}
+
+fn check_work_factor(name: &str, work_factor: u8) -> CryptographyResult<()> {
+    if work_factor == 0 || work_factor > MAX_WORK_FACTOR {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "{name} must be between 1 and {MAX_WORK_FACTOR}"
+            )),
+        ));
+    }
+    Ok(())
+}
+
+// age uses the standard base64 alphabet without padding, and only accepts
+// the canonical encoding.
+fn b64_encode(data: &[u8]) -> String {
+    openssl::base64::encode_block(data)
+        .trim_end_matches('=')
+        .to_string()
+}
+
+fn b64_decode(data: &[u8]) -> Option<Vec<u8>> {
+    if data.len() % 4 == 1
+        || !data
+            .iter()
+            .all(|b| b.is_ascii_alphanumeric() || *b == b'+' || *b == b'/')
+    {
+        return None;
+    }
+    let mut padded = String::from_utf8(data.to_vec()).ok()?;
+    while padded.len() % 4 != 0 {
+        padded.push('=');
+    }
+    let decoded = openssl::base64::decode_block(&padded).ok()?;
+    if b64_encode(&decoded).as_bytes() != data {
+        return None;
+    }
+    Some(decoded)
+}
+
+fn bech32_polymod(values: impl Iterator<Item = u8>) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    let mut checksum = 1u32;
+    for v in values {
+        let top = checksum >> 25;
+        checksum = ((checksum & 0x1ffffff) << 5) ^ u32::from(v);
+        for (i, g) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= g;
+            }
+        }
+    }
+    checksum
+}
+
+fn bech32_hrp_expand(hrp: &str) -> impl Iterator<Item = u8> + '_ {
+    hrp.bytes()
+        .map(|b| b >> 5)
+        .chain(std::iter::once(0))
+        .chain(hrp.bytes().map(|b| b & 31))
+}
+
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Option<Vec<u8>> {
+    let mut acc = 0u32;
+    let mut bits = 0u32;
+    let max = (1u32 << to) - 1;
+    let mut out = vec![];
+    for &v in data {
+        acc = (acc << from) | u32::from(v);
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            out.push(((acc >> bits) & max) as u8);
+        }
+        acc &= (1 << bits) - 1;
+    }
+    if pad {
+        if bits > 0 {
+            out.push(((acc << (to - bits)) & max) as u8);
+        }
+    } else if bits >= from || acc != 0 {
+        return None;
+    }
+    Some(out)
+}
+
+// Bech32 from BIP 173. Like age, this doesn't apply BIP 173's 90 character
+// limit.
+fn bech32_encode(hrp: &str, data: &[u8]) -> String {
+    let values = convert_bits(data, 8, 5, true).unwrap();
+    let polymod = bech32_polymod(
+        bech32_hrp_expand(hrp)
+            .chain(values.iter().copied())
+            .chain([0; 6]),
+    ) ^ 1;
+    let mut s = format!("{hrp}1");
+    for v in values
+        .iter()
+        .copied()
+        .chain((0..6).map(|i| ((polymod >> (5 * (5 - i))) & 31) as u8))
+    {
+        s.push(char::from(BECH32_CHARSET[usize::from(v)]));
+    }
+    s
+}
+
+// Returns the lowercase human readable part and the data.
+fn bech32_decode(s: &str) -> Option<(String, Vec<u8>)> {
+    let lower = s.to_ascii_lowercase();
+    if lower != s && s.to_ascii_uppercase() != s {
+        return None;
+    }
+    let pos = lower.rfind('1')?;
+    let (hrp, data) = (&lower[..pos], &lower.as_bytes()[pos + 1..]);
+    if hrp.is_empty() || data.len() < 6 || !hrp.bytes().all(|b| (33..=126).contains(&b)) {
+        return None;
+    }
+    let values = data
+        .iter()
+        .map(|c| BECH32_CHARSET.iter().position(|x| x == c).map(|p| p as u8))
+        .collect::<Option<Vec<u8>>>()?;
+    if bech32_polymod(bech32_hrp_expand(hrp).chain(values.iter().copied())) != 1 {
+        return None;
+    }
+    let data = convert_bits(&values[..values.len() - 6], 5, 8, false)?;
+    Some((hrp.to_string(), data))
+}
+
+fn chacha20_poly1305() -> openssl::symm::Cipher {
+    cfg_if::cfg_if! {
+        if #[cfg(CRYPTOGRAPHY_IS_BORINGSSL)] {
+            // check_supported() rejects BoringSSL before anything is
+            // encrypted.
+            unreachable!()
+        } else {
+            openssl::symm::Cipher::chacha20_poly1305()
+        }
+    }
+}
+
+fn seal(key: &[u8], nonce: &[u8; 12], data: &[u8]) -> CryptographyResult<Vec<u8>> {
+    let mut tag = [0; TAG_LENGTH];
+    let mut out =
+        openssl::symm::encrypt_aead(chacha20_poly1305(), key, Some(nonce), b"", data, &mut tag)?;
+    out.extend_from_slice(&tag);
+    Ok(out)
+}
+
+fn open(key: &[u8], nonce: &[u8; 12], data: &[u8]) -> Option<Vec<u8>> {
+    if data.len() < TAG_LENGTH {
+        return None;
+    }
+    let (ciphertext, tag) = data.split_at(data.len() - TAG_LENGTH);
+    openssl::symm::decrypt_aead(chacha20_poly1305(), key, Some(nonce), b"", ciphertext, tag).ok()
+}
+
+fn hkdf_sha256(key_material: &[u8], salt: &[u8], info: &[u8]) -> CryptographyResult<Vec<u8>> {
+    kdf::derive_hkdf(
+        openssl::hash::MessageDigest::sha256(),
+        salt,
+        info,
+        32,
+        key_material,
+    )
+}
+
+fn header_mac(
+    file_key: &[u8],
+    header: &[u8],
+) -> CryptographyResult<cryptography_openssl::hmac::DigestBytes> {
+    let key = hkdf_sha256(file_key, b"", b"header")?;
+    let mut h =
+        cryptography_openssl::hmac::Hmac::new(&key, openssl::hash::MessageDigest::sha256())?;
+    h.update(header)?;
+    Ok(h.finish()?)
+}
+
+// Derives the key that wraps the file key in an X25519 stanza. `secret` is
+// either the ephemeral key or the identity, and `peer` the other party.
+fn x25519_wrap_key(
+    secret: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+    peer: &[u8],
+    share: &[u8],
+    recipient: &[u8],
+) -> CryptographyResult<Vec<u8>> {
+    let peer = openssl::pkey::PKey::public_key_from_raw_bytes(peer, openssl::pkey::Id::X25519)?;
+    let mut deriver = openssl::derive::Deriver::new(secret)?;
+    deriver.set_peer(&peer)?;
+    // OpenSSL refuses to return an all-zero shared secret, which is what a
+    // low order point produces.
+    let shared = deriver
+        .derive_to_vec()
+        .map_err(|_| pyo3::exceptions::PyValueError::new_err("Invalid X25519 recipient"))?;
+
+    let mut salt = share.to_vec();
+    salt.extend_from_slice(recipient);
+    hkdf_sha256(&shared, &salt, X25519_LABEL)
+}
+
+#[cfg(not(CRYPTOGRAPHY_IS_LIBRESSL))]
+fn scrypt_wrap_key(passphrase: &[u8], salt: &[u8], work_factor: u8) -> CryptographyResult<Vec<u8>> {
+    let mut scrypt_salt = SCRYPT_LABEL.to_vec();
+    scrypt_salt.extend_from_slice(salt);
+    let mut key = vec![0; 32];
+    openssl::pkcs5::scrypt(
+        passphrase,
+        &scrypt_salt,
+        1 << work_factor,
+        8,
+        1,
+        u64::MAX / 2,
+        &mut key,
+    )
+    .map_err(|_| {
+        pyo3::exceptions::PyMemoryError::new_err("Not enough memory to derive the age scrypt key.")
+    })?;
+    Ok(key)
+}
+
+#[cfg(CRYPTOGRAPHY_IS_LIBRESSL)]
+fn scrypt_wrap_key(_: &[u8], _: &[u8], _: u8) -> CryptographyResult<Vec<u8>> {
+    Err(scrypt_unsupported())
+}
+
+fn scrypt_unsupported() -> CryptographyError {
+    CryptographyError::from(exceptions::UnsupportedAlgorithm::new_err((
+        "This version of OpenSSL does not support scrypt",
+        exceptions::Reasons::UNSUPPORTED_CIPHER,
+    )))
+}
+
+struct Stanza {
+    tag: Vec<u8>,
+    args: Vec<Vec<u8>>,
+    body: Vec<u8>,
+}
+
+impl Stanza {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(STANZA_PREFIX);
+        out.extend_from_slice(&self.tag);
+        for arg in &self.args {
+            out.push(b' ');
+            out.extend_from_slice(arg);
+        }
+        out.push(b'\n');
+        // The body is wrapped at 64 columns and always ends with a line
+        // shorter than that, even if it has to be empty.
+        let body = b64_encode(&self.body);
+        let mut lines = body.as_bytes().chunks(BODY_COLUMNS);
+        loop {
+            let line = lines.next().unwrap_or(b"");
+            out.extend_from_slice(line);
+            out.push(b'\n');
+            if line.len() < BODY_COLUMNS {
+                break;
+            }
+        }
+    }
+}
+
+struct Header {
+    stanzas: Vec<Stanza>,
+    // The header up to and including the `---` the MAC is computed over.
+    mac_data_length: usize,
+    mac: Vec<u8>,
+}
+
+// Returns the next `\n` terminated line from `data` at `pos`, and the position
+// after it, or `None` if the line isn't complete yet.
+fn next_line(data: &[u8], pos: usize) -> Option<(&[u8], usize)> {
+    let length = data[pos..].iter().position(|&b| b == b'\n')?;
+    Some((&data[pos..pos + length], pos + length + 1))
+}
+
+fn is_valid_arg(arg: &[u8]) -> bool {
+    !arg.is_empty() && arg.iter().all(|b| (0x21..=0x7e).contains(b))
+}
+
+// Parses the header at the start of `data`. Returns the header and its
+// length, or `None` if more data is needed to parse it.
+fn parse_header(data: &[u8]) -> CryptographyResult<Option<(Header, usize)>> {
+    // Fail early on data that isn't an age file, rather than buffering it.
+    let prefix_length = std::cmp::min(data.len(), INTRO.len());
+    if data[..prefix_length] != INTRO[..prefix_length] {
+        return Err(invalid_header());
+    }
+    if data.len() < INTRO.len() {
+        return Ok(None);
+    }
+
+    let mut stanzas = vec![];
+    let mut pos = INTRO.len();
+    loop {
+        let (line, line_end) = match next_line(data, pos) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        if let Some(mac) = line.strip_prefix(MAC_PREFIX) {
+            let mac = mac
+                .strip_prefix(b" ")
+                .and_then(b64_decode)
+                .filter(|mac| mac.len() == 32)
+                .ok_or_else(invalid_header)?;
+            if stanzas.is_empty() {
+                return Err(invalid_header());
+            }
+            let header = Header {
+                stanzas,
+                mac_data_length: pos + MAC_PREFIX.len(),
+                mac,
+            };
+            return Ok(Some((header, line_end)));
+        }
+
+        let mut args = line
+            .strip_prefix(STANZA_PREFIX)
+            .ok_or_else(invalid_header)?
+            .split(|&b| b == b' ')
+            .map(|arg| arg.to_vec())
+            .collect::<Vec<_>>();
+        if !args.iter().all(|arg| is_valid_arg(arg)) {
+            return Err(invalid_header());
+        }
+        let tag = args.remove(0);
+
+        let mut body = vec![];
+        pos = line_end;
+        loop {
+            let (line, line_end) = match next_line(data, pos) {
+                Some(v) => v,
+                None => return Ok(None),
+            };
+            if line.len() > BODY_COLUMNS {
+                return Err(invalid_header());
+            }
+            body.extend_from_slice(line);
+            pos = line_end;
+            if line.len() < BODY_COLUMNS {
+                break;
+            }
+        }
+        let body = b64_decode(&body).ok_or_else(invalid_header)?;
+        stanzas.push(Stanza { tag, args, body });
+    }
+}
+
+#[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.age")]
+struct ScryptRecipient {
+    passphrase: Vec<u8>,
+    work_factor: u8,
+}
+
+#[pyo3::prelude::pymethods]
+impl ScryptRecipient {
+    #[new]
+    #[pyo3(signature = (passphrase, work_factor=DEFAULT_WORK_FACTOR))]
+    fn new(passphrase: CffiBuf<'_>, work_factor: u8) -> CryptographyResult<ScryptRecipient> {
+        if cfg!(CRYPTOGRAPHY_IS_LIBRESSL) {
+            return Err(scrypt_unsupported());
+        }
+        if passphrase.as_bytes().is_empty() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("passphrase must not be empty"),
+            ));
+        }
+        check_work_factor("work_factor", work_factor)?;
+        Ok(ScryptRecipient {
+            passphrase: passphrase.as_bytes().to_vec(),
+            work_factor,
+        })
+    }
+
+    #[getter]
+    fn work_factor(&self) -> u8 {
+        self.work_factor
+    }
+}
+
+#[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.age")]
+struct ScryptIdentity {
+    passphrase: Vec<u8>,
+    max_work_factor: u8,
+}
+
+#[pyo3::prelude::pymethods]
+impl ScryptIdentity {
+    #[new]
+    #[pyo3(signature = (passphrase, max_work_factor=DEFAULT_MAX_WORK_FACTOR))]
+    fn new(passphrase: CffiBuf<'_>, max_work_factor: u8) -> CryptographyResult<ScryptIdentity> {
+        if cfg!(CRYPTOGRAPHY_IS_LIBRESSL) {
+            return Err(scrypt_unsupported());
+        }
+        if passphrase.as_bytes().is_empty() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("passphrase must not be empty"),
+            ));
+        }
+        check_work_factor("max_work_factor", max_work_factor)?;
+        Ok(ScryptIdentity {
+            passphrase: passphrase.as_bytes().to_vec(),
+            max_work_factor,
+        })
+    }
+
+    #[getter]
+    fn max_work_factor(&self) -> u8 {
+        self.max_work_factor
+    }
+}
+
+#[derive(pyo3::FromPyObject)]
+enum Recipient<'a> {
+    X25519(pyo3::PyRef<'a, x25519::X25519PublicKey>),
+    Scrypt(pyo3::PyRef<'a, ScryptRecipient>),
+}
+
+impl Recipient<'_> {
+    fn wrap(&self, file_key: &[u8]) -> CryptographyResult<Stanza> {
+        match self {
+            Recipient::X25519(public_key) => {
+                let ephemeral = openssl::pkey::PKey::generate_x25519()?;
+                let share = ephemeral.raw_public_key()?;
+                let recipient = public_key.pkey.raw_public_key()?;
+                let key = x25519_wrap_key(&ephemeral, &recipient, &share, &recipient)?;
+                Ok(Stanza {
+                    tag: b"X25519".to_vec(),
+                    args: vec![b64_encode(&share).into_bytes()],
+                    body: seal(&key, &[0; 12], file_key)?,
+                })
+            }
+            Recipient::Scrypt(recipient) => {
+                let mut salt = [0; SCRYPT_SALT_LENGTH];
+                openssl::rand::rand_bytes(&mut salt)?;
+                let key = scrypt_wrap_key(&recipient.passphrase, &salt, recipient.work_factor)?;
+                Ok(Stanza {
+                    tag: b"scrypt".to_vec(),
+                    args: vec![
+                        b64_encode(&salt).into_bytes(),
+                        recipient.work_factor.to_string().into_bytes(),
+                    ],
+                    body: seal(&key, &[0; 12], file_key)?,
+                })
+            }
+        }
+    }
+}
+
+// Identities are copied out of the Python objects so that a `Decryptor` can
+// hold on to them.
+enum Identity {
+    X25519(openssl::pkey::PKey<openssl::pkey::Private>),
+    Scrypt {
+        passphrase: Vec<u8>,
+        max_work_factor: u8,
+    },
+}
+
+#[derive(pyo3::FromPyObject)]
+enum PyIdentity<'a> {
+    X25519(pyo3::PyRef<'a, x25519::X25519PrivateKey>),
+    Scrypt(pyo3::PyRef<'a, ScryptIdentity>),
+}
+
+impl Identity {
+    // Returns the file key if this identity can unwrap `stanza`. Stanzas of
+    // other types are skipped, but a malformed stanza of this identity's type
+    // is an error.
+    fn open_stanza(&self, stanza: &Stanza) -> CryptographyResult<Option<Vec<u8>>> {
+        let key = match self {
+            Identity::X25519(private_key) => {
+                if stanza.tag != b"X25519" {
+                    return Ok(None);
+                }
+                let share = match &stanza.args[..] {
+                    [share] => b64_decode(share)
+                        .filter(|share| share.len() == 32)
+                        .ok_or_else(invalid_header)?,
+                    _ => return Err(invalid_header()),
+                };
+                if stanza.body.len() != WRAPPED_KEY_LENGTH {
+                    return Err(invalid_header());
+                }
+                let recipient = private_key.raw_public_key()?;
+                x25519_wrap_key(private_key, &share, &share, &recipient)?
+            }
+            Identity::Scrypt {
+                passphrase,
+                max_work_factor,
+            } => {
+                if stanza.tag != b"scrypt" {
+                    return Ok(None);
+                }
+                let (salt, work_factor) = match &stanza.args[..] {
+                    [salt, work_factor] => (
+                        b64_decode(salt)
+                            .filter(|salt| salt.len() == SCRYPT_SALT_LENGTH)
+                            .ok_or_else(invalid_header)?,
+                        parse_work_factor(work_factor).ok_or_else(invalid_header)?,
+                    ),
+                    _ => return Err(invalid_header()),
+                };
+                if stanza.body.len() != WRAPPED_KEY_LENGTH {
+                    return Err(invalid_header());
+                }
+                if work_factor > *max_work_factor {
+                    return Err(CryptographyError::from(
+                        pyo3::exceptions::PyValueError::new_err(format!(
+                            "The scrypt work factor {work_factor} is larger than the maximum of {max_work_factor}"
+                        )),
+                    ));
+                }
+                scrypt_wrap_key(passphrase, &salt, work_factor)?
+            }
+        };
+        Ok(open(&key, &[0; 12], &stanza.body))
+    }
+}
+
+// Work factors are written in decimal without leading zeros.
+fn parse_work_factor(data: &[u8]) -> Option<u8> {
+    if data.is_empty() || data[0] == b'0' || !data.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    std::str::from_utf8(data)
+        .ok()?
+        .parse::<u8>()
+        .ok()
+        .filter(|&work_factor| work_factor <= MAX_WORK_FACTOR)
+}
+
+fn unwrap_file_key(header: &Header, identities: &[Identity]) -> CryptographyResult<Vec<u8>> {
+    // A passphrase must be the only way to decrypt a file, otherwise it
+    // wouldn't be authenticated by knowing the passphrase.
+    if header.stanzas.len() > 1 && header.stanzas.iter().any(|s| s.tag == b"scrypt") {
+        return Err(invalid_header());
+    }
+    for stanza in &header.stanzas {
+        for identity in identities {
+            if let Some(file_key) = identity.open_stanza(stanza)? {
+                return Ok(file_key);
+            }
+        }
+    }
+    Err(CryptographyError::from(
+        pyo3::exceptions::PyValueError::new_err("No identity matched any of the recipients"),
+    ))
+}
+
+// The STREAM construction the payload is encrypted with. Each chunk's nonce
+// is an 11 byte big endian counter followed by a flag for the last chunk.
+struct Stream {
+    key: Vec<u8>,
+    counter: u64,
+}
+
+impl Stream {
+    fn new(file_key: &[u8], nonce: &[u8]) -> CryptographyResult<Stream> {
+        Ok(Stream {
+            key: hkdf_sha256(file_key, nonce, b"payload")?,
+            counter: 0,
+        })
+    }
+
+    fn nonce(&self, last: bool) -> [u8; 12] {
+        let mut nonce = [0; 12];
+        nonce[3..11].copy_from_slice(&self.counter.to_be_bytes());
+        nonce[11] = u8::from(last);
+        nonce
+    }
+
+    fn seal(&mut self, chunk: &[u8], last: bool) -> CryptographyResult<Vec<u8>> {
+        let ciphertext = seal(&self.key, &self.nonce(last), chunk)?;
+        self.counter += 1;
+        Ok(ciphertext)
+    }
+
+    fn open(&mut self, chunk: &[u8], last: bool) -> CryptographyResult<Vec<u8>> {
+        let plaintext = open(&self.key, &self.nonce(last), chunk).ok_or_else(invalid_tag)?;
+        self.counter += 1;
+        Ok(plaintext)
+    }
+}
+
+struct EncryptorState {
+    // The header and payload nonce, until they're returned by `update`.
+    header: Vec<u8>,
+    stream: Stream,
+    buffer: Vec<u8>,
+}
+
+#[pyo3::prelude::pyclass(module = "cryptography.hazmat.bindings._rust.openssl.age")]
+struct Encryptor {
+    state: Option<EncryptorState>,
+}
+
+impl Encryptor {
+    fn get_mut_state(&mut self) -> CryptographyResult<&mut EncryptorState> {
+        if let Some(state) = self.state.as_mut() {
+            return Ok(state);
+        }
+        Err(already_finalized_error())
+    }
+}
+
+#[pyo3::prelude::pymethods]
+impl Encryptor {
+    #[new]
+    fn new(recipients: Vec<Recipient<'_>>) -> CryptographyResult<Encryptor> {
+        check_supported()?;
+        if recipients.is_empty() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("At least one recipient is required"),
+            ));
+        }
+        if recipients.len() > 1 && recipients.iter().any(|r| matches!(r, Recipient::Scrypt(_))) {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "A ScryptRecipient can't be used with other recipients",
+                ),
+            ));
+        }
+
+        let mut file_key = [0; FILE_KEY_LENGTH];
+        openssl::rand::rand_bytes(&mut file_key)?;
+
+        let mut header = INTRO.to_vec();
+        for recipient in &recipients {
+            recipient.wrap(&file_key)?.encode(&mut header);
+        }
+        header.extend_from_slice(MAC_PREFIX);
+        let mac = header_mac(&file_key, &header)?;
+        header.push(b' ');
+        header.extend_from_slice(b64_encode(&mac).as_bytes());
+        header.push(b'\n');
+
+        let mut nonce = [0; NONCE_LENGTH];
+        openssl::rand::rand_bytes(&mut nonce)?;
+        header.extend_from_slice(&nonce);
+
+        Ok(Encryptor {
+            state: Some(EncryptorState {
+                header,
+                stream: Stream::new(&file_key, &nonce)?,
+                buffer: vec![],
+            }),
+        })
+    }
+
+    fn update<'p>(
+        &mut self,
+        py: pyo3::Python<'p>,
+        data: CffiBuf<'_>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let state = self.get_mut_state()?;
+        let mut out = std::mem::take(&mut state.header);
+        state.buffer.extend_from_slice(data.as_bytes());
+        // The last chunk is flagged, so a full chunk can only be encrypted
+        // once it's known that more data follows it.
+        let mut start = 0;
+        while state.buffer.len() - start > CHUNK_LENGTH {
+            let chunk = &state.buffer[start..start + CHUNK_LENGTH];
+            out.extend_from_slice(&state.stream.seal(chunk, false)?);
+            start += CHUNK_LENGTH;
+        }
+        state.buffer.drain(..start);
+        Ok(pyo3::types::PyBytes::new(py, &out))
+    }
+
+    fn finalize<'p>(
+        &mut self,
+        py: pyo3::Python<'p>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let mut state = self.state.take().ok_or_else(already_finalized_error)?;
+        let mut out = std::mem::take(&mut state.header);
+        out.extend_from_slice(&state.stream.seal(&state.buffer, true)?);
+        Ok(pyo3::types::PyBytes::new(py, &out))
+    }
+}
+
+struct DecryptorState {
+    identities: Vec<Identity>,
+    buffer: Vec<u8>,
+    file_key: Option<Vec<u8>>,
+    stream: Option<Stream>,
+}
+
+#[pyo3::prelude::pyclass(module = "cryptography.hazmat.bindings._rust.openssl.age")]
+struct Decryptor {
+    state: Option<DecryptorState>,
+}
+
+impl Decryptor {
+    fn get_mut_state(&mut self) -> CryptographyResult<&mut DecryptorState> {
+        if let Some(state) = self.state.as_mut() {
+            return Ok(state);
+        }
+        Err(already_finalized_error())
+    }
+}
+
+impl DecryptorState {
+    // Consumes the header and payload nonce from the buffer once they're
+    // complete. Returns whether the payload can be decrypted.
+    fn read_header(&mut self) -> CryptographyResult<bool> {
+        if self.file_key.is_none() {
+            let (header, length) = match parse_header(&self.buffer)? {
+                Some(v) => v,
+                None => return Ok(false),
+            };
+            let file_key = unwrap_file_key(&header, &self.identities)?;
+            let mac = header_mac(&file_key, &self.buffer[..header.mac_data_length])?;
+            if !openssl::memcmp::eq(&mac, &header.mac) {
+                return Err(invalid_tag());
+            }
+            self.buffer.drain(..length);
+            self.file_key = Some(file_key);
+        }
+        if self.stream.is_none() {
+            if self.buffer.len() < NONCE_LENGTH {
+                return Ok(false);
+            }
+            let nonce = self.buffer.drain(..NONCE_LENGTH).collect::<Vec<_>>();
+            self.stream = Some(Stream::new(self.file_key.as_ref().unwrap(), &nonce)?);
+        }
+        Ok(true)
+    }
+}
+
+#[pyo3::prelude::pymethods]
+impl Decryptor {
+    #[new]
+    fn new(identities: Vec<PyIdentity<'_>>) -> CryptographyResult<Decryptor> {
+        check_supported()?;
+        if identities.is_empty() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("At least one identity is required"),
+            ));
+        }
+        let identities = identities
+            .iter()
+            .map(|identity| match identity {
+                PyIdentity::X25519(private_key) => Identity::X25519(private_key.pkey.clone()),
+                PyIdentity::Scrypt(identity) => Identity::Scrypt {
+                    passphrase: identity.passphrase.clone(),
+                    max_work_factor: identity.max_work_factor,
+                },
+            })
+            .collect();
+        Ok(Decryptor {
+            state: Some(DecryptorState {
+                identities,
+                buffer: vec![],
+                file_key: None,
+                stream: None,
+            }),
+        })
+    }
+
+    fn update<'p>(
+        &mut self,
+        py: pyo3::Python<'p>,
+        data: CffiBuf<'_>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let state = self.get_mut_state()?;
+        state.buffer.extend_from_slice(data.as_bytes());
+        let mut out = vec![];
+        if !state.read_header()? {
+            return Ok(pyo3::types::PyBytes::new(py, &out));
+        }
+        // As with encryption, a full chunk is only known not to be the last
+        // one once more data follows it.
+        let stream = state.stream.as_mut().unwrap();
+        let mut start = 0;
+        while state.buffer.len() - start > CHUNK_LENGTH + TAG_LENGTH {
+            let chunk = &state.buffer[start..start + CHUNK_LENGTH + TAG_LENGTH];
+            out.extend_from_slice(&stream.open(chunk, false)?);
+            start += CHUNK_LENGTH + TAG_LENGTH;
+        }
+        state.buffer.drain(..start);
+        Ok(pyo3::types::PyBytes::new(py, &out))
+    }
+
+    fn finalize<'p>(
+        &mut self,
+        py: pyo3::Python<'p>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let mut state = self.state.take().ok_or_else(already_finalized_error)?;
+        if !state.read_header()? {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("Truncated age file"),
+            ));
+        }
+        let stream = state.stream.as_mut().unwrap();
+        // Only an empty file ends with an empty chunk.
+        if state.buffer.len() == TAG_LENGTH && stream.counter > 0 {
+            return Err(invalid_tag());
+        }
+        let out = stream.open(&state.buffer, true)?;
+        Ok(pyo3::types::PyBytes::new(py, &out))
+    }
+}
+
+#[pyo3::prelude::pyfunction]
+fn load_x25519_recipient(data: &str) -> CryptographyResult<x25519::X25519PublicKey> {
+    let raw = match bech32_decode(data) {
+        Some((hrp, raw)) if hrp == RECIPIENT_HRP && raw.len() == 32 => raw,
+        _ => {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("Invalid age X25519 recipient"),
+            ))
+        }
+    };
+    let pkey = openssl::pkey::PKey::public_key_from_raw_bytes(&raw, openssl::pkey::Id::X25519)?;
+    Ok(x25519::public_key_from_pkey(&pkey))
+}
+
+#[pyo3::prelude::pyfunction]
+fn load_x25519_identity(data: &str) -> CryptographyResult<x25519::X25519PrivateKey> {
+    let raw = match bech32_decode(data) {
+        Some((hrp, raw)) if hrp == IDENTITY_HRP && raw.len() == 32 => raw,
+        _ => {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("Invalid age X25519 identity"),
+            ))
+        }
+    };
+    let pkey = openssl::pkey::PKey::private_key_from_raw_bytes(&raw, openssl::pkey::Id::X25519)?;
+    Ok(x25519::private_key_from_pkey(&pkey))
+}
+
+#[pyo3::prelude::pyfunction]
+fn encode_x25519_recipient(
+    public_key: pyo3::PyRef<'_, x25519::X25519PublicKey>,
+) -> CryptographyResult<String> {
+    Ok(bech32_encode(
+        RECIPIENT_HRP,
+        &public_key.pkey.raw_public_key()?,
+    ))
+}
+
+#[pyo3::prelude::pyfunction]
+fn encode_x25519_identity(
+    private_key: pyo3::PyRef<'_, x25519::X25519PrivateKey>,
+) -> CryptographyResult<String> {
+    // Identities are conventionally written in uppercase.
+    Ok(bech32_encode(IDENTITY_HRP, &private_key.pkey.raw_private_key()?).to_ascii_uppercase())
+}
+
+pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let m = pyo3::prelude::PyModule::new(py, "age")?;
+    m.add_function(pyo3::wrap_pyfunction!(load_x25519_recipient, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(load_x25519_identity, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(encode_x25519_recipient, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(encode_x25519_identity, m)?)?;
+
+    m.add_class::<ScryptRecipient>()?;
+    m.add_class::<ScryptIdentity>()?;
+    m.add_class::<Encryptor>()?;
+    m.add_class::<Decryptor>()?;
+
+    Ok(m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{b64_decode, b64_encode, bech32_decode, bech32_encode, parse_work_factor};
+
+    #[test]
+    fn test_b64() {
+        assert_eq!(b64_encode(b"\xfb\xff"), "+/8");
+        assert_eq!(b64_decode(b"+/8").unwrap(), b"\xfb\xff");
+        assert_eq!(b64_decode(b"").unwrap(), b"");
+        // Padding, the URL safe alphabet and non-canonical encodings are
+        // rejected.
+        assert!(b64_decode(b"+/8=").is_none());
+        assert!(b64_decode(b"-_8").is_none());
+        assert!(b64_decode(b"+/9").is_none());
+        assert!(b64_decode(b"+/8\n").is_none());
+        assert!(b64_decode(b"A").is_none());
+    }
+
+    #[test]
+    fn test_bech32() {
+        let s = "age1ql3z7hjy54pw3hyww5ayyfg7zqgvc7w3j2elw8zmrj2kg5sfn9aqmcac8p";
+        let (hrp, data) = bech32_decode(s).unwrap();
+        assert_eq!(hrp, "age");
+        assert_eq!(data.len(), 32);
+        assert_eq!(bech32_encode(&hrp, &data), s);
+        assert!(bech32_decode(&s.to_ascii_uppercase()).is_some());
+
+        // Mixed case and a bad checksum.
+        assert!(
+            bech32_decode("Age1ql3z7hjy54pw3hyww5ayyfg7zqgvc7w3j2elw8zmrj2kg5sfn9aqmcac8p")
+                .is_none()
+        );
+        assert!(
+            bech32_decode("age1ql3z7hjy54pw3hyww5ayyfg7zqgvc7w3j2elw8zmrj2kg5sfn9aqmcac8q")
+                .is_none()
+        );
+        assert!(bech32_decode("age1qqqqqb").is_none());
+    }
+
+    #[test]
+    fn test_parse_work_factor() {
+        assert_eq!(parse_work_factor(b"18"), Some(18));
+        assert_eq!(parse_work_factor(b"1"), Some(1));
+        assert_eq!(parse_work_factor(b"018"), None);
+        assert_eq!(parse_work_factor(b"0"), None);
+        assert_eq!(parse_work_factor(b"31"), None);
+        assert_eq!(parse_work_factor(b"+1"), None);
+        assert_eq!(parse_work_factor(b""), None);
+    }
+}
@@ -0,0 +1,111 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+use crate::backend::utils;
+use crate::error::{CryptographyError, CryptographyResult};
+
+// Converts `v` to a `BigNum` which OpenSSL will only operate on with its
+// constant time code paths.
+fn const_time_bn(
+    py: pyo3::Python<'_>,
+    v: &pyo3::types::PyLong,
+    name: &str,
+) -> CryptographyResult<openssl::bn::BigNum> {
+    if v.lt(0)? {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(format!("{name} must be non-negative")),
+        ));
+    }
+    let mut bn = utils::py_int_to_bn(py, v)?;
+    bn.set_const_time();
+    Ok(bn)
+}
+
+fn new_const_time_bn() -> CryptographyResult<openssl::bn::BigNum> {
+    let mut bn = openssl::bn::BigNum::new_secure()?;
+    bn.set_const_time();
+    Ok(bn)
+}
+
+#[pyo3::prelude::pyfunction]
+fn mod_exp<'p>(
+    py: pyo3::Python<'p>,
+    base: &pyo3::types::PyLong,
+    exponent: &pyo3::types::PyLong,
+    modulus: &pyo3::types::PyLong,
+) -> CryptographyResult<&'p pyo3::PyAny> {
+    let base = const_time_bn(py, base, "base")?;
+    let exponent = const_time_bn(py, exponent, "exponent")?;
+    let modulus = const_time_bn(py, modulus, "modulus")?;
+    // OpenSSL's constant time exponentiation uses Montgomery multiplication,
+    // which needs an odd modulus.
+    if !modulus.is_odd() || modulus.num_bits() < 2 {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(
+                "modulus must be an odd integer greater than 1",
+            ),
+        ));
+    }
+
+    let mut ctx = openssl::bn::BigNumContext::new_secure()?;
+    let mut result = new_const_time_bn()?;
+    result.mod_exp(&base, &exponent, &modulus, &mut ctx)?;
+    utils::bn_to_py_int(py, &result)
+}
+
+#[pyo3::prelude::pyfunction]
+fn mod_inverse<'p>(
+    py: pyo3::Python<'p>,
+    value: &pyo3::types::PyLong,
+    modulus: &pyo3::types::PyLong,
+) -> CryptographyResult<&'p pyo3::PyAny> {
+    let value = const_time_bn(py, value, "value")?;
+    let modulus = const_time_bn(py, modulus, "modulus")?;
+    if modulus.num_bits() < 2 {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("modulus must be greater than 1"),
+        ));
+    }
+
+    let mut ctx = openssl::bn::BigNumContext::new_secure()?;
+    let mut result = new_const_time_bn()?;
+    result
+        .mod_inverse(&value, &modulus, &mut ctx)
+        .map_err(|_| {
+            pyo3::exceptions::PyValueError::new_err("value has no inverse modulo modulus")
+        })?;
+    utils::bn_to_py_int(py, &result)
+}
+
+#[pyo3::prelude::pyfunction]
+fn random_in_range<'p>(
+    py: pyo3::Python<'p>,
+    low: &pyo3::types::PyLong,
+    high: &pyo3::types::PyLong,
+) -> CryptographyResult<&'p pyo3::PyAny> {
+    let low = const_time_bn(py, low, "low")?;
+    let high = const_time_bn(py, high, "high")?;
+    if high <= low {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("high must be greater than low"),
+        ));
+    }
+
+    let mut range = new_const_time_bn()?;
+    range.checked_sub(&high, &low)?;
+    let mut offset = new_const_time_bn()?;
+    range.rand_range(&mut offset)?;
+    let mut result = new_const_time_bn()?;
+    result.checked_add(&low, &offset)?;
+    utils::bn_to_py_int(py, &result)
+}
+
+pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let m = pyo3::prelude::PyModule::new(py, "bignum")?;
+    m.add_function(pyo3::wrap_pyfunction!(mod_exp, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(mod_inverse, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(random_in_range, m)?)?;
+
+    Ok(m)
+}
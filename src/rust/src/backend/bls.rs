@@ -0,0 +1,2102 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! BLS signatures over the BLS12-381 curve, following
+//! draft-irtf-cfrg-bls-signature-05 with the proof of possession scheme, and
+//! hashing to the curve as specified by RFC 9380.
+//!
+//! None of the TLS libraries we support implement pairings, so the field
+//! tower, curve arithmetic and optimal ate pairing are implemented here, with
+//! base field elements as 6 64-bit limbs in Montgomery form.
+//!
+//! Signing and deriving the public key only use the private key as the
+//! scalar in `Point::mul`, which is a fixed length ladder over complete
+//! addition formulas, so that neither its branches nor its memory accesses
+//! depend on the scalar.
+
+use crate::backend::kdf;
+use crate::buf::CffiBuf;
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::{exceptions, types};
+
+// The base field modulus p, and the Montgomery constants for R = 2^384.
+const MODULUS: [u64; 6] = hex_limbs(
+    "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab",
+);
+const R2: [u64; 6] = hex_limbs(
+    "11988fe592cae3aa9a793e85b519952d67eb88a9939d83c08de5476c4c95b6d50a76e6a609d104f1f4df1f341c341746",
+);
+const R3: [u64; 6] = hex_limbs(
+    "0aa6346091755d4d2512d4356572472834c04e5e921e17619a53352a615e29dd315f831e03a7adf8ed48ac6bd94ca1e0",
+);
+// -p^-1 mod 2^64
+const INV: u64 = 0x89f3_fffc_fffc_fffd;
+
+const P_MINUS_2: [u64; 6] = hex_limbs(
+    "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaa9",
+);
+const P_PLUS_1_DIV_4: [u64; 6] = hex_limbs(
+    "0680447a8e5ff9a692c6e9ed90d2eb35d91dd2e13ce144afd9cc34a83dac3d8907aaffffac54ffffee7fbfffffffeaab",
+);
+const P_MINUS_3_DIV_4: [u64; 6] = hex_limbs(
+    "0680447a8e5ff9a692c6e9ed90d2eb35d91dd2e13ce144afd9cc34a83dac3d8907aaffffac54ffffee7fbfffffffeaaa",
+);
+const P_MINUS_1_DIV_2: [u64; 6] = hex_limbs(
+    "0d0088f51cbff34d258dd3db21a5d66bb23ba5c279c2895fb39869507b587b120f55ffff58a9ffffdcff7fffffffd555",
+);
+
+// The order r of G1 and G2, as big endian limbs.
+const GROUP_ORDER: [u64; 4] = [
+    0x73ed_a753_299d_7d48,
+    0x3339_d808_09a1_d805,
+    0x53bd_a402_fffe_5bfe,
+    0xffff_ffff_0000_0001,
+];
+
+// |x|, where the curve parameter x = -0xd201000000010000.
+const BLS_X: u64 = 0xd201_0000_0001_0000;
+
+// Parses a big endian hex string into little endian limbs.
+const fn hex_limbs(s: &str) -> [u64; 6] {
+    let s = s.as_bytes();
+    let mut limbs = [0u64; 6];
+    let mut i = 0;
+    while i < s.len() {
+        let c = s[s.len() - 1 - i];
+        let v = match c {
+            b'0'..=b'9' => c - b'0',
+            b'a'..=b'f' => c - b'a' + 10,
+            _ => panic!("invalid hex digit"),
+        };
+        limbs[i / 16] |= (v as u64) << (4 * (i % 16));
+        i += 1;
+    }
+    limbs
+}
+
+const fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = (a as u128) + (b as u128) + (carry as u128);
+    (t as u64, (t >> 64) as u64)
+}
+
+// `borrow` is either 0 or all ones, and so is the returned borrow.
+const fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let t = (a as u128).wrapping_sub((b as u128) + ((borrow >> 63) as u128));
+    (t as u64, (t >> 64) as u64)
+}
+
+const fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let t = (a as u128) + (b as u128) * (c as u128) + (carry as u128);
+    (t as u64, (t >> 64) as u64)
+}
+
+// Returns `a - p` if `a >= p`, and `a` otherwise.
+const fn reduce(a: [u64; 6]) -> [u64; 6] {
+    let mut r = [0u64; 6];
+    let mut borrow = 0;
+    let mut i = 0;
+    while i < 6 {
+        let (d, b) = sbb(a[i], MODULUS[i], borrow);
+        r[i] = d;
+        borrow = b;
+        i += 1;
+    }
+    let mut i = 0;
+    while i < 6 {
+        r[i] = (a[i] & borrow) | (r[i] & !borrow);
+        i += 1;
+    }
+    r
+}
+
+// Computes a * b * R^-1 mod p, for a * b < p * R.
+const fn montgomery_mul(a: &[u64; 6], b: &[u64; 6]) -> [u64; 6] {
+    let mut t = [0u64; 7];
+    let mut i = 0;
+    while i < 6 {
+        let mut carry = 0;
+        let mut j = 0;
+        while j < 6 {
+            let (lo, hi) = mac(t[j], a[j], b[i], carry);
+            t[j] = lo;
+            carry = hi;
+            j += 1;
+        }
+        let (t6, t7) = adc(t[6], carry, 0);
+
+        let m = t[0].wrapping_mul(INV);
+        let (_, mut carry) = mac(t[0], m, MODULUS[0], 0);
+        let mut j = 1;
+        while j < 6 {
+            let (lo, hi) = mac(t[j], m, MODULUS[j], carry);
+            t[j - 1] = lo;
+            carry = hi;
+            j += 1;
+        }
+        let (lo, hi) = adc(t6, carry, 0);
+        t[5] = lo;
+        t[6] = t7 + hi;
+        i += 1;
+    }
+    reduce([t[0], t[1], t[2], t[3], t[4], t[5]])
+}
+
+fn add_mod(a: &[u64; 6], b: &[u64; 6]) -> [u64; 6] {
+    let mut r = [0u64; 6];
+    let mut carry = 0;
+    for (i, limb) in r.iter_mut().enumerate() {
+        (*limb, carry) = adc(a[i], b[i], carry);
+    }
+    reduce(r)
+}
+
+fn sub_mod(a: &[u64; 6], b: &[u64; 6]) -> [u64; 6] {
+    let mut r = [0u64; 6];
+    let mut borrow = 0;
+    for (i, limb) in r.iter_mut().enumerate() {
+        (*limb, borrow) = sbb(a[i], b[i], borrow);
+    }
+    // Adds p back if the subtraction underflowed.
+    let mut carry = 0;
+    for (i, limb) in r.iter_mut().enumerate() {
+        (*limb, carry) = adc(*limb, MODULUS[i] & borrow, carry);
+    }
+    r
+}
+
+fn limbs_from_be_bytes(data: &[u8]) -> [u64; 6] {
+    let mut limbs = [0u64; 6];
+    for (i, chunk) in data.rchunks(8).enumerate() {
+        let mut buf = [0u8; 8];
+        buf[8 - chunk.len()..].copy_from_slice(chunk);
+        limbs[i] = u64::from_be_bytes(buf);
+    }
+    limbs
+}
+
+trait Field:
+    Copy
+    + Eq
+    + std::ops::Add<Output = Self>
+    + std::ops::Sub<Output = Self>
+    + std::ops::Mul<Output = Self>
+    + std::ops::Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    // The extension degree over Fp.
+    const DEGREE: usize;
+
+    fn invert(self) -> Self;
+    fn sqrt(self) -> Option<Self>;
+    fn sgn0(self) -> bool;
+    fn is_lexicographically_largest(self) -> bool;
+    fn from_be_bytes(data: &[u8]) -> Option<Self>;
+    fn write_be_bytes(self, out: &mut [u8]);
+    // Reduces the 64 * DEGREE bytes produced for one element by
+    // hash_to_field.
+    fn from_uniform_bytes(data: &[u8]) -> Self;
+    // Returns `a` if `choice` is 0 and `b` if it's 1, without branching.
+    fn conditional_select(a: Self, b: Self, choice: u64) -> Self;
+
+    fn square(self) -> Self {
+        self * self
+    }
+
+    // `exp` is in little endian limbs.
+    fn pow_vartime(self, exp: &[u64]) -> Self {
+        let mut result = Self::ONE;
+        for limb in exp.iter().rev() {
+            for i in (0..64).rev() {
+                result = result.square();
+                if (limb >> i) & 1 == 1 {
+                    result = result * self;
+                }
+            }
+        }
+        result
+    }
+}
+
+// An element of Fp, in Montgomery form.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Fp([u64; 6]);
+
+const fn fp(s: &str) -> Fp {
+    Fp(montgomery_mul(&hex_limbs(s), &R2))
+}
+
+impl Fp {
+    fn to_canonical(self) -> [u64; 6] {
+        montgomery_mul(&self.0, &[1, 0, 0, 0, 0, 0])
+    }
+}
+
+impl std::ops::Add for Fp {
+    type Output = Fp;
+
+    fn add(self, rhs: Fp) -> Fp {
+        Fp(add_mod(&self.0, &rhs.0))
+    }
+}
+
+impl std::ops::Sub for Fp {
+    type Output = Fp;
+
+    fn sub(self, rhs: Fp) -> Fp {
+        Fp(sub_mod(&self.0, &rhs.0))
+    }
+}
+
+impl std::ops::Neg for Fp {
+    type Output = Fp;
+
+    fn neg(self) -> Fp {
+        Fp([0; 6]) - self
+    }
+}
+
+impl std::ops::Mul for Fp {
+    type Output = Fp;
+
+    fn mul(self, rhs: Fp) -> Fp {
+        Fp(montgomery_mul(&self.0, &rhs.0))
+    }
+}
+
+impl Field for Fp {
+    const ZERO: Fp = Fp([0; 6]);
+    const ONE: Fp = fp("1");
+    const DEGREE: usize = 1;
+
+    fn invert(self) -> Fp {
+        self.pow_vartime(&P_MINUS_2)
+    }
+
+    fn sqrt(self) -> Option<Fp> {
+        let s = self.pow_vartime(&P_PLUS_1_DIV_4);
+        if s.square() == self {
+            Some(s)
+        } else {
+            None
+        }
+    }
+
+    fn sgn0(self) -> bool {
+        self.to_canonical()[0] & 1 == 1
+    }
+
+    fn is_lexicographically_largest(self) -> bool {
+        let v = self.to_canonical();
+        let mut borrow = 0;
+        for i in 0..6 {
+            (_, borrow) = sbb(P_MINUS_1_DIV_2[i], v[i], borrow);
+        }
+        borrow != 0
+    }
+
+    fn from_be_bytes(data: &[u8]) -> Option<Fp> {
+        let limbs = limbs_from_be_bytes(data);
+        if reduce(limbs) != limbs {
+            return None;
+        }
+        Some(Fp(montgomery_mul(&limbs, &R2)))
+    }
+
+    fn write_be_bytes(self, out: &mut [u8]) {
+        for (chunk, limb) in out.chunks_mut(8).zip(self.to_canonical().iter().rev()) {
+            chunk.copy_from_slice(&limb.to_be_bytes());
+        }
+    }
+
+    fn from_uniform_bytes(data: &[u8]) -> Fp {
+        // The 512-bit value is split as hi * 2^384 + lo.
+        let (hi, lo) = data.split_at(16);
+        Fp(montgomery_mul(&limbs_from_be_bytes(lo), &R2))
+            + Fp(montgomery_mul(&limbs_from_be_bytes(hi), &R3))
+    }
+
+    fn conditional_select(a: Fp, b: Fp, choice: u64) -> Fp {
+        let mask = 0u64.wrapping_sub(choice);
+        let mut r = [0u64; 6];
+        for (i, limb) in r.iter_mut().enumerate() {
+            *limb = (a.0[i] & !mask) | (b.0[i] & mask);
+        }
+        Fp(r)
+    }
+}
+
+// An element c0 + c1 * i of Fp2 = Fp[i] / (i^2 + 1).
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Fp2 {
+    c0: Fp,
+    c1: Fp,
+}
+
+const fn fp2(c0: &str, c1: &str) -> Fp2 {
+    Fp2 {
+        c0: fp(c0),
+        c1: fp(c1),
+    }
+}
+
+impl Fp2 {
+    fn conjugate(self) -> Fp2 {
+        Fp2 {
+            c0: self.c0,
+            c1: -self.c1,
+        }
+    }
+
+    // Multiplies by the non-residue 1 + i used to build Fp6.
+    fn mul_by_nonresidue(self) -> Fp2 {
+        Fp2 {
+            c0: self.c0 - self.c1,
+            c1: self.c0 + self.c1,
+        }
+    }
+
+    fn scale(self, s: Fp) -> Fp2 {
+        Fp2 {
+            c0: self.c0 * s,
+            c1: self.c1 * s,
+        }
+    }
+}
+
+impl std::ops::Add for Fp2 {
+    type Output = Fp2;
+
+    fn add(self, rhs: Fp2) -> Fp2 {
+        Fp2 {
+            c0: self.c0 + rhs.c0,
+            c1: self.c1 + rhs.c1,
+        }
+    }
+}
+
+impl std::ops::Sub for Fp2 {
+    type Output = Fp2;
+
+    fn sub(self, rhs: Fp2) -> Fp2 {
+        Fp2 {
+            c0: self.c0 - rhs.c0,
+            c1: self.c1 - rhs.c1,
+        }
+    }
+}
+
+impl std::ops::Neg for Fp2 {
+    type Output = Fp2;
+
+    fn neg(self) -> Fp2 {
+        Fp2 {
+            c0: -self.c0,
+            c1: -self.c1,
+        }
+    }
+}
+
+impl std::ops::Mul for Fp2 {
+    type Output = Fp2;
+
+    fn mul(self, rhs: Fp2) -> Fp2 {
+        let aa = self.c0 * rhs.c0;
+        let bb = self.c1 * rhs.c1;
+        Fp2 {
+            c0: aa - bb,
+            c1: (self.c0 + self.c1) * (rhs.c0 + rhs.c1) - aa - bb,
+        }
+    }
+}
+
+impl Field for Fp2 {
+    const ZERO: Fp2 = Fp2 {
+        c0: Fp::ZERO,
+        c1: Fp::ZERO,
+    };
+    const ONE: Fp2 = Fp2 {
+        c0: Fp::ONE,
+        c1: Fp::ZERO,
+    };
+    const DEGREE: usize = 2;
+
+    fn invert(self) -> Fp2 {
+        let t = (self.c0.square() + self.c1.square()).invert();
+        Fp2 {
+            c0: self.c0 * t,
+            c1: -(self.c1 * t),
+        }
+    }
+
+    // Algorithm 9 from https://eprint.iacr.org/2012/685, for p = 3 mod 4.
+    fn sqrt(self) -> Option<Fp2> {
+        let a1 = self.pow_vartime(&P_MINUS_3_DIV_4);
+        let alpha = a1.square() * self;
+        let x0 = a1 * self;
+        let x = if alpha == -Fp2::ONE {
+            Fp2 {
+                c0: -x0.c1,
+                c1: x0.c0,
+            }
+        } else {
+            (alpha + Fp2::ONE).pow_vartime(&P_MINUS_1_DIV_2) * x0
+        };
+        if x.square() == self {
+            Some(x)
+        } else {
+            None
+        }
+    }
+
+    fn sgn0(self) -> bool {
+        self.c0.sgn0() || (self.c0 == Fp::ZERO && self.c1.sgn0())
+    }
+
+    fn is_lexicographically_largest(self) -> bool {
+        self.c1.is_lexicographically_largest()
+            || (self.c1 == Fp::ZERO && self.c0.is_lexicographically_largest())
+    }
+
+    // Elements are encoded as c1 || c0.
+    fn from_be_bytes(data: &[u8]) -> Option<Fp2> {
+        let (c1, c0) = data.split_at(48);
+        Some(Fp2 {
+            c0: Fp::from_be_bytes(c0)?,
+            c1: Fp::from_be_bytes(c1)?,
+        })
+    }
+
+    fn write_be_bytes(self, out: &mut [u8]) {
+        let (c1, c0) = out.split_at_mut(48);
+        self.c0.write_be_bytes(c0);
+        self.c1.write_be_bytes(c1);
+    }
+
+    fn from_uniform_bytes(data: &[u8]) -> Fp2 {
+        let (c0, c1) = data.split_at(64);
+        Fp2 {
+            c0: Fp::from_uniform_bytes(c0),
+            c1: Fp::from_uniform_bytes(c1),
+        }
+    }
+
+    fn conditional_select(a: Fp2, b: Fp2, choice: u64) -> Fp2 {
+        Fp2 {
+            c0: Fp::conditional_select(a.c0, b.c0, choice),
+            c1: Fp::conditional_select(a.c1, b.c1, choice),
+        }
+    }
+}
+
+// An element c0 + c1 * v + c2 * v^2 of Fp6 = Fp2[v] / (v^3 - (1 + i)).
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Fp6 {
+    c0: Fp2,
+    c1: Fp2,
+    c2: Fp2,
+}
+
+impl Fp6 {
+    const ZERO: Fp6 = Fp6 {
+        c0: Fp2::ZERO,
+        c1: Fp2::ZERO,
+        c2: Fp2::ZERO,
+    };
+
+    // Multiplies by v, the non-residue used to build Fp12.
+    fn mul_by_nonresidue(self) -> Fp6 {
+        Fp6 {
+            c0: self.c2.mul_by_nonresidue(),
+            c1: self.c0,
+            c2: self.c1,
+        }
+    }
+
+    fn invert(self) -> Fp6 {
+        let t0 = self.c0.square() - (self.c1 * self.c2).mul_by_nonresidue();
+        let t1 = self.c2.square().mul_by_nonresidue() - self.c0 * self.c1;
+        let t2 = self.c1.square() - self.c0 * self.c2;
+        let d = (self.c0 * t0 + (self.c2 * t1 + self.c1 * t2).mul_by_nonresidue()).invert();
+        Fp6 {
+            c0: t0 * d,
+            c1: t1 * d,
+            c2: t2 * d,
+        }
+    }
+}
+
+impl std::ops::Add for Fp6 {
+    type Output = Fp6;
+
+    fn add(self, rhs: Fp6) -> Fp6 {
+        Fp6 {
+            c0: self.c0 + rhs.c0,
+            c1: self.c1 + rhs.c1,
+            c2: self.c2 + rhs.c2,
+        }
+    }
+}
+
+impl std::ops::Sub for Fp6 {
+    type Output = Fp6;
+
+    fn sub(self, rhs: Fp6) -> Fp6 {
+        Fp6 {
+            c0: self.c0 - rhs.c0,
+            c1: self.c1 - rhs.c1,
+            c2: self.c2 - rhs.c2,
+        }
+    }
+}
+
+impl std::ops::Neg for Fp6 {
+    type Output = Fp6;
+
+    fn neg(self) -> Fp6 {
+        Fp6 {
+            c0: -self.c0,
+            c1: -self.c1,
+            c2: -self.c2,
+        }
+    }
+}
+
+impl std::ops::Mul for Fp6 {
+    type Output = Fp6;
+
+    fn mul(self, rhs: Fp6) -> Fp6 {
+        Fp6 {
+            c0: self.c0 * rhs.c0 + (self.c1 * rhs.c2 + self.c2 * rhs.c1).mul_by_nonresidue(),
+            c1: self.c0 * rhs.c1 + self.c1 * rhs.c0 + (self.c2 * rhs.c2).mul_by_nonresidue(),
+            c2: self.c0 * rhs.c2 + self.c1 * rhs.c1 + self.c2 * rhs.c0,
+        }
+    }
+}
+
+// An element c0 + c1 * w of Fp12 = Fp6[w] / (w^2 - v).
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Fp12 {
+    c0: Fp6,
+    c1: Fp6,
+}
+
+// (1 + i)^(k * (p - 1) / 6), the factors applied to the coefficient of w^k by
+// the Frobenius map.
+const FROBENIUS_COEFFS: [Fp2; 6] = [
+    fp2(
+        "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001",
+        "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+    ),
+    fp2(
+        "1904d3bf02bb0667c231beb4202c0d1f0fd603fd3cbd5f4f7b2443d784bab9c4f67ea53d63e7813d8d0775ed92235fb8",
+        "00fc3e2b36c4e03288e9e902231f9fb854a14787b6c7b36fec0c8ec971f63c5f282d5ac14d6c7ec22cf78a126ddc4af3",
+    ),
+    fp2(
+        "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+        "1a0111ea397fe699ec02408663d4de85aa0d857d89759ad4897d29650fb85f9b409427eb4f49fffd8bfd00000000aaac",
+    ),
+    fp2(
+        "06af0e0437ff400b6831e36d6bd17ffe48395dabc2d3435e77f76e17009241c5ee67992f72ec05f4c81084fbede3cc09",
+        "06af0e0437ff400b6831e36d6bd17ffe48395dabc2d3435e77f76e17009241c5ee67992f72ec05f4c81084fbede3cc09",
+    ),
+    fp2(
+        "1a0111ea397fe699ec02408663d4de85aa0d857d89759ad4897d29650fb85f9b409427eb4f49fffd8bfd00000000aaad",
+        "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+    ),
+    fp2(
+        "05b2cfd9013a5fd8df47fa6b48b1e045f39816240c0b8fee8beadf4d8e9c0566c63a3e6e257f87329b18fae980078116",
+        "144e4211384586c16bd3ad4afa99cc9170df3560e77982d0db45f3536814f0bd5871c1908bd478cd1ee605167ff82995",
+    ),
+];
+
+impl Fp12 {
+    const ONE: Fp12 = Fp12 {
+        c0: Fp6 {
+            c0: Fp2::ONE,
+            c1: Fp2::ZERO,
+            c2: Fp2::ZERO,
+        },
+        c1: Fp6::ZERO,
+    };
+
+    fn square(self) -> Fp12 {
+        self * self
+    }
+
+    // The inverse of elements of the cyclotomic subgroup.
+    fn conjugate(self) -> Fp12 {
+        Fp12 {
+            c0: self.c0,
+            c1: -self.c1,
+        }
+    }
+
+    fn invert(self) -> Fp12 {
+        let d = (self.c0 * self.c0 - (self.c1 * self.c1).mul_by_nonresidue()).invert();
+        Fp12 {
+            c0: self.c0 * d,
+            c1: -(self.c1 * d),
+        }
+    }
+
+    // Raises to the power p. Since v = w^2, c0.cj and c1.cj are the
+    // coefficients of w^(2j) and w^(2j + 1).
+    fn frobenius(self) -> Fp12 {
+        let f = |c: Fp2, k: usize| c.conjugate() * FROBENIUS_COEFFS[k];
+        Fp12 {
+            c0: Fp6 {
+                c0: f(self.c0.c0, 0),
+                c1: f(self.c0.c1, 2),
+                c2: f(self.c0.c2, 4),
+            },
+            c1: Fp6 {
+                c0: f(self.c1.c0, 1),
+                c1: f(self.c1.c1, 3),
+                c2: f(self.c1.c2, 5),
+            },
+        }
+    }
+
+    // Raises an element of the cyclotomic subgroup to the power x.
+    fn pow_x(self) -> Fp12 {
+        let mut result = Fp12::ONE;
+        for i in (0..64).rev() {
+            result = result.square();
+            if (BLS_X >> i) & 1 == 1 {
+                result = result * self;
+            }
+        }
+        result.conjugate()
+    }
+}
+
+impl std::ops::Mul for Fp12 {
+    type Output = Fp12;
+
+    fn mul(self, rhs: Fp12) -> Fp12 {
+        let t0 = self.c0 * rhs.c0;
+        let t1 = self.c1 * rhs.c1;
+        Fp12 {
+            c0: t0 + t1.mul_by_nonresidue(),
+            c1: (self.c0 + self.c1) * (rhs.c0 + rhs.c1) - t0 - t1,
+        }
+    }
+}
+
+trait Curve: Copy {
+    type Base: Field + 'static;
+
+    // The curve is y^2 = x^3 + B.
+    const B: Self::Base;
+    // 3 * B, for the complete addition formulas.
+    const B3: Self::Base;
+    const GENERATOR: (Self::Base, Self::Base);
+    // The effective cofactor from RFC 9380 section 8.8, as big endian limbs.
+    const H_EFF: &'static [u64];
+
+    // The simplified SWU map's curve y^2 = x^3 + A' * x + B', and the
+    // rational maps of the isogeny from it, from RFC 9380 appendix E. The
+    // polynomials' coefficients are in increasing degree order.
+    const SSWU_Z: Self::Base;
+    const ISO_A: Self::Base;
+    const ISO_B: Self::Base;
+    const ISO_X_NUM: &'static [Self::Base];
+    const ISO_X_DEN: &'static [Self::Base];
+    const ISO_Y_NUM: &'static [Self::Base];
+    const ISO_Y_DEN: &'static [Self::Base];
+}
+
+#[derive(Clone, Copy)]
+struct G1;
+
+impl Curve for G1 {
+    type Base = Fp;
+
+    const B: Fp = fp("4");
+    const B3: Fp = fp("c");
+    const GENERATOR: (Fp, Fp) = (
+        fp("17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb"),
+        fp("08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1"),
+    );
+    const H_EFF: &'static [u64] = &[0xd201_0000_0001_0001];
+
+    const SSWU_Z: Fp = fp("b");
+    const ISO_A: Fp = fp("00144698a3b8e9433d693a02c96d4982b0ea985383ee66a8d8e8981aefd881ac98936f8da0e0f97f5cf428082d584c1d");
+    const ISO_B: Fp = fp("12e2908d11688030018b12e8753eee3b2016c1f0f24f4070a0b9c14fcef35ef55a23215a316ceaa5d1cc48e98e172be0");
+    const ISO_X_NUM: &'static [Fp] = &[
+        fp("11a05f2b1e833340b809101dd99815856b303e88a2d7005ff2627b56cdb4e2c85610c2d5f2e62d6eaeac1662734649b7"),
+        fp("17294ed3e943ab2f0588bab22147a81c7c17e75b2f6a8417f565e33c70d1e86b4838f2a6f318c356e834eef1b3cb83bb"),
+        fp("0d54005db97678ec1d1048c5d10a9a1bce032473295983e56878e501ec68e25c958c3e3d2a09729fe0179f9dac9edcb0"),
+        fp("1778e7166fcc6db74e0609d307e55412d7f5e4656a8dbf25f1b33289f1b330835336e25ce3107193c5b388641d9b6861"),
+        fp("0e99726a3199f4436642b4b3e4118e5499db995a1257fb3f086eeb65982fac18985a286f301e77c451154ce9ac8895d9"),
+        fp("1630c3250d7313ff01d1201bf7a74ab5db3cb17dd952799b9ed3ab9097e68f90a0870d2dcae73d19cd13c1c66f652983"),
+        fp("0d6ed6553fe44d296a3726c38ae652bfb11586264f0f8ce19008e218f9c86b2a8da25128c1052ecaddd7f225a139ed84"),
+        fp("17b81e7701abdbe2e8743884d1117e53356de5ab275b4db1a682c62ef0f2753339b7c8f8c8f475af9ccb5618e3f0c88e"),
+        fp("080d3cf1f9a78fc47b90b33563be990dc43b756ce79f5574a2c596c928c5d1de4fa295f296b74e956d71986a8497e317"),
+        fp("169b1f8e1bcfa7c42e0c37515d138f22dd2ecb803a0c5c99676314baf4bb1b7fa3190b2edc0327797f241067be390c9e"),
+        fp("10321da079ce07e272d8ec09d2565b0dfa7dccdde6787f96d50af36003b14866f69b771f8c285decca67df3f1605fb7b"),
+        fp("06e08c248e260e70bd1e962381edee3d31d79d7e22c837bc23c0bf1bc24c6b68c24b1b80b64d391fa9c8ba2e8ba2d229"),
+    ];
+    const ISO_X_DEN: &'static [Fp] = &[
+        fp("08ca8d548cff19ae18b2e62f4bd3fa6f01d5ef4ba35b48ba9c9588617fc8ac62b558d681be343df8993cf9fa40d21b1c"),
+        fp("12561a5deb559c4348b4711298e536367041e8ca0cf0800c0126c2588c48bf5713daa8846cb026e9e5c8276ec82b3bff"),
+        fp("0b2962fe57a3225e8137e629bff2991f6f89416f5a718cd1fca64e00b11aceacd6a3d0967c94fedcfcc239ba5cb83e19"),
+        fp("03425581a58ae2fec83aafef7c40eb545b08243f16b1655154cca8abc28d6fd04976d5243eecf5c4130de8938dc62cd8"),
+        fp("13a8e162022914a80a6f1d5f43e7a07dffdfc759a12062bb8d6b44e833b306da9bd29ba81f35781d539d395b3532a21e"),
+        fp("0e7355f8e4e667b955390f7f0506c6e9395735e9ce9cad4d0a43bcef24b8982f7400d24bc4228f11c02df9a29f6304a5"),
+        fp("0772caacf16936190f3e0c63e0596721570f5799af53a1894e2e073062aede9cea73b3538f0de06cec2574496ee84a3a"),
+        fp("14a7ac2a9d64a8b230b3f5b074cf01996e7f63c21bca68a81996e1cdf9822c580fa5b9489d11e2d311f7d99bbdcc5a5e"),
+        fp("0a10ecf6ada54f825e920b3dafc7a3cce07f8d1d7161366b74100da67f39883503826692abba43704776ec3a79a1d641"),
+        fp("095fc13ab9e92ad4476d6e3eb3a56680f682b4ee96f7d03776df533978f31c1593174e4b4b7865002d6384d168ecdd0a"),
+        fp("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001"),
+    ];
+    const ISO_Y_NUM: &'static [Fp] = &[
+        fp("090d97c81ba24ee0259d1f094980dcfa11ad138e48a869522b52af6c956543d3cd0c7aee9b3ba3c2be9845719707bb33"),
+        fp("134996a104ee5811d51036d776fb46831223e96c254f383d0f906343eb67ad34d6c56711962fa8bfe097e75a2e41c696"),
+        fp("00cc786baa966e66f4a384c86a3b49942552e2d658a31ce2c344be4b91400da7d26d521628b00523b8dfe240c72de1f6"),
+        fp("01f86376e8981c217898751ad8746757d42aa7b90eeb791c09e4a3ec03251cf9de405aba9ec61deca6355c77b0e5f4cb"),
+        fp("08cc03fdefe0ff135caf4fe2a21529c4195536fbe3ce50b879833fd221351adc2ee7f8dc099040a841b6daecf2e8fedb"),
+        fp("16603fca40634b6a2211e11db8f0a6a074a7d0d4afadb7bd76505c3d3ad5544e203f6326c95a807299b23ab13633a5f0"),
+        fp("04ab0b9bcfac1bbcb2c977d027796b3ce75bb8ca2be184cb5231413c4d634f3747a87ac2460f415ec961f8855fe9d6f2"),
+        fp("0987c8d5333ab86fde9926bd2ca6c674170a05bfe3bdd81ffd038da6c26c842642f64550fedfe935a15e4ca31870fb29"),
+        fp("09fc4018bd96684be88c9e221e4da1bb8f3abd16679dc26c1e8b6e6a1f20cabe69d65201c78607a360370e577bdba587"),
+        fp("0e1bba7a1186bdb5223abde7ada14a23c42a0ca7915af6fe06985e7ed1e4d43b9b3f7055dd4eba6f2bafaaebca731c30"),
+        fp("19713e47937cd1be0dfd0b8f1d43fb93cd2fcbcb6caf493fd1183e416389e61031bf3a5cce3fbafce813711ad011c132"),
+        fp("18b46a908f36f6deb918c143fed2edcc523559b8aaf0c2462e6bfe7f911f643249d9cdf41b44d606ce07c8a4d0074d8e"),
+        fp("0b182cac101b9399d155096004f53f447aa7b12a3426b08ec02710e807b4633f06c851c1919211f20d4c04f00b971ef8"),
+        fp("0245a394ad1eca9b72fc00ae7be315dc757b3b080d4c158013e6632d3c40659cc6cf90ad1c232a6442d9d3f5db980133"),
+        fp("05c129645e44cf1102a159f748c4a3fc5e673d81d7e86568d9ab0f5d396a7ce46ba1049b6579afb7866b1e715475224b"),
+        fp("15e6be4e990f03ce4ea50b3b42df2eb5cb181d8f84965a3957add4fa95af01b2b665027efec01c7704b456be69c8b604"),
+    ];
+    const ISO_Y_DEN: &'static [Fp] = &[
+        fp("16112c4c3a9c98b252181140fad0eae9601a6de578980be6eec3232b5be72e7a07f3688ef60c206d01479253b03663c1"),
+        fp("1962d75c2381201e1a0cbd6c43c348b885c84ff731c4d59ca4a10356f453e01f78a4260763529e3532f6102c2e49a03d"),
+        fp("058df3306640da276faaae7d6e8eb15778c4855551ae7f310c35a5dd279cd2eca6757cd636f96f891e2538b53dbf67f2"),
+        fp("16b7d288798e5395f20d23bf89edb4d1d115c5dbddbcd30e123da489e726af41727364f2c28297ada8d26d98445f5416"),
+        fp("0be0e079545f43e4b00cc912f8228ddcc6d19c9f0f69bbb0542eda0fc9dec916a20b15dc0fd2ededda39142311a5001d"),
+        fp("08d9e5297186db2d9fb266eaac783182b70152c65550d881c5ecd87b6f0f5a6449f38db9dfa9cce202c6477faaf9b7ac"),
+        fp("166007c08a99db2fc3ba8734ace9824b5eecfdfa8d0cf8ef5dd365bc400a0051d5fa9c01a58b1fb93d1a1399126a775c"),
+        fp("16a3ef08be3ea7ea03bcddfabba6ff6ee5a4375efa1f4fd7feb34fd206357132b920f5b00801dee460ee415a15812ed9"),
+        fp("1866c8ed336c61231a1be54fd1d74cc4f9fb0ce4c6af5920abc5750c4bf39b4852cfe2f7bb9248836b233d9d55535d4a"),
+        fp("167a55cda70a6e1cea820597d94a84903216f763e13d87bb5308592e7ea7d4fbc7385ea3d529b35e346ef48bb8913f55"),
+        fp("04d2f259eea405bd48f010a01ad2911d9c6dd039bb61a6290e591b36e636a5c871a5c29f4f83060400f8b49cba8f6aa8"),
+        fp("0accbb67481d033ff5852c1e48c50c477f94ff8aefce42d28c0f9a88cea7913516f968986f7ebbea9684b529e2561092"),
+        fp("0ad6b9514c767fe3c3613144b45f1496543346d98adf02267d5ceef9a00d9b8693000763e3b90ac11e99b138573345cc"),
+        fp("02660400eb2e4f3b628bdd0d53cd76f2bf565b94e72927c1cb748df27942480e420517bd8714cc80d1fadc1326ed06f7"),
+        fp("0e0fa1d816ddc03e6b24255e0d7819c171c40f65e273b853324efcd6356caa205ca2f570f13497804415473a1d634b8f"),
+        fp("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001"),
+    ];
+}
+
+#[derive(Clone, Copy)]
+struct G2;
+
+impl Curve for G2 {
+    type Base = Fp2;
+
+    const B: Fp2 = fp2("4", "4");
+    const B3: Fp2 = fp2("c", "c");
+    const GENERATOR: (Fp2, Fp2) = (
+        fp2(
+            "024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8",
+            "13e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e",
+        ),
+        fp2(
+            "0ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801",
+            "0606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be",
+        ),
+    );
+    const H_EFF: &'static [u64] = &[
+        0x0bc6_9f08_f2ee_75b3,
+        0x584c_6a0e_a91b_3528,
+        0x88e2_a8e9_145a_d768,
+        0x9986_ff03_1508_ffe1,
+        0x329c_2f17_8731_db95,
+        0x6d82_bf01_5d12_12b0,
+        0x2ec0_ec69_d747_7c1a,
+        0xe954_cbc0_6689_f6a3,
+        0x5989_4c0a_debb_f6b4,
+        0xe802_0005_aaa9_5551,
+    ];
+
+    // Z = -(2 + i)
+    const SSWU_Z: Fp2 = fp2(
+        "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaa9",
+        "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaaa",
+    );
+    const ISO_A: Fp2 = fp2("0", "f0");
+    const ISO_B: Fp2 = fp2("3f4", "3f4");
+    const ISO_X_NUM: &'static [Fp2] = &[
+        fp2(
+            "05c759507e8e333ebb5b7a9a47d7ed8532c52d39fd3a042a88b58423c50ae15d5c2638e343d9c71c6238aaaaaaaa97d6",
+            "05c759507e8e333ebb5b7a9a47d7ed8532c52d39fd3a042a88b58423c50ae15d5c2638e343d9c71c6238aaaaaaaa97d6",
+        ),
+        fp2(
+            "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+            "11560bf17baa99bc32126fced787c88f984f87adf7ae0c7f9a208c6b4f20a4181472aaa9cb8d555526a9ffffffffc71a",
+        ),
+        fp2(
+            "11560bf17baa99bc32126fced787c88f984f87adf7ae0c7f9a208c6b4f20a4181472aaa9cb8d555526a9ffffffffc71e",
+            "08ab05f8bdd54cde190937e76bc3e447cc27c3d6fbd7063fcd104635a790520c0a395554e5c6aaaa9354ffffffffe38d",
+        ),
+        fp2(
+            "171d6541fa38ccfaed6dea691f5fb614cb14b4e7f4e810aa22d6108f142b85757098e38d0f671c7188e2aaaaaaaa5ed1",
+            "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+        ),
+    ];
+    const ISO_X_DEN: &'static [Fp2] = &[
+        fp2(
+            "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+            "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaa63",
+        ),
+        fp2(
+            "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c",
+            "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaa9f",
+        ),
+        fp2(
+            "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001",
+            "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+        ),
+    ];
+    const ISO_Y_NUM: &'static [Fp2] = &[
+        fp2(
+            "1530477c7ab4113b59a4c18b076d11930f7da5d4a07f649bf54439d87d27e500fc8c25ebf8c92f6812cfc71c71c6d706",
+            "1530477c7ab4113b59a4c18b076d11930f7da5d4a07f649bf54439d87d27e500fc8c25ebf8c92f6812cfc71c71c6d706",
+        ),
+        fp2(
+            "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+            "05c759507e8e333ebb5b7a9a47d7ed8532c52d39fd3a042a88b58423c50ae15d5c2638e343d9c71c6238aaaaaaaa97be",
+        ),
+        fp2(
+            "11560bf17baa99bc32126fced787c88f984f87adf7ae0c7f9a208c6b4f20a4181472aaa9cb8d555526a9ffffffffc71c",
+            "08ab05f8bdd54cde190937e76bc3e447cc27c3d6fbd7063fcd104635a790520c0a395554e5c6aaaa9354ffffffffe38f",
+        ),
+        fp2(
+            "124c9ad43b6cf79bfbf7043de3811ad0761b0f37a1e26286b0e977c69aa274524e79097a56dc4bd9e1b371c71c718b10",
+            "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+        ),
+    ];
+    const ISO_Y_DEN: &'static [Fp2] = &[
+        fp2(
+            "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffa8fb",
+            "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffa8fb",
+        ),
+        fp2(
+            "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+            "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffa9d3",
+        ),
+        fp2(
+            "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000012",
+            "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaa99",
+        ),
+        fp2(
+            "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001",
+            "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+        ),
+    ];
+}
+
+// A point in homogeneous projective coordinates, (X / Z, Y / Z). The point at
+// infinity has Z = 0.
+#[derive(Clone, Copy)]
+struct Point<C: Curve> {
+    x: C::Base,
+    y: C::Base,
+    z: C::Base,
+}
+
+impl<C: Curve> Point<C> {
+    const IDENTITY: Point<C> = Point {
+        x: C::Base::ZERO,
+        y: C::Base::ONE,
+        z: C::Base::ZERO,
+    };
+
+    fn from_affine(x: C::Base, y: C::Base) -> Point<C> {
+        Point {
+            x,
+            y,
+            z: C::Base::ONE,
+        }
+    }
+
+    fn generator() -> Point<C> {
+        Point::from_affine(C::GENERATOR.0, C::GENERATOR.1)
+    }
+
+    fn is_identity(&self) -> bool {
+        self.z == C::Base::ZERO
+    }
+
+    fn to_affine(self) -> Option<(C::Base, C::Base)> {
+        if self.is_identity() {
+            return None;
+        }
+        let z_inv = self.z.invert();
+        Some((self.x * z_inv, self.y * z_inv))
+    }
+
+    fn conditional_select(a: Point<C>, b: Point<C>, choice: u64) -> Point<C> {
+        Point {
+            x: C::Base::conditional_select(a.x, b.x, choice),
+            y: C::Base::conditional_select(a.y, b.y, choice),
+            z: C::Base::conditional_select(a.z, b.z, choice),
+        }
+    }
+
+    // Algorithm 9 of https://eprint.iacr.org/2015/1060, which is complete:
+    // it handles the point at infinity without branching.
+    fn double(self) -> Point<C> {
+        let t0 = self.y.square();
+        let z3 = t0 + t0;
+        let z3 = z3 + z3;
+        let z3 = z3 + z3;
+        let t1 = self.y * self.z;
+        let t2 = C::B3 * self.z.square();
+        let x3 = t2 * z3;
+        let y3 = t0 + t2;
+        let z3 = t1 * z3;
+        let t1 = t2 + t2;
+        let t2 = t1 + t2;
+        let t0 = t0 - t2;
+        let y3 = x3 + t0 * y3;
+        let t1 = self.x * self.y;
+        let x3 = t0 * t1;
+        Point {
+            x: x3 + x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    // Multiplies by a scalar given as big endian limbs, with a Montgomery
+    // ladder over every bit of every limb. The only scalar dependent
+    // operations are the conditional swaps.
+    fn mul(self, scalar: &[u64]) -> Point<C> {
+        let mut r0 = Point::IDENTITY;
+        let mut r1 = self;
+        for limb in scalar {
+            for i in (0..64).rev() {
+                let bit = (limb >> i) & 1;
+                (r0, r1) = (
+                    Point::conditional_select(r0, r1, bit),
+                    Point::conditional_select(r1, r0, bit),
+                );
+                r1 = r0 + r1;
+                r0 = r0.double();
+                (r0, r1) = (
+                    Point::conditional_select(r0, r1, bit),
+                    Point::conditional_select(r1, r0, bit),
+                );
+            }
+        }
+        r0
+    }
+
+    #[cfg(test)]
+    fn is_on_curve(&self) -> bool {
+        // Y^2 * Z = X^3 + B * Z^3
+        self.y.square() * self.z == self.x.square() * self.x + C::B * self.z.square() * self.z
+    }
+
+    fn is_torsion_free(&self) -> bool {
+        self.mul(&GROUP_ORDER).is_identity()
+    }
+
+    // Points are encoded in the compressed form used by Zcash and the BLS
+    // signature draft: the x coordinate with the top three bits of the first
+    // byte flagging compression, the point at infinity, and which of the two
+    // y coordinates is lexicographically largest.
+    fn to_compressed(self) -> Vec<u8> {
+        let mut out = vec![0; 48 * C::Base::DEGREE];
+        match self.to_affine() {
+            Some((x, y)) => {
+                x.write_be_bytes(&mut out);
+                out[0] |= 0x80;
+                if y.is_lexicographically_largest() {
+                    out[0] |= 0x20;
+                }
+            }
+            None => out[0] = 0xc0,
+        }
+        out
+    }
+
+    // Decodes a compressed point, checking that it's in the prime order
+    // subgroup.
+    fn from_compressed(data: &[u8]) -> Option<Point<C>> {
+        if data.len() != 48 * C::Base::DEGREE || data[0] & 0x80 == 0 {
+            return None;
+        }
+        let flags = data[0] & 0xe0;
+        let mut x_bytes = data.to_vec();
+        x_bytes[0] &= 0x1f;
+        if flags & 0x40 != 0 {
+            if flags == 0xc0 && x_bytes.iter().all(|&b| b == 0) {
+                return Some(Point::IDENTITY);
+            }
+            return None;
+        }
+        let x = C::Base::from_be_bytes(&x_bytes)?;
+        let y = (x.square() * x + C::B).sqrt()?;
+        let y = if y.is_lexicographically_largest() == (flags & 0x20 != 0) {
+            y
+        } else {
+            -y
+        };
+        let point = Point::from_affine(x, y);
+        if !point.is_torsion_free() {
+            return None;
+        }
+        Some(point)
+    }
+}
+
+// Algorithm 7 of https://eprint.iacr.org/2015/1060, which is complete: it
+// handles doubling and the point at infinity without branching.
+impl<C: Curve> std::ops::Add for Point<C> {
+    type Output = Point<C>;
+
+    fn add(self, rhs: Point<C>) -> Point<C> {
+        let t0 = self.x * rhs.x;
+        let t1 = self.y * rhs.y;
+        let t2 = self.z * rhs.z;
+        let t3 = (self.x + self.y) * (rhs.x + rhs.y) - (t0 + t1);
+        let t4 = (self.y + self.z) * (rhs.y + rhs.z) - (t1 + t2);
+        let y3 = (self.x + self.z) * (rhs.x + rhs.z) - (t0 + t2);
+        let t0 = t0 + t0 + t0;
+        let t2 = C::B3 * t2;
+        let z3 = t1 + t2;
+        let t1 = t1 - t2;
+        let y3 = C::B3 * y3;
+        let x3 = t3 * t1 - t4 * y3;
+        let y3 = t1 * z3 + y3 * t0;
+        let z3 = z3 * t4 + t0 * t3;
+        Point {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+}
+
+impl<C: Curve> std::ops::Neg for Point<C> {
+    type Output = Point<C>;
+
+    fn neg(self) -> Point<C> {
+        Point {
+            x: self.x,
+            y: -self.y,
+            z: self.z,
+        }
+    }
+}
+
+impl<C: Curve> PartialEq for Point<C> {
+    fn eq(&self, other: &Point<C>) -> bool {
+        // This also holds for two representations of the point at infinity,
+        // and for no other pair involving it, as it's the only point with
+        // Z = 0 and X = 0.
+        self.x * other.z == other.x * self.z && self.y * other.z == other.y * self.z
+    }
+}
+
+// expand_message_xmd from RFC 9380 section 5.3.1. Callers make sure `len`
+// is at most 255 digests long and `dst` is at most 255 bytes long.
+pub(crate) fn expand_message_xmd(
+    md: openssl::hash::MessageDigest,
+    msg: &[u8],
+    dst: &[u8],
+    len: usize,
+) -> CryptographyResult<Vec<u8>> {
+    let dst_len = [u8::try_from(dst.len()).unwrap()];
+
+    let mut h = openssl::hash::Hasher::new(md)?;
+    h.update(&vec![0; md.block_size()])?;
+    h.update(msg)?;
+    h.update(&u16::try_from(len).unwrap().to_be_bytes())?;
+    h.update(&[0])?;
+    h.update(dst)?;
+    h.update(&dst_len)?;
+    let b0 = h.finish()?;
+
+    let mut output = vec![];
+    let mut previous = vec![0; b0.len()];
+    let mut counter = 1u8;
+    while output.len() < len {
+        let mut h = openssl::hash::Hasher::new(md)?;
+        let xored = b0
+            .iter()
+            .zip(previous.iter())
+            .map(|(a, b)| a ^ b)
+            .collect::<Vec<_>>();
+        h.update(&xored)?;
+        h.update(&[counter])?;
+        h.update(dst)?;
+        h.update(&dst_len)?;
+        previous = h.finish()?.to_vec();
+        output.extend_from_slice(&previous);
+        counter += 1;
+    }
+    output.truncate(len);
+    Ok(output)
+}
+
+fn evaluate<F: Field>(coeffs: &[F], x: F) -> F {
+    coeffs.iter().rev().fold(F::ZERO, |acc, &c| acc * x + c)
+}
+
+// The simplified SWU map (RFC 9380 section 6.6.2) followed by the isogeny
+// onto the curve.
+fn map_to_curve<C: Curve>(u: C::Base) -> Point<C> {
+    let tv1 = C::SSWU_Z * u.square();
+    let tv2 = tv1.square() + tv1;
+    let x1 = if tv2 == C::Base::ZERO {
+        C::ISO_B * (C::SSWU_Z * C::ISO_A).invert()
+    } else {
+        -C::ISO_B * C::ISO_A.invert() * (C::Base::ONE + tv2.invert())
+    };
+    let gx = |x: C::Base| x.square() * x + C::ISO_A * x + C::ISO_B;
+    let (x, y) = match gx(x1).sqrt() {
+        Some(y) => (x1, y),
+        None => {
+            // If g(x1) isn't square then g(Z * u^2 * x1) is.
+            let x2 = tv1 * x1;
+            (x2, gx(x2).sqrt().unwrap())
+        }
+    };
+    let y = if u.sgn0() == y.sgn0() { y } else { -y };
+
+    let x_den = evaluate(C::ISO_X_DEN, x);
+    let y_den = evaluate(C::ISO_Y_DEN, x);
+    if x_den == C::Base::ZERO || y_den == C::Base::ZERO {
+        return Point::IDENTITY;
+    }
+    Point::from_affine(
+        evaluate(C::ISO_X_NUM, x) * x_den.invert(),
+        y * evaluate(C::ISO_Y_NUM, x) * y_den.invert(),
+    )
+}
+
+// hash_to_curve from RFC 9380, using the BLS12381G1_XMD:SHA-256_SSWU_RO_ and
+// BLS12381G2_XMD:SHA-256_SSWU_RO_ suites.
+fn hash_to_curve<C: Curve>(msg: &[u8], dst: &[u8]) -> CryptographyResult<Point<C>> {
+    let len = 64 * C::Base::DEGREE;
+    let uniform = expand_message_xmd(openssl::hash::MessageDigest::sha256(), msg, dst, 2 * len)?;
+    let u0 = C::Base::from_uniform_bytes(&uniform[..len]);
+    let u1 = C::Base::from_uniform_bytes(&uniform[len..]);
+    Ok((map_to_curve::<C>(u0) + map_to_curve::<C>(u1)).mul(C::H_EFF))
+}
+
+type G1Affine = (Fp, Fp);
+type G2Affine = (Fp2, Fp2);
+
+// The Miller loop's running multiple of a G2 point, in the Jacobian
+// coordinates (X / Z^2, Y / Z^3) that algorithms 26 and 27 are written for.
+// Only public points go through the Miller loop.
+struct G2Jacobian {
+    x: Fp2,
+    y: Fp2,
+    z: Fp2,
+}
+
+// Computes the line through the doubled point for the Miller loop,
+// following algorithm 26 of https://eprint.iacr.org/2010/354.
+fn doubling_step(r: &mut G2Jacobian) -> (Fp2, Fp2, Fp2) {
+    let tmp0 = r.x.square();
+    let tmp1 = r.y.square();
+    let tmp2 = tmp1.square();
+    let tmp3 = (tmp1 + r.x).square() - tmp0 - tmp2;
+    let tmp3 = tmp3 + tmp3;
+    let tmp4 = tmp0 + tmp0 + tmp0;
+    let tmp6 = r.x + tmp4;
+    let tmp5 = tmp4.square();
+    let zsquared = r.z.square();
+    r.x = tmp5 - tmp3 - tmp3;
+    r.z = (r.z + r.y).square() - tmp1 - zsquared;
+    let tmp2 = tmp2 + tmp2;
+    let tmp2 = tmp2 + tmp2;
+    let tmp2 = tmp2 + tmp2;
+    r.y = (tmp3 - r.x) * tmp4 - tmp2;
+    let tmp3 = tmp4 * zsquared;
+    let tmp3 = -(tmp3 + tmp3);
+    let tmp1 = tmp1 + tmp1;
+    let tmp1 = tmp1 + tmp1;
+    let tmp6 = tmp6.square() - tmp0 - tmp5 - tmp1;
+    let tmp0 = r.z * zsquared;
+    (tmp0 + tmp0, tmp3, tmp6)
+}
+
+// Computes the line through r and q for the Miller loop, following
+// algorithm 27 of https://eprint.iacr.org/2010/354.
+fn addition_step(r: &mut G2Jacobian, q: &G2Affine) -> (Fp2, Fp2, Fp2) {
+    let (qx, qy) = *q;
+    let zsquared = r.z.square();
+    let ysquared = qy.square();
+    let t0 = zsquared * qx;
+    let t1 = ((qy + r.z).square() - ysquared - zsquared) * zsquared;
+    let t2 = t0 - r.x;
+    let t3 = t2.square();
+    let t4 = t3 + t3;
+    let t4 = t4 + t4;
+    let t5 = t4 * t2;
+    let t6 = t1 - r.y - r.y;
+    let t9 = t6 * qx;
+    let t7 = t4 * r.x;
+    r.x = t6.square() - t5 - t7 - t7;
+    r.z = (r.z + t2).square() - zsquared - t3;
+    let t10 = qy + r.z;
+    let t8 = (t7 - r.x) * t6;
+    let t0 = r.y * t5;
+    r.y = t8 - t0 - t0;
+    let t10 = t10.square() - ysquared - r.z.square();
+    let t9 = t9 + t9 - t10;
+    let t10 = r.z + r.z;
+    let t6 = -t6;
+    (t10, t6 + t6, t9)
+}
+
+// Multiplies f by the line with the given coefficients, evaluated at p.
+fn ell(f: Fp12, coeffs: (Fp2, Fp2, Fp2), p: &G1Affine) -> Fp12 {
+    let line = Fp12 {
+        c0: Fp6 {
+            c0: coeffs.2,
+            c1: coeffs.1.scale(p.0),
+            c2: Fp2::ZERO,
+        },
+        c1: Fp6 {
+            c0: Fp2::ZERO,
+            c1: coeffs.0.scale(p.1),
+            c2: Fp2::ZERO,
+        },
+    };
+    f * line
+}
+
+// The product of the Miller loops of the optimal ate pairing for each pair of
+// (affine) points.
+fn multi_miller_loop(pairs: &[(G1Affine, G2Affine)]) -> Fp12 {
+    let mut f = Fp12::ONE;
+    let mut r = pairs
+        .iter()
+        .map(|(_, q)| G2Jacobian {
+            x: q.0,
+            y: q.1,
+            z: Fp2::ONE,
+        })
+        .collect::<Vec<_>>();
+    for i in (0..63).rev() {
+        if i != 62 {
+            f = f.square();
+        }
+        for ((p, _), r) in pairs.iter().zip(r.iter_mut()) {
+            f = ell(f, doubling_step(r), p);
+        }
+        if (BLS_X >> i) & 1 == 1 {
+            for ((p, q), r) in pairs.iter().zip(r.iter_mut()) {
+                f = ell(f, addition_step(r, q), p);
+            }
+        }
+    }
+    // x is negative.
+    f.conjugate()
+}
+
+fn final_exponentiation(f: Fp12) -> Fp12 {
+    // The easy part, f^((p^6 - 1) * (p^2 + 1)).
+    let f = f.conjugate() * f.invert();
+    let f = f.frobenius().frobenius() * f;
+    // The hard part, raising to 3 * (p^4 - p^2 + 1) / r = l0 + l1 * p +
+    // l2 * p^2 + l3 * p^3, where l3 = (x - 1)^2, l2 = l3 * x,
+    // l1 = l2 * x - l3 and l0 = l1 * x + 3.
+    let a = f.pow_x() * f.conjugate();
+    let a = a.pow_x() * a.conjugate();
+    let b = a.pow_x();
+    let c = b.pow_x() * a.conjugate();
+    let d = c.pow_x() * f.square() * f;
+    d * c.frobenius() * b.frobenius().frobenius() * a.frobenius().frobenius().frobenius()
+}
+
+// Checks whether the product of the pairings of each pair is 1.
+fn pairing_product_is_one(pairs: &[(Point<G1>, Point<G2>)]) -> bool {
+    let affine = pairs
+        .iter()
+        .filter_map(|(p, q)| Some((p.to_affine()?, q.to_affine()?)))
+        .collect::<Vec<_>>();
+    final_exponentiation(multi_miller_loop(&affine)) == Fp12::ONE
+}
+
+// The proof of possession ciphersuites from draft-irtf-cfrg-bls-signature-05
+// section 4.2.3.
+const MIN_PK_SIG_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+const MIN_PK_POP_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+const MIN_SIG_SIG_DST: &[u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+const MIN_SIG_POP_DST: &[u8] = b"BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+
+const PRIVATE_KEY_LENGTH: usize = 32;
+const MIN_IKM_LENGTH: usize = 32;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Variant {
+    // Public keys in G1 and signatures in G2.
+    MinPk,
+    // Signatures in G1 and public keys in G2.
+    MinSig,
+}
+
+impl Variant {
+    fn from_py(py: pyo3::Python<'_>, variant: &pyo3::PyAny) -> CryptographyResult<Variant> {
+        if variant.is(types::BLS_VARIANT_MIN_PK.get(py)?) {
+            Ok(Variant::MinPk)
+        } else if variant.is(types::BLS_VARIANT_MIN_SIG.get(py)?) {
+            Ok(Variant::MinSig)
+        } else {
+            Err(CryptographyError::from(
+                pyo3::exceptions::PyTypeError::new_err("variant must be a BLSVariant"),
+            ))
+        }
+    }
+
+    fn to_py(self, py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::PyAny> {
+        match self {
+            Variant::MinPk => types::BLS_VARIANT_MIN_PK.get(py),
+            Variant::MinSig => types::BLS_VARIANT_MIN_SIG.get(py),
+        }
+    }
+
+    fn signature_dst(self) -> &'static [u8] {
+        match self {
+            Variant::MinPk => MIN_PK_SIG_DST,
+            Variant::MinSig => MIN_SIG_SIG_DST,
+        }
+    }
+
+    fn pop_dst(self) -> &'static [u8] {
+        match self {
+            Variant::MinPk => MIN_PK_POP_DST,
+            Variant::MinSig => MIN_SIG_POP_DST,
+        }
+    }
+
+    fn public_key(self, scalar: &Scalar) -> Element {
+        match self {
+            Variant::MinPk => Element::G1(Point::generator().mul(scalar)),
+            Variant::MinSig => Element::G2(Point::generator().mul(scalar)),
+        }
+    }
+
+    fn hash_to_signature_group(self, msg: &[u8], dst: &[u8]) -> CryptographyResult<Element> {
+        Ok(match self {
+            Variant::MinPk => Element::G2(hash_to_curve(msg, dst)?),
+            Variant::MinSig => Element::G1(hash_to_curve(msg, dst)?),
+        })
+    }
+
+    // Decodes a public key, checking it per KeyValidate.
+    fn decode_public_key(self, data: &[u8]) -> CryptographyResult<Element> {
+        let point = match self {
+            Variant::MinPk => Point::from_compressed(data).map(Element::G1),
+            Variant::MinSig => Point::from_compressed(data).map(Element::G2),
+        };
+        match point {
+            Some(point) if !point.is_identity() => Ok(point),
+            _ => Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("Invalid BLS public key"),
+            )),
+        }
+    }
+
+    fn decode_signature(self, data: &[u8]) -> Option<Element> {
+        match self {
+            Variant::MinPk => Point::from_compressed(data).map(Element::G2),
+            Variant::MinSig => Point::from_compressed(data).map(Element::G1),
+        }
+    }
+}
+
+// Big endian limbs of a scalar modulo r.
+type Scalar = [u64; 4];
+
+#[derive(Clone, Copy, PartialEq)]
+enum Element {
+    G1(Point<G1>),
+    G2(Point<G2>),
+}
+
+impl Element {
+    fn mul(self, scalar: &Scalar) -> Element {
+        match self {
+            Element::G1(p) => Element::G1(p.mul(scalar)),
+            Element::G2(p) => Element::G2(p.mul(scalar)),
+        }
+    }
+
+    // Both elements are always in the same group, as they come from the same
+    // variant.
+    fn add(self, other: Element) -> Element {
+        match (self, other) {
+            (Element::G1(a), Element::G1(b)) => Element::G1(a + b),
+            (Element::G2(a), Element::G2(b)) => Element::G2(a + b),
+            _ => unreachable!(),
+        }
+    }
+
+    fn is_identity(&self) -> bool {
+        match self {
+            Element::G1(p) => p.is_identity(),
+            Element::G2(p) => p.is_identity(),
+        }
+    }
+
+    fn to_compressed(self) -> Vec<u8> {
+        match self {
+            Element::G1(p) => p.to_compressed(),
+            Element::G2(p) => p.to_compressed(),
+        }
+    }
+}
+
+// KeyGen from draft-irtf-cfrg-bls-signature-05 section 2.3.
+fn key_gen(ikm: &[u8], key_info: &[u8]) -> CryptographyResult<Scalar> {
+    let md = openssl::hash::MessageDigest::sha256();
+    let order = openssl::bn::BigNum::from_slice(
+        &GROUP_ORDER
+            .iter()
+            .flat_map(|limb| limb.to_be_bytes())
+            .collect::<Vec<_>>(),
+    )?;
+    let mut ctx = openssl::bn::BigNumContext::new()?;
+    let mut key_material = ikm.to_vec();
+    key_material.push(0);
+    let mut info = key_info.to_vec();
+    info.extend_from_slice(&48u16.to_be_bytes());
+
+    let mut salt = b"BLS-SIG-KEYGEN-SALT-".to_vec();
+    loop {
+        salt = openssl::hash::hash(md, &salt)?.to_vec();
+        let okm = kdf::derive_hkdf(md, &salt, &info, 48, &key_material)?;
+        let okm = openssl::bn::BigNum::from_slice(&okm)?;
+        let mut sk = openssl::bn::BigNum::new()?;
+        sk.nnmod(&okm, &order, &mut ctx)?;
+        if sk.num_bits() != 0 {
+            return Ok(scalar_from_bytes(&sk.to_vec_padded(32)?));
+        }
+    }
+}
+
+fn scalar_from_bytes(data: &[u8]) -> Scalar {
+    let mut scalar = [0; 4];
+    for (limb, chunk) in scalar.iter_mut().zip(data.chunks(8)) {
+        *limb = u64::from_be_bytes(chunk.try_into().unwrap());
+    }
+    scalar
+}
+
+// CoreSign from draft-irtf-cfrg-bls-signature-05 section 2.6.
+fn core_sign(
+    variant: Variant,
+    scalar: &Scalar,
+    msg: &[u8],
+    dst: &[u8],
+) -> CryptographyResult<Vec<u8>> {
+    Ok(variant
+        .hash_to_signature_group(msg, dst)?
+        .mul(scalar)
+        .to_compressed())
+}
+
+// CoreAggregateVerify from draft-irtf-cfrg-bls-signature-05 section 2.9,
+// which is also CoreVerify when there's a single key.
+fn core_aggregate_verify(
+    variant: Variant,
+    public_keys: &[Element],
+    messages: &[&[u8]],
+    signature: &[u8],
+    dst: &[u8],
+) -> CryptographyResult<bool> {
+    let signature = match variant.decode_signature(signature) {
+        Some(signature) => signature,
+        None => return Ok(false),
+    };
+    let mut pairs = vec![];
+    for (public_key, msg) in public_keys.iter().zip(messages) {
+        match (public_key, variant.hash_to_signature_group(msg, dst)?) {
+            (Element::G1(pk), Element::G2(h)) => pairs.push((*pk, h)),
+            (Element::G2(pk), Element::G1(h)) => pairs.push((h, *pk)),
+            _ => unreachable!(),
+        }
+    }
+    match signature {
+        Element::G1(sig) => pairs.push((sig, -Point::<G2>::generator())),
+        Element::G2(sig) => pairs.push((-Point::<G1>::generator(), sig)),
+    }
+    Ok(pairing_product_is_one(&pairs))
+}
+
+fn check_valid(valid: bool) -> CryptographyResult<()> {
+    if !valid {
+        return Err(CryptographyError::from(
+            exceptions::InvalidSignature::new_err(()),
+        ));
+    }
+    Ok(())
+}
+
+#[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.bls")]
+pub(crate) struct BLSPrivateKey {
+    variant: Variant,
+    scalar: Scalar,
+}
+
+#[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.bls")]
+pub(crate) struct BLSPublicKey {
+    variant: Variant,
+    point: Element,
+}
+
+#[pyo3::prelude::pyfunction]
+fn generate_private_key(
+    py: pyo3::Python<'_>,
+    variant: &pyo3::PyAny,
+) -> CryptographyResult<BLSPrivateKey> {
+    let variant = Variant::from_py(py, variant)?;
+    let mut ikm = [0; MIN_IKM_LENGTH];
+    openssl::rand::rand_bytes(&mut ikm)?;
+    Ok(BLSPrivateKey {
+        variant,
+        scalar: key_gen(&ikm, b"")?,
+    })
+}
+
+#[pyo3::prelude::pyfunction]
+fn derive_private_key(
+    py: pyo3::Python<'_>,
+    ikm: CffiBuf<'_>,
+    variant: &pyo3::PyAny,
+    key_info: CffiBuf<'_>,
+) -> CryptographyResult<BLSPrivateKey> {
+    let variant = Variant::from_py(py, variant)?;
+    if ikm.as_bytes().len() < MIN_IKM_LENGTH {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "ikm must be at least {MIN_IKM_LENGTH} bytes long"
+            )),
+        ));
+    }
+    Ok(BLSPrivateKey {
+        variant,
+        scalar: key_gen(ikm.as_bytes(), key_info.as_bytes())?,
+    })
+}
+
+#[pyo3::prelude::pyfunction]
+fn from_private_bytes(
+    py: pyo3::Python<'_>,
+    data: CffiBuf<'_>,
+    variant: &pyo3::PyAny,
+) -> CryptographyResult<BLSPrivateKey> {
+    let variant = Variant::from_py(py, variant)?;
+    let data = data.as_bytes();
+    let invalid = || {
+        CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+            "A BLS private key is 32 bytes long and must be between 1 and the group order",
+        ))
+    };
+    if data.len() != PRIVATE_KEY_LENGTH {
+        return Err(invalid());
+    }
+    let scalar = scalar_from_bytes(data);
+    if scalar == [0; 4] || scalar >= GROUP_ORDER {
+        return Err(invalid());
+    }
+    Ok(BLSPrivateKey { variant, scalar })
+}
+
+#[pyo3::prelude::pyfunction]
+fn from_public_bytes(
+    py: pyo3::Python<'_>,
+    data: CffiBuf<'_>,
+    variant: &pyo3::PyAny,
+) -> CryptographyResult<BLSPublicKey> {
+    let variant = Variant::from_py(py, variant)?;
+    Ok(BLSPublicKey {
+        variant,
+        point: variant.decode_public_key(data.as_bytes())?,
+    })
+}
+
+#[pyo3::prelude::pyfunction]
+fn aggregate_signatures<'p>(
+    py: pyo3::Python<'p>,
+    signatures: Vec<CffiBuf<'_>>,
+    variant: &pyo3::PyAny,
+) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+    let variant = Variant::from_py(py, variant)?;
+    let mut aggregate: Option<Element> = None;
+    for signature in &signatures {
+        let signature = variant
+            .decode_signature(signature.as_bytes())
+            .ok_or_else(|| {
+                CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+                    "Invalid BLS signature",
+                ))
+            })?;
+        aggregate = Some(match aggregate {
+            Some(aggregate) => aggregate.add(signature),
+            None => signature,
+        });
+    }
+    let aggregate = aggregate.ok_or_else(|| {
+        CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+            "At least one signature is required",
+        ))
+    })?;
+    Ok(pyo3::types::PyBytes::new(py, &aggregate.to_compressed()))
+}
+
+// All the keys must be of the same variant, which is returned.
+fn public_keys_variant(
+    public_keys: &[pyo3::PyRef<'_, BLSPublicKey>],
+) -> CryptographyResult<Variant> {
+    let variant = match public_keys.first() {
+        Some(public_key) => public_key.variant,
+        None => {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("At least one public key is required"),
+            ))
+        }
+    };
+    if public_keys.iter().any(|k| k.variant != variant) {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("All public keys must be of the same variant"),
+        ));
+    }
+    Ok(variant)
+}
+
+#[pyo3::prelude::pyfunction]
+fn aggregate_verify(
+    py: pyo3::Python<'_>,
+    public_keys: Vec<pyo3::PyRef<'_, BLSPublicKey>>,
+    messages: Vec<CffiBuf<'_>>,
+    signature: CffiBuf<'_>,
+) -> CryptographyResult<()> {
+    let variant = public_keys_variant(&public_keys)?;
+    if messages.len() != public_keys.len() {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("There must be one message per public key"),
+        ));
+    }
+    let points = public_keys.iter().map(|k| k.point).collect::<Vec<_>>();
+    let messages = messages.iter().map(CffiBuf::as_bytes).collect::<Vec<_>>();
+    let signature = signature.as_bytes();
+    let valid = py.allow_threads(|| {
+        core_aggregate_verify(
+            variant,
+            &points,
+            &messages,
+            signature,
+            variant.signature_dst(),
+        )
+    })?;
+    check_valid(valid)
+}
+
+#[pyo3::prelude::pyfunction]
+fn fast_aggregate_verify(
+    py: pyo3::Python<'_>,
+    public_keys: Vec<pyo3::PyRef<'_, BLSPublicKey>>,
+    message: CffiBuf<'_>,
+    signature: CffiBuf<'_>,
+) -> CryptographyResult<()> {
+    let variant = public_keys_variant(&public_keys)?;
+    let aggregate = public_keys
+        .iter()
+        .skip(1)
+        .fold(public_keys[0].point, |acc, k| acc.add(k.point));
+    let (message, signature) = (message.as_bytes(), signature.as_bytes());
+    let valid = py.allow_threads(|| {
+        core_aggregate_verify(
+            variant,
+            &[aggregate],
+            &[message],
+            signature,
+            variant.signature_dst(),
+        )
+    })?;
+    check_valid(valid)
+}
+
+#[pyo3::prelude::pymethods]
+impl BLSPrivateKey {
+    #[getter]
+    fn variant<'p>(&self, py: pyo3::Python<'p>) -> pyo3::PyResult<&'p pyo3::PyAny> {
+        self.variant.to_py(py)
+    }
+
+    fn public_key(&self) -> BLSPublicKey {
+        BLSPublicKey {
+            variant: self.variant,
+            point: self.variant.public_key(&self.scalar),
+        }
+    }
+
+    fn sign<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        data: CffiBuf<'_>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let data = data.as_bytes();
+        let signature = py.allow_threads(|| {
+            core_sign(
+                self.variant,
+                &self.scalar,
+                data,
+                self.variant.signature_dst(),
+            )
+        })?;
+        Ok(pyo3::types::PyBytes::new(py, &signature))
+    }
+
+    // PopProve from draft-irtf-cfrg-bls-signature-05 section 3.3.2.
+    fn prove_possession<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let public_key = self.variant.public_key(&self.scalar).to_compressed();
+        let proof = core_sign(
+            self.variant,
+            &self.scalar,
+            &public_key,
+            self.variant.pop_dst(),
+        )?;
+        Ok(pyo3::types::PyBytes::new(py, &proof))
+    }
+
+    fn private_bytes_raw<'p>(&self, py: pyo3::Python<'p>) -> &'p pyo3::types::PyBytes {
+        let data = self
+            .scalar
+            .iter()
+            .flat_map(|limb| limb.to_be_bytes())
+            .collect::<Vec<_>>();
+        pyo3::types::PyBytes::new(py, &data)
+    }
+}
+
+#[pyo3::prelude::pymethods]
+impl BLSPublicKey {
+    #[getter]
+    fn variant<'p>(&self, py: pyo3::Python<'p>) -> pyo3::PyResult<&'p pyo3::PyAny> {
+        self.variant.to_py(py)
+    }
+
+    fn verify(
+        &self,
+        py: pyo3::Python<'_>,
+        signature: CffiBuf<'_>,
+        data: CffiBuf<'_>,
+    ) -> CryptographyResult<()> {
+        let (signature, data) = (signature.as_bytes(), data.as_bytes());
+        let valid = py.allow_threads(|| {
+            core_aggregate_verify(
+                self.variant,
+                &[self.point],
+                &[data],
+                signature,
+                self.variant.signature_dst(),
+            )
+        })?;
+        check_valid(valid)
+    }
+
+    // PopVerify from draft-irtf-cfrg-bls-signature-05 section 3.3.3.
+    fn verify_possession(&self, proof: CffiBuf<'_>) -> CryptographyResult<()> {
+        let public_key = self.point.to_compressed();
+        check_valid(core_aggregate_verify(
+            self.variant,
+            &[self.point],
+            &[&public_key],
+            proof.as_bytes(),
+            self.variant.pop_dst(),
+        )?)
+    }
+
+    fn public_bytes_raw<'p>(&self, py: pyo3::Python<'p>) -> &'p pyo3::types::PyBytes {
+        pyo3::types::PyBytes::new(py, &self.point.to_compressed())
+    }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> bool {
+        self.variant == other.variant && self.point == other.point
+    }
+
+    fn __copy__(slf: pyo3::PyRef<'_, Self>) -> pyo3::PyRef<'_, Self> {
+        slf
+    }
+}
+
+pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let m = pyo3::prelude::PyModule::new(py, "bls")?;
+    m.add_function(pyo3::wrap_pyfunction!(generate_private_key, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(derive_private_key, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(from_private_bytes, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(from_public_bytes, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(aggregate_signatures, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(aggregate_verify, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(fast_aggregate_verify, m)?)?;
+
+    m.add_class::<BLSPrivateKey>()?;
+    m.add_class::<BLSPublicKey>()?;
+
+    Ok(m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        core_aggregate_verify, core_sign, expand_message_xmd, hash_to_curve, scalar_from_bytes,
+        Curve, Element, Field, Fp, Fp2, Point, Variant, G1, G2, GROUP_ORDER, MIN_PK_SIG_DST,
+    };
+
+    fn unhex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn fp(s: &str) -> Fp {
+        Fp::from_be_bytes(&unhex(s)).unwrap()
+    }
+
+    fn fp2(c0: &str, c1: &str) -> Fp2 {
+        Fp2 {
+            c0: fp(c0),
+            c1: fp(c1),
+        }
+    }
+
+    fn check_arithmetic<C: Curve>() {
+        let g = Point::<C>::generator();
+        let identity = Point::<C>::IDENTITY;
+        assert!(g.is_on_curve() && identity.is_on_curve());
+
+        // The complete formulas handle the point at infinity and doubling.
+        assert!((g + identity) == g);
+        assert!((identity + g) == g);
+        assert!((identity + identity).is_identity());
+        assert!(identity.double().is_identity());
+        assert!((g + g) == g.double());
+        assert!((g + -g).is_identity());
+        assert!(!(g + g).is_identity());
+        assert!((g + g).is_on_curve() && (g + g + g).is_on_curve());
+
+        assert!(g.mul(&[0]).is_identity());
+        assert!(g.mul(&[1]) == g);
+        assert!(g.mul(&[0, 2]) == g.double());
+        assert!(g.mul(&[5]) == g.double().double() + g);
+        assert!(g.mul(&[1 << 40]) + g.mul(&[3]) == g.mul(&[(1 << 40) + 3]));
+        assert!(g.mul(&[u64::MAX]) + g == g.mul(&[1, 0]));
+        assert!(g.mul(&GROUP_ORDER).is_identity());
+        let mut order_minus_one = GROUP_ORDER;
+        order_minus_one[3] -= 1;
+        assert!(g.mul(&order_minus_one) == -g);
+        assert!(identity.mul(&order_minus_one).is_identity());
+
+        let p = g.mul(&[0x1234_5678_9abc_def0, 0x0fed_cba9_8765_4321]);
+        assert!(p.is_on_curve() && p.is_torsion_free());
+        assert!(Point::<C>::from_compressed(&p.to_compressed()) == Some(p));
+        assert!(Point::<C>::from_compressed(&identity.to_compressed())
+            .unwrap()
+            .is_identity());
+    }
+
+    #[test]
+    fn test_g1_arithmetic() {
+        check_arithmetic::<G1>();
+    }
+
+    #[test]
+    fn test_g2_arithmetic() {
+        check_arithmetic::<G2>();
+    }
+
+    #[test]
+    fn test_expand_message_xmd() {
+        // RFC 9380 appendix K.1.
+        let md = openssl::hash::MessageDigest::sha256();
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        for (msg, len, expected) in [
+            (
+                &b""[..],
+                0x20,
+                "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235",
+            ),
+            (
+                b"abc",
+                0x20,
+                "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615",
+            ),
+            (
+                b"abcdef0123456789",
+                0x20,
+                "eff31487c770a893cfb36f912fbfcbff40d5661771ca4b2cb4eafe524333f5c1",
+            ),
+            (
+                b"",
+                0x80,
+                "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbe\
+                 e0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18\
+                 eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dc\
+                 c541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced",
+            ),
+        ] {
+            assert_eq!(
+                expand_message_xmd(md, msg, dst, len).ok(),
+                Some(unhex(expected))
+            );
+        }
+    }
+
+    #[test]
+    fn test_hash_to_g1() {
+        // RFC 9380 appendix J.9.1.
+        let dst = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+        for (msg, x, y) in [
+            (
+                &b""[..],
+                "052926add2207b76ca4fa57a8734416c8dc95e24501772c814278700eed6d1e4e8cf62d9c09db0fac349612b759e79a1",
+                "08ba738453bfed09cb546dbb0783dbb3a5f1f566ed67bb6be0e8c67e2e81a4cc68ee29813bb7994998f3eae0c9c6a265",
+            ),
+            (
+                b"abc",
+                "03567bc5ef9c690c2ab2ecdf6a96ef1c139cc0b2f284dca0a9a7943388a49a3aee664ba5379a7655d3c68900be2f6903",
+                "0b9c15f3fe6e5cf4211f346271d7b01c8f3b28be689c8429c85b67af215533311f0b8dfaaa154fa6b88176c229f2885d",
+            ),
+            (
+                b"abcdef0123456789",
+                "11e0b079dea29a68f0383ee94fed1b940995272407e3bb916bbf268c263ddd57a6a27200a784cbc248e84f357ce82d98",
+                "03a87ae2caf14e8ee52e51fa2ed8eefe80f02457004ba4d486d6aa1f517c0889501dc7413753f9599b099ebcbbd2d709",
+            ),
+        ] {
+            let p = hash_to_curve::<G1>(msg, dst).ok().unwrap();
+            assert!(p.to_affine() == Some((fp(x), fp(y))));
+        }
+    }
+
+    #[test]
+    fn test_hash_to_g2() {
+        // RFC 9380 appendix J.10.1.
+        let dst = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+        let p = hash_to_curve::<G2>(b"", dst).ok().unwrap();
+        let x = fp2(
+            "0141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a",
+            "05cb8437535e20ecffaef7752baddf98034139c38452458baeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d",
+        );
+        let y = fp2(
+            "0503921d7f6a12805e72940b963c0cf3471c7b2a524950ca195d11062ee75ec076daf2d4bc358c4b190c0c98064fdd92",
+            "12424ac32561493f3fe3c260708a12b7c620e7be00099a974e259ddc7d1f6395c3c811cdd19f1e8dbf3e9ecfdcbab8d6",
+        );
+        assert!(p.to_affine() == Some((x, y)));
+    }
+
+    #[test]
+    fn test_sign_vectors() {
+        // From the Ethereum consensus spec BLS tests.
+        let keys = [
+            (
+                "263dbd792f5b1be47ed85f8938c0f29586af0d3ac7b977f21c278fe1462040e3",
+                "a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20fd6e10c1b77654d067c0618f6e5a7f79a",
+                [
+                    "b6ed936746e01f8ecf281f020953fbf1f01debd5657c4a383940b020b26507f6076334f91e2366c96e9ab279fb5158090352ea1c5b0c9274504f4f0e7053af24802e51e4568d164fe986834f41e55c8e850ce1f98458c0cfc9ab380b55285a55",
+                    "882730e5d03f6b42c3abc26d3372625034e1d871b65a8a6b900a56dae22da98abbe1b68f85e49fe7652a55ec3d0591c20767677e33e5cbb1207315c41a9ac03be39c2e7668edc043d6cb1d9fd93033caa8a1c5b0e84bedaeb6c64972503a43eb",
+                    "91347bccf740d859038fcdcaf233eeceb2a436bcaaee9b2aa3bfb70efe29dfb2677562ccbea1c8e061fb9971b0753c240622fab78489ce96768259fc01360346da5b9f579e5da0d941e4c6ba18a0e64906082375394f337fa1af2b7127b0d121",
+                ],
+            ),
+            (
+                "47b8192d77bf871b62e87859d653922725724a5c031afeabc60bcef5ff665138",
+                "b301803f8b5ac4a1133581fc676dfedc60d891dd5fa99028805e5ea5b08d3491af75d0707adab3b70c6a6a580217bf81",
+                [
+                    "b23c46be3a001c63ca711f87a005c200cc550b9429d5f4eb38d74322144f1b63926da3388979e5321012fb1a0526bcd100b5ef5fe72628ce4cd5e904aeaa3279527843fae5ca9ca675f4f51ed8f83bbf7155da9ecc9663100a885d5dc6df96d9",
+                    "af1390c3c47acdb37131a51216da683c509fce0e954328a59f93aebda7e4ff974ba208d9a4a2a2389f892a9d418d618418dd7f7a6bc7aa0da999a9d3a5b815bc085e14fd001f6a1948768a3f4afefc8b8240dda329f984cb345c6363272ba4fe",
+                    "9674e2228034527f4c083206032b020310face156d4a4685e2fcaec2f6f3665aa635d90347b6ce124eb879266b1e801d185de36a0a289b85e9039662634f2eea1e02e670bc7ab849d006a70b2f93b84597558a05b879c8d445f387a5d5b653df",
+                ],
+            ),
+            (
+                "328388aff0d4a5b7dc9205abd374e7e98f3cd9f3418edb4eafda5fb16473d216",
+                "b53d21a4cfd562c469cc81514d4ce5a6b577d8403d32a394dc265dd190b47fa9f829fdd7963afdf972e5e77854051f6f",
+                [
+                    "948a7cb99f76d616c2c564ce9bf4a519f1bea6b0a624a02276443c245854219fabb8d4ce061d255af5330b078d5380681751aa7053da2c98bae898edc218c75f07e24d8802a17cd1f6833b71e58f5eb5b94208b4d0bb3848cecb075ea21be115",
+                    "a4efa926610b8bd1c8330c918b7a5e9bf374e53435ef8b7ec186abf62e1b1f65aeaaeb365677ac1d1172a1f5b44b4e6d022c252c58486c0a759fbdc7de15a756acc4d343064035667a594b4c2a6f0b0b421975977f297dba63ee2f63ffe47bb6",
+                    "ae82747ddeefe4fd64cf9cedb9b04ae3e8a43420cd255e3c7cd06a8d88b7c7f8638543719981c5d16fa3527c468c25f0026704a6951bde891360c7e8d12ddee0559004ccdbe6046b55bae1b257ee97f7cdb955773d7cf29adf3ccbb9975e4eb9",
+                ],
+            ),
+        ];
+        let messages = [[0x00; 32], [0x56; 32], [0xab; 32]];
+        for (private_key, public_key, signatures) in keys {
+            let scalar = scalar_from_bytes(&unhex(private_key));
+            let pk = Variant::MinPk.public_key(&scalar);
+            assert_eq!(pk.to_compressed(), unhex(public_key));
+            for (msg, signature) in messages.iter().zip(signatures) {
+                let signature = unhex(signature);
+                assert_eq!(
+                    core_sign(Variant::MinPk, &scalar, msg, MIN_PK_SIG_DST).ok(),
+                    Some(signature.clone())
+                );
+                assert!(matches!(
+                    core_aggregate_verify(
+                        Variant::MinPk,
+                        &[pk],
+                        &[msg],
+                        &signature,
+                        MIN_PK_SIG_DST
+                    ),
+                    Ok(true)
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn test_aggregate_vector() {
+        // fast_aggregate_verify_valid_3d6ab7bb from the Ethereum consensus
+        // spec BLS tests, which signs 0xabab...ab with the keys above.
+        let public_keys = [
+            "a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20fd6e10c1b77654d067c0618f6e5a7f79a",
+            "b301803f8b5ac4a1133581fc676dfedc60d891dd5fa99028805e5ea5b08d3491af75d0707adab3b70c6a6a580217bf81",
+            "b53d21a4cfd562c469cc81514d4ce5a6b577d8403d32a394dc265dd190b47fa9f829fdd7963afdf972e5e77854051f6f",
+        ]
+        .map(|pk| {
+            Variant::MinPk
+                .decode_public_key(&unhex(pk))
+                .ok()
+                .unwrap()
+        });
+        let aggregate = public_keys[1..]
+            .iter()
+            .fold(public_keys[0], |acc, &pk| acc.add(pk));
+        let signature = unhex(
+            "9712c3edd73a209c742b8250759db12549b3eaf43b5ca61376d9f30e2747dbcf842d8b2ac0901d2a093713e20284a7670fcf6954e9ab93de991bb9b313e664785a075fc285806fa5224c82bde146561b446ccfc706a64b8579513cfc4ff1d930",
+        );
+        let verify = |pk: Element, msg: &[u8]| {
+            matches!(
+                core_aggregate_verify(Variant::MinPk, &[pk], &[msg], &signature, MIN_PK_SIG_DST),
+                Ok(true)
+            )
+        };
+        let signatures = [
+            "91347bccf740d859038fcdcaf233eeceb2a436bcaaee9b2aa3bfb70efe29dfb2677562ccbea1c8e061fb9971b0753c240622fab78489ce96768259fc01360346da5b9f579e5da0d941e4c6ba18a0e64906082375394f337fa1af2b7127b0d121",
+            "9674e2228034527f4c083206032b020310face156d4a4685e2fcaec2f6f3665aa635d90347b6ce124eb879266b1e801d185de36a0a289b85e9039662634f2eea1e02e670bc7ab849d006a70b2f93b84597558a05b879c8d445f387a5d5b653df",
+            "ae82747ddeefe4fd64cf9cedb9b04ae3e8a43420cd255e3c7cd06a8d88b7c7f8638543719981c5d16fa3527c468c25f0026704a6951bde891360c7e8d12ddee0559004ccdbe6046b55bae1b257ee97f7cdb955773d7cf29adf3ccbb9975e4eb9",
+        ]
+        .map(|sig| Variant::MinPk.decode_signature(&unhex(sig)).unwrap());
+        let aggregate_signature = signatures[1..]
+            .iter()
+            .fold(signatures[0], |acc, &sig| acc.add(sig));
+        assert_eq!(aggregate_signature.to_compressed(), signature);
+        assert!(verify(aggregate, &[0xab; 32]));
+        assert!(!verify(aggregate, &[0x56; 32]));
+        assert!(!verify(public_keys[0], &[0xab; 32]));
+    }
+}
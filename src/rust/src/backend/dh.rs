@@ -5,12 +5,134 @@
 use cryptography_x509::common;
 
 use crate::asn1::encode_der_data;
-use crate::backend::utils;
+use crate::backend::{kdf, utils};
 use crate::error::{CryptographyError, CryptographyResult};
 use crate::{types, x509};
 
 const MIN_MODULUS_SIZE: u32 = 512;
 
+// The groups from RFC 7919, appendix A. Each is a safe prime `p` with a
+// generator of 2.
+const FFDHE_GROUPS: &[(&str, &str)] = &[
+    (
+        "ffdhe2048",
+        concat!(
+            "FFFFFFFFFFFFFFFFADF85458A2BB4A9AAFDC5620273D3CF1D8B9C583CE2D3695",
+            "A9E13641146433FBCC939DCE249B3EF97D2FE363630C75D8F681B202AEC4617A",
+            "D3DF1ED5D5FD65612433F51F5F066ED0856365553DED1AF3B557135E7F57C935",
+            "984F0C70E0E68B77E2A689DAF3EFE8721DF158A136ADE73530ACCA4F483A797A",
+            "BC0AB182B324FB61D108A94BB2C8E3FBB96ADAB760D7F4681D4F42A3DE394DF4",
+            "AE56EDE76372BB190B07A7C8EE0A6D709E02FCE1CDF7E2ECC03404CD28342F61",
+            "9172FE9CE98583FF8E4F1232EEF28183C3FE3B1B4C6FAD733BB5FCBC2EC22005",
+            "C58EF1837D1683B2C6F34A26C1B2EFFA886B423861285C97FFFFFFFFFFFFFFFF",
+        ),
+    ),
+    (
+        "ffdhe3072",
+        concat!(
+            "FFFFFFFFFFFFFFFFADF85458A2BB4A9AAFDC5620273D3CF1D8B9C583CE2D3695",
+            "A9E13641146433FBCC939DCE249B3EF97D2FE363630C75D8F681B202AEC4617A",
+            "D3DF1ED5D5FD65612433F51F5F066ED0856365553DED1AF3B557135E7F57C935",
+            "984F0C70E0E68B77E2A689DAF3EFE8721DF158A136ADE73530ACCA4F483A797A",
+            "BC0AB182B324FB61D108A94BB2C8E3FBB96ADAB760D7F4681D4F42A3DE394DF4",
+            "AE56EDE76372BB190B07A7C8EE0A6D709E02FCE1CDF7E2ECC03404CD28342F61",
+            "9172FE9CE98583FF8E4F1232EEF28183C3FE3B1B4C6FAD733BB5FCBC2EC22005",
+            "C58EF1837D1683B2C6F34A26C1B2EFFA886B4238611FCFDCDE355B3B6519035B",
+            "BC34F4DEF99C023861B46FC9D6E6C9077AD91D2691F7F7EE598CB0FAC186D91C",
+            "AEFE130985139270B4130C93BC437944F4FD4452E2D74DD364F2E21E71F54BFF",
+            "5CAE82AB9C9DF69EE86D2BC522363A0DABC521979B0DEADA1DBF9A42D5C4484E",
+            "0ABCD06BFA53DDEF3C1B20EE3FD59D7C25E41D2B66C62E37FFFFFFFFFFFFFFFF",
+        ),
+    ),
+    (
+        "ffdhe4096",
+        concat!(
+            "FFFFFFFFFFFFFFFFADF85458A2BB4A9AAFDC5620273D3CF1D8B9C583CE2D3695",
+            "A9E13641146433FBCC939DCE249B3EF97D2FE363630C75D8F681B202AEC4617A",
+            "D3DF1ED5D5FD65612433F51F5F066ED0856365553DED1AF3B557135E7F57C935",
+            "984F0C70E0E68B77E2A689DAF3EFE8721DF158A136ADE73530ACCA4F483A797A",
+            "BC0AB182B324FB61D108A94BB2C8E3FBB96ADAB760D7F4681D4F42A3DE394DF4",
+            "AE56EDE76372BB190B07A7C8EE0A6D709E02FCE1CDF7E2ECC03404CD28342F61",
+            "9172FE9CE98583FF8E4F1232EEF28183C3FE3B1B4C6FAD733BB5FCBC2EC22005",
+            "C58EF1837D1683B2C6F34A26C1B2EFFA886B4238611FCFDCDE355B3B6519035B",
+            "BC34F4DEF99C023861B46FC9D6E6C9077AD91D2691F7F7EE598CB0FAC186D91C",
+            "AEFE130985139270B4130C93BC437944F4FD4452E2D74DD364F2E21E71F54BFF",
+            "5CAE82AB9C9DF69EE86D2BC522363A0DABC521979B0DEADA1DBF9A42D5C4484E",
+            "0ABCD06BFA53DDEF3C1B20EE3FD59D7C25E41D2B669E1EF16E6F52C3164DF4FB",
+            "7930E9E4E58857B6AC7D5F42D69F6D187763CF1D5503400487F55BA57E31CC7A",
+            "7135C886EFB4318AED6A1E012D9E6832A907600A918130C46DC778F971AD0038",
+            "092999A333CB8B7A1A1DB93D7140003C2A4ECEA9F98D0ACC0A8291CDCEC97DCF",
+            "8EC9B55A7F88A46B4DB5A851F44182E1C68A007E5E655F6AFFFFFFFFFFFFFFFF",
+        ),
+    ),
+    (
+        "ffdhe6144",
+        concat!(
+            "FFFFFFFFFFFFFFFFADF85458A2BB4A9AAFDC5620273D3CF1D8B9C583CE2D3695",
+            "A9E13641146433FBCC939DCE249B3EF97D2FE363630C75D8F681B202AEC4617A",
+            "D3DF1ED5D5FD65612433F51F5F066ED0856365553DED1AF3B557135E7F57C935",
+            "984F0C70E0E68B77E2A689DAF3EFE8721DF158A136ADE73530ACCA4F483A797A",
+            "BC0AB182B324FB61D108A94BB2C8E3FBB96ADAB760D7F4681D4F42A3DE394DF4",
+            "AE56EDE76372BB190B07A7C8EE0A6D709E02FCE1CDF7E2ECC03404CD28342F61",
+            "9172FE9CE98583FF8E4F1232EEF28183C3FE3B1B4C6FAD733BB5FCBC2EC22005",
+            "C58EF1837D1683B2C6F34A26C1B2EFFA886B4238611FCFDCDE355B3B6519035B",
+            "BC34F4DEF99C023861B46FC9D6E6C9077AD91D2691F7F7EE598CB0FAC186D91C",
+            "AEFE130985139270B4130C93BC437944F4FD4452E2D74DD364F2E21E71F54BFF",
+            "5CAE82AB9C9DF69EE86D2BC522363A0DABC521979B0DEADA1DBF9A42D5C4484E",
+            "0ABCD06BFA53DDEF3C1B20EE3FD59D7C25E41D2B669E1EF16E6F52C3164DF4FB",
+            "7930E9E4E58857B6AC7D5F42D69F6D187763CF1D5503400487F55BA57E31CC7A",
+            "7135C886EFB4318AED6A1E012D9E6832A907600A918130C46DC778F971AD0038",
+            "092999A333CB8B7A1A1DB93D7140003C2A4ECEA9F98D0ACC0A8291CDCEC97DCF",
+            "8EC9B55A7F88A46B4DB5A851F44182E1C68A007E5E0DD9020BFD64B645036C7A",
+            "4E677D2C38532A3A23BA4442CAF53EA63BB454329B7624C8917BDD64B1C0FD4C",
+            "B38E8C334C701C3ACDAD0657FCCFEC719B1F5C3E4E46041F388147FB4CFDB477",
+            "A52471F7A9A96910B855322EDB6340D8A00EF092350511E30ABEC1FFF9E3A26E",
+            "7FB29F8C183023C3587E38DA0077D9B4763E4E4B94B2BBC194C6651E77CAF992",
+            "EEAAC0232A281BF6B3A739C1226116820AE8DB5847A67CBEF9C9091B462D538C",
+            "D72B03746AE77F5E62292C311562A846505DC82DB854338AE49F5235C95B9117",
+            "8CCF2DD5CACEF403EC9D1810C6272B045B3B71F9DC6B80D63FDD4A8E9ADB1E69",
+            "62A69526D43161C1A41D570D7938DAD4A40E329CD0E40E65FFFFFFFFFFFFFFFF",
+        ),
+    ),
+    (
+        "ffdhe8192",
+        concat!(
+            "FFFFFFFFFFFFFFFFADF85458A2BB4A9AAFDC5620273D3CF1D8B9C583CE2D3695",
+            "A9E13641146433FBCC939DCE249B3EF97D2FE363630C75D8F681B202AEC4617A",
+            "D3DF1ED5D5FD65612433F51F5F066ED0856365553DED1AF3B557135E7F57C935",
+            "984F0C70E0E68B77E2A689DAF3EFE8721DF158A136ADE73530ACCA4F483A797A",
+            "BC0AB182B324FB61D108A94BB2C8E3FBB96ADAB760D7F4681D4F42A3DE394DF4",
+            "AE56EDE76372BB190B07A7C8EE0A6D709E02FCE1CDF7E2ECC03404CD28342F61",
+            "9172FE9CE98583FF8E4F1232EEF28183C3FE3B1B4C6FAD733BB5FCBC2EC22005",
+            "C58EF1837D1683B2C6F34A26C1B2EFFA886B4238611FCFDCDE355B3B6519035B",
+            "BC34F4DEF99C023861B46FC9D6E6C9077AD91D2691F7F7EE598CB0FAC186D91C",
+            "AEFE130985139270B4130C93BC437944F4FD4452E2D74DD364F2E21E71F54BFF",
+            "5CAE82AB9C9DF69EE86D2BC522363A0DABC521979B0DEADA1DBF9A42D5C4484E",
+            "0ABCD06BFA53DDEF3C1B20EE3FD59D7C25E41D2B669E1EF16E6F52C3164DF4FB",
+            "7930E9E4E58857B6AC7D5F42D69F6D187763CF1D5503400487F55BA57E31CC7A",
+            "7135C886EFB4318AED6A1E012D9E6832A907600A918130C46DC778F971AD0038",
+            "092999A333CB8B7A1A1DB93D7140003C2A4ECEA9F98D0ACC0A8291CDCEC97DCF",
+            "8EC9B55A7F88A46B4DB5A851F44182E1C68A007E5E0DD9020BFD64B645036C7A",
+            "4E677D2C38532A3A23BA4442CAF53EA63BB454329B7624C8917BDD64B1C0FD4C",
+            "B38E8C334C701C3ACDAD0657FCCFEC719B1F5C3E4E46041F388147FB4CFDB477",
+            "A52471F7A9A96910B855322EDB6340D8A00EF092350511E30ABEC1FFF9E3A26E",
+            "7FB29F8C183023C3587E38DA0077D9B4763E4E4B94B2BBC194C6651E77CAF992",
+            "EEAAC0232A281BF6B3A739C1226116820AE8DB5847A67CBEF9C9091B462D538C",
+            "D72B03746AE77F5E62292C311562A846505DC82DB854338AE49F5235C95B9117",
+            "8CCF2DD5CACEF403EC9D1810C6272B045B3B71F9DC6B80D63FDD4A8E9ADB1E69",
+            "62A69526D43161C1A41D570D7938DAD4A40E329CCFF46AAA36AD004CF600C838",
+            "1E425A31D951AE64FDB23FCEC9509D43687FEB69EDD1CC5E0B8CC3BDF64B10EF",
+            "86B63142A3AB8829555B2F747C932665CB2C0F1CC01BD70229388839D2AF05E4",
+            "54504AC78B7582822846C0BA35C35F5C59160CC046FD8251541FC68C9C86B022",
+            "BB7099876A460E7451A8A93109703FEE1C217E6C3826E52C51AA691E0E423CFC",
+            "99E9E31650C1217B624816CDAD9A95F9D5B8019488D9C0A0A1FE3075A577E231",
+            "83F81D4A3F2FA4571EFC8CE0BA8A4FE8B6855DFE72B0A66EDED2FBABFBE58A30",
+            "FAFABE1C5D71A87E2F741EF8C1FE86FEA6BBFDE530677F0D97D11D49F7A8443D",
+            "0822E506A9F4614E011E2A94838FF88CD68C8BB7C5C6424CFFFFFFFFFFFFFFFF",
+        ),
+    ),
+];
+
 #[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.dh")]
 pub(crate) struct DHPrivateKey {
     pkey: openssl::pkey::PKey<openssl::pkey::Private>,
@@ -22,15 +144,19 @@ pub(crate) struct DHPublicKey {
 }
 
 #[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.dh")]
-struct DHParameters {
+pub(crate) struct DHParameters {
     dh: openssl::dh::Dh<openssl::pkey::Params>,
 }
 
 #[pyo3::prelude::pyfunction]
+#[pyo3(signature = (generator, key_size, backend=None, *, progress=None, cancel=None))]
 fn generate_parameters(
+    py: pyo3::Python<'_>,
     generator: u32,
     key_size: u32,
     backend: Option<&pyo3::PyAny>,
+    progress: Option<&pyo3::PyAny>,
+    cancel: Option<&pyo3::PyAny>,
 ) -> CryptographyResult<DHParameters> {
     let _ = backend;
 
@@ -47,11 +173,64 @@ fn generate_parameters(
         ));
     }
 
-    let dh = openssl::dh::Dh::generate_params(key_size, generator)
-        .map_err(|_| pyo3::exceptions::PyValueError::new_err("Unable to generate DH parameters"))?;
+    let dh = utils::generate_params_without_gil(py, progress, cancel, |callback| {
+        cryptography_openssl::paramgen::generate_dh_params(key_size, generator, callback)
+    })?
+    .map_err(|_| pyo3::exceptions::PyValueError::new_err("Unable to generate DH parameters"))?;
     Ok(DHParameters { dh })
 }
 
+#[pyo3::prelude::pyfunction]
+fn named_group_parameters(name: &str) -> CryptographyResult<DHParameters> {
+    let p = FFDHE_GROUPS
+        .iter()
+        .find(|(group, _)| *group == name)
+        .map(|(_, p)| p)
+        .ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!("Unknown DH group: {name}"))
+        })?;
+
+    // The groups are safe primes, so `q` is `(p - 1) / 2`. OpenSSL 3 fills it
+    // in for any parameters with a named group's prime, so it's set here too,
+    // rather than having it depend on the backend.
+    let p = openssl::bn::BigNum::from_hex_str(p)?;
+    let mut q = openssl::bn::BigNum::new()?;
+    q.rshift1(&p)?;
+    let g = openssl::bn::BigNum::from_u32(2)?;
+    Ok(DHParameters {
+        dh: openssl::dh::Dh::from_pqg(p, Some(q), g)?,
+    })
+}
+
+// Parameters are in a named group if they have its prime and generator, and
+// if they include a `q`, it's `(p - 1) / 2`.
+fn group_name<T: openssl::pkey::HasParams>(
+    dh: &openssl::dh::Dh<T>,
+) -> CryptographyResult<Option<&'static str>> {
+    if dh.generator() != openssl::bn::BigNum::from_u32(2)?.as_ref() {
+        return Ok(None);
+    }
+    for (name, p) in FFDHE_GROUPS {
+        // Each hex digit is 4 bits.
+        if usize::try_from(dh.prime_p().num_bits()).unwrap() != p.len() * 4 {
+            continue;
+        }
+        let p = openssl::bn::BigNum::from_hex_str(p)?;
+        if dh.prime_p() != p.as_ref() {
+            continue;
+        }
+        if let Some(q) = dh.prime_q() {
+            let mut expected_q = openssl::bn::BigNum::new()?;
+            expected_q.rshift1(&p)?;
+            if q != expected_q.as_ref() {
+                return Ok(None);
+            }
+        }
+        return Ok(Some(name));
+    }
+    Ok(None)
+}
+
 pub(crate) fn private_key_from_pkey(
     pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
 ) -> DHPrivateKey {
@@ -85,24 +264,41 @@ fn pkey_from_dh<T: openssl::pkey::HasParams>(
     }
 }
 
-#[pyo3::prelude::pyfunction]
-fn from_der_parameters(
+fn dh_parameters_from_x942_der(
     data: &[u8],
-    backend: Option<&pyo3::PyAny>,
-) -> CryptographyResult<DHParameters> {
-    let _ = backend;
-    let asn1_params = asn1::parse_single::<common::DHParams<'_>>(data)?;
+) -> CryptographyResult<openssl::dh::Dh<openssl::pkey::Params>> {
+    // `j` and `validationParms` aren't used by OpenSSL, so they're ignored.
+    let asn1_params = asn1::parse_single::<common::DHXParams<'_>>(data)?;
 
     let p = openssl::bn::BigNum::from_slice(asn1_params.p.as_bytes())?;
-    let q = asn1_params
-        .q
-        .map(|q| openssl::bn::BigNum::from_slice(q.as_bytes()))
-        .transpose()?;
+    let q = openssl::bn::BigNum::from_slice(asn1_params.q.as_bytes())?;
     let g = openssl::bn::BigNum::from_slice(asn1_params.g.as_bytes())?;
+    Ok(openssl::dh::Dh::from_pqg(p, Some(q), g)?)
+}
 
-    Ok(DHParameters {
-        dh: openssl::dh::Dh::from_pqg(p, q, g)?,
-    })
+fn dh_parameters_from_pkcs3_der(
+    data: &[u8],
+) -> CryptographyResult<openssl::dh::Dh<openssl::pkey::Params>> {
+    let asn1_params = asn1::parse_single::<common::BasicDHParams<'_>>(data)?;
+
+    let p = openssl::bn::BigNum::from_slice(asn1_params.p.as_bytes())?;
+    let g = openssl::bn::BigNum::from_slice(asn1_params.g.as_bytes())?;
+    Ok(openssl::dh::Dh::from_pqg(p, None, g)?)
+}
+
+#[pyo3::prelude::pyfunction]
+pub(crate) fn from_der_parameters(
+    data: &[u8],
+    backend: Option<&pyo3::PyAny>,
+) -> CryptographyResult<DHParameters> {
+    let _ = backend;
+    // DER has no label to tell the two structures apart, so a third integer
+    // is treated as X9.42's `q` rather than PKCS #3's `privateValueLength`.
+    let dh = match dh_parameters_from_x942_der(data) {
+        Ok(dh) => dh,
+        Err(_) => dh_parameters_from_pkcs3_der(data)?,
+    };
+    Ok(DHParameters { dh })
 }
 
 #[pyo3::prelude::pyfunction]
@@ -117,7 +313,18 @@ fn from_pem_parameters(
         "Valid PEM but no BEGIN DH PARAMETERS/END DH PARAMETERS delimiters. Are you sure this is a DH parameters?",
     )?;
 
-    from_der_parameters(parsed.contents(), None)
+    parameters_from_pem(&parsed)
+}
+
+// Unlike DER, a PEM block's label says which structure it holds, so each is
+// only parsed as that one.
+pub(crate) fn parameters_from_pem(parsed: &pem::Pem) -> CryptographyResult<DHParameters> {
+    let dh = if parsed.tag() == "X9.42 DH PARAMETERS" {
+        dh_parameters_from_x942_der(parsed.contents())?
+    } else {
+        dh_parameters_from_pkcs3_der(parsed.contents())?
+    };
+    Ok(DHParameters { dh })
 }
 
 fn dh_parameters_from_numbers(
@@ -144,6 +351,67 @@ fn clone_dh<T: openssl::pkey::HasParams>(
     Ok(openssl::dh::Dh::from_pqg(p, q, g)?)
 }
 
+// Full public key validation from NIST SP 800-56A Rev. 3, section 5.6.2.3.1:
+// y must be in [2, p - 2], and when q is known, y^q mod p must be 1 so that
+// y is in the subgroup of order q.
+fn validate_public_key(
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Public>,
+) -> CryptographyResult<()> {
+    let dh = pkey.dh()?;
+    let p = dh.prime_p();
+    let y = dh.public_key();
+
+    let one = openssl::bn::BigNum::from_u32(1)?;
+    let mut p_minus_one = openssl::bn::BigNum::new()?;
+    p_minus_one.checked_sub(p, &one)?;
+    let mut valid = y > &*one && y < &*p_minus_one;
+    if valid {
+        if let Some(q) = dh.prime_q() {
+            let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+            let mut r = openssl::bn::BigNum::new()?;
+            r.mod_exp(y, q, p, &mut bn_ctx)?;
+            valid = r == one;
+        }
+    }
+
+    if !valid {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("Invalid DH public key"),
+        ));
+    }
+    Ok(())
+}
+
+impl DHPrivateKey {
+    // OpenSSL strips leading zero bytes from the shared secret, so unless
+    // `pad` is false they're restored, giving a value as long as p.
+    pub(crate) fn compute_shared_key(
+        &self,
+        public_key: &DHPublicKey,
+        validate: bool,
+        pad: bool,
+    ) -> CryptographyResult<Vec<u8>> {
+        if validate {
+            validate_public_key(&public_key.pkey)?;
+        }
+        let mut deriver = openssl::derive::Deriver::new(&self.pkey)?;
+        deriver
+            .set_peer(&public_key.pkey)
+            .map_err(|_| pyo3::exceptions::PyValueError::new_err("Error computing shared key."))?;
+
+        let mut shared_key = vec![0; deriver.len()?];
+        let n = deriver.derive(&mut shared_key)?;
+        if pad {
+            let pad_len = shared_key.len() - n;
+            shared_key.copy_within(0..n, pad_len);
+            shared_key[..pad_len].fill(0);
+        } else {
+            shared_key.truncate(n);
+        }
+        Ok(shared_key)
+    }
+}
+
 #[pyo3::prelude::pymethods]
 impl DHPrivateKey {
     #[getter]
@@ -151,28 +419,28 @@ impl DHPrivateKey {
         self.pkey.dh().unwrap().prime_p().num_bits()
     }
 
+    #[pyo3(signature = (public_key, *, validate=false))]
     fn exchange<'p>(
         &self,
         py: pyo3::Python<'p>,
         public_key: &DHPublicKey,
+        validate: bool,
     ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
-        let mut deriver = openssl::derive::Deriver::new(&self.pkey)?;
-        deriver
-            .set_peer(&public_key.pkey)
-            .map_err(|_| pyo3::exceptions::PyValueError::new_err("Error computing shared key."))?;
-
-        Ok(pyo3::types::PyBytes::new_with(py, deriver.len()?, |b| {
-            let n = deriver.derive(b).unwrap();
+        let shared_key = self.compute_shared_key(public_key, validate, true)?;
+        Ok(pyo3::types::PyBytes::new(py, &shared_key))
+    }
 
-            let pad = b.len() - n;
-            if pad > 0 {
-                b.copy_within(0..n, pad);
-                for c in b.iter_mut().take(pad) {
-                    *c = 0;
-                }
-            }
-            Ok(())
-        })?)
+    #[pyo3(signature = (public_key, kdf, *, validate=false, pad=true))]
+    fn exchange_and_derive<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        public_key: &DHPublicKey,
+        kdf: &pyo3::PyAny,
+        validate: bool,
+        pad: bool,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let shared_key = self.compute_shared_key(public_key, validate, pad)?;
+        kdf::derive_with_kdf(py, kdf, &shared_key)
     }
 
     fn private_numbers(&self, py: pyo3::Python<'_>) -> CryptographyResult<DHPrivateNumbers> {
@@ -246,6 +514,22 @@ impl DHPrivateKey {
             false,
         )
     }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> CryptographyResult<bool> {
+        utils::pkey_private_eq(&self.pkey, &other.pkey)
+    }
+
+    fn __hash__(&self) -> CryptographyResult<u64> {
+        utils::pkey_hash(&self.pkey)
+    }
+
+    fn public_key_matches(
+        slf: &pyo3::PyCell<Self>,
+        py: pyo3::Python<'_>,
+        public_key: &pyo3::PyAny,
+    ) -> CryptographyResult<bool> {
+        utils::public_key_matches(py, slf, public_key)
+    }
 }
 
 #[pyo3::prelude::pymethods]
@@ -255,6 +539,10 @@ impl DHPublicKey {
         self.pkey.dh().unwrap().prime_p().num_bits()
     }
 
+    fn validate(&self) -> CryptographyResult<()> {
+        validate_public_key(&self.pkey)
+    }
+
     fn public_bytes<'p>(
         slf: &pyo3::PyCell<Self>,
         py: pyo3::Python<'p>,
@@ -302,6 +590,14 @@ impl DHPublicKey {
         })
     }
 
+    fn fingerprint<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::PyAny> {
+        utils::pkey_fingerprint(py, &self.pkey, algorithm)
+    }
+
     fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> bool {
         self.pkey.public_eq(&other.pkey)
     }
@@ -321,6 +617,11 @@ impl DHParameters {
         })
     }
 
+    #[getter]
+    fn group_name(&self) -> CryptographyResult<Option<&'static str>> {
+        group_name(&self.dh)
+    }
+
     fn parameter_numbers(&self, py: pyo3::Python<'_>) -> CryptographyResult<DHParameterNumbers> {
         let py_p = utils::bn_to_py_int(py, self.dh.prime_p())?;
         let py_q = self
@@ -343,9 +644,19 @@ impl DHParameters {
         encoding: &'p pyo3::PyAny,
         format: &pyo3::PyAny,
     ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
-        if !format.is(types::PARAMETER_FORMAT_PKCS3.get(py)?) {
+        let x942 = format.is(types::PARAMETER_FORMAT_X942.get(py)?);
+        if !x942 && !format.is(types::PARAMETER_FORMAT_PKCS3.get(py)?) {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "Only PKCS3 and X942 serialization are supported",
+                ),
+            ));
+        }
+        if x942 && self.dh.prime_q().is_none() {
             return Err(CryptographyError::from(
-                pyo3::exceptions::PyValueError::new_err("Only PKCS3 serialization is supported"),
+                pyo3::exceptions::PyValueError::new_err(
+                    "X942 serialization requires parameters with a q value",
+                ),
             ));
         }
 
@@ -548,6 +859,7 @@ impl DHParameterNumbers {
 pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
     let m = pyo3::prelude::PyModule::new(py, "dh")?;
     m.add_function(pyo3::wrap_pyfunction!(generate_parameters, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(named_group_parameters, m)?)?;
     m.add_function(pyo3::wrap_pyfunction!(from_der_parameters, m)?)?;
     m.add_function(pyo3::wrap_pyfunction!(from_pem_parameters, m)?)?;
 
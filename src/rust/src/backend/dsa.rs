@@ -50,11 +50,68 @@ pub(crate) fn public_key_from_pkey(
 }
 
 #[pyo3::prelude::pyfunction]
-fn generate_parameters(key_size: u32) -> CryptographyResult<DsaParameters> {
-    let dsa = openssl::dsa::Dsa::generate_params(key_size)?;
+#[pyo3(signature = (key_size, *, progress=None, cancel=None))]
+fn generate_parameters(
+    py: pyo3::Python<'_>,
+    key_size: u32,
+    progress: Option<&pyo3::PyAny>,
+    cancel: Option<&pyo3::PyAny>,
+) -> CryptographyResult<DsaParameters> {
+    let dsa = utils::generate_params_without_gil(py, progress, cancel, |callback| {
+        cryptography_openssl::paramgen::generate_dsa_params(key_size, callback)
+    })??;
     Ok(DsaParameters { dsa })
 }
 
+#[pyo3::prelude::pyfunction]
+fn generate_verifiable_parameters(
+    py: pyo3::Python<'_>,
+    key_size: u32,
+) -> CryptographyResult<(DsaParameters, &pyo3::types::PyBytes, i32)> {
+    cfg_if::cfg_if! {
+        if #[cfg(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER)] {
+            let (dsa, seed, counter) = py.allow_threads(|| {
+                cryptography_openssl::ffc::generate_verifiable_dsa_params(key_size)
+            })?;
+            Ok((DsaParameters { dsa }, pyo3::types::PyBytes::new(py, &seed), counter))
+        } else {
+            let _ = (py, key_size);
+            Err(verifiable_parameters_unsupported())
+        }
+    }
+}
+
+#[pyo3::prelude::pyfunction]
+fn validate_parameters(
+    parameters: &DsaParameters,
+    seed: &[u8],
+    counter: i32,
+) -> CryptographyResult<()> {
+    cfg_if::cfg_if! {
+        if #[cfg(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER)] {
+            if !cryptography_openssl::ffc::validate_dsa_params(&parameters.dsa, seed, counter)? {
+                return Err(CryptographyError::from(
+                    pyo3::exceptions::PyValueError::new_err(
+                        "DSA parameters were not generated from this seed and counter",
+                    ),
+                ));
+            }
+            Ok(())
+        } else {
+            let _ = (parameters, seed, counter);
+            Err(verifiable_parameters_unsupported())
+        }
+    }
+}
+
+#[cfg(not(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER))]
+fn verifiable_parameters_unsupported() -> CryptographyError {
+    CryptographyError::from(exceptions::UnsupportedAlgorithm::new_err((
+        "Verifiable DSA parameters require OpenSSL 3.0.0 or newer.",
+        exceptions::Reasons::UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
+    )))
+}
+
 fn clone_dsa_params<T: openssl::pkey::HasParams>(
     d: &openssl::dsa::Dsa<T>,
 ) -> Result<openssl::dsa::Dsa<openssl::pkey::Params>, openssl::error::ErrorStack> {
@@ -63,16 +120,37 @@ fn clone_dsa_params<T: openssl::pkey::HasParams>(
 
 #[pyo3::prelude::pymethods]
 impl DsaPrivateKey {
+    #[pyo3(signature = (data, algorithm, *, deterministic_signing=false))]
     fn sign<'p>(
         &self,
         py: pyo3::Python<'p>,
         data: &[u8],
         algorithm: &pyo3::PyAny,
+        deterministic_signing: bool,
     ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
-        let (data, _) = utils::calculate_digest_and_algorithm(py, data, algorithm)?;
+        let (data, hash_algorithm) = utils::calculate_digest_and_algorithm(py, data, algorithm)?;
 
         let mut signer = openssl::pkey_ctx::PkeyCtx::new(&self.pkey)?;
         signer.sign_init()?;
+        if deterministic_signing {
+            cfg_if::cfg_if! {
+                if #[cfg(CRYPTOGRAPHY_OPENSSL_320_OR_GREATER)] {
+                    let md =
+                        crate::backend::hashes::message_digest_from_algorithm(py, hash_algorithm)?;
+                    signer.set_signature_md(openssl::md::Md::from_nid(md.type_()).unwrap())?;
+                    cryptography_openssl::nonce::set_deterministic(&signer)?;
+                } else {
+                    let _ = hash_algorithm;
+                    return Err(CryptographyError::from(
+                        exceptions::UnsupportedAlgorithm::new_err((
+                            "Deterministic DSA signatures are not supported by this \
+                             version of OpenSSL.",
+                            exceptions::Reasons::UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
+                        )),
+                    ));
+                }
+            }
+        }
         let mut sig = vec![];
         signer.sign_to_vec(data, &mut sig)?;
         Ok(pyo3::types::PyBytes::new(py, &sig))
@@ -144,6 +222,22 @@ impl DsaPrivateKey {
             false,
         )
     }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> CryptographyResult<bool> {
+        utils::pkey_private_eq(&self.pkey, &other.pkey)
+    }
+
+    fn __hash__(&self) -> CryptographyResult<u64> {
+        utils::pkey_hash(&self.pkey)
+    }
+
+    fn public_key_matches(
+        slf: &pyo3::PyCell<Self>,
+        py: pyo3::Python<'_>,
+        public_key: &pyo3::PyAny,
+    ) -> CryptographyResult<bool> {
+        utils::public_key_matches(py, slf, public_key)
+    }
 }
 
 #[pyo3::prelude::pymethods]
@@ -208,6 +302,14 @@ impl DsaPublicKey {
         utils::pkey_public_bytes(py, slf, &slf.borrow().pkey, encoding, format, true, false)
     }
 
+    fn fingerprint<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::PyAny> {
+        utils::pkey_fingerprint(py, &self.pkey, algorithm)
+    }
+
     fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> bool {
         self.pkey.public_eq(&other.pkey)
     }
@@ -499,6 +601,8 @@ impl DsaParameterNumbers {
 pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
     let m = pyo3::prelude::PyModule::new(py, "dsa")?;
     m.add_function(pyo3::wrap_pyfunction!(generate_parameters, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(generate_verifiable_parameters, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(validate_parameters, m)?)?;
 
     m.add_class::<DsaPrivateKey>()?;
     m.add_class::<DsaPublicKey>()?;
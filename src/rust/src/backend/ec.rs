@@ -5,27 +5,36 @@
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
+use cryptography_x509::common::{DssSignature, EcParameters};
 use pyo3::ToPyObject;
 
-use crate::backend::utils;
+use crate::asn1::{dss_signature_der_to_raw, dss_signature_raw_to_der, encode_der_data};
+use crate::backend::{hashes, jwk, kdf, utils};
+use crate::buf::CffiBuf;
 use crate::error::{CryptographyError, CryptographyResult};
-use crate::{exceptions, types};
+use crate::{exceptions, types, x509};
+
+// A context which is set up once by `precompute()` and then reused by every
+// subsequent signature or verification with the key.
+type CachedPkeyCtx<T> = pyo3::sync::GILOnceCell<std::sync::Mutex<openssl::pkey_ctx::PkeyCtx<T>>>;
 
 #[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.ec")]
 pub(crate) struct ECPrivateKey {
-    pkey: openssl::pkey::PKey<openssl::pkey::Private>,
+    pub(crate) pkey: openssl::pkey::PKey<openssl::pkey::Private>,
     #[pyo3(get)]
     curve: pyo3::Py<pyo3::PyAny>,
+    sign_ctx: CachedPkeyCtx<openssl::pkey::Private>,
 }
 
 #[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.ec")]
 pub(crate) struct ECPublicKey {
-    pkey: openssl::pkey::PKey<openssl::pkey::Public>,
+    pub(crate) pkey: openssl::pkey::PKey<openssl::pkey::Public>,
     #[pyo3(get)]
     curve: pyo3::Py<pyo3::PyAny>,
+    verify_ctx: CachedPkeyCtx<openssl::pkey::Public>,
 }
 
-fn curve_from_py_curve(
+pub(crate) fn curve_from_py_curve(
     py: pyo3::Python<'_>,
     py_curve: &pyo3::PyAny,
     allow_curve_class: bool,
@@ -72,20 +81,29 @@ fn curve_from_py_curve(
         #[cfg(not(CRYPTOGRAPHY_IS_BORINGSSL))]
         "brainpoolP512r1" => openssl::nid::Nid::BRAINPOOL_P512R1,
 
-        _ => {
-            return Err(CryptographyError::from(
-                exceptions::UnsupportedAlgorithm::new_err((
-                    format!("Curve {curve_name} is not supported"),
-                    exceptions::Reasons::UNSUPPORTED_ELLIPTIC_CURVE,
-                )),
-            ));
+        // The NIDs for these differ between OpenSSL forks, or they're only
+        // available in some builds, so they're looked up by name.
+        "secp224k1" | "brainpoolP256t1" | "brainpoolP384t1" | "brainpoolP512t1" | "FRP256v1" => {
+            match cryptography_openssl::nid::from_short_name(curve_name) {
+                Some(nid) => nid,
+                None => return Err(unsupported_curve(curve_name)),
+            }
         }
+
+        _ => return Err(unsupported_curve(curve_name)),
     };
 
-    Ok(openssl::ec::EcGroup::from_curve_name(nid)?)
+    openssl::ec::EcGroup::from_curve_name(nid).map_err(|_| unsupported_curve(curve_name))
+}
+
+fn unsupported_curve(curve_name: &str) -> CryptographyError {
+    CryptographyError::from(exceptions::UnsupportedAlgorithm::new_err((
+        format!("Curve {curve_name} is not supported"),
+        exceptions::Reasons::UNSUPPORTED_ELLIPTIC_CURVE,
+    )))
 }
 
-fn py_curve_from_curve<'p>(
+pub(crate) fn py_curve_from_curve<'p>(
     py: pyo3::Python<'p>,
     curve: &openssl::ec::EcGroupRef,
 ) -> CryptographyResult<&'p pyo3::PyAny> {
@@ -131,11 +149,575 @@ fn check_key_infinity(
     Ok(())
 }
 
+// The length of each of `r` and `s` in an IEEE P1363 signature. Like
+// `der_to_raw_signature` and COSE, this is the byte length of the curve's
+// field elements.
+fn p1363_component_size(group: &openssl::ec::EcGroupRef) -> usize {
+    (group.degree() as usize + 7) / 8
+}
+
+fn uses_p1363(py: pyo3::Python<'_>, algorithm: &pyo3::PyAny) -> pyo3::PyResult<bool> {
+    Ok(algorithm
+        .getattr(pyo3::intern!(py, "signature_format"))?
+        .is(types::ECDSA_SIGNATURE_FORMAT_P1363.get(py)?))
+}
+
+// Replaces `s` in a DER encoded signature with `n - s` if it is greater than
+// half the group order `n`. Both are valid signatures, but some protocols
+// only accept the "low S" form so that signatures aren't malleable.
+fn normalize_der_signature(
+    group: &openssl::ec::EcGroupRef,
+    signature: &[u8],
+) -> CryptographyResult<Vec<u8>> {
+    let sig = asn1::parse_single::<DssSignature<'_>>(signature)?;
+    let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+    let mut order = openssl::bn::BigNum::new()?;
+    group.order(&mut order, &mut bn_ctx)?;
+    let mut half_order = openssl::bn::BigNum::new()?;
+    half_order.rshift1(&order)?;
+
+    let s = openssl::bn::BigNum::from_slice(sig.s.as_bytes())?;
+    if s >= order {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(
+                "Invalid signature, s is not less than the curve order",
+            ),
+        ));
+    }
+    if s <= half_order {
+        return Ok(signature.to_vec());
+    }
+    let mut low_s = openssl::bn::BigNum::new()?;
+    low_s.checked_sub(&order, &s)?;
+    let low_s = utils::bn_to_big_endian_bytes(&low_s)?;
+    Ok(asn1::write_single(&DssSignature {
+        r: sig.r,
+        s: asn1::BigUint::new(&low_s).unwrap(),
+    })?)
+}
+
+#[pyo3::prelude::pyfunction]
+fn normalize_signature<'p>(
+    py: pyo3::Python<'p>,
+    signature: CffiBuf<'_>,
+    py_curve: &pyo3::PyAny,
+    signature_format: &pyo3::PyAny,
+) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+    let curve = curve_from_py_curve(py, py_curve, false)?;
+    let signature = if signature_format.is(types::ECDSA_SIGNATURE_FORMAT_P1363.get(py)?) {
+        let size = p1363_component_size(&curve);
+        let der = dss_signature_raw_to_der(signature.as_bytes(), size).ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "Raw signatures must be {} bytes long",
+                size * 2
+            ))
+        })?;
+        dss_signature_der_to_raw(&normalize_der_signature(&curve, &der)?, size)?
+    } else {
+        normalize_der_signature(&curve, signature.as_bytes())?
+    };
+    Ok(pyo3::types::PyBytes::new(py, &signature))
+}
+
+// Wraps a copy of a key for use in a cached context. Prior to OpenSSL 3.0 a
+// table of multiples of the generator can be stored in the key's group; it's
+// built on the copy because other threads may be using the original without
+// holding the GIL. Later versions ship fixed tables for the common curves
+// instead.
+fn precomputed_pkey<T>(ec: openssl::ec::EcKey<T>) -> CryptographyResult<openssl::pkey::PKey<T>> {
+    cfg_if::cfg_if! {
+        if #[cfg(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER)] {
+            Ok(openssl::pkey::PKey::from_ec_key(ec)?)
+        } else {
+            let mut ec = ec;
+            cryptography_openssl::ec::precompute_mult(&mut ec)?;
+            Ok(openssl::pkey::PKey::from_ec_key(ec)?)
+        }
+    }
+}
+
+pub(crate) fn check_ecdsa_algorithm(
+    py: pyo3::Python<'_>,
+    algorithm: &pyo3::PyAny,
+) -> CryptographyResult<()> {
+    if !algorithm.is_instance(types::ECDSA.get(py)?)? {
+        return Err(CryptographyError::from(
+            exceptions::UnsupportedAlgorithm::new_err((
+                "Unsupported elliptic curve signature algorithm",
+                exceptions::Reasons::UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
+            )),
+        ));
+    }
+    Ok(())
+}
+
+// Produces an ECDSA signature over an already computed digest, applying the
+// options from the `ECDSA` object.
+pub(crate) fn ecdsa_sign_digest(
+    py: pyo3::Python<'_>,
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+    group: &openssl::ec::EcGroupRef,
+    cached_ctx: Option<&std::sync::Mutex<openssl::pkey_ctx::PkeyCtx<openssl::pkey::Private>>>,
+    data: &[u8],
+    hash_algorithm: &pyo3::PyAny,
+    algorithm: &pyo3::PyAny,
+) -> CryptographyResult<Vec<u8>> {
+    let deterministic: bool = algorithm
+        .getattr(pyo3::intern!(py, "deterministic_signing"))?
+        .extract()?;
+    // Deterministic signing changes the context's parameters, so it always
+    // gets a fresh context, as does a thread which finds the cached one in
+    // use.
+    let mut cached = match cached_ctx {
+        Some(ctx) if !deterministic => ctx.try_lock().ok(),
+        _ => None,
+    };
+    let mut fresh;
+    let signer = match cached.as_deref_mut() {
+        Some(ctx) => ctx,
+        None => {
+            fresh = openssl::pkey_ctx::PkeyCtx::new(pkey)?;
+            fresh.sign_init()?;
+            if deterministic {
+                cfg_if::cfg_if! {
+                    if #[cfg(CRYPTOGRAPHY_OPENSSL_320_OR_GREATER)] {
+                        let md = hashes::message_digest_from_algorithm(py, hash_algorithm)?;
+                        fresh.set_signature_md(openssl::md::Md::from_nid(md.type_()).unwrap())?;
+                        cryptography_openssl::nonce::set_deterministic(&fresh)?;
+                    } else {
+                        let _ = hash_algorithm;
+                        return Err(CryptographyError::from(
+                            exceptions::UnsupportedAlgorithm::new_err((
+                                "Deterministic ECDSA signatures are not supported by this \
+                                 version of OpenSSL.",
+                                exceptions::Reasons::UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
+                            )),
+                        ));
+                    }
+                }
+            }
+            &mut fresh
+        }
+    };
+    // TODO: This does an extra allocation and copy. This can't easily use
+    // `PyBytes::new_with` because the exact length of the signature isn't
+    // easily known a priori (if `r` or `s` has a leading 0, the signature
+    // will be a byte or two shorter than the maximum possible length).
+    let mut sig = vec![];
+    // The signing itself does no Python work, so other threads are allowed
+    // to run while it happens. `data` is either a digest we computed or an
+    // immutable `bytes`, so it can't change underneath us.
+    py.allow_threads(|| signer.sign_to_vec(data, &mut sig))?;
+    let low_s: bool = algorithm.getattr(pyo3::intern!(py, "low_s"))?.extract()?;
+    if low_s {
+        sig = normalize_der_signature(group, &sig)?;
+    }
+    if uses_p1363(py, algorithm)? {
+        sig = dss_signature_der_to_raw(&sig, p1363_component_size(group))?;
+    }
+    Ok(sig)
+}
+
+fn ecdsa_verify_digest(
+    py: pyo3::Python<'_>,
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Public>,
+    cached_ctx: Option<&std::sync::Mutex<openssl::pkey_ctx::PkeyCtx<openssl::pkey::Public>>>,
+    signature: &[u8],
+    data: &[u8],
+    algorithm: &pyo3::PyAny,
+) -> CryptographyResult<()> {
+    let der_signature;
+    let signature = if uses_p1363(py, algorithm)? {
+        let size = p1363_component_size(pkey.ec_key().unwrap().group());
+        der_signature = dss_signature_raw_to_der(signature, size)
+            .ok_or_else(|| exceptions::InvalidSignature::new_err(()))?;
+        &der_signature
+    } else {
+        signature
+    };
+
+    let mut cached = cached_ctx.and_then(|ctx| ctx.try_lock().ok());
+    let mut fresh;
+    let verifier = match cached.as_deref_mut() {
+        Some(ctx) => ctx,
+        None => {
+            fresh = openssl::pkey_ctx::PkeyCtx::new(pkey)?;
+            fresh.verify_init()?;
+            &mut fresh
+        }
+    };
+    let valid = py
+        .allow_threads(|| verifier.verify(data, signature))
+        .unwrap_or(false);
+    if !valid {
+        return Err(CryptographyError::from(
+            exceptions::InvalidSignature::new_err(()),
+        ));
+    }
+
+    Ok(())
+}
+
+// Computes the shared point for the ECDH modes OpenSSL's derivation doesn't
+// cover. With `cofactor` this is the ECC CDH primitive from NIST SP 800-56A
+// section 5.7.1.2, `h * d * Q`, where `h` is the curve's cofactor; for curves
+// with a cofactor of 1 this is the same as plain ECDH. The result is the x
+// coordinate, or `x || y` if `full_point` is set.
+fn manual_exchange(
+    private_key: &openssl::ec::EcKeyRef<openssl::pkey::Private>,
+    public_key: &openssl::ec::EcKeyRef<openssl::pkey::Public>,
+    cofactor: bool,
+    full_point: bool,
+) -> CryptographyResult<Vec<u8>> {
+    let group = private_key.group();
+    if group.curve_name() != public_key.group().curve_name() {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("Error computing shared key."),
+        ));
+    }
+
+    let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+    let scalar = if cofactor {
+        let mut h = openssl::bn::BigNum::new()?;
+        group.cofactor(&mut h, &mut bn_ctx)?;
+        let mut scalar = openssl::bn::BigNum::new()?;
+        scalar.checked_mul(private_key.private_key(), &h, &mut bn_ctx)?;
+        scalar
+    } else {
+        private_key.private_key().to_owned()?
+    };
+
+    let mut point = openssl::ec::EcPoint::new(group)?;
+    point.mul(group, public_key.public_key(), &scalar, &bn_ctx)?;
+    if point.is_infinity(group) {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("Error computing shared key."),
+        ));
+    }
+    let mut x = openssl::bn::BigNum::new()?;
+    let mut y = openssl::bn::BigNum::new()?;
+    point.affine_coordinates(group, &mut x, &mut y, &mut bn_ctx)?;
+    let size = ((group.degree() + 7) / 8) as i32;
+    let mut shared = x.to_vec_padded(size)?;
+    if full_point {
+        shared.extend_from_slice(&y.to_vec_padded(size)?);
+    }
+    Ok(shared)
+}
+
+// Recovers the public key from an ECDSA signature, as described in SEC 1
+// v2.0 section 4.1.6. `recovery_id` selects which of the up to four
+// candidate keys is returned: its low bit is the parity of the y coordinate
+// of the point `R`, and its high bit whether `R`'s x coordinate is `r + n`
+// rather than `r`.
+#[pyo3::prelude::pyfunction]
+fn recover_public_key(
+    py: pyo3::Python<'_>,
+    py_curve: &pyo3::PyAny,
+    signature: &[u8],
+    data: &[u8],
+    algorithm: &pyo3::PyAny,
+    recovery_id: u8,
+) -> CryptographyResult<ECPublicKey> {
+    if !algorithm.is_instance(types::ECDSA.get(py)?)? {
+        return Err(CryptographyError::from(
+            exceptions::UnsupportedAlgorithm::new_err((
+                "Unsupported elliptic curve signature algorithm",
+                exceptions::Reasons::UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
+            )),
+        ));
+    }
+    if recovery_id > 3 {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("recovery_id must be between 0 and 3"),
+        ));
+    }
+    let curve_name = py_curve
+        .getattr(pyo3::intern!(py, "name"))?
+        .extract::<&str>()?;
+    if curve_name.starts_with("sect") {
+        return Err(CryptographyError::from(
+            exceptions::UnsupportedAlgorithm::new_err((
+                "Public key recovery is only supported for prime curves",
+                exceptions::Reasons::UNSUPPORTED_ELLIPTIC_CURVE,
+            )),
+        ));
+    }
+    let group = curve_from_py_curve(py, py_curve, false)?;
+    let invalid = || {
+        CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+            "No public key can be recovered from this signature and recovery_id",
+        ))
+    };
+
+    let der_signature;
+    let signature = if uses_p1363(py, algorithm)? {
+        der_signature = dss_signature_raw_to_der(signature, p1363_component_size(&group))
+            .ok_or_else(invalid)?;
+        &der_signature
+    } else {
+        signature
+    };
+    let sig = asn1::parse_single::<DssSignature<'_>>(signature)?;
+    let (data, _) = utils::calculate_digest_and_algorithm(
+        py,
+        data,
+        algorithm.getattr(pyo3::intern!(py, "algorithm"))?,
+    )?;
+
+    let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+    let mut order = openssl::bn::BigNum::new()?;
+    group.order(&mut order, &mut bn_ctx)?;
+    let r = openssl::bn::BigNum::from_slice(sig.r.as_bytes())?;
+    let s = openssl::bn::BigNum::from_slice(sig.s.as_bytes())?;
+    let zero = openssl::bn::BigNum::new()?;
+    if r <= zero || r >= order || s <= zero || s >= order {
+        return Err(invalid());
+    }
+
+    // R's x coordinate is r, or r + n, which must still be a field element.
+    let mut p = openssl::bn::BigNum::new()?;
+    let mut a = openssl::bn::BigNum::new()?;
+    let mut b = openssl::bn::BigNum::new()?;
+    group.components_gfp(&mut p, &mut a, &mut b, &mut bn_ctx)?;
+    let mut x = r.to_owned()?;
+    if recovery_id & 2 != 0 {
+        x.checked_add(&r, &order)?;
+    }
+    if x >= p {
+        return Err(invalid());
+    }
+    let mut encoded_r = vec![0x02 | (recovery_id & 1)];
+    encoded_r.extend_from_slice(&x.to_vec_padded(p1363_component_size(&group) as i32)?);
+    let big_r =
+        openssl::ec::EcPoint::from_bytes(&group, &encoded_r, &mut bn_ctx).map_err(|_| invalid())?;
+
+    // e is the leftmost bits of the digest, up to the length of the order.
+    let mut e = openssl::bn::BigNum::from_slice(data)?;
+    let excess_bits = (data.len() * 8) as i32 - order.num_bits();
+    if excess_bits > 0 {
+        let truncated = e.to_owned()?;
+        e.rshift(&truncated, excess_bits)?;
+    }
+
+    // Q = r^-1 (sR - eG) = (-e * r^-1) G + (s * r^-1) R
+    let mut r_inv = openssl::bn::BigNum::new()?;
+    r_inv.mod_inverse(&r, &order, &mut bn_ctx)?;
+    let mut neg_e = openssl::bn::BigNum::new()?;
+    neg_e.mod_sub(&zero, &e, &order, &mut bn_ctx)?;
+    let mut u1 = openssl::bn::BigNum::new()?;
+    u1.mod_mul(&neg_e, &r_inv, &order, &mut bn_ctx)?;
+    let mut u2 = openssl::bn::BigNum::new()?;
+    u2.mod_mul(&s, &r_inv, &order, &mut bn_ctx)?;
+
+    let mut q = openssl::ec::EcPoint::new(&group)?;
+    q.mul_full(&group, &u1, &big_r, &u2, &mut bn_ctx)?;
+    if q.is_infinity(&group) {
+        return Err(invalid());
+    }
+    let ec = openssl::ec::EcKey::from_public_key(&group, &q)?;
+    Ok(ECPublicKey {
+        pkey: openssl::pkey::PKey::from_ec_key(ec)?,
+        curve: py_curve.into(),
+        verify_ctx: pyo3::sync::GILOnceCell::new(),
+    })
+}
+
+fn check_bip340_curve(group: &openssl::ec::EcGroupRef) -> CryptographyResult<()> {
+    if group.curve_name() != Some(openssl::nid::Nid::SECP256K1) {
+        return Err(CryptographyError::from(
+            exceptions::UnsupportedAlgorithm::new_err((
+                "BIP-340 Schnorr signatures are only supported on the secp256k1 curve",
+                exceptions::Reasons::UNSUPPORTED_ELLIPTIC_CURVE,
+            )),
+        ));
+    }
+    Ok(())
+}
+
+// hash_tag(x) = SHA256(SHA256(tag) || SHA256(tag) || x), reduced modulo the
+// curve order.
+fn bip340_tagged_hash(
+    tag: &str,
+    parts: &[&[u8]],
+    order: &openssl::bn::BigNumRef,
+    bn_ctx: &mut openssl::bn::BigNumContextRef,
+) -> CryptographyResult<openssl::bn::BigNum> {
+    let tag_hash = openssl::sha::sha256(tag.as_bytes());
+    let mut h = openssl::sha::Sha256::new();
+    h.update(&tag_hash);
+    h.update(&tag_hash);
+    for part in parts {
+        h.update(part);
+    }
+    let digest = openssl::bn::BigNum::from_slice(&h.finish())?;
+    let mut result = openssl::bn::BigNum::new()?;
+    result.nnmod(&digest, order, bn_ctx)?;
+    Ok(result)
+}
+
+// Returns the x coordinate of `point` as 32 bytes, and whether its y
+// coordinate is even.
+fn bip340_point_x(
+    group: &openssl::ec::EcGroupRef,
+    point: &openssl::ec::EcPointRef,
+    bn_ctx: &mut openssl::bn::BigNumContextRef,
+) -> CryptographyResult<(Vec<u8>, bool)> {
+    let mut x = openssl::bn::BigNum::new()?;
+    let mut y = openssl::bn::BigNum::new()?;
+    point.affine_coordinates(group, &mut x, &mut y, bn_ctx)?;
+    Ok((x.to_vec_padded(32)?, !y.is_bit_set(0)))
+}
+
+// Signs `data` as described in BIP-340.
+fn bip340_sign(
+    py: pyo3::Python<'_>,
+    ec: &openssl::ec::EcKeyRef<openssl::pkey::Private>,
+    data: &[u8],
+    algorithm: &pyo3::PyAny,
+) -> CryptographyResult<Vec<u8>> {
+    let group = ec.group();
+    check_bip340_curve(group)?;
+
+    let aux_rand = match algorithm
+        .getattr(pyo3::intern!(py, "aux_rand"))?
+        .extract::<Option<&[u8]>>()?
+    {
+        Some(aux_rand) => aux_rand.to_vec(),
+        None => {
+            let mut aux_rand = vec![0; 32];
+            openssl::rand::rand_bytes(&mut aux_rand)?;
+            aux_rand
+        }
+    };
+
+    let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+    let mut order = openssl::bn::BigNum::new()?;
+    group.order(&mut order, &mut bn_ctx)?;
+
+    // The secret key is negated if needed so that the public key has an
+    // even y coordinate, since only its x coordinate is used.
+    let (px, p_even) = bip340_point_x(group, ec.public_key(), &mut bn_ctx)?;
+    let d = if p_even {
+        ec.private_key().to_owned()?
+    } else {
+        let mut d = openssl::bn::BigNum::new()?;
+        d.checked_sub(&order, ec.private_key())?;
+        d
+    };
+
+    let aux_hash = openssl::sha::sha256(b"BIP0340/aux");
+    let mut h = openssl::sha::Sha256::new();
+    h.update(&aux_hash);
+    h.update(&aux_hash);
+    h.update(&aux_rand);
+    let t = d
+        .to_vec_padded(32)?
+        .iter()
+        .zip(h.finish().iter())
+        .map(|(a, b)| a ^ b)
+        .collect::<Vec<u8>>();
+
+    let mut k = bip340_tagged_hash("BIP0340/nonce", &[&t, &px, data], &order, &mut bn_ctx)?;
+    if k.num_bits() == 0 {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("Error computing signature nonce"),
+        ));
+    }
+    let mut r = openssl::ec::EcPoint::new(group)?;
+    r.mul_generator(group, &k, &bn_ctx)?;
+    let (rx, r_even) = bip340_point_x(group, &r, &mut bn_ctx)?;
+    if !r_even {
+        let k_orig = k;
+        k = openssl::bn::BigNum::new()?;
+        k.checked_sub(&order, &k_orig)?;
+    }
+
+    let e = bip340_tagged_hash("BIP0340/challenge", &[&rx, &px, data], &order, &mut bn_ctx)?;
+    let mut ed = openssl::bn::BigNum::new()?;
+    ed.mod_mul(&e, &d, &order, &mut bn_ctx)?;
+    let mut s = openssl::bn::BigNum::new()?;
+    s.mod_add(&k, &ed, &order, &mut bn_ctx)?;
+
+    let mut sig = rx;
+    sig.extend_from_slice(&s.to_vec_padded(32)?);
+    Ok(sig)
+}
+
+// Verifies a BIP-340 signature. Only the x coordinate of the public key is
+// used.
+fn bip340_verify(
+    ec: &openssl::ec::EcKeyRef<openssl::pkey::Public>,
+    signature: &[u8],
+    data: &[u8],
+) -> CryptographyResult<bool> {
+    let group = ec.group();
+    check_bip340_curve(group)?;
+    if signature.len() != 64 {
+        return Ok(false);
+    }
+
+    let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+    let mut order = openssl::bn::BigNum::new()?;
+    group.order(&mut order, &mut bn_ctx)?;
+    let mut p = openssl::bn::BigNum::new()?;
+    let mut a = openssl::bn::BigNum::new()?;
+    let mut b = openssl::bn::BigNum::new()?;
+    group.components_gfp(&mut p, &mut a, &mut b, &mut bn_ctx)?;
+
+    let (px, _) = bip340_point_x(group, ec.public_key(), &mut bn_ctx)?;
+    let mut encoded_p = vec![0x02];
+    encoded_p.extend_from_slice(&px);
+    let public_point = openssl::ec::EcPoint::from_bytes(group, &encoded_p, &mut bn_ctx)?;
+
+    let r = openssl::bn::BigNum::from_slice(&signature[..32])?;
+    let s = openssl::bn::BigNum::from_slice(&signature[32..])?;
+    if r >= p || s >= order {
+        return Ok(false);
+    }
+
+    // R = s * G - e * P
+    let e = bip340_tagged_hash(
+        "BIP0340/challenge",
+        &[&signature[..32], &px, data],
+        &order,
+        &mut bn_ctx,
+    )?;
+    let zero = openssl::bn::BigNum::new()?;
+    let mut neg_e = openssl::bn::BigNum::new()?;
+    neg_e.mod_sub(&zero, &e, &order, &mut bn_ctx)?;
+    let mut big_r = openssl::ec::EcPoint::new(group)?;
+    big_r.mul_full(group, &s, &public_point, &neg_e, &mut bn_ctx)?;
+    if big_r.is_infinity(group) {
+        return Ok(false);
+    }
+    let (rx, r_even) = bip340_point_x(group, &big_r, &mut bn_ctx)?;
+    Ok(r_even && rx == signature[..32])
+}
+
 #[pyo3::prelude::pyfunction]
 fn curve_supported(py: pyo3::Python<'_>, py_curve: &pyo3::PyAny) -> bool {
     curve_from_py_curve(py, py_curve, false).is_ok()
 }
 
+// Returns the classes of all the curves in `_CURVE_TYPES` which this build of
+// OpenSSL supports, without duplicates for curves with several names.
+#[pyo3::prelude::pyfunction]
+fn supported_curves(py: pyo3::Python<'_>) -> CryptographyResult<Vec<&pyo3::types::PyType>> {
+    let mut curves: Vec<&pyo3::types::PyType> = vec![];
+    for py_curve in types::CURVE_TYPES
+        .get(py)?
+        .extract::<&pyo3::types::PyDict>()?
+        .values()
+    {
+        let curve_type = py_curve.get_type();
+        if curve_supported(py, py_curve) && !curves.iter().any(|c| c.is(curve_type)) {
+            curves.push(curve_type);
+        }
+    }
+    Ok(curves)
+}
+
 pub(crate) fn private_key_from_pkey(
     py: pyo3::Python<'_>,
     pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
@@ -145,6 +727,7 @@ pub(crate) fn private_key_from_pkey(
     Ok(ECPrivateKey {
         pkey: pkey.to_owned(),
         curve: curve.into(),
+        sign_ctx: pyo3::sync::GILOnceCell::new(),
     })
 }
 
@@ -158,6 +741,7 @@ pub(crate) fn public_key_from_pkey(
     Ok(ECPublicKey {
         pkey: pkey.to_owned(),
         curve: curve.into(),
+        verify_ctx: pyo3::sync::GILOnceCell::new(),
     })
 }
 #[pyo3::prelude::pyfunction]
@@ -168,12 +752,38 @@ fn generate_private_key(
 ) -> CryptographyResult<ECPrivateKey> {
     let _ = backend;
 
+    // Keys can also be generated from parsed ECParameters.
+    let curve = match curve.downcast::<pyo3::PyCell<EllipticCurveParameters>>() {
+        Ok(parameters) => parameters.get().curve.as_ref(py),
+        Err(_) => curve,
+    };
     let ossl_curve = curve_from_py_curve(py, curve, true)?;
     let key = openssl::ec::EcKey::generate(&ossl_curve)?;
 
     Ok(ECPrivateKey {
         pkey: openssl::pkey::PKey::from_ec_key(key)?,
         curve: py_curve_from_curve(py, &ossl_curve)?.into(),
+        sign_ctx: pyo3::sync::GILOnceCell::new(),
+    })
+}
+
+fn private_key_from_value(
+    curve: &openssl::ec::EcGroupRef,
+    py_curve: &pyo3::PyAny,
+    private_value: &openssl::bn::BigNumRef,
+) -> CryptographyResult<ECPrivateKey> {
+    let mut point = openssl::ec::EcPoint::new(curve)?;
+    let bn_ctx = openssl::bn::BigNumContext::new()?;
+    point.mul_generator(curve, private_value, &bn_ctx)?;
+    let ec = openssl::ec::EcKey::from_private_components(curve, private_value, &point)
+        .map_err(|_| pyo3::exceptions::PyValueError::new_err("Invalid EC key"))?;
+    check_key_infinity(&ec)?;
+    let pkey = openssl::pkey::PKey::from_ec_key(ec)?;
+
+    Ok(ECPrivateKey {
+        pkey,
+        curve: py_curve.into(),
+        sign_ctx: pyo3::sync::GILOnceCell::new(),
     })
 }
 
@@ -185,18 +795,83 @@ fn derive_private_key(
 ) -> CryptographyResult<ECPrivateKey> {
     let curve = curve_from_py_curve(py, py_curve, false)?;
     let private_value = utils::py_int_to_bn(py, py_private_value)?;
+    private_key_from_value(&curve, py_curve, &private_value)
+}
+
+// The private scalar is encoded as a big-endian integer the length of the
+// curve's order, as in SEC 1 section 2.3.7.
+#[pyo3::prelude::pyfunction]
+fn from_private_bytes(
+    py: pyo3::Python<'_>,
+    py_curve: &pyo3::PyAny,
+    data: CffiBuf<'_>,
+) -> CryptographyResult<ECPrivateKey> {
+    let curve = curve_from_py_curve(py, py_curve, false)?;
+    let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+    let mut order = openssl::bn::BigNum::new()?;
+    curve.order(&mut order, &mut bn_ctx)?;
+
+    let length = order.num_bytes() as usize;
+    if data.as_bytes().len() != length {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "An EC private key for this curve must be {length} bytes long"
+            )),
+        ));
+    }
+    let private_value = openssl::bn::BigNum::from_slice(data.as_bytes())?;
+    if private_value.num_bits() == 0 || private_value >= order {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("Invalid EC key"),
+        ));
+    }
+    private_key_from_value(&curve, py_curve, &private_value)
+}
+
+// Derives a private scalar from a seed by expanding it with HKDF-SHA512 to
+// `ceil((bits(n) + k) / 8)` bytes, where `k` is half the bit length of the
+// order, and reducing the result into `[1, n - 1]`. As with the hash_to_field
+// construction from RFC 9380 section 5.2, the extra `k` bits make the bias from
+// the reduction negligible.
+#[pyo3::prelude::pyfunction]
+fn derive_private_key_from_seed(
+    py: pyo3::Python<'_>,
+    seed: CffiBuf<'_>,
+    py_curve: &pyo3::PyAny,
+    info: &[u8],
+) -> CryptographyResult<ECPrivateKey> {
+    let curve = curve_from_py_curve(py, py_curve, false)?;
+    let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+    let mut order = openssl::bn::BigNum::new()?;
+    curve.order(&mut order, &mut bn_ctx)?;
+
+    let order_bits = order.num_bits() as usize;
+    let length = (order_bits + (order_bits + 1) / 2 + 7) / 8;
+    let okm = kdf::derive_hkdf(
+        openssl::hash::MessageDigest::sha512(),
+        b"",
+        info,
+        length,
+        seed.as_bytes(),
+    )?;
+
+    let one = openssl::bn::BigNum::from_u32(1)?;
+    let mut order_minus_one = openssl::bn::BigNum::new()?;
+    order_minus_one.checked_sub(&order, &one)?;
+    let okm = openssl::bn::BigNum::from_slice(&okm)?;
+    let mut private_value = openssl::bn::BigNum::new()?;
+    private_value.nnmod(&okm, &order_minus_one, &mut bn_ctx)?;
+    private_value.add_word(1)?;
 
     let mut point = openssl::ec::EcPoint::new(&curve)?;
-    let bn_ctx = openssl::bn::BigNumContext::new()?;
     point.mul_generator(&curve, &private_value, &bn_ctx)?;
-    let ec = openssl::ec::EcKey::from_private_components(&curve, &private_value, &point)
-        .map_err(|_| pyo3::exceptions::PyValueError::new_err("Invalid EC key"))?;
-    check_key_infinity(&ec)?;
+    let ec = openssl::ec::EcKey::from_private_components(&curve, &private_value, &point)?;
     let pkey = openssl::pkey::PKey::from_ec_key(ec)?;
 
     Ok(ECPrivateKey {
         pkey,
         curve: py_curve.into(),
+        sign_ctx: pyo3::sync::GILOnceCell::new(),
     })
 }
 
@@ -217,9 +892,82 @@ fn from_public_bytes(
     Ok(ECPublicKey {
         pkey,
         curve: py_curve.into(),
+        verify_ctx: pyo3::sync::GILOnceCell::new(),
     })
 }
 
+// Checks that `algorithm` is an `ECDH` instance, returning its `cofactor` and
+// `full_point` options.
+pub(crate) fn ecdh_options(
+    py: pyo3::Python<'_>,
+    algorithm: &pyo3::PyAny,
+) -> CryptographyResult<(bool, bool)> {
+    if !algorithm.is_instance(types::ECDH.get(py)?)? {
+        return Err(CryptographyError::from(
+            exceptions::UnsupportedAlgorithm::new_err((
+                "Unsupported EC exchange algorithm",
+                exceptions::Reasons::UNSUPPORTED_EXCHANGE_ALGORITHM,
+            )),
+        ));
+    }
+
+    let cofactor = algorithm
+        .getattr(pyo3::intern!(py, "cofactor"))?
+        .extract()?;
+    let full_point = algorithm
+        .getattr(pyo3::intern!(py, "full_point"))?
+        .extract()?;
+    Ok((cofactor, full_point))
+}
+
+// Performs an ECDH exchange. This does no Python work, so it can be called
+// without holding the GIL.
+pub(crate) fn ecdh_exchange(
+    private_key: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+    public_key: &openssl::pkey::PKeyRef<openssl::pkey::Public>,
+    cofactor: bool,
+    full_point: bool,
+) -> CryptographyResult<Vec<u8>> {
+    if cofactor || full_point {
+        return manual_exchange(
+            &private_key.ec_key().unwrap(),
+            &public_key.ec_key().unwrap(),
+            cofactor,
+            full_point,
+        );
+    }
+
+    let mut deriver = openssl::derive::Deriver::new(private_key)?;
+    // If `set_peer_ex` is available, we don't valid the key. This is
+    // because we already validated it sufficiently when we created the
+    // ECPublicKey object.
+    #[cfg(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER)]
+    deriver
+        .set_peer_ex(public_key, false)
+        .map_err(|_| pyo3::exceptions::PyValueError::new_err("Error computing shared key."))?;
+
+    #[cfg(not(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER))]
+    deriver
+        .set_peer(public_key)
+        .map_err(|_| pyo3::exceptions::PyValueError::new_err("Error computing shared key."))?;
+
+    Ok(deriver
+        .derive_to_vec()
+        .map_err(|_| pyo3::exceptions::PyValueError::new_err("Error computing shared key."))?)
+}
+
+impl ECPrivateKey {
+    fn compute_shared_key(
+        &self,
+        py: pyo3::Python<'_>,
+        algorithm: &pyo3::PyAny,
+        public_key: &ECPublicKey,
+    ) -> CryptographyResult<Vec<u8>> {
+        let (cofactor, full_point) = ecdh_options(py, algorithm)?;
+        py.allow_threads(|| ecdh_exchange(&self.pkey, &public_key.pkey, cofactor, full_point))
+    }
+}
+
 #[pyo3::prelude::pymethods]
 impl ECPrivateKey {
     #[getter]
@@ -233,36 +981,42 @@ impl ECPrivateKey {
         algorithm: &pyo3::PyAny,
         public_key: &ECPublicKey,
     ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
-        if !algorithm.is_instance(types::ECDH.get(py)?)? {
-            return Err(CryptographyError::from(
-                exceptions::UnsupportedAlgorithm::new_err((
-                    "Unsupported EC exchange algorithm",
-                    exceptions::Reasons::UNSUPPORTED_EXCHANGE_ALGORITHM,
-                )),
-            ));
-        }
+        let shared_key = self.compute_shared_key(py, algorithm, public_key)?;
+        Ok(pyo3::types::PyBytes::new(py, &shared_key))
+    }
 
-        let mut deriver = openssl::derive::Deriver::new(&self.pkey)?;
-        // If `set_peer_ex` is available, we don't valid the key. This is
-        // because we already validated it sufficiently when we created the
-        // ECPublicKey object.
-        #[cfg(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER)]
-        deriver
-            .set_peer_ex(&public_key.pkey, false)
-            .map_err(|_| pyo3::exceptions::PyValueError::new_err("Error computing shared key."))?;
-
-        #[cfg(not(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER))]
-        deriver
-            .set_peer(&public_key.pkey)
-            .map_err(|_| pyo3::exceptions::PyValueError::new_err("Error computing shared key."))?;
-
-        Ok(pyo3::types::PyBytes::new_with(py, deriver.len()?, |b| {
-            let n = deriver.derive(b).map_err(|_| {
-                pyo3::exceptions::PyValueError::new_err("Error computing shared key.")
-            })?;
-            assert_eq!(n, b.len());
-            Ok(())
-        })?)
+    fn exchange_and_derive<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        algorithm: &pyo3::PyAny,
+        public_key: &ECPublicKey,
+        kdf: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let shared_key = self.compute_shared_key(py, algorithm, public_key)?;
+        kdf::derive_with_kdf(py, kdf, &shared_key)
+    }
+
+    fn exchange_many<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        algorithm: &pyo3::PyAny,
+        peer_public_keys: Vec<pyo3::PyRef<'_, ECPublicKey>>,
+    ) -> CryptographyResult<Vec<&'p pyo3::types::PyBytes>> {
+        let (cofactor, full_point) = ecdh_options(py, algorithm)?;
+        let peers = peer_public_keys
+            .iter()
+            .map(|key| key.pkey.clone())
+            .collect::<Vec<_>>();
+        let shared_keys = py.allow_threads(|| {
+            peers
+                .iter()
+                .map(|peer| ecdh_exchange(&self.pkey, peer, cofactor, full_point))
+                .collect::<CryptographyResult<Vec<_>>>()
+        })?;
+        Ok(shared_keys
+            .iter()
+            .map(|shared_key| pyo3::types::PyBytes::new(py, shared_key))
+            .collect())
     }
 
     fn sign<'p>(
@@ -271,32 +1025,60 @@ impl ECPrivateKey {
         data: &[u8],
         algorithm: &pyo3::PyAny,
     ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
-        if !algorithm.is_instance(types::ECDSA.get(py)?)? {
-            return Err(CryptographyError::from(
-                exceptions::UnsupportedAlgorithm::new_err((
-                    "Unsupported elliptic curve signature algorithm",
-                    exceptions::Reasons::UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
-                )),
-            ));
+        if algorithm.is_instance(types::BIP340_SCHNORR.get(py)?)? {
+            let sig = bip340_sign(py, &self.pkey.ec_key().unwrap(), data, algorithm)?;
+            return Ok(pyo3::types::PyBytes::new(py, &sig));
         }
+        check_ecdsa_algorithm(py, algorithm)?;
 
-        let (data, _) = utils::calculate_digest_and_algorithm(
+        let (data, hash_algorithm) = utils::calculate_digest_and_algorithm(
             py,
             data,
             algorithm.getattr(pyo3::intern!(py, "algorithm"))?,
         )?;
-
-        let mut signer = openssl::pkey_ctx::PkeyCtx::new(&self.pkey)?;
-        signer.sign_init()?;
-        // TODO: This does an extra allocation and copy. This can't easily use
-        // `PyBytes::new_with` because the exact length of the signature isn't
-        // easily known a priori (if `r` or `s` has a leading 0, the signature
-        // will be a byte or two shorter than the maximum possible length).
-        let mut sig = vec![];
-        signer.sign_to_vec(data, &mut sig)?;
+        let sig = ecdsa_sign_digest(
+            py,
+            &self.pkey,
+            self.pkey.ec_key().unwrap().group(),
+            self.sign_ctx.get(py),
+            data,
+            hash_algorithm,
+            algorithm,
+        )?;
         Ok(pyo3::types::PyBytes::new(py, &sig))
     }
 
+    fn sign_init(
+        &self,
+        py: pyo3::Python<'_>,
+        signature_algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<ECDSASigningContext> {
+        check_ecdsa_algorithm(py, signature_algorithm)?;
+        let hash = hashes::Hash::new(
+            py,
+            signature_algorithm.getattr(pyo3::intern!(py, "algorithm"))?,
+            None,
+        )?;
+        Ok(ECDSASigningContext {
+            pkey: self.pkey.clone(),
+            algorithm: signature_algorithm.into(),
+            hash,
+        })
+    }
+
+    fn precompute(&self, py: pyo3::Python<'_>) -> CryptographyResult<()> {
+        self.sign_ctx.get_or_try_init(py, || {
+            let ec = self.pkey.ec_key()?;
+            let pkey = precomputed_pkey(openssl::ec::EcKey::private_key_from_der(
+                &ec.private_key_to_der()?,
+            )?)?;
+            let mut ctx = openssl::pkey_ctx::PkeyCtx::new(&pkey)?;
+            ctx.sign_init()?;
+            Ok::<_, CryptographyError>(std::sync::Mutex::new(ctx))
+        })?;
+        Ok(())
+    }
+
     fn public_key(&self, py: pyo3::Python<'_>) -> CryptographyResult<ECPublicKey> {
         let orig_ec = self.pkey.ec_key().unwrap();
         let ec = openssl::ec::EcKey::from_public_key(orig_ec.group(), orig_ec.public_key())?;
@@ -305,6 +1087,7 @@ impl ECPrivateKey {
         Ok(ECPublicKey {
             pkey,
             curve: self.curve.clone_ref(py),
+            verify_ctx: pyo3::sync::GILOnceCell::new(),
         })
     }
 
@@ -336,6 +1119,28 @@ impl ECPrivateKey {
         })
     }
 
+    fn private_bytes_raw<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let ec = self.pkey.ec_key().unwrap();
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let mut order = openssl::bn::BigNum::new()?;
+        ec.group().order(&mut order, &mut bn_ctx)?;
+        let raw_bytes = ec.private_key().to_vec_padded(order.num_bytes())?;
+        Ok(pyo3::types::PyBytes::new(py, &raw_bytes))
+    }
+
+    #[pyo3(signature = (kid=None))]
+    fn jwk_private_bytes<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        kid: Option<&str>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let jwk = jwk::private_jwk(&self.pkey, kid)?;
+        Ok(pyo3::types::PyBytes::new(py, jwk.as_bytes()))
+    }
+
     fn private_bytes<'p>(
         slf: &pyo3::PyCell<Self>,
         py: pyo3::Python<'p>,
@@ -354,6 +1159,22 @@ impl ECPrivateKey {
             false,
         )
     }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> CryptographyResult<bool> {
+        utils::pkey_private_eq(&self.pkey, &other.pkey)
+    }
+
+    fn __hash__(&self) -> CryptographyResult<u64> {
+        utils::pkey_hash(&self.pkey)
+    }
+
+    fn public_key_matches(
+        slf: &pyo3::PyCell<Self>,
+        py: pyo3::Python<'_>,
+        public_key: &pyo3::PyAny,
+    ) -> CryptographyResult<bool> {
+        utils::public_key_matches(py, slf, public_key)
+    }
 }
 
 #[pyo3::prelude::pymethods]
@@ -370,30 +1191,60 @@ impl ECPublicKey {
         data: &[u8],
         signature_algorithm: &pyo3::PyAny,
     ) -> CryptographyResult<()> {
-        if !signature_algorithm.is_instance(types::ECDSA.get(py)?)? {
-            return Err(CryptographyError::from(
-                exceptions::UnsupportedAlgorithm::new_err((
-                    "Unsupported elliptic curve signature algorithm",
-                    exceptions::Reasons::UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
-                )),
-            ));
+        if signature_algorithm.is_instance(types::BIP340_SCHNORR.get(py)?)? {
+            if !bip340_verify(&self.pkey.ec_key().unwrap(), signature, data)? {
+                return Err(CryptographyError::from(
+                    exceptions::InvalidSignature::new_err(()),
+                ));
+            }
+            return Ok(());
         }
+        check_ecdsa_algorithm(py, signature_algorithm)?;
 
         let (data, _) = utils::calculate_digest_and_algorithm(
             py,
             data,
             signature_algorithm.getattr(pyo3::intern!(py, "algorithm"))?,
         )?;
+        ecdsa_verify_digest(
+            py,
+            &self.pkey,
+            self.verify_ctx.get(py),
+            signature,
+            data,
+            signature_algorithm,
+        )
+    }
 
-        let mut verifier = openssl::pkey_ctx::PkeyCtx::new(&self.pkey)?;
-        verifier.verify_init()?;
-        let valid = verifier.verify(data, signature).unwrap_or(false);
-        if !valid {
-            return Err(CryptographyError::from(
-                exceptions::InvalidSignature::new_err(()),
-            ));
-        }
+    fn verify_init(
+        &self,
+        py: pyo3::Python<'_>,
+        signature: &[u8],
+        signature_algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<ECDSAVerificationContext> {
+        check_ecdsa_algorithm(py, signature_algorithm)?;
+        let hash = hashes::Hash::new(
+            py,
+            signature_algorithm.getattr(pyo3::intern!(py, "algorithm"))?,
+            None,
+        )?;
+        Ok(ECDSAVerificationContext {
+            pkey: self.pkey.clone(),
+            signature: signature.to_vec(),
+            algorithm: signature_algorithm.into(),
+            hash,
+        })
+    }
 
+    fn precompute(&self, py: pyo3::Python<'_>) -> CryptographyResult<()> {
+        self.verify_ctx.get_or_try_init(py, || {
+            let pkey = precomputed_pkey(openssl::ec::EcKey::public_key_from_der(
+                &self.pkey.public_key_to_der()?,
+            )?)?;
+            let mut ctx = openssl::pkey_ctx::PkeyCtx::new(&pkey)?;
+            ctx.verify_init()?;
+            Ok::<_, CryptographyError>(std::sync::Mutex::new(ctx))
+        })?;
         Ok(())
     }
 
@@ -418,6 +1269,16 @@ impl ECPublicKey {
         })
     }
 
+    #[pyo3(signature = (kid=None))]
+    fn jwk_public_bytes<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        kid: Option<&str>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let jwk = jwk::public_jwk(&self.pkey, kid)?;
+        Ok(pyo3::types::PyBytes::new(py, jwk.as_bytes()))
+    }
+
     fn public_bytes<'p>(
         slf: &pyo3::PyCell<Self>,
         py: pyo3::Python<'p>,
@@ -427,13 +1288,199 @@ impl ECPublicKey {
         utils::pkey_public_bytes(py, slf, &slf.borrow().pkey, encoding, format, true, false)
     }
 
+    fn fingerprint<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::PyAny> {
+        utils::pkey_fingerprint(py, &self.pkey, algorithm)
+    }
+
+    #[pyo3(signature = (algorithm=None))]
+    fn jwk_thumbprint<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        algorithm: Option<&pyo3::PyAny>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        jwk::public_jwk_thumbprint(py, &self.pkey, algorithm)
+    }
+
     fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> bool {
         self.pkey.public_eq(&other.pkey)
     }
 
+    fn __hash__(&self) -> CryptographyResult<u64> {
+        let mut hasher = DefaultHasher::new();
+        self.pkey.public_key_to_der()?.hash(&mut hasher);
+        Ok(hasher.finish())
+    }
+
     fn __copy__(slf: pyo3::PyRef<'_, Self>) -> pyo3::PyRef<'_, Self> {
         slf
     }
+
+    fn __reduce__<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+    ) -> CryptographyResult<(&'p pyo3::PyAny, (&'p pyo3::types::PyBytes,))> {
+        let der = self.pkey.public_key_to_der()?;
+        Ok((
+            types::EC_LOAD_DER_PUBLIC_KEY.get(py)?,
+            (pyo3::types::PyBytes::new(py, &der),),
+        ))
+    }
+}
+
+#[pyo3::prelude::pyclass(module = "cryptography.hazmat.bindings._rust.openssl.ec")]
+struct ECDSASigningContext {
+    pkey: openssl::pkey::PKey<openssl::pkey::Private>,
+    algorithm: pyo3::Py<pyo3::PyAny>,
+    hash: hashes::Hash,
+}
+
+#[pyo3::prelude::pymethods]
+impl ECDSASigningContext {
+    fn update(&mut self, data: CffiBuf<'_>) -> CryptographyResult<()> {
+        self.hash.update_bytes(data.as_bytes())
+    }
+
+    fn finalize<'p>(
+        &mut self,
+        py: pyo3::Python<'p>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let digest = self.hash.finalize(py)?;
+        let algorithm = self.algorithm.as_ref(py);
+        let sig = ecdsa_sign_digest(
+            py,
+            &self.pkey,
+            self.pkey.ec_key().unwrap().group(),
+            None,
+            digest.as_bytes(),
+            algorithm.getattr(pyo3::intern!(py, "algorithm"))?,
+            algorithm,
+        )?;
+        Ok(pyo3::types::PyBytes::new(py, &sig))
+    }
+}
+
+#[pyo3::prelude::pyclass(module = "cryptography.hazmat.bindings._rust.openssl.ec")]
+struct ECDSAVerificationContext {
+    pkey: openssl::pkey::PKey<openssl::pkey::Public>,
+    signature: Vec<u8>,
+    algorithm: pyo3::Py<pyo3::PyAny>,
+    hash: hashes::Hash,
+}
+
+#[pyo3::prelude::pymethods]
+impl ECDSAVerificationContext {
+    fn update(&mut self, data: CffiBuf<'_>) -> CryptographyResult<()> {
+        self.hash.update_bytes(data.as_bytes())
+    }
+
+    fn verify(&mut self, py: pyo3::Python<'_>) -> CryptographyResult<()> {
+        let digest = self.hash.finalize(py)?;
+        ecdsa_verify_digest(
+            py,
+            &self.pkey,
+            None,
+            &self.signature,
+            digest.as_bytes(),
+            self.algorithm.as_ref(py),
+        )
+    }
+}
+
+// The ECParameters from RFC 5480 section 2.1.1, as written by `openssl
+// ecparam`. Only the namedCurve form is supported.
+#[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.primitives.asymmetric.ec")]
+pub(crate) struct EllipticCurveParameters {
+    #[pyo3(get)]
+    curve: pyo3::Py<pyo3::PyAny>,
+}
+
+#[pyo3::prelude::pyfunction]
+pub(crate) fn from_der_parameters(
+    py: pyo3::Python<'_>,
+    data: &[u8],
+) -> CryptographyResult<EllipticCurveParameters> {
+    let params = asn1::parse_single::<EcParameters<'_>>(data)?;
+    let group = cryptography_key_parsing::ec::group_from_parameters(&params)?;
+    Ok(EllipticCurveParameters {
+        curve: py_curve_from_curve(py, &group)?.into(),
+    })
+}
+
+#[pyo3::prelude::pyfunction]
+fn from_pem_parameters(
+    py: pyo3::Python<'_>,
+    data: &[u8],
+) -> CryptographyResult<EllipticCurveParameters> {
+    let parsed = x509::find_in_pem(
+        data,
+        |p| p.tag() == "EC PARAMETERS",
+        "Valid PEM but no BEGIN EC PARAMETERS/END EC PARAMETERS delimiters. Are you sure this is EC parameters?",
+    )?;
+    from_der_parameters(py, parsed.contents())
+}
+
+#[pyo3::prelude::pymethods]
+impl EllipticCurveParameters {
+    #[new]
+    fn new(py: pyo3::Python<'_>, curve: &pyo3::PyAny) -> CryptographyResult<Self> {
+        // Checks that the curve is supported.
+        curve_from_py_curve(py, curve, false)?;
+        Ok(EllipticCurveParameters {
+            curve: curve.into(),
+        })
+    }
+
+    fn generate_private_key(&self, py: pyo3::Python<'_>) -> CryptographyResult<ECPrivateKey> {
+        generate_private_key(py, self.curve.as_ref(py), None)
+    }
+
+    fn parameter_bytes<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        encoding: &'p pyo3::PyAny,
+        format: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        if !format.is(types::PARAMETER_FORMAT_EC_PARAMETERS.get(py)?) {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "Only ECParameters serialization is supported",
+                ),
+            ));
+        }
+
+        let group = curve_from_py_curve(py, self.curve.as_ref(py), false)?;
+        let oid = cryptography_key_parsing::ec::curve_oid(&group).ok_or_else(|| {
+            exceptions::UnsupportedAlgorithm::new_err((
+                "This curve has no OID to serialize it with",
+                exceptions::Reasons::UNSUPPORTED_ELLIPTIC_CURVE,
+            ))
+        })?;
+        let data = asn1::write_single(&EcParameters::NamedCurve(oid))?;
+        encode_der_data(py, "EC PARAMETERS".to_string(), data, encoding)
+    }
+
+    fn __eq__(&self, py: pyo3::Python<'_>, other: pyo3::PyRef<'_, Self>) -> pyo3::PyResult<bool> {
+        self.curve
+            .as_ref(py)
+            .getattr(pyo3::intern!(py, "name"))?
+            .eq(other.curve.as_ref(py).getattr(pyo3::intern!(py, "name"))?)
+    }
+
+    fn __hash__(&self, py: pyo3::Python<'_>) -> pyo3::PyResult<isize> {
+        self.curve
+            .as_ref(py)
+            .getattr(pyo3::intern!(py, "name"))?
+            .hash()
+    }
+
+    fn __repr__(&self, py: pyo3::Python<'_>) -> pyo3::PyResult<String> {
+        let curve_name = self.curve.as_ref(py).getattr(pyo3::intern!(py, "name"))?;
+        Ok(format!("<EllipticCurveParameters(curve={curve_name})>"))
+    }
 }
 
 #[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.primitives.asymmetric.ec")]
@@ -529,6 +1576,7 @@ impl EllipticCurvePrivateNumbers {
         Ok(ECPrivateKey {
             pkey,
             curve: self.public_numbers.get().curve.clone_ref(py),
+            sign_ctx: pyo3::sync::GILOnceCell::new(),
         })
     }
 
@@ -593,6 +1641,7 @@ impl EllipticCurvePublicNumbers {
         Ok(ECPublicKey {
             pkey,
             curve: self.curve.clone_ref(py),
+            verify_ctx: pyo3::sync::GILOnceCell::new(),
         })
     }
 
@@ -635,6 +1684,27 @@ impl EllipticCurvePublicNumbers {
         Ok(hasher.finish())
     }
 
+    fn __reduce__<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+    ) -> (
+        &'p pyo3::types::PyType,
+        (
+            &'p pyo3::types::PyLong,
+            &'p pyo3::types::PyLong,
+            &'p pyo3::PyAny,
+        ),
+    ) {
+        (
+            py.get_type::<Self>(),
+            (
+                self.x.clone_ref(py).into_ref(py),
+                self.y.clone_ref(py).into_ref(py),
+                self.curve.clone_ref(py).into_ref(py),
+            ),
+        )
+    }
+
     fn __repr__(&self, py: pyo3::Python<'_>) -> pyo3::PyResult<String> {
         let x = self.x.as_ref(py);
         let y = self.y.as_ref(py);
@@ -645,17 +1715,271 @@ impl EllipticCurvePublicNumbers {
     }
 }
 
+#[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.primitives.asymmetric.ec")]
+struct ECPoint {
+    group: openssl::ec::EcGroup,
+    point: openssl::ec::EcPoint,
+    #[pyo3(get)]
+    curve: pyo3::Py<pyo3::PyAny>,
+}
+
+impl ECPoint {
+    fn new(
+        py: pyo3::Python<'_>,
+        py_curve: &pyo3::PyAny,
+        point: impl FnOnce(
+            &openssl::ec::EcGroupRef,
+            &mut openssl::bn::BigNumContextRef,
+        ) -> CryptographyResult<openssl::ec::EcPoint>,
+    ) -> CryptographyResult<ECPoint> {
+        let group = curve_from_py_curve(py, py_curve, false)?;
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let point = point(&group, &mut bn_ctx)?;
+        Ok(ECPoint {
+            group,
+            point,
+            curve: py_curve.into(),
+        })
+    }
+
+    fn check_same_curve(&self, other: &ECPoint) -> CryptographyResult<()> {
+        if self.group.curve_name() != other.group.curve_name() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("Points must be on the same curve"),
+            ));
+        }
+        Ok(())
+    }
+
+    fn coordinates(&self) -> CryptographyResult<(openssl::bn::BigNum, openssl::bn::BigNum)> {
+        if self.point.is_infinity(&self.group) {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("The point at infinity has no coordinates"),
+            ));
+        }
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let mut x = openssl::bn::BigNum::new()?;
+        let mut y = openssl::bn::BigNum::new()?;
+        self.point
+            .affine_coordinates(&self.group, &mut x, &mut y, &mut bn_ctx)?;
+        Ok((x, y))
+    }
+}
+
+#[pyo3::prelude::pymethods]
+impl ECPoint {
+    #[staticmethod]
+    fn generator(py: pyo3::Python<'_>, curve: &pyo3::PyAny) -> CryptographyResult<ECPoint> {
+        ECPoint::new(py, curve, |group, _| {
+            Ok(group.generator().to_owned(group)?)
+        })
+    }
+
+    #[staticmethod]
+    fn infinity(py: pyo3::Python<'_>, curve: &pyo3::PyAny) -> CryptographyResult<ECPoint> {
+        ECPoint::new(py, curve, |group, _| Ok(openssl::ec::EcPoint::new(group)?))
+    }
+
+    #[staticmethod]
+    fn from_encoded_point(
+        py: pyo3::Python<'_>,
+        curve: &pyo3::PyAny,
+        data: &[u8],
+    ) -> CryptographyResult<ECPoint> {
+        ECPoint::new(py, curve, |group, bn_ctx| {
+            openssl::ec::EcPoint::from_bytes(group, data, bn_ctx).map_err(|_| {
+                CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+                    "Invalid encoded point, or the point is not on the curve",
+                ))
+            })
+        })
+    }
+
+    #[staticmethod]
+    fn from_coordinates(
+        py: pyo3::Python<'_>,
+        curve: &pyo3::PyAny,
+        x: &pyo3::types::PyLong,
+        y: &pyo3::types::PyLong,
+    ) -> CryptographyResult<ECPoint> {
+        let numbers = EllipticCurvePublicNumbers {
+            x: x.into(),
+            y: y.into(),
+            curve: curve.into(),
+        };
+        ECPoint::new(py, curve, |group, _| {
+            let ec = public_key_from_numbers(py, &numbers, group)?;
+            Ok(ec.public_key().to_owned(group)?)
+        })
+    }
+
+    #[staticmethod]
+    fn from_public_key(
+        py: pyo3::Python<'_>,
+        key: &pyo3::PyCell<ECPublicKey>,
+    ) -> CryptographyResult<ECPoint> {
+        let key = key.get();
+        let ec = key.pkey.ec_key().unwrap();
+        ECPoint::new(py, key.curve.as_ref(py), |group, _| {
+            Ok(ec.public_key().to_owned(group)?)
+        })
+    }
+
+    #[getter]
+    fn is_infinity(&self) -> bool {
+        self.point.is_infinity(&self.group)
+    }
+
+    #[getter]
+    fn x<'p>(&self, py: pyo3::Python<'p>) -> CryptographyResult<&'p pyo3::PyAny> {
+        let (x, _) = self.coordinates()?;
+        utils::bn_to_py_int(py, &x)
+    }
+
+    #[getter]
+    fn y<'p>(&self, py: pyo3::Python<'p>) -> CryptographyResult<&'p pyo3::PyAny> {
+        let (_, y) = self.coordinates()?;
+        utils::bn_to_py_int(py, &y)
+    }
+
+    #[pyo3(signature = (compressed=false))]
+    fn encode<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        compressed: bool,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let form = if compressed {
+            openssl::ec::PointConversionForm::COMPRESSED
+        } else {
+            openssl::ec::PointConversionForm::UNCOMPRESSED
+        };
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let data = self.point.to_bytes(&self.group, form, &mut bn_ctx)?;
+        Ok(pyo3::types::PyBytes::new(py, &data))
+    }
+
+    fn public_key(&self, py: pyo3::Python<'_>) -> CryptographyResult<ECPublicKey> {
+        if self.point.is_infinity(&self.group) {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "The point at infinity is not a valid public key",
+                ),
+            ));
+        }
+        let ec = openssl::ec::EcKey::from_public_key(&self.group, &self.point)?;
+        Ok(ECPublicKey {
+            pkey: openssl::pkey::PKey::from_ec_key(ec)?,
+            curve: self.curve.clone_ref(py),
+            verify_ctx: pyo3::sync::GILOnceCell::new(),
+        })
+    }
+
+    fn __add__(
+        &self,
+        py: pyo3::Python<'_>,
+        other: pyo3::PyRef<'_, Self>,
+    ) -> CryptographyResult<ECPoint> {
+        self.check_same_curve(&other)?;
+        ECPoint::new(py, self.curve.as_ref(py), |group, bn_ctx| {
+            let mut result = openssl::ec::EcPoint::new(group)?;
+            result.add(group, &self.point, &other.point, bn_ctx)?;
+            Ok(result)
+        })
+    }
+
+    fn __sub__(
+        &self,
+        py: pyo3::Python<'_>,
+        other: pyo3::PyRef<'_, Self>,
+    ) -> CryptographyResult<ECPoint> {
+        self.check_same_curve(&other)?;
+        ECPoint::new(py, self.curve.as_ref(py), |group, bn_ctx| {
+            let mut negated = other.point.to_owned(group)?;
+            negated.invert(group, bn_ctx)?;
+            let mut result = openssl::ec::EcPoint::new(group)?;
+            result.add(group, &self.point, &negated, bn_ctx)?;
+            Ok(result)
+        })
+    }
+
+    fn __neg__(&self, py: pyo3::Python<'_>) -> CryptographyResult<ECPoint> {
+        ECPoint::new(py, self.curve.as_ref(py), |group, bn_ctx| {
+            let mut result = self.point.to_owned(group)?;
+            result.invert(group, bn_ctx)?;
+            Ok(result)
+        })
+    }
+
+    fn __mul__(
+        &self,
+        py: pyo3::Python<'_>,
+        scalar: &pyo3::types::PyLong,
+    ) -> CryptographyResult<ECPoint> {
+        ECPoint::new(py, self.curve.as_ref(py), |group, bn_ctx| {
+            // Negative scalars are handled by reducing them modulo the
+            // group order.
+            let mut order = openssl::bn::BigNum::new()?;
+            group.order(&mut order, bn_ctx)?;
+            let py_order = utils::bn_to_py_int(py, &order)?;
+            let scalar = utils::py_int_to_bn(
+                py,
+                scalar.call_method1(pyo3::intern!(py, "__mod__"), (py_order,))?,
+            )?;
+            let mut result = openssl::ec::EcPoint::new(group)?;
+            result.mul(group, &self.point, &scalar, bn_ctx)?;
+            Ok(result)
+        })
+    }
+
+    fn __rmul__(
+        &self,
+        py: pyo3::Python<'_>,
+        scalar: &pyo3::types::PyLong,
+    ) -> CryptographyResult<ECPoint> {
+        self.__mul__(py, scalar)
+    }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> CryptographyResult<bool> {
+        if self.group.curve_name() != other.group.curve_name() {
+            return Ok(false);
+        }
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        Ok(self.point.eq(&self.group, &other.point, &mut bn_ctx)?)
+    }
+
+    fn __repr__(&self, py: pyo3::Python<'_>) -> CryptographyResult<String> {
+        let curve_name = self.curve.as_ref(py).getattr(pyo3::intern!(py, "name"))?;
+        if self.point.is_infinity(&self.group) {
+            return Ok(format!("<ECPoint(curve={curve_name}, infinity)>"));
+        }
+        let x = self.x(py)?;
+        let y = self.y(py)?;
+        Ok(format!("<ECPoint(curve={curve_name}, x={x}, y={y})>"))
+    }
+}
+
 pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
     let m = pyo3::prelude::PyModule::new(py, "ec")?;
     m.add_function(pyo3::wrap_pyfunction!(curve_supported, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(supported_curves, m)?)?;
     m.add_function(pyo3::wrap_pyfunction!(generate_private_key, m)?)?;
     m.add_function(pyo3::wrap_pyfunction!(derive_private_key, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(derive_private_key_from_seed, m)?)?;
     m.add_function(pyo3::wrap_pyfunction!(from_public_bytes, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(from_private_bytes, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(normalize_signature, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(recover_public_key, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(from_der_parameters, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(from_pem_parameters, m)?)?;
 
     m.add_class::<ECPrivateKey>()?;
     m.add_class::<ECPublicKey>()?;
+    m.add_class::<ECDSASigningContext>()?;
+    m.add_class::<ECDSAVerificationContext>()?;
     m.add_class::<EllipticCurvePrivateNumbers>()?;
     m.add_class::<EllipticCurvePublicNumbers>()?;
+    m.add_class::<ECPoint>()?;
+    m.add_class::<EllipticCurveParameters>()?;
 
     Ok(m)
 }
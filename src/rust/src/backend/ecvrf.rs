@@ -0,0 +1,1125 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Elliptic curve verifiable random functions, as specified by RFC 9381. The
+//! ECVRF-P256-SHA256-TAI and ECVRF-EDWARDS25519-SHA512-TAI suites are
+//! supported, using ordinary P-256 and Ed25519 keys.
+//!
+//! OpenSSL doesn't expose arithmetic on edwards25519 points, so that's done
+//! here with `BigNum`s in extended coordinates. Scalar multiplication, which
+//! is where secret scalars are used, instead uses fixed width field elements
+//! so that its running time doesn't depend on the scalar.
+
+use crate::backend::{ec, ed25519};
+use crate::buf::CffiBuf;
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::{exceptions, types};
+
+// The domain separators for the three hashes in RFC 9381.
+const ENCODE_TO_CURVE_DOMAIN: u8 = 0x01;
+const CHALLENGE_DOMAIN: u8 = 0x02;
+const PROOF_TO_HASH_DOMAIN: u8 = 0x03;
+
+// Both suites truncate challenges to 128 bits, and have 256-bit group orders.
+const CHALLENGE_LENGTH: usize = 16;
+const SCALAR_LENGTH: usize = 32;
+
+// The parts of an ECVRF ciphersuite that depend on the curve. The integer and
+// point encodings match the curve's signature scheme.
+pub(crate) trait Suite {
+    type Point;
+
+    const SUITE_STRING: u8;
+    const POINT_LENGTH: usize;
+    const LITTLE_ENDIAN: bool;
+
+    fn digest() -> openssl::hash::MessageDigest;
+
+    // The string that ECVRF_encode_to_curve_try_and_increment decodes as a
+    // candidate point, given one of its hash outputs.
+    fn hash_to_point_string(hash: &[u8]) -> Vec<u8>;
+
+    fn order(&self) -> &openssl::bn::BigNumRef;
+
+    fn point_to_string(&mut self, point: &Self::Point) -> CryptographyResult<Vec<u8>>;
+
+    fn string_to_point(&mut self, data: &[u8]) -> CryptographyResult<Option<Self::Point>>;
+
+    fn clear_cofactor(&mut self, point: &Self::Point) -> CryptographyResult<Self::Point>;
+
+    fn is_identity(&mut self, point: &Self::Point) -> bool;
+
+    fn mul_generator(&mut self, k: &openssl::bn::BigNumRef) -> CryptographyResult<Self::Point>;
+
+    fn mul(
+        &mut self,
+        point: &Self::Point,
+        k: &openssl::bn::BigNumRef,
+    ) -> CryptographyResult<Self::Point>;
+
+    fn sub(&mut self, a: &Self::Point, b: &Self::Point) -> CryptographyResult<Self::Point>;
+
+    fn nonce(
+        &mut self,
+        nonce_key: &[u8],
+        h_string: &[u8],
+    ) -> CryptographyResult<openssl::bn::BigNum>;
+}
+
+// The secret scalar, and the key material that nonces are derived from.
+struct SecretKey {
+    x: openssl::bn::BigNum,
+    nonce_key: Vec<u8>,
+}
+
+struct P256 {
+    group: openssl::ec::EcGroup,
+    order: openssl::bn::BigNum,
+    bn_ctx: openssl::bn::BigNumContext,
+}
+
+impl P256 {
+    fn new() -> CryptographyResult<P256> {
+        let group = openssl::ec::EcGroup::from_curve_name(openssl::nid::Nid::X9_62_PRIME256V1)?;
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let mut order = openssl::bn::BigNum::new()?;
+        group.order(&mut order, &mut bn_ctx)?;
+        Ok(P256 {
+            group,
+            order,
+            bn_ctx,
+        })
+    }
+}
+
+fn hmac_sha256(key: &[u8], parts: &[&[u8]]) -> CryptographyResult<Vec<u8>> {
+    let mut h = cryptography_openssl::hmac::Hmac::new(key, openssl::hash::MessageDigest::sha256())?;
+    for part in parts {
+        h.update(part)?;
+    }
+    Ok(h.finish()?.to_vec())
+}
+
+impl Suite for P256 {
+    type Point = openssl::ec::EcPoint;
+
+    const SUITE_STRING: u8 = 0x01;
+    const POINT_LENGTH: usize = 33;
+    const LITTLE_ENDIAN: bool = false;
+
+    fn digest() -> openssl::hash::MessageDigest {
+        openssl::hash::MessageDigest::sha256()
+    }
+
+    fn hash_to_point_string(hash: &[u8]) -> Vec<u8> {
+        // The hash is the x coordinate of a point with an even y coordinate.
+        let mut data = vec![0x02];
+        data.extend_from_slice(hash);
+        data
+    }
+
+    fn order(&self) -> &openssl::bn::BigNumRef {
+        &self.order
+    }
+
+    fn point_to_string(&mut self, point: &Self::Point) -> CryptographyResult<Vec<u8>> {
+        Ok(point.to_bytes(
+            &self.group,
+            openssl::ec::PointConversionForm::COMPRESSED,
+            &mut self.bn_ctx,
+        )?)
+    }
+
+    fn string_to_point(&mut self, data: &[u8]) -> CryptographyResult<Option<Self::Point>> {
+        if data.len() != Self::POINT_LENGTH {
+            return Ok(None);
+        }
+        Ok(openssl::ec::EcPoint::from_bytes(&self.group, data, &mut self.bn_ctx).ok())
+    }
+
+    fn clear_cofactor(&mut self, point: &Self::Point) -> CryptographyResult<Self::Point> {
+        Ok(openssl::ec::EcPointRef::to_owned(point, &self.group)?)
+    }
+
+    fn is_identity(&mut self, point: &Self::Point) -> bool {
+        point.is_infinity(&self.group)
+    }
+
+    fn mul_generator(&mut self, k: &openssl::bn::BigNumRef) -> CryptographyResult<Self::Point> {
+        let mut result = openssl::ec::EcPoint::new(&self.group)?;
+        result.mul_generator(&self.group, k, &self.bn_ctx)?;
+        Ok(result)
+    }
+
+    fn mul(
+        &mut self,
+        point: &Self::Point,
+        k: &openssl::bn::BigNumRef,
+    ) -> CryptographyResult<Self::Point> {
+        let mut result = openssl::ec::EcPoint::new(&self.group)?;
+        result.mul(&self.group, point, k, &self.bn_ctx)?;
+        Ok(result)
+    }
+
+    fn sub(&mut self, a: &Self::Point, b: &Self::Point) -> CryptographyResult<Self::Point> {
+        let mut neg_b = openssl::ec::EcPointRef::to_owned(b, &self.group)?;
+        neg_b.invert(&self.group, &self.bn_ctx)?;
+        let mut result = openssl::ec::EcPoint::new(&self.group)?;
+        result.add(&self.group, a, &neg_b, &mut self.bn_ctx)?;
+        Ok(result)
+    }
+
+    // RFC 6979 section 3.2, which is simpler here because the group order and
+    // SHA-256 are both 256 bits long.
+    fn nonce(
+        &mut self,
+        nonce_key: &[u8],
+        h_string: &[u8],
+    ) -> CryptographyResult<openssl::bn::BigNum> {
+        let h1 = openssl::bn::BigNum::from_slice(&openssl::sha::sha256(h_string))?;
+        let mut reduced_h1 = openssl::bn::BigNum::new()?;
+        reduced_h1.nnmod(&h1, &self.order, &mut self.bn_ctx)?;
+        let h1_octets = reduced_h1.to_vec_padded(SCALAR_LENGTH as i32)?;
+
+        let mut v = vec![0x01; 32];
+        let mut k = hmac_sha256(&[0x00; 32], &[&v, &[0x00], nonce_key, &h1_octets])?;
+        v = hmac_sha256(&k, &[&v])?;
+        k = hmac_sha256(&k, &[&v, &[0x01], nonce_key, &h1_octets])?;
+        v = hmac_sha256(&k, &[&v])?;
+        loop {
+            v = hmac_sha256(&k, &[&v])?;
+            let candidate = openssl::bn::BigNum::from_slice(&v)?;
+            if candidate.num_bits() != 0 && candidate < self.order {
+                return Ok(candidate);
+            }
+            k = hmac_sha256(&k, &[&v, &[0x00]])?;
+            v = hmac_sha256(&k, &[&v])?;
+        }
+    }
+}
+
+// Arithmetic modulo p = 2^255 - 19.
+pub(crate) struct Field {
+    pub(crate) p: openssl::bn::BigNum,
+    pub(crate) bn_ctx: openssl::bn::BigNumContext,
+}
+
+impl Field {
+    pub(crate) fn add(
+        &mut self,
+        a: &openssl::bn::BigNumRef,
+        b: &openssl::bn::BigNumRef,
+    ) -> CryptographyResult<openssl::bn::BigNum> {
+        let mut result = openssl::bn::BigNum::new()?;
+        result.mod_add(a, b, &self.p, &mut self.bn_ctx)?;
+        Ok(result)
+    }
+
+    pub(crate) fn sub(
+        &mut self,
+        a: &openssl::bn::BigNumRef,
+        b: &openssl::bn::BigNumRef,
+    ) -> CryptographyResult<openssl::bn::BigNum> {
+        let mut result = openssl::bn::BigNum::new()?;
+        result.mod_sub(a, b, &self.p, &mut self.bn_ctx)?;
+        Ok(result)
+    }
+
+    pub(crate) fn mul(
+        &mut self,
+        a: &openssl::bn::BigNumRef,
+        b: &openssl::bn::BigNumRef,
+    ) -> CryptographyResult<openssl::bn::BigNum> {
+        let mut result = openssl::bn::BigNum::new()?;
+        result.mod_mul(a, b, &self.p, &mut self.bn_ctx)?;
+        Ok(result)
+    }
+
+    pub(crate) fn pow(
+        &mut self,
+        a: &openssl::bn::BigNumRef,
+        e: &openssl::bn::BigNumRef,
+    ) -> CryptographyResult<openssl::bn::BigNum> {
+        let mut result = openssl::bn::BigNum::new()?;
+        result.mod_exp(a, e, &self.p, &mut self.bn_ctx)?;
+        Ok(result)
+    }
+
+    pub(crate) fn inverse(
+        &mut self,
+        a: &openssl::bn::BigNumRef,
+    ) -> CryptographyResult<openssl::bn::BigNum> {
+        let mut result = openssl::bn::BigNum::new()?;
+        result.mod_inverse(a, &self.p, &mut self.bn_ctx)?;
+        Ok(result)
+    }
+}
+
+// p = 2^255 - 19 as little endian limbs, and the Montgomery constants for
+// R = 2^256.
+const FE_MODULUS: [u64; 4] = [
+    0xffff_ffff_ffff_ffed,
+    0xffff_ffff_ffff_ffff,
+    0xffff_ffff_ffff_ffff,
+    0x7fff_ffff_ffff_ffff,
+];
+const FE_R2: [u64; 4] = [0x5a4, 0, 0, 0];
+// -p^-1 mod 2^64
+const FE_INV: u64 = 0x86bc_a1af_286b_ca1b;
+
+fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = (a as u128) + (b as u128) + (carry as u128);
+    (t as u64, (t >> 64) as u64)
+}
+
+// `borrow` is either 0 or all ones, and so is the returned borrow.
+fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let t = (a as u128).wrapping_sub((b as u128) + ((borrow >> 63) as u128));
+    (t as u64, (t >> 64) as u64)
+}
+
+fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let t = (a as u128) + (b as u128) * (c as u128) + (carry as u128);
+    (t as u64, (t >> 64) as u64)
+}
+
+// An element of the field modulo p as 4 64-bit limbs in Montgomery form.
+// None of its operations branch on, or index memory by, its value.
+#[derive(Clone, Copy)]
+struct Fe([u64; 4]);
+
+impl Fe {
+    const ZERO: Fe = Fe([0; 4]);
+    // R mod p
+    const ONE: Fe = Fe([38, 0, 0, 0]);
+
+    fn from_bn(value: &openssl::bn::BigNumRef) -> CryptographyResult<Fe> {
+        let data = value.to_vec_padded(32)?;
+        let mut limbs = [0u64; 4];
+        for (limb, chunk) in limbs.iter_mut().zip(data.rchunks(8)) {
+            *limb = u64::from_be_bytes(chunk.try_into().unwrap());
+        }
+        Ok(Fe(limbs).mul(&Fe(FE_R2)))
+    }
+
+    fn to_bn(self) -> CryptographyResult<openssl::bn::BigNum> {
+        let limbs = self.mul(&Fe([1, 0, 0, 0])).0;
+        let data = limbs
+            .iter()
+            .rev()
+            .flat_map(|limb| limb.to_be_bytes())
+            .collect::<Vec<_>>();
+        Ok(openssl::bn::BigNum::from_slice(&data)?)
+    }
+
+    // Returns `a - p` if `a >= p`, and `a` otherwise.
+    fn reduce(a: [u64; 4]) -> Fe {
+        let mut r = [0u64; 4];
+        let mut borrow = 0;
+        for (i, limb) in r.iter_mut().enumerate() {
+            (*limb, borrow) = sbb(a[i], FE_MODULUS[i], borrow);
+        }
+        for (i, limb) in r.iter_mut().enumerate() {
+            *limb = (a[i] & borrow) | (*limb & !borrow);
+        }
+        Fe(r)
+    }
+
+    fn add(&self, other: &Fe) -> Fe {
+        // Both are less than p < 2^255, so the sum doesn't overflow.
+        let mut r = [0u64; 4];
+        let mut carry = 0;
+        for (i, limb) in r.iter_mut().enumerate() {
+            (*limb, carry) = adc(self.0[i], other.0[i], carry);
+        }
+        Fe::reduce(r)
+    }
+
+    fn sub(&self, other: &Fe) -> Fe {
+        let mut r = [0u64; 4];
+        let mut borrow = 0;
+        for (i, limb) in r.iter_mut().enumerate() {
+            (*limb, borrow) = sbb(self.0[i], other.0[i], borrow);
+        }
+        // Adds p back if the subtraction underflowed.
+        let mut carry = 0;
+        for (i, limb) in r.iter_mut().enumerate() {
+            (*limb, carry) = adc(*limb, FE_MODULUS[i] & borrow, carry);
+        }
+        Fe(r)
+    }
+
+    // Computes a * b * R^-1 mod p, for a * b < p * R.
+    fn mul(&self, other: &Fe) -> Fe {
+        let (a, b) = (&self.0, &other.0);
+        let mut t = [0u64; 5];
+        for &b_i in b {
+            let mut carry = 0;
+            for j in 0..4 {
+                (t[j], carry) = mac(t[j], a[j], b_i, carry);
+            }
+            let (t4, t5) = adc(t[4], carry, 0);
+
+            let m = t[0].wrapping_mul(FE_INV);
+            let (_, mut carry) = mac(t[0], m, FE_MODULUS[0], 0);
+            for j in 1..4 {
+                (t[j - 1], carry) = mac(t[j], m, FE_MODULUS[j], carry);
+            }
+            let (lo, hi) = adc(t4, carry, 0);
+            t[3] = lo;
+            t[4] = t5 + hi;
+        }
+        Fe::reduce([t[0], t[1], t[2], t[3]])
+    }
+
+    // Returns `a` if `choice` is 0 and `b` if it's 1.
+    fn select(a: &Fe, b: &Fe, choice: u64) -> Fe {
+        let mask = 0u64.wrapping_sub(choice);
+        let mut r = [0u64; 4];
+        for (i, limb) in r.iter_mut().enumerate() {
+            *limb = (a.0[i] & !mask) | (b.0[i] & mask);
+        }
+        Fe(r)
+    }
+}
+
+// An `EdwardsPoint` with `Fe` coordinates.
+#[derive(Clone, Copy)]
+struct FePoint {
+    x: Fe,
+    y: Fe,
+    z: Fe,
+    t: Fe,
+}
+
+impl FePoint {
+    const IDENTITY: FePoint = FePoint {
+        x: Fe::ZERO,
+        y: Fe::ONE,
+        z: Fe::ONE,
+        t: Fe::ZERO,
+    };
+
+    // The same unified addition formula as `Edwards25519::add`. It's complete
+    // on edwards25519, so it needs no special cases.
+    fn add(&self, other: &FePoint, d2: &Fe) -> FePoint {
+        let a = self.y.sub(&self.x).mul(&other.y.sub(&other.x));
+        let b = self.y.add(&self.x).mul(&other.y.add(&other.x));
+        let c = self.t.mul(&other.t).mul(d2);
+        let zz = self.z.mul(&other.z);
+        let d = zz.add(&zz);
+        let e = b.sub(&a);
+        let f = d.sub(&c);
+        let g = d.add(&c);
+        let h = b.add(&a);
+        FePoint {
+            x: e.mul(&f),
+            y: g.mul(&h),
+            z: f.mul(&g),
+            t: e.mul(&h),
+        }
+    }
+
+    fn select(a: &FePoint, b: &FePoint, choice: u64) -> FePoint {
+        FePoint {
+            x: Fe::select(&a.x, &b.x, choice),
+            y: Fe::select(&a.y, &b.y, choice),
+            z: Fe::select(&a.z, &b.z, choice),
+            t: Fe::select(&a.t, &b.t, choice),
+        }
+    }
+}
+
+// A point in extended coordinates (X : Y : Z : T), where x = X / Z,
+// y = Y / Z and x * y = T / Z.
+pub(crate) struct EdwardsPoint {
+    pub(crate) x: openssl::bn::BigNum,
+    pub(crate) y: openssl::bn::BigNum,
+    pub(crate) z: openssl::bn::BigNum,
+    pub(crate) t: openssl::bn::BigNum,
+}
+
+impl EdwardsPoint {
+    pub(crate) fn try_clone(&self) -> CryptographyResult<EdwardsPoint> {
+        Ok(EdwardsPoint {
+            x: self.x.to_owned()?,
+            y: self.y.to_owned()?,
+            z: self.z.to_owned()?,
+            t: self.t.to_owned()?,
+        })
+    }
+}
+
+pub(crate) struct Edwards25519 {
+    pub(crate) field: Field,
+    pub(crate) d: openssl::bn::BigNum,
+    d2: openssl::bn::BigNum,
+    pub(crate) sqrt_m1: openssl::bn::BigNum,
+    sqrt_exponent: openssl::bn::BigNum,
+    pub(crate) order: openssl::bn::BigNum,
+    pub(crate) generator: EdwardsPoint,
+}
+
+impl Edwards25519 {
+    pub(crate) fn new() -> CryptographyResult<Edwards25519> {
+        let mut field = Field {
+            p: ed25519::field_prime()?,
+            bn_ctx: openssl::bn::BigNumContext::new()?,
+        };
+        let generator_x = openssl::bn::BigNum::from_hex_str(
+            "216936d3cd6e53fec0a4e231fdd6dc5c692cc7609525a7b2c9562d608f25d51a",
+        )?;
+        let generator_y = openssl::bn::BigNum::from_hex_str(
+            "6666666666666666666666666666666666666666666666666666666666666658",
+        )?;
+        let generator = EdwardsPoint {
+            t: field.mul(&generator_x, &generator_y)?,
+            x: generator_x,
+            y: generator_y,
+            z: openssl::bn::BigNum::from_u32(1)?,
+        };
+        Ok(Edwards25519 {
+            field,
+            // d = -121665 / 121666
+            d: openssl::bn::BigNum::from_hex_str(
+                "52036cee2b6ffe738cc740797779e89800700a4d4141d8ab75eb4dca135978a3",
+            )?,
+            d2: openssl::bn::BigNum::from_hex_str(
+                "2406d9dc56dffce7198e80f2eef3d13000e0149a8283b156ebd69b9426b2f159",
+            )?,
+            sqrt_m1: openssl::bn::BigNum::from_hex_str(
+                "2b8324804fc1df0b2b4d00993dfbd7a72f431806ad2fe478c4ee1b274a0ea0b0",
+            )?,
+            // (p + 3) / 8
+            sqrt_exponent: openssl::bn::BigNum::from_hex_str(
+                "0ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe",
+            )?,
+            order: openssl::bn::BigNum::from_hex_str(
+                "1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ed",
+            )?,
+            generator,
+        })
+    }
+
+    pub(crate) fn identity() -> CryptographyResult<EdwardsPoint> {
+        Ok(EdwardsPoint {
+            x: openssl::bn::BigNum::new()?,
+            y: openssl::bn::BigNum::from_u32(1)?,
+            z: openssl::bn::BigNum::from_u32(1)?,
+            t: openssl::bn::BigNum::new()?,
+        })
+    }
+
+    // The unified addition formula from RFC 8032 section 5.1.4, which is
+    // also used for doubling.
+    pub(crate) fn add(
+        &mut self,
+        p: &EdwardsPoint,
+        q: &EdwardsPoint,
+    ) -> CryptographyResult<EdwardsPoint> {
+        let f = &mut self.field;
+        let p_diff = f.sub(&p.y, &p.x)?;
+        let q_diff = f.sub(&q.y, &q.x)?;
+        let a = f.mul(&p_diff, &q_diff)?;
+        let p_sum = f.add(&p.y, &p.x)?;
+        let q_sum = f.add(&q.y, &q.x)?;
+        let b = f.mul(&p_sum, &q_sum)?;
+        let tt = f.mul(&p.t, &q.t)?;
+        let c = f.mul(&tt, &self.d2)?;
+        let zz = f.mul(&p.z, &q.z)?;
+        let d = f.add(&zz, &zz)?;
+        let e = f.sub(&b, &a)?;
+        let ff = f.sub(&d, &c)?;
+        let g = f.add(&d, &c)?;
+        let h = f.add(&b, &a)?;
+        Ok(EdwardsPoint {
+            x: f.mul(&e, &ff)?,
+            y: f.mul(&g, &h)?,
+            z: f.mul(&ff, &g)?,
+            t: f.mul(&e, &h)?,
+        })
+    }
+
+    // Multiplies by a scalar below 2^256 with a Montgomery ladder over all 256
+    // bits, whatever the scalar's length. The only scalar dependent
+    // operations are the conditional swaps.
+    pub(crate) fn scalar_mul(
+        &mut self,
+        point: &EdwardsPoint,
+        k: &openssl::bn::BigNumRef,
+    ) -> CryptographyResult<EdwardsPoint> {
+        let d2 = Fe::from_bn(&self.d2)?;
+        let mut r0 = FePoint::IDENTITY;
+        let mut r1 = FePoint {
+            x: Fe::from_bn(&point.x)?,
+            y: Fe::from_bn(&point.y)?,
+            z: Fe::from_bn(&point.z)?,
+            t: Fe::from_bn(&point.t)?,
+        };
+        for byte in k.to_vec_padded(32)? {
+            for i in (0..8).rev() {
+                let bit = u64::from((byte >> i) & 1);
+                (r0, r1) = (
+                    FePoint::select(&r0, &r1, bit),
+                    FePoint::select(&r1, &r0, bit),
+                );
+                r1 = r0.add(&r1, &d2);
+                r0 = r0.add(&r0, &d2);
+                (r0, r1) = (
+                    FePoint::select(&r0, &r1, bit),
+                    FePoint::select(&r1, &r0, bit),
+                );
+            }
+        }
+        Ok(EdwardsPoint {
+            x: r0.x.to_bn()?,
+            y: r0.y.to_bn()?,
+            z: r0.z.to_bn()?,
+            t: r0.t.to_bn()?,
+        })
+    }
+
+    // Decodes a 32 byte point as described in RFC 8032 section 5.1.3. Unless
+    // `canonical` is set, y >= p is reduced and x = 0 is accepted with either
+    // sign bit, as ZIP 215 requires.
+    pub(crate) fn decode_point(
+        &mut self,
+        data: &[u8],
+        canonical: bool,
+    ) -> CryptographyResult<Option<EdwardsPoint>> {
+        let mut y = ed25519::decode_field_element(data)?;
+        if y >= self.field.p {
+            if canonical {
+                return Ok(None);
+            }
+            // y < 2^255 < 2 * p, so one subtraction reduces it.
+            y = &y - &self.field.p;
+        }
+        let x_is_odd = data[31] & 0x80 != 0;
+
+        // x^2 = (y^2 - 1) / (d * y^2 + 1)
+        let one = openssl::bn::BigNum::from_u32(1)?;
+        let yy = self.field.mul(&y, &y)?;
+        let u = self.field.sub(&yy, &one)?;
+        let dyy = self.field.mul(&self.d, &yy)?;
+        let v = self.field.add(&dyy, &one)?;
+        let v_inverse = self.field.inverse(&v)?;
+        let xx = self.field.mul(&u, &v_inverse)?;
+
+        let mut x = self.field.pow(&xx, &self.sqrt_exponent)?;
+        if self.field.mul(&x, &x)? != xx {
+            x = self.field.mul(&x, &self.sqrt_m1)?;
+            if self.field.mul(&x, &x)? != xx {
+                return Ok(None);
+            }
+        }
+        if x.num_bits() == 0 && x_is_odd && canonical {
+            return Ok(None);
+        }
+        if x.is_bit_set(0) != x_is_odd {
+            let zero = openssl::bn::BigNum::new()?;
+            x = self.field.sub(&zero, &x)?;
+        }
+        Ok(Some(EdwardsPoint {
+            t: self.field.mul(&x, &y)?,
+            x,
+            y,
+            z: one,
+        }))
+    }
+}
+
+impl Suite for Edwards25519 {
+    type Point = EdwardsPoint;
+
+    const SUITE_STRING: u8 = 0x03;
+    const POINT_LENGTH: usize = 32;
+    const LITTLE_ENDIAN: bool = true;
+
+    fn digest() -> openssl::hash::MessageDigest {
+        openssl::hash::MessageDigest::sha512()
+    }
+
+    fn hash_to_point_string(hash: &[u8]) -> Vec<u8> {
+        hash[..Self::POINT_LENGTH].to_vec()
+    }
+
+    fn order(&self) -> &openssl::bn::BigNumRef {
+        &self.order
+    }
+
+    fn point_to_string(&mut self, point: &Self::Point) -> CryptographyResult<Vec<u8>> {
+        let z_inverse = self.field.inverse(&point.z)?;
+        let x = self.field.mul(&point.x, &z_inverse)?;
+        let y = self.field.mul(&point.y, &z_inverse)?;
+        let mut data = ed25519::encode_field_element(&y)?;
+        if x.is_bit_set(0) {
+            data[31] |= 0x80;
+        }
+        Ok(data)
+    }
+
+    fn string_to_point(&mut self, data: &[u8]) -> CryptographyResult<Option<Self::Point>> {
+        if data.len() != Self::POINT_LENGTH {
+            return Ok(None);
+        }
+        self.decode_point(data, true)
+    }
+
+    fn clear_cofactor(&mut self, point: &Self::Point) -> CryptographyResult<Self::Point> {
+        let point2 = self.add(point, point)?;
+        let point4 = self.add(&point2, &point2)?;
+        self.add(&point4, &point4)
+    }
+
+    fn is_identity(&mut self, point: &Self::Point) -> bool {
+        point.x.num_bits() == 0 && point.y == point.z
+    }
+
+    fn mul_generator(&mut self, k: &openssl::bn::BigNumRef) -> CryptographyResult<Self::Point> {
+        let generator = self.generator.try_clone()?;
+        self.scalar_mul(&generator, k)
+    }
+
+    fn mul(
+        &mut self,
+        point: &Self::Point,
+        k: &openssl::bn::BigNumRef,
+    ) -> CryptographyResult<Self::Point> {
+        self.scalar_mul(point, k)
+    }
+
+    fn sub(&mut self, a: &Self::Point, b: &Self::Point) -> CryptographyResult<Self::Point> {
+        let zero = openssl::bn::BigNum::new()?;
+        let mut neg_b = b.try_clone()?;
+        neg_b.x = self.field.sub(&zero, &b.x)?;
+        neg_b.t = self.field.sub(&zero, &b.t)?;
+        self.add(a, &neg_b)
+    }
+
+    // RFC 9381 section 5.4.2.2: the nonce key is the second half of the
+    // SHA-512 hash of the private key, as in Ed25519.
+    fn nonce(
+        &mut self,
+        nonce_key: &[u8],
+        h_string: &[u8],
+    ) -> CryptographyResult<openssl::bn::BigNum> {
+        let mut h = openssl::sha::Sha512::new();
+        h.update(nonce_key);
+        h.update(h_string);
+        let k = string_to_int::<Self>(&h.finish())?;
+        let mut result = openssl::bn::BigNum::new()?;
+        result.nnmod(&k, &self.order, &mut self.field.bn_ctx)?;
+        Ok(result)
+    }
+}
+
+fn int_to_string<S: Suite>(
+    n: &openssl::bn::BigNumRef,
+    length: usize,
+) -> CryptographyResult<Vec<u8>> {
+    let mut data = n.to_vec_padded(length as i32)?;
+    if S::LITTLE_ENDIAN {
+        data.reverse();
+    }
+    Ok(data)
+}
+
+fn string_to_int<S: Suite>(data: &[u8]) -> CryptographyResult<openssl::bn::BigNum> {
+    let mut data = data.to_vec();
+    if S::LITTLE_ENDIAN {
+        data.reverse();
+    }
+    Ok(openssl::bn::BigNum::from_slice(&data)?)
+}
+
+// Hash(suite_string || domain || parts || 0x00)
+fn suite_hash<S: Suite>(domain: u8, parts: &[&[u8]]) -> CryptographyResult<Vec<u8>> {
+    let mut h = openssl::hash::Hasher::new(S::digest())?;
+    h.update(&[S::SUITE_STRING, domain])?;
+    for part in parts {
+        h.update(part)?;
+    }
+    h.update(&[0x00])?;
+    Ok(h.finish()?.to_vec())
+}
+
+// ECVRF_encode_to_curve_try_and_increment, with the public key as the salt.
+fn encode_to_curve<S: Suite>(
+    suite: &mut S,
+    pk_string: &[u8],
+    alpha: &[u8],
+) -> CryptographyResult<S::Point> {
+    for ctr in 0..=u8::MAX {
+        let hash = suite_hash::<S>(ENCODE_TO_CURVE_DOMAIN, &[pk_string, alpha, &[ctr]])?;
+        if let Some(point) = suite.string_to_point(&S::hash_to_point_string(&hash))? {
+            let point = suite.clear_cofactor(&point)?;
+            if !suite.is_identity(&point) {
+                return Ok(point);
+            }
+        }
+    }
+    // Each attempt succeeds with probability about 1/2.
+    Err(CryptographyError::from(
+        pyo3::exceptions::PyValueError::new_err("Unable to encode the input to a curve point"),
+    ))
+}
+
+fn challenge<S: Suite>(point_strings: &[&[u8]]) -> CryptographyResult<Vec<u8>> {
+    let mut c_string = suite_hash::<S>(CHALLENGE_DOMAIN, point_strings)?;
+    c_string.truncate(CHALLENGE_LENGTH);
+    Ok(c_string)
+}
+
+fn ecvrf_prove<S: Suite>(
+    suite: &mut S,
+    secret: &SecretKey,
+    alpha: &[u8],
+) -> CryptographyResult<Vec<u8>> {
+    let public_point = suite.mul_generator(&secret.x)?;
+    let pk_string = suite.point_to_string(&public_point)?;
+    let h = encode_to_curve(suite, &pk_string, alpha)?;
+    let h_string = suite.point_to_string(&h)?;
+    let gamma = suite.mul(&h, &secret.x)?;
+    let gamma_string = suite.point_to_string(&gamma)?;
+
+    let k = suite.nonce(&secret.nonce_key, &h_string)?;
+    let k_b = suite.mul_generator(&k)?;
+    let k_h = suite.mul(&h, &k)?;
+    let c_string = challenge::<S>(&[
+        &pk_string,
+        &h_string,
+        &gamma_string,
+        &suite.point_to_string(&k_b)?,
+        &suite.point_to_string(&k_h)?,
+    ])?;
+
+    // s = (k + c * x) mod q
+    let c = string_to_int::<S>(&c_string)?;
+    let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+    let mut cx = openssl::bn::BigNum::new()?;
+    cx.mod_mul(&c, &secret.x, suite.order(), &mut bn_ctx)?;
+    let mut s = openssl::bn::BigNum::new()?;
+    s.mod_add(&k, &cx, suite.order(), &mut bn_ctx)?;
+
+    let mut proof = gamma_string;
+    proof.extend_from_slice(&c_string);
+    proof.extend_from_slice(&int_to_string::<S>(&s, SCALAR_LENGTH)?);
+    Ok(proof)
+}
+
+// A proof split into Gamma, the challenge string and s.
+struct DecodedProof<'a, P> {
+    gamma: P,
+    c_string: &'a [u8],
+    s: openssl::bn::BigNum,
+}
+
+// Returns `None` if the proof is malformed.
+fn decode_proof<'a, S: Suite>(
+    suite: &mut S,
+    proof: &'a [u8],
+) -> CryptographyResult<Option<DecodedProof<'a, S::Point>>> {
+    if proof.len() != S::POINT_LENGTH + CHALLENGE_LENGTH + SCALAR_LENGTH {
+        return Ok(None);
+    }
+    let (gamma_string, rest) = proof.split_at(S::POINT_LENGTH);
+    let (c_string, s_string) = rest.split_at(CHALLENGE_LENGTH);
+    let gamma = match suite.string_to_point(gamma_string)? {
+        Some(gamma) => gamma,
+        None => return Ok(None),
+    };
+    let s = string_to_int::<S>(s_string)?;
+    if s >= *suite.order() {
+        return Ok(None);
+    }
+    Ok(Some(DecodedProof { gamma, c_string, s }))
+}
+
+fn gamma_to_hash<S: Suite>(suite: &mut S, gamma: &S::Point) -> CryptographyResult<Vec<u8>> {
+    let point = suite.clear_cofactor(gamma)?;
+    suite_hash::<S>(PROOF_TO_HASH_DOMAIN, &[&suite.point_to_string(&point)?])
+}
+
+fn ecvrf_proof_to_hash<S: Suite>(
+    suite: &mut S,
+    proof: &[u8],
+) -> CryptographyResult<Option<Vec<u8>>> {
+    match decode_proof(suite, proof)? {
+        Some(decoded) => Ok(Some(gamma_to_hash(suite, &decoded.gamma)?)),
+        None => Ok(None),
+    }
+}
+
+// Returns the VRF output if `proof` is valid.
+fn ecvrf_verify<S: Suite>(
+    suite: &mut S,
+    pk_string: &[u8],
+    proof: &[u8],
+    alpha: &[u8],
+) -> CryptographyResult<Option<Vec<u8>>> {
+    let public_point = match suite.string_to_point(pk_string)? {
+        Some(point) => point,
+        None => return Ok(None),
+    };
+    // Public keys of small order would let a prover produce valid proofs for
+    // more than one output, so they're always rejected.
+    let cleared = suite.clear_cofactor(&public_point)?;
+    if suite.is_identity(&cleared) {
+        return Ok(None);
+    }
+    let DecodedProof { gamma, c_string, s } = match decode_proof(suite, proof)? {
+        Some(decoded) => decoded,
+        None => return Ok(None),
+    };
+    let c = string_to_int::<S>(c_string)?;
+    let h = encode_to_curve(suite, pk_string, alpha)?;
+
+    // U = s * B - c * Y, V = s * H - c * Gamma
+    let s_b = suite.mul_generator(&s)?;
+    let c_y = suite.mul(&public_point, &c)?;
+    let u = suite.sub(&s_b, &c_y)?;
+    let s_h = suite.mul(&h, &s)?;
+    let c_gamma = suite.mul(&gamma, &c)?;
+    let v = suite.sub(&s_h, &c_gamma)?;
+
+    let expected_c_string = challenge::<S>(&[
+        pk_string,
+        &suite.point_to_string(&h)?,
+        &proof[..S::POINT_LENGTH],
+        &suite.point_to_string(&u)?,
+        &suite.point_to_string(&v)?,
+    ])?;
+    if expected_c_string != c_string {
+        return Ok(None);
+    }
+    Ok(Some(gamma_to_hash(suite, &gamma)?))
+}
+
+fn check_p256_curve(group: &openssl::ec::EcGroupRef) -> CryptographyResult<()> {
+    if group.curve_name() != Some(openssl::nid::Nid::X9_62_PRIME256V1) {
+        return Err(CryptographyError::from(
+            exceptions::UnsupportedAlgorithm::new_err((
+                "ECVRF is only supported on the SECP256R1 curve",
+                exceptions::Reasons::UNSUPPORTED_ELLIPTIC_CURVE,
+            )),
+        ));
+    }
+    Ok(())
+}
+
+#[pyo3::prelude::pyfunction]
+fn prove<'p>(
+    py: pyo3::Python<'p>,
+    private_key: &pyo3::PyAny,
+    alpha: CffiBuf<'_>,
+) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+    let alpha = alpha.as_bytes();
+    let proof = if let Ok(key) = private_key.extract::<pyo3::PyRef<'_, ec::ECPrivateKey>>() {
+        let ec = key.pkey.ec_key()?;
+        check_p256_curve(ec.group())?;
+        let secret = SecretKey {
+            x: ec.private_key().to_owned()?,
+            nonce_key: ec.private_key().to_vec_padded(SCALAR_LENGTH as i32)?,
+        };
+        py.allow_threads(|| ecvrf_prove(&mut P256::new()?, &secret, alpha))?
+    } else if let Ok(key) = private_key.extract::<pyo3::PyRef<'_, ed25519::Ed25519PrivateKey>>() {
+        // The secret scalar and nonce key are derived from the private key
+        // the same way Ed25519 derives them.
+        let digest = openssl::sha::sha512(&key.pkey.raw_private_key()?);
+        let mut scalar = digest[..32].to_vec();
+        scalar[0] &= 248;
+        scalar[31] &= 127;
+        scalar[31] |= 64;
+        scalar.reverse();
+        let secret = SecretKey {
+            x: openssl::bn::BigNum::from_slice(&scalar)?,
+            nonce_key: digest[32..].to_vec(),
+        };
+        py.allow_threads(|| ecvrf_prove(&mut Edwards25519::new()?, &secret, alpha))?
+    } else {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyTypeError::new_err(
+                "private_key must be a SECP256R1 EllipticCurvePrivateKey or an Ed25519PrivateKey",
+            ),
+        ));
+    };
+    Ok(pyo3::types::PyBytes::new(py, &proof))
+}
+
+#[pyo3::prelude::pyfunction]
+fn verify<'p>(
+    py: pyo3::Python<'p>,
+    public_key: &pyo3::PyAny,
+    proof: CffiBuf<'_>,
+    alpha: CffiBuf<'_>,
+) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+    let proof = proof.as_bytes();
+    let alpha = alpha.as_bytes();
+    let beta = if let Ok(key) = public_key.extract::<pyo3::PyRef<'_, ec::ECPublicKey>>() {
+        let ec = key.pkey.ec_key()?;
+        check_p256_curve(ec.group())?;
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let pk_string = ec.public_key().to_bytes(
+            ec.group(),
+            openssl::ec::PointConversionForm::COMPRESSED,
+            &mut bn_ctx,
+        )?;
+        py.allow_threads(|| ecvrf_verify(&mut P256::new()?, &pk_string, proof, alpha))?
+    } else if let Ok(key) = public_key.extract::<pyo3::PyRef<'_, ed25519::Ed25519PublicKey>>() {
+        let pk_string = key.pkey.raw_public_key()?;
+        py.allow_threads(|| ecvrf_verify(&mut Edwards25519::new()?, &pk_string, proof, alpha))?
+    } else {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyTypeError::new_err(
+                "public_key must be a SECP256R1 EllipticCurvePublicKey or an Ed25519PublicKey",
+            ),
+        ));
+    };
+    match beta {
+        Some(beta) => Ok(pyo3::types::PyBytes::new(py, &beta)),
+        None => Err(CryptographyError::from(
+            exceptions::InvalidSignature::new_err(()),
+        )),
+    }
+}
+
+#[pyo3::prelude::pyfunction]
+fn proof_to_hash<'p>(
+    py: pyo3::Python<'p>,
+    proof: CffiBuf<'_>,
+    suite: &pyo3::PyAny,
+) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+    let beta = if suite.is(types::ECVRF_SUITE_P256_SHA256_TAI.get(py)?) {
+        ecvrf_proof_to_hash(&mut P256::new()?, proof.as_bytes())?
+    } else if suite.is(types::ECVRF_SUITE_EDWARDS25519_SHA512_TAI.get(py)?) {
+        ecvrf_proof_to_hash(&mut Edwards25519::new()?, proof.as_bytes())?
+    } else {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyTypeError::new_err("suite must be an ECVRFSuite"),
+        ));
+    };
+    match beta {
+        Some(beta) => Ok(pyo3::types::PyBytes::new(py, &beta)),
+        None => Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("Invalid proof"),
+        )),
+    }
+}
+
+pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let m = pyo3::prelude::PyModule::new(py, "ecvrf")?;
+    m.add_function(pyo3::wrap_pyfunction!(prove, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(verify, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(proof_to_hash, m)?)?;
+
+    Ok(m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ecvrf_prove, ecvrf_verify, Edwards25519, SecretKey, Suite};
+
+    fn unhex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_scalar_mul() {
+        let mut curve = Edwards25519::new().ok().unwrap();
+        let generator = curve.generator.try_clone().ok().unwrap();
+        let encode = |curve: &mut Edwards25519, k: &openssl::bn::BigNumRef| {
+            let point = curve.mul_generator(k).ok().unwrap();
+            curve.point_to_string(&point).ok().unwrap()
+        };
+
+        // Repeated addition with the `BigNum` formulas.
+        let mut expected = Edwards25519::identity().ok().unwrap();
+        for i in 0..20 {
+            let k = openssl::bn::BigNum::from_u32(i).unwrap();
+            assert_eq!(
+                encode(&mut curve, &k),
+                curve.point_to_string(&expected).ok().unwrap()
+            );
+            expected = curve.add(&expected, &generator).ok().unwrap();
+        }
+
+        let order = curve.order.to_owned().unwrap();
+        let identity = curve.mul_generator(&order).ok().unwrap();
+        assert!(curve.is_identity(&identity));
+        let mut order_minus_one = order.to_owned().unwrap();
+        order_minus_one.sub_word(1).unwrap();
+        let minus_generator = curve
+            .sub(&Edwards25519::identity().ok().unwrap(), &generator)
+            .ok()
+            .unwrap();
+        assert_eq!(
+            encode(&mut curve, &order_minus_one),
+            curve.point_to_string(&minus_generator).ok().unwrap()
+        );
+
+        // (a + b) * G = a * G + b * G, for scalars using all 256 bits.
+        let a = openssl::bn::BigNum::from_slice(&[0xa5; 32]).unwrap();
+        let b = openssl::bn::BigNum::from_slice(&[0x3c; 32]).unwrap();
+        let sum = &a + &b;
+        let mut ctx = openssl::bn::BigNumContext::new().unwrap();
+        let mut sum_mod = openssl::bn::BigNum::new().unwrap();
+        sum_mod.nnmod(&sum, &order, &mut ctx).unwrap();
+        let a_g = curve.mul_generator(&a).ok().unwrap();
+        let b_g = curve.mul_generator(&b).ok().unwrap();
+        let a_g_plus_b_g = curve.add(&a_g, &b_g).ok().unwrap();
+        assert_eq!(
+            encode(&mut curve, &sum_mod),
+            curve.point_to_string(&a_g_plus_b_g).ok().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_ed25519_vector() {
+        // RFC 8032 section 7.1 test 1 and RFC 9381 appendix B.3 example 16,
+        // which use the same private key.
+        let digest = openssl::sha::sha512(&unhex(
+            "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+        ));
+        let mut scalar = digest[..32].to_vec();
+        scalar[0] &= 248;
+        scalar[31] &= 127;
+        scalar[31] |= 64;
+        scalar.reverse();
+        let secret = SecretKey {
+            x: openssl::bn::BigNum::from_slice(&scalar).unwrap(),
+            nonce_key: digest[32..].to_vec(),
+        };
+
+        let mut curve = Edwards25519::new().ok().unwrap();
+        let public_key = curve.mul_generator(&secret.x).ok().unwrap();
+        let pk_string = curve.point_to_string(&public_key).ok().unwrap();
+        assert_eq!(
+            pk_string,
+            unhex("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a")
+        );
+
+        let proof = ecvrf_prove(&mut curve, &secret, b"").ok().unwrap();
+        assert_eq!(
+            proof,
+            unhex(
+                "8657106690b5526245a92b003bb079ccd1a92130477671f6fc01ad16f26f723f\
+                 26f8a57ccaed74ee1b190bed1f479d9727d2d0f9b005a6e456a35d4fb0daab12\
+                 68a1b0db10836d9826a528ca76567805"
+            )
+        );
+        assert_eq!(
+            ecvrf_verify(&mut curve, &pk_string, &proof, b"")
+                .ok()
+                .flatten(),
+            Some(unhex(
+                "90cf1df3b703cce59e2a35b925d411164068269d7b2d29f3301c03dd757876ff\
+                 66b71dda49d2de59d03450451af026798e8f81cd2e333de5cdf4f3e140fdd8ae"
+            ))
+        );
+    }
+}
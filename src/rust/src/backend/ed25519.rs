@@ -2,19 +2,20 @@
 // 2.0, and the BSD License. See the LICENSE file in the root of this repository
 // for complete details.
 
-use crate::backend::utils;
+use crate::backend::ecvrf::{self, Suite};
+use crate::backend::{jwk, utils, x25519};
 use crate::buf::CffiBuf;
 use crate::error::{CryptographyError, CryptographyResult};
 use crate::exceptions;
 
 #[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.ed25519")]
 pub(crate) struct Ed25519PrivateKey {
-    pkey: openssl::pkey::PKey<openssl::pkey::Private>,
+    pub(crate) pkey: openssl::pkey::PKey<openssl::pkey::Private>,
 }
 
 #[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.ed25519")]
 pub(crate) struct Ed25519PublicKey {
-    pkey: openssl::pkey::PKey<openssl::pkey::Public>,
+    pub(crate) pkey: openssl::pkey::PKey<openssl::pkey::Public>,
 }
 
 #[pyo3::prelude::pyfunction]
@@ -40,6 +41,71 @@ pub(crate) fn public_key_from_pkey(
     }
 }
 
+// p = 2^255 - 19, the prime field shared by Curve25519 and edwards25519.
+pub(crate) fn field_prime() -> CryptographyResult<openssl::bn::BigNum> {
+    Ok(openssl::bn::BigNum::from_hex_str(
+        "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed",
+    )?)
+}
+
+// Field elements are encoded as 32 byte little endian integers. The top bit is
+// ignored: for Ed25519 it holds the sign of x, and RFC 7748 says to mask it.
+pub(crate) fn decode_field_element(data: &[u8]) -> CryptographyResult<openssl::bn::BigNum> {
+    let mut be = data.to_vec();
+    be.reverse();
+    be[0] &= 0x7f;
+    Ok(openssl::bn::BigNum::from_slice(&be)?)
+}
+
+pub(crate) fn encode_field_element(value: &openssl::bn::BigNumRef) -> CryptographyResult<Vec<u8>> {
+    let mut le = value.to_vec_padded(32)?;
+    le.reverse();
+    Ok(le)
+}
+
+// Computes `(a + b) / (a - b) mod p`, which is the birational map between the
+// two curves in both directions: u = (1 + y) / (1 - y) and
+// y = (u - 1) / (u + 1).
+fn birational_map(
+    a: &openssl::bn::BigNumRef,
+    b: &openssl::bn::BigNumRef,
+) -> CryptographyResult<openssl::bn::BigNum> {
+    let p = field_prime()?;
+    let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+    let mut numerator = openssl::bn::BigNum::new()?;
+    numerator.mod_add(a, b, &p, &mut bn_ctx)?;
+    let mut denominator = openssl::bn::BigNum::new()?;
+    denominator.mod_sub(a, b, &p, &mut bn_ctx)?;
+    if denominator.num_bits() == 0 {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("The key has no equivalent on the other curve"),
+        ));
+    }
+    let mut inverse = openssl::bn::BigNum::new()?;
+    inverse.mod_inverse(&denominator, &p, &mut bn_ctx)?;
+    let mut result = openssl::bn::BigNum::new()?;
+    result.mod_mul(&numerator, &inverse, &p, &mut bn_ctx)?;
+    Ok(result)
+}
+
+// Converts an X25519 public key to the Ed25519 public key whose x coordinate
+// is positive, since the Montgomery form doesn't contain the sign of x.
+pub(crate) fn public_key_from_x25519(raw: &[u8]) -> CryptographyResult<Ed25519PublicKey> {
+    let u = decode_field_element(raw)?;
+    let p = field_prime()?;
+    let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+    let mut reduced_u = openssl::bn::BigNum::new()?;
+    reduced_u.nnmod(&u, &p, &mut bn_ctx)?;
+    let mut minus_one = p.to_owned()?;
+    minus_one.sub_word(1)?;
+    let y = birational_map(&reduced_u, &minus_one)?;
+    let pkey = openssl::pkey::PKey::public_key_from_raw_bytes(
+        &encode_field_element(&y)?,
+        openssl::pkey::Id::ED25519,
+    )?;
+    Ok(Ed25519PublicKey { pkey })
+}
+
 #[pyo3::prelude::pyfunction]
 fn from_private_bytes(data: CffiBuf<'_>) -> pyo3::PyResult<Ed25519PrivateKey> {
     let pkey = openssl::pkey::PKey::private_key_from_raw_bytes(
@@ -52,6 +118,109 @@ fn from_private_bytes(data: CffiBuf<'_>) -> pyo3::PyResult<Ed25519PrivateKey> {
     Ok(Ed25519PrivateKey { pkey })
 }
 
+// Ed25519ctx needs a non-empty context, and RFC 8032 limits contexts to 255
+// bytes.
+fn check_context(context: &[u8], prehash: bool) -> CryptographyResult<()> {
+    if context.len() > 255 || (context.is_empty() && !prehash) {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(
+                "context must be at most 255 bytes long, and can only be empty with prehash",
+            ),
+        ));
+    }
+    Ok(())
+}
+
+// Verifies an Ed25519, Ed25519ctx or Ed25519ph signature as described in RFC
+// 8032 section 5.1.7, with explicit rules where implementations disagree. S
+// must always be reduced. With `cofactored` the check is
+// [8][S]B = [8]R + [8][k]A, rather than [S]B = R + [k]A. With `strict`, A and
+// R must be canonically encoded and not of small order; otherwise
+// non-canonical encodings are accepted, as in ZIP 215.
+fn verify_explicit(
+    public_key: &[u8],
+    signature: &[u8],
+    data: &[u8],
+    context: Option<&[u8]>,
+    prehash: bool,
+    cofactored: bool,
+    strict: bool,
+) -> CryptographyResult<bool> {
+    if signature.len() != 64 {
+        return Ok(false);
+    }
+    let (r_bytes, s_bytes) = signature.split_at(32);
+
+    let mut curve = ecvrf::Edwards25519::new()?;
+    let mut s_be = s_bytes.to_vec();
+    s_be.reverse();
+    let s = openssl::bn::BigNum::from_slice(&s_be)?;
+    if s >= curve.order {
+        return Ok(false);
+    }
+    let a = match curve.decode_point(public_key, strict)? {
+        Some(a) => a,
+        None => return Ok(false),
+    };
+    let r = match curve.decode_point(r_bytes, strict)? {
+        Some(r) => r,
+        None => return Ok(false),
+    };
+    if strict {
+        let a8 = curve.clear_cofactor(&a)?;
+        let r8 = curve.clear_cofactor(&r)?;
+        if curve.is_identity(&a8) || curve.is_identity(&r8) {
+            return Ok(false);
+        }
+    }
+
+    let mut hasher = openssl::sha::Sha512::new();
+    if context.is_some() || prehash {
+        let context = context.unwrap_or_default();
+        hasher.update(b"SigEd25519 no Ed25519 collisions");
+        hasher.update(&[u8::from(prehash), context.len() as u8]);
+        hasher.update(context);
+    }
+    hasher.update(r_bytes);
+    hasher.update(public_key);
+    if prehash {
+        hasher.update(&openssl::sha::sha512(data));
+    } else {
+        hasher.update(data);
+    }
+    let mut k_be = hasher.finish().to_vec();
+    k_be.reverse();
+    let digest = openssl::bn::BigNum::from_slice(&k_be)?;
+    let mut k = openssl::bn::BigNum::new()?;
+    k.nnmod(&digest, &curve.order, &mut curve.field.bn_ctx)?;
+
+    let sb = curve.mul_generator(&s)?;
+    let ka = curve.scalar_mul(&a, &k)?;
+    let rka = curve.add(&r, &ka)?;
+    let mut difference = curve.sub(&sb, &rka)?;
+    if cofactored {
+        difference = curve.clear_cofactor(&difference)?;
+    }
+    Ok(curve.is_identity(&difference))
+}
+
+#[cfg(CRYPTOGRAPHY_OPENSSL_320_OR_GREATER)]
+fn instance(prehash: bool) -> cryptography_openssl::eddsa::Instance {
+    if prehash {
+        cryptography_openssl::eddsa::Instance::Ed25519ph
+    } else {
+        cryptography_openssl::eddsa::Instance::Ed25519ctx
+    }
+}
+
+#[cfg(not(CRYPTOGRAPHY_OPENSSL_320_OR_GREATER))]
+fn variants_unsupported() -> CryptographyError {
+    CryptographyError::from(exceptions::UnsupportedAlgorithm::new_err((
+        "Ed25519ctx and Ed25519ph are not supported by this version of OpenSSL.",
+        exceptions::Reasons::UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
+    )))
+}
+
 #[pyo3::prelude::pyfunction]
 fn from_public_bytes(data: &[u8]) -> pyo3::PyResult<Ed25519PublicKey> {
     let pkey = openssl::pkey::PKey::public_key_from_raw_bytes(data, openssl::pkey::Id::ED25519)
@@ -63,11 +232,32 @@ fn from_public_bytes(data: &[u8]) -> pyo3::PyResult<Ed25519PublicKey> {
 
 #[pyo3::prelude::pymethods]
 impl Ed25519PrivateKey {
+    #[pyo3(signature = (data, *, context=None, prehash=false))]
     fn sign<'p>(
         &self,
         py: pyo3::Python<'p>,
         data: &[u8],
+        context: Option<&[u8]>,
+        prehash: bool,
     ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        if context.is_some() || prehash {
+            let context = context.unwrap_or_default();
+            check_context(context, prehash)?;
+            cfg_if::cfg_if! {
+                if #[cfg(CRYPTOGRAPHY_OPENSSL_320_OR_GREATER)] {
+                    let signature = cryptography_openssl::eddsa::sign(
+                        &self.pkey,
+                        instance(prehash),
+                        context,
+                        data,
+                    )?;
+                    return Ok(pyo3::types::PyBytes::new(py, &signature));
+                } else {
+                    return Err(variants_unsupported());
+                }
+            }
+        }
+
         let mut signer = openssl::sign::Signer::new_without_digest(&self.pkey)?;
         Ok(pyo3::types::PyBytes::new_with(py, signer.len()?, |b| {
             let n = signer
@@ -88,6 +278,18 @@ impl Ed25519PrivateKey {
         })
     }
 
+    // The X25519 private key is the clamped lower half of SHA-512(seed), which
+    // is the same scalar Ed25519 uses to compute its public key.
+    fn to_x25519_private_key(&self) -> CryptographyResult<x25519::X25519PrivateKey> {
+        let digest = openssl::sha::sha512(&self.pkey.raw_private_key()?);
+        let mut scalar = [0; 32];
+        scalar.copy_from_slice(&digest[..32]);
+        x25519::clamp_scalar(&mut scalar);
+        let pkey =
+            openssl::pkey::PKey::private_key_from_raw_bytes(&scalar, openssl::pkey::Id::X25519)?;
+        Ok(x25519::private_key_from_pkey(&pkey))
+    }
+
     fn private_bytes_raw<'p>(
         &self,
         py: pyo3::Python<'p>,
@@ -96,6 +298,16 @@ impl Ed25519PrivateKey {
         Ok(pyo3::types::PyBytes::new(py, &raw_bytes))
     }
 
+    #[pyo3(signature = (kid=None))]
+    fn jwk_private_bytes<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        kid: Option<&str>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let jwk = jwk::private_jwk(&self.pkey, kid)?;
+        Ok(pyo3::types::PyBytes::new(py, jwk.as_bytes()))
+    }
+
     fn private_bytes<'p>(
         slf: &pyo3::PyCell<Self>,
         py: pyo3::Python<'p>,
@@ -114,14 +326,77 @@ impl Ed25519PrivateKey {
             true,
         )
     }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> CryptographyResult<bool> {
+        utils::pkey_private_eq(&self.pkey, &other.pkey)
+    }
+
+    fn __hash__(&self) -> CryptographyResult<u64> {
+        utils::pkey_hash(&self.pkey)
+    }
+
+    fn public_key_matches(
+        slf: &pyo3::PyCell<Self>,
+        py: pyo3::Python<'_>,
+        public_key: &pyo3::PyAny,
+    ) -> CryptographyResult<bool> {
+        utils::public_key_matches(py, slf, public_key)
+    }
 }
 
 #[pyo3::prelude::pymethods]
 impl Ed25519PublicKey {
-    fn verify(&self, signature: &[u8], data: &[u8]) -> CryptographyResult<()> {
-        let valid = openssl::sign::Verifier::new_without_digest(&self.pkey)?
-            .verify_oneshot(signature, data)
-            .unwrap_or(false);
+    #[pyo3(signature = (
+        signature,
+        data,
+        *,
+        context=None,
+        prehash=false,
+        cofactored=false,
+        strict=false,
+    ))]
+    fn verify(
+        &self,
+        signature: &[u8],
+        data: &[u8],
+        context: Option<&[u8]>,
+        prehash: bool,
+        cofactored: bool,
+        strict: bool,
+    ) -> CryptographyResult<()> {
+        if context.is_some() || prehash {
+            check_context(context.unwrap_or_default(), prehash)?;
+        }
+        let valid = if cofactored || strict {
+            verify_explicit(
+                &self.pkey.raw_public_key()?,
+                signature,
+                data,
+                context,
+                prehash,
+                cofactored,
+                strict,
+            )?
+        } else if context.is_some() || prehash {
+            cfg_if::cfg_if! {
+                if #[cfg(CRYPTOGRAPHY_OPENSSL_320_OR_GREATER)] {
+                    cryptography_openssl::eddsa::verify(
+                        &self.pkey,
+                        instance(prehash),
+                        context.unwrap_or_default(),
+                        signature,
+                        data,
+                    )
+                    .unwrap_or(false)
+                } else {
+                    return Err(variants_unsupported());
+                }
+            }
+        } else {
+            openssl::sign::Verifier::new_without_digest(&self.pkey)?
+                .verify_oneshot(signature, data)
+                .unwrap_or(false)
+        };
 
         if !valid {
             return Err(CryptographyError::from(
@@ -140,6 +415,32 @@ impl Ed25519PublicKey {
         Ok(pyo3::types::PyBytes::new(py, &raw_bytes))
     }
 
+    fn to_x25519_public_key(&self) -> CryptographyResult<x25519::X25519PublicKey> {
+        let y = decode_field_element(&self.pkey.raw_public_key()?)?;
+        if y >= field_prime()? {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("Invalid Ed25519 public key"),
+            ));
+        }
+        let one = openssl::bn::BigNum::from_u32(1)?;
+        let u = birational_map(&one, &y)?;
+        let pkey = openssl::pkey::PKey::public_key_from_raw_bytes(
+            &encode_field_element(&u)?,
+            openssl::pkey::Id::X25519,
+        )?;
+        Ok(x25519::public_key_from_pkey(&pkey))
+    }
+
+    #[pyo3(signature = (kid=None))]
+    fn jwk_public_bytes<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        kid: Option<&str>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let jwk = jwk::public_jwk(&self.pkey, kid)?;
+        Ok(pyo3::types::PyBytes::new(py, jwk.as_bytes()))
+    }
+
     fn public_bytes<'p>(
         slf: &pyo3::PyCell<Self>,
         py: pyo3::Python<'p>,
@@ -149,6 +450,23 @@ impl Ed25519PublicKey {
         utils::pkey_public_bytes(py, slf, &slf.borrow().pkey, encoding, format, true, true)
     }
 
+    fn fingerprint<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::PyAny> {
+        utils::pkey_fingerprint(py, &self.pkey, algorithm)
+    }
+
+    #[pyo3(signature = (algorithm=None))]
+    fn jwk_thumbprint<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        algorithm: Option<&pyo3::PyAny>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        jwk::public_jwk_thumbprint(py, &self.pkey, algorithm)
+    }
+
     fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> bool {
         self.pkey.public_eq(&other.pkey)
     }
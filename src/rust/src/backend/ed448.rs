@@ -2,7 +2,7 @@
 // 2.0, and the BSD License. See the LICENSE file in the root of this repository
 // for complete details.
 
-use crate::backend::utils;
+use crate::backend::{jwk, utils};
 use crate::buf::CffiBuf;
 use crate::error::{CryptographyError, CryptographyResult};
 use crate::exceptions;
@@ -50,6 +50,33 @@ fn from_private_bytes(data: CffiBuf<'_>) -> pyo3::PyResult<Ed448PrivateKey> {
     Ok(Ed448PrivateKey { pkey })
 }
 
+// RFC 8032 limits contexts to 255 bytes.
+fn check_context(context: &[u8]) -> CryptographyResult<()> {
+    if context.len() > 255 {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("context must be at most 255 bytes long"),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(CRYPTOGRAPHY_OPENSSL_320_OR_GREATER)]
+fn instance(prehash: bool) -> cryptography_openssl::eddsa::Instance {
+    if prehash {
+        cryptography_openssl::eddsa::Instance::Ed448ph
+    } else {
+        cryptography_openssl::eddsa::Instance::Ed448
+    }
+}
+
+#[cfg(not(CRYPTOGRAPHY_OPENSSL_320_OR_GREATER))]
+fn variants_unsupported() -> CryptographyError {
+    CryptographyError::from(exceptions::UnsupportedAlgorithm::new_err((
+        "Ed448 contexts and Ed448ph are not supported by this version of OpenSSL.",
+        exceptions::Reasons::UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
+    )))
+}
+
 #[pyo3::prelude::pyfunction]
 fn from_public_bytes(data: &[u8]) -> pyo3::PyResult<Ed448PublicKey> {
     let pkey = openssl::pkey::PKey::public_key_from_raw_bytes(data, openssl::pkey::Id::ED448)
@@ -61,11 +88,32 @@ fn from_public_bytes(data: &[u8]) -> pyo3::PyResult<Ed448PublicKey> {
 
 #[pyo3::prelude::pymethods]
 impl Ed448PrivateKey {
+    #[pyo3(signature = (data, *, context=None, prehash=false))]
     fn sign<'p>(
         &self,
         py: pyo3::Python<'p>,
         data: &[u8],
+        context: Option<&[u8]>,
+        prehash: bool,
     ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let context = context.unwrap_or_default();
+        check_context(context)?;
+        if !context.is_empty() || prehash {
+            cfg_if::cfg_if! {
+                if #[cfg(CRYPTOGRAPHY_OPENSSL_320_OR_GREATER)] {
+                    let signature = cryptography_openssl::eddsa::sign(
+                        &self.pkey,
+                        instance(prehash),
+                        context,
+                        data,
+                    )?;
+                    return Ok(pyo3::types::PyBytes::new(py, &signature));
+                } else {
+                    return Err(variants_unsupported());
+                }
+            }
+        }
+
         let mut signer = openssl::sign::Signer::new_without_digest(&self.pkey)?;
         Ok(pyo3::types::PyBytes::new_with(py, signer.len()?, |b| {
             let n = signer
@@ -94,6 +142,16 @@ impl Ed448PrivateKey {
         Ok(pyo3::types::PyBytes::new(py, &raw_bytes))
     }
 
+    #[pyo3(signature = (kid=None))]
+    fn jwk_private_bytes<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        kid: Option<&str>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let jwk = jwk::private_jwk(&self.pkey, kid)?;
+        Ok(pyo3::types::PyBytes::new(py, jwk.as_bytes()))
+    }
+
     fn private_bytes<'p>(
         slf: &pyo3::PyCell<Self>,
         py: pyo3::Python<'p>,
@@ -112,13 +170,55 @@ impl Ed448PrivateKey {
             true,
         )
     }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> CryptographyResult<bool> {
+        utils::pkey_private_eq(&self.pkey, &other.pkey)
+    }
+
+    fn __hash__(&self) -> CryptographyResult<u64> {
+        utils::pkey_hash(&self.pkey)
+    }
+
+    fn public_key_matches(
+        slf: &pyo3::PyCell<Self>,
+        py: pyo3::Python<'_>,
+        public_key: &pyo3::PyAny,
+    ) -> CryptographyResult<bool> {
+        utils::public_key_matches(py, slf, public_key)
+    }
 }
 
 #[pyo3::prelude::pymethods]
 impl Ed448PublicKey {
-    fn verify(&self, signature: &[u8], data: &[u8]) -> CryptographyResult<()> {
-        let valid = openssl::sign::Verifier::new_without_digest(&self.pkey)?
-            .verify_oneshot(signature, data)?;
+    #[pyo3(signature = (signature, data, *, context=None, prehash=false))]
+    fn verify(
+        &self,
+        signature: &[u8],
+        data: &[u8],
+        context: Option<&[u8]>,
+        prehash: bool,
+    ) -> CryptographyResult<()> {
+        let context = context.unwrap_or_default();
+        check_context(context)?;
+        let valid = if !context.is_empty() || prehash {
+            cfg_if::cfg_if! {
+                if #[cfg(CRYPTOGRAPHY_OPENSSL_320_OR_GREATER)] {
+                    cryptography_openssl::eddsa::verify(
+                        &self.pkey,
+                        instance(prehash),
+                        context,
+                        signature,
+                        data,
+                    )
+                    .unwrap_or(false)
+                } else {
+                    return Err(variants_unsupported());
+                }
+            }
+        } else {
+            openssl::sign::Verifier::new_without_digest(&self.pkey)?
+                .verify_oneshot(signature, data)?
+        };
 
         if !valid {
             return Err(CryptographyError::from(
@@ -137,6 +237,16 @@ impl Ed448PublicKey {
         Ok(pyo3::types::PyBytes::new(py, &raw_bytes))
     }
 
+    #[pyo3(signature = (kid=None))]
+    fn jwk_public_bytes<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        kid: Option<&str>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let jwk = jwk::public_jwk(&self.pkey, kid)?;
+        Ok(pyo3::types::PyBytes::new(py, jwk.as_bytes()))
+    }
+
     fn public_bytes<'p>(
         slf: &pyo3::PyCell<Self>,
         py: pyo3::Python<'p>,
@@ -146,6 +256,23 @@ impl Ed448PublicKey {
         utils::pkey_public_bytes(py, slf, &slf.borrow().pkey, encoding, format, true, true)
     }
 
+    fn fingerprint<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::PyAny> {
+        utils::pkey_fingerprint(py, &self.pkey, algorithm)
+    }
+
+    #[pyo3(signature = (algorithm=None))]
+    fn jwk_thumbprint<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        algorithm: Option<&pyo3::PyAny>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        jwk::public_jwk_thumbprint(py, &self.pkey, algorithm)
+    }
+
     fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> bool {
         self.pkey.public_eq(&other.pkey)
     }
@@ -0,0 +1,328 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Falcon signature verification, following the round 3 specification
+//! "Falcon: Fast-Fourier Lattice-based Compact Signatures over NTRU" v1.2,
+//! which NIST is standardizing as FN-DSA.
+//!
+//! None of the TLS libraries we support implement Falcon, so the encodings
+//! and the arithmetic in Z_q[x]/(x^n + 1) are implemented here. Only
+//! verification is supported, which only handles public data, so none of it
+//! needs to be constant time. The product s2 * h is computed directly rather
+//! than with an NTT, as it's cheap enough for a single verification.
+
+use crate::buf::CffiBuf;
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::{exceptions, types};
+
+const Q: u32 = 12289;
+const NONCE_LENGTH: usize = 40;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Variant {
+    Falcon512,
+    Falcon1024,
+}
+
+impl Variant {
+    fn from_logn(logn: u8) -> Option<Variant> {
+        match logn {
+            9 => Some(Variant::Falcon512),
+            10 => Some(Variant::Falcon1024),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Variant::Falcon512 => "Falcon-512",
+            Variant::Falcon1024 => "Falcon-1024",
+        }
+    }
+
+    fn logn(self) -> u8 {
+        match self {
+            Variant::Falcon512 => 9,
+            Variant::Falcon1024 => 10,
+        }
+    }
+
+    fn n(self) -> usize {
+        1 << self.logn()
+    }
+
+    // A header byte followed by the coefficients of h, 14 bits each.
+    fn public_key_length(self) -> usize {
+        1 + self.n() * 14 / 8
+    }
+
+    // The largest allowed squared norm of (s1, s2), floor(beta^2).
+    fn bound(self) -> u64 {
+        match self {
+            Variant::Falcon512 => 34034726,
+            Variant::Falcon1024 => 70265242,
+        }
+    }
+}
+
+// Decodes the coefficients of h, which are packed big-endian into 14 bits
+// each and must be less than q.
+fn decode_public_key(variant: Variant, data: &[u8]) -> Option<Vec<u16>> {
+    let mut h = Vec::with_capacity(variant.n());
+    let mut acc = 0u32;
+    let mut acc_len = 0;
+    for &b in data {
+        acc = (acc << 8) | u32::from(b);
+        acc_len += 8;
+        if acc_len >= 14 {
+            acc_len -= 14;
+            let w = (acc >> acc_len) & 0x3fff;
+            if w >= Q {
+                return None;
+            }
+            h.push(w as u16);
+        }
+    }
+    Some(h)
+}
+
+// Decodes the compressed encoding of s2: for each coefficient a sign bit,
+// the low 7 bits of the absolute value, and then the high bits in unary,
+// terminated by a 1. The encoding must use exactly all of `data`, with any
+// unused bits of the last byte set to zero, and "-0" isn't allowed.
+fn decompress(data: &[u8], n: usize) -> Option<Vec<i16>> {
+    let mut bytes = data.iter();
+    let mut s2 = Vec::with_capacity(n);
+    let mut acc = 0u32;
+    let mut acc_len = 0;
+    for _ in 0..n {
+        acc = (acc << 8) | u32::from(*bytes.next()?);
+        let b = acc >> acc_len;
+        let negative = b & 0x80 != 0;
+        let mut m = b & 0x7f;
+        loop {
+            if acc_len == 0 {
+                acc = (acc << 8) | u32::from(*bytes.next()?);
+                acc_len = 8;
+            }
+            acc_len -= 1;
+            if (acc >> acc_len) & 1 != 0 {
+                break;
+            }
+            m += 128;
+            if m > 2047 {
+                return None;
+            }
+        }
+        if negative && m == 0 {
+            return None;
+        }
+        let m = m as i16;
+        s2.push(if negative { -m } else { m });
+    }
+    if bytes.next().is_some() || acc & ((1 << acc_len) - 1) != 0 {
+        return None;
+    }
+    Some(s2)
+}
+
+// HashToPoint: SHAKE256 over the nonce and message, read as 16-bit
+// big-endian values, keeping those below 5q reduced modulo q.
+fn hash_to_point(nonce: &[u8], data: &[u8], n: usize) -> CryptographyResult<Vec<u32>> {
+    // Each value is kept with probability 5q / 2^16, so twice as many values
+    // as needed is almost always enough. Otherwise a longer output is
+    // squeezed, which starts with the same bytes.
+    let mut length = 4 * n;
+    loop {
+        let mut output = vec![0; length];
+        let mut h = openssl::hash::Hasher::new(openssl::hash::MessageDigest::shake_256())?;
+        h.update(nonce)?;
+        h.update(data)?;
+        h.finish_xof(&mut output)?;
+
+        let c = output
+            .chunks_exact(2)
+            .map(|w| u32::from(u16::from_be_bytes([w[0], w[1]])))
+            .filter(|&w| w < 5 * Q)
+            .map(|w| w % Q)
+            .take(n)
+            .collect::<Vec<_>>();
+        if c.len() == n {
+            return Ok(c);
+        }
+        length *= 2;
+    }
+}
+
+// Checks that s1 = c - s2 * h mod (q, x^n + 1) and s2 are short enough.
+fn is_valid(variant: Variant, h: &[u16], c: &[u32], s2: &[i16]) -> bool {
+    let n = variant.n();
+    // The positive and negative (wrapped around x^n = -1) parts of each
+    // coefficient of s2 * h, with s2 reduced modulo q.
+    let mut positive = vec![0u64; n];
+    let mut negative = vec![0u64; n];
+    for (i, &s) in s2.iter().enumerate() {
+        let s = u64::from((i32::from(s) + Q as i32) as u32 % Q);
+        if s == 0 {
+            continue;
+        }
+        for (j, &hj) in h.iter().enumerate() {
+            let product = s * u64::from(hj);
+            if i + j < n {
+                positive[i + j] += product;
+            } else {
+                negative[i + j - n] += product;
+            }
+        }
+    }
+
+    let q = u64::from(Q);
+    let mut norm = s2
+        .iter()
+        .map(|&s| u64::from(s.unsigned_abs()).pow(2))
+        .sum::<u64>();
+    for ((&c, positive), negative) in c.iter().zip(positive).zip(negative) {
+        let product = (positive % q + q - negative % q) % q;
+        let s1 = (u64::from(c) + q - product) % q;
+        // Centered in [-q/2, q/2].
+        let s1 = if s1 > q / 2 { q - s1 } else { s1 };
+        norm += s1 * s1;
+    }
+    norm <= variant.bound()
+}
+
+#[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.falcon")]
+pub(crate) struct FalconPublicKey {
+    variant: Variant,
+    h: Vec<u16>,
+    raw: Vec<u8>,
+}
+
+fn invalid_public_key() -> CryptographyError {
+    CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+        "Invalid Falcon public key",
+    ))
+}
+
+#[pyo3::prelude::pyfunction]
+fn from_public_bytes(data: &[u8]) -> CryptographyResult<FalconPublicKey> {
+    // The header byte is 0000nnnn, where n is log2 of the degree.
+    let variant = data
+        .first()
+        .and_then(|&b| Variant::from_logn(b))
+        .ok_or_else(invalid_public_key)?;
+    if data.len() != variant.public_key_length() {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "A {} public key is {} bytes long",
+                variant.name(),
+                variant.public_key_length()
+            )),
+        ));
+    }
+    let h = decode_public_key(variant, &data[1..]).ok_or_else(invalid_public_key)?;
+    Ok(FalconPublicKey {
+        variant,
+        h,
+        raw: data.to_vec(),
+    })
+}
+
+#[pyo3::prelude::pymethods]
+impl FalconPublicKey {
+    #[getter]
+    fn parameter_set<'p>(&self, py: pyo3::Python<'p>) -> pyo3::PyResult<&'p pyo3::PyAny> {
+        types::FALCON_PARAMETER_SET
+            .get(py)?
+            .call1((self.variant.name(),))
+    }
+
+    fn verify(
+        &self,
+        py: pyo3::Python<'_>,
+        signature: CffiBuf<'_>,
+        data: CffiBuf<'_>,
+    ) -> CryptographyResult<()> {
+        let signature = signature.as_bytes();
+        // The header byte is 0cc1nnnn, where cc = 01 is the compressed
+        // encoding of s2, followed by the nonce and s2.
+        let valid = signature.len() > 1 + NONCE_LENGTH
+            && signature[0] == 0x30 + self.variant.logn()
+            && match decompress(&signature[1 + NONCE_LENGTH..], self.variant.n()) {
+                Some(s2) => {
+                    let c = hash_to_point(
+                        &signature[1..1 + NONCE_LENGTH],
+                        data.as_bytes(),
+                        self.variant.n(),
+                    )?;
+                    py.allow_threads(|| is_valid(self.variant, &self.h, &c, &s2))
+                }
+                None => false,
+            };
+        if !valid {
+            return Err(CryptographyError::from(
+                exceptions::InvalidSignature::new_err(()),
+            ));
+        }
+        Ok(())
+    }
+
+    fn public_bytes_raw<'p>(&self, py: pyo3::Python<'p>) -> &'p pyo3::types::PyBytes {
+        pyo3::types::PyBytes::new(py, &self.raw)
+    }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> bool {
+        self.raw == other.raw
+    }
+
+    fn __copy__(slf: pyo3::PyRef<'_, Self>) -> pyo3::PyRef<'_, Self> {
+        slf
+    }
+}
+
+pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let m = pyo3::prelude::PyModule::new(py, "falcon")?;
+    m.add_function(pyo3::wrap_pyfunction!(from_public_bytes, m)?)?;
+
+    m.add_class::<FalconPublicKey>()?;
+
+    Ok(m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_public_key, decompress, Variant};
+
+    #[test]
+    fn test_decompress() {
+        assert_eq!(decompress(&[0x01, 0xc1, 0x20], 2), Some(vec![1, -130]));
+        assert_eq!(decompress(&[0x00, 0x80, 0x40], 2), Some(vec![0, 0]));
+        assert_eq!(decompress(&[0x00, 0x80], 1), Some(vec![0]));
+        // Truncated, trailing data and non-zero padding.
+        assert_eq!(decompress(&[0x01, 0xc1], 2), None);
+        assert_eq!(decompress(&[0x00, 0x80, 0x00], 1), None);
+        assert_eq!(decompress(&[0x00, 0x81], 1), None);
+        // "-0" and values above 2047.
+        assert_eq!(decompress(&[0x80, 0x80], 1), None);
+        assert_eq!(decompress(&[0x00, 0x00, 0x00], 1), None);
+    }
+
+    #[test]
+    fn test_decode_public_key() {
+        let n = Variant::Falcon512.n();
+        let h = decode_public_key(Variant::Falcon512, &vec![0; n * 14 / 8]).unwrap();
+        assert_eq!(h, vec![0; n]);
+
+        // q - 1 = 12288 is the largest allowed coefficient.
+        let mut data = vec![0; n * 14 / 8];
+        data[0] = 0xc0;
+        data[1] = 0x00;
+        assert_eq!(
+            decode_public_key(Variant::Falcon512, &data).unwrap()[0],
+            12288
+        );
+        data[1] = 0x04;
+        assert_eq!(decode_public_key(Variant::Falcon512, &data), None);
+    }
+}
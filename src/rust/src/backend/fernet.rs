@@ -0,0 +1,548 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+use crate::buf::CffiBuf;
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::{exceptions, types};
+
+const VERSION: u8 = 0x80;
+const VERSION_2: u8 = 0x81;
+const MAX_CLOCK_SKEW: i128 = 60;
+const IV_LENGTH: usize = 16;
+const HMAC_LENGTH: usize = 32;
+// version || algorithm || key id || timestamp
+const V2_HEADER_LENGTH: usize = 14;
+const TAG_LENGTH: usize = 16;
+
+#[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.fernet")]
+pub(crate) struct Fernet {
+    signing_key: Vec<u8>,
+    encryption_key: Vec<u8>,
+}
+
+fn invalid_token() -> CryptographyError {
+    CryptographyError::from(exceptions::InvalidToken::new_err(()))
+}
+
+fn b64_encode(data: &[u8]) -> String {
+    openssl::base64::encode_block(data)
+        .chars()
+        .map(|c| match c {
+            '+' => '-',
+            '/' => '_',
+            c => c,
+        })
+        .collect()
+}
+
+fn b64_decode(data: &[u8]) -> Option<Vec<u8>> {
+    // Like `base64.urlsafe_b64decode` we accept both alphabets, and
+    // whitespace is ignored so tokens read from files keep working.
+    let mut normalized = String::with_capacity(data.len());
+    for &b in data {
+        match b {
+            b'-' => normalized.push('+'),
+            b'_' => normalized.push('/'),
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'+' | b'/' | b'=' => {
+                normalized.push(b as char)
+            }
+            b' ' | b'\t' | b'\r' | b'\n' => {}
+            _ => return None,
+        }
+    }
+    if normalized.len() % 4 != 0 {
+        return None;
+    }
+    openssl::base64::decode_block(&normalized).ok()
+}
+
+// Returns the decoded token and its (unverified) timestamp.
+fn decode_token(token: &pyo3::PyAny) -> CryptographyResult<(Vec<u8>, u64)> {
+    let data = decode_token_bytes(token)?;
+    let timestamp = match data.first() {
+        Some(&VERSION) if data.len() >= 9 => u64::from_be_bytes(data[1..9].try_into().unwrap()),
+        Some(&VERSION_2) if data.len() >= V2_HEADER_LENGTH => {
+            u64::from_be_bytes(data[6..V2_HEADER_LENGTH].try_into().unwrap())
+        }
+        _ => return Err(invalid_token()),
+    };
+    Ok((data, timestamp))
+}
+
+fn decode_token_bytes(token: &pyo3::PyAny) -> CryptographyResult<Vec<u8>> {
+    let raw = if let Ok(s) = token.downcast::<pyo3::types::PyString>() {
+        s.to_str()?.as_bytes()
+    } else if let Ok(b) = token.downcast::<pyo3::types::PyBytes>() {
+        b.as_bytes()
+    } else {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyTypeError::new_err("token must be bytes or str"),
+        ));
+    };
+
+    b64_decode(raw).ok_or_else(invalid_token)
+}
+
+fn check_time(timestamp: u64, time_info: Option<(i64, i64)>) -> CryptographyResult<()> {
+    if let Some((ttl, current_time)) = time_info {
+        let (timestamp, ttl, current_time) = (
+            i128::from(timestamp),
+            i128::from(ttl),
+            i128::from(current_time),
+        );
+        if timestamp + ttl < current_time || current_time + MAX_CLOCK_SKEW < timestamp {
+            return Err(invalid_token());
+        }
+    }
+    Ok(())
+}
+
+impl Fernet {
+    fn sign(&self, data: &[u8]) -> CryptographyResult<cryptography_openssl::hmac::DigestBytes> {
+        let mut h = cryptography_openssl::hmac::Hmac::new(
+            &self.signing_key,
+            openssl::hash::MessageDigest::sha256(),
+        )?;
+        h.update(data)?;
+        Ok(h.finish()?)
+    }
+
+    fn signature_matches(&self, data: &[u8]) -> CryptographyResult<bool> {
+        if data[0] != VERSION || data.len() < 9 + HMAC_LENGTH {
+            return Ok(false);
+        }
+        let (signed, signature) = data.split_at(data.len() - HMAC_LENGTH);
+        Ok(openssl::memcmp::eq(&self.sign(signed)?, signature))
+    }
+
+    // `data` must already have had its signature verified.
+    fn decrypt_verified(&self, data: &[u8]) -> CryptographyResult<Vec<u8>> {
+        if data.len() < 9 + IV_LENGTH + HMAC_LENGTH {
+            return Err(invalid_token());
+        }
+        let iv = &data[9..9 + IV_LENGTH];
+        let ciphertext = &data[9 + IV_LENGTH..data.len() - HMAC_LENGTH];
+        openssl::symm::decrypt(
+            openssl::symm::Cipher::aes_128_cbc(),
+            &self.encryption_key,
+            Some(iv),
+            ciphertext,
+        )
+        .map_err(|_| invalid_token())
+    }
+
+    fn encrypt_token(&self, data: &[u8], timestamp: u64, iv: &[u8]) -> CryptographyResult<String> {
+        if iv.len() != IV_LENGTH {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("iv must be 16 bytes"),
+            ));
+        }
+        let ciphertext = openssl::symm::encrypt(
+            openssl::symm::Cipher::aes_128_cbc(),
+            &self.encryption_key,
+            Some(iv),
+            data,
+        )?;
+
+        let mut token = Vec::with_capacity(9 + IV_LENGTH + ciphertext.len() + HMAC_LENGTH);
+        token.push(VERSION);
+        token.extend_from_slice(&timestamp.to_be_bytes());
+        token.extend_from_slice(iv);
+        token.extend_from_slice(&ciphertext);
+        let signature = self.sign(&token)?;
+        token.extend_from_slice(&signature);
+        Ok(b64_encode(&token))
+    }
+}
+
+#[pyo3::prelude::pymethods]
+impl Fernet {
+    #[new]
+    fn new(key: CffiBuf<'_>) -> CryptographyResult<Fernet> {
+        let key = key.as_bytes();
+        if key.len() != 32 {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("Fernet key must be 32 bytes."),
+            ));
+        }
+        Ok(Fernet {
+            signing_key: key[..16].to_vec(),
+            encryption_key: key[16..].to_vec(),
+        })
+    }
+
+    fn encrypt<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        data: CffiBuf<'_>,
+        current_time: u64,
+        iv: CffiBuf<'_>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let token = self.encrypt_token(data.as_bytes(), current_time, iv.as_bytes())?;
+        Ok(pyo3::types::PyBytes::new(py, token.as_bytes()))
+    }
+
+    fn extract_timestamp(&self, token: &pyo3::PyAny) -> CryptographyResult<u64> {
+        let (data, timestamp) = decode_token(token)?;
+        // Verify the token was not tampered with.
+        if !self.signature_matches(&data)? {
+            return Err(invalid_token());
+        }
+        Ok(timestamp)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum V2Algorithm {
+    Aes256Gcm,
+    XChaCha20Poly1305,
+}
+
+impl V2Algorithm {
+    fn from_id(id: u8) -> Option<V2Algorithm> {
+        match id {
+            1 => Some(V2Algorithm::Aes256Gcm),
+            2 => Some(V2Algorithm::XChaCha20Poly1305),
+            _ => None,
+        }
+    }
+
+    fn id(self) -> u8 {
+        match self {
+            V2Algorithm::Aes256Gcm => 1,
+            V2Algorithm::XChaCha20Poly1305 => 2,
+        }
+    }
+
+    fn nonce_length(self) -> usize {
+        match self {
+            V2Algorithm::Aes256Gcm => 12,
+            V2Algorithm::XChaCha20Poly1305 => 24,
+        }
+    }
+}
+
+fn quarter_round(s: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    s[a] = s[a].wrapping_add(s[b]);
+    s[d] = (s[d] ^ s[a]).rotate_left(16);
+    s[c] = s[c].wrapping_add(s[d]);
+    s[b] = (s[b] ^ s[c]).rotate_left(12);
+    s[a] = s[a].wrapping_add(s[b]);
+    s[d] = (s[d] ^ s[a]).rotate_left(8);
+    s[c] = s[c].wrapping_add(s[d]);
+    s[b] = (s[b] ^ s[c]).rotate_left(7);
+}
+
+// HChaCha20 from draft-irtf-cfrg-xchacha, Section 2.2. OpenSSL doesn't
+// expose XChaCha20, so the subkey is derived here and the regular
+// ChaCha20-Poly1305 is used with the remaining 8 bytes of the nonce.
+fn hchacha20(key: &[u8], nonce: &[u8]) -> [u8; 32] {
+    let mut state = [0u32; 16];
+    state[..4].copy_from_slice(&[0x61707865, 0x3320646e, 0x79622d32, 0x6b206574]);
+    for (s, k) in state[4..12].iter_mut().zip(key.chunks(4)) {
+        *s = u32::from_le_bytes(k.try_into().unwrap());
+    }
+    for (s, n) in state[12..].iter_mut().zip(nonce.chunks(4)) {
+        *s = u32::from_le_bytes(n.try_into().unwrap());
+    }
+    for _ in 0..10 {
+        quarter_round(&mut state, 0, 4, 8, 12);
+        quarter_round(&mut state, 1, 5, 9, 13);
+        quarter_round(&mut state, 2, 6, 10, 14);
+        quarter_round(&mut state, 3, 7, 11, 15);
+        quarter_round(&mut state, 0, 5, 10, 15);
+        quarter_round(&mut state, 1, 6, 11, 12);
+        quarter_round(&mut state, 2, 7, 8, 13);
+        quarter_round(&mut state, 3, 4, 9, 14);
+    }
+    let mut out = [0; 32];
+    for (o, s) in out
+        .chunks_mut(4)
+        .zip(state[..4].iter().chain(state[12..].iter()))
+    {
+        o.copy_from_slice(&s.to_le_bytes());
+    }
+    out
+}
+
+#[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.fernet")]
+pub(crate) struct FernetV2 {
+    key: Vec<u8>,
+    algorithm: V2Algorithm,
+    key_id: u32,
+}
+
+impl FernetV2 {
+    // Returns the cipher, key and nonce to use with OpenSSL for `nonce`.
+    fn cipher_params(&self, nonce: &[u8]) -> (openssl::symm::Cipher, Vec<u8>, Vec<u8>) {
+        match self.algorithm {
+            V2Algorithm::Aes256Gcm => (
+                openssl::symm::Cipher::aes_256_gcm(),
+                self.key.clone(),
+                nonce.to_vec(),
+            ),
+            #[cfg(not(CRYPTOGRAPHY_IS_BORINGSSL))]
+            V2Algorithm::XChaCha20Poly1305 => {
+                let subkey = hchacha20(&self.key, &nonce[..16]);
+                let mut chacha_nonce = vec![0; 4];
+                chacha_nonce.extend_from_slice(&nonce[16..]);
+                (
+                    openssl::symm::Cipher::chacha20_poly1305(),
+                    subkey.to_vec(),
+                    chacha_nonce,
+                )
+            }
+            #[cfg(CRYPTOGRAPHY_IS_BORINGSSL)]
+            V2Algorithm::XChaCha20Poly1305 => unreachable!(),
+        }
+    }
+
+    fn matches(&self, data: &[u8]) -> bool {
+        data[0] == VERSION_2
+            && data.len() >= V2_HEADER_LENGTH
+            && data[1] == self.algorithm.id()
+            && data[2..6] == self.key_id.to_be_bytes()
+    }
+
+    fn decrypt_token(&self, data: &[u8]) -> CryptographyResult<Vec<u8>> {
+        let nonce_length = self.algorithm.nonce_length();
+        if data.len() < V2_HEADER_LENGTH + nonce_length + TAG_LENGTH {
+            return Err(invalid_token());
+        }
+        let (aad, rest) = data.split_at(V2_HEADER_LENGTH + nonce_length);
+        let nonce = &aad[V2_HEADER_LENGTH..];
+        let (ciphertext, tag) = rest.split_at(rest.len() - TAG_LENGTH);
+        let (cipher, key, nonce) = self.cipher_params(nonce);
+        openssl::symm::decrypt_aead(cipher, &key, Some(&nonce), aad, ciphertext, tag)
+            .map_err(|_| invalid_token())
+    }
+
+    fn encrypt_token(
+        &self,
+        data: &[u8],
+        timestamp: u64,
+        nonce: &[u8],
+    ) -> CryptographyResult<String> {
+        if nonce.len() != self.algorithm.nonce_length() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "nonce must be {} bytes",
+                    self.algorithm.nonce_length()
+                )),
+            ));
+        }
+        let mut token =
+            Vec::with_capacity(V2_HEADER_LENGTH + nonce.len() + data.len() + TAG_LENGTH);
+        token.push(VERSION_2);
+        token.push(self.algorithm.id());
+        token.extend_from_slice(&self.key_id.to_be_bytes());
+        token.extend_from_slice(&timestamp.to_be_bytes());
+        token.extend_from_slice(nonce);
+
+        let (cipher, key, nonce) = self.cipher_params(nonce);
+        let mut tag = [0; TAG_LENGTH];
+        let ciphertext =
+            openssl::symm::encrypt_aead(cipher, &key, Some(&nonce), &token, data, &mut tag)?;
+        token.extend_from_slice(&ciphertext);
+        token.extend_from_slice(&tag);
+        Ok(b64_encode(&token))
+    }
+}
+
+#[pyo3::prelude::pymethods]
+impl FernetV2 {
+    #[new]
+    fn new(key: CffiBuf<'_>, algorithm: u8, key_id: Option<u32>) -> CryptographyResult<FernetV2> {
+        let key = key.as_bytes();
+        if key.len() != 32 {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("Fernet key must be 32 bytes."),
+            ));
+        }
+        let algorithm = V2Algorithm::from_id(algorithm).ok_or_else(|| {
+            exceptions::UnsupportedAlgorithm::new_err((
+                "Unknown Fernet v2 algorithm",
+                exceptions::Reasons::UNSUPPORTED_CIPHER,
+            ))
+        })?;
+        if algorithm == V2Algorithm::XChaCha20Poly1305
+            && (cfg!(CRYPTOGRAPHY_IS_BORINGSSL) || cryptography_openssl::fips::is_enabled())
+        {
+            return Err(CryptographyError::from(
+                exceptions::UnsupportedAlgorithm::new_err((
+                    "XChaCha20Poly1305 is not supported by this version of OpenSSL",
+                    exceptions::Reasons::UNSUPPORTED_CIPHER,
+                )),
+            ));
+        }
+        let key_id = match key_id {
+            Some(key_id) => key_id,
+            // By default the key id is derived from the key, so that tokens
+            // select the right key without any configuration.
+            None => {
+                let digest = openssl::hash::hash(openssl::hash::MessageDigest::sha256(), key)?;
+                u32::from_be_bytes(digest[..4].try_into().unwrap())
+            }
+        };
+        Ok(FernetV2 {
+            key: key.to_vec(),
+            algorithm,
+            key_id,
+        })
+    }
+
+    #[getter]
+    fn key_id(&self) -> u32 {
+        self.key_id
+    }
+
+    #[getter]
+    fn nonce_length(&self) -> usize {
+        self.algorithm.nonce_length()
+    }
+
+    fn encrypt<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        data: CffiBuf<'_>,
+        current_time: u64,
+        nonce: CffiBuf<'_>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let token = self.encrypt_token(data.as_bytes(), current_time, nonce.as_bytes())?;
+        Ok(pyo3::types::PyBytes::new(py, token.as_bytes()))
+    }
+
+    fn extract_timestamp(&self, token: &pyo3::PyAny) -> CryptographyResult<u64> {
+        let (data, timestamp) = decode_token(token)?;
+        // Verify the token was not tampered with.
+        if !self.matches(&data) {
+            return Err(invalid_token());
+        }
+        self.decrypt_token(&data)?;
+        Ok(timestamp)
+    }
+}
+
+#[derive(pyo3::FromPyObject)]
+enum AnyFernet<'a> {
+    V1(pyo3::PyRef<'a, Fernet>),
+    V2(pyo3::PyRef<'a, FernetV2>),
+}
+
+impl AnyFernet<'_> {
+    fn encrypt_token(
+        &self,
+        py: pyo3::Python<'_>,
+        data: &[u8],
+        timestamp: u64,
+    ) -> CryptographyResult<String> {
+        let nonce_length = match self {
+            AnyFernet::V1(_) => IV_LENGTH,
+            AnyFernet::V2(f) => f.algorithm.nonce_length(),
+        };
+        let nonce = types::OS_URANDOM
+            .get(py)?
+            .call1((nonce_length,))?
+            .extract::<&[u8]>()?;
+        match self {
+            AnyFernet::V1(f) => f.encrypt_token(data, timestamp, nonce),
+            AnyFernet::V2(f) => f.encrypt_token(data, timestamp, nonce),
+        }
+    }
+}
+
+// Finds the key for `data` and decrypts it. For version 1 tokens only the
+// HMAC is computed for each candidate, version 2 tokens carry the id of the
+// key they were encrypted with.
+fn decrypt_with(fernets: &[AnyFernet<'_>], data: &[u8]) -> CryptographyResult<Vec<u8>> {
+    for f in fernets {
+        match f {
+            AnyFernet::V1(f) if f.signature_matches(data)? => {
+                return f.decrypt_verified(data);
+            }
+            AnyFernet::V2(f) if f.matches(data) => {
+                // Several keys may share an id, keep looking if this one
+                // doesn't authenticate the token.
+                if let Ok(plaintext) = f.decrypt_token(data) {
+                    return Ok(plaintext);
+                }
+            }
+            _ => {}
+        }
+    }
+    Err(invalid_token())
+}
+
+#[pyo3::prelude::pyfunction]
+fn decrypt<'p>(
+    py: pyo3::Python<'p>,
+    fernets: Vec<AnyFernet<'_>>,
+    token: &pyo3::PyAny,
+    time_info: Option<(i64, i64)>,
+) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+    let (data, timestamp) = decode_token(token)?;
+    check_time(timestamp, time_info)?;
+    let plaintext = decrypt_with(&fernets, &data)?;
+    Ok(pyo3::types::PyBytes::new(py, &plaintext))
+}
+
+#[pyo3::prelude::pyfunction]
+fn rotate<'p>(
+    py: pyo3::Python<'p>,
+    fernets: Vec<AnyFernet<'_>>,
+    token: &pyo3::PyAny,
+) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+    let (data, timestamp) = decode_token(token)?;
+    let plaintext = decrypt_with(&fernets, &data)?;
+    let token = fernets[0].encrypt_token(py, &plaintext, timestamp)?;
+    Ok(pyo3::types::PyBytes::new(py, token.as_bytes()))
+}
+
+pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let m = pyo3::prelude::PyModule::new(py, "fernet")?;
+    m.add_function(pyo3::wrap_pyfunction!(decrypt, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(rotate, m)?)?;
+
+    m.add_class::<Fernet>()?;
+    m.add_class::<FernetV2>()?;
+
+    Ok(m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{b64_decode, b64_encode, hchacha20};
+
+    #[test]
+    fn test_b64_roundtrip() {
+        for data in [&b""[..], b"\xfb\xff", b"\xfb\xff\xfe", b"abcd"] {
+            assert_eq!(b64_decode(b64_encode(data).as_bytes()).unwrap(), data);
+        }
+        assert_eq!(b64_encode(b"\xfb\xff"), "-_8=");
+    }
+
+    #[test]
+    fn test_b64_decode_invalid() {
+        assert_eq!(b64_decode(b"-_8=\n").unwrap(), b"\xfb\xff");
+        assert_eq!(b64_decode(b"+/8=").unwrap(), b"\xfb\xff");
+        assert!(b64_decode(b"-_8").is_none());
+        assert!(b64_decode(b"-_8*").is_none());
+    }
+
+    #[test]
+    fn test_hchacha20() {
+        // draft-irtf-cfrg-xchacha-03, Section 2.2.1
+        let key = (0..32).collect::<Vec<u8>>();
+        let nonce = [
+            0x00, 0x00, 0x00, 0x09, 0x00, 0x00, 0x00, 0x4a, 0x00, 0x00, 0x00, 0x00, 0x31, 0x41,
+            0x59, 0x27,
+        ];
+        let expected = [
+            0x82, 0x41, 0x3b, 0x42, 0x27, 0xb2, 0x7b, 0xfe, 0xd3, 0x0e, 0x42, 0x50, 0x8a, 0x87,
+            0x7d, 0x73, 0xa0, 0xf9, 0xe4, 0xd5, 0x8a, 0x74, 0xa8, 0x53, 0xc1, 0x2e, 0xc4, 0x13,
+            0x26, 0xd3, 0xec, 0xdc,
+        ];
+        assert_eq!(hchacha20(&key, &nonce), expected);
+    }
+}
@@ -0,0 +1,377 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Hybrid ECDHE and ML-KEM key exchange, using the TLS key shares from
+//! https://datatracker.ietf.org/doc/draft-ietf-tls-ecdhe-mlkem/
+//!
+//! The initiator's key share is its ECDH public key and its ML-KEM
+//! encapsulation key, and the responder's is its ECDH public key and the
+//! ML-KEM ciphertext. The shared secret is the ECDH and ML-KEM shared secrets
+//! concatenated in the same order as the key shares, and it's only ever
+//! passed to a KDF.
+
+use cryptography_openssl::kem;
+use cryptography_openssl::mlkem::{self, Variant};
+
+use crate::backend::kdf;
+use crate::buf::CffiBuf;
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::types;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Group {
+    X25519MlKem768,
+    SecP256r1MlKem768,
+    SecP384r1MlKem1024,
+}
+
+impl Group {
+    fn from_name(name: &str) -> Option<Group> {
+        match name {
+            "X25519MLKEM768" => Some(Group::X25519MlKem768),
+            "SecP256r1MLKEM768" => Some(Group::SecP256r1MlKem768),
+            "SecP384r1MLKEM1024" => Some(Group::SecP384r1MlKem1024),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Group::X25519MlKem768 => "X25519MLKEM768",
+            Group::SecP256r1MlKem768 => "SecP256r1MLKEM768",
+            Group::SecP384r1MlKem1024 => "SecP384r1MLKEM1024",
+        }
+    }
+
+    fn variant(self) -> Variant {
+        match self {
+            Group::X25519MlKem768 | Group::SecP256r1MlKem768 => Variant::MlKem768,
+            Group::SecP384r1MlKem1024 => Variant::MlKem1024,
+        }
+    }
+
+    fn curve(self) -> Option<openssl::nid::Nid> {
+        match self {
+            Group::X25519MlKem768 => None,
+            Group::SecP256r1MlKem768 => Some(openssl::nid::Nid::X9_62_PRIME256V1),
+            Group::SecP384r1MlKem1024 => Some(openssl::nid::Nid::SECP384R1),
+        }
+    }
+
+    // The length of an ECDH private key, which for the NIST curves is the
+    // big-endian private scalar.
+    fn ecdh_private_key_length(self) -> usize {
+        match self {
+            Group::X25519MlKem768 | Group::SecP256r1MlKem768 => 32,
+            Group::SecP384r1MlKem1024 => 48,
+        }
+    }
+
+    // The length of an ECDH public key, which for the NIST curves is an
+    // uncompressed point.
+    fn ecdh_public_key_length(self) -> usize {
+        match self {
+            Group::X25519MlKem768 => 32,
+            Group::SecP256r1MlKem768 => 65,
+            Group::SecP384r1MlKem1024 => 97,
+        }
+    }
+
+    // X25519MLKEM768 puts the ML-KEM part first, so that the shared secret
+    // starts with a FIPS approved one. The other groups put ECDH first.
+    fn mlkem_first(self) -> bool {
+        self == Group::X25519MlKem768
+    }
+
+    // Joins the ECDH and ML-KEM parts of a key share or shared secret.
+    fn join(self, ecdh: &[u8], mlkem: &[u8]) -> Vec<u8> {
+        if self.mlkem_first() {
+            [mlkem, ecdh].concat()
+        } else {
+            [ecdh, mlkem].concat()
+        }
+    }
+
+    // Splits a key share whose ML-KEM part is `mlkem_length` bytes long into
+    // its ECDH and ML-KEM parts.
+    fn split(self, key_share: &[u8], mlkem_length: usize) -> CryptographyResult<(&[u8], &[u8])> {
+        let length = self.ecdh_public_key_length() + mlkem_length;
+        if key_share.len() != length {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "A {} key share must be {} bytes long",
+                    self.name(),
+                    length
+                )),
+            ));
+        }
+        if self.mlkem_first() {
+            let (mlkem, ecdh) = key_share.split_at(mlkem_length);
+            Ok((ecdh, mlkem))
+        } else {
+            Ok(key_share.split_at(self.ecdh_public_key_length()))
+        }
+    }
+}
+
+fn group_from_py(py: pyo3::Python<'_>, group: &pyo3::PyAny) -> CryptographyResult<Group> {
+    if !group.is_instance(types::HYBRID_GROUP.get(py)?)? {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyTypeError::new_err("group must be a HybridGroup"),
+        ));
+    }
+    // The enum's values are the names of the TLS groups.
+    let name = group
+        .getattr(pyo3::intern!(py, "value"))?
+        .extract::<&str>()?;
+    Ok(Group::from_name(name).unwrap())
+}
+
+fn invalid_key_share() -> CryptographyError {
+    CryptographyError::from(pyo3::exceptions::PyValueError::new_err("Invalid key share"))
+}
+
+fn ec_group(nid: openssl::nid::Nid) -> CryptographyResult<openssl::ec::EcGroup> {
+    Ok(openssl::ec::EcGroup::from_curve_name(nid)?)
+}
+
+fn generate_ecdh_key(
+    group: Group,
+) -> CryptographyResult<openssl::pkey::PKey<openssl::pkey::Private>> {
+    match group.curve() {
+        Some(nid) => {
+            let curve = ec_group(nid)?;
+            let ec = openssl::ec::EcKey::generate(&curve)?;
+            Ok(openssl::pkey::PKey::from_ec_key(ec)?)
+        }
+        None => Ok(openssl::pkey::PKey::generate_x25519()?),
+    }
+}
+
+fn ecdh_private_key_from_bytes(
+    group: Group,
+    data: &[u8],
+) -> CryptographyResult<openssl::pkey::PKey<openssl::pkey::Private>> {
+    match group.curve() {
+        Some(nid) => {
+            let curve = ec_group(nid)?;
+            let private_value = openssl::bn::BigNum::from_slice(data)?;
+            let mut point = openssl::ec::EcPoint::new(&curve)?;
+            let bn_ctx = openssl::bn::BigNumContext::new()?;
+            point.mul_generator(&curve, &private_value, &bn_ctx)?;
+            // This checks that the scalar is in range.
+            let ec = openssl::ec::EcKey::from_private_components(&curve, &private_value, &point)
+                .and_then(|ec| ec.check_key().map(|()| ec))
+                .map_err(|_| pyo3::exceptions::PyValueError::new_err("Invalid private key"))?;
+            Ok(openssl::pkey::PKey::from_ec_key(ec)?)
+        }
+        None => Ok(openssl::pkey::PKey::private_key_from_raw_bytes(
+            data,
+            openssl::pkey::Id::X25519,
+        )?),
+    }
+}
+
+fn ecdh_private_bytes(
+    group: Group,
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+) -> CryptographyResult<Vec<u8>> {
+    match group.curve() {
+        Some(_) => Ok(pkey
+            .ec_key()?
+            .private_key()
+            .to_vec_padded(group.ecdh_private_key_length() as i32)?),
+        None => Ok(pkey.raw_private_key()?),
+    }
+}
+
+fn ecdh_public_bytes(
+    group: Group,
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+) -> CryptographyResult<Vec<u8>> {
+    match group.curve() {
+        Some(_) => {
+            let ec = pkey.ec_key()?;
+            let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+            Ok(ec.public_key().to_bytes(
+                ec.group(),
+                openssl::ec::PointConversionForm::UNCOMPRESSED,
+                &mut bn_ctx,
+            )?)
+        }
+        None => Ok(pkey.raw_public_key()?),
+    }
+}
+
+// TLS only allows uncompressed points for the NIST curves, and they must be
+// on the curve.
+fn ecdh_peer_key(
+    group: Group,
+    data: &[u8],
+) -> CryptographyResult<openssl::pkey::PKey<openssl::pkey::Public>> {
+    match group.curve() {
+        Some(nid) => {
+            if data.first() != Some(&0x04) {
+                return Err(invalid_key_share());
+            }
+            let curve = ec_group(nid)?;
+            let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+            let point = openssl::ec::EcPoint::from_bytes(&curve, data, &mut bn_ctx)
+                .map_err(|_| invalid_key_share())?;
+            let ec = openssl::ec::EcKey::from_public_key(&curve, &point)?;
+            ec.check_key().map_err(|_| invalid_key_share())?;
+            Ok(openssl::pkey::PKey::from_ec_key(ec)?)
+        }
+        None => Ok(openssl::pkey::PKey::public_key_from_raw_bytes(
+            data,
+            openssl::pkey::Id::X25519,
+        )?),
+    }
+}
+
+// Fails if an X25519 result is all zeros, i.e. the peer's key is a small
+// order point.
+fn ecdh(
+    private: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+    public: &openssl::pkey::PKeyRef<openssl::pkey::Public>,
+) -> CryptographyResult<Vec<u8>> {
+    let mut deriver = openssl::derive::Deriver::new(private)?;
+    deriver.set_peer(public)?;
+    deriver.derive_to_vec().map_err(|_| {
+        CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+            "Error computing shared key.",
+        ))
+    })
+}
+
+#[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.hybrid")]
+pub(crate) struct HybridPrivateKeyShare {
+    group: Group,
+    mlkem_seed: Vec<u8>,
+    mlkem: openssl::pkey::PKey<openssl::pkey::Private>,
+    ecdh: openssl::pkey::PKey<openssl::pkey::Private>,
+}
+
+#[pyo3::prelude::pyfunction]
+fn generate_key_share(
+    py: pyo3::Python<'_>,
+    group: &pyo3::PyAny,
+) -> CryptographyResult<HybridPrivateKeyShare> {
+    let group = group_from_py(py, group)?;
+    let mut mlkem_seed = vec![0; mlkem::SEED_LENGTH];
+    openssl::rand::rand_bytes(&mut mlkem_seed)?;
+    Ok(HybridPrivateKeyShare {
+        group,
+        mlkem: mlkem::private_key_from_seed(group.variant(), &mlkem_seed)?,
+        mlkem_seed,
+        ecdh: generate_ecdh_key(group)?,
+    })
+}
+
+#[pyo3::prelude::pyfunction]
+fn from_private_bytes(
+    py: pyo3::Python<'_>,
+    group: &pyo3::PyAny,
+    data: CffiBuf<'_>,
+) -> CryptographyResult<HybridPrivateKeyShare> {
+    let group = group_from_py(py, group)?;
+    let length = mlkem::SEED_LENGTH + group.ecdh_private_key_length();
+    if data.as_bytes().len() != length {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "A {} private key share must be {} bytes long",
+                group.name(),
+                length
+            )),
+        ));
+    }
+    let (mlkem_seed, ecdh_key) = data.as_bytes().split_at(mlkem::SEED_LENGTH);
+    Ok(HybridPrivateKeyShare {
+        group,
+        mlkem_seed: mlkem_seed.to_vec(),
+        mlkem: mlkem::private_key_from_seed(group.variant(), mlkem_seed)?,
+        ecdh: ecdh_private_key_from_bytes(group, ecdh_key)?,
+    })
+}
+
+#[pyo3::prelude::pyfunction]
+fn respond<'p>(
+    py: pyo3::Python<'p>,
+    group: &pyo3::PyAny,
+    peer_key_share: CffiBuf<'_>,
+    kdf: &pyo3::PyAny,
+) -> CryptographyResult<(&'p pyo3::types::PyBytes, &'p pyo3::types::PyBytes)> {
+    let group = group_from_py(py, group)?;
+    let (peer_ecdh, peer_mlkem) = group.split(
+        peer_key_share.as_bytes(),
+        group.variant().public_key_length(),
+    )?;
+    let peer_ecdh = ecdh_peer_key(group, peer_ecdh)?;
+    let peer_mlkem = mlkem::public_key_from_raw_bytes(group.variant(), peer_mlkem)
+        .map_err(|_| invalid_key_share())?;
+
+    let (mlkem_secret, mlkem_ciphertext) = kem::encapsulate(&peer_mlkem, None)?;
+    let ephemeral = generate_ecdh_key(group)?;
+    let ecdh_secret = ecdh(&ephemeral, &peer_ecdh)?;
+
+    let key_share = group.join(&ecdh_public_bytes(group, &ephemeral)?, &mlkem_ciphertext);
+    let derived_key = kdf::derive_with_kdf(py, kdf, &group.join(&ecdh_secret, &mlkem_secret))?;
+    Ok((pyo3::types::PyBytes::new(py, &key_share), derived_key))
+}
+
+#[pyo3::prelude::pymethods]
+impl HybridPrivateKeyShare {
+    #[getter]
+    fn group<'p>(&self, py: pyo3::Python<'p>) -> pyo3::PyResult<&'p pyo3::PyAny> {
+        types::HYBRID_GROUP.get(py)?.call1((self.group.name(),))
+    }
+
+    fn key_share<'p>(&self, py: pyo3::Python<'p>) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let key_share = self.group.join(
+            &ecdh_public_bytes(self.group, &self.ecdh)?,
+            &self.mlkem.raw_public_key()?,
+        );
+        Ok(pyo3::types::PyBytes::new(py, &key_share))
+    }
+
+    fn derive<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        peer_key_share: CffiBuf<'_>,
+        kdf: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let (peer_ecdh, mlkem_ciphertext) = self.group.split(
+            peer_key_share.as_bytes(),
+            self.group.variant().ciphertext_length(),
+        )?;
+        let peer_ecdh = ecdh_peer_key(self.group, peer_ecdh)?;
+
+        // ML-KEM decapsulation uses implicit rejection, so a modified
+        // ciphertext results in a different shared secret rather than an
+        // error.
+        let mlkem_secret = kem::decapsulate(&self.mlkem, None, mlkem_ciphertext)?;
+        let ecdh_secret = ecdh(&self.ecdh, &peer_ecdh)?;
+        kdf::derive_with_kdf(py, kdf, &self.group.join(&ecdh_secret, &mlkem_secret))
+    }
+
+    fn private_bytes_raw<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let mut raw_bytes = self.mlkem_seed.clone();
+        raw_bytes.extend_from_slice(&ecdh_private_bytes(self.group, &self.ecdh)?);
+        Ok(pyo3::types::PyBytes::new(py, &raw_bytes))
+    }
+}
+
+pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let m = pyo3::prelude::PyModule::new(py, "hybrid")?;
+    m.add_function(pyo3::wrap_pyfunction!(generate_key_share, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(from_private_bytes, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(respond, m)?)?;
+
+    m.add_class::<HybridPrivateKeyShare>()?;
+
+    Ok(m)
+}
@@ -0,0 +1,494 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! JSON Web Keys, as described in RFC 7517, for the key types from RFC 7518
+//! (RSA and EC) and RFC 8037 (OKP).
+
+use crate::backend::{hashes, keys};
+use crate::buf::CffiBuf;
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::{exceptions, types};
+
+fn invalid_jwk(msg: String) -> CryptographyError {
+    CryptographyError::from(pyo3::exceptions::PyValueError::new_err(msg))
+}
+
+// Binary values in JWKs are base64url encoded without padding, as described
+// in RFC 7515 section 2.
+fn base64url_encode(data: &[u8]) -> String {
+    openssl::base64::encode_block(data)
+        .trim_end_matches('=')
+        .replace('+', "-")
+        .replace('/', "_")
+}
+
+fn base64url_decode(name: &str, data: &str) -> CryptographyResult<Vec<u8>> {
+    let mut encoded = data.replace('-', "+").replace('_', "/");
+    match encoded.len() % 4 {
+        2 => encoded.push_str("=="),
+        3 => encoded.push('='),
+        _ => {}
+    }
+    if data
+        .bytes()
+        .any(|b| !(b.is_ascii_alphanumeric() || b == b'-' || b == b'_'))
+        || encoded.len() % 4 != 0
+    {
+        return Err(invalid_jwk(format!(
+            "The \"{name}\" member of the JWK isn't valid base64url"
+        )));
+    }
+    Ok(openssl::base64::decode_block(&encoded)?)
+}
+
+fn json_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+// Encodes the members as a JSON object with no whitespace and the members
+// sorted, which is the form RFC 7638 requires for computing thumbprints.
+fn encode_json(mut members: Vec<(&str, String)>) -> String {
+    members.sort();
+    let mut out = String::from("{");
+    for (i, (name, value)) in members.iter().enumerate() {
+        if i != 0 {
+            out.push(',');
+        }
+        json_string(&mut out, name);
+        out.push(':');
+        json_string(&mut out, value);
+    }
+    out.push('}');
+    out
+}
+
+fn curve_name(group: &openssl::ec::EcGroupRef) -> CryptographyResult<&'static str> {
+    match group.curve_name() {
+        Some(openssl::nid::Nid::X9_62_PRIME256V1) => Ok("P-256"),
+        Some(openssl::nid::Nid::SECP384R1) => Ok("P-384"),
+        Some(openssl::nid::Nid::SECP521R1) => Ok("P-521"),
+        // From RFC 8812
+        Some(openssl::nid::Nid::SECP256K1) => Ok("secp256k1"),
+        _ => Err(CryptographyError::from(
+            exceptions::UnsupportedAlgorithm::new_err((
+                "Curve is not supported in JWKs",
+                exceptions::Reasons::UNSUPPORTED_ELLIPTIC_CURVE,
+            )),
+        )),
+    }
+}
+
+fn curve_nid(crv: &str) -> CryptographyResult<openssl::nid::Nid> {
+    match crv {
+        "P-256" => Ok(openssl::nid::Nid::X9_62_PRIME256V1),
+        "P-384" => Ok(openssl::nid::Nid::SECP384R1),
+        "P-521" => Ok(openssl::nid::Nid::SECP521R1),
+        "secp256k1" => Ok(openssl::nid::Nid::SECP256K1),
+        _ => Err(CryptographyError::from(
+            exceptions::UnsupportedAlgorithm::new_err((
+                format!("Unsupported JWK curve: {crv}"),
+                exceptions::Reasons::UNSUPPORTED_ELLIPTIC_CURVE,
+            )),
+        )),
+    }
+}
+
+fn okp_curve_name(id: openssl::pkey::Id) -> Option<&'static str> {
+    match id {
+        openssl::pkey::Id::ED25519 => Some("Ed25519"),
+        openssl::pkey::Id::X25519 => Some("X25519"),
+        #[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
+        openssl::pkey::Id::ED448 => Some("Ed448"),
+        #[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
+        openssl::pkey::Id::X448 => Some("X448"),
+        _ => None,
+    }
+}
+
+fn okp_id(crv: &str) -> CryptographyResult<openssl::pkey::Id> {
+    match crv {
+        "Ed25519" => Ok(openssl::pkey::Id::ED25519),
+        "X25519" => Ok(openssl::pkey::Id::X25519),
+        #[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
+        "Ed448" => Ok(openssl::pkey::Id::ED448),
+        #[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
+        "X448" => Ok(openssl::pkey::Id::X448),
+        _ => Err(CryptographyError::from(
+            exceptions::UnsupportedAlgorithm::new_err((
+                format!("Unsupported JWK curve: {crv}"),
+                exceptions::Reasons::UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
+            )),
+        )),
+    }
+}
+
+// The members of the JWK for a public key, which are exactly the members
+// RFC 7638 uses for its thumbprint.
+fn public_members<T: openssl::pkey::HasPublic>(
+    pkey: &openssl::pkey::PKeyRef<T>,
+) -> CryptographyResult<Vec<(&'static str, String)>> {
+    if let Some(crv) = okp_curve_name(pkey.id()) {
+        return Ok(vec![
+            ("kty", "OKP".to_string()),
+            ("crv", crv.to_string()),
+            ("x", base64url_encode(&pkey.raw_public_key()?)),
+        ]);
+    }
+
+    match pkey.id() {
+        openssl::pkey::Id::RSA => {
+            let rsa = pkey.rsa()?;
+            Ok(vec![
+                ("kty", "RSA".to_string()),
+                ("n", base64url_encode(&rsa.n().to_vec())),
+                ("e", base64url_encode(&rsa.e().to_vec())),
+            ])
+        }
+        openssl::pkey::Id::EC => {
+            let ec = pkey.ec_key()?;
+            let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+            let mut x = openssl::bn::BigNum::new()?;
+            let mut y = openssl::bn::BigNum::new()?;
+            ec.public_key()
+                .affine_coordinates(ec.group(), &mut x, &mut y, &mut bn_ctx)?;
+            let field_len = (ec.group().degree() as i32 + 7) / 8;
+            Ok(vec![
+                ("kty", "EC".to_string()),
+                ("crv", curve_name(ec.group())?.to_string()),
+                ("x", base64url_encode(&x.to_vec_padded(field_len)?)),
+                ("y", base64url_encode(&y.to_vec_padded(field_len)?)),
+            ])
+        }
+        _ => Err(CryptographyError::from(
+            exceptions::UnsupportedAlgorithm::new_err((
+                "Key type is not supported in JWKs",
+                exceptions::Reasons::UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
+            )),
+        )),
+    }
+}
+
+pub(crate) fn public_jwk<T: openssl::pkey::HasPublic>(
+    pkey: &openssl::pkey::PKeyRef<T>,
+    kid: Option<&str>,
+) -> CryptographyResult<String> {
+    let mut members = public_members(pkey)?;
+    if let Some(kid) = kid {
+        members.push(("kid", kid.to_string()));
+    }
+    Ok(encode_json(members))
+}
+
+pub(crate) fn private_jwk(
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+    kid: Option<&str>,
+) -> CryptographyResult<String> {
+    let mut members = public_members(pkey)?;
+    if okp_curve_name(pkey.id()).is_some() {
+        members.push(("d", base64url_encode(&pkey.raw_private_key()?)));
+    } else if pkey.id() == openssl::pkey::Id::RSA {
+        let rsa = pkey.rsa()?;
+        if crate::backend::rsa::is_multi_prime(&rsa)? {
+            return Err(CryptographyError::from(
+                exceptions::UnsupportedAlgorithm::new_err((
+                    "Multi-prime RSA JWKs are not supported",
+                    exceptions::Reasons::UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
+                )),
+            ));
+        }
+        members.extend([
+            ("d", base64url_encode(&rsa.d().to_vec())),
+            ("p", base64url_encode(&rsa.p().unwrap().to_vec())),
+            ("q", base64url_encode(&rsa.q().unwrap().to_vec())),
+            ("dp", base64url_encode(&rsa.dmp1().unwrap().to_vec())),
+            ("dq", base64url_encode(&rsa.dmq1().unwrap().to_vec())),
+            ("qi", base64url_encode(&rsa.iqmp().unwrap().to_vec())),
+        ]);
+    } else {
+        let ec = pkey.ec_key()?;
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let mut order = openssl::bn::BigNum::new()?;
+        ec.group().order(&mut order, &mut bn_ctx)?;
+        members.push((
+            "d",
+            base64url_encode(&ec.private_key().to_vec_padded(order.num_bytes())?),
+        ));
+    }
+    if let Some(kid) = kid {
+        members.push(("kid", kid.to_string()));
+    }
+    Ok(encode_json(members))
+}
+
+struct Jwk<'a> {
+    object: &'a pyo3::types::PyDict,
+}
+
+impl<'a> Jwk<'a> {
+    fn parse(py: pyo3::Python<'a>, data: &[u8]) -> CryptographyResult<Jwk<'a>> {
+        let object = types::JSON_LOADS
+            .get(py)?
+            .call1((pyo3::types::PyBytes::new(py, data),))?;
+        let object = object.downcast::<pyo3::types::PyDict>().map_err(|_| {
+            CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+                "A JWK must be a JSON object",
+            ))
+        })?;
+        Ok(Jwk { object })
+    }
+
+    fn get(&self, name: &str) -> CryptographyResult<Option<&'a str>> {
+        match self.object.get_item(name)? {
+            Some(value) => Ok(Some(value.extract::<&str>().map_err(|_| {
+                invalid_jwk(format!("The \"{name}\" member of the JWK must be a string"))
+            })?)),
+            None => Ok(None),
+        }
+    }
+
+    fn get_required(&self, name: &str) -> CryptographyResult<&'a str> {
+        self.get(name)?
+            .ok_or_else(|| invalid_jwk(format!("The JWK is missing the \"{name}\" member")))
+    }
+
+    fn get_bytes(&self, name: &str) -> CryptographyResult<Vec<u8>> {
+        base64url_decode(name, self.get_required(name)?)
+    }
+
+    fn get_bn(&self, name: &str) -> CryptographyResult<openssl::bn::BigNum> {
+        Ok(openssl::bn::BigNum::from_slice(&self.get_bytes(name)?)?)
+    }
+
+    // Decodes a member that must be exactly `len` bytes long.
+    fn get_fixed_bn(&self, name: &str, len: i32) -> CryptographyResult<openssl::bn::BigNum> {
+        let value = self.get_bytes(name)?;
+        if value.len() != len as usize {
+            return Err(invalid_jwk(format!(
+                "The \"{name}\" member of the JWK must be {len} bytes long"
+            )));
+        }
+        Ok(openssl::bn::BigNum::from_slice(&value)?)
+    }
+
+    fn is_private(&self) -> CryptographyResult<bool> {
+        Ok(self.object.contains("d")?)
+    }
+}
+
+enum LoadedKey {
+    Private(openssl::pkey::PKey<openssl::pkey::Private>),
+    Public(openssl::pkey::PKey<openssl::pkey::Public>),
+}
+
+fn load_rsa(jwk: &Jwk<'_>) -> CryptographyResult<LoadedKey> {
+    let n = jwk.get_bn("n")?;
+    let e = jwk.get_bn("e")?;
+    if !jwk.is_private()? {
+        let rsa = openssl::rsa::Rsa::from_public_components(n, e)?;
+        return Ok(LoadedKey::Public(openssl::pkey::PKey::from_rsa(rsa)?));
+    }
+
+    if jwk.object.contains("oth")? {
+        return Err(CryptographyError::from(
+            exceptions::UnsupportedAlgorithm::new_err((
+                "Multi-prime RSA JWKs are not supported",
+                exceptions::Reasons::UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
+            )),
+        ));
+    }
+    let d = jwk.get_bn("d")?;
+    // The CRT parameters are optional, but must all be present if any are
+    // (RFC 7518, section 6.3.2).
+    let [p, q, dp, dq, qi] = if jwk.object.contains("p")? {
+        [
+            jwk.get_bn("p")?,
+            jwk.get_bn("q")?,
+            jwk.get_bn("dp")?,
+            jwk.get_bn("dq")?,
+            jwk.get_bn("qi")?,
+        ]
+    } else {
+        for name in ["q", "dp", "dq", "qi"] {
+            if jwk.object.contains(name)? {
+                return Err(invalid_jwk(format!(
+                    "The JWK has the \"{name}\" member but is missing \"p\""
+                )));
+            }
+        }
+        crate::backend::rsa::recover_crt_params(&n, &e, &d)?
+    };
+    let rsa = openssl::rsa::Rsa::from_private_components(n, e, d, p, q, dp, dq, qi)?;
+    // The key is checked as it's converted to a Python object.
+    Ok(LoadedKey::Private(openssl::pkey::PKey::from_rsa(rsa)?))
+}
+
+fn load_ec(jwk: &Jwk<'_>) -> CryptographyResult<LoadedKey> {
+    let group = openssl::ec::EcGroup::from_curve_name(curve_nid(jwk.get_required("crv")?)?)?;
+    let field_len = (group.degree() as i32 + 7) / 8;
+    let x = jwk.get_fixed_bn("x", field_len)?;
+    let y = jwk.get_fixed_bn("y", field_len)?;
+    let invalid_key =
+        || CryptographyError::from(pyo3::exceptions::PyValueError::new_err("Invalid EC key"));
+    let public = openssl::ec::EcKey::from_public_key_affine_coordinates(&group, &x, &y)
+        .map_err(|_| invalid_key())?;
+    if !jwk.is_private()? {
+        return Ok(LoadedKey::Public(openssl::pkey::PKey::from_ec_key(public)?));
+    }
+
+    let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+    let mut order = openssl::bn::BigNum::new()?;
+    group.order(&mut order, &mut bn_ctx)?;
+    let d = jwk.get_fixed_bn("d", order.num_bytes())?;
+    let ec = openssl::ec::EcKey::from_private_components(&group, &d, public.public_key())
+        .map_err(|_| invalid_key())?;
+    // Checks that the public key matches the private key.
+    ec.check_key().map_err(|_| invalid_key())?;
+    Ok(LoadedKey::Private(openssl::pkey::PKey::from_ec_key(ec)?))
+}
+
+fn load_okp(jwk: &Jwk<'_>) -> CryptographyResult<LoadedKey> {
+    let id = okp_id(jwk.get_required("crv")?)?;
+    let x = jwk.get_bytes("x")?;
+    let invalid_key =
+        || CryptographyError::from(pyo3::exceptions::PyValueError::new_err("Invalid OKP key"));
+    if !jwk.is_private()? {
+        let pkey =
+            openssl::pkey::PKey::public_key_from_raw_bytes(&x, id).map_err(|_| invalid_key())?;
+        return Ok(LoadedKey::Public(pkey));
+    }
+
+    let pkey = openssl::pkey::PKey::private_key_from_raw_bytes(&jwk.get_bytes("d")?, id)
+        .map_err(|_| invalid_key())?;
+    if pkey.raw_public_key()? != x {
+        return Err(invalid_key());
+    }
+    Ok(LoadedKey::Private(pkey))
+}
+
+#[pyo3::prelude::pyfunction]
+fn load_jwk_key(py: pyo3::Python<'_>, data: CffiBuf<'_>) -> CryptographyResult<pyo3::PyObject> {
+    let jwk = Jwk::parse(py, data.as_bytes())?;
+    let pkey = match jwk.get_required("kty")? {
+        "RSA" => load_rsa(&jwk)?,
+        "EC" => load_ec(&jwk)?,
+        "OKP" => load_okp(&jwk)?,
+        kty => {
+            return Err(CryptographyError::from(
+                exceptions::UnsupportedAlgorithm::new_err((
+                    format!("Unsupported JWK key type: {kty}"),
+                    exceptions::Reasons::UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
+                )),
+            ))
+        }
+    };
+    match pkey {
+        // RSA keys are validated here, and one that fails can leave OpenSSL's
+        // reasons on the error stack, which are cleared as it's reported as a
+        // ValueError.
+        LoadedKey::Private(pkey) => keys::private_key_from_pkey(py, &pkey, false).map_err(|e| {
+            openssl::error::ErrorStack::get();
+            e
+        }),
+        LoadedKey::Public(pkey) => keys::public_key_from_pkey(py, &pkey, pkey.id()),
+    }
+}
+
+// Computes the RFC 7638 thumbprint of a JWK, which hashes its required public
+// members.
+#[pyo3::prelude::pyfunction]
+#[pyo3(signature = (data, algorithm=None))]
+fn jwk_thumbprint<'p>(
+    py: pyo3::Python<'p>,
+    data: CffiBuf<'_>,
+    algorithm: Option<&pyo3::PyAny>,
+) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+    let jwk = Jwk::parse(py, data.as_bytes())?;
+    let kty = jwk.get_required("kty")?;
+    let names: &[&str] = match kty {
+        "RSA" => &["e", "n"],
+        "EC" => &["crv", "x", "y"],
+        "OKP" => &["crv", "x"],
+        _ => {
+            return Err(CryptographyError::from(
+                exceptions::UnsupportedAlgorithm::new_err((
+                    format!("Unsupported JWK key type: {kty}"),
+                    exceptions::Reasons::UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
+                )),
+            ))
+        }
+    };
+    let mut members = vec![("kty", kty.to_string())];
+    for name in names {
+        members.push((*name, jwk.get_required(name)?.to_string()));
+    }
+    thumbprint(py, members, algorithm)
+}
+
+// Computes the RFC 7638 thumbprint of the JWK for a public key.
+pub(crate) fn public_jwk_thumbprint<'p, T: openssl::pkey::HasPublic>(
+    py: pyo3::Python<'p>,
+    pkey: &openssl::pkey::PKeyRef<T>,
+    algorithm: Option<&pyo3::PyAny>,
+) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+    thumbprint(py, public_members(pkey)?, algorithm)
+}
+
+fn thumbprint<'p>(
+    py: pyo3::Python<'p>,
+    members: Vec<(&str, String)>,
+    algorithm: Option<&pyo3::PyAny>,
+) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+    let md = match algorithm {
+        Some(algorithm) => hashes::message_digest_from_algorithm(py, algorithm)?,
+        None => openssl::hash::MessageDigest::sha256(),
+    };
+    let digest = openssl::hash::hash(md, encode_json(members).as_bytes())?;
+    Ok(pyo3::types::PyBytes::new(py, &digest))
+}
+
+pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let m = pyo3::prelude::PyModule::new(py, "jwk")?;
+    m.add_function(pyo3::wrap_pyfunction!(load_jwk_key, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(jwk_thumbprint, m)?)?;
+
+    Ok(m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{base64url_decode, base64url_encode, encode_json};
+
+    #[test]
+    fn test_base64url() {
+        assert_eq!(base64url_encode(b"\xfb\xff"), "-_8");
+        assert_eq!(
+            base64url_decode("x", "-_8").ok().as_deref(),
+            Some(&b"\xfb\xff"[..])
+        );
+        assert_eq!(base64url_decode("x", "").ok().as_deref(), Some(&b""[..]));
+        assert!(base64url_decode("x", "-_8=").is_err());
+        assert!(base64url_decode("x", "+/8").is_err());
+        assert!(base64url_decode("x", "A").is_err());
+    }
+
+    #[test]
+    fn test_encode_json() {
+        assert_eq!(
+            encode_json(vec![
+                ("kty", "EC".to_string()),
+                ("kid", "a\"\\\n".to_string())
+            ]),
+            "{\"kid\":\"a\\\"\\\\\\u000a\",\"kty\":\"EC\"}"
+        );
+    }
+}
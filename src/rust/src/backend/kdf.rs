@@ -2,9 +2,10 @@
 // 2.0, and the BSD License. See the LICENSE file in the root of this repository
 // for complete details.
 
-use crate::backend::hashes;
+use crate::backend::{dh, ec, hashes};
 use crate::buf::CffiBuf;
-use crate::error::CryptographyResult;
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::{exceptions, types};
 
 #[pyo3::prelude::pyfunction]
 fn derive_pbkdf2_hmac<'p>(
@@ -48,12 +49,191 @@ fn derive_scrypt<'p>(
     })?)
 }
 
+// KDF objects can only be used once, so this enforces that the same way their
+// `derive` methods do.
+fn mark_kdf_used(py: pyo3::Python<'_>, kdf: &pyo3::PyAny) -> CryptographyResult<()> {
+    if kdf.getattr(pyo3::intern!(py, "_used"))?.is_true()? {
+        return Err(CryptographyError::from(
+            exceptions::AlreadyFinalized::new_err(()),
+        ));
+    }
+    kdf.setattr(pyo3::intern!(py, "_used"), true)?;
+    Ok(())
+}
+
+// Derives `length` bytes by concatenating `block(counter)` for a 32-bit big
+// endian counter starting at 1, as X9.63 and Concat KDF both do.
+fn derive_counter_mode(
+    length: usize,
+    mut block: impl FnMut(&[u8; 4]) -> CryptographyResult<Vec<u8>>,
+) -> CryptographyResult<Vec<u8>> {
+    let mut output = vec![];
+    let mut counter = 1u32;
+    while output.len() < length {
+        output.extend_from_slice(&block(&counter.to_be_bytes())?);
+        counter += 1;
+    }
+    output.truncate(length);
+    Ok(output)
+}
+
+pub(crate) fn derive_hkdf(
+    md: openssl::hash::MessageDigest,
+    salt: &[u8],
+    info: &[u8],
+    length: usize,
+    key_material: &[u8],
+) -> CryptographyResult<Vec<u8>> {
+    let mut h = cryptography_openssl::hmac::Hmac::new(salt, md)?;
+    h.update(key_material)?;
+    let prk = h.finish()?;
+
+    let mut output = vec![];
+    let mut previous = vec![];
+    let mut counter = 1u8;
+    while output.len() < length {
+        let mut h = cryptography_openssl::hmac::Hmac::new(&prk, md)?;
+        h.update(&previous)?;
+        h.update(info)?;
+        h.update(&[counter])?;
+        previous = h.finish()?.to_vec();
+        output.extend_from_slice(&previous);
+        counter += 1;
+    }
+    output.truncate(length);
+    Ok(output)
+}
+
+// Runs one of the supported KDF objects over `key_material` without the key
+// material ever being exposed to Python. The KDF's parameters were all
+// validated when it was constructed.
+pub(crate) fn derive_with_kdf<'p>(
+    py: pyo3::Python<'p>,
+    kdf: &pyo3::PyAny,
+    key_material: &[u8],
+) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+    let algorithm = kdf.getattr(pyo3::intern!(py, "_algorithm"))?;
+    let md = hashes::message_digest_from_algorithm(py, algorithm)?;
+
+    let output = if kdf.is_instance(types::HKDF.get(py)?)? {
+        let expand = kdf.getattr(pyo3::intern!(py, "_hkdf_expand"))?;
+        mark_kdf_used(py, expand)?;
+        let salt = kdf
+            .getattr(pyo3::intern!(py, "_salt"))?
+            .extract::<&[u8]>()?;
+        let info = expand
+            .getattr(pyo3::intern!(py, "_info"))?
+            .extract::<&[u8]>()?;
+        let length = expand
+            .getattr(pyo3::intern!(py, "_length"))?
+            .extract::<usize>()?;
+        derive_hkdf(md, salt, info, length, key_material)?
+    } else if kdf.is_instance(types::X963KDF.get(py)?)? {
+        mark_kdf_used(py, kdf)?;
+        let sharedinfo = kdf
+            .getattr(pyo3::intern!(py, "_sharedinfo"))?
+            .extract::<Option<&[u8]>>()?;
+        let length = kdf.getattr(pyo3::intern!(py, "_length"))?.extract()?;
+        derive_counter_mode(length, |counter| {
+            let mut h = openssl::hash::Hasher::new(md)?;
+            h.update(key_material)?;
+            h.update(counter)?;
+            if let Some(sharedinfo) = sharedinfo {
+                h.update(sharedinfo)?;
+            }
+            Ok(h.finish()?.to_vec())
+        })?
+    } else if kdf.is_instance(types::CONCATKDF_HASH.get(py)?)? {
+        mark_kdf_used(py, kdf)?;
+        let otherinfo = kdf
+            .getattr(pyo3::intern!(py, "_otherinfo"))?
+            .extract::<&[u8]>()?;
+        let length = kdf.getattr(pyo3::intern!(py, "_length"))?.extract()?;
+        derive_counter_mode(length, |counter| {
+            let mut h = openssl::hash::Hasher::new(md)?;
+            h.update(counter)?;
+            h.update(key_material)?;
+            h.update(otherinfo)?;
+            Ok(h.finish()?.to_vec())
+        })?
+    } else if kdf.is_instance(types::CONCATKDF_HMAC.get(py)?)? {
+        mark_kdf_used(py, kdf)?;
+        let salt = kdf
+            .getattr(pyo3::intern!(py, "_salt"))?
+            .extract::<&[u8]>()?;
+        let otherinfo = kdf
+            .getattr(pyo3::intern!(py, "_otherinfo"))?
+            .extract::<&[u8]>()?;
+        let length = kdf.getattr(pyo3::intern!(py, "_length"))?.extract()?;
+        derive_counter_mode(length, |counter| {
+            let mut h = cryptography_openssl::hmac::Hmac::new(salt, md)?;
+            h.update(counter)?;
+            h.update(key_material)?;
+            h.update(otherinfo)?;
+            Ok(h.finish()?.to_vec())
+        })?
+    } else {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyTypeError::new_err(
+                "kdf must be an instance of HKDF, X963KDF, ConcatKDFHash or ConcatKDFHMAC",
+            ),
+        ));
+    };
+
+    Ok(pyo3::types::PyBytes::new(py, &output))
+}
+
+// Derives a key from the concatenation of the shared secrets of each
+// `(private_key, peer_public_key)` pair, in order. This covers the SP 800-56A
+// C(2e, 2s), C(1e, 2s) and C(1e, 1s) schemes, where the ephemeral shared
+// secret comes first. DH public keys are validated and DH shared secrets are
+// padded to the length of p. EC keys use the cofactor Diffie-Hellman
+// primitive.
+#[pyo3::prelude::pyfunction]
+fn derive_unified_model<'p>(
+    py: pyo3::Python<'p>,
+    exchanges: Vec<(&pyo3::PyAny, &pyo3::PyAny)>,
+    kdf: &pyo3::PyAny,
+) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+    if exchanges.is_empty() {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("At least one exchange is required"),
+        ));
+    }
+
+    let mut shared_secret = vec![];
+    for (private_key, public_key) in exchanges {
+        let z = if let (Ok(private_key), Ok(public_key)) = (
+            private_key.extract::<pyo3::PyRef<'_, ec::ECPrivateKey>>(),
+            public_key.extract::<pyo3::PyRef<'_, ec::ECPublicKey>>(),
+        ) {
+            ec::ecdh_exchange(&private_key.pkey, &public_key.pkey, true, false)?
+        } else if let (Ok(private_key), Ok(public_key)) = (
+            private_key.extract::<pyo3::PyRef<'_, dh::DHPrivateKey>>(),
+            public_key.extract::<pyo3::PyRef<'_, dh::DHPublicKey>>(),
+        ) {
+            private_key.compute_shared_key(&public_key, true, true)?
+        } else {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyTypeError::new_err(
+                    "Each exchange must be a DH or EC private key and a peer public key of the \
+                     same type",
+                ),
+            ));
+        };
+        shared_secret.extend_from_slice(&z);
+    }
+
+    derive_with_kdf(py, kdf, &shared_secret)
+}
+
 pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
     let m = pyo3::prelude::PyModule::new(py, "kdf")?;
 
     m.add_function(pyo3::wrap_pyfunction!(derive_pbkdf2_hmac, m)?)?;
     #[cfg(not(CRYPTOGRAPHY_IS_LIBRESSL))]
     m.add_function(pyo3::wrap_pyfunction!(derive_scrypt, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(derive_unified_model, m)?)?;
 
     Ok(m)
 }
@@ -0,0 +1,179 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! A common interface to key encapsulation mechanisms (KEMs), so that
+//! protocols built on top of a KEM, such as HPKE or CMS KEMRecipientInfo,
+//! don't need to know which one they're using.
+
+use cryptography_openssl::kem::Operation;
+
+use crate::backend::{ec, rsa, x25519, x448};
+use crate::buf::CffiBuf;
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::exceptions;
+
+pub(crate) trait KemPublicKey {
+    /// Generates a random shared secret, returning it along with the
+    /// ciphertext that encapsulates it for this key.
+    fn encapsulate(&self) -> CryptographyResult<(Vec<u8>, Vec<u8>)>;
+}
+
+pub(crate) trait KemPrivateKey {
+    /// Recovers the shared secret from a ciphertext created by the
+    /// `KemPublicKey::encapsulate` of the corresponding public key.
+    fn decapsulate(&self, ciphertext: &[u8]) -> CryptographyResult<Vec<u8>>;
+}
+
+// A KEM that OpenSSL implements for the type of `pkey`.
+struct PkeyKem<T> {
+    pkey: openssl::pkey::PKey<T>,
+    operation: Operation,
+}
+
+impl<T: openssl::pkey::HasPublic> KemPublicKey for PkeyKem<T> {
+    fn encapsulate(&self) -> CryptographyResult<(Vec<u8>, Vec<u8>)> {
+        cryptography_openssl::kem::encapsulate(&self.pkey, Some(self.operation)).map_err(|_| {
+            CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+                "Error computing shared key.",
+            ))
+        })
+    }
+}
+
+impl KemPrivateKey for PkeyKem<openssl::pkey::Private> {
+    fn decapsulate(&self, ciphertext: &[u8]) -> CryptographyResult<Vec<u8>> {
+        cryptography_openssl::kem::decapsulate(&self.pkey, Some(self.operation), ciphertext)
+            .map_err(|_| {
+                CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+                    "Invalid ciphertext",
+                ))
+            })
+    }
+}
+
+// RFC 9180 only defines DHKEM for these curves, and OpenSSL doesn't support
+// any others.
+fn check_dhkem_curve<T: openssl::pkey::HasPublic>(
+    pkey: &openssl::pkey::PKeyRef<T>,
+) -> CryptographyResult<()> {
+    let curve_name = pkey.ec_key()?.group().curve_name();
+    if !matches!(
+        curve_name,
+        Some(
+            openssl::nid::Nid::X9_62_PRIME256V1
+                | openssl::nid::Nid::SECP384R1
+                | openssl::nid::Nid::SECP521R1
+        )
+    ) {
+        return Err(CryptographyError::from(
+            exceptions::UnsupportedAlgorithm::new_err((
+                "DHKEM is only supported for the P-256, P-384 and P-521 curves",
+                exceptions::Reasons::UNSUPPORTED_ELLIPTIC_CURVE,
+            )),
+        ));
+    }
+    Ok(())
+}
+
+fn dhkem<T>(pkey: &openssl::pkey::PKey<T>) -> PkeyKem<T> {
+    PkeyKem {
+        pkey: pkey.clone(),
+        operation: Operation::DhKem,
+    }
+}
+
+fn rsa_kem<T>(pkey: &openssl::pkey::PKey<T>) -> PkeyKem<T> {
+    PkeyKem {
+        pkey: pkey.clone(),
+        operation: Operation::RsaSve,
+    }
+}
+
+/// The KEM for `public_key`, which can be an X25519, X448, EC or RSA public
+/// key, or a key of a KEM such as X-Wing.
+pub(crate) fn public_key_kem(
+    public_key: &pyo3::PyAny,
+) -> CryptographyResult<Box<dyn KemPublicKey>> {
+    if let Ok(k) = public_key.extract::<pyo3::PyRef<'_, x25519::X25519PublicKey>>() {
+        Ok(Box::new(dhkem(&k.pkey)))
+    } else if let Ok(k) = public_key.extract::<pyo3::PyRef<'_, x448::X448PublicKey>>() {
+        Ok(Box::new(dhkem(&k.pkey)))
+    } else if let Ok(k) = public_key.extract::<pyo3::PyRef<'_, ec::ECPublicKey>>() {
+        check_dhkem_curve(&k.pkey)?;
+        Ok(Box::new(dhkem(&k.pkey)))
+    } else if let Ok(k) = public_key.extract::<pyo3::PyRef<'_, rsa::RsaPublicKey>>() {
+        Ok(Box::new(rsa_kem(&k.pkey)))
+    } else {
+        #[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+        if let Ok(k) =
+            public_key.extract::<pyo3::PyRef<'_, crate::backend::xwing::XWingPublicKey>>()
+        {
+            return Ok(Box::new((*k).clone()));
+        }
+        Err(CryptographyError::from(
+            pyo3::exceptions::PyTypeError::new_err(
+                "public_key must be an X25519, X448, EC, RSA or X-Wing public key",
+            ),
+        ))
+    }
+}
+
+/// The KEM for `private_key`, which can be any of the key types that
+/// `public_key_kem` accepts.
+pub(crate) fn private_key_kem(
+    private_key: &pyo3::PyAny,
+) -> CryptographyResult<Box<dyn KemPrivateKey>> {
+    if let Ok(k) = private_key.extract::<pyo3::PyRef<'_, x25519::X25519PrivateKey>>() {
+        Ok(Box::new(dhkem(&k.pkey)))
+    } else if let Ok(k) = private_key.extract::<pyo3::PyRef<'_, x448::X448PrivateKey>>() {
+        Ok(Box::new(dhkem(&k.pkey)))
+    } else if let Ok(k) = private_key.extract::<pyo3::PyRef<'_, ec::ECPrivateKey>>() {
+        check_dhkem_curve(&k.pkey)?;
+        Ok(Box::new(dhkem(&k.pkey)))
+    } else if let Ok(k) = private_key.extract::<pyo3::PyRef<'_, rsa::RsaPrivateKey>>() {
+        Ok(Box::new(rsa_kem(&k.pkey)))
+    } else {
+        #[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+        if let Ok(k) =
+            private_key.extract::<pyo3::PyRef<'_, crate::backend::xwing::XWingPrivateKey>>()
+        {
+            return Ok(Box::new((*k).clone()));
+        }
+        Err(CryptographyError::from(
+            pyo3::exceptions::PyTypeError::new_err(
+                "private_key must be an X25519, X448, EC, RSA or X-Wing private key",
+            ),
+        ))
+    }
+}
+
+#[pyo3::prelude::pyfunction]
+fn encapsulate<'p>(
+    py: pyo3::Python<'p>,
+    public_key: &pyo3::PyAny,
+) -> CryptographyResult<(&'p pyo3::types::PyBytes, &'p pyo3::types::PyBytes)> {
+    let (shared_secret, ciphertext) = public_key_kem(public_key)?.encapsulate()?;
+    Ok((
+        pyo3::types::PyBytes::new(py, &shared_secret),
+        pyo3::types::PyBytes::new(py, &ciphertext),
+    ))
+}
+
+#[pyo3::prelude::pyfunction]
+fn decapsulate<'p>(
+    py: pyo3::Python<'p>,
+    private_key: &pyo3::PyAny,
+    ciphertext: CffiBuf<'_>,
+) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+    let shared_secret = private_key_kem(private_key)?.decapsulate(ciphertext.as_bytes())?;
+    Ok(pyo3::types::PyBytes::new(py, &shared_secret))
+}
+
+pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let m = pyo3::prelude::PyModule::new(py, "kem")?;
+    m.add_function(pyo3::wrap_pyfunction!(encapsulate, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(decapsulate, m)?)?;
+
+    Ok(m)
+}
@@ -2,25 +2,164 @@
 // 2.0, and the BSD License. See the LICENSE file in the root of this repository
 // for complete details.
 
+use asn1::SimpleAsn1Readable;
+use cryptography_key_parsing::pkcs8::{self, PrivateKeyFormat};
+use cryptography_key_parsing::{pbe, KeyParsingError};
+use cryptography_x509::common::SubjectPublicKeyInfo;
+use cryptography_x509::pkcs12::Pfx;
+use cryptography_x509::pkcs8::{EncryptedPrivateKeyInfo, PrivateKeyInfo};
 use foreign_types_shared::ForeignTypeRef;
 use pyo3::IntoPy;
 
-use crate::backend::utils;
+use crate::backend::{openssh, utils};
 use crate::buf::CffiBuf;
 use crate::error::{CryptographyError, CryptographyResult};
-use crate::exceptions;
+use crate::{exceptions, source, types, x509};
 
 #[pyo3::prelude::pyfunction]
-#[pyo3(signature = (data, password, backend=None, *, unsafe_skip_rsa_key_validation=false))]
+#[pyo3(signature = (
+    data,
+    password,
+    backend=None,
+    *,
+    unsafe_skip_rsa_key_validation=false,
+    allow_legacy=false,
+))]
 fn load_der_private_key(
     py: pyo3::Python<'_>,
     data: CffiBuf<'_>,
-    password: Option<CffiBuf<'_>>,
+    password: Option<&pyo3::PyAny>,
     backend: Option<&pyo3::PyAny>,
     unsafe_skip_rsa_key_validation: bool,
+    allow_legacy: bool,
 ) -> CryptographyResult<pyo3::PyObject> {
     let _ = backend;
-    if let Ok(pkey) = openssl::pkey::PKey::private_key_from_der(data.as_bytes()) {
+    let data = data.as_bytes();
+    let password = resolve_password(password, || der_private_key_is_encrypted(data))?;
+    let password = password.as_ref().map(CffiBuf::as_bytes);
+
+    let format = match pkcs8::identify_private_key(data) {
+        Ok(format) => format,
+        // Anything we can't identify, such as BER encoded keys, is left to
+        // OpenSSL to try each format in turn.
+        Err(KeyParsingError::Parse(_) | KeyParsingError::InvalidKey) => {
+            let pkey = load_der_private_key_any_format(py, data, password)?;
+            return private_key_from_pkey(py, &pkey, unsafe_skip_rsa_key_validation);
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let decode: fn(
+        &[u8],
+    ) -> Result<
+        openssl::pkey::PKey<openssl::pkey::Private>,
+        openssl::error::ErrorStack,
+    > = match format {
+        PrivateKeyFormat::EncryptedPkcs8(info) => {
+            let pkey = if allow_legacy && pbe::is_legacy_pbe(&info.encryption_algorithm) {
+                decrypt_legacy_pkcs8(&info, password)?
+            } else {
+                let mut status = utils::PasswordCallbackStatus::Unused;
+                let pkey = openssl::pkey::PKey::private_key_from_pkcs8_callback(
+                    data,
+                    utils::password_callback(&mut status, password),
+                );
+                utils::handle_key_load_result(py, pkey, status, password)?
+            };
+            return private_key_from_pkey(py, &pkey, unsafe_skip_rsa_key_validation);
+        }
+        PrivateKeyFormat::Pkcs8(info) if info.public_key.is_some() => {
+            let pkey = load_one_asymmetric_key(py, info, password)?;
+            return private_key_from_pkey(py, &pkey, unsafe_skip_rsa_key_validation);
+        }
+        PrivateKeyFormat::Pkcs8(_) => openssl::pkey::PKey::private_key_from_pkcs8,
+        PrivateKeyFormat::Sec1 => {
+            |data| openssl::pkey::PKey::from_ec_key(openssl::ec::EcKey::private_key_from_der(data)?)
+        }
+        PrivateKeyFormat::Pkcs1Rsa => {
+            |data| openssl::pkey::PKey::from_rsa(openssl::rsa::Rsa::private_key_from_der(data)?)
+        }
+        PrivateKeyFormat::TraditionalDsa => |data| {
+            openssl::pkey::PKey::from_dsa(cryptography_openssl::dsa::private_key_from_der(data)?)
+        },
+    };
+    if password.is_some() {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyTypeError::new_err(
+                "Password was given but private key is not encrypted.",
+            ),
+        ));
+    }
+    let pkey = utils::handle_key_load_result(
+        py,
+        decode(data),
+        utils::PasswordCallbackStatus::Unused,
+        None,
+    )?;
+    private_key_from_pkey(py, &pkey, unsafe_skip_rsa_key_validation)
+}
+
+// The password for a key may be given as a callable, which is only called,
+// with no arguments, if the key is actually encrypted.
+fn resolve_password<'p>(
+    password: Option<&'p pyo3::PyAny>,
+    is_encrypted: impl FnOnce() -> bool,
+) -> CryptographyResult<Option<CffiBuf<'p>>> {
+    let password = match password {
+        Some(password) if password.is_callable() => {
+            if !is_encrypted() {
+                return Ok(None);
+            }
+            let password = password.call0()?;
+            if !password.is_instance_of::<pyo3::types::PyBytes>() {
+                return Err(CryptographyError::from(
+                    pyo3::exceptions::PyTypeError::new_err(
+                        "The password callback must return bytes",
+                    ),
+                ));
+            }
+            password
+        }
+        Some(password) => password,
+        None => return Ok(None),
+    };
+    Ok(Some(password.extract()?))
+}
+
+fn der_private_key_is_encrypted(data: &[u8]) -> bool {
+    match pkcs8::identify_private_key(data) {
+        Ok(format) => matches!(format, PrivateKeyFormat::EncryptedPkcs8(_)),
+        // Keys we can't identify are assumed to be encrypted if OpenSSL can't
+        // load them without a password.
+        Err(_) => openssl::pkey::PKey::private_key_from_der(data).is_err(),
+    }
+}
+
+fn pem_private_key_is_encrypted(data: &[u8]) -> bool {
+    let blocks = match pem::parse_many(data) {
+        Ok(blocks) => blocks,
+        Err(_) => return false,
+    };
+    match blocks
+        .iter()
+        .find(|p| PRIVATE_KEY_PEM_TAGS.contains(&p.tag()))
+    {
+        Some(p) => {
+            p.tag() == "ENCRYPTED PRIVATE KEY"
+                || matches!(
+                    p.headers().get("Proc-Type"),
+                    Some(proc_type) if proc_type.ends_with("ENCRYPTED")
+                )
+        }
+        None => false,
+    }
+}
+
+fn load_der_private_key_any_format(
+    py: pyo3::Python<'_>,
+    data: &[u8],
+    password: Option<&[u8]>,
+) -> CryptographyResult<openssl::pkey::PKey<openssl::pkey::Private>> {
+    if let Ok(pkey) = openssl::pkey::PKey::private_key_from_der(data) {
         if password.is_some() {
             return Err(CryptographyError::from(
                 pyo3::exceptions::PyTypeError::new_err(
@@ -28,39 +167,674 @@ fn load_der_private_key(
                 ),
             ));
         }
-        return private_key_from_pkey(py, &pkey, unsafe_skip_rsa_key_validation);
+        return Ok(pkey);
     }
 
-    let password = password.as_ref().map(CffiBuf::as_bytes);
     let mut status = utils::PasswordCallbackStatus::Unused;
     let pkey = openssl::pkey::PKey::private_key_from_pkcs8_callback(
-        data.as_bytes(),
+        data,
         utils::password_callback(&mut status, password),
     );
-    let pkey = utils::handle_key_load_result(py, pkey, status, password)?;
-    private_key_from_pkey(py, &pkey, unsafe_skip_rsa_key_validation)
+    utils::handle_key_load_result(py, pkey, status, password)
 }
 
 #[pyo3::prelude::pyfunction]
-#[pyo3(signature = (data, password, backend=None, *, unsafe_skip_rsa_key_validation=false))]
+#[pyo3(signature = (
+    data,
+    password,
+    backend=None,
+    *,
+    unsafe_skip_rsa_key_validation=false,
+    allow_legacy=false,
+))]
 fn load_pem_private_key(
     py: pyo3::Python<'_>,
     data: CffiBuf<'_>,
-    password: Option<CffiBuf<'_>>,
+    password: Option<&pyo3::PyAny>,
     backend: Option<&pyo3::PyAny>,
     unsafe_skip_rsa_key_validation: bool,
+    allow_legacy: bool,
 ) -> CryptographyResult<pyo3::PyObject> {
     let _ = backend;
+    let password = resolve_password(password, || pem_private_key_is_encrypted(data.as_bytes()))?;
     let password = password.as_ref().map(CffiBuf::as_bytes);
+    // If the data is a bundle, such as a key and its certificate chain, load
+    // the first key in it.
+    let bundle_key = match pem::parse_many(data.as_bytes()) {
+        Ok(blocks) if blocks.len() > 1 => blocks
+            .iter()
+            .find(|p| PRIVATE_KEY_PEM_TAGS.contains(&p.tag()))
+            .map(pem::encode),
+        _ => None,
+    };
+    let data = bundle_key
+        .as_ref()
+        .map_or(data.as_bytes(), |key| key.as_bytes());
+    if allow_legacy {
+        if let Ok(p) = pem::parse(data) {
+            if p.tag() == "ENCRYPTED PRIVATE KEY" {
+                if let Ok(PrivateKeyFormat::EncryptedPkcs8(info)) =
+                    pkcs8::identify_private_key(p.contents())
+                {
+                    if pbe::is_legacy_pbe(&info.encryption_algorithm) {
+                        let pkey = decrypt_legacy_pkcs8(&info, password)?;
+                        return private_key_from_pkey(py, &pkey, unsafe_skip_rsa_key_validation);
+                    }
+                }
+            } else if let Some(dek_info) = p.headers().get("DEK-Info") {
+                if pbe::is_legacy_pem_cipher(dek_info) {
+                    let pkey = decrypt_legacy_pem(&p, dek_info, password)?;
+                    return private_key_from_pkey(py, &pkey, unsafe_skip_rsa_key_validation);
+                }
+            }
+        }
+    }
+    load_pem_private_key_bytes(py, data, password, unsafe_skip_rsa_key_validation)
+}
+
+#[pyo3::prelude::pyfunction]
+#[pyo3(signature = (
+    file,
+    password,
+    *,
+    unsafe_skip_rsa_key_validation=false,
+    allow_legacy=false,
+))]
+fn load_pem_private_key_from_file(
+    py: pyo3::Python<'_>,
+    file: &pyo3::PyAny,
+    password: Option<&pyo3::PyAny>,
+    unsafe_skip_rsa_key_validation: bool,
+    allow_legacy: bool,
+) -> CryptographyResult<pyo3::PyObject> {
+    // Reading stops at the first private key, so the rest of a large bundle
+    // isn't read at all.
+    let mut key = None;
+    source::for_each_pem_block(py, file, |p| {
+        if PRIVATE_KEY_PEM_TAGS.contains(&p.tag()) {
+            key = Some(pem::encode(&p));
+            return Ok(false);
+        }
+        Ok(true)
+    })?;
+    let key = key.ok_or(pem::PemError::MalformedFraming)?;
+
+    load_pem_private_key(
+        py,
+        pyo3::types::PyBytes::new(py, key.as_bytes()).extract()?,
+        password,
+        None,
+        unsafe_skip_rsa_key_validation,
+        allow_legacy,
+    )
+}
+
+// The labels of the PEM blocks that OpenSSL loads private keys from.
+const PRIVATE_KEY_PEM_TAGS: &[&str] = &[
+    "PRIVATE KEY",
+    "ENCRYPTED PRIVATE KEY",
+    "RSA PRIVATE KEY",
+    "DSA PRIVATE KEY",
+    "EC PRIVATE KEY",
+];
+
+#[pyo3::prelude::pyfunction]
+#[pyo3(signature = (data, password, backend=None, *, unsafe_skip_rsa_key_validation=false))]
+fn load_pem_private_keys(
+    py: pyo3::Python<'_>,
+    data: CffiBuf<'_>,
+    password: Option<CffiBuf<'_>>,
+    backend: Option<&pyo3::PyAny>,
+    unsafe_skip_rsa_key_validation: bool,
+) -> CryptographyResult<Vec<pyo3::PyObject>> {
+    let _ = backend;
+    let password = password.as_ref().map(CffiBuf::as_bytes);
+    let keys = pem::parse_many(data.as_bytes())?
+        .iter()
+        .filter(|p| PRIVATE_KEY_PEM_TAGS.contains(&p.tag()))
+        .map(|p| {
+            load_pem_private_key_bytes(
+                py,
+                pem::encode(p).as_bytes(),
+                password,
+                unsafe_skip_rsa_key_validation,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if keys.is_empty() {
+        return Err(CryptographyError::from(pem::PemError::MalformedFraming));
+    }
+
+    Ok(keys)
+}
+
+// Loads a bundle holding a private key and the certificate chain that goes
+// with it, as web servers commonly expect. The certificate whose public key
+// matches the private key is returned separately, and the others are returned
+// in the order they appear.
+#[pyo3::prelude::pyfunction]
+#[pyo3(signature = (data, password, *, unsafe_skip_rsa_key_validation=false))]
+fn load_pem_key_and_certificates(
+    py: pyo3::Python<'_>,
+    data: CffiBuf<'_>,
+    password: Option<CffiBuf<'_>>,
+    unsafe_skip_rsa_key_validation: bool,
+) -> CryptographyResult<(
+    Option<pyo3::PyObject>,
+    Option<pyo3::PyObject>,
+    Vec<pyo3::PyObject>,
+)> {
+    let password = password.as_ref().map(CffiBuf::as_bytes);
+    let mut key = None;
+    let mut certs = vec![];
+    for p in pem::parse_many(data.as_bytes())? {
+        if PRIVATE_KEY_PEM_TAGS.contains(&p.tag()) {
+            if key.is_some() {
+                return Err(CryptographyError::from(
+                    pyo3::exceptions::PyValueError::new_err(
+                        "data contains more than one private key",
+                    ),
+                ));
+            }
+            key = Some(load_pem_private_key_bytes(
+                py,
+                pem::encode(&p).as_bytes(),
+                password,
+                unsafe_skip_rsa_key_validation,
+            )?);
+        } else if p.tag() == "CERTIFICATE" || p.tag() == "X509 CERTIFICATE" {
+            let cert = x509::certificate::load_der_x509_certificate(
+                py,
+                pyo3::types::PyBytes::new(py, p.contents()).into_py(py),
+                None,
+            )?;
+            certs.push(pyo3::Py::new(py, cert)?.into_py(py));
+        }
+    }
+
+    if key.is_none() && certs.is_empty() {
+        return Err(CryptographyError::from(pem::PemError::MalformedFraming));
+    }
+
+    let mut position = None;
+    if let Some(key) = &key {
+        let public_key = key
+            .as_ref(py)
+            .call_method0(pyo3::intern!(py, "public_key"))?;
+        for (i, cert) in certs.iter().enumerate() {
+            let cert_public_key = cert
+                .as_ref(py)
+                .call_method0(pyo3::intern!(py, "public_key"))?;
+            if public_key.eq(cert_public_key)? {
+                position = Some(i);
+                break;
+            }
+        }
+    }
+    let cert = position.map(|i| certs.remove(i));
+
+    Ok((key, cert, certs))
+}
+
+// The labels of the PEM blocks that parameters are loaded from.
+const PARAMETERS_PEM_TAGS: &[&str] = &["DH PARAMETERS", "X9.42 DH PARAMETERS", "EC PARAMETERS"];
+
+#[pyo3::prelude::pyfunction]
+fn load_pem_parameters(
+    py: pyo3::Python<'_>,
+    data: &[u8],
+    backend: Option<&pyo3::PyAny>,
+) -> CryptographyResult<pyo3::PyObject> {
+    let _ = backend;
+    let parsed = x509::find_in_pem(
+        data,
+        |p| PARAMETERS_PEM_TAGS.contains(&p.tag()),
+        "Valid PEM but no BEGIN DH PARAMETERS/END DH PARAMETERS or BEGIN EC PARAMETERS/END EC PARAMETERS delimiters. Are you sure these are parameters?",
+    )?;
+    if parsed.tag() == "EC PARAMETERS" {
+        Ok(crate::backend::ec::from_der_parameters(py, parsed.contents())?.into_py(py))
+    } else {
+        Ok(crate::backend::dh::parameters_from_pem(&parsed)?.into_py(py))
+    }
+}
+
+#[pyo3::prelude::pyfunction]
+fn load_der_parameters(
+    py: pyo3::Python<'_>,
+    data: &[u8],
+    backend: Option<&pyo3::PyAny>,
+) -> CryptographyResult<pyo3::PyObject> {
+    let _ = backend;
+    // DH parameters are a SEQUENCE, while named curve EC parameters are an
+    // OBJECT IDENTIFIER.
+    if data.first() == Some(&asn1::ObjectIdentifier::TAG.as_u8().unwrap()) {
+        Ok(crate::backend::ec::from_der_parameters(py, data)?.into_py(py))
+    } else {
+        Ok(crate::backend::dh::from_der_parameters(data, None)?.into_py(py))
+    }
+}
+
+// Decrypts an EncryptedPrivateKeyInfo using one of the legacy PBES1 or
+// PKCS#12 PBE schemes, without relying on OpenSSL's legacy provider.
+fn decrypt_legacy_pkcs8(
+    info: &EncryptedPrivateKeyInfo<'_>,
+    password: Option<&[u8]>,
+) -> CryptographyResult<openssl::pkey::PKey<openssl::pkey::Private>> {
+    let password = match password {
+        None | Some(b"") => {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyTypeError::new_err(
+                    "Password was not given but private key is encrypted",
+                ),
+            ))
+        }
+        Some(password) => password,
+    };
+    let der = pbe::decrypt(&info.encryption_algorithm, password, info.encrypted_data)?;
+    // A wrong password can still produce valid looking padding, in which case
+    // the plaintext won't be a PrivateKeyInfo.
+    openssl::pkey::PKey::private_key_from_pkcs8(&der).map_err(|_| {
+        CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+            "Bad decrypt. Incorrect password?",
+        ))
+    })
+}
+
+// Decrypts a traditional PEM key whose DEK-Info header names a DES or RC2
+// cipher, without relying on OpenSSL's legacy provider.
+fn decrypt_legacy_pem(
+    p: &pem::Pem,
+    dek_info: &str,
+    password: Option<&[u8]>,
+) -> CryptographyResult<openssl::pkey::PKey<openssl::pkey::Private>> {
+    let password = match password {
+        None | Some(b"") => {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyTypeError::new_err(
+                    "Password was not given but private key is encrypted",
+                ),
+            ))
+        }
+        Some(password) => password,
+    };
+    let der = pbe::decrypt_pem(dek_info, password, p.contents())?;
+    let decode: fn(
+        &[u8],
+    ) -> Result<
+        openssl::pkey::PKey<openssl::pkey::Private>,
+        openssl::error::ErrorStack,
+    > = match p.tag() {
+        "RSA PRIVATE KEY" => {
+            |der| openssl::pkey::PKey::from_rsa(openssl::rsa::Rsa::private_key_from_der(der)?)
+        }
+        "DSA PRIVATE KEY" => |der| {
+            openssl::pkey::PKey::from_dsa(cryptography_openssl::dsa::private_key_from_der(der)?)
+        },
+        "EC PRIVATE KEY" => {
+            |der| openssl::pkey::PKey::from_ec_key(openssl::ec::EcKey::private_key_from_der(der)?)
+        }
+        _ => openssl::pkey::PKey::private_key_from_der,
+    };
+    // As with PKCS8, a wrong password can produce valid looking padding.
+    decode(&der).map_err(|_| {
+        CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+            "Bad decrypt. Incorrect password?",
+        ))
+    })
+}
+
+// OpenSSL can't parse a OneAsymmetricKey (RFC 5958) that includes its public
+// key, so the key is loaded without it, and the public key is then checked
+// against the one derived from the private key.
+fn load_one_asymmetric_key(
+    py: pyo3::Python<'_>,
+    info: PrivateKeyInfo<'_>,
+    password: Option<&[u8]>,
+) -> CryptographyResult<openssl::pkey::PKey<openssl::pkey::Private>> {
+    let PrivateKeyInfo {
+        algorithm,
+        private_key,
+        attributes,
+        public_key,
+        ..
+    } = info;
+    let der = asn1::write_single(&PrivateKeyInfo {
+        version: 0,
+        algorithm,
+        private_key,
+        attributes,
+        public_key: None,
+    })?;
+    let pkey = utils::handle_key_load_result(
+        py,
+        openssl::pkey::PKey::private_key_from_pkcs8(&der),
+        utils::PasswordCallbackStatus::Unused,
+        password,
+    )?;
+    if let Some(public_key) = public_key {
+        if !public_key_matches(&pkey, &public_key)? {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "The public key in the OneAsymmetricKey doesn't match the private key",
+                ),
+            ));
+        }
+    }
+    Ok(pkey)
+}
+
+fn public_key_matches(
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+    public_key: &asn1::BitString<'_>,
+) -> CryptographyResult<bool> {
+    if public_key.padding_bits() != 0 {
+        return Ok(false);
+    }
+    // EC public keys may be in compressed form, so the points are compared
+    // rather than their encodings.
+    if let Ok(ec) = pkey.ec_key() {
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        return match openssl::ec::EcPoint::from_bytes(
+            ec.group(),
+            public_key.as_bytes(),
+            &mut bn_ctx,
+        ) {
+            Ok(point) => Ok(point.eq(ec.group(), ec.public_key(), &mut bn_ctx)?),
+            Err(_) => Ok(false),
+        };
+    }
+    let spki_der = pkey.public_key_to_der()?;
+    let spki = asn1::parse_single::<SubjectPublicKeyInfo<'_>>(&spki_der)?;
+    Ok(spki.subject_public_key.as_bytes() == public_key.as_bytes())
+}
+
+pub(crate) fn load_pem_private_key_bytes(
+    py: pyo3::Python<'_>,
+    data: &[u8],
+    password: Option<&[u8]>,
+    unsafe_skip_rsa_key_validation: bool,
+) -> CryptographyResult<pyo3::PyObject> {
+    if let Ok(p) = pem::parse(data) {
+        if p.tag() == "PRIVATE KEY" {
+            if let Ok(PrivateKeyFormat::Pkcs8(info)) = pkcs8::identify_private_key(p.contents()) {
+                if info.public_key.is_some() {
+                    let pkey = load_one_asymmetric_key(py, info, password)?;
+                    return private_key_from_pkey(py, &pkey, unsafe_skip_rsa_key_validation);
+                }
+            }
+        }
+    }
     let mut status = utils::PasswordCallbackStatus::Unused;
     let pkey = openssl::pkey::PKey::private_key_from_pem_callback(
-        data.as_bytes(),
+        data,
         utils::password_callback(&mut status, password),
     );
     let pkey = utils::handle_key_load_result(py, pkey, status, password)?;
     private_key_from_pkey(py, &pkey, unsafe_skip_rsa_key_validation)
 }
 
+#[pyo3::prelude::pyfunction]
+#[pyo3(signature = (data, password, backend=None))]
+fn load_ssh_private_key(
+    py: pyo3::Python<'_>,
+    data: CffiBuf<'_>,
+    password: Option<CffiBuf<'_>>,
+    backend: Option<&pyo3::PyAny>,
+) -> CryptographyResult<pyo3::PyObject> {
+    let _ = backend;
+    let key = match pem::parse(data.as_bytes()) {
+        Ok(p) if p.tag() == openssh::PEM_TAG => {
+            openssh::load_private_key(py, p.contents(), password.as_ref().map(CffiBuf::as_bytes))?
+        }
+        _ => {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("Not OpenSSH private key format"),
+            ))
+        }
+    };
+    let pkey = match key {
+        openssh::PrivateKey::Key(pkey) => pkey,
+        openssh::PrivateKey::SecurityKey(public_key, fields) => {
+            return Ok(types::LOAD_SSH_SECURITY_KEY_PRIVATE_KEY
+                .get(py)?
+                .call1((
+                    pyo3::types::PyBytes::new(py, &public_key),
+                    fields.flags,
+                    pyo3::types::PyBytes::new(py, &fields.key_handle),
+                    pyo3::types::PyBytes::new(py, &fields.reserved),
+                ))?
+                .into())
+        }
+    };
+    if pkey.id() == openssl::pkey::Id::DSA {
+        pyo3::PyErr::warn(
+            py,
+            types::DEPRECATED_IN_40.get(py)?,
+            "SSH DSA keys are deprecated and will be removed in a future release.",
+            1,
+        )?;
+    }
+    private_key_from_pkey(py, &pkey, false)
+}
+
+// The encodings of private keys that load_private_key can tell apart.
+enum PrivateKeyEncoding {
+    Pem,
+    OpenSsh,
+    Der,
+    Pkcs12,
+}
+
+fn identify_private_key_encoding(data: &[u8]) -> PrivateKeyEncoding {
+    match pem::parse_many(data) {
+        // OpenSSH keys are only loaded as such when they're the first block,
+        // the same as load_ssh_private_key.
+        Ok(blocks) if !blocks.is_empty() => {
+            if blocks[0].tag() == openssh::PEM_TAG {
+                PrivateKeyEncoding::OpenSsh
+            } else {
+                PrivateKeyEncoding::Pem
+            }
+        }
+        // A PFX starts with a version of 3, whereas the private key formats
+        // start with a version of 0 or 1, or an AlgorithmIdentifier.
+        _ => match asn1::parse_single::<Pfx<'_>>(data) {
+            Ok(pfx) if pfx.version == 3 => PrivateKeyEncoding::Pkcs12,
+            _ => PrivateKeyEncoding::Der,
+        },
+    }
+}
+
+#[pyo3::prelude::pyfunction]
+#[pyo3(signature = (data, password, *, unsafe_skip_rsa_key_validation=false))]
+fn load_private_key(
+    py: pyo3::Python<'_>,
+    data: CffiBuf<'_>,
+    password: Option<&pyo3::PyAny>,
+    unsafe_skip_rsa_key_validation: bool,
+) -> CryptographyResult<pyo3::PyObject> {
+    match identify_private_key_encoding(data.as_bytes()) {
+        PrivateKeyEncoding::Pem => load_pem_private_key(
+            py,
+            data,
+            password,
+            None,
+            unsafe_skip_rsa_key_validation,
+            false,
+        ),
+        PrivateKeyEncoding::Der => load_der_private_key(
+            py,
+            data,
+            password,
+            None,
+            unsafe_skip_rsa_key_validation,
+            false,
+        ),
+        PrivateKeyEncoding::OpenSsh => {
+            let password: Option<CffiBuf<'_>> = password.map(|p| p.extract()).transpose()?;
+            // `load_ssh_private_key` ignores a password for an unencrypted
+            // key, but the other formats reject it, so this does too.
+            if password.is_some() {
+                let p = pem::parse(data.as_bytes())?;
+                if !openssh::is_encrypted(p.contents())? {
+                    return Err(CryptographyError::from(
+                        pyo3::exceptions::PyTypeError::new_err(
+                            "Password was given but private key is not encrypted.",
+                        ),
+                    ));
+                }
+            }
+            load_ssh_private_key(py, data, password, None)
+        }
+        PrivateKeyEncoding::Pkcs12 => {
+            let (key, _, _): (&pyo3::PyAny, &pyo3::PyAny, &pyo3::PyAny) =
+                types::LOAD_KEY_AND_CERTIFICATES
+                    .get(py)?
+                    .call1((pyo3::types::PyBytes::new(py, data.as_bytes()), password))?
+                    .extract()?;
+            if key.is_none() {
+                return Err(CryptographyError::from(
+                    pyo3::exceptions::PyValueError::new_err(
+                        "The PKCS12 data doesn't contain a private key.",
+                    ),
+                ));
+            }
+            Ok(key.into())
+        }
+    }
+}
+
+#[pyo3::prelude::pyfunction]
+#[pyo3(signature = (data, old_password, new_password, params=None))]
+fn reencrypt_private_key<'p>(
+    py: pyo3::Python<'p>,
+    data: CffiBuf<'_>,
+    old_password: Option<CffiBuf<'_>>,
+    new_password: &[u8],
+    params: Option<&pyo3::PyAny>,
+) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+    let old_password = old_password.as_ref().map(CffiBuf::as_bytes);
+    if new_password.is_empty() {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("Password must be 1 or more bytes."),
+        ));
+    }
+    if new_password.len() > 1023 {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(
+                "Passwords longer than 1023 bytes are not supported by this backend",
+            ),
+        ));
+    }
+    if let Some(params) = params {
+        if !params.is_instance(types::KEY_SERIALIZATION_ENCRYPTION_BUILDER.get(py)?)? {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyTypeError::new_err(
+                    "params must be a KeySerializationEncryptionBuilder",
+                ),
+            ));
+        }
+    }
+
+    let data = data.as_bytes();
+    let p = match pem::parse(data) {
+        Ok(p) => p,
+        Err(_) => {
+            // Not PEM, so this must be a DER encoded PKCS#8 key.
+            if params.is_some() {
+                return Err(CryptographyError::from(
+                    pyo3::exceptions::PyValueError::new_err(
+                        "params are only supported for OpenSSH private keys",
+                    ),
+                ));
+            }
+            let pkey = if let Ok(pkey) = openssl::pkey::PKey::private_key_from_pkcs8(data) {
+                if old_password.is_some() {
+                    return Err(CryptographyError::from(
+                        pyo3::exceptions::PyTypeError::new_err(
+                            "Password was given but private key is not encrypted.",
+                        ),
+                    ));
+                }
+                pkey
+            } else {
+                let mut status = utils::PasswordCallbackStatus::Unused;
+                let pkey = openssl::pkey::PKey::private_key_from_pkcs8_callback(
+                    data,
+                    utils::password_callback(&mut status, old_password),
+                );
+                utils::handle_key_load_result(py, pkey, status, old_password)?
+            };
+            let der_bytes = pkey.private_key_to_pkcs8_passphrase(
+                openssl::symm::Cipher::aes_256_cbc(),
+                new_password,
+            )?;
+            return Ok(pyo3::types::PyBytes::new(py, &der_bytes));
+        }
+    };
+
+    match p.tag() {
+        "PRIVATE KEY" | "ENCRYPTED PRIVATE KEY" => {
+            if params.is_some() {
+                return Err(CryptographyError::from(
+                    pyo3::exceptions::PyValueError::new_err(
+                        "params are only supported for OpenSSH private keys",
+                    ),
+                ));
+            }
+            let mut status = utils::PasswordCallbackStatus::Unused;
+            let pkey = openssl::pkey::PKey::private_key_from_pem_callback(
+                data,
+                utils::password_callback(&mut status, old_password),
+            );
+            let pkey = utils::handle_key_load_result(py, pkey, status, old_password)?;
+            let pem_bytes = pkey.private_key_to_pem_pkcs8_passphrase(
+                openssl::symm::Cipher::aes_256_cbc(),
+                new_password,
+            )?;
+            Ok(pyo3::types::PyBytes::new(py, &pem_bytes))
+        }
+        openssh::PEM_TAG => {
+            let rounds = match params {
+                Some(params) => {
+                    if !params
+                        .getattr(pyo3::intern!(py, "_format"))?
+                        .is(types::PRIVATE_FORMAT_OPENSSH.get(py)?)
+                    {
+                        return Err(CryptographyError::from(
+                            pyo3::exceptions::PyValueError::new_err(
+                                "params must be built for PrivateFormat.OpenSSH",
+                            ),
+                        ));
+                    }
+                    params
+                        .getattr(pyo3::intern!(py, "_kdf_rounds"))?
+                        .extract::<Option<u32>>()?
+                        .unwrap_or(openssh::DEFAULT_ROUNDS)
+                }
+                None => openssh::DEFAULT_ROUNDS,
+            };
+            let contents = openssh::reencrypt_private_key(
+                py,
+                p.contents(),
+                old_password,
+                new_password,
+                rounds,
+            )?;
+            Ok(pyo3::types::PyBytes::new(
+                py,
+                openssh::encode_pem(contents).as_bytes(),
+            ))
+        }
+        _ => Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(
+                "Only PKCS8 and OpenSSH private keys can be re-encrypted",
+            ),
+        )),
+    }
+}
+
 #[pyo3::prelude::pyfunction]
 fn private_key_from_ptr(
     py: pyo3::Python<'_>,
@@ -72,7 +846,7 @@ fn private_key_from_ptr(
     private_key_from_pkey(py, pkey, unsafe_skip_rsa_key_validation)
 }
 
-fn private_key_from_pkey(
+pub(crate) fn private_key_from_pkey(
     py: pyo3::Python<'_>,
     pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
     unsafe_skip_rsa_key_validation: bool,
@@ -84,18 +858,18 @@ fn private_key_from_pkey(
         )?
         .into_py(py)),
         #[cfg(any(not(CRYPTOGRAPHY_IS_LIBRESSL), CRYPTOGRAPHY_LIBRESSL_380_OR_GREATER))]
-        openssl::pkey::Id::RSA_PSS => {
-            // At the moment the way we handle RSA PSS keys is to strip the
-            // PSS constraints from them and treat them as normal RSA keys
-            // Unfortunately the RSA * itself tracks this data so we need to
-            // extract, serialize, and reload it without the constraints.
-            let der_bytes = pkey.rsa()?.private_key_to_der()?;
-            let rsa = openssl::rsa::Rsa::private_key_from_der(&der_bytes)?;
-            let pkey = openssl::pkey::PKey::from_rsa(rsa)?;
-            Ok(
-                crate::backend::rsa::private_key_from_pkey(&pkey, unsafe_skip_rsa_key_validation)?
-                    .into_py(py),
-            )
+        openssl::pkey::Id::RSA_PSS => Ok(crate::backend::rsa::pss_private_key_from_pkey(
+            pkey,
+            unsafe_skip_rsa_key_validation,
+        )?
+        .into_py(py)),
+        #[cfg(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER)]
+        _ if cryptography_openssl::sm2::is_sm2(pkey) => {
+            Ok(crate::backend::sm2::private_key_from_pkey(pkey)?.into_py(py))
+        }
+        #[cfg(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER)]
+        openssl::pkey::Id::EC if crate::backend::sm2::is_sm2_curve(pkey.ec_key()?.group()) => {
+            Ok(crate::backend::sm2::private_key_from_pkey(pkey)?.into_py(py))
         }
         openssl::pkey::Id::EC => {
             Ok(crate::backend::ec::private_key_from_pkey(py, pkey)?.into_py(py))
@@ -122,6 +896,17 @@ fn private_key_from_pkey(
 
         #[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
         openssl::pkey::Id::DHX => Ok(crate::backend::dh::private_key_from_pkey(pkey).into_py(py)),
+
+        // ML-DSA and SLH-DSA keys don't have an `Id`, so they're identified
+        // by name.
+        #[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+        _ if cryptography_openssl::mldsa::Variant::from_pkey(pkey).is_some() => {
+            Ok(crate::backend::mldsa::private_key_from_pkey(pkey).into_py(py))
+        }
+        #[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+        _ if cryptography_openssl::slhdsa::Variant::from_pkey(pkey).is_some() => {
+            Ok(crate::backend::slhdsa::private_key_from_pkey(pkey).into_py(py))
+        }
         _ => Err(CryptographyError::from(
             exceptions::UnsupportedAlgorithm::new_err("Unsupported key type."),
         )),
@@ -172,7 +957,7 @@ fn load_pem_public_key(
     public_key_from_pkey(py, &pkey, pkey.id())
 }
 
-fn public_key_from_pkey(
+pub(crate) fn public_key_from_pkey(
     py: pyo3::Python<'_>,
     pkey: &openssl::pkey::PKeyRef<openssl::pkey::Public>,
     id: openssl::pkey::Id,
@@ -181,6 +966,18 @@ fn public_key_from_pkey(
     // unsupported.
     match id {
         openssl::pkey::Id::RSA => Ok(crate::backend::rsa::public_key_from_pkey(pkey).into_py(py)),
+        #[cfg(any(not(CRYPTOGRAPHY_IS_LIBRESSL), CRYPTOGRAPHY_LIBRESSL_380_OR_GREATER))]
+        openssl::pkey::Id::RSA_PSS => {
+            Ok(crate::backend::rsa::pss_public_key_from_pkey(pkey)?.into_py(py))
+        }
+        #[cfg(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER)]
+        _ if cryptography_openssl::sm2::is_sm2(pkey) => {
+            Ok(crate::backend::sm2::public_key_from_pkey(pkey)?.into_py(py))
+        }
+        #[cfg(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER)]
+        openssl::pkey::Id::EC if crate::backend::sm2::is_sm2_curve(pkey.ec_key()?.group()) => {
+            Ok(crate::backend::sm2::public_key_from_pkey(pkey)?.into_py(py))
+        }
         openssl::pkey::Id::EC => {
             Ok(crate::backend::ec::public_key_from_pkey(py, pkey)?.into_py(py))
         }
@@ -204,22 +1001,203 @@ fn public_key_from_pkey(
         #[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
         openssl::pkey::Id::DHX => Ok(crate::backend::dh::public_key_from_pkey(pkey).into_py(py)),
 
+        #[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+        _ if cryptography_openssl::mldsa::Variant::from_pkey(pkey).is_some() => {
+            Ok(crate::backend::mldsa::public_key_from_pkey(pkey).into_py(py))
+        }
+        #[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+        _ if cryptography_openssl::slhdsa::Variant::from_pkey(pkey).is_some() => {
+            Ok(crate::backend::slhdsa::public_key_from_pkey(pkey).into_py(py))
+        }
         _ => Err(CryptographyError::from(
             exceptions::UnsupportedAlgorithm::new_err("Unsupported key type."),
         )),
     }
 }
 
+#[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.keys")]
+struct KeyInfo {
+    #[pyo3(get)]
+    algorithm: Option<&'static str>,
+    #[pyo3(get)]
+    key_size: Option<u32>,
+    #[pyo3(get)]
+    curve: Option<String>,
+    #[pyo3(get)]
+    private: bool,
+    #[pyo3(get)]
+    encrypted: bool,
+}
+
+impl KeyInfo {
+    fn encrypted(algorithm: Option<&'static str>) -> KeyInfo {
+        KeyInfo {
+            algorithm,
+            key_size: None,
+            curve: None,
+            private: true,
+            encrypted: true,
+        }
+    }
+
+    fn from_pkey<T: openssl::pkey::HasPublic>(
+        py: pyo3::Python<'_>,
+        pkey: &openssl::pkey::PKeyRef<T>,
+        private: bool,
+    ) -> CryptographyResult<KeyInfo> {
+        let (algorithm, sized) = match pkey.id() {
+            openssl::pkey::Id::RSA => ("RSA", true),
+            #[cfg(any(not(CRYPTOGRAPHY_IS_LIBRESSL), CRYPTOGRAPHY_LIBRESSL_380_OR_GREATER))]
+            openssl::pkey::Id::RSA_PSS => ("RSA", true),
+            #[cfg(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER)]
+            openssl::pkey::Id::SM2 => ("SM2", true),
+            openssl::pkey::Id::EC => ("EC", true),
+            openssl::pkey::Id::DSA => ("DSA", true),
+            openssl::pkey::Id::DH => ("DH", true),
+            #[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
+            openssl::pkey::Id::DHX => ("DH", true),
+            openssl::pkey::Id::ED25519 => ("Ed25519", false),
+            openssl::pkey::Id::X25519 => ("X25519", false),
+            #[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
+            openssl::pkey::Id::ED448 => ("Ed448", false),
+            #[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
+            openssl::pkey::Id::X448 => ("X448", false),
+            #[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+            _ if cryptography_openssl::mldsa::Variant::from_pkey(pkey).is_some() => {
+                ("ML-DSA", false)
+            }
+            #[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+            _ if cryptography_openssl::slhdsa::Variant::from_pkey(pkey).is_some() => {
+                ("SLH-DSA", false)
+            }
+            _ => {
+                return Err(CryptographyError::from(
+                    exceptions::UnsupportedAlgorithm::new_err("Unsupported key type."),
+                ))
+            }
+        };
+        // Curves we don't support, or keys with explicit curve parameters,
+        // are reported without a curve.
+        let curve = match pkey
+            .ec_key()
+            .ok()
+            .and_then(|ec| crate::backend::ec::py_curve_from_curve(py, ec.group()).ok())
+        {
+            Some(curve) => Some(curve.getattr(pyo3::intern!(py, "name"))?.extract()?),
+            None => None,
+        };
+        Ok(KeyInfo {
+            algorithm: Some(algorithm),
+            key_size: if sized { Some(pkey.bits()) } else { None },
+            curve,
+            private,
+            encrypted: false,
+        })
+    }
+}
+
+fn identify_der_key(py: pyo3::Python<'_>, data: &[u8]) -> CryptographyResult<KeyInfo> {
+    match pkcs8::identify_private_key(data) {
+        Ok(PrivateKeyFormat::EncryptedPkcs8(_)) => return Ok(KeyInfo::encrypted(None)),
+        Ok(PrivateKeyFormat::Pkcs8(info)) if info.public_key.is_some() => {
+            let pkey = load_one_asymmetric_key(py, info, None)?;
+            return KeyInfo::from_pkey(py, &pkey, true);
+        }
+        _ => {}
+    }
+    // Keys are parsed without any validation, so this is cheap even for RSA
+    // keys.
+    if let Ok(pkey) = openssl::pkey::PKey::private_key_from_der(data) {
+        return KeyInfo::from_pkey(py, &pkey, true);
+    }
+    if let Ok(pkey) = cryptography_key_parsing::spki::parse_public_key(data) {
+        return KeyInfo::from_pkey(py, &pkey, false);
+    }
+    Err(CryptographyError::from(
+        pyo3::exceptions::PyValueError::new_err("Could not identify the key."),
+    ))
+}
+
+#[pyo3::prelude::pyfunction]
+fn identify_key(py: pyo3::Python<'_>, data: CffiBuf<'_>) -> CryptographyResult<KeyInfo> {
+    let data = data.as_bytes();
+    let blocks = match pem::parse_many(data) {
+        Ok(blocks) if !blocks.is_empty() => blocks,
+        _ => return identify_der_key(py, data),
+    };
+    let p = blocks
+        .iter()
+        .find(|p| {
+            PRIVATE_KEY_PEM_TAGS.contains(&p.tag())
+                || p.tag() == "PUBLIC KEY"
+                || p.tag() == "RSA PUBLIC KEY"
+        })
+        .ok_or_else(|| {
+            CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+                "No key found in the PEM data.",
+            ))
+        })?;
+    // Traditional OpenSSL keys are encrypted with PEM headers, so only the
+    // algorithm can be identified.
+    let traditional_algorithm = match p.tag() {
+        "RSA PRIVATE KEY" => Some("RSA"),
+        "DSA PRIVATE KEY" => Some("DSA"),
+        "EC PRIVATE KEY" => Some("EC"),
+        _ => None,
+    };
+    if traditional_algorithm.is_some()
+        && matches!(
+            p.headers().get("Proc-Type"),
+            Some(proc_type) if proc_type.ends_with("ENCRYPTED")
+        )
+    {
+        return Ok(KeyInfo::encrypted(traditional_algorithm));
+    }
+    match p.tag() {
+        "RSA PUBLIC KEY" => {
+            let pkey = cryptography_key_parsing::rsa::parse_pkcs1_public_key(p.contents())?;
+            KeyInfo::from_pkey(py, &pkey, false)
+        }
+        "RSA PRIVATE KEY" => {
+            let rsa = openssl::rsa::Rsa::private_key_from_der(p.contents())?;
+            let pkey = openssl::pkey::PKey::from_rsa(rsa)?;
+            KeyInfo::from_pkey(py, &pkey, true)
+        }
+        "DSA PRIVATE KEY" => {
+            let dsa = cryptography_openssl::dsa::private_key_from_der(p.contents())?;
+            let pkey = openssl::pkey::PKey::from_dsa(dsa)?;
+            KeyInfo::from_pkey(py, &pkey, true)
+        }
+        "EC PRIVATE KEY" => {
+            let ec = openssl::ec::EcKey::private_key_from_der(p.contents())?;
+            let pkey = openssl::pkey::PKey::from_ec_key(ec)?;
+            KeyInfo::from_pkey(py, &pkey, true)
+        }
+        _ => identify_der_key(py, p.contents()),
+    }
+}
+
 pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
     let m = pyo3::prelude::PyModule::new(py, "keys")?;
 
     m.add_function(pyo3::wrap_pyfunction!(load_pem_private_key, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(load_pem_private_keys, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(load_pem_private_key_from_file, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(load_pem_key_and_certificates, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(load_pem_parameters, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(load_der_parameters, m)?)?;
     m.add_function(pyo3::wrap_pyfunction!(load_der_private_key, m)?)?;
     m.add_function(pyo3::wrap_pyfunction!(load_der_public_key, m)?)?;
     m.add_function(pyo3::wrap_pyfunction!(load_pem_public_key, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(load_ssh_private_key, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(load_private_key, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(reencrypt_private_key, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(identify_key, m)?)?;
 
     m.add_function(pyo3::wrap_pyfunction!(private_key_from_ptr, m)?)?;
 
+    m.add_class::<KeyInfo>()?;
+
     Ok(m)
 }
 
@@ -0,0 +1,170 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Signing keys held by the operating system's key store. Their private
+//! components usually can't be exported, so they're exposed as external
+//! signers rather than as private keys.
+
+use crate::backend::{keys, utils};
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::exceptions;
+use cryptography_keystore::{Curve, HashAlgorithm, KeystoreError, PublicKey};
+
+#[pyo3::prelude::pyclass(
+    frozen,
+    module = "cryptography.hazmat.bindings._rust.openssl.keystore",
+    name = "KeystoreKey"
+)]
+struct KeystoreKey {
+    key: cryptography_keystore::Key,
+    public_key: pyo3::PyObject,
+    public_pkey: openssl::pkey::PKey<openssl::pkey::Public>,
+}
+
+fn keystore_error(e: KeystoreError) -> CryptographyError {
+    match e {
+        KeystoreError::Unsupported => {
+            CryptographyError::from(exceptions::UnsupportedAlgorithm::new_err((
+                "The operating system's key store is only supported on Windows and macOS.",
+                exceptions::Reasons::BACKEND_MISSING_INTERFACE,
+            )))
+        }
+        KeystoreError::Os(function, status) => {
+            CryptographyError::from(pyo3::exceptions::PyValueError::new_err(format!(
+                "{function} failed with status {status:#x}"
+            )))
+        }
+    }
+}
+
+fn unsupported_hash() -> CryptographyError {
+    CryptographyError::from(exceptions::UnsupportedAlgorithm::new_err((
+        "Keys from the key store only sign with SHA1, SHA256, SHA384, or SHA512.",
+        exceptions::Reasons::UNSUPPORTED_HASH,
+    )))
+}
+
+fn public_pkey(
+    public_key: &PublicKey,
+) -> CryptographyResult<openssl::pkey::PKey<openssl::pkey::Public>> {
+    let pkey = match public_key {
+        PublicKey::Rsa { n, e } => {
+            let rsa = openssl::rsa::Rsa::from_public_components(
+                openssl::bn::BigNum::from_slice(n)?,
+                openssl::bn::BigNum::from_slice(e)?,
+            )?;
+            openssl::pkey::PKey::from_rsa(rsa)?
+        }
+        PublicKey::RsaPkcs1(der) => {
+            openssl::pkey::PKey::from_rsa(openssl::rsa::Rsa::public_key_from_der_pkcs1(der)?)?
+        }
+        PublicKey::Ec { curve, point } => {
+            let nid = match curve {
+                Curve::P256 => openssl::nid::Nid::X9_62_PRIME256V1,
+                Curve::P384 => openssl::nid::Nid::SECP384R1,
+                Curve::P521 => openssl::nid::Nid::SECP521R1,
+            };
+            let group = openssl::ec::EcGroup::from_curve_name(nid)?;
+            let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+            let point = openssl::ec::EcPoint::from_bytes(&group, point, &mut bn_ctx)?;
+            openssl::pkey::PKey::from_ec_key(openssl::ec::EcKey::from_public_key(&group, &point)?)?
+        }
+    };
+    Ok(pkey)
+}
+
+#[pyo3::prelude::pyfunction]
+fn list_keys(py: pyo3::Python<'_>) -> CryptographyResult<Vec<KeystoreKey>> {
+    // Listing keys may prompt the user or talk to a smart card, so other
+    // threads are allowed to run meanwhile.
+    py.allow_threads(cryptography_keystore::list_keys)
+        .map_err(keystore_error)?
+        .into_iter()
+        .map(|key| {
+            let public_pkey = public_pkey(&key.public_key)?;
+            let public_key = keys::public_key_from_pkey(py, &public_pkey, public_pkey.id())?;
+            Ok(KeystoreKey {
+                key,
+                public_key,
+                public_pkey,
+            })
+        })
+        .collect()
+}
+
+#[pyo3::prelude::pymethods]
+impl KeystoreKey {
+    #[getter]
+    fn name(&self) -> &str {
+        &self.key.name
+    }
+
+    fn public_key(&self, py: pyo3::Python<'_>) -> pyo3::PyObject {
+        self.public_key.clone_ref(py)
+    }
+
+    fn sign<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        data: &[u8],
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        if algorithm.is_none() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyTypeError::new_err(
+                    "Keys from the key store require a hash algorithm.",
+                ),
+            ));
+        }
+        let (digest, algorithm) = utils::calculate_digest_and_algorithm(py, data, algorithm)?;
+        let name: &str = algorithm.getattr(pyo3::intern!(py, "name"))?.extract()?;
+        let hash = match name {
+            "sha1" => HashAlgorithm::Sha1,
+            "sha256" => HashAlgorithm::Sha256,
+            "sha384" => HashAlgorithm::Sha384,
+            "sha512" => HashAlgorithm::Sha512,
+            _ => return Err(unsupported_hash()),
+        };
+
+        // The key store may prompt the user before signing.
+        let sig = py
+            .allow_threads(|| self.key.sign_digest(hash, digest))
+            .map_err(keystore_error)?;
+        let sig = match self.key.public_key {
+            PublicKey::Ec { .. } => {
+                // ECDSA signatures are returned as r || s, but are DER
+                // encoded everywhere else.
+                let (r, s) = sig.split_at(sig.len() / 2);
+                openssl::ecdsa::EcdsaSig::from_private_components(
+                    openssl::bn::BigNum::from_slice(r)?,
+                    openssl::bn::BigNum::from_slice(s)?,
+                )?
+                .to_der()?
+            }
+            PublicKey::Rsa { .. } | PublicKey::RsaPkcs1(_) => sig,
+        };
+        Ok(pyo3::types::PyBytes::new(py, &sig))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<KeystoreKey(name={:?})>", self.key.name)
+    }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> bool {
+        self.public_pkey.public_eq(&other.public_pkey)
+    }
+
+    fn __hash__(&self) -> CryptographyResult<u64> {
+        utils::pkey_hash(&self.public_pkey)
+    }
+}
+
+pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let m = pyo3::prelude::PyModule::new(py, "keystore")?;
+    m.add_function(pyo3::wrap_pyfunction!(list_keys, m)?)?;
+
+    m.add_class::<KeystoreKey>()?;
+
+    Ok(m)
+}
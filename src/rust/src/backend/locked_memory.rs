@@ -0,0 +1,123 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+use cryptography_openssl::secure_heap;
+
+use crate::buf::CffiBuf;
+use crate::error::{CryptographyError, CryptographyResult};
+
+#[pyo3::prelude::pyfunction]
+fn initialize(size: usize, min_size: usize) -> CryptographyResult<()> {
+    if !size.is_power_of_two() || !min_size.is_power_of_two() || min_size >= size {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(
+                "size and min_size must be powers of two, and min_size must be smaller than size",
+            ),
+        ));
+    }
+    if secure_heap::is_initialized() {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("Locked memory is already initialized"),
+        ));
+    }
+    let status = secure_heap::init(size, min_size)
+        .map_err(|_| pyo3::exceptions::PyOSError::new_err("Unable to allocate locked memory"))?;
+    if status == secure_heap::InitStatus::NotLocked {
+        // Memory that can be swapped out doesn't provide any of the
+        // guarantees callers asked for, so don't leave it in place.
+        secure_heap::done();
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyOSError::new_err(
+                "Unable to lock memory, check the RLIMIT_MEMLOCK resource limit",
+            ),
+        ));
+    }
+    Ok(())
+}
+
+#[pyo3::prelude::pyfunction]
+fn is_initialized() -> bool {
+    secure_heap::is_initialized()
+}
+
+#[pyo3::prelude::pyfunction]
+fn used() -> usize {
+    secure_heap::used()
+}
+
+#[pyo3::prelude::pyclass(module = "cryptography.hazmat.bindings._rust.openssl.locked_memory")]
+pub(crate) struct LockedBuffer {
+    buf: secure_heap::SecureBuffer,
+}
+
+impl LockedBuffer {
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        self.buf.as_slice()
+    }
+}
+
+#[pyo3::prelude::pymethods]
+impl LockedBuffer {
+    #[new]
+    fn new(size: usize) -> CryptographyResult<LockedBuffer> {
+        if size == 0 {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("size must be positive"),
+            ));
+        }
+        if !secure_heap::is_initialized() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "Locked memory must be initialized before allocating a LockedBuffer",
+                ),
+            ));
+        }
+        let buf = secure_heap::SecureBuffer::new(size)
+            .map_err(|_| pyo3::exceptions::PyMemoryError::new_err("Locked memory is exhausted"))?;
+        Ok(LockedBuffer { buf })
+    }
+
+    fn __len__(&self) -> usize {
+        self.buf.as_slice().len()
+    }
+
+    #[pyo3(signature = (data, offset=0))]
+    fn write(&mut self, data: CffiBuf<'_>, offset: usize) -> CryptographyResult<()> {
+        let data = data.as_bytes();
+        let out = self.buf.as_mut_slice();
+        if offset
+            .checked_add(data.len())
+            .map_or(true, |end| end > out.len())
+        {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("data doesn't fit in the buffer"),
+            ));
+        }
+        out[offset..offset + data.len()].copy_from_slice(data);
+        Ok(())
+    }
+
+    fn randomize(&mut self) -> CryptographyResult<()> {
+        openssl::rand::rand_bytes(self.buf.as_mut_slice())?;
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        self.buf.as_mut_slice().fill(0);
+    }
+
+    fn to_bytes<'p>(&self, py: pyo3::Python<'p>) -> &'p pyo3::types::PyBytes {
+        pyo3::types::PyBytes::new(py, self.buf.as_slice())
+    }
+}
+
+pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let m = pyo3::prelude::PyModule::new(py, "locked_memory")?;
+    m.add_function(pyo3::wrap_pyfunction!(initialize, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(is_initialized, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(used, m)?)?;
+    m.add_class::<LockedBuffer>()?;
+
+    Ok(m)
+}
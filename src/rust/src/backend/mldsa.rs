@@ -0,0 +1,272 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+use cryptography_openssl::mldsa::{self, Variant};
+
+use crate::backend::utils;
+use crate::buf::CffiBuf;
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::{exceptions, types};
+
+#[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.mldsa")]
+pub(crate) struct MLDSAPrivateKey {
+    pkey: openssl::pkey::PKey<openssl::pkey::Private>,
+}
+
+#[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.mldsa")]
+pub(crate) struct MLDSAPublicKey {
+    pkey: openssl::pkey::PKey<openssl::pkey::Public>,
+}
+
+fn parameter_set_from_py(
+    py: pyo3::Python<'_>,
+    parameter_set: &pyo3::PyAny,
+) -> CryptographyResult<Variant> {
+    if parameter_set.is(types::ML_DSA_PARAMETER_SET_44.get(py)?) {
+        Ok(Variant::MlDsa44)
+    } else if parameter_set.is(types::ML_DSA_PARAMETER_SET_65.get(py)?) {
+        Ok(Variant::MlDsa65)
+    } else if parameter_set.is(types::ML_DSA_PARAMETER_SET_87.get(py)?) {
+        Ok(Variant::MlDsa87)
+    } else {
+        Err(CryptographyError::from(
+            pyo3::exceptions::PyTypeError::new_err("parameter_set must be an MLDSAParameterSet"),
+        ))
+    }
+}
+
+fn parameter_set_to_py<'p, T>(
+    py: pyo3::Python<'p>,
+    pkey: &openssl::pkey::PKeyRef<T>,
+) -> pyo3::PyResult<&'p pyo3::PyAny> {
+    // Keys are only ever created for one of the parameter sets.
+    match Variant::from_pkey(pkey).unwrap() {
+        Variant::MlDsa44 => types::ML_DSA_PARAMETER_SET_44.get(py),
+        Variant::MlDsa65 => types::ML_DSA_PARAMETER_SET_65.get(py),
+        Variant::MlDsa87 => types::ML_DSA_PARAMETER_SET_87.get(py),
+    }
+}
+
+// FIPS 204 limits contexts to 255 bytes.
+fn check_context(context: &[u8]) -> CryptographyResult<()> {
+    if context.len() > 255 {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("context must be at most 255 bytes long"),
+        ));
+    }
+    Ok(())
+}
+
+pub(crate) fn private_key_from_pkey(
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+) -> MLDSAPrivateKey {
+    MLDSAPrivateKey {
+        pkey: pkey.to_owned(),
+    }
+}
+
+pub(crate) fn public_key_from_pkey(
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Public>,
+) -> MLDSAPublicKey {
+    MLDSAPublicKey {
+        pkey: pkey.to_owned(),
+    }
+}
+
+#[pyo3::prelude::pyfunction]
+fn generate_key(
+    py: pyo3::Python<'_>,
+    parameter_set: &pyo3::PyAny,
+) -> CryptographyResult<MLDSAPrivateKey> {
+    let variant = parameter_set_from_py(py, parameter_set)?;
+    Ok(MLDSAPrivateKey {
+        pkey: mldsa::generate(variant)?,
+    })
+}
+
+#[pyo3::prelude::pyfunction]
+fn from_seed_bytes(
+    py: pyo3::Python<'_>,
+    parameter_set: &pyo3::PyAny,
+    data: CffiBuf<'_>,
+) -> CryptographyResult<MLDSAPrivateKey> {
+    let variant = parameter_set_from_py(py, parameter_set)?;
+    if data.as_bytes().len() != mldsa::SEED_LENGTH {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("An ML-DSA seed is 32 bytes long"),
+        ));
+    }
+    Ok(MLDSAPrivateKey {
+        pkey: mldsa::private_key_from_seed(variant, data.as_bytes())?,
+    })
+}
+
+#[pyo3::prelude::pyfunction]
+fn from_public_bytes(
+    py: pyo3::Python<'_>,
+    parameter_set: &pyo3::PyAny,
+    data: &[u8],
+) -> CryptographyResult<MLDSAPublicKey> {
+    let variant = parameter_set_from_py(py, parameter_set)?;
+    let pkey = mldsa::public_key_from_raw_bytes(variant, data).map_err(|_| {
+        pyo3::exceptions::PyValueError::new_err("Invalid public key for this ML-DSA parameter set")
+    })?;
+    Ok(MLDSAPublicKey { pkey })
+}
+
+#[pyo3::prelude::pymethods]
+impl MLDSAPrivateKey {
+    #[getter]
+    fn parameter_set<'p>(&self, py: pyo3::Python<'p>) -> pyo3::PyResult<&'p pyo3::PyAny> {
+        parameter_set_to_py(py, &self.pkey)
+    }
+
+    #[pyo3(signature = (data, *, context=None))]
+    fn sign<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        data: &[u8],
+        context: Option<&[u8]>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let context = context.unwrap_or_default();
+        check_context(context)?;
+        let signature = mldsa::sign(&self.pkey, context, data)?;
+        Ok(pyo3::types::PyBytes::new(py, &signature))
+    }
+
+    fn public_key(&self) -> CryptographyResult<MLDSAPublicKey> {
+        let variant = Variant::from_pkey(&self.pkey).unwrap();
+        let raw_bytes = self.pkey.raw_public_key()?;
+        Ok(MLDSAPublicKey {
+            pkey: mldsa::public_key_from_raw_bytes(variant, &raw_bytes)?,
+        })
+    }
+
+    fn private_bytes_raw<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let seed = mldsa::seed(&self.pkey).ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(
+                "This key was loaded without its seed, so it can only be serialized as PKCS8",
+            )
+        })?;
+        Ok(pyo3::types::PyBytes::new(py, &seed))
+    }
+
+    fn private_bytes<'p>(
+        slf: &pyo3::PyCell<Self>,
+        py: pyo3::Python<'p>,
+        encoding: &pyo3::PyAny,
+        format: &pyo3::PyAny,
+        encryption_algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        // The raw encoding of an ML-DSA private key is its seed, rather than
+        // the expanded key which OpenSSL would return.
+        if encoding.is(types::ENCODING_RAW.get(py)?)
+            && format.is(types::PRIVATE_FORMAT_RAW.get(py)?)
+            && encryption_algorithm.is_instance(types::NO_ENCRYPTION.get(py)?)?
+        {
+            return slf.borrow().private_bytes_raw(py);
+        }
+        utils::pkey_private_bytes(
+            py,
+            slf,
+            &slf.borrow().pkey,
+            encoding,
+            format,
+            encryption_algorithm,
+            false,
+            true,
+        )
+    }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> CryptographyResult<bool> {
+        utils::pkey_private_eq(&self.pkey, &other.pkey)
+    }
+
+    fn __hash__(&self) -> CryptographyResult<u64> {
+        utils::pkey_hash(&self.pkey)
+    }
+
+    fn public_key_matches(
+        slf: &pyo3::PyCell<Self>,
+        py: pyo3::Python<'_>,
+        public_key: &pyo3::PyAny,
+    ) -> CryptographyResult<bool> {
+        utils::public_key_matches(py, slf, public_key)
+    }
+}
+
+#[pyo3::prelude::pymethods]
+impl MLDSAPublicKey {
+    #[getter]
+    fn parameter_set<'p>(&self, py: pyo3::Python<'p>) -> pyo3::PyResult<&'p pyo3::PyAny> {
+        parameter_set_to_py(py, &self.pkey)
+    }
+
+    #[pyo3(signature = (signature, data, *, context=None))]
+    fn verify(
+        &self,
+        signature: &[u8],
+        data: &[u8],
+        context: Option<&[u8]>,
+    ) -> CryptographyResult<()> {
+        let context = context.unwrap_or_default();
+        check_context(context)?;
+        let valid = mldsa::verify(&self.pkey, context, signature, data).unwrap_or(false);
+        if !valid {
+            return Err(CryptographyError::from(
+                exceptions::InvalidSignature::new_err(()),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn public_bytes_raw<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let raw_bytes = self.pkey.raw_public_key()?;
+        Ok(pyo3::types::PyBytes::new(py, &raw_bytes))
+    }
+
+    fn public_bytes<'p>(
+        slf: &pyo3::PyCell<Self>,
+        py: pyo3::Python<'p>,
+        encoding: &pyo3::PyAny,
+        format: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        utils::pkey_public_bytes(py, slf, &slf.borrow().pkey, encoding, format, false, true)
+    }
+
+    fn fingerprint<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::PyAny> {
+        utils::pkey_fingerprint(py, &self.pkey, algorithm)
+    }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> bool {
+        self.pkey.public_eq(&other.pkey)
+    }
+
+    fn __copy__(slf: pyo3::PyRef<'_, Self>) -> pyo3::PyRef<'_, Self> {
+        slf
+    }
+}
+
+pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let m = pyo3::prelude::PyModule::new(py, "mldsa")?;
+    m.add_function(pyo3::wrap_pyfunction!(generate_key, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(from_seed_bytes, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(from_public_bytes, m)?)?;
+
+    m.add_class::<MLDSAPrivateKey>()?;
+    m.add_class::<MLDSAPublicKey>()?;
+
+    Ok(m)
+}
@@ -3,47 +3,112 @@
 // for complete details.
 
 pub(crate) mod aead;
+pub(crate) mod age;
+#[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
+pub(crate) mod bignum;
+pub(crate) mod bls;
 pub(crate) mod cipher_registry;
 pub(crate) mod cmac;
 pub(crate) mod dh;
 pub(crate) mod dsa;
 pub(crate) mod ec;
+pub(crate) mod ecvrf;
 pub(crate) mod ed25519;
 #[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
 pub(crate) mod ed448;
+#[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
+pub(crate) mod falcon;
+pub(crate) mod fernet;
 pub(crate) mod hashes;
 pub(crate) mod hmac;
+#[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+pub(crate) mod hybrid;
+pub(crate) mod jwk;
 pub(crate) mod kdf;
+#[cfg(CRYPTOGRAPHY_OPENSSL_320_OR_GREATER)]
+pub(crate) mod kem;
 pub(crate) mod keys;
+pub(crate) mod keystore;
+#[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
+pub(crate) mod locked_memory;
+#[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+pub(crate) mod mldsa;
+pub(crate) mod openpgp;
+pub(crate) mod openssh;
+pub(crate) mod pbes2;
 pub(crate) mod poly1305;
+pub(crate) mod ppk;
+pub(crate) mod ristretto255;
 pub(crate) mod rsa;
+#[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+pub(crate) mod slhdsa;
+#[cfg(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER)]
+pub(crate) mod sm2;
+pub(crate) mod ssh_files;
+pub(crate) mod store;
+pub(crate) mod threshold_ecdsa;
+pub(crate) mod twofactor;
 pub(crate) mod utils;
 pub(crate) mod x25519;
 #[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
 pub(crate) mod x448;
+pub(crate) mod xeddsa;
+#[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+pub(crate) mod xwing;
 
 pub(crate) fn add_to_module(module: &pyo3::prelude::PyModule) -> pyo3::PyResult<()> {
     module.add_submodule(aead::create_module(module.py())?)?;
+    module.add_submodule(age::create_module(module.py())?)?;
+    #[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
+    module.add_submodule(bignum::create_module(module.py())?)?;
+    module.add_submodule(bls::create_module(module.py())?)?;
     module.add_submodule(cmac::create_module(module.py())?)?;
     module.add_submodule(dh::create_module(module.py())?)?;
     module.add_submodule(dsa::create_module(module.py())?)?;
     module.add_submodule(ec::create_module(module.py())?)?;
+    module.add_submodule(ecvrf::create_module(module.py())?)?;
+    module.add_submodule(fernet::create_module(module.py())?)?;
+    module.add_submodule(jwk::create_module(module.py())?)?;
     module.add_submodule(keys::create_module(module.py())?)?;
+    module.add_submodule(keystore::create_module(module.py())?)?;
+    #[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
+    module.add_submodule(locked_memory::create_module(module.py())?)?;
+    #[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+    module.add_submodule(mldsa::create_module(module.py())?)?;
+    #[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+    module.add_submodule(slhdsa::create_module(module.py())?)?;
 
     module.add_submodule(ed25519::create_module(module.py())?)?;
     #[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
     module.add_submodule(ed448::create_module(module.py())?)?;
+    #[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
+    module.add_submodule(falcon::create_module(module.py())?)?;
 
     module.add_submodule(x25519::create_module(module.py())?)?;
     #[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
     module.add_submodule(x448::create_module(module.py())?)?;
+    #[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+    module.add_submodule(xwing::create_module(module.py())?)?;
+    #[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+    module.add_submodule(hybrid::create_module(module.py())?)?;
 
+    module.add_submodule(openpgp::create_module(module.py())?)?;
     module.add_submodule(poly1305::create_module(module.py())?)?;
+    module.add_submodule(ppk::create_module(module.py())?)?;
+    module.add_submodule(ristretto255::create_module(module.py())?)?;
 
     module.add_submodule(hashes::create_module(module.py())?)?;
     module.add_submodule(hmac::create_module(module.py())?)?;
     module.add_submodule(kdf::create_module(module.py())?)?;
+    #[cfg(CRYPTOGRAPHY_OPENSSL_320_OR_GREATER)]
+    module.add_submodule(kem::create_module(module.py())?)?;
     module.add_submodule(rsa::create_module(module.py())?)?;
+    #[cfg(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER)]
+    module.add_submodule(sm2::create_module(module.py())?)?;
+    module.add_submodule(ssh_files::create_module(module.py())?)?;
+    module.add_submodule(store::create_module(module.py())?)?;
+    module.add_submodule(threshold_ecdsa::create_module(module.py())?)?;
+    module.add_submodule(twofactor::create_module(module.py())?)?;
 
     Ok(())
 }
@@ -0,0 +1,1079 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Loading of OpenPGP transferable public and secret keys, as specified by
+//! RFC 9580. Version 4 keys (also described by RFC 4880) and version 6 keys
+//! are supported. Only the key packets and user IDs are read: signatures,
+//! including the binding signatures of subkeys and user IDs, aren't checked.
+
+use std::borrow::Cow;
+
+use pyo3::IntoPy;
+
+use crate::backend::{kdf, keys, openssh};
+use crate::buf::CffiBuf;
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::{exceptions, types};
+
+// Packet type IDs.
+const SIGNATURE_TAG: u8 = 2;
+const SECRET_KEY_TAG: u8 = 5;
+const PUBLIC_KEY_TAG: u8 = 6;
+const SECRET_SUBKEY_TAG: u8 = 7;
+const MARKER_TAG: u8 = 10;
+const TRUST_TAG: u8 = 12;
+const USER_ID_TAG: u8 = 13;
+const PUBLIC_SUBKEY_TAG: u8 = 14;
+const USER_ATTRIBUTE_TAG: u8 = 17;
+const PADDING_TAG: u8 = 21;
+
+// Public key algorithm IDs.
+const RSA: u8 = 1;
+const RSA_ENCRYPT_ONLY: u8 = 2;
+const RSA_SIGN_ONLY: u8 = 3;
+const DSA: u8 = 17;
+const ECDH: u8 = 18;
+const ECDSA: u8 = 19;
+const EDDSA_LEGACY: u8 = 22;
+const X25519: u8 = 25;
+#[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
+const X448: u8 = 26;
+const ED25519: u8 = 27;
+#[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
+const ED448: u8 = 28;
+
+// Curve OIDs, without their DER tag and length.
+const P256_OID: &[u8] = b"\x2a\x86\x48\xce\x3d\x03\x01\x07";
+const P384_OID: &[u8] = b"\x2b\x81\x04\x00\x22";
+const P521_OID: &[u8] = b"\x2b\x81\x04\x00\x23";
+const SECP256K1_OID: &[u8] = b"\x2b\x81\x04\x00\x0a";
+#[cfg(not(CRYPTOGRAPHY_IS_BORINGSSL))]
+const BRAINPOOL_P256R1_OID: &[u8] = b"\x2b\x24\x03\x03\x02\x08\x01\x01\x07";
+#[cfg(not(CRYPTOGRAPHY_IS_BORINGSSL))]
+const BRAINPOOL_P384R1_OID: &[u8] = b"\x2b\x24\x03\x03\x02\x08\x01\x01\x0b";
+#[cfg(not(CRYPTOGRAPHY_IS_BORINGSSL))]
+const BRAINPOOL_P512R1_OID: &[u8] = b"\x2b\x24\x03\x03\x02\x08\x01\x01\x0d";
+const ED25519_LEGACY_OID: &[u8] = b"\x2b\x06\x01\x04\x01\xda\x47\x0f\x01";
+const CURVE25519_LEGACY_OID: &[u8] = b"\x2b\x06\x01\x04\x01\x97\x55\x01\x05\x01";
+
+// S2K usage octets.
+const S2K_USAGE_NONE: u8 = 0;
+const S2K_USAGE_AEAD: u8 = 253;
+const S2K_USAGE_CFB: u8 = 254;
+const S2K_USAGE_MALLEABLE_CFB: u8 = 255;
+
+// S2K specifier types. 101 is GnuPG's extension for secret keys which are
+// missing or held on a smartcard.
+const S2K_SIMPLE: u8 = 0;
+const S2K_SALTED: u8 = 1;
+const S2K_ITERATED_SALTED: u8 = 3;
+const S2K_ARGON2: u8 = 4;
+const S2K_GNU: u8 = 101;
+
+// AEAD algorithm IDs.
+const AEAD_OCB: u8 = 2;
+const AEAD_GCM: u8 = 3;
+const AEAD_TAG_LEN: usize = 16;
+
+const ARMOR_PREFIX: &str = "-----BEGIN PGP ";
+const PUBLIC_KEY_ARMOR: &str = "PUBLIC KEY BLOCK";
+const PRIVATE_KEY_ARMOR: &str = "PRIVATE KEY BLOCK";
+
+fn corrupt(msg: &'static str) -> CryptographyError {
+    CryptographyError::from(pyo3::exceptions::PyValueError::new_err(msg))
+}
+
+fn incorrect_password() -> CryptographyError {
+    corrupt("Incorrect password, could not decrypt key")
+}
+
+fn unsupported(msg: String, reason: exceptions::Reasons) -> CryptographyError {
+    CryptographyError::from(exceptions::UnsupportedAlgorithm::new_err((msg, reason)))
+}
+
+fn unsupported_curve() -> CryptographyError {
+    unsupported(
+        "Unsupported OpenPGP elliptic curve".to_string(),
+        exceptions::Reasons::UNSUPPORTED_ELLIPTIC_CURVE,
+    )
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn read_raw(&mut self, n: usize) -> CryptographyResult<&'a [u8]> {
+        if n > self.data.len() {
+            return Err(corrupt("Corrupt OpenPGP data: truncated packet"));
+        }
+        let (v, rest) = self.data.split_at(n);
+        self.data = rest;
+        Ok(v)
+    }
+
+    fn read_u8(&mut self) -> CryptographyResult<u8> {
+        Ok(self.read_raw(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> CryptographyResult<u16> {
+        let v = self.read_raw(2)?;
+        Ok(u16::from_be_bytes([v[0], v[1]]))
+    }
+
+    fn read_u32(&mut self) -> CryptographyResult<u32> {
+        let v = self.read_raw(4)?;
+        Ok(u32::from_be_bytes([v[0], v[1], v[2], v[3]]))
+    }
+
+    // A multiprecision integer: a two octet bit count, followed by the
+    // big-endian value.
+    fn read_mpi(&mut self) -> CryptographyResult<&'a [u8]> {
+        let bits = self.read_u16()?;
+        self.read_raw((usize::from(bits) + 7) / 8)
+    }
+
+    fn read_bn(&mut self) -> CryptographyResult<openssl::bn::BigNum> {
+        Ok(openssl::bn::BigNum::from_slice(self.read_mpi()?)?)
+    }
+
+    fn read_curve_oid(&mut self) -> CryptographyResult<&'a [u8]> {
+        let n = self.read_u8()?;
+        // Lengths 0 and 0xff are reserved for future extensions.
+        if n == 0 || n == 0xff {
+            return Err(corrupt("Corrupt OpenPGP data: invalid curve OID"));
+        }
+        self.read_raw(n.into())
+    }
+
+    fn check_empty(&self) -> CryptographyResult<()> {
+        if !self.data.is_empty() {
+            return Err(corrupt("Corrupt OpenPGP data: unparsed data"));
+        }
+        Ok(())
+    }
+}
+
+// The CRC-24 used by ASCII armor checksums.
+fn crc24(data: &[u8]) -> u32 {
+    let mut crc = 0xb7_04ce_u32;
+    for b in data {
+        crc ^= u32::from(*b) << 16;
+        for _ in 0..8 {
+            crc <<= 1;
+            if crc & 0x100_0000 != 0 {
+                crc ^= 0x186_4cfb;
+            }
+        }
+    }
+    crc & 0xff_ffff
+}
+
+// Removes ASCII armor, if `data` has it. The armor's type has to be
+// `expected`, and its checksum is verified if there is one.
+fn dearmor<'a>(data: &'a [u8], expected: &str) -> CryptographyResult<Cow<'a, [u8]>> {
+    let text = match std::str::from_utf8(data) {
+        Ok(text) if text.trim_start().starts_with(ARMOR_PREFIX) => text,
+        _ => return Ok(Cow::Borrowed(data)),
+    };
+    let mut lines = text.trim_start().lines().map(str::trim_end);
+    let kind = lines
+        .next()
+        .and_then(|line| line.strip_prefix(ARMOR_PREFIX))
+        .and_then(|line| line.strip_suffix("-----"));
+    if kind != Some(expected) {
+        return Err(corrupt("Unexpected OpenPGP armor type"));
+    }
+
+    // Armor headers are followed by an empty line.
+    for line in lines.by_ref() {
+        if line.is_empty() {
+            break;
+        }
+        if !line.contains(": ") {
+            return Err(corrupt("Corrupt OpenPGP armor: invalid header"));
+        }
+    }
+
+    let end = format!("-----END PGP {expected}-----");
+    let mut encoded = String::new();
+    let mut checksum = None;
+    let mut found_end = false;
+    for line in lines {
+        if line == end {
+            found_end = true;
+            break;
+        }
+        if checksum.is_some() {
+            return Err(corrupt("Corrupt OpenPGP armor: data after checksum"));
+        }
+        match line.strip_prefix('=') {
+            Some(crc) if crc.len() == 4 => checksum = Some(crc),
+            _ => encoded.push_str(line),
+        }
+    }
+    if !found_end {
+        return Err(corrupt("Corrupt OpenPGP armor: missing end line"));
+    }
+    let decoded = openssl::base64::decode_block(&encoded)
+        .map_err(|_| corrupt("Corrupt OpenPGP armor: invalid base64"))?;
+    if let Some(checksum) = checksum {
+        let expected_crc = openssl::base64::decode_block(checksum)
+            .map_err(|_| corrupt("Corrupt OpenPGP armor: invalid base64"))?;
+        if expected_crc != crc24(&decoded).to_be_bytes()[1..] {
+            return Err(corrupt("Corrupt OpenPGP armor: checksum mismatch"));
+        }
+    }
+    Ok(Cow::Owned(decoded))
+}
+
+struct Packet<'a> {
+    tag: u8,
+    body: &'a [u8],
+}
+
+// Splits `data` into packets, in either the current or the legacy packet
+// format. Partial body lengths are only allowed for data packets, so they're
+// rejected.
+fn read_packets(data: &[u8]) -> CryptographyResult<Vec<Packet<'_>>> {
+    let mut reader = Reader { data };
+    let mut packets = vec![];
+    while !reader.data.is_empty() {
+        let header = reader.read_u8()?;
+        if header & 0x80 == 0 {
+            return Err(corrupt("Not an OpenPGP key"));
+        }
+        let (tag, len) = if header & 0x40 != 0 {
+            let len = match reader.read_u8()? {
+                n @ 0..=191 => usize::from(n),
+                n @ 192..=223 => {
+                    let low = usize::from(reader.read_u8()?);
+                    (usize::from(n - 192) << 8) + low + 192
+                }
+                255 => reader.read_u32()? as usize,
+                _ => return Err(corrupt("Corrupt OpenPGP data: partial body length")),
+            };
+            (header & 0x3f, len)
+        } else {
+            let len = match header & 0x03 {
+                0 => usize::from(reader.read_u8()?),
+                1 => usize::from(reader.read_u16()?),
+                2 => reader.read_u32()? as usize,
+                _ => reader.data.len(),
+            };
+            ((header >> 2) & 0x0f, len)
+        };
+        packets.push(Packet {
+            tag,
+            body: reader.read_raw(len)?,
+        });
+    }
+    Ok(packets)
+}
+
+// The key material from a public key packet.
+enum PublicKey<'a> {
+    Rsa {
+        n: openssl::bn::BigNum,
+        e: openssl::bn::BigNum,
+    },
+    Dsa {
+        p: openssl::bn::BigNum,
+        q: openssl::bn::BigNum,
+        g: openssl::bn::BigNum,
+        y: openssl::bn::BigNum,
+    },
+    Ec {
+        nid: openssl::nid::Nid,
+        point: &'a [u8],
+    },
+    // Curve25519 and Curve448 keys, in their native encodings.
+    Raw {
+        id: openssl::pkey::Id,
+        point: &'a [u8],
+    },
+}
+
+struct KeyPacket<'a> {
+    version: u8,
+    creation_time: u32,
+    algorithm: u8,
+    public_key: PublicKey<'a>,
+    // The fields of the packet which make up the public key, which are
+    // hashed for the fingerprint.
+    public_body: &'a [u8],
+}
+
+fn curve_nid(oid: &[u8]) -> CryptographyResult<openssl::nid::Nid> {
+    match oid {
+        P256_OID => Ok(openssl::nid::Nid::X9_62_PRIME256V1),
+        P384_OID => Ok(openssl::nid::Nid::SECP384R1),
+        P521_OID => Ok(openssl::nid::Nid::SECP521R1),
+        SECP256K1_OID => Ok(openssl::nid::Nid::SECP256K1),
+        #[cfg(not(CRYPTOGRAPHY_IS_BORINGSSL))]
+        BRAINPOOL_P256R1_OID => Ok(openssl::nid::Nid::BRAINPOOL_P256R1),
+        #[cfg(not(CRYPTOGRAPHY_IS_BORINGSSL))]
+        BRAINPOOL_P384R1_OID => Ok(openssl::nid::Nid::BRAINPOOL_P384R1),
+        #[cfg(not(CRYPTOGRAPHY_IS_BORINGSSL))]
+        BRAINPOOL_P512R1_OID => Ok(openssl::nid::Nid::BRAINPOOL_P512R1),
+        _ => Err(unsupported_curve()),
+    }
+}
+
+// The legacy encodings of Ed25519 and X25519 points are prefixed with 0x40.
+fn legacy_point(point: &[u8]) -> CryptographyResult<&[u8]> {
+    match point.split_first() {
+        Some((0x40, point)) if point.len() == 32 => Ok(point),
+        _ => Err(corrupt("Corrupt OpenPGP data: invalid point")),
+    }
+}
+
+impl<'a> PublicKey<'a> {
+    fn read(algorithm: u8, reader: &mut Reader<'a>) -> CryptographyResult<PublicKey<'a>> {
+        match algorithm {
+            RSA | RSA_ENCRYPT_ONLY | RSA_SIGN_ONLY => Ok(PublicKey::Rsa {
+                n: reader.read_bn()?,
+                e: reader.read_bn()?,
+            }),
+            DSA => Ok(PublicKey::Dsa {
+                p: reader.read_bn()?,
+                q: reader.read_bn()?,
+                g: reader.read_bn()?,
+                y: reader.read_bn()?,
+            }),
+            ECDSA => {
+                let nid = curve_nid(reader.read_curve_oid()?)?;
+                Ok(PublicKey::Ec {
+                    nid,
+                    point: reader.read_mpi()?,
+                })
+            }
+            ECDH => {
+                let oid = reader.read_curve_oid()?;
+                let point = reader.read_mpi()?;
+                // The KDF parameters are only used for encryption.
+                let kdf_params_len = reader.read_u8()?;
+                reader.read_raw(kdf_params_len.into())?;
+                if oid == CURVE25519_LEGACY_OID {
+                    Ok(PublicKey::Raw {
+                        id: openssl::pkey::Id::X25519,
+                        point: legacy_point(point)?,
+                    })
+                } else {
+                    Ok(PublicKey::Ec {
+                        nid: curve_nid(oid)?,
+                        point,
+                    })
+                }
+            }
+            EDDSA_LEGACY => {
+                if reader.read_curve_oid()? != ED25519_LEGACY_OID {
+                    return Err(unsupported_curve());
+                }
+                Ok(PublicKey::Raw {
+                    id: openssl::pkey::Id::ED25519,
+                    point: legacy_point(reader.read_mpi()?)?,
+                })
+            }
+            X25519 => Ok(PublicKey::Raw {
+                id: openssl::pkey::Id::X25519,
+                point: reader.read_raw(32)?,
+            }),
+            ED25519 => Ok(PublicKey::Raw {
+                id: openssl::pkey::Id::ED25519,
+                point: reader.read_raw(32)?,
+            }),
+            #[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
+            X448 => Ok(PublicKey::Raw {
+                id: openssl::pkey::Id::X448,
+                point: reader.read_raw(56)?,
+            }),
+            #[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
+            ED448 => Ok(PublicKey::Raw {
+                id: openssl::pkey::Id::ED448,
+                point: reader.read_raw(57)?,
+            }),
+            _ => Err(unsupported(
+                format!("Unsupported OpenPGP public key algorithm: {algorithm}"),
+                exceptions::Reasons::UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
+            )),
+        }
+    }
+
+    fn to_pkey(&self) -> CryptographyResult<openssl::pkey::PKey<openssl::pkey::Public>> {
+        match self {
+            PublicKey::Rsa { n, e } => {
+                let rsa = openssl::rsa::Rsa::from_public_components(
+                    n.as_ref().to_owned()?,
+                    e.as_ref().to_owned()?,
+                )?;
+                Ok(openssl::pkey::PKey::from_rsa(rsa)?)
+            }
+            PublicKey::Dsa { p, q, g, y } => {
+                let dsa = openssl::dsa::Dsa::from_public_components(
+                    p.as_ref().to_owned()?,
+                    q.as_ref().to_owned()?,
+                    g.as_ref().to_owned()?,
+                    y.as_ref().to_owned()?,
+                )?;
+                Ok(openssl::pkey::PKey::from_dsa(dsa)?)
+            }
+            PublicKey::Ec { nid, point } => {
+                let group = openssl::ec::EcGroup::from_curve_name(*nid)?;
+                let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+                let point = openssl::ec::EcPoint::from_bytes(&group, point, &mut bn_ctx)
+                    .map_err(|_| corrupt("Corrupt OpenPGP data: invalid point"))?;
+                let ec = openssl::ec::EcKey::from_public_key(&group, &point)?;
+                Ok(openssl::pkey::PKey::from_ec_key(ec)?)
+            }
+            PublicKey::Raw { id, point } => {
+                Ok(openssl::pkey::PKey::public_key_from_raw_bytes(point, *id)
+                    .map_err(|_| corrupt("Corrupt OpenPGP data: invalid point"))?)
+            }
+        }
+    }
+
+    // Reads the secret key material matching this public key.
+    fn read_private_key(
+        &self,
+        algorithm: u8,
+        reader: &mut Reader<'_>,
+    ) -> CryptographyResult<openssl::pkey::PKey<openssl::pkey::Private>> {
+        match self {
+            PublicKey::Rsa { n, e } => {
+                let d = reader.read_bn()?;
+                let p = reader.read_bn()?;
+                let q = reader.read_bn()?;
+                // OpenPGP stores the inverse of p mod q, so the primes are
+                // swapped to get the CRT coefficient OpenSSL expects.
+                let u = reader.read_bn()?;
+                openssh::rsa_private_key(n.as_ref().to_owned()?, e.as_ref().to_owned()?, d, u, q, p)
+            }
+            PublicKey::Dsa { p, q, g, y } => {
+                let x = reader.read_bn()?;
+                let dsa = openssl::dsa::Dsa::from_private_components(
+                    p.as_ref().to_owned()?,
+                    q.as_ref().to_owned()?,
+                    g.as_ref().to_owned()?,
+                    x,
+                    y.as_ref().to_owned()?,
+                )?;
+                Ok(openssl::pkey::PKey::from_dsa(dsa)?)
+            }
+            PublicKey::Ec { nid, .. } => {
+                let secret = reader.read_bn()?;
+                openssh::ec_private_key(*nid, &secret)
+            }
+            PublicKey::Raw { id, point } => {
+                let secret = match algorithm {
+                    // The legacy encoding is a big-endian MPI, which may have
+                    // had leading zeros removed.
+                    EDDSA_LEGACY => left_pad(reader.read_mpi()?, 32)?,
+                    // X25519 secrets are stored as big-endian integers,
+                    // rather than in their native little-endian form.
+                    ECDH => {
+                        let mut secret = left_pad(reader.read_mpi()?, 32)?;
+                        secret.reverse();
+                        secret
+                    }
+                    // Native secrets are the same length as their public
+                    // keys.
+                    _ => reader.read_raw(point.len())?.to_vec(),
+                };
+                Ok(
+                    openssl::pkey::PKey::private_key_from_raw_bytes(&secret, *id)
+                        .map_err(|_| corrupt("Corrupt OpenPGP data: invalid secret key"))?,
+                )
+            }
+        }
+    }
+}
+
+fn left_pad(value: &[u8], len: usize) -> CryptographyResult<Vec<u8>> {
+    if value.len() > len {
+        return Err(corrupt("Corrupt OpenPGP data: invalid secret key"));
+    }
+    let mut padded = vec![0; len - value.len()];
+    padded.extend_from_slice(value);
+    Ok(padded)
+}
+
+impl<'a> KeyPacket<'a> {
+    // Reads the public fields of a key packet, returning the packet and the
+    // data following them, which holds any secret key material.
+    fn read(body: &'a [u8]) -> CryptographyResult<(KeyPacket<'a>, &'a [u8])> {
+        let mut reader = Reader { data: body };
+        let version = reader.read_u8()?;
+        if version != 4 && version != 6 {
+            return Err(unsupported(
+                format!("Unsupported OpenPGP key version: {version}"),
+                exceptions::Reasons::UNSUPPORTED_SERIALIZATION,
+            ));
+        }
+        let creation_time = reader.read_u32()?;
+        let algorithm = reader.read_u8()?;
+        let public_key = if version == 6 {
+            let len = reader.read_u32()? as usize;
+            let mut material = Reader {
+                data: reader.read_raw(len)?,
+            };
+            let public_key = PublicKey::read(algorithm, &mut material)?;
+            material.check_empty()?;
+            public_key
+        } else {
+            PublicKey::read(algorithm, &mut reader)?
+        };
+        let public_len = body.len() - reader.data.len();
+        if version == 4 && public_len > u16::MAX.into() {
+            return Err(corrupt("Corrupt OpenPGP data: key packet too long"));
+        }
+        Ok((
+            KeyPacket {
+                version,
+                creation_time,
+                algorithm,
+                public_key,
+                public_body: &body[..public_len],
+            },
+            reader.data,
+        ))
+    }
+
+    fn fingerprint(&self) -> Vec<u8> {
+        if self.version == 4 {
+            let mut h = openssl::sha::Sha1::new();
+            h.update(&[0x99]);
+            h.update(&u16::try_from(self.public_body.len()).unwrap().to_be_bytes());
+            h.update(self.public_body);
+            h.finish().to_vec()
+        } else {
+            let mut h = openssl::sha::Sha256::new();
+            h.update(&[0x9b]);
+            h.update(&u32::try_from(self.public_body.len()).unwrap().to_be_bytes());
+            h.update(self.public_body);
+            h.finish().to_vec()
+        }
+    }
+}
+
+enum S2k<'a> {
+    Simple {
+        md: openssl::hash::MessageDigest,
+    },
+    Salted {
+        md: openssl::hash::MessageDigest,
+        salt: &'a [u8],
+    },
+    IteratedSalted {
+        md: openssl::hash::MessageDigest,
+        salt: &'a [u8],
+        count: usize,
+    },
+    Argon2 {
+        salt: &'a [u8],
+        passes: u8,
+        parallelism: u8,
+        memory_exponent: u8,
+    },
+    // The secret key isn't present.
+    GnuDummy,
+}
+
+fn hash_algorithm(id: u8) -> CryptographyResult<openssl::hash::MessageDigest> {
+    let name = match id {
+        1 => "MD5",
+        2 => "SHA1",
+        3 => "RIPEMD160",
+        8 => "SHA256",
+        9 => "SHA384",
+        10 => "SHA512",
+        11 => "SHA224",
+        12 => "SHA3-256",
+        14 => "SHA3-512",
+        _ => "",
+    };
+    openssl::hash::MessageDigest::from_name(name).ok_or_else(|| {
+        unsupported(
+            format!("Unsupported OpenPGP S2K hash algorithm: {id}"),
+            exceptions::Reasons::UNSUPPORTED_HASH,
+        )
+    })
+}
+
+impl<'a> S2k<'a> {
+    fn read(reader: &mut Reader<'a>) -> CryptographyResult<S2k<'a>> {
+        match reader.read_u8()? {
+            S2K_SIMPLE => Ok(S2k::Simple {
+                md: hash_algorithm(reader.read_u8()?)?,
+            }),
+            S2K_SALTED => Ok(S2k::Salted {
+                md: hash_algorithm(reader.read_u8()?)?,
+                salt: reader.read_raw(8)?,
+            }),
+            S2K_ITERATED_SALTED => {
+                let md = hash_algorithm(reader.read_u8()?)?;
+                let salt = reader.read_raw(8)?;
+                let c = usize::from(reader.read_u8()?);
+                Ok(S2k::IteratedSalted {
+                    md,
+                    salt,
+                    count: (16 + (c & 15)) << ((c >> 4) + 6),
+                })
+            }
+            S2K_ARGON2 => {
+                let salt = reader.read_raw(16)?;
+                let passes = reader.read_u8()?;
+                let parallelism = reader.read_u8()?;
+                let memory_exponent = reader.read_u8()?;
+                if memory_exponent > 31 {
+                    return Err(corrupt("Corrupt OpenPGP data: invalid Argon2 memory size"));
+                }
+                Ok(S2k::Argon2 {
+                    salt,
+                    passes,
+                    parallelism,
+                    memory_exponent,
+                })
+            }
+            S2K_GNU => {
+                // A (meaningless) hash algorithm, the string "GNU" and the
+                // extension's mode: 1 for a missing secret key, and 2 for
+                // one held on a smartcard.
+                reader.read_u8()?;
+                if reader.read_raw(3)? != b"GNU" {
+                    return Err(corrupt("Corrupt OpenPGP data: invalid S2K specifier"));
+                }
+                match reader.read_u8()? {
+                    1 | 2 => Ok(S2k::GnuDummy),
+                    _ => Err(unsupported(
+                        "Unsupported GnuPG S2K extension".to_string(),
+                        exceptions::Reasons::UNSUPPORTED_SERIALIZATION,
+                    )),
+                }
+            }
+            s2k_type => Err(unsupported(
+                format!("Unsupported OpenPGP S2K type: {s2k_type}"),
+                exceptions::Reasons::UNSUPPORTED_SERIALIZATION,
+            )),
+        }
+    }
+
+    fn derive_key(
+        &self,
+        py: pyo3::Python<'_>,
+        password: &[u8],
+        key_len: usize,
+    ) -> CryptographyResult<Vec<u8>> {
+        let (md, salt, count) = match self {
+            S2k::Simple { md } => (*md, &b""[..], 0),
+            S2k::Salted { md, salt } => (*md, *salt, 0),
+            S2k::IteratedSalted { md, salt, count } => (*md, *salt, *count),
+            S2k::Argon2 {
+                salt,
+                passes,
+                parallelism,
+                memory_exponent,
+            } => {
+                let derived = types::ARGON2_KDF
+                    .get(py)?
+                    .call1((
+                        "Argon2id",
+                        password,
+                        *salt,
+                        *passes,
+                        1u64 << memory_exponent,
+                        *parallelism,
+                        key_len,
+                    ))?
+                    .extract::<&[u8]>()?;
+                return Ok(derived.to_vec());
+            }
+            S2k::GnuDummy => unreachable!(),
+        };
+
+        // The salt and password are hashed repeatedly until `count` bytes
+        // have been hashed, and always at least once. When one hash isn't
+        // long enough for the key, further hash contexts are used, each
+        // preloaded with one more zero byte than the last.
+        let input_len = salt.len() + password.len();
+        let count = std::cmp::max(count, input_len);
+        let mut key = vec![];
+        let mut preload = 0;
+        while key.len() < key_len {
+            let mut h = openssl::hash::Hasher::new(md)?;
+            h.update(&vec![0; preload])?;
+            let mut remaining = count;
+            while remaining > 0 {
+                let n = std::cmp::min(remaining, salt.len());
+                h.update(&salt[..n])?;
+                let m = std::cmp::min(remaining - n, password.len());
+                h.update(&password[..m])?;
+                remaining -= n + m;
+            }
+            key.extend_from_slice(&h.finish()?);
+            preload += 1;
+        }
+        key.truncate(key_len);
+        Ok(key)
+    }
+}
+
+fn unsupported_symmetric_algorithm(id: u8) -> CryptographyError {
+    unsupported(
+        format!("Unsupported OpenPGP symmetric algorithm: {id}"),
+        exceptions::Reasons::UNSUPPORTED_CIPHER,
+    )
+}
+
+fn cfb_cipher(id: u8) -> CryptographyResult<openssl::symm::Cipher> {
+    match id {
+        #[cfg(not(CRYPTOGRAPHY_IS_BORINGSSL))]
+        2 => Ok(openssl::symm::Cipher::des_ede3_cfb64()),
+        7 => Ok(openssl::symm::Cipher::aes_128_cfb128()),
+        8 => Ok(openssl::symm::Cipher::aes_192_cfb128()),
+        9 => Ok(openssl::symm::Cipher::aes_256_cfb128()),
+        _ => Err(unsupported_symmetric_algorithm(id)),
+    }
+}
+
+fn gcm_cipher(id: u8) -> CryptographyResult<openssl::symm::Cipher> {
+    match id {
+        7 => Ok(openssl::symm::Cipher::aes_128_gcm()),
+        8 => Ok(openssl::symm::Cipher::aes_192_gcm()),
+        9 => Ok(openssl::symm::Cipher::aes_256_gcm()),
+        _ => Err(unsupported_symmetric_algorithm(id)),
+    }
+}
+
+fn ocb_cipher(id: u8) -> CryptographyResult<openssl::symm::Cipher> {
+    cfg_if::cfg_if! {
+        if #[cfg(any(CRYPTOGRAPHY_IS_LIBRESSL, CRYPTOGRAPHY_IS_BORINGSSL))] {
+            let _ = id;
+        } else {
+            if !cryptography_openssl::fips::is_enabled() {
+                return match id {
+                    7 => Ok(openssl::symm::Cipher::aes_128_ocb()),
+                    8 => Ok(openssl::symm::Cipher::aes_192_ocb()),
+                    9 => Ok(openssl::symm::Cipher::aes_256_ocb()),
+                    _ => Err(unsupported_symmetric_algorithm(id)),
+                };
+            }
+        }
+    }
+    Err(unsupported(
+        "AES-OCB3 is not supported by this version of OpenSSL".to_string(),
+        exceptions::Reasons::UNSUPPORTED_CIPHER,
+    ))
+}
+
+fn password_required(password: Option<&[u8]>) -> CryptographyResult<&[u8]> {
+    password.ok_or_else(|| {
+        CryptographyError::from(pyo3::exceptions::PyTypeError::new_err(
+            "Password was not given but private key is encrypted",
+        ))
+    })
+}
+
+// Two octet checksums are the sum of all the octets, modulo 65536.
+fn checksum(data: &[u8]) -> u16 {
+    data.iter()
+        .fold(0u16, |sum, b| sum.wrapping_add(u16::from(*b)))
+}
+
+// Reads and, if necessary, decrypts the secret key material that follows the
+// public fields of a secret key packet. Returns `None` if the packet has no
+// secret key, as with GnuPG's stubs for keys held on smartcards.
+fn read_secret_key(
+    py: pyo3::Python<'_>,
+    tag: u8,
+    packet: &KeyPacket<'_>,
+    data: &[u8],
+    password: Option<&[u8]>,
+) -> CryptographyResult<Option<openssl::pkey::PKey<openssl::pkey::Private>>> {
+    let mut reader = Reader { data };
+    let usage = reader.read_u8()?;
+    if usage == S2K_USAGE_NONE {
+        let fields = if packet.version == 4 {
+            if reader.data.len() < 2 {
+                return Err(corrupt("Corrupt OpenPGP data: truncated packet"));
+            }
+            let (fields, check) = reader.data.split_at(reader.data.len() - 2);
+            if checksum(fields).to_be_bytes() != check {
+                return Err(corrupt(
+                    "Corrupt OpenPGP data: secret key checksum mismatch",
+                ));
+            }
+            fields
+        } else {
+            reader.data
+        };
+        return read_private_key(packet, fields).map(Some);
+    }
+    if usage != S2K_USAGE_AEAD && usage != S2K_USAGE_CFB && usage != S2K_USAGE_MALLEABLE_CFB {
+        return Err(unsupported(
+            format!("Unsupported OpenPGP S2K usage: {usage}"),
+            exceptions::Reasons::UNSUPPORTED_SERIALIZATION,
+        ));
+    }
+
+    // Version 6 keys give the length of the parameters, and of the S2K
+    // specifier, so that readers can skip them.
+    let mut params = if packet.version == 6 {
+        let len = reader.read_u8()?;
+        Reader {
+            data: reader.read_raw(len.into())?,
+        }
+    } else {
+        Reader { data: reader.data }
+    };
+    let symmetric_id = params.read_u8()?;
+    let aead_id = if usage == S2K_USAGE_AEAD {
+        Some(params.read_u8()?)
+    } else {
+        None
+    };
+    let s2k = if packet.version == 6 {
+        let len = params.read_u8()?;
+        let mut s2k_reader = Reader {
+            data: params.read_raw(len.into())?,
+        };
+        let s2k = S2k::read(&mut s2k_reader)?;
+        if !matches!(s2k, S2k::GnuDummy) {
+            s2k_reader.check_empty()?;
+        }
+        s2k
+    } else {
+        S2k::read(&mut params)?
+    };
+    if let S2k::GnuDummy = s2k {
+        return Ok(None);
+    }
+
+    let (cipher, iv_len) = match aead_id {
+        None => {
+            let cipher = cfb_cipher(symmetric_id)?;
+            (cipher, cipher.iv_len().unwrap_or(0))
+        }
+        Some(AEAD_OCB) => (ocb_cipher(symmetric_id)?, 15),
+        Some(AEAD_GCM) => (gcm_cipher(symmetric_id)?, 12),
+        Some(aead_id) => {
+            return Err(unsupported(
+                format!("Unsupported OpenPGP AEAD algorithm: {aead_id}"),
+                exceptions::Reasons::UNSUPPORTED_CIPHER,
+            ))
+        }
+    };
+    let iv = params.read_raw(iv_len)?;
+    let encrypted = if packet.version == 6 {
+        params.check_empty()?;
+        reader.data
+    } else {
+        params.data
+    };
+
+    let password = password_required(password)?;
+    let key = s2k.derive_key(py, password, cipher.key_len())?;
+    let fields = match aead_id {
+        Some(aead_id) => {
+            if encrypted.len() < AEAD_TAG_LEN {
+                return Err(corrupt("Corrupt OpenPGP data: truncated packet"));
+            }
+            let info = [0xc0 | tag, packet.version, symmetric_id, aead_id];
+            let key = kdf::derive_hkdf(
+                openssl::hash::MessageDigest::sha256(),
+                b"",
+                &info,
+                cipher.key_len(),
+                &key,
+            )?;
+            let mut aad = vec![0xc0 | tag];
+            aad.extend_from_slice(packet.public_body);
+            let (ciphertext, auth_tag) = encrypted.split_at(encrypted.len() - AEAD_TAG_LEN);
+            openssl::symm::decrypt_aead(cipher, &key, Some(iv), &aad, ciphertext, auth_tag)
+                .map_err(|_| incorrect_password())?
+        }
+        None => {
+            let plaintext = openssl::symm::decrypt(cipher, &key, Some(iv), encrypted)?;
+            // The secret fields are followed by their SHA-1 hash or, in the
+            // deprecated malleable mode, their checksum. Either one failing
+            // to match means the password was wrong.
+            let check_len = if usage == S2K_USAGE_CFB { 20 } else { 2 };
+            if plaintext.len() < check_len {
+                return Err(incorrect_password());
+            }
+            let (fields, check) = plaintext.split_at(plaintext.len() - check_len);
+            let valid = if usage == S2K_USAGE_CFB {
+                openssl::sha::sha1(fields) == check
+            } else {
+                checksum(fields).to_be_bytes() == check
+            };
+            if !valid {
+                return Err(incorrect_password());
+            }
+            fields.to_vec()
+        }
+    };
+    read_private_key(packet, &fields).map(Some)
+}
+
+fn read_private_key(
+    packet: &KeyPacket<'_>,
+    fields: &[u8],
+) -> CryptographyResult<openssl::pkey::PKey<openssl::pkey::Private>> {
+    let mut reader = Reader { data: fields };
+    let pkey = packet
+        .public_key
+        .read_private_key(packet.algorithm, &mut reader)?;
+    reader.check_empty()?;
+    if pkey.public_key_to_der()? != packet.public_key.to_pkey()?.public_key_to_der()? {
+        return Err(corrupt("Corrupt OpenPGP data: public key mismatch"));
+    }
+    Ok(pkey)
+}
+
+type PyOpenPGPKey = (u8, u32, pyo3::PyObject, pyo3::PyObject);
+
+fn key_tuple(py: pyo3::Python<'_>, packet: &KeyPacket<'_>, key: pyo3::PyObject) -> PyOpenPGPKey {
+    (
+        packet.version,
+        packet.creation_time,
+        pyo3::types::PyBytes::new(py, &packet.fingerprint()).into_py(py),
+        key,
+    )
+}
+
+// Loads a transferable key: a primary key, followed by its user IDs and
+// subkeys. If `secret` is set, the primary key must be a secret key, and any
+// secret key material is decrypted with `password`. Otherwise only public
+// keys are allowed.
+fn load_transferable_key(
+    py: pyo3::Python<'_>,
+    data: &[u8],
+    secret: bool,
+    password: Option<&[u8]>,
+) -> CryptographyResult<(PyOpenPGPKey, Vec<String>, Vec<PyOpenPGPKey>)> {
+    let data = dearmor(
+        data,
+        if secret {
+            PRIVATE_KEY_ARMOR
+        } else {
+            PUBLIC_KEY_ARMOR
+        },
+    )?;
+    let packets = read_packets(&data)?;
+
+    let mut primary = None;
+    let mut user_ids = vec![];
+    let mut subkeys = vec![];
+    for packet in packets {
+        match packet.tag {
+            SECRET_KEY_TAG | PUBLIC_KEY_TAG | SECRET_SUBKEY_TAG | PUBLIC_SUBKEY_TAG => {
+                let is_primary = packet.tag == SECRET_KEY_TAG || packet.tag == PUBLIC_KEY_TAG;
+                let is_secret = packet.tag == SECRET_KEY_TAG || packet.tag == SECRET_SUBKEY_TAG;
+                if is_primary && primary.is_some() {
+                    return Err(corrupt("OpenPGP data contains more than one key"));
+                }
+                if !is_primary && primary.is_none() {
+                    return Err(corrupt("Corrupt OpenPGP data: subkey before primary key"));
+                }
+                if is_secret && !secret {
+                    return Err(corrupt(
+                        "OpenPGP data contains a secret key, use load_openpgp_private_key",
+                    ));
+                }
+                // Secret keys may have public subkeys, but not the reverse.
+                if is_primary && !is_secret && secret {
+                    return Err(corrupt("OpenPGP data doesn't contain a secret key"));
+                }
+
+                let (key_packet, rest) = KeyPacket::read(packet.body)?;
+                let private_key = if is_secret {
+                    read_secret_key(py, packet.tag, &key_packet, rest, password)?
+                } else {
+                    Reader { data: rest }.check_empty()?;
+                    None
+                };
+                let key = match private_key {
+                    // We don't support turning off RSA key validation when
+                    // loading OpenPGP keys. A key that fails validation can
+                    // leave OpenSSL's reasons on the error stack, which are
+                    // cleared as it's reported as a ValueError.
+                    Some(pkey) => keys::private_key_from_pkey(py, &pkey, false).map_err(|e| {
+                        openssl::error::ErrorStack::get();
+                        e
+                    })?,
+                    None => {
+                        let pkey = key_packet.public_key.to_pkey()?;
+                        keys::public_key_from_pkey(py, &pkey, pkey.id())?
+                    }
+                };
+                let key = key_tuple(py, &key_packet, key);
+                if is_primary {
+                    primary = Some(key);
+                } else {
+                    subkeys.push(key);
+                }
+            }
+            USER_ID_TAG => {
+                if primary.is_none() {
+                    return Err(corrupt("Corrupt OpenPGP data: user ID before primary key"));
+                }
+                user_ids.push(String::from_utf8_lossy(packet.body).into_owned());
+            }
+            // Signatures, including self-signatures, aren't verified.
+            SIGNATURE_TAG | TRUST_TAG | USER_ATTRIBUTE_TAG | PADDING_TAG | MARKER_TAG => {}
+            _ => return Err(corrupt("Corrupt OpenPGP data: unexpected packet")),
+        }
+    }
+
+    let primary = primary.ok_or_else(|| corrupt("OpenPGP data doesn't contain a key"))?;
+    Ok((primary, user_ids, subkeys))
+}
+
+#[pyo3::prelude::pyfunction]
+fn load_openpgp_public_key(
+    py: pyo3::Python<'_>,
+    data: CffiBuf<'_>,
+) -> CryptographyResult<(PyOpenPGPKey, Vec<String>, Vec<PyOpenPGPKey>)> {
+    load_transferable_key(py, data.as_bytes(), false, None)
+}
+
+#[pyo3::prelude::pyfunction]
+fn load_openpgp_private_key(
+    py: pyo3::Python<'_>,
+    data: CffiBuf<'_>,
+    password: Option<CffiBuf<'_>>,
+) -> CryptographyResult<(PyOpenPGPKey, Vec<String>, Vec<PyOpenPGPKey>)> {
+    load_transferable_key(
+        py,
+        data.as_bytes(),
+        true,
+        password.as_ref().map(CffiBuf::as_bytes),
+    )
+}
+
+pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let m = pyo3::prelude::PyModule::new(py, "openpgp")?;
+    m.add_function(pyo3::wrap_pyfunction!(load_openpgp_public_key, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(load_openpgp_private_key, m)?)?;
+
+    Ok(m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{checksum, crc24};
+
+    #[test]
+    fn test_crc24() {
+        assert_eq!(crc24(b""), 0xb7_04ce);
+        assert_eq!(crc24(b"123456789"), 0x21_cf02);
+    }
+
+    #[test]
+    fn test_checksum() {
+        assert_eq!(checksum(b""), 0);
+        assert_eq!(checksum(&[0xff; 258]), 254);
+    }
+}
@@ -0,0 +1,781 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Handling of the container OpenSSH uses for private keys, as described in
+//! PROTOCOL.key in the OpenSSH sources.
+
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::{exceptions, types};
+
+pub(crate) const PEM_TAG: &str = "OPENSSH PRIVATE KEY";
+const PEM_LINE_WRAP: usize = 70;
+
+const MAGIC: &[u8] = b"openssh-key-v1\0";
+const NONE: &[u8] = b"none";
+const BCRYPT: &[u8] = b"bcrypt";
+const DEFAULT_CIPHER: &[u8] = b"aes256-ctr";
+pub(crate) const DEFAULT_ROUNDS: u32 = 16;
+const SALT_LENGTH: usize = 16;
+const UNENCRYPTED_BLOCK_LEN: usize = 8;
+const PADDING: [u8; 16] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+
+struct SshCipher {
+    name: &'static [u8],
+    cipher: fn() -> openssl::symm::Cipher,
+    key_len: usize,
+    iv_len: usize,
+    block_len: usize,
+    tag_len: usize,
+}
+
+const CIPHERS: &[SshCipher] = &[
+    SshCipher {
+        name: b"aes256-ctr",
+        cipher: openssl::symm::Cipher::aes_256_ctr,
+        key_len: 32,
+        iv_len: 16,
+        block_len: 16,
+        tag_len: 0,
+    },
+    SshCipher {
+        name: b"aes256-cbc",
+        cipher: openssl::symm::Cipher::aes_256_cbc,
+        key_len: 32,
+        iv_len: 16,
+        block_len: 16,
+        tag_len: 0,
+    },
+    SshCipher {
+        name: b"aes256-gcm@openssh.com",
+        cipher: openssl::symm::Cipher::aes_256_gcm,
+        key_len: 32,
+        iv_len: 12,
+        block_len: 16,
+        tag_len: 16,
+    },
+];
+
+fn lookup_cipher(name: &[u8]) -> CryptographyResult<&'static SshCipher> {
+    CIPHERS.iter().find(|c| c.name == name).ok_or_else(|| {
+        CryptographyError::from(exceptions::UnsupportedAlgorithm::new_err(format!(
+            "Unsupported cipher: {}",
+            String::from_utf8_lossy(name)
+        )))
+    })
+}
+
+// The number of length prefixed fields in the public half of the FIDO
+// security key types described in PROTOCOL.u2f, or `None` if `key_type`
+// isn't one.
+fn security_key_public_field_count(key_type: &[u8]) -> Option<usize> {
+    match key_type {
+        b"sk-ssh-ed25519@openssh.com" => Some(2),
+        b"sk-ecdsa-sha2-nistp256@openssh.com" => Some(3),
+        _ => None,
+    }
+}
+
+// The number of length prefixed fields that follow the key type in the
+// private section of each supported key type.
+fn private_field_count(key_type: &[u8]) -> CryptographyResult<usize> {
+    match key_type {
+        b"ssh-ed25519" => Ok(2),
+        b"ssh-rsa" => Ok(6),
+        b"ssh-dss" => Ok(5),
+        b"ecdsa-sha2-nistp256" | b"ecdsa-sha2-nistp384" | b"ecdsa-sha2-nistp521" => Ok(3),
+        _ => Err(CryptographyError::from(
+            exceptions::UnsupportedAlgorithm::new_err(format!(
+                "Unsupported key type: {}",
+                String::from_utf8_lossy(key_type)
+            )),
+        )),
+    }
+}
+
+pub(crate) fn corrupt(msg: &'static str) -> CryptographyError {
+    CryptographyError::from(pyo3::exceptions::PyValueError::new_err(msg))
+}
+
+pub(crate) struct Reader<'a> {
+    pub(crate) data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn read_raw(&mut self, n: usize) -> CryptographyResult<&'a [u8]> {
+        if n > self.data.len() {
+            return Err(corrupt("Invalid data"));
+        }
+        let (v, rest) = self.data.split_at(n);
+        self.data = rest;
+        Ok(v)
+    }
+
+    pub(crate) fn read_u32(&mut self) -> CryptographyResult<u32> {
+        let v = self.read_raw(4)?;
+        Ok(u32::from_be_bytes([v[0], v[1], v[2], v[3]]))
+    }
+
+    pub(crate) fn read_string(&mut self) -> CryptographyResult<&'a [u8]> {
+        let n = self.read_u32()?;
+        self.read_raw(n as usize)
+    }
+
+    pub(crate) fn read_mpint(&mut self) -> CryptographyResult<openssl::bn::BigNum> {
+        let v = self.read_string()?;
+        // Negative values never appear in keys.
+        if matches!(v.first(), Some(b) if *b > 0x7f) {
+            return Err(corrupt("Invalid data"));
+        }
+        Ok(openssl::bn::BigNum::from_slice(v)?)
+    }
+
+    pub(crate) fn check_empty(&self) -> CryptographyResult<()> {
+        if !self.data.is_empty() {
+            return Err(corrupt("Corrupt data: unparsed data"));
+        }
+        Ok(())
+    }
+}
+
+fn put_u32(out: &mut Vec<u8>, v: u32) {
+    out.extend_from_slice(&v.to_be_bytes());
+}
+
+fn put_string(out: &mut Vec<u8>, v: &[u8]) {
+    put_u32(out, v.len().try_into().unwrap());
+    out.extend_from_slice(v);
+}
+
+fn derive_key_iv(
+    py: pyo3::Python<'_>,
+    cipher: &SshCipher,
+    password: &[u8],
+    salt: &[u8],
+    rounds: u32,
+) -> CryptographyResult<Vec<u8>> {
+    let seed = types::BCRYPT_KDF.get(py)?.call1((
+        password,
+        salt,
+        cipher.key_len + cipher.iv_len,
+        rounds,
+        true,
+    ))?;
+    Ok(seed.extract::<&[u8]>()?.to_vec())
+}
+
+fn apply_cipher(
+    cipher: &SshCipher,
+    mode: openssl::symm::Mode,
+    seed: &[u8],
+    data: &[u8],
+    tag: &mut [u8],
+) -> CryptographyResult<Vec<u8>> {
+    let (key, iv) = seed.split_at(cipher.key_len);
+    if cipher.tag_len != 0 {
+        return Ok(match mode {
+            openssl::symm::Mode::Encrypt => {
+                openssl::symm::encrypt_aead((cipher.cipher)(), key, Some(iv), b"", data, tag)?
+            }
+            openssl::symm::Mode::Decrypt => {
+                openssl::symm::decrypt_aead((cipher.cipher)(), key, Some(iv), b"", data, tag)
+                    .map_err(|_| exceptions::InvalidTag::new_err(()))?
+            }
+        });
+    }
+
+    let mut crypter = openssl::symm::Crypter::new((cipher.cipher)(), mode, key, Some(iv))?;
+    crypter.pad(false);
+    let mut out = vec![0; data.len() + cipher.block_len];
+    let mut n = crypter.update(data, &mut out)?;
+    n += crypter.finalize(&mut out[n..])?;
+    assert_eq!(n, data.len());
+    out.truncate(n);
+    Ok(out)
+}
+
+// Validates the decrypted private section: the check integers, the key type,
+// the key's fields (which `read_key` consumes), the comment and the padding.
+// Returns the result of `read_key` and the part of the section holding the
+// key and its comment.
+fn parse_private_section<'a, T>(
+    plaintext: &'a [u8],
+    public_key_type: &[u8],
+    read_key: impl FnOnce(&mut Reader<'a>) -> CryptographyResult<T>,
+) -> CryptographyResult<(T, &'a [u8])> {
+    let mut reader = Reader { data: plaintext };
+    let check1 = reader.read_u32()?;
+    let check2 = reader.read_u32()?;
+    if check1 != check2 {
+        return Err(corrupt("Corrupt data: broken checksum"));
+    }
+    let body_start = plaintext.len() - reader.data.len();
+
+    let key_type = reader.read_string()?;
+    if key_type != public_key_type {
+        return Err(corrupt("Corrupt data: key type mismatch"));
+    }
+    let key = read_key(&mut reader)?;
+    // The comment
+    reader.read_string()?;
+    let body_end = plaintext.len() - reader.data.len();
+
+    if reader.data.len() > PADDING.len() || reader.data != &PADDING[..reader.data.len()] {
+        return Err(corrupt("Corrupt data: invalid padding"));
+    }
+
+    Ok((key, &plaintext[body_start..body_end]))
+}
+
+// Returns the part of the decrypted private section that holds the key and
+// its comment, without parsing the key's fields.
+fn private_key_body<'a>(
+    plaintext: &'a [u8],
+    public_key_type: &[u8],
+) -> CryptographyResult<&'a [u8]> {
+    let (_, body) = parse_private_section(plaintext, public_key_type, |reader| {
+        if let Some(count) = security_key_public_field_count(public_key_type) {
+            read_security_key_fields(reader, count)?;
+            return Ok(());
+        }
+        for _ in 0..private_field_count(public_key_type)? {
+            reader.read_string()?;
+        }
+        Ok(())
+    })?;
+    Ok(body)
+}
+
+// The parts of an openssh-key-v1 container holding a single key.
+struct Container<'a> {
+    cipher_name: &'a [u8],
+    kdf_name: &'a [u8],
+    kdf_options: &'a [u8],
+    public_key: &'a [u8],
+    private_section: &'a [u8],
+    // Whatever follows the private section, which is the tag for AEAD
+    // ciphers. See https://bugzilla.mindrot.org/show_bug.cgi?id=3553
+    tag: &'a [u8],
+}
+
+impl<'a> Container<'a> {
+    fn parse(data: &'a [u8]) -> CryptographyResult<Container<'a>> {
+        let mut reader = Reader { data };
+        if reader.read_raw(MAGIC.len()).ok() != Some(MAGIC) {
+            return Err(corrupt("Not OpenSSH private key format"));
+        }
+        let cipher_name = reader.read_string()?;
+        let kdf_name = reader.read_string()?;
+        let kdf_options = reader.read_string()?;
+        if reader.read_u32()? != 1 {
+            return Err(corrupt("Only one key supported"));
+        }
+        let public_key = reader.read_string()?;
+        let private_section = reader.read_string()?;
+        Ok(Container {
+            cipher_name,
+            kdf_name,
+            kdf_options,
+            public_key,
+            private_section,
+            tag: reader.data,
+        })
+    }
+
+    // Checks that the cipher and KDF are supported and that the private
+    // section and tag have valid lengths. Returns the cipher, or `None` if
+    // the private section isn't encrypted.
+    fn cipher(&self) -> CryptographyResult<Option<&'static SshCipher>> {
+        if self.cipher_name == NONE && self.kdf_name == NONE {
+            Reader { data: self.tag }.check_empty()?;
+            if self.private_section.is_empty()
+                || self.private_section.len() % UNENCRYPTED_BLOCK_LEN != 0
+            {
+                return Err(corrupt("Corrupt data: missing padding"));
+            }
+            return Ok(None);
+        }
+
+        let cipher = lookup_cipher(self.cipher_name)?;
+        if self.kdf_name != BCRYPT {
+            return Err(CryptographyError::from(
+                exceptions::UnsupportedAlgorithm::new_err(format!(
+                    "Unsupported KDF: {}",
+                    String::from_utf8_lossy(self.kdf_name)
+                )),
+            ));
+        }
+        if self.tag.len() != cipher.tag_len {
+            return Err(corrupt("Corrupt data: invalid tag length for cipher"));
+        }
+        if self.private_section.is_empty() || self.private_section.len() % cipher.block_len != 0 {
+            return Err(corrupt("Corrupt data: missing padding"));
+        }
+        Ok(Some(cipher))
+    }
+
+    fn decrypt(
+        &self,
+        py: pyo3::Python<'_>,
+        cipher: &SshCipher,
+        password: &[u8],
+    ) -> CryptographyResult<Vec<u8>> {
+        let mut options = Reader {
+            data: self.kdf_options,
+        };
+        let salt = options.read_string()?;
+        let rounds = options.read_u32()?;
+        options.check_empty()?;
+
+        let seed = derive_key_iv(py, cipher, password, salt, rounds)?;
+        apply_cipher(
+            cipher,
+            openssl::symm::Mode::Decrypt,
+            &seed,
+            self.private_section,
+            &mut self.tag.to_vec(),
+        )
+    }
+}
+
+/// Decrypts the private section of the OpenSSH private key in `data` (the
+/// contents of the PEM block) with `old_password` and re-encrypts it with
+/// `new_password`, using `aes256-ctr` and a fresh salt. The decrypted key is
+/// only ever held in Rust.
+pub(crate) fn reencrypt_private_key(
+    py: pyo3::Python<'_>,
+    data: &[u8],
+    old_password: Option<&[u8]>,
+    new_password: &[u8],
+    rounds: u32,
+) -> CryptographyResult<Vec<u8>> {
+    let container = Container::parse(data)?;
+    let public_key_type = Reader {
+        data: container.public_key,
+    }
+    .read_string()?;
+
+    let plaintext = match container.cipher()? {
+        None => {
+            if old_password.is_some() {
+                return Err(CryptographyError::from(
+                    pyo3::exceptions::PyTypeError::new_err(
+                        "Password was given but private key is not encrypted.",
+                    ),
+                ));
+            }
+            container.private_section.to_vec()
+        }
+        Some(cipher) => match old_password {
+            Some(p) if !p.is_empty() => container.decrypt(py, cipher, p)?,
+            _ => {
+                return Err(CryptographyError::from(
+                    pyo3::exceptions::PyTypeError::new_err(
+                        "Password was not given but private key is encrypted",
+                    ),
+                ))
+            }
+        },
+    };
+    let body = private_key_body(&plaintext, public_key_type)?;
+
+    let cipher = lookup_cipher(DEFAULT_CIPHER)?;
+    let mut check = [0; 4];
+    openssl::rand::rand_bytes(&mut check)?;
+    let mut secrets = Vec::with_capacity(body.len() + 8 + cipher.block_len);
+    secrets.extend_from_slice(&check);
+    secrets.extend_from_slice(&check);
+    secrets.extend_from_slice(body);
+    let padding = cipher.block_len - secrets.len() % cipher.block_len;
+    secrets.extend_from_slice(&PADDING[..padding]);
+
+    let mut salt = [0; SALT_LENGTH];
+    openssl::rand::rand_bytes(&mut salt)?;
+    let mut new_options = vec![];
+    put_string(&mut new_options, &salt);
+    put_u32(&mut new_options, rounds);
+
+    let seed = derive_key_iv(py, cipher, new_password, &salt, rounds)?;
+    let encrypted = apply_cipher(
+        cipher,
+        openssl::symm::Mode::Encrypt,
+        &seed,
+        &secrets,
+        &mut [],
+    )?;
+
+    let mut out = MAGIC.to_vec();
+    put_string(&mut out, DEFAULT_CIPHER);
+    put_string(&mut out, BCRYPT);
+    put_string(&mut out, &new_options);
+    put_u32(&mut out, 1);
+    put_string(&mut out, container.public_key);
+    put_string(&mut out, &encrypted);
+    Ok(out)
+}
+
+pub(crate) fn invalid_key(msg: &'static str) -> CryptographyError {
+    CryptographyError::from(pyo3::exceptions::PyValueError::new_err(msg))
+}
+
+// The fields of a key's public half. The private section repeats them, and
+// they have to match.
+pub(crate) enum PublicKey<'a> {
+    Rsa {
+        e: openssl::bn::BigNum,
+        n: openssl::bn::BigNum,
+    },
+    Dsa {
+        p: openssl::bn::BigNum,
+        q: openssl::bn::BigNum,
+        g: openssl::bn::BigNum,
+        y: openssl::bn::BigNum,
+    },
+    Ecdsa {
+        nid: openssl::nid::Nid,
+        curve_name: &'a [u8],
+        point: &'a [u8],
+    },
+    Ed25519 {
+        point: &'a [u8],
+    },
+}
+
+impl<'a> PublicKey<'a> {
+    pub(crate) fn read(
+        key_type: &[u8],
+        reader: &mut Reader<'a>,
+    ) -> CryptographyResult<PublicKey<'a>> {
+        let (ssh_curve_name, nid) = match key_type {
+            b"ssh-rsa" => {
+                let e = reader.read_mpint()?;
+                let n = reader.read_mpint()?;
+                return Ok(PublicKey::Rsa { e, n });
+            }
+            b"ssh-dss" => {
+                return Ok(PublicKey::Dsa {
+                    p: reader.read_mpint()?,
+                    q: reader.read_mpint()?,
+                    g: reader.read_mpint()?,
+                    y: reader.read_mpint()?,
+                });
+            }
+            b"ssh-ed25519" => {
+                return Ok(PublicKey::Ed25519 {
+                    point: reader.read_string()?,
+                });
+            }
+            b"ecdsa-sha2-nistp256" => (&b"nistp256"[..], openssl::nid::Nid::X9_62_PRIME256V1),
+            b"ecdsa-sha2-nistp384" => (&b"nistp384"[..], openssl::nid::Nid::SECP384R1),
+            b"ecdsa-sha2-nistp521" => (&b"nistp521"[..], openssl::nid::Nid::SECP521R1),
+            _ => {
+                return Err(CryptographyError::from(
+                    exceptions::UnsupportedAlgorithm::new_err(format!(
+                        "Unsupported key type: {}",
+                        String::from_utf8_lossy(key_type)
+                    )),
+                ))
+            }
+        };
+
+        let curve_name = reader.read_string()?;
+        let point = reader.read_string()?;
+        if curve_name != ssh_curve_name {
+            return Err(corrupt("Curve name mismatch"));
+        }
+        if point.first() != Some(&4) {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyNotImplementedError::new_err("Need uncompressed point"),
+            ));
+        }
+        Ok(PublicKey::Ecdsa {
+            nid,
+            curve_name,
+            point,
+        })
+    }
+
+    // Reads the fields of the private key matching this public key from the
+    // private section.
+    fn read_private_key(
+        &self,
+        reader: &mut Reader<'_>,
+    ) -> CryptographyResult<openssl::pkey::PKey<openssl::pkey::Private>> {
+        match self {
+            PublicKey::Rsa { e, n } => {
+                let n2 = reader.read_mpint()?;
+                let e2 = reader.read_mpint()?;
+                let d = reader.read_mpint()?;
+                let iqmp = reader.read_mpint()?;
+                let p = reader.read_mpint()?;
+                let q = reader.read_mpint()?;
+                if e2 != *e || n2 != *n {
+                    return Err(corrupt("Corrupt data: rsa field mismatch"));
+                }
+                rsa_private_key(n2, e2, d, iqmp, p, q)
+            }
+            PublicKey::Dsa { p, q, g, y } => {
+                let p2 = reader.read_mpint()?;
+                let q2 = reader.read_mpint()?;
+                let g2 = reader.read_mpint()?;
+                let y2 = reader.read_mpint()?;
+                let x = reader.read_mpint()?;
+                if p2 != *p || q2 != *q || g2 != *g || y2 != *y {
+                    return Err(corrupt("Corrupt data: dsa field mismatch"));
+                }
+                check_dsa_private_components(&p2, &q2, &g2, &y2, &x)?;
+                let dsa = openssl::dsa::Dsa::from_private_components(p2, q2, g2, x, y2)?;
+                Ok(openssl::pkey::PKey::from_dsa(dsa)?)
+            }
+            PublicKey::Ecdsa {
+                nid,
+                curve_name,
+                point,
+            } => {
+                let curve_name2 = reader.read_string()?;
+                let point2 = reader.read_string()?;
+                let secret = reader.read_mpint()?;
+                if curve_name2 != *curve_name || point2 != *point {
+                    return Err(corrupt("Corrupt data: ecdsa field mismatch"));
+                }
+                ec_private_key(*nid, &secret)
+            }
+            PublicKey::Ed25519 { point } => {
+                let point2 = reader.read_string()?;
+                let keypair = reader.read_string()?;
+                let (secret, point3) = keypair.split_at(keypair.len().min(32));
+                if point2 != *point || point3 != *point {
+                    return Err(corrupt("Corrupt data: ed25519 field mismatch"));
+                }
+                openssl::pkey::PKey::private_key_from_raw_bytes(secret, openssl::pkey::Id::ED25519)
+                    .map_err(|_| invalid_key("An Ed25519 private key is 32 bytes long"))
+            }
+        }
+    }
+}
+
+// Builds an RSA key from the fields SSH stores, computing the CRT exponents.
+pub(crate) fn rsa_private_key(
+    n: openssl::bn::BigNum,
+    e: openssl::bn::BigNum,
+    d: openssl::bn::BigNum,
+    iqmp: openssl::bn::BigNum,
+    p: openssl::bn::BigNum,
+    q: openssl::bn::BigNum,
+) -> CryptographyResult<openssl::pkey::PKey<openssl::pkey::Private>> {
+    let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+    let mut crt_exponent = |prime: &openssl::bn::BigNumRef| {
+        let one = openssl::bn::BigNum::from_u32(1)?;
+        let mut prime_minus_one = openssl::bn::BigNum::new()?;
+        prime_minus_one.checked_sub(prime, &one)?;
+        let mut exponent = openssl::bn::BigNum::new()?;
+        exponent
+            .nnmod(&d, &prime_minus_one, &mut bn_ctx)
+            .map_err(|_| invalid_key("Invalid private key"))?;
+        Ok::<_, CryptographyError>(exponent)
+    };
+    let dmp1 = crt_exponent(&p)?;
+    let dmq1 = crt_exponent(&q)?;
+    let rsa = openssl::rsa::Rsa::from_private_components(n, e, d, p, q, dmp1, dmq1, iqmp)?;
+    // The key is checked as it's converted to a Python object.
+    Ok(openssl::pkey::PKey::from_rsa(rsa)?)
+}
+
+// Builds an EC key from its secret scalar. As with `ec.derive_private_key`,
+// the public point is computed from the secret rather than loaded.
+pub(crate) fn ec_private_key(
+    nid: openssl::nid::Nid,
+    secret: &openssl::bn::BigNumRef,
+) -> CryptographyResult<openssl::pkey::PKey<openssl::pkey::Private>> {
+    let group = openssl::ec::EcGroup::from_curve_name(nid)?;
+    let bn_ctx = openssl::bn::BigNumContext::new()?;
+    let mut public_point = openssl::ec::EcPoint::new(&group)?;
+    public_point.mul_generator(&group, secret, &bn_ctx)?;
+    let ec = openssl::ec::EcKey::from_private_components(&group, secret, &public_point)
+        .map_err(|_| invalid_key("Invalid EC key"))?;
+    Ok(openssl::pkey::PKey::from_ec_key(ec)?)
+}
+
+// The checks `DSAPrivateNumbers.private_key()` makes, except that SSH only
+// supports 1024 bit keys.
+pub(crate) fn check_dsa_private_components(
+    p: &openssl::bn::BigNumRef,
+    q: &openssl::bn::BigNumRef,
+    g: &openssl::bn::BigNumRef,
+    y: &openssl::bn::BigNumRef,
+    x: &openssl::bn::BigNumRef,
+) -> CryptographyResult<()> {
+    if p.num_bits() != 1024 {
+        return Err(invalid_key("SSH supports only 1024 bit DSA keys"));
+    }
+    if ![160, 224, 256].contains(&q.num_bits()) {
+        return Err(invalid_key("q must be exactly 160, 224, or 256 bits long"));
+    }
+    let one = openssl::bn::BigNum::from_u32(1)?;
+    if g <= one.as_ref() || g >= p {
+        return Err(invalid_key("g, p don't satisfy 1 < g < p."));
+    }
+    if x.num_bits() == 0 || x >= q {
+        return Err(invalid_key("x must be > 0 and < q."));
+    }
+    let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+    let mut expected_y = openssl::bn::BigNum::new()?;
+    expected_y.mod_exp(g, x, p, &mut bn_ctx)?;
+    if y != expected_y.as_ref() {
+        return Err(invalid_key("y must be equal to (g ** x % p)."));
+    }
+    Ok(())
+}
+
+// The private fields of a FIDO security key, which only refer to the key
+// held by the authenticator.
+pub(crate) struct SecurityKeyFields {
+    pub(crate) flags: u8,
+    pub(crate) key_handle: Vec<u8>,
+    pub(crate) reserved: Vec<u8>,
+}
+
+// Reads the private section of a security key: its `public_field_count`
+// public fields, which are returned in their encoded form, then the flags, key
+// handle and reserved field.
+fn read_security_key_fields<'a>(
+    reader: &mut Reader<'a>,
+    public_field_count: usize,
+) -> CryptographyResult<(&'a [u8], SecurityKeyFields)> {
+    let start = reader.data;
+    for _ in 0..public_field_count {
+        reader.read_string()?;
+    }
+    let public_fields = &start[..start.len() - reader.data.len()];
+    let flags = reader.read_raw(1)?[0];
+    let key_handle = reader.read_string()?.to_vec();
+    let reserved = reader.read_string()?.to_vec();
+    Ok((
+        public_fields,
+        SecurityKeyFields {
+            flags,
+            key_handle,
+            reserved,
+        },
+    ))
+}
+
+pub(crate) enum PrivateKey {
+    Key(openssl::pkey::PKey<openssl::pkey::Private>),
+    // The public key blob, which is parsed in Python, and the private fields.
+    SecurityKey(Vec<u8>, SecurityKeyFields),
+}
+
+/// Loads the OpenSSH private key in `data` (the contents of the PEM block),
+/// decrypting it with `password` if it's encrypted. A password is ignored if
+/// the key isn't encrypted.
+/// Whether the OpenSSH private key in `data` (the contents of the PEM block)
+/// is encrypted.
+pub(crate) fn is_encrypted(data: &[u8]) -> CryptographyResult<bool> {
+    Ok(Container::parse(data)?.cipher()?.is_some())
+}
+
+pub(crate) fn load_private_key(
+    py: pyo3::Python<'_>,
+    data: &[u8],
+    password: Option<&[u8]>,
+) -> CryptographyResult<PrivateKey> {
+    let container = Container::parse(data)?;
+    let mut public_reader = Reader {
+        data: container.public_key,
+    };
+    let key_type = public_reader.read_string()?;
+    let public_key = match security_key_public_field_count(key_type) {
+        Some(_) => None,
+        None => {
+            let public_key = PublicKey::read(key_type, &mut public_reader)?;
+            public_reader.check_empty()?;
+            Some(public_key)
+        }
+    };
+
+    let plaintext = match container.cipher()? {
+        None => container.private_section.to_vec(),
+        Some(cipher) => match password {
+            Some(p) if !p.is_empty() => container.decrypt(py, cipher, p)?,
+            _ => return Err(invalid_key("Key is password-protected.")),
+        },
+    };
+    let (key, _) = parse_private_section(&plaintext, key_type, |reader| match &public_key {
+        Some(public_key) => Ok(PrivateKey::Key(public_key.read_private_key(reader)?)),
+        None => {
+            let count = security_key_public_field_count(key_type).unwrap();
+            let (public_fields, fields) = read_security_key_fields(reader, count)?;
+            if public_fields != public_reader.data {
+                return Err(corrupt("Corrupt data: security key field mismatch"));
+            }
+            Ok(PrivateKey::SecurityKey(
+                container.public_key.to_vec(),
+                fields,
+            ))
+        }
+    })?;
+    Ok(key)
+}
+
+pub(crate) fn encode_pem(data: Vec<u8>) -> String {
+    pem::encode_config(
+        &pem::Pem::new(PEM_TAG, data),
+        pem::EncodeConfig::new()
+            .set_line_ending(pem::LineEnding::LF)
+            .set_line_wrap(PEM_LINE_WRAP),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{private_key_body, Reader};
+
+    #[test]
+    fn test_reader() {
+        let mut r = Reader {
+            data: b"\x00\x00\x00\x02ab\x00\x00\x00\x05a",
+        };
+        assert_eq!(r.read_string().ok(), Some(&b"ab"[..]));
+        assert!(r.check_empty().is_err());
+        assert!(r.read_string().is_err());
+
+        let mut r = Reader {
+            data: b"\x00\x00\x00\x02\x01\x00\x00\x00\x00\x00\x00\x00\x00\x01\x80",
+        };
+        assert_eq!(
+            r.read_mpint().ok().map(|v| v.to_vec()),
+            Some(b"\x01\x00".to_vec())
+        );
+        assert_eq!(r.read_mpint().ok().map(|v| v.num_bits()), Some(0));
+        assert!(r.read_mpint().is_err());
+    }
+
+    #[test]
+    fn test_private_key_body() {
+        let mut plaintext = b"\x01\x02\x03\x04\x01\x02\x03\x04".to_vec();
+        let body = b"\x00\x00\x00\x0bssh-ed25519\x00\x00\x00\x01a\x00\x00\x00\x01b\x00\x00\x00\x00";
+        plaintext.extend_from_slice(body);
+        plaintext.extend_from_slice(b"\x01\x02\x03");
+        assert_eq!(
+            private_key_body(&plaintext, b"ssh-ed25519").ok(),
+            Some(&body[..])
+        );
+
+        assert!(private_key_body(&plaintext, b"ssh-rsa").is_err());
+        let last = plaintext.len() - 1;
+        plaintext[last] = 4;
+        assert!(private_key_body(&plaintext, b"ssh-ed25519").is_err());
+        plaintext[0] = 0;
+        assert!(private_key_body(&plaintext, b"ssh-ed25519").is_err());
+
+        let mut plaintext = b"\x01\x02\x03\x04\x01\x02\x03\x04".to_vec();
+        let body = b"\x00\x00\x00\x1ask-ssh-ed25519@openssh.com\x00\x00\x00\x01a\x00\x00\x00\x04ssh:\x01\x00\x00\x00\x01b\x00\x00\x00\x00\x00\x00\x00\x00";
+        plaintext.extend_from_slice(body);
+        plaintext.extend_from_slice(b"\x01\x02");
+        assert_eq!(
+            private_key_body(&plaintext, b"sk-ssh-ed25519@openssh.com").ok(),
+            Some(&body[..])
+        );
+    }
+}
@@ -0,0 +1,166 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Encryption of PKCS#8 private keys with PBES2 (RFC 8018 Section 6.2), for
+//! the parameters OpenSSL's PKCS#8 serialization doesn't let us choose.
+
+use cryptography_x509::common::{
+    AlgorithmIdentifier, AlgorithmParameters, Pbes2Params, Pbkdf2Params,
+};
+use cryptography_x509::pkcs8::EncryptedPrivateKeyInfo;
+
+use crate::error::CryptographyResult;
+
+pub(crate) enum Kdf {
+    Pbkdf2 { iterations: u64, prf: Prf },
+    Scrypt { n: u64, r: u64, p: u64 },
+}
+
+// The HMAC hash used as PBKDF2's pseudorandom function.
+#[derive(Clone, Copy)]
+pub(crate) enum Prf {
+    HmacSha1,
+    HmacSha224,
+    HmacSha256,
+    HmacSha384,
+    HmacSha512,
+}
+
+impl Prf {
+    fn message_digest(self) -> openssl::hash::MessageDigest {
+        match self {
+            Prf::HmacSha1 => openssl::hash::MessageDigest::sha1(),
+            Prf::HmacSha224 => openssl::hash::MessageDigest::sha224(),
+            Prf::HmacSha256 => openssl::hash::MessageDigest::sha256(),
+            Prf::HmacSha384 => openssl::hash::MessageDigest::sha384(),
+            Prf::HmacSha512 => openssl::hash::MessageDigest::sha512(),
+        }
+    }
+
+    // hmacWithSHA1 is the default, so it's omitted.
+    fn algorithm_identifier(self) -> Option<AlgorithmIdentifier<'static>> {
+        let params = match self {
+            Prf::HmacSha1 => return None,
+            Prf::HmacSha224 => AlgorithmParameters::HmacWithSha224(Some(())),
+            Prf::HmacSha256 => AlgorithmParameters::HmacWithSha256(Some(())),
+            Prf::HmacSha384 => AlgorithmParameters::HmacWithSha384(Some(())),
+            Prf::HmacSha512 => AlgorithmParameters::HmacWithSha512(Some(())),
+        };
+        Some(AlgorithmIdentifier {
+            oid: asn1::DefinedByMarker::marker(),
+            params,
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum Cipher {
+    Aes128Cbc,
+    Aes256Cbc,
+}
+
+impl Cipher {
+    pub(crate) fn openssl_cipher(self) -> openssl::symm::Cipher {
+        match self {
+            Cipher::Aes128Cbc => openssl::symm::Cipher::aes_128_cbc(),
+            Cipher::Aes256Cbc => openssl::symm::Cipher::aes_256_cbc(),
+        }
+    }
+
+    fn params(self, iv: &[u8]) -> AlgorithmParameters<'_> {
+        match self {
+            Cipher::Aes128Cbc => AlgorithmParameters::Aes128Cbc(iv),
+            Cipher::Aes256Cbc => AlgorithmParameters::Aes256Cbc(iv),
+        }
+    }
+}
+
+const SALT_LEN: usize = 16;
+
+// Derives the key for `kdf`, returning it along with the AlgorithmIdentifier
+// that describes the derivation.
+fn derive_key<'a>(
+    kdf: &Kdf,
+    password: &[u8],
+    salt: &'a [u8],
+    key: &mut [u8],
+) -> CryptographyResult<AlgorithmIdentifier<'a>> {
+    match *kdf {
+        Kdf::Pbkdf2 { iterations, prf } => {
+            let rounds = usize::try_from(iterations)
+                .map_err(|_| pyo3::exceptions::PyValueError::new_err("kdf_rounds is too large"))?;
+            openssl::pkcs5::pbkdf2_hmac(password, salt, rounds, prf.message_digest(), key)?;
+            Ok(AlgorithmIdentifier {
+                oid: asn1::DefinedByMarker::marker(),
+                params: AlgorithmParameters::Pbkdf2(Box::new(Pbkdf2Params {
+                    salt,
+                    iteration_count: iterations,
+                    key_length: None,
+                    prf: prf.algorithm_identifier(),
+                })),
+            })
+        }
+        #[cfg(not(CRYPTOGRAPHY_IS_LIBRESSL))]
+        Kdf::Scrypt { n, r, p } => {
+            // The memory scrypt needs, computed the same way as OpenSSL does.
+            let max_mem = 128u64
+                .saturating_mul(r)
+                .saturating_mul(n.saturating_add(p).saturating_add(2));
+            openssl::pkcs5::scrypt(password, salt, n, r, p, max_mem, key)?;
+            Ok(AlgorithmIdentifier {
+                oid: asn1::DefinedByMarker::marker(),
+                params: AlgorithmParameters::Scrypt(cryptography_x509::common::ScryptParams {
+                    salt,
+                    cost_parameter: n,
+                    block_size: r,
+                    parallelization_parameter: p,
+                    key_length: None,
+                }),
+            })
+        }
+        #[cfg(CRYPTOGRAPHY_IS_LIBRESSL)]
+        Kdf::Scrypt { .. } => {
+            let _ = (password, salt, key);
+            Err(crate::error::CryptographyError::from(
+                crate::exceptions::UnsupportedAlgorithm::new_err((
+                    "This version of OpenSSL does not support scrypt",
+                    crate::exceptions::Reasons::UNSUPPORTED_CIPHER,
+                )),
+            ))
+        }
+    }
+}
+
+/// Encrypts a DER encoded PrivateKeyInfo with `cipher`, returning a DER
+/// encoded EncryptedPrivateKeyInfo.
+pub(crate) fn encrypt_private_key_info(
+    der: &[u8],
+    password: &[u8],
+    kdf: &Kdf,
+    cipher: Cipher,
+) -> CryptographyResult<Vec<u8>> {
+    let ossl_cipher = cipher.openssl_cipher();
+    let mut salt = [0; SALT_LEN];
+    openssl::rand::rand_bytes(&mut salt)?;
+    let mut iv = vec![0; ossl_cipher.iv_len().unwrap()];
+    openssl::rand::rand_bytes(&mut iv)?;
+
+    let mut key = vec![0; ossl_cipher.key_len()];
+    let key_derivation_func = derive_key(kdf, password, &salt, &mut key)?;
+    let encrypted_data = openssl::symm::encrypt(ossl_cipher, &key, Some(&iv), der)?;
+
+    Ok(asn1::write_single(&EncryptedPrivateKeyInfo {
+        encryption_algorithm: AlgorithmIdentifier {
+            oid: asn1::DefinedByMarker::marker(),
+            params: AlgorithmParameters::Pbes2(Pbes2Params {
+                key_derivation_func: Box::new(key_derivation_func),
+                encryption_scheme: Box::new(AlgorithmIdentifier {
+                    oid: asn1::DefinedByMarker::marker(),
+                    params: cipher.params(&iv),
+                }),
+            }),
+        },
+        encrypted_data: &encrypted_data,
+    })?)
+}
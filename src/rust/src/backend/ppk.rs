@@ -0,0 +1,391 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Loading of private keys in PuTTY's PPK format, versions 2 and 3, as
+//! described in the "PPK file format" appendix of the PuTTY manual.
+
+use crate::backend::keys;
+use crate::backend::openssh::{self, PublicKey, Reader};
+use crate::buf::CffiBuf;
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::{exceptions, types};
+
+const HEADER_PREFIX: &str = "PuTTY-User-Key-File-";
+const NONE: &str = "none";
+const AES256_CBC: &str = "aes256-cbc";
+const V2_MAC_KEY_PREFIX: &[u8] = b"putty-private-key-file-mac-key";
+
+const KEY_LEN: usize = 32;
+const IV_LEN: usize = 16;
+const BLOCK_LEN: usize = 16;
+const V3_MAC_KEY_LEN: usize = 32;
+
+fn corrupt(msg: &'static str) -> CryptographyError {
+    CryptographyError::from(pyo3::exceptions::PyValueError::new_err(msg))
+}
+
+fn hex_decode(value: &str) -> CryptographyResult<Vec<u8>> {
+    if value.len() % 2 != 0 {
+        return Err(corrupt("Invalid hex value in PPK file"));
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| {
+            value
+                .get(i..i + 2)
+                .and_then(|b| u8::from_str_radix(b, 16).ok())
+                .ok_or_else(|| corrupt("Invalid hex value in PPK file"))
+        })
+        .collect()
+}
+
+// The parameters for deriving the keys of an encrypted version 3 file.
+struct Argon2Params<'a> {
+    flavor: &'a str,
+    memory: u32,
+    passes: u32,
+    parallelism: u32,
+    salt: Vec<u8>,
+}
+
+struct PpkFile<'a> {
+    version: u8,
+    algorithm: &'a str,
+    encryption: &'a str,
+    comment: &'a str,
+    public_blob: Vec<u8>,
+    argon2: Option<Argon2Params<'a>>,
+    private_blob: Vec<u8>,
+    mac: Vec<u8>,
+}
+
+struct Lines<'a> {
+    lines: std::str::Lines<'a>,
+}
+
+impl<'a> Lines<'a> {
+    // Reads a `Name: value` header line, returning the value.
+    fn header(&mut self, name: &str) -> CryptographyResult<&'a str> {
+        match self.lines.next().and_then(|line| line.split_once(": ")) {
+            Some((n, value)) if n == name => Ok(value),
+            _ => Err(corrupt("Corrupt PPK file: missing header")),
+        }
+    }
+
+    fn number(&mut self, name: &str) -> CryptographyResult<u32> {
+        self.header(name)?
+            .parse()
+            .map_err(|_| corrupt("Corrupt PPK file: invalid number"))
+    }
+
+    // Reads a count of lines and then that many lines of base64.
+    fn base64(&mut self, name: &str) -> CryptographyResult<Vec<u8>> {
+        let count = self.number(name)?;
+        let mut encoded = String::new();
+        for _ in 0..count {
+            encoded.push_str(
+                self.lines
+                    .next()
+                    .ok_or_else(|| corrupt("Corrupt PPK file: missing data"))?,
+            );
+        }
+        openssl::base64::decode_block(&encoded)
+            .map_err(|_| corrupt("Corrupt PPK file: invalid base64"))
+    }
+}
+
+impl<'a> PpkFile<'a> {
+    fn parse(data: &'a [u8]) -> CryptographyResult<PpkFile<'a>> {
+        let text = std::str::from_utf8(data).map_err(|_| corrupt("Not a PPK file"))?;
+        let mut lines = Lines {
+            lines: text.lines(),
+        };
+
+        let (version, algorithm) = match lines
+            .lines
+            .next()
+            .and_then(|line| line.strip_prefix(HEADER_PREFIX))
+            .and_then(|line| line.split_once(": "))
+        {
+            Some(("2", algorithm)) => (2, algorithm),
+            Some(("3", algorithm)) => (3, algorithm),
+            Some(_) => {
+                return Err(CryptographyError::from(
+                    exceptions::UnsupportedAlgorithm::new_err(
+                        "Only version 2 and 3 PPK files are supported",
+                    ),
+                ))
+            }
+            None => return Err(corrupt("Not a PPK file")),
+        };
+        let encryption = lines.header("Encryption")?;
+        if encryption != NONE && encryption != AES256_CBC {
+            return Err(CryptographyError::from(
+                exceptions::UnsupportedAlgorithm::new_err(format!(
+                    "Unsupported PPK encryption: {encryption}"
+                )),
+            ));
+        }
+        let comment = lines.header("Comment")?;
+        let public_blob = lines.base64("Public-Lines")?;
+
+        let argon2 = if version == 3 && encryption != NONE {
+            Some(Argon2Params {
+                flavor: lines.header("Key-Derivation")?,
+                memory: lines.number("Argon2-Memory")?,
+                passes: lines.number("Argon2-Passes")?,
+                parallelism: lines.number("Argon2-Parallelism")?,
+                salt: hex_decode(lines.header("Argon2-Salt")?)?,
+            })
+        } else {
+            None
+        };
+        let private_blob = lines.base64("Private-Lines")?;
+        let mac = hex_decode(lines.header("Private-MAC")?)?;
+
+        Ok(PpkFile {
+            version,
+            algorithm,
+            encryption,
+            comment,
+            public_blob,
+            argon2,
+            private_blob,
+            mac,
+        })
+    }
+
+    // Derives the cipher key, IV and MAC key.
+    fn derive_keys(
+        &self,
+        py: pyo3::Python<'_>,
+        password: &[u8],
+    ) -> CryptographyResult<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+        if self.version == 2 {
+            let mut mac_key = V2_MAC_KEY_PREFIX.to_vec();
+            mac_key.extend_from_slice(password);
+            let mac_key = openssl::sha::sha1(&mac_key).to_vec();
+            if self.encryption == NONE {
+                return Ok((vec![], vec![], mac_key));
+            }
+
+            let mut key = vec![];
+            for i in 0u32..2 {
+                let mut h = openssl::sha::Sha1::new();
+                h.update(&i.to_be_bytes());
+                h.update(password);
+                key.extend_from_slice(&h.finish());
+            }
+            key.truncate(KEY_LEN);
+            return Ok((key, vec![0; IV_LEN], mac_key));
+        }
+
+        let params = match &self.argon2 {
+            Some(params) => params,
+            // Unencrypted version 3 files use an empty MAC key.
+            None => return Ok((vec![], vec![], vec![])),
+        };
+        let derived = types::ARGON2_KDF
+            .get(py)?
+            .call1((
+                params.flavor,
+                password,
+                params.salt.as_slice(),
+                params.passes,
+                params.memory,
+                params.parallelism,
+                KEY_LEN + IV_LEN + V3_MAC_KEY_LEN,
+            ))?
+            .extract::<&[u8]>()?;
+        if derived.len() != KEY_LEN + IV_LEN + V3_MAC_KEY_LEN {
+            return Err(corrupt("Invalid Argon2 output"));
+        }
+        let (key, rest) = derived.split_at(KEY_LEN);
+        let (iv, mac_key) = rest.split_at(IV_LEN);
+        Ok((key.to_vec(), iv.to_vec(), mac_key.to_vec()))
+    }
+
+    // Decrypts the private blob and checks the MAC, which covers the private
+    // blob and every header that describes it.
+    fn decrypt(&self, py: pyo3::Python<'_>, password: &[u8]) -> CryptographyResult<Vec<u8>> {
+        let (key, iv, mac_key) = self.derive_keys(py, password)?;
+        let private_blob = if self.encryption == NONE {
+            self.private_blob.clone()
+        } else {
+            if self.private_blob.is_empty() || self.private_blob.len() % BLOCK_LEN != 0 {
+                return Err(corrupt("Corrupt PPK file: invalid private data length"));
+            }
+            let mut crypter = openssl::symm::Crypter::new(
+                openssl::symm::Cipher::aes_256_cbc(),
+                openssl::symm::Mode::Decrypt,
+                &key,
+                Some(&iv),
+            )?;
+            crypter.pad(false);
+            let mut out = vec![0; self.private_blob.len() + BLOCK_LEN];
+            let mut n = crypter.update(&self.private_blob, &mut out)?;
+            n += crypter.finalize(&mut out[n..])?;
+            out.truncate(n);
+            out
+        };
+
+        let md = if self.version == 2 {
+            openssl::hash::MessageDigest::sha1()
+        } else {
+            openssl::hash::MessageDigest::sha256()
+        };
+        let mut h = cryptography_openssl::hmac::Hmac::new(&mac_key, md)?;
+        for field in [
+            self.algorithm.as_bytes(),
+            self.encryption.as_bytes(),
+            self.comment.as_bytes(),
+            self.public_blob.as_slice(),
+            private_blob.as_slice(),
+        ] {
+            h.update(&u32::try_from(field.len()).unwrap().to_be_bytes())?;
+            h.update(field)?;
+        }
+        let mac = h.finish()?;
+        if mac.len() != self.mac.len() || !openssl::memcmp::eq(&mac, &self.mac) {
+            return Err(if self.encryption == NONE {
+                corrupt("Corrupt PPK file: MAC mismatch")
+            } else {
+                corrupt("Incorrect password, could not decrypt key")
+            });
+        }
+        Ok(private_blob)
+    }
+}
+
+// Reads the private fields, which unlike OpenSSH's format don't repeat the
+// public key, and checks them against the public key.
+fn read_private_key(
+    public_key: PublicKey<'_>,
+    reader: &mut Reader<'_>,
+) -> CryptographyResult<openssl::pkey::PKey<openssl::pkey::Private>> {
+    match public_key {
+        PublicKey::Rsa { e, n } => {
+            let d = reader.read_mpint()?;
+            let p = reader.read_mpint()?;
+            let q = reader.read_mpint()?;
+            let iqmp = reader.read_mpint()?;
+            openssh::rsa_private_key(n, e, d, iqmp, p, q)
+        }
+        PublicKey::Dsa { p, q, g, y } => {
+            let x = reader.read_mpint()?;
+            openssh::check_dsa_private_components(&p, &q, &g, &y, &x)?;
+            let dsa = openssl::dsa::Dsa::from_private_components(p, q, g, x, y)?;
+            Ok(openssl::pkey::PKey::from_dsa(dsa)?)
+        }
+        PublicKey::Ecdsa { nid, point, .. } => {
+            let secret = reader.read_mpint()?;
+            let pkey = openssh::ec_private_key(nid, &secret)?;
+            let ec = pkey.ec_key()?;
+            let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+            let expected = ec.public_key().to_bytes(
+                ec.group(),
+                openssl::ec::PointConversionForm::UNCOMPRESSED,
+                &mut bn_ctx,
+            )?;
+            if expected != point {
+                return Err(corrupt("Corrupt PPK file: public key mismatch"));
+            }
+            Ok(pkey)
+        }
+        PublicKey::Ed25519 { point } => {
+            let secret = reader.read_string()?;
+            let pkey =
+                openssl::pkey::PKey::private_key_from_raw_bytes(secret, openssl::pkey::Id::ED25519)
+                    .map_err(|_| openssh::invalid_key("An Ed25519 private key is 32 bytes long"))?;
+            if pkey.raw_public_key()? != point {
+                return Err(corrupt("Corrupt PPK file: public key mismatch"));
+            }
+            Ok(pkey)
+        }
+    }
+}
+
+fn load_private_key(
+    py: pyo3::Python<'_>,
+    data: &[u8],
+    password: Option<&[u8]>,
+) -> CryptographyResult<openssl::pkey::PKey<openssl::pkey::Private>> {
+    let ppk = PpkFile::parse(data)?;
+
+    let mut public_reader = Reader {
+        data: &ppk.public_blob,
+    };
+    let key_type = public_reader.read_string()?;
+    if key_type != ppk.algorithm.as_bytes() {
+        return Err(corrupt("Corrupt PPK file: key type mismatch"));
+    }
+    let public_key = PublicKey::read(key_type, &mut public_reader)?;
+    public_reader.check_empty()?;
+
+    let password = if ppk.encryption == NONE {
+        b""
+    } else {
+        match password {
+            Some(p) if !p.is_empty() => p,
+            _ => return Err(openssh::invalid_key("Key is password-protected.")),
+        }
+    };
+    let private_blob = ppk.decrypt(py, password)?;
+    // Any data after the private fields is padding, which is covered by the
+    // MAC.
+    read_private_key(
+        public_key,
+        &mut Reader {
+            data: &private_blob,
+        },
+    )
+}
+
+#[pyo3::prelude::pyfunction]
+#[pyo3(signature = (data, password, backend=None))]
+fn load_ppk_private_key(
+    py: pyo3::Python<'_>,
+    data: CffiBuf<'_>,
+    password: Option<CffiBuf<'_>>,
+    backend: Option<&pyo3::PyAny>,
+) -> CryptographyResult<pyo3::PyObject> {
+    let _ = backend;
+    let pkey = load_private_key(
+        py,
+        data.as_bytes(),
+        password.as_ref().map(CffiBuf::as_bytes),
+    )?;
+    if pkey.id() == openssl::pkey::Id::DSA {
+        pyo3::PyErr::warn(
+            py,
+            types::DEPRECATED_IN_40.get(py)?,
+            "SSH DSA keys are deprecated and will be removed in a future release.",
+            1,
+        )?;
+    }
+    keys::private_key_from_pkey(py, &pkey, false)
+}
+
+pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let m = pyo3::prelude::PyModule::new(py, "ppk")?;
+    m.add_function(pyo3::wrap_pyfunction!(load_ppk_private_key, m)?)?;
+
+    Ok(m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hex_decode;
+
+    #[test]
+    fn test_hex_decode() {
+        assert_eq!(
+            hex_decode("00ff1A").ok().as_deref(),
+            Some(&b"\x00\xff\x1a"[..])
+        );
+        assert_eq!(hex_decode("").ok().as_deref(), Some(&b""[..]));
+        assert!(hex_decode("0").is_err());
+        assert!(hex_decode("zz").is_err());
+    }
+}
@@ -0,0 +1,547 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! The ristretto255 prime order group, as specified by RFC 9496, with hashing
+//! to the group from RFC 9380 appendix B.
+//!
+//! Elements are represented by edwards25519 points and use the arithmetic
+//! from the ECVRF module. Scalars are `BigNum`s reduced modulo the group
+//! order.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::backend::ecvrf::{Edwards25519, EdwardsPoint};
+use crate::backend::{bls, ed25519};
+use crate::buf::CffiBuf;
+use crate::error::{CryptographyError, CryptographyResult};
+
+const ELEMENT_LENGTH: usize = 32;
+const SCALAR_LENGTH: usize = 32;
+const UNIFORM_LENGTH: usize = 64;
+
+struct Ristretto255 {
+    curve: Edwards25519,
+    // 1 / sqrt(a - d)
+    invsqrt_a_minus_d: openssl::bn::BigNum,
+    // sqrt(a * d - 1)
+    sqrt_ad_minus_one: openssl::bn::BigNum,
+    // 1 - d^2
+    one_minus_d_sq: openssl::bn::BigNum,
+    // (d - 1)^2
+    d_minus_one_sq: openssl::bn::BigNum,
+    // (p - 5) / 8
+    sqrt_ratio_exponent: openssl::bn::BigNum,
+}
+
+fn is_negative(x: &openssl::bn::BigNumRef) -> bool {
+    x.is_bit_set(0)
+}
+
+impl Ristretto255 {
+    fn new() -> CryptographyResult<Ristretto255> {
+        Ok(Ristretto255 {
+            curve: Edwards25519::new()?,
+            invsqrt_a_minus_d: openssl::bn::BigNum::from_hex_str(
+                "786c8905cfaffca216c27b91fe01d8409d2f16175a4172be99c8fdaa805d40ea",
+            )?,
+            sqrt_ad_minus_one: openssl::bn::BigNum::from_hex_str(
+                "376931bf2b8348ac0f3cfcc931f5d1fdaf9d8e0c1b7854bd7e97f6a0497b2e1b",
+            )?,
+            one_minus_d_sq: openssl::bn::BigNum::from_hex_str(
+                "029072a8b2b3e0d79994abddbe70dfe42c81a138cd5e350fe27c09c1945fc176",
+            )?,
+            d_minus_one_sq: openssl::bn::BigNum::from_hex_str(
+                "5968b37af66c22414cdcd32f529b4eebd29e4a2cb01e199931ad5aaa44ed4d20",
+            )?,
+            sqrt_ratio_exponent: openssl::bn::BigNum::from_hex_str(
+                "0ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffd",
+            )?,
+        })
+    }
+
+    fn neg(&mut self, x: &openssl::bn::BigNumRef) -> CryptographyResult<openssl::bn::BigNum> {
+        let zero = openssl::bn::BigNum::new()?;
+        self.curve.field.sub(&zero, x)
+    }
+
+    fn abs(&mut self, x: &openssl::bn::BigNumRef) -> CryptographyResult<openssl::bn::BigNum> {
+        if is_negative(x) {
+            self.neg(x)
+        } else {
+            Ok(x.to_owned()?)
+        }
+    }
+
+    // SQRT_RATIO_M1 from RFC 9496 section 4.2: returns whether u / v is
+    // square, and the non-negative square root of either u / v or
+    // sqrt(-1) * u / v.
+    fn sqrt_ratio_m1(
+        &mut self,
+        u: &openssl::bn::BigNumRef,
+        v: &openssl::bn::BigNumRef,
+    ) -> CryptographyResult<(bool, openssl::bn::BigNum)> {
+        let f = &mut self.curve.field;
+        let v2 = f.mul(v, v)?;
+        let v3 = f.mul(&v2, v)?;
+        let v4 = f.mul(&v2, &v2)?;
+        let v7 = f.mul(&v4, &v3)?;
+        let uv3 = f.mul(u, &v3)?;
+        let uv7 = f.mul(u, &v7)?;
+        let power = f.pow(&uv7, &self.sqrt_ratio_exponent)?;
+        let mut r = f.mul(&uv3, &power)?;
+
+        let rr = f.mul(&r, &r)?;
+        let check = f.mul(v, &rr)?;
+        let zero = openssl::bn::BigNum::new()?;
+        let minus_u = f.sub(&zero, u)?;
+        let minus_u_i = f.mul(&minus_u, &self.curve.sqrt_m1)?;
+        let correct = check == *u;
+        let flipped = check == minus_u;
+        let flipped_i = check == minus_u_i;
+        if flipped || flipped_i {
+            r = f.mul(&r, &self.curve.sqrt_m1)?;
+        }
+        Ok((correct || flipped, self.abs(&r)?))
+    }
+
+    // RFC 9496 section 4.3.1.
+    fn decode(&mut self, data: &[u8]) -> CryptographyResult<Option<EdwardsPoint>> {
+        if data.len() != ELEMENT_LENGTH {
+            return Ok(None);
+        }
+        let mut be = data.to_vec();
+        be.reverse();
+        let s = openssl::bn::BigNum::from_slice(&be)?;
+        if s >= self.curve.field.p || is_negative(&s) {
+            return Ok(None);
+        }
+
+        let one = openssl::bn::BigNum::from_u32(1)?;
+        let f = &mut self.curve.field;
+        let ss = f.mul(&s, &s)?;
+        let u1 = f.sub(&one, &ss)?;
+        let u2 = f.add(&one, &ss)?;
+        let u2_sqr = f.mul(&u2, &u2)?;
+        let u1_sqr = f.mul(&u1, &u1)?;
+        let d_u1_sqr = f.mul(&self.curve.d, &u1_sqr)?;
+        let zero = openssl::bn::BigNum::new()?;
+        let minus_d_u1_sqr = f.sub(&zero, &d_u1_sqr)?;
+        let v = f.sub(&minus_d_u1_sqr, &u2_sqr)?;
+        let v_u2_sqr = f.mul(&v, &u2_sqr)?;
+
+        let (was_square, invsqrt) = self.sqrt_ratio_m1(&one, &v_u2_sqr)?;
+        let f = &mut self.curve.field;
+        let den_x = f.mul(&invsqrt, &u2)?;
+        let invsqrt_den_x = f.mul(&invsqrt, &den_x)?;
+        let den_y = f.mul(&invsqrt_den_x, &v)?;
+        let two_s = f.add(&s, &s)?;
+        let x = f.mul(&two_s, &den_x)?;
+        let x = self.abs(&x)?;
+        let f = &mut self.curve.field;
+        let y = f.mul(&u1, &den_y)?;
+        let t = f.mul(&x, &y)?;
+        if !was_square || is_negative(&t) || y.num_bits() == 0 {
+            return Ok(None);
+        }
+        Ok(Some(EdwardsPoint { x, y, z: one, t }))
+    }
+
+    // RFC 9496 section 4.3.2.
+    fn encode(&mut self, point: &EdwardsPoint) -> CryptographyResult<Vec<u8>> {
+        let one = openssl::bn::BigNum::from_u32(1)?;
+        let f = &mut self.curve.field;
+        let z_plus_y = f.add(&point.z, &point.y)?;
+        let z_minus_y = f.sub(&point.z, &point.y)?;
+        let u1 = f.mul(&z_plus_y, &z_minus_y)?;
+        let u2 = f.mul(&point.x, &point.y)?;
+        let u2_sqr = f.mul(&u2, &u2)?;
+        let u1_u2_sqr = f.mul(&u1, &u2_sqr)?;
+        let (_, invsqrt) = self.sqrt_ratio_m1(&one, &u1_u2_sqr)?;
+
+        let f = &mut self.curve.field;
+        let den1 = f.mul(&invsqrt, &u1)?;
+        let den2 = f.mul(&invsqrt, &u2)?;
+        let den1_den2 = f.mul(&den1, &den2)?;
+        let z_inv = f.mul(&den1_den2, &point.t)?;
+        let t_z_inv = f.mul(&point.t, &z_inv)?;
+        let rotate = is_negative(&t_z_inv);
+        let (x, mut y, den_inv) = if rotate {
+            (
+                f.mul(&point.y, &self.curve.sqrt_m1)?,
+                f.mul(&point.x, &self.curve.sqrt_m1)?,
+                f.mul(&den1, &self.invsqrt_a_minus_d)?,
+            )
+        } else {
+            (point.x.to_owned()?, point.y.to_owned()?, den2)
+        };
+        let x_z_inv = f.mul(&x, &z_inv)?;
+        if is_negative(&x_z_inv) {
+            y = self.neg(&y)?;
+        }
+        let f = &mut self.curve.field;
+        let z_minus_y = f.sub(&point.z, &y)?;
+        let s = f.mul(&den_inv, &z_minus_y)?;
+        let s = self.abs(&s)?;
+        ed25519::encode_field_element(&s)
+    }
+
+    // MAP from RFC 9496 section 4.3.4.
+    fn map(&mut self, t: &openssl::bn::BigNumRef) -> CryptographyResult<EdwardsPoint> {
+        let one = openssl::bn::BigNum::from_u32(1)?;
+        let f = &mut self.curve.field;
+        let tt = f.mul(t, t)?;
+        let r = f.mul(&self.curve.sqrt_m1, &tt)?;
+        let r_plus_one = f.add(&r, &one)?;
+        let u = f.mul(&r_plus_one, &self.one_minus_d_sq)?;
+        let zero = openssl::bn::BigNum::new()?;
+        let minus_one = f.sub(&zero, &one)?;
+        let rd = f.mul(&r, &self.curve.d)?;
+        let minus_one_minus_rd = f.sub(&minus_one, &rd)?;
+        let r_plus_d = f.add(&r, &self.curve.d)?;
+        let v = f.mul(&minus_one_minus_rd, &r_plus_d)?;
+
+        let (was_square, s) = self.sqrt_ratio_m1(&u, &v)?;
+        let (s, c) = if was_square {
+            (s, minus_one)
+        } else {
+            let st = self.curve.field.mul(&s, t)?;
+            let st = self.abs(&st)?;
+            (self.neg(&st)?, r.to_owned()?)
+        };
+
+        let f = &mut self.curve.field;
+        let r_minus_one = f.sub(&r, &one)?;
+        let c_r_minus_one = f.mul(&c, &r_minus_one)?;
+        let n = f.mul(&c_r_minus_one, &self.d_minus_one_sq)?;
+        let n = f.sub(&n, &v)?;
+        let two_s = f.add(&s, &s)?;
+        let w0 = f.mul(&two_s, &v)?;
+        let w1 = f.mul(&n, &self.sqrt_ad_minus_one)?;
+        let ss = f.mul(&s, &s)?;
+        let w2 = f.sub(&one, &ss)?;
+        let w3 = f.add(&one, &ss)?;
+        Ok(EdwardsPoint {
+            x: f.mul(&w0, &w3)?,
+            y: f.mul(&w2, &w1)?,
+            z: f.mul(&w1, &w3)?,
+            t: f.mul(&w0, &w2)?,
+        })
+    }
+
+    // The element derivation function from RFC 9496 section 4.3.4.
+    fn derive_element(&mut self, data: &[u8]) -> CryptographyResult<EdwardsPoint> {
+        let mut points = vec![];
+        for half in data.chunks(UNIFORM_LENGTH / 2) {
+            // decode_field_element masks the top bit, as the RFC requires.
+            let t = ed25519::decode_field_element(half)?;
+            let mut reduced = openssl::bn::BigNum::new()?;
+            reduced.nnmod(&t, &self.curve.field.p, &mut self.curve.field.bn_ctx)?;
+            points.push(self.map(&reduced)?);
+        }
+        self.curve.add(&points[0], &points[1])
+    }
+
+    // RFC 9496 section 4.3.3.
+    fn equals(&mut self, a: &EdwardsPoint, b: &EdwardsPoint) -> CryptographyResult<bool> {
+        let f = &mut self.curve.field;
+        let x1_y2 = f.mul(&a.x, &b.y)?;
+        let y1_x2 = f.mul(&a.y, &b.x)?;
+        let y1_y2 = f.mul(&a.y, &b.y)?;
+        let x1_x2 = f.mul(&a.x, &b.x)?;
+        Ok(x1_y2 == y1_x2 || y1_y2 == x1_x2)
+    }
+
+    fn negate(&mut self, point: &EdwardsPoint) -> CryptographyResult<EdwardsPoint> {
+        Ok(EdwardsPoint {
+            x: self.neg(&point.x)?,
+            y: point.y.to_owned()?,
+            z: point.z.to_owned()?,
+            t: self.neg(&point.t)?,
+        })
+    }
+}
+
+fn invalid_length(name: &str, length: usize) -> CryptographyError {
+    CryptographyError::from(pyo3::exceptions::PyValueError::new_err(format!(
+        "{name} must be {length} bytes long"
+    )))
+}
+
+#[pyo3::prelude::pyclass(
+    frozen,
+    module = "cryptography.hazmat.bindings._rust.openssl.ristretto255"
+)]
+pub(crate) struct Ristretto255Element {
+    point: EdwardsPoint,
+}
+
+#[pyo3::prelude::pyclass(
+    frozen,
+    module = "cryptography.hazmat.bindings._rust.openssl.ristretto255"
+)]
+pub(crate) struct Ristretto255Scalar {
+    value: openssl::bn::BigNum,
+}
+
+#[pyo3::prelude::pyfunction]
+fn element_from_bytes(data: CffiBuf<'_>) -> CryptographyResult<Ristretto255Element> {
+    let point = Ristretto255::new()?
+        .decode(data.as_bytes())?
+        .ok_or_else(|| {
+            CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+                "Invalid ristretto255 element encoding",
+            ))
+        })?;
+    Ok(Ristretto255Element { point })
+}
+
+#[pyo3::prelude::pyfunction]
+fn element_from_uniform_bytes(data: CffiBuf<'_>) -> CryptographyResult<Ristretto255Element> {
+    let data = data.as_bytes();
+    if data.len() != UNIFORM_LENGTH {
+        return Err(invalid_length("data", UNIFORM_LENGTH));
+    }
+    Ok(Ristretto255Element {
+        point: Ristretto255::new()?.derive_element(data)?,
+    })
+}
+
+// hash_to_ristretto255 from RFC 9380 appendix B, with expand_message_xmd and
+// SHA-512.
+#[pyo3::prelude::pyfunction]
+fn hash_to_group(data: CffiBuf<'_>, dst: CffiBuf<'_>) -> CryptographyResult<Ristretto255Element> {
+    let dst = dst.as_bytes();
+    if dst.is_empty() || dst.len() > 255 {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("dst must be between 1 and 255 bytes long"),
+        ));
+    }
+    let uniform = bls::expand_message_xmd(
+        openssl::hash::MessageDigest::sha512(),
+        data.as_bytes(),
+        dst,
+        UNIFORM_LENGTH,
+    )?;
+    Ok(Ristretto255Element {
+        point: Ristretto255::new()?.derive_element(&uniform)?,
+    })
+}
+
+#[pyo3::prelude::pyfunction]
+fn generator() -> CryptographyResult<Ristretto255Element> {
+    Ok(Ristretto255Element {
+        point: Edwards25519::new()?.generator,
+    })
+}
+
+#[pyo3::prelude::pyfunction]
+fn identity() -> CryptographyResult<Ristretto255Element> {
+    Ok(Ristretto255Element {
+        point: Edwards25519::identity()?,
+    })
+}
+
+fn reduce_scalar(data: &[u8]) -> CryptographyResult<openssl::bn::BigNum> {
+    let mut be = data.to_vec();
+    be.reverse();
+    let value = openssl::bn::BigNum::from_slice(&be)?;
+    let mut curve = Edwards25519::new()?;
+    let mut reduced = openssl::bn::BigNum::new()?;
+    reduced.nnmod(&value, &curve.order, &mut curve.field.bn_ctx)?;
+    Ok(reduced)
+}
+
+#[pyo3::prelude::pyfunction]
+fn scalar_from_bytes(data: CffiBuf<'_>) -> CryptographyResult<Ristretto255Scalar> {
+    let data = data.as_bytes();
+    if data.len() != SCALAR_LENGTH {
+        return Err(invalid_length("A ristretto255 scalar", SCALAR_LENGTH));
+    }
+    let mut be = data.to_vec();
+    be.reverse();
+    let value = openssl::bn::BigNum::from_slice(&be)?;
+    if value >= Edwards25519::new()?.order {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(
+                "A ristretto255 scalar must be less than the group order",
+            ),
+        ));
+    }
+    Ok(Ristretto255Scalar { value })
+}
+
+#[pyo3::prelude::pyfunction]
+fn scalar_from_uniform_bytes(data: CffiBuf<'_>) -> CryptographyResult<Ristretto255Scalar> {
+    let data = data.as_bytes();
+    if data.len() != UNIFORM_LENGTH {
+        return Err(invalid_length("data", UNIFORM_LENGTH));
+    }
+    Ok(Ristretto255Scalar {
+        value: reduce_scalar(data)?,
+    })
+}
+
+#[pyo3::prelude::pyfunction]
+fn random_scalar() -> CryptographyResult<Ristretto255Scalar> {
+    let mut data = [0; UNIFORM_LENGTH];
+    openssl::rand::rand_bytes(&mut data)?;
+    Ok(Ristretto255Scalar {
+        value: reduce_scalar(&data)?,
+    })
+}
+
+#[pyo3::prelude::pymethods]
+impl Ristretto255Element {
+    fn to_bytes<'p>(&self, py: pyo3::Python<'p>) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let data = Ristretto255::new()?.encode(&self.point)?;
+        Ok(pyo3::types::PyBytes::new(py, &data))
+    }
+
+    fn __add__(&self, other: pyo3::PyRef<'_, Self>) -> CryptographyResult<Ristretto255Element> {
+        Ok(Ristretto255Element {
+            point: Edwards25519::new()?.add(&self.point, &other.point)?,
+        })
+    }
+
+    fn __sub__(&self, other: pyo3::PyRef<'_, Self>) -> CryptographyResult<Ristretto255Element> {
+        let mut group = Ristretto255::new()?;
+        let negated = group.negate(&other.point)?;
+        Ok(Ristretto255Element {
+            point: group.curve.add(&self.point, &negated)?,
+        })
+    }
+
+    fn __neg__(&self) -> CryptographyResult<Ristretto255Element> {
+        Ok(Ristretto255Element {
+            point: Ristretto255::new()?.negate(&self.point)?,
+        })
+    }
+
+    fn __mul__(
+        &self,
+        scalar: pyo3::PyRef<'_, Ristretto255Scalar>,
+    ) -> CryptographyResult<Ristretto255Element> {
+        Ok(Ristretto255Element {
+            point: Edwards25519::new()?.scalar_mul(&self.point, &scalar.value)?,
+        })
+    }
+
+    fn __rmul__(
+        &self,
+        scalar: pyo3::PyRef<'_, Ristretto255Scalar>,
+    ) -> CryptographyResult<Ristretto255Element> {
+        self.__mul__(scalar)
+    }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> CryptographyResult<bool> {
+        Ristretto255::new()?.equals(&self.point, &other.point)
+    }
+
+    fn __hash__(&self) -> CryptographyResult<u64> {
+        let mut hasher = DefaultHasher::new();
+        Ristretto255::new()?.encode(&self.point)?.hash(&mut hasher);
+        Ok(hasher.finish())
+    }
+
+    fn __copy__(slf: pyo3::PyRef<'_, Self>) -> pyo3::PyRef<'_, Self> {
+        slf
+    }
+}
+
+#[pyo3::prelude::pymethods]
+impl Ristretto255Scalar {
+    fn to_bytes<'p>(&self, py: pyo3::Python<'p>) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let mut data = self.value.to_vec_padded(SCALAR_LENGTH as i32)?;
+        data.reverse();
+        Ok(pyo3::types::PyBytes::new(py, &data))
+    }
+
+    fn invert(&self) -> CryptographyResult<Ristretto255Scalar> {
+        if self.value.num_bits() == 0 {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("Zero has no inverse"),
+            ));
+        }
+        let mut curve = Edwards25519::new()?;
+        let mut value = openssl::bn::BigNum::new()?;
+        value.mod_inverse(&self.value, &curve.order, &mut curve.field.bn_ctx)?;
+        Ok(Ristretto255Scalar { value })
+    }
+
+    fn __add__(&self, other: pyo3::PyRef<'_, Self>) -> CryptographyResult<Ristretto255Scalar> {
+        let mut curve = Edwards25519::new()?;
+        let mut value = openssl::bn::BigNum::new()?;
+        value.mod_add(
+            &self.value,
+            &other.value,
+            &curve.order,
+            &mut curve.field.bn_ctx,
+        )?;
+        Ok(Ristretto255Scalar { value })
+    }
+
+    fn __sub__(&self, other: pyo3::PyRef<'_, Self>) -> CryptographyResult<Ristretto255Scalar> {
+        let mut curve = Edwards25519::new()?;
+        let mut value = openssl::bn::BigNum::new()?;
+        value.mod_sub(
+            &self.value,
+            &other.value,
+            &curve.order,
+            &mut curve.field.bn_ctx,
+        )?;
+        Ok(Ristretto255Scalar { value })
+    }
+
+    fn __mul__(&self, other: pyo3::PyRef<'_, Self>) -> CryptographyResult<Ristretto255Scalar> {
+        let mut curve = Edwards25519::new()?;
+        let mut value = openssl::bn::BigNum::new()?;
+        value.mod_mul(
+            &self.value,
+            &other.value,
+            &curve.order,
+            &mut curve.field.bn_ctx,
+        )?;
+        Ok(Ristretto255Scalar { value })
+    }
+
+    fn __neg__(&self) -> CryptographyResult<Ristretto255Scalar> {
+        let mut curve = Edwards25519::new()?;
+        let zero = openssl::bn::BigNum::new()?;
+        let mut value = openssl::bn::BigNum::new()?;
+        value.mod_sub(&zero, &self.value, &curve.order, &mut curve.field.bn_ctx)?;
+        Ok(Ristretto255Scalar { value })
+    }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> bool {
+        self.value == other.value
+    }
+
+    fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.value.to_vec().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn __copy__(slf: pyo3::PyRef<'_, Self>) -> pyo3::PyRef<'_, Self> {
+        slf
+    }
+}
+
+pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let m = pyo3::prelude::PyModule::new(py, "ristretto255")?;
+    m.add_function(pyo3::wrap_pyfunction!(element_from_bytes, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(element_from_uniform_bytes, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(hash_to_group, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(generator, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(identity, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(scalar_from_bytes, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(scalar_from_uniform_bytes, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(random_scalar, m)?)?;
+
+    m.add_class::<Ristretto255Element>()?;
+    m.add_class::<Ristretto255Scalar>()?;
+
+    Ok(m)
+}
@@ -5,8 +5,13 @@
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
-use crate::backend::{hashes, utils};
+use cryptography_x509::common::{
+    AlgorithmParameters, Asn1ReadableOrWritable, SubjectPublicKeyInfo,
+};
+
+use crate::backend::{hashes, jwk, utils};
 use crate::error::{CryptographyError, CryptographyResult};
+use crate::x509::sign;
 use crate::{exceptions, types};
 
 #[pyo3::prelude::pyclass(
@@ -15,7 +20,7 @@ use crate::{exceptions, types};
     name = "RSAPrivateKey"
 )]
 pub(crate) struct RsaPrivateKey {
-    pkey: openssl::pkey::PKey<openssl::pkey::Private>,
+    pub(crate) pkey: openssl::pkey::PKey<openssl::pkey::Private>,
 }
 
 #[pyo3::prelude::pyclass(
@@ -24,7 +29,37 @@ pub(crate) struct RsaPrivateKey {
     name = "RSAPublicKey"
 )]
 pub(crate) struct RsaPublicKey {
+    pub(crate) pkey: openssl::pkey::PKey<openssl::pkey::Public>,
+}
+
+#[pyo3::prelude::pyclass(
+    frozen,
+    module = "cryptography.hazmat.bindings._rust.openssl.rsa",
+    name = "RSAPSSPrivateKey"
+)]
+pub(crate) struct RsaPssPrivateKey {
+    pkey: openssl::pkey::PKey<openssl::pkey::Private>,
+    restrictions: Option<PssRestrictions>,
+}
+
+#[pyo3::prelude::pyclass(
+    frozen,
+    module = "cryptography.hazmat.bindings._rust.openssl.rsa",
+    name = "RSAPSSPublicKey"
+)]
+pub(crate) struct RsaPssPublicKey {
     pkey: openssl::pkey::PKey<openssl::pkey::Public>,
+    restrictions: Option<PssRestrictions>,
+}
+
+// How an RSASSA-PSS key may be used, from the parameters in its
+// AlgorithmIdentifier. Keys without parameters may use any hash and salt
+// length, but are still only for PSS signatures.
+#[derive(PartialEq)]
+struct PssRestrictions {
+    hash: asn1::ObjectIdentifier,
+    mgf1_hash: asn1::ObjectIdentifier,
+    min_salt_length: u16,
 }
 
 fn check_rsa_private_key(
@@ -59,20 +94,263 @@ pub(crate) fn public_key_from_pkey(
     }
 }
 
-#[pyo3::prelude::pyfunction]
-fn generate_private_key(public_exponent: u32, key_size: u32) -> CryptographyResult<RsaPrivateKey> {
+// OpenSSL enforces the restrictions of RSASSA-PSS keys itself, they're only
+// parsed so that they can be exposed, and so that misuse gets a clear error.
+fn pss_restrictions<T: openssl::pkey::HasPublic>(
+    pkey: &openssl::pkey::PKeyRef<T>,
+) -> CryptographyResult<Option<PssRestrictions>> {
+    let spki_der = pkey.public_key_to_der()?;
+    let spki = asn1::parse_single::<SubjectPublicKeyInfo<'_>>(&spki_der)?;
+    match spki.algorithm.params {
+        AlgorithmParameters::RsaPss(Some(params)) => Ok(Some(PssRestrictions {
+            hash: params.hash_algorithm.oid().clone(),
+            mgf1_hash: params.mask_gen_algorithm.params.oid().clone(),
+            min_salt_length: params.salt_length,
+        })),
+        _ => Ok(None),
+    }
+}
+
+pub(crate) fn pss_private_key_from_pkey(
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+    unsafe_skip_rsa_key_validation: bool,
+) -> CryptographyResult<RsaPssPrivateKey> {
+    if !unsafe_skip_rsa_key_validation {
+        check_rsa_private_key(&pkey.rsa().unwrap())?;
+    }
+    Ok(RsaPssPrivateKey {
+        pkey: pkey.to_owned(),
+        restrictions: pss_restrictions(pkey)?,
+    })
+}
+
+pub(crate) fn pss_public_key_from_pkey(
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Public>,
+) -> CryptographyResult<RsaPssPublicKey> {
+    Ok(RsaPssPublicKey {
+        pkey: pkey.to_owned(),
+        restrictions: pss_restrictions(pkey)?,
+    })
+}
+
+fn generate_rsa(
+    public_exponent: u32,
+    key_size: u32,
+    primes: u32,
+) -> CryptographyResult<openssl::rsa::Rsa<openssl::pkey::Private>> {
     let e = openssl::bn::BigNum::from_u32(public_exponent)?;
-    let rsa = openssl::rsa::Rsa::generate_with_e(key_size, &e)?;
+    if primes == 2 {
+        return Ok(openssl::rsa::Rsa::generate_with_e(key_size, &e)?);
+    }
+    cfg_if::cfg_if! {
+        if #[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))] {
+            Ok(cryptography_openssl::rsa::generate_multi_prime_key(key_size, primes, &e)
+                .map_err(|_| {
+                    pyo3::exceptions::PyValueError::new_err(format!(
+                        "{primes} primes are not supported for {key_size}-bit keys."
+                    ))
+                })?)
+        } else {
+            Err(CryptographyError::from(
+                exceptions::UnsupportedAlgorithm::new_err(
+                    "Multi-prime RSA keys are not supported by this backend.",
+                ),
+            ))
+        }
+    }
+}
+
+#[pyo3::prelude::pyfunction]
+#[pyo3(signature = (public_exponent, key_size, primes = 2))]
+fn generate_private_key(
+    py: pyo3::Python<'_>,
+    public_exponent: u32,
+    key_size: u32,
+    primes: u32,
+) -> CryptographyResult<RsaPrivateKey> {
+    // Finding primes can take seconds for large keys, so other threads are
+    // allowed to run meanwhile.
+    let rsa = py.allow_threads(|| generate_rsa(public_exponent, key_size, primes))?;
     let pkey = openssl::pkey::PKey::from_rsa(rsa)?;
     Ok(RsaPrivateKey { pkey })
 }
 
+#[pyo3::prelude::pyfunction]
+#[pyo3(signature = (count, public_exponent, key_size, primes = 2))]
+fn generate_private_keys(
+    py: pyo3::Python<'_>,
+    count: usize,
+    public_exponent: u32,
+    key_size: u32,
+    primes: u32,
+) -> CryptographyResult<Vec<RsaPrivateKey>> {
+    let threads = std::thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(count);
+    let keys = py.allow_threads(|| {
+        // Each thread takes keys to generate from a shared counter, since
+        // how long a key takes varies a lot.
+        let next = std::sync::atomic::AtomicUsize::new(0);
+        std::thread::scope(|s| {
+            let workers = (0..threads)
+                .map(|_| {
+                    s.spawn(|| {
+                        let mut keys = vec![];
+                        while next.fetch_add(1, std::sync::atomic::Ordering::Relaxed) < count {
+                            keys.push(generate_rsa(public_exponent, key_size, primes)?);
+                        }
+                        CryptographyResult::Ok(keys)
+                    })
+                })
+                .collect::<Vec<_>>();
+            let mut keys = Vec::with_capacity(count);
+            for worker in workers {
+                keys.extend(worker.join().unwrap()?);
+            }
+            CryptographyResult::Ok(keys)
+        })
+    })?;
+    keys.into_iter()
+        .map(|rsa| {
+            Ok(RsaPrivateKey {
+                pkey: openssl::pkey::PKey::from_rsa(rsa)?,
+            })
+        })
+        .collect()
+}
+
+// The same limit as rsa_recover_prime_factors in Python.
+const MAX_RECOVERY_ATTEMPTS: u32 = 1000;
+
+/// Recovers p, q, dmp1, dmq1, and iqmp from the modulus and exponents, with
+/// p > q. The primes are found from the private exponent as in section
+/// 8.2.2(i) of the Handbook of Applied Cryptography, so n is assumed to have
+/// two factors.
+pub(crate) fn recover_crt_params(
+    n: &openssl::bn::BigNumRef,
+    e: &openssl::bn::BigNumRef,
+    d: &openssl::bn::BigNumRef,
+) -> CryptographyResult<[openssl::bn::BigNum; 5]> {
+    let recovery_error = || {
+        CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+            "Unable to compute factors p and q from exponent d.",
+        ))
+    };
+    let mut ctx = openssl::bn::BigNumContext::new()?;
+
+    // d*e - 1 is a multiple of phi(n), and is t*2^s with t odd.
+    let mut ktot = openssl::bn::BigNum::new()?;
+    ktot.checked_mul(d, e, &mut ctx)?;
+    ktot.sub_word(1)?;
+    if ktot.is_negative() || ktot.num_bits() == 0 {
+        return Err(recovery_error());
+    }
+    let mut t = ktot.to_owned()?;
+    let mut s = 0;
+    while !t.is_odd() {
+        let mut half = openssl::bn::BigNum::new()?;
+        half.rshift1(&t)?;
+        t = half;
+        s += 1;
+    }
+
+    let one = openssl::bn::BigNum::from_u32(1)?;
+    let mut n_minus_one = n.to_owned()?;
+    n_minus_one.sub_word(1)?;
+    // Look for a non-trivial square root of 1 mod n among a^(t*2^i). Each a
+    // has at least a 50% chance of leading to one.
+    let mut p = None;
+    'outer: for a in (2..MAX_RECOVERY_ATTEMPTS).step_by(2) {
+        let mut cand = openssl::bn::BigNum::new()?;
+        let base = openssl::bn::BigNum::from_u32(a)?;
+        cand.mod_exp(&base, &t, n, &mut ctx)?;
+        for _ in 0..s {
+            let mut square = openssl::bn::BigNum::new()?;
+            square.mod_sqr(&cand, n, &mut ctx)?;
+            if cand != one && cand != n_minus_one && square == one {
+                // (cand - 1)(cand + 1) = 0 mod n, so either term shares a
+                // factor with n.
+                cand.add_word(1)?;
+                let mut factor = openssl::bn::BigNum::new()?;
+                factor.gcd(&cand, n, &mut ctx)?;
+                p = Some(factor);
+                break 'outer;
+            }
+            cand = square;
+        }
+    }
+    let mut p = p.ok_or_else(recovery_error)?;
+
+    let mut q = openssl::bn::BigNum::new()?;
+    let mut rem = openssl::bn::BigNum::new()?;
+    q.div_rem(&mut rem, n, &p, &mut ctx)?;
+    if rem.num_bits() != 0 {
+        return Err(recovery_error());
+    }
+    if p < q {
+        std::mem::swap(&mut p, &mut q);
+    }
+
+    let mut dmp1 = openssl::bn::BigNum::new()?;
+    let mut p_minus_one = p.to_owned()?;
+    p_minus_one.sub_word(1)?;
+    dmp1.nnmod(d, &p_minus_one, &mut ctx)?;
+    let mut dmq1 = openssl::bn::BigNum::new()?;
+    let mut q_minus_one = q.to_owned()?;
+    q_minus_one.sub_word(1)?;
+    dmq1.nnmod(d, &q_minus_one, &mut ctx)?;
+    let mut iqmp = openssl::bn::BigNum::new()?;
+    iqmp.mod_inverse(&q, &p, &mut ctx)?;
+    Ok([p, q, dmp1, dmq1, iqmp])
+}
+
+#[pyo3::prelude::pyfunction]
+fn recover_private_numbers(
+    py: pyo3::Python<'_>,
+    n: &pyo3::types::PyLong,
+    e: &pyo3::types::PyLong,
+    d: &pyo3::types::PyLong,
+) -> CryptographyResult<RsaPrivateNumbers> {
+    let n_bn = utils::py_int_to_bn(py, n)?;
+    let e_bn = utils::py_int_to_bn(py, e)?;
+    let d_bn = utils::py_int_to_bn(py, d)?;
+    let [p, q, dmp1, dmq1, iqmp] = recover_crt_params(&n_bn, &e_bn, &d_bn)?;
+    let to_py_int =
+        |v: &openssl::bn::BigNumRef| -> CryptographyResult<pyo3::Py<pyo3::types::PyLong>> {
+            Ok(utils::bn_to_py_int(py, v)?.extract()?)
+        };
+    Ok(RsaPrivateNumbers {
+        p: to_py_int(&p)?,
+        q: to_py_int(&q)?,
+        d: d.into(),
+        dmp1: to_py_int(&dmp1)?,
+        dmq1: to_py_int(&dmq1)?,
+        iqmp: to_py_int(&iqmp)?,
+        public_numbers: pyo3::Py::new(
+            py,
+            RsaPublicNumbers {
+                e: e.into(),
+                n: n.into(),
+            },
+        )?,
+        other_primes: vec![],
+    })
+}
+
 fn oaep_hash_supported(md: &openssl::hash::MessageDigest) -> bool {
     (!cryptography_openssl::fips::is_enabled() && md == &openssl::hash::MessageDigest::sha1())
         || md == &openssl::hash::MessageDigest::sha224()
         || md == &openssl::hash::MessageDigest::sha256()
         || md == &openssl::hash::MessageDigest::sha384()
         || md == &openssl::hash::MessageDigest::sha512()
+        || is_sha3(md)
+}
+
+// SHA-3 isn't available with every backend, so it's looked up by name.
+fn is_sha3(md: &openssl::hash::MessageDigest) -> bool {
+    ["sha3-224", "sha3-256", "sha3-384", "sha3-512"]
+        .iter()
+        .any(|name| openssl::hash::MessageDigest::from_name(name).as_ref() == Some(md))
 }
 
 fn setup_encryption_ctx(
@@ -276,26 +554,299 @@ fn setup_signature_ctx(
     Ok(())
 }
 
+// Shared with keys whose private components can't be accessed, such as those
+// loaded from a provider.
+pub(crate) fn sign_with_pkey<'p>(
+    py: pyo3::Python<'p>,
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+    data: &[u8],
+    padding: &pyo3::PyAny,
+    algorithm: &pyo3::PyAny,
+) -> CryptographyResult<&'p pyo3::PyAny> {
+    let (data, algorithm) = utils::calculate_digest_and_algorithm(py, data, algorithm)?;
+
+    let mut ctx = openssl::pkey_ctx::PkeyCtx::new(pkey)?;
+    ctx.sign_init().map_err(|_| {
+        pyo3::exceptions::PyValueError::new_err("Unable to sign/verify with this key")
+    })?;
+    setup_signature_ctx(py, &mut ctx, padding, algorithm, pkey.size(), true)?;
+
+    let length = ctx.sign(data, None)?;
+    Ok(pyo3::types::PyBytes::new_with(py, length, |b| {
+        let length = ctx.sign(data, Some(b)).map_err(|_| {
+            pyo3::exceptions::PyValueError::new_err(
+                "Digest or salt length too long for key size. Use a larger key or shorter salt length if you are specifying a PSS salt",
+            )
+        })?;
+        assert_eq!(length, b.len());
+        Ok(())
+    })?)
+}
+
+pub(crate) fn decrypt_with_pkey<'p>(
+    py: pyo3::Python<'p>,
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+    ciphertext: &[u8],
+    padding: &pyo3::PyAny,
+) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+    let key_size_bytes = pkey.size();
+    if key_size_bytes != ciphertext.len() {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("Ciphertext length must be equal to key size."),
+        ));
+    }
+
+    let mut ctx = openssl::pkey_ctx::PkeyCtx::new(pkey)?;
+    ctx.decrypt_init()?;
+
+    setup_encryption_ctx(py, &mut ctx, padding)?;
+
+    // Everything from this line onwards is written with the goal of being
+    // as constant-time as is practical given the constraints of
+    // rust-openssl and our API. See Bleichenbacher's '98 attack on RSA,
+    // and its many many variants. As such, you should not attempt to
+    // change this (particularly to "clean it up") without understanding
+    // why it was written this way (see Chesterton's Fence), and without
+    // measuring to verify you have not introduced observable time
+    // differences.
+    //
+    // Once OpenSSL 3.2.0 is out, this can be simplified, as OpenSSL will
+    // have its own mitigations for Bleichenbacher's attack.
+    let length = ctx.decrypt(ciphertext, None).unwrap();
+    let mut plaintext = vec![0; length];
+    let result = ctx.decrypt(ciphertext, Some(&mut plaintext));
+
+    let py_result =
+        pyo3::types::PyBytes::new(py, &plaintext[..*result.as_ref().unwrap_or(&length)]);
+    if result.is_err() {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("Decryption failed"),
+        ));
+    }
+    Ok(py_result)
+}
+
+// Textbook RSA has no padding, so the input must be a full-length integer
+// that is smaller than the modulus.
+fn check_raw_input(n: &openssl::bn::BigNumRef, data: &[u8]) -> CryptographyResult<()> {
+    if data.len() != usize::try_from(n.num_bytes()).unwrap() {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("Input length must be equal to key size."),
+        ));
+    }
+    if openssl::bn::BigNum::from_slice(data)?.ucmp(n) != std::cmp::Ordering::Less {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("Input must be less than the modulus."),
+        ));
+    }
+    Ok(())
+}
+
+pub(crate) fn raw_decrypt_with_pkey<'p>(
+    py: pyo3::Python<'p>,
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+    n: &openssl::bn::BigNumRef,
+    ciphertext: &[u8],
+) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+    check_raw_input(n, ciphertext)?;
+
+    let mut ctx = openssl::pkey_ctx::PkeyCtx::new(pkey)?;
+    ctx.decrypt_init()?;
+    ctx.set_rsa_padding(openssl::rsa::Padding::NONE)?;
+
+    let mut plaintext = vec![0; ciphertext.len()];
+    let length = ctx.decrypt(ciphertext, Some(&mut plaintext))?;
+    assert_eq!(length, plaintext.len());
+    Ok(pyo3::types::PyBytes::new(py, &plaintext))
+}
+
+fn verify_with_pkey(
+    py: pyo3::Python<'_>,
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Public>,
+    signature: &[u8],
+    data: &[u8],
+    padding: &pyo3::PyAny,
+    algorithm: &pyo3::PyAny,
+) -> CryptographyResult<()> {
+    let (data, algorithm) = utils::calculate_digest_and_algorithm(py, data, algorithm)?;
+
+    let mut ctx = openssl::pkey_ctx::PkeyCtx::new(pkey)?;
+    ctx.verify_init()?;
+    setup_signature_ctx(py, &mut ctx, padding, algorithm, pkey.size(), false)?;
+
+    let valid = ctx.verify(data, signature).unwrap_or(false);
+    if !valid {
+        return Err(CryptographyError::from(
+            exceptions::InvalidSignature::new_err(()),
+        ));
+    }
+
+    Ok(())
+}
+
+// Verifies a PSS signature and returns the length of the signer's salt,
+// which OpenSSL doesn't expose. The salt is recovered from the encoded
+// message as in EMSA-PSS-VERIFY from RFC 8017, section 9.1.2.
+fn verify_pss_with_pkey(
+    py: pyo3::Python<'_>,
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Public>,
+    signature: &[u8],
+    data: &[u8],
+    padding: &pyo3::PyAny,
+    mut algorithm: &pyo3::PyAny,
+) -> CryptographyResult<usize> {
+    if !padding.is_instance(types::PSS.get(py)?)? {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyTypeError::new_err("Padding must be an instance of PSS."),
+        ));
+    }
+    verify_with_pkey(py, pkey, signature, data, padding, algorithm)?;
+
+    if algorithm.is_instance(types::PREHASHED.get(py)?)? {
+        algorithm = algorithm.getattr(pyo3::intern!(py, "_algorithm"))?;
+    }
+    let h_len = hashes::message_digest_from_algorithm(py, algorithm)?.size();
+    let mgf1_md = hashes::message_digest_from_algorithm(
+        py,
+        padding
+            .getattr(pyo3::intern!(py, "_mgf"))?
+            .getattr(pyo3::intern!(py, "_algorithm"))?,
+    )?;
+
+    let rsa = pkey.rsa()?;
+    let size = rsa.size() as usize;
+    // Signatures that verified may still be shorter than the modulus.
+    let mut sig = vec![0; size - signature.len()];
+    sig.extend_from_slice(signature);
+    let mut em = vec![0; size];
+    rsa.public_encrypt(&sig, &mut em, openssl::rsa::Padding::NONE)?;
+
+    let em_bits = rsa.n().num_bits() as usize - 1;
+    let em_len = (em_bits + 7) / 8;
+    let em = &em[size - em_len..];
+    let db_len = em_len - h_len - 1;
+    let (masked_db, h) = em[..em_len - 1].split_at(db_len);
+    let mut db = mgf1(mgf1_md, h, db_len)?;
+    for (b, m) in db.iter_mut().zip(masked_db) {
+        *b ^= m;
+    }
+    db[0] &= 0xff >> (8 * em_len - em_bits);
+    // The signature verified, so DB is PS || 0x01 || salt.
+    let one = db.iter().position(|b| *b != 0).unwrap();
+    Ok(db_len - one - 1)
+}
+
+// Signer, verifier and encryptor objects keep an `EVP_PKEY_CTX` that is set
+// up once with the padding and hash algorithm, so that repeated operations
+// with the same parameters don't pay for that setup on every call.
+#[pyo3::prelude::pyclass(
+    module = "cryptography.hazmat.primitives.asymmetric.rsa",
+    name = "RSASigner"
+)]
+pub(crate) struct RsaSigner {
+    ctx: openssl::pkey_ctx::PkeyCtx<openssl::pkey::Private>,
+    algorithm: pyo3::Py<pyo3::PyAny>,
+}
+
+#[pyo3::prelude::pyclass(
+    module = "cryptography.hazmat.primitives.asymmetric.rsa",
+    name = "RSAVerifier"
+)]
+struct RsaVerifier {
+    ctx: openssl::pkey_ctx::PkeyCtx<openssl::pkey::Public>,
+    algorithm: pyo3::Py<pyo3::PyAny>,
+}
+
+#[pyo3::prelude::pyclass(
+    module = "cryptography.hazmat.primitives.asymmetric.rsa",
+    name = "RSAEncryptor"
+)]
+struct RsaEncryptor {
+    ctx: openssl::pkey_ctx::PkeyCtx<openssl::pkey::Public>,
+}
+
+// The digest is computed for each message, so the context is set up with the
+// algorithm that the digest will be computed with.
+fn context_hash_algorithm<'p>(
+    py: pyo3::Python<'p>,
+    algorithm: &'p pyo3::PyAny,
+) -> CryptographyResult<&'p pyo3::PyAny> {
+    let hash_algorithm = if algorithm.is_instance(types::PREHASHED.get(py)?)? {
+        algorithm.getattr(pyo3::intern!(py, "_algorithm"))?
+    } else {
+        algorithm
+    };
+    if !hash_algorithm.is_instance(types::HASH_ALGORITHM.get(py)?)? {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyTypeError::new_err("Expected instance of hashes.HashAlgorithm."),
+        ));
+    }
+    Ok(hash_algorithm)
+}
+
+pub(crate) fn signer_with_pkey(
+    py: pyo3::Python<'_>,
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+    padding: &pyo3::PyAny,
+    algorithm: &pyo3::PyAny,
+) -> CryptographyResult<RsaSigner> {
+    let hash_algorithm = context_hash_algorithm(py, algorithm)?;
+
+    let mut ctx = openssl::pkey_ctx::PkeyCtx::new(pkey)?;
+    ctx.sign_init().map_err(|_| {
+        pyo3::exceptions::PyValueError::new_err("Unable to sign/verify with this key")
+    })?;
+    setup_signature_ctx(py, &mut ctx, padding, hash_algorithm, pkey.size(), true)?;
+
+    Ok(RsaSigner {
+        ctx,
+        algorithm: algorithm.into(),
+    })
+}
+
+fn verifier_with_pkey(
+    py: pyo3::Python<'_>,
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Public>,
+    padding: &pyo3::PyAny,
+    algorithm: &pyo3::PyAny,
+) -> CryptographyResult<RsaVerifier> {
+    let hash_algorithm = context_hash_algorithm(py, algorithm)?;
+
+    let mut ctx = openssl::pkey_ctx::PkeyCtx::new(pkey)?;
+    ctx.verify_init()?;
+    setup_signature_ctx(py, &mut ctx, padding, hash_algorithm, pkey.size(), false)?;
+
+    Ok(RsaVerifier {
+        ctx,
+        algorithm: algorithm.into(),
+    })
+}
+
+fn encryptor_with_pkey(
+    py: pyo3::Python<'_>,
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Public>,
+    padding: &pyo3::PyAny,
+) -> CryptographyResult<RsaEncryptor> {
+    let mut ctx = openssl::pkey_ctx::PkeyCtx::new(pkey)?;
+    ctx.encrypt_init()?;
+
+    setup_encryption_ctx(py, &mut ctx, padding)?;
+
+    Ok(RsaEncryptor { ctx })
+}
+
 #[pyo3::prelude::pymethods]
-impl RsaPrivateKey {
+impl RsaSigner {
     fn sign<'p>(
-        &self,
+        &mut self,
         py: pyo3::Python<'p>,
         data: &[u8],
-        padding: &pyo3::PyAny,
-        algorithm: &pyo3::PyAny,
-    ) -> CryptographyResult<&'p pyo3::PyAny> {
-        let (data, algorithm) = utils::calculate_digest_and_algorithm(py, data, algorithm)?;
-
-        let mut ctx = openssl::pkey_ctx::PkeyCtx::new(&self.pkey)?;
-        ctx.sign_init().map_err(|_| {
-            pyo3::exceptions::PyValueError::new_err("Unable to sign/verify with this key")
-        })?;
-        setup_signature_ctx(py, &mut ctx, padding, algorithm, self.pkey.size(), true)?;
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let (data, _) = utils::calculate_digest_and_algorithm(py, data, self.algorithm.as_ref(py))?;
 
-        let length = ctx.sign(data, None)?;
+        let length = self.ctx.sign(data, None)?;
         Ok(pyo3::types::PyBytes::new_with(py, length, |b| {
-            let length = ctx.sign(data, Some(b)).map_err(|_| {
+            let length = self.ctx.sign(data, Some(b)).map_err(|_| {
                 pyo3::exceptions::PyValueError::new_err(
                     "Digest or salt length too long for key size. Use a larger key or shorter salt length if you are specifying a PSS salt",
                 )
@@ -304,51 +855,774 @@ impl RsaPrivateKey {
             Ok(())
         })?)
     }
+}
+
+#[pyo3::prelude::pymethods]
+impl RsaVerifier {
+    fn verify(
+        &mut self,
+        py: pyo3::Python<'_>,
+        signature: &[u8],
+        data: &[u8],
+    ) -> CryptographyResult<()> {
+        let (data, _) = utils::calculate_digest_and_algorithm(py, data, self.algorithm.as_ref(py))?;
+
+        let valid = self.ctx.verify(data, signature).unwrap_or(false);
+        if !valid {
+            return Err(CryptographyError::from(
+                exceptions::InvalidSignature::new_err(()),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[pyo3::prelude::pymethods]
+impl RsaEncryptor {
+    fn encrypt<'p>(
+        &mut self,
+        py: pyo3::Python<'p>,
+        plaintext: &[u8],
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let length = self.ctx.encrypt(plaintext, None)?;
+        Ok(pyo3::types::PyBytes::new_with(py, length, |b| {
+            let length = self
+                .ctx
+                .encrypt(plaintext, Some(b))
+                .map_err(|_| pyo3::exceptions::PyValueError::new_err("Encryption failed"))?;
+            assert_eq!(length, b.len());
+            Ok(())
+        })?)
+    }
+}
+
+fn private_numbers(
+    py: pyo3::Python<'_>,
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+) -> CryptographyResult<RsaPrivateNumbers> {
+    let rsa = pkey.rsa().unwrap();
+
+    let py_p = utils::bn_to_py_int(py, rsa.p().unwrap())?;
+    let py_q = utils::bn_to_py_int(py, rsa.q().unwrap())?;
+    let py_d = utils::bn_to_py_int(py, rsa.d())?;
+    let py_dmp1 = utils::bn_to_py_int(py, rsa.dmp1().unwrap())?;
+    let py_dmq1 = utils::bn_to_py_int(py, rsa.dmq1().unwrap())?;
+    let py_iqmp = utils::bn_to_py_int(py, rsa.iqmp().unwrap())?;
+    let py_e = utils::bn_to_py_int(py, rsa.e())?;
+    let py_n = utils::bn_to_py_int(py, rsa.n())?;
+
+    let public_numbers = RsaPublicNumbers {
+        e: py_e.extract()?,
+        n: py_n.extract()?,
+    };
+    Ok(RsaPrivateNumbers {
+        p: py_p.extract()?,
+        q: py_q.extract()?,
+        d: py_d.extract()?,
+        dmp1: py_dmp1.extract()?,
+        dmq1: py_dmq1.extract()?,
+        iqmp: py_iqmp.extract()?,
+        public_numbers: pyo3::Py::new(py, public_numbers)?,
+        other_primes: other_primes(py, &rsa)?,
+    })
+}
+
+fn py_int_to_biguint(v: &pyo3::types::PyLong) -> CryptographyResult<asn1::BigUint<'_>> {
+    let bytes = crate::asn1::py_uint_to_big_endian_bytes(v.py(), v)?;
+    Ok(asn1::BigUint::new(bytes).unwrap())
+}
+
+type OtherPrime = (
+    pyo3::Py<pyo3::types::PyLong>,
+    pyo3::Py<pyo3::types::PyLong>,
+    pyo3::Py<pyo3::types::PyLong>,
+);
+
+pub(crate) fn is_multi_prime(
+    rsa: &openssl::rsa::RsaRef<openssl::pkey::Private>,
+) -> CryptographyResult<bool> {
+    let (p, q) = match (rsa.p(), rsa.q()) {
+        (Some(p), Some(q)) => (p, q),
+        _ => return Ok(false),
+    };
+    let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+    let mut pq = openssl::bn::BigNum::new()?;
+    pq.checked_mul(p, q, &mut bn_ctx)?;
+    Ok(pq != *rsa.n())
+}
+
+// The primes of a multi-prime key after the first two, with their CRT
+// exponents and coefficients. OpenSSL doesn't expose these other than by
+// serializing the key.
+fn other_prime_bns(
+    rsa: &openssl::rsa::RsaRef<openssl::pkey::Private>,
+) -> CryptographyResult<Vec<[openssl::bn::BigNum; 3]>> {
+    if !is_multi_prime(rsa)? {
+        return Ok(vec![]);
+    }
+
+    let der = rsa.private_key_to_der()?;
+    let key = asn1::parse_single::<cryptography_key_parsing::rsa::Pkcs1RsaPrivateKey<'_>>(&der)?;
+    let infos = match key.other_prime_infos {
+        Some(infos) => infos.unwrap_read().clone(),
+        None => return Ok(vec![]),
+    };
+    let to_bn = |v: asn1::BigUint<'_>| openssl::bn::BigNum::from_slice(v.as_bytes());
+    Ok(infos
+        .map(|info| {
+            Ok([
+                to_bn(info.prime)?,
+                to_bn(info.exponent)?,
+                to_bn(info.coefficient)?,
+            ])
+        })
+        .collect::<Result<_, openssl::error::ErrorStack>>()?)
+}
+
+fn other_primes(
+    py: pyo3::Python<'_>,
+    rsa: &openssl::rsa::RsaRef<openssl::pkey::Private>,
+) -> CryptographyResult<Vec<OtherPrime>> {
+    other_prime_bns(rsa)?
+        .iter()
+        .map(|[r, d, t]| {
+            Ok((
+                utils::bn_to_py_int(py, r)?.extract()?,
+                utils::bn_to_py_int(py, d)?.extract()?,
+                utils::bn_to_py_int(py, t)?.extract()?,
+            ))
+        })
+        .collect()
+}
+
+#[pyo3::prelude::pyclass(
+    frozen,
+    module = "cryptography.hazmat.primitives.asymmetric.rsa",
+    name = "RSAKeyCheck"
+)]
+struct RsaKeyCheck {
+    #[pyo3(get)]
+    public_exponent_valid: bool,
+    #[pyo3(get)]
+    modulus_matches_primes: bool,
+    #[pyo3(get)]
+    primes_are_prime: bool,
+    #[pyo3(get)]
+    prime_sizes_valid: bool,
+    #[pyo3(get)]
+    private_exponent_valid: bool,
+    #[pyo3(get)]
+    crt_exponents_valid: bool,
+    #[pyo3(get)]
+    crt_coefficients_valid: bool,
+}
+
+#[pyo3::prelude::pymethods]
+impl RsaKeyCheck {
+    #[getter]
+    fn valid(&self) -> bool {
+        self.public_exponent_valid
+            && self.modulus_matches_primes
+            && self.primes_are_prime
+            && self.prime_sizes_valid
+            && self.private_exponent_valid
+            && self.crt_exponents_valid
+            && self.crt_coefficients_valid
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<RSAKeyCheck(public_exponent_valid={}, modulus_matches_primes={}, primes_are_prime={}, prime_sizes_valid={}, private_exponent_valid={}, crt_exponents_valid={}, crt_coefficients_valid={})>",
+            py_bool(self.public_exponent_valid),
+            py_bool(self.modulus_matches_primes),
+            py_bool(self.primes_are_prime),
+            py_bool(self.prime_sizes_valid),
+            py_bool(self.private_exponent_valid),
+            py_bool(self.crt_exponents_valid),
+            py_bool(self.crt_coefficients_valid),
+        )
+    }
+}
+
+fn py_bool(v: bool) -> &'static str {
+    if v {
+        "True"
+    } else {
+        "False"
+    }
+}
+
+// Checks each part of a private key separately, so that a malformed key can
+// be reported on rather than just rejected as OpenSSL's RSA_check_key does.
+// Arithmetic that fails on a malformed key, such as reducing modulo zero,
+// counts as the check failing.
+fn check_key(
+    rsa: &openssl::rsa::RsaRef<openssl::pkey::Private>,
+) -> CryptographyResult<RsaKeyCheck> {
+    type CheckResult = Result<bool, openssl::error::ErrorStack>;
+
+    // Whether a * b = 1 mod m, with a reduced mod m.
+    fn is_inverse(
+        a: &openssl::bn::BigNumRef,
+        b: &openssl::bn::BigNumRef,
+        m: &openssl::bn::BigNumRef,
+        ctx: &mut openssl::bn::BigNumContextRef,
+    ) -> CheckResult {
+        let mut v = openssl::bn::BigNum::new()?;
+        v.mod_mul(a, b, m, ctx)?;
+        Ok(a.ucmp(m) == std::cmp::Ordering::Less && v.num_bits() == 1)
+    }
+
+    fn minus_one(
+        r: &openssl::bn::BigNumRef,
+    ) -> Result<openssl::bn::BigNum, openssl::error::ErrorStack> {
+        let mut v = openssl::bn::BigNum::new()?;
+        let one = openssl::bn::BigNum::from_u32(1)?;
+        v.checked_sub(r, &one)?;
+        Ok(v)
+    }
+
+    let mut ctx = openssl::bn::BigNumContext::new()?;
+    let (n, e, d) = (rsa.n(), rsa.e(), rsa.d());
+    let (p, q) = (rsa.p().unwrap(), rsa.q().unwrap());
+    let others = other_prime_bns(rsa)?;
+    // Each prime with its CRT exponent.
+    let primes = [(p, rsa.dmp1().unwrap()), (q, rsa.dmq1().unwrap())]
+        .into_iter()
+        .chain(others.iter().map(|[r, d, _]| (&**r, &**d)))
+        .collect::<Vec<_>>();
+
+    let public_exponent_valid =
+        e.is_odd() && e.num_bits() > 1 && e.ucmp(n) == std::cmp::Ordering::Less;
+
+    let modulus_matches_primes = (|| -> CheckResult {
+        let mut product = openssl::bn::BigNum::from_u32(1)?;
+        for (r, _) in &primes {
+            let mut v = openssl::bn::BigNum::new()?;
+            v.checked_mul(&product, r, &mut ctx)?;
+            product = v;
+        }
+        Ok(product == *n)
+    })()
+    .unwrap_or(false);
+
+    let primes_are_prime = (|| -> CheckResult {
+        for (r, _) in &primes {
+            if !r.is_prime(0, &mut ctx)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    })()
+    .unwrap_or(false);
+
+    // The primes should be the same size, and p and q shouldn't be so close
+    // that n can be factored by searching around its square root (FIPS 186-5,
+    // appendix A.1.3). That distance only applies to two prime keys, so with
+    // more primes each is instead checked to be its share of n's size.
+    let prime_sizes_valid = (|| -> CheckResult {
+        if !others.is_empty() {
+            let share = n.num_bits() / i32::try_from(primes.len()).unwrap();
+            return Ok(primes
+                .iter()
+                .all(|(r, _)| (r.num_bits() - share).abs() <= 1));
+        }
+        let bits = primes.iter().map(|(r, _)| r.num_bits());
+        if bits.clone().max().unwrap() - bits.min().unwrap() > 1 {
+            return Ok(false);
+        }
+        let mut diff = openssl::bn::BigNum::new()?;
+        diff.checked_sub(p, q)?;
+        diff.set_negative(false);
+        Ok(diff.num_bits() > (n.num_bits() / 2 - 100).max(1))
+    })()
+    .unwrap_or(false);
+
+    let private_exponent_valid = (|| -> CheckResult {
+        if d.num_bits() <= 1 || d.ucmp(n) != std::cmp::Ordering::Less {
+            return Ok(false);
+        }
+        for (r, _) in &primes {
+            let mut ed = openssl::bn::BigNum::new()?;
+            let order = minus_one(r)?;
+            ed.mod_mul(e, d, &order, &mut ctx)?;
+            if ed.num_bits() != 1 {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    })()
+    .unwrap_or(false);
+
+    let crt_exponents_valid = (|| -> CheckResult {
+        for (r, exponent) in &primes {
+            let mut v = openssl::bn::BigNum::new()?;
+            let order = minus_one(r)?;
+            v.nnmod(d, &order, &mut ctx)?;
+            if v != **exponent {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    })()
+    .unwrap_or(false);
+
+    // iqmp is the inverse of q mod p, and the coefficient of each further
+    // prime is the inverse of the product of the primes before it.
+    let crt_coefficients_valid = (|| -> CheckResult {
+        if !is_inverse(rsa.iqmp().unwrap(), q, p, &mut ctx)? {
+            return Ok(false);
+        }
+        let mut product = openssl::bn::BigNum::new()?;
+        product.checked_mul(p, q, &mut ctx)?;
+        for [r, _, t] in &others {
+            if !is_inverse(t, &product, r, &mut ctx)? {
+                return Ok(false);
+            }
+            let mut v = openssl::bn::BigNum::new()?;
+            v.checked_mul(&product, r, &mut ctx)?;
+            product = v;
+        }
+        Ok(true)
+    })()
+    .unwrap_or(false);
+
+    Ok(RsaKeyCheck {
+        public_exponent_valid,
+        modulus_matches_primes,
+        primes_are_prime,
+        prime_sizes_valid,
+        private_exponent_valid,
+        crt_exponents_valid,
+        crt_coefficients_valid,
+    })
+}
+
+fn public_numbers<T: openssl::pkey::HasPublic>(
+    py: pyo3::Python<'_>,
+    pkey: &openssl::pkey::PKeyRef<T>,
+) -> CryptographyResult<RsaPublicNumbers> {
+    let rsa = pkey.rsa().unwrap();
+
+    let py_e = utils::bn_to_py_int(py, rsa.e())?;
+    let py_n = utils::bn_to_py_int(py, rsa.n())?;
+
+    Ok(RsaPublicNumbers {
+        e: py_e.extract()?,
+        n: py_n.extract()?,
+    })
+}
+
+fn pss_only_error() -> CryptographyError {
+    CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+        "RSA-PSS keys can only be used for PSS signatures.",
+    ))
+}
+
+// Raises an error for uses of an RSASSA-PSS key that OpenSSL would reject,
+// so that they don't surface as an opaque OpenSSL error.
+fn check_pss_usage(
+    py: pyo3::Python<'_>,
+    restrictions: &Option<PssRestrictions>,
+    padding: &pyo3::PyAny,
+    algorithm: &pyo3::PyAny,
+) -> CryptographyResult<()> {
+    if !padding.is_instance(types::PSS.get(py)?)? {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(
+                "RSA-PSS keys can only be used with PSS padding.",
+            ),
+        ));
+    }
+    let r = match restrictions {
+        Some(r) => r,
+        None => return Ok(()),
+    };
+
+    let algorithm = if algorithm.is_instance(types::PREHASHED.get(py)?)? {
+        algorithm.getattr(pyo3::intern!(py, "_algorithm"))?
+    } else {
+        algorithm
+    };
+    // Anything else is rejected when the signature context is set up.
+    if !algorithm.is_instance(types::HASH_ALGORITHM.get(py)?)? {
+        return Ok(());
+    }
+    let hash = sign::hash_oid_py_hash(py, r.hash.clone())?;
+    if !algorithm
+        .getattr(pyo3::intern!(py, "name"))?
+        .eq(hash.getattr(pyo3::intern!(py, "name"))?)?
+    {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "This RSA-PSS key can only be used with {}.",
+                hash.getattr(pyo3::intern!(py, "name"))?
+            )),
+        ));
+    }
+
+    let mgf = padding.getattr(pyo3::intern!(py, "_mgf"))?;
+    let mgf1_hash = sign::hash_oid_py_hash(py, r.mgf1_hash.clone())?;
+    if mgf.is_instance(types::MGF1.get(py)?)?
+        && !mgf
+            .getattr(pyo3::intern!(py, "_algorithm"))?
+            .getattr(pyo3::intern!(py, "name"))?
+            .eq(mgf1_hash.getattr(pyo3::intern!(py, "name"))?)?
+    {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "This RSA-PSS key can only be used with MGF1 using {}.",
+                mgf1_hash.getattr(pyo3::intern!(py, "name"))?
+            )),
+        ));
+    }
+
+    let salt = padding.getattr(pyo3::intern!(py, "_salt_length"))?;
+    let salt_length = if salt.is_instance(types::PADDING_MAX_LENGTH.get(py)?)? {
+        None
+    } else if salt.is_instance(types::PADDING_AUTO.get(py)?)? {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(
+                "PSS salt length can't be Auto for RSA-PSS keys with a minimum salt length.",
+            ),
+        ));
+    } else if salt.is_instance(types::PADDING_DIGEST_LENGTH.get(py)?)? {
+        Some(
+            algorithm
+                .getattr(pyo3::intern!(py, "digest_size"))?
+                .extract::<u64>()?,
+        )
+    } else {
+        Some(salt.extract::<u64>()?)
+    };
+    if let Some(salt_length) = salt_length {
+        if salt_length < u64::from(r.min_salt_length) {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "This RSA-PSS key requires a salt length of at least {}.",
+                    r.min_salt_length
+                )),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn pss_hash_algorithm<'p>(
+    py: pyo3::Python<'p>,
+    restrictions: &Option<PssRestrictions>,
+) -> CryptographyResult<Option<&'p pyo3::PyAny>> {
+    match restrictions {
+        Some(r) => Ok(Some(sign::hash_oid_py_hash(py, r.hash.clone())?)),
+        None => Ok(None),
+    }
+}
+
+fn pss_padding<'p>(
+    py: pyo3::Python<'p>,
+    restrictions: &Option<PssRestrictions>,
+) -> CryptographyResult<Option<&'p pyo3::PyAny>> {
+    match restrictions {
+        Some(r) => {
+            let mgf1_hash = sign::hash_oid_py_hash(py, r.mgf1_hash.clone())?;
+            let mgf = types::MGF1.get(py)?.call1((mgf1_hash,))?;
+            Ok(Some(types::PSS.get(py)?.call1((mgf, r.min_salt_length))?))
+        }
+        None => Ok(None),
+    }
+}
+
+#[pyo3::prelude::pymethods]
+impl RsaPrivateKey {
+    fn sign<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        data: &[u8],
+        padding: &pyo3::PyAny,
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::PyAny> {
+        sign_with_pkey(py, &self.pkey, data, padding, algorithm)
+    }
+
+    fn signer(
+        &self,
+        py: pyo3::Python<'_>,
+        padding: &pyo3::PyAny,
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<RsaSigner> {
+        signer_with_pkey(py, &self.pkey, padding, algorithm)
+    }
+
+    fn decrypt<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        ciphertext: &[u8],
+        padding: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        decrypt_with_pkey(py, &self.pkey, ciphertext, padding)
+    }
+
+    fn unsafe_raw_decrypt<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        ciphertext: &[u8],
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let rsa = self.pkey.rsa().unwrap();
+        raw_decrypt_with_pkey(py, &self.pkey, rsa.n(), ciphertext)
+    }
+
+    #[getter]
+    fn key_size(&self) -> i32 {
+        self.pkey.rsa().unwrap().n().num_bits()
+    }
+
+    fn public_key(&self) -> CryptographyResult<RsaPublicKey> {
+        let priv_rsa = self.pkey.rsa().unwrap();
+        let rsa = openssl::rsa::Rsa::from_public_components(
+            priv_rsa.n().to_owned()?,
+            priv_rsa.e().to_owned()?,
+        )
+        .unwrap();
+        let pkey = openssl::pkey::PKey::from_rsa(rsa)?;
+        Ok(RsaPublicKey { pkey })
+    }
+
+    fn private_numbers(&self, py: pyo3::Python<'_>) -> CryptographyResult<RsaPrivateNumbers> {
+        private_numbers(py, &self.pkey)
+    }
+
+    fn check(&self) -> CryptographyResult<RsaKeyCheck> {
+        check_key(&self.pkey.rsa().unwrap())
+    }
+
+    #[pyo3(signature = (kid=None))]
+    fn jwk_private_bytes<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        kid: Option<&str>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let jwk = jwk::private_jwk(&self.pkey, kid)?;
+        Ok(pyo3::types::PyBytes::new(py, jwk.as_bytes()))
+    }
+
+    fn private_bytes<'p>(
+        slf: &pyo3::PyCell<Self>,
+        py: pyo3::Python<'p>,
+        encoding: &pyo3::PyAny,
+        format: &pyo3::PyAny,
+        encryption_algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        utils::pkey_private_bytes(
+            py,
+            slf,
+            &slf.borrow().pkey,
+            encoding,
+            format,
+            encryption_algorithm,
+            true,
+            false,
+        )
+    }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> CryptographyResult<bool> {
+        utils::pkey_private_eq(&self.pkey, &other.pkey)
+    }
+
+    fn __hash__(&self) -> CryptographyResult<u64> {
+        utils::pkey_hash(&self.pkey)
+    }
+
+    fn public_key_matches(
+        slf: &pyo3::PyCell<Self>,
+        py: pyo3::Python<'_>,
+        public_key: &pyo3::PyAny,
+    ) -> CryptographyResult<bool> {
+        utils::public_key_matches(py, slf, public_key)
+    }
+}
+
+#[pyo3::prelude::pymethods]
+impl RsaPublicKey {
+    fn verify(
+        &self,
+        py: pyo3::Python<'_>,
+        signature: &[u8],
+        data: &[u8],
+        padding: &pyo3::PyAny,
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<()> {
+        verify_with_pkey(py, &self.pkey, signature, data, padding, algorithm)
+    }
+
+    fn verify_pss(
+        &self,
+        py: pyo3::Python<'_>,
+        signature: &[u8],
+        data: &[u8],
+        padding: &pyo3::PyAny,
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<usize> {
+        verify_pss_with_pkey(py, &self.pkey, signature, data, padding, algorithm)
+    }
+
+    fn verifier(
+        &self,
+        py: pyo3::Python<'_>,
+        padding: &pyo3::PyAny,
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<RsaVerifier> {
+        verifier_with_pkey(py, &self.pkey, padding, algorithm)
+    }
+
+    fn encrypt<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        plaintext: &[u8],
+        padding: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        encryptor_with_pkey(py, &self.pkey, padding)?.encrypt(py, plaintext)
+    }
+
+    fn encryptor(
+        &self,
+        py: pyo3::Python<'_>,
+        padding: &pyo3::PyAny,
+    ) -> CryptographyResult<RsaEncryptor> {
+        encryptor_with_pkey(py, &self.pkey, padding)
+    }
+
+    fn unsafe_raw_encrypt<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        plaintext: &[u8],
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        check_raw_input(self.pkey.rsa().unwrap().n(), plaintext)?;
+
+        let mut ctx = openssl::pkey_ctx::PkeyCtx::new(&self.pkey)?;
+        ctx.encrypt_init()?;
+        ctx.set_rsa_padding(openssl::rsa::Padding::NONE)?;
+
+        let mut ciphertext = vec![0; plaintext.len()];
+        let length = ctx.encrypt(plaintext, Some(&mut ciphertext))?;
+        assert_eq!(length, ciphertext.len());
+        Ok(pyo3::types::PyBytes::new(py, &ciphertext))
+    }
+
+    fn recover_data_from_signature<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        signature: &[u8],
+        padding: &pyo3::PyAny,
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        if algorithm.is_instance(types::PREHASHED.get(py)?)? {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyTypeError::new_err(
+                    "Prehashed is only supported in the sign and verify methods. It cannot be used with recover_data_from_signature.",
+                ),
+            ));
+        }
+
+        let mut ctx = openssl::pkey_ctx::PkeyCtx::new(&self.pkey)?;
+        ctx.verify_recover_init()?;
+        setup_signature_ctx(py, &mut ctx, padding, algorithm, self.pkey.size(), false)?;
+
+        let length = ctx.verify_recover(signature, None)?;
+        let mut buf = vec![0u8; length];
+        let length = ctx
+            .verify_recover(signature, Some(&mut buf))
+            .map_err(|_| exceptions::InvalidSignature::new_err(()))?;
+
+        Ok(pyo3::types::PyBytes::new(py, &buf[..length]))
+    }
+
+    #[getter]
+    fn key_size(&self) -> i32 {
+        self.pkey.rsa().unwrap().n().num_bits()
+    }
+
+    fn public_numbers(&self, py: pyo3::Python<'_>) -> CryptographyResult<RsaPublicNumbers> {
+        public_numbers(py, &self.pkey)
+    }
+
+    #[pyo3(signature = (kid=None))]
+    fn jwk_public_bytes<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        kid: Option<&str>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let jwk = jwk::public_jwk(&self.pkey, kid)?;
+        Ok(pyo3::types::PyBytes::new(py, jwk.as_bytes()))
+    }
+
+    fn public_bytes<'p>(
+        slf: &pyo3::PyCell<Self>,
+        py: pyo3::Python<'p>,
+        encoding: &pyo3::PyAny,
+        format: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        utils::pkey_public_bytes(py, slf, &slf.borrow().pkey, encoding, format, true, false)
+    }
+
+    fn fingerprint<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::PyAny> {
+        utils::pkey_fingerprint(py, &self.pkey, algorithm)
+    }
+
+    #[pyo3(signature = (algorithm=None))]
+    fn jwk_thumbprint<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        algorithm: Option<&pyo3::PyAny>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        jwk::public_jwk_thumbprint(py, &self.pkey, algorithm)
+    }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> bool {
+        self.pkey.public_eq(&other.pkey)
+    }
+
+    fn __copy__(slf: pyo3::PyRef<'_, Self>) -> pyo3::PyRef<'_, Self> {
+        slf
+    }
+}
+
+#[pyo3::prelude::pymethods]
+impl RsaPssPrivateKey {
+    fn sign<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        data: &[u8],
+        padding: &pyo3::PyAny,
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::PyAny> {
+        check_pss_usage(py, &self.restrictions, padding, algorithm)?;
+        sign_with_pkey(py, &self.pkey, data, padding, algorithm)
+    }
 
-    fn decrypt<'p>(
+    fn signer(
         &self,
-        py: pyo3::Python<'p>,
-        ciphertext: &[u8],
+        py: pyo3::Python<'_>,
         padding: &pyo3::PyAny,
-    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
-        let key_size_bytes =
-            usize::try_from((self.pkey.rsa().unwrap().n().num_bits() + 7) / 8).unwrap();
-        if key_size_bytes != ciphertext.len() {
-            return Err(CryptographyError::from(
-                pyo3::exceptions::PyValueError::new_err(
-                    "Ciphertext length must be equal to key size.",
-                ),
-            ));
-        }
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<RsaSigner> {
+        check_pss_usage(py, &self.restrictions, padding, algorithm)?;
+        signer_with_pkey(py, &self.pkey, padding, algorithm)
+    }
 
-        let mut ctx = openssl::pkey_ctx::PkeyCtx::new(&self.pkey)?;
-        ctx.decrypt_init()?;
-
-        setup_encryption_ctx(py, &mut ctx, padding)?;
-
-        // Everything from this line onwards is written with the goal of being
-        // as constant-time as is practical given the constraints of
-        // rust-openssl and our API. See Bleichenbacher's '98 attack on RSA,
-        // and its many many variants. As such, you should not attempt to
-        // change this (particularly to "clean it up") without understanding
-        // why it was written this way (see Chesterton's Fence), and without
-        // measuring to verify you have not introduced observable time
-        // differences.
-        //
-        // Once OpenSSL 3.2.0 is out, this can be simplified, as OpenSSL will
-        // have its own mitigations for Bleichenbacher's attack.
-        let length = ctx.decrypt(ciphertext, None).unwrap();
-        let mut plaintext = vec![0; length];
-        let result = ctx.decrypt(ciphertext, Some(&mut plaintext));
-
-        let py_result =
-            pyo3::types::PyBytes::new(py, &plaintext[..*result.as_ref().unwrap_or(&length)]);
-        if result.is_err() {
-            return Err(CryptographyError::from(
-                pyo3::exceptions::PyValueError::new_err("Decryption failed"),
-            ));
-        }
-        Ok(py_result)
+    fn decrypt(&self, ciphertext: &[u8], padding: &pyo3::PyAny) -> CryptographyResult<()> {
+        let _ = (ciphertext, padding);
+        Err(pss_only_error())
+    }
+
+    fn unsafe_raw_decrypt(&self, ciphertext: &[u8]) -> CryptographyResult<()> {
+        let _ = ciphertext;
+        Err(pss_only_error())
     }
 
     #[getter]
@@ -356,42 +1630,40 @@ impl RsaPrivateKey {
         self.pkey.rsa().unwrap().n().num_bits()
     }
 
-    fn public_key(&self) -> CryptographyResult<RsaPublicKey> {
-        let priv_rsa = self.pkey.rsa().unwrap();
-        let rsa = openssl::rsa::Rsa::from_public_components(
-            priv_rsa.n().to_owned()?,
-            priv_rsa.e().to_owned()?,
-        )
-        .unwrap();
-        let pkey = openssl::pkey::PKey::from_rsa(rsa)?;
-        Ok(RsaPublicKey { pkey })
+    #[getter]
+    fn hash_algorithm<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+    ) -> CryptographyResult<Option<&'p pyo3::PyAny>> {
+        pss_hash_algorithm(py, &self.restrictions)
+    }
+
+    #[getter]
+    fn padding<'p>(&self, py: pyo3::Python<'p>) -> CryptographyResult<Option<&'p pyo3::PyAny>> {
+        pss_padding(py, &self.restrictions)
+    }
+
+    fn public_key(&self) -> CryptographyResult<RsaPssPublicKey> {
+        // Round tripping through the SPKI keeps the PSS parameters.
+        let der = self.pkey.public_key_to_der()?;
+        let pkey = openssl::pkey::PKey::public_key_from_der(&der)?;
+        pss_public_key_from_pkey(&pkey)
     }
 
     fn private_numbers(&self, py: pyo3::Python<'_>) -> CryptographyResult<RsaPrivateNumbers> {
-        let rsa = self.pkey.rsa().unwrap();
+        private_numbers(py, &self.pkey)
+    }
 
-        let py_p = utils::bn_to_py_int(py, rsa.p().unwrap())?;
-        let py_q = utils::bn_to_py_int(py, rsa.q().unwrap())?;
-        let py_d = utils::bn_to_py_int(py, rsa.d())?;
-        let py_dmp1 = utils::bn_to_py_int(py, rsa.dmp1().unwrap())?;
-        let py_dmq1 = utils::bn_to_py_int(py, rsa.dmq1().unwrap())?;
-        let py_iqmp = utils::bn_to_py_int(py, rsa.iqmp().unwrap())?;
-        let py_e = utils::bn_to_py_int(py, rsa.e())?;
-        let py_n = utils::bn_to_py_int(py, rsa.n())?;
-
-        let public_numbers = RsaPublicNumbers {
-            e: py_e.extract()?,
-            n: py_n.extract()?,
-        };
-        Ok(RsaPrivateNumbers {
-            p: py_p.extract()?,
-            q: py_q.extract()?,
-            d: py_d.extract()?,
-            dmp1: py_dmp1.extract()?,
-            dmq1: py_dmq1.extract()?,
-            iqmp: py_iqmp.extract()?,
-            public_numbers: pyo3::Py::new(py, public_numbers)?,
-        })
+    fn check(&self) -> CryptographyResult<RsaKeyCheck> {
+        check_key(&self.pkey.rsa().unwrap())
+    }
+
+    #[pyo3(signature = (kid=None))]
+    fn jwk_private_bytes(&self, kid: Option<&str>) -> CryptographyResult<()> {
+        let _ = kid;
+        Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("RSA-PSS keys can't be encoded as a JWK."),
+        ))
     }
 
     fn private_bytes<'p>(
@@ -401,6 +1673,14 @@ impl RsaPrivateKey {
         format: &pyo3::PyAny,
         encryption_algorithm: &pyo3::PyAny,
     ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        // The traditional format has no room for the PSS parameters.
+        if format.is(types::PRIVATE_FORMAT_TRADITIONAL_OPENSSL.get(py)?) {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "RSA-PSS keys can't be serialized as TraditionalOpenSSL, use PKCS8.",
+                ),
+            ));
+        }
         utils::pkey_private_bytes(
             py,
             slf,
@@ -408,14 +1688,31 @@ impl RsaPrivateKey {
             encoding,
             format,
             encryption_algorithm,
-            true,
+            false,
             false,
         )
     }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> CryptographyResult<bool> {
+        Ok(self.restrictions == other.restrictions
+            && utils::pkey_private_eq(&self.pkey, &other.pkey)?)
+    }
+
+    fn __hash__(&self) -> CryptographyResult<u64> {
+        utils::pkey_hash(&self.pkey)
+    }
+
+    fn public_key_matches(
+        slf: &pyo3::PyCell<Self>,
+        py: pyo3::Python<'_>,
+        public_key: &pyo3::PyAny,
+    ) -> CryptographyResult<bool> {
+        utils::public_key_matches(py, slf, public_key)
+    }
 }
 
 #[pyo3::prelude::pymethods]
-impl RsaPublicKey {
+impl RsaPssPublicKey {
     fn verify(
         &self,
         py: pyo3::Python<'_>,
@@ -424,69 +1721,55 @@ impl RsaPublicKey {
         padding: &pyo3::PyAny,
         algorithm: &pyo3::PyAny,
     ) -> CryptographyResult<()> {
-        let (data, algorithm) = utils::calculate_digest_and_algorithm(py, data, algorithm)?;
-
-        let mut ctx = openssl::pkey_ctx::PkeyCtx::new(&self.pkey)?;
-        ctx.verify_init()?;
-        setup_signature_ctx(py, &mut ctx, padding, algorithm, self.pkey.size(), false)?;
-
-        let valid = ctx.verify(data, signature).unwrap_or(false);
-        if !valid {
-            return Err(CryptographyError::from(
-                exceptions::InvalidSignature::new_err(()),
-            ));
-        }
+        check_pss_usage(py, &self.restrictions, padding, algorithm)?;
+        verify_with_pkey(py, &self.pkey, signature, data, padding, algorithm)
+    }
 
-        Ok(())
+    fn verify_pss(
+        &self,
+        py: pyo3::Python<'_>,
+        signature: &[u8],
+        data: &[u8],
+        padding: &pyo3::PyAny,
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<usize> {
+        check_pss_usage(py, &self.restrictions, padding, algorithm)?;
+        verify_pss_with_pkey(py, &self.pkey, signature, data, padding, algorithm)
     }
 
-    fn encrypt<'p>(
+    fn verifier(
         &self,
-        py: pyo3::Python<'p>,
-        plaintext: &[u8],
+        py: pyo3::Python<'_>,
         padding: &pyo3::PyAny,
-    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
-        let mut ctx = openssl::pkey_ctx::PkeyCtx::new(&self.pkey)?;
-        ctx.encrypt_init()?;
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<RsaVerifier> {
+        check_pss_usage(py, &self.restrictions, padding, algorithm)?;
+        verifier_with_pkey(py, &self.pkey, padding, algorithm)
+    }
 
-        setup_encryption_ctx(py, &mut ctx, padding)?;
+    fn encrypt(&self, plaintext: &[u8], padding: &pyo3::PyAny) -> CryptographyResult<()> {
+        let _ = (plaintext, padding);
+        Err(pss_only_error())
+    }
 
-        let length = ctx.encrypt(plaintext, None)?;
-        Ok(pyo3::types::PyBytes::new_with(py, length, |b| {
-            let length = ctx
-                .encrypt(plaintext, Some(b))
-                .map_err(|_| pyo3::exceptions::PyValueError::new_err("Encryption failed"))?;
-            assert_eq!(length, b.len());
-            Ok(())
-        })?)
+    fn encryptor(&self, padding: &pyo3::PyAny) -> CryptographyResult<()> {
+        let _ = padding;
+        Err(pss_only_error())
     }
 
-    fn recover_data_from_signature<'p>(
+    fn unsafe_raw_encrypt(&self, plaintext: &[u8]) -> CryptographyResult<()> {
+        let _ = plaintext;
+        Err(pss_only_error())
+    }
+
+    fn recover_data_from_signature(
         &self,
-        py: pyo3::Python<'p>,
         signature: &[u8],
         padding: &pyo3::PyAny,
         algorithm: &pyo3::PyAny,
-    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
-        if algorithm.is_instance(types::PREHASHED.get(py)?)? {
-            return Err(CryptographyError::from(
-                pyo3::exceptions::PyTypeError::new_err(
-                    "Prehashed is only supported in the sign and verify methods. It cannot be used with recover_data_from_signature.",
-                ),
-            ));
-        }
-
-        let mut ctx = openssl::pkey_ctx::PkeyCtx::new(&self.pkey)?;
-        ctx.verify_recover_init()?;
-        setup_signature_ctx(py, &mut ctx, padding, algorithm, self.pkey.size(), false)?;
-
-        let length = ctx.verify_recover(signature, None)?;
-        let mut buf = vec![0u8; length];
-        let length = ctx
-            .verify_recover(signature, Some(&mut buf))
-            .map_err(|_| exceptions::InvalidSignature::new_err(()))?;
-
-        Ok(pyo3::types::PyBytes::new(py, &buf[..length]))
+    ) -> CryptographyResult<()> {
+        let _ = (signature, padding, algorithm);
+        Err(pss_only_error())
     }
 
     #[getter]
@@ -494,16 +1777,29 @@ impl RsaPublicKey {
         self.pkey.rsa().unwrap().n().num_bits()
     }
 
-    fn public_numbers(&self, py: pyo3::Python<'_>) -> CryptographyResult<RsaPublicNumbers> {
-        let rsa = self.pkey.rsa().unwrap();
+    #[getter]
+    fn hash_algorithm<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+    ) -> CryptographyResult<Option<&'p pyo3::PyAny>> {
+        pss_hash_algorithm(py, &self.restrictions)
+    }
+
+    #[getter]
+    fn padding<'p>(&self, py: pyo3::Python<'p>) -> CryptographyResult<Option<&'p pyo3::PyAny>> {
+        pss_padding(py, &self.restrictions)
+    }
 
-        let py_e = utils::bn_to_py_int(py, rsa.e())?;
-        let py_n = utils::bn_to_py_int(py, rsa.n())?;
+    fn public_numbers(&self, py: pyo3::Python<'_>) -> CryptographyResult<RsaPublicNumbers> {
+        public_numbers(py, &self.pkey)
+    }
 
-        Ok(RsaPublicNumbers {
-            e: py_e.extract()?,
-            n: py_n.extract()?,
-        })
+    #[pyo3(signature = (kid=None))]
+    fn jwk_public_bytes(&self, kid: Option<&str>) -> CryptographyResult<()> {
+        let _ = kid;
+        Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("RSA-PSS keys can't be encoded as a JWK."),
+        ))
     }
 
     fn public_bytes<'p>(
@@ -512,11 +1808,35 @@ impl RsaPublicKey {
         encoding: &pyo3::PyAny,
         format: &pyo3::PyAny,
     ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
-        utils::pkey_public_bytes(py, slf, &slf.borrow().pkey, encoding, format, true, false)
+        // PKCS1 has no room for the PSS parameters.
+        if format.is(types::PUBLIC_FORMAT_PKCS1.get(py)?) {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "RSA-PSS keys can't be serialized as PKCS1, use SubjectPublicKeyInfo.",
+                ),
+            ));
+        }
+        utils::pkey_public_bytes(py, slf, &slf.borrow().pkey, encoding, format, false, false)
+    }
+
+    fn fingerprint<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::PyAny> {
+        utils::pkey_fingerprint(py, &self.pkey, algorithm)
+    }
+
+    #[pyo3(signature = (algorithm=None))]
+    fn jwk_thumbprint(&self, algorithm: Option<&pyo3::PyAny>) -> CryptographyResult<()> {
+        let _ = algorithm;
+        Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("RSA-PSS keys can't be encoded as a JWK."),
+        ))
     }
 
     fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> bool {
-        self.pkey.public_eq(&other.pkey)
+        self.restrictions == other.restrictions && self.pkey.public_eq(&other.pkey)
     }
 
     fn __copy__(slf: pyo3::PyRef<'_, Self>) -> pyo3::PyRef<'_, Self> {
@@ -544,6 +1864,7 @@ struct RsaPrivateNumbers {
     iqmp: pyo3::Py<pyo3::types::PyLong>,
     #[pyo3(get)]
     public_numbers: pyo3::Py<RsaPublicNumbers>,
+    other_primes: Vec<OtherPrime>,
 }
 
 #[pyo3::prelude::pyclass(
@@ -568,6 +1889,7 @@ fn check_private_key_components(
     iqmp: &pyo3::types::PyLong,
     public_exponent: &pyo3::types::PyLong,
     modulus: &pyo3::types::PyLong,
+    other_primes: &[OtherPrime],
 ) -> CryptographyResult<()> {
     if modulus.lt(3)? {
         return Err(CryptographyError::from(
@@ -636,9 +1958,31 @@ fn check_private_key_components(
         ));
     }
 
-    if p.call_method1("__mul__", (q,))?.ne(modulus)? {
+    if other_primes.is_empty() {
+        if p.call_method1("__mul__", (q,))?.ne(modulus)? {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("p*q must equal modulus."),
+            ));
+        }
+        return Ok(());
+    }
+
+    let py = modulus.py();
+    let mut product = p.call_method1("__mul__", (q,))?;
+    for (r, d, t) in other_primes {
+        let (r, d, t) = (r.as_ref(py), d.as_ref(py), t.as_ref(py));
+        if r.ge(modulus)? || d.ge(modulus)? || t.ge(modulus)? {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("other_primes values must be < modulus."),
+            ));
+        }
+        product = product.call_method1("__mul__", (r,))?;
+    }
+    if product.ne(modulus)? {
         return Err(CryptographyError::from(
-            pyo3::exceptions::PyValueError::new_err("p*q must equal modulus."),
+            pyo3::exceptions::PyValueError::new_err(
+                "The product of the primes must equal modulus.",
+            ),
         ));
     }
 
@@ -648,6 +1992,8 @@ fn check_private_key_components(
 #[pyo3::prelude::pymethods]
 impl RsaPrivateNumbers {
     #[new]
+    #[pyo3(signature = (p, q, d, dmp1, dmq1, iqmp, public_numbers, *, other_primes = vec![]))]
+    #[allow(clippy::too_many_arguments)]
     fn new(
         p: pyo3::Py<pyo3::types::PyLong>,
         q: pyo3::Py<pyo3::types::PyLong>,
@@ -656,6 +2002,7 @@ impl RsaPrivateNumbers {
         dmq1: pyo3::Py<pyo3::types::PyLong>,
         iqmp: pyo3::Py<pyo3::types::PyLong>,
         public_numbers: pyo3::Py<RsaPublicNumbers>,
+        other_primes: Vec<OtherPrime>,
     ) -> RsaPrivateNumbers {
         Self {
             p,
@@ -665,9 +2012,20 @@ impl RsaPrivateNumbers {
             dmq1,
             iqmp,
             public_numbers,
+            other_primes,
         }
     }
 
+    #[getter]
+    fn other_primes<'p>(&self, py: pyo3::Python<'p>) -> &'p pyo3::types::PyTuple {
+        pyo3::types::PyTuple::new(
+            py,
+            self.other_primes
+                .iter()
+                .map(|(r, d, t)| (r.clone_ref(py), d.clone_ref(py), t.clone_ref(py))),
+        )
+    }
+
     #[pyo3(signature = (backend = None, *, unsafe_skip_rsa_key_validation = false))]
     fn private_key(
         &self,
@@ -686,19 +2044,58 @@ impl RsaPrivateNumbers {
             self.iqmp.as_ref(py),
             self.public_numbers.get().e.as_ref(py),
             self.public_numbers.get().n.as_ref(py),
+            &self.other_primes,
         )?;
         let public_numbers = self.public_numbers.get();
-        let rsa = openssl::rsa::Rsa::from_private_components(
-            utils::py_int_to_bn(py, public_numbers.n.as_ref(py))?,
-            utils::py_int_to_bn(py, public_numbers.e.as_ref(py))?,
-            utils::py_int_to_bn(py, self.d.as_ref(py))?,
-            utils::py_int_to_bn(py, self.p.as_ref(py))?,
-            utils::py_int_to_bn(py, self.q.as_ref(py))?,
-            utils::py_int_to_bn(py, self.dmp1.as_ref(py))?,
-            utils::py_int_to_bn(py, self.dmq1.as_ref(py))?,
-            utils::py_int_to_bn(py, self.iqmp.as_ref(py))?,
-        )
-        .unwrap();
+        let rsa = if self.other_primes.is_empty() {
+            openssl::rsa::Rsa::from_private_components(
+                utils::py_int_to_bn(py, public_numbers.n.as_ref(py))?,
+                utils::py_int_to_bn(py, public_numbers.e.as_ref(py))?,
+                utils::py_int_to_bn(py, self.d.as_ref(py))?,
+                utils::py_int_to_bn(py, self.p.as_ref(py))?,
+                utils::py_int_to_bn(py, self.q.as_ref(py))?,
+                utils::py_int_to_bn(py, self.dmp1.as_ref(py))?,
+                utils::py_int_to_bn(py, self.dmq1.as_ref(py))?,
+                utils::py_int_to_bn(py, self.iqmp.as_ref(py))?,
+            )
+            .unwrap()
+        } else {
+            // OpenSSL can only be given the other primes as a serialized key.
+            fn uint<'p>(
+                py: pyo3::Python<'p>,
+                v: &'p pyo3::Py<pyo3::types::PyLong>,
+            ) -> CryptographyResult<asn1::BigUint<'p>> {
+                py_int_to_biguint(v.as_ref(py))
+            }
+            let infos = self
+                .other_primes
+                .iter()
+                .map(|(r, d, t)| {
+                    Ok(cryptography_key_parsing::rsa::OtherPrimeInfo {
+                        prime: uint(py, r)?,
+                        exponent: uint(py, d)?,
+                        coefficient: uint(py, t)?,
+                    })
+                })
+                .collect::<CryptographyResult<Vec<_>>>()?;
+            let key = cryptography_key_parsing::rsa::Pkcs1RsaPrivateKey {
+                version: 1,
+                n: uint(py, &public_numbers.n)?,
+                e: uint(py, &public_numbers.e)?,
+                d: uint(py, &self.d)?,
+                p: uint(py, &self.p)?,
+                q: uint(py, &self.q)?,
+                dmp1: uint(py, &self.dmp1)?,
+                dmq1: uint(py, &self.dmq1)?,
+                iqmp: uint(py, &self.iqmp)?,
+                other_prime_infos: Some(Asn1ReadableOrWritable::new_write(
+                    asn1::SequenceOfWriter::new(infos),
+                )),
+            };
+            openssl::rsa::Rsa::private_key_from_der(&asn1::write_single(&key)?).map_err(|_| {
+                pyo3::exceptions::PyValueError::new_err("Invalid multi-prime private key.")
+            })?
+        };
         if !unsafe_skip_rsa_key_validation {
             check_rsa_private_key(&rsa)?;
         }
@@ -720,7 +2117,8 @@ impl RsaPrivateNumbers {
             && self
                 .public_numbers
                 .as_ref(py)
-                .eq(other.public_numbers.as_ref(py))?)
+                .eq(other.public_numbers.as_ref(py))?
+            && self.other_primes(py).eq(other.other_primes(py))?)
     }
 
     fn __hash__(&self, py: pyo3::Python<'_>) -> CryptographyResult<u64> {
@@ -732,6 +2130,7 @@ impl RsaPrivateNumbers {
         self.dmq1.as_ref(py).hash()?.hash(&mut hasher);
         self.iqmp.as_ref(py).hash()?.hash(&mut hasher);
         self.public_numbers.as_ref(py).hash()?.hash(&mut hasher);
+        self.other_primes(py).hash()?.hash(&mut hasher);
         Ok(hasher.finish())
     }
 }
@@ -812,14 +2211,308 @@ impl RsaPublicNumbers {
     }
 }
 
+// MGF1 from RFC 8017, appendix B.2.1.
+fn mgf1(
+    md: openssl::hash::MessageDigest,
+    seed: &[u8],
+    length: usize,
+) -> CryptographyResult<Vec<u8>> {
+    let mut mask = Vec::with_capacity(length + md.size());
+    let mut counter = 0u32;
+    while mask.len() < length {
+        let mut h = openssl::hash::Hasher::new(md)?;
+        h.update(seed)?;
+        h.update(&counter.to_be_bytes())?;
+        mask.extend_from_slice(&h.finish()?);
+        counter += 1;
+    }
+    mask.truncate(length);
+    Ok(mask)
+}
+
+// EMSA-PSS-ENCODE from RFC 8017, section 9.1.1. OpenSSL only applies PSS
+// padding as part of signing, but blinding needs the encoded message itself.
+fn emsa_pss_encode(
+    md: openssl::hash::MessageDigest,
+    msg: &[u8],
+    em_bits: usize,
+    salt_length: usize,
+) -> CryptographyResult<Vec<u8>> {
+    let h_len = md.size();
+    let em_len = (em_bits + 7) / 8;
+    if em_len < h_len + salt_length + 2 {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(
+                "Digest and salt length too large for key size. Use a larger key.",
+            ),
+        ));
+    }
+    let m_hash = openssl::hash::hash(md, msg)?;
+    let mut salt = vec![0; salt_length];
+    openssl::rand::rand_bytes(&mut salt)?;
+
+    let mut h = openssl::hash::Hasher::new(md)?;
+    h.update(&[0; 8])?;
+    h.update(&m_hash)?;
+    h.update(&salt)?;
+    let h = h.finish()?;
+
+    // DB is PS || 0x01 || salt, masked by MGF1(H).
+    let db_len = em_len - h_len - 1;
+    let mut em = mgf1(md, &h, db_len)?;
+    em[db_len - salt_length - 1] ^= 0x01;
+    for (b, s) in em[db_len - salt_length..].iter_mut().zip(&salt) {
+        *b ^= s;
+    }
+    em[0] &= 0xff >> (8 * em_len - em_bits);
+    em.extend_from_slice(&h);
+    em.push(0xbc);
+    Ok(em)
+}
+
+fn bssa_error(msg: &'static str) -> CryptographyError {
+    CryptographyError::from(pyo3::exceptions::PyValueError::new_err(msg))
+}
+
+/// RSA blind signatures with appendix (RSABSSA) from RFC 9474.
+#[pyo3::prelude::pyclass(
+    frozen,
+    module = "cryptography.hazmat.primitives.asymmetric.rsa",
+    name = "RSABSSA"
+)]
+struct RsaBssa {
+    #[pyo3(get)]
+    algorithm: pyo3::Py<pyo3::PyAny>,
+    #[pyo3(get)]
+    salt_length: usize,
+    #[pyo3(get)]
+    randomized: bool,
+}
+
+impl RsaBssa {
+    fn padding<'p>(&self, py: pyo3::Python<'p>) -> pyo3::PyResult<&'p pyo3::PyAny> {
+        let mgf = types::MGF1.get(py)?.call1((self.algorithm.as_ref(py),))?;
+        types::PSS.get(py)?.call1((mgf, self.salt_length))
+    }
+
+    fn public_pkey(
+        &self,
+        py: pyo3::Python<'_>,
+        public_key: &pyo3::PyAny,
+    ) -> CryptographyResult<openssl::pkey::PKey<openssl::pkey::Public>> {
+        if let Ok(k) = public_key.extract::<pyo3::PyRef<'_, RsaPublicKey>>() {
+            Ok(k.pkey.clone())
+        } else if let Ok(k) = public_key.extract::<pyo3::PyRef<'_, RsaPssPublicKey>>() {
+            check_pss_usage(
+                py,
+                &k.restrictions,
+                self.padding(py)?,
+                self.algorithm.as_ref(py),
+            )?;
+            Ok(k.pkey.clone())
+        } else {
+            Err(CryptographyError::from(
+                pyo3::exceptions::PyTypeError::new_err("public_key must be an RSAPublicKey"),
+            ))
+        }
+    }
+
+    fn private_rsa(
+        &self,
+        py: pyo3::Python<'_>,
+        private_key: &pyo3::PyAny,
+    ) -> CryptographyResult<openssl::rsa::Rsa<openssl::pkey::Private>> {
+        if let Ok(k) = private_key.extract::<pyo3::PyRef<'_, RsaPrivateKey>>() {
+            Ok(k.pkey.rsa()?)
+        } else if let Ok(k) = private_key.extract::<pyo3::PyRef<'_, RsaPssPrivateKey>>() {
+            check_pss_usage(
+                py,
+                &k.restrictions,
+                self.padding(py)?,
+                self.algorithm.as_ref(py),
+            )?;
+            Ok(k.pkey.rsa()?)
+        } else {
+            Err(CryptographyError::from(
+                pyo3::exceptions::PyTypeError::new_err("private_key must be an RSAPrivateKey"),
+            ))
+        }
+    }
+}
+
+#[pyo3::prelude::pymethods]
+impl RsaBssa {
+    #[new]
+    #[pyo3(signature = (algorithm, salt_length, *, randomized))]
+    fn new(
+        py: pyo3::Python<'_>,
+        algorithm: pyo3::Py<pyo3::PyAny>,
+        salt_length: usize,
+        randomized: bool,
+    ) -> CryptographyResult<RsaBssa> {
+        // Checks that the hash is one that can be used for signatures.
+        hashes::message_digest_from_algorithm(py, algorithm.as_ref(py))?;
+        Ok(RsaBssa {
+            algorithm,
+            salt_length,
+            randomized,
+        })
+    }
+
+    fn prepare<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        msg: &[u8],
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        if !self.randomized {
+            return Ok(pyo3::types::PyBytes::new(py, msg));
+        }
+        Ok(pyo3::types::PyBytes::new_with(py, 32 + msg.len(), |b| {
+            openssl::rand::rand_bytes(&mut b[..32]).map_err(CryptographyError::from)?;
+            b[32..].copy_from_slice(msg);
+            Ok(())
+        })?)
+    }
+
+    fn blind<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        public_key: &pyo3::PyAny,
+        msg: &[u8],
+    ) -> CryptographyResult<(&'p pyo3::types::PyBytes, &'p pyo3::types::PyBytes)> {
+        let pkey = self.public_pkey(py, public_key)?;
+        let rsa = pkey.rsa()?;
+        let n = rsa.n();
+        let md = hashes::message_digest_from_algorithm(py, self.algorithm.as_ref(py))?;
+        let encoded = emsa_pss_encode(md, msg, n.num_bits() as usize - 1, self.salt_length)?;
+
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let m = openssl::bn::BigNum::from_slice(&encoded)?;
+        let mut gcd = openssl::bn::BigNum::new()?;
+        gcd.gcd(&m, n, &mut bn_ctx)?;
+        if gcd.num_bits() != 1 {
+            return Err(bssa_error("Invalid input for blinding."));
+        }
+
+        let mut r = openssl::bn::BigNum::new()?;
+        loop {
+            n.rand_range(&mut r)?;
+            if r.num_bits() != 0 {
+                break;
+            }
+        }
+        let mut inv = openssl::bn::BigNum::new()?;
+        inv.mod_inverse(&r, n, &mut bn_ctx)
+            .map_err(|_| bssa_error("Invalid blind."))?;
+        let mut x = openssl::bn::BigNum::new()?;
+        x.mod_exp(&r, rsa.e(), n, &mut bn_ctx)?;
+        let mut z = openssl::bn::BigNum::new()?;
+        z.mod_mul(&m, &x, n, &mut bn_ctx)?;
+
+        let length = rsa.size() as i32;
+        Ok((
+            pyo3::types::PyBytes::new(py, &z.to_vec_padded(length)?),
+            pyo3::types::PyBytes::new(py, &inv.to_vec_padded(length)?),
+        ))
+    }
+
+    fn blind_sign<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        private_key: &pyo3::PyAny,
+        blinded_msg: &[u8],
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let rsa = self.private_rsa(py, private_key)?;
+        let length = rsa.size() as usize;
+        if blinded_msg.len() != length {
+            return Err(bssa_error("Blinded message has the wrong length."));
+        }
+        let m = openssl::bn::BigNum::from_slice(blinded_msg)?;
+        if m.ucmp(rsa.n()) != std::cmp::Ordering::Less {
+            return Err(bssa_error("Blinded message is out of range."));
+        }
+
+        let mut sig = vec![0; length];
+        rsa.private_decrypt(blinded_msg, &mut sig, openssl::rsa::Padding::NONE)?;
+        // Guards against faults in the CRT computation leaking the key.
+        let mut check = vec![0; length];
+        rsa.public_encrypt(&sig, &mut check, openssl::rsa::Padding::NONE)?;
+        if !openssl::memcmp::eq(&check, blinded_msg) {
+            return Err(bssa_error("Signing failed."));
+        }
+        Ok(pyo3::types::PyBytes::new(py, &sig))
+    }
+
+    fn finalize<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        public_key: &pyo3::PyAny,
+        msg: &[u8],
+        blind_sig: &[u8],
+        inv: &[u8],
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let pkey = self.public_pkey(py, public_key)?;
+        let rsa = pkey.rsa()?;
+        let length = rsa.size() as usize;
+        if blind_sig.len() != length || inv.len() != length {
+            return Err(bssa_error(
+                "Blind signature or inverse has the wrong length.",
+            ));
+        }
+
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let blind_sig = openssl::bn::BigNum::from_slice(blind_sig)?;
+        let inv = openssl::bn::BigNum::from_slice(inv)?;
+        let mut s = openssl::bn::BigNum::new()?;
+        s.mod_mul(&blind_sig, &inv, rsa.n(), &mut bn_ctx)?;
+        let sig = s.to_vec_padded(length as i32)?;
+        verify_with_pkey(
+            py,
+            &pkey,
+            &sig,
+            msg,
+            self.padding(py)?,
+            self.algorithm.as_ref(py),
+        )?;
+        Ok(pyo3::types::PyBytes::new(py, &sig))
+    }
+
+    fn verify(
+        &self,
+        py: pyo3::Python<'_>,
+        public_key: &pyo3::PyAny,
+        signature: &[u8],
+        msg: &[u8],
+    ) -> CryptographyResult<()> {
+        let pkey = self.public_pkey(py, public_key)?;
+        verify_with_pkey(
+            py,
+            &pkey,
+            signature,
+            msg,
+            self.padding(py)?,
+            self.algorithm.as_ref(py),
+        )
+    }
+}
+
 pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
     let m = pyo3::prelude::PyModule::new(py, "rsa")?;
     m.add_function(pyo3::wrap_pyfunction!(generate_private_key, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(generate_private_keys, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(recover_private_numbers, m)?)?;
 
     m.add_class::<RsaPrivateKey>()?;
     m.add_class::<RsaPublicKey>()?;
+    m.add_class::<RsaPssPrivateKey>()?;
+    m.add_class::<RsaPssPublicKey>()?;
     m.add_class::<RsaPrivateNumbers>()?;
     m.add_class::<RsaPublicNumbers>()?;
+    m.add_class::<RsaBssa>()?;
+    m.add_class::<RsaKeyCheck>()?;
+    m.add_class::<RsaSigner>()?;
+    m.add_class::<RsaVerifier>()?;
+    m.add_class::<RsaEncryptor>()?;
 
     Ok(m)
 }
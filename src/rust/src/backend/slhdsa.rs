@@ -0,0 +1,265 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+use cryptography_openssl::slhdsa::{self, Variant};
+
+use crate::backend::utils;
+use crate::buf::CffiBuf;
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::{exceptions, types};
+
+#[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.slhdsa")]
+pub(crate) struct SLHDSAPrivateKey {
+    pkey: openssl::pkey::PKey<openssl::pkey::Private>,
+}
+
+#[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.slhdsa")]
+pub(crate) struct SLHDSAPublicKey {
+    pkey: openssl::pkey::PKey<openssl::pkey::Public>,
+}
+
+fn parameter_set_from_py(
+    py: pyo3::Python<'_>,
+    parameter_set: &pyo3::PyAny,
+) -> CryptographyResult<Variant> {
+    if !parameter_set.is_instance(types::SLH_DSA_PARAMETER_SET.get(py)?)? {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyTypeError::new_err("parameter_set must be an SLHDSAParameterSet"),
+        ));
+    }
+    // The enum's values are the names of the parameter sets.
+    let name = parameter_set
+        .getattr(pyo3::intern!(py, "value"))?
+        .extract::<&str>()?;
+    Ok(Variant::from_name(name).unwrap())
+}
+
+fn parameter_set_to_py<'p, T>(
+    py: pyo3::Python<'p>,
+    pkey: &openssl::pkey::PKeyRef<T>,
+) -> pyo3::PyResult<&'p pyo3::PyAny> {
+    // Keys are only ever created for one of the parameter sets.
+    let variant = Variant::from_pkey(pkey).unwrap();
+    types::SLH_DSA_PARAMETER_SET
+        .get(py)?
+        .call1((variant.name(),))
+}
+
+// FIPS 205 limits contexts to 255 bytes.
+fn check_context(context: &[u8]) -> CryptographyResult<()> {
+    if context.len() > 255 {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("context must be at most 255 bytes long"),
+        ));
+    }
+    Ok(())
+}
+
+pub(crate) fn private_key_from_pkey(
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+) -> SLHDSAPrivateKey {
+    SLHDSAPrivateKey {
+        pkey: pkey.to_owned(),
+    }
+}
+
+pub(crate) fn public_key_from_pkey(
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Public>,
+) -> SLHDSAPublicKey {
+    SLHDSAPublicKey {
+        pkey: pkey.to_owned(),
+    }
+}
+
+#[pyo3::prelude::pyfunction]
+fn generate_key(
+    py: pyo3::Python<'_>,
+    parameter_set: &pyo3::PyAny,
+) -> CryptographyResult<SLHDSAPrivateKey> {
+    let variant = parameter_set_from_py(py, parameter_set)?;
+    Ok(SLHDSAPrivateKey {
+        pkey: slhdsa::generate(variant)?,
+    })
+}
+
+#[pyo3::prelude::pyfunction]
+fn from_private_bytes(
+    py: pyo3::Python<'_>,
+    parameter_set: &pyo3::PyAny,
+    data: CffiBuf<'_>,
+) -> CryptographyResult<SLHDSAPrivateKey> {
+    let variant = parameter_set_from_py(py, parameter_set)?;
+    if data.as_bytes().len() != 4 * variant.n() {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "An {} private key is {} bytes long",
+                variant.name(),
+                4 * variant.n()
+            )),
+        ));
+    }
+    let pkey = slhdsa::private_key_from_raw_bytes(variant, data.as_bytes()).map_err(|_| {
+        pyo3::exceptions::PyValueError::new_err(
+            "Invalid private key for this SLH-DSA parameter set",
+        )
+    })?;
+    Ok(SLHDSAPrivateKey { pkey })
+}
+
+#[pyo3::prelude::pyfunction]
+fn from_public_bytes(
+    py: pyo3::Python<'_>,
+    parameter_set: &pyo3::PyAny,
+    data: &[u8],
+) -> CryptographyResult<SLHDSAPublicKey> {
+    let variant = parameter_set_from_py(py, parameter_set)?;
+    let pkey = slhdsa::public_key_from_raw_bytes(variant, data).map_err(|_| {
+        pyo3::exceptions::PyValueError::new_err("Invalid public key for this SLH-DSA parameter set")
+    })?;
+    Ok(SLHDSAPublicKey { pkey })
+}
+
+#[pyo3::prelude::pymethods]
+impl SLHDSAPrivateKey {
+    #[getter]
+    fn parameter_set<'p>(&self, py: pyo3::Python<'p>) -> pyo3::PyResult<&'p pyo3::PyAny> {
+        parameter_set_to_py(py, &self.pkey)
+    }
+
+    #[pyo3(signature = (data, *, context=None))]
+    fn sign<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        data: &[u8],
+        context: Option<&[u8]>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let context = context.unwrap_or_default();
+        check_context(context)?;
+        let signature = slhdsa::sign(&self.pkey, context, data)?;
+        Ok(pyo3::types::PyBytes::new(py, &signature))
+    }
+
+    fn public_key(&self) -> CryptographyResult<SLHDSAPublicKey> {
+        let variant = Variant::from_pkey(&self.pkey).unwrap();
+        let raw_bytes = self.pkey.raw_public_key()?;
+        Ok(SLHDSAPublicKey {
+            pkey: slhdsa::public_key_from_raw_bytes(variant, &raw_bytes)?,
+        })
+    }
+
+    fn private_bytes_raw<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let raw_bytes = self.pkey.raw_private_key()?;
+        Ok(pyo3::types::PyBytes::new(py, &raw_bytes))
+    }
+
+    fn private_bytes<'p>(
+        slf: &pyo3::PyCell<Self>,
+        py: pyo3::Python<'p>,
+        encoding: &pyo3::PyAny,
+        format: &pyo3::PyAny,
+        encryption_algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        utils::pkey_private_bytes(
+            py,
+            slf,
+            &slf.borrow().pkey,
+            encoding,
+            format,
+            encryption_algorithm,
+            false,
+            true,
+        )
+    }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> CryptographyResult<bool> {
+        utils::pkey_private_eq(&self.pkey, &other.pkey)
+    }
+
+    fn __hash__(&self) -> CryptographyResult<u64> {
+        utils::pkey_hash(&self.pkey)
+    }
+
+    fn public_key_matches(
+        slf: &pyo3::PyCell<Self>,
+        py: pyo3::Python<'_>,
+        public_key: &pyo3::PyAny,
+    ) -> CryptographyResult<bool> {
+        utils::public_key_matches(py, slf, public_key)
+    }
+}
+
+#[pyo3::prelude::pymethods]
+impl SLHDSAPublicKey {
+    #[getter]
+    fn parameter_set<'p>(&self, py: pyo3::Python<'p>) -> pyo3::PyResult<&'p pyo3::PyAny> {
+        parameter_set_to_py(py, &self.pkey)
+    }
+
+    #[pyo3(signature = (signature, data, *, context=None))]
+    fn verify(
+        &self,
+        signature: &[u8],
+        data: &[u8],
+        context: Option<&[u8]>,
+    ) -> CryptographyResult<()> {
+        let context = context.unwrap_or_default();
+        check_context(context)?;
+        let valid = slhdsa::verify(&self.pkey, context, signature, data).unwrap_or(false);
+        if !valid {
+            return Err(CryptographyError::from(
+                exceptions::InvalidSignature::new_err(()),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn public_bytes_raw<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let raw_bytes = self.pkey.raw_public_key()?;
+        Ok(pyo3::types::PyBytes::new(py, &raw_bytes))
+    }
+
+    fn public_bytes<'p>(
+        slf: &pyo3::PyCell<Self>,
+        py: pyo3::Python<'p>,
+        encoding: &pyo3::PyAny,
+        format: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        utils::pkey_public_bytes(py, slf, &slf.borrow().pkey, encoding, format, false, true)
+    }
+
+    fn fingerprint<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::PyAny> {
+        utils::pkey_fingerprint(py, &self.pkey, algorithm)
+    }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> bool {
+        self.pkey.public_eq(&other.pkey)
+    }
+
+    fn __copy__(slf: pyo3::PyRef<'_, Self>) -> pyo3::PyRef<'_, Self> {
+        slf
+    }
+}
+
+pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let m = pyo3::prelude::PyModule::new(py, "slhdsa")?;
+    m.add_function(pyo3::wrap_pyfunction!(generate_key, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(from_private_bytes, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(from_public_bytes, m)?)?;
+
+    m.add_class::<SLHDSAPrivateKey>()?;
+    m.add_class::<SLHDSAPublicKey>()?;
+
+    Ok(m)
+}
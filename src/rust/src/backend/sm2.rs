@@ -0,0 +1,297 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+use crate::backend::utils;
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::exceptions;
+
+// The default user ID from GM/T 0009-2012. OpenSSL defaults to an empty ID
+// instead, but most other implementations use this one.
+const DEFAULT_USER_ID: &[u8] = b"1234567812345678";
+
+#[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.sm2")]
+pub(crate) struct SM2PrivateKey {
+    pkey: openssl::pkey::PKey<openssl::pkey::Private>,
+}
+
+#[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.sm2")]
+pub(crate) struct SM2PublicKey {
+    pkey: openssl::pkey::PKey<openssl::pkey::Public>,
+}
+
+pub(crate) fn is_sm2_curve(group: &openssl::ec::EcGroupRef) -> bool {
+    group.curve_name() == Some(openssl::nid::Nid::SM2)
+}
+
+// OpenSSL only uses the SM2 algorithms with keys of its SM2 type, rather than
+// EC keys which happen to be on the SM2 curve. Keys built from an `EcKey` are
+// always the latter, so they're round tripped through PKCS#8, which OpenSSL
+// decodes as the SM2 type based on the curve.
+pub(crate) fn private_key_from_pkey(
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+) -> CryptographyResult<SM2PrivateKey> {
+    let pkey = if cryptography_openssl::sm2::is_sm2(pkey) {
+        pkey.to_owned()
+    } else {
+        openssl::pkey::PKey::private_key_from_pkcs8(&pkey.private_key_to_pkcs8()?)?
+    };
+    Ok(SM2PrivateKey { pkey })
+}
+
+pub(crate) fn public_key_from_pkey(
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Public>,
+) -> CryptographyResult<SM2PublicKey> {
+    let pkey = if cryptography_openssl::sm2::is_sm2(pkey) {
+        pkey.to_owned()
+    } else {
+        openssl::pkey::PKey::public_key_from_der(&pkey.public_key_to_der()?)?
+    };
+    Ok(SM2PublicKey { pkey })
+}
+
+// `PKey::ec_key` doesn't work on keys of OpenSSL's SM2 type, so these get an
+// `EcKey` by way of the key's encodings, which are the same for SM2 and EC.
+pub(crate) fn public_ec_key<T: openssl::pkey::HasPublic>(
+    pkey: &openssl::pkey::PKeyRef<T>,
+) -> CryptographyResult<openssl::ec::EcKey<openssl::pkey::Public>> {
+    Ok(openssl::ec::EcKey::public_key_from_der(
+        &pkey.public_key_to_der()?,
+    )?)
+}
+
+pub(crate) fn private_ec_key(
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+) -> CryptographyResult<openssl::ec::EcKey<openssl::pkey::Private>> {
+    Ok(openssl::ec::EcKey::private_key_from_der(
+        &pkey.private_key_to_der()?,
+    )?)
+}
+
+fn sm2_group() -> CryptographyResult<openssl::ec::EcGroup> {
+    Ok(openssl::ec::EcGroup::from_curve_name(
+        openssl::nid::Nid::SM2,
+    )?)
+}
+
+#[pyo3::prelude::pyfunction]
+fn generate_private_key() -> CryptographyResult<SM2PrivateKey> {
+    let group = sm2_group()?;
+    let ec = openssl::ec::EcKey::generate(&group)?;
+    let pkey = openssl::pkey::PKey::from_ec_key(ec)?;
+    private_key_from_pkey(&pkey)
+}
+
+#[pyo3::prelude::pyfunction]
+fn from_encoded_point(data: &[u8]) -> CryptographyResult<SM2PublicKey> {
+    let group = sm2_group()?;
+    let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+    let point = openssl::ec::EcPoint::from_bytes(&group, data, &mut bn_ctx)
+        .map_err(|_| pyo3::exceptions::PyValueError::new_err("Invalid SM2 public key point"))?;
+    let ec = openssl::ec::EcKey::from_public_key(&group, &point)?;
+    let pkey = openssl::pkey::PKey::from_ec_key(ec)?;
+    public_key_from_pkey(&pkey)
+}
+
+// Z = SM3(ENTL || ID || a || b || xG || yG || xA || yA), as defined in
+// GB/T 32918.2 section 5.5, where ENTL is the bit length of the user ID.
+fn compute_z(
+    ec: &openssl::ec::EcKeyRef<impl openssl::pkey::HasPublic>,
+    user_id: Option<&[u8]>,
+) -> CryptographyResult<Vec<u8>> {
+    let user_id = user_id.unwrap_or(DEFAULT_USER_ID);
+    let entl = u16::try_from(user_id.len() * 8).map_err(|_| {
+        pyo3::exceptions::PyValueError::new_err("user_id must be at most 8191 bytes long")
+    })?;
+
+    let group = ec.group();
+    let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+    let mut p = openssl::bn::BigNum::new()?;
+    let mut a = openssl::bn::BigNum::new()?;
+    let mut b = openssl::bn::BigNum::new()?;
+    group.components_gfp(&mut p, &mut a, &mut b, &mut bn_ctx)?;
+    let size = p.num_bytes();
+
+    let mut h = openssl::hash::Hasher::new(openssl::hash::MessageDigest::sm3())?;
+    h.update(&entl.to_be_bytes())?;
+    h.update(user_id)?;
+    h.update(&a.to_vec_padded(size)?)?;
+    h.update(&b.to_vec_padded(size)?)?;
+    for point in [group.generator(), ec.public_key()] {
+        let mut x = openssl::bn::BigNum::new()?;
+        let mut y = openssl::bn::BigNum::new()?;
+        point.affine_coordinates(group, &mut x, &mut y, &mut bn_ctx)?;
+        h.update(&x.to_vec_padded(size)?)?;
+        h.update(&y.to_vec_padded(size)?)?;
+    }
+    Ok(h.finish()?.to_vec())
+}
+
+// e = SM3(Z || M), which is what the SM2 signature algorithm actually signs.
+fn compute_e(
+    ec: &openssl::ec::EcKeyRef<impl openssl::pkey::HasPublic>,
+    user_id: Option<&[u8]>,
+    data: &[u8],
+) -> CryptographyResult<Vec<u8>> {
+    let mut h = openssl::hash::Hasher::new(openssl::hash::MessageDigest::sm3())?;
+    h.update(&compute_z(ec, user_id)?)?;
+    h.update(data)?;
+    Ok(h.finish()?.to_vec())
+}
+
+#[pyo3::prelude::pymethods]
+impl SM2PrivateKey {
+    #[pyo3(signature = (data, user_id=None))]
+    fn sign<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        data: &[u8],
+        user_id: Option<&[u8]>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let ec = public_ec_key(&self.pkey)?;
+        let e = compute_e(&ec, user_id, data)?;
+        let mut signer = openssl::pkey_ctx::PkeyCtx::new(&self.pkey)?;
+        signer.sign_init()?;
+        let mut sig = vec![];
+        signer.sign_to_vec(&e, &mut sig)?;
+        Ok(pyo3::types::PyBytes::new(py, &sig))
+    }
+
+    fn decrypt<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        ciphertext: &[u8],
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let mut ctx = openssl::pkey_ctx::PkeyCtx::new(&self.pkey)?;
+        ctx.decrypt_init()?;
+        let mut plaintext = vec![];
+        ctx.decrypt_to_vec(ciphertext, &mut plaintext)
+            .map_err(|_| pyo3::exceptions::PyValueError::new_err("Decryption failed"))?;
+        Ok(pyo3::types::PyBytes::new(py, &plaintext))
+    }
+
+    fn public_key(&self) -> CryptographyResult<SM2PublicKey> {
+        let der = self.pkey.public_key_to_der()?;
+        Ok(SM2PublicKey {
+            pkey: openssl::pkey::PKey::public_key_from_der(&der)?,
+        })
+    }
+
+    fn private_bytes<'p>(
+        slf: &pyo3::PyCell<Self>,
+        py: pyo3::Python<'p>,
+        encoding: &pyo3::PyAny,
+        format: &pyo3::PyAny,
+        encryption_algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        utils::pkey_private_bytes(
+            py,
+            slf,
+            &slf.borrow().pkey,
+            encoding,
+            format,
+            encryption_algorithm,
+            false,
+            false,
+        )
+    }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> CryptographyResult<bool> {
+        utils::pkey_private_eq(&self.pkey, &other.pkey)
+    }
+
+    fn __hash__(&self) -> CryptographyResult<u64> {
+        utils::pkey_hash(&self.pkey)
+    }
+
+    fn public_key_matches(
+        slf: &pyo3::PyCell<Self>,
+        py: pyo3::Python<'_>,
+        public_key: &pyo3::PyAny,
+    ) -> CryptographyResult<bool> {
+        utils::public_key_matches(py, slf, public_key)
+    }
+}
+
+#[pyo3::prelude::pymethods]
+impl SM2PublicKey {
+    #[pyo3(signature = (signature, data, user_id=None))]
+    fn verify(
+        &self,
+        signature: &[u8],
+        data: &[u8],
+        user_id: Option<&[u8]>,
+    ) -> CryptographyResult<()> {
+        let ec = public_ec_key(&self.pkey)?;
+        let e = compute_e(&ec, user_id, data)?;
+        let mut verifier = openssl::pkey_ctx::PkeyCtx::new(&self.pkey)?;
+        verifier.verify_init()?;
+        let valid = verifier.verify(&e, signature).unwrap_or(false);
+        if !valid {
+            return Err(CryptographyError::from(
+                exceptions::InvalidSignature::new_err(()),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn encrypt<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        plaintext: &[u8],
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let mut ctx = openssl::pkey_ctx::PkeyCtx::new(&self.pkey)?;
+        ctx.encrypt_init()?;
+        let mut ciphertext = vec![];
+        ctx.encrypt_to_vec(plaintext, &mut ciphertext)?;
+        Ok(pyo3::types::PyBytes::new(py, &ciphertext))
+    }
+
+    #[pyo3(signature = (user_id=None))]
+    fn z_value<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        user_id: Option<&[u8]>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let ec = public_ec_key(&self.pkey)?;
+        let z = compute_z(&ec, user_id)?;
+        Ok(pyo3::types::PyBytes::new(py, &z))
+    }
+
+    fn public_bytes<'p>(
+        slf: &pyo3::PyCell<Self>,
+        py: pyo3::Python<'p>,
+        encoding: &pyo3::PyAny,
+        format: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        utils::pkey_public_bytes(py, slf, &slf.borrow().pkey, encoding, format, false, false)
+    }
+
+    fn fingerprint<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::PyAny> {
+        utils::pkey_fingerprint(py, &self.pkey, algorithm)
+    }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> bool {
+        self.pkey.public_eq(&other.pkey)
+    }
+
+    fn __copy__(slf: pyo3::PyRef<'_, Self>) -> pyo3::PyRef<'_, Self> {
+        slf
+    }
+}
+
+pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let m = pyo3::prelude::PyModule::new(py, "sm2")?;
+    m.add_function(pyo3::wrap_pyfunction!(generate_private_key, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(from_encoded_point, m)?)?;
+
+    m.add_class::<SM2PrivateKey>()?;
+    m.add_class::<SM2PublicKey>()?;
+
+    Ok(m)
+}
@@ -0,0 +1,611 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Parsing and generation of the lines of OpenSSH's authorized_keys and
+//! known_hosts files, as described in the "AUTHORIZED_KEYS FILE FORMAT" and
+//! "SSH_KNOWN_HOSTS FILE FORMAT" sections of sshd(8).
+
+use crate::backend::openssh::Reader;
+use crate::buf::CffiBuf;
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::types;
+
+const CERT_SUFFIX: &[u8] = b"-cert-v01@openssh.com";
+const CERT_AUTHORITY_MARKER: &[u8] = b"@cert-authority";
+const REVOKED_MARKER: &[u8] = b"@revoked";
+// Hashed hostnames are "|1|<base64 salt>|<base64 HMAC-SHA1 of the name>".
+const HASH_PREFIX: &[u8] = b"|1|";
+const HASH_SALT_LENGTH: usize = 20;
+const DEFAULT_PORT: u16 = 22;
+
+fn invalid(msg: &'static str) -> CryptographyError {
+    CryptographyError::from(pyo3::exceptions::PyValueError::new_err(msg))
+}
+
+fn trim(data: &[u8]) -> &[u8] {
+    let start = data
+        .iter()
+        .position(|c| !c.is_ascii_whitespace())
+        .unwrap_or(data.len());
+    let end = data
+        .iter()
+        .rposition(|c| !c.is_ascii_whitespace())
+        .map_or(start, |i| i + 1);
+    &data[start..end]
+}
+
+// The lines of `data` that aren't blank or comments.
+fn lines(data: &[u8]) -> impl Iterator<Item = &[u8]> {
+    data.split(|c| *c == b'\n')
+        .map(trim)
+        .filter(|line| !line.is_empty() && !line.starts_with(b"#"))
+}
+
+// Splits off the first whitespace separated field of `line`. Whitespace
+// between double quotes, in which a quote may be escaped with a backslash,
+// doesn't end the field.
+fn split_field(line: &[u8]) -> CryptographyResult<(&[u8], &[u8])> {
+    let mut in_quotes = false;
+    let mut i = 0;
+    while i < line.len() {
+        match line[i] {
+            b'\\' if in_quotes => i += 1,
+            b'"' => in_quotes = !in_quotes,
+            b' ' | b'\t' if !in_quotes => break,
+            _ => {}
+        }
+        i += 1;
+    }
+    if in_quotes {
+        return Err(invalid("Unterminated quote"));
+    }
+    let (field, rest) = line.split_at(i.min(line.len()));
+    Ok((field, trim(rest)))
+}
+
+struct KeyFields<'a> {
+    key_type: &'a [u8],
+    data: &'a [u8],
+    comment: &'a [u8],
+}
+
+// Parses "<key type> <base64 key> [comment]", returning `None` if `line`
+// doesn't start with a key.
+fn parse_key(line: &[u8]) -> CryptographyResult<Option<KeyFields<'_>>> {
+    let (key_type, rest) = split_field(line)?;
+    let (data, comment) = split_field(rest)?;
+    let blob = match std::str::from_utf8(data)
+        .ok()
+        .and_then(|data| openssl::base64::decode_block(data).ok())
+    {
+        Some(blob) => blob,
+        None => return Ok(None),
+    };
+    if (Reader { data: &blob }).read_string().ok() != Some(key_type) {
+        return Ok(None);
+    }
+    Ok(Some(KeyFields {
+        key_type,
+        data,
+        comment,
+    }))
+}
+
+fn load_public_key(
+    py: pyo3::Python<'_>,
+    fields: &KeyFields<'_>,
+) -> CryptographyResult<pyo3::PyObject> {
+    if fields.key_type.ends_with(CERT_SUFFIX) {
+        return Err(invalid("Expected a public key, not a certificate"));
+    }
+    let data = [fields.key_type, b" ", fields.data].concat();
+    Ok(types::LOAD_SSH_PUBLIC_IDENTITY
+        .get(py)?
+        .call1((pyo3::types::PyBytes::new(py, &data),))?
+        .into())
+}
+
+fn comment_bytes<'p>(
+    py: pyo3::Python<'p>,
+    comment: &Option<Vec<u8>>,
+) -> Option<&'p pyo3::types::PyBytes> {
+    comment
+        .as_ref()
+        .map(|comment| pyo3::types::PyBytes::new(py, comment))
+}
+
+fn check_comment(comment: Option<&[u8]>) -> CryptographyResult<()> {
+    if comment.map_or(false, |c| c.contains(&b'\n') || c.contains(&b'\r')) {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("comment must not contain newlines."),
+        ));
+    }
+    Ok(())
+}
+
+// Appends the serialized key and comment to `line`.
+fn put_key(
+    py: pyo3::Python<'_>,
+    line: &mut Vec<u8>,
+    public_key: &pyo3::PyAny,
+    comment: Option<&[u8]>,
+) -> CryptographyResult<()> {
+    check_comment(comment)?;
+    let key = types::SERIALIZE_SSH_PUBLIC_KEY
+        .get(py)?
+        .call1((public_key,))?
+        .extract::<&[u8]>()?;
+    line.extend_from_slice(key);
+    if let Some(comment) = comment.filter(|c| !c.is_empty()) {
+        line.push(b' ');
+        line.extend_from_slice(comment);
+    }
+    Ok(())
+}
+
+type AuthorizedKeyOption = (Vec<u8>, Option<Vec<u8>>);
+
+// Reads a double quoted value from the start of `data`, in which quotes are
+// escaped with a backslash, returning it and the data following it.
+fn unquote(data: &[u8]) -> CryptographyResult<(Vec<u8>, &[u8])> {
+    let mut rest = data
+        .strip_prefix(b"\"")
+        .ok_or_else(|| invalid("Option values must be quoted"))?;
+    let mut value = vec![];
+    loop {
+        match rest.split_first() {
+            None => return Err(invalid("Unterminated quote")),
+            Some((b'"', tail)) => return Ok((value, tail)),
+            Some((b'\\', [b'"', tail @ ..])) => {
+                value.push(b'"');
+                rest = tail;
+            }
+            Some((c, tail)) => {
+                value.push(*c);
+                rest = tail;
+            }
+        }
+    }
+}
+
+// Parses a comma separated list of `name` or `name="value"` options.
+fn parse_options(field: &[u8]) -> CryptographyResult<Vec<AuthorizedKeyOption>> {
+    let mut options = vec![];
+    let mut rest = field;
+    loop {
+        let name_len = rest
+            .iter()
+            .position(|c| *c == b',' || *c == b'=')
+            .unwrap_or(rest.len());
+        let (name, tail) = rest.split_at(name_len);
+        if name.is_empty() {
+            return Err(invalid("Invalid authorized_keys option"));
+        }
+        rest = tail;
+        let mut value = None;
+        if let Some(tail) = rest.strip_prefix(b"=") {
+            let (v, tail) = unquote(tail)?;
+            value = Some(v);
+            rest = tail;
+        }
+        options.push((name.to_vec(), value));
+
+        match rest.split_first() {
+            None => return Ok(options),
+            Some((b',', tail)) => rest = tail,
+            Some(_) => return Err(invalid("Invalid authorized_keys option")),
+        }
+    }
+}
+
+#[pyo3::prelude::pyclass(
+    frozen,
+    module = "cryptography.hazmat.bindings._rust.openssl.ssh_files",
+    name = "SSHAuthorizedKey"
+)]
+struct SSHAuthorizedKey {
+    options: Vec<AuthorizedKeyOption>,
+    public_key: pyo3::PyObject,
+    comment: Option<Vec<u8>>,
+}
+
+#[pyo3::prelude::pymethods]
+impl SSHAuthorizedKey {
+    #[getter]
+    fn options<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+    ) -> Vec<(&'p pyo3::types::PyBytes, Option<&'p pyo3::types::PyBytes>)> {
+        self.options
+            .iter()
+            .map(|(name, value)| {
+                (
+                    pyo3::types::PyBytes::new(py, name),
+                    value.as_ref().map(|v| pyo3::types::PyBytes::new(py, v)),
+                )
+            })
+            .collect()
+    }
+
+    fn public_key(&self, py: pyo3::Python<'_>) -> pyo3::PyObject {
+        self.public_key.clone_ref(py)
+    }
+
+    #[getter]
+    fn comment<'p>(&self, py: pyo3::Python<'p>) -> Option<&'p pyo3::types::PyBytes> {
+        comment_bytes(py, &self.comment)
+    }
+}
+
+fn parse_authorized_key(py: pyo3::Python<'_>, line: &[u8]) -> CryptographyResult<SSHAuthorizedKey> {
+    // The options are optional, and there's no marker for them, so the
+    // line is only taken to start with options if it doesn't start with a
+    // key.
+    let (options, fields) = match parse_key(line)? {
+        Some(fields) => (vec![], fields),
+        None => {
+            let (options, rest) = split_field(line)?;
+            let fields = parse_key(rest)?.ok_or_else(|| invalid("Invalid authorized_keys line"))?;
+            (parse_options(options)?, fields)
+        }
+    };
+    Ok(SSHAuthorizedKey {
+        options,
+        public_key: load_public_key(py, &fields)?,
+        comment: Some(fields.comment.to_vec()).filter(|c| !c.is_empty()),
+    })
+}
+
+#[pyo3::prelude::pyfunction]
+fn load_ssh_authorized_keys(
+    py: pyo3::Python<'_>,
+    data: CffiBuf<'_>,
+) -> CryptographyResult<Vec<SSHAuthorizedKey>> {
+    lines(data.as_bytes())
+        .map(|line| parse_authorized_key(py, line))
+        .collect()
+}
+
+// (name, value) pairs, where flag options have no value.
+type KeyOptions<'a> = Vec<(&'a [u8], Option<&'a [u8]>)>;
+
+#[pyo3::prelude::pyfunction]
+#[pyo3(signature = (public_key, *, options=None, comment=None))]
+fn serialize_ssh_authorized_key<'p>(
+    py: pyo3::Python<'p>,
+    public_key: &pyo3::PyAny,
+    options: Option<KeyOptions<'_>>,
+    comment: Option<&[u8]>,
+) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+    let mut line = vec![];
+    for (name, value) in options.unwrap_or_default() {
+        if name.is_empty()
+            || name
+                .iter()
+                .any(|c| matches!(c, b',' | b'=' | b'"') || c.is_ascii_whitespace())
+        {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "Invalid option name: {:?}",
+                    String::from_utf8_lossy(name)
+                )),
+            ));
+        }
+        if !line.is_empty() {
+            line.push(b',');
+        }
+        line.extend_from_slice(name);
+        if let Some(value) = value {
+            if value.contains(&b'\n') || value.contains(&b'\r') {
+                return Err(CryptographyError::from(
+                    pyo3::exceptions::PyValueError::new_err(
+                        "Option values must not contain newlines.",
+                    ),
+                ));
+            }
+            line.extend_from_slice(b"=\"");
+            for c in value {
+                if *c == b'"' {
+                    line.push(b'\\');
+                }
+                line.push(*c);
+            }
+            line.push(b'"');
+        }
+    }
+    if !line.is_empty() {
+        line.push(b' ');
+    }
+    put_key(py, &mut line, public_key, comment)?;
+    Ok(pyo3::types::PyBytes::new(py, &line))
+}
+
+enum Hosts {
+    Patterns(Vec<Vec<u8>>),
+    Hashed { salt: Vec<u8>, hash: Vec<u8> },
+}
+
+// The name a host is recorded under, which includes the port if it isn't the
+// default one.
+fn host_entry_name(hostname: &[u8], port: u16) -> Vec<u8> {
+    if port == DEFAULT_PORT {
+        hostname.to_vec()
+    } else {
+        [&b"["[..], hostname, b"]:", port.to_string().as_bytes()].concat()
+    }
+}
+
+fn hash_host_entry(salt: &[u8], name: &[u8]) -> CryptographyResult<Vec<u8>> {
+    let mut h = cryptography_openssl::hmac::Hmac::new(salt, openssl::hash::MessageDigest::sha1())?;
+    h.update(name)?;
+    Ok(h.finish()?.to_vec())
+}
+
+fn parse_hashed_host(field: &[u8]) -> CryptographyResult<Hosts> {
+    let decode = |data: &[u8]| {
+        std::str::from_utf8(data)
+            .ok()
+            .and_then(|data| openssl::base64::decode_block(data).ok())
+            .filter(|data| data.len() == HASH_SALT_LENGTH)
+            .ok_or_else(|| invalid("Invalid hashed hostname"))
+    };
+    let mut parts = field[HASH_PREFIX.len()..].split(|c| *c == b'|');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(salt), Some(hash), None) => Ok(Hosts::Hashed {
+            salt: decode(salt)?,
+            hash: decode(hash)?,
+        }),
+        _ => Err(invalid("Invalid hashed hostname")),
+    }
+}
+
+fn match_pattern(value: &[u8], pattern: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => value.is_empty(),
+        Some((b'*', rest)) => (0..=value.len()).any(|i| match_pattern(&value[i..], rest)),
+        Some((b'?', rest)) => !value.is_empty() && match_pattern(&value[1..], rest),
+        Some((c, rest)) => value.first() == Some(c) && match_pattern(&value[1..], rest),
+    }
+}
+
+// Matches `value` against a list of patterns as OpenSSH does: a negated
+// pattern that matches rejects the value, even if another pattern matches it.
+fn match_pattern_list(value: &[u8], patterns: &[Vec<u8>]) -> bool {
+    let mut matched = false;
+    for pattern in patterns {
+        let pattern = pattern.to_ascii_lowercase();
+        match pattern.strip_prefix(b"!") {
+            Some(negated) => {
+                if match_pattern(value, negated) {
+                    return false;
+                }
+            }
+            None => matched |= match_pattern(value, &pattern),
+        }
+    }
+    matched
+}
+
+#[pyo3::prelude::pyclass(
+    frozen,
+    module = "cryptography.hazmat.bindings._rust.openssl.ssh_files",
+    name = "SSHKnownHost"
+)]
+struct SSHKnownHost {
+    hosts: Hosts,
+    #[pyo3(get)]
+    cert_authority: bool,
+    #[pyo3(get)]
+    revoked: bool,
+    public_key: pyo3::PyObject,
+    comment: Option<Vec<u8>>,
+}
+
+#[pyo3::prelude::pymethods]
+impl SSHKnownHost {
+    #[getter]
+    fn hosts<'p>(&self, py: pyo3::Python<'p>) -> Option<Vec<&'p pyo3::types::PyBytes>> {
+        match &self.hosts {
+            Hosts::Patterns(patterns) => Some(
+                patterns
+                    .iter()
+                    .map(|p| pyo3::types::PyBytes::new(py, p))
+                    .collect(),
+            ),
+            Hosts::Hashed { .. } => None,
+        }
+    }
+
+    fn public_key(&self, py: pyo3::Python<'_>) -> pyo3::PyObject {
+        self.public_key.clone_ref(py)
+    }
+
+    #[getter]
+    fn comment<'p>(&self, py: pyo3::Python<'p>) -> Option<&'p pyo3::types::PyBytes> {
+        comment_bytes(py, &self.comment)
+    }
+
+    #[pyo3(signature = (hostname, port=DEFAULT_PORT))]
+    fn matches(&self, hostname: &[u8], port: u16) -> CryptographyResult<bool> {
+        let name = host_entry_name(&hostname.to_ascii_lowercase(), port);
+        match &self.hosts {
+            Hosts::Patterns(patterns) => Ok(match_pattern_list(&name, patterns)),
+            Hosts::Hashed { salt, hash } => {
+                Ok(openssl::memcmp::eq(&hash_host_entry(salt, &name)?, hash))
+            }
+        }
+    }
+}
+
+fn parse_known_host(py: pyo3::Python<'_>, line: &[u8]) -> CryptographyResult<SSHKnownHost> {
+    let (mut field, mut rest) = split_field(line)?;
+    let mut cert_authority = false;
+    let mut revoked = false;
+    if field.starts_with(b"@") {
+        match field {
+            CERT_AUTHORITY_MARKER => cert_authority = true,
+            REVOKED_MARKER => revoked = true,
+            _ => return Err(invalid("Unknown known_hosts marker")),
+        }
+        (field, rest) = split_field(rest)?;
+    }
+
+    let hosts = if field.starts_with(HASH_PREFIX) {
+        parse_hashed_host(field)?
+    } else {
+        Hosts::Patterns(field.split(|c| *c == b',').map(<[u8]>::to_vec).collect())
+    };
+    let fields = parse_key(rest)?.ok_or_else(|| invalid("Invalid known_hosts line"))?;
+    Ok(SSHKnownHost {
+        hosts,
+        cert_authority,
+        revoked,
+        public_key: load_public_key(py, &fields)?,
+        comment: Some(fields.comment.to_vec()).filter(|c| !c.is_empty()),
+    })
+}
+
+#[pyo3::prelude::pyfunction]
+fn load_ssh_known_hosts(
+    py: pyo3::Python<'_>,
+    data: CffiBuf<'_>,
+) -> CryptographyResult<Vec<SSHKnownHost>> {
+    lines(data.as_bytes())
+        .map(|line| parse_known_host(py, line))
+        .collect()
+}
+
+#[pyo3::prelude::pyfunction]
+#[pyo3(signature = (
+    public_key,
+    hostnames,
+    *,
+    port=DEFAULT_PORT,
+    hash_hostname=false,
+    cert_authority=false,
+    revoked=false,
+    comment=None,
+))]
+#[allow(clippy::too_many_arguments)]
+fn serialize_ssh_known_host<'p>(
+    py: pyo3::Python<'p>,
+    public_key: &pyo3::PyAny,
+    hostnames: Vec<&[u8]>,
+    port: u16,
+    hash_hostname: bool,
+    cert_authority: bool,
+    revoked: bool,
+    comment: Option<&[u8]>,
+) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+    if hostnames.is_empty() {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("At least one hostname is required."),
+        ));
+    }
+    if hostnames.iter().any(|h| {
+        h.is_empty()
+            || h.starts_with(b"@")
+            || h.starts_with(b"|")
+            || h.iter().any(|c| *c == b',' || c.is_ascii_whitespace())
+    }) {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("Invalid hostname."),
+        ));
+    }
+    if cert_authority && revoked {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(
+                "A key can't be both a certificate authority and revoked.",
+            ),
+        ));
+    }
+
+    let mut line = vec![];
+    if cert_authority {
+        line.extend_from_slice(CERT_AUTHORITY_MARKER);
+        line.push(b' ');
+    } else if revoked {
+        line.extend_from_slice(REVOKED_MARKER);
+        line.push(b' ');
+    }
+    if hash_hostname {
+        // A hashed entry can only ever match the single name it was made
+        // from.
+        if hostnames.len() != 1 {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("Only a single hostname can be hashed."),
+            ));
+        }
+        let mut salt = [0; HASH_SALT_LENGTH];
+        openssl::rand::rand_bytes(&mut salt)?;
+        let hash = hash_host_entry(&salt, &host_entry_name(hostnames[0], port))?;
+        line.extend_from_slice(HASH_PREFIX);
+        line.extend_from_slice(openssl::base64::encode_block(&salt).as_bytes());
+        line.push(b'|');
+        line.extend_from_slice(openssl::base64::encode_block(&hash).as_bytes());
+    } else {
+        let names = hostnames
+            .iter()
+            .map(|h| host_entry_name(h, port))
+            .collect::<Vec<_>>();
+        line.extend_from_slice(&names.join(&b","[..]));
+    }
+    line.push(b' ');
+    put_key(py, &mut line, public_key, comment)?;
+    Ok(pyo3::types::PyBytes::new(py, &line))
+}
+
+pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let m = pyo3::prelude::PyModule::new(py, "ssh_files")?;
+    m.add_function(pyo3::wrap_pyfunction!(load_ssh_authorized_keys, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(serialize_ssh_authorized_key, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(load_ssh_known_hosts, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(serialize_ssh_known_host, m)?)?;
+
+    m.add_class::<SSHAuthorizedKey>()?;
+    m.add_class::<SSHKnownHost>()?;
+
+    Ok(m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{match_pattern_list, parse_options, split_field};
+
+    #[test]
+    fn test_split_field() {
+        assert_eq!(split_field(b"a b").ok(), Some((&b"a"[..], &b"b"[..])));
+        assert_eq!(split_field(b"a").ok(), Some((&b"a"[..], &b""[..])));
+        assert_eq!(
+            split_field(b"a=\"b \\\" c\" d\t e").ok(),
+            Some((&b"a=\"b \\\" c\""[..], &b"d\t e"[..]))
+        );
+        assert!(split_field(b"a=\"b c").is_err());
+    }
+
+    #[test]
+    fn test_parse_options() {
+        assert_eq!(
+            parse_options(b"no-pty,command=\"echo \\\"a,b\\\"\",from=\"*\"").ok(),
+            Some(vec![
+                (b"no-pty".to_vec(), None),
+                (b"command".to_vec(), Some(b"echo \"a,b\"".to_vec())),
+                (b"from".to_vec(), Some(b"*".to_vec())),
+            ])
+        );
+        assert!(parse_options(b"").is_err());
+        assert!(parse_options(b"a,").is_err());
+        assert!(parse_options(b"a=b").is_err());
+        assert!(parse_options(b"a=\"b\"c").is_err());
+    }
+
+    #[test]
+    fn test_match_pattern_list() {
+        let patterns = [b"*.example.com".to_vec(), b"!bad.example.com".to_vec()];
+        assert!(match_pattern_list(b"good.example.com", &patterns));
+        assert!(!match_pattern_list(b"bad.example.com", &patterns));
+        assert!(!match_pattern_list(b"example.org", &patterns));
+        assert!(match_pattern_list(b"[h]:2222", &[b"[H]:22??".to_vec()]));
+    }
+}
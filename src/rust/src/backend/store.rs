@@ -0,0 +1,360 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Private keys loaded by URI through OpenSSL 3's store API. These are
+//! usually held by a provider, such as an HSM accessed with pkcs11-provider,
+//! so their private components can't be read. They're still usable for the
+//! operations the provider implements: signing, decryption, and key exchange.
+
+use crate::backend::{ec, kdf, rsa, utils};
+use crate::buf::CffiBuf;
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::exceptions;
+
+#[pyo3::prelude::pyclass(
+    frozen,
+    module = "cryptography.hazmat.bindings._rust.openssl.store",
+    name = "OpaqueRSAPrivateKey"
+)]
+struct OpaqueRsaPrivateKey {
+    pkey: openssl::pkey::PKey<openssl::pkey::Private>,
+    public_pkey: openssl::pkey::PKey<openssl::pkey::Public>,
+}
+
+#[pyo3::prelude::pyclass(
+    frozen,
+    module = "cryptography.hazmat.bindings._rust.openssl.store",
+    name = "OpaqueEllipticCurvePrivateKey"
+)]
+struct OpaqueEcPrivateKey {
+    pkey: openssl::pkey::PKey<openssl::pkey::Private>,
+    public_pkey: openssl::pkey::PKey<openssl::pkey::Public>,
+    #[pyo3(get)]
+    curve: pyo3::Py<pyo3::PyAny>,
+}
+
+fn not_exportable() -> CryptographyError {
+    CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+        "Private key material can't be exported from this key",
+    ))
+}
+
+#[cfg(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER)]
+fn load_uri(
+    py: pyo3::Python<'_>,
+    uri: &str,
+    pin: Option<&[u8]>,
+) -> CryptographyResult<openssl::pkey::PKey<openssl::pkey::Private>> {
+    let uri = std::ffi::CString::new(uri)
+        .map_err(|_| pyo3::exceptions::PyValueError::new_err("URI must not contain NUL bytes"))?;
+    // Providers may need to talk to a token, so other threads are allowed to
+    // run while the key is loaded.
+    let pkey = py
+        .allow_threads(|| cryptography_openssl::store::load_private_key(&uri, pin))
+        .map_err(|_| {
+            pyo3::exceptions::PyValueError::new_err(
+                "Could not load a private key from the URI. The URI may be invalid, the PIN incorrect, or no provider may handle it.",
+            )
+        })?;
+    pkey.ok_or_else(|| {
+        CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+            "No private key was found at the URI",
+        ))
+    })
+}
+
+#[cfg(not(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER))]
+fn load_uri(
+    _py: pyo3::Python<'_>,
+    _uri: &str,
+    _pin: Option<&[u8]>,
+) -> CryptographyResult<openssl::pkey::PKey<openssl::pkey::Private>> {
+    Err(CryptographyError::from(
+        exceptions::UnsupportedAlgorithm::new_err((
+            "Loading keys by URI requires OpenSSL 3.0.0 or newer.",
+            exceptions::Reasons::BACKEND_MISSING_INTERFACE,
+        )),
+    ))
+}
+
+#[pyo3::prelude::pyfunction]
+#[pyo3(signature = (uri, *, pin=None))]
+fn load_private_key_from_uri(
+    py: pyo3::Python<'_>,
+    uri: &str,
+    pin: Option<CffiBuf<'_>>,
+) -> CryptographyResult<pyo3::PyObject> {
+    let pkey = load_uri(py, uri, pin.as_ref().map(|p| p.as_bytes()))?;
+    // The public half is copied into an ordinary key, both so that it can
+    // be used without the provider and so that its type and parameters can
+    // be inspected.
+    let public_pkey = openssl::pkey::PKey::public_key_from_der(&pkey.public_key_to_der()?)?;
+
+    match public_pkey.id() {
+        openssl::pkey::Id::RSA => Ok(pyo3::IntoPy::into_py(
+            OpaqueRsaPrivateKey { pkey, public_pkey },
+            py,
+        )),
+        openssl::pkey::Id::EC => {
+            let curve = ec::py_curve_from_curve(py, public_pkey.ec_key()?.group())?;
+            Ok(pyo3::IntoPy::into_py(
+                OpaqueEcPrivateKey {
+                    pkey,
+                    public_pkey,
+                    curve: curve.into(),
+                },
+                py,
+            ))
+        }
+        _ => Err(CryptographyError::from(
+            exceptions::UnsupportedAlgorithm::new_err((
+                "Unsupported key type for loading by URI.",
+                exceptions::Reasons::UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
+            )),
+        )),
+    }
+}
+
+#[pyo3::prelude::pymethods]
+impl OpaqueRsaPrivateKey {
+    fn sign<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        data: &[u8],
+        padding: &pyo3::PyAny,
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::PyAny> {
+        rsa::sign_with_pkey(py, &self.pkey, data, padding, algorithm)
+    }
+
+    fn signer(
+        &self,
+        py: pyo3::Python<'_>,
+        padding: &pyo3::PyAny,
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<rsa::RsaSigner> {
+        rsa::signer_with_pkey(py, &self.pkey, padding, algorithm)
+    }
+
+    fn decrypt<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        ciphertext: &[u8],
+        padding: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        rsa::decrypt_with_pkey(py, &self.pkey, ciphertext, padding)
+    }
+
+    fn unsafe_raw_decrypt<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        ciphertext: &[u8],
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let rsa = self.public_pkey.rsa()?;
+        rsa::raw_decrypt_with_pkey(py, &self.pkey, rsa.n(), ciphertext)
+    }
+
+    #[getter]
+    fn key_size(&self) -> u32 {
+        self.public_pkey.bits()
+    }
+
+    fn public_key(&self) -> rsa::RsaPublicKey {
+        rsa::public_key_from_pkey(&self.public_pkey)
+    }
+
+    fn private_numbers(&self) -> CryptographyResult<()> {
+        Err(not_exportable())
+    }
+
+    fn check(&self) -> CryptographyResult<()> {
+        Err(not_exportable())
+    }
+
+    fn private_bytes(
+        &self,
+        _encoding: &pyo3::PyAny,
+        _format: &pyo3::PyAny,
+        _encryption_algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<()> {
+        Err(not_exportable())
+    }
+
+    #[pyo3(signature = (kid=None))]
+    fn jwk_private_bytes(&self, kid: Option<&str>) -> CryptographyResult<()> {
+        let _ = kid;
+        Err(not_exportable())
+    }
+
+    fn __copy__(slf: pyo3::PyRef<'_, Self>) -> pyo3::PyRef<'_, Self> {
+        slf
+    }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> bool {
+        // The private key material isn't accessible, so keys are equal if
+        // they have the same public key.
+        self.public_pkey.public_eq(&other.public_pkey)
+    }
+
+    fn __hash__(&self) -> CryptographyResult<u64> {
+        utils::pkey_hash(&self.public_pkey)
+    }
+
+    fn public_key_matches(
+        slf: &pyo3::PyCell<Self>,
+        py: pyo3::Python<'_>,
+        public_key: &pyo3::PyAny,
+    ) -> CryptographyResult<bool> {
+        utils::public_key_matches(py, slf, public_key)
+    }
+}
+
+impl OpaqueEcPrivateKey {
+    fn compute_shared_key(
+        &self,
+        py: pyo3::Python<'_>,
+        algorithm: &pyo3::PyAny,
+        public_key: &ec::ECPublicKey,
+    ) -> CryptographyResult<Vec<u8>> {
+        let (cofactor, full_point) = ec::ecdh_options(py, algorithm)?;
+        // These are computed by hand from the private scalar.
+        if cofactor || full_point {
+            return Err(CryptographyError::from(
+                exceptions::UnsupportedAlgorithm::new_err((
+                    "Cofactor and full point ECDH are not supported with keys loaded by URI.",
+                    exceptions::Reasons::UNSUPPORTED_EXCHANGE_ALGORITHM,
+                )),
+            ));
+        }
+        py.allow_threads(|| ec::ecdh_exchange(&self.pkey, &public_key.pkey, false, false))
+    }
+}
+
+#[pyo3::prelude::pymethods]
+impl OpaqueEcPrivateKey {
+    #[getter]
+    fn key_size<'p>(&'p self, py: pyo3::Python<'p>) -> pyo3::PyResult<&'p pyo3::PyAny> {
+        self.curve.as_ref(py).getattr(pyo3::intern!(py, "key_size"))
+    }
+
+    fn exchange<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        algorithm: &pyo3::PyAny,
+        public_key: &ec::ECPublicKey,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let shared_key = self.compute_shared_key(py, algorithm, public_key)?;
+        Ok(pyo3::types::PyBytes::new(py, &shared_key))
+    }
+
+    fn exchange_and_derive<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        algorithm: &pyo3::PyAny,
+        public_key: &ec::ECPublicKey,
+        kdf: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let shared_key = self.compute_shared_key(py, algorithm, public_key)?;
+        kdf::derive_with_kdf(py, kdf, &shared_key)
+    }
+
+    fn exchange_many<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        algorithm: &pyo3::PyAny,
+        peer_public_keys: Vec<pyo3::PyRef<'_, ec::ECPublicKey>>,
+    ) -> CryptographyResult<Vec<&'p pyo3::types::PyBytes>> {
+        peer_public_keys
+            .iter()
+            .map(|key| {
+                let shared_key = self.compute_shared_key(py, algorithm, key)?;
+                Ok(pyo3::types::PyBytes::new(py, &shared_key))
+            })
+            .collect()
+    }
+
+    fn sign<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        data: &[u8],
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        ec::check_ecdsa_algorithm(py, algorithm)?;
+
+        let (data, hash_algorithm) = utils::calculate_digest_and_algorithm(
+            py,
+            data,
+            algorithm.getattr(pyo3::intern!(py, "algorithm"))?,
+        )?;
+        let sig = ec::ecdsa_sign_digest(
+            py,
+            &self.pkey,
+            self.public_pkey.ec_key()?.group(),
+            None,
+            data,
+            hash_algorithm,
+            algorithm,
+        )?;
+        Ok(pyo3::types::PyBytes::new(py, &sig))
+    }
+
+    fn public_key(&self, py: pyo3::Python<'_>) -> CryptographyResult<ec::ECPublicKey> {
+        ec::public_key_from_pkey(py, &self.public_pkey)
+    }
+
+    fn private_numbers(&self) -> CryptographyResult<()> {
+        Err(not_exportable())
+    }
+
+    fn private_bytes_raw(&self) -> CryptographyResult<()> {
+        Err(not_exportable())
+    }
+
+    fn private_bytes(
+        &self,
+        _encoding: &pyo3::PyAny,
+        _format: &pyo3::PyAny,
+        _encryption_algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<()> {
+        Err(not_exportable())
+    }
+
+    #[pyo3(signature = (kid=None))]
+    fn jwk_private_bytes(&self, kid: Option<&str>) -> CryptographyResult<()> {
+        let _ = kid;
+        Err(not_exportable())
+    }
+
+    fn __copy__(slf: pyo3::PyRef<'_, Self>) -> pyo3::PyRef<'_, Self> {
+        slf
+    }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> bool {
+        // The private key material isn't accessible, so keys are equal if
+        // they have the same public key.
+        self.public_pkey.public_eq(&other.public_pkey)
+    }
+
+    fn __hash__(&self) -> CryptographyResult<u64> {
+        utils::pkey_hash(&self.public_pkey)
+    }
+
+    fn public_key_matches(
+        slf: &pyo3::PyCell<Self>,
+        py: pyo3::Python<'_>,
+        public_key: &pyo3::PyAny,
+    ) -> CryptographyResult<bool> {
+        utils::public_key_matches(py, slf, public_key)
+    }
+}
+
+pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let m = pyo3::prelude::PyModule::new(py, "store")?;
+    m.add_function(pyo3::wrap_pyfunction!(load_private_key_from_uri, m)?)?;
+
+    m.add_class::<OpaqueRsaPrivateKey>()?;
+    m.add_class::<OpaqueEcPrivateKey>()?;
+
+    Ok(m)
+}
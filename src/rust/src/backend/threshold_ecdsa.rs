@@ -0,0 +1,1550 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Two-party (2-of-2) ECDSA, following Lindell's "Fast Secure Two-Party ECDSA
+//! Signing" (CRYPTO 2017). Party 1 holds a Paillier key pair and an encryption
+//! of its key share is given to party 2, which lets party 2 compute an
+//! encrypted partial signature that only party 1 can complete.
+//!
+//! During key generation party 1 proves, non-interactively, that its Paillier
+//! modulus is well formed and that the encrypted share is small and matches
+//! its public share. Without these proofs a malicious party 1 could learn
+//! party 2's share from the partial signatures.
+
+use crate::backend::{ec, hashes, utils};
+use crate::buf::CffiBuf;
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::{exceptions, types};
+
+const DOMAIN: &[u8] = b"cryptography 2P-ECDSA v1";
+
+const MSG_KEYGEN_1: u8 = 1;
+const MSG_KEYGEN_2: u8 = 2;
+const MSG_SIGN_COMMITMENT: u8 = 3;
+const MSG_SIGN_2: u8 = 4;
+const MSG_SIGN_DECOMMITMENT: u8 = 5;
+const MSG_SIGN_PARTIAL: u8 = 6;
+const SHARE_PARTY_1: u8 = 0x81;
+const SHARE_PARTY_2: u8 = 0x82;
+
+const MIN_PAILLIER_PRIME_BITS: i32 = 1024;
+const COMMITMENT_NONCE_LENGTH: usize = 32;
+
+// A cheating party 1 passes each round of the range proof with probability
+// 1/2.
+const RANGE_PROOF_ROUNDS: usize = 128;
+// The length of the encrypted share proof's challenge, and how much longer
+// than the challenge times the share its randomness is, so the response
+// statistically hides the share.
+const CHALLENGE_BITS: i32 = 128;
+const STATISTICAL_SECURITY_BITS: i32 = 128;
+// The Paillier modulus must have no prime factor below this bound, which
+// limits the probability of each N-th root in the proof that it's well formed
+// existing for a bad modulus to 2^-16.
+const PAILLIER_PROOF_PRIME_BOUND: usize = 1 << 16;
+const PAILLIER_PROOF_ROUNDS: u32 = 8;
+
+fn invalid_message() -> CryptographyError {
+    CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+        "Invalid two-party ECDSA message",
+    ))
+}
+
+fn invalid_proof() -> CryptographyError {
+    CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+        "Invalid proof in two-party ECDSA key generation message",
+    ))
+}
+
+// Messages and key shares are encoded as a type byte followed by
+// length-prefixed fields.
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn new(msg_type: u8) -> Writer {
+        Writer(vec![msg_type])
+    }
+
+    fn field(mut self, data: &[u8]) -> Writer {
+        self.0
+            .extend_from_slice(&u32::try_from(data.len()).unwrap().to_be_bytes());
+        self.0.extend_from_slice(data);
+        self
+    }
+
+    fn finish<'p>(self, py: pyo3::Python<'p>) -> &'p pyo3::types::PyBytes {
+        pyo3::types::PyBytes::new(py, &self.0)
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8], msg_type: u8) -> CryptographyResult<Reader<'a>> {
+        match data.split_first() {
+            Some((&t, rest)) if t == msg_type => Ok(Reader(rest)),
+            _ => Err(invalid_message()),
+        }
+    }
+
+    fn field(&mut self) -> CryptographyResult<&'a [u8]> {
+        if self.0.len() < 4 {
+            return Err(invalid_message());
+        }
+        let (len, rest) = self.0.split_at(4);
+        let len = u32::from_be_bytes(len.try_into().unwrap()) as usize;
+        if rest.len() < len {
+            return Err(invalid_message());
+        }
+        let (field, rest) = rest.split_at(len);
+        self.0 = rest;
+        Ok(field)
+    }
+
+    fn bn(&mut self) -> CryptographyResult<openssl::bn::BigNum> {
+        Ok(openssl::bn::BigNum::from_slice(self.field()?)?)
+    }
+
+    fn finish(self) -> CryptographyResult<()> {
+        if !self.0.is_empty() {
+            return Err(invalid_message());
+        }
+        Ok(())
+    }
+}
+
+// Fiat-Shamir challenges hash the domain separator, a label and the
+// statement, each value prefixed with its length.
+struct Transcript(openssl::sha::Sha256);
+
+impl Transcript {
+    fn new(label: &[u8]) -> Transcript {
+        Transcript(openssl::sha::Sha256::new())
+            .append(DOMAIN)
+            .append(label)
+    }
+
+    fn append(mut self, data: &[u8]) -> Transcript {
+        self.0
+            .update(&u32::try_from(data.len()).unwrap().to_be_bytes());
+        self.0.update(data);
+        self
+    }
+
+    fn finish(self) -> [u8; 32] {
+        self.0.finish()
+    }
+}
+
+struct Curve {
+    group: openssl::ec::EcGroup,
+    order: openssl::bn::BigNum,
+}
+
+impl Curve {
+    fn from_group(group: openssl::ec::EcGroup) -> CryptographyResult<Curve> {
+        let mut order = openssl::bn::BigNum::new()?;
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        group.order(&mut order, &mut bn_ctx)?;
+        Ok(Curve { group, order })
+    }
+
+    fn from_py(py: pyo3::Python<'_>, py_curve: &pyo3::PyAny) -> CryptographyResult<Curve> {
+        Curve::from_group(ec::curve_from_py_curve(py, py_curve, false)?)
+    }
+
+    fn from_nid(data: &[u8]) -> CryptographyResult<Curve> {
+        let nid = i32::from_be_bytes(data.try_into().map_err(|_| invalid_message())?);
+        let group = openssl::ec::EcGroup::from_curve_name(openssl::nid::Nid::from_raw(nid))
+            .map_err(|_| invalid_message())?;
+        Curve::from_group(group)
+    }
+
+    fn nid_bytes(&self) -> [u8; 4] {
+        self.group.curve_name().unwrap().as_raw().to_be_bytes()
+    }
+
+    fn scalar_length(&self) -> usize {
+        (self.order.num_bits() as usize + 7) / 8
+    }
+
+    fn random_scalar(&self) -> CryptographyResult<openssl::bn::BigNum> {
+        // Uniform in [1, q).
+        let mut upper = openssl::bn::BigNum::new()?;
+        upper.checked_sub(&self.order, openssl::bn::BigNum::from_u32(1)?.as_ref())?;
+        let mut k = openssl::bn::BigNum::new()?;
+        upper.rand_range(&mut k)?;
+        k.add_word(1)?;
+        Ok(k)
+    }
+
+    fn mul_generator(
+        &self,
+        k: &openssl::bn::BigNumRef,
+    ) -> CryptographyResult<openssl::ec::EcPoint> {
+        let bn_ctx = openssl::bn::BigNumContext::new()?;
+        let mut point = openssl::ec::EcPoint::new(&self.group)?;
+        point.mul_generator(&self.group, k, &bn_ctx)?;
+        Ok(point)
+    }
+
+    fn mul(
+        &self,
+        point: &openssl::ec::EcPointRef,
+        k: &openssl::bn::BigNumRef,
+    ) -> CryptographyResult<openssl::ec::EcPoint> {
+        let bn_ctx = openssl::bn::BigNumContext::new()?;
+        let mut result = openssl::ec::EcPoint::new(&self.group)?;
+        result.mul(&self.group, point, k, &bn_ctx)?;
+        Ok(result)
+    }
+
+    fn point_bytes(&self, point: &openssl::ec::EcPointRef) -> CryptographyResult<Vec<u8>> {
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        Ok(point.to_bytes(
+            &self.group,
+            openssl::ec::PointConversionForm::COMPRESSED,
+            &mut bn_ctx,
+        )?)
+    }
+
+    fn point_from_bytes(&self, data: &[u8]) -> CryptographyResult<openssl::ec::EcPoint> {
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let point = openssl::ec::EcPoint::from_bytes(&self.group, data, &mut bn_ctx)
+            .map_err(|_| invalid_message())?;
+        if point.is_infinity(&self.group) {
+            return Err(invalid_message());
+        }
+        Ok(point)
+    }
+
+    fn scalar_from_bytes(&self, data: &[u8]) -> CryptographyResult<openssl::bn::BigNum> {
+        let k = openssl::bn::BigNum::from_slice(data)?;
+        if k.num_bits() == 0 || k >= self.order {
+            return Err(invalid_message());
+        }
+        Ok(k)
+    }
+
+    // The x coordinate of `point`, reduced modulo the group order.
+    fn x_mod_order(
+        &self,
+        point: &openssl::ec::EcPointRef,
+    ) -> CryptographyResult<openssl::bn::BigNum> {
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let mut x = openssl::bn::BigNum::new()?;
+        let mut y = openssl::bn::BigNum::new()?;
+        point.affine_coordinates(&self.group, &mut x, &mut y, &mut bn_ctx)?;
+        let mut r = openssl::bn::BigNum::new()?;
+        r.nnmod(&x, &self.order, &mut bn_ctx)?;
+        Ok(r)
+    }
+
+    // Converts a digest to an integer the same way ECDSA does, by keeping its
+    // leftmost bits.
+    fn digest_to_scalar(&self, digest: &[u8]) -> CryptographyResult<openssl::bn::BigNum> {
+        let mut m = openssl::bn::BigNum::from_slice(digest)?;
+        let digest_bits = digest.len() as i32 * 8;
+        if digest_bits > self.order.num_bits() {
+            let mut shifted = openssl::bn::BigNum::new()?;
+            shifted.rshift(&m, digest_bits - self.order.num_bits())?;
+            m = shifted;
+        }
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let mut result = openssl::bn::BigNum::new()?;
+        result.nnmod(&m, &self.order, &mut bn_ctx)?;
+        Ok(result)
+    }
+
+    fn ec_key(
+        &self,
+        point: &openssl::ec::EcPointRef,
+    ) -> CryptographyResult<openssl::ec::EcKey<openssl::pkey::Public>> {
+        Ok(openssl::ec::EcKey::from_public_key(&self.group, point)?)
+    }
+}
+
+// A non-interactive Schnorr proof of knowledge of the discrete log of a
+// point, made non-interactive with the Fiat-Shamir transform.
+struct DlogProof {
+    commitment: openssl::ec::EcPoint,
+    response: openssl::bn::BigNum,
+}
+
+fn dlog_challenge(
+    curve: &Curve,
+    label: &[u8],
+    public: &openssl::ec::EcPointRef,
+    commitment: &openssl::ec::EcPointRef,
+) -> CryptographyResult<openssl::bn::BigNum> {
+    let h = Transcript::new(label)
+        .append(&curve.nid_bytes())
+        .append(&curve.point_bytes(public)?)
+        .append(&curve.point_bytes(commitment)?);
+    let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+    let digest = openssl::bn::BigNum::from_slice(&h.finish())?;
+    let mut e = openssl::bn::BigNum::new()?;
+    e.nnmod(&digest, &curve.order, &mut bn_ctx)?;
+    Ok(e)
+}
+
+impl DlogProof {
+    fn prove(
+        curve: &Curve,
+        label: &[u8],
+        secret: &openssl::bn::BigNumRef,
+        public: &openssl::ec::EcPointRef,
+    ) -> CryptographyResult<DlogProof> {
+        let k = curve.random_scalar()?;
+        let commitment = curve.mul_generator(&k)?;
+        let e = dlog_challenge(curve, label, public, &commitment)?;
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let mut ex = openssl::bn::BigNum::new()?;
+        ex.mod_mul(&e, secret, &curve.order, &mut bn_ctx)?;
+        let mut response = openssl::bn::BigNum::new()?;
+        response.mod_add(&k, &ex, &curve.order, &mut bn_ctx)?;
+        Ok(DlogProof {
+            commitment,
+            response,
+        })
+    }
+
+    fn verify(
+        &self,
+        curve: &Curve,
+        label: &[u8],
+        public: &openssl::ec::EcPointRef,
+    ) -> CryptographyResult<()> {
+        let e = dlog_challenge(curve, label, public, &self.commitment)?;
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        // z*G == T + e*X
+        let lhs = curve.mul_generator(&self.response)?;
+        let ex = curve.mul(public, &e)?;
+        let mut rhs = openssl::ec::EcPoint::new(&curve.group)?;
+        rhs.add(&curve.group, &self.commitment, &ex, &mut bn_ctx)?;
+        if !lhs.eq(&curve.group, &rhs, &mut bn_ctx)? {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "Invalid proof of knowledge in two-party ECDSA message",
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    fn write(&self, curve: &Curve, w: Writer) -> CryptographyResult<Writer> {
+        Ok(w.field(&curve.point_bytes(&self.commitment)?)
+            .field(&self.response.to_vec_padded(curve.scalar_length() as i32)?))
+    }
+
+    fn read(curve: &Curve, r: &mut Reader<'_>) -> CryptographyResult<DlogProof> {
+        let commitment = curve.point_from_bytes(r.field()?)?;
+        let response = openssl::bn::BigNum::from_slice(r.field()?)?;
+        if response >= curve.order {
+            return Err(invalid_message());
+        }
+        Ok(DlogProof {
+            commitment,
+            response,
+        })
+    }
+}
+
+struct PaillierPublicKey {
+    n: openssl::bn::BigNum,
+    n_squared: openssl::bn::BigNum,
+}
+
+impl PaillierPublicKey {
+    fn new(n: openssl::bn::BigNum) -> CryptographyResult<PaillierPublicKey> {
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let mut n_squared = openssl::bn::BigNum::new()?;
+        n_squared.sqr(&n, &mut bn_ctx)?;
+        Ok(PaillierPublicKey { n, n_squared })
+    }
+
+    // A uniformly random element of Z_N^*.
+    fn random_unit(&self) -> CryptographyResult<openssl::bn::BigNum> {
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let mut r = openssl::bn::BigNum::new()?;
+        let mut gcd = openssl::bn::BigNum::new()?;
+        loop {
+            self.n.rand_range(&mut r)?;
+            gcd.gcd(&r, &self.n, &mut bn_ctx)?;
+            if r.num_bits() != 0 && gcd == *openssl::bn::BigNum::from_u32(1)? {
+                return Ok(r);
+            }
+        }
+    }
+
+    fn encrypt(&self, m: &openssl::bn::BigNumRef) -> CryptographyResult<openssl::bn::BigNum> {
+        let r = self.random_unit()?;
+        self.encrypt_with(m, &r)
+    }
+
+    // Enc(m; r) = (1 + m*N) * r^N mod N^2
+    fn encrypt_with(
+        &self,
+        m: &openssl::bn::BigNumRef,
+        r: &openssl::bn::BigNumRef,
+    ) -> CryptographyResult<openssl::bn::BigNum> {
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let mut rn = openssl::bn::BigNum::new()?;
+        rn.mod_exp(r, &self.n, &self.n_squared, &mut bn_ctx)?;
+        let mut mn = openssl::bn::BigNum::new()?;
+        mn.checked_mul(m, &self.n, &mut bn_ctx)?;
+        mn.add_word(1)?;
+        let mut c = openssl::bn::BigNum::new()?;
+        c.mod_mul(&mn, &rn, &self.n_squared, &mut bn_ctx)?;
+        Ok(c)
+    }
+
+    // Enc(m1 + m2) = Enc(m1) * Enc(m2) mod N^2
+    fn add(
+        &self,
+        c1: &openssl::bn::BigNumRef,
+        c2: &openssl::bn::BigNumRef,
+    ) -> CryptographyResult<openssl::bn::BigNum> {
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let mut c = openssl::bn::BigNum::new()?;
+        c.mod_mul(c1, c2, &self.n_squared, &mut bn_ctx)?;
+        Ok(c)
+    }
+
+    // Checks that `r` is in Z_N^*, as encryption randomness must be.
+    fn check_unit(&self, r: &openssl::bn::BigNumRef) -> CryptographyResult<()> {
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let mut gcd = openssl::bn::BigNum::new()?;
+        gcd.gcd(r, &self.n, &mut bn_ctx)?;
+        if r.num_bits() == 0 || r >= &*self.n || gcd != *openssl::bn::BigNum::from_u32(1)? {
+            return Err(invalid_message());
+        }
+        Ok(())
+    }
+
+    fn check_ciphertext(&self, c: &openssl::bn::BigNumRef) -> CryptographyResult<()> {
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let mut gcd = openssl::bn::BigNum::new()?;
+        gcd.gcd(c, &self.n, &mut bn_ctx)?;
+        if c.num_bits() == 0 || c >= &*self.n_squared || gcd != *openssl::bn::BigNum::from_u32(1)? {
+            return Err(invalid_message());
+        }
+        Ok(())
+    }
+}
+
+struct PaillierPrivateKey {
+    public: PaillierPublicKey,
+    p: openssl::bn::BigNum,
+    q: openssl::bn::BigNum,
+    phi: openssl::bn::BigNum,
+    mu: openssl::bn::BigNum,
+}
+
+impl PaillierPrivateKey {
+    fn from_primes(
+        p: openssl::bn::BigNum,
+        q: openssl::bn::BigNum,
+    ) -> CryptographyResult<PaillierPrivateKey> {
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let mut n = openssl::bn::BigNum::new()?;
+        n.checked_mul(&p, &q, &mut bn_ctx)?;
+        let one = openssl::bn::BigNum::from_u32(1)?;
+        let mut p1 = openssl::bn::BigNum::new()?;
+        p1.checked_sub(&p, &one)?;
+        let mut q1 = openssl::bn::BigNum::new()?;
+        q1.checked_sub(&q, &one)?;
+        let mut phi = openssl::bn::BigNum::new()?;
+        phi.checked_mul(&p1, &q1, &mut bn_ctx)?;
+        let mut mu = openssl::bn::BigNum::new()?;
+        mu.mod_inverse(&phi, &n, &mut bn_ctx)
+            .map_err(|_| invalid_message())?;
+        Ok(PaillierPrivateKey {
+            public: PaillierPublicKey::new(n)?,
+            p,
+            q,
+            phi,
+            mu,
+        })
+    }
+
+    fn generate(prime_bits: i32) -> CryptographyResult<PaillierPrivateKey> {
+        loop {
+            let mut p = openssl::bn::BigNum::new()?;
+            p.generate_prime(prime_bits, false, None, None)?;
+            let mut q = openssl::bn::BigNum::new()?;
+            q.generate_prime(prime_bits, false, None, None)?;
+            if p != q {
+                return PaillierPrivateKey::from_primes(p, q);
+            }
+        }
+    }
+
+    // Dec(c) = L(c^phi mod N^2) * mu mod N, where L(u) = (u - 1) / N
+    fn decrypt(&self, c: &openssl::bn::BigNumRef) -> CryptographyResult<openssl::bn::BigNum> {
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let mut u = openssl::bn::BigNum::new()?;
+        u.mod_exp(c, &self.phi, &self.public.n_squared, &mut bn_ctx)?;
+        let one = openssl::bn::BigNum::from_u32(1)?;
+        let mut l = openssl::bn::BigNum::new()?;
+        l.checked_sub(&u, &one)?;
+        let mut l_div = openssl::bn::BigNum::new()?;
+        l_div.checked_div(&l, &self.public.n, &mut bn_ctx)?;
+        let mut m = openssl::bn::BigNum::new()?;
+        m.mod_mul(&l_div, &self.mu, &self.public.n, &mut bn_ctx)?;
+        Ok(m)
+    }
+}
+
+// The Paillier modulus must be large enough that the homomorphic operations
+// performed by party 2 (a value below 2*q^3) never wrap around.
+fn paillier_prime_bits(curve: &Curve) -> i32 {
+    std::cmp::max(MIN_PAILLIER_PRIME_BITS, 2 * curve.order.num_bits() + 2)
+}
+
+// The values, derived from N, whose N-th roots party 1 reveals to show that
+// its Paillier modulus is well formed.
+fn paillier_key_proof_values(
+    n: &openssl::bn::BigNumRef,
+) -> CryptographyResult<Vec<openssl::bn::BigNum>> {
+    let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+    let n_bytes = n.to_vec();
+    let mut values = vec![];
+    for i in 0..PAILLIER_PROOF_ROUNDS {
+        // Twice the length of N, so the reduction modulo N is close to
+        // uniform.
+        let mut data = vec![];
+        let mut block = 0u32;
+        while data.len() < 2 * n_bytes.len() {
+            let h = Transcript::new(b"paillier key")
+                .append(&n_bytes)
+                .append(&i.to_be_bytes())
+                .append(&block.to_be_bytes());
+            data.extend_from_slice(&h.finish());
+            block += 1;
+        }
+        let h = openssl::bn::BigNum::from_slice(&data)?;
+        let mut value = openssl::bn::BigNum::new()?;
+        value.nnmod(&h, n, &mut bn_ctx)?;
+        values.push(value);
+    }
+    Ok(values)
+}
+
+// The primes below PAILLIER_PROOF_PRIME_BOUND, by the sieve of Eratosthenes.
+fn small_primes() -> Vec<u32> {
+    let mut composite = vec![false; PAILLIER_PROOF_PRIME_BOUND];
+    let mut primes = vec![];
+    for i in 2..PAILLIER_PROOF_PRIME_BOUND {
+        if composite[i] {
+            continue;
+        }
+        primes.push(i as u32);
+        for multiple in (i * i..PAILLIER_PROOF_PRIME_BOUND).step_by(i) {
+            composite[multiple] = true;
+        }
+    }
+    primes
+}
+
+// A proof that gcd(N, phi(N)) = 1, from Goldberg, Reyzin, Sagga and
+// Baldimtsi's "Efficient Noninteractive Certification of RSA Moduli and
+// Beyond". Raising to the N-th power is then a permutation of Z_N^*, so
+// every ciphertext has a single plaintext. Otherwise a prime p divides both N
+// and phi(N), and only 1/p of the values derived from N have an N-th root.
+struct PaillierKeyProof {
+    roots: Vec<openssl::bn::BigNum>,
+}
+
+impl PaillierKeyProof {
+    fn prove(key: &PaillierPrivateKey) -> CryptographyResult<PaillierKeyProof> {
+        let n = &key.public.n;
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let mut d = openssl::bn::BigNum::new()?;
+        d.mod_inverse(n, &key.phi, &mut bn_ctx)?;
+        let mut roots = vec![];
+        for value in paillier_key_proof_values(n)? {
+            let mut root = openssl::bn::BigNum::new()?;
+            root.mod_exp(&value, &d, n, &mut bn_ctx)?;
+            roots.push(root);
+        }
+        Ok(PaillierKeyProof { roots })
+    }
+
+    fn verify(&self, key: &PaillierPublicKey) -> CryptographyResult<()> {
+        let n = &key.n;
+        for p in small_primes() {
+            if n.mod_word(p)? == 0 {
+                return Err(invalid_proof());
+            }
+        }
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        for (root, value) in self.roots.iter().zip(paillier_key_proof_values(n)?) {
+            let mut power = openssl::bn::BigNum::new()?;
+            power.mod_exp(root, n, n, &mut bn_ctx)?;
+            if root >= n || power != value {
+                return Err(invalid_proof());
+            }
+        }
+        Ok(())
+    }
+
+    fn write(&self, w: Writer) -> Writer {
+        self.roots.iter().fold(w, |w, root| w.field(&root.to_vec()))
+    }
+
+    fn read(r: &mut Reader<'_>) -> CryptographyResult<PaillierKeyProof> {
+        let roots = (0..PAILLIER_PROOF_ROUNDS)
+            .map(|_| r.bn())
+            .collect::<CryptographyResult<_>>()?;
+        Ok(PaillierKeyProof { roots })
+    }
+}
+
+// What party 1 proves about its encrypted share: that `c_key` encrypts, under
+// `key`, the discrete log of `q1`.
+struct ShareStatement<'a> {
+    curve: &'a Curve,
+    q1: &'a openssl::ec::EcPointRef,
+    key: &'a PaillierPublicKey,
+    c_key: &'a openssl::bn::BigNumRef,
+}
+
+impl ShareStatement<'_> {
+    fn transcript(&self, label: &[u8]) -> CryptographyResult<Transcript> {
+        Ok(Transcript::new(label)
+            .append(&self.curve.nid_bytes())
+            .append(&self.curve.point_bytes(self.q1)?)
+            .append(&self.key.n.to_vec())
+            .append(&self.c_key.to_vec()))
+    }
+}
+
+fn challenge_bit(challenge: &[u8], i: usize) -> bool {
+    (challenge[i / 8] >> (i % 8)) & 1 == 1
+}
+
+enum RangeResponse {
+    // Both values and the randomness of their encryptions.
+    Open([(openssl::bn::BigNum, openssl::bn::BigNum); 2]),
+    // z = x + w_j and s = r * r_j mod N, which open c_key * C_j.
+    Shift {
+        j: usize,
+        z: openssl::bn::BigNum,
+        s: openssl::bn::BigNum,
+    },
+}
+
+// A cut-and-choose proof that c_key encrypts a value in (-q, 2q), for a share
+// in [0, q), as used by Lindell's key generation. In each round party 1
+// encrypts w_low in [0, q) and w_high = w_low + q, in a random order, as C_0
+// and C_1. Depending on the challenge bit it either opens both, or opens
+// c_key * C_j to whichever of x + w_low and x + w_high is in [q, 2q), which is
+// uniformly distributed there. A party 1 that can answer both challenges
+// knows that Dec(c_key) = z - w_j is in (-q, 2q).
+struct RangeProof {
+    commitments: Vec<[openssl::bn::BigNum; 2]>,
+    responses: Vec<RangeResponse>,
+}
+
+fn range_challenge(
+    statement: &ShareStatement<'_>,
+    commitments: &[[openssl::bn::BigNum; 2]],
+) -> CryptographyResult<[u8; 32]> {
+    let h = commitments
+        .iter()
+        .flatten()
+        .fold(statement.transcript(b"range")?, |h, c| {
+            h.append(&c.to_vec())
+        });
+    Ok(h.finish())
+}
+
+impl RangeProof {
+    fn prove(
+        statement: &ShareStatement<'_>,
+        x: &openssl::bn::BigNumRef,
+        r: &openssl::bn::BigNumRef,
+    ) -> CryptographyResult<RangeProof> {
+        let q = &statement.curve.order;
+        let key = statement.key;
+        let mut openings = vec![];
+        let mut commitments = vec![];
+        for _ in 0..RANGE_PROOF_ROUNDS {
+            let mut w_low = openssl::bn::BigNum::new()?;
+            q.rand_range(&mut w_low)?;
+            let mut w_high = openssl::bn::BigNum::new()?;
+            w_high.checked_add(&w_low, q)?;
+            let mut pair = [(w_low, key.random_unit()?), (w_high, key.random_unit()?)];
+            let mut swap = [0];
+            openssl::rand::rand_bytes(&mut swap)?;
+            if swap[0] & 1 == 1 {
+                pair.swap(0, 1);
+            }
+            commitments.push([
+                key.encrypt_with(&pair[0].0, &pair[0].1)?,
+                key.encrypt_with(&pair[1].0, &pair[1].1)?,
+            ]);
+            openings.push(pair);
+        }
+
+        let challenge = range_challenge(statement, &commitments)?;
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let mut responses = vec![];
+        for (i, pair) in openings.into_iter().enumerate() {
+            if !challenge_bit(&challenge, i) {
+                responses.push(RangeResponse::Open(pair));
+                continue;
+            }
+            let low = usize::from(pair[0].0 > pair[1].0);
+            let mut z = openssl::bn::BigNum::new()?;
+            z.checked_add(x, &pair[low].0)?;
+            let j = if z >= *q {
+                low
+            } else {
+                z.checked_add(x, &pair[1 - low].0)?;
+                1 - low
+            };
+            let mut s = openssl::bn::BigNum::new()?;
+            s.mod_mul(r, &pair[j].1, &key.n, &mut bn_ctx)?;
+            responses.push(RangeResponse::Shift { j, z, s });
+        }
+        Ok(RangeProof {
+            commitments,
+            responses,
+        })
+    }
+
+    fn verify(&self, statement: &ShareStatement<'_>) -> CryptographyResult<()> {
+        if self.commitments.len() != RANGE_PROOF_ROUNDS {
+            return Err(invalid_message());
+        }
+        let q = &statement.curve.order;
+        let key = statement.key;
+        let mut two_q = openssl::bn::BigNum::new()?;
+        two_q.lshift1(q)?;
+
+        let challenge = range_challenge(statement, &self.commitments)?;
+        for (i, (commitment, response)) in self.commitments.iter().zip(&self.responses).enumerate()
+        {
+            key.check_ciphertext(&commitment[0])?;
+            key.check_ciphertext(&commitment[1])?;
+            match (challenge_bit(&challenge, i), response) {
+                (false, RangeResponse::Open(pair)) => {
+                    for ((w, r), c) in pair.iter().zip(commitment) {
+                        key.check_unit(r)?;
+                        if key.encrypt_with(w, r)? != *c {
+                            return Err(invalid_proof());
+                        }
+                    }
+                    // One value is in [0, q) and the other is q more.
+                    let (low, high) = if pair[0].0 < pair[1].0 {
+                        (&pair[0].0, &pair[1].0)
+                    } else {
+                        (&pair[1].0, &pair[0].0)
+                    };
+                    let mut difference = openssl::bn::BigNum::new()?;
+                    difference.checked_sub(high, low)?;
+                    if low >= q || difference != *q {
+                        return Err(invalid_proof());
+                    }
+                }
+                (true, RangeResponse::Shift { j, z, s }) => {
+                    key.check_unit(s)?;
+                    if z < q || *z >= two_q {
+                        return Err(invalid_proof());
+                    }
+                    if key.encrypt_with(z, s)? != key.add(statement.c_key, &commitment[*j])? {
+                        return Err(invalid_proof());
+                    }
+                }
+                _ => return Err(invalid_proof()),
+            }
+        }
+        Ok(())
+    }
+
+    fn write(&self, w: Writer) -> Writer {
+        let w = self
+            .commitments
+            .iter()
+            .flatten()
+            .fold(w, |w, c| w.field(&c.to_vec()));
+        self.responses.iter().fold(w, |w, response| match response {
+            RangeResponse::Open([(w0, r0), (w1, r1)]) => w
+                .field(&[0])
+                .field(&w0.to_vec())
+                .field(&r0.to_vec())
+                .field(&w1.to_vec())
+                .field(&r1.to_vec()),
+            RangeResponse::Shift { j, z, s } => w
+                .field(&[1 + *j as u8])
+                .field(&z.to_vec())
+                .field(&s.to_vec()),
+        })
+    }
+
+    fn read(r: &mut Reader<'_>) -> CryptographyResult<RangeProof> {
+        let mut commitments = vec![];
+        for _ in 0..RANGE_PROOF_ROUNDS {
+            commitments.push([r.bn()?, r.bn()?]);
+        }
+        let mut responses = vec![];
+        for _ in 0..RANGE_PROOF_ROUNDS {
+            responses.push(match r.field()? {
+                [0] => RangeResponse::Open([(r.bn()?, r.bn()?), (r.bn()?, r.bn()?)]),
+                &[t @ (1 | 2)] => RangeResponse::Shift {
+                    j: usize::from(t - 1),
+                    z: r.bn()?,
+                    s: r.bn()?,
+                },
+                _ => return Err(invalid_message()),
+            });
+        }
+        Ok(RangeProof {
+            commitments,
+            responses,
+        })
+    }
+}
+
+// A Sigma protocol showing that Dec(c_key) is the discrete log of Q1 modulo
+// q, once the range proof has shown that it's small. Party 1 sends
+// A = Enc(alpha; beta) and B = alpha*G, and for a challenge e below 2^128
+// responds with z = alpha + e*x and w = beta * r^e mod N. As alpha is 128
+// bits longer than e*x, z statistically hides x. Enc(z; w) == A * c_key^e and
+// z*G == B + e*Q1, for two challenges, give (e - e')*Dec(c_key) = z - z' over
+// the integers, as neither side is large enough to wrap around modulo N, and
+// so Dec(c_key) = x modulo q.
+struct EncryptedShareProof {
+    a: openssl::bn::BigNum,
+    b: openssl::ec::EcPoint,
+    z: openssl::bn::BigNum,
+    w: openssl::bn::BigNum,
+}
+
+fn encrypted_share_challenge(
+    statement: &ShareStatement<'_>,
+    a: &openssl::bn::BigNumRef,
+    b: &openssl::ec::EcPointRef,
+) -> CryptographyResult<openssl::bn::BigNum> {
+    let h = statement
+        .transcript(b"encrypted share")?
+        .append(&a.to_vec())
+        .append(&statement.curve.point_bytes(b)?);
+    Ok(openssl::bn::BigNum::from_slice(
+        &h.finish()[..CHALLENGE_BITS as usize / 8],
+    )?)
+}
+
+impl EncryptedShareProof {
+    fn prove(
+        statement: &ShareStatement<'_>,
+        x: &openssl::bn::BigNumRef,
+        r: &openssl::bn::BigNumRef,
+    ) -> CryptographyResult<EncryptedShareProof> {
+        let curve = statement.curve;
+        let key = statement.key;
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let mut alpha_bound = openssl::bn::BigNum::new()?;
+        alpha_bound.lshift(&curve.order, CHALLENGE_BITS + STATISTICAL_SECURITY_BITS)?;
+        let mut alpha = openssl::bn::BigNum::new()?;
+        alpha_bound.rand_range(&mut alpha)?;
+        let beta = key.random_unit()?;
+
+        let a = key.encrypt_with(&alpha, &beta)?;
+        let mut alpha_mod_q = openssl::bn::BigNum::new()?;
+        alpha_mod_q.nnmod(&alpha, &curve.order, &mut bn_ctx)?;
+        let b = curve.mul_generator(&alpha_mod_q)?;
+        let e = encrypted_share_challenge(statement, &a, &b)?;
+
+        let mut ex = openssl::bn::BigNum::new()?;
+        ex.checked_mul(&e, x, &mut bn_ctx)?;
+        let mut z = openssl::bn::BigNum::new()?;
+        z.checked_add(&alpha, &ex)?;
+        let mut re = openssl::bn::BigNum::new()?;
+        re.mod_exp(r, &e, &key.n, &mut bn_ctx)?;
+        let mut w = openssl::bn::BigNum::new()?;
+        w.mod_mul(&beta, &re, &key.n, &mut bn_ctx)?;
+        Ok(EncryptedShareProof { a, b, z, w })
+    }
+
+    fn verify(&self, statement: &ShareStatement<'_>) -> CryptographyResult<()> {
+        let curve = statement.curve;
+        let key = statement.key;
+        key.check_ciphertext(&self.a)?;
+        key.check_unit(&self.w)?;
+        let mut z_bound = openssl::bn::BigNum::new()?;
+        z_bound.lshift(&curve.order, CHALLENGE_BITS + STATISTICAL_SECURITY_BITS + 1)?;
+        if self.z >= z_bound {
+            return Err(invalid_proof());
+        }
+        let e = encrypted_share_challenge(statement, &self.a, &self.b)?;
+
+        // Enc(z; w) == A * c_key^e
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let mut ce = openssl::bn::BigNum::new()?;
+        ce.mod_exp(statement.c_key, &e, &key.n_squared, &mut bn_ctx)?;
+        if key.encrypt_with(&self.z, &self.w)? != key.add(&self.a, &ce)? {
+            return Err(invalid_proof());
+        }
+
+        // z*G == B + e*Q1
+        let mut z_mod_q = openssl::bn::BigNum::new()?;
+        z_mod_q.nnmod(&self.z, &curve.order, &mut bn_ctx)?;
+        let lhs = curve.mul_generator(&z_mod_q)?;
+        let eq = curve.mul(statement.q1, &e)?;
+        let mut rhs = openssl::ec::EcPoint::new(&curve.group)?;
+        rhs.add(&curve.group, &self.b, &eq, &mut bn_ctx)?;
+        if !lhs.eq(&curve.group, &rhs, &mut bn_ctx)? {
+            return Err(invalid_proof());
+        }
+        Ok(())
+    }
+
+    fn write(&self, curve: &Curve, w: Writer) -> CryptographyResult<Writer> {
+        Ok(w.field(&self.a.to_vec())
+            .field(&curve.point_bytes(&self.b)?)
+            .field(&self.z.to_vec())
+            .field(&self.w.to_vec()))
+    }
+
+    fn read(curve: &Curve, r: &mut Reader<'_>) -> CryptographyResult<EncryptedShareProof> {
+        Ok(EncryptedShareProof {
+            a: r.bn()?,
+            b: curve.point_from_bytes(r.field()?)?,
+            z: r.bn()?,
+            w: r.bn()?,
+        })
+    }
+}
+
+fn digest_for(
+    py: pyo3::Python<'_>,
+    curve: &Curve,
+    data: &[u8],
+    algorithm: &pyo3::PyAny,
+) -> CryptographyResult<(Vec<u8>, openssl::bn::BigNum)> {
+    if !algorithm.is_instance(types::ECDSA.get(py)?)? {
+        return Err(CryptographyError::from(
+            exceptions::UnsupportedAlgorithm::new_err((
+                "Unsupported elliptic curve signature algorithm",
+                exceptions::Reasons::UNSUPPORTED_PUBLIC_KEY_ALGORITHM,
+            )),
+        ));
+    }
+    let (digest, _) = utils::calculate_digest_and_algorithm(
+        py,
+        data,
+        algorithm.getattr(pyo3::intern!(py, "algorithm"))?,
+    )?;
+    Ok((digest.to_vec(), curve.digest_to_scalar(digest)?))
+}
+
+fn public_key_object(
+    py: pyo3::Python<'_>,
+    curve: &Curve,
+    point: &openssl::ec::EcPointRef,
+) -> CryptographyResult<ec::ECPublicKey> {
+    let pkey = openssl::pkey::PKey::from_ec_key(curve.ec_key(point)?)?;
+    ec::public_key_from_pkey(py, &pkey)
+}
+
+#[pyo3::prelude::pyclass(
+    frozen,
+    module = "cryptography.hazmat.bindings._rust.openssl.threshold_ecdsa"
+)]
+struct Party1KeyGen {
+    curve: Curve,
+    x1: openssl::bn::BigNum,
+    paillier: PaillierPrivateKey,
+    message: Vec<u8>,
+}
+
+#[pyo3::prelude::pymethods]
+impl Party1KeyGen {
+    #[new]
+    fn new(py: pyo3::Python<'_>, curve: &pyo3::PyAny) -> CryptographyResult<Party1KeyGen> {
+        let curve = Curve::from_py(py, curve)?;
+        let (x1, paillier, message) = py.allow_threads(|| -> CryptographyResult<_> {
+            let x1 = curve.random_scalar()?;
+            let q1 = curve.mul_generator(&x1)?;
+            let paillier = PaillierPrivateKey::generate(paillier_prime_bits(&curve))?;
+            let r = paillier.public.random_unit()?;
+            let c_key = paillier.public.encrypt_with(&x1, &r)?;
+            let statement = ShareStatement {
+                curve: &curve,
+                q1: &q1,
+                key: &paillier.public,
+                c_key: &c_key,
+            };
+
+            let proof = DlogProof::prove(&curve, b"keygen 1", &x1, &q1)?;
+            let w = Writer::new(MSG_KEYGEN_1)
+                .field(&curve.nid_bytes())
+                .field(&curve.point_bytes(&q1)?);
+            let w = proof
+                .write(&curve, w)?
+                .field(&paillier.public.n.to_vec())
+                .field(&c_key.to_vec());
+            let w = PaillierKeyProof::prove(&paillier)?.write(w);
+            let w = RangeProof::prove(&statement, &x1, &r)?.write(w);
+            let message = EncryptedShareProof::prove(&statement, &x1, &r)?
+                .write(&curve, w)?
+                .0;
+            Ok((x1, paillier, message))
+        })?;
+        Ok(Party1KeyGen {
+            curve,
+            x1,
+            paillier,
+            message,
+        })
+    }
+
+    fn message<'p>(&self, py: pyo3::Python<'p>) -> &'p pyo3::types::PyBytes {
+        pyo3::types::PyBytes::new(py, &self.message)
+    }
+
+    fn finish(&self, message: CffiBuf<'_>) -> CryptographyResult<Party1KeyShare> {
+        let curve = &self.curve;
+        let mut r = Reader::new(message.as_bytes(), MSG_KEYGEN_2)?;
+        if r.field()? != curve.nid_bytes() {
+            return Err(invalid_message());
+        }
+        let q2 = curve.point_from_bytes(r.field()?)?;
+        let proof = DlogProof::read(curve, &mut r)?;
+        r.finish()?;
+        proof.verify(curve, b"keygen 2", &q2)?;
+
+        let public = curve.mul(&q2, &self.x1)?;
+        Ok(Party1KeyShare {
+            curve: Curve::from_group(clone_group(&curve.group)?)?,
+            x1: self.x1.to_owned()?,
+            public,
+            paillier: PaillierPrivateKey::from_primes(
+                self.paillier.p.to_owned()?,
+                self.paillier.q.to_owned()?,
+            )?,
+        })
+    }
+}
+
+fn clone_group(group: &openssl::ec::EcGroupRef) -> CryptographyResult<openssl::ec::EcGroup> {
+    Ok(openssl::ec::EcGroup::from_curve_name(
+        group.curve_name().unwrap(),
+    )?)
+}
+
+#[pyo3::prelude::pyclass(
+    frozen,
+    module = "cryptography.hazmat.bindings._rust.openssl.threshold_ecdsa"
+)]
+struct Party2KeyGen {
+    curve: Curve,
+    x2: openssl::bn::BigNum,
+    message: Vec<u8>,
+}
+
+#[pyo3::prelude::pymethods]
+impl Party2KeyGen {
+    #[new]
+    fn new(py: pyo3::Python<'_>, curve: &pyo3::PyAny) -> CryptographyResult<Party2KeyGen> {
+        let curve = Curve::from_py(py, curve)?;
+        let x2 = curve.random_scalar()?;
+        let q2 = curve.mul_generator(&x2)?;
+        let proof = DlogProof::prove(&curve, b"keygen 2", &x2, &q2)?;
+        let w = Writer::new(MSG_KEYGEN_2)
+            .field(&curve.nid_bytes())
+            .field(&curve.point_bytes(&q2)?);
+        let message = proof.write(&curve, w)?.0;
+        Ok(Party2KeyGen { curve, x2, message })
+    }
+
+    fn message<'p>(&self, py: pyo3::Python<'p>) -> &'p pyo3::types::PyBytes {
+        pyo3::types::PyBytes::new(py, &self.message)
+    }
+
+    fn finish(
+        &self,
+        py: pyo3::Python<'_>,
+        message: CffiBuf<'_>,
+    ) -> CryptographyResult<Party2KeyShare> {
+        let curve = &self.curve;
+        let mut r = Reader::new(message.as_bytes(), MSG_KEYGEN_1)?;
+        if r.field()? != curve.nid_bytes() {
+            return Err(invalid_message());
+        }
+        let q1 = curve.point_from_bytes(r.field()?)?;
+        let proof = DlogProof::read(curve, &mut r)?;
+        let n = r.bn()?;
+        let c_key = r.bn()?;
+        let key_proof = PaillierKeyProof::read(&mut r)?;
+        let range_proof = RangeProof::read(&mut r)?;
+        let share_proof = EncryptedShareProof::read(curve, &mut r)?;
+        r.finish()?;
+        proof.verify(curve, b"keygen 1", &q1)?;
+
+        if n.num_bits() < 2 * paillier_prime_bits(curve) - 1 || !n.is_bit_set(0) {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("Paillier modulus is too small"),
+            ));
+        }
+        let paillier = PaillierPublicKey::new(n)?;
+        paillier.check_ciphertext(&c_key)?;
+        let statement = ShareStatement {
+            curve,
+            q1: &q1,
+            key: &paillier,
+            c_key: &c_key,
+        };
+        py.allow_threads(|| {
+            key_proof.verify(&paillier)?;
+            range_proof.verify(&statement)?;
+            share_proof.verify(&statement)
+        })?;
+
+        let public = curve.mul(&q1, &self.x2)?;
+        Ok(Party2KeyShare {
+            curve: Curve::from_group(clone_group(&curve.group)?)?,
+            x2: self.x2.to_owned()?,
+            public,
+            paillier,
+            c_key,
+        })
+    }
+}
+
+#[pyo3::prelude::pyclass(
+    frozen,
+    module = "cryptography.hazmat.bindings._rust.openssl.threshold_ecdsa"
+)]
+struct Party1KeyShare {
+    curve: Curve,
+    x1: openssl::bn::BigNum,
+    public: openssl::ec::EcPoint,
+    paillier: PaillierPrivateKey,
+}
+
+#[pyo3::prelude::pymethods]
+impl Party1KeyShare {
+    fn public_key(&self, py: pyo3::Python<'_>) -> CryptographyResult<ec::ECPublicKey> {
+        public_key_object(py, &self.curve, &self.public)
+    }
+
+    fn share_bytes<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let curve = &self.curve;
+        Ok(Writer::new(SHARE_PARTY_1)
+            .field(&curve.nid_bytes())
+            .field(&self.x1.to_vec_padded(curve.scalar_length() as i32)?)
+            .field(&curve.point_bytes(&self.public)?)
+            .field(&self.paillier.p.to_vec())
+            .field(&self.paillier.q.to_vec())
+            .finish(py))
+    }
+
+    fn sign_session(
+        slf: pyo3::PyRef<'_, Self>,
+        py: pyo3::Python<'_>,
+        data: CffiBuf<'_>,
+        signature_algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<Party1SignSession> {
+        let curve = &slf.curve;
+        let (digest, _) = digest_for(py, curve, data.as_bytes(), signature_algorithm)?;
+        let k1 = curve.random_scalar()?;
+        let r1 = curve.mul_generator(&k1)?;
+        let proof = DlogProof::prove(curve, b"sign 1", &k1, &r1)?;
+
+        let mut nonce = [0; COMMITMENT_NONCE_LENGTH];
+        openssl::rand::rand_bytes(&mut nonce)?;
+        let w = Writer::new(MSG_SIGN_DECOMMITMENT)
+            .field(&nonce)
+            .field(&curve.point_bytes(&r1)?);
+        let decommitment = proof.write(curve, w)?.0;
+        let commitment = Writer::new(MSG_SIGN_COMMITMENT)
+            .field(&openssl::sha::sha256(&decommitment))
+            .0;
+
+        Ok(Party1SignSession {
+            share: slf.into(),
+            digest,
+            k1: Some(k1),
+            r2: None,
+            commitment,
+            decommitment,
+        })
+    }
+}
+
+#[pyo3::prelude::pyclass(
+    frozen,
+    module = "cryptography.hazmat.bindings._rust.openssl.threshold_ecdsa"
+)]
+struct Party2KeyShare {
+    curve: Curve,
+    x2: openssl::bn::BigNum,
+    public: openssl::ec::EcPoint,
+    paillier: PaillierPublicKey,
+    c_key: openssl::bn::BigNum,
+}
+
+#[pyo3::prelude::pymethods]
+impl Party2KeyShare {
+    fn public_key(&self, py: pyo3::Python<'_>) -> CryptographyResult<ec::ECPublicKey> {
+        public_key_object(py, &self.curve, &self.public)
+    }
+
+    fn share_bytes<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let curve = &self.curve;
+        Ok(Writer::new(SHARE_PARTY_2)
+            .field(&curve.nid_bytes())
+            .field(&self.x2.to_vec_padded(curve.scalar_length() as i32)?)
+            .field(&curve.point_bytes(&self.public)?)
+            .field(&self.paillier.n.to_vec())
+            .field(&self.c_key.to_vec())
+            .finish(py))
+    }
+
+    fn sign_session(
+        slf: pyo3::PyRef<'_, Self>,
+        py: pyo3::Python<'_>,
+        data: CffiBuf<'_>,
+        signature_algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<Party2SignSession> {
+        let (_, m) = digest_for(py, &slf.curve, data.as_bytes(), signature_algorithm)?;
+        Ok(Party2SignSession {
+            share: slf.into(),
+            m,
+            k2: None,
+            commitment: None,
+        })
+    }
+}
+
+#[pyo3::prelude::pyclass(module = "cryptography.hazmat.bindings._rust.openssl.threshold_ecdsa")]
+struct Party1SignSession {
+    share: pyo3::Py<Party1KeyShare>,
+    digest: Vec<u8>,
+    // `None` once the session has produced a signature, the nonce must never
+    // be used twice.
+    k1: Option<openssl::bn::BigNum>,
+    r2: Option<openssl::ec::EcPoint>,
+    commitment: Vec<u8>,
+    decommitment: Vec<u8>,
+}
+
+#[pyo3::prelude::pymethods]
+impl Party1SignSession {
+    fn commitment<'p>(&self, py: pyo3::Python<'p>) -> &'p pyo3::types::PyBytes {
+        pyo3::types::PyBytes::new(py, &self.commitment)
+    }
+
+    fn decommit<'p>(
+        &mut self,
+        py: pyo3::Python<'p>,
+        message: CffiBuf<'_>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        if self.k1.is_none() || self.r2.is_some() {
+            return Err(hashes::already_finalized_error());
+        }
+        let curve = &self.share.get().curve;
+        let mut r = Reader::new(message.as_bytes(), MSG_SIGN_2)?;
+        let r2 = curve.point_from_bytes(r.field()?)?;
+        let proof = DlogProof::read(curve, &mut r)?;
+        r.finish()?;
+        proof.verify(curve, b"sign 2", &r2)?;
+
+        self.r2 = Some(r2);
+        Ok(pyo3::types::PyBytes::new(py, &self.decommitment))
+    }
+
+    fn finish<'p>(
+        &mut self,
+        py: pyo3::Python<'p>,
+        message: CffiBuf<'_>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let share = self.share.get();
+        let curve = &share.curve;
+        if self.k1.is_none() || self.r2.is_none() {
+            return Err(hashes::already_finalized_error());
+        }
+        let k1 = self.k1.take().unwrap();
+        let r2 = self.r2.as_ref().unwrap();
+
+        let mut r = Reader::new(message.as_bytes(), MSG_SIGN_PARTIAL)?;
+        let c3 = r.bn()?;
+        r.finish()?;
+        share.paillier.public.check_ciphertext(&c3)?;
+
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let big_r = curve.mul(r2, &k1)?;
+        let sig_r = curve.x_mod_order(&big_r)?;
+        let m = share.paillier.decrypt(&c3)?;
+        let mut s_prime = openssl::bn::BigNum::new()?;
+        s_prime.nnmod(&m, &curve.order, &mut bn_ctx)?;
+        let mut k1_inv = openssl::bn::BigNum::new()?;
+        k1_inv.mod_inverse(&k1, &curve.order, &mut bn_ctx)?;
+        let mut s = openssl::bn::BigNum::new()?;
+        s.mod_mul(&k1_inv, &s_prime, &curve.order, &mut bn_ctx)?;
+        // Use the low-S form, as in Lindell's protocol.
+        let mut neg_s = openssl::bn::BigNum::new()?;
+        neg_s.checked_sub(&curve.order, &s)?;
+        if neg_s < s {
+            s = neg_s;
+        }
+
+        let sig = openssl::ecdsa::EcdsaSig::from_private_components(sig_r, s)?;
+        // Party 2 may have sent a malformed partial signature, so the result
+        // is checked before it is released.
+        let ec_key = curve.ec_key(&share.public)?;
+        if !sig.verify(&self.digest, &ec_key)? {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("The two-party ECDSA signature is invalid"),
+            ));
+        }
+        Ok(pyo3::types::PyBytes::new(py, &sig.to_der()?))
+    }
+}
+
+#[pyo3::prelude::pyclass(module = "cryptography.hazmat.bindings._rust.openssl.threshold_ecdsa")]
+struct Party2SignSession {
+    share: pyo3::Py<Party2KeyShare>,
+    m: openssl::bn::BigNum,
+    k2: Option<openssl::bn::BigNum>,
+    commitment: Option<Vec<u8>>,
+}
+
+#[pyo3::prelude::pymethods]
+impl Party2SignSession {
+    fn message<'p>(
+        &mut self,
+        py: pyo3::Python<'p>,
+        commitment: CffiBuf<'_>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        if self.commitment.is_some() {
+            return Err(hashes::already_finalized_error());
+        }
+        let curve = &self.share.get().curve;
+        let mut r = Reader::new(commitment.as_bytes(), MSG_SIGN_COMMITMENT)?;
+        let commitment = r.field()?.to_vec();
+        r.finish()?;
+
+        let k2 = curve.random_scalar()?;
+        let r2 = curve.mul_generator(&k2)?;
+        let proof = DlogProof::prove(curve, b"sign 2", &k2, &r2)?;
+        let w = Writer::new(MSG_SIGN_2).field(&curve.point_bytes(&r2)?);
+        let message = proof.write(curve, w)?;
+
+        self.k2 = Some(k2);
+        self.commitment = Some(commitment);
+        Ok(message.finish(py))
+    }
+
+    fn partial_signature<'p>(
+        &mut self,
+        py: pyo3::Python<'p>,
+        message: CffiBuf<'_>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        if self.k2.is_none() || self.commitment.is_none() {
+            return Err(hashes::already_finalized_error());
+        }
+        let k2 = self.k2.take().unwrap();
+        let share = self.share.get();
+        let curve = &share.curve;
+
+        let digest = openssl::sha::sha256(message.as_bytes());
+        if !openssl::memcmp::eq(&digest, self.commitment.as_ref().unwrap()) {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "Decommitment does not match the commitment",
+                ),
+            ));
+        }
+        let mut r = Reader::new(message.as_bytes(), MSG_SIGN_DECOMMITMENT)?;
+        r.field()?;
+        let r1 = curve.point_from_bytes(r.field()?)?;
+        let proof = DlogProof::read(curve, &mut r)?;
+        r.finish()?;
+        proof.verify(curve, b"sign 1", &r1)?;
+
+        let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+        let big_r = curve.mul(&r1, &k2)?;
+        let sig_r = curve.x_mod_order(&big_r)?;
+        let mut k2_inv = openssl::bn::BigNum::new()?;
+        k2_inv.mod_inverse(&k2, &curve.order, &mut bn_ctx)?;
+
+        // c1 = Enc(rho * q + k2^-1 * m mod q), with rho masking the value
+        // party 1 decrypts.
+        let mut q_squared = openssl::bn::BigNum::new()?;
+        q_squared.sqr(&curve.order, &mut bn_ctx)?;
+        let mut rho = openssl::bn::BigNum::new()?;
+        q_squared.rand_range(&mut rho)?;
+        let mut masked = openssl::bn::BigNum::new()?;
+        masked.checked_mul(&rho, &curve.order, &mut bn_ctx)?;
+        let mut km = openssl::bn::BigNum::new()?;
+        km.mod_mul(&k2_inv, &self.m, &curve.order, &mut bn_ctx)?;
+        let mut plaintext = openssl::bn::BigNum::new()?;
+        plaintext.checked_add(&masked, &km)?;
+        let c1 = share.paillier.encrypt(&plaintext)?;
+
+        // c2 = Enc(x1)^(k2^-1 * r * x2 mod q)
+        let mut v = openssl::bn::BigNum::new()?;
+        v.mod_mul(&k2_inv, &sig_r, &curve.order, &mut bn_ctx)?;
+        let mut v2 = openssl::bn::BigNum::new()?;
+        v2.mod_mul(&v, &share.x2, &curve.order, &mut bn_ctx)?;
+        let mut c2 = openssl::bn::BigNum::new()?;
+        c2.mod_exp(&share.c_key, &v2, &share.paillier.n_squared, &mut bn_ctx)?;
+
+        let mut c3 = openssl::bn::BigNum::new()?;
+        c3.mod_mul(&c1, &c2, &share.paillier.n_squared, &mut bn_ctx)?;
+        Ok(Writer::new(MSG_SIGN_PARTIAL).field(&c3.to_vec()).finish(py))
+    }
+}
+
+#[pyo3::prelude::pyfunction]
+fn load_party1_key_share(data: CffiBuf<'_>) -> CryptographyResult<Party1KeyShare> {
+    let mut r = Reader::new(data.as_bytes(), SHARE_PARTY_1)?;
+    let curve = Curve::from_nid(r.field()?)?;
+    let x1 = curve.scalar_from_bytes(r.field()?)?;
+    let public = curve.point_from_bytes(r.field()?)?;
+    let p = r.bn()?;
+    let q = r.bn()?;
+    r.finish()?;
+    let paillier = PaillierPrivateKey::from_primes(p, q)?;
+    Ok(Party1KeyShare {
+        curve,
+        x1,
+        public,
+        paillier,
+    })
+}
+
+#[pyo3::prelude::pyfunction]
+fn load_party2_key_share(data: CffiBuf<'_>) -> CryptographyResult<Party2KeyShare> {
+    let mut r = Reader::new(data.as_bytes(), SHARE_PARTY_2)?;
+    let curve = Curve::from_nid(r.field()?)?;
+    let x2 = curve.scalar_from_bytes(r.field()?)?;
+    let public = curve.point_from_bytes(r.field()?)?;
+    let paillier = PaillierPublicKey::new(r.bn()?)?;
+    let c_key = r.bn()?;
+    r.finish()?;
+    paillier.check_ciphertext(&c_key)?;
+    Ok(Party2KeyShare {
+        curve,
+        x2,
+        public,
+        paillier,
+        c_key,
+    })
+}
+
+pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let m = pyo3::prelude::PyModule::new(py, "threshold_ecdsa")?;
+    m.add_function(pyo3::wrap_pyfunction!(load_party1_key_share, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(load_party2_key_share, m)?)?;
+
+    m.add_class::<Party1KeyGen>()?;
+    m.add_class::<Party2KeyGen>()?;
+    m.add_class::<Party1KeyShare>()?;
+    m.add_class::<Party2KeyShare>()?;
+    m.add_class::<Party1SignSession>()?;
+    m.add_class::<Party2SignSession>()?;
+
+    Ok(m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Curve, EncryptedShareProof, PaillierKeyProof, PaillierPrivateKey, PaillierPublicKey,
+        RangeProof, ShareStatement,
+    };
+
+    #[test]
+    fn test_paillier_roundtrip() {
+        let key = PaillierPrivateKey::from_primes(
+            openssl::bn::BigNum::from_u32(1_000_003).unwrap(),
+            openssl::bn::BigNum::from_u32(1_000_033).unwrap(),
+        )
+        .ok()
+        .unwrap();
+        let m = openssl::bn::BigNum::from_u32(123_456_789).unwrap();
+        let c = key.public.encrypt(&m).ok().unwrap();
+        assert!(key.decrypt(&c).ok().unwrap() == m);
+    }
+
+    #[test]
+    fn test_paillier_key_proof() {
+        let key = PaillierPrivateKey::generate(512).ok().unwrap();
+        let mut proof = PaillierKeyProof::prove(&key).ok().unwrap();
+        assert!(proof.verify(&key.public).is_ok());
+
+        // A modulus with a small factor.
+        let mut bn_ctx = openssl::bn::BigNumContext::new().unwrap();
+        let mut n = openssl::bn::BigNum::new().unwrap();
+        let three = openssl::bn::BigNum::from_u32(3).unwrap();
+        n.checked_mul(&key.public.n, &three, &mut bn_ctx).unwrap();
+        let other = PaillierPublicKey::new(n).ok().unwrap();
+        assert!(proof.verify(&other).is_err());
+
+        proof.roots[0].add_word(1).unwrap();
+        assert!(proof.verify(&key.public).is_err());
+    }
+
+    #[test]
+    fn test_encrypted_share_proofs() {
+        let group =
+            openssl::ec::EcGroup::from_curve_name(openssl::nid::Nid::X9_62_PRIME256V1).unwrap();
+        let curve = Curve::from_group(group).ok().unwrap();
+        let key = PaillierPrivateKey::generate(512).ok().unwrap();
+        let x = curve.random_scalar().ok().unwrap();
+        let q1 = curve.mul_generator(&x).ok().unwrap();
+        let r = key.public.random_unit().ok().unwrap();
+        let c_key = key.public.encrypt_with(&x, &r).ok().unwrap();
+        let statement = ShareStatement {
+            curve: &curve,
+            q1: &q1,
+            key: &key.public,
+            c_key: &c_key,
+        };
+        let range_proof = RangeProof::prove(&statement, &x, &r).ok().unwrap();
+        assert!(range_proof.verify(&statement).is_ok());
+        let share_proof = EncryptedShareProof::prove(&statement, &x, &r).ok().unwrap();
+        assert!(share_proof.verify(&statement).is_ok());
+
+        // A share that is congruent to the discrete log of Q1, but too large.
+        let mut four_q = openssl::bn::BigNum::new().unwrap();
+        four_q.lshift(&curve.order, 2).unwrap();
+        let mut large_x = openssl::bn::BigNum::new().unwrap();
+        large_x.checked_add(&four_q, &x).unwrap();
+        let c_large = key.public.encrypt_with(&large_x, &r).ok().unwrap();
+        let statement = ShareStatement {
+            c_key: &c_large,
+            ..statement
+        };
+        let range_proof = RangeProof::prove(&statement, &large_x, &r).ok().unwrap();
+        assert!(range_proof.verify(&statement).is_err());
+
+        // A share that isn't the discrete log of Q1.
+        let mut other_x = x.to_owned().unwrap();
+        other_x.add_word(1).unwrap();
+        let c_other = key.public.encrypt_with(&other_x, &r).ok().unwrap();
+        let statement = ShareStatement {
+            c_key: &c_other,
+            ..statement
+        };
+        let range_proof = RangeProof::prove(&statement, &other_x, &r).ok().unwrap();
+        assert!(range_proof.verify(&statement).is_ok());
+        let share_proof = EncryptedShareProof::prove(&statement, &other_x, &r)
+            .ok()
+            .unwrap();
+        assert!(share_proof.verify(&statement).is_err());
+    }
+}
@@ -0,0 +1,132 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+use crate::backend::hashes::message_digest_from_algorithm;
+use crate::buf::CffiBuf;
+use crate::error::{CryptographyError, CryptographyResult};
+
+const STEAM_GUARD_ALPHABET: &[u8] = b"23456789BCDFGHJKMNPQRTVWXY";
+const STEAM_GUARD_LENGTH: usize = 5;
+// Keep in sync with `_MAX_WINDOW` in hotp.py.
+const MAX_WINDOW: u64 = 10;
+
+#[pyo3::prelude::pyclass(
+    frozen,
+    module = "cryptography.hazmat.bindings._rust.openssl.twofactor",
+    name = "HOTP"
+)]
+struct Hotp {
+    key: Vec<u8>,
+    md: openssl::hash::MessageDigest,
+    length: usize,
+    steam_guard: bool,
+}
+
+impl Hotp {
+    // RFC 4226 Section 5.3
+    fn truncate(&self, counter: u64) -> CryptographyResult<u32> {
+        let mut h = cryptography_openssl::hmac::Hmac::new(&self.key, self.md)?;
+        h.update(&counter.to_be_bytes())?;
+        let mac = h.finish()?;
+        let offset = usize::from(mac[mac.len() - 1] & 0xf);
+        let p = u32::from_be_bytes(mac[offset..offset + 4].try_into().unwrap());
+        Ok(p & 0x7fff_ffff)
+    }
+
+    fn code(&self, counter: u64) -> CryptographyResult<Vec<u8>> {
+        let value = self.truncate(counter)?;
+        if self.steam_guard {
+            let base = STEAM_GUARD_ALPHABET.len() as u32;
+            Ok((0..STEAM_GUARD_LENGTH as u32)
+                .map(|i| STEAM_GUARD_ALPHABET[(value / base.pow(i) % base) as usize])
+                .collect())
+        } else {
+            let value = value % 10u32.pow(self.length as u32);
+            Ok(format!("{:0width$}", value, width = self.length).into_bytes())
+        }
+    }
+}
+
+#[pyo3::prelude::pymethods]
+impl Hotp {
+    #[new]
+    fn new(
+        py: pyo3::Python<'_>,
+        key: CffiBuf<'_>,
+        length: usize,
+        algorithm: &pyo3::PyAny,
+        steam_guard: bool,
+    ) -> CryptographyResult<Hotp> {
+        if !steam_guard && !(6..=8).contains(&length) {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "Length of HOTP has to be between 6 and 8.",
+                ),
+            ));
+        }
+        Ok(Hotp {
+            key: key.as_bytes().to_vec(),
+            md: message_digest_from_algorithm(py, algorithm)?,
+            length: if steam_guard {
+                STEAM_GUARD_LENGTH
+            } else {
+                length
+            },
+            steam_guard,
+        })
+    }
+
+    fn dynamic_truncate(&self, counter: u64) -> CryptographyResult<u32> {
+        self.truncate(counter)
+    }
+
+    fn generate<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        counter: u64,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        Ok(pyo3::types::PyBytes::new(py, &self.code(counter)?))
+    }
+
+    // Checks `code` against every counter in
+    // [counter - look_behind, counter + look_ahead] and returns the offset of
+    // the matching counter. Every candidate is compared so that the time
+    // taken doesn't depend on which one matched.
+    fn verify(
+        &self,
+        code: CffiBuf<'_>,
+        counter: u64,
+        look_behind: u64,
+        look_ahead: u64,
+    ) -> CryptographyResult<Option<i64>> {
+        if look_behind > MAX_WINDOW || look_ahead > MAX_WINDOW {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "window must be between 0 and {MAX_WINDOW}."
+                )),
+            ));
+        }
+        let code = code.as_bytes();
+        let first = counter.saturating_sub(look_behind);
+        let last = counter.saturating_add(look_ahead);
+        let mut matched = None;
+        for candidate in first..=last {
+            let expected = self.code(candidate)?;
+            if expected.len() == code.len()
+                && openssl::memcmp::eq(&expected, code)
+                && matched.is_none()
+            {
+                matched = Some(candidate as i128 - counter as i128);
+            }
+        }
+        Ok(matched.map(|offset| offset as i64))
+    }
+}
+
+pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let m = pyo3::prelude::PyModule::new(py, "twofactor")?;
+    m.add_class::<Hotp>()?;
+
+    Ok(m)
+}
@@ -2,9 +2,16 @@
 // 2.0, and the BSD License. See the LICENSE file in the root of this repository
 // for complete details.
 
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash as _, Hasher};
+
+use cryptography_x509::common::SubjectPublicKeyInfo;
+use cryptography_x509::pkcs8::PrivateKeyInfo;
+
 use crate::backend::hashes::Hash;
+use crate::backend::pbes2;
 use crate::error::{CryptographyError, CryptographyResult};
-use crate::{error, types};
+use crate::{error, exceptions, types};
 
 pub(crate) fn py_int_to_bn(
     py: pyo3::Python<'_>,
@@ -113,25 +120,56 @@ pub(crate) fn pkey_private_bytes<'p>(
         ));
     }
 
+    if !password.is_empty() && cryptography_openssl::fips::is_enabled() {
+        check_fips_encryption(py, encoding, format, encryption_algorithm)?;
+    }
+
     if format.is(types::PRIVATE_FORMAT_PKCS8.get(py)?) {
+        let (kdf, cipher) =
+            if encryption_algorithm.is_instance(types::ENCRYPTION_BUILDER.get(py)?)? {
+                (
+                    private_key_kdf(py, encryption_algorithm)?,
+                    private_key_cipher(
+                        py,
+                        encryption_algorithm.getattr(pyo3::intern!(py, "_cipher"))?,
+                    )?,
+                )
+            } else {
+                (None, pbes2::Cipher::Aes256Cbc)
+            };
+        if let Some(kdf) = kdf {
+            let der_bytes = pbes2::encrypt_private_key_info(
+                &pkey.private_key_to_pkcs8()?,
+                password,
+                &kdf,
+                cipher,
+            )?;
+            if encoding.is(types::ENCODING_PEM.get(py)?) {
+                let pem_bytes = pem::encode_config(
+                    &pem::Pem::new("ENCRYPTED PRIVATE KEY", der_bytes),
+                    pem::EncodeConfig::new().set_line_ending(pem::LineEnding::LF),
+                );
+                return Ok(pyo3::types::PyBytes::new(py, pem_bytes.as_bytes()));
+            } else if encoding.is(types::ENCODING_DER.get(py)?) {
+                return Ok(pyo3::types::PyBytes::new(py, &der_bytes));
+            }
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("Unsupported encoding for PKCS8"),
+            ));
+        }
+
         if encoding.is(types::ENCODING_PEM.get(py)?) {
             let pem_bytes = if password.is_empty() {
                 pkey.private_key_to_pem_pkcs8()?
             } else {
-                pkey.private_key_to_pem_pkcs8_passphrase(
-                    openssl::symm::Cipher::aes_256_cbc(),
-                    password,
-                )?
+                pkey.private_key_to_pem_pkcs8_passphrase(cipher.openssl_cipher(), password)?
             };
             return Ok(pyo3::types::PyBytes::new(py, &pem_bytes));
         } else if encoding.is(types::ENCODING_DER.get(py)?) {
             let der_bytes = if password.is_empty() {
                 pkey.private_key_to_pkcs8()?
             } else {
-                pkey.private_key_to_pkcs8_passphrase(
-                    openssl::symm::Cipher::aes_256_cbc(),
-                    password,
-                )?
+                pkey.private_key_to_pkcs8_passphrase(cipher.openssl_cipher(), password)?
             };
             return Ok(pyo3::types::PyBytes::new(py, &der_bytes));
         }
@@ -140,6 +178,29 @@ pub(crate) fn pkey_private_bytes<'p>(
         ));
     }
 
+    if format.is(types::PRIVATE_FORMAT_ONE_ASYMMETRIC_KEY.get(py)?) {
+        if !password.is_empty() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "Encryption is not supported for OneAsymmetricKey private keys",
+                ),
+            ));
+        }
+        if encoding.is(types::ENCODING_PEM.get(py)?) {
+            let pem_bytes = pem::encode_config(
+                &pem::Pem::new("PRIVATE KEY", one_asymmetric_key_der(pkey)?),
+                pem::EncodeConfig::new().set_line_ending(pem::LineEnding::LF),
+            );
+            return Ok(pyo3::types::PyBytes::new(py, pem_bytes.as_bytes()));
+        } else if encoding.is(types::ENCODING_DER.get(py)?) {
+            let der_bytes = one_asymmetric_key_der(pkey)?;
+            return Ok(pyo3::types::PyBytes::new(py, &der_bytes));
+        }
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("Unsupported encoding for PKCS8"),
+        ));
+    }
+
     if format.is(types::PRIVATE_FORMAT_TRADITIONAL_OPENSSL.get(py)?) {
         if let Ok(rsa) = pkey.rsa() {
             if encoding.is(types::ENCODING_PEM.get(py)?) {
@@ -187,7 +248,7 @@ pub(crate) fn pkey_private_bytes<'p>(
                 let der_bytes = dsa.private_key_to_der()?;
                 return Ok(pyo3::types::PyBytes::new(py, &der_bytes));
             }
-        } else if let Ok(ec) = pkey.ec_key() {
+        } else if let Ok(ec) = ec_private_key(pkey) {
             if encoding.is(types::ENCODING_PEM.get(py)?) {
                 let pem_bytes = if password.is_empty() {
                     ec.private_key_to_pem()?
@@ -234,6 +295,158 @@ pub(crate) fn pkey_private_bytes<'p>(
     ))
 }
 
+// The fewest PBKDF2 iterations the FIPS provider accepts.
+const FIPS_MIN_PBKDF2_ITERATIONS: u64 = 1000;
+
+// In FIPS mode, OpenSSL refuses to encrypt keys with algorithms that aren't
+// approved, but only reports that as a generic error from deep inside the
+// encoder, so the request is checked up front instead.
+// Keys of OpenSSL's SM2 type are EC keys as far as their encodings go, but
+// `PKey::ec_key` only works on keys of the EC type.
+fn ec_private_key(
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+) -> CryptographyResult<openssl::ec::EcKey<openssl::pkey::Private>> {
+    #[cfg(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER)]
+    if cryptography_openssl::sm2::is_sm2(pkey) {
+        return crate::backend::sm2::private_ec_key(pkey);
+    }
+    Ok(pkey.ec_key()?)
+}
+
+fn ec_public_key(
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Public>,
+) -> CryptographyResult<openssl::ec::EcKey<openssl::pkey::Public>> {
+    #[cfg(CRYPTOGRAPHY_OPENSSL_300_OR_GREATER)]
+    if cryptography_openssl::sm2::is_sm2(pkey) {
+        return crate::backend::sm2::public_ec_key(pkey);
+    }
+    Ok(pkey.ec_key()?)
+}
+
+fn check_fips_encryption(
+    py: pyo3::Python<'_>,
+    encoding: &pyo3::PyAny,
+    format: &pyo3::PyAny,
+    encryption_algorithm: &pyo3::PyAny,
+) -> CryptographyResult<()> {
+    let violation = if format.is(types::PRIVATE_FORMAT_TRADITIONAL_OPENSSL.get(py)?) {
+        if !encoding.is(types::ENCODING_PEM.get(py)?) {
+            return Ok(());
+        }
+        "encrypted TraditionalOpenSSL keys derive their key with MD5".to_string()
+    } else if format.is(types::PRIVATE_FORMAT_PKCS8.get(py)?)
+        && encryption_algorithm.is_instance(types::ENCRYPTION_BUILDER.get(py)?)?
+    {
+        let rounds = encryption_algorithm
+            .getattr(pyo3::intern!(py, "_kdf_rounds"))?
+            .extract::<Option<u64>>()?;
+        if !encryption_algorithm
+            .getattr(pyo3::intern!(py, "_scrypt"))?
+            .is_none()
+        {
+            "scrypt is not an approved key derivation function".to_string()
+        } else if let Some(rounds) = rounds.filter(|r| *r < FIPS_MIN_PBKDF2_ITERATIONS) {
+            format!(
+                "PBKDF2 requires at least {FIPS_MIN_PBKDF2_ITERATIONS} iterations, but {rounds} were requested"
+            )
+        } else {
+            return Ok(());
+        }
+    } else {
+        return Ok(());
+    };
+
+    Err(CryptographyError::from(exceptions::FIPSViolation::new_err(
+        format!("This key encryption is not allowed in FIPS mode: {violation}."),
+    )))
+}
+
+// Encodes the key as a version 2 PKCS#8 OneAsymmetricKey (RFC 5958), which
+// includes the public key alongside the private key.
+fn one_asymmetric_key_der(
+    pkey: &openssl::pkey::PKey<openssl::pkey::Private>,
+) -> CryptographyResult<Vec<u8>> {
+    let pkcs8_der = pkey.private_key_to_pkcs8()?;
+    let spki_der = pkey.public_key_to_der()?;
+    let info = asn1::parse_single::<PrivateKeyInfo<'_>>(&pkcs8_der)?;
+    let spki = asn1::parse_single::<SubjectPublicKeyInfo<'_>>(&spki_der)?;
+    Ok(asn1::write_single(&PrivateKeyInfo {
+        version: 1,
+        public_key: Some(spki.subject_public_key),
+        ..info
+    })?)
+}
+
+// The PBKDF2 iteration count OpenSSL uses for PKCS#8 keys, used when only
+// the PRF is chosen.
+const PBKDF2_DEFAULT_ITERATIONS: u64 = 2048;
+
+// Returns the KDF chosen with KeySerializationEncryptionBuilder for a PKCS#8
+// key, or `None` if the builder's defaults are used, in which case OpenSSL
+// encrypts the key itself.
+fn private_key_kdf(
+    py: pyo3::Python<'_>,
+    encryption_algorithm: &pyo3::PyAny,
+) -> CryptographyResult<Option<pbes2::Kdf>> {
+    if let Some((n, r, p)) = encryption_algorithm
+        .getattr(pyo3::intern!(py, "_scrypt"))?
+        .extract::<Option<(u64, u64, u64)>>()?
+    {
+        return Ok(Some(pbes2::Kdf::Scrypt { n, r, p }));
+    }
+
+    let iterations = encryption_algorithm
+        .getattr(pyo3::intern!(py, "_kdf_rounds"))?
+        .extract::<Option<u64>>()?;
+    let prf = encryption_algorithm.getattr(pyo3::intern!(py, "_pbkdf2_prf"))?;
+    if iterations.is_none() && prf.is_none() {
+        return Ok(None);
+    }
+    // OpenSSL also defaults to hmacWithSHA256.
+    let prf = if prf.is_none() {
+        pbes2::Prf::HmacSha256
+    } else {
+        match prf.getattr(pyo3::intern!(py, "name"))?.extract::<&str>()? {
+            "sha1" => pbes2::Prf::HmacSha1,
+            "sha224" => pbes2::Prf::HmacSha224,
+            "sha256" => pbes2::Prf::HmacSha256,
+            "sha384" => pbes2::Prf::HmacSha384,
+            "sha512" => pbes2::Prf::HmacSha512,
+            _ => {
+                return Err(CryptographyError::from(
+                    exceptions::UnsupportedAlgorithm::new_err((
+                        "Unsupported PBKDF2 PRF hash algorithm",
+                        exceptions::Reasons::UNSUPPORTED_HASH,
+                    )),
+                ))
+            }
+        }
+    };
+    Ok(Some(pbes2::Kdf::Pbkdf2 {
+        iterations: iterations.unwrap_or(PBKDF2_DEFAULT_ITERATIONS),
+        prf,
+    }))
+}
+
+// Maps the cipher chosen with KeySerializationEncryptionBuilder.cipher() to
+// the PBES2 cipher, defaulting to AES-256-CBC.
+fn private_key_cipher(
+    py: pyo3::Python<'_>,
+    cipher: &pyo3::PyAny,
+) -> CryptographyResult<pbes2::Cipher> {
+    if cipher.is_none() || cipher.is(types::PRIVATE_KEY_CIPHER_AES256CBC.get(py)?) {
+        Ok(pbes2::Cipher::Aes256Cbc)
+    } else if cipher.is(types::PRIVATE_KEY_CIPHER_AES128CBC.get(py)?) {
+        Ok(pbes2::Cipher::Aes128Cbc)
+    } else {
+        Err(CryptographyError::from(
+            pyo3::exceptions::PyTypeError::new_err(
+                "cipher must be an item from the PrivateKeyCipher enum",
+            ),
+        ))
+    }
+}
+
 pub(crate) fn pkey_public_bytes<'p>(
     py: pyo3::Python<'p>,
     key_obj: &pyo3::PyAny,
@@ -291,7 +504,7 @@ pub(crate) fn pkey_public_bytes<'p>(
         ));
     }
 
-    if let Ok(ec) = pkey.ec_key() {
+    if let Ok(ec) = ec_public_key(pkey) {
         if encoding.is(types::ENCODING_X962.get(py)?) {
             let point_form = if format.is(types::PUBLIC_FORMAT_UNCOMPRESSED_POINT.get(py)?) {
                 openssl::ec::PointConversionForm::UNCOMPRESSED
@@ -350,6 +563,103 @@ pub(crate) fn pkey_public_bytes<'p>(
     ))
 }
 
+// Compares two private keys by their key material, rather than by any
+// particular serialization of them. The private components are compared in
+// constant time.
+pub(crate) fn pkey_private_eq(
+    a: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+    b: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+) -> CryptographyResult<bool> {
+    if a.id() != b.id() || !a.public_eq(b) {
+        return Ok(false);
+    }
+    let padded_eq = |x: &openssl::bn::BigNumRef,
+                     y: &openssl::bn::BigNumRef,
+                     len: i32|
+     -> CryptographyResult<bool> {
+        Ok(openssl::memcmp::eq(
+            &x.to_vec_padded(len)?,
+            &y.to_vec_padded(len)?,
+        ))
+    };
+    let rsa_eq = || -> CryptographyResult<bool> {
+        let (a, b) = (a.rsa()?, b.rsa()?);
+        let len = a.n().num_bytes();
+        Ok(match (a.p(), a.q(), b.p(), b.q()) {
+            // The same key may have its primes in either order.
+            (Some(p1), Some(q1), Some(p2), Some(q2)) => {
+                (padded_eq(p1, p2, len)? & padded_eq(q1, q2, len)?)
+                    | (padded_eq(p1, q2, len)? & padded_eq(q1, p2, len)?)
+            }
+            _ => padded_eq(a.d(), b.d(), len)?,
+        })
+    };
+    Ok(match a.id() {
+        openssl::pkey::Id::RSA => rsa_eq()?,
+        #[cfg(any(not(CRYPTOGRAPHY_IS_LIBRESSL), CRYPTOGRAPHY_LIBRESSL_380_OR_GREATER))]
+        openssl::pkey::Id::RSA_PSS => rsa_eq()?,
+        openssl::pkey::Id::EC => {
+            let (a, b) = (a.ec_key()?, b.ec_key()?);
+            let len = (a.group().order_bits() as i32 + 7) / 8;
+            padded_eq(a.private_key(), b.private_key(), len)?
+        }
+        openssl::pkey::Id::DSA => {
+            let (a, b) = (a.dsa()?, b.dsa()?);
+            padded_eq(a.priv_key(), b.priv_key(), a.p().num_bytes())?
+        }
+        openssl::pkey::Id::DH | openssl::pkey::Id::DHX => {
+            let (a, b) = (a.dh()?, b.dh()?);
+            padded_eq(a.private_key(), b.private_key(), a.prime_p().num_bytes())?
+        }
+        openssl::pkey::Id::ED25519
+        | openssl::pkey::Id::ED448
+        | openssl::pkey::Id::X25519
+        | openssl::pkey::Id::X448 => {
+            openssl::memcmp::eq(&a.raw_private_key()?, &b.raw_private_key()?)
+        }
+        // Other keys are determined by their public key.
+        _ => true,
+    })
+}
+
+// Hashes a key by its public key, so that keys which compare equal with
+// `pkey_private_eq` have the same hash.
+pub(crate) fn pkey_hash<T: openssl::pkey::HasPublic>(
+    pkey: &openssl::pkey::PKeyRef<T>,
+) -> CryptographyResult<u64> {
+    let mut hasher = DefaultHasher::new();
+    pkey.public_key_to_der()?.hash(&mut hasher);
+    Ok(hasher.finish())
+}
+
+// Hashes the DER encoded SubjectPublicKeyInfo of a key with `algorithm`.
+pub(crate) fn pkey_fingerprint<'p, T: openssl::pkey::HasPublic>(
+    py: pyo3::Python<'p>,
+    pkey: &openssl::pkey::PKeyRef<T>,
+    algorithm: &pyo3::PyAny,
+) -> CryptographyResult<&'p pyo3::PyAny> {
+    let mut h = Hash::new(py, algorithm, None)?;
+    h.update_bytes(&pkey.public_key_to_der()?)?;
+    Ok(h.finalize(py)?)
+}
+
+// Checks whether `public_key`, or the public key of a certificate, is the
+// public key of `private_key`. Anything else never matches.
+pub(crate) fn public_key_matches(
+    py: pyo3::Python<'_>,
+    private_key: &pyo3::PyAny,
+    public_key: &pyo3::PyAny,
+) -> CryptographyResult<bool> {
+    let public_key = if public_key.is_instance_of::<crate::x509::certificate::Certificate>() {
+        public_key.call_method0(pyo3::intern!(py, "public_key"))?
+    } else {
+        public_key
+    };
+    Ok(private_key
+        .call_method0(pyo3::intern!(py, "public_key"))?
+        .eq(public_key)?)
+}
+
 pub(crate) fn calculate_digest_and_algorithm<'p>(
     py: pyo3::Python<'p>,
     mut data: &'p [u8],
@@ -439,3 +749,60 @@ pub(crate) fn handle_key_load_result<T>(
         }
     }
 }
+
+// Generates DH or DSA parameters without holding the GIL, which can take
+// minutes for large keys. `progress` is called with OpenSSL's progress
+// indicators, and generation stops once `cancel.is_set()` returns true.
+// Checking either one reacquires the GIL, so it's only done when they're
+// provided.
+pub(crate) fn generate_params_without_gil<T: Send>(
+    py: pyo3::Python<'_>,
+    progress: Option<&pyo3::PyAny>,
+    cancel: Option<&pyo3::PyAny>,
+    generate: impl FnOnce(&mut dyn FnMut(i32, i32) -> bool) -> Result<T, openssl::error::ErrorStack>
+        + Send,
+) -> CryptographyResult<Result<T, openssl::error::ErrorStack>> {
+    let progress = progress.map(pyo3::PyObject::from);
+    let cancel = cancel.map(pyo3::PyObject::from);
+    let mut error = None;
+    let mut cancelled = false;
+
+    let result = py.allow_threads(|| {
+        generate(&mut |stage, count| {
+            if progress.is_none() && cancel.is_none() {
+                return true;
+            }
+            pyo3::Python::with_gil(|py| {
+                let r = (|| -> pyo3::PyResult<bool> {
+                    if let Some(cancel) = &cancel {
+                        if cancel
+                            .call_method0(py, pyo3::intern!(py, "is_set"))?
+                            .is_true(py)?
+                        {
+                            cancelled = true;
+                            return Ok(false);
+                        }
+                    }
+                    if let Some(progress) = &progress {
+                        progress.call1(py, (stage, count))?;
+                    }
+                    Ok(true)
+                })();
+                r.unwrap_or_else(|e| {
+                    error = Some(e);
+                    false
+                })
+            })
+        })
+    });
+
+    if let Some(e) = error {
+        return Err(CryptographyError::from(e));
+    }
+    if cancelled {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("Parameter generation was cancelled"),
+        ));
+    }
+    Ok(result)
+}
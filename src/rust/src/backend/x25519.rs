@@ -2,18 +2,19 @@
 // 2.0, and the BSD License. See the LICENSE file in the root of this repository
 // for complete details.
 
-use crate::backend::utils;
+use crate::backend::{ed25519, jwk, utils, xeddsa};
 use crate::buf::CffiBuf;
-use crate::error::CryptographyResult;
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::exceptions;
 
 #[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.x25519")]
 pub(crate) struct X25519PrivateKey {
-    pkey: openssl::pkey::PKey<openssl::pkey::Private>,
+    pub(crate) pkey: openssl::pkey::PKey<openssl::pkey::Private>,
 }
 
 #[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.x25519")]
 pub(crate) struct X25519PublicKey {
-    pkey: openssl::pkey::PKey<openssl::pkey::Public>,
+    pub(crate) pkey: openssl::pkey::PKey<openssl::pkey::Public>,
 }
 
 #[pyo3::prelude::pyfunction]
@@ -51,6 +52,37 @@ fn from_private_bytes(data: CffiBuf<'_>) -> pyo3::PyResult<X25519PrivateKey> {
     Ok(X25519PrivateKey { pkey })
 }
 
+// Clamps a scalar as described in RFC 7748 section 5, which X25519 always
+// does before using it.
+pub(crate) fn clamp_scalar(scalar: &mut [u8]) {
+    scalar[0] &= 248;
+    scalar[31] &= 127;
+    scalar[31] |= 64;
+}
+
+#[pyo3::prelude::pyfunction]
+#[pyo3(signature = (data, *, clamp=true))]
+fn from_scalar(data: CffiBuf<'_>, clamp: bool) -> CryptographyResult<X25519PrivateKey> {
+    let scalar = data.as_bytes();
+    if scalar.len() != 32 {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("An X25519 scalar is 32 bytes long"),
+        ));
+    }
+    let mut clamped = scalar.to_vec();
+    clamp_scalar(&mut clamped);
+    if !clamp && clamped != scalar {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(
+                "The scalar isn't clamped, and X25519 always clamps the scalar it uses",
+            ),
+        ));
+    }
+    let pkey =
+        openssl::pkey::PKey::private_key_from_raw_bytes(&clamped, openssl::pkey::Id::X25519)?;
+    Ok(X25519PrivateKey { pkey })
+}
+
 #[pyo3::prelude::pyfunction]
 fn from_public_bytes(data: &[u8]) -> pyo3::PyResult<X25519PublicKey> {
     let pkey = openssl::pkey::PKey::public_key_from_raw_bytes(data, openssl::pkey::Id::X25519)
@@ -79,6 +111,34 @@ impl X25519PrivateKey {
         })?)
     }
 
+    // Releases the GIL for the whole batch, so that a fan-out to many peers
+    // costs one call rather than one per peer.
+    fn exchange_many<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        peer_keys: Vec<pyo3::PyRef<'_, X25519PublicKey>>,
+    ) -> CryptographyResult<&'p pyo3::types::PyList> {
+        let peers = peer_keys.iter().map(|k| &k.pkey).collect::<Vec<_>>();
+        let shared_keys = py.allow_threads(|| {
+            peers
+                .iter()
+                .map(|peer| {
+                    let mut deriver = openssl::derive::Deriver::new(&self.pkey)?;
+                    deriver.set_peer(peer)?;
+                    deriver.derive_to_vec().map_err(|_| {
+                        CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+                            "Error computing shared key.",
+                        ))
+                    })
+                })
+                .collect::<CryptographyResult<Vec<_>>>()
+        })?;
+        Ok(pyo3::types::PyList::new(
+            py,
+            shared_keys.iter().map(|k| pyo3::types::PyBytes::new(py, k)),
+        ))
+    }
+
     fn public_key(&self) -> CryptographyResult<X25519PublicKey> {
         let raw_bytes = self.pkey.raw_public_key()?;
         Ok(X25519PublicKey {
@@ -97,6 +157,36 @@ impl X25519PrivateKey {
         Ok(pyo3::types::PyBytes::new(py, &raw_bytes))
     }
 
+    fn clamped_scalar<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let mut scalar = self.pkey.raw_private_key()?;
+        clamp_scalar(&mut scalar);
+        Ok(pyo3::types::PyBytes::new(py, &scalar))
+    }
+
+    fn xeddsa_sign<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        data: CffiBuf<'_>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let mut random = [0; 64];
+        openssl::rand::rand_bytes(&mut random)?;
+        let signature = xeddsa::sign(&self.pkey.raw_private_key()?, data.as_bytes(), &random)?;
+        Ok(pyo3::types::PyBytes::new(py, &signature))
+    }
+
+    #[pyo3(signature = (kid=None))]
+    fn jwk_private_bytes<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        kid: Option<&str>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let jwk = jwk::private_jwk(&self.pkey, kid)?;
+        Ok(pyo3::types::PyBytes::new(py, jwk.as_bytes()))
+    }
+
     fn private_bytes<'p>(
         slf: &pyo3::PyCell<Self>,
         py: pyo3::Python<'p>,
@@ -115,6 +205,22 @@ impl X25519PrivateKey {
             true,
         )
     }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> CryptographyResult<bool> {
+        utils::pkey_private_eq(&self.pkey, &other.pkey)
+    }
+
+    fn __hash__(&self) -> CryptographyResult<u64> {
+        utils::pkey_hash(&self.pkey)
+    }
+
+    fn public_key_matches(
+        slf: &pyo3::PyCell<Self>,
+        py: pyo3::Python<'_>,
+        public_key: &pyo3::PyAny,
+    ) -> CryptographyResult<bool> {
+        utils::public_key_matches(py, slf, public_key)
+    }
 }
 
 #[pyo3::prelude::pymethods]
@@ -127,6 +233,33 @@ impl X25519PublicKey {
         Ok(pyo3::types::PyBytes::new(py, &raw_bytes))
     }
 
+    fn to_ed25519_public_key(&self) -> CryptographyResult<ed25519::Ed25519PublicKey> {
+        ed25519::public_key_from_x25519(&self.pkey.raw_public_key()?)
+    }
+
+    fn xeddsa_verify(&self, signature: CffiBuf<'_>, data: CffiBuf<'_>) -> CryptographyResult<()> {
+        if !xeddsa::verify(
+            &self.pkey.raw_public_key()?,
+            signature.as_bytes(),
+            data.as_bytes(),
+        )? {
+            return Err(CryptographyError::from(
+                exceptions::InvalidSignature::new_err(()),
+            ));
+        }
+        Ok(())
+    }
+
+    #[pyo3(signature = (kid=None))]
+    fn jwk_public_bytes<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        kid: Option<&str>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let jwk = jwk::public_jwk(&self.pkey, kid)?;
+        Ok(pyo3::types::PyBytes::new(py, jwk.as_bytes()))
+    }
+
     fn public_bytes<'p>(
         slf: &pyo3::PyCell<Self>,
         py: pyo3::Python<'p>,
@@ -136,6 +269,23 @@ impl X25519PublicKey {
         utils::pkey_public_bytes(py, slf, &slf.borrow().pkey, encoding, format, false, true)
     }
 
+    fn fingerprint<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::PyAny> {
+        utils::pkey_fingerprint(py, &self.pkey, algorithm)
+    }
+
+    #[pyo3(signature = (algorithm=None))]
+    fn jwk_thumbprint<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        algorithm: Option<&pyo3::PyAny>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        jwk::public_jwk_thumbprint(py, &self.pkey, algorithm)
+    }
+
     fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> bool {
         self.pkey.public_eq(&other.pkey)
     }
@@ -149,6 +299,7 @@ pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelu
     let m = pyo3::prelude::PyModule::new(py, "x25519")?;
     m.add_function(pyo3::wrap_pyfunction!(generate_key, m)?)?;
     m.add_function(pyo3::wrap_pyfunction!(from_private_bytes, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(from_scalar, m)?)?;
     m.add_function(pyo3::wrap_pyfunction!(from_public_bytes, m)?)?;
 
     m.add_class::<X25519PrivateKey>()?;
@@ -2,18 +2,18 @@
 // 2.0, and the BSD License. See the LICENSE file in the root of this repository
 // for complete details.
 
-use crate::backend::utils;
+use crate::backend::{jwk, utils};
 use crate::buf::CffiBuf;
-use crate::error::CryptographyResult;
+use crate::error::{CryptographyError, CryptographyResult};
 
 #[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.x448")]
 pub(crate) struct X448PrivateKey {
-    pkey: openssl::pkey::PKey<openssl::pkey::Private>,
+    pub(crate) pkey: openssl::pkey::PKey<openssl::pkey::Private>,
 }
 
 #[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.x448")]
 pub(crate) struct X448PublicKey {
-    pkey: openssl::pkey::PKey<openssl::pkey::Public>,
+    pub(crate) pkey: openssl::pkey::PKey<openssl::pkey::Public>,
 }
 
 #[pyo3::prelude::pyfunction]
@@ -50,6 +50,35 @@ fn from_private_bytes(data: CffiBuf<'_>) -> pyo3::PyResult<X448PrivateKey> {
         })?;
     Ok(X448PrivateKey { pkey })
 }
+// Clamps a scalar as described in RFC 7748 section 5, which X448 always
+// does before using it.
+pub(crate) fn clamp_scalar(scalar: &mut [u8]) {
+    scalar[0] &= 252;
+    scalar[55] |= 128;
+}
+
+#[pyo3::prelude::pyfunction]
+#[pyo3(signature = (data, *, clamp=true))]
+fn from_scalar(data: CffiBuf<'_>, clamp: bool) -> CryptographyResult<X448PrivateKey> {
+    let scalar = data.as_bytes();
+    if scalar.len() != 56 {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("An X448 scalar is 56 bytes long"),
+        ));
+    }
+    let mut clamped = scalar.to_vec();
+    clamp_scalar(&mut clamped);
+    if !clamp && clamped != scalar {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(
+                "The scalar isn't clamped, and X448 always clamps the scalar it uses",
+            ),
+        ));
+    }
+    let pkey = openssl::pkey::PKey::private_key_from_raw_bytes(&clamped, openssl::pkey::Id::X448)?;
+    Ok(X448PrivateKey { pkey })
+}
+
 #[pyo3::prelude::pyfunction]
 fn from_public_bytes(data: &[u8]) -> pyo3::PyResult<X448PublicKey> {
     let pkey = openssl::pkey::PKey::public_key_from_raw_bytes(data, openssl::pkey::Id::X448)
@@ -96,6 +125,25 @@ impl X448PrivateKey {
         Ok(pyo3::types::PyBytes::new(py, &raw_bytes))
     }
 
+    fn clamped_scalar<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let mut scalar = self.pkey.raw_private_key()?;
+        clamp_scalar(&mut scalar);
+        Ok(pyo3::types::PyBytes::new(py, &scalar))
+    }
+
+    #[pyo3(signature = (kid=None))]
+    fn jwk_private_bytes<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        kid: Option<&str>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let jwk = jwk::private_jwk(&self.pkey, kid)?;
+        Ok(pyo3::types::PyBytes::new(py, jwk.as_bytes()))
+    }
+
     fn private_bytes<'p>(
         slf: &pyo3::PyCell<Self>,
         py: pyo3::Python<'p>,
@@ -114,6 +162,22 @@ impl X448PrivateKey {
             true,
         )
     }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> CryptographyResult<bool> {
+        utils::pkey_private_eq(&self.pkey, &other.pkey)
+    }
+
+    fn __hash__(&self) -> CryptographyResult<u64> {
+        utils::pkey_hash(&self.pkey)
+    }
+
+    fn public_key_matches(
+        slf: &pyo3::PyCell<Self>,
+        py: pyo3::Python<'_>,
+        public_key: &pyo3::PyAny,
+    ) -> CryptographyResult<bool> {
+        utils::public_key_matches(py, slf, public_key)
+    }
 }
 
 #[pyo3::prelude::pymethods]
@@ -126,6 +190,16 @@ impl X448PublicKey {
         Ok(pyo3::types::PyBytes::new(py, &raw_bytes))
     }
 
+    #[pyo3(signature = (kid=None))]
+    fn jwk_public_bytes<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        kid: Option<&str>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let jwk = jwk::public_jwk(&self.pkey, kid)?;
+        Ok(pyo3::types::PyBytes::new(py, jwk.as_bytes()))
+    }
+
     fn public_bytes<'p>(
         slf: &pyo3::PyCell<Self>,
         py: pyo3::Python<'p>,
@@ -135,6 +209,23 @@ impl X448PublicKey {
         utils::pkey_public_bytes(py, slf, &slf.borrow().pkey, encoding, format, false, true)
     }
 
+    fn fingerprint<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        algorithm: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::PyAny> {
+        utils::pkey_fingerprint(py, &self.pkey, algorithm)
+    }
+
+    #[pyo3(signature = (algorithm=None))]
+    fn jwk_thumbprint<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        algorithm: Option<&pyo3::PyAny>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        jwk::public_jwk_thumbprint(py, &self.pkey, algorithm)
+    }
+
     fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> bool {
         self.pkey.public_eq(&other.pkey)
     }
@@ -148,6 +239,7 @@ pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelu
     let m = pyo3::prelude::PyModule::new(py, "x448")?;
     m.add_function(pyo3::wrap_pyfunction!(generate_key, m)?)?;
     m.add_function(pyo3::wrap_pyfunction!(from_private_bytes, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(from_scalar, m)?)?;
     m.add_function(pyo3::wrap_pyfunction!(from_public_bytes, m)?)?;
 
     m.add_class::<X448PrivateKey>()?;
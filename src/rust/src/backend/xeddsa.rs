@@ -0,0 +1,261 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! XEdDSA signatures with X25519 keys, as described in
+//! https://signal.org/docs/specifications/xeddsa/
+//!
+//! Signing needs `k * B` on edwards25519 for an arbitrary scalar `k`, which
+//! OpenSSL's X25519 and Ed25519 APIs can't do because they clamp or hash the
+//! private key first. Instead the multiplications are done on the short
+//! Weierstrass form of Curve25519, where `EC_POINT_mul` uses a constant time
+//! ladder, and the results are mapped to edwards25519. Verification is
+//! Ed25519 verification with the public key converted from the Montgomery u
+//! coordinate.
+
+use crate::backend::{ed25519, x25519};
+use crate::error::{CryptographyError, CryptographyResult};
+
+// The Montgomery curve coefficient A.
+const MONTGOMERY_A: u32 = 486662;
+
+// hash_1 from the XEdDSA specification prefixes its input with 2^256 - 2
+// encoded as 32 little endian bytes.
+const HASH1_PREFIX: [u8; 32] = {
+    let mut prefix = [0xff; 32];
+    prefix[0] = 0xfe;
+    prefix
+};
+
+// q = 2^252 + 27742317777372353535851937790883648493, the order of the base
+// point.
+fn group_order() -> CryptographyResult<openssl::bn::BigNum> {
+    Ok(openssl::bn::BigNum::from_hex_str(
+        "1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ed",
+    )?)
+}
+
+fn mod_div(
+    a: &openssl::bn::BigNumRef,
+    b: &openssl::bn::BigNumRef,
+    p: &openssl::bn::BigNumRef,
+    bn_ctx: &mut openssl::bn::BigNumContextRef,
+) -> CryptographyResult<openssl::bn::BigNum> {
+    let mut inverse = openssl::bn::BigNum::new()?;
+    inverse.mod_inverse(b, p, bn_ctx)?;
+    let mut result = openssl::bn::BigNum::new()?;
+    result.mod_mul(a, &inverse, p, bn_ctx)?;
+    Ok(result)
+}
+
+// Decodes a little endian integer and reduces it modulo `q`.
+fn decode_scalar(
+    data: &[u8],
+    q: &openssl::bn::BigNumRef,
+    bn_ctx: &mut openssl::bn::BigNumContextRef,
+) -> CryptographyResult<openssl::bn::BigNum> {
+    let mut be = data.to_vec();
+    be.reverse();
+    let value = openssl::bn::BigNum::from_slice(&be)?;
+    let mut scalar = openssl::bn::BigNum::new()?;
+    scalar.nnmod(&value, q, bn_ctx)?;
+    Ok(scalar)
+}
+
+struct Curve {
+    group: openssl::ec::EcGroup,
+    p: openssl::bn::BigNum,
+    q: openssl::bn::BigNum,
+    // A / 3, to convert the Weierstrass x coordinate back to u.
+    a_over_3: openssl::bn::BigNum,
+    // sqrt(-(A + 2)), the scale factor in the map from (u, v) to
+    // edwards25519: x = scale * u / v and y = (u - 1) / (u + 1).
+    scale: openssl::bn::BigNum,
+}
+
+impl Curve {
+    // Builds y^2 = x^3 + a*x + b with a = (3 - A^2) / 3 and
+    // b = (2*A^3 - 9*A) / 27, which is isomorphic to v^2 = u^3 + A*u^2 + u
+    // through x = u + A/3.
+    fn new(bn_ctx: &mut openssl::bn::BigNumContextRef) -> CryptographyResult<Curve> {
+        let p = ed25519::field_prime()?;
+        let big_a = openssl::bn::BigNum::from_u32(MONTGOMERY_A)?;
+        let three = openssl::bn::BigNum::from_u32(3)?;
+        let zero = openssl::bn::BigNum::new()?;
+
+        let mut a_squared = openssl::bn::BigNum::new()?;
+        a_squared.mod_sqr(&big_a, &p, bn_ctx)?;
+        let mut numerator = openssl::bn::BigNum::new()?;
+        numerator.mod_sub(&three, &a_squared, &p, bn_ctx)?;
+        let a = mod_div(&numerator, &three, &p, bn_ctx)?;
+
+        let mut a_cubed = openssl::bn::BigNum::new()?;
+        a_cubed.mod_mul(&a_squared, &big_a, &p, bn_ctx)?;
+        let mut twice_a_cubed = openssl::bn::BigNum::new()?;
+        twice_a_cubed.mod_add(&a_cubed, &a_cubed, &p, bn_ctx)?;
+        let nine = openssl::bn::BigNum::from_u32(9)?;
+        let mut nine_a = openssl::bn::BigNum::new()?;
+        nine_a.mod_mul(&big_a, &nine, &p, bn_ctx)?;
+        numerator.mod_sub(&twice_a_cubed, &nine_a, &p, bn_ctx)?;
+        let twenty_seven = openssl::bn::BigNum::from_u32(27)?;
+        let b = mod_div(&numerator, &twenty_seven, &p, bn_ctx)?;
+
+        let a_over_3 = mod_div(&big_a, &three, &p, bn_ctx)?;
+        let a_plus_2 = openssl::bn::BigNum::from_u32(MONTGOMERY_A + 2)?;
+        let mut minus_a_minus_2 = openssl::bn::BigNum::new()?;
+        minus_a_minus_2.mod_sub(&zero, &a_plus_2, &p, bn_ctx)?;
+        let mut scale = openssl::bn::BigNum::new()?;
+        scale.mod_sqrt(&minus_a_minus_2, &p, bn_ctx)?;
+
+        // The base point has u = 9 and v^2 = u^3 + A*u^2 + u. Either square
+        // root works for v as long as the base point maps to the Ed25519
+        // base point, whose x is even.
+        let u = openssl::bn::BigNum::from_u32(9)?;
+        let mut u_plus_a = openssl::bn::BigNum::new()?;
+        u_plus_a.mod_add(&u, &big_a, &p, bn_ctx)?;
+        let mut u_squared = openssl::bn::BigNum::new()?;
+        u_squared.mod_sqr(&u, &p, bn_ctx)?;
+        let mut rhs = openssl::bn::BigNum::new()?;
+        rhs.mod_mul(&u_squared, &u_plus_a, &p, bn_ctx)?;
+        let mut v_squared = openssl::bn::BigNum::new()?;
+        v_squared.mod_add(&rhs, &u, &p, bn_ctx)?;
+        let mut v = openssl::bn::BigNum::new()?;
+        v.mod_sqrt(&v_squared, &p, bn_ctx)?;
+
+        let mut scaled_u = openssl::bn::BigNum::new()?;
+        scaled_u.mod_mul(&scale, &u, &p, bn_ctx)?;
+        if mod_div(&scaled_u, &v, &p, bn_ctx)?.is_bit_set(0) {
+            let mut negated = openssl::bn::BigNum::new()?;
+            negated.mod_sub(&zero, &v, &p, bn_ctx)?;
+            v = negated;
+        }
+
+        let mut x = openssl::bn::BigNum::new()?;
+        x.mod_add(&u, &a_over_3, &p, bn_ctx)?;
+        let mut group = openssl::ec::EcGroup::from_components(p.to_owned()?, a, b, bn_ctx)?;
+        let mut generator = openssl::ec::EcPoint::new(&group)?;
+        generator.set_affine_coordinates_gfp(&group, &x, &v, bn_ctx)?;
+        group.set_generator(generator, group_order()?, openssl::bn::BigNum::from_u32(8)?)?;
+
+        Ok(Curve {
+            group,
+            p,
+            q: group_order()?,
+            a_over_3,
+            scale,
+        })
+    }
+
+    // Computes `scalar * B` and returns the edwards25519 encoding of the
+    // result, with the sign of x in the top bit.
+    fn mul_base(
+        &self,
+        scalar: &openssl::bn::BigNumRef,
+        bn_ctx: &mut openssl::bn::BigNumContextRef,
+    ) -> CryptographyResult<Vec<u8>> {
+        let mut point = openssl::ec::EcPoint::new(&self.group)?;
+        point.mul_generator(&self.group, scalar, bn_ctx)?;
+        if point.is_infinity(&self.group) {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("Error computing signature."),
+            ));
+        }
+        let mut x = openssl::bn::BigNum::new()?;
+        let mut v = openssl::bn::BigNum::new()?;
+        point.affine_coordinates(&self.group, &mut x, &mut v, bn_ctx)?;
+
+        let p = &self.p;
+        let mut u = openssl::bn::BigNum::new()?;
+        u.mod_sub(&x, &self.a_over_3, p, bn_ctx)?;
+        let one = openssl::bn::BigNum::from_u32(1)?;
+        let mut u_minus_one = openssl::bn::BigNum::new()?;
+        u_minus_one.mod_sub(&u, &one, p, bn_ctx)?;
+        let mut u_plus_one = openssl::bn::BigNum::new()?;
+        u_plus_one.mod_add(&u, &one, p, bn_ctx)?;
+        let y = mod_div(&u_minus_one, &u_plus_one, p, bn_ctx)?;
+        let mut scaled_u = openssl::bn::BigNum::new()?;
+        scaled_u.mod_mul(&self.scale, &u, p, bn_ctx)?;
+        let edwards_x = mod_div(&scaled_u, &v, p, bn_ctx)?;
+
+        let mut encoded = ed25519::encode_field_element(&y)?;
+        if edwards_x.is_bit_set(0) {
+            encoded[31] |= 0x80;
+        }
+        Ok(encoded)
+    }
+}
+
+fn encode_scalar(scalar: &openssl::bn::BigNumRef) -> CryptographyResult<Vec<u8>> {
+    let mut le = scalar.to_vec_padded(32)?;
+    le.reverse();
+    Ok(le)
+}
+
+/// Creates an XEdDSA signature over `data` with the raw X25519 private key
+/// `private_key`, using the 64 random bytes `random`.
+pub(crate) fn sign(
+    private_key: &[u8],
+    data: &[u8],
+    random: &[u8; 64],
+) -> CryptographyResult<Vec<u8>> {
+    let mut bn_ctx = openssl::bn::BigNumContext::new()?;
+    let curve = Curve::new(&mut bn_ctx)?;
+
+    let mut clamped = private_key.to_vec();
+    x25519::clamp_scalar(&mut clamped);
+    let k = decode_scalar(&clamped, &curve.q, &mut bn_ctx)?;
+
+    // calculate_key_pair: the public key always has a positive x, so the
+    // scalar is negated if k * B has a negative one.
+    let mut public_key = curve.mul_base(&k, &mut bn_ctx)?;
+    let a = if public_key[31] & 0x80 != 0 {
+        public_key[31] &= 0x7f;
+        let zero = openssl::bn::BigNum::new()?;
+        let mut negated = openssl::bn::BigNum::new()?;
+        negated.mod_sub(&zero, &k, &curve.q, &mut bn_ctx)?;
+        negated
+    } else {
+        k
+    };
+    let encoded_a = encode_scalar(&a)?;
+
+    let mut h = openssl::sha::Sha512::new();
+    h.update(&HASH1_PREFIX);
+    h.update(&encoded_a);
+    h.update(data);
+    h.update(random);
+    let r = decode_scalar(&h.finish(), &curve.q, &mut bn_ctx)?;
+    let big_r = curve.mul_base(&r, &mut bn_ctx)?;
+
+    let mut h = openssl::sha::Sha512::new();
+    h.update(&big_r);
+    h.update(&public_key);
+    h.update(data);
+    let challenge = decode_scalar(&h.finish(), &curve.q, &mut bn_ctx)?;
+
+    let mut product = openssl::bn::BigNum::new()?;
+    product.mod_mul(&challenge, &a, &curve.q, &mut bn_ctx)?;
+    let mut s = openssl::bn::BigNum::new()?;
+    s.mod_add(&r, &product, &curve.q, &mut bn_ctx)?;
+
+    let mut signature = big_r;
+    signature.extend_from_slice(&encode_scalar(&s)?);
+    Ok(signature)
+}
+
+/// Verifies an XEdDSA signature with the raw X25519 public key `public_key`.
+pub(crate) fn verify(public_key: &[u8], signature: &[u8], data: &[u8]) -> CryptographyResult<bool> {
+    // u must be reduced, and s must fit in 253 bits.
+    let u = ed25519::decode_field_element(public_key)?;
+    if u >= ed25519::field_prime()? || signature.len() != 64 || signature[63] & 0xe0 != 0 {
+        return Ok(false);
+    }
+    let public_key = match ed25519::public_key_from_x25519(public_key) {
+        Ok(public_key) => public_key,
+        Err(_) => return Ok(false),
+    };
+    let valid = openssl::sign::Verifier::new_without_digest(&public_key.pkey)?
+        .verify_oneshot(signature, data)
+        .unwrap_or(false);
+    Ok(valid)
+}
@@ -0,0 +1,236 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! X-Wing, the hybrid of ML-KEM-768 and X25519 described in
+//! https://datatracker.ietf.org/doc/draft-connolly-cfrg-xwing-kem/
+//!
+//! A private key is a 32 byte seed, which is expanded with SHAKE256 into the
+//! ML-KEM-768 seed and the X25519 private key. Public keys and ciphertexts
+//! are the ML-KEM-768 one followed by the X25519 one, and the shared secret
+//! is SHA3-256 over both shared secrets, the X25519 ciphertext and public key,
+//! and a label.
+
+use cryptography_openssl::kem;
+use cryptography_openssl::mlkem::{self, Variant};
+
+use crate::backend::kem::{KemPrivateKey, KemPublicKey};
+use crate::buf::CffiBuf;
+use crate::error::{CryptographyError, CryptographyResult};
+
+const PRIVATE_KEY_LENGTH: usize = 32;
+const X25519_LENGTH: usize = 32;
+const PUBLIC_KEY_LENGTH: usize = 1184 + X25519_LENGTH;
+const CIPHERTEXT_LENGTH: usize = 1088 + X25519_LENGTH;
+
+// XWingLabel, the ASCII art "\./" "/^\".
+const LABEL: &[u8] = b"\\.//^\\";
+
+#[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.xwing")]
+#[derive(Clone)]
+pub(crate) struct XWingPrivateKey {
+    seed: Vec<u8>,
+    mlkem: openssl::pkey::PKey<openssl::pkey::Private>,
+    x25519: openssl::pkey::PKey<openssl::pkey::Private>,
+}
+
+#[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.openssl.xwing")]
+#[derive(Clone)]
+pub(crate) struct XWingPublicKey {
+    mlkem: openssl::pkey::PKey<openssl::pkey::Public>,
+    x25519: openssl::pkey::PKey<openssl::pkey::Public>,
+}
+
+fn private_key_from_seed(seed: &[u8]) -> CryptographyResult<XWingPrivateKey> {
+    let mut expanded = [0; mlkem::SEED_LENGTH + X25519_LENGTH];
+    let mut h = openssl::hash::Hasher::new(openssl::hash::MessageDigest::shake_256())?;
+    h.update(seed)?;
+    h.finish_xof(&mut expanded)?;
+    let (mlkem_seed, x25519_key) = expanded.split_at(mlkem::SEED_LENGTH);
+    Ok(XWingPrivateKey {
+        seed: seed.to_vec(),
+        mlkem: mlkem::private_key_from_seed(Variant::MlKem768, mlkem_seed)?,
+        x25519: openssl::pkey::PKey::private_key_from_raw_bytes(
+            x25519_key,
+            openssl::pkey::Id::X25519,
+        )?,
+    })
+}
+
+// Fails if the result is all zeros, i.e. `public` is a small order point.
+fn x25519<T: openssl::pkey::HasPublic>(
+    private: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+    public: &openssl::pkey::PKeyRef<T>,
+) -> CryptographyResult<Vec<u8>> {
+    let mut deriver = openssl::derive::Deriver::new(private)?;
+    deriver.set_peer(public)?;
+    deriver.derive_to_vec().map_err(|_| {
+        CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+            "Error computing shared key.",
+        ))
+    })
+}
+
+fn combine(
+    mlkem_secret: &[u8],
+    x25519_secret: &[u8],
+    x25519_ciphertext: &[u8],
+    x25519_public_key: &[u8],
+) -> CryptographyResult<Vec<u8>> {
+    let mut h = openssl::hash::Hasher::new(openssl::hash::MessageDigest::sha3_256())?;
+    h.update(mlkem_secret)?;
+    h.update(x25519_secret)?;
+    h.update(x25519_ciphertext)?;
+    h.update(x25519_public_key)?;
+    h.update(LABEL)?;
+    Ok(h.finish()?.to_vec())
+}
+
+impl KemPrivateKey for XWingPrivateKey {
+    fn decapsulate(&self, ciphertext: &[u8]) -> CryptographyResult<Vec<u8>> {
+        if ciphertext.len() != CIPHERTEXT_LENGTH {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("An X-Wing ciphertext is 1120 bytes long"),
+            ));
+        }
+        let (mlkem_ciphertext, x25519_ciphertext) =
+            ciphertext.split_at(CIPHERTEXT_LENGTH - X25519_LENGTH);
+        let mlkem_secret = kem::decapsulate(&self.mlkem, None, mlkem_ciphertext)?;
+        let peer = openssl::pkey::PKey::public_key_from_raw_bytes(
+            x25519_ciphertext,
+            openssl::pkey::Id::X25519,
+        )?;
+        let x25519_secret = x25519(&self.x25519, &peer)?;
+        combine(
+            &mlkem_secret,
+            &x25519_secret,
+            x25519_ciphertext,
+            &self.x25519.raw_public_key()?,
+        )
+    }
+}
+
+impl KemPublicKey for XWingPublicKey {
+    fn encapsulate(&self) -> CryptographyResult<(Vec<u8>, Vec<u8>)> {
+        let (mlkem_secret, mut ciphertext) = kem::encapsulate(&self.mlkem, None)?;
+        let ephemeral = openssl::pkey::PKey::generate_x25519()?;
+        let x25519_ciphertext = ephemeral.raw_public_key()?;
+        let x25519_secret = x25519(&ephemeral, &self.x25519)?;
+        let secret = combine(
+            &mlkem_secret,
+            &x25519_secret,
+            &x25519_ciphertext,
+            &self.x25519.raw_public_key()?,
+        )?;
+        ciphertext.extend_from_slice(&x25519_ciphertext);
+        Ok((secret, ciphertext))
+    }
+}
+
+#[pyo3::prelude::pyfunction]
+fn generate_key() -> CryptographyResult<XWingPrivateKey> {
+    let mut seed = [0; PRIVATE_KEY_LENGTH];
+    openssl::rand::rand_bytes(&mut seed)?;
+    private_key_from_seed(&seed)
+}
+
+#[pyo3::prelude::pyfunction]
+fn from_private_bytes(data: CffiBuf<'_>) -> CryptographyResult<XWingPrivateKey> {
+    if data.as_bytes().len() != PRIVATE_KEY_LENGTH {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("An X-Wing private key is 32 bytes long"),
+        ));
+    }
+    private_key_from_seed(data.as_bytes())
+}
+
+#[pyo3::prelude::pyfunction]
+fn from_public_bytes(data: &[u8]) -> CryptographyResult<XWingPublicKey> {
+    if data.len() != PUBLIC_KEY_LENGTH {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("An X-Wing public key is 1216 bytes long"),
+        ));
+    }
+    let (mlkem_key, x25519_key) = data.split_at(PUBLIC_KEY_LENGTH - X25519_LENGTH);
+    let mlkem = mlkem::public_key_from_raw_bytes(Variant::MlKem768, mlkem_key)
+        .map_err(|_| pyo3::exceptions::PyValueError::new_err("Invalid X-Wing public key"))?;
+    let x25519 =
+        openssl::pkey::PKey::public_key_from_raw_bytes(x25519_key, openssl::pkey::Id::X25519)?;
+    Ok(XWingPublicKey { mlkem, x25519 })
+}
+
+#[pyo3::prelude::pymethods]
+impl XWingPrivateKey {
+    fn decapsulate<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        ciphertext: CffiBuf<'_>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let secret = KemPrivateKey::decapsulate(self, ciphertext.as_bytes())?;
+        Ok(pyo3::types::PyBytes::new(py, &secret))
+    }
+
+    fn public_key(&self) -> CryptographyResult<XWingPublicKey> {
+        Ok(XWingPublicKey {
+            mlkem: mlkem::public_key_from_raw_bytes(
+                Variant::MlKem768,
+                &self.mlkem.raw_public_key()?,
+            )?,
+            x25519: openssl::pkey::PKey::public_key_from_raw_bytes(
+                &self.x25519.raw_public_key()?,
+                openssl::pkey::Id::X25519,
+            )?,
+        })
+    }
+
+    fn private_bytes_raw<'p>(&self, py: pyo3::Python<'p>) -> &'p pyo3::types::PyBytes {
+        pyo3::types::PyBytes::new(py, &self.seed)
+    }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> bool {
+        openssl::memcmp::eq(&self.seed, &other.seed)
+    }
+}
+
+#[pyo3::prelude::pymethods]
+impl XWingPublicKey {
+    fn encapsulate<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+    ) -> CryptographyResult<(&'p pyo3::types::PyBytes, &'p pyo3::types::PyBytes)> {
+        let (secret, ciphertext) = KemPublicKey::encapsulate(self)?;
+        Ok((
+            pyo3::types::PyBytes::new(py, &secret),
+            pyo3::types::PyBytes::new(py, &ciphertext),
+        ))
+    }
+
+    fn public_bytes_raw<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let mut raw_bytes = self.mlkem.raw_public_key()?;
+        raw_bytes.extend_from_slice(&self.x25519.raw_public_key()?);
+        Ok(pyo3::types::PyBytes::new(py, &raw_bytes))
+    }
+
+    fn __eq__(&self, other: pyo3::PyRef<'_, Self>) -> bool {
+        self.mlkem.public_eq(&other.mlkem) && self.x25519.public_eq(&other.x25519)
+    }
+
+    fn __copy__(slf: pyo3::PyRef<'_, Self>) -> pyo3::PyRef<'_, Self> {
+        slf
+    }
+}
+
+pub(crate) fn create_module(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let m = pyo3::prelude::PyModule::new(py, "xwing")?;
+    m.add_function(pyo3::wrap_pyfunction!(generate_key, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(from_private_bytes, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(from_public_bytes, m)?)?;
+
+    m.add_class::<XWingPrivateKey>()?;
+    m.add_class::<XWingPublicKey>()?;
+
+    Ok(m)
+}
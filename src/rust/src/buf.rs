@@ -22,6 +22,21 @@ impl<'a> pyo3::conversion::FromPyObject<'a> for CffiBuf<'a> {
     fn extract(pyobj: &'a pyo3::PyAny) -> pyo3::PyResult<Self> {
         let py = pyobj.py();
 
+        #[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
+        if let Ok(locked) =
+            pyobj.downcast::<pyo3::PyCell<crate::backend::locked_memory::LockedBuffer>>()
+        {
+            // SAFETY: We're keeping pyobj alive, which keeps the allocation
+            // alive. As with other buffers there's no protection against
+            // concurrent mutation through `LockedBuffer.write`.
+            let locked = unsafe { locked.try_borrow_unguarded()? };
+            return Ok(CffiBuf {
+                _pyobj: pyobj,
+                _bufobj: pyobj,
+                buf: locked.as_bytes(),
+            });
+        }
+
         let (bufobj, ptrval): (&pyo3::PyAny, usize) = types::EXTRACT_BUFFER_LENGTH
             .get(py)?
             .call1((pyobj,))?
@@ -0,0 +1,351 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! A minimal CBOR (RFC 8949) encoder and decoder. Only the subset of CBOR
+//! needed by COSE is supported: definite-length items, integers, byte and
+//! text strings, arrays, maps, tags, booleans and null.
+
+use pyo3::ToPyObject;
+
+const MAJOR_UNSIGNED: u8 = 0;
+const MAJOR_NEGATIVE: u8 = 1;
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_TEXT: u8 = 3;
+const MAJOR_ARRAY: u8 = 4;
+const MAJOR_MAP: u8 = 5;
+const MAJOR_TAG: u8 = 6;
+const MAJOR_SIMPLE: u8 = 7;
+
+const SIMPLE_FALSE: u8 = 20;
+const SIMPLE_TRUE: u8 = 21;
+const SIMPLE_NULL: u8 = 22;
+
+// Nesting deeper than this is never needed for COSE structures, and bounding
+// it keeps malicious inputs from exhausting the stack.
+const MAX_DEPTH: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Value {
+    Unsigned(u64),
+    // Holds `n` for the integer `-1 - n`, mirroring CBOR's own encoding.
+    Negative(u64),
+    Bytes(Vec<u8>),
+    Text(String),
+    Array(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+    Tag(u64, Box<Value>),
+    Bool(bool),
+    Null,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum CborError {
+    UnexpectedEnd,
+    TrailingData,
+    InvalidUtf8,
+    NestingTooDeep,
+    Unsupported,
+}
+
+impl Value {
+    pub(crate) fn from_i64(v: i64) -> Value {
+        if v >= 0 {
+            Value::Unsigned(v as u64)
+        } else {
+            Value::Negative(!(v as u64))
+        }
+    }
+
+    pub(crate) fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Unsigned(v) => i64::try_from(*v).ok(),
+            Value::Negative(n) => i64::try_from(*n).ok().map(|n| -1 - n),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn map_get(&self, key: &Value) -> Option<&Value> {
+        match self {
+            Value::Map(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+}
+
+fn write_head(out: &mut Vec<u8>, major: u8, v: u64) {
+    let major = major << 5;
+    if v < 24 {
+        out.push(major | v as u8);
+    } else if v <= u64::from(u8::MAX) {
+        out.push(major | 24);
+        out.push(v as u8);
+    } else if v <= u64::from(u16::MAX) {
+        out.push(major | 25);
+        out.extend_from_slice(&(v as u16).to_be_bytes());
+    } else if v <= u64::from(u32::MAX) {
+        out.push(major | 26);
+        out.extend_from_slice(&(v as u32).to_be_bytes());
+    } else {
+        out.push(major | 27);
+        out.extend_from_slice(&v.to_be_bytes());
+    }
+}
+
+fn write_value(out: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::Unsigned(v) => write_head(out, MAJOR_UNSIGNED, *v),
+        Value::Negative(n) => write_head(out, MAJOR_NEGATIVE, *n),
+        Value::Bytes(b) => {
+            write_head(out, MAJOR_BYTES, b.len() as u64);
+            out.extend_from_slice(b);
+        }
+        Value::Text(t) => {
+            write_head(out, MAJOR_TEXT, t.len() as u64);
+            out.extend_from_slice(t.as_bytes());
+        }
+        Value::Array(items) => {
+            write_head(out, MAJOR_ARRAY, items.len() as u64);
+            for item in items {
+                write_value(out, item);
+            }
+        }
+        Value::Map(entries) => {
+            write_head(out, MAJOR_MAP, entries.len() as u64);
+            for (k, v) in entries {
+                write_value(out, k);
+                write_value(out, v);
+            }
+        }
+        Value::Tag(tag, inner) => {
+            write_head(out, MAJOR_TAG, *tag);
+            write_value(out, inner);
+        }
+        Value::Bool(false) => out.push(MAJOR_SIMPLE << 5 | SIMPLE_FALSE),
+        Value::Bool(true) => out.push(MAJOR_SIMPLE << 5 | SIMPLE_TRUE),
+        Value::Null => out.push(MAJOR_SIMPLE << 5 | SIMPLE_NULL),
+    }
+}
+
+pub(crate) fn encode(value: &Value) -> Vec<u8> {
+    let mut out = vec![];
+    write_value(&mut out, value);
+    out
+}
+
+struct Parser<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Parser<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], CborError> {
+        if self.data.len() < n {
+            return Err(CborError::UnexpectedEnd);
+        }
+        let (head, rest) = self.data.split_at(n);
+        self.data = rest;
+        Ok(head)
+    }
+
+    fn read_head(&mut self) -> Result<(u8, u8, u64), CborError> {
+        let initial = self.take(1)?[0];
+        let major = initial >> 5;
+        let info = initial & 0x1f;
+        let v = match info {
+            0..=23 => u64::from(info),
+            24 => u64::from(self.take(1)?[0]),
+            25 => u64::from(u16::from_be_bytes(self.take(2)?.try_into().unwrap())),
+            26 => u64::from(u32::from_be_bytes(self.take(4)?.try_into().unwrap())),
+            27 => u64::from_be_bytes(self.take(8)?.try_into().unwrap()),
+            // Reserved values and indefinite lengths.
+            _ => return Err(CborError::Unsupported),
+        };
+        Ok((major, info, v))
+    }
+
+    fn read_len(&mut self, v: u64) -> Result<usize, CborError> {
+        let len = usize::try_from(v).map_err(|_| CborError::UnexpectedEnd)?;
+        // Every element occupies at least one byte, so this bounds
+        // allocations by the size of the input.
+        if len > self.data.len() {
+            return Err(CborError::UnexpectedEnd);
+        }
+        Ok(len)
+    }
+
+    fn read_value(&mut self, depth: usize) -> Result<Value, CborError> {
+        if depth > MAX_DEPTH {
+            return Err(CborError::NestingTooDeep);
+        }
+        let (major, info, v) = self.read_head()?;
+        match major {
+            MAJOR_UNSIGNED => Ok(Value::Unsigned(v)),
+            MAJOR_NEGATIVE => Ok(Value::Negative(v)),
+            MAJOR_BYTES => {
+                let len = self.read_len(v)?;
+                Ok(Value::Bytes(self.take(len)?.to_vec()))
+            }
+            MAJOR_TEXT => {
+                let len = self.read_len(v)?;
+                let s = std::str::from_utf8(self.take(len)?).map_err(|_| CborError::InvalidUtf8)?;
+                Ok(Value::Text(s.to_string()))
+            }
+            MAJOR_ARRAY => {
+                let len = self.read_len(v)?;
+                let mut items = Vec::with_capacity(len);
+                for _ in 0..len {
+                    items.push(self.read_value(depth + 1)?);
+                }
+                Ok(Value::Array(items))
+            }
+            MAJOR_MAP => {
+                let len = self.read_len(v)?;
+                let mut entries = Vec::with_capacity(len);
+                for _ in 0..len {
+                    let k = self.read_value(depth + 1)?;
+                    let v = self.read_value(depth + 1)?;
+                    entries.push((k, v));
+                }
+                Ok(Value::Map(entries))
+            }
+            MAJOR_TAG => Ok(Value::Tag(v, Box::new(self.read_value(depth + 1)?))),
+            _ => match info {
+                SIMPLE_FALSE => Ok(Value::Bool(false)),
+                SIMPLE_TRUE => Ok(Value::Bool(true)),
+                SIMPLE_NULL => Ok(Value::Null),
+                _ => Err(CborError::Unsupported),
+            },
+        }
+    }
+}
+
+pub(crate) fn decode(data: &[u8]) -> Result<Value, CborError> {
+    let mut parser = Parser { data };
+    let value = parser.read_value(0)?;
+    if !parser.data.is_empty() {
+        return Err(CborError::TrailingData);
+    }
+    Ok(value)
+}
+
+pub(crate) fn value_to_py(py: pyo3::Python<'_>, value: &Value) -> pyo3::PyResult<pyo3::PyObject> {
+    Ok(match value {
+        Value::Unsigned(v) => v.to_object(py),
+        Value::Negative(n) => (-1 - i128::from(*n)).to_object(py),
+        Value::Bytes(b) => pyo3::types::PyBytes::new(py, b).to_object(py),
+        Value::Text(t) => t.to_object(py),
+        Value::Array(items) => {
+            let l = pyo3::types::PyList::empty(py);
+            for item in items {
+                l.append(value_to_py(py, item)?)?;
+            }
+            l.to_object(py)
+        }
+        Value::Map(entries) => {
+            let d = pyo3::types::PyDict::new(py);
+            for (k, v) in entries {
+                d.set_item(value_to_py(py, k)?, value_to_py(py, v)?)?;
+            }
+            d.to_object(py)
+        }
+        Value::Tag(..) => {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "Tagged CBOR values are not supported in headers",
+            ))
+        }
+        Value::Bool(b) => b.to_object(py),
+        Value::Null => py.None(),
+    })
+}
+
+pub(crate) fn py_to_value(obj: &pyo3::PyAny) -> pyo3::PyResult<Value> {
+    if obj.is_none() {
+        Ok(Value::Null)
+    } else if let Ok(b) = obj.downcast::<pyo3::types::PyBool>() {
+        Ok(Value::Bool(b.is_true()))
+    } else if obj.is_instance_of::<pyo3::types::PyLong>() {
+        let v = obj.extract::<i128>()?;
+        if v >= 0 {
+            Ok(Value::Unsigned(u64::try_from(v).map_err(|_| {
+                pyo3::exceptions::PyValueError::new_err("Integer too large for CBOR")
+            })?))
+        } else {
+            Ok(Value::Negative(u64::try_from(-1 - v).map_err(|_| {
+                pyo3::exceptions::PyValueError::new_err("Integer too small for CBOR")
+            })?))
+        }
+    } else if let Ok(b) = obj.downcast::<pyo3::types::PyBytes>() {
+        Ok(Value::Bytes(b.as_bytes().to_vec()))
+    } else if let Ok(s) = obj.downcast::<pyo3::types::PyString>() {
+        Ok(Value::Text(s.to_str()?.to_string()))
+    } else if let Ok(d) = obj.downcast::<pyo3::types::PyDict>() {
+        let mut entries = vec![];
+        for (k, v) in d.iter() {
+            entries.push((py_to_value(k)?, py_to_value(v)?));
+        }
+        Ok(Value::Map(entries))
+    } else if obj.is_instance_of::<pyo3::types::PyList>()
+        || obj.is_instance_of::<pyo3::types::PyTuple>()
+    {
+        let mut items = vec![];
+        for item in obj.iter()? {
+            items.push(py_to_value(item?)?);
+        }
+        Ok(Value::Array(items))
+    } else {
+        Err(pyo3::exceptions::PyTypeError::new_err(
+            "Header values must be int, bytes, str, bool, None, list, or dict",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, encode, CborError, Value};
+
+    #[test]
+    fn test_roundtrip() {
+        for value in [
+            Value::Unsigned(0),
+            Value::Unsigned(23),
+            Value::Unsigned(24),
+            Value::Unsigned(1000),
+            Value::Unsigned(100_000),
+            Value::Unsigned(u64::MAX),
+            Value::Negative(0),
+            Value::Negative(u64::MAX),
+            Value::Bytes(vec![1, 2, 3]),
+            Value::Text("Signature1".to_string()),
+            Value::Array(vec![Value::Null, Value::Bool(true), Value::Bool(false)]),
+            Value::Map(vec![(Value::from_i64(1), Value::from_i64(-7))]),
+            Value::Tag(18, Box::new(Value::Array(vec![]))),
+        ] {
+            assert_eq!(decode(&encode(&value)), Ok(value));
+        }
+    }
+
+    #[test]
+    fn test_known_encodings() {
+        assert_eq!(
+            encode(&Value::Map(vec![(Value::from_i64(1), Value::from_i64(-7))])),
+            b"\xa1\x01\x26"
+        );
+        assert_eq!(encode(&Value::Unsigned(500)), b"\x19\x01\xf4");
+        assert_eq!(Value::from_i64(-7).as_i64(), Some(-7));
+        assert_eq!(Value::Unsigned(u64::MAX).as_i64(), None);
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert_eq!(decode(b""), Err(CborError::UnexpectedEnd));
+        assert_eq!(decode(b"\x01\x01"), Err(CborError::TrailingData));
+        assert_eq!(decode(b"\x5f"), Err(CborError::Unsupported));
+        assert_eq!(decode(b"\xf9\x00\x00"), Err(CborError::Unsupported));
+        assert_eq!(decode(b"\x62\xff\xff"), Err(CborError::InvalidUtf8));
+        assert_eq!(
+            decode(b"\x5a\xff\xff\xff\xff"),
+            Err(CborError::UnexpectedEnd)
+        );
+        assert_eq!(decode(&[0x81; 64]), Err(CborError::NestingTooDeep));
+    }
+}
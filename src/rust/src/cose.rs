@@ -0,0 +1,436 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+use crate::asn1::{dss_signature_der_to_raw, dss_signature_raw_to_der};
+use crate::buf::CffiBuf;
+use crate::cbor::{self, Value};
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::x509::sign::{identify_key_type, identify_public_key_type, KeyType};
+use crate::{exceptions, types};
+
+// RFC 9052 Section 4.2
+const COSE_SIGN1_TAG: u64 = 18;
+const HEADER_ALG: i64 = 1;
+const SIGNATURE1_CONTEXT: &str = "Signature1";
+
+#[derive(Clone, Copy)]
+enum CoseAlgorithm {
+    Es256,
+    Es384,
+    Es512,
+    EdDsa,
+    Ps256,
+    Ps384,
+    Ps512,
+    Rs256,
+    Rs384,
+    Rs512,
+}
+
+impl CoseAlgorithm {
+    fn from_id(id: i64) -> CryptographyResult<CoseAlgorithm> {
+        match id {
+            -7 => Ok(CoseAlgorithm::Es256),
+            -35 => Ok(CoseAlgorithm::Es384),
+            -36 => Ok(CoseAlgorithm::Es512),
+            -8 => Ok(CoseAlgorithm::EdDsa),
+            -37 => Ok(CoseAlgorithm::Ps256),
+            -38 => Ok(CoseAlgorithm::Ps384),
+            -39 => Ok(CoseAlgorithm::Ps512),
+            -257 => Ok(CoseAlgorithm::Rs256),
+            -258 => Ok(CoseAlgorithm::Rs384),
+            -259 => Ok(CoseAlgorithm::Rs512),
+            _ => Err(CryptographyError::from(
+                exceptions::UnsupportedAlgorithm::new_err(format!(
+                    "COSE algorithm {id} is not supported"
+                )),
+            )),
+        }
+    }
+
+    fn key_type(self) -> KeyType {
+        match self {
+            CoseAlgorithm::Es256 | CoseAlgorithm::Es384 | CoseAlgorithm::Es512 => KeyType::Ec,
+            CoseAlgorithm::EdDsa => KeyType::Ed25519,
+            CoseAlgorithm::Ps256
+            | CoseAlgorithm::Ps384
+            | CoseAlgorithm::Ps512
+            | CoseAlgorithm::Rs256
+            | CoseAlgorithm::Rs384
+            | CoseAlgorithm::Rs512 => KeyType::Rsa,
+        }
+    }
+
+    // RFC 9053 Section 2.1: each ECDSA algorithm is used with one curve.
+    fn curve_name(self) -> Option<&'static str> {
+        match self {
+            CoseAlgorithm::Es256 => Some("secp256r1"),
+            CoseAlgorithm::Es384 => Some("secp384r1"),
+            CoseAlgorithm::Es512 => Some("secp521r1"),
+            _ => None,
+        }
+    }
+
+    fn hash_name(self) -> Option<&'static str> {
+        match self {
+            CoseAlgorithm::Es256 | CoseAlgorithm::Ps256 | CoseAlgorithm::Rs256 => Some("SHA256"),
+            CoseAlgorithm::Es384 | CoseAlgorithm::Ps384 | CoseAlgorithm::Rs384 => Some("SHA384"),
+            CoseAlgorithm::Es512 | CoseAlgorithm::Ps512 | CoseAlgorithm::Rs512 => Some("SHA512"),
+            CoseAlgorithm::EdDsa => None,
+        }
+    }
+}
+
+fn check_key_type(
+    py: pyo3::Python<'_>,
+    alg: CoseAlgorithm,
+    key_type: KeyType,
+    key: &pyo3::PyAny,
+) -> CryptographyResult<()> {
+    let matches = match alg.key_type() {
+        KeyType::Ed25519 => matches!(key_type, KeyType::Ed25519 | KeyType::Ed448),
+        expected => expected == key_type,
+    };
+    if !matches {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(
+                "The key type does not match the COSE algorithm",
+            ),
+        ));
+    }
+    if let Some(expected) = alg.curve_name() {
+        let curve = key
+            .getattr(pyo3::intern!(py, "curve"))?
+            .getattr(pyo3::intern!(py, "name"))?
+            .extract::<&str>()?;
+        if curve != expected {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "The key's curve does not match the COSE algorithm",
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+// Returns the signature algorithm arguments (e.g. `(ECDSA(SHA256()),)` or
+// `(PSS(...), SHA256())`) for the Python-level `sign` and `verify` methods.
+fn signature_args<'p>(
+    py: pyo3::Python<'p>,
+    alg: CoseAlgorithm,
+) -> CryptographyResult<Vec<&'p pyo3::PyAny>> {
+    let hash = match alg.hash_name() {
+        Some(name) => types::HASHES_MODULE.get(py)?.getattr(name)?.call0()?,
+        None => return Ok(vec![]),
+    };
+    Ok(match alg {
+        CoseAlgorithm::Es256 | CoseAlgorithm::Es384 | CoseAlgorithm::Es512 => {
+            vec![types::ECDSA.get(py)?.call1((hash,))?]
+        }
+        CoseAlgorithm::Ps256 | CoseAlgorithm::Ps384 | CoseAlgorithm::Ps512 => {
+            // RFC 8230 Section 2: the salt length is the size of the digest.
+            let mgf = types::MGF1.get(py)?.call1((hash,))?;
+            let salt_length = hash.getattr(pyo3::intern!(py, "digest_size"))?;
+            vec![types::PSS.get(py)?.call1((mgf, salt_length))?, hash]
+        }
+        CoseAlgorithm::Rs256 | CoseAlgorithm::Rs384 | CoseAlgorithm::Rs512 => {
+            vec![types::PKCS1V15.get(py)?.call0()?, hash]
+        }
+        CoseAlgorithm::EdDsa => unreachable!(),
+    })
+}
+
+fn ec_coordinate_size(py: pyo3::Python<'_>, key: &pyo3::PyAny) -> pyo3::PyResult<usize> {
+    let key_size = key
+        .getattr(pyo3::intern!(py, "curve"))?
+        .getattr(pyo3::intern!(py, "key_size"))?
+        .extract::<usize>()?;
+    Ok((key_size + 7) / 8)
+}
+
+fn sig_structure(protected: &[u8], external_aad: &[u8], payload: &[u8]) -> Vec<u8> {
+    cbor::encode(&Value::Array(vec![
+        Value::Text(SIGNATURE1_CONTEXT.to_string()),
+        Value::Bytes(protected.to_vec()),
+        Value::Bytes(external_aad.to_vec()),
+        Value::Bytes(payload.to_vec()),
+    ]))
+}
+
+fn headers_from_py(headers: Option<&pyo3::types::PyDict>) -> pyo3::PyResult<Vec<(Value, Value)>> {
+    let mut entries = vec![];
+    if let Some(headers) = headers {
+        for (k, v) in headers.iter() {
+            let k = cbor::py_to_value(k)?;
+            if !matches!(k, Value::Unsigned(_) | Value::Negative(_) | Value::Text(_)) {
+                return Err(pyo3::exceptions::PyTypeError::new_err(
+                    "Header labels must be int or str",
+                ));
+            }
+            entries.push((k, cbor::py_to_value(v)?));
+        }
+    }
+    Ok(entries)
+}
+
+fn invalid_structure(e: cbor::CborError) -> CryptographyError {
+    CryptographyError::from(pyo3::exceptions::PyValueError::new_err(format!(
+        "Invalid COSE_Sign1 structure: {e:?}"
+    )))
+}
+
+#[pyo3::prelude::pyfunction]
+#[pyo3(signature = (payload, private_key, algorithm, *, protected_headers=None, unprotected_headers=None, external_aad=None, detached=false))]
+#[allow(clippy::too_many_arguments)]
+fn sign1<'p>(
+    py: pyo3::Python<'p>,
+    payload: CffiBuf<'_>,
+    private_key: &'p pyo3::PyAny,
+    algorithm: i64,
+    protected_headers: Option<&pyo3::types::PyDict>,
+    unprotected_headers: Option<&pyo3::types::PyDict>,
+    external_aad: Option<CffiBuf<'_>>,
+    detached: bool,
+) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+    let alg = CoseAlgorithm::from_id(algorithm)?;
+    let key_type = identify_key_type(py, private_key)?;
+    check_key_type(py, alg, key_type, private_key)?;
+
+    let alg_label = Value::from_i64(HEADER_ALG);
+    let mut protected = vec![(alg_label.clone(), Value::from_i64(algorithm))];
+    for (k, v) in headers_from_py(protected_headers)? {
+        if k == alg_label {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "The alg header is set from the algorithm argument",
+                ),
+            ));
+        }
+        protected.push((k, v));
+    }
+    let unprotected = headers_from_py(unprotected_headers)?;
+    if unprotected.iter().any(|(k, _)| k == &alg_label) {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(
+                "The alg header must be in the protected headers",
+            ),
+        ));
+    }
+
+    let protected_bytes = cbor::encode(&Value::Map(protected));
+    let external_aad = external_aad.as_ref().map_or(&[][..], CffiBuf::as_bytes);
+    let tbs = sig_structure(&protected_bytes, external_aad, payload.as_bytes());
+
+    let mut args = vec![pyo3::types::PyBytes::new(py, &tbs).as_ref()];
+    args.extend(signature_args(py, alg)?);
+    let signature = private_key
+        .call_method1(
+            pyo3::intern!(py, "sign"),
+            pyo3::types::PyTuple::new(py, args),
+        )?
+        .extract::<&[u8]>()?;
+    let signature = if key_type == KeyType::Ec {
+        dss_signature_der_to_raw(signature, ec_coordinate_size(py, private_key)?)?
+    } else {
+        signature.to_vec()
+    };
+
+    let payload = if detached {
+        Value::Null
+    } else {
+        Value::Bytes(payload.as_bytes().to_vec())
+    };
+    let message = Value::Tag(
+        COSE_SIGN1_TAG,
+        Box::new(Value::Array(vec![
+            Value::Bytes(protected_bytes),
+            Value::Map(unprotected),
+            payload,
+            Value::Bytes(signature),
+        ])),
+    );
+    Ok(pyo3::types::PyBytes::new(py, &cbor::encode(&message)))
+}
+
+#[pyo3::prelude::pyclass(
+    frozen,
+    module = "cryptography.hazmat.bindings._rust.cose",
+    name = "COSESign1"
+)]
+struct CoseSign1 {
+    protected_raw: Vec<u8>,
+    protected: Value,
+    unprotected: Value,
+    payload: Option<Vec<u8>>,
+    signature: Vec<u8>,
+    algorithm: i64,
+}
+
+#[pyo3::prelude::pyfunction]
+fn load_cose_sign1(data: CffiBuf<'_>) -> CryptographyResult<CoseSign1> {
+    let value = cbor::decode(data.as_bytes()).map_err(invalid_structure)?;
+    // The tag is optional, since it may be implied by the application.
+    let value = match value {
+        Value::Tag(COSE_SIGN1_TAG, inner) => *inner,
+        Value::Tag(..) => {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("Not a COSE_Sign1 message"),
+            ))
+        }
+        v => v,
+    };
+    let (protected_raw, unprotected, payload, signature) = match value {
+        Value::Array(items) => match <[Value; 4]>::try_from(items) {
+            Ok([Value::Bytes(p), u @ Value::Map(_), payload, Value::Bytes(s)]) => {
+                (p, u, payload, s)
+            }
+            _ => {
+                return Err(CryptographyError::from(
+                    pyo3::exceptions::PyValueError::new_err("Not a COSE_Sign1 message"),
+                ))
+            }
+        },
+        _ => {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("Not a COSE_Sign1 message"),
+            ))
+        }
+    };
+    let payload = match payload {
+        Value::Bytes(p) => Some(p),
+        Value::Null => None,
+        _ => {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "COSE_Sign1 payload must be a byte string or nil",
+                ),
+            ))
+        }
+    };
+
+    // A zero-length protected header is equivalent to an empty map.
+    let protected = if protected_raw.is_empty() {
+        Value::Map(vec![])
+    } else {
+        cbor::decode(&protected_raw).map_err(invalid_structure)?
+    };
+    if !matches!(protected, Value::Map(_)) {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("Protected header must be a map"),
+        ));
+    }
+    let algorithm = protected
+        .map_get(&Value::from_i64(HEADER_ALG))
+        .and_then(Value::as_i64)
+        .ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(
+                "COSE_Sign1 message has no protected alg header",
+            )
+        })?;
+
+    Ok(CoseSign1 {
+        protected_raw,
+        protected,
+        unprotected,
+        payload,
+        signature,
+        algorithm,
+    })
+}
+
+#[pyo3::prelude::pymethods]
+impl CoseSign1 {
+    #[getter]
+    fn algorithm(&self) -> i64 {
+        self.algorithm
+    }
+
+    #[getter]
+    fn protected_headers(&self, py: pyo3::Python<'_>) -> pyo3::PyResult<pyo3::PyObject> {
+        cbor::value_to_py(py, &self.protected)
+    }
+
+    #[getter]
+    fn unprotected_headers(&self, py: pyo3::Python<'_>) -> pyo3::PyResult<pyo3::PyObject> {
+        cbor::value_to_py(py, &self.unprotected)
+    }
+
+    #[getter]
+    fn payload<'p>(&self, py: pyo3::Python<'p>) -> Option<&'p pyo3::types::PyBytes> {
+        self.payload
+            .as_ref()
+            .map(|p| pyo3::types::PyBytes::new(py, p))
+    }
+
+    #[getter]
+    fn signature<'p>(&self, py: pyo3::Python<'p>) -> &'p pyo3::types::PyBytes {
+        pyo3::types::PyBytes::new(py, &self.signature)
+    }
+
+    #[pyo3(signature = (public_key, *, external_aad=None, detached_payload=None))]
+    fn verify<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        public_key: &'p pyo3::PyAny,
+        external_aad: Option<CffiBuf<'_>>,
+        detached_payload: Option<CffiBuf<'_>>,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        let alg = CoseAlgorithm::from_id(self.algorithm)?;
+        let key_type = identify_public_key_type(py, public_key)?;
+        check_key_type(py, alg, key_type, public_key)?;
+
+        let payload = match (&self.payload, &detached_payload) {
+            (Some(p), None) => &p[..],
+            (None, Some(p)) => p.as_bytes(),
+            (Some(_), Some(_)) => {
+                return Err(CryptographyError::from(
+                    pyo3::exceptions::PyValueError::new_err(
+                        "detached_payload must not be provided when the message has a payload",
+                    ),
+                ))
+            }
+            (None, None) => {
+                return Err(CryptographyError::from(
+                    pyo3::exceptions::PyValueError::new_err(
+                        "The message has a detached payload, detached_payload is required",
+                    ),
+                ))
+            }
+        };
+
+        let external_aad = external_aad.as_ref().map_or(&[][..], CffiBuf::as_bytes);
+        let tbs = sig_structure(&self.protected_raw, external_aad, payload);
+
+        let signature = if key_type == KeyType::Ec {
+            dss_signature_raw_to_der(&self.signature, ec_coordinate_size(py, public_key)?)
+                .ok_or_else(|| exceptions::InvalidSignature::new_err(()))?
+        } else {
+            self.signature.clone()
+        };
+
+        let mut args = vec![
+            pyo3::types::PyBytes::new(py, &signature).as_ref(),
+            pyo3::types::PyBytes::new(py, &tbs).as_ref(),
+        ];
+        args.extend(signature_args(py, alg)?);
+        public_key.call_method1(
+            pyo3::intern!(py, "verify"),
+            pyo3::types::PyTuple::new(py, args),
+        )?;
+
+        Ok(pyo3::types::PyBytes::new(py, payload))
+    }
+}
+
+pub(crate) fn create_submodule(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let submod = pyo3::prelude::PyModule::new(py, "cose")?;
+
+    submod.add_function(pyo3::wrap_pyfunction!(sign1, submod)?)?;
+    submod.add_function(pyo3::wrap_pyfunction!(load_cose_sign1, submod)?)?;
+
+    submod.add_class::<CoseSign1>()?;
+
+    Ok(submod)
+}
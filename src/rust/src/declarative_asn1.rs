@@ -0,0 +1,717 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Encoding and decoding of ASN.1 structures described by a schema built in
+//! Python (see `cryptography.hazmat.asn1`). Only DER is supported, both when
+//! encoding and when decoding.
+
+use pyo3::types::IntoPyDict;
+
+use crate::asn1::{big_byte_slice_to_py_int, oid_to_py_oid, py_oid_to_oid};
+use crate::buf::CffiBuf;
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::{types, x509};
+
+const CLASS_UNIVERSAL: u8 = 0;
+
+const TAG_BOOLEAN: u32 = 1;
+const TAG_INTEGER: u32 = 2;
+const TAG_BIT_STRING: u32 = 3;
+const TAG_OCTET_STRING: u32 = 4;
+const TAG_NULL: u32 = 5;
+const TAG_OID: u32 = 6;
+const TAG_ENUMERATED: u32 = 10;
+const TAG_UTF8_STRING: u32 = 12;
+const TAG_SEQUENCE: u32 = 16;
+const TAG_SET: u32 = 17;
+const TAG_PRINTABLE_STRING: u32 = 19;
+const TAG_IA5_STRING: u32 = 22;
+const TAG_UTC_TIME: u32 = 23;
+const TAG_GENERALIZED_TIME: u32 = 24;
+
+fn error(msg: impl Into<String>) -> CryptographyError {
+    CryptographyError::from(pyo3::exceptions::PyValueError::new_err(msg.into()))
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct TagId {
+    class: u8,
+    constructed: bool,
+    number: u32,
+}
+
+impl TagId {
+    fn universal(number: u32, constructed: bool) -> TagId {
+        TagId {
+            class: CLASS_UNIVERSAL,
+            constructed,
+            number,
+        }
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        let first = (self.class << 6) | if self.constructed { 0x20 } else { 0 };
+        if self.number < 31 {
+            out.push(first | self.number as u8);
+        } else {
+            out.push(first | 0x1f);
+            let mut digits = vec![];
+            let mut n = self.number;
+            loop {
+                digits.push((n & 0x7f) as u8);
+                n >>= 7;
+                if n == 0 {
+                    break;
+                }
+            }
+            for (i, d) in digits.iter().enumerate().rev() {
+                out.push(if i == 0 { *d } else { d | 0x80 });
+            }
+        }
+    }
+}
+
+struct Tlv<'a> {
+    tag: TagId,
+    content: &'a [u8],
+    full: &'a [u8],
+}
+
+// Reads a single DER TLV from the start of `data`, rejecting non-minimal
+// tag and length encodings as well as indefinite lengths.
+fn read_tlv(data: &[u8]) -> CryptographyResult<(Tlv<'_>, &[u8])> {
+    let truncated = || error("Truncated ASN.1 data");
+    let (&first, mut rest) = data.split_first().ok_or_else(truncated)?;
+    let mut number = u32::from(first & 0x1f);
+    if number == 0x1f {
+        number = 0;
+        loop {
+            let (&b, r) = rest.split_first().ok_or_else(truncated)?;
+            rest = r;
+            if number == 0 && b == 0x80 {
+                return Err(error("Non-minimal ASN.1 tag encoding"));
+            }
+            if number > (u32::MAX >> 7) {
+                return Err(error("ASN.1 tag number is too large"));
+            }
+            number = (number << 7) | u32::from(b & 0x7f);
+            if b & 0x80 == 0 {
+                break;
+            }
+        }
+        if number < 31 {
+            return Err(error("Non-minimal ASN.1 tag encoding"));
+        }
+    }
+    let tag = TagId {
+        class: first >> 6,
+        constructed: first & 0x20 != 0,
+        number,
+    };
+
+    let (&len_byte, mut rest) = rest.split_first().ok_or_else(truncated)?;
+    let length = if len_byte & 0x80 == 0 {
+        usize::from(len_byte)
+    } else {
+        let num_bytes = usize::from(len_byte & 0x7f);
+        if num_bytes == 0 {
+            return Err(error("Indefinite length encoding is not allowed in DER"));
+        }
+        if num_bytes > std::mem::size_of::<usize>() || rest.len() < num_bytes {
+            return Err(truncated());
+        }
+        let (len_bytes, r) = rest.split_at(num_bytes);
+        rest = r;
+        if len_bytes[0] == 0 {
+            return Err(error("Non-minimal ASN.1 length encoding"));
+        }
+        let length = len_bytes
+            .iter()
+            .fold(0usize, |acc, b| (acc << 8) | usize::from(*b));
+        if length < 128 {
+            return Err(error("Non-minimal ASN.1 length encoding"));
+        }
+        length
+    };
+    if rest.len() < length {
+        return Err(truncated());
+    }
+    let header_length = data.len() - rest.len();
+    let (content, rest) = rest.split_at(length);
+    Ok((
+        Tlv {
+            tag,
+            content,
+            full: &data[..header_length + length],
+        },
+        rest,
+    ))
+}
+
+fn write_tlv(tag: TagId, content: &[u8]) -> Vec<u8> {
+    let mut out = vec![];
+    tag.write(&mut out);
+    if content.len() < 128 {
+        out.push(content.len() as u8);
+    } else {
+        let len_bytes = content.len().to_be_bytes();
+        let start = len_bytes.iter().position(|b| *b != 0).unwrap();
+        out.push(0x80 | (len_bytes.len() - start) as u8);
+        out.extend_from_slice(&len_bytes[start..]);
+    }
+    out.extend_from_slice(content);
+    out
+}
+
+#[derive(Clone, Copy)]
+enum Primitive {
+    Boolean,
+    Integer,
+    Enumerated,
+    Null,
+    OctetString,
+    BitString,
+    ObjectIdentifier,
+    Utf8String,
+    PrintableString,
+    Ia5String,
+    UtcTime,
+    GeneralizedTime,
+}
+
+impl Primitive {
+    fn from_name(name: &str) -> Option<Primitive> {
+        Some(match name {
+            "boolean" => Primitive::Boolean,
+            "integer" => Primitive::Integer,
+            "enumerated" => Primitive::Enumerated,
+            "null" => Primitive::Null,
+            "octet_string" => Primitive::OctetString,
+            "bit_string" => Primitive::BitString,
+            "object_identifier" => Primitive::ObjectIdentifier,
+            "utf8_string" => Primitive::Utf8String,
+            "printable_string" => Primitive::PrintableString,
+            "ia5_string" => Primitive::Ia5String,
+            "utc_time" => Primitive::UtcTime,
+            "generalized_time" => Primitive::GeneralizedTime,
+            _ => return None,
+        })
+    }
+
+    fn tag_number(self) -> u32 {
+        match self {
+            Primitive::Boolean => TAG_BOOLEAN,
+            Primitive::Integer => TAG_INTEGER,
+            Primitive::Enumerated => TAG_ENUMERATED,
+            Primitive::Null => TAG_NULL,
+            Primitive::OctetString => TAG_OCTET_STRING,
+            Primitive::BitString => TAG_BIT_STRING,
+            Primitive::ObjectIdentifier => TAG_OID,
+            Primitive::Utf8String => TAG_UTF8_STRING,
+            Primitive::PrintableString => TAG_PRINTABLE_STRING,
+            Primitive::Ia5String => TAG_IA5_STRING,
+            Primitive::UtcTime => TAG_UTC_TIME,
+            Primitive::GeneralizedTime => TAG_GENERALIZED_TIME,
+        }
+    }
+
+    // Decodes `content`, the contents octets of a value of this type. The
+    // `asn1` crate does the DER validation, so the universal TLV is rebuilt
+    // for it to parse.
+    fn decode<'p>(
+        self,
+        py: pyo3::Python<'p>,
+        content: &[u8],
+    ) -> CryptographyResult<&'p pyo3::PyAny> {
+        let der = write_tlv(TagId::universal(self.tag_number(), false), content);
+        Ok(match self {
+            Primitive::Boolean => pyo3::types::PyBool::new(py, asn1::parse_single::<bool>(&der)?),
+            Primitive::Integer => big_byte_slice_to_py_int(
+                py,
+                asn1::parse_single::<asn1::BigInt<'_>>(&der)?.as_bytes(),
+            )?,
+            Primitive::Enumerated => {
+                let v = asn1::parse_single::<asn1::Enumerated>(&der)?.value();
+                pyo3::ToPyObject::to_object(&v, py).into_ref(py)
+            }
+            Primitive::Null => {
+                asn1::parse_single::<()>(&der)?;
+                py.None().into_ref(py)
+            }
+            Primitive::OctetString => {
+                pyo3::types::PyBytes::new(py, asn1::parse_single::<&[u8]>(&der)?)
+            }
+            Primitive::BitString => {
+                let bs = asn1::parse_single::<asn1::BitString<'_>>(&der)?;
+                types::ASN1_BIT_STRING_VALUE.get(py)?.call1((
+                    pyo3::types::PyBytes::new(py, bs.as_bytes()),
+                    bs.padding_bits(),
+                ))?
+            }
+            Primitive::ObjectIdentifier => {
+                oid_to_py_oid(py, &asn1::parse_single::<asn1::ObjectIdentifier>(&der)?)?
+            }
+            Primitive::Utf8String => pyo3::types::PyString::new(
+                py,
+                asn1::parse_single::<asn1::Utf8String<'_>>(&der)?.as_str(),
+            ),
+            Primitive::PrintableString => pyo3::types::PyString::new(
+                py,
+                asn1::parse_single::<asn1::PrintableString<'_>>(&der)?.as_str(),
+            ),
+            Primitive::Ia5String => pyo3::types::PyString::new(
+                py,
+                asn1::parse_single::<asn1::IA5String<'_>>(&der)?.as_str(),
+            ),
+            Primitive::UtcTime => {
+                x509::datetime_to_py(py, asn1::parse_single::<asn1::UtcTime>(&der)?.as_datetime())?
+            }
+            Primitive::GeneralizedTime => x509::datetime_to_py(
+                py,
+                asn1::parse_single::<asn1::GeneralizedTime>(&der)?.as_datetime(),
+            )?,
+        })
+    }
+
+    // Returns the DER encoding of `value` with its universal tag.
+    fn encode(self, py: pyo3::Python<'_>, value: &pyo3::PyAny) -> CryptographyResult<Vec<u8>> {
+        let invalid = |type_name: &str| {
+            CryptographyError::from(pyo3::exceptions::PyValueError::new_err(format!(
+                "Value can't be encoded as {type_name}"
+            )))
+        };
+        Ok(match self {
+            Primitive::Boolean => asn1::write_single(&value.extract::<bool>()?)?,
+            Primitive::Integer => {
+                let v = value.downcast::<pyo3::types::PyLong>()?;
+                asn1::write_single(&asn1::BigInt::new(&py_int_to_der_bytes(py, v)?).unwrap())?
+            }
+            Primitive::Enumerated => {
+                asn1::write_single(&asn1::Enumerated::new(value.extract::<u32>()?))?
+            }
+            Primitive::Null => {
+                if !value.is_none() {
+                    return Err(invalid("NULL"));
+                }
+                asn1::write_single(&())?
+            }
+            Primitive::OctetString => {
+                asn1::write_single(&value.extract::<CffiBuf<'_>>()?.as_bytes())?
+            }
+            Primitive::BitString => {
+                let data = value
+                    .getattr(pyo3::intern!(py, "data"))?
+                    .extract::<&[u8]>()?;
+                let padding_bits = value
+                    .getattr(pyo3::intern!(py, "padding_bits"))?
+                    .extract::<u8>()?;
+                asn1::write_single(
+                    &asn1::BitString::new(data, padding_bits)
+                        .ok_or_else(|| invalid("BIT STRING"))?,
+                )?
+            }
+            Primitive::ObjectIdentifier => asn1::write_single(&py_oid_to_oid(value)?)?,
+            Primitive::Utf8String => {
+                asn1::write_single(&asn1::Utf8String::new(value.extract::<&str>()?))?
+            }
+            Primitive::PrintableString => asn1::write_single(
+                &asn1::PrintableString::new(value.extract::<&str>()?)
+                    .ok_or_else(|| invalid("PrintableString"))?,
+            )?,
+            Primitive::Ia5String => asn1::write_single(
+                &asn1::IA5String::new(value.extract::<&str>()?)
+                    .ok_or_else(|| invalid("IA5String"))?,
+            )?,
+            Primitive::UtcTime => asn1::write_single(
+                &asn1::UtcTime::new(x509::py_to_datetime(py, value)?)
+                    .map_err(|_| invalid("UTCTime"))?,
+            )?,
+            Primitive::GeneralizedTime => asn1::write_single(
+                &asn1::GeneralizedTime::new(x509::py_to_datetime(py, value)?)
+                    .map_err(|_| invalid("GeneralizedTime"))?,
+            )?,
+        })
+    }
+}
+
+// Returns the minimal two's complement encoding of `v`.
+fn py_int_to_der_bytes(py: pyo3::Python<'_>, v: &pyo3::types::PyLong) -> pyo3::PyResult<Vec<u8>> {
+    let magnitude = if v.lt(0)? {
+        v.call_method0(pyo3::intern!(py, "__invert__"))?
+    } else {
+        v.as_ref()
+    };
+    let n = magnitude
+        .call_method0(pyo3::intern!(py, "bit_length"))?
+        .extract::<usize>()?
+        / 8
+        + 1;
+    let kwargs = [("signed", true)].into_py_dict(py);
+    v.call_method(pyo3::intern!(py, "to_bytes"), (n, "big"), Some(kwargs))?
+        .extract()
+}
+
+struct Field {
+    name: pyo3::Py<pyo3::types::PyString>,
+    spec: Spec,
+    optional: bool,
+    default: Option<pyo3::PyObject>,
+}
+
+enum Spec {
+    Primitive(Primitive),
+    Any,
+    Sequence {
+        cls: pyo3::PyObject,
+        fields: Vec<Field>,
+    },
+    SequenceOf(Box<Spec>),
+    SetOf(Box<Spec>),
+    Choice(Vec<(pyo3::Py<pyo3::types::PyString>, Spec)>),
+    Implicit(TagId, Box<Spec>),
+    Explicit(TagId, Box<Spec>),
+}
+
+impl Spec {
+    fn from_py(spec: &pyo3::PyAny) -> CryptographyResult<Spec> {
+        let spec = spec.downcast::<pyo3::types::PyTuple>()?;
+        let kind = spec.get_item(0)?.extract::<&str>()?;
+        if let Some(p) = Primitive::from_name(kind) {
+            return Ok(Spec::Primitive(p));
+        }
+        Ok(match kind {
+            "any" => Spec::Any,
+            "sequence" => {
+                let mut fields = vec![];
+                for field in spec.get_item(2)?.iter()? {
+                    let (name, field_spec, optional, has_default, default) = field?.extract::<(
+                        pyo3::Py<pyo3::types::PyString>,
+                        &pyo3::PyAny,
+                        bool,
+                        bool,
+                        pyo3::PyObject,
+                    )>(
+                    )?;
+                    fields.push(Field {
+                        name,
+                        spec: Spec::from_py(field_spec)?,
+                        optional,
+                        default: if has_default { Some(default) } else { None },
+                    });
+                }
+                Spec::Sequence {
+                    cls: spec.get_item(1)?.into(),
+                    fields,
+                }
+            }
+            "sequence_of" => Spec::SequenceOf(Box::new(Spec::from_py(spec.get_item(1)?)?)),
+            "set_of" => Spec::SetOf(Box::new(Spec::from_py(spec.get_item(1)?)?)),
+            "choice" => {
+                let mut alternatives = vec![];
+                for alt in spec.get_item(1)?.iter()? {
+                    let (name, alt_spec) =
+                        alt?.extract::<(pyo3::Py<pyo3::types::PyString>, &pyo3::PyAny)>()?;
+                    alternatives.push((name, Spec::from_py(alt_spec)?));
+                }
+                Spec::Choice(alternatives)
+            }
+            "implicit" | "explicit" => {
+                let class = spec.get_item(1)?.extract::<u8>()?;
+                let number = spec.get_item(2)?.extract::<u32>()?;
+                let inner = Spec::from_py(spec.get_item(3)?)?;
+                if kind == "implicit" {
+                    let constructed = match inner.tag() {
+                        Some(tag) => tag.constructed,
+                        None => {
+                            return Err(error("CHOICE and ANY types can't be implicitly tagged"))
+                        }
+                    };
+                    Spec::Implicit(
+                        TagId {
+                            class,
+                            constructed,
+                            number,
+                        },
+                        Box::new(inner),
+                    )
+                } else {
+                    Spec::Explicit(
+                        TagId {
+                            class,
+                            constructed: true,
+                            number,
+                        },
+                        Box::new(inner),
+                    )
+                }
+            }
+            _ => return Err(error(format!("Unknown ASN.1 type {kind}"))),
+        })
+    }
+
+    // The tag of encoded values, `None` for types which can have any tag.
+    fn tag(&self) -> Option<TagId> {
+        match self {
+            Spec::Primitive(p) => Some(TagId::universal(p.tag_number(), false)),
+            Spec::Sequence { .. } | Spec::SequenceOf(_) => {
+                Some(TagId::universal(TAG_SEQUENCE, true))
+            }
+            Spec::SetOf(_) => Some(TagId::universal(TAG_SET, true)),
+            Spec::Implicit(tag, _) | Spec::Explicit(tag, _) => Some(*tag),
+            Spec::Any | Spec::Choice(_) => None,
+        }
+    }
+
+    fn matches(&self, tag: TagId) -> bool {
+        match self {
+            Spec::Any => true,
+            Spec::Choice(alternatives) => alternatives.iter().any(|(_, s)| s.matches(tag)),
+            _ => self.tag() == Some(tag),
+        }
+    }
+
+    fn decode<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        tlv: &Tlv<'_>,
+    ) -> CryptographyResult<&'p pyo3::PyAny> {
+        if !self.matches(tlv.tag) {
+            return Err(error(format!("Unexpected ASN.1 tag {:?}", tlv.tag)));
+        }
+        match self {
+            Spec::Primitive(p) => p.decode(py, tlv.content),
+            Spec::Any => Ok(pyo3::types::PyBytes::new(py, tlv.full)),
+            Spec::Sequence { cls, fields } => {
+                let kwargs = pyo3::types::PyDict::new(py);
+                let mut data = tlv.content;
+                for field in fields {
+                    let next = if data.is_empty() {
+                        None
+                    } else {
+                        Some(read_tlv(data)?)
+                    };
+                    match next {
+                        Some((tlv, rest)) if field.spec.matches(tlv.tag) => {
+                            let value = field.spec.decode(py, &tlv)?;
+                            if let Some(default) = &field.default {
+                                if value.eq(default)? {
+                                    return Err(error(format!(
+                                        "Field {} is encoded with its default value",
+                                        field.name.as_ref(py)
+                                    )));
+                                }
+                            }
+                            kwargs.set_item(field.name.as_ref(py), value)?;
+                            data = rest;
+                        }
+                        _ if field.optional || field.default.is_some() => {}
+                        _ => {
+                            return Err(error(format!(
+                                "Missing required field {}",
+                                field.name.as_ref(py)
+                            )))
+                        }
+                    }
+                }
+                if !data.is_empty() {
+                    return Err(error("Unexpected data at the end of a SEQUENCE"));
+                }
+                Ok(cls.clone_ref(py).into_ref(py).call((), Some(kwargs))?)
+            }
+            Spec::SequenceOf(element) | Spec::SetOf(element) => {
+                let list = pyo3::types::PyList::empty(py);
+                let mut data = tlv.content;
+                let mut previous: Option<&[u8]> = None;
+                while !data.is_empty() {
+                    let (tlv, rest) = read_tlv(data)?;
+                    if matches!(self, Spec::SetOf(_)) {
+                        if previous.map_or(false, |p| p > tlv.full) {
+                            return Err(error("SET OF elements must be sorted in DER"));
+                        }
+                        previous = Some(tlv.full);
+                    }
+                    list.append(element.decode(py, &tlv)?)?;
+                    data = rest;
+                }
+                Ok(list)
+            }
+            Spec::Choice(alternatives) => {
+                let (name, spec) = alternatives
+                    .iter()
+                    .find(|(_, s)| s.matches(tlv.tag))
+                    .unwrap();
+                Ok(pyo3::types::PyTuple::new(
+                    py,
+                    [name.as_ref(py).as_ref(), spec.decode(py, tlv)?],
+                ))
+            }
+            Spec::Implicit(_, inner) => {
+                let natural = inner.tag().unwrap();
+                inner.decode(
+                    py,
+                    &Tlv {
+                        tag: natural,
+                        content: tlv.content,
+                        full: tlv.full,
+                    },
+                )
+            }
+            Spec::Explicit(_, inner) => {
+                let (inner_tlv, rest) = read_tlv(tlv.content)?;
+                if !rest.is_empty() {
+                    return Err(error("Unexpected data in an explicitly tagged value"));
+                }
+                inner.decode(py, &inner_tlv)
+            }
+        }
+    }
+
+    fn encode(&self, py: pyo3::Python<'_>, value: &pyo3::PyAny) -> CryptographyResult<Vec<u8>> {
+        match self {
+            Spec::Primitive(p) => p.encode(py, value),
+            Spec::Any => {
+                let data = value.extract::<&[u8]>()?;
+                let (_, rest) = read_tlv(data)?;
+                if !rest.is_empty() {
+                    return Err(error("ANY values must be a single DER element"));
+                }
+                Ok(data.to_vec())
+            }
+            Spec::Sequence { cls, fields } => {
+                if !value.is_instance(cls.as_ref(py))? {
+                    return Err(CryptographyError::from(
+                        pyo3::exceptions::PyTypeError::new_err(format!(
+                            "Expected an instance of {}",
+                            cls.as_ref(py)
+                        )),
+                    ));
+                }
+                let mut content = vec![];
+                for field in fields {
+                    let v = value.getattr(field.name.as_ref(py))?;
+                    if field.optional && v.is_none() {
+                        continue;
+                    }
+                    if let Some(default) = &field.default {
+                        if v.eq(default)? {
+                            continue;
+                        }
+                    }
+                    content.extend_from_slice(&field.spec.encode(py, v)?);
+                }
+                Ok(write_tlv(self.tag().unwrap(), &content))
+            }
+            Spec::SequenceOf(element) | Spec::SetOf(element) => {
+                let mut encoded = vec![];
+                for v in value.iter()? {
+                    encoded.push(element.encode(py, v?)?);
+                }
+                if matches!(self, Spec::SetOf(_)) {
+                    encoded.sort();
+                }
+                Ok(write_tlv(self.tag().unwrap(), &encoded.concat()))
+            }
+            Spec::Choice(alternatives) => {
+                let (name, v) = value.extract::<(&str, &pyo3::PyAny)>()?;
+                for (alt_name, spec) in alternatives {
+                    if alt_name.as_ref(py).to_str()? == name {
+                        return spec.encode(py, v);
+                    }
+                }
+                Err(error(format!("Unknown CHOICE alternative {name}")))
+            }
+            Spec::Implicit(tag, inner) => {
+                let der = inner.encode(py, value)?;
+                let (tlv, _) = read_tlv(&der)?;
+                Ok(write_tlv(*tag, tlv.content))
+            }
+            Spec::Explicit(tag, inner) => Ok(write_tlv(*tag, &inner.encode(py, value)?)),
+        }
+    }
+}
+
+#[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.asn1")]
+struct Schema {
+    spec: Spec,
+}
+
+#[pyo3::prelude::pymethods]
+impl Schema {
+    #[new]
+    fn new(spec: &pyo3::PyAny) -> CryptographyResult<Schema> {
+        Ok(Schema {
+            spec: Spec::from_py(spec)?,
+        })
+    }
+
+    fn decode_der<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        data: CffiBuf<'_>,
+    ) -> CryptographyResult<&'p pyo3::PyAny> {
+        let (tlv, rest) = read_tlv(data.as_bytes())?;
+        if !rest.is_empty() {
+            return Err(error("Unexpected data after the ASN.1 value"));
+        }
+        self.spec.decode(py, &tlv)
+    }
+
+    fn encode_der<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        value: &pyo3::PyAny,
+    ) -> CryptographyResult<&'p pyo3::types::PyBytes> {
+        Ok(pyo3::types::PyBytes::new(py, &self.spec.encode(py, value)?))
+    }
+}
+
+pub(crate) fn add_to_module(module: &pyo3::prelude::PyModule) -> pyo3::PyResult<()> {
+    module.add_class::<Schema>()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_tlv, write_tlv, TagId};
+
+    #[test]
+    fn test_tlv_roundtrip() {
+        for (tag, len) in [
+            (TagId::universal(16, true), 0),
+            (TagId::universal(4, false), 200),
+            (
+                TagId {
+                    class: 2,
+                    constructed: false,
+                    number: 1000,
+                },
+                70000,
+            ),
+        ] {
+            let content = vec![0x42; len];
+            let der = write_tlv(tag, &content);
+            let (tlv, rest) = read_tlv(&der).ok().unwrap();
+            assert!(rest.is_empty());
+            assert_eq!(tlv.tag, tag);
+            assert_eq!(tlv.content, &content[..]);
+            assert_eq!(tlv.full, &der[..]);
+        }
+    }
+
+    #[test]
+    fn test_read_tlv_non_der() {
+        for der in [
+            &b"\x04\x80\x00\x00"[..],
+            b"\x04\x81\x01\x00",
+            b"\x04\x82\x00\x81",
+            b"\x9f\x80\x01\x00",
+            b"\x9f\x1e\x00",
+            b"\x04\x02\x00",
+            b"",
+        ] {
+            assert!(read_tlv(der).is_err());
+        }
+    }
+}
@@ -76,6 +76,15 @@ impl From<cryptography_key_parsing::KeyParsingError> for CryptographyError {
                     exceptions::Reasons::UNSUPPORTED_ELLIPTIC_CURVE,
                 )))
             }
+            cryptography_key_parsing::KeyParsingError::UnsupportedEncryptionAlgorithm(oid) => {
+                CryptographyError::Py(exceptions::UnsupportedAlgorithm::new_err((
+                    format!("Encryption algorithm {oid} is not supported"),
+                    exceptions::Reasons::UNSUPPORTED_CIPHER,
+                )))
+            }
+            cryptography_key_parsing::KeyParsingError::IncorrectPassword => CryptographyError::Py(
+                pyo3::exceptions::PyValueError::new_err("Bad decrypt. Incorrect password?"),
+            ),
         }
     }
 }
@@ -24,10 +24,12 @@ pub(crate) enum Reasons {
 }
 
 pyo3::import_exception!(cryptography.exceptions, AlreadyFinalized);
+pyo3::import_exception!(cryptography.exceptions, FIPSViolation);
 pyo3::import_exception!(cryptography.exceptions, InternalError);
 pyo3::import_exception!(cryptography.exceptions, InvalidSignature);
 pyo3::import_exception!(cryptography.exceptions, InvalidTag);
 pyo3::import_exception!(cryptography.exceptions, UnsupportedAlgorithm);
+pyo3::import_exception!(cryptography.fernet, InvalidToken);
 pyo3::import_exception!(cryptography.x509, AttributeNotFound);
 pyo3::import_exception!(cryptography.x509, DuplicateExtension);
 pyo3::import_exception!(cryptography.x509, UnsupportedGeneralNameType);
@@ -0,0 +1,81 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+use cryptography_key_parsing::jks::parse_jks;
+use cryptography_key_parsing::KeyParsingError;
+use pyo3::IntoPy;
+
+use crate::backend::keys;
+use crate::buf::CffiBuf;
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::x509;
+
+type PyJksPrivateKey = (String, pyo3::PyObject, Vec<pyo3::PyObject>);
+type PyJksTrustedCertificate = (String, pyo3::PyObject);
+
+fn load_certificate(py: pyo3::Python<'_>, der: &[u8]) -> CryptographyResult<pyo3::PyObject> {
+    let cert = x509::certificate::load_der_x509_certificate(
+        py,
+        pyo3::types::PyBytes::new(py, der).into_py(py),
+        None,
+    )?;
+    Ok(cert.into_py(py))
+}
+
+#[pyo3::prelude::pyfunction]
+fn load_jks(
+    py: pyo3::Python<'_>,
+    data: CffiBuf<'_>,
+    password: CffiBuf<'_>,
+    key_password: Option<CffiBuf<'_>>,
+) -> CryptographyResult<(Vec<PyJksPrivateKey>, Vec<PyJksTrustedCertificate>)> {
+    let key_password = key_password.as_ref().unwrap_or(&password);
+    let parsed = parse_jks(
+        data.as_bytes(),
+        password.as_bytes(),
+        key_password.as_bytes(),
+    )
+    .map_err(|e| match e {
+        KeyParsingError::Parse(_) | KeyParsingError::InvalidKey => CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("Could not deserialize JKS data"),
+        ),
+        KeyParsingError::IncorrectPassword => CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("Invalid password or JKS data"),
+        ),
+        e => CryptographyError::from(e),
+    })?;
+
+    let mut private_keys = vec![];
+    for entry in parsed.keys {
+        let pkey = openssl::pkey::PKey::private_key_from_pkcs8(&entry.der).map_err(|_| {
+            CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+                "Invalid password or JKS data",
+            ))
+        })?;
+        // We don't support turning off RSA key validation when loading JKS
+        // keys
+        let key = keys::private_key_from_pkey(py, &pkey, false)?;
+        let cert_chain = entry
+            .cert_chain
+            .iter()
+            .map(|der| load_certificate(py, der))
+            .collect::<CryptographyResult<Vec<_>>>()?;
+        private_keys.push((entry.alias, key, cert_chain));
+    }
+
+    let trusted_certs = parsed
+        .certs
+        .into_iter()
+        .map(|entry| Ok((entry.alias, load_certificate(py, &entry.der)?)))
+        .collect::<CryptographyResult<Vec<_>>>()?;
+    Ok((private_keys, trusted_certs))
+}
+
+pub(crate) fn create_submodule(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let submod = pyo3::prelude::PyModule::new(py, "jks")?;
+
+    submod.add_function(pyo3::wrap_pyfunction!(load_jks, submod)?)?;
+
+    Ok(submod)
+}
@@ -7,11 +7,18 @@
 mod asn1;
 mod backend;
 mod buf;
+mod cbor;
+mod cose;
+mod declarative_asn1;
 mod error;
 mod exceptions;
+mod jks;
 pub(crate) mod oid;
 mod padding;
+mod pem_objects;
+mod pkcs12;
 mod pkcs7;
+mod source;
 pub(crate) mod types;
 mod x509;
 
@@ -32,6 +39,10 @@ fn _rust(py: pyo3::Python<'_>, m: &pyo3::types::PyModule) -> pyo3::PyResult<()>
     m.add_class::<oid::ObjectIdentifier>()?;
 
     m.add_submodule(asn1::create_submodule(py)?)?;
+    m.add_submodule(cose::create_submodule(py)?)?;
+    m.add_submodule(jks::create_submodule(py)?)?;
+    m.add_submodule(pem_objects::create_submodule(py)?)?;
+    m.add_submodule(pkcs12::create_submodule(py)?)?;
     m.add_submodule(pkcs7::create_submodule(py)?)?;
     m.add_submodule(exceptions::create_submodule(py)?)?;
 
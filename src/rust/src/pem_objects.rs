@@ -0,0 +1,124 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+use pyo3::IntoPy;
+
+use crate::backend::keys;
+use crate::buf::CffiBuf;
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::types;
+use crate::x509::{certificate, crl, csr};
+
+#[pyo3::prelude::pyclass(
+    frozen,
+    module = "cryptography.hazmat.bindings._rust.pem",
+    name = "PEMObject"
+)]
+struct PemObject {
+    #[pyo3(get)]
+    label: String,
+    headers: Vec<(String, String)>,
+    #[pyo3(get)]
+    der: pyo3::Py<pyo3::types::PyBytes>,
+    #[pyo3(get)]
+    value: pyo3::PyObject,
+}
+
+#[pyo3::prelude::pymethods]
+impl PemObject {
+    #[getter]
+    fn headers<'p>(&self, py: pyo3::Python<'p>) -> pyo3::PyResult<&'p pyo3::types::PyDict> {
+        let headers = pyo3::types::PyDict::new(py);
+        for (k, v) in &self.headers {
+            headers.set_item(k, v)?;
+        }
+        Ok(headers)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<PEMObject(label={})>", self.label)
+    }
+}
+
+fn is_encrypted(p: &pem::Pem) -> bool {
+    p.tag() == "ENCRYPTED PRIVATE KEY"
+        || p.headers()
+            .get("Proc-Type")
+            .map_or(false, |v| v.contains("ENCRYPTED"))
+}
+
+// Converts a PEM block into the object it contains. Blocks with labels that
+// aren't recognized have a value of `None`.
+fn load_value(
+    py: pyo3::Python<'_>,
+    p: &pem::Pem,
+    password: Option<&[u8]>,
+    unsafe_skip_rsa_key_validation: bool,
+) -> CryptographyResult<pyo3::PyObject> {
+    let der = || pyo3::types::PyBytes::new(py, p.contents()).into_py(py);
+    Ok(match p.tag() {
+        "CERTIFICATE" | "X509 CERTIFICATE" => {
+            certificate::load_der_x509_certificate(py, der(), None)?.into_py(py)
+        }
+        "X509 CRL" => crl::load_der_x509_crl(py, der(), None)?.into_py(py),
+        "CERTIFICATE REQUEST" | "NEW CERTIFICATE REQUEST" => {
+            csr::load_der_x509_csr(py, der(), None)?.into_py(py)
+        }
+        "PKCS7" => types::LOAD_DER_PKCS7_CERTIFICATES
+            .get(py)?
+            .call1((der(),))?
+            .into_py(py),
+        "PRIVATE KEY"
+        | "ENCRYPTED PRIVATE KEY"
+        | "RSA PRIVATE KEY"
+        | "EC PRIVATE KEY"
+        | "DSA PRIVATE KEY" => keys::load_pem_private_key_bytes(
+            py,
+            pem::encode(p).as_bytes(),
+            if is_encrypted(p) { password } else { None },
+            unsafe_skip_rsa_key_validation,
+        )?,
+        "PUBLIC KEY" | "RSA PUBLIC KEY" => keys::load_der_public_key_bytes(py, p.contents())?,
+        _ => py.None(),
+    })
+}
+
+#[pyo3::prelude::pyfunction]
+#[pyo3(signature = (data, password=None, *, unsafe_skip_rsa_key_validation=false))]
+fn parse_all(
+    py: pyo3::Python<'_>,
+    data: CffiBuf<'_>,
+    password: Option<CffiBuf<'_>>,
+    unsafe_skip_rsa_key_validation: bool,
+) -> CryptographyResult<Vec<PemObject>> {
+    let blocks = pem::parse_many(data.as_bytes())?;
+    if blocks.is_empty() {
+        return Err(CryptographyError::from(pem::PemError::MalformedFraming));
+    }
+    let password = password.as_ref().map(CffiBuf::as_bytes);
+
+    blocks
+        .iter()
+        .map(|p| {
+            Ok(PemObject {
+                label: p.tag().to_string(),
+                headers: p
+                    .headers()
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+                der: pyo3::types::PyBytes::new(py, p.contents()).into_py(py),
+                value: load_value(py, p, password, unsafe_skip_rsa_key_validation)?,
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn create_submodule(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let submod = pyo3::prelude::PyModule::new(py, "pem")?;
+    submod.add_function(pyo3::wrap_pyfunction!(parse_all, submod)?)?;
+    submod.add_class::<PemObject>()?;
+
+    Ok(submod)
+}
@@ -0,0 +1,109 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+use cryptography_key_parsing::pkcs12::{parse_legacy_pkcs12, Pkcs12Item};
+use cryptography_key_parsing::KeyParsingError;
+use pyo3::IntoPy;
+
+use crate::backend::keys;
+use crate::buf::CffiBuf;
+use crate::error::{CryptographyError, CryptographyResult};
+use crate::x509;
+
+type PyPkcs12Certificate = (pyo3::PyObject, Option<pyo3::PyObject>);
+
+fn certificate_from_item(
+    py: pyo3::Python<'_>,
+    item: &Pkcs12Item,
+) -> CryptographyResult<PyPkcs12Certificate> {
+    let cert = x509::certificate::load_der_x509_certificate(
+        py,
+        pyo3::types::PyBytes::new(py, &item.der).into_py(py),
+        None,
+    )?;
+    let name = item
+        .friendly_name
+        .as_ref()
+        .map(|name| pyo3::types::PyBytes::new(py, name.as_bytes()).into_py(py));
+    Ok((cert.into_py(py), name))
+}
+
+// Returns the index of the certificate belonging to `key`: the one with a
+// matching local key ID, or failing that, the first one with a matching
+// public key.
+fn find_key_certificate(
+    key: &Pkcs12Item,
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Private>,
+    certs: &[Pkcs12Item],
+) -> CryptographyResult<Option<usize>> {
+    if key.local_key_id.is_some() {
+        if let Some(idx) = certs
+            .iter()
+            .position(|c| c.local_key_id == key.local_key_id)
+        {
+            return Ok(Some(idx));
+        }
+    }
+    for (idx, cert) in certs.iter().enumerate() {
+        let cert_key = openssl::x509::X509::from_der(&cert.der)?.public_key()?;
+        if cert_key.public_eq(pkey) {
+            return Ok(Some(idx));
+        }
+    }
+    Ok(None)
+}
+
+#[pyo3::prelude::pyfunction]
+fn load_legacy_pkcs12(
+    py: pyo3::Python<'_>,
+    data: CffiBuf<'_>,
+    password: Option<CffiBuf<'_>>,
+) -> CryptographyResult<(
+    Option<pyo3::PyObject>,
+    Option<PyPkcs12Certificate>,
+    Vec<PyPkcs12Certificate>,
+)> {
+    let password = password.as_ref().map_or(&b""[..], CffiBuf::as_bytes);
+    let mut parsed = parse_legacy_pkcs12(data.as_bytes(), password).map_err(|e| match e {
+        KeyParsingError::Parse(_) | KeyParsingError::InvalidKey => CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("Could not deserialize PKCS12 data"),
+        ),
+        KeyParsingError::IncorrectPassword => CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err("Invalid password or PKCS12 data"),
+        ),
+        e => CryptographyError::from(e),
+    })?;
+
+    let mut key = None;
+    let mut cert = None;
+    // Like OpenSSL, only the first key is returned.
+    if let Some(key_item) = parsed.keys.first() {
+        let pkey = openssl::pkey::PKey::private_key_from_pkcs8(&key_item.der).map_err(|_| {
+            CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+                "Invalid password or PKCS12 data",
+            ))
+        })?;
+        // We don't support turning off RSA key validation when loading
+        // PKCS12 keys
+        key = Some(keys::private_key_from_pkey(py, &pkey, false)?);
+        if let Some(idx) = find_key_certificate(key_item, &pkey, &parsed.certs)? {
+            cert = Some(certificate_from_item(py, &parsed.certs.remove(idx))?);
+        }
+    }
+
+    let additional_certs = parsed
+        .certs
+        .iter()
+        .map(|item| certificate_from_item(py, item))
+        .collect::<CryptographyResult<Vec<_>>>()?;
+    Ok((key, cert, additional_certs))
+}
+
+pub(crate) fn create_submodule(py: pyo3::Python<'_>) -> pyo3::PyResult<&pyo3::prelude::PyModule> {
+    let submod = pyo3::prelude::PyModule::new(py, "pkcs12")?;
+
+    submod.add_function(pyo3::wrap_pyfunction!(load_legacy_pkcs12, submod)?)?;
+
+    Ok(submod)
+}
@@ -0,0 +1,145 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Reads PEM blocks from a path or a binary file object one at a time, so
+//! that large bundles don't have to be read into memory all at once.
+
+use pyo3::types::IntoPyDict;
+
+use crate::buf::CffiBuf;
+use crate::error::CryptographyResult;
+use crate::types;
+
+// Files opened from a path that are at least this large are memory mapped
+// rather than read.
+const MMAP_THRESHOLD: u64 = 1 << 20;
+// How much is read from a file object at a time.
+const CHUNK_SIZE: usize = 1 << 16;
+
+const BEGIN: &[u8] = b"-----BEGIN ";
+const END: &[u8] = b"-----END ";
+const DASHES: &[u8] = b"-----";
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+// Returns the offset just past the first complete PEM block in `data`.
+fn pem_block_end(data: &[u8]) -> Option<usize> {
+    let begin = find(data, BEGIN)?;
+    let end = begin + find(&data[begin..], END)? + END.len();
+    Some(end + find(&data[end..], DASHES)? + DASHES.len())
+}
+
+/// Calls `f` with each PEM block in `file`, which is either a path or a file
+/// object opened in binary mode, until it returns `false`. Text outside of
+/// PEM blocks is ignored.
+pub(crate) fn for_each_pem_block<F>(
+    py: pyo3::Python<'_>,
+    file: &pyo3::PyAny,
+    f: F,
+) -> CryptographyResult<()>
+where
+    F: FnMut(pem::Pem) -> CryptographyResult<bool>,
+{
+    if !file.is_instance_of::<pyo3::types::PyString>()
+        && !file.hasattr(pyo3::intern!(py, "__fspath__"))?
+    {
+        return read_pem_blocks(py, file, f);
+    }
+
+    let file = types::IO_OPEN.get(py)?.call1((file, "rb"))?;
+    let result = open_pem_blocks(py, file, f);
+    file.call_method0(pyo3::intern!(py, "close"))?;
+    result
+}
+
+fn open_pem_blocks<F>(py: pyo3::Python<'_>, file: &pyo3::PyAny, mut f: F) -> CryptographyResult<()>
+where
+    F: FnMut(pem::Pem) -> CryptographyResult<bool>,
+{
+    let fileno = file.call_method0(pyo3::intern!(py, "fileno"))?;
+    let size: u64 = types::OS_FSTAT
+        .get(py)?
+        .call1((fileno,))?
+        .getattr(pyo3::intern!(py, "st_size"))?
+        .extract()?;
+    if size < MMAP_THRESHOLD {
+        return read_pem_blocks(py, file, f);
+    }
+
+    // The map holds its own reference to the file, so it stays valid after
+    // the file is closed, and is unmapped once it's no longer referenced.
+    let kwargs = [("access", types::MMAP_ACCESS_READ.get(py)?)].into_py_dict(py);
+    let map = types::MMAP_MMAP.get(py)?.call((fileno, 0), Some(kwargs))?;
+    let buf = map.extract::<CffiBuf<'_>>()?;
+    let mut data = buf.as_bytes();
+    while let Some(end) = pem_block_end(data) {
+        if !f(pem::parse(&data[..end])?)? {
+            break;
+        }
+        data = &data[end..];
+    }
+    Ok(())
+}
+
+fn read_pem_blocks<F>(py: pyo3::Python<'_>, file: &pyo3::PyAny, mut f: F) -> CryptographyResult<()>
+where
+    F: FnMut(pem::Pem) -> CryptographyResult<bool>,
+{
+    let mut buf = vec![];
+    loop {
+        let chunk = file.call_method1(pyo3::intern!(py, "read"), (CHUNK_SIZE,))?;
+        let chunk = chunk
+            .downcast::<pyo3::types::PyBytes>()
+            .map_err(|_| {
+                pyo3::exceptions::PyTypeError::new_err("file must be opened in binary mode.")
+            })?
+            .as_bytes();
+        buf.extend_from_slice(chunk);
+
+        let mut start = 0;
+        while let Some(end) = pem_block_end(&buf[start..]) {
+            if !f(pem::parse(&buf[start..start + end])?)? {
+                return Ok(());
+            }
+            start += end;
+        }
+        buf.drain(..start);
+        // Only the start of a block needs to be kept, anything before it is
+        // discarded so that long stretches of other text aren't buffered.
+        match find(&buf, BEGIN) {
+            Some(begin) => {
+                buf.drain(..begin);
+            }
+            None => {
+                buf.drain(..buf.len().saturating_sub(BEGIN.len()));
+            }
+        }
+
+        if chunk.is_empty() {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pem_block_end;
+
+    #[test]
+    fn test_pem_block_end() {
+        assert_eq!(pem_block_end(b""), None);
+        assert_eq!(pem_block_end(b"-----BEGIN A-----\n"), None);
+        assert_eq!(pem_block_end(b"-----BEGIN A-----\n-----END A---"), None);
+
+        let block = b"-----BEGIN A-----\nAAAA\n-----END A-----";
+        assert_eq!(pem_block_end(block), Some(block.len()));
+
+        let mut data = b"junk\n".to_vec();
+        data.extend_from_slice(block);
+        data.extend_from_slice(b"\n-----BEGIN B-----\n");
+        assert_eq!(pem_block_end(&data), Some(5 + block.len()));
+    }
+}
@@ -36,9 +36,19 @@ pub static DATETIME_TIMEZONE_UTC: LazyPyImport =
 pub static IPADDRESS_IPADDRESS: LazyPyImport = LazyPyImport::new("ipaddress", &["ip_address"]);
 pub static IPADDRESS_IPNETWORK: LazyPyImport = LazyPyImport::new("ipaddress", &["ip_network"]);
 pub static OS_URANDOM: LazyPyImport = LazyPyImport::new("os", &["urandom"]);
+pub static OS_FSTAT: LazyPyImport = LazyPyImport::new("os", &["fstat"]);
+pub static IO_OPEN: LazyPyImport = LazyPyImport::new("io", &["open"]);
+pub static MMAP_MMAP: LazyPyImport = LazyPyImport::new("mmap", &["mmap"]);
+pub static MMAP_ACCESS_READ: LazyPyImport = LazyPyImport::new("mmap", &["ACCESS_READ"]);
+pub static JSON_LOADS: LazyPyImport = LazyPyImport::new("json", &["loads"]);
+
+pub static ASN1_BIT_STRING_VALUE: LazyPyImport =
+    LazyPyImport::new("cryptography.hazmat.asn1", &["BitStringValue"]);
 
 pub static DEPRECATED_IN_36: LazyPyImport =
     LazyPyImport::new("cryptography.utils", &["DeprecatedIn36"]);
+pub static DEPRECATED_IN_40: LazyPyImport =
+    LazyPyImport::new("cryptography.utils", &["DeprecatedIn40"]);
 pub static DEPRECATED_IN_41: LazyPyImport =
     LazyPyImport::new("cryptography.utils", &["DeprecatedIn41"]);
 pub static DEPRECATED_IN_42: LazyPyImport =
@@ -73,6 +83,7 @@ pub static ENCODING_X962: LazyPyImport = LazyPyImport::new(
     &["Encoding", "X962"],
 );
 
+
 pub static PRIVATE_FORMAT: LazyPyImport = LazyPyImport::new(
     "cryptography.hazmat.primitives.serialization",
     &["PrivateFormat"],
@@ -85,6 +96,10 @@ pub static PRIVATE_FORMAT_PKCS8: LazyPyImport = LazyPyImport::new(
     "cryptography.hazmat.primitives.serialization",
     &["PrivateFormat", "PKCS8"],
 );
+pub static PRIVATE_FORMAT_ONE_ASYMMETRIC_KEY: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.serialization",
+    &["PrivateFormat", "OneAsymmetricKey"],
+);
 pub static PRIVATE_FORMAT_RAW: LazyPyImport = LazyPyImport::new(
     "cryptography.hazmat.primitives.serialization",
     &["PrivateFormat", "Raw"],
@@ -127,6 +142,14 @@ pub static PARAMETER_FORMAT_PKCS3: LazyPyImport = LazyPyImport::new(
     "cryptography.hazmat.primitives.serialization",
     &["ParameterFormat", "PKCS3"],
 );
+pub static PARAMETER_FORMAT_X942: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.serialization",
+    &["ParameterFormat", "X942"],
+);
+pub static PARAMETER_FORMAT_EC_PARAMETERS: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.serialization",
+    &["ParameterFormat", "ECParameters"],
+);
 
 pub static KEY_SERIALIZATION_ENCRYPTION: LazyPyImport = LazyPyImport::new(
     "cryptography.hazmat.primitives.serialization",
@@ -144,6 +167,18 @@ pub static ENCRYPTION_BUILDER: LazyPyImport = LazyPyImport::new(
     "cryptography.hazmat.primitives.serialization",
     &["_KeySerializationEncryption"],
 );
+pub static KEY_SERIALIZATION_ENCRYPTION_BUILDER: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives._serialization",
+    &["KeySerializationEncryptionBuilder"],
+);
+pub static PRIVATE_KEY_CIPHER_AES128CBC: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.serialization",
+    &["PrivateKeyCipher", "AES128CBC"],
+);
+pub static PRIVATE_KEY_CIPHER_AES256CBC: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.serialization",
+    &["PrivateKeyCipher", "AES256CBC"],
+);
 
 pub static SERIALIZE_SSH_PRIVATE_KEY: LazyPyImport = LazyPyImport::new(
     "cryptography.hazmat.primitives.serialization.ssh",
@@ -153,6 +188,26 @@ pub static SERIALIZE_SSH_PUBLIC_KEY: LazyPyImport = LazyPyImport::new(
     "cryptography.hazmat.primitives.serialization.ssh",
     &["serialize_ssh_public_key"],
 );
+pub static LOAD_SSH_PUBLIC_IDENTITY: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.serialization.ssh",
+    &["load_ssh_public_identity"],
+);
+pub static LOAD_SSH_SECURITY_KEY_PRIVATE_KEY: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.serialization.ssh",
+    &["_load_ssh_security_key_private_key"],
+);
+pub static LOAD_KEY_AND_CERTIFICATES: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.serialization.pkcs12",
+    &["load_key_and_certificates"],
+);
+pub static BCRYPT_KDF: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.serialization.ssh",
+    &["_bcrypt_kdf"],
+);
+pub static ARGON2_KDF: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.serialization.ppk",
+    &["_argon2_kdf"],
+);
 
 pub static SIG_OIDS_TO_HASH: LazyPyImport =
     LazyPyImport::new("cryptography.hazmat._oid", &["_SIG_OIDS_TO_HASH"]);
@@ -207,6 +262,18 @@ pub static NAME_CONSTRAINTS: LazyPyImport =
     LazyPyImport::new("cryptography.x509", &["NameConstraints"]);
 pub static MS_CERTIFICATE_TEMPLATE: LazyPyImport =
     LazyPyImport::new("cryptography.x509", &["MSCertificateTemplate"]);
+pub static ANDROID_KEY_ATTESTATION: LazyPyImport =
+    LazyPyImport::new("cryptography.x509", &["AndroidKeyAttestation"]);
+pub static ANDROID_AUTHORIZATION_LIST: LazyPyImport =
+    LazyPyImport::new("cryptography.x509", &["AndroidAuthorizationList"]);
+pub static ANDROID_ROOT_OF_TRUST: LazyPyImport =
+    LazyPyImport::new("cryptography.x509", &["AndroidRootOfTrust"]);
+pub static ANDROID_SECURITY_LEVEL: LazyPyImport =
+    LazyPyImport::new("cryptography.x509", &["AndroidSecurityLevel"]);
+pub static ANDROID_VERIFIED_BOOT_STATE: LazyPyImport =
+    LazyPyImport::new("cryptography.x509", &["AndroidVerifiedBootState"]);
+pub static APPLE_ATTESTATION_NONCE: LazyPyImport =
+    LazyPyImport::new("cryptography.x509", &["AppleAttestationNonce"]);
 pub static CRL_DISTRIBUTION_POINTS: LazyPyImport =
     LazyPyImport::new("cryptography.x509", &["CRLDistributionPoints"]);
 pub static BASIC_CONSTRAINTS: LazyPyImport =
@@ -322,6 +389,10 @@ pub static SMIME_ENCODE: LazyPyImport = LazyPyImport::new(
     "cryptography.hazmat.primitives.serialization.pkcs7",
     &["_smime_encode"],
 );
+pub static LOAD_DER_PKCS7_CERTIFICATES: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.serialization.pkcs7",
+    &["load_der_pkcs7_certificates"],
+);
 
 pub static HASHES_MODULE: LazyPyImport =
     LazyPyImport::new("cryptography.hazmat.primitives.hashes", &[]);
@@ -334,6 +405,19 @@ pub static EXTENDABLE_OUTPUT_FUNCTION: LazyPyImport = LazyPyImport::new(
 pub static SHA1: LazyPyImport =
     LazyPyImport::new("cryptography.hazmat.primitives.hashes", &["SHA1"]);
 
+pub static HKDF: LazyPyImport =
+    LazyPyImport::new("cryptography.hazmat.primitives.kdf.hkdf", &["HKDF"]);
+pub static X963KDF: LazyPyImport =
+    LazyPyImport::new("cryptography.hazmat.primitives.kdf.x963kdf", &["X963KDF"]);
+pub static CONCATKDF_HASH: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.kdf.concatkdf",
+    &["ConcatKDFHash"],
+);
+pub static CONCATKDF_HMAC: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.kdf.concatkdf",
+    &["ConcatKDFHMAC"],
+);
+
 pub static PREHASHED: LazyPyImport = LazyPyImport::new(
     "cryptography.hazmat.primitives.asymmetric.utils",
     &["Prehashed"],
@@ -401,6 +485,10 @@ pub static ELLIPTIC_CURVE_PRIVATE_KEY: LazyPyImport = LazyPyImport::new(
     "cryptography.hazmat.primitives.asymmetric.ec",
     &["EllipticCurvePrivateKey"],
 );
+pub static EC_LOAD_DER_PUBLIC_KEY: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.asymmetric.ec",
+    &["_load_der_public_key"],
+);
 pub static ELLIPTIC_CURVE_PUBLIC_KEY: LazyPyImport = LazyPyImport::new(
     "cryptography.hazmat.primitives.asymmetric.ec",
     &["EllipticCurvePublicKey"],
@@ -409,11 +497,37 @@ pub static CURVE_TYPES: LazyPyImport = LazyPyImport::new(
     "cryptography.hazmat.primitives.asymmetric.ec",
     &["_CURVE_TYPES"],
 );
+pub static BIP340_SCHNORR: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.asymmetric.ec",
+    &["BIP340Schnorr"],
+);
 pub static ECDSA: LazyPyImport =
     LazyPyImport::new("cryptography.hazmat.primitives.asymmetric.ec", &["ECDSA"]);
+pub static ECDSA_SIGNATURE_FORMAT_P1363: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.asymmetric.ec",
+    &["ECDSASignatureFormat", "P1363"],
+);
 pub static ECDH: LazyPyImport =
     LazyPyImport::new("cryptography.hazmat.primitives.asymmetric.ec", &["ECDH"]);
 
+pub static BLS_VARIANT_MIN_PK: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.asymmetric.bls",
+    &["BLSVariant", "MIN_PK"],
+);
+pub static BLS_VARIANT_MIN_SIG: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.asymmetric.bls",
+    &["BLSVariant", "MIN_SIG"],
+);
+
+pub static ECVRF_SUITE_P256_SHA256_TAI: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.asymmetric.ecvrf",
+    &["ECVRFSuite", "P256_SHA256_TAI"],
+);
+pub static ECVRF_SUITE_EDWARDS25519_SHA512_TAI: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.asymmetric.ecvrf",
+    &["ECVRFSuite", "EDWARDS25519_SHA512_TAI"],
+);
+
 pub static ED25519_PRIVATE_KEY: LazyPyImport = LazyPyImport::new(
     "cryptography.hazmat.primitives.asymmetric.ed25519",
     &["Ed25519PrivateKey"],
@@ -432,6 +546,58 @@ pub static ED448_PUBLIC_KEY: LazyPyImport = LazyPyImport::new(
     &["Ed448PublicKey"],
 );
 
+pub static ML_DSA_PARAMETER_SET_44: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.asymmetric.mldsa",
+    &["MLDSAParameterSet", "ML_DSA_44"],
+);
+pub static ML_DSA_PARAMETER_SET_65: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.asymmetric.mldsa",
+    &["MLDSAParameterSet", "ML_DSA_65"],
+);
+#[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+pub static ML_DSA_PARAMETER_SET_87: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.asymmetric.mldsa",
+    &["MLDSAParameterSet", "ML_DSA_87"],
+);
+pub static MLDSA_PRIVATE_KEY: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.asymmetric.mldsa",
+    &["MLDSAPrivateKey"],
+);
+pub static MLDSA_PUBLIC_KEY: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.asymmetric.mldsa",
+    &["MLDSAPublicKey"],
+);
+
+#[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+pub static SLH_DSA_PARAMETER_SET: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.asymmetric.slhdsa",
+    &["SLHDSAParameterSet"],
+);
+pub static SLHDSA_PRIVATE_KEY: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.asymmetric.slhdsa",
+    &["SLHDSAPrivateKey"],
+);
+pub static SLHDSA_PUBLIC_KEY: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.asymmetric.slhdsa",
+    &["SLHDSAPublicKey"],
+);
+
+pub static FALCON_PARAMETER_SET: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.asymmetric.falcon",
+    &["FalconParameterSet"],
+);
+
+#[cfg(CRYPTOGRAPHY_OPENSSL_350_OR_GREATER)]
+pub static HYBRID_GROUP: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.asymmetric.hybrid",
+    &["HybridGroup"],
+);
+
+pub static EXTERNAL_SIGNER: LazyPyImport = LazyPyImport::new(
+    "cryptography.hazmat.primitives.asymmetric.signer",
+    &["ExternalSigner"],
+);
+
 pub static DSA_PRIVATE_KEY: LazyPyImport = LazyPyImport::new(
     "cryptography.hazmat.primitives.asymmetric.dsa",
     &["DSAPrivateKey"],
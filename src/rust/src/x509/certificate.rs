@@ -8,6 +8,7 @@ use std::hash::{Hash, Hasher};
 use cryptography_x509::certificate::Certificate as RawCertificate;
 use cryptography_x509::common::{AlgorithmParameters, Asn1ReadableOrWritable};
 use cryptography_x509::extensions::{
+    AndroidAuthorizationList, AndroidKeyDescription, AndroidRootOfTrust, AppleAttestationNonce,
     AuthorityKeyIdentifier, BasicConstraints, DisplayText, DistributionPoint,
     DistributionPointName, DuplicateExtensionsError, IssuerAlternativeName, KeyUsage,
     MSCertificateTemplate, NameConstraints, PolicyConstraints, PolicyInformation,
@@ -26,7 +27,7 @@ use crate::backend::{hashes, keys};
 use crate::error::{CryptographyError, CryptographyResult};
 use crate::x509::verify::PyCryptoOps;
 use crate::x509::{extensions, sct, sign};
-use crate::{exceptions, types, x509};
+use crate::{exceptions, source, types, x509};
 
 self_cell::self_cell!(
     pub(crate) struct OwnedCertificate {
@@ -378,7 +379,31 @@ fn load_pem_x509_certificates(
 }
 
 #[pyo3::prelude::pyfunction]
-fn load_der_x509_certificate(
+fn load_pem_x509_certificates_from_file(
+    py: pyo3::Python<'_>,
+    file: &pyo3::PyAny,
+) -> CryptographyResult<Vec<Certificate>> {
+    let mut certs = vec![];
+    source::for_each_pem_block(py, file, |p| {
+        if p.tag() == "CERTIFICATE" || p.tag() == "X509 CERTIFICATE" {
+            certs.push(load_der_x509_certificate(
+                py,
+                pyo3::types::PyBytes::new(py, p.contents()).into_py(py),
+                None,
+            )?);
+        }
+        Ok(true)
+    })?;
+
+    if certs.is_empty() {
+        return Err(CryptographyError::from(pem::PemError::MalformedFraming));
+    }
+
+    Ok(certs)
+}
+
+#[pyo3::prelude::pyfunction]
+pub(crate) fn load_der_x509_certificate(
     py: pyo3::Python<'_>,
     data: pyo3::Py<pyo3::types::PyBytes>,
     backend: Option<&pyo3::PyAny>,
@@ -694,6 +719,66 @@ pub(crate) fn parse_access_descriptions(
     Ok(ads.to_object(py))
 }
 
+fn parse_android_authorization_list<'p>(
+    py: pyo3::Python<'p>,
+    list: &AndroidAuthorizationList<'_>,
+) -> Result<&'p pyo3::PyAny, CryptographyError> {
+    let kwargs = pyo3::types::PyDict::new(py);
+    for tlv in list.unwrap_read().clone() {
+        let mut field = None;
+        for (number, name, kind) in extensions::ANDROID_AUTHORIZATION_FIELDS {
+            if tlv.tag() == extensions::android_authorization_tag(*number)? {
+                field = Some((name, kind));
+                break;
+            }
+        }
+        // Fields we don't know about are skipped, newer Keystore versions
+        // routinely add them.
+        let (name, kind) = match field {
+            Some(field) => field,
+            None => continue,
+        };
+        let value = match kind {
+            extensions::AndroidFieldKind::Integer => {
+                asn1::parse_single::<i64>(tlv.data())?.to_object(py)
+            }
+            extensions::AndroidFieldKind::IntegerSet => {
+                let values =
+                    asn1::parse_single::<asn1::SetOf<'_, i64>>(tlv.data())?.collect::<Vec<_>>();
+                pyo3::types::PyFrozenSet::new(py, &values)?.to_object(py)
+            }
+            extensions::AndroidFieldKind::Null => {
+                asn1::parse_single::<()>(tlv.data())?;
+                true.to_object(py)
+            }
+            extensions::AndroidFieldKind::OctetString => {
+                pyo3::types::PyBytes::new(py, asn1::parse_single::<&[u8]>(tlv.data())?)
+                    .to_object(py)
+            }
+            extensions::AndroidFieldKind::RootOfTrust => {
+                let rot = asn1::parse_single::<AndroidRootOfTrust<'_>>(tlv.data())?;
+                let state = types::ANDROID_VERIFIED_BOOT_STATE
+                    .get(py)?
+                    .call1((rot.verified_boot_state.value(),))?;
+                types::ANDROID_ROOT_OF_TRUST
+                    .get(py)?
+                    .call1((
+                        pyo3::types::PyBytes::new(py, rot.verified_boot_key),
+                        rot.device_locked,
+                        state,
+                        rot.verified_boot_hash
+                            .map(|h| pyo3::types::PyBytes::new(py, h)),
+                    ))?
+                    .to_object(py)
+            }
+        };
+        kwargs.set_item(*name, value)?;
+    }
+    Ok(types::ANDROID_AUTHORIZATION_LIST
+        .get(py)?
+        .call((), Some(kwargs))?)
+}
+
 pub fn parse_cert_ext<'p>(
     py: pyo3::Python<'p>,
     ext: &Extension<'_>,
@@ -828,6 +913,28 @@ pub fn parse_cert_ext<'p>(
                 ms_cert_tpl.minor_version,
             ))?))
         }
+        oid::ANDROID_KEY_ATTESTATION_OID => {
+            let kd = ext.value::<AndroidKeyDescription<'_>>()?;
+            let security_level = types::ANDROID_SECURITY_LEVEL.get(py)?;
+            Ok(Some(types::ANDROID_KEY_ATTESTATION.get(py)?.call1((
+                kd.attestation_version,
+                security_level.call1((kd.attestation_security_level.value(),))?,
+                kd.keymaster_version,
+                security_level.call1((kd.keymaster_security_level.value(),))?,
+                pyo3::types::PyBytes::new(py, kd.attestation_challenge),
+                pyo3::types::PyBytes::new(py, kd.unique_id),
+                parse_android_authorization_list(py, &kd.software_enforced)?,
+                parse_android_authorization_list(py, &kd.hardware_enforced)?,
+            ))?))
+        }
+        oid::APPLE_ATTESTATION_NONCE_OID => {
+            let nonce = ext.value::<AppleAttestationNonce<'_>>()?;
+            Ok(Some(
+                types::APPLE_ATTESTATION_NONCE
+                    .get(py)?
+                    .call1((pyo3::types::PyBytes::new(py, nonce.nonce),))?,
+            ))
+        }
         _ => Ok(None),
     }
 }
@@ -923,6 +1030,10 @@ pub(crate) fn add_to_module(module: &pyo3::prelude::PyModule) -> pyo3::PyResult<
     module.add_function(pyo3::wrap_pyfunction!(load_der_x509_certificate, module)?)?;
     module.add_function(pyo3::wrap_pyfunction!(load_pem_x509_certificate, module)?)?;
     module.add_function(pyo3::wrap_pyfunction!(load_pem_x509_certificates, module)?)?;
+    module.add_function(pyo3::wrap_pyfunction!(
+        load_pem_x509_certificates_from_file,
+        module
+    )?)?;
     module.add_function(pyo3::wrap_pyfunction!(create_x509_certificate, module)?)?;
 
     module.add_class::<Certificate>()?;
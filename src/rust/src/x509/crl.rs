@@ -24,7 +24,7 @@ use crate::x509::{certificate, extensions, sign};
 use crate::{exceptions, types, x509};
 
 #[pyo3::prelude::pyfunction]
-fn load_der_x509_crl(
+pub(crate) fn load_der_x509_crl(
     py: pyo3::Python<'_>,
     data: pyo3::Py<pyo3::types::PyBytes>,
     backend: Option<&pyo3::PyAny>,
@@ -81,7 +81,7 @@ self_cell::self_cell!(
 );
 
 #[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.x509")]
-struct CertificateRevocationList {
+pub(crate) struct CertificateRevocationList {
     owned: Arc<OwnedCertificateRevocationList>,
 
     revoked_certs: pyo3::sync::GILOnceCell<Vec<OwnedRevokedCertificate>>,
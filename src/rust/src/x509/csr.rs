@@ -26,7 +26,7 @@ self_cell::self_cell!(
 );
 
 #[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.x509")]
-struct CertificateSigningRequest {
+pub(crate) struct CertificateSigningRequest {
     raw: OwnedCsr,
     cached_extensions: pyo3::sync::GILOnceCell<pyo3::PyObject>,
 }
@@ -243,7 +243,7 @@ fn load_pem_x509_csr(
 }
 
 #[pyo3::prelude::pyfunction]
-fn load_der_x509_csr(
+pub(crate) fn load_der_x509_csr(
     py: pyo3::Python<'_>,
     data: pyo3::Py<pyo3::types::PyBytes>,
     backend: Option<&pyo3::PyAny>,
@@ -378,6 +378,199 @@ fn encode_scts(ext: &pyo3::PyAny) -> CryptographyResult<Vec<u8>> {
     Ok(asn1::write_single(&result.as_slice())?)
 }
 
+pub(crate) enum AndroidFieldKind {
+    Integer,
+    IntegerSet,
+    Null,
+    OctetString,
+    RootOfTrust,
+}
+
+// The typed `AuthorizationList` fields, as (tag number, attribute name, kind),
+// in ascending tag order as required for DER.
+pub(crate) const ANDROID_AUTHORIZATION_FIELDS: &[(u32, &str, AndroidFieldKind)] = &[
+    (1, "purpose", AndroidFieldKind::IntegerSet),
+    (2, "algorithm", AndroidFieldKind::Integer),
+    (3, "key_size", AndroidFieldKind::Integer),
+    (5, "digest", AndroidFieldKind::IntegerSet),
+    (6, "padding", AndroidFieldKind::IntegerSet),
+    (10, "ec_curve", AndroidFieldKind::Integer),
+    (200, "rsa_public_exponent", AndroidFieldKind::Integer),
+    (203, "mgf_digest", AndroidFieldKind::IntegerSet),
+    (303, "rollback_resistance", AndroidFieldKind::Null),
+    (305, "early_boot_only", AndroidFieldKind::Null),
+    (400, "active_date_time", AndroidFieldKind::Integer),
+    (
+        401,
+        "origination_expire_date_time",
+        AndroidFieldKind::Integer,
+    ),
+    (402, "usage_expire_date_time", AndroidFieldKind::Integer),
+    (405, "usage_count_limit", AndroidFieldKind::Integer),
+    (503, "no_auth_required", AndroidFieldKind::Null),
+    (504, "user_auth_type", AndroidFieldKind::Integer),
+    (505, "auth_timeout", AndroidFieldKind::Integer),
+    (506, "allow_while_on_body", AndroidFieldKind::Null),
+    (
+        507,
+        "trusted_user_presence_required",
+        AndroidFieldKind::Null,
+    ),
+    (508, "trusted_confirmation_required", AndroidFieldKind::Null),
+    (509, "unlocked_device_required", AndroidFieldKind::Null),
+    (701, "creation_date_time", AndroidFieldKind::Integer),
+    (702, "origin", AndroidFieldKind::Integer),
+    (704, "root_of_trust", AndroidFieldKind::RootOfTrust),
+    (705, "os_version", AndroidFieldKind::Integer),
+    (706, "os_patch_level", AndroidFieldKind::Integer),
+    (
+        709,
+        "attestation_application_id",
+        AndroidFieldKind::OctetString,
+    ),
+    (710, "attestation_id_brand", AndroidFieldKind::OctetString),
+    (711, "attestation_id_device", AndroidFieldKind::OctetString),
+    (712, "attestation_id_product", AndroidFieldKind::OctetString),
+    (713, "attestation_id_serial", AndroidFieldKind::OctetString),
+    (714, "attestation_id_imei", AndroidFieldKind::OctetString),
+    (715, "attestation_id_meid", AndroidFieldKind::OctetString),
+    (
+        716,
+        "attestation_id_manufacturer",
+        AndroidFieldKind::OctetString,
+    ),
+    (717, "attestation_id_model", AndroidFieldKind::OctetString),
+    (718, "vendor_patch_level", AndroidFieldKind::Integer),
+    (719, "boot_patch_level", AndroidFieldKind::Integer),
+    (720, "device_unique_attestation", AndroidFieldKind::Null),
+    (
+        723,
+        "attestation_id_second_imei",
+        AndroidFieldKind::OctetString,
+    ),
+    (724, "module_hash", AndroidFieldKind::OctetString),
+];
+
+// Returns the tag of an explicitly tagged `AuthorizationList` field. Most
+// field numbers are above 30, so they use the high tag number form.
+pub(crate) fn android_authorization_tag(number: u32) -> asn1::ParseResult<asn1::Tag> {
+    let mut header = vec![];
+    if number < 31 {
+        header.push(0xa0 | number as u8);
+    } else {
+        header.push(0xbf);
+        let groups = (32 - number.leading_zeros() + 6) / 7;
+        for i in (0..groups).rev() {
+            let b = ((number >> (i * 7)) & 0x7f) as u8;
+            header.push(if i == 0 { b } else { b | 0x80 });
+        }
+    }
+    Ok(asn1::Tag::from_bytes(&header)?.0)
+}
+
+fn encode_android_authorization_list(
+    py: pyo3::Python<'_>,
+    list: &pyo3::PyAny,
+) -> CryptographyResult<Vec<(asn1::Tag, Vec<u8>)>> {
+    let mut fields = vec![];
+    for (number, name, kind) in ANDROID_AUTHORIZATION_FIELDS {
+        let value = list.getattr(*name)?;
+        if value.is_none() {
+            continue;
+        }
+        let der = match kind {
+            AndroidFieldKind::Integer => asn1::write_single(&value.extract::<i64>()?)?,
+            AndroidFieldKind::IntegerSet => {
+                let mut values = value
+                    .iter()?
+                    .map(|v| v?.extract::<i64>())
+                    .collect::<pyo3::PyResult<Vec<_>>>()?;
+                values.sort_unstable();
+                asn1::write_single(&asn1::SetOfWriter::new(values))?
+            }
+            AndroidFieldKind::Null => {
+                if !value.is_true()? {
+                    continue;
+                }
+                asn1::write_single(&())?
+            }
+            AndroidFieldKind::OctetString => asn1::write_single(&value.extract::<&[u8]>()?)?,
+            AndroidFieldKind::RootOfTrust => {
+                let verified_boot_state = value
+                    .getattr(pyo3::intern!(py, "verified_boot_state"))?
+                    .getattr(pyo3::intern!(py, "value"))?
+                    .extract::<u32>()?;
+                asn1::write_single(&extensions::AndroidRootOfTrust {
+                    verified_boot_key: value
+                        .getattr(pyo3::intern!(py, "verified_boot_key"))?
+                        .extract()?,
+                    device_locked: value
+                        .getattr(pyo3::intern!(py, "device_locked"))?
+                        .extract()?,
+                    verified_boot_state: asn1::Enumerated::new(verified_boot_state),
+                    verified_boot_hash: value
+                        .getattr(pyo3::intern!(py, "verified_boot_hash"))?
+                        .extract()?,
+                })?
+            }
+        };
+        fields.push((android_authorization_tag(*number)?, der));
+    }
+    Ok(fields)
+}
+
+fn android_authorization_list_writer(
+    fields: &[(asn1::Tag, Vec<u8>)],
+) -> extensions::AndroidAuthorizationList<'_> {
+    common::Asn1ReadableOrWritable::new_write(asn1::SequenceOfWriter::new(
+        fields
+            .iter()
+            .map(|(tag, value)| common::RawTlv::new(*tag, value))
+            .collect(),
+    ))
+}
+
+fn encode_android_key_attestation(
+    py: pyo3::Python<'_>,
+    ext: &pyo3::PyAny,
+) -> CryptographyResult<Vec<u8>> {
+    let security_level = |name: &pyo3::types::PyString| -> pyo3::PyResult<asn1::Enumerated> {
+        Ok(asn1::Enumerated::new(
+            ext.getattr(name)?
+                .getattr(pyo3::intern!(py, "value"))?
+                .extract()?,
+        ))
+    };
+    let software_enforced = encode_android_authorization_list(
+        py,
+        ext.getattr(pyo3::intern!(py, "software_enforced"))?,
+    )?;
+    let hardware_enforced = encode_android_authorization_list(
+        py,
+        ext.getattr(pyo3::intern!(py, "hardware_enforced"))?,
+    )?;
+    let key_description = extensions::AndroidKeyDescription {
+        attestation_version: ext
+            .getattr(pyo3::intern!(py, "attestation_version"))?
+            .extract()?,
+        attestation_security_level: security_level(pyo3::intern!(
+            py,
+            "attestation_security_level"
+        ))?,
+        keymaster_version: ext
+            .getattr(pyo3::intern!(py, "keymaster_version"))?
+            .extract()?,
+        keymaster_security_level: security_level(pyo3::intern!(py, "keymaster_security_level"))?,
+        attestation_challenge: ext
+            .getattr(pyo3::intern!(py, "attestation_challenge"))?
+            .extract()?,
+        unique_id: ext.getattr(pyo3::intern!(py, "unique_id"))?.extract()?,
+        software_enforced: android_authorization_list_writer(&software_enforced),
+        hardware_enforced: android_authorization_list_writer(&hardware_enforced),
+    };
+    Ok(asn1::write_single(&key_description)?)
+}
+
 pub(crate) fn encode_extension(
     py: pyo3::Python<'_>,
     oid: &asn1::ObjectIdentifier,
@@ -505,6 +698,16 @@ pub(crate) fn encode_extension(
             };
             Ok(Some(asn1::write_single(&mstpl)?))
         }
+        &oid::ANDROID_KEY_ATTESTATION_OID => {
+            let der = encode_android_key_attestation(py, ext)?;
+            Ok(Some(der))
+        }
+        &oid::APPLE_ATTESTATION_NONCE_OID => {
+            let nonce = extensions::AppleAttestationNonce {
+                nonce: ext.getattr(pyo3::intern!(py, "nonce"))?.extract()?,
+            };
+            Ok(Some(asn1::write_single(&nonce)?))
+        }
         _ => Ok(None),
     }
 }
@@ -17,7 +17,8 @@ use crate::error::{CryptographyError, CryptographyResult};
 use crate::x509::{certificate, crl, extensions, ocsp, py_to_datetime, sct};
 use crate::{exceptions, types, x509};
 
-const BASIC_RESPONSE_OID: asn1::ObjectIdentifier = asn1::oid!(1, 3, 6, 1, 5, 5, 7, 48, 1, 1);
+pub(crate) const BASIC_RESPONSE_OID: asn1::ObjectIdentifier =
+    asn1::oid!(1, 3, 6, 1, 5, 5, 7, 48, 1, 1);
 
 #[pyo3::prelude::pyfunction]
 fn load_der_ocsp_response(
@@ -182,23 +183,17 @@ impl OCSPResponse {
         &self,
         py: pyo3::Python<'p>,
     ) -> Result<&'p pyo3::PyAny, CryptographyError> {
-        let hash_alg = types::SIG_OIDS_TO_HASH
-            .get(py)?
-            .get_item(self.signature_algorithm_oid(py)?);
-        match hash_alg {
-            Ok(data) => Ok(data),
-            Err(_) => {
-                let exc_messsage = format!(
-                    "Signature algorithm OID: {} not recognized",
-                    self.requires_successful_response()?
-                        .signature_algorithm
-                        .oid()
-                );
-                Err(CryptographyError::from(
-                    exceptions::UnsupportedAlgorithm::new_err(exc_messsage),
-                ))
-            }
-        }
+        let resp = self.requires_successful_response()?;
+        x509::sign::identify_signature_hash_algorithm(py, &resp.signature_algorithm)
+    }
+
+    #[getter]
+    fn signature_algorithm_parameters<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+    ) -> CryptographyResult<&'p pyo3::PyAny> {
+        let resp = self.requires_successful_response()?;
+        x509::sign::identify_signature_algorithm_parameters(py, &resp.signature_algorithm)
     }
 
     #[getter]
@@ -542,6 +537,7 @@ fn create_ocsp_response(
     builder: &pyo3::PyAny,
     private_key: &pyo3::PyAny,
     hash_algorithm: &pyo3::PyAny,
+    rsa_padding: &pyo3::PyAny,
 ) -> CryptographyResult<OCSPResponse> {
     let response_status = status
         .getattr(pyo3::intern!(py, "value"))?
@@ -657,20 +653,11 @@ fn create_ocsp_response(
             )?,
         };
 
-        let sigalg = x509::sign::compute_signature_algorithm(
-            py,
-            private_key,
-            hash_algorithm,
-            py.None().into_ref(py),
-        )?;
+        let sigalg =
+            x509::sign::compute_signature_algorithm(py, private_key, hash_algorithm, rsa_padding)?;
         let tbs_bytes = asn1::write_single(&tbs_response_data)?;
-        let signature = x509::sign::sign_data(
-            py,
-            private_key,
-            hash_algorithm,
-            py.None().into_ref(py),
-            &tbs_bytes,
-        )?;
+        let signature =
+            x509::sign::sign_data(py, private_key, hash_algorithm, rsa_padding, &tbs_bytes)?;
 
         if !responder_cert
             .call_method0(pyo3::intern!(py, "public_key"))?
@@ -7,8 +7,10 @@ use std::hash::{Hash, Hasher};
 
 use pyo3::ToPyObject;
 
-use crate::error::CryptographyError;
+use crate::error::{CryptographyError, CryptographyResult};
 use crate::types;
+use crate::x509::certificate::Certificate;
+use crate::x509::sign;
 
 struct TLSReader<'a> {
     data: &'a [u8],
@@ -140,6 +142,51 @@ pub(crate) struct Sct {
     pub(crate) sct_data: Vec<u8>,
 }
 
+impl Sct {
+    // Reconstructs the `digitally-signed` structure from RFC 6962 section
+    // 3.2 that the log signed.
+    fn signed_data(
+        &self,
+        py: pyo3::Python<'_>,
+        certificate: &pyo3::PyCell<Certificate>,
+        issuer: Option<&pyo3::PyCell<Certificate>>,
+    ) -> CryptographyResult<Vec<u8>> {
+        // sct_version (v1) and signature_type (certificate_timestamp)
+        let mut data = vec![0, 0];
+        data.extend_from_slice(&self.timestamp.to_be_bytes());
+        let entry = match self.entry_type {
+            LogEntryType::Certificate => {
+                data.extend_from_slice(&[0, 0]);
+                asn1::write_single(certificate.get().raw.borrow_dependent())?
+            }
+            LogEntryType::PreCertificate => {
+                let issuer = issuer.ok_or_else(|| {
+                    pyo3::exceptions::PyValueError::new_err(
+                        "issuer is required to verify an SCT for a pre-certificate",
+                    )
+                })?;
+                data.extend_from_slice(&[0, 1]);
+                let spki = issuer.get().raw.borrow_dependent().tbs_cert.spki.tlv();
+                data.extend_from_slice(&openssl::sha::sha256(spki.full_data()));
+                certificate
+                    .getattr(pyo3::intern!(py, "tbs_precertificate_bytes"))?
+                    .extract::<&[u8]>()?
+                    .to_vec()
+            }
+        };
+        if entry.len() >= 1 << 24 {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("Certificate is too large"),
+            ));
+        }
+        data.extend_from_slice(&(entry.len() as u32).to_be_bytes()[1..]);
+        data.extend_from_slice(&entry);
+        data.extend_from_slice(&(self.extension_bytes.len() as u16).to_be_bytes());
+        data.extend_from_slice(&self.extension_bytes);
+        Ok(data)
+    }
+}
+
 #[pyo3::prelude::pymethods]
 impl Sct {
     fn __eq__(&self, other: pyo3::PyRef<'_, Sct>) -> bool {
@@ -213,6 +260,53 @@ impl Sct {
     fn extension_bytes(&self) -> &[u8] {
         &self.extension_bytes
     }
+
+    #[pyo3(signature = (public_key, certificate, issuer=None))]
+    fn verify(
+        &self,
+        py: pyo3::Python<'_>,
+        public_key: &pyo3::PyAny,
+        certificate: &pyo3::PyCell<Certificate>,
+        issuer: Option<&pyo3::PyCell<Certificate>>,
+    ) -> CryptographyResult<()> {
+        let data = pyo3::types::PyBytes::new(py, &self.signed_data(py, certificate, issuer)?);
+        let signature = pyo3::types::PyBytes::new(py, &self.signature);
+        let hash_algorithm = self.signature_hash_algorithm(py)?;
+        match (
+            &self.signature_algorithm,
+            sign::identify_public_key_type(py, public_key)?,
+        ) {
+            (SignatureAlgorithm::Ecdsa, sign::KeyType::Ec) => public_key.call_method1(
+                pyo3::intern!(py, "verify"),
+                (
+                    signature,
+                    data,
+                    types::ECDSA.get(py)?.call1((hash_algorithm,))?,
+                ),
+            )?,
+            (SignatureAlgorithm::Rsa, sign::KeyType::Rsa) => public_key.call_method1(
+                pyo3::intern!(py, "verify"),
+                (
+                    signature,
+                    data,
+                    types::PKCS1V15.get(py)?.call0()?,
+                    hash_algorithm,
+                ),
+            )?,
+            (SignatureAlgorithm::Dsa, sign::KeyType::Dsa) => public_key.call_method1(
+                pyo3::intern!(py, "verify"),
+                (signature, data, hash_algorithm),
+            )?,
+            _ => {
+                return Err(CryptographyError::from(
+                    pyo3::exceptions::PyValueError::new_err(
+                        "SCT signature algorithm does not match the log's key type",
+                    ),
+                ))
+            }
+        };
+        Ok(())
+    }
 }
 
 pub(crate) fn parse_scts(
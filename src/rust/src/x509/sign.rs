@@ -27,13 +27,15 @@ static HASH_OIDS_TO_HASH: Lazy<HashMap<&asn1::ObjectIdentifier, &str>> = Lazy::n
     h
 });
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub(crate) enum KeyType {
     Rsa,
     Dsa,
     Ec,
     Ed25519,
     Ed448,
+    MlDsa,
+    SlhDsa,
 }
 
 enum HashType {
@@ -48,7 +50,10 @@ enum HashType {
     Sha3_512,
 }
 
-fn identify_key_type(py: pyo3::Python<'_>, private_key: &pyo3::PyAny) -> pyo3::PyResult<KeyType> {
+pub(crate) fn identify_key_type(
+    py: pyo3::Python<'_>,
+    private_key: &pyo3::PyAny,
+) -> pyo3::PyResult<KeyType> {
     if private_key.is_instance(types::RSA_PRIVATE_KEY.get(py)?)? {
         Ok(KeyType::Rsa)
     } else if private_key.is_instance(types::DSA_PRIVATE_KEY.get(py)?)? {
@@ -59,13 +64,31 @@ fn identify_key_type(py: pyo3::Python<'_>, private_key: &pyo3::PyAny) -> pyo3::P
         Ok(KeyType::Ed25519)
     } else if private_key.is_instance(types::ED448_PRIVATE_KEY.get(py)?)? {
         Ok(KeyType::Ed448)
+    } else if private_key.is_instance(types::MLDSA_PRIVATE_KEY.get(py)?)? {
+        Ok(KeyType::MlDsa)
+    } else if private_key.is_instance(types::SLHDSA_PRIVATE_KEY.get(py)?)? {
+        Ok(KeyType::SlhDsa)
     } else {
         Err(pyo3::exceptions::PyTypeError::new_err(
-            "Key must be an rsa, dsa, ec, ed25519, or ed448 private key.",
+            "Key must be an rsa, dsa, ec, ed25519, ed448, ml-dsa, or slh-dsa private key.",
         ))
     }
 }
 
+// An ExternalSigner, such as a key held by a KMS or HSM, is treated as a key
+// of the same type as its public key.
+fn identify_signer_key_type(
+    py: pyo3::Python<'_>,
+    private_key: &pyo3::PyAny,
+) -> pyo3::PyResult<KeyType> {
+    if private_key.is_instance(types::EXTERNAL_SIGNER.get(py)?)? {
+        let public_key = private_key.call_method0(pyo3::intern!(py, "public_key"))?;
+        identify_public_key_type(py, public_key)
+    } else {
+        identify_key_type(py, private_key)
+    }
+}
+
 fn identify_hash_type(
     py: pyo3::Python<'_>,
     hash_algorithm: &pyo3::PyAny,
@@ -129,7 +152,7 @@ pub(crate) fn compute_signature_algorithm<'p>(
     hash_algorithm: &'p pyo3::PyAny,
     rsa_padding: &'p pyo3::PyAny,
 ) -> pyo3::PyResult<common::AlgorithmIdentifier<'static>> {
-    let key_type = identify_key_type(py, private_key)?;
+    let key_type = identify_signer_key_type(py, private_key)?;
     let hash_type = identify_hash_type(py, hash_algorithm)?;
 
     // If this is RSA-PSS we need to compute the signature algorithm from the
@@ -180,6 +203,64 @@ pub(crate) fn compute_signature_algorithm<'p>(
             "Algorithm must be None when signing via ed25519 or ed448",
         )),
 
+        // Each ML-DSA parameter set has its own signature algorithm.
+        (KeyType::MlDsa, HashType::None) => {
+            let key = if private_key.is_instance(types::EXTERNAL_SIGNER.get(py)?)? {
+                private_key.call_method0(pyo3::intern!(py, "public_key"))?
+            } else {
+                private_key
+            };
+            let parameter_set = key.getattr(pyo3::intern!(py, "parameter_set"))?;
+            let params = if parameter_set.is(types::ML_DSA_PARAMETER_SET_44.get(py)?) {
+                common::AlgorithmParameters::MlDsa44
+            } else if parameter_set.is(types::ML_DSA_PARAMETER_SET_65.get(py)?) {
+                common::AlgorithmParameters::MlDsa65
+            } else {
+                common::AlgorithmParameters::MlDsa87
+            };
+            Ok(common::AlgorithmIdentifier {
+                oid: asn1::DefinedByMarker::marker(),
+                params,
+            })
+        }
+        (KeyType::MlDsa, _) => Err(pyo3::exceptions::PyValueError::new_err(
+            "Algorithm must be None when signing via ml-dsa",
+        )),
+
+        // Likewise for SLH-DSA, whose parameter sets are named by their values.
+        (KeyType::SlhDsa, HashType::None) => {
+            let key = if private_key.is_instance(types::EXTERNAL_SIGNER.get(py)?)? {
+                private_key.call_method0(pyo3::intern!(py, "public_key"))?
+            } else {
+                private_key
+            };
+            let parameter_set = key
+                .getattr(pyo3::intern!(py, "parameter_set"))?
+                .getattr(pyo3::intern!(py, "value"))?
+                .extract::<&str>()?;
+            let params = match parameter_set {
+                "SLH-DSA-SHA2-128s" => common::AlgorithmParameters::SlhDsaSha2_128s,
+                "SLH-DSA-SHA2-128f" => common::AlgorithmParameters::SlhDsaSha2_128f,
+                "SLH-DSA-SHA2-192s" => common::AlgorithmParameters::SlhDsaSha2_192s,
+                "SLH-DSA-SHA2-192f" => common::AlgorithmParameters::SlhDsaSha2_192f,
+                "SLH-DSA-SHA2-256s" => common::AlgorithmParameters::SlhDsaSha2_256s,
+                "SLH-DSA-SHA2-256f" => common::AlgorithmParameters::SlhDsaSha2_256f,
+                "SLH-DSA-SHAKE-128s" => common::AlgorithmParameters::SlhDsaShake128s,
+                "SLH-DSA-SHAKE-128f" => common::AlgorithmParameters::SlhDsaShake128f,
+                "SLH-DSA-SHAKE-192s" => common::AlgorithmParameters::SlhDsaShake192s,
+                "SLH-DSA-SHAKE-192f" => common::AlgorithmParameters::SlhDsaShake192f,
+                "SLH-DSA-SHAKE-256s" => common::AlgorithmParameters::SlhDsaShake256s,
+                _ => common::AlgorithmParameters::SlhDsaShake256f,
+            };
+            Ok(common::AlgorithmIdentifier {
+                oid: asn1::DefinedByMarker::marker(),
+                params,
+            })
+        }
+        (KeyType::SlhDsa, _) => Err(pyo3::exceptions::PyValueError::new_err(
+            "Algorithm must be None when signing via slh-dsa",
+        )),
+
         (KeyType::Ec, HashType::Sha224) => Ok(common::AlgorithmIdentifier {
             oid: asn1::DefinedByMarker::marker(),
             params: common::AlgorithmParameters::EcDsaWithSha224(None),
@@ -281,10 +362,17 @@ pub(crate) fn sign_data<'p>(
     rsa_padding: &'p pyo3::PyAny,
     data: &[u8],
 ) -> pyo3::PyResult<&'p [u8]> {
+    if private_key.is_instance(types::EXTERNAL_SIGNER.get(py)?)? {
+        // External signers pick the signature scheme from their key type, so
+        // they're only given the hash algorithm.
+        return private_key
+            .call_method1(pyo3::intern!(py, "sign"), (data, hash_algorithm))?
+            .extract();
+    }
     let key_type = identify_key_type(py, private_key)?;
 
     let signature = match key_type {
-        KeyType::Ed25519 | KeyType::Ed448 => {
+        KeyType::Ed25519 | KeyType::Ed448 | KeyType::MlDsa | KeyType::SlhDsa => {
             private_key.call_method1(pyo3::intern!(py, "sign"), (data,))?
         }
         KeyType::Ec => {
@@ -325,7 +413,7 @@ pub(crate) fn verify_signature_with_signature_algorithm<'p>(
         identify_signature_algorithm_parameters(py, signature_algorithm)?;
     let py_signature_hash_algorithm = identify_signature_hash_algorithm(py, signature_algorithm)?;
     match key_type {
-        KeyType::Ed25519 | KeyType::Ed448 => {
+        KeyType::Ed25519 | KeyType::Ed448 | KeyType::MlDsa | KeyType::SlhDsa => {
             issuer_public_key.call_method1(pyo3::intern!(py, "verify"), (signature, data))?
         }
         KeyType::Ec => issuer_public_key.call_method1(
@@ -363,9 +451,13 @@ pub(crate) fn identify_public_key_type(
         Ok(KeyType::Ed25519)
     } else if public_key.is_instance(types::ED448_PUBLIC_KEY.get(py)?)? {
         Ok(KeyType::Ed448)
+    } else if public_key.is_instance(types::MLDSA_PUBLIC_KEY.get(py)?)? {
+        Ok(KeyType::MlDsa)
+    } else if public_key.is_instance(types::SLHDSA_PUBLIC_KEY.get(py)?)? {
+        Ok(KeyType::SlhDsa)
     } else {
         Err(pyo3::exceptions::PyTypeError::new_err(
-            "Key must be an rsa, dsa, ec, ed25519, or ed448 public key.",
+            "Key must be an rsa, dsa, ec, ed25519, ed448, ml-dsa, or slh-dsa public key.",
         ))
     }
 }
@@ -393,6 +485,21 @@ fn identify_key_type_for_algorithm_params(
         | common::AlgorithmParameters::EcDsaWithSha3_512 => Ok(KeyType::Ec),
         common::AlgorithmParameters::Ed25519 => Ok(KeyType::Ed25519),
         common::AlgorithmParameters::Ed448 => Ok(KeyType::Ed448),
+        common::AlgorithmParameters::MlDsa44
+        | common::AlgorithmParameters::MlDsa65
+        | common::AlgorithmParameters::MlDsa87 => Ok(KeyType::MlDsa),
+        common::AlgorithmParameters::SlhDsaSha2_128s
+        | common::AlgorithmParameters::SlhDsaSha2_128f
+        | common::AlgorithmParameters::SlhDsaSha2_192s
+        | common::AlgorithmParameters::SlhDsaSha2_192f
+        | common::AlgorithmParameters::SlhDsaSha2_256s
+        | common::AlgorithmParameters::SlhDsaSha2_256f
+        | common::AlgorithmParameters::SlhDsaShake128s
+        | common::AlgorithmParameters::SlhDsaShake128f
+        | common::AlgorithmParameters::SlhDsaShake192s
+        | common::AlgorithmParameters::SlhDsaShake192f
+        | common::AlgorithmParameters::SlhDsaShake256s
+        | common::AlgorithmParameters::SlhDsaShake256f => Ok(KeyType::SlhDsa),
         common::AlgorithmParameters::DsaWithSha224(..)
         | common::AlgorithmParameters::DsaWithSha256(..)
         | common::AlgorithmParameters::DsaWithSha384(..)
@@ -421,7 +528,7 @@ fn identify_alg_params_for_hash_type(
     }
 }
 
-fn hash_oid_py_hash(
+pub(crate) fn hash_oid_py_hash(
     py: pyo3::Python<'_>,
     oid: asn1::ObjectIdentifier,
 ) -> CryptographyResult<&pyo3::PyAny> {
@@ -581,6 +688,57 @@ mod tests {
             (&common::AlgorithmParameters::EcDsaWithSha3_512, KeyType::Ec),
             (&common::AlgorithmParameters::Ed25519, KeyType::Ed25519),
             (&common::AlgorithmParameters::Ed448, KeyType::Ed448),
+            (&common::AlgorithmParameters::MlDsa44, KeyType::MlDsa),
+            (&common::AlgorithmParameters::MlDsa65, KeyType::MlDsa),
+            (&common::AlgorithmParameters::MlDsa87, KeyType::MlDsa),
+            (
+                &common::AlgorithmParameters::SlhDsaSha2_128s,
+                KeyType::SlhDsa,
+            ),
+            (
+                &common::AlgorithmParameters::SlhDsaSha2_128f,
+                KeyType::SlhDsa,
+            ),
+            (
+                &common::AlgorithmParameters::SlhDsaSha2_192s,
+                KeyType::SlhDsa,
+            ),
+            (
+                &common::AlgorithmParameters::SlhDsaSha2_192f,
+                KeyType::SlhDsa,
+            ),
+            (
+                &common::AlgorithmParameters::SlhDsaSha2_256s,
+                KeyType::SlhDsa,
+            ),
+            (
+                &common::AlgorithmParameters::SlhDsaSha2_256f,
+                KeyType::SlhDsa,
+            ),
+            (
+                &common::AlgorithmParameters::SlhDsaShake128s,
+                KeyType::SlhDsa,
+            ),
+            (
+                &common::AlgorithmParameters::SlhDsaShake128f,
+                KeyType::SlhDsa,
+            ),
+            (
+                &common::AlgorithmParameters::SlhDsaShake192s,
+                KeyType::SlhDsa,
+            ),
+            (
+                &common::AlgorithmParameters::SlhDsaShake192f,
+                KeyType::SlhDsa,
+            ),
+            (
+                &common::AlgorithmParameters::SlhDsaShake256s,
+                KeyType::SlhDsa,
+            ),
+            (
+                &common::AlgorithmParameters::SlhDsaShake256f,
+                KeyType::SlhDsa,
+            ),
             (
                 &common::AlgorithmParameters::DsaWithSha224(None),
                 KeyType::Dsa,
@@ -3,6 +3,15 @@
 // for complete details.
 
 use cryptography_x509::certificate::Certificate;
+use cryptography_x509::common;
+use cryptography_x509::extensions::SubjectAlternativeName;
+use cryptography_x509::ocsp_req::CertID;
+use cryptography_x509::ocsp_resp::{
+    BasicOCSPResponse, CertStatus, OCSPResponse as RawOCSPResponse, ResponderId,
+};
+use cryptography_x509::oid::{
+    EKU_OCSP_SIGNING_OID, EXTENDED_KEY_USAGE_OID, SUBJECT_ALTERNATIVE_NAME_OID,
+};
 use cryptography_x509_verification::{
     ops::{CryptoOps, VerificationCertificate},
     policy::{Policy, Subject},
@@ -11,10 +20,12 @@ use cryptography_x509_verification::{
 };
 
 use crate::backend::keys;
+use crate::buf::CffiBuf;
 use crate::error::{CryptographyError, CryptographyResult};
 use crate::types;
 use crate::x509::certificate::Certificate as PyCertificate;
 use crate::x509::common::{datetime_now, datetime_to_py, py_to_datetime};
+use crate::x509::ocsp_resp::BASIC_RESPONSE_OID;
 use crate::x509::sign;
 
 pub(crate) struct PyCryptoOps {}
@@ -197,6 +208,34 @@ impl PyServerVerifier {
     fn as_policy(&self) -> &Policy<'_, PyCryptoOps> {
         &self.policy.borrow_dependent().0
     }
+
+    fn verify_chain(
+        &self,
+        py: pyo3::Python<'_>,
+        leaf: &pyo3::Py<PyCertificate>,
+        intermediates: &[pyo3::Py<PyCertificate>],
+    ) -> CryptographyResult<Vec<pyo3::Py<PyCertificate>>> {
+        let policy = self.as_policy();
+        let store = self.store.get();
+
+        let chain = cryptography_x509_verification::verify(
+            &VerificationCertificate::new(
+                leaf.get().raw.borrow_dependent().clone(),
+                leaf.clone_ref(py),
+            ),
+            intermediates.iter().map(|i| {
+                VerificationCertificate::new(
+                    i.get().raw.borrow_dependent().clone(),
+                    i.clone_ref(py),
+                )
+            }),
+            policy,
+            store.raw.borrow_dependent(),
+        )
+        .map_err(|e| VerificationError::new_err(format!("validation failed: {e:?}")))?;
+
+        Ok(chain.iter().map(|c| c.extra().clone_ref(py)).collect())
+    }
 }
 
 #[pyo3::pymethods]
@@ -217,30 +256,202 @@ impl PyServerVerifier {
         leaf: pyo3::Py<PyCertificate>,
         intermediates: Vec<pyo3::Py<PyCertificate>>,
     ) -> CryptographyResult<&'p pyo3::types::PyList> {
-        let policy = self.as_policy();
-        let store = self.store.get();
+        let chain = self.verify_chain(py, &leaf, &intermediates)?;
+        Ok(pyo3::types::PyList::new(py, chain))
+    }
 
-        let chain = cryptography_x509_verification::verify(
-            &VerificationCertificate::new(
-                leaf.get().raw.borrow_dependent().clone(),
-                leaf.clone_ref(py),
-            ),
-            intermediates.iter().map(|i| {
-                VerificationCertificate::new(
-                    i.get().raw.borrow_dependent().clone(),
-                    i.clone_ref(py),
-                )
-            }),
-            policy,
-            store.raw.borrow_dependent(),
-        )
-        .map_err(|e| VerificationError::new_err(format!("validation failed: {e:?}")))?;
+    fn verify_with_ocsp<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        leaf: pyo3::Py<PyCertificate>,
+        intermediates: Vec<pyo3::Py<PyCertificate>>,
+        ocsp_response: CffiBuf<'_>,
+    ) -> CryptographyResult<&'p pyo3::types::PyList> {
+        let chain = self.verify_chain(py, &leaf, &intermediates)?;
+        // The chain always ends in a trust anchor, so a chain of length one
+        // means the leaf itself is trusted and has no issuer to vouch for it.
+        let issuer = match chain.get(1) {
+            Some(issuer) => issuer,
+            None => return Err(ocsp_error("the leaf certificate is a trust anchor")),
+        };
+        check_ocsp_response(
+            py,
+            self.as_policy(),
+            leaf.get().raw.borrow_dependent(),
+            issuer.get().raw.borrow_dependent(),
+            ocsp_response.as_bytes(),
+        )?;
+        Ok(pyo3::types::PyList::new(py, chain))
+    }
+}
+
+fn ocsp_error(reason: &str) -> CryptographyError {
+    CryptographyError::from(VerificationError::new_err(format!(
+        "OCSP validation failed: {reason}"
+    )))
+}
+
+fn cert_id_digest(alg: &common::AlgorithmIdentifier<'_>) -> Option<openssl::hash::MessageDigest> {
+    match alg.params {
+        common::AlgorithmParameters::Sha1(_) => Some(openssl::hash::MessageDigest::sha1()),
+        common::AlgorithmParameters::Sha224(_) => Some(openssl::hash::MessageDigest::sha224()),
+        common::AlgorithmParameters::Sha256(_) => Some(openssl::hash::MessageDigest::sha256()),
+        common::AlgorithmParameters::Sha384(_) => Some(openssl::hash::MessageDigest::sha384()),
+        common::AlgorithmParameters::Sha512(_) => Some(openssl::hash::MessageDigest::sha512()),
+        _ => None,
+    }
+}
+
+fn cert_id_matches(
+    cert_id: &CertID<'_>,
+    leaf: &Certificate<'_>,
+    issuer: &Certificate<'_>,
+) -> CryptographyResult<bool> {
+    let md = match cert_id_digest(&cert_id.hash_algorithm) {
+        Some(md) => md,
+        None => return Ok(false),
+    };
+    let issuer_name_hash = openssl::hash::hash(md, &asn1::write_single(&leaf.tbs_cert.issuer)?)?;
+    let issuer_key_hash =
+        openssl::hash::hash(md, issuer.tbs_cert.spki.subject_public_key.as_bytes())?;
+    Ok(
+        cert_id.serial_number.as_bytes() == leaf.tbs_cert.serial.as_bytes()
+            && cert_id.issuer_name_hash == &*issuer_name_hash
+            && cert_id.issuer_key_hash == &*issuer_key_hash,
+    )
+}
+
+fn is_responder(responder_id: &ResponderId<'_>, cert: &Certificate<'_>) -> bool {
+    match responder_id {
+        ResponderId::ByName(name) => name.unwrap_read() == cert.subject(),
+        ResponderId::ByKey(key_hash) => {
+            *key_hash == openssl::sha::sha1(cert.tbs_cert.spki.subject_public_key.as_bytes())
+        }
+    }
+}
+
+// RFC 6960 section 4.2.2.2: a delegated responder must be issued directly by
+// the CA that issued the certificate in question, and must have the
+// id-kp-OCSPSigning extended key usage.
+fn is_delegated_responder(
+    policy: &Policy<'_, PyCryptoOps>,
+    cert: &Certificate<'_>,
+    issuer: &Certificate<'_>,
+) -> CryptographyResult<bool> {
+    if cert.issuer() != issuer.subject() {
+        return Ok(false);
+    }
+    let not_before = cert.tbs_cert.validity.not_before.as_datetime();
+    let not_after = cert.tbs_cert.validity.not_after.as_datetime();
+    if &policy.validation_time < not_before || &policy.validation_time > not_after {
+        return Ok(false);
+    }
+    let extensions = match cert.extensions() {
+        Ok(extensions) => extensions,
+        Err(_) => return Ok(false),
+    };
+    let has_ocsp_signing = match extensions.get_extension(&EXTENDED_KEY_USAGE_OID) {
+        Some(ext) => ext
+            .value::<asn1::SequenceOf<'_, asn1::ObjectIdentifier>>()?
+            .any(|oid| oid == EKU_OCSP_SIGNING_OID),
+        None => false,
+    };
+    if !has_ocsp_signing {
+        return Ok(false);
+    }
+
+    let ops = PyCryptoOps {};
+    let issuer_key = ops.public_key(issuer)?;
+    Ok(ops.verify_signed_by(cert, &issuer_key).is_ok())
+}
+
+fn is_signed_by(
+    py: pyo3::Python<'_>,
+    basic: &BasicOCSPResponse<'_>,
+    cert: &Certificate<'_>,
+) -> CryptographyResult<bool> {
+    let key = keys::load_der_public_key_bytes(py, cert.tbs_cert.spki.tlv().full_data())?;
+    Ok(sign::verify_signature_with_signature_algorithm(
+        py,
+        key.as_ref(py),
+        &basic.signature_algorithm,
+        basic.signature.as_bytes(),
+        &asn1::write_single(&basic.tbs_response_data)?,
+    )
+    .is_ok())
+}
+
+fn check_ocsp_response(
+    py: pyo3::Python<'_>,
+    policy: &Policy<'_, PyCryptoOps>,
+    leaf: &Certificate<'_>,
+    issuer: &Certificate<'_>,
+    data: &[u8],
+) -> CryptographyResult<()> {
+    let response = asn1::parse_single::<RawOCSPResponse<'_>>(data)
+        .map_err(|_| ocsp_error("the response is malformed"))?;
+    // 0 is the successful response status.
+    if response.response_status.value() != 0 {
+        return Err(ocsp_error("the response status isn't successful"));
+    }
+    let basic = match response.response_bytes.as_ref() {
+        Some(bytes) if bytes.response_type == BASIC_RESPONSE_OID => bytes.response.get(),
+        _ => return Err(ocsp_error("the response isn't a basic OCSP response")),
+    };
+    let tbs = &basic.tbs_response_data;
+
+    let mut single = None;
+    for r in tbs.responses.unwrap_read().clone() {
+        if cert_id_matches(&r.cert_id, leaf, issuer)? {
+            single = Some(r);
+            break;
+        }
+    }
+    let single = match single {
+        Some(single) => single,
+        None => {
+            return Err(ocsp_error(
+                "the response doesn't cover the leaf certificate",
+            ))
+        }
+    };
+
+    if single.this_update.as_datetime() > &policy.validation_time {
+        return Err(ocsp_error("the response isn't valid yet"));
+    }
+    if let Some(next_update) = &single.next_update {
+        if next_update.as_datetime() < &policy.validation_time {
+            return Err(ocsp_error("the response has expired"));
+        }
+    }
 
-        let result = pyo3::types::PyList::empty(py);
-        for c in chain {
-            result.append(c.extra())?;
+    let mut authorized =
+        is_responder(&tbs.responder_id, issuer) && is_signed_by(py, basic, issuer)?;
+    if !authorized {
+        if let Some(certs) = &basic.certs {
+            for cert in certs.unwrap_read().clone() {
+                if is_responder(&tbs.responder_id, &cert)
+                    && is_delegated_responder(policy, &cert, issuer)?
+                    && is_signed_by(py, basic, &cert)?
+                {
+                    authorized = true;
+                    break;
+                }
+            }
         }
-        Ok(result)
+    }
+    if !authorized {
+        return Err(ocsp_error(
+            "the response isn't signed by an authorized responder",
+        ));
+    }
+
+    match single.cert_status {
+        CertStatus::Good(()) => Ok(()),
+        CertStatus::Revoked(_) => Err(ocsp_error("the leaf certificate is revoked")),
+        CertStatus::Unknown(()) => Err(ocsp_error(
+            "the responder doesn't know the leaf certificate",
+        )),
     }
 }
 
@@ -290,6 +501,28 @@ fn build_subject<'a>(
     }
 }
 
+#[pyo3::prelude::pyfunction]
+fn subject_matches(
+    py: pyo3::Python<'_>,
+    certificate: &pyo3::PyCell<PyCertificate>,
+    subject: pyo3::PyObject,
+) -> CryptographyResult<bool> {
+    let subject_owner = build_subject_owner(py, &subject)?;
+    let subject = build_subject(py, &subject_owner)?;
+
+    let cert = certificate.get().raw.borrow_dependent();
+    let extensions = match cert.extensions() {
+        Ok(extensions) => extensions,
+        Err(_) => return Ok(false),
+    };
+    // Like the verifier, only the subjectAltName is considered, the subject's
+    // common name is never used (RFC 6125 6.4.4).
+    match extensions.get_extension(&SUBJECT_ALTERNATIVE_NAME_OID) {
+        Some(ext) => Ok(subject.matches(&ext.value::<SubjectAlternativeName<'_>>()?)),
+        None => Ok(false),
+    }
+}
+
 type PyCryptoOpsStore<'a> = Store<'a, PyCryptoOps>;
 
 self_cell::self_cell!(
@@ -336,6 +569,7 @@ pub(crate) fn add_to_module(module: &pyo3::prelude::PyModule) -> pyo3::PyResult<
     module.add_class::<PyServerVerifier>()?;
     module.add_class::<PyStore>()?;
     module.add_class::<PolicyBuilder>()?;
+    module.add_function(pyo3::wrap_pyfunction!(subject_matches, module)?)?;
     module.add(
         "VerificationError",
         module.py().get_type::<VerificationError>(),
@@ -206,6 +206,21 @@ class TestOpenSSLRSA:
                 is True
             )
 
+    def test_rsa_padding_supported_oaep_sha3(self):
+        for alg in [
+            hashes.SHA3_224(),
+            hashes.SHA3_256(),
+            hashes.SHA3_384(),
+            hashes.SHA3_512(),
+        ]:
+            assert backend.rsa_padding_supported(
+                padding.OAEP(
+                    mgf=padding.MGF1(algorithm=alg),
+                    algorithm=alg,
+                    label=None,
+                ),
+            ) == backend.hash_supported(alg)
+
     def test_rsa_padding_unsupported_mgf(self):
         assert (
             backend.rsa_padding_supported(
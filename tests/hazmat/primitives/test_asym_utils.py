@@ -2,15 +2,23 @@
 # 2.0, and the BSD License. See the LICENSE file in the root of this repository
 # for complete details.
 
+import typing
 
 import pytest
 
+from cryptography.exceptions import AlreadyFinalized
 from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import dh, ec
 from cryptography.hazmat.primitives.asymmetric.utils import (
     Prehashed,
     decode_dss_signature,
+    der_to_raw_signature,
     encode_dss_signature,
+    raw_to_der_signature,
+    unified_exchange_and_derive,
 )
+from cryptography.hazmat.primitives.kdf.concatkdf import ConcatKDFHash
+from cryptography.hazmat.primitives.kdf.pbkdf2 import PBKDF2HMAC
 
 
 def test_dss_signature():
@@ -74,6 +82,38 @@ def test_decode_dss_invalid_asn1():
         decode_dss_signature(b"\x00\x00")
 
 
+def test_raw_signature_roundtrip():
+    r_s = (
+        1037234182290683143945502320610861668562885151617,
+        559776156650501990899426031439030258256861634312,
+    )
+    der = encode_dss_signature(*r_s)
+    raw = der_to_raw_signature(der, 20)
+    assert raw == b"".join(v.to_bytes(20, "big") for v in r_s)
+    assert raw_to_der_signature(raw, 20) == der
+
+    der = encode_dss_signature(1, 0)
+    raw = der_to_raw_signature(der, 32)
+    assert raw == b"\x00" * 31 + b"\x01" + b"\x00" * 32
+    assert raw_to_der_signature(raw, 32) == der
+
+
+def test_der_to_raw_signature_invalid():
+    with pytest.raises(ValueError):
+        der_to_raw_signature(encode_dss_signature(2**160, 1), 20)
+    with pytest.raises(ValueError):
+        der_to_raw_signature(b"0\x06\x02\x01\x01\x02\x01\x01\x00", 32)
+    with pytest.raises(ValueError):
+        der_to_raw_signature(encode_dss_signature(1, 1), 0)
+
+
+def test_raw_to_der_signature_invalid():
+    with pytest.raises(ValueError):
+        raw_to_der_signature(b"\x01" * 63, 32)
+    with pytest.raises(ValueError):
+        raw_to_der_signature(b"", 0)
+
+
 def test_pass_invalid_prehashed_arg():
     with pytest.raises(TypeError):
         Prehashed(object())  # type: ignore[arg-type]
@@ -82,3 +122,128 @@ def test_pass_invalid_prehashed_arg():
 def test_prehashed_digest_size():
     p = Prehashed(hashes.SHA256())
     assert p.digest_size == 32
+
+
+def _concat_kdf():
+    return ConcatKDFHash(hashes.SHA256(), 32, b"party info")
+
+
+def test_unified_exchange_and_derive_ec(backend):
+    curve = ec.SECP256R1()
+    ephemeral, static, peer_ephemeral, peer_static = (
+        ec.generate_private_key(curve) for _ in range(4)
+    )
+    z = ephemeral.exchange(ec.ECDH(), peer_ephemeral.public_key())
+    z += static.exchange(ec.ECDH(), peer_static.public_key())
+    expected = _concat_kdf().derive(z)
+
+    # C(2e, 2s)
+    derived = unified_exchange_and_derive(
+        [
+            (ephemeral, peer_ephemeral.public_key()),
+            (static, peer_static.public_key()),
+        ],
+        _concat_kdf(),
+    )
+    peer_derived = unified_exchange_and_derive(
+        [
+            (peer_ephemeral, ephemeral.public_key()),
+            (peer_static, static.public_key()),
+        ],
+        _concat_kdf(),
+    )
+    assert derived == peer_derived == expected
+
+    # C(1e, 2s)
+    derived = unified_exchange_and_derive(
+        [
+            (ephemeral, peer_static.public_key()),
+            (static, peer_static.public_key()),
+        ],
+        _concat_kdf(),
+    )
+    peer_derived = unified_exchange_and_derive(
+        [
+            (peer_static, ephemeral.public_key()),
+            (peer_static, static.public_key()),
+        ],
+        _concat_kdf(),
+    )
+    assert derived == peer_derived
+
+    # C(1e, 1s)
+    z = ephemeral.exchange(ec.ECDH(), peer_static.public_key())
+    derived = unified_exchange_and_derive(
+        [(ephemeral, peer_static.public_key())], _concat_kdf()
+    )
+    assert derived == _concat_kdf().derive(z)
+
+
+def test_unified_exchange_and_derive_ec_cofactor(backend):
+    curve = ec.SECT233K1()
+    if not backend.elliptic_curve_exchange_algorithm_supported(
+        ec.ECDH(), curve
+    ):
+        pytest.skip(f"Exchange with {curve.name} is not supported")
+    key = ec.generate_private_key(curve)
+    peer = ec.generate_private_key(curve).public_key()
+    z = key.exchange(ec.ECDH(cofactor=True), peer)
+    derived = unified_exchange_and_derive([(key, peer)], _concat_kdf())
+    assert derived == _concat_kdf().derive(z)
+
+
+def test_unified_exchange_and_derive_dh(backend):
+    parameters = dh.named_group_parameters("ffdhe2048")
+    ephemeral, static, peer_ephemeral, peer_static = (
+        parameters.generate_private_key() for _ in range(4)
+    )
+    z = ephemeral.exchange(peer_ephemeral.public_key())
+    z += static.exchange(peer_static.public_key())
+    derived = unified_exchange_and_derive(
+        [
+            (ephemeral, peer_ephemeral.public_key()),
+            (static, peer_static.public_key()),
+        ],
+        _concat_kdf(),
+    )
+    peer_derived = unified_exchange_and_derive(
+        [
+            (peer_ephemeral, ephemeral.public_key()),
+            (peer_static, static.public_key()),
+        ],
+        _concat_kdf(),
+    )
+    assert derived == peer_derived == _concat_kdf().derive(z)
+
+    numbers = parameters.parameter_numbers()
+    invalid = dh.DHPublicNumbers(1, numbers).public_key(backend)
+    with pytest.raises(ValueError):
+        unified_exchange_and_derive([(static, invalid)], _concat_kdf())
+
+
+def test_unified_exchange_and_derive_invalid(backend):
+    key = ec.generate_private_key(ec.SECP256R1())
+    dh_key = dh.named_group_parameters("ffdhe2048").generate_private_key()
+
+    with pytest.raises(ValueError):
+        unified_exchange_and_derive([], _concat_kdf())
+
+    invalid: typing.List[typing.Any] = [
+        (key, dh_key.public_key()),
+        (key.public_key(), key.public_key()),
+        key,
+    ]
+    for exchange in invalid:
+        with pytest.raises(TypeError):
+            unified_exchange_and_derive([exchange], _concat_kdf())
+
+    kdf = PBKDF2HMAC(hashes.SHA256(), 32, b"salt", 1)
+    with pytest.raises(TypeError):
+        unified_exchange_and_derive(
+            [(key, key.public_key())], kdf  # type: ignore[arg-type]
+        )
+
+    kdf = _concat_kdf()
+    kdf.derive(b"key material")
+    with pytest.raises(AlreadyFinalized):
+        unified_exchange_and_derive([(key, key.public_key())], kdf)
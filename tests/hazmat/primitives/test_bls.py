@@ -0,0 +1,312 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+
+import binascii
+import copy
+
+import pytest
+
+from cryptography.exceptions import InvalidSignature
+from cryptography.hazmat.primitives.asymmetric.bls import (
+    BLSPrivateKey,
+    BLSPublicKey,
+    BLSVariant,
+    aggregate_signatures,
+    aggregate_verify,
+    fast_aggregate_verify,
+)
+
+_PRIVATE_KEY = binascii.unhexlify(
+    "263dbd792f5b1be47ed85f8938c0f29586af0d3ac7b977f21c278fe1462040e3"
+)
+
+# Private keys, public keys and their signatures of 0x00 * 32, 0x56 * 32
+# and 0xab * 32, from the Ethereum consensus spec BLS tests.
+_MIN_PK_VECTORS = [
+    (
+        "263dbd792f5b1be47ed85f8938c0f29586af0d3ac7b977f21c278fe1462040e3",
+        "a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20f"
+        "d6e10c1b77654d067c0618f6e5a7f79a",
+        [
+            "b6ed936746e01f8ecf281f020953fbf1f01debd5657c4a383940b020b26507f6"
+            "076334f91e2366c96e9ab279fb5158090352ea1c5b0c9274504f4f0e7053af24"
+            "802e51e4568d164fe986834f41e55c8e850ce1f98458c0cfc9ab380b55285a55",
+            "882730e5d03f6b42c3abc26d3372625034e1d871b65a8a6b900a56dae22da98a"
+            "bbe1b68f85e49fe7652a55ec3d0591c20767677e33e5cbb1207315c41a9ac03b"
+            "e39c2e7668edc043d6cb1d9fd93033caa8a1c5b0e84bedaeb6c64972503a43eb",
+            "91347bccf740d859038fcdcaf233eeceb2a436bcaaee9b2aa3bfb70efe29dfb2"
+            "677562ccbea1c8e061fb9971b0753c240622fab78489ce96768259fc01360346"
+            "da5b9f579e5da0d941e4c6ba18a0e64906082375394f337fa1af2b7127b0d121",
+        ],
+    ),
+    (
+        "47b8192d77bf871b62e87859d653922725724a5c031afeabc60bcef5ff665138",
+        "b301803f8b5ac4a1133581fc676dfedc60d891dd5fa99028805e5ea5b08d3491"
+        "af75d0707adab3b70c6a6a580217bf81",
+        [
+            "b23c46be3a001c63ca711f87a005c200cc550b9429d5f4eb38d74322144f1b63"
+            "926da3388979e5321012fb1a0526bcd100b5ef5fe72628ce4cd5e904aeaa3279"
+            "527843fae5ca9ca675f4f51ed8f83bbf7155da9ecc9663100a885d5dc6df96d9",
+            "af1390c3c47acdb37131a51216da683c509fce0e954328a59f93aebda7e4ff97"
+            "4ba208d9a4a2a2389f892a9d418d618418dd7f7a6bc7aa0da999a9d3a5b815bc"
+            "085e14fd001f6a1948768a3f4afefc8b8240dda329f984cb345c6363272ba4fe",
+            "9674e2228034527f4c083206032b020310face156d4a4685e2fcaec2f6f3665a"
+            "a635d90347b6ce124eb879266b1e801d185de36a0a289b85e9039662634f2eea"
+            "1e02e670bc7ab849d006a70b2f93b84597558a05b879c8d445f387a5d5b653df",
+        ],
+    ),
+    (
+        "328388aff0d4a5b7dc9205abd374e7e98f3cd9f3418edb4eafda5fb16473d216",
+        "b53d21a4cfd562c469cc81514d4ce5a6b577d8403d32a394dc265dd190b47fa9"
+        "f829fdd7963afdf972e5e77854051f6f",
+        [
+            "948a7cb99f76d616c2c564ce9bf4a519f1bea6b0a624a02276443c245854219f"
+            "abb8d4ce061d255af5330b078d5380681751aa7053da2c98bae898edc218c75f"
+            "07e24d8802a17cd1f6833b71e58f5eb5b94208b4d0bb3848cecb075ea21be115",
+            "a4efa926610b8bd1c8330c918b7a5e9bf374e53435ef8b7ec186abf62e1b1f65"
+            "aeaaeb365677ac1d1172a1f5b44b4e6d022c252c58486c0a759fbdc7de15a756"
+            "acc4d343064035667a594b4c2a6f0b0b421975977f297dba63ee2f63ffe47bb6",
+            "ae82747ddeefe4fd64cf9cedb9b04ae3e8a43420cd255e3c7cd06a8d88b7c7f8"
+            "638543719981c5d16fa3527c468c25f0026704a6951bde891360c7e8d12ddee0"
+            "559004ccdbe6046b55bae1b257ee97f7cdb955773d7cf29adf3ccbb9975e4eb9",
+        ],
+    ),
+]
+_MESSAGES = [b"\x00" * 32, b"\x56" * 32, b"\xab" * 32]
+
+
+class TestBLS:
+    def test_min_pk_vector(self, backend):
+        # From the Ethereum consensus spec BLS tests.
+        key = BLSPrivateKey.from_private_bytes(_PRIVATE_KEY, BLSVariant.MIN_PK)
+        signature = key.sign(b"\x00" * 32)
+        assert signature == binascii.unhexlify(
+            "b6ed936746e01f8ecf281f020953fbf1f01debd5657c4a383940b020b26507f6"
+            "076334f91e2366c96e9ab279fb5158090352ea1c5b0c9274504f4f0e7053af24"
+            "802e51e4568d164fe986834f41e55c8e850ce1f98458c0cfc9ab380b55285a55"
+        )
+        public_key = key.public_key()
+        assert public_key.public_bytes_raw() == binascii.unhexlify(
+            "a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20f"
+            "d6e10c1b77654d067c0618f6e5a7f79a"
+        )
+        public_key.verify(signature, b"\x00" * 32)
+        with pytest.raises(InvalidSignature):
+            public_key.verify(signature, b"\x00" * 31)
+
+    @pytest.mark.parametrize("vector", _MIN_PK_VECTORS)
+    def test_min_pk_sign_vectors(self, vector, backend):
+        private_bytes, public_bytes, signatures = vector
+        key = BLSPrivateKey.from_private_bytes(
+            binascii.unhexlify(private_bytes), BLSVariant.MIN_PK
+        )
+        public_key = key.public_key()
+        assert public_key.public_bytes_raw() == binascii.unhexlify(
+            public_bytes
+        )
+        for msg, signature in zip(_MESSAGES, signatures):
+            assert key.sign(msg) == binascii.unhexlify(signature)
+            public_key.verify(binascii.unhexlify(signature), msg)
+
+    def test_fast_aggregate_verify_vector(self, backend):
+        # fast_aggregate_verify_valid_3d6ab7bb from the Ethereum consensus
+        # spec BLS tests.
+        public_keys = [
+            BLSPublicKey.from_public_bytes(
+                binascii.unhexlify(public_bytes), BLSVariant.MIN_PK
+            )
+            for _, public_bytes, _ in _MIN_PK_VECTORS
+        ]
+        signature = binascii.unhexlify(
+            "9712c3edd73a209c742b8250759db12549b3eaf43b5ca61376d9f30e2747dbcf"
+            "842d8b2ac0901d2a093713e20284a7670fcf6954e9ab93de991bb9b313e66478"
+            "5a075fc285806fa5224c82bde146561b446ccfc706a64b8579513cfc4ff1d930"
+        )
+        fast_aggregate_verify(public_keys, b"\xab" * 32, signature)
+        assert signature == aggregate_signatures(
+            [binascii.unhexlify(sigs[2]) for _, _, sigs in _MIN_PK_VECTORS],
+            BLSVariant.MIN_PK,
+        )
+        with pytest.raises(InvalidSignature):
+            fast_aggregate_verify(public_keys, b"\x56" * 32, signature)
+
+    def test_min_sig_vector(self, backend):
+        key = BLSPrivateKey.from_private_bytes(
+            _PRIVATE_KEY, BLSVariant.MIN_SIG
+        )
+        signature = key.sign(b"abc")
+        assert signature == binascii.unhexlify(
+            "8fb10052b82bb7a49df8997cc8737faeaf75eef17766f6603709bf778571404c"
+            "f2aa56f927d572843e7b7c32a13ec31e"
+        )
+        public_key = key.public_key()
+        assert public_key.public_bytes_raw() == binascii.unhexlify(
+            "ac400b70f6f8cd35648f5c126cce5417f3be4d8eefbd42ceb4286a14df7e0313"
+            "5313fe5845e3a575faab3e8b949d248814856c22d8cdb2967c720e963eedc999"
+            "e738373b14172f06fc915769d3cc5ab7ae0a1b9c38f48b5585fb09d4bd2733bb"
+        )
+        public_key.verify(signature, b"abc")
+        with pytest.raises(InvalidSignature):
+            public_key.verify(signature, b"abd")
+
+    def test_derive(self, backend):
+        # The master key from EIP-2333 test case 0, which uses the same
+        # KeyGen.
+        seed = binascii.unhexlify(
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e5349553"
+            "1f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        )
+        key = BLSPrivateKey.derive(seed, BLSVariant.MIN_PK)
+        assert key.private_bytes_raw() == binascii.unhexlify(
+            "0d7359d57963ab8fbbde1852dcf553fedbc31f464d80ee7d40ae683122b45070"
+        )
+        assert (
+            BLSPrivateKey.derive(
+                seed, BLSVariant.MIN_PK, b"info"
+            ).private_bytes_raw()
+            != key.private_bytes_raw()
+        )
+
+        with pytest.raises(ValueError):
+            BLSPrivateKey.derive(b"\x00" * 31, BLSVariant.MIN_PK)
+
+    @pytest.mark.parametrize("variant", list(BLSVariant))
+    def test_sign_verify(self, variant, backend):
+        key = BLSPrivateKey.generate(variant)
+        assert key.variant is variant
+        public_key = key.public_key()
+        assert public_key.variant is variant
+        signature = key.sign(b"data")
+        public_key.verify(signature, b"data")
+        with pytest.raises(InvalidSignature):
+            public_key.verify(signature, b"other data")
+        with pytest.raises(InvalidSignature):
+            BLSPrivateKey.generate(variant).public_key().verify(
+                signature, b"data"
+            )
+        with pytest.raises(InvalidSignature):
+            public_key.verify(signature[:-1], b"data")
+        with pytest.raises(InvalidSignature):
+            public_key.verify(b"\xc0" + b"\x00" * (len(signature) - 1), b"")
+
+    @pytest.mark.parametrize("variant", list(BLSVariant))
+    def test_proof_of_possession(self, variant, backend):
+        key = BLSPrivateKey.generate(variant)
+        proof = key.prove_possession()
+        key.public_key().verify_possession(proof)
+        # A proof isn't a signature of the public key.
+        with pytest.raises(InvalidSignature):
+            key.public_key().verify(proof, key.public_key().public_bytes_raw())
+        with pytest.raises(InvalidSignature):
+            BLSPrivateKey.generate(variant).public_key().verify_possession(
+                proof
+            )
+
+    @pytest.mark.parametrize("variant", list(BLSVariant))
+    def test_aggregate_verify(self, variant, backend):
+        keys = [BLSPrivateKey.generate(variant) for _ in range(3)]
+        public_keys = [k.public_key() for k in keys]
+        messages = [b"message 1", b"message 2", b"message 3"]
+        signature = aggregate_signatures(
+            [k.sign(m) for k, m in zip(keys, messages)], variant
+        )
+        aggregate_verify(public_keys, messages, signature)
+        with pytest.raises(InvalidSignature):
+            aggregate_verify(public_keys, messages[::-1], signature)
+        with pytest.raises(InvalidSignature):
+            aggregate_verify(public_keys[:2], messages[:2], signature)
+
+    @pytest.mark.parametrize("variant", list(BLSVariant))
+    def test_fast_aggregate_verify(self, variant, backend):
+        keys = [BLSPrivateKey.generate(variant) for _ in range(3)]
+        public_keys = [k.public_key() for k in keys]
+        signature = aggregate_signatures(
+            [k.sign(b"data") for k in keys], variant
+        )
+        fast_aggregate_verify(public_keys, b"data", signature)
+        with pytest.raises(InvalidSignature):
+            fast_aggregate_verify(public_keys, b"other data", signature)
+        with pytest.raises(InvalidSignature):
+            fast_aggregate_verify(public_keys[1:], b"data", signature)
+
+    def test_aggregate_errors(self, backend):
+        min_pk = BLSPrivateKey.generate(BLSVariant.MIN_PK)
+        min_sig = BLSPrivateKey.generate(BLSVariant.MIN_SIG)
+        with pytest.raises(ValueError):
+            aggregate_signatures([], BLSVariant.MIN_PK)
+        with pytest.raises(ValueError):
+            aggregate_signatures([min_sig.sign(b"data")], BLSVariant.MIN_PK)
+        with pytest.raises(ValueError):
+            aggregate_verify([], [], min_pk.sign(b"data"))
+        with pytest.raises(ValueError):
+            aggregate_verify(
+                [min_pk.public_key()], [b"a", b"b"], min_pk.sign(b"a")
+            )
+        with pytest.raises(ValueError):
+            fast_aggregate_verify([], b"data", min_pk.sign(b"data"))
+        with pytest.raises(ValueError):
+            fast_aggregate_verify(
+                [min_pk.public_key(), min_sig.public_key()],
+                b"data",
+                min_pk.sign(b"data"),
+            )
+
+    @pytest.mark.parametrize("variant", list(BLSVariant))
+    def test_round_trip(self, variant, backend):
+        key = BLSPrivateKey.generate(variant)
+        loaded = BLSPrivateKey.from_private_bytes(
+            key.private_bytes_raw(), variant
+        )
+        assert loaded.private_bytes_raw() == key.private_bytes_raw()
+        public_key = BLSPublicKey.from_public_bytes(
+            key.public_key().public_bytes_raw(), variant
+        )
+        assert public_key == key.public_key()
+
+    def test_invalid_private_bytes(self, backend):
+        order = binascii.unhexlify(
+            "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001"
+        )
+        for data in [b"\x00" * 32, order, b"\x01" * 31, b"\xff" * 32]:
+            with pytest.raises(ValueError):
+                BLSPrivateKey.from_private_bytes(data, BLSVariant.MIN_PK)
+        with pytest.raises(TypeError):
+            BLSPrivateKey.from_private_bytes(
+                _PRIVATE_KEY,
+                "min-pk",  # type: ignore[arg-type]
+            )
+
+    def test_invalid_public_bytes(self, backend):
+        public_key = (
+            BLSPrivateKey.generate(BLSVariant.MIN_PK)
+            .public_key()
+            .public_bytes_raw()
+        )
+        for data in [
+            # The point at infinity.
+            b"\xc0" + b"\x00" * 47,
+            # Not compressed.
+            bytes([public_key[0] & 0x7F]) + public_key[1:],
+            # Not on the curve.
+            b"\x80" + b"\x00" * 46 + b"\x01",
+            public_key[:-1],
+        ]:
+            with pytest.raises(ValueError):
+                BLSPublicKey.from_public_bytes(data, BLSVariant.MIN_PK)
+        with pytest.raises(ValueError):
+            BLSPublicKey.from_public_bytes(public_key, BLSVariant.MIN_SIG)
+
+    def test_public_key_equality(self, backend):
+        key = BLSPrivateKey.from_private_bytes(_PRIVATE_KEY, BLSVariant.MIN_PK)
+        key1 = key.public_key()
+        key2 = BLSPrivateKey.generate(BLSVariant.MIN_PK).public_key()
+        min_sig_key = BLSPrivateKey.from_private_bytes(
+            _PRIVATE_KEY, BLSVariant.MIN_SIG
+        ).public_key()
+        assert key1 == copy.copy(key1)
+        assert key1 == key.public_key()
+        assert key1 != key2
+        assert key1 != min_sig_key
+        assert key1 != object()
@@ -5,8 +5,11 @@
 
 import pytest
 
+from cryptography.exceptions import _Reasons
 from cryptography.hazmat.primitives import constant_time
 
+from ...utils import raises_unsupported_algorithm
+
 
 class TestConstantTimeBytesEq:
     def test_reject_unicode(self):
@@ -27,3 +30,66 @@ class TestConstantTimeBytesEq:
         assert constant_time.bytes_eq(b"foobar", b"foo") is False
 
         assert constant_time.bytes_eq(b"foo", b"foobar") is False
+
+
+@pytest.mark.supported(
+    only_if=lambda backend: backend.constant_time_bignum_supported(),
+    skip_message="Requires OpenSSL with constant time bignum support",
+)
+class TestConstantTimeBignum:
+    def test_mod_exp(self, backend):
+        p = 2**255 - 19
+        for base, exponent in [(0, 5), (2, 0), (3, p - 2), (p + 5, 2**300)]:
+            assert constant_time.mod_exp(base, exponent, p) == pow(
+                base, exponent, p
+            )
+
+    def test_mod_exp_invalid(self, backend):
+        with pytest.raises(ValueError):
+            constant_time.mod_exp(2, 3, 10)
+        with pytest.raises(ValueError):
+            constant_time.mod_exp(2, 3, 1)
+        with pytest.raises(ValueError):
+            constant_time.mod_exp(-2, 3, 11)
+        with pytest.raises(ValueError):
+            constant_time.mod_exp(2, -3, 11)
+        with pytest.raises(TypeError):
+            constant_time.mod_exp(2, 3, 11.0)  # type: ignore[arg-type]
+
+    def test_mod_inverse(self, backend):
+        for value, modulus in [(3, 11), (7, 2**64), (2**300 + 1, 2**255 - 19)]:
+            inverse = constant_time.mod_inverse(value, modulus)
+            assert 0 < inverse < modulus
+            assert (value * inverse) % modulus == 1
+
+    def test_mod_inverse_invalid(self, backend):
+        with pytest.raises(ValueError):
+            constant_time.mod_inverse(4, 10)
+        with pytest.raises(ValueError):
+            constant_time.mod_inverse(0, 11)
+        with pytest.raises(ValueError):
+            constant_time.mod_inverse(1, 1)
+        with pytest.raises(ValueError):
+            constant_time.mod_inverse(-1, 11)
+
+    def test_random_in_range(self, backend):
+        values = {constant_time.random_in_range(10, 14) for _ in range(200)}
+        assert values == {10, 11, 12, 13}
+        assert constant_time.random_in_range(2**255, 2**255 + 1) == 2**255
+
+    def test_random_in_range_invalid(self, backend):
+        with pytest.raises(ValueError):
+            constant_time.random_in_range(5, 5)
+        with pytest.raises(ValueError):
+            constant_time.random_in_range(6, 5)
+        with pytest.raises(ValueError):
+            constant_time.random_in_range(-1, 5)
+
+
+@pytest.mark.supported(
+    only_if=lambda backend: not backend.constant_time_bignum_supported(),
+    skip_message="Requires OpenSSL without constant time bignum support",
+)
+def test_bignum_unsupported(backend):
+    with raises_unsupported_algorithm(_Reasons.BACKEND_MISSING_INTERFACE):
+        constant_time.mod_exp(2, 3, 11)
@@ -0,0 +1,223 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+
+import pytest
+
+from cryptography.exceptions import InvalidSignature, UnsupportedAlgorithm
+from cryptography.hazmat.primitives.asymmetric import dsa, ec, ed25519
+from cryptography.hazmat.primitives.serialization import cose
+
+from .fixtures_rsa import RSA_KEY_2048
+
+
+@pytest.fixture(scope="module")
+def rsa_key():
+    return RSA_KEY_2048.private_key(unsafe_skip_rsa_key_validation=True)
+
+
+def _ec_key(curve):
+    return ec.generate_private_key(curve)
+
+
+class TestCOSESign1:
+    @pytest.mark.parametrize(
+        ("alg", "curve", "sig_len"),
+        [
+            (cose.COSEAlgorithm.ES256, ec.SECP256R1(), 64),
+            (cose.COSEAlgorithm.ES384, ec.SECP384R1(), 96),
+            (cose.COSEAlgorithm.ES512, ec.SECP521R1(), 132),
+        ],
+    )
+    def test_ecdsa_roundtrip(self, alg, curve, sig_len, backend):
+        key = _ec_key(curve)
+        data = cose.sign1(b"hello world", key, alg)
+        msg = cose.load_cose_sign1(data)
+        assert msg.algorithm == alg
+        assert msg.payload == b"hello world"
+        assert len(msg.signature) == sig_len
+        assert msg.verify(key.public_key()) == b"hello world"
+
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.ed25519_supported(),
+        skip_message="Requires OpenSSL with Ed25519 support",
+    )
+    def test_eddsa_roundtrip(self, backend):
+        key = ed25519.Ed25519PrivateKey.generate()
+        data = cose.sign1(b"payload", key, cose.COSEAlgorithm.EdDSA)
+        msg = cose.load_cose_sign1(data)
+        assert msg.algorithm == cose.COSEAlgorithm.EdDSA
+        assert msg.verify(key.public_key()) == b"payload"
+
+    @pytest.mark.parametrize(
+        "alg",
+        [
+            cose.COSEAlgorithm.PS256,
+            cose.COSEAlgorithm.PS384,
+            cose.COSEAlgorithm.PS512,
+            cose.COSEAlgorithm.RS256,
+            cose.COSEAlgorithm.RS384,
+            cose.COSEAlgorithm.RS512,
+        ],
+    )
+    def test_rsa_roundtrip(self, alg, rsa_key, backend):
+        data = cose.sign1(b"payload", rsa_key, alg)
+        msg = cose.load_cose_sign1(data)
+        assert msg.algorithm == alg
+        assert msg.verify(rsa_key.public_key()) == b"payload"
+
+    def test_tagged(self, backend):
+        key = _ec_key(ec.SECP256R1())
+        data = cose.sign1(b"x", key, cose.COSEAlgorithm.ES256)
+        # Tag 18 followed by a 4 element array
+        assert data[:2] == b"\xd2\x84"
+        # Untagged messages are accepted as well
+        msg = cose.load_cose_sign1(data[1:])
+        assert msg.verify(key.public_key()) == b"x"
+
+    def test_headers(self, backend):
+        key = _ec_key(ec.SECP256R1())
+        data = cose.sign1(
+            b"payload",
+            key,
+            cose.COSEAlgorithm.ES256,
+            protected_headers={cose.COSEHeader.CONTENT_TYPE: "text/plain"},
+            unprotected_headers={cose.COSEHeader.KID: b"key-1", "x": [1, -2]},
+        )
+        msg = cose.load_cose_sign1(data)
+        assert msg.protected_headers == {
+            cose.COSEHeader.ALG: cose.COSEAlgorithm.ES256,
+            cose.COSEHeader.CONTENT_TYPE: "text/plain",
+        }
+        assert msg.unprotected_headers == {
+            cose.COSEHeader.KID: b"key-1",
+            "x": [1, -2],
+        }
+        assert msg.verify(key.public_key()) == b"payload"
+
+    def test_external_aad(self, backend):
+        key = _ec_key(ec.SECP256R1())
+        data = cose.sign1(
+            b"payload", key, cose.COSEAlgorithm.ES256, external_aad=b"aad"
+        )
+        msg = cose.load_cose_sign1(data)
+        assert msg.verify(key.public_key(), external_aad=b"aad") == b"payload"
+        with pytest.raises(InvalidSignature):
+            msg.verify(key.public_key())
+        with pytest.raises(InvalidSignature):
+            msg.verify(key.public_key(), external_aad=b"other")
+
+    def test_detached(self, backend):
+        key = _ec_key(ec.SECP256R1())
+        data = cose.sign1(
+            b"payload", key, cose.COSEAlgorithm.ES256, detached=True
+        )
+        msg = cose.load_cose_sign1(data)
+        assert msg.payload is None
+        assert (
+            msg.verify(key.public_key(), detached_payload=b"payload")
+            == b"payload"
+        )
+        with pytest.raises(InvalidSignature):
+            msg.verify(key.public_key(), detached_payload=b"other")
+        with pytest.raises(ValueError):
+            msg.verify(key.public_key())
+
+    def test_detached_payload_with_attached(self, backend):
+        key = _ec_key(ec.SECP256R1())
+        msg = cose.load_cose_sign1(
+            cose.sign1(b"payload", key, cose.COSEAlgorithm.ES256)
+        )
+        with pytest.raises(ValueError):
+            msg.verify(key.public_key(), detached_payload=b"payload")
+
+    def test_tampered_payload(self, backend):
+        key = _ec_key(ec.SECP256R1())
+        data = bytearray(cose.sign1(b"payload", key, cose.COSEAlgorithm.ES256))
+        idx = data.index(b"payload")
+        data[idx] ^= 1
+        msg = cose.load_cose_sign1(bytes(data))
+        with pytest.raises(InvalidSignature):
+            msg.verify(key.public_key())
+
+    def test_wrong_key(self, backend):
+        key = _ec_key(ec.SECP256R1())
+        msg = cose.load_cose_sign1(
+            cose.sign1(b"payload", key, cose.COSEAlgorithm.ES256)
+        )
+        with pytest.raises(InvalidSignature):
+            msg.verify(_ec_key(ec.SECP256R1()).public_key())
+
+    def test_key_algorithm_mismatch(self, rsa_key, backend):
+        key = _ec_key(ec.SECP256R1())
+        with pytest.raises(ValueError):
+            cose.sign1(b"payload", key, cose.COSEAlgorithm.PS256)
+        with pytest.raises(ValueError):
+            cose.sign1(b"payload", rsa_key, cose.COSEAlgorithm.ES256)
+        with pytest.raises(ValueError):
+            cose.sign1(b"payload", key, cose.COSEAlgorithm.ES384)
+
+        msg = cose.load_cose_sign1(
+            cose.sign1(b"payload", key, cose.COSEAlgorithm.ES256)
+        )
+        with pytest.raises(ValueError):
+            msg.verify(rsa_key.public_key())
+
+    def test_unsupported_key_type(self, backend):
+        key = dsa.generate_private_key(2048)
+        with pytest.raises(ValueError):
+            cose.sign1(b"payload", key, cose.COSEAlgorithm.ES256)
+
+    def test_unsupported_algorithm(self, backend):
+        key = _ec_key(ec.SECP256R1())
+        with pytest.raises(UnsupportedAlgorithm):
+            cose.sign1(b"payload", key, -65535)
+
+    def test_alg_header_not_allowed(self, backend):
+        key = _ec_key(ec.SECP256R1())
+        with pytest.raises(ValueError):
+            cose.sign1(
+                b"payload",
+                key,
+                cose.COSEAlgorithm.ES256,
+                protected_headers={cose.COSEHeader.ALG: -7},
+            )
+        with pytest.raises(ValueError):
+            cose.sign1(
+                b"payload",
+                key,
+                cose.COSEAlgorithm.ES256,
+                unprotected_headers={cose.COSEHeader.ALG: -7},
+            )
+
+    def test_invalid_header_label(self, backend):
+        key = _ec_key(ec.SECP256R1())
+        with pytest.raises(TypeError):
+            cose.sign1(
+                b"payload",
+                key,
+                cose.COSEAlgorithm.ES256,
+                protected_headers={b"bytes": 1},  # type: ignore[dict-item]
+            )
+
+    @pytest.mark.parametrize(
+        "data",
+        [
+            b"",
+            b"\x84",
+            # An array with only three elements
+            b"\x83\x40\xa0\x40",
+            # A different tag (COSE_Sign, 98)
+            b"\xd8\x62\x84\x40\xa0\x40\x40",
+            # Protected header is not a bstr
+            b"\x84\xa0\xa0\x40\x40",
+            # Protected header is missing alg
+            b"\x84\x41\xa0\xa0\x40\x40",
+            # Trailing data
+            b"\x84\x43\xa1\x01\x26\xa0\x40\x40\x00",
+        ],
+    )
+    def test_load_invalid(self, data, backend):
+        with pytest.raises(ValueError):
+            cose.load_cose_sign1(data)
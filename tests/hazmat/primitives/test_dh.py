@@ -7,12 +7,21 @@ import binascii
 import copy
 import itertools
 import os
+import threading
 import typing
 
 import pytest
 
-from cryptography.hazmat.primitives import serialization
+from cryptography.exceptions import AlreadyFinalized
+from cryptography.hazmat.primitives import hashes, serialization
 from cryptography.hazmat.primitives.asymmetric import dh
+from cryptography.hazmat.primitives.kdf.concatkdf import (
+    ConcatKDFHash,
+    ConcatKDFHMAC,
+)
+from cryptography.hazmat.primitives.kdf.hkdf import HKDF
+from cryptography.hazmat.primitives.kdf.pbkdf2 import PBKDF2HMAC
+from cryptography.hazmat.primitives.kdf.x963kdf import X963KDF
 
 from ...doubles import DummyKeySerializationEncryption
 from ...utils import load_nist_vectors, load_vectors_from_file
@@ -156,6 +165,78 @@ class TestDH:
         with pytest.raises(ValueError):
             dh.generate_parameters(2, 1 << 30)
 
+    @pytest.mark.skip_fips(reason="FIPS requires key size >= 2048")
+    def test_generate_dh_progress(self, backend):
+        calls = []
+        parameters = dh.generate_parameters(
+            2, 512, progress=lambda stage, count: calls.append(stage)
+        )
+        assert isinstance(parameters, dh.DHParameters)
+        assert calls
+        assert all(isinstance(stage, int) for stage in calls)
+
+    def test_generate_dh_cancelled(self, backend):
+        cancel = threading.Event()
+        cancel.set()
+        with pytest.raises(ValueError, match="cancelled"):
+            dh.generate_parameters(2, 2048, cancel=cancel)
+
+    def test_generate_dh_progress_raises(self, backend):
+        def progress(stage, count):
+            raise ZeroDivisionError
+
+        with pytest.raises(ZeroDivisionError):
+            dh.generate_parameters(2, 2048, progress=progress)
+
+    @pytest.mark.parametrize(
+        ("name", "key_size"),
+        [
+            ("ffdhe2048", 2048),
+            ("ffdhe3072", 3072),
+            ("ffdhe4096", 4096),
+            ("ffdhe6144", 6144),
+            ("ffdhe8192", 8192),
+        ],
+    )
+    def test_named_group_parameters(self, backend, name, key_size):
+        parameters = dh.named_group_parameters(name)
+        assert parameters.group_name == name
+        numbers = parameters.parameter_numbers()
+        assert numbers.p.bit_length() == key_size
+        assert numbers.g == 2
+        assert numbers.q == (numbers.p - 1) // 2
+
+        key1 = parameters.generate_private_key()
+        key2 = parameters.generate_private_key()
+        assert key1.key_size == key_size
+        assert key1.parameters().group_name == name
+        assert key1.exchange(key2.public_key()) == key2.exchange(
+            key1.public_key()
+        )
+
+    def test_named_group_recognized(self, backend):
+        parameters = FFDH3072_P.parameters(backend)
+        assert parameters.group_name == "ffdhe3072"
+        pem = parameters.parameter_bytes(
+            serialization.Encoding.PEM, serialization.ParameterFormat.PKCS3
+        )
+        loaded = serialization.load_pem_parameters(pem)
+        assert isinstance(loaded, dh.DHParameters)
+        assert loaded.group_name == "ffdhe3072"
+
+        p = FFDH3072_P.p
+        with_q = dh.DHParameterNumbers(p, 2, (p - 1) // 2)
+        assert with_q.parameters(backend).group_name == "ffdhe3072"
+        wrong_q = dh.DHParameterNumbers(p, 2, (p - 1) // 4)
+        assert wrong_q.parameters(backend).group_name is None
+        assert dh.DHParameterNumbers(p, 5).parameters().group_name is None
+
+    def test_named_group_unknown(self, backend):
+        with pytest.raises(ValueError):
+            dh.named_group_parameters("ffdhe1024")
+        with pytest.raises(ValueError):
+            dh.named_group_parameters("modp2048")
+
     @pytest.mark.parametrize(
         "vector",
         load_vectors_from_file(
@@ -297,6 +378,45 @@ class TestDH:
         symkey2 = key2.exchange(key1.public_key())
         assert symkey1 == symkey2
 
+    @pytest.mark.parametrize(
+        "make_kdf",
+        [
+            lambda: HKDF(hashes.SHA256(), 100, None, b"info"),
+            lambda: X963KDF(hashes.SHA256(), 100, b"sharedinfo"),
+            lambda: ConcatKDFHash(hashes.SHA256(), 100, b"otherinfo"),
+            lambda: ConcatKDFHMAC(hashes.SHA256(), 100, b"salt", b"other"),
+        ],
+    )
+    def test_exchange_and_derive(self, make_kdf, backend):
+        parameters = FFDH3072_P.parameters(backend)
+        key = parameters.generate_private_key()
+        peer = parameters.generate_private_key().public_key()
+
+        expected = make_kdf().derive(key.exchange(peer))
+        kdf = make_kdf()
+        assert key.exchange_and_derive(peer, kdf) == expected
+        with pytest.raises(AlreadyFinalized):
+            kdf.derive(b"\x00" * 32)
+        with pytest.raises(AlreadyFinalized):
+            key.exchange_and_derive(peer, kdf)
+
+    def test_exchange_and_derive_invalid(self, backend):
+        parameters = FFDH3072_P.parameters(backend)
+        key = parameters.generate_private_key()
+        with pytest.raises(TypeError):
+            key.exchange_and_derive(
+                key.public_key(),
+                PBKDF2HMAC(hashes.SHA256(), 32, b"salt", 1),
+            )
+
+        invalid = dh.DHPublicNumbers(1, FFDH3072_P).public_key(backend)
+        with pytest.raises(ValueError):
+            key.exchange_and_derive(
+                invalid,
+                HKDF(hashes.SHA256(), 32, None, None),
+                validate=True,
+            )
+
     def test_exchange_algorithm(self, backend):
         parameters = FFDH3072_P.parameters(backend)
         key1 = parameters.generate_private_key()
@@ -344,6 +464,15 @@ class TestDH:
         assert len(symkey) == 512 // 8
         assert symkey[:1] == b"\x00"
 
+        def make_kdf():
+            return HKDF(hashes.SHA256(), 32, None, None)
+
+        peer = public.public_key(backend)
+        padded = key.exchange_and_derive(peer, make_kdf())
+        assert padded == make_kdf().derive(symkey)
+        stripped = key.exchange_and_derive(peer, make_kdf(), pad=False)
+        assert stripped == make_kdf().derive(symkey.lstrip(b"\x00"))
+
     @pytest.mark.parametrize(
         "vector",
         load_vectors_from_file(
@@ -443,6 +572,47 @@ class TestDH:
         assert int.from_bytes(symkey1, "big") == int(vector["z"], 16)
         assert int.from_bytes(symkey2, "big") == int(vector["z"], 16)
 
+    def test_validate_public_key(self, backend):
+        parameters = FFDH3072_P.parameters(backend)
+        key1 = parameters.generate_private_key()
+        key2 = parameters.generate_private_key()
+        key2.public_key().validate()
+        symkey = key1.exchange(key2.public_key(), validate=True)
+        assert symkey == key1.exchange(key2.public_key())
+
+        # Without q only the range of the public value can be checked.
+        dh.DHPublicNumbers(2, FFDH3072_P).public_key(backend).validate()
+        for y in [1, FFDH3072_P.p - 1]:
+            public_key = dh.DHPublicNumbers(y, FFDH3072_P).public_key(backend)
+            with pytest.raises(ValueError):
+                public_key.validate()
+            with pytest.raises(ValueError):
+                key1.exchange(public_key, validate=True)
+
+    def test_validate_public_key_with_q(self, backend):
+        _skip_dhx_unsupported(backend, True)
+        public_key = load_vectors_from_file(
+            os.path.join("asymmetric", "DH", "dhpub_rfc5114_2.pem"),
+            lambda f: serialization.load_pem_public_key(f.read()),
+            mode="rb",
+        )
+        assert isinstance(public_key, dh.DHPublicKey)
+        public_key.validate()
+
+        parameter_numbers = public_key.public_numbers().parameter_numbers
+        assert parameter_numbers.q is not None
+        # 2 is in range, but isn't in the subgroup of order q.
+        assert pow(2, parameter_numbers.q, parameter_numbers.p) != 1
+        key = parameter_numbers.parameters(backend).generate_private_key()
+        for y in [1, 2, parameter_numbers.p - 1]:
+            invalid = dh.DHPublicNumbers(y, parameter_numbers).public_key(
+                backend
+            )
+            with pytest.raises(ValueError):
+                invalid.validate()
+            with pytest.raises(ValueError):
+                key.exchange(invalid, validate=True)
+
     def test_public_key_equality(self, backend):
         key_bytes = load_vectors_from_file(
             os.path.join("asymmetric", "DH", "dhpub.pem"),
@@ -838,6 +1008,101 @@ class TestDHParameterSerialization:
         loaded_param_num = loaded_key.parameter_numbers()
         assert loaded_param_num == parameters.parameter_numbers()
 
+    @pytest.mark.parametrize(
+        ("encoding", "vec_path"),
+        [
+            (
+                serialization.Encoding.PEM,
+                os.path.join("asymmetric", "DH", "dhp_rfc5114_2.pem"),
+            ),
+            (
+                serialization.Encoding.DER,
+                os.path.join("asymmetric", "DH", "dhp_rfc5114_2.der"),
+            ),
+        ],
+    )
+    def test_parameter_bytes_x942(self, backend, encoding, vec_path):
+        _skip_dhx_unsupported(backend, True)
+        param_bytes = load_vectors_from_file(
+            vec_path, lambda f: f.read(), mode="rb"
+        )
+        parameters = serialization.load_der_parameters(
+            load_vectors_from_file(
+                os.path.join("asymmetric", "DH", "dhp_rfc5114_2.der"),
+                lambda f: f.read(),
+                mode="rb",
+            )
+        )
+        serialized = parameters.parameter_bytes(
+            encoding, serialization.ParameterFormat.X942
+        )
+        assert serialized == param_bytes
+
+    def test_parameter_bytes_x942_requires_q(self, backend):
+        # Not a named group, which OpenSSL 3 would fill in q for.
+        parameters = load_vectors_from_file(
+            os.path.join("asymmetric", "DH", "dhp.pem"),
+            lambda f: serialization.load_pem_parameters(f.read()),
+            mode="rb",
+        )
+        assert parameters.parameter_numbers().q is None
+        with pytest.raises(ValueError):
+            parameters.parameter_bytes(
+                serialization.Encoding.PEM,
+                serialization.ParameterFormat.X942,
+            )
+
+    def test_load_x942_parameters_with_j(self, backend):
+        _skip_dhx_unsupported(backend, True)
+        parameters = load_vectors_from_file(
+            os.path.join("asymmetric", "DH", "dhp_rfc5114_2_j.pem"),
+            lambda f: serialization.load_pem_parameters(f.read()),
+            mode="rb",
+        )
+        vec = load_vectors_from_file(
+            os.path.join("asymmetric", "DH", "dhkey_rfc5114_2.txt"),
+            load_nist_vectors,
+        )[0]
+        assert parameters.parameter_numbers() == dh.DHParameterNumbers(
+            int(vec["p"], 16), int(vec["g"], 16), int(vec["q"], 16)
+        )
+
+    def test_load_pkcs3_parameters_with_private_value_length(self, backend):
+        parameters = load_vectors_from_file(
+            os.path.join("asymmetric", "DH", "dhp_private_value_length.pem"),
+            lambda f: serialization.load_pem_parameters(f.read()),
+            mode="rb",
+        )
+        vec = load_vectors_from_file(
+            os.path.join("asymmetric", "DH", "dhkey.txt"),
+            load_nist_vectors,
+        )[0]
+        assert parameters.parameter_numbers() == dh.DHParameterNumbers(
+            int(vec["p"], 16), int(vec["g"], 16)
+        )
+
+    def test_x942_key_round_trip(self, backend):
+        _skip_dhx_unsupported(backend, True)
+        parameters = load_vectors_from_file(
+            os.path.join("asymmetric", "DH", "dhp_rfc5114_2.pem"),
+            lambda f: serialization.load_pem_parameters(f.read()),
+            mode="rb",
+        )
+        key = parameters.generate_private_key()
+        serialized = key.private_bytes(
+            serialization.Encoding.PEM,
+            serialization.PrivateFormat.PKCS8,
+            serialization.NoEncryption(),
+        )
+        loaded = serialization.load_pem_private_key(serialized, None)
+        assert isinstance(loaded, dh.DHPrivateKey)
+        assert loaded.private_numbers() == key.private_numbers()
+        assert (
+            loaded.parameters().parameter_numbers()
+            == parameters.parameter_numbers()
+        )
+        assert parameters.parameter_numbers().q is not None
+
     @pytest.mark.parametrize(
         ("param_path", "loader_func", "encoding", "is_dhx"),
         [
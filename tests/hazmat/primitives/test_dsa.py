@@ -6,12 +6,13 @@
 import copy
 import itertools
 import os
+import threading
 import typing
 
 import pytest
 
 from cryptography import utils
-from cryptography.exceptions import InvalidSignature
+from cryptography.exceptions import InvalidSignature, _Reasons
 from cryptography.hazmat.primitives import hashes, serialization
 from cryptography.hazmat.primitives.asymmetric import dsa
 from cryptography.hazmat.primitives.asymmetric.utils import (
@@ -24,6 +25,7 @@ from ...utils import (
     load_fips_dsa_key_pair_vectors,
     load_fips_dsa_sig_vectors,
     load_vectors_from_file,
+    raises_unsupported_algorithm,
 )
 from .fixtures_dsa import DSA_KEY_1024, DSA_KEY_2048, DSA_KEY_3072
 from .utils import skip_fips_traditional_openssl
@@ -70,6 +72,69 @@ class TestDSA:
         with pytest.raises(ValueError):
             dsa.generate_parameters(1, backend)
 
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.dsa_verifiable_parameters_supported(),
+        skip_message="Requires OpenSSL 3.0.0+",
+    )
+    def test_generate_verifiable_parameters(self, backend):
+        parameters, seed, counter = dsa.generate_verifiable_parameters(2048)
+        assert isinstance(parameters, dsa.DSAParameters)
+        assert parameters.parameter_numbers().q.bit_length() == 256
+        assert len(seed) == 32
+        assert counter >= 0
+        dsa.validate_parameters(parameters, seed, counter)
+
+        with pytest.raises(ValueError):
+            dsa.validate_parameters(parameters, seed, counter + 1)
+        tampered = bytes([seed[0] ^ 1]) + seed[1:]
+        with pytest.raises(ValueError):
+            dsa.validate_parameters(parameters, tampered, counter)
+        with pytest.raises(ValueError):
+            dsa.validate_parameters(
+                DSA_KEY_2048.public_numbers.parameter_numbers.parameters(
+                    backend
+                ),
+                seed,
+                counter,
+            )
+
+    def test_generate_verifiable_parameters_invalid_size(self, backend):
+        with pytest.raises(ValueError):
+            dsa.generate_verifiable_parameters(4096)
+
+    @pytest.mark.supported(
+        only_if=lambda backend: (
+            not backend.dsa_verifiable_parameters_supported()
+        ),
+        skip_message="Requires OpenSSL older than 3.0.0",
+    )
+    def test_verifiable_parameters_unsupported(self, backend):
+        with raises_unsupported_algorithm(
+            _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+        ):
+            dsa.generate_verifiable_parameters(2048)
+
+    def test_generate_dsa_parameters_progress(self, backend):
+        calls = []
+        parameters = dsa.generate_parameters(
+            2048, progress=lambda stage, count: calls.append(stage)
+        )
+        assert isinstance(parameters, dsa.DSAParameters)
+        assert calls
+
+    def test_generate_dsa_parameters_cancelled(self, backend):
+        cancel = threading.Event()
+        cancel.set()
+        with pytest.raises(ValueError, match="cancelled"):
+            dsa.generate_parameters(2048, cancel=cancel)
+
+    def test_generate_dsa_parameters_progress_raises(self, backend):
+        def progress(stage, count):
+            raise ZeroDivisionError
+
+        with pytest.raises(ZeroDivisionError):
+            dsa.generate_parameters(2048, progress=progress)
+
     @pytest.mark.parametrize(
         "vector",
         load_vectors_from_file(
@@ -399,6 +464,21 @@ class TestDSA:
         with pytest.raises(TypeError):
             key1 < key2  # type: ignore[operator]
 
+    def test_private_key_equality(self, backend):
+        key_bytes = load_vectors_from_file(
+            os.path.join("asymmetric", "PKCS8", "unenc-dsa-pkcs8.pem"),
+            lambda pemfile: pemfile.read().encode(),
+        )
+        key1 = serialization.load_pem_private_key(key_bytes, None)
+        key2 = serialization.load_pem_private_key(key_bytes, None)
+        key3 = DSA_KEY_2048.private_key()
+        assert isinstance(key1, dsa.DSAPrivateKey)
+        assert key1 == key2
+        assert key1 != key3
+        assert key1 != object()
+        assert key1.public_key_matches(key2.public_key())
+        assert not key1.public_key_matches(key3.public_key())
+
     def test_public_key_copy(self):
         key_bytes = load_vectors_from_file(
             os.path.join("asymmetric", "PKCS8", "unenc-dsa-pkcs8.pem"),
@@ -514,6 +594,76 @@ class TestDSASignature:
                     signature, vector["msg"], algorithm
                 )
 
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.dsa_deterministic_supported(),
+        skip_message="Requires OpenSSL with deterministic DSA support",
+    )
+    def test_deterministic_signing(self, backend, subtests):
+        vectors = load_vectors_from_file(
+            os.path.join("asymmetric", "DSA", "RFC6979", "SigGen.txt"),
+            load_fips_dsa_sig_vectors,
+        )
+        for vector in vectors:
+            with subtests.test():
+                digest_algorithm = vector["digest_algorithm"].replace("-", "")
+                algorithm = _ALGORITHMS_DICT[digest_algorithm]
+
+                _skip_if_dsa_not_supported(
+                    backend, algorithm, vector["p"], vector["q"], vector["g"]
+                )
+
+                private_key = dsa.DSAPrivateNumbers(
+                    public_numbers=dsa.DSAPublicNumbers(
+                        parameter_numbers=dsa.DSAParameterNumbers(
+                            vector["p"], vector["q"], vector["g"]
+                        ),
+                        y=vector["y"],
+                    ),
+                    x=vector["x"],
+                ).private_key(backend)
+                signature = private_key.sign(
+                    vector["msg"], algorithm, deterministic_signing=True
+                )
+                assert signature == encode_dss_signature(
+                    vector["r"], vector["s"]
+                )
+                private_key.public_key().verify(
+                    signature, vector["msg"], algorithm
+                )
+
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.dsa_deterministic_supported(),
+        skip_message="Requires OpenSSL with deterministic DSA support",
+    )
+    def test_deterministic_signing_prehashed(self, backend):
+        private_key = DSA_KEY_2048.private_key(backend)
+        message = b"one little message"
+        h = hashes.Hash(hashes.SHA256(), backend)
+        h.update(message)
+        digest = h.finalize()
+        signature = private_key.sign(
+            digest,
+            Prehashed(hashes.SHA256()),
+            deterministic_signing=True,
+        )
+        assert signature == private_key.sign(
+            message, hashes.SHA256(), deterministic_signing=True
+        )
+        private_key.public_key().verify(signature, message, hashes.SHA256())
+
+    @pytest.mark.supported(
+        only_if=lambda backend: not backend.dsa_deterministic_supported(),
+        skip_message="Requires OpenSSL without deterministic DSA support",
+    )
+    def test_deterministic_signing_unsupported(self, backend):
+        private_key = DSA_KEY_2048.private_key(backend)
+        with raises_unsupported_algorithm(
+            _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+        ):
+            private_key.sign(
+                b"message", hashes.SHA256(), deterministic_signing=True
+            )
+
     def test_sign(self, backend):
         private_key = DSA_KEY_1024.private_key(backend)
         message = b"one little message"
@@ -4,8 +4,11 @@
 
 import binascii
 import copy
+import csv
+import hashlib
 import itertools
 import os
+import pickle
 import textwrap
 import typing
 from binascii import hexlify
@@ -17,8 +20,18 @@ from cryptography.hazmat.primitives import hashes, serialization
 from cryptography.hazmat.primitives.asymmetric import ec
 from cryptography.hazmat.primitives.asymmetric.utils import (
     Prehashed,
+    decode_dss_signature,
+    der_to_raw_signature,
     encode_dss_signature,
+    raw_to_der_signature,
 )
+from cryptography.hazmat.primitives.kdf.concatkdf import (
+    ConcatKDFHash,
+    ConcatKDFHMAC,
+)
+from cryptography.hazmat.primitives.kdf.hkdf import HKDF
+from cryptography.hazmat.primitives.kdf.pbkdf2 import PBKDF2HMAC
+from cryptography.hazmat.primitives.kdf.x963kdf import X963KDF
 
 from ...doubles import DummyKeySerializationEncryption
 from ...utils import (
@@ -32,6 +45,10 @@ from ...utils import (
 from .fixtures_ec import EC_KEY_SECP384R1
 from .utils import skip_fips_traditional_openssl
 
+_SECP256R1_ORDER = int(
+    "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551", 16
+)
+
 _HASH_TYPES: typing.Dict[str, typing.Type[hashes.HashAlgorithm]] = {
     "SHA-1": hashes.SHA1,
     "SHA-224": hashes.SHA224,
@@ -98,6 +115,19 @@ def test_skip_ecdsa_vector(backend):
         _skip_ecdsa_vector(backend, DummyCurve(), hashes.SHA256)
 
 
+def test_supported_curves(backend):
+    curves = ec.supported_curves()
+    assert len(curves) == len(set(curves))
+    for curve in curves:
+        assert issubclass(curve, ec.EllipticCurve)
+        assert backend.elliptic_curve_supported(curve())
+
+    for curve_instance in ec._CURVE_TYPES.values():
+        assert (
+            type(curve_instance) in curves
+        ) == backend.elliptic_curve_supported(curve_instance)
+
+
 def test_derive_private_key_success(backend):
     curve = ec.SECP256K1()
     _skip_curve_unsupported(backend, curve)
@@ -125,6 +155,66 @@ def test_derive_private_key_errors(backend):
         ec.derive_private_key(-7, curve, backend)
 
 
+@pytest.mark.parametrize(
+    ("curve", "private_value"),
+    [
+        (
+            ec.SECP256R1(),
+            "bee184c32ee48e6361c4bc1c6b0e9b3352333d38bdf5ec5d71200b19dcf97134",
+        ),
+        (
+            ec.SECP384R1(),
+            "1b952fdfbce2ab0b070c33617bdc86e8f7c19e78e59e41a4735f9b9046d4a7d5"
+            "2d904d16f3dde26a309f7790e4c57cd1",
+        ),
+        (
+            ec.SECP521R1(),
+            "54b351b42d2861e5f3efd65133268197913eb0f971689651e0e52121fb102610"
+            "bd93ae443524b7a371e4f0cb1685cfdb5ee28af3693a29e69ca4aca52590687c"
+            "6",
+        ),
+    ],
+)
+def test_derive_private_key_from_seed(curve, private_value, backend):
+    _skip_curve_unsupported(backend, curve)
+    seed = bytes(range(32))
+    key = ec.derive_private_key_from_seed(seed, curve, b"device 1")
+    assert isinstance(key.curve, type(curve))
+    assert key.private_numbers().private_value == int(private_value, 16)
+    assert (
+        key.private_numbers()
+        == ec.derive_private_key_from_seed(
+            bytearray(seed), curve, b"device 1"
+        ).private_numbers()
+    )
+    assert (
+        key.private_numbers()
+        != ec.derive_private_key_from_seed(
+            seed, curve, b"device 2"
+        ).private_numbers()
+    )
+
+
+def test_derive_private_key_from_seed_errors(backend):
+    curve = ec.SECP256R1()
+    _skip_curve_unsupported(backend, curve)
+
+    with pytest.raises(ValueError):
+        ec.derive_private_key_from_seed(b"\x00" * 15, curve)
+    with pytest.raises(TypeError):
+        ec.derive_private_key_from_seed(
+            "seed" * 4, curve  # type: ignore[arg-type]
+        )
+    with pytest.raises(TypeError):
+        ec.derive_private_key_from_seed(
+            b"\x00" * 16, curve, "info"  # type: ignore[arg-type]
+        )
+    with pytest.raises(TypeError):
+        ec.derive_private_key_from_seed(
+            b"\x00" * 16, "curve"  # type: ignore[arg-type]
+        )
+
+
 def test_derive_point_at_infinity(backend):
     curve = ec.SECP256R1()
     _skip_curve_unsupported(backend, curve)
@@ -139,6 +229,69 @@ def test_derive_point_at_infinity(backend):
         ec.derive_private_key(q, ec.SECP256R1())
 
 
+@pytest.mark.parametrize(
+    ("curve", "length"),
+    [
+        (ec.SECP256R1(), 32),
+        (ec.SECP384R1(), 48),
+        (ec.SECP521R1(), 66),
+    ],
+)
+def test_private_bytes_raw(curve, length, backend):
+    _skip_curve_unsupported(backend, curve)
+    key = ec.generate_private_key(curve)
+    raw = key.private_bytes_raw()
+    assert len(raw) == length
+    assert int.from_bytes(raw, "big") == key.private_numbers().private_value
+
+    loaded = ec.EllipticCurvePrivateKey.from_private_bytes(curve, raw)
+    assert isinstance(loaded.curve, type(curve))
+    assert loaded.private_numbers() == key.private_numbers()
+    loaded = ec.EllipticCurvePrivateKey.from_private_bytes(
+        curve, bytearray(raw)
+    )
+    assert loaded.private_numbers() == key.private_numbers()
+
+
+def test_private_bytes_raw_padded(backend):
+    curve = ec.SECP256R1()
+    _skip_curve_unsupported(backend, curve)
+    key = ec.derive_private_key(1, curve)
+    assert key.private_bytes_raw() == b"\x00" * 31 + b"\x01"
+    loaded = ec.EllipticCurvePrivateKey.from_private_bytes(
+        curve, b"\x00" * 31 + b"\x01"
+    )
+    assert loaded.private_numbers() == key.private_numbers()
+
+
+def test_from_private_bytes_errors(backend):
+    curve = ec.SECP256R1()
+    _skip_curve_unsupported(backend, curve)
+    # order of the curve
+    q = 0xFFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551
+
+    with pytest.raises(ValueError):
+        ec.EllipticCurvePrivateKey.from_private_bytes(curve, b"\x01" * 31)
+    with pytest.raises(ValueError):
+        ec.EllipticCurvePrivateKey.from_private_bytes(curve, b"\x01" * 33)
+    with pytest.raises(ValueError):
+        ec.EllipticCurvePrivateKey.from_private_bytes(curve, b"\x00" * 32)
+    with pytest.raises(ValueError):
+        ec.EllipticCurvePrivateKey.from_private_bytes(
+            curve, q.to_bytes(32, "big")
+        )
+    with pytest.raises(ValueError):
+        ec.EllipticCurvePrivateKey.from_private_bytes(curve, b"\xff" * 32)
+    with pytest.raises(TypeError):
+        ec.EllipticCurvePrivateKey.from_private_bytes(
+            curve, "\x01" * 32  # type: ignore[arg-type]
+        )
+    with pytest.raises(TypeError):
+        ec.EllipticCurvePrivateKey.from_private_bytes(
+            "curve", b"\x01" * 32  # type: ignore[arg-type]
+        )
+
+
 def test_ec_numbers():
     numbers = ec.EllipticCurvePrivateNumbers(
         1, ec.EllipticCurvePublicNumbers(2, 3, DummyCurve())
@@ -187,6 +340,14 @@ def test_ec_public_numbers_hash():
     assert hash(pn1) != hash(pn3)
 
 
+def test_ec_public_numbers_pickle():
+    pn = ec.EllipticCurvePublicNumbers(2, 3, ec.SECP256R1())
+    loaded = pickle.loads(pickle.dumps(pn))
+    assert loaded == pn
+    assert isinstance(loaded, ec.EllipticCurvePublicNumbers)
+    assert isinstance(loaded.curve, ec.SECP256R1)
+
+
 def test_ec_private_numbers_hash():
     numbers1 = ec.EllipticCurvePrivateNumbers(
         1, ec.EllipticCurvePublicNumbers(2, 3, DummyCurve())
@@ -302,6 +463,37 @@ class TestECDSAVectors:
         assert type(pkey.curve) is type(curve)
         assert key.curve.key_size == pkey.curve.key_size
 
+    @pytest.mark.parametrize(
+        "curve",
+        [
+            ec.SECP224K1(),
+            ec.BrainpoolP256T1(),
+            ec.BrainpoolP384T1(),
+            ec.BrainpoolP512T1(),
+            ec.FRP256V1(),
+        ],
+    )
+    def test_additional_curves(self, backend, curve):
+        if not backend.elliptic_curve_supported(curve):
+            with raises_unsupported_algorithm(
+                exceptions._Reasons.UNSUPPORTED_ELLIPTIC_CURVE
+            ):
+                ec.generate_private_key(curve)
+            return
+
+        key = ec.generate_private_key(curve)
+        signature = key.sign(b"data", ec.ECDSA(hashes.SHA256()))
+        key.public_key().verify(signature, b"data", ec.ECDSA(hashes.SHA256()))
+
+        der = key.public_key().public_bytes(
+            serialization.Encoding.DER,
+            serialization.PublicFormat.SubjectPublicKeyInfo,
+        )
+        loaded = serialization.load_der_public_key(der)
+        assert isinstance(loaded, ec.EllipticCurvePublicKey)
+        assert type(loaded.curve) is type(curve)
+        assert loaded == key.public_key()
+
     def test_generate_unknown_curve(self, backend):
         with raises_unsupported_algorithm(
             exceptions._Reasons.UNSUPPORTED_ELLIPTIC_CURVE
@@ -528,6 +720,485 @@ class TestECDSAVectors:
         public_key = private_key.public_key()
         public_key.verify(signature, message, ec.ECDSA(hashes.SHA1()))
 
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.ecdsa_deterministic_supported(),
+        skip_message="Requires OpenSSL with deterministic ECDSA support",
+    )
+    def test_deterministic_signing(self, backend, subtests):
+        vectors = load_vectors_from_file(
+            os.path.join("asymmetric", "ECDSA", "RFC6979", "SigGen.txt"),
+            load_fips_ecdsa_signing_vectors,
+        )
+        for vector in vectors:
+            with subtests.test():
+                hash_type = _HASH_TYPES[vector["digest_algorithm"]]
+                curve = ec._CURVE_TYPES[vector["curve"]]
+
+                _skip_ecdsa_vector(backend, curve, hash_type)
+
+                private_key = ec.derive_private_key(vector["d"], curve)
+                algorithm = ec.ECDSA(hash_type(), deterministic_signing=True)
+                signature = private_key.sign(vector["message"], algorithm)
+                assert signature == encode_dss_signature(
+                    vector["r"], vector["s"]
+                )
+                private_key.public_key().verify(
+                    signature, vector["message"], algorithm
+                )
+
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.ecdsa_deterministic_supported(),
+        skip_message="Requires OpenSSL with deterministic ECDSA support",
+    )
+    def test_deterministic_signing_prehashed(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        message = b"one little message"
+        h = hashes.Hash(hashes.SHA256(), backend)
+        h.update(message)
+        data = h.finalize()
+        private_key = ec.generate_private_key(ec.SECP256R1(), backend)
+        signature = private_key.sign(
+            data,
+            ec.ECDSA(Prehashed(hashes.SHA256()), deterministic_signing=True),
+        )
+        assert signature == private_key.sign(
+            message, ec.ECDSA(hashes.SHA256(), deterministic_signing=True)
+        )
+        private_key.public_key().verify(
+            signature, message, ec.ECDSA(hashes.SHA256())
+        )
+
+    @pytest.mark.supported(
+        only_if=lambda backend: not backend.ecdsa_deterministic_supported(),
+        skip_message="Requires OpenSSL without deterministic ECDSA support",
+    )
+    def test_deterministic_signing_unsupported(self, backend):
+        with raises_unsupported_algorithm(
+            exceptions._Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+        ):
+            ec.ECDSA(hashes.SHA256(), deterministic_signing=True)
+
+    def test_streaming_verify_vectors(self, backend, subtests):
+        vectors = load_vectors_from_file(
+            os.path.join("asymmetric", "ECDSA", "FIPS_186-3", "SigGen.txt"),
+            load_fips_ecdsa_signing_vectors,
+        )
+        for vector in vectors:
+            with subtests.test():
+                hash_type = _HASH_TYPES[vector["digest_algorithm"]]
+                curve = ec._CURVE_TYPES[vector["curve"]]
+
+                _skip_ecdsa_vector(backend, curve, hash_type)
+
+                key = ec.EllipticCurvePublicNumbers(
+                    vector["x"], vector["y"], curve
+                ).public_key(backend)
+                signature = encode_dss_signature(vector["r"], vector["s"])
+                ctx = key.verify_init(signature, ec.ECDSA(hash_type()))
+                message = vector["message"]
+                for i in range(0, len(message), 17):
+                    ctx.update(message[i : i + 17])
+                ctx.verify()
+
+                ctx = key.verify_init(signature, ec.ECDSA(hash_type()))
+                ctx.update(message + b"\x00")
+                with pytest.raises(exceptions.InvalidSignature):
+                    ctx.verify()
+
+    @pytest.mark.parametrize(
+        "signature_format",
+        [ec.ECDSASignatureFormat.DER, ec.ECDSASignatureFormat.P1363],
+    )
+    def test_streaming_sign(self, signature_format, backend):
+        _skip_curve_unsupported(backend, ec.SECP384R1())
+        algorithm = ec.ECDSA(
+            hashes.SHA384(), signature_format=signature_format
+        )
+        private_key = ec.generate_private_key(ec.SECP384R1())
+        public_key = private_key.public_key()
+
+        ctx = private_key.sign_init(algorithm)
+        ctx.update(b"one little ")
+        ctx.update(bytearray(b"message"))
+        signature = ctx.finalize()
+        public_key.verify(signature, b"one little message", algorithm)
+
+        ctx = public_key.verify_init(signature, algorithm)
+        ctx.update(b"one little message")
+        ctx.verify()
+
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.ecdsa_deterministic_supported(),
+        skip_message="Requires OpenSSL with deterministic ECDSA support",
+    )
+    def test_streaming_sign_deterministic(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        algorithm = ec.ECDSA(hashes.SHA256(), deterministic_signing=True)
+        private_key = ec.generate_private_key(ec.SECP256R1())
+        ctx = private_key.sign_init(algorithm)
+        ctx.update(b"one little ")
+        ctx.update(b"message")
+        assert ctx.finalize() == private_key.sign(
+            b"one little message", algorithm
+        )
+
+    @pytest.mark.parametrize(
+        "signature_format",
+        [ec.ECDSASignatureFormat.DER, ec.ECDSASignatureFormat.P1363],
+    )
+    def test_precompute(self, signature_format, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        algorithm = ec.ECDSA(
+            hashes.SHA256(), signature_format=signature_format
+        )
+        private_key = ec.generate_private_key(ec.SECP256R1())
+        public_key = private_key.public_key()
+        private_key.precompute()
+        private_key.precompute()
+        public_key.precompute()
+
+        for i in range(3):
+            data = b"message %d" % i
+            signature = private_key.sign(data, algorithm)
+            public_key.verify(signature, data, algorithm)
+            with pytest.raises(exceptions.InvalidSignature):
+                public_key.verify(signature, data + b"\x00", algorithm)
+
+        other_key = ec.generate_private_key(ec.SECP256R1())
+        with pytest.raises(exceptions.InvalidSignature):
+            public_key.verify(
+                other_key.sign(b"data", algorithm), b"data", algorithm
+            )
+
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.ecdsa_deterministic_supported(),
+        skip_message="Requires OpenSSL with deterministic ECDSA support",
+    )
+    def test_precompute_deterministic(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        private_key = ec.generate_private_key(ec.SECP256R1())
+        private_key.precompute()
+        deterministic = ec.ECDSA(hashes.SHA256(), deterministic_signing=True)
+        signature = private_key.sign(b"data", deterministic)
+        assert signature == private_key.sign(b"data", deterministic)
+        # The cached context is unaffected by deterministic signing.
+        randomized = ec.ECDSA(hashes.SHA256())
+        assert private_key.sign(b"data", randomized) != private_key.sign(
+            b"data", randomized
+        )
+
+    def test_streaming_already_finalized(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        algorithm = ec.ECDSA(hashes.SHA256())
+        private_key = ec.generate_private_key(ec.SECP256R1())
+        ctx = private_key.sign_init(algorithm)
+        signature = ctx.finalize()
+        with pytest.raises(exceptions.AlreadyFinalized):
+            ctx.update(b"data")
+        with pytest.raises(exceptions.AlreadyFinalized):
+            ctx.finalize()
+
+        verify_ctx = private_key.public_key().verify_init(
+            signature, algorithm
+        )
+        verify_ctx.verify()
+        with pytest.raises(exceptions.AlreadyFinalized):
+            verify_ctx.update(b"data")
+        with pytest.raises(exceptions.AlreadyFinalized):
+            verify_ctx.verify()
+
+    def test_streaming_invalid_algorithm(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        private_key = ec.generate_private_key(ec.SECP256R1())
+        public_key = private_key.public_key()
+        with pytest.raises(TypeError):
+            private_key.sign_init(ec.ECDSA(Prehashed(hashes.SHA256())))
+        with pytest.raises(TypeError):
+            public_key.verify_init(b"", ec.ECDSA(Prehashed(hashes.SHA256())))
+        with raises_unsupported_algorithm(
+            exceptions._Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+        ):
+            private_key.sign_init(
+                DummySignatureAlgorithm()  # type: ignore[arg-type]
+            )
+        with raises_unsupported_algorithm(
+            exceptions._Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+        ):
+            public_key.verify_init(
+                b"", DummySignatureAlgorithm()  # type: ignore[arg-type]
+            )
+
+    def test_p1363_signatures(self, backend, subtests):
+        vectors = load_vectors_from_file(
+            os.path.join("asymmetric", "ECDSA", "FIPS_186-3", "SigGen.txt"),
+            load_fips_ecdsa_signing_vectors,
+        )
+        for vector in vectors:
+            with subtests.test():
+                hash_type = _HASH_TYPES[vector["digest_algorithm"]]
+                curve = ec._CURVE_TYPES[vector["curve"]]
+
+                _skip_ecdsa_vector(backend, curve, hash_type)
+
+                key = ec.EllipticCurvePublicNumbers(
+                    vector["x"], vector["y"], curve
+                ).public_key(backend)
+                size = (curve.key_size + 7) // 8
+                r = vector["r"].to_bytes(size, "big")
+                s = vector["s"].to_bytes(size, "big")
+                signature = r + s
+                algorithm = ec.ECDSA(
+                    hash_type(),
+                    signature_format=ec.ECDSASignatureFormat.P1363,
+                )
+                key.verify(signature, vector["message"], algorithm)
+                with pytest.raises(exceptions.InvalidSignature):
+                    key.verify(signature[:-1], vector["message"], algorithm)
+
+    @pytest.mark.parametrize(
+        ("hash_algorithm", "hashlib_name"),
+        [
+            (hashes.SHAKE128(digest_size=32), "shake_128"),
+            (hashes.SHAKE256(digest_size=64), "shake_256"),
+            (hashes.SHAKE256(digest_size=16), "shake_256"),
+        ],
+    )
+    def test_sign_verify_shake(self, hash_algorithm, hashlib_name, backend):
+        _skip_curve_unsupported(backend, ec.SECP384R1())
+        if not backend.hash_supported(hash_algorithm):
+            pytest.skip(f"{hash_algorithm.name} is not supported")
+
+        message = b"one little message"
+        private_key = ec.generate_private_key(ec.SECP384R1())
+        public_key = private_key.public_key()
+        algorithm = ec.ECDSA(hash_algorithm)
+        signature = private_key.sign(message, algorithm)
+        public_key.verify(signature, message, algorithm)
+
+        digest = getattr(hashlib, hashlib_name)(message).digest(
+            hash_algorithm.digest_size
+        )
+        public_key.verify(
+            signature, digest, ec.ECDSA(Prehashed(hash_algorithm))
+        )
+        with pytest.raises(exceptions.InvalidSignature):
+            public_key.verify(signature, message + b"\x00", algorithm)
+
+        ctx = private_key.sign_init(algorithm)
+        ctx.update(message)
+        public_key.verify(ctx.finalize(), message, algorithm)
+
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.ecdsa_deterministic_supported(),
+        skip_message="Requires OpenSSL with deterministic ECDSA support",
+    )
+    def test_deterministic_signing_shake(self, backend):
+        with pytest.raises(ValueError):
+            ec.ECDSA(hashes.SHAKE256(64), deterministic_signing=True)
+        with pytest.raises(ValueError):
+            ec.ECDSA(
+                Prehashed(hashes.SHAKE128(32)), deterministic_signing=True
+            )
+
+    def test_sign_p1363(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP521R1())
+        message = b"one little message"
+        algorithm = ec.ECDSA(
+            hashes.SHA256(), signature_format=ec.ECDSASignatureFormat.P1363
+        )
+        assert algorithm.signature_format is ec.ECDSASignatureFormat.P1363
+        private_key = ec.generate_private_key(ec.SECP521R1(), backend)
+        public_key = private_key.public_key()
+        signature = private_key.sign(message, algorithm)
+        assert len(signature) == 132
+        public_key.verify(signature, message, algorithm)
+        public_key.verify(
+            raw_to_der_signature(signature, 66),
+            message,
+            ec.ECDSA(hashes.SHA256()),
+        )
+        with pytest.raises(exceptions.InvalidSignature):
+            public_key.verify(
+                raw_to_der_signature(signature, 66), message, algorithm
+            )
+
+    def test_invalid_signature_format(self):
+        with pytest.raises(TypeError):
+            ec.ECDSA(
+                hashes.SHA256(),
+                signature_format="P1363",  # type: ignore[arg-type]
+            )
+
+    def test_sign_low_s(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        message = b"one little message"
+        algorithm = ec.ECDSA(hashes.SHA256(), low_s=True)
+        assert algorithm.low_s is True
+        private_key = ec.generate_private_key(ec.SECP256R1(), backend)
+        public_key = private_key.public_key()
+        for _ in range(20):
+            signature = private_key.sign(message, algorithm)
+            _, s = decode_dss_signature(signature)
+            assert s <= _SECP256R1_ORDER // 2
+            public_key.verify(signature, message, algorithm)
+
+    def test_normalize_signature(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        message = b"one little message"
+        algorithm = ec.ECDSA(hashes.SHA256())
+        private_key = ec.generate_private_key(ec.SECP256R1(), backend)
+        public_key = private_key.public_key()
+        r, s = decode_dss_signature(private_key.sign(message, algorithm))
+        low_s = min(s, _SECP256R1_ORDER - s)
+        high_s = max(s, _SECP256R1_ORDER - s)
+        low = encode_dss_signature(r, low_s)
+        high = encode_dss_signature(r, high_s)
+        public_key.verify(high, message, algorithm)
+
+        assert ec.normalize_signature(high, ec.SECP256R1()) == low
+        assert ec.normalize_signature(low, ec.SECP256R1()) == low
+        public_key.verify(low, message, algorithm)
+
+        raw_high = der_to_raw_signature(high, 32)
+        raw_low = ec.normalize_signature(
+            raw_high, ec.SECP256R1(), ec.ECDSASignatureFormat.P1363
+        )
+        assert raw_low == der_to_raw_signature(low, 32)
+
+    def test_normalize_signature_invalid(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        with pytest.raises(ValueError):
+            ec.normalize_signature(
+                encode_dss_signature(1, _SECP256R1_ORDER), ec.SECP256R1()
+            )
+        with pytest.raises(ValueError):
+            ec.normalize_signature(b"\x30\x00", ec.SECP256R1())
+        with pytest.raises(ValueError):
+            ec.normalize_signature(
+                b"\x01" * 63, ec.SECP256R1(), ec.ECDSASignatureFormat.P1363
+            )
+        with pytest.raises(TypeError):
+            ec.normalize_signature(
+                encode_dss_signature(1, 1),
+                ec.SECP256R1(),
+                "DER",  # type: ignore[arg-type]
+            )
+
+    @pytest.mark.parametrize(
+        "curve", [ec.SECP256K1(), ec.SECP256R1(), ec.SECP521R1()]
+    )
+    @pytest.mark.parametrize(
+        "signature_format",
+        [ec.ECDSASignatureFormat.DER, ec.ECDSASignatureFormat.P1363],
+    )
+    def test_recover_public_key(self, backend, curve, signature_format):
+        _skip_curve_unsupported(backend, curve)
+        key = ec.generate_private_key(curve)
+        algorithm = ec.ECDSA(
+            hashes.SHA256(), signature_format=signature_format
+        )
+        data = b"recover me"
+        signature = key.sign(data, algorithm)
+
+        recovered = []
+        for recovery_id in range(4):
+            try:
+                public_key = ec.recover_public_key(
+                    curve, signature, data, algorithm, recovery_id
+                )
+            except ValueError:
+                continue
+            assert isinstance(public_key, ec.EllipticCurvePublicKey)
+            assert public_key.curve.name == curve.name
+            public_key.verify(signature, data, algorithm)
+            recovered.append(public_key)
+
+        assert key.public_key() in recovered
+
+    def test_recover_public_key_prehashed(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        key = ec.generate_private_key(ec.SECP256R1())
+        digest = hashes.Hash(hashes.SHA512())
+        digest.update(b"some data")
+        data = digest.finalize()
+        algorithm = ec.ECDSA(Prehashed(hashes.SHA512()))
+        signature = key.sign(data, algorithm)
+
+        recovered = []
+        for recovery_id in range(2):
+            recovered.append(
+                ec.recover_public_key(
+                    ec.SECP256R1(), signature, data, algorithm, recovery_id
+                )
+            )
+        assert key.public_key() in recovered
+
+    def test_recover_public_key_invalid(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        algorithm = ec.ECDSA(hashes.SHA256())
+        key = ec.generate_private_key(ec.SECP256R1())
+        signature = key.sign(b"data", algorithm)
+
+        with pytest.raises(ValueError):
+            ec.recover_public_key(
+                ec.SECP256R1(), signature, b"data", algorithm, 4
+            )
+        for r, s in [(0, 1), (1, 0), (_SECP256R1_ORDER, 1)]:
+            with pytest.raises(ValueError):
+                ec.recover_public_key(
+                    ec.SECP256R1(),
+                    encode_dss_signature(r, s),
+                    b"data",
+                    algorithm,
+                    0,
+                )
+        with pytest.raises(ValueError):
+            ec.recover_public_key(
+                ec.SECP256R1(),
+                b"\x01" * 63,
+                b"data",
+                ec.ECDSA(
+                    hashes.SHA256(),
+                    signature_format=ec.ECDSASignatureFormat.P1363,
+                ),
+                0,
+            )
+        # r + n is larger than the field prime for P-256.
+        with pytest.raises(ValueError):
+            ec.recover_public_key(
+                ec.SECP256R1(), signature, b"data", algorithm, 2
+            )
+        with raises_unsupported_algorithm(
+            exceptions._Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+        ):
+            ec.recover_public_key(
+                ec.SECP256R1(),
+                signature,
+                b"data",
+                DummySignatureAlgorithm(),
+                0,
+            )
+        with pytest.raises(TypeError):
+            ec.recover_public_key(
+                "secp256r1",  # type: ignore[arg-type]
+                signature,
+                b"data",
+                algorithm,
+                0,
+            )
+
+    def test_recover_public_key_binary_curve(self, backend):
+        _skip_curve_unsupported(backend, ec.SECT233K1())
+        key = ec.generate_private_key(ec.SECT233K1())
+        algorithm = ec.ECDSA(hashes.SHA256())
+        signature = key.sign(b"data", algorithm)
+        with raises_unsupported_algorithm(
+            exceptions._Reasons.UNSUPPORTED_ELLIPTIC_CURVE
+        ):
+            ec.recover_public_key(
+                ec.SECT233K1(), signature, b"data", algorithm, 0
+            )
+
     def test_sign_prehashed_digest_mismatch(self, backend):
         _skip_curve_unsupported(backend, ec.SECP256R1())
         message = b"one little message"
@@ -625,6 +1296,34 @@ class TestECEquality:
         with pytest.raises(TypeError):
             key1 < key2  # type: ignore[operator]
 
+    def test_private_key_equality(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        key_bytes = load_vectors_from_file(
+            os.path.join("asymmetric", "PKCS8", "ec_private_key.pem"),
+            lambda pemfile: pemfile.read().encode(),
+        )
+        key1 = serialization.load_pem_private_key(key_bytes, None)
+        key2 = serialization.load_pem_private_key(key_bytes, None)
+        key3 = ec.generate_private_key(ec.SECP256R1())
+        assert key1 == key2
+        assert hash(key1) == hash(key2)
+        assert key1 != key3
+        assert key1 != key1.public_key()
+        assert key1 != object()
+        with pytest.raises(TypeError):
+            key1 < key2  # type: ignore[operator]
+
+    def test_public_key_matches(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        key = ec.generate_private_key(ec.SECP256R1())
+        assert key.public_key_matches(key.public_key())
+        assert not key.public_key_matches(
+            ec.generate_private_key(ec.SECP256R1()).public_key()
+        )
+        assert not key.public_key_matches(
+            ec.generate_private_key(ec.SECP384R1()).public_key()
+        )
+
     def test_public_key_copy(self, backend):
         _skip_curve_unsupported(backend, ec.SECP256R1())
         key_bytes = load_vectors_from_file(
@@ -636,6 +1335,25 @@ class TestECEquality:
 
         assert key1 == key2
 
+    def test_public_key_hash(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        key1 = ec.generate_private_key(ec.SECP256R1()).public_key()
+        key2 = key1.public_numbers().public_key()
+        key3 = ec.generate_private_key(ec.SECP256R1()).public_key()
+
+        assert hash(key1) == hash(key2)
+        assert hash(key1) != hash(key3)
+        assert len({key1, key2, key3}) == 2
+
+    def test_public_key_pickle(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        key = ec.generate_private_key(ec.SECP256R1()).public_key()
+        loaded = pickle.loads(pickle.dumps(key))
+        assert isinstance(loaded, ec.EllipticCurvePublicKey)
+        assert isinstance(loaded.curve, ec.SECP256R1)
+        assert loaded == key
+        assert hash(loaded) == hash(key)
+
 
 class TestECSerialization:
     @pytest.mark.parametrize(
@@ -1300,6 +2018,116 @@ class TestECDH:
         shared_secret_2 = peer.exchange(ec.ECDH(), key.public_key())
         assert shared_secret_2 == binascii.unhexlify(vector["x_z"])
 
+    def test_cofactor_exchange(self, backend):
+        # sect233k1 has a cofactor of 4. Generated with OpenSSL's
+        # ecdh_cofactor_mode option.
+        curve = ec.SECT233K1()
+        _skip_exchange_algorithm_unsupported(backend, ec.ECDH(), curve)
+        key = ec.derive_private_key(
+            int(
+                "2a1231aa9a6d13e25868c86ebd8719b2eb5152458c760728269e609945",
+                16,
+            ),
+            curve,
+        )
+        peer = ec.EllipticCurvePublicKey.from_encoded_point(
+            curve,
+            binascii.unhexlify(
+                "0400e472761a902285fd55f42a6176e5ad57f2c1cc6672841a083a2c"
+                "5eb77f001c4ac09cead2b7b8fc5045a68543510ef1ebcc8c36350802"
+                "1b35e650b1"
+            ),
+        )
+        algorithm = ec.ECDH(cofactor=True)
+        assert algorithm.cofactor is True
+        assert key.exchange(algorithm, peer) == binascii.unhexlify(
+            "0082cfab9d65ad2655b34aff3e0cc52b41d052406fa73ea70ea5dc637be2"
+        )
+        assert key.exchange(ec.ECDH(), peer) == binascii.unhexlify(
+            "01edb8a3e54040b3e2d719bfa64078e82acc80b64334bf82ba0c88be7870"
+        )
+
+    def test_cofactor_exchange_cofactor_one(self, backend):
+        _skip_exchange_algorithm_unsupported(
+            backend, ec.ECDH(), ec.SECP256R1()
+        )
+        key = ec.generate_private_key(ec.SECP256R1())
+        peer = ec.generate_private_key(ec.SECP256R1()).public_key()
+        assert key.exchange(ec.ECDH(cofactor=True), peer) == key.exchange(
+            ec.ECDH(), peer
+        )
+
+    def test_cofactor_exchange_non_matching_curve(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        _skip_curve_unsupported(backend, ec.SECP384R1())
+        key = ec.generate_private_key(ec.SECP256R1())
+        public_key = EC_KEY_SECP384R1.public_numbers.public_key(backend)
+        with pytest.raises(ValueError):
+            key.exchange(ec.ECDH(cofactor=True), public_key)
+
+    @pytest.mark.parametrize(
+        "curve", [ec.SECP256R1(), ec.SECP384R1(), ec.SECP521R1()]
+    )
+    def test_full_point_exchange(self, backend, curve):
+        _skip_exchange_algorithm_unsupported(backend, ec.ECDH(), curve)
+        key = ec.generate_private_key(curve)
+        peer = ec.generate_private_key(curve).public_key()
+        algorithm = ec.ECDH(full_point=True)
+        assert algorithm.full_point is True
+        assert ec.ECDH().full_point is False
+
+        shared = key.exchange(algorithm, peer)
+        point = ec.ECPoint.from_public_key(peer) * (
+            key.private_numbers().private_value
+        )
+        assert shared == point.encode()[1:]
+        assert shared[: len(shared) // 2] == key.exchange(ec.ECDH(), peer)
+
+    def test_full_point_cofactor_exchange(self, backend):
+        _skip_exchange_algorithm_unsupported(
+            backend, ec.ECDH(), ec.SECT233K1()
+        )
+        key = ec.generate_private_key(ec.SECT233K1())
+        peer = ec.generate_private_key(ec.SECT233K1()).public_key()
+        shared = key.exchange(ec.ECDH(cofactor=True, full_point=True), peer)
+        assert len(shared) == 60
+        assert shared[:30] == key.exchange(ec.ECDH(cofactor=True), peer)
+
+    @pytest.mark.parametrize("cofactor", [False, True])
+    def test_exchange_many(self, backend, cofactor):
+        _skip_exchange_algorithm_unsupported(
+            backend, ec.ECDH(), ec.SECP256R1()
+        )
+        key = ec.generate_private_key(ec.SECP256R1())
+        peers = [
+            ec.generate_private_key(ec.SECP256R1()).public_key()
+            for _ in range(5)
+        ]
+        algorithm = ec.ECDH(cofactor=cofactor)
+        assert key.exchange_many(algorithm, peers) == [
+            key.exchange(algorithm, peer) for peer in peers
+        ]
+        assert key.exchange_many(algorithm, tuple(peers)) == [
+            key.exchange(algorithm, peer) for peer in peers
+        ]
+        assert key.exchange_many(algorithm, []) == []
+
+    def test_exchange_many_errors(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        _skip_curve_unsupported(backend, ec.SECP384R1())
+        key = ec.generate_private_key(ec.SECP256R1())
+        peer = ec.generate_private_key(ec.SECP256R1()).public_key()
+        with pytest.raises(ValueError):
+            key.exchange_many(
+                ec.ECDH(), [peer, EC_KEY_SECP384R1.public_numbers.public_key()]
+            )
+        with pytest.raises(TypeError):
+            key.exchange_many(ec.ECDH(), [peer, b"not a key"])
+        with raises_unsupported_algorithm(
+            exceptions._Reasons.UNSUPPORTED_EXCHANGE_ALGORITHM
+        ):
+            key.exchange_many(None, [peer])  # type: ignore[arg-type]
+
     def test_exchange_unsupported_algorithm(self, backend):
         _skip_curve_unsupported(backend, ec.SECP256R1())
 
@@ -1331,3 +2159,368 @@ class TestECDH:
 
         with pytest.raises(ValueError):
             key.exchange(ec.ECDH(), public_key)
+
+    @pytest.mark.parametrize(
+        "make_kdf",
+        [
+            lambda: HKDF(hashes.SHA256(), 100, None, b"info"),
+            lambda: HKDF(hashes.SHA384(), 32, b"salt", b""),
+            lambda: X963KDF(hashes.SHA256(), 100, b"sharedinfo"),
+            lambda: X963KDF(hashes.SHA512(), 16, None),
+            lambda: ConcatKDFHash(hashes.SHA256(), 100, b"otherinfo"),
+            lambda: ConcatKDFHMAC(hashes.SHA256(), 100, b"salt", b"other"),
+            lambda: ConcatKDFHMAC(hashes.SHA512(), 32, None, None),
+        ],
+    )
+    @pytest.mark.parametrize("cofactor", [False, True])
+    def test_exchange_and_derive(self, make_kdf, cofactor, backend):
+        _skip_exchange_algorithm_unsupported(
+            backend, ec.ECDH(), ec.SECP256R1()
+        )
+        key = ec.generate_private_key(ec.SECP256R1())
+        peer = ec.generate_private_key(ec.SECP256R1()).public_key()
+        algorithm = ec.ECDH(cofactor=cofactor)
+
+        expected = make_kdf().derive(key.exchange(algorithm, peer))
+        kdf = make_kdf()
+        assert key.exchange_and_derive(algorithm, peer, kdf) == expected
+        with pytest.raises(exceptions.AlreadyFinalized):
+            kdf.derive(b"\x00" * 32)
+        with pytest.raises(exceptions.AlreadyFinalized):
+            key.exchange_and_derive(algorithm, peer, kdf)
+
+    def test_exchange_and_derive_used_kdf(self, backend):
+        _skip_exchange_algorithm_unsupported(
+            backend, ec.ECDH(), ec.SECP256R1()
+        )
+        key = ec.generate_private_key(ec.SECP256R1())
+        kdf = X963KDF(hashes.SHA256(), 32, None)
+        kdf.derive(b"\x00" * 32)
+        with pytest.raises(exceptions.AlreadyFinalized):
+            key.exchange_and_derive(ec.ECDH(), key.public_key(), kdf)
+
+    def test_exchange_and_derive_invalid(self, backend):
+        _skip_exchange_algorithm_unsupported(
+            backend, ec.ECDH(), ec.SECP256R1()
+        )
+        _skip_curve_unsupported(backend, ec.SECP384R1())
+        key = ec.generate_private_key(ec.SECP256R1())
+        kdf = HKDF(hashes.SHA256(), 32, None, None)
+
+        with pytest.raises(TypeError):
+            key.exchange_and_derive(
+                ec.ECDH(),
+                key.public_key(),
+                PBKDF2HMAC(hashes.SHA256(), 32, b"salt", 1),
+            )
+        with raises_unsupported_algorithm(
+            exceptions._Reasons.UNSUPPORTED_EXCHANGE_ALGORITHM
+        ):
+            key.exchange_and_derive(
+                None,  # type: ignore[arg-type]
+                key.public_key(),
+                kdf,
+            )
+        public_key = EC_KEY_SECP384R1.public_numbers.public_key(backend)
+        with pytest.raises(ValueError):
+            key.exchange_and_derive(ec.ECDH(), public_key, kdf)
+
+
+class TestECPoint:
+    def test_generator(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        g = ec.ECPoint.generator(ec.SECP256R1())
+        assert isinstance(g.curve, ec.SECP256R1)
+        assert not g.is_infinity
+        assert g.x == int(
+            "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+            16,
+        )
+        assert g.y == int(
+            "4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5",
+            16,
+        )
+        assert repr(g) == f"<ECPoint(curve=secp256r1, x={g.x}, y={g.y})>"
+
+    def test_arithmetic(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        g = ec.ECPoint.generator(ec.SECP256R1())
+        infinity = ec.ECPoint.infinity(ec.SECP256R1())
+        assert infinity.is_infinity
+        assert g + g == 2 * g == g * 2
+        assert 3 * g - g == g + g
+        assert g + infinity == g
+        assert (g - g).is_infinity
+        assert (-g + g).is_infinity
+        assert (-1) * g == -g
+        assert (_SECP256R1_ORDER * g).is_infinity
+        assert (_SECP256R1_ORDER + 5) * g == 5 * g
+        assert 0 * g == infinity
+        assert g != 2 * g
+
+        a, b = 0x1234, 0x5678
+        assert a * (b * g) == b * (a * g) == (a * b) * g
+
+    def test_scalar_multiply_matches_keys(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP384R1())
+        key = ec.generate_private_key(ec.SECP384R1())
+        scalar = key.private_numbers().private_value
+        point = scalar * ec.ECPoint.generator(ec.SECP384R1())
+        assert point == ec.ECPoint.from_public_key(key.public_key())
+        assert point.public_key() == key.public_key()
+        numbers = key.public_key().public_numbers()
+        assert (point.x, point.y) == (numbers.x, numbers.y)
+
+    def test_encoding(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        key = ec.generate_private_key(ec.SECP256R1())
+        public_key = key.public_key()
+        point = ec.ECPoint.from_public_key(public_key)
+
+        uncompressed = point.encode()
+        compressed = point.encode(compressed=True)
+        assert uncompressed == public_key.public_bytes(
+            serialization.Encoding.X962,
+            serialization.PublicFormat.UncompressedPoint,
+        )
+        assert compressed == public_key.public_bytes(
+            serialization.Encoding.X962,
+            serialization.PublicFormat.CompressedPoint,
+        )
+        decompressed = ec.ECPoint.from_encoded_point(
+            ec.SECP256R1(), compressed
+        )
+        assert decompressed == point
+        assert decompressed.encode() == uncompressed
+
+        infinity = ec.ECPoint.infinity(ec.SECP256R1())
+        assert infinity.encode() == b"\x00"
+        decoded = ec.ECPoint.from_encoded_point(ec.SECP256R1(), b"\x00")
+        assert decoded.is_infinity
+
+    def test_from_coordinates(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        g = ec.ECPoint.generator(ec.SECP256R1())
+        assert ec.ECPoint.from_coordinates(ec.SECP256R1(), g.x, g.y) == g
+        with pytest.raises(ValueError):
+            ec.ECPoint.from_coordinates(ec.SECP256R1(), g.x, g.y + 1)
+
+    def test_invalid_encoded_point(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        point = ec.ECPoint.generator(ec.SECP256R1()).encode()
+        with pytest.raises(ValueError):
+            ec.ECPoint.from_encoded_point(
+                ec.SECP256R1(), point[:-1] + bytes([point[-1] ^ 1])
+            )
+        with pytest.raises(ValueError):
+            ec.ECPoint.from_encoded_point(ec.SECP256R1(), point[:-1])
+
+    def test_infinity(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        infinity = ec.ECPoint.infinity(ec.SECP256R1())
+        with pytest.raises(ValueError):
+            infinity.x
+        with pytest.raises(ValueError):
+            infinity.y
+        with pytest.raises(ValueError):
+            infinity.public_key()
+        assert repr(infinity) == "<ECPoint(curve=secp256r1, infinity)>"
+
+    def test_mismatched_curves(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        _skip_curve_unsupported(backend, ec.SECP384R1())
+        p256 = ec.ECPoint.generator(ec.SECP256R1())
+        p384 = ec.ECPoint.generator(ec.SECP384R1())
+        assert p256 != p384
+        with pytest.raises(ValueError):
+            p256 + p384
+        with pytest.raises(ValueError):
+            p256 - p384
+
+    def test_unsupported_operands(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        g = ec.ECPoint.generator(ec.SECP256R1())
+        assert g != object()
+        with pytest.raises(TypeError):
+            g + 1  # type: ignore[operator]
+        with pytest.raises(TypeError):
+            g * 1.5  # type: ignore[operator]
+
+
+class TestEllipticCurveParameters:
+    # The output of `openssl ecparam -name prime256v1`.
+    PEM = (
+        b"-----BEGIN EC PARAMETERS-----\n"
+        b"BggqhkjOPQMBBw==\n"
+        b"-----END EC PARAMETERS-----\n"
+    )
+    DER = b"\x06\x08*\x86H\xce=\x03\x01\x07"
+
+    def test_load(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        for parameters in [
+            serialization.load_pem_parameters(self.PEM),
+            serialization.load_der_parameters(self.DER),
+        ]:
+            assert isinstance(parameters, ec.EllipticCurveParameters)
+            assert isinstance(parameters.curve, ec.SECP256R1)
+            assert parameters == ec.EllipticCurveParameters(ec.SECP256R1())
+            assert repr(parameters) == (
+                "<EllipticCurveParameters(curve=secp256r1)>"
+            )
+
+    def test_parameter_bytes(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        parameters = ec.EllipticCurveParameters(ec.SECP256R1())
+        fmt = serialization.ParameterFormat.ECParameters
+        assert (
+            parameters.parameter_bytes(serialization.Encoding.PEM, fmt)
+            == self.PEM
+        )
+        assert (
+            parameters.parameter_bytes(serialization.Encoding.DER, fmt)
+            == self.DER
+        )
+        with pytest.raises(ValueError):
+            parameters.parameter_bytes(
+                serialization.Encoding.DER,
+                serialization.ParameterFormat.PKCS3,
+            )
+        with pytest.raises(TypeError):
+            parameters.parameter_bytes(serialization.Encoding.Raw, fmt)
+
+    @pytest.mark.parametrize(
+        "curve", [ec.SECP384R1(), ec.SECP521R1(), ec.SECP256K1()]
+    )
+    def test_round_trip(self, backend, curve):
+        _skip_curve_unsupported(backend, curve)
+        parameters = ec.EllipticCurveParameters(curve)
+        for encoding, load in [
+            (serialization.Encoding.PEM, serialization.load_pem_parameters),
+            (serialization.Encoding.DER, serialization.load_der_parameters),
+        ]:
+            data = parameters.parameter_bytes(
+                encoding, serialization.ParameterFormat.ECParameters
+            )
+            loaded = load(data)
+            assert loaded == parameters
+            assert hash(loaded) == hash(parameters)
+
+    def test_generate_private_key(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        parameters = serialization.load_pem_parameters(self.PEM)
+        assert isinstance(parameters, ec.EllipticCurveParameters)
+        key = ec.generate_private_key(parameters)
+        assert isinstance(key.curve, ec.SECP256R1)
+        key = parameters.generate_private_key()
+        assert isinstance(key.curve, ec.SECP256R1)
+
+    def test_invalid(self, backend):
+        with pytest.raises(TypeError):
+            ec.EllipticCurveParameters(object())  # type: ignore[arg-type]
+        # An unknown curve OID.
+        with raises_unsupported_algorithm(
+            exceptions._Reasons.UNSUPPORTED_ELLIPTIC_CURVE
+        ):
+            serialization.load_der_parameters(b"\x06\x02\x2a\x03")
+        # The implicitCurve form.
+        with pytest.raises(ValueError):
+            serialization.load_pem_parameters(
+                b"-----BEGIN EC PARAMETERS-----\n"
+                b"BQA=\n"
+                b"-----END EC PARAMETERS-----\n"
+            )
+
+
+class TestBIP340Schnorr:
+    def test_vectors(self, backend, subtests):
+        _skip_curve_unsupported(backend, ec.SECP256K1())
+        vectors = load_vectors_from_file(
+            os.path.join("asymmetric", "Schnorr", "bip340.csv"),
+            lambda f: list(csv.DictReader(f)),
+        )
+        for vector in vectors:
+            with subtests.test():
+                x_only = binascii.unhexlify(vector["public key"])
+                message = binascii.unhexlify(vector["message"])
+                signature = binascii.unhexlify(vector["signature"])
+                valid = vector["verification result"] == "TRUE"
+
+                if vector["secret key"]:
+                    key = ec.derive_private_key(
+                        int(vector["secret key"], 16), ec.SECP256K1()
+                    )
+                    public_numbers = key.public_key().public_numbers()
+                    assert public_numbers.x.to_bytes(32, "big") == x_only
+                    algorithm = ec.BIP340Schnorr(
+                        binascii.unhexlify(vector["aux_rand"])
+                    )
+                    assert key.sign(message, algorithm) == signature
+
+                try:
+                    public_key = ec.EllipticCurvePublicKey.from_encoded_point(
+                        ec.SECP256K1(), b"\x02" + x_only
+                    )
+                except ValueError:
+                    assert not valid
+                    continue
+
+                if valid:
+                    public_key.verify(signature, message, ec.BIP340Schnorr())
+                else:
+                    with pytest.raises(exceptions.InvalidSignature):
+                        public_key.verify(
+                            signature, message, ec.BIP340Schnorr()
+                        )
+
+    def test_sign_verify(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256K1())
+        key = ec.generate_private_key(ec.SECP256K1())
+        signature = key.sign(b"data", ec.BIP340Schnorr())
+        assert len(signature) == 64
+        assert signature != key.sign(b"data", ec.BIP340Schnorr())
+        key.public_key().verify(signature, b"data", ec.BIP340Schnorr())
+        with pytest.raises(exceptions.InvalidSignature):
+            key.public_key().verify(signature, b"other", ec.BIP340Schnorr())
+        with pytest.raises(exceptions.InvalidSignature):
+            key.public_key().verify(
+                signature[:-1], b"data", ec.BIP340Schnorr()
+            )
+
+    def test_odd_y_public_key(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256K1())
+        # The public key for this private value has an odd y coordinate. Only
+        # its x coordinate is used, so the key with the even y coordinate
+        # verifies the same signatures.
+        key = ec.derive_private_key(6, ec.SECP256K1())
+        public_numbers = key.public_key().public_numbers()
+        assert public_numbers.y % 2 == 1
+        signature = key.sign(b"data", ec.BIP340Schnorr())
+        key.public_key().verify(signature, b"data", ec.BIP340Schnorr())
+        even_key = ec.EllipticCurvePublicKey.from_encoded_point(
+            ec.SECP256K1(), b"\x02" + public_numbers.x.to_bytes(32, "big")
+        )
+        even_key.verify(signature, b"data", ec.BIP340Schnorr())
+
+    def test_unsupported_curve(self, backend):
+        _skip_curve_unsupported(backend, ec.SECP256R1())
+        key = ec.generate_private_key(ec.SECP256R1())
+        with raises_unsupported_algorithm(
+            exceptions._Reasons.UNSUPPORTED_ELLIPTIC_CURVE
+        ):
+            key.sign(b"data", ec.BIP340Schnorr())
+        with raises_unsupported_algorithm(
+            exceptions._Reasons.UNSUPPORTED_ELLIPTIC_CURVE
+        ):
+            key.public_key().verify(
+                b"\x00" * 64, b"data", ec.BIP340Schnorr()
+            )
+
+    def test_aux_rand(self):
+        assert ec.BIP340Schnorr().aux_rand is None
+        assert ec.BIP340Schnorr(b"\x00" * 32).aux_rand == b"\x00" * 32
+        assert isinstance(ec.BIP340Schnorr().algorithm, hashes.SHA256)
+        with pytest.raises(ValueError):
+            ec.BIP340Schnorr(b"\x00" * 31)
+        with pytest.raises(TypeError):
+            ec.BIP340Schnorr("0" * 32)  # type: ignore[arg-type]
@@ -0,0 +1,157 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+
+import binascii
+
+import pytest
+
+from cryptography.exceptions import InvalidSignature, _Reasons
+from cryptography.hazmat.primitives.asymmetric import ec, ecvrf, ed25519
+
+from ...utils import raises_unsupported_algorithm
+
+
+def _p256_key() -> ec.EllipticCurvePrivateKey:
+    return ec.derive_private_key(
+        0xC9AFA9D845BA75166B5C215767B1D6934E50C3DB36E89B127B8A622B120F6721,
+        ec.SECP256R1(),
+    )
+
+
+def _ed25519_key() -> ed25519.Ed25519PrivateKey:
+    return ed25519.Ed25519PrivateKey.from_private_bytes(
+        binascii.unhexlify(
+            "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60"
+        )
+    )
+
+
+class TestECVRF:
+    def test_p256_vector(self, backend):
+        # RFC 9381 appendix B.1, example 10.
+        proof = ecvrf.prove(_p256_key(), b"sample")
+        assert proof == binascii.unhexlify(
+            "035b5c726e8c0e2c488a107c600578ee75cb702343c153cb1eb8dec77f4b5071"
+            "b4a53f0a46f018bc2c56e58d383f2305e0975972c26feea0eb122fe7893c15af"
+            "376b33edf7de17c6ea056d4d82de6bc02f"
+        )
+        beta = binascii.unhexlify(
+            "a3ad7b0ef73d8fc6655053ea22f9bede8c743f08bbed3d38821f0e16474b505e"
+        )
+        assert ecvrf.verify(_p256_key().public_key(), proof, b"sample") == beta
+        assert (
+            ecvrf.proof_to_hash(proof, ecvrf.ECVRFSuite.P256_SHA256_TAI)
+            == beta
+        )
+
+    def test_ed25519_vector(self, backend):
+        # RFC 9381 appendix B.3, example 16.
+        proof = ecvrf.prove(_ed25519_key(), b"")
+        assert proof == binascii.unhexlify(
+            "8657106690b5526245a92b003bb079ccd1a92130477671f6fc01ad16f26f723f"
+            "26f8a57ccaed74ee1b190bed1f479d9727d2d0f9b005a6e456a35d4fb0daab12"
+            "68a1b0db10836d9826a528ca76567805"
+        )
+        beta = binascii.unhexlify(
+            "90cf1df3b703cce59e2a35b925d411164068269d7b2d29f3301c03dd757876ff"
+            "66b71dda49d2de59d03450451af026798e8f81cd2e333de5cdf4f3e140fdd8ae"
+        )
+        assert ecvrf.verify(_ed25519_key().public_key(), proof, b"") == beta
+        assert (
+            ecvrf.proof_to_hash(
+                proof, ecvrf.ECVRFSuite.EDWARDS25519_SHA512_TAI
+            )
+            == beta
+        )
+
+    @pytest.mark.parametrize(
+        "generate",
+        [
+            lambda: ec.generate_private_key(ec.SECP256R1()),
+            ed25519.Ed25519PrivateKey.generate,
+        ],
+    )
+    def test_prove_verify(self, generate, backend):
+        private_key = generate()
+        public_key = private_key.public_key()
+        proof = ecvrf.prove(private_key, b"input")
+        assert ecvrf.prove(private_key, b"input") == proof
+        beta = ecvrf.verify(public_key, proof, b"input")
+        other_proof = ecvrf.prove(private_key, b"other input")
+        assert ecvrf.verify(public_key, other_proof, b"other input") != beta
+
+        with pytest.raises(InvalidSignature):
+            ecvrf.verify(public_key, proof, b"other input")
+        with pytest.raises(InvalidSignature):
+            ecvrf.verify(public_key, proof[:-1], b"input")
+        with pytest.raises(InvalidSignature):
+            ecvrf.verify(public_key, proof + b"\x00", b"input")
+        for i in [0, len(proof) - 40, len(proof) - 1]:
+            tampered = bytearray(proof)
+            tampered[i] ^= 0x01
+            with pytest.raises(InvalidSignature):
+                ecvrf.verify(public_key, bytes(tampered), b"input")
+
+    def test_wrong_key(self, backend):
+        proof = ecvrf.prove(_p256_key(), b"input")
+        with pytest.raises(InvalidSignature):
+            ecvrf.verify(
+                ec.generate_private_key(ec.SECP256R1()).public_key(),
+                proof,
+                b"input",
+            )
+        proof = ecvrf.prove(_ed25519_key(), b"input")
+        with pytest.raises(InvalidSignature):
+            ecvrf.verify(
+                ed25519.Ed25519PrivateKey.generate().public_key(),
+                proof,
+                b"input",
+            )
+
+    def test_small_order_public_key(self, backend):
+        # The identity point, which has order 1.
+        public_key = ed25519.Ed25519PublicKey.from_public_bytes(
+            b"\x01" + b"\x00" * 31
+        )
+        proof = ecvrf.prove(_ed25519_key(), b"input")
+        with pytest.raises(InvalidSignature):
+            ecvrf.verify(public_key, proof, b"input")
+
+    def test_unsupported_curve(self, backend):
+        key = ec.generate_private_key(ec.SECP384R1())
+        with raises_unsupported_algorithm(
+            _Reasons.UNSUPPORTED_ELLIPTIC_CURVE
+        ):
+            ecvrf.prove(key, b"input")
+        with raises_unsupported_algorithm(
+            _Reasons.UNSUPPORTED_ELLIPTIC_CURVE
+        ):
+            ecvrf.verify(key.public_key(), b"\x00" * 81, b"input")
+
+    def test_invalid_types(self, backend):
+        with pytest.raises(TypeError):
+            ecvrf.prove(object(), b"input")  # type: ignore[arg-type]
+        with pytest.raises(TypeError):
+            ecvrf.verify(object(), b"", b"input")  # type: ignore[arg-type]
+        with pytest.raises(TypeError):
+            ecvrf.proof_to_hash(
+                b"\x00" * 80,
+                "ECVRF-EDWARDS25519-SHA512-TAI",  # type: ignore[arg-type]
+            )
+
+    def test_proof_to_hash_invalid(self, backend):
+        proof = ecvrf.prove(_ed25519_key(), b"input")
+        with pytest.raises(ValueError):
+            ecvrf.proof_to_hash(proof, ecvrf.ECVRFSuite.P256_SHA256_TAI)
+        with pytest.raises(ValueError):
+            ecvrf.proof_to_hash(
+                proof[:-1], ecvrf.ECVRFSuite.EDWARDS25519_SHA512_TAI
+            )
+        # s must be less than the group order.
+        with pytest.raises(ValueError):
+            ecvrf.proof_to_hash(
+                proof[:48] + b"\xff" * 32,
+                ecvrf.ECVRFSuite.EDWARDS25519_SHA512_TAI,
+            )
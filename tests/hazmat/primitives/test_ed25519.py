@@ -15,9 +15,17 @@ from cryptography.hazmat.primitives.asymmetric.ed25519 import (
     Ed25519PrivateKey,
     Ed25519PublicKey,
 )
+from cryptography.hazmat.primitives.asymmetric.x25519 import (
+    X25519PrivateKey,
+    X25519PublicKey,
+)
 
 from ...doubles import DummyKeySerializationEncryption
-from ...utils import load_vectors_from_file, raises_unsupported_algorithm
+from ...utils import (
+    load_nist_vectors,
+    load_vectors_from_file,
+    raises_unsupported_algorithm,
+)
 
 
 def load_ed25519_vectors(vector_data):
@@ -117,6 +125,159 @@ class TestEd25519Signing:
         with pytest.raises(InvalidSignature):
             key.public_key().verify(b"0" * 64, b"test data")
 
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.ed25519_variants_supported(),
+        skip_message="Requires OpenSSL with Ed25519ctx and Ed25519ph",
+    )
+    @pytest.mark.parametrize(
+        "vector",
+        load_vectors_from_file(
+            os.path.join("asymmetric", "Ed25519", "rfc8032.txt"),
+            load_nist_vectors,
+        ),
+    )
+    def test_sign_verify_variants(self, vector, backend):
+        sk = binascii.unhexlify(vector["secret"])
+        pk = binascii.unhexlify(vector["public"])
+        message = binascii.unhexlify(vector["message"])
+        context = binascii.unhexlify(vector["context"])
+        signature = binascii.unhexlify(vector["signature"])
+        prehash = vector["instance"] == b"Ed25519ph"
+        private_key = Ed25519PrivateKey.from_private_bytes(sk)
+        computed_sig = private_key.sign(
+            message, context=context, prehash=prehash
+        )
+        assert computed_sig == signature
+        public_key = private_key.public_key()
+        assert public_key.public_bytes_raw() == pk
+        public_key.verify(signature, message, context=context, prehash=prehash)
+
+        with pytest.raises(InvalidSignature):
+            public_key.verify(signature, message)
+        with pytest.raises(InvalidSignature):
+            public_key.verify(
+                signature, message, context=b"other", prehash=prehash
+            )
+
+    def test_invalid_context(self, backend):
+        key = Ed25519PrivateKey.generate()
+        for context, prehash in [(b"", False), (b"x" * 256, True)]:
+            with pytest.raises(ValueError):
+                key.sign(b"test data", context=context, prehash=prehash)
+            with pytest.raises(ValueError):
+                key.public_key().verify(
+                    b"0" * 64, b"test data", context=context, prehash=prehash
+                )
+
+    @pytest.mark.supported(
+        only_if=lambda backend: not backend.ed25519_variants_supported(),
+        skip_message="Requires OpenSSL without Ed25519ctx and Ed25519ph",
+    )
+    def test_variants_unsupported(self, backend):
+        key = Ed25519PrivateKey.generate()
+        with raises_unsupported_algorithm(
+            _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+        ):
+            key.sign(b"test data", context=b"context")
+        with raises_unsupported_algorithm(
+            _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+        ):
+            key.public_key().verify(b"0" * 64, b"test data", prehash=True)
+
+    @pytest.mark.parametrize(
+        "vector",
+        load_vectors_from_file(
+            os.path.join("asymmetric", "Ed25519", "rfc8032.txt"),
+            load_nist_vectors,
+        ),
+    )
+    @pytest.mark.parametrize(
+        ("cofactored", "strict"), [(True, False), (False, True), (True, True)]
+    )
+    def test_verify_explicit(self, vector, cofactored, strict, backend):
+        public_key = Ed25519PublicKey.from_public_bytes(
+            binascii.unhexlify(vector["public"])
+        )
+        message = binascii.unhexlify(vector["message"])
+        context = binascii.unhexlify(vector["context"])
+        signature = binascii.unhexlify(vector["signature"])
+        prehash = vector["instance"] == b"Ed25519ph"
+        public_key.verify(
+            signature,
+            message,
+            context=context,
+            prehash=prehash,
+            cofactored=cofactored,
+            strict=strict,
+        )
+        with pytest.raises(InvalidSignature):
+            public_key.verify(
+                signature,
+                message,
+                context=b"other",
+                prehash=prehash,
+                cofactored=cofactored,
+                strict=strict,
+            )
+
+    @pytest.mark.parametrize(
+        ("cofactored", "strict"),
+        [(False, False), (True, False), (False, True), (True, True)],
+    )
+    def test_verify_non_canonical_s(self, cofactored, strict, backend):
+        key = Ed25519PrivateKey.generate()
+        signature = key.sign(b"test data")
+        s = int.from_bytes(signature[32:], "little")
+        s += 2**252 + 27742317777372353535851937790883648493
+        signature = signature[:32] + s.to_bytes(32, "little")
+        with pytest.raises(InvalidSignature):
+            key.public_key().verify(
+                signature, b"test data", cofactored=cofactored, strict=strict
+            )
+
+    def test_verify_mixed_order_key(self, backend):
+        # A = aB + T for a point T of order 8, so [S]B = R + [k]A only holds
+        # up to a small order component.
+        public_key = Ed25519PublicKey.from_public_bytes(
+            binascii.unhexlify(
+                b"6f72b650dc68f3fd3ecf036653006fd5"
+                b"d35a8b6c697e9526df8dc99db39946a5"
+            )
+        )
+        signature = binascii.unhexlify(
+            b"c9a3f86aae465f0e56513864510f3997561fa2c9e85ea21dc2292309f3cd6022"
+            b"d96c03b93d1e9a38ab2f80c4818325920647c66e1125de6a8824e73969efd309"
+        )
+        public_key.verify(signature, b"ZIP 215", cofactored=True)
+        public_key.verify(signature, b"ZIP 215", cofactored=True, strict=True)
+        with pytest.raises(InvalidSignature):
+            public_key.verify(signature, b"ZIP 215")
+        with pytest.raises(InvalidSignature):
+            public_key.verify(signature, b"ZIP 215", strict=True)
+
+    @pytest.mark.parametrize(
+        ("public_key", "r"),
+        [
+            # The identity, canonically encoded.
+            (b"01" + b"00" * 31, b"01" + b"00" * 31),
+            # The identity with y = p + 1.
+            (b"01" + b"00" * 31, b"ee" + b"ff" * 30 + b"7f"),
+            (b"ee" + b"ff" * 30 + b"7f", b"01" + b"00" * 31),
+            # The identity with the sign bit of x = 0 set.
+            (b"01" + b"00" * 30 + b"80", b"01" + b"00" * 31),
+        ],
+    )
+    def test_verify_small_order(self, public_key, r, backend):
+        key = Ed25519PublicKey.from_public_bytes(
+            binascii.unhexlify(public_key)
+        )
+        signature = binascii.unhexlify(r) + b"\x00" * 32
+        key.verify(signature, b"test data", cofactored=True)
+        with pytest.raises(InvalidSignature):
+            key.verify(signature, b"test data", strict=True)
+        with pytest.raises(InvalidSignature):
+            key.verify(signature, b"test data", cofactored=True, strict=True)
+
     def test_generate(self, backend):
         key = Ed25519PrivateKey.generate()
         assert key
@@ -311,3 +472,81 @@ def test_public_key_copy(backend):
     key2 = copy.copy(key1)
 
     assert key1 == key2
+
+
+@pytest.mark.supported(
+    only_if=lambda backend: (
+        backend.ed25519_supported() and backend.x25519_supported()
+    ),
+    skip_message="Requires OpenSSL with Ed25519 and X25519 support",
+)
+class TestX25519Conversion:
+    def test_vector(self, backend):
+        # The first test vector from RFC 8032 section 7.1.
+        private_key = Ed25519PrivateKey.from_private_bytes(
+            binascii.unhexlify(
+                b"9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae"
+                b"7f60"
+            )
+        )
+        public_key = private_key.public_key()
+        x25519_private_key = private_key.to_x25519_private_key()
+        x25519_public_key = public_key.to_x25519_public_key()
+        assert isinstance(x25519_private_key, X25519PrivateKey)
+        assert isinstance(x25519_public_key, X25519PublicKey)
+        assert x25519_private_key.private_bytes_raw() == binascii.unhexlify(
+            b"307c83864f2833cb427a2ef1c00a013cfdff2768d980c0a3a520f006904de94f"
+        )
+        assert x25519_public_key.public_bytes_raw() == binascii.unhexlify(
+            b"d85e07ec22b0ad881537c2f44d662d1a143cf830c57aca4305d85c7a90f6b62e"
+        )
+        assert x25519_private_key.public_key() == x25519_public_key
+        assert x25519_public_key.to_ed25519_public_key() == public_key
+
+    def test_round_trip(self, backend):
+        private_key = Ed25519PrivateKey.generate()
+        public_key = private_key.public_key()
+        x25519_public_key = public_key.to_x25519_public_key()
+        assert (
+            private_key.to_x25519_private_key().public_key()
+            == x25519_public_key
+        )
+
+        # The sign of x is lost when converting to X25519, so the result
+        # always has the top bit clear.
+        ed25519_public_bytes = (
+            x25519_public_key.to_ed25519_public_key().public_bytes_raw()
+        )
+        expected = bytearray(public_key.public_bytes_raw())
+        expected[31] &= 0x7F
+        assert ed25519_public_bytes == expected
+
+    def test_exchange(self, backend):
+        alice = Ed25519PrivateKey.generate()
+        bob = Ed25519PrivateKey.generate()
+        shared_key = alice.to_x25519_private_key().exchange(
+            bob.public_key().to_x25519_public_key()
+        )
+        assert shared_key == bob.to_x25519_private_key().exchange(
+            alice.public_key().to_x25519_public_key()
+        )
+
+    def test_invalid(self, backend):
+        # y = 1, the identity point.
+        identity = Ed25519PublicKey.from_public_bytes(b"\x01" + b"\x00" * 31)
+        with pytest.raises(ValueError):
+            identity.to_x25519_public_key()
+
+        # y = p, which isn't a canonical encoding.
+        non_canonical = Ed25519PublicKey.from_public_bytes(
+            b"\xed" + b"\xff" * 30 + b"\x7f"
+        )
+        with pytest.raises(ValueError):
+            non_canonical.to_x25519_public_key()
+
+        # u = p - 1, which has no equivalent Edwards point.
+        x25519_public_key = X25519PublicKey.from_public_bytes(
+            b"\xec" + b"\xff" * 30 + b"\x7f"
+        )
+        with pytest.raises(ValueError):
+            x25519_public_key.to_ed25519_public_key()
@@ -58,15 +58,19 @@ class TestEd448Signing:
         ),
     )
     def test_sign_input(self, vector, backend):
-        if vector.get("context") is not None:
-            pytest.skip("ed448 contexts are not currently supported")
+        context = binascii.unhexlify(vector.get("context", b""))
+        prehash = vector.get("instance") == b"Ed448ph"
+        if (context or prehash) and not backend.ed448_variants_supported():
+            pytest.skip("Requires OpenSSL with Ed448 contexts and Ed448ph")
 
         sk = binascii.unhexlify(vector["secret"])
         pk = binascii.unhexlify(vector["public"])
         message = binascii.unhexlify(vector["message"])
         signature = binascii.unhexlify(vector["signature"])
         private_key = Ed448PrivateKey.from_private_bytes(sk)
-        computed_sig = private_key.sign(message)
+        computed_sig = private_key.sign(
+            message, context=context, prehash=prehash
+        )
         assert computed_sig == signature
         public_key = private_key.public_key()
         assert (
@@ -75,7 +79,54 @@ class TestEd448Signing:
             )
             == pk
         )
-        public_key.verify(signature, message)
+        public_key.verify(signature, message, context=context, prehash=prehash)
+        if context or prehash:
+            with pytest.raises(InvalidSignature):
+                public_key.verify(signature, message)
+
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.ed448_variants_supported(),
+        skip_message="Requires OpenSSL with Ed448 contexts and Ed448ph",
+    )
+    def test_wrong_context(self, backend):
+        key = Ed448PrivateKey.generate()
+        signature = key.sign(b"test data", context=b"context")
+        with pytest.raises(InvalidSignature):
+            key.public_key().verify(signature, b"test data", context=b"other")
+        with pytest.raises(InvalidSignature):
+            key.public_key().verify(
+                signature, b"test data", context=b"context", prehash=True
+            )
+
+    def test_context_too_long(self, backend):
+        key = Ed448PrivateKey.generate()
+        with pytest.raises(ValueError):
+            key.sign(b"test data", context=b"x" * 256)
+        with pytest.raises(ValueError):
+            key.public_key().verify(
+                b"0" * 114, b"test data", context=b"x" * 256
+            )
+
+    def test_empty_context(self, backend):
+        key = Ed448PrivateKey.generate()
+        signature = key.sign(b"test data", context=b"")
+        assert signature == key.sign(b"test data")
+        key.public_key().verify(signature, b"test data", context=b"")
+
+    @pytest.mark.supported(
+        only_if=lambda backend: not backend.ed448_variants_supported(),
+        skip_message="Requires OpenSSL without Ed448 contexts and Ed448ph",
+    )
+    def test_variants_unsupported(self, backend):
+        key = Ed448PrivateKey.generate()
+        with raises_unsupported_algorithm(
+            _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+        ):
+            key.sign(b"test data", context=b"context")
+        with raises_unsupported_algorithm(
+            _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+        ):
+            key.public_key().verify(b"0" * 114, b"test data", prehash=True)
 
     def test_invalid_signature(self, backend):
         key = Ed448PrivateKey.generate()
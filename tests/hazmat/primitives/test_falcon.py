@@ -0,0 +1,148 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+
+import binascii
+import copy
+import os
+
+import pytest
+
+from cryptography.exceptions import InvalidSignature, _Reasons
+from cryptography.hazmat.primitives.asymmetric.falcon import (
+    FalconParameterSet,
+    FalconPublicKey,
+)
+
+from ...utils import (
+    load_nist_vectors,
+    load_vectors_from_file,
+    raises_unsupported_algorithm,
+)
+
+
+def _load_vectors():
+    return load_vectors_from_file(
+        os.path.join("asymmetric", "Falcon", "falcon.txt"),
+        load_nist_vectors,
+    )
+
+
+def _vector(parameter_set):
+    header = b"09" if parameter_set is FalconParameterSet.FALCON_512 else b"0a"
+    vector = next(v for v in _load_vectors() if v["pk"].startswith(header))
+    return (
+        binascii.unhexlify(vector["pk"]),
+        binascii.unhexlify(vector["msg"]),
+        binascii.unhexlify(vector["sig"]),
+    )
+
+
+@pytest.mark.supported(
+    only_if=lambda backend: not backend.falcon_supported(),
+    skip_message="Requires OpenSSL without Falcon support",
+)
+def test_falcon_unsupported(backend):
+    with raises_unsupported_algorithm(
+        _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+    ):
+        FalconPublicKey.from_public_bytes(b"\x09" + b"\x00" * 896)
+
+
+@pytest.mark.supported(
+    only_if=lambda backend: backend.falcon_supported(),
+    skip_message="Requires OpenSSL with Falcon support",
+)
+class TestFalcon:
+    @pytest.mark.parametrize("vector", _load_vectors())
+    def test_vectors(self, vector, backend):
+        public_bytes = binascii.unhexlify(vector["pk"])
+        message = binascii.unhexlify(vector["msg"])
+        signature = binascii.unhexlify(vector["sig"])
+
+        key = FalconPublicKey.from_public_bytes(public_bytes)
+        assert key.public_bytes_raw() == public_bytes
+        key.verify(signature, message)
+        key.verify(bytearray(signature), bytearray(message))
+
+        with pytest.raises(InvalidSignature):
+            key.verify(signature, message + b"\x00")
+
+    @pytest.mark.parametrize(
+        ("parameter_set", "length"),
+        [
+            (FalconParameterSet.FALCON_512, 897),
+            (FalconParameterSet.FALCON_1024, 1793),
+        ],
+    )
+    def test_parameter_set(self, parameter_set, length, backend):
+        public_bytes, _, _ = _vector(parameter_set)
+        assert len(public_bytes) == length
+        key = FalconPublicKey.from_public_bytes(public_bytes)
+        assert key.parameter_set is parameter_set
+
+    @pytest.mark.parametrize(
+        "parameter_set",
+        [FalconParameterSet.FALCON_512, FalconParameterSet.FALCON_1024],
+    )
+    def test_invalid_signature(self, parameter_set, backend):
+        public_bytes, message, signature = _vector(parameter_set)
+        key = FalconPublicKey.from_public_bytes(public_bytes)
+
+        invalid = [
+            b"",
+            signature[:41],
+            signature[:-1],
+            signature + b"\x00",
+            # A different encoding in the header byte.
+            b"\x29" + signature[1:],
+            # The nonce is hashed with the message.
+            signature[:1] + bytes([signature[1] ^ 1]) + signature[2:],
+            # s2 is changed, so s1 isn't short any more.
+            signature[:100] + bytes([signature[100] ^ 0x80]) + signature[101:],
+        ]
+        for sig in invalid:
+            with pytest.raises(InvalidSignature):
+                key.verify(sig, message)
+
+    def test_wrong_parameter_set(self, backend):
+        public_bytes, _, _ = _vector(FalconParameterSet.FALCON_1024)
+        _, message, signature = _vector(FalconParameterSet.FALCON_512)
+        key = FalconPublicKey.from_public_bytes(public_bytes)
+        with pytest.raises(InvalidSignature):
+            key.verify(signature, message)
+        with pytest.raises(InvalidSignature):
+            key.verify(b"\x3a" + signature[1:], message)
+
+    def test_invalid_public_key(self, backend):
+        public_bytes, _, _ = _vector(FalconParameterSet.FALCON_512)
+        with pytest.raises(ValueError):
+            FalconPublicKey.from_public_bytes(b"")
+        with pytest.raises(ValueError):
+            FalconPublicKey.from_public_bytes(public_bytes[:-1])
+        with pytest.raises(ValueError):
+            FalconPublicKey.from_public_bytes(public_bytes + b"\x00")
+        # The header is the log2 of the degree, only 9 and 10 are allowed.
+        with pytest.raises(ValueError):
+            FalconPublicKey.from_public_bytes(b"\x08" + public_bytes[1:])
+        with pytest.raises(ValueError):
+            FalconPublicKey.from_public_bytes(b"\x0a" + public_bytes[1:])
+        # The coefficients of h must be less than q.
+        with pytest.raises(ValueError):
+            FalconPublicKey.from_public_bytes(
+                public_bytes[:1] + b"\xff\xfc" + public_bytes[3:]
+            )
+
+    def test_eq(self, backend):
+        public_bytes, _, _ = _vector(FalconParameterSet.FALCON_512)
+        other_bytes, _, _ = _vector(FalconParameterSet.FALCON_1024)
+        key = FalconPublicKey.from_public_bytes(public_bytes)
+        assert key == FalconPublicKey.from_public_bytes(public_bytes)
+        assert key != FalconPublicKey.from_public_bytes(other_bytes)
+        assert key != object()
+
+    def test_copy(self, backend):
+        public_bytes, _, _ = _vector(FalconParameterSet.FALCON_512)
+        key = FalconPublicKey.from_public_bytes(public_bytes)
+        assert copy.copy(key) is key
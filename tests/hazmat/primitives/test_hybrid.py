@@ -0,0 +1,208 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+
+import binascii
+import os
+
+import pytest
+
+from cryptography.exceptions import _Reasons
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import x25519
+from cryptography.hazmat.primitives.asymmetric.hybrid import (
+    HybridGroup,
+    HybridPrivateKeyShare,
+    respond,
+)
+from cryptography.hazmat.primitives.kdf.hkdf import HKDF
+
+from ...utils import (
+    load_nist_vectors,
+    load_vectors_from_file,
+    raises_unsupported_algorithm,
+)
+
+
+def _hkdf() -> HKDF:
+    return HKDF(hashes.SHA256(), 32, salt=None, info=b"hybrid test")
+
+
+@pytest.mark.supported(
+    only_if=lambda backend: not backend.hybrid_key_exchange_supported(),
+    skip_message="Requires OpenSSL without hybrid key exchange support",
+)
+def test_hybrid_unsupported(backend):
+    with raises_unsupported_algorithm(_Reasons.UNSUPPORTED_EXCHANGE_ALGORITHM):
+        HybridPrivateKeyShare.generate(HybridGroup.X25519MLKEM768)
+
+    with raises_unsupported_algorithm(_Reasons.UNSUPPORTED_EXCHANGE_ALGORITHM):
+        HybridPrivateKeyShare.from_private_bytes(
+            HybridGroup.X25519MLKEM768, b"\x00" * 96
+        )
+
+    with raises_unsupported_algorithm(_Reasons.UNSUPPORTED_EXCHANGE_ALGORITHM):
+        respond(HybridGroup.X25519MLKEM768, b"\x00" * 1216, _hkdf())
+
+
+@pytest.mark.supported(
+    only_if=lambda backend: backend.hybrid_key_exchange_supported(),
+    skip_message="Requires OpenSSL with hybrid key exchange support",
+)
+class TestHybrid:
+    @pytest.mark.parametrize(
+        "vector",
+        load_vectors_from_file(
+            os.path.join("asymmetric", "Hybrid", "tls-ecdhe-mlkem.txt"),
+            load_nist_vectors,
+        ),
+    )
+    def test_vectors(self, vector, backend):
+        group = HybridGroup(vector["group"].decode())
+        private_bytes = binascii.unhexlify(vector["private_key_share"])
+        peer_key_share = binascii.unhexlify(vector["peer_key_share"])
+        shared_secret = binascii.unhexlify(vector["shared_secret"])
+
+        key_share = HybridPrivateKeyShare.from_private_bytes(
+            group, private_bytes
+        )
+        assert key_share.group is group
+        assert key_share.key_share() == binascii.unhexlify(
+            vector["key_share"]
+        )
+        assert key_share.private_bytes_raw() == private_bytes
+
+        expected = _hkdf().derive(shared_secret)
+        assert key_share.derive(peer_key_share, _hkdf()) == expected
+        assert key_share.derive(bytearray(peer_key_share), _hkdf()) == expected
+
+    @pytest.mark.parametrize(
+        ("group", "key_share_length", "response_length"),
+        [
+            (HybridGroup.X25519MLKEM768, 1216, 1120),
+            (HybridGroup.SECP256R1MLKEM768, 1249, 1153),
+            (HybridGroup.SECP384R1MLKEM1024, 1665, 1665),
+        ],
+    )
+    def test_round_trip(
+        self, group, key_share_length, response_length, backend
+    ):
+        key_share = HybridPrivateKeyShare.generate(group)
+        assert key_share.group is group
+        assert len(key_share.key_share()) == key_share_length
+
+        response, derived_key = respond(group, key_share.key_share(), _hkdf())
+        assert len(response) == response_length
+        assert len(derived_key) == 32
+        assert key_share.derive(response, _hkdf()) == derived_key
+
+        other_response, other_key = respond(
+            group, key_share.key_share(), _hkdf()
+        )
+        assert other_response != response
+        assert other_key != derived_key
+
+        loaded = HybridPrivateKeyShare.from_private_bytes(
+            group, key_share.private_bytes_raw()
+        )
+        assert loaded.key_share() == key_share.key_share()
+        assert loaded.derive(response, _hkdf()) == derived_key
+
+    def test_modified_response(self, backend):
+        group = HybridGroup.X25519MLKEM768
+        key_share = HybridPrivateKeyShare.generate(group)
+        response, derived_key = respond(group, key_share.key_share(), _hkdf())
+        # ML-KEM uses implicit rejection, so a modified ciphertext gives a
+        # different key instead of an error.
+        modified = bytearray(response)
+        modified[0] ^= 1
+        assert key_share.derive(bytes(modified), _hkdf()) != derived_key
+
+    def test_invalid_key_share_length(self, backend):
+        group = HybridGroup.SECP256R1MLKEM768
+        key_share = HybridPrivateKeyShare.generate(group)
+        with pytest.raises(ValueError):
+            respond(group, key_share.key_share()[:-1], _hkdf())
+        with pytest.raises(ValueError):
+            key_share.derive(b"\x00" * 1152, _hkdf())
+        # An X25519MLKEM768 key share is the wrong length for this group.
+        other = HybridPrivateKeyShare.generate(HybridGroup.X25519MLKEM768)
+        with pytest.raises(ValueError):
+            respond(group, other.key_share(), _hkdf())
+
+    def test_invalid_ec_point(self, backend):
+        group = HybridGroup.SECP256R1MLKEM768
+        key_share = HybridPrivateKeyShare.generate(group).key_share()
+        # Only uncompressed points are allowed.
+        with pytest.raises(ValueError):
+            respond(group, b"\x03" + key_share[1:], _hkdf())
+        # The point must be on the curve.
+        modified = bytearray(key_share)
+        modified[10] ^= 1
+        with pytest.raises(ValueError):
+            respond(group, bytes(modified), _hkdf())
+
+    def test_invalid_mlkem_key(self, backend):
+        group = HybridGroup.SECP256R1MLKEM768
+        key_share = HybridPrivateKeyShare.generate(group).key_share()
+        # The coefficients of the encapsulation key must be reduced.
+        with pytest.raises(ValueError):
+            respond(group, key_share[:65] + b"\xff" * 1184, _hkdf())
+
+    def test_small_order_x25519(self, backend):
+        group = HybridGroup.X25519MLKEM768
+        key_share = HybridPrivateKeyShare.generate(group)
+        mlkem_key = key_share.key_share()[:1184]
+        with pytest.raises(ValueError):
+            respond(group, mlkem_key + b"\x00" * 32, _hkdf())
+        with pytest.raises(ValueError):
+            key_share.derive(b"\x00" * 1120, _hkdf())
+
+    def test_x25519_key_share(self, backend):
+        group = HybridGroup.X25519MLKEM768
+        key_share = HybridPrivateKeyShare.generate(group)
+        private_bytes = key_share.private_bytes_raw()
+        x25519_key = x25519.X25519PrivateKey.from_private_bytes(
+            private_bytes[64:]
+        )
+        assert (
+            key_share.key_share()[1184:]
+            == x25519_key.public_key().public_bytes_raw()
+        )
+
+    @pytest.mark.parametrize(
+        ("group", "length"),
+        [
+            (HybridGroup.X25519MLKEM768, 96),
+            (HybridGroup.SECP256R1MLKEM768, 96),
+            (HybridGroup.SECP384R1MLKEM1024, 112),
+        ],
+    )
+    def test_invalid_private_bytes(self, group, length, backend):
+        with pytest.raises(ValueError):
+            HybridPrivateKeyShare.from_private_bytes(
+                group, b"\x00" * (length - 1)
+            )
+        if group is not HybridGroup.X25519MLKEM768:
+            # The scalar must be non-zero and less than the order.
+            with pytest.raises(ValueError):
+                HybridPrivateKeyShare.from_private_bytes(
+                    group, b"\x00" * length
+                )
+            with pytest.raises(ValueError):
+                HybridPrivateKeyShare.from_private_bytes(
+                    group, b"\x00" * 64 + b"\xff" * (length - 64)
+                )
+
+    def test_invalid_group(self, backend):
+        with pytest.raises(TypeError):
+            HybridPrivateKeyShare.generate(
+                "X25519MLKEM768"  # type: ignore[arg-type]
+            )
+        with pytest.raises(TypeError):
+            respond(
+                "X25519MLKEM768",  # type: ignore[arg-type]
+                b"\x00" * 1216,
+                _hkdf(),
+            )
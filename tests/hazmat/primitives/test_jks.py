@@ -0,0 +1,286 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+
+import hashlib
+import os
+import struct
+
+import pytest
+
+from cryptography import x509
+from cryptography.hazmat.primitives import serialization
+from cryptography.hazmat.primitives.asymmetric import ec, rsa
+from cryptography.hazmat.primitives.serialization.jks import (
+    JKSKeyStore,
+    JKSPrivateKeyEntry,
+    JKSTrustedCertificateEntry,
+    load_jks,
+)
+
+from ...utils import load_vectors_from_file
+
+
+def _load_jks(filename, password, key_password=None):
+    return load_vectors_from_file(
+        os.path.join("jks", filename),
+        lambda f: load_jks(f.read(), password, key_password),
+        mode="rb",
+    )
+
+
+def _utf(s):
+    # Java's modified UTF-8 encodes each half of a surrogate pair separately.
+    units = s.encode("utf-16-be")
+    data = b"".join(
+        chr(int.from_bytes(units[i : i + 2], "big")).encode(
+            "utf-8", "surrogatepass"
+        )
+        for i in range(0, len(units), 2)
+    )
+    return struct.pack(">H", len(data)) + data
+
+
+def _keystore(entries, *, version=2, password=b"password"):
+    # Builds a JKS keystore around already encoded entries.
+    contents = struct.pack(">III", 0xFEEDFEED, version, len(entries))
+    contents += b"".join(entries)
+    mac = hashlib.sha1(
+        password.decode().encode("utf-16-be")
+        + b"Mighty Aphrodite"
+        + contents
+    ).digest()
+    return contents + mac
+
+
+def _trusted_cert_entry(alias, der, cert_type="X.509"):
+    return (
+        struct.pack(">I", 2)
+        + _utf(alias)
+        + struct.pack(">Q", 0)
+        + _utf(cert_type)
+        + struct.pack(">I", len(der))
+        + der
+    )
+
+
+def _load_cert(path):
+    return load_vectors_from_file(
+        path,
+        lambda pemfile: x509.load_pem_x509_certificate(pemfile.read()),
+        mode="rb",
+    )
+
+
+@pytest.mark.skip_fips(reason="JKS keystores use MD5, SHA-1, and 3DES.")
+class TestJKSLoading:
+    @pytest.mark.parametrize(
+        "filename", ["rsa-chain-and-ca.jks", "rsa-chain-and-ca.jceks"]
+    )
+    def test_load_chain_and_ca(self, filename):
+        keystore = _load_jks(filename, b"password")
+        assert len(keystore.private_keys) == 1
+        entry = keystore.private_keys[0]
+        assert entry.alias == "leaf"
+        assert isinstance(entry.key, rsa.RSAPrivateKey)
+        assert entry.key.key_size == 2048
+        assert [c.subject.rfc4514_string() for c in entry.certificates] == [
+            "CN=JKS Test Leaf",
+            "CN=JKS Test CA",
+        ]
+        assert entry.certificates[0].public_key() == entry.key.public_key()
+
+        assert len(keystore.trusted_certificates) == 1
+        trusted = keystore.trusted_certificates[0]
+        assert trusted.alias == "ca"
+        assert trusted.certificate == entry.certificates[1]
+
+    @pytest.mark.parametrize(
+        "filename", ["ec-key-password.jks", "ec-key-password.jceks"]
+    )
+    def test_load_key_password(self, filename):
+        keystore = _load_jks(filename, b"password", b"keypassword")
+        assert keystore.trusted_certificates == []
+        [entry] = keystore.private_keys
+        assert entry.alias == "schlüssel"
+        assert isinstance(entry.key, ec.EllipticCurvePrivateKey)
+        assert isinstance(entry.key.curve, ec.SECP384R1)
+        [cert] = entry.certificates
+        assert cert.public_key() == entry.key.public_key()
+
+    @pytest.mark.parametrize(
+        "filename", ["ec-key-password.jks", "ec-key-password.jceks"]
+    )
+    def test_wrong_key_password(self, filename):
+        # The key password defaults to the keystore password.
+        with pytest.raises(ValueError, match="Invalid password"):
+            _load_jks(filename, b"password")
+        with pytest.raises(ValueError, match="Invalid password"):
+            _load_jks(filename, b"password", b"wrong")
+
+    @pytest.mark.parametrize(
+        "filename", ["rsa-chain-and-ca.jks", "rsa-chain-and-ca.jceks"]
+    )
+    def test_wrong_password(self, filename):
+        with pytest.raises(ValueError, match="Invalid password"):
+            _load_jks(filename, b"wrong", b"password")
+
+    def test_modified_keystore(self):
+        data = bytearray(
+            load_vectors_from_file(
+                os.path.join("jks", "rsa-chain-and-ca.jks"),
+                lambda f: f.read(),
+                mode="rb",
+            )
+        )
+        data[100] ^= 1
+        with pytest.raises(ValueError, match="Invalid password"):
+            load_jks(bytes(data), b"password")
+
+    def test_secret_key_entry(self):
+        with pytest.raises(ValueError, match="Could not deserialize"):
+            _load_jks("aes-secret-key.jceks", b"password")
+
+    @pytest.mark.parametrize(
+        "data",
+        [
+            b"",
+            b"invalid",
+            b"\x00" * 40,
+            # A PKCS#12 file
+            bytes.fromhex("3082") + b"\x00" * 40,
+        ],
+    )
+    def test_invalid(self, data):
+        with pytest.raises(ValueError, match="Could not deserialize"):
+            load_jks(data, b"password")
+
+    @pytest.mark.parametrize("version", [1, 2])
+    def test_empty(self, version):
+        keystore = load_jks(_keystore([], version=version), b"password")
+        assert keystore == JKSKeyStore([], [])
+
+    def test_non_ascii_password(self):
+        password = "pässword".encode()
+        keystore = load_jks(_keystore([], password=password), password)
+        assert keystore == JKSKeyStore([], [])
+
+    def test_constructed_trusted_cert(self):
+        cert = _load_cert(os.path.join("x509", "cryptography.io.pem"))
+        der = cert.public_bytes(serialization.Encoding.DER)
+        keystore = load_jks(
+            _keystore([_trusted_cert_entry("\U0001f600", der)]), b"password"
+        )
+        assert keystore.trusted_certificates == [
+            JKSTrustedCertificateEntry("\U0001f600", cert)
+        ]
+
+    @pytest.mark.parametrize(
+        "entry",
+        [
+            # Unknown cert type
+            _trusted_cert_entry("ca", b"\x30\x00", "X.510"),
+            # Unknown entry tag
+            struct.pack(">I", 4) + _utf("ca") + struct.pack(">Q", 0),
+            # Truncated entry
+            _trusted_cert_entry("ca", b"\x30\x00")[:-1],
+            # Trailing data
+            _trusted_cert_entry("ca", b"\x30\x00") + b"\x00",
+        ],
+    )
+    def test_invalid_entries(self, entry):
+        with pytest.raises(ValueError):
+            load_jks(_keystore([entry]), b"password")
+
+
+class TestJKSObjects:
+    def test_private_key_entry_constructor(self):
+        cert = _load_cert(os.path.join("x509", "cryptography.io.pem"))
+        key = ec.generate_private_key(ec.SECP256R1())
+        with pytest.raises(TypeError):
+            JKSPrivateKeyEntry(b"alias", key, [])  # type:ignore[arg-type]
+        with pytest.raises(TypeError):
+            JKSPrivateKeyEntry(
+                "alias",
+                key,
+                [cert, "hello"],  # type:ignore[list-item]
+            )
+
+    def test_private_key_entry_equality(self):
+        cert = _load_cert(os.path.join("x509", "cryptography.io.pem"))
+        cert2 = _load_cert(os.path.join("x509", "letsencryptx3.pem"))
+        key = ec.generate_private_key(ec.SECP256R1())
+        key2 = ec.generate_private_key(ec.SECP256R1())
+
+        a = JKSPrivateKeyEntry("a", key, [cert])
+        b = JKSPrivateKeyEntry("b", key, [cert])
+        c = JKSPrivateKeyEntry("a", key2, [cert])
+        d = JKSPrivateKeyEntry("a", key, [cert, cert2])
+
+        assert a == JKSPrivateKeyEntry("a", key, [cert])
+        assert hash(a) == hash(JKSPrivateKeyEntry("a", key, [cert]))
+        assert a != b
+        assert a != c
+        assert a != d
+        assert a != "test"
+
+    def test_private_key_entry_repr(self):
+        cert = _load_cert(os.path.join("x509", "cryptography.io.pem"))
+        key = ec.generate_private_key(ec.SECP256R1())
+        assert repr(JKSPrivateKeyEntry("a", key, [cert])) == (
+            f"<JKSPrivateKeyEntry(alias='a', key={key}, "
+            f"certificates=[{cert}])>"
+        )
+
+    def test_trusted_certificate_entry_constructor(self):
+        cert = _load_cert(os.path.join("x509", "cryptography.io.pem"))
+        with pytest.raises(TypeError):
+            JKSTrustedCertificateEntry(None, cert)  # type:ignore[arg-type]
+        with pytest.raises(TypeError):
+            JKSTrustedCertificateEntry("a", "hello")  # type:ignore[arg-type]
+
+    def test_trusted_certificate_entry_equality(self):
+        cert = _load_cert(os.path.join("x509", "cryptography.io.pem"))
+        cert2 = _load_cert(os.path.join("x509", "letsencryptx3.pem"))
+
+        a = JKSTrustedCertificateEntry("a", cert)
+        assert a == JKSTrustedCertificateEntry("a", cert)
+        assert hash(a) == hash(JKSTrustedCertificateEntry("a", cert))
+        assert a != JKSTrustedCertificateEntry("b", cert)
+        assert a != JKSTrustedCertificateEntry("a", cert2)
+        assert a != "test"
+
+    def test_trusted_certificate_entry_repr(self):
+        cert = _load_cert(os.path.join("x509", "cryptography.io.pem"))
+        assert repr(JKSTrustedCertificateEntry("a", cert)) == (
+            f"<JKSTrustedCertificateEntry(alias='a', certificate={cert})>"
+        )
+
+    def test_keystore_constructor(self):
+        cert = _load_cert(os.path.join("x509", "cryptography.io.pem"))
+        entry = JKSTrustedCertificateEntry("a", cert)
+        with pytest.raises(TypeError):
+            JKSKeyStore([entry], [])  # type:ignore[list-item]
+        with pytest.raises(TypeError):
+            JKSKeyStore([], ["hello"])  # type:ignore[list-item]
+
+    def test_keystore_equality(self):
+        cert = _load_cert(os.path.join("x509", "cryptography.io.pem"))
+        key = ec.generate_private_key(ec.SECP256R1())
+        key_entry = JKSPrivateKeyEntry("a", key, [cert])
+        cert_entry = JKSTrustedCertificateEntry("a", cert)
+
+        a = JKSKeyStore([key_entry], [cert_entry])
+        assert a == JKSKeyStore([key_entry], [cert_entry])
+        assert hash(a) == hash(JKSKeyStore([key_entry], [cert_entry]))
+        assert a != JKSKeyStore([], [cert_entry])
+        assert a != JKSKeyStore([key_entry], [])
+        assert a != "test"
+
+    def test_keystore_repr(self):
+        cert = _load_cert(os.path.join("x509", "cryptography.io.pem"))
+        entry = JKSTrustedCertificateEntry("a", cert)
+        assert repr(JKSKeyStore([], [entry])) == (
+            f"<JKSKeyStore(private_keys=[], trusted_certificates=[{entry}])>"
+        )
@@ -0,0 +1,179 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+
+import binascii
+import os
+
+import pytest
+
+from cryptography.exceptions import _Reasons
+from cryptography.hazmat.primitives.asymmetric import (
+    ec,
+    ed25519,
+    kem,
+    rsa,
+    x448,
+    x25519,
+)
+from cryptography.hazmat.primitives.asymmetric.xwing import XWingPrivateKey
+
+from ...utils import (
+    load_nist_vectors,
+    load_vectors_from_file,
+    raises_unsupported_algorithm,
+)
+
+
+@pytest.mark.supported(
+    only_if=lambda backend: not backend.kem_supported(),
+    skip_message="Requires OpenSSL without KEM support",
+)
+def test_kem_unsupported(backend):
+    key = x25519.X25519PrivateKey.generate()
+    with raises_unsupported_algorithm(
+        _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+    ):
+        kem.encapsulate(key.public_key())
+
+    with raises_unsupported_algorithm(
+        _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+    ):
+        kem.decapsulate(key, b"\x00" * 32)
+
+
+@pytest.mark.supported(
+    only_if=lambda backend: backend.kem_supported(),
+    skip_message="Requires OpenSSL with KEM support",
+)
+class TestKEM:
+    @pytest.mark.parametrize(
+        "vector",
+        load_vectors_from_file(
+            os.path.join("asymmetric", "KEM", "rfc9180-dhkem.txt"),
+            load_nist_vectors,
+        ),
+    )
+    def test_dhkem_vectors(self, vector, backend):
+        sk = binascii.unhexlify(vector["skrm"])
+        key: kem.KEMPrivateKeyTypes
+        if vector["curve"] == b"X25519":
+            key = x25519.X25519PrivateKey.from_private_bytes(sk)
+        else:
+            assert vector["curve"] == b"P-256"
+            key = ec.derive_private_key(
+                int.from_bytes(sk, "big"), ec.SECP256R1()
+            )
+        enc = binascii.unhexlify(vector["enc"])
+        shared_secret = binascii.unhexlify(vector["shared_secret"])
+        assert kem.decapsulate(key, enc) == shared_secret
+        assert kem.decapsulate(key, bytearray(enc)) == shared_secret
+
+    @pytest.mark.parametrize(
+        ("curve", "secret_length", "ciphertext_length"),
+        [
+            (ec.SECP256R1(), 32, 65),
+            (ec.SECP384R1(), 48, 97),
+            (ec.SECP521R1(), 64, 133),
+        ],
+    )
+    def test_dhkem_ec(self, curve, secret_length, ciphertext_length, backend):
+        key = ec.generate_private_key(curve)
+        shared_secret, ciphertext = kem.encapsulate(key.public_key())
+        assert len(shared_secret) == secret_length
+        assert len(ciphertext) == ciphertext_length
+        assert kem.decapsulate(key, ciphertext) == shared_secret
+
+        other = ec.generate_private_key(curve)
+        assert kem.decapsulate(other, ciphertext) != shared_secret
+        with pytest.raises(ValueError):
+            kem.decapsulate(key, ciphertext[:-1])
+
+    def test_dhkem_unsupported_curve(self, backend):
+        key = ec.generate_private_key(ec.SECP256K1())
+        with raises_unsupported_algorithm(
+            _Reasons.UNSUPPORTED_ELLIPTIC_CURVE
+        ):
+            kem.encapsulate(key.public_key())
+        with raises_unsupported_algorithm(
+            _Reasons.UNSUPPORTED_ELLIPTIC_CURVE
+        ):
+            kem.decapsulate(key, b"\x04" + b"\x00" * 64)
+
+    def test_dhkem_x25519(self, backend):
+        key = x25519.X25519PrivateKey.generate()
+        shared_secret, ciphertext = kem.encapsulate(key.public_key())
+        assert len(shared_secret) == 32
+        assert len(ciphertext) == 32
+        assert kem.decapsulate(key, ciphertext) == shared_secret
+
+        # The encapsulated key is an ephemeral public key.
+        ephemeral = x25519.X25519PublicKey.from_public_bytes(ciphertext)
+        assert ephemeral != key.public_key()
+        _, other_ciphertext = kem.encapsulate(key.public_key())
+        assert other_ciphertext != ciphertext
+
+        with pytest.raises(ValueError):
+            kem.decapsulate(key, ciphertext[:-1])
+        # A small order point gives an all zero Diffie-Hellman output.
+        with pytest.raises(ValueError):
+            kem.decapsulate(key, b"\x00" * 32)
+
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.x448_supported(),
+        skip_message="Requires OpenSSL with X448 support",
+    )
+    def test_dhkem_x448(self, backend):
+        key = x448.X448PrivateKey.generate()
+        shared_secret, ciphertext = kem.encapsulate(key.public_key())
+        assert len(shared_secret) == 64
+        assert len(ciphertext) == 56
+        assert kem.decapsulate(key, ciphertext) == shared_secret
+
+    def test_rsa_kem(self, backend):
+        key = rsa.generate_private_key(65537, 2048)
+        shared_secret, ciphertext = kem.encapsulate(key.public_key())
+        assert len(shared_secret) == 256
+        assert len(ciphertext) == 256
+        assert kem.decapsulate(key, ciphertext) == shared_secret
+
+        # The shared secret is a random integer, encrypted with raw RSA.
+        numbers = key.public_key().public_numbers()
+        z = int.from_bytes(shared_secret, "big")
+        assert pow(z, numbers.e, numbers.n) == int.from_bytes(
+            ciphertext, "big"
+        )
+
+    def test_rsa_kem_invalid_ciphertext(self, backend):
+        key = rsa.generate_private_key(65537, 2048)
+        _, ciphertext = kem.encapsulate(key.public_key())
+        with pytest.raises(ValueError):
+            kem.decapsulate(key, ciphertext[:-1])
+        with pytest.raises(ValueError):
+            kem.decapsulate(key, ciphertext + b"\x00")
+        # The ciphertext must be less than the modulus.
+        with pytest.raises(ValueError):
+            kem.decapsulate(key, b"\xff" * 256)
+
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.xwing_supported(),
+        skip_message="Requires OpenSSL with X-Wing support",
+    )
+    def test_xwing(self, backend):
+        key = XWingPrivateKey.generate()
+        shared_secret, ciphertext = kem.encapsulate(key.public_key())
+        assert kem.decapsulate(key, ciphertext) == shared_secret
+        assert key.decapsulate(ciphertext) == shared_secret
+
+        shared_secret, ciphertext = key.public_key().encapsulate()
+        assert kem.decapsulate(key, ciphertext) == shared_secret
+
+    def test_invalid_key_type(self, backend):
+        key = ed25519.Ed25519PrivateKey.generate()
+        with pytest.raises(TypeError):
+            kem.encapsulate(key.public_key())  # type: ignore[arg-type]
+        with pytest.raises(TypeError):
+            kem.decapsulate(key, b"\x00" * 32)  # type: ignore[arg-type]
+        with pytest.raises(TypeError):
+            kem.encapsulate(object())  # type: ignore[arg-type]
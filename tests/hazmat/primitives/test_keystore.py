@@ -0,0 +1,33 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+import sys
+
+import pytest
+
+from cryptography.exceptions import _Reasons
+from cryptography.hazmat.primitives.asymmetric import ec, keystore, rsa
+from cryptography.hazmat.primitives.asymmetric.signer import ExternalSigner
+
+from ...utils import raises_unsupported_algorithm
+
+_SUPPORTED = sys.platform in {"win32", "darwin"}
+
+
+@pytest.mark.skipif(_SUPPORTED, reason="Key store is supported")
+def test_unsupported_platform():
+    with raises_unsupported_algorithm(_Reasons.BACKEND_MISSING_INTERFACE):
+        keystore.list_keys()
+
+
+@pytest.mark.skipif(not _SUPPORTED, reason="Key store is unsupported")
+def test_list_keys():
+    # Signing may prompt the user, so only the listing is checked.
+    for key in keystore.list_keys():
+        assert isinstance(key, ExternalSigner)
+        assert isinstance(key.name, str)
+        assert isinstance(
+            key.public_key(), (rsa.RSAPublicKey, ec.EllipticCurvePublicKey)
+        )
+        assert key == key
@@ -0,0 +1,117 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+import os
+import subprocess
+import sys
+import textwrap
+
+import pytest
+
+from cryptography.exceptions import UnsupportedAlgorithm, _Reasons
+from cryptography.hazmat.primitives import locked_memory
+
+from ...utils import raises_unsupported_algorithm
+
+# The secure heap can only be initialized once per process and changes where
+# OpenSSL allocates private keys, so anything which initializes it runs in a
+# separate interpreter.
+LOCKED_MEMORY_SCRIPT = textwrap.dedent(
+    """
+    import pytest
+
+    from cryptography.hazmat.primitives import hashes, hmac, locked_memory
+    from cryptography.hazmat.primitives.asymmetric import ec
+
+    assert not locked_memory.is_initialized()
+    locked_memory.initialize(16384)
+    assert locked_memory.is_initialized()
+    with pytest.raises(ValueError):
+        locked_memory.initialize(16384)
+
+    buf = locked_memory.LockedBuffer.allocate(32)
+    assert isinstance(buf, locked_memory.LockedBuffer)
+    assert len(buf) == 32
+    assert buf.to_bytes() == b"\\x00" * 32
+    assert locked_memory.bytes_used() >= 32
+
+    buf.write(b"\\x01" * 16, offset=16)
+    assert buf.to_bytes() == b"\\x00" * 16 + b"\\x01" * 16
+    with pytest.raises(ValueError):
+        buf.write(b"\\x01", offset=32)
+
+    buf.randomize()
+    assert buf.to_bytes() != b"\\x00" * 32
+
+    # A LockedBuffer can be used anywhere a key is accepted as bytes.
+    h = hmac.HMAC(buf, hashes.SHA256())
+    h.update(b"data")
+    expected = hmac.HMAC(buf.to_bytes(), hashes.SHA256())
+    expected.update(b"data")
+    assert h.finalize() == expected.finalize()
+
+    buf.clear()
+    assert buf.to_bytes() == b"\\x00" * 32
+
+    # Private keys are stored in the secure heap once it's initialized.
+    used = locked_memory.bytes_used()
+    key = ec.generate_private_key(ec.SECP256R1())
+    assert locked_memory.bytes_used() > used
+    del key
+    """
+)
+
+
+@pytest.mark.supported(
+    only_if=lambda backend: backend.locked_memory_supported(),
+    skip_message="Requires OpenSSL with a secure heap",
+)
+@pytest.mark.skipif(
+    sys.platform == "win32", reason="Windows builds have no secure heap"
+)
+class TestLockedMemory:
+    def test_locked_buffer(self, backend):
+        env = os.environ.copy()
+        env["PYTHONPATH"] = os.pathsep.join(sys.path)
+        # pytest-cov instruments subprocesses, which isn't needed here.
+        env.pop("COV_CORE_CONFIG", None)
+        env.pop("COV_CORE_DATAFILE", None)
+        env.pop("COV_CORE_SOURCE", None)
+
+        proc = subprocess.run(
+            [sys.executable, "-c", LOCKED_MEMORY_SCRIPT],
+            env=env,
+            stdout=subprocess.PIPE,
+            stderr=subprocess.PIPE,
+        )
+        if b"RLIMIT_MEMLOCK" in proc.stderr:
+            pytest.skip("Unable to lock memory in this environment")
+        assert proc.returncode == 0, proc.stderr.decode()
+
+    def test_not_initialized(self, backend):
+        assert not locked_memory.is_initialized()
+        with pytest.raises(ValueError):
+            locked_memory.LockedBuffer.allocate(32)
+
+    @pytest.mark.parametrize(
+        ("size", "min_size"), [(3, 1), (4096, 3), (16, 16), (16, 32)]
+    )
+    def test_invalid_sizes(self, size, min_size, backend):
+        with pytest.raises(ValueError):
+            locked_memory.initialize(size, min_size)
+        assert not locked_memory.is_initialized()
+
+
+@pytest.mark.supported(
+    only_if=lambda backend: not backend.locked_memory_supported(),
+    skip_message="Requires OpenSSL without a secure heap",
+)
+def test_locked_memory_unsupported(backend):
+    assert not locked_memory.is_initialized()
+    with raises_unsupported_algorithm(_Reasons.BACKEND_MISSING_INTERFACE):
+        locked_memory.initialize(16384)
+    with raises_unsupported_algorithm(_Reasons.BACKEND_MISSING_INTERFACE):
+        locked_memory.LockedBuffer.allocate(32)
+    with pytest.raises(UnsupportedAlgorithm):
+        locked_memory.bytes_used()
@@ -0,0 +1,317 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+
+import binascii
+import copy
+import hashlib
+import os
+
+import pytest
+
+from cryptography.exceptions import InvalidSignature, _Reasons
+from cryptography.hazmat.primitives import hashes, serialization
+from cryptography.hazmat.primitives.asymmetric.mldsa import (
+    MLDSAParameterSet,
+    MLDSAPrivateKey,
+    MLDSAPublicKey,
+)
+
+from ...utils import (
+    load_nist_vectors,
+    load_vectors_from_file,
+    raises_unsupported_algorithm,
+)
+
+_SEED = bytes(range(32))
+
+
+@pytest.mark.supported(
+    only_if=lambda backend: not backend.mldsa_supported(),
+    skip_message="Requires OpenSSL without ML-DSA support",
+)
+def test_mldsa_unsupported(backend):
+    with raises_unsupported_algorithm(
+        _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+    ):
+        MLDSAPrivateKey.generate(MLDSAParameterSet.ML_DSA_44)
+
+    with raises_unsupported_algorithm(
+        _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+    ):
+        MLDSAPrivateKey.from_seed_bytes(MLDSAParameterSet.ML_DSA_44, _SEED)
+
+    with raises_unsupported_algorithm(
+        _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+    ):
+        MLDSAPublicKey.from_public_bytes(
+            MLDSAParameterSet.ML_DSA_44, b"\x00" * 1312
+        )
+
+
+@pytest.mark.supported(
+    only_if=lambda backend: backend.mldsa_supported(),
+    skip_message="Requires OpenSSL with ML-DSA support",
+)
+class TestMLDSA:
+    @pytest.mark.parametrize(
+        "vector",
+        load_vectors_from_file(
+            os.path.join("asymmetric", "MLDSA", "sign.txt"),
+            load_nist_vectors,
+        ),
+    )
+    def test_verify_vectors(self, vector, backend):
+        parameter_set = MLDSAParameterSet(vector["parameter_set"].decode())
+        seed = binascii.unhexlify(vector["seed"])
+        pk = binascii.unhexlify(vector["pk"])
+        message = binascii.unhexlify(vector["msg"])
+        context = binascii.unhexlify(vector["context"])
+        signature = binascii.unhexlify(vector["signature"])
+
+        private_key = MLDSAPrivateKey.from_seed_bytes(parameter_set, seed)
+        assert private_key.parameter_set is parameter_set
+        assert private_key.private_bytes_raw() == seed
+        assert private_key.public_key().public_bytes_raw() == pk
+
+        public_key = MLDSAPublicKey.from_public_bytes(parameter_set, pk)
+        assert public_key.parameter_set is parameter_set
+        public_key.verify(signature, message, context=context)
+        with pytest.raises(InvalidSignature):
+            public_key.verify(signature, message + b"\x00", context=context)
+        with pytest.raises(InvalidSignature):
+            public_key.verify(
+                signature, message, context=context[:-1] + b"\x00"
+            )
+
+    @pytest.mark.parametrize("parameter_set", list(MLDSAParameterSet))
+    def test_sign_verify(self, parameter_set, backend):
+        key = MLDSAPrivateKey.generate(parameter_set)
+        public_key = key.public_key()
+        signature = key.sign(b"data")
+        public_key.verify(signature, b"data")
+        public_key.verify(signature, b"data", context=b"")
+        with pytest.raises(InvalidSignature):
+            public_key.verify(signature, b"other data")
+        with pytest.raises(InvalidSignature):
+            public_key.verify(signature, b"data", context=b"context")
+        with pytest.raises(InvalidSignature):
+            public_key.verify(signature[:-1], b"data")
+
+        signature = key.sign(b"", context=b"context")
+        public_key.verify(signature, b"", context=b"context")
+        with pytest.raises(InvalidSignature):
+            public_key.verify(signature, b"")
+
+        other = MLDSAPrivateKey.generate(parameter_set).public_key()
+        with pytest.raises(InvalidSignature):
+            other.verify(signature, b"", context=b"context")
+
+    def test_context_too_long(self, backend):
+        key = MLDSAPrivateKey.generate(MLDSAParameterSet.ML_DSA_44)
+        signature = key.sign(b"data", context=b"a" * 255)
+        key.public_key().verify(signature, b"data", context=b"a" * 255)
+        with pytest.raises(ValueError):
+            key.sign(b"data", context=b"a" * 256)
+        with pytest.raises(ValueError):
+            key.public_key().verify(signature, b"data", context=b"a" * 256)
+
+    def test_from_seed_bytes(self, backend):
+        key = MLDSAPrivateKey.from_seed_bytes(
+            MLDSAParameterSet.ML_DSA_44, bytearray(_SEED)
+        )
+        assert key.private_bytes_raw() == _SEED
+        assert key == MLDSAPrivateKey.from_seed_bytes(
+            MLDSAParameterSet.ML_DSA_44, _SEED
+        )
+        assert key != MLDSAPrivateKey.from_seed_bytes(
+            MLDSAParameterSet.ML_DSA_65, _SEED
+        )
+        with pytest.raises(ValueError):
+            MLDSAPrivateKey.from_seed_bytes(
+                MLDSAParameterSet.ML_DSA_44, _SEED[:-1]
+            )
+        with pytest.raises(ValueError):
+            MLDSAPrivateKey.from_seed_bytes(
+                MLDSAParameterSet.ML_DSA_44, _SEED + b"\x00"
+            )
+
+    def test_invalid_parameter_set(self, backend):
+        with pytest.raises(TypeError):
+            MLDSAPrivateKey.generate("ML-DSA-44")  # type: ignore[arg-type]
+        with pytest.raises(TypeError):
+            MLDSAPrivateKey.from_seed_bytes(
+                "ML-DSA-44",  # type: ignore[arg-type]
+                _SEED,
+            )
+
+    def test_invalid_public_bytes_length(self, backend):
+        public_key = MLDSAPrivateKey.generate(
+            MLDSAParameterSet.ML_DSA_44
+        ).public_key()
+        data = public_key.public_bytes_raw()
+        with pytest.raises(ValueError):
+            MLDSAPublicKey.from_public_bytes(
+                MLDSAParameterSet.ML_DSA_44, data[:-1]
+            )
+        with pytest.raises(ValueError):
+            MLDSAPublicKey.from_public_bytes(MLDSAParameterSet.ML_DSA_65, data)
+
+    @pytest.mark.parametrize(
+        ("encoding", "fmt", "encryption", "load_func"),
+        [
+            (
+                serialization.Encoding.PEM,
+                serialization.PrivateFormat.PKCS8,
+                serialization.BestAvailableEncryption(b"password"),
+                serialization.load_pem_private_key,
+            ),
+            (
+                serialization.Encoding.DER,
+                serialization.PrivateFormat.PKCS8,
+                serialization.NoEncryption(),
+                serialization.load_der_private_key,
+            ),
+        ],
+    )
+    @pytest.mark.parametrize("parameter_set", list(MLDSAParameterSet))
+    def test_round_trip_private_serialization(
+        self, parameter_set, encoding, fmt, encryption, load_func, backend
+    ):
+        key = MLDSAPrivateKey.generate(parameter_set)
+        serialized = key.private_bytes(encoding, fmt, encryption)
+        password = getattr(encryption, "password", None)
+        loaded_key = load_func(serialized, password)
+        assert isinstance(loaded_key, MLDSAPrivateKey)
+        assert loaded_key.parameter_set is parameter_set
+        assert loaded_key == key
+        assert loaded_key.private_bytes_raw() == key.private_bytes_raw()
+        signature = loaded_key.sign(b"data")
+        key.public_key().verify(signature, b"data")
+
+    @pytest.mark.parametrize("parameter_set", list(MLDSAParameterSet))
+    def test_round_trip_public_serialization(self, parameter_set, backend):
+        public_key = MLDSAPrivateKey.generate(parameter_set).public_key()
+        der = public_key.public_bytes(
+            serialization.Encoding.DER,
+            serialization.PublicFormat.SubjectPublicKeyInfo,
+        )
+        loaded = serialization.load_der_public_key(der)
+        assert isinstance(loaded, MLDSAPublicKey)
+        assert loaded.parameter_set is parameter_set
+        assert loaded == public_key
+        pem = public_key.public_bytes(
+            serialization.Encoding.PEM,
+            serialization.PublicFormat.SubjectPublicKeyInfo,
+        )
+        assert serialization.load_pem_public_key(pem) == public_key
+
+        raw = public_key.public_bytes(
+            serialization.Encoding.Raw, serialization.PublicFormat.Raw
+        )
+        assert raw == public_key.public_bytes_raw()
+        assert (
+            MLDSAPublicKey.from_public_bytes(parameter_set, raw) == public_key
+        )
+
+    def test_raw_private_bytes(self, backend):
+        key = MLDSAPrivateKey.from_seed_bytes(
+            MLDSAParameterSet.ML_DSA_87, _SEED
+        )
+        raw = key.private_bytes(
+            serialization.Encoding.Raw,
+            serialization.PrivateFormat.Raw,
+            serialization.NoEncryption(),
+        )
+        assert raw == _SEED
+
+    def test_invalid_private_bytes(self, backend):
+        key = MLDSAPrivateKey.generate(MLDSAParameterSet.ML_DSA_44)
+        with pytest.raises(ValueError):
+            key.private_bytes(
+                serialization.Encoding.PEM,
+                serialization.PrivateFormat.OpenSSH,
+                serialization.NoEncryption(),
+            )
+        with pytest.raises(ValueError):
+            key.private_bytes(
+                serialization.Encoding.Raw,
+                serialization.PrivateFormat.Raw,
+                serialization.BestAvailableEncryption(b"password"),
+            )
+        with pytest.raises(ValueError):
+            key.private_bytes(
+                serialization.Encoding.Raw,
+                serialization.PrivateFormat.PKCS8,
+                serialization.NoEncryption(),
+            )
+
+    def test_load_without_seed(self, backend):
+        key = load_vectors_from_file(
+            os.path.join("asymmetric", "MLDSA", "mldsa44-priv-only.pem"),
+            lambda f: serialization.load_pem_private_key(f.read(), None),
+            mode="rb",
+        )
+        assert isinstance(key, MLDSAPrivateKey)
+        assert key.parameter_set is MLDSAParameterSet.ML_DSA_44
+        assert key == MLDSAPrivateKey.from_seed_bytes(
+            MLDSAParameterSet.ML_DSA_44, _SEED
+        )
+        with pytest.raises(ValueError):
+            key.private_bytes_raw()
+        with pytest.raises(ValueError):
+            key.private_bytes(
+                serialization.Encoding.Raw,
+                serialization.PrivateFormat.Raw,
+                serialization.NoEncryption(),
+            )
+
+        signature = key.sign(b"data")
+        key.public_key().verify(signature, b"data")
+        serialized = key.private_bytes(
+            serialization.Encoding.DER,
+            serialization.PrivateFormat.PKCS8,
+            serialization.NoEncryption(),
+        )
+        assert serialization.load_der_private_key(serialized, None) == key
+
+    def test_public_key_matches(self, backend):
+        key = MLDSAPrivateKey.generate(MLDSAParameterSet.ML_DSA_65)
+        assert key.public_key_matches(key.public_key())
+        other = MLDSAPrivateKey.generate(MLDSAParameterSet.ML_DSA_65)
+        assert not key.public_key_matches(other.public_key())
+
+    def test_private_key_equality(self, backend):
+        key = MLDSAPrivateKey.generate(MLDSAParameterSet.ML_DSA_44)
+        other = MLDSAPrivateKey.generate(MLDSAParameterSet.ML_DSA_44)
+        assert key == key
+        assert key != other
+        assert key != object()
+        assert hash(key) == hash(
+            MLDSAPrivateKey.from_seed_bytes(
+                MLDSAParameterSet.ML_DSA_44, key.private_bytes_raw()
+            )
+        )
+
+    def test_public_key_equality(self, backend):
+        key1 = MLDSAPrivateKey.generate(MLDSAParameterSet.ML_DSA_44)
+        key2 = MLDSAPrivateKey.generate(MLDSAParameterSet.ML_DSA_44)
+        public_key = key1.public_key()
+        assert public_key == copy.copy(public_key)
+        assert public_key == key1.public_key()
+        assert public_key != key2.public_key()
+        assert public_key != object()
+
+    def test_fingerprint(self, backend):
+        public_key = MLDSAPrivateKey.generate(
+            MLDSAParameterSet.ML_DSA_44
+        ).public_key()
+        der = public_key.public_bytes(
+            serialization.Encoding.DER,
+            serialization.PublicFormat.SubjectPublicKeyInfo,
+        )
+        assert (
+            public_key.fingerprint(hashes.SHA256())
+            == hashlib.sha256(der).digest()
+        )
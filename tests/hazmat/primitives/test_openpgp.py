@@ -0,0 +1,494 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+
+import datetime
+import os
+
+import pytest
+
+from cryptography.exceptions import UnsupportedAlgorithm, _Reasons
+from cryptography.hazmat.primitives.asymmetric import (
+    dsa,
+    ec,
+    ed25519,
+    rsa,
+    x25519,
+)
+from cryptography.hazmat.primitives.ciphers.aead import AESOCB3
+from cryptography.hazmat.primitives.serialization import ppk
+from cryptography.hazmat.primitives.serialization.openpgp import (
+    OpenPGPKey,
+    OpenPGPTransferableKey,
+    load_openpgp_private_key,
+    load_openpgp_public_key,
+)
+
+from ...utils import load_vectors_from_file, raises_unsupported_algorithm
+from .fixtures_rsa import RSA_KEY_1024
+
+
+def _load_vector(filename):
+    return load_vectors_from_file(
+        os.path.join("asymmetric", "OpenPGP", filename),
+        lambda f: f.read(),
+        mode="rb",
+    )
+
+
+def _ocb_supported():
+    try:
+        AESOCB3(b"0" * 32)
+        return True
+    except UnsupportedAlgorithm:
+        return False
+
+
+def _packet(tag, body):
+    # New format packets, with one or two octet lengths.
+    if len(body) < 192:
+        return bytes([0xC0 | tag, len(body)]) + body
+    n = len(body) - 192
+    return bytes([0xC0 | tag, 192 + (n >> 8), n & 0xFF]) + body
+
+
+def _mpi(value):
+    return (value.bit_length()).to_bytes(2, "big") + value.to_bytes(
+        (value.bit_length() + 7) // 8, "big"
+    )
+
+
+def _v4_key_body(algorithm, material):
+    return b"\x04" + (1234).to_bytes(4, "big") + bytes([algorithm]) + material
+
+
+_UTC = datetime.timezone.utc
+
+_RSA_PUBLIC_BODY = _v4_key_body(
+    1,
+    _mpi(RSA_KEY_1024.public_numbers.n) + _mpi(RSA_KEY_1024.public_numbers.e),
+)
+
+
+class TestOpenPGPPublicKeys:
+    def test_rsa(self):
+        key = load_openpgp_public_key(_load_vector("rsa-2048-public.asc"))
+        primary = key.primary_key
+        assert isinstance(primary.key, rsa.RSAPublicKey)
+        assert primary.key.key_size == 2048
+        assert primary.version == 4
+        assert primary.fingerprint == bytes.fromhex(
+            "BF0F0231DB6DB118E0EF09F25093B8C20F8AB572"
+        )
+        assert primary.key_id == bytes.fromhex("5093B8C20F8AB572")
+        assert primary.creation_time == datetime.datetime(
+            2026, 10, 16, 14, 55, 1, tzinfo=_UTC
+        )
+        assert key.user_ids == ["RSA Test <rsa@example.com>"]
+
+        [subkey] = key.subkeys
+        assert isinstance(subkey.key, rsa.RSAPublicKey)
+        assert subkey.fingerprint == bytes.fromhex(
+            "6CBE55726C13C5B4F2046052FE84F51151043F34"
+        )
+        assert subkey.creation_time == datetime.datetime(
+            2026, 10, 16, 14, 55, 3, tzinfo=_UTC
+        )
+
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.ed25519_supported()
+        and backend.x25519_supported(),
+        skip_message="Requires OpenSSL with Ed25519 and X25519 support",
+    )
+    def test_ed25519_binary(self, backend):
+        key = load_openpgp_public_key(_load_vector("ed25519-public.gpg"))
+        assert isinstance(key.primary_key.key, ed25519.Ed25519PublicKey)
+        assert key.primary_key.fingerprint == bytes.fromhex(
+            "415CD6AAD3BD6719E03719D2AEBCDCA8948007DC"
+        )
+        assert key.user_ids == [
+            "Ed25519 Test <ed25519@example.com>",
+            "Second UID <second@example.com>",
+        ]
+        [subkey] = key.subkeys
+        assert isinstance(subkey.key, x25519.X25519PublicKey)
+        assert subkey.fingerprint == bytes.fromhex(
+            "9E6BDAAB416390EFD3CA6F8A026CEE13544D1407"
+        )
+
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.ed25519_supported()
+        and backend.x25519_supported(),
+        skip_message="Requires OpenSSL with Ed25519 and X25519 support",
+    )
+    def test_v6(self, backend):
+        key = load_openpgp_public_key(_load_vector("v6-ed25519-public.asc"))
+        primary = key.primary_key
+        assert isinstance(primary.key, ed25519.Ed25519PublicKey)
+        assert primary.version == 6
+        assert primary.fingerprint == bytes.fromhex(
+            "CB186C4F0609A697E4D52DFA6C722B0C"
+            "1F1E27C18A56708F6525EC27BAD9ACC9"
+        )
+        assert primary.key_id == bytes.fromhex("CB186C4F0609A697")
+        assert primary.creation_time == datetime.datetime(
+            2022, 11, 30, 16, 8, 3, tzinfo=_UTC
+        )
+        assert key.user_ids == []
+        [subkey] = key.subkeys
+        assert isinstance(subkey.key, x25519.X25519PublicKey)
+        assert subkey.fingerprint == bytes.fromhex(
+            "12C83F1E706F6308FE151A417743A1F0"
+            "33790E93E9978488D1DB378DA9930885"
+        )
+
+    def test_secret_key(self):
+        with pytest.raises(ValueError):
+            load_openpgp_public_key(_load_vector("rsa-2048-secret.asc"))
+        # Without armor, the secret key packet is found instead.
+        with pytest.raises(ValueError, match="secret key"):
+            load_openpgp_public_key(_packet(5, _RSA_PUBLIC_BODY + b"\x00"))
+
+    def test_corrupt_checksum(self):
+        data = _load_vector("rsa-2048-public.asc")
+        lines = data.splitlines()
+        assert lines[-2].startswith(b"=")
+        lines[-2] = b"=AAAA"
+        with pytest.raises(ValueError, match="checksum"):
+            load_openpgp_public_key(b"\n".join(lines))
+
+    def test_missing_checksum(self):
+        data = _load_vector("rsa-2048-public.asc")
+        lines = [line for line in data.splitlines() if line[:1] != b"="]
+        key = load_openpgp_public_key(b"\n".join(lines))
+        assert key == load_openpgp_public_key(data)
+
+    @pytest.mark.parametrize(
+        "data",
+        [
+            b"",
+            b"invalid",
+            b"-----BEGIN PGP MESSAGE-----\n\n-----END PGP MESSAGE-----\n",
+            b"-----BEGIN PGP PUBLIC KEY BLOCK-----\n\nxioG\n",
+            # A user ID without a key
+            _packet(13, b"Test <test@example.com>"),
+            # A truncated key
+            _packet(6, b"\x04\x00\x00\x00\x00\x01\x00"),
+            # A partial body length
+            b"\xc6\xe0",
+            # Trailing data after the public key material
+            _packet(6, _RSA_PUBLIC_BODY + b"\x00"),
+            # Two primary keys
+            _packet(6, _RSA_PUBLIC_BODY) * 2,
+            # An unexpected packet
+            _packet(6, _RSA_PUBLIC_BODY) + _packet(11, b""),
+        ],
+    )
+    def test_invalid(self, data):
+        with pytest.raises(ValueError):
+            load_openpgp_public_key(data)
+
+    def test_old_format_packets(self):
+        body = _RSA_PUBLIC_BODY
+        # An old format header with a tag of 6 and a two octet length.
+        key = load_openpgp_public_key(
+            b"\x99" + len(body).to_bytes(2, "big") + body
+        )
+        assert key.primary_key.key == RSA_KEY_1024.public_numbers.public_key()
+
+    def test_unsupported_version(self):
+        body = b"\x05" + _RSA_PUBLIC_BODY[1:]
+        with raises_unsupported_algorithm(_Reasons.UNSUPPORTED_SERIALIZATION):
+            load_openpgp_public_key(_packet(6, body))
+
+    def test_unsupported_algorithm(self):
+        # Elgamal
+        body = _v4_key_body(16, _mpi(23) + _mpi(5) + _mpi(8))
+        with raises_unsupported_algorithm(
+            _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+        ):
+            load_openpgp_public_key(_packet(6, body))
+
+    def test_unsupported_curve(self):
+        # ECDSA with an unknown curve OID
+        body = _v4_key_body(19, b"\x03\x2b\x65\x70" + _mpi(4))
+        with raises_unsupported_algorithm(_Reasons.UNSUPPORTED_ELLIPTIC_CURVE):
+            load_openpgp_public_key(_packet(6, body))
+
+
+class TestOpenPGPPrivateKeys:
+    def test_rsa(self):
+        key = load_openpgp_private_key(
+            _load_vector("rsa-2048-secret.asc"), b"password"
+        )
+        public = load_openpgp_public_key(_load_vector("rsa-2048-public.asc"))
+        assert isinstance(key.primary_key.key, rsa.RSAPrivateKey)
+        assert key.primary_key.key.public_key() == public.primary_key.key
+        assert key.primary_key.fingerprint == public.primary_key.fingerprint
+        assert key.user_ids == public.user_ids
+        [subkey] = key.subkeys
+        assert isinstance(subkey.key, rsa.RSAPrivateKey)
+        assert subkey.key.public_key() == public.subkeys[0].key
+        assert subkey.fingerprint == public.subkeys[0].fingerprint
+
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.ed25519_supported()
+        and backend.x25519_supported(),
+        skip_message="Requires OpenSSL with Ed25519 and X25519 support",
+    )
+    def test_ed25519(self, backend):
+        key = load_openpgp_private_key(
+            _load_vector("ed25519-secret.asc"), b"password"
+        )
+        public = load_openpgp_public_key(_load_vector("ed25519-public.gpg"))
+        assert isinstance(key.primary_key.key, ed25519.Ed25519PrivateKey)
+        assert key.primary_key.key.public_key() == public.primary_key.key
+        [subkey] = key.subkeys
+        assert isinstance(subkey.key, x25519.X25519PrivateKey)
+        assert subkey.key.public_key() == public.subkeys[0].key
+
+        signature = key.primary_key.key.sign(b"data")
+        public.primary_key.key.verify(signature, b"data")
+
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.ed25519_supported()
+        and backend.x25519_supported(),
+        skip_message="Requires OpenSSL with Ed25519 and X25519 support",
+    )
+    def test_gnu_dummy_primary_key(self, backend):
+        key = load_openpgp_private_key(
+            _load_vector("ed25519-secret-subkeys.asc"), b"password"
+        )
+        public = load_openpgp_public_key(_load_vector("ed25519-public.gpg"))
+        # The primary key's secret isn't in the file.
+        assert key.primary_key == public.primary_key
+        [subkey] = key.subkeys
+        assert isinstance(subkey.key, x25519.X25519PrivateKey)
+
+    def test_p256(self):
+        key = load_openpgp_private_key(
+            _load_vector("p256-secret.asc"), b"password"
+        )
+        assert isinstance(key.primary_key.key, ec.EllipticCurvePrivateKey)
+        assert isinstance(key.primary_key.key.curve, ec.SECP256R1)
+        assert key.primary_key.fingerprint == bytes.fromhex(
+            "E70CA6BD74632808E215CF435190EF8F324DA481"
+        )
+        [subkey] = key.subkeys
+        assert isinstance(subkey.key, ec.EllipticCurvePrivateKey)
+        assert isinstance(subkey.key.curve, ec.SECP256R1)
+
+    @pytest.mark.parametrize("password", [None, b"password"])
+    def test_unencrypted(self, password):
+        key = load_openpgp_private_key(
+            _load_vector("dsa-2048-secret-unencrypted.asc"), password
+        )
+        assert isinstance(key.primary_key.key, dsa.DSAPrivateKey)
+        assert key.primary_key.key.key_size == 2048
+        assert key.user_ids == ["DSA Test <dsa@example.com>"]
+        assert key.subkeys == []
+
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.ed25519_supported()
+        and backend.x25519_supported(),
+        skip_message="Requires OpenSSL with Ed25519 and X25519 support",
+    )
+    def test_v6(self, backend):
+        key = load_openpgp_private_key(
+            _load_vector("v6-ed25519-secret.asc"), None
+        )
+        public = load_openpgp_public_key(
+            _load_vector("v6-ed25519-public.asc")
+        )
+        assert isinstance(key.primary_key.key, ed25519.Ed25519PrivateKey)
+        assert key.primary_key.key.public_key() == public.primary_key.key
+        assert key.primary_key.fingerprint == public.primary_key.fingerprint
+        assert key.primary_key.version == 6
+        [subkey] = key.subkeys
+        assert isinstance(subkey.key, x25519.X25519PrivateKey)
+        assert subkey.key.public_key() == public.subkeys[0].key
+
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.ed25519_supported()
+        and backend.x25519_supported(),
+        skip_message="Requires OpenSSL with Ed25519 and X25519 support",
+    )
+    @pytest.mark.parametrize(
+        "filename",
+        ["v6-ed25519-secret-ocb.asc", "v6-ed25519-secret-gcm.asc"],
+    )
+    def test_v6_argon2(self, filename, backend):
+        if not ppk._argon2_supported:
+            pytest.skip("Requires argon2-cffi module")
+        if "ocb" in filename and not _ocb_supported():
+            pytest.skip("Requires OpenSSL with AES-OCB3 support")
+
+        key = load_openpgp_private_key(_load_vector(filename), b"password")
+        expected = load_openpgp_private_key(
+            _load_vector("v6-ed25519-secret.asc"), None
+        )
+        assert key.primary_key.fingerprint == expected.primary_key.fingerprint
+        assert key.primary_key.key.private_bytes_raw() == (
+            expected.primary_key.key.private_bytes_raw()
+        )
+        assert key.subkeys[0].key.private_bytes_raw() == (
+            expected.subkeys[0].key.private_bytes_raw()
+        )
+
+        with pytest.raises(ValueError, match="Incorrect password"):
+            load_openpgp_private_key(_load_vector(filename), b"wrong")
+
+    @pytest.mark.parametrize(
+        "filename", ["rsa-2048-secret.asc", "p256-secret.asc"]
+    )
+    def test_wrong_password(self, filename):
+        with pytest.raises(ValueError, match="Incorrect password"):
+            load_openpgp_private_key(_load_vector(filename), b"wrong")
+
+    @pytest.mark.parametrize(
+        "filename", ["rsa-2048-secret.asc", "p256-secret.asc"]
+    )
+    def test_missing_password(self, filename):
+        with pytest.raises(TypeError):
+            load_openpgp_private_key(_load_vector(filename), None)
+
+    def test_public_key(self):
+        with pytest.raises(ValueError):
+            load_openpgp_private_key(
+                _load_vector("rsa-2048-public.asc"), b"password"
+            )
+        with pytest.raises(ValueError, match="secret key"):
+            load_openpgp_private_key(_packet(6, _RSA_PUBLIC_BODY), None)
+
+    def test_public_key_mismatch(self):
+        # An unencrypted RSA key whose secret doesn't match its public key.
+        other = rsa.generate_private_key(65537, 1024).private_numbers()
+        secret = (
+            _mpi(other.d) + _mpi(other.p) + _mpi(other.q) + _mpi(other.iqmp)
+        )
+        checksum = (sum(secret) % 65536).to_bytes(2, "big")
+        body = _RSA_PUBLIC_BODY + b"\x00" + secret + checksum
+        with pytest.raises(ValueError):
+            load_openpgp_private_key(_packet(5, body), None)
+
+    def test_unencrypted_bad_checksum(self):
+        key = ec.derive_private_key(1, ec.SECP256R1())
+        point = key.public_key().public_numbers()
+        encoded_point = (
+            b"\x04" + point.x.to_bytes(32, "big") + point.y.to_bytes(32, "big")
+        )
+        body = _v4_key_body(
+            19,
+            b"\x08\x2a\x86\x48\xce\x3d\x03\x01\x07"
+            + (515).to_bytes(2, "big")
+            + encoded_point,
+        )
+        valid = body + b"\x00" + _mpi(1) + b"\x00\x02"
+        loaded = load_openpgp_private_key(_packet(5, valid), None)
+        assert loaded.primary_key.key.private_numbers().private_value == 1
+
+        invalid = body + b"\x00" + _mpi(1) + b"\x00\x03"
+        with pytest.raises(ValueError, match="checksum"):
+            load_openpgp_private_key(_packet(5, invalid), None)
+
+    def test_unsupported_s2k_usage(self):
+        key = ec.derive_private_key(1, ec.SECP256R1())
+        point = key.public_key().public_numbers()
+        body = _v4_key_body(
+            19,
+            b"\x08\x2a\x86\x48\xce\x3d\x03\x01\x07"
+            + (515).to_bytes(2, "big")
+            + b"\x04"
+            + point.x.to_bytes(32, "big")
+            + point.y.to_bytes(32, "big"),
+        )
+        # A usage octet of 7 is the legacy form of AES-128 with MD5.
+        with raises_unsupported_algorithm(_Reasons.UNSUPPORTED_SERIALIZATION):
+            load_openpgp_private_key(
+                _packet(5, body + b"\x07" + b"\x00" * 16), b"password"
+            )
+
+
+class TestOpenPGPObjects:
+    def test_key_constructor(self):
+        key = ec.generate_private_key(ec.SECP256R1())
+        now = datetime.datetime.now(_UTC)
+        with pytest.raises(TypeError):
+            OpenPGPKey(key, "abc", now, 4)  # type:ignore[arg-type]
+        with pytest.raises(TypeError):
+            OpenPGPKey(key, b"abc", 0, 4)  # type:ignore[arg-type]
+        with pytest.raises(TypeError):
+            OpenPGPKey(key, b"abc", now, "4")  # type:ignore[arg-type]
+
+    def test_key_id(self):
+        key = ec.generate_private_key(ec.SECP256R1())
+        now = datetime.datetime.now(_UTC)
+        fingerprint = bytes(range(32))
+        assert OpenPGPKey(key, fingerprint, now, 4).key_id == bytes(
+            range(24, 32)
+        )
+        assert OpenPGPKey(key, fingerprint, now, 6).key_id == bytes(range(8))
+
+    def test_key_equality(self):
+        key = ec.generate_private_key(ec.SECP256R1())
+        key2 = ec.generate_private_key(ec.SECP256R1())
+        now = datetime.datetime.now(_UTC)
+
+        a = OpenPGPKey(key, b"abc", now, 4)
+        assert a == OpenPGPKey(key, b"abc", now, 4)
+        assert hash(a) == hash(OpenPGPKey(key, b"abc", now, 4))
+        assert a != OpenPGPKey(key2, b"abc", now, 4)
+        assert a != OpenPGPKey(key, b"abd", now, 4)
+        assert a != OpenPGPKey(
+            key, b"abc", now + datetime.timedelta(seconds=1), 4
+        )
+        assert a != OpenPGPKey(key, b"abc", now, 6)
+        assert a != "test"
+
+    def test_key_repr(self):
+        key = ec.generate_private_key(ec.SECP256R1())
+        now = datetime.datetime.now(_UTC)
+        assert repr(OpenPGPKey(key, b"\xab\xcd", now, 4)) == (
+            f"<OpenPGPKey(fingerprint=abcd, version=4, key={key})>"
+        )
+
+    def test_transferable_key_constructor(self):
+        key = OpenPGPKey(
+            ec.generate_private_key(ec.SECP256R1()),
+            b"abc",
+            datetime.datetime.now(_UTC),
+            4,
+        )
+        with pytest.raises(TypeError):
+            OpenPGPTransferableKey("key", [], [])  # type:ignore[arg-type]
+        with pytest.raises(TypeError):
+            OpenPGPTransferableKey(key, [b"uid"], [])  # type:ignore[list-item]
+        with pytest.raises(TypeError):
+            OpenPGPTransferableKey(key, [], ["key"])  # type:ignore[list-item]
+
+    def test_transferable_key_equality(self):
+        now = datetime.datetime.now(_UTC)
+        key = OpenPGPKey(
+            ec.generate_private_key(ec.SECP256R1()), b"abc", now, 4
+        )
+        subkey = OpenPGPKey(
+            ec.generate_private_key(ec.SECP256R1()), b"def", now, 4
+        )
+
+        a = OpenPGPTransferableKey(key, ["uid"], [subkey])
+        assert a == OpenPGPTransferableKey(key, ["uid"], [subkey])
+        assert hash(a) == hash(OpenPGPTransferableKey(key, ["uid"], [subkey]))
+        assert a != OpenPGPTransferableKey(subkey, ["uid"], [subkey])
+        assert a != OpenPGPTransferableKey(key, [], [subkey])
+        assert a != OpenPGPTransferableKey(key, ["uid"], [])
+        assert a != "test"
+
+    def test_transferable_key_repr(self):
+        now = datetime.datetime.now(_UTC)
+        key = OpenPGPKey(
+            ec.generate_private_key(ec.SECP256R1()), b"abc", now, 4
+        )
+        assert repr(OpenPGPTransferableKey(key, ["uid"], [])) == (
+            f"<OpenPGPTransferableKey(primary_key={key}, user_ids=['uid'])>"
+        )
@@ -0,0 +1,120 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+import os
+
+import pytest
+
+from cryptography import x509
+from cryptography.hazmat.primitives import serialization
+from cryptography.hazmat.primitives.asymmetric import ec
+from cryptography.hazmat.primitives.serialization import pem
+
+from ...utils import load_vectors_from_file
+
+
+def _vector(*path):
+    return load_vectors_from_file(
+        os.path.join(*path), lambda f: f.read(), mode="rb"
+    )
+
+
+class TestParseAll:
+    def test_mixed_objects(self, backend):
+        cert = _vector("x509", "cryptography.io.pem")
+        crl = _vector("x509", "custom", "crl_all_reasons.pem")
+        csr = _vector("x509", "requests", "rsa_sha256.pem")
+        p7 = _vector("pkcs7", "isrg.pem")
+        key = ec.generate_private_key(ec.SECP256R1())
+        key_pem = key.private_bytes(
+            serialization.Encoding.PEM,
+            serialization.PrivateFormat.PKCS8,
+            serialization.NoEncryption(),
+        )
+        public_pem = key.public_key().public_bytes(
+            serialization.Encoding.PEM,
+            serialization.PublicFormat.SubjectPublicKeyInfo,
+        )
+
+        objects = pem.parse_all(
+            b"garbage\n" + cert + crl + csr + p7 + key_pem + public_pem
+        )
+        assert [o.label for o in objects] == [
+            "CERTIFICATE",
+            "X509 CRL",
+            "CERTIFICATE REQUEST",
+            "PKCS7",
+            "PRIVATE KEY",
+            "PUBLIC KEY",
+        ]
+        assert objects[0].value == x509.load_pem_x509_certificate(cert)
+        assert objects[0].der == x509.load_pem_x509_certificate(
+            cert
+        ).public_bytes(serialization.Encoding.DER)
+        assert objects[1].value == x509.load_pem_x509_crl(crl)
+        assert objects[2].value == x509.load_pem_x509_csr(csr)
+        assert isinstance(objects[3].value, list)
+        assert len(objects[3].value) == 1
+        assert isinstance(objects[4].value, ec.EllipticCurvePrivateKey)
+        assert objects[4].value.private_numbers() == key.private_numbers()
+        assert objects[5].value == key.public_key()
+        assert all(o.headers == {} for o in objects)
+
+    def test_encrypted_keys(self, backend):
+        key = ec.generate_private_key(ec.SECP256R1())
+        pkcs8 = key.private_bytes(
+            serialization.Encoding.PEM,
+            serialization.PrivateFormat.PKCS8,
+            serialization.BestAvailableEncryption(b"password"),
+        )
+        traditional = key.private_bytes(
+            serialization.Encoding.PEM,
+            serialization.PrivateFormat.TraditionalOpenSSL,
+            serialization.BestAvailableEncryption(b"password"),
+        )
+        unencrypted = key.private_bytes(
+            serialization.Encoding.PEM,
+            serialization.PrivateFormat.TraditionalOpenSSL,
+            serialization.NoEncryption(),
+        )
+
+        objects = pem.parse_all(
+            pkcs8 + traditional + unencrypted, password=b"password"
+        )
+        assert [o.label for o in objects] == [
+            "ENCRYPTED PRIVATE KEY",
+            "EC PRIVATE KEY",
+            "EC PRIVATE KEY",
+        ]
+        assert objects[1].headers["Proc-Type"] == "4,ENCRYPTED"
+        assert "DEK-Info" in objects[1].headers
+        for o in objects:
+            assert o.value.private_numbers() == key.private_numbers()
+
+        with pytest.raises(TypeError):
+            pem.parse_all(pkcs8)
+        with pytest.raises(ValueError):
+            pem.parse_all(traditional, password=b"wrong")
+
+    def test_unknown_label(self):
+        objects = pem.parse_all(
+            b"-----BEGIN FOO-----\nAAAA\n-----END FOO-----\n"
+        )
+        assert len(objects) == 1
+        assert objects[0].label == "FOO"
+        assert objects[0].der == b"\x00\x00\x00"
+        assert objects[0].value is None
+        assert repr(objects[0]) == "<PEMObject(label=FOO)>"
+
+    def test_no_pem(self):
+        with pytest.raises(ValueError):
+            pem.parse_all(b"not pem")
+
+    def test_invalid_object(self):
+        with pytest.raises(ValueError):
+            pem.parse_all(
+                b"-----BEGIN CERTIFICATE-----\n"
+                b"AAAA\n"
+                b"-----END CERTIFICATE-----\n"
+            )
@@ -253,6 +253,99 @@ class TestPKCS12Loading:
         assert pkcs12.additional_certs[1].certificate == cert3
         assert pkcs12.additional_certs[1].friendly_name == name3
 
+    @pytest.mark.parametrize(
+        ("filename", "password"),
+        [
+            ("cert-rc2-key-3des.p12", b"cryptography"),
+            ("no-password.p12", None),
+            ("no-password.p12", b""),
+            # PBES2 encrypted files are still loaded by OpenSSL.
+            ("cert-key-aes256cbc.p12", b"cryptography"),
+        ],
+    )
+    def test_load_pkcs12_allow_legacy(self, filename, password, backend):
+        cert, key = _load_ca(backend)
+        assert isinstance(key, ec.EllipticCurvePrivateKey)
+        parsed_key, parsed_cert, parsed_more_certs = load_vectors_from_file(
+            os.path.join("pkcs12", filename),
+            lambda derfile: load_key_and_certificates(
+                derfile.read(), password, allow_legacy=True
+            ),
+            mode="rb",
+        )
+        assert isinstance(parsed_key, ec.EllipticCurvePrivateKey)
+        assert parsed_cert == cert
+        assert parsed_key.private_numbers() == key.private_numbers()
+        assert parsed_more_certs == []
+
+    @pytest.mark.parametrize(
+        ("name", "name2", "name3", "filename", "password"),
+        [
+            (b"name", b"name2", b"name3", "name-all-no-pwd.p12", None),
+            (b"name", b"name2", b"name3", "name-all-pwd.p12", b"password"),
+            (None, b"name2", None, "name-2-pwd.p12", b"password"),
+            (
+                "☺".encode(),
+                "ä".encode(),
+                "ç".encode(),
+                "name-unicode-pwd.p12",
+                b"password",
+            ),
+        ],
+    )
+    def test_load_object_allow_legacy(
+        self, filename, name, name2, name3, password, backend
+    ):
+        cert, key = _load_ca(backend)
+        cert2 = _load_cert(
+            backend, os.path.join("x509", "cryptography.io.pem")
+        )
+        cert3 = _load_cert(backend, os.path.join("x509", "letsencryptx3.pem"))
+
+        pkcs12 = load_vectors_from_file(
+            os.path.join("pkcs12", filename),
+            lambda derfile: load_pkcs12(
+                derfile.read(), password, allow_legacy=True
+            ),
+            mode="rb",
+        )
+        assert pkcs12.cert is not None
+        assert pkcs12.cert.certificate == cert
+        assert pkcs12.cert.friendly_name == name
+        assert isinstance(pkcs12.key, ec.EllipticCurvePrivateKey)
+        assert pkcs12.key.private_numbers() == key.private_numbers()
+        assert pkcs12.additional_certs == [
+            PKCS12Certificate(cert2, name2),
+            PKCS12Certificate(cert3, name3),
+        ]
+
+    def test_load_object_no_cert_key_allow_legacy(self, backend):
+        pkcs12 = load_vectors_from_file(
+            os.path.join("pkcs12", "no-cert-name-all-pwd.p12"),
+            lambda derfile: load_pkcs12(
+                derfile.read(), b"password", allow_legacy=True
+            ),
+            mode="rb",
+        )
+        assert pkcs12.cert is None
+        assert pkcs12.key is None
+        assert [c.friendly_name for c in pkcs12.additional_certs] == [
+            b"name2",
+            b"name3",
+        ]
+
+    def test_allow_legacy_errors(self, backend):
+        with pytest.raises(ValueError):
+            load_key_and_certificates(b"invalid", b"pass", allow_legacy=True)
+        with pytest.raises(ValueError):
+            load_vectors_from_file(
+                os.path.join("pkcs12", "cert-rc2-key-3des.p12"),
+                lambda derfile: load_key_and_certificates(
+                    derfile.read(), b"invalid", allow_legacy=True
+                ),
+                mode="rb",
+            )
+
 
 def _load_cert(backend, path):
     return load_vectors_from_file(
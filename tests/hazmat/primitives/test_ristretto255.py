@@ -0,0 +1,221 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+
+import binascii
+import copy
+import hashlib
+
+import pytest
+
+from cryptography.hazmat.primitives.asymmetric.ristretto255 import (
+    Ristretto255Element,
+    Ristretto255Scalar,
+)
+
+# RFC 9496 appendix A.1: encodings of multiples of the generator.
+_MULTIPLES = [
+    "0000000000000000000000000000000000000000000000000000000000000000",
+    "e2f2ae0a6abc4e71a884a961c500515f58e30b6aa582dd8db6a65945e08d2d76",
+    "6a493210f7499cd17fecb510ae0cea23a110e8d5b901f8acadd3095c73a3b919",
+    "94741f5d5d52755ece4f23f044ee27d5d1ea1e2bd196b462166b16152a9d0259",
+    "da80862773358b466ffadfe0b3293ab3d9fd53c5ea6c955358f568322daf6a57",
+    "e882b131016b52c1d3337080187cf768423efccbb517bb495ab812c4160ff44e",
+    "f64746d3c92b13050ed8d80236a7f0007c3b3f962f5ba793d19a601ebb1df403",
+    "44f53520926ec81fbd5a387845beb7df85a96a24ece18738bdcfa6a7822a176d",
+    "903293d8f2287ebe10e2374dc1a53e0bc887e592699f02d077d5263cdd55601c",
+    "02622ace8f7303a31cafc63f8fc48fdc16e1c8c8d234b2f0d6685282a9076031",
+    "20706fd788b2720a1ed2a5dad4952b01f413bcf0e7564de8cdc816689e2db95f",
+    "bce83f8ba5dd2fa572864c24ba1810f9522bc6004afe95877ac73241cafdab42",
+    "e4549ee16b9aa03099ca208c67adafcafa4c3f3e4e5303de6026e3ca8ff84460",
+    "aa52e000df2e16f55fb1032fc33bc42742dad6bd5a8fc0be0167436c5948501f",
+    "46376b80f409b29dc2b5f6f0c52591990896e5716f41477cd30085ab7f10301e",
+    "e0c418f7c8d9c4cdd7395b93ea124f3ad99021bb681dfc3302a9d99a2e53e64e",
+]
+
+# RFC 9496 appendix A.2: non-canonical, negative and non-square encodings.
+_INVALID_ENCODINGS = [
+    "00ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+    "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+    "f3ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+    "edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+    "0100000000000000000000000000000000000000000000000000000000000000",
+    "01ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+    "ed57ffd8c914fb201471d1c3d245ce3c746fcbe63a3679d51b6a516ebebe0e20",
+    "26948d35ca62e643e26a83177332e6b6afeb9d08e4268b650f1f5bbd8d81d371",
+]
+
+# RFC 9496 appendix A.3: the SHA-512 hashes of these labels map to these
+# elements.
+_DERIVED = [
+    (
+        b"Ristretto is traditionally a short shot of espresso coffee",
+        "3066f82a1a747d45120d1740f14358531a8f04bbffe6a819f86dfe50f44a0a46",
+    ),
+    (
+        b"made with the normal amount of ground coffee but extracted with",
+        "f26e5b6f7d362d2d2a94c5d0e7602cb4773c95a2e5c31a64f133189fa76ed61b",
+    ),
+    (
+        b"about half the amount of water in the same amount of time",
+        "006ccd2a9e6867e6a2c5cea83d3302cc9de128dd2a9a57dd8ee7b9d7ffe02826",
+    ),
+    (
+        b"by using a finer grind.",
+        "f8f0c87cf237953c5890aec3998169005dae3eca1fbb04548c635953c817f92a",
+    ),
+    (
+        b"This produces a concentrated shot of coffee per volume.",
+        "ae81e7dedf20a497e10c304a765c1767a42d6e06029758d2d7e8ef7cc4c41179",
+    ),
+    (
+        b"Just pulling a normal shot short will produce a weaker shot",
+        "e2705652ff9f5e44d3e841bf1c251cf7dddb77d140870d1ab2ed64f1a9ce8628",
+    ),
+    (
+        b"and is not a Ristretto as some believe.",
+        "80bd07262511cdde4863f8a7434cef696750681cb9510eea557088f76d9e5065",
+    ),
+]
+
+_ORDER = 2**252 + 27742317777372353535851937790883648493
+
+
+def _scalar(n):
+    return Ristretto255Scalar.from_bytes((n % _ORDER).to_bytes(32, "little"))
+
+
+def _int(scalar):
+    return int.from_bytes(scalar.to_bytes(), "little")
+
+
+class TestRistretto255Element:
+    def test_generator_multiples(self, backend):
+        generator = Ristretto255Element.generator()
+        element = Ristretto255Element.identity()
+        for i, encoding in enumerate(_MULTIPLES):
+            data = binascii.unhexlify(encoding)
+            assert element.to_bytes() == data
+            assert Ristretto255Element.from_bytes(data) == element
+            assert generator * _scalar(i) == element
+            element = element + generator
+
+    @pytest.mark.parametrize("encoding", _INVALID_ENCODINGS)
+    def test_invalid_encodings(self, encoding, backend):
+        with pytest.raises(ValueError):
+            Ristretto255Element.from_bytes(binascii.unhexlify(encoding))
+
+    def test_invalid_length(self, backend):
+        with pytest.raises(ValueError):
+            Ristretto255Element.from_bytes(b"\x00" * 31)
+        with pytest.raises(ValueError):
+            Ristretto255Element.from_bytes(b"\x00" * 33)
+        with pytest.raises(ValueError):
+            Ristretto255Element.from_uniform_bytes(b"\x00" * 32)
+
+    @pytest.mark.parametrize(("label", "encoding"), _DERIVED)
+    def test_from_uniform_bytes(self, label, encoding, backend):
+        element = Ristretto255Element.from_uniform_bytes(
+            hashlib.sha512(label).digest()
+        )
+        assert element.to_bytes() == binascii.unhexlify(encoding)
+
+    def test_hash_to_group(self, backend):
+        dst = b"QUUX-V01-CS02-with-ristretto255_XMD:SHA-512_R255MAP_RO_"
+        element = Ristretto255Element.hash_to_group(b"abc", dst)
+        assert element.to_bytes() == binascii.unhexlify(
+            "627b997b104ee62543358e22576c75a98dff9dc5f348d5ab228689735d77b258"
+        )
+        assert Ristretto255Element.hash_to_group(b"abc", dst) == element
+        assert Ristretto255Element.hash_to_group(b"abd", dst) != element
+        assert Ristretto255Element.hash_to_group(b"abc", b"other") != element
+
+    def test_hash_to_group_invalid_dst(self, backend):
+        with pytest.raises(ValueError):
+            Ristretto255Element.hash_to_group(b"abc", b"")
+        with pytest.raises(ValueError):
+            Ristretto255Element.hash_to_group(b"abc", b"a" * 256)
+
+    def test_arithmetic(self, backend):
+        a = Ristretto255Element.from_uniform_bytes(b"\x01" * 64)
+        b = Ristretto255Element.from_uniform_bytes(b"\x02" * 64)
+        identity = Ristretto255Element.identity()
+        assert a + b == b + a
+        assert a + identity == a
+        assert a - a == identity
+        assert -a + a == identity
+        assert (a + b) - b == a
+        k = Ristretto255Scalar.random()
+        assert k * a == a * k
+        assert (a + b) * k == a * k + b * k
+        assert a * _scalar(0) == identity
+        assert a * _scalar(-1) == -a
+
+    def test_equality_and_hash(self, backend):
+        a = Ristretto255Element.from_uniform_bytes(b"\x01" * 64)
+        same = Ristretto255Element.from_bytes(a.to_bytes())
+        # The same element, represented by a different edwards25519 point.
+        other = a + Ristretto255Element.identity()
+        assert a == same == other
+        assert hash(a) == hash(same) == hash(other)
+        assert a != Ristretto255Element.generator()
+        assert a != object()
+
+    def test_invalid_operands(self, backend):
+        element = Ristretto255Element.generator()
+        with pytest.raises(TypeError):
+            element + Ristretto255Scalar.random()  # type: ignore[operator]
+        with pytest.raises(TypeError):
+            element * element  # type: ignore[operator]
+        with pytest.raises(TypeError):
+            element * 2  # type: ignore[operator]
+
+    def test_copy(self, backend):
+        element = Ristretto255Element.generator()
+        assert copy.copy(element) is element
+
+
+class TestRistretto255Scalar:
+    def test_from_bytes(self, backend):
+        data = (_ORDER - 1).to_bytes(32, "little")
+        assert Ristretto255Scalar.from_bytes(data).to_bytes() == data
+        with pytest.raises(ValueError):
+            Ristretto255Scalar.from_bytes(_ORDER.to_bytes(32, "little"))
+        with pytest.raises(ValueError):
+            Ristretto255Scalar.from_bytes(b"\xff" * 32)
+        with pytest.raises(ValueError):
+            Ristretto255Scalar.from_bytes(b"\x00" * 31)
+
+    def test_from_uniform_bytes(self, backend):
+        data = hashlib.sha512(b"scalar").digest()
+        scalar = Ristretto255Scalar.from_uniform_bytes(data)
+        assert _int(scalar) == int.from_bytes(data, "little") % _ORDER
+        with pytest.raises(ValueError):
+            Ristretto255Scalar.from_uniform_bytes(b"\x00" * 32)
+
+    def test_arithmetic(self, backend):
+        a, b = Ristretto255Scalar.random(), Ristretto255Scalar.random()
+        assert _int(a + b) == (_int(a) + _int(b)) % _ORDER
+        assert _int(a - b) == (_int(a) - _int(b)) % _ORDER
+        assert _int(a * b) == (_int(a) * _int(b)) % _ORDER
+        assert _int(-a) == -_int(a) % _ORDER
+        assert a * a.invert() == _scalar(1)
+        generator = Ristretto255Element.generator()
+        assert (a * generator) * b == generator * (a * b)
+
+    def test_invert_zero(self, backend):
+        with pytest.raises(ValueError):
+            _scalar(0).invert()
+
+    def test_random(self, backend):
+        assert Ristretto255Scalar.random() != Ristretto255Scalar.random()
+
+    def test_equality_and_hash(self, backend):
+        a = _scalar(12345)
+        b = _scalar(12345)
+        assert a == b
+        assert hash(a) == hash(b)
+        assert a != _scalar(12346)
+        assert a != object()
+        assert copy.copy(a) is a
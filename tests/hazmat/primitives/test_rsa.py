@@ -10,13 +10,14 @@ import os
 
 import pytest
 
+from cryptography import x509
 from cryptography.exceptions import (
     InvalidSignature,
     UnsupportedAlgorithm,
     _Reasons,
 )
 from cryptography.hazmat.primitives import hashes, serialization
-from cryptography.hazmat.primitives.asymmetric import padding, rsa
+from cryptography.hazmat.primitives.asymmetric import ec, padding, rsa
 from cryptography.hazmat.primitives.asymmetric import utils as asym_utils
 from cryptography.hazmat.primitives.asymmetric.rsa import (
     RSAPrivateNumbers,
@@ -187,6 +188,134 @@ class TestRSA:
         pkey = skey.public_key()
         assert isinstance(pkey.public_numbers(), rsa.RSAPublicNumbers)
 
+    @pytest.mark.supported(
+        only_if=lambda backend: (
+            not backend._lib.CRYPTOGRAPHY_IS_LIBRESSL
+            and not backend._lib.CRYPTOGRAPHY_IS_BORINGSSL
+            and not backend._fips_enabled
+        ),
+        skip_message="Does not support multi-prime RSA keys",
+    )
+    def test_generate_multi_prime(self, backend):
+        key = rsa.generate_private_key(65537, 2048, primes=3)
+        assert key.key_size == 2048
+        numbers = key.private_numbers()
+        assert len(numbers.other_primes) == 1
+        r, d, t = numbers.other_primes[0]
+        assert numbers.p * numbers.q * r == numbers.public_numbers.n
+        assert d == numbers.d % (r - 1)
+        assert t == rsa._modinv(numbers.p * numbers.q, r)
+        assert numbers.private_key() == key
+
+        signature = key.sign(b"msg", padding.PKCS1v15(), hashes.SHA256())
+        key.public_key().verify(
+            signature, b"msg", padding.PKCS1v15(), hashes.SHA256()
+        )
+
+        for fmt in [
+            serialization.PrivateFormat.TraditionalOpenSSL,
+            serialization.PrivateFormat.PKCS8,
+        ]:
+            serialized = key.private_bytes(
+                serialization.Encoding.PEM,
+                fmt,
+                serialization.NoEncryption(),
+            )
+            loaded = serialization.load_pem_private_key(serialized, None)
+            assert isinstance(loaded, rsa.RSAPrivateKey)
+            assert loaded.private_numbers() == numbers
+            assert hash(loaded.private_numbers()) == hash(numbers)
+
+        with pytest.raises(UnsupportedAlgorithm):
+            key.jwk_private_bytes()
+
+    @pytest.mark.supported(
+        only_if=lambda backend: (
+            not backend._lib.CRYPTOGRAPHY_IS_LIBRESSL
+            and not backend._lib.CRYPTOGRAPHY_IS_BORINGSSL
+            and not backend._fips_enabled
+        ),
+        skip_message="Does not support multi-prime RSA keys",
+    )
+    def test_multi_prime_numbers(self, backend):
+        numbers = rsa.generate_private_key(
+            65537, 2048, primes=3
+        ).private_numbers()
+        r, d, t = numbers.other_primes[0]
+        two_prime = RSAPrivateNumbers(
+            numbers.p,
+            numbers.q,
+            numbers.d,
+            numbers.dmp1,
+            numbers.dmq1,
+            numbers.iqmp,
+            numbers.public_numbers,
+        )
+        assert two_prime.other_primes == ()
+        assert two_prime != numbers
+        with pytest.raises(ValueError):
+            two_prime.private_key()
+
+        with pytest.raises(ValueError):
+            RSAPrivateNumbers(
+                numbers.p,
+                numbers.q,
+                numbers.d,
+                numbers.dmp1,
+                numbers.dmq1,
+                numbers.iqmp,
+                numbers.public_numbers,
+                other_primes=[(r + 2, d, t)],
+            ).private_key()
+        with pytest.raises(ValueError):
+            RSAPrivateNumbers(
+                numbers.p,
+                numbers.q,
+                numbers.d,
+                numbers.dmp1,
+                numbers.dmq1,
+                numbers.iqmp,
+                numbers.public_numbers,
+                other_primes=[(numbers.public_numbers.n, d, t)],
+            ).private_key()
+
+    def test_generate_many(self, backend):
+        keys = rsa.generate_private_keys(5, 65537, 2048)
+        assert len(keys) == 5
+        for key in keys:
+            assert isinstance(key, rsa.RSAPrivateKey)
+            assert key.key_size == 2048
+            assert key.public_key().public_numbers().e == 65537
+        assert len({key.public_key().public_numbers().n for key in keys}) == 5
+
+        assert rsa.generate_private_keys(0, 65537, 2048) == []
+
+    def test_generate_many_bad_args(self, backend):
+        with pytest.raises(ValueError):
+            rsa.generate_private_keys(-1, 65537, 2048)
+        with pytest.raises(ValueError):
+            rsa.generate_private_keys(2, 1, 2048)
+        with pytest.raises(ValueError):
+            rsa.generate_private_keys(2, 65537, 256)
+        with pytest.raises(ValueError):
+            rsa.generate_private_keys(2, 65537, 2048, primes=1)
+
+    def test_generate_bad_primes(self, backend):
+        with pytest.raises(ValueError):
+            rsa.generate_private_key(65537, 2048, primes=1)
+
+    @pytest.mark.supported(
+        only_if=lambda backend: (
+            not backend._lib.CRYPTOGRAPHY_IS_LIBRESSL
+            and not backend._lib.CRYPTOGRAPHY_IS_BORINGSSL
+            and not backend._fips_enabled
+        ),
+        skip_message="Does not support multi-prime RSA keys",
+    )
+    def test_generate_too_many_primes(self, backend):
+        with pytest.raises(ValueError):
+            rsa.generate_private_key(65537, 1024, primes=4)
+
     def test_generate_bad_public_exponent(self, backend):
         with pytest.raises(ValueError):
             rsa.generate_private_key(
@@ -275,7 +404,7 @@ class TestRSA:
             ),
         ],
     )
-    def test_load_pss_keys_strips_constraints(self, path, backend):
+    def test_load_pss_keys_keeps_constraints(self, path, backend):
         key = load_vectors_from_file(
             filename=path,
             loader=lambda p: serialization.load_pem_private_key(
@@ -283,14 +412,134 @@ class TestRSA:
             ),
             mode="rb",
         )
-        # These keys have constraints that prohibit PKCS1v15 signing,
-        # but for now we load them without the constraint and test that
-        # it's truly removed by performing a disallowed signature.
+        assert isinstance(key, rsa.RSAPSSPrivateKey)
         assert isinstance(key, rsa.RSAPrivateKey)
-        signature = key.sign(b"whatever", padding.PKCS1v15(), hashes.SHA224())
-        key.public_key().verify(
-            signature, b"whatever", padding.PKCS1v15(), hashes.SHA224()
+        assert isinstance(key.public_key(), rsa.RSAPSSPublicKey)
+        with pytest.raises(ValueError):
+            key.sign(b"whatever", padding.PKCS1v15(), hashes.SHA256())
+        with pytest.raises(ValueError):
+            key.decrypt(b"whatever", padding.PKCS1v15())
+
+        # Signing with the key's own padding is always allowed.
+        pss = key.padding or padding.PSS(
+            padding.MGF1(hashes.SHA256()), padding.PSS.MAX_LENGTH
+        )
+        signature = key.sign(b"whatever", pss, hashes.SHA256())
+        key.public_key().verify(signature, b"whatever", pss, hashes.SHA256())
+        with pytest.raises(ValueError):
+            key.public_key().verify(
+                signature, b"whatever", padding.PKCS1v15(), hashes.SHA256()
+            )
+
+        # PKCS8 keeps the restrictions, the traditional format can't.
+        serialized = key.private_bytes(
+            serialization.Encoding.PEM,
+            serialization.PrivateFormat.PKCS8,
+            serialization.NoEncryption(),
+        )
+        loaded = serialization.load_pem_private_key(serialized, None)
+        assert isinstance(loaded, rsa.RSAPSSPrivateKey)
+        assert loaded == key
+        with pytest.raises(ValueError):
+            key.private_bytes(
+                serialization.Encoding.PEM,
+                serialization.PrivateFormat.TraditionalOpenSSL,
+                serialization.NoEncryption(),
+            )
+
+    @pytest.mark.supported(
+        only_if=lambda backend: (
+            not backend._lib.CRYPTOGRAPHY_IS_BORINGSSL
+            and (
+                not backend._lib.CRYPTOGRAPHY_OPENSSL_LESS_THAN_111E
+                or backend._lib.CRYPTOGRAPHY_IS_LIBRESSL
+                and not backend._lib.CRYPTOGRAPHY_LIBRESSL_LESS_THAN_380
+            )
+        ),
+        skip_message="Does not support RSA PSS loading",
+    )
+    def test_pss_key_restrictions(self, backend):
+        key = load_vectors_from_file(
+            filename=os.path.join(
+                "asymmetric", "PKCS8", "rsa_pss_2048_hash_mask_salt.pem"
+            ),
+            loader=lambda p: serialization.load_pem_private_key(
+                p.read(), password=None, unsafe_skip_rsa_key_validation=True
+            ),
+            mode="rb",
+        )
+        assert isinstance(key, rsa.RSAPSSPrivateKey)
+        assert isinstance(key.hash_algorithm, hashes.SHA256)
+        assert isinstance(key.padding, padding.PSS)
+        assert isinstance(key.padding.mgf, padding.MGF1)
+        assert isinstance(key.padding.mgf._algorithm, hashes.SHA256)
+        assert key.padding._salt_length == 32
+
+        mgf = padding.MGF1(hashes.SHA256())
+        with pytest.raises(ValueError):
+            key.sign(b"whatever", padding.PSS(mgf, 32), hashes.SHA512())
+        with pytest.raises(ValueError):
+            key.sign(
+                b"whatever",
+                padding.PSS(padding.MGF1(hashes.SHA1()), 32),
+                hashes.SHA256(),
+            )
+        with pytest.raises(ValueError):
+            key.sign(b"whatever", padding.PSS(mgf, 20), hashes.SHA256())
+
+        public_key = key.public_key()
+        assert isinstance(public_key.hash_algorithm, hashes.SHA256)
+        signature = key.sign(
+            b"whatever", padding.PSS(mgf, 40), hashes.SHA256()
+        )
+        public_key.verify(
+            signature, b"whatever", padding.PSS(mgf, 40), hashes.SHA256()
+        )
+        assert (
+            public_key.verify_pss(
+                signature, b"whatever", padding.PSS(mgf, 40), hashes.SHA256()
+            )
+            == 40
+        )
+        signer = key.signer(padding.PSS(mgf, 40), hashes.SHA256())
+        verifier = public_key.verifier(padding.PSS(mgf, 40), hashes.SHA256())
+        verifier.verify(signer.sign(b"whatever"), b"whatever")
+        with pytest.raises(ValueError):
+            key.signer(padding.PSS(mgf, 20), hashes.SHA256())
+        with pytest.raises(ValueError):
+            public_key.verifier(padding.PKCS1v15(), hashes.SHA256())
+        with pytest.raises(ValueError):
+            public_key.encryptor(padding.PKCS1v15())
+        with pytest.raises(ValueError):
+            public_key.verify(
+                signature, b"whatever", padding.PSS(mgf, 40), hashes.SHA512()
+            )
+        with pytest.raises(ValueError):
+            public_key.verify(
+                signature,
+                b"whatever",
+                padding.PSS(mgf, padding.PSS.AUTO),
+                hashes.SHA256(),
+            )
+        with pytest.raises(ValueError):
+            public_key.encrypt(b"whatever", padding.PKCS1v15())
+        with pytest.raises(ValueError):
+            public_key.unsafe_raw_encrypt(b"\x00" * 256)
+        with pytest.raises(ValueError):
+            key.unsafe_raw_decrypt(b"\x00" * 256)
+
+        serialized = public_key.public_bytes(
+            serialization.Encoding.DER,
+            serialization.PublicFormat.SubjectPublicKeyInfo,
         )
+        loaded = serialization.load_der_public_key(serialized)
+        assert isinstance(loaded, rsa.RSAPSSPublicKey)
+        assert loaded == public_key
+        assert loaded.padding._salt_length == 32
+        with pytest.raises(ValueError):
+            public_key.public_bytes(
+                serialization.Encoding.DER, serialization.PublicFormat.PKCS1
+            )
 
     @pytest.mark.supported(
         only_if=lambda backend: (
@@ -303,7 +552,7 @@ class TestRSA:
         ),
         skip_message="Does not support RSA PSS loading",
     )
-    def test_load_pss_pub_keys_strips_constraints(self, backend):
+    def test_load_pss_pub_keys(self, backend):
         key = load_vectors_from_file(
             filename=os.path.join(
                 "asymmetric", "PKCS8", "rsa_pss_2048_pub.der"
@@ -313,11 +562,21 @@ class TestRSA:
             ),
             mode="rb",
         )
+        assert isinstance(key, rsa.RSAPSSPublicKey)
         assert isinstance(key, rsa.RSAPublicKey)
-        with pytest.raises(InvalidSignature):
+        assert key.hash_algorithm is None
+        assert key.padding is None
+        with pytest.raises(ValueError):
             key.verify(
                 b"badsig", b"whatever", padding.PKCS1v15(), hashes.SHA256()
             )
+        with pytest.raises(InvalidSignature):
+            key.verify(
+                b"badsig",
+                b"whatever",
+                padding.PSS(padding.MGF1(hashes.SHA256()), 32),
+                hashes.SHA256(),
+            )
 
     @pytest.mark.supported(
         only_if=lambda backend: (
@@ -569,6 +828,37 @@ class TestRSASignature:
         signature = private_key.sign(msg, pss, hash_alg)
         public_key.verify(signature, msg, pss, hash_alg)
 
+    @pytest.mark.parametrize(
+        "hash_alg",
+        [
+            hashes.SHA3_224(),
+            hashes.SHA3_256(),
+            hashes.SHA3_384(),
+            hashes.SHA3_512(),
+        ],
+    )
+    def test_pss_signing_sha3(self, rsa_key_2048, hash_alg, backend):
+        _skip_pss_hash_algorithm_unsupported(backend, hash_alg)
+        if not backend.signature_hash_supported(hash_alg):
+            pytest.skip(f"Does not support {hash_alg.name} signatures.")
+        private_key = rsa_key_2048
+        public_key = private_key.public_key()
+        pss = padding.PSS(
+            mgf=padding.MGF1(hash_alg), salt_length=padding.PSS.DIGEST_LENGTH
+        )
+        msg = b"testing signature"
+        signature = private_key.sign(msg, pss, hash_alg)
+        public_key.verify(signature, msg, pss, hash_alg)
+        assert (
+            public_key.verify_pss(
+                signature,
+                msg,
+                padding.PSS(padding.MGF1(hash_alg), padding.PSS.AUTO),
+                hash_alg,
+            )
+            == hash_alg.digest_size
+        )
+
     @pytest.mark.supported(
         only_if=lambda backend: backend.rsa_padding_supported(
             padding.PSS(
@@ -1420,6 +1710,74 @@ class TestRSAVerification:
         with pytest.raises(ValueError):
             public_key.verify(b"\x00" * 64, data, pkcs, prehashed_alg)
 
+    @pytest.mark.parametrize(
+        "salt_length",
+        [
+            0,
+            20,
+            padding.PSS.DIGEST_LENGTH,
+            padding.PSS.MAX_LENGTH,
+        ],
+    )
+    def test_verify_pss(
+        self, rsa_key_2048: rsa.RSAPrivateKey, salt_length, backend
+    ):
+        public_key = rsa_key_2048.public_key()
+        message = b"one little message"
+        mgf = padding.MGF1(hashes.SHA1())
+        signature = rsa_key_2048.sign(
+            message, padding.PSS(mgf, salt_length), hashes.SHA256()
+        )
+        if salt_length is padding.PSS.DIGEST_LENGTH:
+            expected = 32
+        elif salt_length is padding.PSS.MAX_LENGTH:
+            expected = padding.calculate_max_pss_salt_length(
+                public_key, hashes.SHA256()
+            )
+        else:
+            expected = salt_length
+
+        for pss in [
+            padding.PSS(mgf, padding.PSS.AUTO),
+            padding.PSS(mgf, expected),
+        ]:
+            assert (
+                public_key.verify_pss(signature, message, pss, hashes.SHA256())
+                == expected
+            )
+
+        digest = hashes.Hash(hashes.SHA256())
+        digest.update(message)
+        assert (
+            public_key.verify_pss(
+                signature,
+                digest.finalize(),
+                padding.PSS(mgf, padding.PSS.AUTO),
+                asym_utils.Prehashed(hashes.SHA256()),
+            )
+            == expected
+        )
+
+    def test_verify_pss_invalid(self, rsa_key_2048: rsa.RSAPrivateKey):
+        public_key = rsa_key_2048.public_key()
+        pss = padding.PSS(padding.MGF1(hashes.SHA256()), 32)
+        signature = rsa_key_2048.sign(b"message", pss, hashes.SHA256())
+        with pytest.raises(InvalidSignature):
+            public_key.verify_pss(
+                signature, b"other message", pss, hashes.SHA256()
+            )
+        with pytest.raises(InvalidSignature):
+            public_key.verify_pss(
+                signature,
+                b"message",
+                padding.PSS(padding.MGF1(hashes.SHA256()), 20),
+                hashes.SHA256(),
+            )
+        with pytest.raises(TypeError):
+            public_key.verify_pss(
+                signature, b"message", padding.PKCS1v15(), hashes.SHA256()
+            )
+
 
 class TestRSAPSSMGF1Verification:
     test_rsa_pss_mgf1_sha1 = pytest.mark.supported(
@@ -2100,6 +2458,37 @@ class TestRSAEncryption:
         recovered_pt = private_key.decrypt(ct, pad)
         assert recovered_pt == pt
 
+    @pytest.mark.parametrize(
+        ("mgf1hash", "oaephash"),
+        [
+            (hashes.SHA3_224(), hashes.SHA3_224()),
+            (hashes.SHA3_256(), hashes.SHA3_256()),
+            (hashes.SHA3_384(), hashes.SHA3_384()),
+            (hashes.SHA3_512(), hashes.SHA3_512()),
+            (hashes.SHA256(), hashes.SHA3_256()),
+            (hashes.SHA3_256(), hashes.SHA256()),
+        ],
+    )
+    def test_rsa_encrypt_oaep_sha3(
+        self, rsa_key_2048: rsa.RSAPrivateKey, mgf1hash, oaephash, backend
+    ):
+        pad = padding.OAEP(
+            mgf=padding.MGF1(algorithm=mgf1hash),
+            algorithm=oaephash,
+            label=None,
+        )
+        if not backend.rsa_encryption_supported(pad):
+            pytest.skip(
+                f"Does not support OAEP using {mgf1hash.name} MGF1 "
+                f"or {oaephash.name} hash."
+            )
+        private_key = rsa_key_2048
+        pt = b"encrypt me using sha3 hashes!"
+        public_key = private_key.public_key()
+        ct = public_key.encrypt(pt, pad)
+        assert len(ct) == (public_key.key_size + 7) // 8
+        assert private_key.decrypt(ct, pad) == pt
+
     @pytest.mark.supported(
         only_if=lambda backend: backend.rsa_encryption_supported(
             padding.PKCS1v15()
@@ -2439,6 +2828,36 @@ class TestRSAPrimeFactorRecovery:
         with pytest.raises(ValueError):
             rsa.rsa_recover_prime_factors(34, 3, 7)
 
+    def test_recover_private_numbers(self, subtests):
+        for key in [
+            RSA_KEY_1024,
+            RSA_KEY_1025,
+            RSA_KEY_1536,
+            RSA_KEY_2048,
+        ]:
+            with subtests.test():
+                numbers = rsa.rsa_recover_private_numbers(
+                    key.public_numbers.n,
+                    key.public_numbers.e,
+                    key.d,
+                )
+                assert numbers.p > numbers.q
+                assert sorted([numbers.p, numbers.q]) == sorted(
+                    [key.p, key.q]
+                )
+                assert numbers.d == key.d
+                assert numbers.public_numbers == key.public_numbers
+                assert numbers.dmp1 == rsa.rsa_crt_dmp1(key.d, numbers.p)
+                assert numbers.dmq1 == rsa.rsa_crt_dmq1(key.d, numbers.q)
+                assert numbers.iqmp == rsa.rsa_crt_iqmp(numbers.p, numbers.q)
+                assert numbers.other_primes == ()
+                numbers.private_key()
+
+    @pytest.mark.parametrize(("n", "e", "d"), [(34, 3, 7), (35, 3, 0)])
+    def test_invalid_recover_private_numbers(self, n, e, d):
+        with pytest.raises(ValueError):
+            rsa.rsa_recover_private_numbers(n, e, d)
+
 
 class TestRSAPrivateKeySerialization:
     @pytest.mark.parametrize(
@@ -2799,8 +3218,365 @@ class TestRSAPEMPublicKeySerialization:
         with pytest.raises(TypeError):
             key1 < key2  # type: ignore[operator]
 
+    def test_private_key_equality(self, rsa_key_2048: rsa.RSAPrivateKey):
+        key1 = rsa_key_2048
+        key2 = RSA_KEY_2048.private_key(unsafe_skip_rsa_key_validation=True)
+        key3 = RSA_KEY_2048_ALT.private_key(
+            unsafe_skip_rsa_key_validation=True
+        )
+        # The same key with its primes swapped is still the same key.
+        numbers = RSA_KEY_2048
+        key4 = rsa.RSAPrivateNumbers(
+            p=numbers.q,
+            q=numbers.p,
+            d=numbers.d,
+            dmp1=numbers.dmq1,
+            dmq1=numbers.dmp1,
+            iqmp=rsa.rsa_crt_iqmp(numbers.q, numbers.p),
+            public_numbers=numbers.public_numbers,
+        ).private_key()
+        assert key1 == key2
+        assert key1 == key4
+        assert hash(key1) == hash(key2)
+        assert key1 != key3
+        assert key1 != key1.public_key()
+        assert key1 != object()
+        with pytest.raises(TypeError):
+            key1 < key2  # type: ignore[operator]
+
+    def test_public_key_matches(self, rsa_key_2048: rsa.RSAPrivateKey):
+        other = RSA_KEY_2048_ALT.private_key(
+            unsafe_skip_rsa_key_validation=True
+        )
+        assert rsa_key_2048.public_key_matches(rsa_key_2048.public_key())
+        assert not rsa_key_2048.public_key_matches(other.public_key())
+        assert not rsa_key_2048.public_key_matches(
+            ec.generate_private_key(ec.SECP256R1()).public_key()
+        )
+
+        cert = load_vectors_from_file(
+            os.path.join("x509", "custom", "ca", "rsa_ca.pem"),
+            lambda f: x509.load_pem_x509_certificate(f.read()),
+            mode="rb",
+        )
+        key = load_vectors_from_file(
+            os.path.join("x509", "custom", "ca", "rsa_key.pem"),
+            lambda f: serialization.load_pem_private_key(f.read(), None),
+            mode="rb",
+        )
+        assert isinstance(key, rsa.RSAPrivateKey)
+        assert key.public_key_matches(cert)
+        assert not rsa_key_2048.public_key_matches(cert)
+
     def test_public_key_copy(self, rsa_key_2048: rsa.RSAPrivateKey):
         key1 = rsa_key_2048.public_key()
         key2 = copy.copy(key1)
 
         assert key1 == key2
+
+
+def _replace_numbers(numbers: rsa.RSAPrivateNumbers, **kwargs):
+    values = {
+        "p": numbers.p,
+        "q": numbers.q,
+        "d": numbers.d,
+        "dmp1": numbers.dmp1,
+        "dmq1": numbers.dmq1,
+        "iqmp": numbers.iqmp,
+        "public_numbers": numbers.public_numbers,
+    }
+    values.update(kwargs)
+    return rsa.RSAPrivateNumbers(**values).private_key(
+        unsafe_skip_rsa_key_validation=True
+    )
+
+
+class TestRSAKeyCheck:
+    def test_valid(self, rsa_key_2048: rsa.RSAPrivateKey):
+        result = rsa_key_2048.check()
+        assert isinstance(result, rsa.RSAKeyCheck)
+        assert result.valid
+        assert result.public_exponent_valid
+        assert result.modulus_matches_primes
+        assert result.primes_are_prime
+        assert result.prime_sizes_valid
+        assert result.private_exponent_valid
+        assert result.crt_exponents_valid
+        assert result.crt_coefficients_valid
+        assert repr(result) == (
+            "<RSAKeyCheck(public_exponent_valid=True, "
+            "modulus_matches_primes=True, primes_are_prime=True, "
+            "prime_sizes_valid=True, private_exponent_valid=True, "
+            "crt_exponents_valid=True, crt_coefficients_valid=True)>"
+        )
+
+    @pytest.mark.supported(
+        only_if=lambda backend: (
+            not backend._lib.CRYPTOGRAPHY_IS_LIBRESSL
+            and not backend._lib.CRYPTOGRAPHY_IS_BORINGSSL
+            and not backend._fips_enabled
+        ),
+        skip_message="Does not support multi-prime RSA keys",
+    )
+    def test_valid_multi_prime(self, backend):
+        key = rsa.generate_private_key(65537, 2048, primes=3)
+        assert key.check().valid
+
+    def test_bad_crt_coefficient(self, rsa_key_2048: rsa.RSAPrivateKey):
+        numbers = rsa_key_2048.private_numbers()
+        key = _replace_numbers(numbers, iqmp=numbers.iqmp ^ 2)
+        result = key.check()
+        assert not result.valid
+        assert not result.crt_coefficients_valid
+        assert result.crt_exponents_valid
+        assert result.private_exponent_valid
+        assert result.modulus_matches_primes
+
+    def test_bad_crt_exponent(self, rsa_key_2048: rsa.RSAPrivateKey):
+        numbers = rsa_key_2048.private_numbers()
+        key = _replace_numbers(numbers, dmq1=numbers.dmq1 ^ 2)
+        result = key.check()
+        assert not result.valid
+        assert not result.crt_exponents_valid
+        assert result.crt_coefficients_valid
+        assert result.private_exponent_valid
+
+    def test_bad_private_exponent(self, rsa_key_2048: rsa.RSAPrivateKey):
+        numbers = rsa_key_2048.private_numbers()
+        key = _replace_numbers(numbers, d=numbers.d ^ 2)
+        result = key.check()
+        assert not result.valid
+        assert not result.private_exponent_valid
+        assert not result.crt_exponents_valid
+        assert result.crt_coefficients_valid
+        assert result.primes_are_prime
+
+
+class TestRSARawOperations:
+    def test_round_trip(self, rsa_key_2048: rsa.RSAPrivateKey):
+        public_key = rsa_key_2048.public_key()
+        numbers = public_key.public_numbers()
+        m = int.from_bytes(os.urandom(255), "big")
+        plaintext = m.to_bytes(256, "big")
+
+        ciphertext = public_key.unsafe_raw_encrypt(plaintext)
+        assert len(ciphertext) == 256
+        assert int.from_bytes(ciphertext, "big") == pow(
+            m, numbers.e, numbers.n
+        )
+        assert rsa_key_2048.unsafe_raw_decrypt(ciphertext) == plaintext
+
+    def test_small_values(self, rsa_key_2048: rsa.RSAPrivateKey):
+        public_key = rsa_key_2048.public_key()
+        zero = b"\x00" * 256
+        one = b"\x00" * 255 + b"\x01"
+        assert public_key.unsafe_raw_encrypt(zero) == zero
+        assert public_key.unsafe_raw_encrypt(one) == one
+        assert rsa_key_2048.unsafe_raw_decrypt(zero) == zero
+        assert rsa_key_2048.unsafe_raw_decrypt(one) == one
+
+    def test_invalid_input(self, rsa_key_2048: rsa.RSAPrivateKey):
+        public_key = rsa_key_2048.public_key()
+        n = public_key.public_numbers().n
+        for data in [
+            b"\x01" * 255,
+            b"\x01" * 257,
+            n.to_bytes(256, "big"),
+            b"\xff" * 256,
+        ]:
+            with pytest.raises(ValueError):
+                public_key.unsafe_raw_encrypt(data)
+            with pytest.raises(ValueError):
+                rsa_key_2048.unsafe_raw_decrypt(data)
+
+
+class TestRSAOperationContexts:
+    @pytest.mark.parametrize(
+        "pad",
+        [
+            padding.PKCS1v15(),
+            padding.PSS(
+                mgf=padding.MGF1(hashes.SHA256()),
+                salt_length=padding.PSS.DIGEST_LENGTH,
+            ),
+        ],
+    )
+    def test_sign_verify(self, rsa_key_2048: rsa.RSAPrivateKey, pad):
+        public_key = rsa_key_2048.public_key()
+        signer = rsa_key_2048.signer(pad, hashes.SHA256())
+        verifier = public_key.verifier(pad, hashes.SHA256())
+        assert isinstance(signer, rsa.RSASigner)
+        assert isinstance(verifier, rsa.RSAVerifier)
+        for message in [b"one", b"two", b"three"]:
+            signature = signer.sign(message)
+            public_key.verify(signature, message, pad, hashes.SHA256())
+            verifier.verify(signature, message)
+            verifier.verify(
+                rsa_key_2048.sign(message, pad, hashes.SHA256()), message
+            )
+            with pytest.raises(InvalidSignature):
+                verifier.verify(signature, message + b"x")
+
+    def test_prehashed(self, rsa_key_2048: rsa.RSAPrivateKey):
+        pad = padding.PKCS1v15()
+        prehashed = asym_utils.Prehashed(hashes.SHA256())
+        signer = rsa_key_2048.signer(pad, prehashed)
+        verifier = rsa_key_2048.public_key().verifier(pad, prehashed)
+        for message in [b"one", b"two"]:
+            h = hashes.Hash(hashes.SHA256())
+            h.update(message)
+            digest = h.finalize()
+            signature = signer.sign(digest)
+            assert signature == rsa_key_2048.sign(
+                message, pad, hashes.SHA256()
+            )
+            verifier.verify(signature, digest)
+        with pytest.raises(ValueError):
+            signer.sign(b"too short")
+        with pytest.raises(ValueError):
+            verifier.verify(signature, b"too short")
+
+    def test_invalid_parameters(self, rsa_key_2048: rsa.RSAPrivateKey):
+        public_key = rsa_key_2048.public_key()
+        with pytest.raises(TypeError):
+            rsa_key_2048.signer(padding.PKCS1v15(), None)  # type: ignore
+        with pytest.raises(TypeError):
+            public_key.verifier(padding.PKCS1v15(), object())  # type: ignore
+        with pytest.raises(TypeError):
+            rsa_key_2048.signer(
+                object(),  # type: ignore[arg-type]
+                hashes.SHA256(),
+            )
+        with pytest.raises(ValueError):
+            rsa_key_2048.signer(
+                padding.PSS(
+                    mgf=padding.MGF1(hashes.SHA256()),
+                    salt_length=padding.PSS.AUTO,
+                ),
+                hashes.SHA256(),
+            )
+        with pytest.raises(TypeError):
+            public_key.encryptor(object())  # type: ignore[arg-type]
+
+    def test_encrypt(self, rsa_key_2048: rsa.RSAPrivateKey):
+        pad = padding.OAEP(
+            mgf=padding.MGF1(hashes.SHA256()),
+            algorithm=hashes.SHA256(),
+            label=None,
+        )
+        encryptor = rsa_key_2048.public_key().encryptor(pad)
+        assert isinstance(encryptor, rsa.RSAEncryptor)
+        ciphertexts = set()
+        for plaintext in [b"one", b"two", b"two"]:
+            ciphertext = encryptor.encrypt(plaintext)
+            assert rsa_key_2048.decrypt(ciphertext, pad) == plaintext
+            ciphertexts.add(ciphertext)
+        assert len(ciphertexts) == 3
+        with pytest.raises(ValueError):
+            encryptor.encrypt(b"\x00" * 256)
+
+
+class TestRSABSSA:
+    @pytest.mark.parametrize(
+        "bssa",
+        [
+            rsa.RSABSSA_SHA384_PSS_RANDOMIZED,
+            rsa.RSABSSA_SHA384_PSSZERO_RANDOMIZED,
+            rsa.RSABSSA_SHA384_PSS_DETERMINISTIC,
+            rsa.RSABSSA_SHA384_PSSZERO_DETERMINISTIC,
+        ],
+    )
+    def test_round_trip(self, bssa, rsa_key_2048: rsa.RSAPrivateKey):
+        public_key = rsa_key_2048.public_key()
+        msg = bssa.prepare(b"a message")
+        if bssa.randomized:
+            assert len(msg) == 32 + len(b"a message")
+            assert msg.endswith(b"a message")
+        else:
+            assert msg == b"a message"
+
+        blinded_msg, inv = bssa.blind(public_key, msg)
+        assert len(blinded_msg) == len(inv) == 256
+        blind_sig = bssa.blind_sign(rsa_key_2048, blinded_msg)
+        signature = bssa.finalize(public_key, msg, blind_sig, inv)
+        bssa.verify(public_key, signature, msg)
+        # The result is an ordinary RSASSA-PSS signature.
+        public_key.verify(
+            signature,
+            msg,
+            padding.PSS(padding.MGF1(hashes.SHA384()), bssa.salt_length),
+            hashes.SHA384(),
+        )
+
+        with pytest.raises(InvalidSignature):
+            bssa.verify(public_key, signature, msg + b"x")
+        with pytest.raises(InvalidSignature):
+            bssa.finalize(public_key, msg, blind_sig, blinded_msg)
+
+    def test_deterministic(self, rsa_key_2048: rsa.RSAPrivateKey):
+        bssa = rsa.RSABSSA_SHA384_PSSZERO_DETERMINISTIC
+        public_key = rsa_key_2048.public_key()
+        signatures = set()
+        for _ in range(2):
+            blinded_msg, inv = bssa.blind(public_key, b"a message")
+            blind_sig = bssa.blind_sign(rsa_key_2048, blinded_msg)
+            signatures.add(
+                bssa.finalize(public_key, b"a message", blind_sig, inv)
+            )
+        assert len(signatures) == 1
+
+    def test_attributes(self):
+        bssa = rsa.RSABSSA_SHA384_PSS_RANDOMIZED
+        assert isinstance(bssa.algorithm, hashes.SHA384)
+        assert bssa.salt_length == 48
+        assert bssa.randomized is True
+
+        bssa = rsa.RSABSSA(hashes.SHA256(), 32, randomized=False)
+        assert isinstance(bssa.algorithm, hashes.SHA256)
+        assert bssa.salt_length == 32
+        assert bssa.randomized is False
+
+    def test_invalid_algorithm(self):
+        with pytest.raises(TypeError):
+            rsa.RSABSSA(
+                "sha384",  # type: ignore[arg-type]
+                48,
+                randomized=True,
+            )
+
+    def test_invalid_blind_sign(self, rsa_key_2048: rsa.RSAPrivateKey):
+        bssa = rsa.RSABSSA_SHA384_PSS_RANDOMIZED
+        with pytest.raises(ValueError):
+            bssa.blind_sign(rsa_key_2048, b"\x00" * 255)
+        with pytest.raises(ValueError):
+            bssa.blind_sign(rsa_key_2048, b"\xff" * 256)
+
+    def test_invalid_finalize(self, rsa_key_2048: rsa.RSAPrivateKey):
+        bssa = rsa.RSABSSA_SHA384_PSS_RANDOMIZED
+        public_key = rsa_key_2048.public_key()
+        msg = bssa.prepare(b"a message")
+        blinded_msg, inv = bssa.blind(public_key, msg)
+        blind_sig = bssa.blind_sign(rsa_key_2048, blinded_msg)
+        with pytest.raises(ValueError):
+            bssa.finalize(public_key, msg, blind_sig[1:], inv)
+        with pytest.raises(ValueError):
+            bssa.finalize(public_key, msg, blind_sig, inv[1:])
+
+    def test_key_too_small(self, rsa_key_512: rsa.RSAPrivateKey):
+        bssa = rsa.RSABSSA_SHA384_PSS_RANDOMIZED
+        with pytest.raises(ValueError):
+            bssa.blind(rsa_key_512.public_key(), b"a message")
+
+    def test_wrong_key_type(self):
+        bssa = rsa.RSABSSA_SHA384_PSS_RANDOMIZED
+        key = ec.generate_private_key(ec.SECP256R1())
+        with pytest.raises(TypeError):
+            bssa.blind(
+                key.public_key(),  # type: ignore[arg-type]
+                b"a message",
+            )
+        with pytest.raises(TypeError):
+            bssa.blind_sign(
+                key,  # type: ignore[arg-type]
+                b"\x00" * 256,
+            )
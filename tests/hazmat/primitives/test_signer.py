@@ -0,0 +1,231 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+
+import datetime
+
+import pytest
+
+from cryptography import x509
+from cryptography.hazmat.primitives import hashes, serialization
+from cryptography.hazmat.primitives.asymmetric import ec, ed25519, padding
+from cryptography.hazmat.primitives.asymmetric.signer import ExternalSigner
+from cryptography.hazmat.primitives.serialization import pkcs7
+from cryptography.x509 import ocsp
+from cryptography.x509.oid import NameOID
+
+from .fixtures_rsa import RSA_KEY_2048
+
+_NAME = x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, "Signer")])
+_TIME = datetime.datetime(2020, 1, 1)
+
+
+class _Signer(ExternalSigner):
+    def __init__(self, key):
+        self._key = key
+        self.calls = []
+
+    def public_key(self):
+        return self._key.public_key()
+
+    def sign(self, data, algorithm):
+        self.calls.append(algorithm)
+        if isinstance(self._key, ed25519.Ed25519PrivateKey):
+            return self._key.sign(data)
+        elif isinstance(self._key, ec.EllipticCurvePrivateKey):
+            return self._key.sign(data, ec.ECDSA(algorithm))
+        return self._key.sign(data, padding.PKCS1v15(), algorithm)
+
+
+def _certificate_builder(public_key):
+    return (
+        x509.CertificateBuilder()
+        .subject_name(_NAME)
+        .issuer_name(_NAME)
+        .public_key(public_key)
+        .serial_number(1)
+        .not_valid_before(_TIME)
+        .not_valid_after(_TIME + datetime.timedelta(days=1))
+    )
+
+
+def _crl_builder():
+    return (
+        x509.CertificateRevocationListBuilder()
+        .issuer_name(_NAME)
+        .last_update(_TIME)
+        .next_update(_TIME + datetime.timedelta(days=1))
+    )
+
+
+class TestExternalSigner:
+    def test_certificate_rsa(self, backend):
+        key = RSA_KEY_2048.private_key()
+        signer = _Signer(key)
+        builder = _certificate_builder(key.public_key())
+        cert = builder.sign(signer, hashes.SHA256())
+        # PKCS1v15 signatures are deterministic.
+        assert cert == builder.sign(key, hashes.SHA256())
+        assert len(signer.calls) == 1
+        assert isinstance(signer.calls[0], hashes.SHA256)
+
+    def test_certificate_ec(self, backend):
+        key = ec.generate_private_key(ec.SECP256R1())
+        signer = _Signer(key)
+        cert = _certificate_builder(key.public_key()).sign(
+            signer, hashes.SHA384()
+        )
+        assert isinstance(cert.signature_hash_algorithm, hashes.SHA384)
+        assert (
+            cert.signature_algorithm_oid
+            == x509.SignatureAlgorithmOID.ECDSA_WITH_SHA384
+        )
+        cert.verify_directly_issued_by(cert)
+
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.ed25519_supported(),
+        skip_message="Requires OpenSSL with Ed25519 support",
+    )
+    def test_certificate_ed25519(self, backend):
+        key = ed25519.Ed25519PrivateKey.generate()
+        signer = _Signer(key)
+        builder = _certificate_builder(key.public_key())
+        cert = builder.sign(signer, None)
+        assert cert == builder.sign(key, None)
+        assert signer.calls == [None]
+
+        with pytest.raises(ValueError):
+            builder.sign(signer, hashes.SHA256())
+
+    def test_csr(self, backend):
+        key = ec.generate_private_key(ec.SECP256R1())
+        csr = (
+            x509.CertificateSigningRequestBuilder()
+            .subject_name(_NAME)
+            .sign(_Signer(key), hashes.SHA256())
+        )
+        assert csr.public_key() == key.public_key()
+        assert csr.is_signature_valid
+
+    def test_crl(self, backend):
+        key = RSA_KEY_2048.private_key()
+        builder = _crl_builder()
+        crl = builder.sign(_Signer(key), hashes.SHA256())
+        assert crl == builder.sign(key, hashes.SHA256())
+        assert crl.is_signature_valid(key.public_key())
+
+    def test_ocsp_response(self, backend):
+        key = RSA_KEY_2048.private_key()
+        cert = _certificate_builder(key.public_key()).sign(
+            key, hashes.SHA256()
+        )
+        resp = (
+            ocsp.OCSPResponseBuilder()
+            .add_response(
+                cert,
+                cert,
+                hashes.SHA256(),
+                ocsp.OCSPCertStatus.GOOD,
+                _TIME,
+                None,
+                None,
+                None,
+            )
+            .responder_id(ocsp.OCSPResponderEncoding.HASH, cert)
+            .sign(_Signer(key), hashes.SHA256())
+        )
+        key.public_key().verify(
+            resp.signature,
+            resp.tbs_response_bytes,
+            padding.PKCS1v15(),
+            hashes.SHA256(),
+        )
+
+    def test_ocsp_response_wrong_responder(self, backend):
+        key = RSA_KEY_2048.private_key()
+        cert = _certificate_builder(key.public_key()).sign(
+            key, hashes.SHA256()
+        )
+        builder = (
+            ocsp.OCSPResponseBuilder()
+            .add_response(
+                cert,
+                cert,
+                hashes.SHA256(),
+                ocsp.OCSPCertStatus.GOOD,
+                _TIME,
+                None,
+                None,
+                None,
+            )
+            .responder_id(ocsp.OCSPResponderEncoding.HASH, cert)
+        )
+        other = _Signer(ec.generate_private_key(ec.SECP256R1()))
+        with pytest.raises(ValueError):
+            builder.sign(other, hashes.SHA256())
+
+    def test_pkcs7(self, backend):
+        key = RSA_KEY_2048.private_key()
+        cert = _certificate_builder(key.public_key()).sign(
+            key, hashes.SHA256()
+        )
+        options = [pkcs7.PKCS7Options.NoAttributes]
+
+        def sign(private_key):
+            return (
+                pkcs7.PKCS7SignatureBuilder()
+                .set_data(b"data")
+                .add_signer(cert, private_key, hashes.SHA256())
+                .sign(serialization.Encoding.DER, options)
+            )
+
+        assert sign(_Signer(key)) == sign(key)
+
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.ed25519_supported(),
+        skip_message="Requires OpenSSL with Ed25519 support",
+    )
+    def test_pkcs7_unsupported_key(self, backend):
+        key = ed25519.Ed25519PrivateKey.generate()
+        cert = _certificate_builder(key.public_key()).sign(key, None)
+        with pytest.raises(TypeError):
+            pkcs7.PKCS7SignatureBuilder().set_data(b"data").add_signer(
+                cert,
+                _Signer(key),
+                hashes.SHA256(),
+            )
+
+    def test_rsa_padding(self, backend):
+        key = RSA_KEY_2048.private_key()
+        builder = _certificate_builder(key.public_key())
+        with pytest.raises(TypeError):
+            builder.sign(
+                _Signer(key), hashes.SHA256(), rsa_padding=padding.PKCS1v15()
+            )
+
+    def test_unsupported_public_key(self, backend):
+        class _BadSigner(_Signer):
+            def public_key(self):
+                return object()
+
+        key = ec.generate_private_key(ec.SECP256R1())
+        with pytest.raises(TypeError):
+            _certificate_builder(key.public_key()).sign(
+                _BadSigner(key), hashes.SHA256()
+            )
+
+    def test_sign_returns_non_bytes(self, backend):
+        class _BadSigner(_Signer):
+            def sign(self, data, algorithm):
+                return None
+
+        key = ec.generate_private_key(ec.SECP256R1())
+        with pytest.raises(TypeError):
+            _certificate_builder(key.public_key()).sign(
+                _BadSigner(key), hashes.SHA256()
+            )
+
+    def test_abstract(self):
+        with pytest.raises(TypeError):
+            ExternalSigner()  # type: ignore[abstract]
@@ -0,0 +1,313 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+
+import binascii
+import copy
+import hashlib
+import os
+
+import pytest
+
+from cryptography.exceptions import InvalidSignature, _Reasons
+from cryptography.hazmat.primitives import hashes, serialization
+from cryptography.hazmat.primitives.asymmetric.slhdsa import (
+    SLHDSAParameterSet,
+    SLHDSAPrivateKey,
+    SLHDSAPublicKey,
+)
+
+from ...utils import (
+    load_nist_vectors,
+    load_vectors_from_file,
+    raises_unsupported_algorithm,
+)
+
+
+@pytest.mark.supported(
+    only_if=lambda backend: not backend.slhdsa_supported(),
+    skip_message="Requires OpenSSL without SLH-DSA support",
+)
+def test_slhdsa_unsupported(backend):
+    with raises_unsupported_algorithm(
+        _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+    ):
+        SLHDSAPrivateKey.generate(SLHDSAParameterSet.SLH_DSA_SHA2_128F)
+
+    with raises_unsupported_algorithm(
+        _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+    ):
+        SLHDSAPrivateKey.from_private_bytes(
+            SLHDSAParameterSet.SLH_DSA_SHA2_128F, b"\x00" * 64
+        )
+
+    with raises_unsupported_algorithm(
+        _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+    ):
+        SLHDSAPublicKey.from_public_bytes(
+            SLHDSAParameterSet.SLH_DSA_SHA2_128F, b"\x00" * 32
+        )
+
+
+@pytest.mark.supported(
+    only_if=lambda backend: backend.slhdsa_supported(),
+    skip_message="Requires OpenSSL with SLH-DSA support",
+)
+class TestSLHDSA:
+    @pytest.mark.parametrize(
+        "vector",
+        load_vectors_from_file(
+            os.path.join("asymmetric", "SLHDSA", "sign.txt"),
+            load_nist_vectors,
+        ),
+    )
+    def test_verify_vectors(self, vector, backend):
+        parameter_set = SLHDSAParameterSet(vector["parameter_set"].decode())
+        sk = binascii.unhexlify(vector["sk"])
+        pk = binascii.unhexlify(vector["pk"])
+        message = binascii.unhexlify(vector["msg"])
+        context = binascii.unhexlify(vector["context"])
+        signature = binascii.unhexlify(vector["signature"])
+
+        private_key = SLHDSAPrivateKey.from_private_bytes(parameter_set, sk)
+        assert private_key.parameter_set is parameter_set
+        assert private_key.private_bytes_raw() == sk
+        assert private_key.public_key().public_bytes_raw() == pk
+
+        public_key = SLHDSAPublicKey.from_public_bytes(parameter_set, pk)
+        assert public_key.parameter_set is parameter_set
+        public_key.verify(signature, message, context=context)
+        with pytest.raises(InvalidSignature):
+            public_key.verify(signature, message + b"\x00", context=context)
+        with pytest.raises(InvalidSignature):
+            public_key.verify(signature, message, context=context + b"\x00")
+
+    @pytest.mark.parametrize("parameter_set", list(SLHDSAParameterSet))
+    def test_sign_verify(self, parameter_set, backend):
+        key = SLHDSAPrivateKey.generate(parameter_set)
+        public_key = key.public_key()
+        assert key.parameter_set is parameter_set
+        assert public_key.parameter_set is parameter_set
+
+        signature = key.sign(b"data", context=b"context")
+        public_key.verify(signature, b"data", context=b"context")
+        with pytest.raises(InvalidSignature):
+            public_key.verify(signature, b"data")
+        with pytest.raises(InvalidSignature):
+            public_key.verify(signature, b"other data", context=b"context")
+        with pytest.raises(InvalidSignature):
+            public_key.verify(signature[:-1], b"data", context=b"context")
+
+    def test_sign_without_context(self, backend):
+        key = SLHDSAPrivateKey.generate(SLHDSAParameterSet.SLH_DSA_SHA2_128F)
+        signature = key.sign(b"data")
+        key.public_key().verify(signature, b"data")
+        key.public_key().verify(signature, b"data", context=b"")
+
+        other = SLHDSAPrivateKey.generate(
+            SLHDSAParameterSet.SLH_DSA_SHA2_128F
+        ).public_key()
+        with pytest.raises(InvalidSignature):
+            other.verify(signature, b"data")
+
+    def test_context_too_long(self, backend):
+        key = SLHDSAPrivateKey.generate(SLHDSAParameterSet.SLH_DSA_SHA2_128F)
+        signature = key.sign(b"data", context=b"a" * 255)
+        key.public_key().verify(signature, b"data", context=b"a" * 255)
+        with pytest.raises(ValueError):
+            key.sign(b"data", context=b"a" * 256)
+        with pytest.raises(ValueError):
+            key.public_key().verify(signature, b"data", context=b"a" * 256)
+
+    def test_invalid_parameter_set(self, backend):
+        with pytest.raises(TypeError):
+            SLHDSAPrivateKey.generate(
+                "SLH-DSA-SHA2-128f"  # type: ignore[arg-type]
+            )
+        with pytest.raises(TypeError):
+            SLHDSAPublicKey.from_public_bytes(
+                "SLH-DSA-SHA2-128f",  # type: ignore[arg-type]
+                b"\x00" * 32,
+            )
+
+    def test_invalid_private_bytes_length(self, backend):
+        key = SLHDSAPrivateKey.generate(SLHDSAParameterSet.SLH_DSA_SHA2_128F)
+        data = key.private_bytes_raw()
+        assert key == SLHDSAPrivateKey.from_private_bytes(
+            SLHDSAParameterSet.SLH_DSA_SHA2_128F, bytearray(data)
+        )
+        with pytest.raises(ValueError):
+            SLHDSAPrivateKey.from_private_bytes(
+                SLHDSAParameterSet.SLH_DSA_SHA2_128F, data[:-1]
+            )
+        with pytest.raises(ValueError):
+            SLHDSAPrivateKey.from_private_bytes(
+                SLHDSAParameterSet.SLH_DSA_SHA2_192F, data
+            )
+
+    def test_mismatched_private_bytes(self, backend):
+        data = bytearray(
+            SLHDSAPrivateKey.generate(
+                SLHDSAParameterSet.SLH_DSA_SHA2_128F
+            ).private_bytes_raw()
+        )
+        # The last half of the private key is the public key.
+        data[-1] ^= 1
+        with pytest.raises(ValueError):
+            SLHDSAPrivateKey.from_private_bytes(
+                SLHDSAParameterSet.SLH_DSA_SHA2_128F, bytes(data)
+            )
+
+    def test_invalid_public_bytes_length(self, backend):
+        public_key = SLHDSAPrivateKey.generate(
+            SLHDSAParameterSet.SLH_DSA_SHA2_128F
+        ).public_key()
+        data = public_key.public_bytes_raw()
+        with pytest.raises(ValueError):
+            SLHDSAPublicKey.from_public_bytes(
+                SLHDSAParameterSet.SLH_DSA_SHA2_128F, data[:-1]
+            )
+        with pytest.raises(ValueError):
+            SLHDSAPublicKey.from_public_bytes(
+                SLHDSAParameterSet.SLH_DSA_SHA2_256F, data
+            )
+
+    @pytest.mark.parametrize(
+        ("encoding", "fmt", "encryption", "load_func"),
+        [
+            (
+                serialization.Encoding.PEM,
+                serialization.PrivateFormat.PKCS8,
+                serialization.BestAvailableEncryption(b"password"),
+                serialization.load_pem_private_key,
+            ),
+            (
+                serialization.Encoding.DER,
+                serialization.PrivateFormat.PKCS8,
+                serialization.NoEncryption(),
+                serialization.load_der_private_key,
+            ),
+        ],
+    )
+    @pytest.mark.parametrize(
+        "parameter_set",
+        [
+            SLHDSAParameterSet.SLH_DSA_SHA2_128F,
+            SLHDSAParameterSet.SLH_DSA_SHAKE_256F,
+        ],
+    )
+    def test_round_trip_private_serialization(
+        self, parameter_set, encoding, fmt, encryption, load_func, backend
+    ):
+        key = SLHDSAPrivateKey.generate(parameter_set)
+        serialized = key.private_bytes(encoding, fmt, encryption)
+        password = getattr(encryption, "password", None)
+        loaded_key = load_func(serialized, password)
+        assert isinstance(loaded_key, SLHDSAPrivateKey)
+        assert loaded_key.parameter_set is parameter_set
+        assert loaded_key == key
+        assert loaded_key.private_bytes_raw() == key.private_bytes_raw()
+
+    @pytest.mark.parametrize(
+        "parameter_set",
+        [
+            SLHDSAParameterSet.SLH_DSA_SHA2_128F,
+            SLHDSAParameterSet.SLH_DSA_SHAKE_256F,
+        ],
+    )
+    def test_round_trip_public_serialization(self, parameter_set, backend):
+        public_key = SLHDSAPrivateKey.generate(parameter_set).public_key()
+        der = public_key.public_bytes(
+            serialization.Encoding.DER,
+            serialization.PublicFormat.SubjectPublicKeyInfo,
+        )
+        loaded = serialization.load_der_public_key(der)
+        assert isinstance(loaded, SLHDSAPublicKey)
+        assert loaded.parameter_set is parameter_set
+        assert loaded == public_key
+        pem = public_key.public_bytes(
+            serialization.Encoding.PEM,
+            serialization.PublicFormat.SubjectPublicKeyInfo,
+        )
+        assert serialization.load_pem_public_key(pem) == public_key
+
+        raw = public_key.public_bytes(
+            serialization.Encoding.Raw, serialization.PublicFormat.Raw
+        )
+        assert raw == public_key.public_bytes_raw()
+        assert (
+            SLHDSAPublicKey.from_public_bytes(parameter_set, raw) == public_key
+        )
+
+    def test_raw_private_bytes(self, backend):
+        key = SLHDSAPrivateKey.generate(SLHDSAParameterSet.SLH_DSA_SHAKE_192F)
+        raw = key.private_bytes(
+            serialization.Encoding.Raw,
+            serialization.PrivateFormat.Raw,
+            serialization.NoEncryption(),
+        )
+        assert raw == key.private_bytes_raw()
+        assert len(raw) == 96
+        assert raw[48:] == key.public_key().public_bytes_raw()
+
+    def test_invalid_private_bytes(self, backend):
+        key = SLHDSAPrivateKey.generate(SLHDSAParameterSet.SLH_DSA_SHA2_128F)
+        with pytest.raises(ValueError):
+            key.private_bytes(
+                serialization.Encoding.PEM,
+                serialization.PrivateFormat.OpenSSH,
+                serialization.NoEncryption(),
+            )
+        with pytest.raises(ValueError):
+            key.private_bytes(
+                serialization.Encoding.Raw,
+                serialization.PrivateFormat.Raw,
+                serialization.BestAvailableEncryption(b"password"),
+            )
+        with pytest.raises(ValueError):
+            key.private_bytes(
+                serialization.Encoding.Raw,
+                serialization.PrivateFormat.PKCS8,
+                serialization.NoEncryption(),
+            )
+
+    def test_public_key_matches(self, backend):
+        key = SLHDSAPrivateKey.generate(SLHDSAParameterSet.SLH_DSA_SHA2_128F)
+        assert key.public_key_matches(key.public_key())
+        other = SLHDSAPrivateKey.generate(SLHDSAParameterSet.SLH_DSA_SHA2_128F)
+        assert not key.public_key_matches(other.public_key())
+
+    def test_private_key_equality(self, backend):
+        key = SLHDSAPrivateKey.generate(SLHDSAParameterSet.SLH_DSA_SHA2_128F)
+        other = SLHDSAPrivateKey.generate(SLHDSAParameterSet.SLH_DSA_SHA2_128F)
+        assert key == key
+        assert key != other
+        assert key != object()
+        assert hash(key) == hash(
+            SLHDSAPrivateKey.from_private_bytes(
+                SLHDSAParameterSet.SLH_DSA_SHA2_128F, key.private_bytes_raw()
+            )
+        )
+
+    def test_public_key_equality(self, backend):
+        key1 = SLHDSAPrivateKey.generate(SLHDSAParameterSet.SLH_DSA_SHA2_128F)
+        key2 = SLHDSAPrivateKey.generate(SLHDSAParameterSet.SLH_DSA_SHA2_128F)
+        public_key = key1.public_key()
+        assert public_key == copy.copy(public_key)
+        assert public_key == key1.public_key()
+        assert public_key != key2.public_key()
+        assert public_key != object()
+
+    def test_fingerprint(self, backend):
+        public_key = SLHDSAPrivateKey.generate(
+            SLHDSAParameterSet.SLH_DSA_SHA2_128F
+        ).public_key()
+        der = public_key.public_bytes(
+            serialization.Encoding.DER,
+            serialization.PublicFormat.SubjectPublicKeyInfo,
+        )
+        assert (
+            public_key.fingerprint(hashes.SHA256())
+            == hashlib.sha256(der).digest()
+        )
@@ -0,0 +1,229 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+
+import binascii
+import copy
+import os
+
+import pytest
+
+from cryptography.exceptions import InvalidSignature, _Reasons
+from cryptography.hazmat.primitives import serialization
+from cryptography.hazmat.primitives.asymmetric import ec
+from cryptography.hazmat.primitives.asymmetric.sm2 import (
+    SM2PrivateKey,
+    SM2PublicKey,
+)
+
+from ...utils import (
+    load_nist_vectors,
+    load_vectors_from_file,
+    raises_unsupported_algorithm,
+)
+
+_PUBLIC_POINT = binascii.unhexlify(
+    "042b65b6a49597633d47f36f0ca66bbdbd1a328da8f1059690185bcf2e0d65bc472ff00c"
+    "523ce476eefe95ee26fd47cf72b96fe4db461409293727b561f196dfd8"
+)
+
+
+def _load_private_key() -> SM2PrivateKey:
+    key = load_vectors_from_file(
+        os.path.join("asymmetric", "SM2", "sm2-pkcs8.pem"),
+        lambda f: serialization.load_pem_private_key(f.read(), None),
+        mode="rb",
+    )
+    assert isinstance(key, SM2PrivateKey)
+    return key
+
+
+@pytest.mark.supported(
+    only_if=lambda backend: not backend.sm2_supported(),
+    skip_message="Requires OpenSSL without SM2 support",
+)
+def test_sm2_unsupported(backend):
+    with raises_unsupported_algorithm(
+        _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+    ):
+        SM2PrivateKey.generate()
+
+    with raises_unsupported_algorithm(
+        _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+    ):
+        SM2PublicKey.from_encoded_point(_PUBLIC_POINT)
+
+
+@pytest.mark.supported(
+    only_if=lambda backend: backend.sm2_supported(),
+    skip_message="Requires OpenSSL with SM2 support",
+)
+class TestSM2:
+    @pytest.mark.parametrize(
+        "vector",
+        load_vectors_from_file(
+            os.path.join("asymmetric", "SM2", "sign.txt"),
+            load_nist_vectors,
+        ),
+    )
+    def test_verify_vectors(self, vector, backend):
+        public_key = _load_private_key().public_key()
+        user_id = binascii.unhexlify(vector["userid"])
+        message = binascii.unhexlify(vector["msg"])
+        signature = binascii.unhexlify(vector["signature"])
+        public_key.verify(signature, message, user_id)
+        with pytest.raises(InvalidSignature):
+            public_key.verify(signature, message + b"\x00", user_id)
+        with pytest.raises(InvalidSignature):
+            public_key.verify(signature, message, user_id + b"\x00")
+
+    @pytest.mark.parametrize(
+        "vector",
+        load_vectors_from_file(
+            os.path.join("asymmetric", "SM2", "encrypt.txt"),
+            load_nist_vectors,
+        ),
+    )
+    def test_decrypt_vectors(self, vector, backend):
+        key = _load_private_key()
+        ciphertext = binascii.unhexlify(vector["ciphertext"])
+        assert key.decrypt(ciphertext) == binascii.unhexlify(vector["msg"])
+
+    def test_sign_verify(self, backend):
+        key = SM2PrivateKey.generate()
+        public_key = key.public_key()
+        signature = key.sign(b"data")
+        public_key.verify(signature, b"data")
+        public_key.verify(signature, b"data", b"1234567812345678")
+        with pytest.raises(InvalidSignature):
+            public_key.verify(signature, b"data", b"")
+        with pytest.raises(InvalidSignature):
+            public_key.verify(signature, b"other data")
+        with pytest.raises(InvalidSignature):
+            public_key.verify(b"\x00" * 8, b"data")
+
+        signature = key.sign(b"data", user_id=b"alice@example.com")
+        public_key.verify(signature, b"data", user_id=b"alice@example.com")
+        with pytest.raises(InvalidSignature):
+            public_key.verify(signature, b"data")
+
+    def test_user_id_too_long(self, backend):
+        key = SM2PrivateKey.generate()
+        key.sign(b"data", b"a" * 8191)
+        with pytest.raises(ValueError):
+            key.sign(b"data", b"a" * 8192)
+        with pytest.raises(ValueError):
+            key.public_key().verify(b"", b"data", b"a" * 8192)
+
+    def test_z_value(self, backend):
+        public_key = SM2PublicKey.from_encoded_point(_PUBLIC_POINT)
+        assert public_key.z_value() == binascii.unhexlify(
+            "b3112cf378acbf0fd6e8b5bab395ee146173dd16eaafb6779406c02448cabace"
+        )
+        assert public_key.z_value(b"") == binascii.unhexlify(
+            "b25df3ed6f9838347cfbda06395181a622b6131b627ee8979db962f46ee952ef"
+        )
+
+    def test_encrypt_decrypt(self, backend):
+        key = SM2PrivateKey.generate()
+        ciphertext = key.public_key().encrypt(b"secret message")
+        assert key.decrypt(ciphertext) == b"secret message"
+
+        other = SM2PrivateKey.generate()
+        with pytest.raises(ValueError):
+            other.decrypt(ciphertext)
+        with pytest.raises(ValueError):
+            key.decrypt(b"\x00" * 32)
+
+    def test_from_encoded_point(self, backend):
+        public_key = SM2PublicKey.from_encoded_point(_PUBLIC_POINT)
+        assert public_key == _load_private_key().public_key()
+        assert (
+            public_key.public_bytes(
+                serialization.Encoding.X962,
+                serialization.PublicFormat.UncompressedPoint,
+            )
+            == _PUBLIC_POINT
+        )
+        compressed = public_key.public_bytes(
+            serialization.Encoding.X962,
+            serialization.PublicFormat.CompressedPoint,
+        )
+        assert SM2PublicKey.from_encoded_point(compressed) == public_key
+
+        with pytest.raises(ValueError):
+            SM2PublicKey.from_encoded_point(b"\x04" + b"\x00" * 64)
+
+    def test_load_public_key(self, backend):
+        public_key = load_vectors_from_file(
+            os.path.join("asymmetric", "SM2", "sm2-pub.pem"),
+            lambda f: serialization.load_pem_public_key(f.read()),
+            mode="rb",
+        )
+        assert isinstance(public_key, SM2PublicKey)
+        assert public_key == SM2PublicKey.from_encoded_point(_PUBLIC_POINT)
+
+    def test_traditional_openssl_key(self, backend):
+        # Keys in the SEC1 format decode as EC keys with the SM2 curve, which
+        # are still treated as SM2 keys.
+        key = _load_private_key()
+        der = key.private_bytes(
+            serialization.Encoding.DER,
+            serialization.PrivateFormat.TraditionalOpenSSL,
+            serialization.NoEncryption(),
+        )
+        loaded = serialization.load_der_private_key(der, None)
+        assert isinstance(loaded, SM2PrivateKey)
+        assert not isinstance(loaded, ec.EllipticCurvePrivateKey)
+        assert loaded.public_key() == key.public_key()
+
+    @pytest.mark.parametrize(
+        ("encoding", "fmt", "encryption", "load_func"),
+        [
+            (
+                serialization.Encoding.PEM,
+                serialization.PrivateFormat.PKCS8,
+                serialization.BestAvailableEncryption(b"password"),
+                serialization.load_pem_private_key,
+            ),
+            (
+                serialization.Encoding.DER,
+                serialization.PrivateFormat.PKCS8,
+                serialization.NoEncryption(),
+                serialization.load_der_private_key,
+            ),
+        ],
+    )
+    def test_round_trip_private_serialization(
+        self, encoding, fmt, encryption, load_func, backend
+    ):
+        key = SM2PrivateKey.generate()
+        serialized = key.private_bytes(encoding, fmt, encryption)
+        password = getattr(encryption, "password", None)
+        loaded_key = load_func(serialized, password)
+        assert isinstance(loaded_key, SM2PrivateKey)
+        signature = loaded_key.sign(b"data")
+        key.public_key().verify(signature, b"data")
+
+    def test_invalid_private_bytes(self, backend):
+        key = SM2PrivateKey.generate()
+        with pytest.raises(ValueError):
+            key.private_bytes(
+                serialization.Encoding.PEM,
+                serialization.PrivateFormat.OpenSSH,
+                serialization.NoEncryption(),
+            )
+        with pytest.raises(ValueError):
+            key.private_bytes(
+                serialization.Encoding.Raw,
+                serialization.PrivateFormat.Raw,
+                serialization.NoEncryption(),
+            )
+
+    def test_public_key_equality(self, backend):
+        key1 = SM2PrivateKey.generate().public_key()
+        key2 = SM2PrivateKey.generate().public_key()
+        assert key1 == copy.copy(key1)
+        assert key1 != key2
+        assert key1 != object()
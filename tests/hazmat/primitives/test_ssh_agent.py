@@ -0,0 +1,302 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+
+import base64
+import os
+import socket
+import sys
+import threading
+import typing
+
+import pytest
+
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import (
+    ec,
+    ed25519,
+    padding,
+    rsa,
+)
+from cryptography.hazmat.primitives.asymmetric.utils import (
+    decode_dss_signature,
+)
+from cryptography.hazmat.primitives.serialization import (
+    Encoding,
+    PublicFormat,
+    SSHCertificateBuilder,
+    SSHCertificateType,
+    ssh,
+    ssh_agent,
+)
+
+from ...utils import load_vectors_from_file, raises_unsupported_algorithm
+from .fixtures_rsa import RSA_KEY_2048
+
+_IDENTITIES_ANSWER = ssh_agent._SSH_AGENT_IDENTITIES_ANSWER
+
+pytestmark = pytest.mark.skipif(
+    not hasattr(socket, "AF_UNIX"), reason="Requires Unix sockets"
+)
+
+
+def _key_blob(public_key) -> bytes:
+    data = public_key.public_bytes(Encoding.OpenSSH, PublicFormat.OpenSSH)
+    return base64.b64decode(data.split(b" ")[1])
+
+
+class _FakeAgent:
+    """Answers agent requests with software keys, one per connection."""
+
+    def __init__(self, path: str, keys: list) -> None:
+        self.keys = keys
+        self.sign_flags: list[int] = []
+        self._sock = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+        self._sock.bind(path)
+        self._sock.listen()
+        self._thread = threading.Thread(target=self._serve, daemon=True)
+        self._thread.start()
+
+    def close(self) -> None:
+        self._sock.close()
+
+    def _serve(self) -> None:
+        while True:
+            try:
+                conn, _ = self._sock.accept()
+            except OSError:
+                return
+            with conn:
+                length = int.from_bytes(conn.recv(4), "big")
+                message = b""
+                while len(message) < length:
+                    chunk = conn.recv(length - len(message))
+                    if not chunk:
+                        break
+                    message += chunk
+                conn.sendall(self.frame(self.respond(message)))
+
+    def frame(self, response: bytes) -> bytes:
+        return len(response).to_bytes(4, "big") + response
+
+    def respond(self, message: bytes) -> bytes:
+        if message[0] == ssh_agent._SSH_AGENTC_REQUEST_IDENTITIES:
+            f = ssh._FragList([bytes([_IDENTITIES_ANSWER])])
+            f.put_u32(len(self.keys))
+            for key, comment in self.keys:
+                f.put_sshstr(_key_blob(key.public_key()))
+                f.put_sshstr(comment)
+            return f.tobytes()
+
+        assert message[0] == ssh_agent._SSH_AGENTC_SIGN_REQUEST
+        key_blob, rest = ssh._get_sshstr(memoryview(message)[1:])
+        data, rest = ssh._get_sshstr(rest)
+        flags, _ = ssh._get_u32(rest)
+        self.sign_flags.append(flags)
+        for key, _ in self.keys:
+            if _key_blob(key.public_key()) == key_blob:
+                break
+        else:
+            return bytes([5])
+
+        sig = ssh._FragList()
+        if isinstance(key, ed25519.Ed25519PrivateKey):
+            sig.put_sshstr(b"ssh-ed25519")
+            sig.put_sshstr(key.sign(bytes(data)))
+        elif isinstance(key, ec.EllipticCurvePrivateKey):
+            r, s = decode_dss_signature(
+                key.sign(bytes(data), ec.ECDSA(hashes.SHA256()))
+            )
+            blob = ssh._FragList()
+            blob.put_mpint(r)
+            blob.put_mpint(s)
+            sig.put_sshstr(b"ecdsa-sha2-nistp256")
+            sig.put_sshstr(blob)
+        else:
+            assert isinstance(key, rsa.RSAPrivateKey)
+            sig.put_sshstr(b"rsa-sha2-512")
+            sig.put_sshstr(
+                key.sign(bytes(data), padding.PKCS1v15(), hashes.SHA512())
+            )
+        f = ssh._FragList([bytes([ssh_agent._SSH_AGENT_SIGN_RESPONSE])])
+        f.put_sshstr(sig)
+        return f.tobytes()
+
+
+class _CannedAgent(_FakeAgent):
+    """Sends the same raw bytes in response to any request."""
+
+    def __init__(self, path: str, response: bytes) -> None:
+        self.response = response
+        super().__init__(path, [])
+
+    def respond(self, message: bytes) -> bytes:
+        return self.response
+
+    def frame(self, response: bytes) -> bytes:
+        return response
+
+
+@pytest.fixture
+def agent_path(tmp_path):
+    return str(tmp_path / "agent.sock")
+
+
+@pytest.fixture
+def agent_keys(backend):
+    keys: list[typing.Any] = [
+        (ec.generate_private_key(ec.SECP256R1()), b"ec key"),
+        (
+            RSA_KEY_2048.private_key(unsafe_skip_rsa_key_validation=True),
+            b"rsa key",
+        ),
+    ]
+    if backend.ed25519_supported():
+        keys.append((ed25519.Ed25519PrivateKey.generate(), b"ed25519 key"))
+    return keys
+
+
+@pytest.fixture
+def agent(agent_path, agent_keys):
+    agent = _FakeAgent(agent_path, agent_keys)
+    yield agent
+    agent.close()
+
+
+class TestSSHAgentClient:
+    def test_list_identities(self, agent, agent_path, agent_keys):
+        client = ssh_agent.SSHAgentClient(agent_path)
+        identities = client.list_identities()
+        assert len(identities) == len(agent_keys)
+        for identity, (key, comment) in zip(identities, agent_keys):
+            assert identity.comment == comment
+            assert identity.key_blob == _key_blob(key.public_key())
+            assert identity.public_key() == key.public_key()
+
+    def test_list_identities_empty(self, agent_path):
+        agent = _FakeAgent(agent_path, [])
+        try:
+            client = ssh_agent.SSHAgentClient(agent_path)
+            assert client.list_identities() == []
+        finally:
+            agent.close()
+
+    def test_path_from_environment(self, agent, agent_path, monkeypatch):
+        monkeypatch.setenv("SSH_AUTH_SOCK", agent_path)
+        client = ssh_agent.SSHAgentClient()
+        assert len(client.list_identities()) == len(agent.keys)
+
+    @pytest.mark.skipif(
+        sys.platform == "win32", reason="Uses the default pipe"
+    )
+    def test_no_path(self, monkeypatch):
+        monkeypatch.delenv("SSH_AUTH_SOCK", raising=False)
+        with pytest.raises(ValueError):
+            ssh_agent.SSHAgentClient()
+
+    def test_invalid_path_type(self):
+        with pytest.raises(TypeError):
+            ssh_agent.SSHAgentClient(
+                b"/tmp/agent.sock"  # type: ignore[arg-type]
+            )
+
+    def test_sign(self, agent, agent_path, agent_keys):
+        client = ssh_agent.SSHAgentClient(agent_path)
+        for identity, (key, _) in zip(client.list_identities(), agent_keys):
+            signature = identity.sign(b"data")
+            sig_type, _ = ssh._get_sshstr(memoryview(signature))
+            if isinstance(key, rsa.RSAPrivateKey):
+                assert bytes(sig_type) == b"rsa-sha2-512"
+                assert agent.sign_flags[-1] == 4
+            else:
+                assert bytes(sig_type) == ssh._get_ssh_key_type(key)
+                assert agent.sign_flags[-1] == 0
+
+    def test_sign_refused(self, agent, agent_path):
+        client = ssh_agent.SSHAgentClient(agent_path)
+        identity = client.list_identities()[0]
+        agent.keys = []
+        with pytest.raises(ValueError):
+            identity.sign(b"data")
+
+    @pytest.mark.parametrize(
+        "response",
+        [
+            # SSH_AGENT_FAILURE
+            b"\x00\x00\x00\x01\x05",
+            # Truncated identity list
+            b"\x00\x00\x00\x0a\x0c\x00\x00\x00\x01\x00\x00\x00\x05a",
+            # Trailing data
+            b"\x00\x00\x00\x06\x0c\x00\x00\x00\x00\x00",
+            # Empty message
+            b"\x00\x00\x00\x00",
+            # Message longer than the agent may send
+            b"\x00\x04\x00\x01\x0c",
+            # Connection closed before the whole message arrived
+            b"\x00\x00\x00\x05\x0c",
+            b"\x00\x00",
+        ],
+    )
+    def test_invalid_identities_response(self, agent_path, response):
+        agent = _CannedAgent(agent_path, response)
+        try:
+            client = ssh_agent.SSHAgentClient(agent_path)
+            with pytest.raises(ValueError):
+                client.list_identities()
+        finally:
+            agent.close()
+
+    def test_connection_error(self, agent_path):
+        client = ssh_agent.SSHAgentClient(agent_path)
+        with pytest.raises(OSError):
+            client.list_identities()
+
+    def test_certificate_builder(self, agent, agent_path, agent_keys):
+        public_key = ec.generate_private_key(ec.SECP256R1()).public_key()
+        builder = (
+            SSHCertificateBuilder()
+            .public_key(public_key)
+            .type(SSHCertificateType.USER)
+            .valid_principals([b"alice"])
+            .valid_after(0)
+            .valid_before(2**32)
+        )
+        client = ssh_agent.SSHAgentClient(agent_path)
+        for identity, (key, _) in zip(client.list_identities(), agent_keys):
+            cert = builder.sign(identity)
+            cert.verify_cert_signature()
+            assert cert.signature_key() == key.public_key()
+            assert cert.public_key() == public_key
+
+    def test_dsa_identity(self, agent_path):
+        data = load_vectors_from_file(
+            os.path.join("asymmetric", "OpenSSH", "dsa-nopsw.key.pub"),
+            lambda f: f.read(),
+            mode="rb",
+        )
+        f = ssh._FragList([bytes([_IDENTITIES_ANSWER])])
+        f.put_u32(1)
+        f.put_sshstr(base64.b64decode(data.split(b" ")[1]))
+        f.put_sshstr(b"dsa key")
+        agent = _CannedAgent(agent_path, f.size().to_bytes(4, "big"))
+        agent.response += f.tobytes()
+        try:
+            client = ssh_agent.SSHAgentClient(agent_path)
+            identity = client.list_identities()[0]
+            assert identity.comment == b"dsa key"
+            with raises_unsupported_algorithm(None):
+                identity.public_key()
+            public_key = ec.generate_private_key(ec.SECP256R1()).public_key()
+            builder = (
+                SSHCertificateBuilder()
+                .public_key(public_key)
+                .type(SSHCertificateType.USER)
+                .valid_for_all_principals()
+                .valid_after(0)
+                .valid_before(2**32)
+            )
+            with raises_unsupported_algorithm(None):
+                builder.sign(identity)
+        finally:
+            agent.close()
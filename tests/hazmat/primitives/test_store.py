@@ -0,0 +1,194 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+
+import copy
+import datetime
+
+import pytest
+
+from cryptography import x509
+from cryptography.exceptions import _Reasons
+from cryptography.hazmat.primitives import hashes, serialization
+from cryptography.hazmat.primitives.asymmetric import (
+    ec,
+    ed25519,
+    padding,
+    rsa,
+)
+from cryptography.hazmat.primitives.kdf.hkdf import HKDF
+from cryptography.x509.oid import NameOID
+
+from ...utils import raises_unsupported_algorithm
+from .fixtures_rsa import RSA_KEY_2048
+
+
+def _write_key(tmp_path, key, encryption=None):
+    if encryption is None:
+        encryption = serialization.NoEncryption()
+    path = tmp_path / "key.pem"
+    path.write_bytes(
+        key.private_bytes(
+            serialization.Encoding.PEM,
+            serialization.PrivateFormat.PKCS8,
+            encryption,
+        )
+    )
+    return path.as_uri()
+
+
+@pytest.mark.supported(
+    only_if=lambda backend: not backend.key_uri_supported(),
+    skip_message="Requires OpenSSL without the store API",
+)
+def test_key_uri_unsupported(backend, tmp_path):
+    key = ec.generate_private_key(ec.SECP256R1())
+    with raises_unsupported_algorithm(_Reasons.BACKEND_MISSING_INTERFACE):
+        serialization.load_private_key_from_uri(_write_key(tmp_path, key))
+
+
+@pytest.mark.supported(
+    only_if=lambda backend: backend.key_uri_supported(),
+    skip_message="Requires OpenSSL with the store API",
+)
+class TestLoadPrivateKeyFromURI:
+    def test_rsa(self, backend, tmp_path):
+        key = RSA_KEY_2048.private_key()
+        loaded = serialization.load_private_key_from_uri(
+            _write_key(tmp_path, key)
+        )
+        assert isinstance(loaded, rsa.RSAPrivateKey)
+        assert loaded.key_size == 2048
+        assert loaded.public_key() == key.public_key()
+        assert copy.copy(loaded) is loaded
+
+        pss = padding.PSS(
+            mgf=padding.MGF1(hashes.SHA256()),
+            salt_length=padding.PSS.DIGEST_LENGTH,
+        )
+        signature = loaded.sign(b"data", pss, hashes.SHA256())
+        key.public_key().verify(signature, b"data", pss, hashes.SHA256())
+        signature = loaded.signer(pss, hashes.SHA256()).sign(b"data")
+        key.public_key().verify(signature, b"data", pss, hashes.SHA256())
+
+        oaep = padding.OAEP(
+            mgf=padding.MGF1(hashes.SHA256()),
+            algorithm=hashes.SHA256(),
+            label=None,
+        )
+        ciphertext = key.public_key().encrypt(b"secret", oaep)
+        assert loaded.decrypt(ciphertext, oaep) == b"secret"
+        with pytest.raises(ValueError):
+            loaded.decrypt(ciphertext[:-1], oaep)
+
+    def test_ec(self, backend, tmp_path):
+        key = ec.generate_private_key(ec.SECP384R1())
+        loaded = serialization.load_private_key_from_uri(
+            _write_key(tmp_path, key)
+        )
+        assert isinstance(loaded, ec.EllipticCurvePrivateKey)
+        assert isinstance(loaded.curve, ec.SECP384R1)
+        assert loaded.key_size == 384
+        assert loaded.public_key() == key.public_key()
+
+        signature = loaded.sign(b"data", ec.ECDSA(hashes.SHA384()))
+        key.public_key().verify(signature, b"data", ec.ECDSA(hashes.SHA384()))
+
+        peer = ec.generate_private_key(ec.SECP384R1())
+        shared_key = loaded.exchange(ec.ECDH(), peer.public_key())
+        assert shared_key == peer.exchange(ec.ECDH(), key.public_key())
+        assert loaded.exchange_many(ec.ECDH(), [peer.public_key()]) == [
+            shared_key
+        ]
+        kdf = HKDF(hashes.SHA256(), 32, salt=None, info=None)
+        assert loaded.exchange_and_derive(
+            ec.ECDH(), peer.public_key(), kdf
+        ) == HKDF(hashes.SHA256(), 32, salt=None, info=None).derive(
+            shared_key
+        )
+
+        with raises_unsupported_algorithm(
+            _Reasons.UNSUPPORTED_EXCHANGE_ALGORITHM
+        ):
+            loaded.exchange(ec.ECDH(cofactor=True), peer.public_key())
+        with raises_unsupported_algorithm(
+            _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+        ):
+            loaded.sign(b"data", ec.BIP340Schnorr())
+
+    @pytest.mark.parametrize(
+        "key",
+        [
+            RSA_KEY_2048.private_key(),
+            ec.generate_private_key(ec.SECP256R1()),
+        ],
+    )
+    def test_private_key_material_not_exportable(
+        self, key, backend, tmp_path
+    ):
+        loaded = serialization.load_private_key_from_uri(
+            _write_key(tmp_path, key)
+        )
+        with pytest.raises(ValueError):
+            loaded.private_bytes(
+                serialization.Encoding.PEM,
+                serialization.PrivateFormat.PKCS8,
+                serialization.NoEncryption(),
+            )
+        with pytest.raises(ValueError):
+            loaded.private_numbers()
+        with pytest.raises(ValueError):
+            loaded.jwk_private_bytes()
+
+    def test_pin(self, backend, tmp_path):
+        key = ec.generate_private_key(ec.SECP256R1())
+        uri = _write_key(
+            tmp_path, key, serialization.BestAvailableEncryption(b"1234")
+        )
+        loaded = serialization.load_private_key_from_uri(uri, pin=b"1234")
+        assert loaded.public_key() == key.public_key()
+
+        with pytest.raises(ValueError):
+            serialization.load_private_key_from_uri(uri, pin=b"4321")
+        with pytest.raises(ValueError):
+            serialization.load_private_key_from_uri(uri)
+
+    def test_no_key(self, backend, tmp_path):
+        path = tmp_path / "empty.pem"
+        path.write_bytes(b"")
+        with pytest.raises(ValueError):
+            serialization.load_private_key_from_uri(path.as_uri())
+        with pytest.raises(ValueError):
+            serialization.load_private_key_from_uri(
+                (tmp_path / "missing.pem").as_uri()
+            )
+
+    def test_nul_in_uri(self, backend):
+        with pytest.raises(ValueError):
+            serialization.load_private_key_from_uri("file:///key\x00.pem")
+
+    def test_unsupported_key_type(self, backend, tmp_path):
+        key = ed25519.Ed25519PrivateKey.generate()
+        with raises_unsupported_algorithm(
+            _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+        ):
+            serialization.load_private_key_from_uri(_write_key(tmp_path, key))
+
+    def test_sign_certificate(self, backend, tmp_path):
+        key = ec.generate_private_key(ec.SECP256R1())
+        loaded = serialization.load_private_key_from_uri(
+            _write_key(tmp_path, key)
+        )
+        name = x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, "HSM")])
+        cert = (
+            x509.CertificateBuilder()
+            .subject_name(name)
+            .issuer_name(name)
+            .public_key(loaded.public_key())
+            .serial_number(1)
+            .not_valid_before(datetime.datetime(2020, 1, 1))
+            .not_valid_after(datetime.datetime(2030, 1, 1))
+            .sign(loaded, hashes.SHA256())
+        )
+        cert.verify_directly_issued_by(cert)
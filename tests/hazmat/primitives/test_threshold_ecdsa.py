@@ -0,0 +1,182 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+
+import pytest
+
+from cryptography.exceptions import (
+    AlreadyFinalized,
+    InvalidSignature,
+    UnsupportedAlgorithm,
+)
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import ec, threshold_ecdsa
+from cryptography.hazmat.primitives.asymmetric.utils import (
+    decode_dss_signature,
+)
+
+
+def _keygen(curve):
+    p1 = threshold_ecdsa.Party1KeyGen(curve)
+    p2 = threshold_ecdsa.Party2KeyGen(curve)
+    return p1.finish(p2.message()), p2.finish(p1.message())
+
+
+def _sign(share1, share2, data, algorithm):
+    s1 = share1.sign_session(data, algorithm)
+    s2 = share2.sign_session(data, algorithm)
+    msg2 = s2.message(s1.commitment())
+    msg3 = s1.decommit(msg2)
+    msg4 = s2.partial_signature(msg3)
+    return s1.finish(msg4)
+
+
+@pytest.fixture(scope="module")
+def keygen():
+    return (
+        threshold_ecdsa.Party1KeyGen(ec.SECP256R1()),
+        threshold_ecdsa.Party2KeyGen(ec.SECP256R1()),
+    )
+
+
+@pytest.fixture(scope="module")
+def shares(keygen):
+    p1, p2 = keygen
+    return p1.finish(p2.message()), p2.finish(p1.message())
+
+
+class TestTwoPartyECDSA:
+    def test_keygen(self, shares, backend):
+        share1, share2 = shares
+        assert share1.public_key() == share2.public_key()
+        assert isinstance(share1.public_key().curve, ec.SECP256R1)
+
+    def test_sign(self, shares, backend):
+        share1, share2 = shares
+        algorithm = ec.ECDSA(hashes.SHA256())
+        signature = _sign(share1, share2, b"data", algorithm)
+        share1.public_key().verify(signature, b"data", algorithm)
+        with pytest.raises(InvalidSignature):
+            share1.public_key().verify(signature, b"other", algorithm)
+
+        # Signatures are in the low-S form.
+        _, s = decode_dss_signature(signature)
+        order = int(
+            "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+            16,
+        )
+        assert s <= order // 2
+
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.elliptic_curve_supported(
+            ec.SECP256K1()
+        ),
+        skip_message="Does not support secp256k1",
+    )
+    def test_secp256k1(self, backend):
+        share1, share2 = _keygen(ec.SECP256K1())
+        algorithm = ec.ECDSA(hashes.SHA256())
+        signature = _sign(share1, share2, b"data", algorithm)
+        share2.public_key().verify(signature, b"data", algorithm)
+
+    def test_share_serialization(self, shares, backend):
+        share1, share2 = shares
+        loaded1 = threshold_ecdsa.load_party1_key_share(share1.share_bytes())
+        loaded2 = threshold_ecdsa.load_party2_key_share(share2.share_bytes())
+        assert loaded1.public_key() == share1.public_key()
+        algorithm = ec.ECDSA(hashes.SHA384())
+        signature = _sign(loaded1, loaded2, b"data", algorithm)
+        share1.public_key().verify(signature, b"data", algorithm)
+
+        with pytest.raises(ValueError):
+            threshold_ecdsa.load_party1_key_share(share2.share_bytes())
+        with pytest.raises(ValueError):
+            threshold_ecdsa.load_party2_key_share(share1.share_bytes()[:-1])
+
+    def test_sessions_single_use(self, shares, backend):
+        share1, share2 = shares
+        algorithm = ec.ECDSA(hashes.SHA256())
+        s1 = share1.sign_session(b"data", algorithm)
+        s2 = share2.sign_session(b"data", algorithm)
+        msg2 = s2.message(s1.commitment())
+        with pytest.raises(AlreadyFinalized):
+            s2.message(s1.commitment())
+        msg3 = s1.decommit(msg2)
+        with pytest.raises(AlreadyFinalized):
+            s1.decommit(msg2)
+        msg4 = s2.partial_signature(msg3)
+        with pytest.raises(AlreadyFinalized):
+            s2.partial_signature(msg3)
+        s1.finish(msg4)
+        with pytest.raises(AlreadyFinalized):
+            s1.finish(msg4)
+
+    def test_finish_before_decommit(self, shares, backend):
+        share1, _ = shares
+        s1 = share1.sign_session(b"data", ec.ECDSA(hashes.SHA256()))
+        with pytest.raises(AlreadyFinalized):
+            s1.finish(b"\x06")
+
+    def test_different_messages(self, shares, backend):
+        share1, share2 = shares
+        s1 = share1.sign_session(b"data", ec.ECDSA(hashes.SHA256()))
+        s2 = share2.sign_session(b"other", ec.ECDSA(hashes.SHA256()))
+        msg3 = s1.decommit(s2.message(s1.commitment()))
+        with pytest.raises(ValueError):
+            s1.finish(s2.partial_signature(msg3))
+
+    def test_wrong_decommitment(self, shares, backend):
+        share1, share2 = shares
+        algorithm = ec.ECDSA(hashes.SHA256())
+        s1 = share1.sign_session(b"data", algorithm)
+        other = share1.sign_session(b"data", algorithm)
+        s2 = share2.sign_session(b"data", algorithm)
+        s2.message(s1.commitment())
+        msg3 = other.decommit(
+            share2.sign_session(b"data", algorithm).message(
+                other.commitment()
+            )
+        )
+        with pytest.raises(ValueError):
+            s2.partial_signature(msg3)
+
+    def test_mismatched_curves(self, keygen, backend):
+        p1, _ = keygen
+        p2 = threshold_ecdsa.Party2KeyGen(ec.SECP384R1())
+        with pytest.raises(ValueError):
+            p1.finish(p2.message())
+        with pytest.raises(ValueError):
+            p2.finish(p1.message())
+
+    def test_invalid_messages(self, keygen, backend):
+        p1, p2 = keygen
+        # Wrong message type
+        with pytest.raises(ValueError):
+            p1.finish(p1.message())
+        with pytest.raises(ValueError):
+            p1.finish(p2.message() + b"\x00")
+        # A proof for a different point
+        msg = bytearray(p2.message())
+        msg[-1] ^= 1
+        with pytest.raises(ValueError):
+            p1.finish(bytes(msg))
+
+    def test_invalid_party1_proofs(self, keygen, backend):
+        p1, p2 = keygen
+        message = p1.message()
+        # Corrupt the Paillier key proof, the range proof and the encrypted
+        # share proof.
+        for offset in [1200, len(message) // 2, len(message) - 1]:
+            msg = bytearray(message)
+            msg[offset] ^= 1
+            with pytest.raises(ValueError):
+                p2.finish(bytes(msg))
+
+    def test_unsupported_algorithm(self, shares, backend):
+        share1, _ = shares
+        with pytest.raises(UnsupportedAlgorithm):
+            share1.sign_session(
+                b"data",
+                hashes.SHA256(),  # type: ignore[arg-type]
+            )
@@ -9,7 +9,7 @@ import os
 
 import pytest
 
-from cryptography.exceptions import _Reasons
+from cryptography.exceptions import InvalidSignature, _Reasons
 from cryptography.hazmat.primitives import serialization
 from cryptography.hazmat.primitives.asymmetric.x25519 import (
     X25519PrivateKey,
@@ -96,6 +96,30 @@ class TestX25519Exchange:
         with pytest.raises(ValueError):
             private_key.exchange(public_key)
 
+    def test_exchange_many(self, backend):
+        private_key = X25519PrivateKey.generate()
+        peers = [X25519PrivateKey.generate().public_key() for _ in range(5)]
+        assert private_key.exchange_many(peers) == [
+            private_key.exchange(peer) for peer in peers
+        ]
+        assert private_key.exchange_many([]) == []
+
+    def test_exchange_many_null_shared_key(self, backend):
+        private_key = X25519PrivateKey.generate()
+        public = binascii.unhexlify(
+            "5f9c95bca3508c24b1d0b1559c83ef5b04445cc4581c8e86d8224eddd09f1157"
+        )
+        public_key = X25519PublicKey.from_public_bytes(public)
+        with pytest.raises(ValueError):
+            private_key.exchange_many(
+                [X25519PrivateKey.generate().public_key(), public_key]
+            )
+
+    def test_invalid_type_exchange_many(self, backend):
+        key = X25519PrivateKey.generate()
+        with pytest.raises(TypeError):
+            key.exchange_many([object()])  # type: ignore[list-item]
+
     def test_public_bytes_bad_args(self, backend):
         key = X25519PrivateKey.generate().public_key()
         with pytest.raises(TypeError):
@@ -196,6 +220,62 @@ class TestX25519Exchange:
         with pytest.raises(ValueError):
             X25519PrivateKey.from_private_bytes(b"a" * 33)
 
+    def test_from_scalar(self, backend):
+        scalar = b"\xff" * 32
+        clamped = b"\xf8" + b"\xff" * 30 + b"\x7f"
+        key = X25519PrivateKey.from_scalar(scalar)
+        assert key.private_bytes_raw() == clamped
+        assert key.clamped_scalar() == clamped
+        assert X25519PrivateKey.from_scalar(clamped, clamp=False) == key
+
+        # The unclamped scalar is clamped when it's used.
+        unclamped_key = X25519PrivateKey.from_private_bytes(scalar)
+        assert unclamped_key.private_bytes_raw() == scalar
+        assert unclamped_key.clamped_scalar() == clamped
+        assert unclamped_key.public_key() == key.public_key()
+        peer = X25519PrivateKey.generate().public_key()
+        assert unclamped_key.exchange(peer) == key.exchange(peer)
+
+    def test_from_scalar_invalid(self, backend):
+        with pytest.raises(ValueError):
+            X25519PrivateKey.from_scalar(b"\xff" * 32, clamp=False)
+        with pytest.raises(ValueError):
+            X25519PrivateKey.from_scalar(b"\x00" * 31)
+        with pytest.raises(ValueError):
+            X25519PrivateKey.from_scalar(b"\x00" * 33)
+
+    def test_xeddsa_sign_verify(self, backend):
+        # Enough keys that both signs of the Edwards x coordinate come up.
+        for _ in range(16):
+            key = X25519PrivateKey.generate()
+            public_key = key.public_key()
+            data = os.urandom(32)
+            signature = key.xeddsa_sign(data)
+            assert len(signature) == 64
+            public_key.xeddsa_verify(signature, data)
+            public_key.to_ed25519_public_key().verify(signature, data)
+            # Signing is randomized.
+            assert key.xeddsa_sign(data) != signature
+
+    def test_xeddsa_verify_invalid(self, backend):
+        key = X25519PrivateKey.generate()
+        public_key = key.public_key()
+        signature = key.xeddsa_sign(b"test data")
+        with pytest.raises(InvalidSignature):
+            public_key.xeddsa_verify(signature, b"wrong data")
+        with pytest.raises(InvalidSignature):
+            public_key.xeddsa_verify(signature[:-1], b"test data")
+        tampered = bytes([signature[0] ^ 1]) + signature[1:]
+        with pytest.raises(InvalidSignature):
+            public_key.xeddsa_verify(tampered, b"test data")
+        # s must be less than 2**253.
+        high_s = signature[:-1] + bytes([signature[-1] | 0x80])
+        with pytest.raises(InvalidSignature):
+            public_key.xeddsa_verify(high_s, b"test data")
+        other = X25519PrivateKey.generate().public_key()
+        with pytest.raises(InvalidSignature):
+            other.xeddsa_verify(signature, b"test data")
+
     def test_invalid_private_bytes(self, backend):
         key = X25519PrivateKey.generate()
         with pytest.raises(TypeError):
@@ -355,6 +435,29 @@ def test_public_key_equality(backend):
         key1 < key2  # type: ignore[operator]
 
 
+@pytest.mark.supported(
+    only_if=lambda backend: backend.x25519_supported(),
+    skip_message="Requires OpenSSL with X25519 support",
+)
+def test_private_key_equality(backend):
+    key_bytes = load_vectors_from_file(
+        os.path.join("asymmetric", "X25519", "x25519-pkcs8.der"),
+        lambda derfile: derfile.read(),
+        mode="rb",
+    )
+    key1 = serialization.load_der_private_key(key_bytes, None)
+    key2 = X25519PrivateKey.from_private_bytes(
+        key1.private_bytes_raw()  # type: ignore[union-attr]
+    )
+    key3 = X25519PrivateKey.generate()
+    assert key1 == key2
+    assert hash(key1) == hash(key2)
+    assert key1 != key3
+    assert key1 != object()
+    assert key3.public_key_matches(key3.public_key())
+    assert not key3.public_key_matches(key2.public_key())
+
+
 @pytest.mark.supported(
     only_if=lambda backend: backend.x25519_supported(),
     skip_message="Requires OpenSSL with X25519 support",
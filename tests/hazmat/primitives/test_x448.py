@@ -200,6 +200,30 @@ class TestX448Exchange:
         with pytest.raises(ValueError):
             X448PrivateKey.from_private_bytes(b"a" * 57)
 
+    def test_from_scalar(self, backend):
+        scalar = b"\xff" * 56
+        clamped = b"\xfc" + b"\xff" * 55
+        key = X448PrivateKey.from_scalar(scalar)
+        assert key.private_bytes_raw() == clamped
+        assert key.clamped_scalar() == clamped
+        assert X448PrivateKey.from_scalar(clamped, clamp=False) == key
+
+        # The unclamped scalar is clamped when it's used.
+        unclamped_key = X448PrivateKey.from_private_bytes(scalar)
+        assert unclamped_key.private_bytes_raw() == scalar
+        assert unclamped_key.clamped_scalar() == clamped
+        assert unclamped_key.public_key() == key.public_key()
+        peer = X448PrivateKey.generate().public_key()
+        assert unclamped_key.exchange(peer) == key.exchange(peer)
+
+    def test_from_scalar_invalid(self, backend):
+        with pytest.raises(ValueError):
+            X448PrivateKey.from_scalar(b"\xff" * 56, clamp=False)
+        with pytest.raises(ValueError):
+            X448PrivateKey.from_scalar(b"\x00" * 55)
+        with pytest.raises(ValueError):
+            X448PrivateKey.from_scalar(b"\x00" * 57)
+
     def test_invalid_private_bytes(self, backend):
         key = X448PrivateKey.generate()
         with pytest.raises(TypeError):
@@ -0,0 +1,159 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+
+import binascii
+import copy
+import os
+
+import pytest
+
+from cryptography.exceptions import _Reasons
+from cryptography.hazmat.primitives.asymmetric.xwing import (
+    XWingPrivateKey,
+    XWingPublicKey,
+)
+
+from ...utils import (
+    load_nist_vectors,
+    load_vectors_from_file,
+    raises_unsupported_algorithm,
+)
+
+
+@pytest.mark.supported(
+    only_if=lambda backend: not backend.xwing_supported(),
+    skip_message="Requires OpenSSL without X-Wing support",
+)
+def test_xwing_unsupported(backend):
+    with raises_unsupported_algorithm(
+        _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+    ):
+        XWingPrivateKey.generate()
+
+    with raises_unsupported_algorithm(
+        _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+    ):
+        XWingPrivateKey.from_private_bytes(b"\x00" * 32)
+
+    with raises_unsupported_algorithm(
+        _Reasons.UNSUPPORTED_PUBLIC_KEY_ALGORITHM
+    ):
+        XWingPublicKey.from_public_bytes(b"\x00" * 1216)
+
+
+@pytest.mark.supported(
+    only_if=lambda backend: backend.xwing_supported(),
+    skip_message="Requires OpenSSL with X-Wing support",
+)
+class TestXWing:
+    @pytest.mark.parametrize(
+        "vector",
+        load_vectors_from_file(
+            os.path.join("asymmetric", "XWing", "xwing.txt"),
+            load_nist_vectors,
+        ),
+    )
+    def test_decapsulate_vectors(self, vector, backend):
+        sk = binascii.unhexlify(vector["sk"])
+        pk = binascii.unhexlify(vector["pk"])
+        ct = binascii.unhexlify(vector["ct"])
+        ss = binascii.unhexlify(vector["ss"])
+
+        private_key = XWingPrivateKey.from_private_bytes(sk)
+        assert private_key.private_bytes_raw() == sk
+        assert private_key.public_key().public_bytes_raw() == pk
+        assert private_key.decapsulate(ct) == ss
+
+        public_key = XWingPublicKey.from_public_bytes(pk)
+        assert public_key == private_key.public_key()
+        shared_secret, ciphertext = public_key.encapsulate()
+        assert private_key.decapsulate(ciphertext) == shared_secret
+
+    def test_encapsulate_decapsulate(self, backend):
+        key = XWingPrivateKey.generate()
+        public_key = key.public_key()
+        shared_secret, ciphertext = public_key.encapsulate()
+        assert len(shared_secret) == 32
+        assert len(ciphertext) == 1120
+        assert key.decapsulate(ciphertext) == shared_secret
+        assert key.decapsulate(bytearray(ciphertext)) == shared_secret
+
+        other_secret, other_ciphertext = public_key.encapsulate()
+        assert other_secret != shared_secret
+        assert other_ciphertext != ciphertext
+
+    def test_decapsulate_wrong_key(self, backend):
+        key = XWingPrivateKey.generate()
+        shared_secret, ciphertext = key.public_key().encapsulate()
+        other = XWingPrivateKey.generate()
+        assert other.decapsulate(ciphertext) != shared_secret
+
+    def test_decapsulate_modified_ciphertext(self, backend):
+        key = XWingPrivateKey.generate()
+        shared_secret, ciphertext = key.public_key().encapsulate()
+        # Both the ML-KEM-768 and the X25519 parts are bound to the secret.
+        for i in [0, 1087, 1088, 1119]:
+            modified = bytearray(ciphertext)
+            modified[i] ^= 0x01
+            assert key.decapsulate(bytes(modified)) != shared_secret
+
+    def test_decapsulate_invalid_ciphertext(self, backend):
+        key = XWingPrivateKey.generate()
+        _, ciphertext = key.public_key().encapsulate()
+        with pytest.raises(ValueError):
+            key.decapsulate(ciphertext[:-1])
+        with pytest.raises(ValueError):
+            key.decapsulate(ciphertext + b"\x00")
+        # An X25519 ciphertext of zero is a small order point.
+        with pytest.raises(ValueError):
+            key.decapsulate(ciphertext[:1088] + b"\x00" * 32)
+
+    def test_invalid_private_bytes(self, backend):
+        with pytest.raises(ValueError):
+            XWingPrivateKey.from_private_bytes(b"\x00" * 31)
+        with pytest.raises(ValueError):
+            XWingPrivateKey.from_private_bytes(b"\x00" * 33)
+
+    def test_invalid_public_bytes(self, backend):
+        pk = XWingPrivateKey.generate().public_key().public_bytes_raw()
+        assert len(pk) == 1216
+        with pytest.raises(ValueError):
+            XWingPublicKey.from_public_bytes(pk[:-1])
+        with pytest.raises(ValueError):
+            XWingPublicKey.from_public_bytes(pk + b"\x00")
+        # The ML-KEM-768 coefficients must be less than the modulus.
+        with pytest.raises(ValueError):
+            XWingPublicKey.from_public_bytes(b"\xff" * 1216)
+
+    def test_round_trip(self, backend):
+        key = XWingPrivateKey.generate()
+        loaded = XWingPrivateKey.from_private_bytes(
+            bytearray(key.private_bytes_raw())
+        )
+        assert loaded == key
+        assert loaded.public_key() == key.public_key()
+        public_key = XWingPublicKey.from_public_bytes(
+            key.public_key().public_bytes_raw()
+        )
+        assert public_key == key.public_key()
+
+    def test_private_key_equality(self, backend):
+        key = XWingPrivateKey.generate()
+        other = XWingPrivateKey.generate()
+        assert key == key
+        assert key == XWingPrivateKey.from_private_bytes(
+            key.private_bytes_raw()
+        )
+        assert key != other
+        assert key != object()
+
+    def test_public_key_equality(self, backend):
+        key1 = XWingPrivateKey.generate()
+        key2 = XWingPrivateKey.generate()
+        public_key = key1.public_key()
+        assert public_key == copy.copy(public_key)
+        assert public_key == key1.public_key()
+        assert public_key != key2.public_key()
+        assert public_key != object()
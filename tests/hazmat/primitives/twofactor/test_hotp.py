@@ -82,6 +82,28 @@ class TestHOTP:
         with pytest.raises(InvalidToken):
             hotp.verify(b"123456", counter)
 
+    def test_verify_window(self, backend):
+        secret = b"12345678901234567890"
+        hotp = HOTP(secret, 6, SHA1(), backend)
+
+        assert hotp.verify(b"755224", 0) == 0
+        assert hotp.verify(b"969429", 0, window=3) == 3
+        assert hotp.verify(b"969429", 1, window=5) == 2
+        # The window only looks ahead
+        with pytest.raises(InvalidToken):
+            hotp.verify(b"755224", 1, window=5)
+        with pytest.raises(InvalidToken):
+            hotp.verify(b"338314", 0, window=3)
+        with pytest.raises(InvalidToken):
+            hotp.verify(b"75522", 0, window=3)
+        with pytest.raises(ValueError):
+            hotp.verify(b"755224", 0, window=-1)
+        assert hotp.verify(b"520489", 0, window=10) == 9
+        with pytest.raises(ValueError):
+            hotp.verify(b"755224", 0, window=11)
+        with pytest.raises(ValueError):
+            hotp.verify(b"755224", 0, window=2**64)
+
     def test_length_not_int(self, backend):
         secret = b"12345678901234567890"
 
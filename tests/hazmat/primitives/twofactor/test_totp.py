@@ -7,7 +7,10 @@ import pytest
 
 from cryptography.hazmat.primitives import hashes
 from cryptography.hazmat.primitives.twofactor import InvalidToken
-from cryptography.hazmat.primitives.twofactor.totp import TOTP
+from cryptography.hazmat.primitives.twofactor.totp import (
+    TOTP,
+    SteamGuardTOTP,
+)
 
 from ....utils import load_nist_vectors, load_vectors_from_file
 
@@ -114,6 +117,25 @@ class TestTOTP:
         with pytest.raises(InvalidToken):
             totp.verify(b"12345678", time)
 
+    def test_verify_window(self, backend):
+        secret = b"12345678901234567890"
+        totp = TOTP(secret, 8, hashes.SHA1(), 30, backend)
+        code = totp.generate(59)
+
+        assert totp.verify(code, 59) == 0
+        assert totp.verify(code, 89, window=1) == -1
+        assert totp.verify(code, 29, window=1) == 1
+        assert totp.verify(code, 119, window=2) == -2
+        with pytest.raises(InvalidToken):
+            totp.verify(code, 89)
+        with pytest.raises(InvalidToken):
+            totp.verify(code, 119, window=1)
+        with pytest.raises(ValueError):
+            totp.verify(code, 59, window=-1)
+        assert totp.verify(code, 359, window=10) == -10
+        with pytest.raises(ValueError):
+            totp.verify(code, 59, window=11)
+
     def test_floating_point_time_generate(self, backend):
         secret = b"12345678901234567890"
         time = 59.1
@@ -142,3 +164,24 @@ class TestTOTP:
         totp = TOTP(key, 8, hashes.SHA512(), 30, backend)
         time = 60
         assert totp.generate(time) == b"53049576"
+
+
+@pytest.mark.supported(
+    only_if=lambda backend: backend.hmac_supported(hashes.SHA1()),
+    skip_message="Does not support HMAC-SHA1.",
+)
+class TestSteamGuardTOTP:
+    def test_generate(self, backend):
+        totp = SteamGuardTOTP(b"12345678901234567890")
+        assert totp.generate(0) == b"GG5F5"
+        assert totp.generate(59) == b"PV9M4"
+        assert totp.generate(60.5) == b"B26KJ"
+
+    def test_verify(self, backend):
+        totp = SteamGuardTOTP(b"12345678901234567890")
+        assert totp.verify(b"PV9M4", 59) == 0
+        assert totp.verify(b"PV9M4", 60, window=1) == -1
+        with pytest.raises(InvalidToken):
+            totp.verify(b"PV9M4", 60)
+        with pytest.raises(InvalidToken):
+            totp.verify(b"123456", 59, window=1)
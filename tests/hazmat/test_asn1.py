@@ -0,0 +1,292 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+import datetime
+
+import pytest
+
+from cryptography.hazmat import asn1
+from cryptography.hazmat._oid import ObjectIdentifier
+
+
+class AlgorithmIdentifier(asn1.Sequence):
+    algorithm = asn1.ObjectIdentifier()
+    parameters = asn1.Any(optional=True)
+
+
+class Example(asn1.Sequence):
+    version = asn1.Integer(explicit=0, default=0)
+    serial = asn1.Integer()
+    name = asn1.UTF8String(implicit=1, optional=True)
+    flags = asn1.BitString()
+    items = asn1.SetOf(asn1.OctetString())
+    alg = asn1.Nested(AlgorithmIdentifier)
+
+
+SHA256_WITH_RSA = AlgorithmIdentifier(
+    algorithm=ObjectIdentifier("1.2.840.113549.1.1.11"),
+    parameters=b"\x05\x00",
+)
+SHA256_WITH_RSA_DER = bytes.fromhex("300d06092a864886f70d01010b0500")
+
+
+class TestSequence:
+    def test_roundtrip(self):
+        value = Example(
+            version=2,
+            serial=-129,
+            name="hi",
+            flags=asn1.BitStringValue(b"\x80", 7),
+            items=[b"\x01\x01", b"\x02"],
+            alg=SHA256_WITH_RSA,
+        )
+        der = (
+            bytes.fromhex(
+                "3029a003020102 0202ff7f 81026869 03020780 3107040102040201 01"
+            )
+            + SHA256_WITH_RSA_DER
+        )
+        assert value.encode_der() == der
+
+        decoded = Example.decode_der(der)
+        assert decoded.version == 2
+        assert decoded.serial == -129
+        assert decoded.name == "hi"
+        assert decoded.flags == asn1.BitStringValue(b"\x80", 7)
+        # SET OF elements come back in DER order.
+        assert decoded.items == [b"\x02", b"\x01\x01"]
+        assert decoded.alg == SHA256_WITH_RSA
+
+    def test_defaults_and_optionals(self):
+        value = Example(
+            serial=1,
+            flags=asn1.BitStringValue(b"", 0),
+            items=[],
+            alg=AlgorithmIdentifier(
+                algorithm=ObjectIdentifier("1.3.101.112")
+            ),
+        )
+        assert value.version == 0
+        assert value.name is None
+        der = bytes.fromhex("300f 020101 030100 3100 3005 06032b6570")
+        assert value.encode_der() == der
+        assert Example.decode_der(der) == value
+
+    def test_encoded_default_rejected(self):
+        der = bytes.fromhex(
+            "3014 a003020100 020101 030100 3100 3005 06032b6570"
+        )
+        with pytest.raises(ValueError):
+            Example.decode_der(der)
+
+    def test_unsorted_set_of_rejected(self):
+        der = bytes.fromhex(
+            "3016 020101 030100 3107 04020101 040102 3005 06032b6570"
+        )
+        with pytest.raises(ValueError):
+            Example.decode_der(der)
+
+    def test_missing_required_field(self):
+        with pytest.raises(ValueError):
+            AlgorithmIdentifier.decode_der(b"\x30\x00")
+
+    def test_trailing_data(self):
+        with pytest.raises(ValueError):
+            AlgorithmIdentifier.decode_der(SHA256_WITH_RSA_DER + b"\x00")
+        with pytest.raises(ValueError):
+            AlgorithmIdentifier.decode_der(
+                bytes.fromhex("300f06092a864886f70d01010b05000500")
+            )
+
+    def test_non_der_length(self):
+        with pytest.raises(ValueError):
+            AlgorithmIdentifier.decode_der(
+                bytes.fromhex("3080 06032b6570 0000")
+            )
+        with pytest.raises(ValueError):
+            AlgorithmIdentifier.decode_der(bytes.fromhex("3081 05 06032b6570"))
+
+    def test_constructor(self):
+        with pytest.raises(TypeError):
+            AlgorithmIdentifier()
+        with pytest.raises(TypeError):
+            AlgorithmIdentifier(
+                algorithm=ObjectIdentifier("1.3.101.112"), unknown=1
+            )
+
+    def test_encode_wrong_type(self):
+        class Other(asn1.Sequence):
+            alg = asn1.Nested(AlgorithmIdentifier)
+
+        with pytest.raises(TypeError):
+            Other(alg=Other(alg=SHA256_WITH_RSA)).encode_der()
+
+    def test_eq_repr(self):
+        assert SHA256_WITH_RSA != AlgorithmIdentifier(
+            algorithm=ObjectIdentifier("1.3.101.112")
+        )
+        assert SHA256_WITH_RSA != object()
+        assert repr(
+            AlgorithmIdentifier(algorithm=ObjectIdentifier("1.3.101.112"))
+        ) == (
+            "<AlgorithmIdentifier(algorithm=<ObjectIdentifier(oid=1.3.101.112"
+            ", name=ed25519)>, parameters=None)>"
+        )
+
+    def test_inheritance(self):
+        class Base(asn1.Sequence):
+            number = asn1.Integer()
+
+        class Extended(Base):
+            extra = asn1.Boolean(implicit=0, default=False)
+
+        value = Extended(number=1, extra=True)
+        der = bytes.fromhex("3006 020101 8001ff")
+        assert value.encode_der() == der
+        assert Extended.decode_der(der) == value
+
+
+class TestFields:
+    @pytest.mark.parametrize(
+        ("field", "value", "der"),
+        [
+            (asn1.Boolean(), True, b"\x01\x01\xff"),
+            (asn1.Integer(), 0, b"\x02\x01\x00"),
+            (asn1.Integer(), 128, b"\x02\x02\x00\x80"),
+            (asn1.Integer(), -128, b"\x02\x01\x80"),
+            (asn1.Integer(), 2**64, b"\x02\x09\x01" + b"\x00" * 8),
+            (asn1.Enumerated(), 3, b"\x0a\x01\x03"),
+            (asn1.Null(), None, b"\x05\x00"),
+            (asn1.OctetString(), b"abc", b"\x04\x03abc"),
+            (
+                asn1.ObjectIdentifier(),
+                ObjectIdentifier("1.3.101.112"),
+                b"\x06\x03\x2b\x65\x70",
+            ),
+            (asn1.UTF8String(), "é", b"\x0c\x02\xc3\xa9"),
+            (asn1.PrintableString(), "ab", b"\x13\x02ab"),
+            (asn1.IA5String(), "a@b", b"\x16\x03a@b"),
+            (
+                asn1.UTCTime(),
+                datetime.datetime(2017, 1, 1),
+                b"\x17\x0d170101000000Z",
+            ),
+            (
+                asn1.GeneralizedTime(),
+                datetime.datetime(2050, 1, 1),
+                b"\x18\x0f20500101000000Z",
+            ),
+            (asn1.Any(), b"\x05\x00", b"\x05\x00"),
+            (
+                asn1.SequenceOf(asn1.Integer()),
+                [2, 1],
+                b"\x30\x06\x02\x01\x02\x02\x01\x01",
+            ),
+            (
+                asn1.Integer(implicit=5, tag_class=asn1.TagClass.APPLICATION),
+                1,
+                b"\x45\x01\x01",
+            ),
+            (asn1.Integer(implicit=31), 1, b"\x9f\x1f\x01\x01"),
+            (
+                asn1.Integer(explicit=2, tag_class=asn1.TagClass.PRIVATE),
+                1,
+                b"\xe2\x03\x02\x01\x01",
+            ),
+            (
+                asn1.SequenceOf(asn1.Integer(), implicit=0),
+                [1],
+                b"\xa0\x03\x02\x01\x01",
+            ),
+        ],
+    )
+    def test_roundtrip(self, field, value, der):
+        assert field.encode_der(value) == der
+        assert field.decode_der(der) == value
+
+    @pytest.mark.parametrize(
+        ("field", "der"),
+        [
+            (asn1.Boolean(), b"\x01\x01\x01"),
+            (asn1.Integer(), b"\x02\x02\x00\x01"),
+            (asn1.Integer(), b"\x04\x01\x00"),
+            (asn1.Integer(implicit=0), b"\x02\x01\x00"),
+            (asn1.Integer(implicit=31), b"\x9f\x80\x1f\x01\x01"),
+            (asn1.PrintableString(), b"\x13\x01*"),
+            (asn1.Any(), b""),
+        ],
+    )
+    def test_decode_invalid(self, field, der):
+        with pytest.raises(ValueError):
+            field.decode_der(der)
+
+    @pytest.mark.parametrize(
+        ("field", "value"),
+        [
+            (asn1.PrintableString(), "*"),
+            (asn1.Null(), 0),
+            (asn1.Any(), b"\x05\x00\x05\x00"),
+            (asn1.UTCTime(), datetime.datetime(2050, 1, 1)),
+        ],
+    )
+    def test_encode_invalid(self, field, value):
+        with pytest.raises(ValueError):
+            field.encode_der(value)
+
+    def test_choice(self):
+        field = asn1.Choice(
+            {"number": asn1.Integer(), "text": asn1.UTF8String(explicit=0)}
+        )
+        assert field.encode_der(("number", 1)) == b"\x02\x01\x01"
+        assert field.decode_der(b"\x02\x01\x01") == ("number", 1)
+        assert field.encode_der(("text", "a")) == b"\xa0\x03\x0c\x01a"
+        assert field.decode_der(b"\xa0\x03\x0c\x01a") == ("text", "a")
+
+        with pytest.raises(ValueError):
+            field.encode_der(("unknown", 1))
+        with pytest.raises(ValueError):
+            field.decode_der(b"\x04\x00")
+
+    def test_implicit_choice(self):
+        field = asn1.Choice({"number": asn1.Integer()}, implicit=0)
+        with pytest.raises(ValueError):
+            field.encode_der(("number", 1))
+
+    def test_invalid_options(self):
+        with pytest.raises(ValueError):
+            asn1.Integer(implicit=0, explicit=1)
+        with pytest.raises(ValueError):
+            asn1.Integer(implicit=-1)
+        with pytest.raises(ValueError):
+            asn1.Integer(optional=True, default=0)
+        with pytest.raises(TypeError):
+            asn1.Integer(implicit=0, tag_class=2)  # type: ignore[arg-type]
+        with pytest.raises(TypeError):
+            asn1.SequenceOf(int)  # type: ignore[arg-type]
+        with pytest.raises(ValueError):
+            asn1.Choice({})
+        with pytest.raises(TypeError):
+            asn1.Choice({"a": int})  # type: ignore[dict-item]
+        with pytest.raises(TypeError):
+            asn1.Nested(int)  # type: ignore[arg-type]
+
+
+class TestBitStringValue:
+    def test_invalid(self):
+        with pytest.raises(TypeError):
+            asn1.BitStringValue("abc", 0)  # type: ignore[arg-type]
+        with pytest.raises(ValueError):
+            asn1.BitStringValue(b"\x00", 8)
+        with pytest.raises(ValueError):
+            asn1.BitStringValue(b"", 1)
+
+    def test_eq_hash_repr(self):
+        value = asn1.BitStringValue(b"\x80", 7)
+        assert value == asn1.BitStringValue(b"\x80", 7)
+        assert value != asn1.BitStringValue(b"\x80", 6)
+        assert value != object()
+        assert hash(value) == hash(asn1.BitStringValue(b"\x80", 7))
+        assert repr(value) == (
+            "<BitStringValue(data=b'\\x80', padding_bits=7)>"
+        )
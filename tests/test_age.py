@@ -0,0 +1,327 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+
+import os
+
+import pytest
+
+from cryptography import age
+from cryptography.exceptions import (
+    AlreadyFinalized,
+    InvalidTag,
+    UnsupportedAlgorithm,
+)
+from cryptography.hazmat.primitives.asymmetric import x25519
+from cryptography.hazmat.primitives.ciphers.aead import ChaCha20Poly1305
+
+from .utils import load_vectors_from_file
+
+_CHUNK_LENGTH = 64 * 1024
+_MESSAGE = b"age test vector\n"
+
+
+def _age_supported(backend):
+    try:
+        ChaCha20Poly1305(b"\x00" * 32)
+    except UnsupportedAlgorithm:
+        return False
+    return backend.x25519_supported()
+
+
+def _load_vector(name):
+    return load_vectors_from_file(
+        os.path.join("age", name), lambda f: f.read(), mode="rb"
+    )
+
+
+def _load_identity():
+    data = _load_vector("x25519.key").decode("ascii")
+    return age.load_x25519_identity(data.splitlines()[-1])
+
+
+def _skip_if_scrypt_unsupported(backend):
+    if not backend.scrypt_supported():
+        pytest.skip("Does not support scrypt")
+
+
+@pytest.mark.supported(
+    only_if=_age_supported,
+    skip_message="Does not support X25519 and ChaCha20Poly1305",
+)
+class TestAge:
+    @pytest.mark.parametrize(
+        ("vector", "plaintext"),
+        [
+            ("x25519.age", _MESSAGE),
+            ("x25519-empty.age", b""),
+            ("x25519-two-chunks.age", b"\x2a" * (2 * _CHUNK_LENGTH)),
+            ("x25519-multiple.age", _MESSAGE),
+        ],
+    )
+    def test_decrypt_x25519_vectors(self, backend, vector, plaintext):
+        data = _load_vector(vector)
+        assert age.decrypt(data, [_load_identity()]) == plaintext
+
+    def test_decrypt_scrypt_vector(self, backend):
+        _skip_if_scrypt_unsupported(backend)
+        data = _load_vector("scrypt.age")
+        identity = age.ScryptIdentity(b"password")
+        assert age.decrypt(data, [identity]) == _MESSAGE
+
+        with pytest.raises(ValueError):
+            age.decrypt(data, [age.ScryptIdentity(b"wrong password")])
+        # The vector uses a work factor of 10.
+        with pytest.raises(ValueError):
+            age.decrypt(data, [age.ScryptIdentity(b"password", 9)])
+
+    def test_decrypt_scrypt_not_alone(self, backend):
+        _skip_if_scrypt_unsupported(backend)
+        data = _load_vector("scrypt-and-x25519.age")
+        with pytest.raises(ValueError):
+            age.decrypt(data, [_load_identity()])
+
+    def test_decrypt_bad_mac(self, backend):
+        data = _load_vector("x25519-bad-mac.age")
+        with pytest.raises(InvalidTag):
+            age.decrypt(data, [_load_identity()])
+
+    def test_identity_encoding(self, backend):
+        data = _load_vector("x25519.key").decode("ascii")
+        comment, encoded = data.splitlines()
+        identity = age.load_x25519_identity(encoded)
+        assert age.encode_x25519_identity(identity) == encoded
+        recipient = comment.split(": ")[1]
+        assert (
+            age.encode_x25519_recipient(identity.public_key()) == recipient
+        )
+        assert age.load_x25519_recipient(recipient) == identity.public_key()
+        assert age.load_x25519_identity(encoded.lower()).private_bytes_raw()
+
+    @pytest.mark.parametrize(
+        "data",
+        [
+            "",
+            "age1",
+            # Bad checksum
+            "age1ql3z7hjy54pw3hyww5ayyfg7zqgvc7w3j2elw8zmrj2kg5sfn9aqmcac8q",
+            # Mixed case
+            "Age1ql3z7hjy54pw3hyww5ayyfg7zqgvc7w3j2elw8zmrj2kg5sfn9aqmcac8p",
+            # Wrong human readable part
+            "bge1ql3z7hjy54pw3hyww5ayyfg7zqgvc7w3j2elw8zmrj2kg5sfn9aqcq2m57",
+            # 31 bytes
+            "age1ql3z7hjy54pw3hyww5ayyfg7zqgvc7w3j2elw8zmrj2kg5sfnyv83t26",
+        ],
+    )
+    def test_load_invalid_recipient(self, backend, data):
+        with pytest.raises(ValueError):
+            age.load_x25519_recipient(data)
+
+    def test_load_invalid_identity(self, backend):
+        recipient = age.encode_x25519_recipient(
+            x25519.X25519PrivateKey.generate().public_key()
+        )
+        with pytest.raises(ValueError):
+            age.load_x25519_identity(recipient)
+        with pytest.raises(ValueError):
+            age.load_x25519_recipient(
+                _load_vector("x25519.key").decode("ascii").splitlines()[-1]
+            )
+
+    @pytest.mark.parametrize(
+        "length",
+        [
+            0,
+            1,
+            _CHUNK_LENGTH - 1,
+            _CHUNK_LENGTH,
+            _CHUNK_LENGTH + 1,
+            3 * _CHUNK_LENGTH,
+        ],
+    )
+    def test_roundtrip(self, backend, length):
+        private_key = x25519.X25519PrivateKey.generate()
+        other_key = x25519.X25519PrivateKey.generate()
+        data = os.urandom(length)
+        ciphertext = age.encrypt(
+            data, [other_key.public_key(), private_key.public_key()]
+        )
+        assert age.decrypt(ciphertext, [private_key]) == data
+        assert age.decrypt(ciphertext, [other_key]) == data
+
+        # Truncated at a chunk boundary, and with data after the last chunk.
+        if length > _CHUNK_LENGTH:
+            last_chunk = (length % _CHUNK_LENGTH or _CHUNK_LENGTH) + 16
+            with pytest.raises(InvalidTag):
+                age.decrypt(ciphertext[:-last_chunk], [private_key])
+        with pytest.raises(InvalidTag):
+            age.decrypt(ciphertext + b"\x00" * 17, [private_key])
+
+    @pytest.mark.parametrize("step", [1, 17, 4096, _CHUNK_LENGTH + 16])
+    def test_streaming(self, backend, step):
+        private_key = x25519.X25519PrivateKey.generate()
+        data = os.urandom(2 * _CHUNK_LENGTH + 100)
+
+        encryptor = age.Encryptor([private_key.public_key()])
+        ciphertext = b"".join(
+            encryptor.update(data[i : i + step])
+            for i in range(0, len(data), step)
+        )
+        ciphertext += encryptor.finalize()
+
+        decryptor = age.Decryptor([private_key])
+        plaintext = b""
+        for i in range(0, len(ciphertext), step):
+            plaintext += decryptor.update(ciphertext[i : i + step])
+        plaintext += decryptor.finalize()
+        assert plaintext == data
+
+    def test_streaming_output(self, backend):
+        private_key = x25519.X25519PrivateKey.generate()
+        encryptor = age.Encryptor([private_key.public_key()])
+        header = encryptor.update(b"")
+        assert header.startswith(b"age-encryption.org/v1\n-> X25519 ")
+        # A full chunk is held back until it's known not to be the last.
+        assert encryptor.update(b"\x00" * _CHUNK_LENGTH) == b""
+        chunk = encryptor.update(b"\x00")
+        assert len(chunk) == _CHUNK_LENGTH + 16
+        last = encryptor.finalize()
+        assert len(last) == 1 + 16
+
+        decryptor = age.Decryptor([private_key])
+        assert decryptor.update(header + chunk) == b""
+        assert decryptor.update(last) == b"\x00" * _CHUNK_LENGTH
+        assert decryptor.finalize() == b"\x00"
+
+    def test_scrypt_roundtrip(self, backend):
+        _skip_if_scrypt_unsupported(backend)
+        recipient = age.ScryptRecipient(b"passphrase", work_factor=10)
+        assert recipient.work_factor == 10
+        ciphertext = age.encrypt(b"data", [recipient])
+        assert b"\n-> scrypt " in ciphertext
+        assert b" 10\n" in ciphertext
+
+        identity = age.ScryptIdentity(b"passphrase")
+        assert identity.max_work_factor == 22
+        assert age.decrypt(ciphertext, [identity]) == b"data"
+
+        # X25519 identities skip scrypt stanzas.
+        private_key = x25519.X25519PrivateKey.generate()
+        with pytest.raises(ValueError):
+            age.decrypt(ciphertext, [private_key])
+        assert age.decrypt(ciphertext, [private_key, identity]) == b"data"
+
+    def test_scrypt_invalid(self, backend):
+        _skip_if_scrypt_unsupported(backend)
+        with pytest.raises(ValueError):
+            age.ScryptRecipient(b"")
+        with pytest.raises(ValueError):
+            age.ScryptIdentity(b"")
+        for work_factor in [0, 31]:
+            with pytest.raises(ValueError):
+                age.ScryptRecipient(b"passphrase", work_factor)
+            with pytest.raises(ValueError):
+                age.ScryptIdentity(b"passphrase", work_factor)
+
+        private_key = x25519.X25519PrivateKey.generate()
+        with pytest.raises(ValueError):
+            age.Encryptor(
+                [
+                    age.ScryptRecipient(b"passphrase"),
+                    private_key.public_key(),
+                ]
+            )
+
+    def test_no_recipients(self, backend):
+        with pytest.raises(ValueError):
+            age.Encryptor([])
+        with pytest.raises(ValueError):
+            age.Decryptor([])
+        with pytest.raises(TypeError):
+            age.Encryptor(
+                [object()]  # type: ignore[list-item]
+            )
+        public_key = x25519.X25519PrivateKey.generate().public_key()
+        with pytest.raises(TypeError):
+            age.Decryptor(
+                [public_key]  # type: ignore[list-item]
+            )
+
+    def test_wrong_identity(self, backend):
+        with pytest.raises(ValueError):
+            age.decrypt(
+                _load_vector("x25519.age"),
+                [x25519.X25519PrivateKey.generate()],
+            )
+
+    def test_already_finalized(self, backend):
+        private_key = x25519.X25519PrivateKey.generate()
+        encryptor = age.Encryptor([private_key.public_key()])
+        ciphertext = encryptor.finalize()
+        with pytest.raises(AlreadyFinalized):
+            encryptor.update(b"")
+        with pytest.raises(AlreadyFinalized):
+            encryptor.finalize()
+
+        decryptor = age.Decryptor([private_key])
+        assert decryptor.update(ciphertext) == b""
+        assert decryptor.finalize() == b""
+        with pytest.raises(AlreadyFinalized):
+            decryptor.update(ciphertext)
+        with pytest.raises(AlreadyFinalized):
+            decryptor.finalize()
+
+    @pytest.mark.parametrize(
+        "header",
+        [
+            b"age-encryption.org/v2\n",
+            b"-----BEGIN AGE ENCRYPTED FILE-----\n",
+            # No stanzas
+            b"age-encryption.org/v1\n--- ",
+            # Bad stanza prefix
+            b"age-encryption.org/v1\n-- X25519 abc\n",
+            # Empty argument
+            b"age-encryption.org/v1\n-> X25519  abc\n",
+            # Body line too long
+            b"age-encryption.org/v1\n-> X25519 abc\n" + b"A" * 65 + b"\n",
+            # Padded base64 body
+            b"age-encryption.org/v1\n-> X25519 abc\nAA==\n",
+        ],
+    )
+    def test_invalid_header(self, backend, header):
+        decryptor = age.Decryptor([x25519.X25519PrivateKey.generate()])
+        with pytest.raises(ValueError):
+            decryptor.update(header + b"\n" * 2 + b"--- " + b"A" * 43 + b"\n")
+
+    @pytest.mark.parametrize(
+        "stanza",
+        [
+            # Two arguments
+            b"-> X25519 AAAA AAAA\n" + b"A" * 43 + b"\n",
+            # Share of the wrong length
+            b"-> X25519 AAAA\n" + b"A" * 43 + b"\n",
+            # Body of the wrong length
+            b"-> X25519 " + b"A" * 43 + b"\nAAAA\n",
+            # A low order share
+            b"-> X25519 " + b"A" * 43 + b"\n" + b"A" * 43 + b"\n",
+        ],
+    )
+    def test_invalid_x25519_stanza(self, backend, stanza):
+        data = b"age-encryption.org/v1\n" + stanza + b"--- " + b"A" * 43
+        with pytest.raises(ValueError):
+            age.decrypt(
+                data + b"\n" + b"\x00" * 32,
+                [x25519.X25519PrivateKey.generate()],
+            )
+
+    def test_truncated(self, backend):
+        private_key = x25519.X25519PrivateKey.generate()
+        ciphertext = age.encrypt(b"data", [private_key.public_key()])
+        header_length = ciphertext.index(b"\n--- ") + 5 + 43 + 1
+        for length in [10, header_length - 1, header_length + 15]:
+            with pytest.raises(ValueError):
+                age.decrypt(ciphertext[:length], [private_key])
+        for length in [header_length + 16, len(ciphertext) - 1]:
+            with pytest.raises(InvalidTag):
+                age.decrypt(ciphertext[:length], [private_key])
@@ -13,8 +13,16 @@ import pretend
 import pytest
 
 import cryptography_vectors
-from cryptography.fernet import Fernet, InvalidToken, MultiFernet
+from cryptography.exceptions import UnsupportedAlgorithm
+from cryptography.fernet import (
+    Fernet,
+    FernetV2,
+    FernetV2Algorithm,
+    InvalidToken,
+    MultiFernet,
+)
 from cryptography.hazmat.primitives.ciphers import algorithms, modes
+from cryptography.hazmat.primitives.ciphers.aead import ChaCha20Poly1305
 
 
 def json_parametrize(keys, filename):
@@ -113,6 +121,17 @@ class TestFernet:
         with pytest.raises(InvalidToken):
             f.decrypt("nonsensetoken")
 
+    def test_non_ascii_token(self, backend):
+        f = Fernet(base64.urlsafe_b64encode(b"\x00" * 32), backend=backend)
+        with pytest.raises(InvalidToken):
+            f.decrypt("\u2603" * 4)
+
+    def test_token_whitespace(self, backend):
+        f = Fernet(base64.urlsafe_b64encode(b"\x00" * 32), backend=backend)
+        token = f.encrypt(b"encrypt me")
+        assert f.decrypt(token + b"\n") == b"encrypt me"
+        assert f.decrypt(token.decode("ascii") + "\r\n") == b"encrypt me"
+
     def test_invalid_types(self, backend):
         f = Fernet(base64.urlsafe_b64encode(b"\x00" * 32), backend=backend)
         with pytest.raises(TypeError):
@@ -261,13 +280,20 @@ class TestMultiFernet:
         original_time = int(time.time()) - 5 * 60
         mf1_ciphertext = mf1.encrypt_at_time(plaintext, original_time)
 
-        rotated_time, _ = Fernet._get_unverified_token_data(
-            mf2.rotate(mf1_ciphertext)
-        )
+        rotated_time = f2.extract_timestamp(mf2.rotate(mf1_ciphertext))
 
         assert int(time.time()) != rotated_time
         assert original_time == rotated_time
 
+    def test_decrypt_ttl_checked_once(self, backend):
+        f1 = Fernet(base64.urlsafe_b64encode(b"\x00" * 32), backend=backend)
+        f2 = Fernet(base64.urlsafe_b64encode(b"\x01" * 32), backend=backend)
+        mf = MultiFernet([f1, f2])
+        token = f2.encrypt_at_time(b"abc", current_time=100)
+        assert mf.decrypt_at_time(token, ttl=1, current_time=100) == b"abc"
+        with pytest.raises(InvalidToken):
+            mf.decrypt_at_time(token, ttl=1, current_time=102)
+
     def test_rotate_decrypt_no_shared_keys(self, backend):
         f1 = Fernet(base64.urlsafe_b64encode(b"\x00" * 32), backend=backend)
         f2 = Fernet(base64.urlsafe_b64encode(b"\x01" * 32), backend=backend)
@@ -277,3 +303,140 @@ class TestMultiFernet:
 
         with pytest.raises(InvalidToken):
             mf2.rotate(mf1.encrypt(b"abc"))
+
+
+def _chacha20poly1305_supported():
+    try:
+        ChaCha20Poly1305(b"\x00" * 32)
+    except UnsupportedAlgorithm:
+        return False
+    return True
+
+
+def _fernet_v2(key_byte, algorithm=FernetV2Algorithm.AES_256_GCM, **kwargs):
+    if (
+        algorithm == FernetV2Algorithm.XCHACHA20_POLY1305
+        and not _chacha20poly1305_supported()
+    ):
+        pytest.skip("Does not support ChaCha20Poly1305")
+    key = base64.urlsafe_b64encode(bytes([key_byte]) * 32)
+    return FernetV2(key, algorithm=algorithm, **kwargs)
+
+
+@pytest.mark.parametrize(
+    ("algorithm", "nonce_length"),
+    [
+        (FernetV2Algorithm.AES_256_GCM, 12),
+        (FernetV2Algorithm.XCHACHA20_POLY1305, 24),
+    ],
+)
+class TestFernetV2:
+    def test_roundtrip(self, algorithm, nonce_length, backend):
+        f = _fernet_v2(0, algorithm)
+        token = f.encrypt_at_time(b"payload", current_time=100)
+        data = base64.urlsafe_b64decode(token)
+        assert data[:2] == bytes([0x81, algorithm.value])
+        assert data[2:6] == f.key_id.to_bytes(4, "big")
+        assert data[6:14] == (100).to_bytes(8, "big")
+        assert len(data) == 14 + nonce_length + len(b"payload") + 16
+        assert f.decrypt(token) == b"payload"
+        assert f.decrypt(token.decode("ascii")) == b"payload"
+        assert f.extract_timestamp(token) == 100
+
+    def test_ttl(self, algorithm, nonce_length, backend):
+        f = _fernet_v2(0, algorithm)
+        token = f.encrypt_at_time(b"payload", current_time=100)
+        assert f.decrypt_at_time(token, ttl=1, current_time=101) == b"payload"
+        with pytest.raises(InvalidToken):
+            f.decrypt_at_time(token, ttl=1, current_time=102)
+
+    def test_deterministic(self, algorithm, nonce_length, backend):
+        f = _fernet_v2(0, algorithm, key_id=7)
+        nonce = b"\x00" * nonce_length
+        token = f._encrypt_from_parts(b"abc", 100, nonce)
+        assert token == f._encrypt_from_parts(b"abc", 100, nonce)
+        assert base64.urlsafe_b64decode(token)[2:6] == b"\x00\x00\x00\x07"
+        with pytest.raises(ValueError):
+            f._encrypt_from_parts(b"abc", 100, nonce + b"\x00")
+
+    def test_tampered(self, algorithm, nonce_length, backend):
+        f = _fernet_v2(0, algorithm)
+        data = bytearray(
+            base64.urlsafe_b64decode(f.encrypt_at_time(b"abc", 100))
+        )
+        # Flip a bit in the authenticated header and in the ciphertext
+        for idx in [7, 14 + nonce_length]:
+            tampered = bytearray(data)
+            tampered[idx] ^= 1
+            token = base64.urlsafe_b64encode(bytes(tampered))
+            with pytest.raises(InvalidToken):
+                f.decrypt(token)
+            with pytest.raises(InvalidToken):
+                f.extract_timestamp(token)
+
+    def test_wrong_key(self, algorithm, nonce_length, backend):
+        token = _fernet_v2(0, algorithm, key_id=1).encrypt(b"abc")
+        with pytest.raises(InvalidToken):
+            _fernet_v2(1, algorithm, key_id=1).decrypt(token)
+        with pytest.raises(InvalidToken):
+            _fernet_v2(0, algorithm, key_id=2).decrypt(token)
+
+    def test_v1_token(self, algorithm, nonce_length, backend):
+        key = base64.urlsafe_b64encode(b"\x00" * 32)
+        token = Fernet(key).encrypt(b"abc")
+        with pytest.raises(InvalidToken):
+            _fernet_v2(0, algorithm).decrypt(token)
+        with pytest.raises(InvalidToken):
+            Fernet(key).decrypt(_fernet_v2(0, algorithm).encrypt(b"abc"))
+
+    def test_multifernet_migration(self, algorithm, nonce_length, backend):
+        f1 = Fernet(base64.urlsafe_b64encode(b"\x00" * 32))
+        f2 = _fernet_v2(1, algorithm)
+        v1_token = f1.encrypt_at_time(b"abc", current_time=100)
+
+        mf = MultiFernet([f2, f1])
+        assert mf.decrypt(v1_token) == b"abc"
+        rotated = mf.rotate(v1_token)
+        assert base64.urlsafe_b64decode(rotated)[0] == 0x81
+        assert f2.decrypt(rotated) == b"abc"
+        assert f2.extract_timestamp(rotated) == 100
+
+        # And back again
+        rotated = MultiFernet([f1, f2]).rotate(rotated)
+        assert f1.decrypt(rotated) == b"abc"
+        assert f1.extract_timestamp(rotated) == 100
+
+    def test_multifernet_key_id(self, algorithm, nonce_length, backend):
+        f1 = _fernet_v2(0, algorithm, key_id=1)
+        f2 = _fernet_v2(1, algorithm, key_id=1)
+        f3 = _fernet_v2(2, algorithm)
+        mf = MultiFernet([f1, f2, f3])
+        assert mf.decrypt(f2.encrypt(b"abc")) == b"abc"
+        assert mf.decrypt(f3.encrypt(b"abc")) == b"abc"
+
+
+class TestFernetV2Construction:
+    def test_default_key_id(self, backend):
+        key = FernetV2.generate_key()
+        assert FernetV2(key).key_id == FernetV2(key).key_id
+        assert FernetV2(key, key_id=3).key_id == 3
+
+    def test_invalid_key_id(self, backend):
+        key = FernetV2.generate_key()
+        with pytest.raises(ValueError):
+            FernetV2(key, key_id=-1)
+        with pytest.raises(ValueError):
+            FernetV2(key, key_id=2**32)
+
+    def test_invalid_algorithm(self, backend):
+        with pytest.raises(TypeError):
+            FernetV2(
+                FernetV2.generate_key(),
+                algorithm=1,  # type: ignore[arg-type]
+            )
+
+    def test_invalid_key(self, backend):
+        with pytest.raises(ValueError):
+            FernetV2(base64.urlsafe_b64encode(b"\x00" * 16))
+        with pytest.raises(ValueError):
+            FernetV2(b"!")
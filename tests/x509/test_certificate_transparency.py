@@ -0,0 +1,381 @@
+# This file is dual licensed under the terms of the Apache License, Version
+# 2.0, and the BSD License. See the LICENSE file in the root of this repository
+# for complete details.
+
+import base64
+import datetime
+import hashlib
+import json
+import struct
+
+import pytest
+
+from cryptography import x509
+from cryptography.exceptions import InvalidSignature
+from cryptography.hazmat.primitives import hashes, serialization
+from cryptography.hazmat.primitives.asymmetric import ec, ed25519
+from cryptography.x509 import certificate_transparency as ct
+from cryptography.x509.oid import ExtensionOID, NameOID
+
+_NOW = datetime.datetime(2024, 6, 1, tzinfo=datetime.timezone.utc)
+_SCT_TIME = datetime.datetime(2024, 1, 1, tzinfo=datetime.timezone.utc)
+
+
+def _name(common_name):
+    return x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, common_name)])
+
+
+def _spki(key):
+    return key.public_key().public_bytes(
+        serialization.Encoding.DER,
+        serialization.PublicFormat.SubjectPublicKeyInfo,
+    )
+
+
+def _log_id(key):
+    return hashlib.sha256(_spki(key)).digest()
+
+
+def _der_octet_string(data):
+    if len(data) < 0x80:
+        return b"\x04" + bytes([len(data)]) + data
+    length = len(data).to_bytes((len(data).bit_length() + 7) // 8, "big")
+    return b"\x04" + bytes([0x80 | len(length)]) + length + data
+
+
+def _sct_extension(scts):
+    sct_list = b"".join(struct.pack(">H", len(sct)) + sct for sct in scts)
+    return x509.UnrecognizedExtension(
+        ExtensionOID.PRECERT_SIGNED_CERTIFICATE_TIMESTAMPS,
+        _der_octet_string(struct.pack(">H", len(sct_list)) + sct_list),
+    )
+
+
+def _issue(ca_key, ca_cert, log_keys, sct_time=_SCT_TIME):
+    leaf_key = ec.generate_private_key(ec.SECP256R1())
+    builder = (
+        x509.CertificateBuilder()
+        .subject_name(_name("leaf"))
+        .issuer_name(ca_cert.subject)
+        .public_key(leaf_key.public_key())
+        .serial_number(1)
+        .not_valid_before(_SCT_TIME - datetime.timedelta(days=1))
+        .not_valid_after(_NOW + datetime.timedelta(days=30))
+    )
+    # The TBSCertificate the logs sign doesn't contain the SCT list, so it
+    # can be computed from a certificate carrying an empty one.
+    tbs = (
+        builder.add_extension(_sct_extension([]), critical=False)
+        .sign(ca_key, hashes.SHA256())
+        .tbs_precertificate_bytes
+    )
+    issuer_key_hash = hashlib.sha256(
+        ca_cert.public_key().public_bytes(
+            serialization.Encoding.DER,
+            serialization.PublicFormat.SubjectPublicKeyInfo,
+        )
+    ).digest()
+    timestamp = struct.pack(">Q", int(sct_time.timestamp() * 1000))
+
+    scts = []
+    for log_key in log_keys:
+        signed_data = (
+            b"\x00\x00"
+            + timestamp
+            + b"\x00\x01"
+            + issuer_key_hash
+            + len(tbs).to_bytes(3, "big")
+            + tbs
+            + b"\x00\x00"
+        )
+        signature = log_key.sign(signed_data, ec.ECDSA(hashes.SHA256()))
+        scts.append(
+            b"\x00"
+            + _log_id(log_key)
+            + timestamp
+            + b"\x00\x00"
+            + b"\x04\x03"
+            + struct.pack(">H", len(signature))
+            + signature
+        )
+
+    return builder.add_extension(_sct_extension(scts), critical=False).sign(
+        ca_key, hashes.SHA256()
+    )
+
+
+def _log_json(
+    key, description, state="usable", timestamp="2023-01-01T00:00:00Z"
+):
+    return {
+        "description": description,
+        "log_id": base64.b64encode(_log_id(key)).decode(),
+        "key": base64.b64encode(_spki(key)).decode(),
+        "url": f"https://{description}.example.com/",
+        "mmd": 86400,
+        "state": {state: {"timestamp": timestamp}},
+    }
+
+
+def _log_list(*operators):
+    return json.dumps(
+        {
+            "version": "1.0",
+            "log_list_timestamp": "2024-01-01T00:00:00Z",
+            "operators": [
+                {"name": name, "email": [], "logs": logs}
+                for name, logs in operators
+            ],
+        }
+    ).encode()
+
+
+@pytest.fixture(scope="module")
+def ca():
+    ca_key = ec.generate_private_key(ec.SECP256R1())
+    ca_cert = (
+        x509.CertificateBuilder()
+        .subject_name(_name("ca"))
+        .issuer_name(_name("ca"))
+        .public_key(ca_key.public_key())
+        .serial_number(1)
+        .not_valid_before(_SCT_TIME - datetime.timedelta(days=30))
+        .not_valid_after(_NOW + datetime.timedelta(days=365))
+        .sign(ca_key, hashes.SHA256())
+    )
+    return ca_key, ca_cert
+
+
+@pytest.fixture(scope="module")
+def log_keys():
+    return [ec.generate_private_key(ec.SECP256R1()) for _ in range(3)]
+
+
+class TestSCTVerify:
+    def test_verify(self, ca, log_keys):
+        ca_key, ca_cert = ca
+        cert = _issue(ca_key, ca_cert, log_keys[:1])
+        [sct] = cert.extensions.get_extension_for_class(
+            x509.PrecertificateSignedCertificateTimestamps
+        ).value
+        sct.verify(log_keys[0].public_key(), cert, ca_cert)
+
+        with pytest.raises(InvalidSignature):
+            sct.verify(log_keys[1].public_key(), cert, ca_cert)
+        with pytest.raises(InvalidSignature):
+            sct.verify(log_keys[0].public_key(), cert, cert)
+        with pytest.raises(ValueError):
+            sct.verify(log_keys[0].public_key(), cert)
+        with pytest.raises(ValueError):
+            sct.verify(
+                ed25519.Ed25519PrivateKey.generate().public_key(),
+                cert,
+                ca_cert,
+            )
+
+
+class TestLoadLogList:
+    def test_load(self, log_keys):
+        log_list = ct.load_log_list(
+            _log_list(
+                ("Operator A", [_log_json(log_keys[0], "a1")]),
+                (
+                    "Operator B",
+                    [
+                        _log_json(log_keys[1], "b1", "pending"),
+                        _log_json(
+                            log_keys[2],
+                            "b2",
+                            "retired",
+                            "2024-02-01T00:00:00Z",
+                        ),
+                    ],
+                ),
+            )
+        )
+        assert len(log_list) == 3
+        assert log_list.get(b"\x00" * 32) is None
+
+        log = log_list.get(_log_id(log_keys[0]))
+        assert log is not None
+        assert log.description == "a1"
+        assert log.operator == "Operator A"
+        assert log.url == "https://a1.example.com/"
+        assert log.mmd == 86400
+        assert log.state == ct.LogState.USABLE
+        assert log.state_timestamp == datetime.datetime(
+            2023, 1, 1, tzinfo=datetime.timezone.utc
+        )
+        assert log.public_key == log_keys[0].public_key()
+
+        retired = log_list.get(_log_id(log_keys[2]))
+        assert retired is not None
+        assert retired.state == ct.LogState.RETIRED
+        assert retired.is_qualifying(_SCT_TIME)
+        assert not retired.is_qualifying(_NOW)
+        pending = log_list.get(_log_id(log_keys[1]))
+        assert pending is not None
+        assert not pending.is_qualifying(_SCT_TIME)
+
+    def test_tiled_logs(self, log_keys):
+        tiled = _log_json(log_keys[0], "t1")
+        del tiled["url"]
+        tiled["submission_url"] = "https://t1.example.com/submit/"
+        tiled["monitoring_url"] = "https://t1.example.com/monitor/"
+        log_list = ct.load_log_list(
+            json.dumps(
+                {
+                    "operators": [
+                        {
+                            "name": "Operator T",
+                            "logs": [],
+                            "tiled_logs": [tiled],
+                        }
+                    ]
+                }
+            ).encode()
+        )
+        [log] = log_list.logs
+        assert log.url == "https://t1.example.com/submit/"
+
+    def test_log_id_mismatch(self, log_keys):
+        log = _log_json(log_keys[0], "a1")
+        log["log_id"] = base64.b64encode(_log_id(log_keys[1])).decode()
+        with pytest.raises(ValueError):
+            ct.load_log_list(_log_list(("Operator A", [log])))
+
+    def test_duplicate_log(self, log_keys):
+        log = _log_json(log_keys[0], "a1")
+        with pytest.raises(ValueError):
+            ct.load_log_list(
+                _log_list(("Operator A", [log]), ("Operator B", [log]))
+            )
+
+    @pytest.mark.parametrize(
+        "data",
+        [
+            b"not json",
+            b"[]",
+            b"{}",
+            b'{"operators": [{"name": "A", "logs": [{}]}]}',
+        ],
+    )
+    def test_invalid(self, data):
+        with pytest.raises(ValueError):
+            ct.load_log_list(data)
+
+
+class TestSCTPolicy:
+    def _policy(self, log_keys, min_logs=2, min_operators=1, **states):
+        return ct.SCTPolicy(
+            ct.load_log_list(
+                _log_list(
+                    (
+                        "Operator A",
+                        [
+                            _log_json(log_keys[0], "a1", **states),
+                            _log_json(log_keys[1], "a2"),
+                        ],
+                    ),
+                    ("Operator B", [_log_json(log_keys[2], "b1")]),
+                )
+            ),
+            min_logs=min_logs,
+            min_operators=min_operators,
+        )
+
+    def test_invalid_policy(self, log_keys):
+        log_list = ct.LogList([])
+        with pytest.raises(ValueError):
+            ct.SCTPolicy(log_list, min_logs=0)
+        with pytest.raises(ValueError):
+            ct.SCTPolicy(log_list, min_logs=1, min_operators=2)
+
+    def test_compliant(self, ca, log_keys):
+        ca_key, ca_cert = ca
+        cert = _issue(ca_key, ca_cert, log_keys)
+        policy = self._policy(log_keys, min_logs=3, min_operators=2)
+        scts = policy.verify(cert, ca_cert, _NOW)
+        assert {sct.log_id for sct in scts} == {
+            _log_id(key) for key in log_keys
+        }
+
+    def test_too_few_logs(self, ca, log_keys):
+        ca_key, ca_cert = ca
+        cert = _issue(ca_key, ca_cert, log_keys[:1])
+        with pytest.raises(ValueError):
+            self._policy(log_keys).verify(cert, ca_cert, _NOW)
+
+    def test_too_few_operators(self, ca, log_keys):
+        ca_key, ca_cert = ca
+        cert = _issue(ca_key, ca_cert, log_keys[:2])
+        policy = self._policy(log_keys, min_operators=2)
+        with pytest.raises(ValueError):
+            policy.verify(cert, ca_cert, _NOW)
+
+    def test_unknown_log(self, ca, log_keys):
+        ca_key, ca_cert = ca
+        unknown = ec.generate_private_key(ec.SECP256R1())
+        cert = _issue(ca_key, ca_cert, [log_keys[0], unknown])
+        with pytest.raises(ValueError):
+            self._policy(log_keys).verify(cert, ca_cert, _NOW)
+
+    def test_no_scts(self, ca, log_keys):
+        ca_key, ca_cert = ca
+        with pytest.raises(ValueError):
+            self._policy(log_keys, min_logs=1).verify(ca_cert, ca_cert, _NOW)
+
+    def test_bad_signature(self, ca, log_keys):
+        ca_key, ca_cert = ca
+        cert = _issue(ca_key, ca_cert, log_keys[:2])
+        # The SCTs' signatures cover the issuer's key, so they don't verify
+        # with the wrong issuer.
+        with pytest.raises(ValueError):
+            self._policy(log_keys).verify(cert, cert, _NOW)
+
+    @pytest.mark.parametrize(
+        ("states", "compliant"),
+        [
+            ({"state": "pending"}, False),
+            ({"state": "rejected"}, False),
+            ({"state": "qualified"}, True),
+            ({"state": "readonly"}, True),
+            (
+                {"state": "retired", "timestamp": "2024-02-01T00:00:00Z"},
+                True,
+            ),
+            (
+                {"state": "retired", "timestamp": "2023-12-01T00:00:00Z"},
+                False,
+            ),
+        ],
+    )
+    def test_log_states(self, ca, log_keys, states, compliant):
+        ca_key, ca_cert = ca
+        cert = _issue(ca_key, ca_cert, log_keys[:2])
+        policy = self._policy(log_keys, **states)
+        if compliant:
+            assert len(policy.verify(cert, ca_cert, _NOW)) == 2
+        else:
+            with pytest.raises(ValueError):
+                policy.verify(cert, ca_cert, _NOW)
+
+    def test_sct_from_the_future(self, ca, log_keys):
+        ca_key, ca_cert = ca
+        cert = _issue(
+            ca_key,
+            ca_cert,
+            log_keys[:2],
+            sct_time=_NOW + datetime.timedelta(days=1),
+        )
+        policy = self._policy(log_keys)
+        with pytest.raises(ValueError):
+            policy.verify(cert, ca_cert, _NOW)
+        assert (
+            len(
+                policy.verify(
+                    cert, ca_cert, _NOW + datetime.timedelta(days=2)
+                )
+            )
+            == 2
+        )
@@ -12,11 +12,18 @@ import pytest
 from cryptography import x509
 from cryptography.exceptions import UnsupportedAlgorithm
 from cryptography.hazmat.primitives import hashes, serialization
-from cryptography.hazmat.primitives.asymmetric import ec, ed448, ed25519, rsa
+from cryptography.hazmat.primitives.asymmetric import (
+    ec,
+    ed448,
+    ed25519,
+    padding,
+    rsa,
+)
 from cryptography.hazmat.primitives.asymmetric.padding import PKCS1v15
 from cryptography.x509 import ocsp
 
 from ..hazmat.primitives.fixtures_ec import EC_KEY_SECP256R1
+from ..hazmat.primitives.fixtures_rsa import RSA_KEY_2048
 from ..utils import load_vectors_from_file, raises_unsupported_algorithm
 from .test_x509 import DummyExtension, _load_cert
 
@@ -1013,6 +1020,88 @@ class TestOCSPResponseBuilder:
         with pytest.raises(TypeError):
             builder.sign(private_key, None)
 
+    def _rsa_builder(self):
+        private_key = RSA_KEY_2048.private_key(
+            unsafe_skip_rsa_key_validation=True
+        )
+        root_cert, _ = _generate_root(private_key)
+        cert, issuer = _cert_and_issuer()
+        this_update = datetime.datetime(2023, 1, 1)
+        builder = (
+            ocsp.OCSPResponseBuilder()
+            .responder_id(ocsp.OCSPResponderEncoding.NAME, root_cert)
+            .add_response(
+                cert,
+                issuer,
+                hashes.SHA1(),
+                ocsp.OCSPCertStatus.GOOD,
+                this_update,
+                this_update + datetime.timedelta(days=7),
+                None,
+                None,
+            )
+        )
+        return builder, private_key
+
+    def test_sign_rsa_pss(self):
+        builder, private_key = self._rsa_builder()
+        pss = padding.PSS(
+            mgf=padding.MGF1(hashes.SHA256()),
+            salt_length=padding.PSS.DIGEST_LENGTH,
+        )
+        resp = builder.sign(private_key, hashes.SHA256(), rsa_padding=pss)
+        assert (
+            resp.signature_algorithm_oid
+            == x509.SignatureAlgorithmOID.RSASSA_PSS
+        )
+        assert isinstance(resp.signature_hash_algorithm, hashes.SHA256)
+        params = resp.signature_algorithm_parameters
+        assert isinstance(params, padding.PSS)
+        assert params._salt_length == 32
+        assert isinstance(params.mgf, padding.MGF1)
+        assert isinstance(params.mgf._algorithm, hashes.SHA256)
+        private_key.public_key().verify(
+            resp.signature, resp.tbs_response_bytes, params, hashes.SHA256()
+        )
+
+        loaded = ocsp.load_der_ocsp_response(
+            resp.public_bytes(serialization.Encoding.DER)
+        )
+        assert isinstance(loaded.signature_algorithm_parameters, padding.PSS)
+
+    def test_sign_rsa_pkcs1v15(self):
+        builder, private_key = self._rsa_builder()
+        resp = builder.sign(
+            private_key, hashes.SHA256(), rsa_padding=PKCS1v15()
+        )
+        assert (
+            resp.signature_algorithm_oid
+            == x509.SignatureAlgorithmOID.RSA_WITH_SHA256
+        )
+        assert isinstance(resp.signature_algorithm_parameters, PKCS1v15)
+
+    def test_sign_invalid_rsa_padding(self):
+        builder, private_key = self._rsa_builder()
+        with pytest.raises(TypeError):
+            builder.sign(
+                private_key,
+                hashes.SHA256(),
+                rsa_padding=b"notapadding",  # type: ignore[arg-type]
+            )
+        with pytest.raises(TypeError):
+            builder.sign(
+                EC_KEY_SECP256R1.private_key(),
+                hashes.SHA256(),
+                rsa_padding=PKCS1v15(),
+            )
+
+    def test_unsuccessful_signature_algorithm_parameters(self):
+        resp = ocsp.OCSPResponseBuilder.build_unsuccessful(
+            ocsp.OCSPResponseStatus.TRY_LATER
+        )
+        with pytest.raises(ValueError):
+            resp.signature_algorithm_parameters
+
 
 class TestSignedCertificateTimestampsExtension:
     def test_init(self):
@@ -6,6 +6,7 @@
 import binascii
 import copy
 import datetime
+import io
 import ipaddress
 import os
 import typing
@@ -22,8 +23,10 @@ from cryptography.hazmat.primitives.asymmetric import (
     ec,
     ed448,
     ed25519,
+    mldsa,
     padding,
     rsa,
+    slhdsa,
     types,
     x448,
     x25519,
@@ -3654,6 +3657,116 @@ class TestCertificateBuilder:
         assert isinstance(cert.signature_hash_algorithm, hashes.SHA256)
         assert isinstance(cert.public_key(), ed448.Ed448PublicKey)
 
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.mldsa_supported(),
+        skip_message="Requires OpenSSL with ML-DSA support",
+    )
+    @pytest.mark.parametrize(
+        ("parameter_set", "oid"),
+        [
+            (
+                mldsa.MLDSAParameterSet.ML_DSA_44,
+                SignatureAlgorithmOID.ML_DSA_44,
+            ),
+            (
+                mldsa.MLDSAParameterSet.ML_DSA_65,
+                SignatureAlgorithmOID.ML_DSA_65,
+            ),
+            (
+                mldsa.MLDSAParameterSet.ML_DSA_87,
+                SignatureAlgorithmOID.ML_DSA_87,
+            ),
+        ],
+    )
+    def test_build_cert_with_mldsa(self, parameter_set, oid, backend):
+        issuer_private_key = mldsa.MLDSAPrivateKey.generate(parameter_set)
+        subject_private_key = mldsa.MLDSAPrivateKey.generate(parameter_set)
+
+        builder = (
+            x509.CertificateBuilder()
+            .serial_number(777)
+            .issuer_name(
+                x509.Name([x509.NameAttribute(NameOID.COUNTRY_NAME, "US")])
+            )
+            .subject_name(
+                x509.Name([x509.NameAttribute(NameOID.COUNTRY_NAME, "US")])
+            )
+            .public_key(subject_private_key.public_key())
+            .not_valid_before(datetime.datetime(2002, 1, 1, 12, 1))
+            .not_valid_after(datetime.datetime(2030, 12, 31, 8, 30))
+        )
+
+        with pytest.raises(ValueError):
+            builder.sign(issuer_private_key, hashes.SHA256(), backend)
+
+        cert = builder.sign(issuer_private_key, None, backend)
+        issuer_private_key.public_key().verify(
+            cert.signature, cert.tbs_certificate_bytes
+        )
+        assert cert.signature_algorithm_oid == oid
+        assert cert.signature_hash_algorithm is None
+        assert cert.signature_algorithm_parameters is None
+        public_key = cert.public_key()
+        assert isinstance(public_key, mldsa.MLDSAPublicKey)
+        assert public_key == subject_private_key.public_key()
+
+        issuer = (
+            x509.CertificateBuilder()
+            .serial_number(1)
+            .issuer_name(cert.issuer)
+            .subject_name(cert.issuer)
+            .public_key(issuer_private_key.public_key())
+            .not_valid_before(datetime.datetime(2002, 1, 1, 12, 1))
+            .not_valid_after(datetime.datetime(2030, 12, 31, 8, 30))
+            .sign(issuer_private_key, None, backend)
+        )
+        cert.verify_directly_issued_by(issuer)
+
+    @pytest.mark.supported(
+        only_if=lambda backend: backend.slhdsa_supported(),
+        skip_message="Requires OpenSSL with SLH-DSA support",
+    )
+    @pytest.mark.parametrize(
+        ("parameter_set", "oid"),
+        [
+            (
+                slhdsa.SLHDSAParameterSet.SLH_DSA_SHA2_128F,
+                SignatureAlgorithmOID.SLH_DSA_SHA2_128F,
+            ),
+            (
+                slhdsa.SLHDSAParameterSet.SLH_DSA_SHAKE_128F,
+                SignatureAlgorithmOID.SLH_DSA_SHAKE_128F,
+            ),
+        ],
+    )
+    def test_build_cert_with_slhdsa(self, parameter_set, oid, backend):
+        private_key = slhdsa.SLHDSAPrivateKey.generate(parameter_set)
+        name = x509.Name([x509.NameAttribute(NameOID.COUNTRY_NAME, "US")])
+        builder = (
+            x509.CertificateBuilder()
+            .serial_number(777)
+            .issuer_name(name)
+            .subject_name(name)
+            .public_key(private_key.public_key())
+            .not_valid_before(datetime.datetime(2002, 1, 1, 12, 1))
+            .not_valid_after(datetime.datetime(2030, 12, 31, 8, 30))
+        )
+
+        with pytest.raises(ValueError):
+            builder.sign(private_key, hashes.SHA256(), backend)
+
+        cert = builder.sign(private_key, None, backend)
+        private_key.public_key().verify(
+            cert.signature, cert.tbs_certificate_bytes
+        )
+        assert cert.signature_algorithm_oid == oid
+        assert cert.signature_hash_algorithm is None
+        assert cert.signature_algorithm_parameters is None
+        public_key = cert.public_key()
+        assert isinstance(public_key, slhdsa.SLHDSAPublicKey)
+        assert public_key == private_key.public_key()
+        cert.verify_directly_issued_by(cert)
+
     @pytest.mark.supported(
         only_if=lambda backend: (
             backend.x25519_supported() and backend.x448_supported()
@@ -6619,3 +6732,35 @@ def test_load_pem_x509_certificates():
     assert len(certs) == 2
     assert certs[0].serial_number == 16160
     assert certs[1].serial_number == 146039
+
+
+def test_load_pem_x509_certificates_from_file(tmp_path):
+    certs = load_vectors_from_file(
+        filename=os.path.join(
+            "x509", "cryptography.io.chain_with_garbage.pem"
+        ),
+        loader=x509.load_pem_x509_certificates_from_file,
+        mode="rb",
+    )
+    assert [c.serial_number for c in certs] == [16160, 146039]
+
+    data = load_vectors_from_file(
+        filename=os.path.join("x509", "cryptography.io.chain.pem"),
+        loader=lambda pemfile: pemfile.read(),
+        mode="rb",
+    )
+    expected = x509.load_pem_x509_certificates(data)
+    path = tmp_path / "bundle.pem"
+    # Enough certificates that blocks span the chunks files are read in,
+    # and the file is large enough to be memory mapped.
+    path.write_bytes(data * 500)
+    for source in [path, str(path), io.BytesIO(data * 500)]:
+        certs = x509.load_pem_x509_certificates_from_file(source)
+        assert certs == expected * 500
+
+    with pytest.raises(ValueError):
+        x509.load_pem_x509_certificates_from_file(io.BytesIO(b""))
+    with pytest.raises(ValueError):
+        x509.load_pem_x509_certificates_from_file(io.BytesIO(b"#" * 100000))
+    with pytest.raises(TypeError):
+        x509.load_pem_x509_certificates_from_file(io.StringIO(data.decode()))
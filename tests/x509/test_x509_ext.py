@@ -6296,6 +6296,225 @@ class TestMSCertificateTemplate:
         )
 
 
+class TestAndroidKeyAttestation:
+    def _attestation(self, hardware_enforced=None):
+        return x509.AndroidKeyAttestation(
+            attestation_version=3,
+            attestation_security_level=(
+                x509.AndroidSecurityLevel.TRUSTED_ENVIRONMENT
+            ),
+            keymaster_version=4,
+            keymaster_security_level=(
+                x509.AndroidSecurityLevel.TRUSTED_ENVIRONMENT
+            ),
+            attestation_challenge=b"abc",
+            unique_id=b"",
+            software_enforced=x509.AndroidAuthorizationList(),
+            hardware_enforced=(
+                hardware_enforced or x509.AndroidAuthorizationList()
+            ),
+        )
+
+    def test_invalid_types(self):
+        with pytest.raises(TypeError):
+            x509.AndroidAuthorizationList(
+                algorithm="3"  # type:ignore[arg-type]
+            )
+        with pytest.raises(TypeError):
+            x509.AndroidAuthorizationList(
+                purpose=["sign"]  # type:ignore[list-item]
+            )
+        with pytest.raises(TypeError):
+            x509.AndroidAuthorizationList(
+                no_auth_required=None  # type:ignore[arg-type]
+            )
+        with pytest.raises(TypeError):
+            x509.AndroidAuthorizationList(
+                attestation_id_brand="google"  # type:ignore[arg-type]
+            )
+        with pytest.raises(TypeError):
+            x509.AndroidAuthorizationList(
+                root_of_trust=b""  # type:ignore[arg-type]
+            )
+        with pytest.raises(TypeError):
+            x509.AndroidRootOfTrust(
+                b"", True, 0, None  # type:ignore[arg-type]
+            )
+        with pytest.raises(TypeError):
+            x509.AndroidKeyAttestation(
+                3,
+                1,  # type:ignore[arg-type]
+                4,
+                x509.AndroidSecurityLevel.SOFTWARE,
+                b"",
+                b"",
+                x509.AndroidAuthorizationList(),
+                x509.AndroidAuthorizationList(),
+            )
+
+    def test_eq_hash(self):
+        auth1 = x509.AndroidAuthorizationList(purpose=[2, 3], algorithm=3)
+        auth2 = x509.AndroidAuthorizationList(purpose={3, 2}, algorithm=3)
+        auth3 = x509.AndroidAuthorizationList(purpose=[2], algorithm=3)
+        assert auth1 == auth2
+        assert auth1 != auth3
+        assert auth1 != object()
+        assert hash(auth1) == hash(auth2)
+        assert self._attestation(auth1) == self._attestation(auth2)
+        assert self._attestation(auth1) != self._attestation(auth3)
+        assert self._attestation() != object()
+        assert hash(self._attestation(auth1)) == hash(
+            self._attestation(auth2)
+        )
+
+    def test_repr(self):
+        assert repr(x509.AppleAttestationNonce(b"\x00")) == (
+            "<AppleAttestationNonce(nonce=b'\\x00')>"
+        )
+        assert repr(self._attestation()) == (
+            "<AndroidKeyAttestation(attestation_version=3, "
+            "attestation_security_level=AndroidSecurityLevel."
+            "TRUSTED_ENVIRONMENT, attestation_challenge=b'abc')>"
+        )
+
+    def test_public_bytes(self):
+        ext = self._attestation(
+            x509.AndroidAuthorizationList(algorithm=3, no_auth_required=True)
+        )
+        assert ext.public_bytes() == (
+            b"\x30\x22\x02\x01\x03\x0a\x01\x01\x02\x01\x04\x0a\x01\x01"
+            b"\x04\x03abc\x04\x00\x30\x00"
+            b"\x30\x0b\xa2\x03\x02\x01\x03\xbf\x83\x77\x02\x05\x00"
+        )
+
+    def test_round_trip(self, rsa_key_2048: rsa.RSAPrivateKey, backend):
+        root_of_trust = x509.AndroidRootOfTrust(
+            b"\x01" * 32,
+            True,
+            x509.AndroidVerifiedBootState.VERIFIED,
+            b"\x02" * 32,
+        )
+        hardware_enforced = x509.AndroidAuthorizationList(
+            purpose=[2, 3],
+            algorithm=3,
+            key_size=256,
+            digest=[4],
+            ec_curve=1,
+            no_auth_required=True,
+            origin=0,
+            root_of_trust=root_of_trust,
+            os_version=130000,
+            os_patch_level=202310,
+            attestation_id_brand=b"google",
+            vendor_patch_level=20231001,
+            boot_patch_level=20231001,
+        )
+        software_enforced = x509.AndroidAuthorizationList(
+            creation_date_time=1697500000000,
+            attestation_application_id=b"\x30\x00",
+        )
+        attestation = x509.AndroidKeyAttestation(
+            200,
+            x509.AndroidSecurityLevel.STRONG_BOX,
+            200,
+            x509.AndroidSecurityLevel.STRONG_BOX,
+            b"challenge",
+            b"",
+            software_enforced,
+            hardware_enforced,
+        )
+        cert = (
+            _make_certbuilder(rsa_key_2048)
+            .add_extension(attestation, critical=False)
+            .sign(rsa_key_2048, hashes.SHA256(), backend)
+        )
+        ext = cert.extensions.get_extension_for_class(
+            x509.AndroidKeyAttestation
+        )
+        assert ext.oid == ExtensionOID.ANDROID_KEY_ATTESTATION
+        assert ext.value == attestation
+        assert ext.value.hardware_enforced.purpose == frozenset([2, 3])
+        assert ext.value.hardware_enforced.root_of_trust == root_of_trust
+        assert ext.value.software_enforced.no_auth_required is False
+
+    def test_unknown_fields_ignored(
+        self, rsa_key_2048: rsa.RSAPrivateKey, backend
+    ):
+        # hardwareEnforced contains [2] algorithm and an unknown [900] NULL
+        value = (
+            b"\x30\x22\x02\x01\x03\x0a\x01\x01\x02\x01\x04\x0a\x01\x01"
+            b"\x04\x03abc\x04\x00\x30\x00"
+            b"\x30\x0b\xa2\x03\x02\x01\x03\xbf\x87\x04\x02\x05\x00"
+        )
+        cert = (
+            _make_certbuilder(rsa_key_2048)
+            .add_extension(
+                x509.UnrecognizedExtension(
+                    ExtensionOID.ANDROID_KEY_ATTESTATION, value
+                ),
+                critical=False,
+            )
+            .sign(rsa_key_2048, hashes.SHA256(), backend)
+        )
+        ext = cert.extensions.get_extension_for_class(
+            x509.AndroidKeyAttestation
+        )
+        assert ext.value == self._attestation(
+            x509.AndroidAuthorizationList(algorithm=3)
+        )
+
+    def test_invalid_security_level(
+        self, rsa_key_2048: rsa.RSAPrivateKey, backend
+    ):
+        value = (
+            b"\x30\x17\x02\x01\x03\x0a\x01\x07\x02\x01\x04\x0a\x01\x01"
+            b"\x04\x03abc\x04\x00\x30\x00\x30\x00"
+        )
+        cert = (
+            _make_certbuilder(rsa_key_2048)
+            .add_extension(
+                x509.UnrecognizedExtension(
+                    ExtensionOID.ANDROID_KEY_ATTESTATION, value
+                ),
+                critical=False,
+            )
+            .sign(rsa_key_2048, hashes.SHA256(), backend)
+        )
+        with pytest.raises(ValueError):
+            cert.extensions
+
+
+class TestAppleAttestationNonce:
+    def test_invalid_type(self):
+        with pytest.raises(TypeError):
+            x509.AppleAttestationNonce("nonce")  # type:ignore[arg-type]
+
+    def test_eq_hash(self):
+        nonce1 = x509.AppleAttestationNonce(b"\x00" * 32)
+        nonce2 = x509.AppleAttestationNonce(b"\x00" * 32)
+        nonce3 = x509.AppleAttestationNonce(b"\x01" * 32)
+        assert nonce1 == nonce2
+        assert nonce1 != nonce3
+        assert nonce1 != object()
+        assert hash(nonce1) == hash(nonce2)
+
+    def test_public_bytes(self):
+        ext = x509.AppleAttestationNonce(b"\xaa\xbb")
+        assert ext.public_bytes() == b"\x30\x06\xa1\x04\x04\x02\xaa\xbb"
+
+    def test_round_trip(self, rsa_key_2048: rsa.RSAPrivateKey, backend):
+        nonce = x509.AppleAttestationNonce(b"\x42" * 32)
+        cert = (
+            _make_certbuilder(rsa_key_2048)
+            .add_extension(nonce, critical=False)
+            .sign(rsa_key_2048, hashes.SHA256(), backend)
+        )
+        ext = cert.extensions.get_extension_for_oid(
+            ExtensionOID.APPLE_ATTESTATION_NONCE
+        )
+        assert ext.value == nonce
+
+
 def test_all_extension_oid_members_have_names_defined():
     for oid in dir(ExtensionOID):
         if oid.startswith("__"):
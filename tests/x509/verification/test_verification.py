@@ -5,13 +5,23 @@
 import datetime
 import os
 from functools import lru_cache
-from ipaddress import IPv4Address
+from ipaddress import IPv4Address, IPv6Address
 
 import pytest
 
 from cryptography import x509
+from cryptography.hazmat.primitives import hashes, serialization
+from cryptography.hazmat.primitives.asymmetric import ec, padding
+from cryptography.x509 import ocsp
 from cryptography.x509.general_name import DNSName, IPAddress
-from cryptography.x509.verification import PolicyBuilder, Store
+from cryptography.x509.oid import ExtendedKeyUsageOID, NameOID
+from cryptography.x509.verification import (
+    PolicyBuilder,
+    Store,
+    VerificationError,
+    subject_matches,
+)
+from tests.hazmat.primitives.fixtures_rsa import RSA_KEY_2048
 from tests.x509.test_x509 import _load_cert
 
 
@@ -103,3 +113,273 @@ class TestStore:
     def test_store_rejects_non_certificates(self):
         with pytest.raises(TypeError):
             Store(["not a cert"])  # type: ignore[list-item]
+
+
+_NOW = datetime.datetime(2024, 1, 1)
+
+
+def _name(common_name):
+    return x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, common_name)])
+
+
+def _issue(subject, subject_key, issuer, issuer_key, extensions):
+    builder = (
+        x509.CertificateBuilder()
+        .subject_name(_name(subject))
+        .issuer_name(_name(issuer))
+        .public_key(subject_key.public_key())
+        .serial_number(x509.random_serial_number())
+        .not_valid_before(_NOW - datetime.timedelta(days=1))
+        .not_valid_after(_NOW + datetime.timedelta(days=30))
+        .add_extension(
+            x509.AuthorityKeyIdentifier.from_issuer_public_key(
+                issuer_key.public_key()
+            ),
+            critical=False,
+        )
+    )
+    for extension, critical in extensions:
+        builder = builder.add_extension(extension, critical=critical)
+    return builder.sign(issuer_key, hashes.SHA256())
+
+
+def _key_usage(**kwargs):
+    usages = dict.fromkeys(
+        [
+            "digital_signature",
+            "content_commitment",
+            "key_encipherment",
+            "data_encipherment",
+            "key_agreement",
+            "key_cert_sign",
+            "crl_sign",
+            "encipher_only",
+            "decipher_only",
+        ],
+        False,
+    )
+    usages.update(kwargs)
+    return x509.KeyUsage(**usages)
+
+
+class TestVerifyWithOCSP:
+    @pytest.fixture(scope="class")
+    def pki(self):
+        ca_key = ec.generate_private_key(ec.SECP256R1())
+        ca = _issue(
+            "ca",
+            ca_key,
+            "ca",
+            ca_key,
+            [
+                (x509.BasicConstraints(ca=True, path_length=None), True),
+                (_key_usage(key_cert_sign=True, crl_sign=True), True),
+            ],
+        )
+        leaf_key = ec.generate_private_key(ec.SECP256R1())
+        leaf = _issue(
+            "leaf",
+            leaf_key,
+            "ca",
+            ca_key,
+            [
+                (
+                    x509.SubjectAlternativeName(
+                        [x509.DNSName("cryptography.io")]
+                    ),
+                    False,
+                ),
+            ],
+        )
+        responder_key = ec.generate_private_key(ec.SECP256R1())
+        responder = _issue(
+            "responder",
+            responder_key,
+            "ca",
+            ca_key,
+            [
+                (
+                    x509.ExtendedKeyUsage([ExtendedKeyUsageOID.OCSP_SIGNING]),
+                    False,
+                ),
+            ],
+        )
+        pss_responder_key = RSA_KEY_2048.private_key(
+            unsafe_skip_rsa_key_validation=True
+        )
+        pss_responder = _issue(
+            "pss-responder",
+            pss_responder_key,
+            "ca",
+            ca_key,
+            [
+                (
+                    x509.ExtendedKeyUsage([ExtendedKeyUsageOID.OCSP_SIGNING]),
+                    False,
+                ),
+            ],
+        )
+        verifier = (
+            PolicyBuilder()
+            .store(Store([ca]))
+            .time(_NOW)
+            .build_server_verifier(DNSName("cryptography.io"))
+        )
+        return {
+            "ca": (ca, ca_key),
+            "leaf": (leaf, leaf_key),
+            "responder": (responder, responder_key),
+            "pss-responder": (pss_responder, pss_responder_key),
+            "verifier": verifier,
+        }
+
+    def _response(
+        self,
+        pki,
+        signer="ca",
+        status=ocsp.OCSPCertStatus.GOOD,
+        this_update=_NOW - datetime.timedelta(hours=1),
+        next_update=_NOW + datetime.timedelta(days=1),
+        cert=None,
+        rsa_padding=None,
+    ):
+        ca, _ = pki["ca"]
+        signer_cert, signer_key = pki[signer]
+        revocation_time = (
+            this_update if status == ocsp.OCSPCertStatus.REVOKED else None
+        )
+        builder = (
+            ocsp.OCSPResponseBuilder()
+            .add_response(
+                cert or pki["leaf"][0],
+                ca,
+                hashes.SHA1(),
+                status,
+                this_update,
+                next_update,
+                revocation_time,
+                None,
+            )
+            .responder_id(ocsp.OCSPResponderEncoding.HASH, signer_cert)
+        )
+        if signer != "ca":
+            builder = builder.certificates([signer_cert])
+        return builder.sign(
+            signer_key, hashes.SHA256(), rsa_padding=rsa_padding
+        ).public_bytes(serialization.Encoding.DER)
+
+    @pytest.mark.parametrize("signer", ["ca", "responder"])
+    def test_good(self, pki, signer):
+        leaf, _ = pki["leaf"]
+        ca, _ = pki["ca"]
+        chain = pki["verifier"].verify_with_ocsp(
+            leaf, [], self._response(pki, signer=signer)
+        )
+        assert chain == [leaf, ca]
+
+    def test_good_pss(self, pki):
+        leaf, _ = pki["leaf"]
+        ca, _ = pki["ca"]
+        pss = padding.PSS(
+            mgf=padding.MGF1(hashes.SHA256()),
+            salt_length=padding.PSS.DIGEST_LENGTH,
+        )
+        response = self._response(pki, signer="pss-responder", rsa_padding=pss)
+        chain = pki["verifier"].verify_with_ocsp(leaf, [], response)
+        assert chain == [leaf, ca]
+
+    @pytest.mark.parametrize(
+        "kwargs",
+        [
+            {"status": ocsp.OCSPCertStatus.REVOKED},
+            {"status": ocsp.OCSPCertStatus.UNKNOWN},
+            {"this_update": _NOW + datetime.timedelta(hours=1)},
+            {
+                "this_update": _NOW - datetime.timedelta(days=2),
+                "next_update": _NOW - datetime.timedelta(days=1),
+            },
+            # Certificates without the OCSP signing EKU can't sign responses.
+            {"signer": "leaf"},
+        ],
+    )
+    def test_rejected(self, pki, kwargs):
+        leaf, _ = pki["leaf"]
+        with pytest.raises(VerificationError):
+            pki["verifier"].verify_with_ocsp(
+                leaf, [], self._response(pki, **kwargs)
+            )
+
+    def test_wrong_certificate(self, pki):
+        leaf, _ = pki["leaf"]
+        responder, _ = pki["responder"]
+        with pytest.raises(VerificationError):
+            pki["verifier"].verify_with_ocsp(
+                leaf, [], self._response(pki, cert=responder)
+            )
+
+    def test_unsuccessful_response(self, pki):
+        leaf, _ = pki["leaf"]
+        response = ocsp.OCSPResponseBuilder.build_unsuccessful(
+            ocsp.OCSPResponseStatus.TRY_LATER
+        ).public_bytes(serialization.Encoding.DER)
+        with pytest.raises(VerificationError):
+            pki["verifier"].verify_with_ocsp(leaf, [], response)
+        with pytest.raises(VerificationError):
+            pki["verifier"].verify_with_ocsp(leaf, [], b"not ocsp")
+
+    def test_invalid_chain(self, pki):
+        responder, _ = pki["responder"]
+        with pytest.raises(VerificationError):
+            pki["verifier"].verify_with_ocsp(
+                responder, [], self._response(pki, cert=responder)
+            )
+
+
+class TestSubjectMatches:
+    @pytest.fixture(scope="class")
+    def certs(self):
+        key = ec.generate_private_key(ec.SECP256R1())
+        san = x509.SubjectAlternativeName(
+            [
+                DNSName("*.example.com"),
+                DNSName("Foo.Example.ORG"),
+                DNSName("xn--bcher-kva.example"),
+                IPAddress(IPv4Address("192.0.2.1")),
+            ]
+        )
+        with_san = _issue("leaf", key, "leaf", key, [(san, False)])
+        without_san = _issue("example.com", key, "example.com", key, [])
+        return with_san, without_san
+
+    @pytest.mark.parametrize(
+        ("subject", "expected"),
+        [
+            (DNSName("a.example.com"), True),
+            (DNSName("A.EXAMPLE.COM"), True),
+            (DNSName("example.com"), False),
+            (DNSName("a.b.example.com"), False),
+            (DNSName("foo.example.org"), True),
+            (DNSName("bar.foo.example.org"), False),
+            (DNSName("xn--bcher-kva.example"), True),
+            (DNSName("192.0.2.1"), False),
+            (IPAddress(IPv4Address("192.0.2.1")), True),
+            (IPAddress(IPv4Address("192.0.2.2")), False),
+            (IPAddress(IPv6Address("::ffff:192.0.2.1")), False),
+        ],
+    )
+    def test_matches(self, certs, subject, expected):
+        with_san, _ = certs
+        assert subject_matches(with_san, subject) is expected
+
+    def test_common_name_ignored(self, certs):
+        _, without_san = certs
+        assert subject_matches(without_san, DNSName("example.com")) is False
+
+    def test_invalid_subject(self, certs):
+        with_san, _ = certs
+        with pytest.raises(ValueError):
+            subject_matches(with_san, DNSName("example..com"))
+        with pytest.raises(TypeError):
+            subject_matches(
+                with_san, "a.example.com"  # type: ignore[arg-type]
+            )